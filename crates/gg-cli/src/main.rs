@@ -4,7 +4,7 @@
 
 use std::process::exit;
 
-use clap::{Parser, Subcommand};
+use clap::{Args, Parser, Subcommand};
 use console::style;
 
 #[derive(Parser, Debug)]
@@ -25,6 +25,10 @@ struct Cli {
     )]
     _client_operation_id: Option<String>,
 
+    /// Use ASCII markers instead of emoji, box-drawing and spinners, and disable color
+    #[arg(long, global = true)]
+    plain: bool,
+
     #[clap(subcommand)]
     command: Option<Commands>,
 }
@@ -61,13 +65,95 @@ enum Commands {
         /// Stack name to create or switch to
         stack_name: Option<String>,
 
-        /// Base branch to use (default: main/master/trunk)
+        /// Base branch to use (default: main/master/trunk), or one of your stacks to stack on top of it
         #[arg(short, long)]
         base: Option<String>,
 
+        /// Remote the new stack pushes to and rebases from (default: origin)
+        #[arg(long)]
+        remote: Option<String>,
+
+        /// Start the new stack from this tag, commit or branch instead of the tip of its base
+        #[arg(long, value_name = "REF")]
+        from: Option<String>,
+
+        /// Describe what the stack is for (see `gg describe`)
+        #[arg(short, long)]
+        description: Option<String>,
+
         /// Create or reuse a git worktree for this stack
         #[arg(long = "worktree", short = 'w', alias = "wt")]
         worktree: bool,
+
+        /// Check the stack out here even with defaults.always_worktree
+        #[arg(long, conflicts_with = "worktree")]
+        no_worktree: bool,
+    },
+
+    /// Select the active stack without checking it out
+    #[command(name = "use")]
+    Use {
+        /// Stack to select (omit to show the current selection)
+        #[arg(value_name = "STACK")]
+        stack_name: Option<String>,
+
+        /// Clear the selection and follow the checked-out branch again
+        #[arg(long, conflicts_with = "stack_name")]
+        clear: bool,
+    },
+
+    /// Show or set the description of a stack
+    Describe {
+        /// What the stack is for (omit to show the current description)
+        description: Option<String>,
+
+        /// Stack to describe (default: the current stack)
+        #[arg(short, long)]
+        stack: Option<String>,
+
+        /// Remove the description
+        #[arg(long, conflicts_with = "description")]
+        clear: bool,
+
+        /// Output as JSON
+        #[arg(long)]
+        json: bool,
+    },
+
+    /// Show or set the path scope of the current stack (monorepos)
+    Scope {
+        /// Path globs the stack may touch, e.g. `services/payments/**`
+        /// (omit to show the current scope)
+        #[arg(value_name = "GLOB")]
+        patterns: Vec<String>,
+
+        /// Remove the scope
+        #[arg(long, conflicts_with = "patterns")]
+        clear: bool,
+    },
+
+    /// Attach key=value metadata to a stack entry (stored in git notes)
+    #[command(name = "annotate")]
+    Annotate {
+        /// Entry to annotate: position (1-indexed), GG-ID, or SHA
+        target: String,
+
+        /// Annotations to set: `key=value`, or a bare `key` flag
+        /// (omit to show the entry's annotations)
+        #[arg(value_name = "KEY=VALUE")]
+        pairs: Vec<String>,
+
+        /// Remove an annotation (repeatable)
+        #[arg(long, value_name = "KEY")]
+        remove: Vec<String>,
+
+        /// Remove every annotation from the entry
+        #[arg(long)]
+        clear: bool,
+
+        /// Output as JSON
+        #[arg(long)]
+        json: bool,
     },
 
     /// List current stack or all stacks
@@ -85,6 +171,27 @@ enum Commands {
         #[arg(long)]
         remote: bool,
 
+        /// List stacks of every repository registered with `gg setup --register`
+        #[arg(long, conflicts_with_all = ["all", "remote", "filter", "sort", "prs"])]
+        global: bool,
+
+        /// Show each entry's PR/MR review state, CI and unresolved threads (implies --refresh)
+        #[arg(long, conflicts_with = "remote")]
+        prs: bool,
+
+        /// Only list stacks matching a name substring, `author:<text>` or
+        /// `state:<open|draft|merged|closed|none>` (implies --all)
+        #[arg(long, value_name = "FILTER", value_parser = gg_core::commands::ls::parse_filter, conflicts_with = "remote")]
+        filter: Option<gg_core::commands::ls::StackFilter>,
+
+        /// Order stacks by name, last update or number of commits (implies --all)
+        #[arg(long, value_enum, conflicts_with = "remote")]
+        sort: Option<gg_core::commands::ls::StackSort>,
+
+        /// With --remote: show another user's pushed stacks (read-only)
+        #[arg(long, value_name = "NAME", requires = "remote")]
+        user: Option<String>,
+
         /// Output structured JSON
         #[arg(long)]
         json: bool,
@@ -102,8 +209,86 @@ enum Commands {
         refresh: bool,
     },
 
+    /// Show the patch of a stack entry, a range (`2..4`), or the whole stack
+    #[command(name = "diff")]
+    Diff {
+        /// Entry (position, short SHA, or GG-ID) or range `A..B`; omit for the whole stack
+        target: Option<String>,
+
+        /// Show a diffstat instead of the patch
+        #[arg(long, conflicts_with = "name_only")]
+        stat: bool,
+
+        /// Show only the names of changed files
+        #[arg(long)]
+        name_only: bool,
+    },
+
+    /// Export the stack as numbered patches, an mbox, or a git bundle
+    #[command(name = "export")]
+    Export {
+        /// Output directory (created if missing)
+        dir: std::path::PathBuf,
+
+        /// Output format
+        #[arg(long, value_enum, default_value_t)]
+        format: gg_core::commands::export::ExportFormat,
+    },
+
+    /// Open PRs/MRs of the stack in the browser
+    #[command(name = "open")]
+    Open {
+        /// Entry to open: position (1-indexed), short SHA, or GG-ID (default: current)
+        #[arg(conflicts_with = "all")]
+        target: Option<String>,
+
+        /// Open every entry that has a PR/MR
+        #[arg(short, long)]
+        all: bool,
+
+        /// Print the URLs instead of opening them
+        #[arg(long)]
+        print: bool,
+    },
+
+    /// Mark draft PRs/MRs of the stack as ready for review
+    #[command(name = "ready")]
+    Ready {
+        /// Entry to promote: position (1-indexed), short SHA, or GG-ID (default: current)
+        #[arg(conflicts_with = "all")]
+        target: Option<String>,
+
+        /// Promote every draft PR/MR in the stack
+        #[arg(short, long)]
+        all: bool,
+    },
+
+    /// Interactive dashboard: browse the stack, sync, land, reorder, open PRs/MRs
+    #[command(name = "ui")]
+    Ui,
+
+    /// Watch CI and approvals for every PR/MR in the stack until they land
+    #[command(name = "watch")]
+    Watch {
+        /// Seconds between polls (default: 10)
+        #[arg(long, value_name = "SECS")]
+        interval: Option<u64>,
+
+        /// Stream one JSON event per line for every change
+        #[arg(long)]
+        json: bool,
+    },
+
+    /// Show the current stack position, PR/CI state and working-tree changes
+    #[command(name = "status", alias = "st")]
+    Status {
+        /// Output structured JSON
+        #[arg(long)]
+        json: bool,
+    },
+
     /// Sync stack with remote (push branches and create/update PRs/MRs)
-    #[command(name = "sync", alias = "diff")]
+    #[command(name = "sync")]
     Sync {
         /// Create new PRs/MRs as drafts
         #[arg(short, long)]
@@ -145,16 +330,37 @@ enum Commands {
         #[arg(short, long)]
         until: Option<String>,
 
+        /// Sync just this commit's branch and PR/MR (position, GG-ID, or SHA)
+        #[arg(long, conflicts_with = "until")]
+        only: Option<String>,
+
+        /// Request a review on new PRs/MRs from this user or `org/team` (repeatable)
+        #[arg(long = "reviewer", value_name = "USER")]
+        reviewers: Vec<String>,
+
         /// Skip the pre-push hook for pushes performed by this sync
         #[arg(long = "no-verify")]
         no_verify: bool,
+
+        /// Report what would be pushed and which PRs/MRs would change, without doing it
+        #[arg(short = 'n', long, conflicts_with = "jsonl")]
+        dry_run: bool,
     },
 
-    /// Move to a specific commit in the stack
+    /// Move to a specific commit in the stack, or move it to another stack with --to
     #[command(name = "mv", alias = "move")]
     Move {
-        /// Position (1-indexed), entry ID, or commit SHA
-        target: String,
+        /// Position (1-indexed), entry ID, PR/MR number (#123), or commit SHA
+        /// (omit to pick an entry interactively)
+        target: Option<String>,
+
+        /// Move the commit to this stack instead of navigating to it
+        #[arg(long, requires = "target")]
+        to: Option<String>,
+
+        /// Override the immutability check when moving (--to)
+        #[arg(short, long, requires = "to")]
+        force: bool,
     },
 
     /// Move to the first commit in the stack
@@ -173,9 +379,18 @@ enum Commands {
     #[command(name = "next")]
     Next,
 
-    /// Squash staged changes into the current commit
+    /// Squash staged changes into the current commit, or into TARGET
     #[command(name = "sc", aliases = ["squash", "amend"])]
     Squash {
+        /// Amend into this entry instead of the current one: position
+        /// (1-indexed), short SHA, or GG-ID. HEAD stays where it is.
+        #[arg(value_name = "TARGET")]
+        target: Option<String>,
+
+        /// Same as TARGET: amend into this entry instead of the current one
+        #[arg(long, value_name = "TARGET", conflicts_with = "target")]
+        into: Option<String>,
+
         /// Squash all changes (staged and unstaged)
         #[arg(short, long)]
         all: bool,
@@ -187,6 +402,11 @@ enum Commands {
         /// Override the immutability check and rewrite merged/base commits anyway
         #[arg(short = 'f', long = "force", alias = "ignore-immutable")]
         force: bool,
+
+        /// Skip git hooks for the amend and the rebase after it, whatever
+        /// defaults.run_hooks says
+        #[arg(long)]
+        no_verify: bool,
     },
 
     /// Drop (remove) commits from the stack
@@ -203,6 +423,75 @@ enum Commands {
         /// merged/base commits protected.
         #[arg(short = 'y', long = "yes")]
         yes: bool,
+        /// Close the dropped entries' open PRs/MRs, leaving a comment
+        #[arg(long)]
+        close_pr: bool,
+        /// Output as JSON
+        #[arg(long)]
+        json: bool,
+    },
+
+    /// Squash the whole stack (or entries up to --until) into one commit
+    #[command(name = "squash-all")]
+    SquashAll {
+        /// Only squash entries 1..=N
+        #[arg(long, value_name = "N")]
+        until: Option<usize>,
+        /// Message for the squashed commit (default: the concatenated messages)
+        #[arg(short, long, conflicts_with = "edit")]
+        message: Option<String>,
+        /// Edit the concatenated message in $EDITOR
+        #[arg(short, long)]
+        edit: bool,
+        /// Leave the PRs/MRs of the squashed entries open instead of closing them
+        #[arg(long)]
+        keep_prs: bool,
+        /// Override the immutability check and rewrite merged/base commits
+        /// anyway. Implies `--yes`.
+        #[arg(short = 'f', long = "force")]
+        force: bool,
+        /// Skip the confirmation prompt
+        #[arg(short = 'y', long = "yes")]
+        yes: bool,
+        /// Output as JSON
+        #[arg(long)]
+        json: bool,
+    },
+
+    /// Reword a commit anywhere in the stack, keeping its GG-ID
+    Edit {
+        /// Entry to reword: position (1-indexed), short SHA, or GG-ID
+        /// (default: the current commit)
+        #[arg(value_name = "TARGET")]
+        target: Option<String>,
+        /// New commit message (default: open $EDITOR)
+        #[arg(short, long)]
+        message: Option<String>,
+        /// Also update the PR/MR title and description
+        #[arg(long)]
+        update_pr: bool,
+        /// Override the immutability check and rewrite merged/base commits anyway
+        #[arg(short = 'f', long = "force", alias = "ignore-immutable")]
+        force: bool,
+    },
+
+    /// Fold a commit into the one below it (the inverse of split)
+    Fold {
+        /// Entry to fold: position (1-indexed), short SHA, or GG-ID
+        #[arg(value_name = "TARGET")]
+        target: String,
+        /// Message for the folded commit (default: both messages concatenated)
+        #[arg(short, long, conflicts_with = "edit")]
+        message: Option<String>,
+        /// Edit the concatenated message in $EDITOR
+        #[arg(short, long)]
+        edit: bool,
+        /// Leave the folded entry's PR/MR open instead of closing it
+        #[arg(long)]
+        keep_pr: bool,
+        /// Override the immutability check and rewrite merged/base commits anyway
+        #[arg(short = 'f', long = "force", alias = "ignore-immutable")]
+        force: bool,
         /// Output as JSON
         #[arg(long)]
         json: bool,
@@ -220,12 +509,28 @@ enum Commands {
         #[arg(long)]
         no_tui: bool,
 
-        /// Override the immutability check and rewrite merged/base commits anyway
+        /// Arrange the stack in the TUI (or the editor with --no-tui); the
+        /// default when --order is not given
+        #[arg(short, long, conflicts_with = "order")]
+        interactive: bool,
+
+        /// Override the immutability check and the dependency check, and
+        /// rewrite anyway
         #[arg(short = 'f', long = "force", alias = "ignore-immutable")]
         force: bool,
+
+        /// Report which entries the new order puts below the ones they build
+        /// on, without rewriting
+        #[arg(short = 'n', long)]
+        dry_run: bool,
+
+        /// Print the dependencies between entries (and the ones --order
+        /// breaks) as JSON, without rewriting
+        #[arg(long, conflicts_with = "interactive")]
+        json: bool,
     },
 
-    /// Split a commit into two
+    /// Split a commit into two (or one commit per file/hunk)
     #[command(name = "split")]
     Split {
         /// Target commit: position (1-indexed), short SHA, or GG-ID
@@ -271,6 +576,20 @@ enum Commands {
         #[arg(long, requires = "structured_split")]
         json: bool,
 
+        /// Split into one commit per changed file
+        #[arg(
+            long,
+            conflicts_with_all = ["by_hunk", "describe", "plan_json", "message", "files"]
+        )]
+        by_file: bool,
+
+        /// Split into one commit per hunk
+        #[arg(
+            long,
+            conflicts_with_all = ["by_file", "describe", "plan_json", "message", "files"]
+        )]
+        by_hunk: bool,
+
         /// Files to include in the new commit
         #[arg(value_name = "FILES")]
         files: Vec<String>,
@@ -322,19 +641,41 @@ enum Commands {
         #[arg(long)]
         json: bool,
 
-        /// (GitLab only) Request auto-merge ("merge when pipeline succeeds") instead of merging immediately
+        /// Request auto-merge (GitHub native auto-merge / GitLab "merge when pipeline succeeds") instead of merging immediately
         #[arg(long)]
         auto_merge: bool,
 
-        /// Disable squash when merging (default: squash enabled)
-        #[arg(long = "no-squash")]
+        /// How to merge: squash, merge or rebase (default: `defaults.merge_strategy`, or squash)
+        #[arg(long, value_enum, value_name = "STRATEGY")]
+        strategy: Option<gg_core::config::MergeStrategy>,
+
+        /// Same as `--strategy merge`
+        #[arg(long = "no-squash", conflicts_with = "strategy")]
         no_squash: bool,
 
+        /// If restacking the remaining branches conflicts, rebase the stack locally and resume landing after `gg continue`
+        #[arg(long, conflicts_with = "json")]
+        auto_resolve_restack: bool,
+
+        /// With --wait, re-run failed CI jobs (up to 2 times per PR/MR) instead of asking
+        #[arg(long, requires = "wait")]
+        retrigger_ci: bool,
+
+        /// Pick up the last interrupted or failed land with the flags it was started with
+        #[arg(
+            long,
+            conflicts_with_all = [
+                "all", "auto_merge", "strategy", "no_squash", "auto_resolve_restack",
+                "wait", "until", "clean", "no_clean", "admin", "retrigger_ci"
+            ]
+        )]
+        resume: bool,
+
         /// Wait for CI to pass and approvals before merging
         #[arg(short, long)]
         wait: bool,
 
-        /// Land commits only up to this target (position, GG-ID, or SHA)
+        /// Land commits only up to this target (position, GG-ID, SHA, or #PR/!MR number)
         #[arg(short, long)]
         until: Option<String>,
 
@@ -349,6 +690,16 @@ enum Commands {
         /// (GitHub only) Use admin privileges to bypass branch protection approval requirements
         #[arg(long)]
         admin: bool,
+
+        /// With --wait, receive CI/merge webhooks on ADDR instead of polling every 10s
+        #[arg(
+            long,
+            value_name = "ADDR",
+            requires = "wait",
+            num_args = 0..=1,
+            default_missing_value = gg_core::webhook::DEFAULT_LISTEN_ADDR
+        )]
+        listen: Option<String>,
     },
 
     /// Clean up merged stacks
@@ -358,11 +709,44 @@ enum Commands {
         #[arg(short, long)]
         all: bool,
 
+        /// List every stack that would be deleted or skipped, and why, without deleting
+        #[arg(short = 'n', long)]
+        dry_run: bool,
+
+        /// Keep merged stack branches under refs/gg/archive instead of deleting them
+        #[arg(long)]
+        archive: bool,
+
+        /// Also clean stacks untouched for this long (e.g. 30d, 2w) with no open PRs/MRs
+        #[arg(long, value_name = "AGE", value_parser = gg_core::commands::clean::parse_age_days)]
+        stale: Option<u64>,
+
         /// Output structured JSON
         #[arg(long)]
         json: bool,
     },
 
+    /// List or restore stacks archived by `gg clean --archive`
+    #[command(name = "archive")]
+    Archive {
+        #[command(subcommand)]
+        action: ArchiveAction,
+    },
+
+    /// List or restore the backups taken before destructive operations
+    #[command(name = "snapshot")]
+    Snapshot {
+        #[command(subcommand)]
+        action: SnapshotAction,
+    },
+
+    /// List, prune or move the linked worktrees of stacks
+    #[command(name = "worktree")]
+    Worktree {
+        #[command(subcommand)]
+        action: WorktreeAction,
+    },
+
     /// Rebase the stack onto the updated base branch
     #[command(name = "rebase")]
     Rebase {
@@ -389,6 +773,30 @@ enum Commands {
         #[arg(short, long)]
         until: Option<usize>,
 
+        /// Start at this commit position (default: 1)
+        #[arg(short, long)]
+        from: Option<usize>,
+
+        /// Lint only commit positions N..M (inclusive; N.. and ..M leave an end open)
+        #[arg(long, value_name = "N..M", conflicts_with_all = ["until", "from"])]
+        range: Option<String>,
+
+        /// Commits to lint in parallel (0 = auto, 1 = sequential; default: defaults.lint_jobs)
+        #[arg(short, long)]
+        jobs: Option<usize>,
+
+        /// Output structured JSON
+        #[arg(long)]
+        json: bool,
+    },
+
+    /// Check commit subjects against the commit message policy (Conventional Commits by default)
+    #[command(name = "check-msgs")]
+    CheckMsgs {
+        /// Check `<from>..<to>` instead of the current stack (a single ref means `<ref>..HEAD`)
+        #[arg(long, value_name = "RANGE")]
+        range: Option<String>,
+
         /// Output structured JSON
         #[arg(long)]
         json: bool,
@@ -432,6 +840,21 @@ enum Commands {
         /// Configure all options (grouped by category)
         #[arg(long)]
         all: bool,
+
+        /// Only record this repository for `gg ls --global`
+        #[arg(long, conflicts_with_all = ["all", "unregister"])]
+        register: bool,
+
+        /// Only remove this repository from `gg ls --global`
+        #[arg(long, conflicts_with = "all")]
+        unregister: bool,
+    },
+
+    /// Inspect and edit the gg config
+    #[command(name = "config")]
+    Config {
+        #[command(subcommand)]
+        action: ConfigAction,
     },
 
     /// Absorb staged changes into the appropriate commits
@@ -464,6 +887,37 @@ enum Commands {
         /// Override the immutability check and rewrite merged/base commits anyway
         #[arg(short = 'f', long = "force", alias = "ignore-immutable")]
         force: bool,
+
+        /// Review each staged hunk and the commit it goes to before absorbing
+        #[arg(short = 'i', long, conflicts_with_all = ["dry_run", "whole_file"])]
+        interactive: bool,
+
+        /// Absorb every attributed hunk and print the hunk-to-commit mapping
+        /// and the fixups created as JSON (with --dry-run, only the mapping)
+        #[arg(long, conflicts_with_all = ["interactive", "whole_file"])]
+        json: bool,
+
+        /// Amend all staged changes into this entry instead of attributing
+        /// hunks: position (1-indexed), short SHA, or GG-ID
+        #[arg(
+            long,
+            value_name = "TARGET",
+            conflicts_with_all = ["dry_run", "interactive", "whole_file", "and_rebase", "one_fixup_per_commit", "squash", "json"]
+        )]
+        into: Option<String>,
+
+        /// Stage unstaged changes to tracked files and absorb them too
+        #[arg(long, conflicts_with = "dry_run")]
+        all: bool,
+
+        /// With --all, also stage and absorb untracked files
+        #[arg(long, requires = "all")]
+        include_untracked: bool,
+
+        /// Skip git hooks for the rebase (and amend) absorb runs, whatever
+        /// defaults.run_hooks says
+        #[arg(long)]
+        no_verify: bool,
     },
 
     /// Generate shell completions
@@ -474,12 +928,25 @@ enum Commands {
         shell: clap_complete::Shell,
     },
 
-    /// Generate shell integration for parent-shell features
+    /// Generate shell integration, or migrate existing PRs into stacks (--migrate)
     #[command(name = "init")]
     Init {
         /// Shell to generate integration for
-        #[arg(value_enum)]
-        shell: gg_core::commands::init::Shell,
+        #[arg(
+            value_enum,
+            required_unless_present = "migrate",
+            conflicts_with_all = ["migrate", "dry_run"]
+        )]
+        shell: Option<gg_core::commands::init::Shell>,
+
+        /// Adopt your open PRs/MRs as stacks: group dependent ones, add
+        /// GG-IDs, record PR mappings and rename their branches
+        #[arg(long)]
+        migrate: bool,
+
+        /// With --migrate: show what would be migrated without changing anything
+        #[arg(short = 'n', long)]
+        dry_run: bool,
     },
 
     /// Arrange commits in the stack: reorder and/or drop interactively (alias for reorder)
@@ -494,9 +961,25 @@ enum Commands {
         #[arg(long)]
         no_tui: bool,
 
-        /// Override the immutability check and rewrite merged/base commits anyway
+        /// Arrange the stack in the TUI (or the editor with --no-tui); the
+        /// default when --order is not given
+        #[arg(short, long, conflicts_with = "order")]
+        interactive: bool,
+
+        /// Override the immutability check and the dependency check, and
+        /// rewrite anyway
         #[arg(short = 'f', long = "force", alias = "ignore-immutable")]
         force: bool,
+
+        /// Report which entries the new order puts below the ones they build
+        /// on, without rewriting
+        #[arg(short = 'n', long)]
+        dry_run: bool,
+
+        /// Print the dependencies between entries (and the ones --order
+        /// breaks) as JSON, without rewriting
+        #[arg(long, conflicts_with = "interactive")]
+        json: bool,
     },
 
     /// Reconcile stacks that were pushed without using `gg sync`
@@ -512,6 +995,21 @@ enum Commands {
         yes: bool,
     },
 
+    /// Adopt an existing branch (and its open PR/MR) as a stack
+    #[command(name = "import")]
+    Import {
+        /// Branch to adopt (local, or `origin/<branch>`)
+        branch: String,
+
+        /// Stack name (default: derived from the branch name)
+        #[arg(long)]
+        name: Option<String>,
+
+        /// Base branch (default: auto-detected main/master/trunk)
+        #[arg(short, long)]
+        base: Option<String>,
+    },
+
     /// Show actionable inbox triage across all stacks
     #[command(name = "inbox")]
     Inbox {
@@ -524,75 +1022,620 @@ enum Commands {
         json: bool,
     },
 
-    /// Repair stack ancestry after manual history changes (amend, cherry-pick, rebase)
-    #[command(name = "restack")]
-    Restack {
-        /// Show what would be done without making changes
-        #[arg(short = 'n', long)]
-        dry_run: bool,
-        /// Repair only from this commit upward (position, SHA, or GG-ID)
-        #[arg(long)]
-        from: Option<String>,
+    /// Show landing metrics for your stacks (sync cycles, conflicts, PRs per week)
+    ///
+    /// Metrics come from the local operation log only, so review turnaround
+    /// and time-to-merge (which need provider PR/MR history) are not reported.
+    #[command(name = "insights")]
+    Insights {
         /// Output structured JSON
         #[arg(long)]
         json: bool,
     },
 
-    /// Undo the last local-only gg operation (see `gg undo --list`)
-    #[command(name = "undo")]
-    Undo {
-        /// List recent operations and their undoable status instead of undoing.
-        #[arg(long)]
-        list: bool,
+    /// Post a comment on an entry's PR/MR (or on every PR/MR in the stack)
+    #[command(name = "comment")]
+    Comment {
+        /// Entry to comment on: position (1-indexed), short SHA, or GG-ID (default: current)
+        #[arg(conflicts_with = "all")]
+        target: Option<String>,
 
-        /// Specific operation id to undo (defaults to most-recent-undoable).
-        #[arg(value_name = "OPERATION_ID")]
-        operation_id: Option<String>,
+        /// Comment body (Markdown)
+        #[arg(short, long)]
+        message: String,
 
-        /// Output as JSON
+        /// Comment on every entry that has a PR/MR
+        #[arg(short, long)]
+        all: bool,
+
+        /// Output structured JSON
         #[arg(long)]
         json: bool,
-
-        /// Limit for `--list` (default 100, matches the op-log cap). Has no
-        /// effect unless `--list` is also passed.
-        #[arg(long, default_value_t = 100, requires = "list")]
-        limit: usize,
     },
-}
 
-fn main() {
-    let cli = Cli::parse();
+    /// Show inline review threads for the stack, grouped by commit and file
+    #[command(name = "comments", args_conflicts_with_subcommands = true)]
+    Comments {
+        #[command(subcommand)]
+        action: Option<CommentsAction>,
 
-    let (result, json_mode, jsonl) = match cli.command {
-        // No command = show stacks (like `gg ls`)
-        None => (
-            gg_core::commands::ls::run(false, false, false, false),
-            false,
-            false,
-        ),
+        /// Only show threads for this entry (position, GG-ID, or SHA)
+        #[arg(value_name = "TARGET")]
+        target: Option<String>,
+
+        /// Hide resolved threads
+        #[arg(short, long)]
+        unresolved: bool,
+
+        /// Output structured JSON
+        #[arg(long)]
+        json: bool,
+    },
+
+    /// Show unresolved review threads of an entry's PR/MR, by file and line
+    #[command(name = "review")]
+    Review {
+        /// Entry to review: position (1-indexed), short SHA, or GG-ID (default: current)
+        target: Option<String>,
+
+        /// Output structured JSON
+        #[arg(long)]
+        json: bool,
+    },
+
+    /// CI helpers for stack entries
+    #[command(name = "ci")]
+    Ci {
+        #[command(subcommand)]
+        action: CiAction,
+    },
+
+    /// Add labels to an entry's PR/MR, or remove them with --remove
+    #[command(name = "label")]
+    Label {
+        /// Position (1-indexed), GG-ID, or commit SHA
+        target: String,
+
+        /// Labels to add or remove
+        #[arg(required = true)]
+        labels: Vec<String>,
+
+        /// Remove the labels instead of adding them
+        #[arg(short, long)]
+        remove: bool,
+    },
+
+    /// Stash changes tagged with the current stack and position
+    #[command(name = "stash")]
+    Stash {
+        #[command(subcommand)]
+        action: StashAction,
+    },
+
+    /// Check gg's internal invariants (GG-IDs, PR mappings, entry branches, worktrees)
+    #[command(name = "verify")]
+    Verify {
+        /// Repair the issues that can be fixed without losing work
+        #[arg(long)]
+        fix: bool,
+        /// Output structured JSON
+        #[arg(long)]
+        json: bool,
+    },
+
+    /// Diagnose git, provider auth, remotes, config and stack state
+    #[command(name = "doctor")]
+    Doctor {
+        /// Apply the repairs that cannot lose work
+        #[arg(long)]
+        fix: bool,
+        /// Output structured JSON
+        #[arg(long)]
+        json: bool,
+    },
+
+    /// Check that each PR/MR targets the previous entry's branch and fix drifted targets
+    #[command(name = "retarget-prs")]
+    RetargetPrs {
+        /// Only report drifted targets, don't change them
+        #[arg(short = 'n', long)]
+        dry_run: bool,
+        /// Output structured JSON
+        #[arg(long)]
+        json: bool,
+    },
+
+    /// Repair stack ancestry after manual history changes (amend, cherry-pick, rebase)
+    #[command(name = "restack")]
+    Restack {
+        /// Show what would be done without making changes
+        #[arg(short = 'n', long)]
+        dry_run: bool,
+        /// Repair only from this commit upward (position, SHA, or GG-ID)
+        #[arg(long)]
+        from: Option<String>,
+        /// Output structured JSON
+        #[arg(long)]
+        json: bool,
+    },
+
+    /// Undo the last local-only gg operation (see `gg undo --list`)
+    #[command(name = "undo")]
+    Undo {
+        /// List recent operations and their undoable status instead of undoing.
+        #[arg(long)]
+        list: bool,
+
+        /// Specific operation id to undo (defaults to most-recent-undoable).
+        #[arg(value_name = "OPERATION_ID")]
+        operation_id: Option<String>,
+
+        /// Output as JSON
+        #[arg(long)]
+        json: bool,
+
+        /// Limit for `--list` (default 100, matches the op-log cap). Has no
+        /// effect unless `--list` is also passed.
+        #[arg(long, default_value_t = 100, requires = "list")]
+        limit: usize,
+    },
+
+    /// Redo the last `gg undo`
+    #[command(name = "redo")]
+    Redo {
+        /// Output as JSON
+        #[arg(long)]
+        json: bool,
+    },
+}
+
+#[derive(Subcommand, Debug)]
+enum CommentsAction {
+    /// Mark a review thread as resolved
+    Resolve {
+        /// Thread id as shown by `gg comments` (a unique prefix is enough)
+        #[arg(value_name = "THREAD_ID")]
+        id: String,
+
+        /// Output as JSON
+        #[arg(long)]
+        json: bool,
+    },
+}
+
+#[derive(Subcommand, Debug)]
+enum CiAction {
+    /// Re-run CI for an entry's PR/MR
+    Rerun {
+        /// Entry to re-run CI for (position, GG-ID, or SHA)
+        #[arg(value_name = "TARGET")]
+        target: String,
+
+        /// Only re-run failed jobs
+        #[arg(long)]
+        failed_only: bool,
+
+        /// Output as JSON
+        #[arg(long)]
+        json: bool,
+    },
+}
+
+#[derive(Subcommand, Debug)]
+enum ArchiveAction {
+    /// List archived stacks, newest first per stack
+    List {
+        /// Output as JSON
+        #[arg(long)]
+        json: bool,
+    },
+
+    /// Recreate a stack's branch and config from its newest archive
+    Restore {
+        /// Stack name, or an archive ref (`refs/gg/archive/<stack>/<date>`)
+        stack: String,
+
+        /// Output as JSON
+        #[arg(long)]
+        json: bool,
+    },
+}
+
+#[derive(Subcommand, Debug)]
+enum SnapshotAction {
+    /// List snapshots, newest first per stack
+    List {
+        /// Only show snapshots of this stack
+        stack: Option<String>,
+
+        /// Output as JSON
+        #[arg(long)]
+        json: bool,
+    },
+
+    /// Move a stack branch back to a snapshot (undo with `gg undo`)
+    Restore {
+        /// Stack name for its newest snapshot, or a snapshot ref
+        /// (`refs/gg/backup/<stack>/<timestamp>`); default: the current stack
+        target: Option<String>,
+
+        /// Output as JSON
+        #[arg(long)]
+        json: bool,
+    },
+}
+
+#[derive(Subcommand, Debug)]
+enum WorktreeAction {
+    /// List stacks with a linked worktree and whether it is still in use
+    List {
+        /// Output as JSON
+        #[arg(long)]
+        json: bool,
+    },
+
+    /// Remove the worktrees of deleted stacks and forget missing ones
+    Prune {
+        /// Show what would be removed without removing anything
+        #[arg(long)]
+        dry_run: bool,
+
+        /// Also remove worktrees with uncommitted changes
+        #[arg(short = 'f', long)]
+        force: bool,
+
+        /// Output as JSON
+        #[arg(long)]
+        json: bool,
+    },
+
+    /// Move a stack's worktree to another directory
+    Move {
+        /// Stack whose worktree to move
+        stack: String,
+
+        /// New location (must not exist yet)
+        path: std::path::PathBuf,
+
+        /// Output as JSON
+        #[arg(long)]
+        json: bool,
+    },
+}
+
+#[derive(Subcommand, Debug)]
+enum ConfigAction {
+    /// Open the effective config in $EDITOR; it is validated before saving
+    Edit,
+
+    /// Print the effective value of a key (e.g. `sync_jobs`, `hooks.pre_sync`)
+    Get {
+        /// Dotted key; keys outside `defaults` need their section
+        key: String,
+
+        #[command(flatten)]
+        scope: ConfigScopeArgs,
+
+        /// Also print where the value comes from
+        #[arg(long)]
+        origin: bool,
+
+        /// Output as JSON
+        #[arg(long)]
+        json: bool,
+    },
+
+    /// Set a key (in the repo config unless --global, --team or --stack)
+    Set {
+        /// Dotted key; keys outside `defaults` need their section
+        key: String,
+
+        /// New value: JSON (numbers, booleans, lists) or a plain string
+        value: String,
+
+        #[command(flatten)]
+        scope: ConfigScopeArgs,
+    },
+
+    /// Remove a key (from the repo config unless --global, --team or --stack)
+    Unset {
+        /// Dotted key; keys outside `defaults` need their section
+        key: String,
+
+        #[command(flatten)]
+        scope: ConfigScopeArgs,
+    },
+
+    /// List every set value
+    List {
+        #[command(flatten)]
+        scope: ConfigScopeArgs,
+
+        /// Also print where each value comes from
+        #[arg(long)]
+        origin: bool,
+
+        /// Output as JSON
+        #[arg(long)]
+        json: bool,
+    },
+}
+
+/// Which config `gg config` reads or writes.
+#[derive(Args, Debug)]
+#[group(multiple = false)]
+struct ConfigScopeArgs {
+    /// The global config (~/.config/gg/config.json)
+    #[arg(long)]
+    global: bool,
+
+    /// The team config committed to the repository (.gg/config.json)
+    #[arg(long)]
+    team: bool,
+
+    /// The repository config (.git/gg/config.json)
+    #[arg(long)]
+    repo: bool,
+
+    /// One stack's settings in the repository config
+    #[arg(long, value_name = "NAME")]
+    stack: Option<String>,
+}
+
+impl ConfigScopeArgs {
+    fn scope(self) -> Option<gg_core::commands::config_cmd::ConfigScope> {
+        use gg_core::commands::config_cmd::ConfigScope;
+        match self {
+            ConfigScopeArgs { global: true, .. } => Some(ConfigScope::Global),
+            ConfigScopeArgs { team: true, .. } => Some(ConfigScope::Team),
+            ConfigScopeArgs { repo: true, .. } => Some(ConfigScope::Repo),
+            ConfigScopeArgs {
+                stack: Some(name), ..
+            } => Some(ConfigScope::Stack(name)),
+            _ => None,
+        }
+    }
+}
+
+#[derive(Subcommand, Debug)]
+enum StashAction {
+    /// Stash working tree changes, tagged with the current stack and position
+    Push {
+        /// Stash message
+        #[arg(short, long)]
+        message: Option<String>,
+
+        /// Also stash untracked files
+        #[arg(short = 'u', long)]
+        include_untracked: bool,
+
+        /// Output as JSON
+        #[arg(long)]
+        json: bool,
+    },
+
+    /// Restore a gg stash (defaults to the newest one for the current stack)
+    Pop {
+        /// Stash to restore (`stash@{N}` or `N`)
+        #[arg(value_name = "STASH")]
+        stash: Option<String>,
+
+        /// Output as JSON
+        #[arg(long)]
+        json: bool,
+    },
+
+    /// List gg stashes with the stack and position they were taken from
+    List {
+        /// Include stashes not created by gg
+        #[arg(short, long)]
+        all: bool,
+
+        /// Output as JSON
+        #[arg(long)]
+        json: bool,
+    },
+}
+
+/// The repository's config, for process-wide settings chosen at startup.
+/// `None` outside a repository or when the config cannot be read, so those
+/// settings keep their defaults.
+fn startup_config() -> Option<gg_core::config::Config> {
+    gg_core::git::open_repo()
+        .ok()
+        .and_then(|repo| gg_core::config::Config::load(repo.commondir()).ok())
+}
+
+fn main() {
+    let cli = Cli::parse();
+    let config = startup_config();
+
+    if cli.plain || config.as_ref().is_some_and(|c| c.defaults.plain_output) {
+        gg_core::plain::set_enabled(true);
+    }
+
+    if let Some(config) = &config {
+        match gg_core::branch_naming::BranchNaming::from_config(config) {
+            Ok(naming) => gg_core::branch_naming::set(naming),
+            // Keep `gg config` and `gg doctor` usable so the templates can
+            // be fixed
+            Err(e)
+                if !matches!(
+                    cli.command,
+                    Some(Commands::Config { .. } | Commands::Doctor { .. })
+                ) =>
+            {
+                let e = gg_core::error::GgError::Config(e);
+                eprintln!(
+                    "{} {}",
+                    style(format!("error[{}]:", e.code())).red().bold(),
+                    e
+                );
+                exit(1);
+            }
+            Err(_) => {}
+        }
+        gg_core::signing::set(config.defaults.sign_commits);
+        gg_core::hooks::set_git_hooks(config.defaults.run_hooks);
+    }
+    // `--no-verify` wins over defaults.run_hooks
+    if matches!(
+        cli.command,
+        Some(
+            Commands::Squash {
+                no_verify: true,
+                ..
+            } | Commands::Absorb {
+                no_verify: true,
+                ..
+            }
+        )
+    ) {
+        gg_core::hooks::set_git_hooks(gg_core::config::RunHooks::Never);
+    }
+
+    let (result, json_mode, jsonl) = match cli.command {
+        // No command = show stacks (like `gg ls`)
+        None => (
+            gg_core::commands::ls::run(gg_core::commands::ls::LsOptions::default()),
+            false,
+            false,
+        ),
 
         Some(Commands::Checkout {
             stack_name,
             base,
+            remote,
+            from,
+            description,
             worktree,
+            no_worktree,
         }) => (
-            gg_core::commands::checkout::run(stack_name, base, worktree),
+            gg_core::commands::checkout::run(
+                stack_name,
+                base,
+                remote,
+                from,
+                description,
+                (worktree || no_worktree).then_some(worktree),
+            ),
             false,
             false,
         ),
-        Some(Commands::List {
-            all,
-            refresh,
-            remote,
+        Some(Commands::Use { stack_name, clear }) => (
+            gg_core::commands::use_cmd::run(stack_name, clear),
+            false,
+            false,
+        ),
+        Some(Commands::Describe {
+            description,
+            stack,
+            clear,
             json,
         }) => (
-            gg_core::commands::ls::run(all, refresh, remote, json),
+            gg_core::commands::describe::run(stack, description, clear, json),
             json,
             false,
         ),
+        Some(Commands::Scope { patterns, clear }) => {
+            (gg_core::commands::scope::run(patterns, clear), false, false)
+        }
+        Some(Commands::Annotate {
+            target,
+            pairs,
+            remove,
+            clear,
+            json,
+        }) => (
+            gg_core::commands::annotate::run(gg_core::commands::annotate::AnnotateOptions {
+                target,
+                pairs,
+                remove,
+                clear,
+                json,
+            }),
+            json,
+            false,
+        ),
+        Some(Commands::List {
+            all,
+            refresh,
+            remote,
+            global,
+            prs,
+            filter,
+            sort,
+            user,
+            json,
+        }) => match user {
+            Some(user) => (
+                gg_core::commands::ls::run_remote_for_user(&user, json),
+                json,
+                false,
+            ),
+            None if global => (gg_core::commands::ls::run_global(json), json, false),
+            None => (
+                gg_core::commands::ls::run(gg_core::commands::ls::LsOptions {
+                    all,
+                    refresh,
+                    remote,
+                    prs,
+                    filter,
+                    sort,
+                    json,
+                }),
+                json,
+                false,
+            ),
+        },
         Some(Commands::Log { json, refresh }) => {
             (gg_core::commands::log::run(json, refresh), json, false)
         }
+        Some(Commands::Export { dir, format }) => (
+            gg_core::commands::export::run(gg_core::commands::export::ExportOptions {
+                dir,
+                format,
+            }),
+            false,
+            false,
+        ),
+        Some(Commands::Diff {
+            target,
+            stat,
+            name_only,
+        }) => (
+            gg_core::commands::diff::run(gg_core::commands::diff::DiffOptions {
+                target,
+                stat,
+                name_only,
+            }),
+            false,
+            false,
+        ),
+        Some(Commands::Open { target, all, print }) => (
+            gg_core::commands::open::run(gg_core::commands::open::OpenOptions {
+                target,
+                all,
+                print,
+            }),
+            false,
+            false,
+        ),
+        Some(Commands::Ready { target, all }) => (
+            gg_core::commands::ready::run(gg_core::commands::ready::ReadyOptions { target, all }),
+            false,
+            false,
+        ),
+        Some(Commands::Ui) => (gg_core::commands::ui::run(), false, false),
+        Some(Commands::Watch { interval, json }) => (
+            gg_core::commands::watch::run(gg_core::commands::watch::WatchOptions {
+                interval,
+                json,
+            }),
+            json,
+            json,
+        ),
+        Some(Commands::Status { json }) => (gg_core::commands::status::run(json), json, false),
         Some(Commands::Sync {
             draft,
             json,
@@ -604,7 +1647,10 @@ fn main() {
             lint,
             no_lint,
             until,
+            only,
+            reviewers,
             no_verify,
+            dry_run,
         }) => {
             // Determine run_lint based on flags and config
             let run_lint = if lint {
@@ -616,7 +1662,7 @@ fn main() {
             } else {
                 // No explicit flag, use config default
                 match gg_core::git::open_repo()
-                    .and_then(|repo| gg_core::config::Config::load_with_global(repo.commondir()))
+                    .and_then(|repo| gg_core::config::Config::load(repo.commondir()))
                 {
                     Ok(cfg) => cfg.get_sync_auto_lint(),
                     Err(_) => false, // If we can't load config, default to false
@@ -634,23 +1680,49 @@ fn main() {
                     update_title,
                     run_lint,
                     until,
+                    only,
+                    reviewers,
                     no_verify,
+                    dry_run,
                 ),
                 json || jsonl,
                 jsonl,
             )
         }
-        Some(Commands::Move { target }) => (gg_core::commands::nav::move_to(&target), false, false),
+        Some(Commands::Move {
+            target: Some(target),
+            to: Some(to),
+            force,
+        }) => (
+            gg_core::commands::move_cmd::run(gg_core::commands::move_cmd::MoveOptions {
+                target,
+                to,
+                force,
+            }),
+            false,
+            false,
+        ),
+        Some(Commands::Move { target, .. }) => (
+            gg_core::commands::nav::move_to(target.as_deref()),
+            false,
+            false,
+        ),
         Some(Commands::First) => (gg_core::commands::nav::first(), false, false),
         Some(Commands::Last) => (gg_core::commands::nav::last(), false, false),
         Some(Commands::Prev) => (gg_core::commands::nav::prev(), false, false),
         Some(Commands::Next) => (gg_core::commands::nav::next(), false, false),
         Some(Commands::Squash {
+            target,
+            into,
             all,
             staged_only,
             force,
+            no_verify: _,
         }) => (
-            gg_core::commands::squash::run(all, staged_only, force),
+            match target.or(into) {
+                Some(target) => gg_core::commands::squash::run_into(&target, all, force),
+                None => gg_core::commands::squash::run(all, staged_only, force),
+            },
             false,
             false,
         ),
@@ -658,12 +1730,69 @@ fn main() {
             targets,
             force,
             yes,
+            close_pr,
             json,
         }) => (
             gg_core::commands::drop_cmd::run(gg_core::commands::drop_cmd::DropOptions {
                 targets,
                 force,
                 yes,
+                close_pr,
+                json,
+            }),
+            json,
+            false,
+        ),
+        Some(Commands::SquashAll {
+            until,
+            message,
+            edit,
+            keep_prs,
+            force,
+            yes,
+            json,
+        }) => (
+            gg_core::commands::squash_all::run(gg_core::commands::squash_all::SquashAllOptions {
+                until,
+                message,
+                edit,
+                keep_prs,
+                yes,
+                force,
+                json,
+            }),
+            json,
+            false,
+        ),
+        Some(Commands::Edit {
+            target,
+            message,
+            update_pr,
+            force,
+        }) => (
+            gg_core::commands::edit::run(gg_core::commands::edit::EditOptions {
+                target,
+                message,
+                update_pr,
+                force,
+            }),
+            false,
+            false,
+        ),
+        Some(Commands::Fold {
+            target,
+            message,
+            edit,
+            keep_pr,
+            force,
+            json,
+        }) => (
+            gg_core::commands::fold::run(gg_core::commands::fold::FoldOptions {
+                target,
+                message,
+                edit,
+                keep_pr,
+                force,
                 json,
             }),
             json,
@@ -672,14 +1801,20 @@ fn main() {
         Some(Commands::Reorder {
             order,
             no_tui,
+            interactive,
             force,
+            dry_run,
+            json,
         }) => (
             gg_core::commands::reorder::run(gg_core::commands::reorder::ReorderOptions {
                 order,
                 no_tui,
+                interactive,
                 force,
+                dry_run,
+                json,
             }),
-            false,
+            json,
             false,
         ),
         Some(Commands::Split {
@@ -691,6 +1826,8 @@ fn main() {
             describe,
             plan_json,
             json,
+            by_file,
+            by_hunk,
             files,
         }) => (
             gg_core::commands::split::run(gg_core::commands::split::SplitOptions {
@@ -703,6 +1840,8 @@ fn main() {
                 describe,
                 plan_json: plan_json.clone(),
                 json,
+                by_file,
+                by_hunk,
             }),
             describe || plan_json.is_some(),
             false,
@@ -732,16 +1871,21 @@ fn main() {
             all,
             json,
             auto_merge,
+            strategy,
             no_squash,
+            auto_resolve_restack,
+            retrigger_ci,
+            resume,
             wait,
             until,
             clean,
             no_clean,
             admin,
+            listen,
         }) => {
             // Load config once for resolving defaults
             let land_cfg = gg_core::git::open_repo()
-                .and_then(|repo| gg_core::config::Config::load_with_global(repo.commondir()))
+                .and_then(|repo| gg_core::config::Config::load(repo.commondir()))
                 .ok();
 
             let auto_clean = if clean {
@@ -756,31 +1900,122 @@ fn main() {
 
             let admin = admin || land_cfg.as_ref().is_some_and(|cfg| cfg.get_land_admin());
 
+            let strategy = strategy
+                .or(no_squash.then_some(gg_core::config::MergeStrategy::Merge))
+                .unwrap_or_else(|| {
+                    land_cfg
+                        .as_ref()
+                        .map(|cfg| cfg.get_merge_strategy())
+                        .unwrap_or_default()
+                });
+
             (
                 gg_core::commands::land::run(gg_core::commands::land::LandOptions {
                     land_all: all,
                     json,
-                    squash: !no_squash,
+                    strategy,
                     wait,
                     auto_clean,
                     auto_merge_flag: auto_merge,
                     until,
                     admin,
+                    listen,
+                    auto_resolve_restack,
+                    resume,
+                    retrigger_ci,
                 }),
                 json,
                 false,
             )
         }
-        Some(Commands::Clean { all, json }) => {
-            (gg_core::commands::clean::run(all, json), json, false)
-        }
+        Some(Commands::Clean {
+            all,
+            dry_run,
+            archive,
+            stale,
+            json,
+        }) => (
+            gg_core::commands::clean::run(gg_core::commands::clean::CleanOptions {
+                all,
+                json,
+                dry_run,
+                archive,
+                stale_days: stale,
+            }),
+            json,
+            false,
+        ),
+        Some(Commands::Archive { action }) => match action {
+            ArchiveAction::List { json } => {
+                (gg_core::commands::archive::run_list(json), json, false)
+            }
+            ArchiveAction::Restore { stack, json } => (
+                gg_core::commands::archive::run_restore(&stack, json),
+                json,
+                false,
+            ),
+        },
+        Some(Commands::Snapshot { action }) => match action {
+            SnapshotAction::List { stack, json } => (
+                gg_core::commands::snapshot::run_list(stack.as_deref(), json),
+                json,
+                false,
+            ),
+            SnapshotAction::Restore { target, json } => (
+                gg_core::commands::snapshot::run_restore(target.as_deref(), json),
+                json,
+                false,
+            ),
+        },
+        Some(Commands::Worktree { action }) => match action {
+            WorktreeAction::List { json } => {
+                (gg_core::commands::worktree::run_list(json), json, false)
+            }
+            WorktreeAction::Prune {
+                dry_run,
+                force,
+                json,
+            } => (
+                gg_core::commands::worktree::run_prune(dry_run, force, json),
+                json,
+                false,
+            ),
+            WorktreeAction::Move { stack, path, json } => (
+                gg_core::commands::worktree::run_move(&stack, &path, json),
+                json,
+                false,
+            ),
+        },
         Some(Commands::Rebase { target, force }) => {
             (gg_core::commands::rebase::run(target, force), false, false)
         }
         Some(Commands::Continue) => (gg_core::commands::rebase::continue_rebase(), false, false),
         Some(Commands::Abort) => (gg_core::commands::rebase::abort_rebase(), false, false),
-        Some(Commands::Lint { until, json }) => (
-            gg_core::commands::lint::run(until, json, json).map(|_| ()),
+        Some(Commands::CheckMsgs { range, json }) => {
+            (gg_core::commands::check_msgs::run(range, json), json, false)
+        }
+        Some(Commands::Lint {
+            until,
+            from,
+            range,
+            jobs,
+            json,
+        }) => (
+            range
+                .as_deref()
+                .map(gg_core::commands::lint::parse_range)
+                .transpose()
+                .and_then(|parsed| {
+                    let (from, until) = parsed.unwrap_or((from, until));
+                    gg_core::commands::lint::run(gg_core::commands::lint::LintOptions {
+                        from,
+                        until,
+                        jobs,
+                        json,
+                        emit_json_output: json,
+                    })
+                })
+                .map(|_| ()),
             json,
             false,
         ),
@@ -804,12 +2039,16 @@ fn main() {
             match gg_core::commands::run::execute(gg_core::commands::run::RunOptions {
                 commands: vec![gg_core::commands::run::RunCommand::Argv(command)],
                 change_mode,
+                from: None,
                 until,
                 stop_on_error: !keep_going,
                 json,
                 emit_json_output: json,
                 header_label: None,
                 jobs,
+                scope: vec![],
+                command_paths: vec![],
+                commit_commands: None,
             }) {
                 Ok(true) => (Ok(()), json, false),
                 // `execute` has already emitted the JSON run payload (when
@@ -821,7 +2060,53 @@ fn main() {
                 Err(e) => (Err(e), json, false),
             }
         }
-        Some(Commands::Setup { all }) => (gg_core::commands::setup::run(all), false, false),
+        Some(Commands::Setup {
+            all,
+            register,
+            unregister,
+        }) => {
+            if register || unregister {
+                (
+                    gg_core::commands::setup::run_register(unregister),
+                    false,
+                    false,
+                )
+            } else {
+                (gg_core::commands::setup::run(all), false, false)
+            }
+        }
+        Some(Commands::Config { action }) => match action {
+            ConfigAction::Edit => (gg_core::commands::config_cmd::run_edit(), false, false),
+            ConfigAction::Get {
+                key,
+                scope,
+                origin,
+                json,
+            } => (
+                gg_core::commands::config_cmd::run_get(&key, scope.scope(), origin, json),
+                json,
+                false,
+            ),
+            ConfigAction::Set { key, value, scope } => (
+                gg_core::commands::config_cmd::run_set(&key, &value, scope.scope()),
+                false,
+                false,
+            ),
+            ConfigAction::Unset { key, scope } => (
+                gg_core::commands::config_cmd::run_unset(&key, scope.scope()),
+                false,
+                false,
+            ),
+            ConfigAction::List {
+                scope,
+                origin,
+                json,
+            } => (
+                gg_core::commands::config_cmd::run_list(scope.scope(), origin, json),
+                json,
+                false,
+            ),
+        },
         Some(Commands::Absorb {
             dry_run,
             and_rebase,
@@ -830,6 +2115,12 @@ fn main() {
             no_limit,
             squash,
             force,
+            interactive,
+            json,
+            into,
+            all,
+            include_untracked,
+            no_verify: _,
         }) => (
             gg_core::commands::absorb::run(gg_core::commands::absorb::AbsorbOptions {
                 dry_run,
@@ -839,27 +2130,42 @@ fn main() {
                 no_limit,
                 squash,
                 force,
+                interactive,
+                json,
+                into,
+                all,
+                include_untracked,
             }),
-            false,
+            json,
             false,
         ),
         Some(Commands::Arrange {
             order,
             no_tui,
+            interactive,
             force,
+            dry_run,
+            json,
         }) => (
             gg_core::commands::reorder::run(gg_core::commands::reorder::ReorderOptions {
                 order,
                 no_tui,
+                interactive,
                 force,
+                dry_run,
+                json,
             }),
-            false,
+            json,
             false,
         ),
         Some(Commands::Completions { shell }) => {
             (gg_core::commands::completions::run(shell), false, false)
         }
-        Some(Commands::Init { shell }) => (gg_core::commands::init::run(shell), false, false),
+        Some(Commands::Init { shell, dry_run, .. }) => match shell {
+            Some(shell) => (gg_core::commands::init::run(shell), false, false),
+            // Clap only allows a missing shell together with --migrate
+            None => (gg_core::commands::migrate::run(dry_run), false, false),
+        },
         Some(Commands::Reconcile { dry_run, yes }) => (
             gg_core::commands::reconcile::run(gg_core::commands::reconcile::ReconcileOptions {
                 dry_run,
@@ -868,9 +2174,100 @@ fn main() {
             false,
             false,
         ),
+        Some(Commands::Import { branch, name, base }) => (
+            gg_core::commands::import::run(gg_core::commands::import::ImportOptions {
+                branch,
+                name,
+                base,
+            }),
+            false,
+            false,
+        ),
         Some(Commands::Inbox { all, json }) => {
             (gg_core::commands::inbox::run(all, json), json, false)
         }
+        Some(Commands::Insights { json }) => (gg_core::commands::insights::run(json), json, false),
+        Some(Commands::Comment {
+            target,
+            message,
+            all,
+            json,
+        }) => (
+            gg_core::commands::comment::run(gg_core::commands::comment::CommentOptions {
+                target,
+                all,
+                message,
+                json,
+            }),
+            json,
+            false,
+        ),
+        Some(Commands::Comments {
+            action,
+            target,
+            unresolved,
+            json,
+        }) => match action {
+            Some(CommentsAction::Resolve { id, json }) => (
+                gg_core::commands::comments::run_resolve(id, json),
+                json,
+                false,
+            ),
+            None => (
+                gg_core::commands::comments::run(target, unresolved, json),
+                json,
+                false,
+            ),
+        },
+        Some(Commands::Review { target, json }) => {
+            (gg_core::commands::review::run(target, json), json, false)
+        }
+        Some(Commands::Ci { action }) => match action {
+            CiAction::Rerun {
+                target,
+                failed_only,
+                json,
+            } => (
+                gg_core::commands::ci::run_rerun(target, failed_only, json),
+                json,
+                false,
+            ),
+        },
+        Some(Commands::Label {
+            target,
+            labels,
+            remove,
+        }) => (
+            gg_core::commands::label::run(target, labels, remove),
+            false,
+            false,
+        ),
+        Some(Commands::Stash { action }) => match action {
+            StashAction::Push {
+                message,
+                include_untracked,
+                json,
+            } => (
+                gg_core::commands::stash::run_push(message, include_untracked, json),
+                json,
+                false,
+            ),
+            StashAction::Pop { stash, json } => {
+                (gg_core::commands::stash::run_pop(stash, json), json, false)
+            }
+            StashAction::List { all, json } => {
+                (gg_core::commands::stash::run_list(all, json), json, false)
+            }
+        },
+        Some(Commands::Verify { fix, json }) => {
+            (gg_core::commands::verify::run(fix, json), json, false)
+        }
+        Some(Commands::Doctor { fix, json }) => {
+            (gg_core::commands::doctor::run(fix, json), json, false)
+        }
+        Some(Commands::RetargetPrs { dry_run, json }) => {
+            (gg_core::commands::retarget::run(dry_run, json), json, false)
+        }
         Some(Commands::Restack {
             dry_run,
             from,
@@ -884,6 +2281,7 @@ fn main() {
             json,
             false,
         ),
+        Some(Commands::Redo { json }) => (gg_core::commands::undo::run_redo(json), json, false),
         Some(Commands::Undo {
             list,
             operation_id,
@@ -915,15 +2313,20 @@ fn main() {
                         version: gg_core::output::OUTPUT_VERSION,
                         command: "sync".to_string(),
                         event: gg_core::output::SyncStreamingEvent::Error {
+                            code: e.code().to_string(),
                             message: e.to_string(),
                         },
                     },
                     1,
                 );
             }
-            gg_core::output::print_json_error(&e.to_string());
+            gg_core::output::print_json_error(&e);
         } else {
-            eprintln!("{} {}", style("error:").red().bold(), e);
+            eprintln!(
+                "{} {}",
+                style(format!("error[{}]:", e.code())).red().bold(),
+                e
+            );
         }
         exit(1);
     }
@@ -542,3 +542,180 @@ fn test_absorb_squash_runs_from_worktree() {
         log
     );
 }
+
+#[test]
+fn test_absorb_dry_run_json_maps_hunks_to_commits() {
+    let (_temp_dir, repo_path) = create_test_repo();
+    setup_absorb_stack(&repo_path, "absorb-json-plan");
+
+    fs::write(repo_path.join("stack.txt"), "line1 updated\nline2\nline3\n")
+        .expect("Failed to write file");
+    fs::write(repo_path.join("new.txt"), "new\n").expect("Failed to write file");
+    run_git(&repo_path, &["add", "."]);
+    let (_, head_before) = run_git(&repo_path, &["rev-parse", "HEAD"]);
+
+    let (success, stdout, stderr) = run_gg(&repo_path, &["absorb", "--dry-run", "--json"]);
+    assert!(success, "absorb --dry-run --json failed: {}", stderr);
+    let parsed: serde_json::Value = serde_json::from_str(&stdout).expect("stdout must be JSON");
+    let absorb = &parsed["absorb"];
+    assert_eq!(absorb["dry_run"], true);
+
+    let hunks = absorb["hunks"].as_array().unwrap();
+    assert_eq!(hunks.len(), 2, "{}", stdout);
+    assert_eq!(hunks[0]["path"], "stack.txt");
+    assert_eq!(hunks[0]["status"], "attributed");
+    assert_eq!(hunks[0]["target"]["position"], 1);
+    assert_eq!(hunks[0]["target"]["title"], "Add line1");
+    // An insertion goes to the commit that wrote the line above it
+    assert_eq!(hunks[1]["target"]["position"], 2);
+    assert_eq!(absorb["unsupported_paths"], serde_json::json!(["new.txt"]));
+
+    let (_, head_after) = run_git(&repo_path, &["rev-parse", "HEAD"]);
+    assert_eq!(head_before, head_after, "dry run must not commit");
+}
+
+#[test]
+fn test_absorb_json_rejects_whole_file_and_interactive_requires_tty() {
+    let (_temp_dir, repo_path) = create_test_repo();
+    setup_absorb_stack(&repo_path, "absorb-flags");
+
+    let (success, _stdout, stderr) = run_gg(&repo_path, &["absorb", "--json", "--whole-file"]);
+    assert!(!success);
+    assert!(stderr.contains("--whole-file"), "stderr: {}", stderr);
+
+    fs::write(repo_path.join("stack.txt"), "line1 updated\nline2\n").expect("Failed to write file");
+    run_git(&repo_path, &["add", "stack.txt"]);
+    let (success, _stdout, stderr) = run_gg(&repo_path, &["absorb", "-i"]);
+    assert!(!success);
+    assert!(
+        stderr.contains("interactive terminal"),
+        "stderr: {}",
+        stderr
+    );
+}
+
+#[test]
+fn test_absorb_json_creates_fixups_for_attributed_hunks() {
+    let (_temp_dir, repo_path) = create_test_repo();
+    setup_absorb_stack(&repo_path, "absorb-json");
+
+    fs::write(repo_path.join("stack.txt"), "line1 updated\nline2\nline3\n")
+        .expect("Failed to write file");
+    fs::write(repo_path.join("new.txt"), "new\n").expect("Failed to write file");
+    run_git(&repo_path, &["add", "."]);
+
+    let (success, stdout, stderr) = run_gg(&repo_path, &["absorb", "--json"]);
+    assert!(success, "absorb --json failed: {}", stderr);
+    let parsed: serde_json::Value = serde_json::from_str(&stdout).expect("stdout must be JSON");
+    let absorb = &parsed["absorb"];
+    assert_eq!(absorb["dry_run"], false);
+    assert_eq!(absorb["rebased"], false);
+    assert_eq!(absorb["hunks"].as_array().unwrap().len(), 2);
+
+    let fixups = absorb["fixups"].as_array().unwrap();
+    assert_eq!(fixups.len(), 2, "{}", stdout);
+    assert_eq!(fixups[0]["target"]["position"], 1);
+    assert_eq!(fixups[0]["hunks"], 1);
+    assert_eq!(fixups[1]["target"]["position"], 2);
+
+    let (_, log) = run_git(&repo_path, &["log", "--format=%s", "-2"]);
+    assert_eq!(log.trim(), "fixup! Add line2\nfixup! Add line1");
+    // The new file has no blame and stays staged
+    let (_, status) = run_git(&repo_path, &["status", "--porcelain"]);
+    assert_eq!(status.trim(), "A  new.txt");
+}
+
+#[test]
+fn test_absorb_into_amends_all_staged_changes_into_target() {
+    let (_temp_dir, repo_path) = create_test_repo();
+    setup_absorb_stack(&repo_path, "absorb-into");
+
+    // A new file has no blame, so plain absorb could not place it
+    fs::write(repo_path.join("extra.txt"), "extra\n").expect("Failed to write file");
+    run_git(&repo_path, &["add", "extra.txt"]);
+
+    let (success, stdout, stderr) = run_gg(&repo_path, &["absorb", "--into", "1"]);
+    assert!(success, "absorb --into failed: {} {}", stdout, stderr);
+
+    let (_, log) = run_git(&repo_path, &["log", "--format=%s", "-3"]);
+    assert!(!log.contains("fixup!"), "log={}", log);
+    let (_, first_files) = run_git(&repo_path, &["show", "--name-only", "--format=", "HEAD~1"]);
+    assert!(
+        first_files.contains("extra.txt"),
+        "extra.txt should be in entry 1: {}",
+        first_files
+    );
+    let (_, status) = run_git(&repo_path, &["status", "--porcelain"]);
+    assert!(status.trim().is_empty(), "status={}", status);
+}
+
+#[test]
+fn test_absorb_into_rejects_and_rebase() {
+    let (_temp_dir, repo_path) = create_test_repo();
+    setup_absorb_stack(&repo_path, "absorb-into-rebase");
+    fs::write(repo_path.join("extra.txt"), "extra\n").expect("Failed to write file");
+    run_git(&repo_path, &["add", "extra.txt"]);
+    let (_, head_before) = run_git(&repo_path, &["rev-parse", "HEAD"]);
+
+    let (success, _, stderr) = run_gg(&repo_path, &["absorb", "--into", "1", "--and-rebase"]);
+    assert!(!success, "--into with --and-rebase should be refused");
+    assert!(stderr.contains("cannot be used with"), "stderr={}", stderr);
+
+    // Nothing was amended
+    let (_, head_after) = run_git(&repo_path, &["rev-parse", "HEAD"]);
+    assert_eq!(head_before, head_after);
+    let (_, status) = run_git(&repo_path, &["status", "--porcelain"]);
+    assert_eq!(status.trim(), "A  extra.txt");
+}
+
+#[test]
+fn test_absorb_all_includes_unstaged_changes() {
+    let (_temp_dir, repo_path) = create_test_repo();
+    setup_absorb_stack(&repo_path, "absorb-all");
+
+    // Unstaged edit of a line introduced by the first entry
+    fs::write(repo_path.join("stack.txt"), "line1 fixed\nline2\n").expect("Failed to write file");
+
+    let (success, stdout, _) = run_gg(&repo_path, &["absorb"]);
+    assert!(success);
+    assert!(stdout.contains("No staged changes"), "stdout={}", stdout);
+
+    let (success, stdout, stderr) = run_gg(&repo_path, &["absorb", "--all"]);
+    assert!(success, "absorb --all failed: {} {}", stdout, stderr);
+    let (_, log) = run_git(&repo_path, &["log", "--format=%s", "-1"]);
+    assert!(log.starts_with("fixup! "), "log={}", log);
+
+    let (_, status) = run_git(&repo_path, &["status", "--porcelain"]);
+    assert!(status.trim().is_empty(), "status={}", status);
+
+    let (success, _, stderr) = run_gg(&repo_path, &["absorb", "--include-untracked"]);
+    assert!(!success);
+    assert!(stderr.contains("--all"), "stderr={}", stderr);
+}
+
+#[test]
+fn test_absorb_respects_unstaged_action() {
+    let (_temp_dir, repo_path) = create_test_repo();
+    setup_absorb_stack(&repo_path, "absorb-unstaged-action");
+    fs::write(repo_path.join("stack.txt"), "line1 fixed\nline2\n").expect("Failed to write file");
+
+    let config_path = repo_path.join(".git/gg/config.json");
+    fs::write(
+        &config_path,
+        r#"{"defaults":{"branch_username":"testuser","unstaged_action":"abort"}}"#,
+    )
+    .expect("Failed to write config");
+    let (success, _, stderr) = run_gg(&repo_path, &["absorb"]);
+    assert!(!success);
+    assert!(stderr.contains("unstaged_action"), "stderr={}", stderr);
+
+    fs::write(
+        &config_path,
+        r#"{"defaults":{"branch_username":"testuser","unstaged_action":"add"}}"#,
+    )
+    .expect("Failed to write config");
+    let (success, stdout, stderr) = run_gg(&repo_path, &["absorb"]);
+    assert!(success, "absorb failed: {} {}", stdout, stderr);
+    let (_, log) = run_git(&repo_path, &["log", "--format=%s", "-1"]);
+    assert!(log.starts_with("fixup! "), "log={}", log);
+}
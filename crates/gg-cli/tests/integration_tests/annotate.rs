@@ -0,0 +1,81 @@
+use crate::helpers::{create_test_repo, run_gg, run_git};
+
+use serde_json::Value;
+use std::fs;
+
+fn setup_stack(repo_path: &std::path::Path) {
+    let gg_dir = repo_path.join(".git/gg");
+    fs::create_dir_all(&gg_dir).expect("Failed to create gg dir");
+    fs::write(
+        gg_dir.join("config.json"),
+        r#"{"defaults":{"branch_username":"testuser","unstaged_action":"add"}}"#,
+    )
+    .expect("Failed to write config");
+
+    let (success, _stdout, stderr) = run_gg(repo_path, &["co", "notes"]);
+    assert!(success, "Failed to create stack: {}", stderr);
+
+    for (file, msg) in [("a.txt", "First"), ("b.txt", "Second")] {
+        fs::write(repo_path.join(file), "content\n").expect("Failed to write file");
+        run_git(repo_path, &["add", file]);
+        run_git(repo_path, &["commit", "-m", msg]);
+    }
+}
+
+fn ls_annotations(repo_path: &std::path::Path, position: usize) -> Value {
+    let (success, stdout, stderr) = run_gg(repo_path, &["ls", "--json"]);
+    assert!(success, "gg ls failed: {}", stderr);
+    let parsed: Value = serde_json::from_str(&stdout).expect("valid JSON");
+    parsed["stack"]["entries"][position - 1]["annotations"].clone()
+}
+
+#[test]
+fn test_gg_annotate_sets_and_shows_annotations() {
+    let (_temp_dir, repo_path) = create_test_repo();
+    setup_stack(&repo_path);
+
+    let (success, stdout, stderr) = run_gg(&repo_path, &["annotate", "1", "risk=high", "needs-qa"]);
+    assert!(success, "gg annotate failed: {}", stderr);
+    assert!(stdout.contains("risk = high"), "stdout: {}", stdout);
+
+    let annotations = ls_annotations(&repo_path, 1);
+    assert_eq!(annotations["risk"], "high");
+    assert_eq!(annotations["needs-qa"], "");
+    assert!(ls_annotations(&repo_path, 2).is_null());
+
+    let (_, note) = run_git(&repo_path, &["notes", "--ref=gg", "show", "HEAD~1"]);
+    assert!(note.contains("risk=high"), "note: {}", note);
+
+    let (success, stdout, _stderr) = run_gg(&repo_path, &["ls"]);
+    assert!(success);
+    assert!(stdout.contains("needs-qa  risk=high"), "stdout: {}", stdout);
+
+    let (success, stdout, _stderr) = run_gg(&repo_path, &["log", "--json"]);
+    assert!(success);
+    let parsed: Value = serde_json::from_str(&stdout).expect("valid JSON");
+    assert_eq!(parsed["log"]["entries"][0]["annotations"]["risk"], "high");
+}
+
+#[test]
+fn test_gg_annotate_survives_amend_and_can_be_removed() {
+    let (_temp_dir, repo_path) = create_test_repo();
+    setup_stack(&repo_path);
+
+    let (success, _stdout, stderr) = run_gg(&repo_path, &["annotate", "2", "owner=payments"]);
+    assert!(success, "gg annotate failed: {}", stderr);
+
+    fs::write(repo_path.join("b.txt"), "changed\n").expect("Failed to write file");
+    let (success, _stdout, stderr) = run_gg(&repo_path, &["sc"]);
+    assert!(success, "gg sc failed: {}", stderr);
+    assert_eq!(ls_annotations(&repo_path, 2)["owner"], "payments");
+
+    let (success, stdout, stderr) = run_gg(
+        &repo_path,
+        &["annotate", "2", "--remove", "owner", "--json"],
+    );
+    assert!(success, "gg annotate --remove failed: {}", stderr);
+    let parsed: Value = serde_json::from_str(&stdout).expect("valid JSON");
+    assert_eq!(parsed["position"], 2);
+    assert!(parsed["annotations"].as_object().unwrap().is_empty());
+    assert!(ls_annotations(&repo_path, 2).is_null());
+}
@@ -0,0 +1,92 @@
+use crate::helpers::{create_test_repo, run_gg, run_git};
+
+use serde_json::Value;
+use std::fs;
+use std::path::Path;
+
+fn setup_stack(repo_path: &Path, commit_messages: &str) {
+    let gg_dir = repo_path.join(".git/gg");
+    fs::create_dir_all(&gg_dir).expect("Failed to create gg dir");
+    fs::write(
+        gg_dir.join("config.json"),
+        format!(
+            r#"{{"defaults":{{"branch_username":"testuser","commit_messages":{}}}}}"#,
+            commit_messages
+        ),
+    )
+    .expect("Failed to write config");
+
+    let (success, _, stderr) = run_gg(repo_path, &["co", "parser"]);
+    assert!(success, "Failed to create stack: {}", stderr);
+    for (file, message) in [
+        ("a.txt", "feat(parser)!: Stream large files"),
+        ("b.txt", "Fix empty input"),
+        ("c.txt", "docs: Explain streaming"),
+    ] {
+        fs::write(repo_path.join(file), message).expect("Failed to write file");
+        run_git(repo_path, &["add", "."]);
+        run_git(repo_path, &["commit", "-m", message]);
+    }
+}
+
+#[test]
+fn test_gg_check_msgs_reports_non_conventional_subjects() {
+    let (_temp_dir, repo_path) = create_test_repo();
+    setup_stack(&repo_path, "{}");
+
+    let (success, stdout, _) = run_gg(&repo_path, &["check-msgs"]);
+    assert!(!success, "check-msgs should fail: {}", stdout);
+    assert!(stdout.contains("1 of 3"), "{}", stdout);
+    assert!(stdout.contains("Fix empty input"), "{}", stdout);
+
+    let (success, stdout, _) = run_gg(&repo_path, &["check-msgs", "--json"]);
+    assert!(!success);
+    let parsed: Value = serde_json::from_str(&stdout).expect("valid JSON");
+    let result = &parsed["check_msgs"];
+    assert_eq!(result["ok"], false);
+    assert_eq!(result["violations"], 1);
+    let commits = result["commits"].as_array().expect("commits");
+    assert_eq!(commits[0]["type"], "feat");
+    assert_eq!(commits[0]["scope"], "parser");
+    assert_eq!(commits[0]["breaking"], true);
+    assert_eq!(commits[1]["ok"], false);
+    assert_eq!(commits[2]["type"], "docs");
+
+    // CI: a range instead of the current stack
+    run_git(&repo_path, &["checkout", "--detach", "HEAD"]);
+    let (success, stdout, stderr) = run_gg(&repo_path, &["check-msgs", "--range", "HEAD~1"]);
+    assert!(success, "check-msgs --range failed: {} {}", stdout, stderr);
+    assert!(stdout.contains("1 commit subject(s) follow"), "{}", stdout);
+}
+
+#[test]
+fn test_gg_check_msgs_uses_custom_pattern() {
+    let (_temp_dir, repo_path) = create_test_repo();
+    setup_stack(&repo_path, r#"{"pattern":"^[A-Za-z]+[:(]?"}"#);
+
+    let (success, stdout, stderr) = run_gg(&repo_path, &["check-msgs"]);
+    assert!(success, "check-msgs failed: {} {}", stdout, stderr);
+}
+
+#[test]
+fn test_gg_sync_blocks_on_commit_message_violations() {
+    let (_temp_dir, repo_path) = create_test_repo();
+    setup_stack(&repo_path, r#"{"check":"block"}"#);
+
+    let (success, stdout, stderr) = run_gg(&repo_path, &["sync"]);
+    assert!(!success, "sync should be blocked: {}", stdout);
+    assert!(
+        stderr.contains("commit message policy") && stderr.contains("Fix empty input"),
+        "{}",
+        stderr
+    );
+
+    // Syncing only the entry below the offending one is allowed
+    let (_, stdout, stderr) = run_gg(&repo_path, &["sync", "--until", "1"]);
+    assert!(
+        !stderr.contains("commit message policy"),
+        "{} {}",
+        stdout,
+        stderr
+    );
+}
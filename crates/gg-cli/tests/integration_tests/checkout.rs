@@ -316,3 +316,144 @@ fn test_gg_checkout_failure_does_not_request_shell_cd() {
     assert!(!success, "invalid stack checkout should fail");
     assert!(!cd_file.exists(), "failed checkout should not request cd");
 }
+
+#[test]
+fn test_gg_checkout_base_stack_creates_dependent_stack() {
+    let (_temp_dir, repo_path) = create_test_repo();
+
+    let gg_dir = repo_path.join(".git/gg");
+    fs::create_dir_all(&gg_dir).expect("Failed to create gg dir");
+    fs::write(
+        gg_dir.join("config.json"),
+        r#"{"defaults":{"branch_username":"testuser"}}"#,
+    )
+    .expect("Failed to write config");
+
+    let (success, _, stderr) = run_gg(&repo_path, &["co", "parent"]);
+    assert!(success, "Failed to create parent stack: {}", stderr);
+    fs::write(repo_path.join("parent.txt"), "parent").expect("Failed to write file");
+    run_git(&repo_path, &["add", "."]);
+    run_git(&repo_path, &["commit", "-m", "Parent commit"]);
+
+    let (success, stdout, stderr) = run_gg(&repo_path, &["co", "child", "--base", "parent"]);
+    assert!(success, "Failed to create child stack: {}", stderr);
+    assert!(stdout.contains("on top of stack"), "{}", stdout);
+
+    fs::write(repo_path.join("child.txt"), "child").expect("Failed to write file");
+    run_git(&repo_path, &["add", "."]);
+    run_git(&repo_path, &["commit", "-m", "Child commit"]);
+
+    let config = fs::read_to_string(gg_dir.join("config.json")).expect("Failed to read config");
+    let config: serde_json::Value = serde_json::from_str(&config).expect("valid config");
+    assert_eq!(config["stacks"]["child"]["parent"], "parent");
+
+    let (success, stdout, stderr) = run_gg(&repo_path, &["ls", "--json"]);
+    assert!(success, "ls failed: {} {}", stdout, stderr);
+    let parsed: serde_json::Value = serde_json::from_str(&stdout).expect("valid JSON");
+    assert_eq!(parsed["stack"]["base"], "testuser/parent");
+    let entries = parsed["stack"]["entries"].as_array().expect("entries");
+    assert_eq!(entries.len(), 1, "only the child's own commit: {}", stdout);
+    assert_eq!(entries[0]["title"], "Child commit");
+}
+
+#[test]
+fn test_gg_checkout_with_custom_branch_templates() {
+    let (_temp_dir, repo_path) = create_test_repo();
+
+    let gg_dir = repo_path.join(".git/gg");
+    fs::create_dir_all(&gg_dir).expect("Failed to create gg dir");
+    fs::write(
+        gg_dir.join("config.json"),
+        r#"{"defaults":{"branch_username":"testuser","branch_template":"feature/{user}/{stack}","entry_branch_template":"review/{user}/{stack}/{id}"}}"#,
+    )
+    .expect("Failed to write config");
+
+    // `--` is allowed once entry branches do not use it
+    let (success, _, stderr) = run_gg(&repo_path, &["co", "my--feature"]);
+    assert!(success, "Failed to create stack: {}", stderr);
+    let (_, branch) = run_git(&repo_path, &["rev-parse", "--abbrev-ref", "HEAD"]);
+    assert_eq!(branch.trim(), "feature/testuser/my--feature");
+
+    fs::write(repo_path.join("a.txt"), "a\n").expect("Failed to write file");
+    run_git(&repo_path, &["add", "a.txt"]);
+    run_git(&repo_path, &["commit", "-m", "Add a"]);
+
+    let (success, stdout, stderr) = run_gg(&repo_path, &["ls"]);
+    assert!(success, "gg ls failed: {}", stderr);
+    assert!(stdout.contains("Add a"), "stdout: {}", stdout);
+
+    // Entry branches cannot live under the stack branch's ref directory
+    let (success, _, stderr) = run_gg(
+        &repo_path,
+        &[
+            "config",
+            "set",
+            "defaults.entry_branch_template",
+            "feature/{user}/{stack}/{id}",
+        ],
+    );
+    assert!(!success);
+    assert!(stderr.contains("cannot live under"), "stderr: {}", stderr);
+}
+
+#[test]
+fn test_gg_checkout_from_tag_starts_stack_there() {
+    let (_temp_dir, repo_path) = create_test_repo();
+
+    let gg_dir = repo_path.join(".git/gg");
+    fs::create_dir_all(&gg_dir).expect("Failed to create gg dir");
+    fs::write(
+        gg_dir.join("config.json"),
+        r#"{"defaults":{"branch_username":"testuser"}}"#,
+    )
+    .expect("Failed to write config");
+
+    // A release tag on a branch of its own; main has moved on
+    run_git(&repo_path, &["checkout", "-b", "release/1.0"]);
+    fs::write(repo_path.join("version.txt"), "1.0\n").expect("Failed to write file");
+    run_git(&repo_path, &["add", "."]);
+    run_git(&repo_path, &["commit", "-m", "Release 1.0"]);
+    run_git(&repo_path, &["tag", "-a", "v1.0", "-m", "v1.0"]);
+    run_git(&repo_path, &["checkout", "main"]);
+    fs::write(repo_path.join("next.txt"), "next\n").expect("Failed to write file");
+    run_git(&repo_path, &["add", "."]);
+    run_git(&repo_path, &["commit", "-m", "Next feature"]);
+
+    let (success, stdout, stderr) = run_gg(&repo_path, &["co", "hotfix", "--from", "v1.0"]);
+    assert!(success, "co --from failed: {} {}", stdout, stderr);
+    assert!(stdout.contains("from v1.0"), "{}", stdout);
+    let (_, head) = run_git(&repo_path, &["rev-parse", "HEAD"]);
+    let (_, tag) = run_git(&repo_path, &["rev-parse", "v1.0^{commit}"]);
+    assert_eq!(head.trim(), tag.trim());
+
+    let config = fs::read_to_string(gg_dir.join("config.json")).expect("Failed to read config");
+    let config: serde_json::Value = serde_json::from_str(&config).expect("valid config");
+    assert_eq!(config["stacks"]["hotfix"]["from"], "v1.0");
+
+    fs::write(repo_path.join("fix.txt"), "fix\n").expect("Failed to write file");
+    run_git(&repo_path, &["add", "."]);
+    run_git(&repo_path, &["commit", "-m", "Fix crash"]);
+
+    // Only the stack's own commit, not the release commit below the tag
+    let (success, stdout, stderr) = run_gg(&repo_path, &["ls", "--json"]);
+    assert!(success, "ls failed: {} {}", stdout, stderr);
+    let parsed: serde_json::Value = serde_json::from_str(&stdout).expect("valid JSON");
+    let entries = parsed["stack"]["entries"].as_array().expect("entries");
+    assert_eq!(entries.len(), 1, "{}", stdout);
+    assert_eq!(entries[0]["title"], "Fix crash");
+
+    // `gg rebase` keeps it on the tag instead of moving it onto main
+    let (success, stdout, stderr) = run_gg(&repo_path, &["rebase"]);
+    assert!(success, "rebase failed: {} {}", stdout, stderr);
+    let (_, parent) = run_git(&repo_path, &["rev-parse", "HEAD~1"]);
+    assert_eq!(parent.trim(), tag.trim());
+
+    // A branch works like --base
+    run_git(&repo_path, &["checkout", "main"]);
+    let (success, _, stderr) = run_gg(&repo_path, &["co", "backport", "--from", "release/1.0"]);
+    assert!(success, "co --from <branch> failed: {}", stderr);
+    let config = fs::read_to_string(gg_dir.join("config.json")).expect("Failed to read config");
+    let config: serde_json::Value = serde_json::from_str(&config).expect("valid config");
+    assert_eq!(config["stacks"]["backport"]["base"], "release/1.0");
+    assert!(config["stacks"]["backport"]["from"].is_null());
+}
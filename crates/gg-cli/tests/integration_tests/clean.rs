@@ -1127,3 +1127,277 @@ fn test_gg_clean_current_branch_with_main_in_linked_worktree() {
         .current_dir(&repo_path)
         .output();
 }
+
+#[test]
+fn test_gg_clean_dry_run_lists_reasons_without_deleting() {
+    let (_temp_dir, repo_path) = create_test_repo();
+
+    let gg_dir = repo_path.join(".git/gg");
+    fs::create_dir_all(&gg_dir).expect("Failed to create gg dir");
+    fs::write(
+        gg_dir.join("config.json"),
+        r#"{"defaults":{"branch_username":"testuser","base":"main"}}"#,
+    )
+    .expect("Failed to write config");
+
+    for (stack, file) in [("merged-one", "merged.txt"), ("open-one", "open.txt")] {
+        let (success, _, stderr) = run_gg(&repo_path, &["co", stack]);
+        assert!(success, "Failed to create stack {}: {}", stack, stderr);
+        fs::write(repo_path.join(file), "content").expect("Failed to write");
+        run_git(&repo_path, &["add", "."]);
+        run_git(&repo_path, &["commit", "-m", &format!("feat: {}", stack)]);
+        run_git(&repo_path, &["checkout", "main"]);
+    }
+    let (success, _) = run_git(&repo_path, &["merge", "--ff-only", "testuser/merged-one"]);
+    assert!(success, "Expected fast-forward merge to succeed");
+
+    let (success, stdout, stderr) = run_gg(&repo_path, &["clean", "--dry-run"]);
+    assert!(success, "clean --dry-run failed: {}", stderr);
+    assert!(
+        stdout.contains("Would delete 'merged-one': branch merged locally"),
+        "stdout: {}",
+        stdout
+    );
+    assert!(
+        stdout.contains("Would skip 'open-one': unmerged commits"),
+        "stdout: {}",
+        stdout
+    );
+
+    let (success, stdout, stderr) = run_gg(&repo_path, &["clean", "--dry-run", "--json"]);
+    assert!(success, "clean --dry-run --json failed: {}", stderr);
+    let parsed: Value = serde_json::from_str(&stdout).expect("stdout must be valid JSON");
+    assert_eq!(parsed["clean"]["dry_run"], true);
+    assert_eq!(
+        parsed["clean"]["cleaned"],
+        serde_json::json!(["merged-one"])
+    );
+    assert_eq!(parsed["clean"]["skipped"], serde_json::json!(["open-one"]));
+    let stacks = parsed["clean"]["stacks"].as_array().expect("stacks array");
+    let reason = |name: &str| {
+        stacks
+            .iter()
+            .find(|s| s["name"] == name)
+            .map(|s| (s["action"].clone(), s["reason"].clone()))
+            .expect("stack listed")
+    };
+    assert_eq!(
+        reason("merged-one"),
+        ("delete".into(), "merged_locally".into())
+    );
+    assert_eq!(reason("open-one"), ("skip".into(), "unmerged".into()));
+
+    // Nothing was deleted
+    let (_, branches, _) = run_git_full(&repo_path, &["branch", "--list"]);
+    assert!(branches.contains("testuser/merged-one"), "{}", branches);
+    assert!(branches.contains("testuser/open-one"), "{}", branches);
+}
+
+#[test]
+fn test_gg_clean_skips_merged_stack_with_dirty_worktree() {
+    let (_parent_dir, repo_path) = create_test_repo_with_worktree_support();
+
+    let gg_dir = repo_path.join(".git/gg");
+    fs::create_dir_all(&gg_dir).expect("Failed to create gg dir");
+    fs::write(
+        gg_dir.join("config.json"),
+        r#"{"defaults":{"branch_username":"testuser","base":"main"}}"#,
+    )
+    .expect("Failed to write config");
+
+    let (success, _stdout, stderr) = run_gg(&repo_path, &["co", "dirty-wt", "--worktree"]);
+    assert!(success, "checkout --worktree should succeed: {}", stderr);
+    let worktree_path = repo_path.parent().expect("repo parent").join(format!(
+        "{}.{}",
+        repo_path.file_name().expect("repo name").to_string_lossy(),
+        "dirty-wt"
+    ));
+
+    fs::write(worktree_path.join("feature.txt"), "hello").expect("Failed to write");
+    run_git(&worktree_path, &["add", "."]);
+    let (success, _) = run_git(&worktree_path, &["commit", "-m", "feat: worktree change"]);
+    assert!(success, "Expected commit in worktree to succeed");
+    let (success, _) = run_git(&repo_path, &["merge", "--ff-only", "testuser/dirty-wt"]);
+    assert!(success, "Expected fast-forward merge into main to succeed");
+
+    fs::write(worktree_path.join("feature.txt"), "work in progress").expect("Failed to write");
+
+    let (success, stdout, stderr) = run_gg(&repo_path, &["clean", "--dry-run"]);
+    assert!(success, "clean --dry-run failed: {}", stderr);
+    assert!(
+        stdout.contains("Would skip 'dirty-wt': worktree") && stdout.contains("uncommitted"),
+        "stdout: {}",
+        stdout
+    );
+
+    let (success, stdout, stderr) = run_gg(&repo_path, &["clean", "--all"]);
+    assert!(success, "clean --all failed: {}", stderr);
+    assert!(
+        stdout.contains("Stack 'dirty-wt' skipped: worktree"),
+        "stdout: {}",
+        stdout
+    );
+    assert!(worktree_path.join("feature.txt").exists());
+    assert_eq!(
+        fs::read_to_string(worktree_path.join("feature.txt")).unwrap(),
+        "work in progress"
+    );
+}
+
+#[test]
+fn test_gg_clean_archive_keeps_stack_restorable() {
+    let (_temp_dir, repo_path) = create_test_repo();
+
+    let gg_dir = repo_path.join(".git/gg");
+    fs::create_dir_all(&gg_dir).expect("Failed to create gg dir");
+    fs::write(
+        gg_dir.join("config.json"),
+        r#"{"defaults":{"branch_username":"testuser","base":"main"},"stacks":{"shelved":{"reviewers":["alice"]}}}"#,
+    )
+    .expect("Failed to write config");
+
+    let (success, _, stderr) = run_gg(&repo_path, &["co", "shelved"]);
+    assert!(success, "Failed to create stack: {}", stderr);
+    fs::write(repo_path.join("shelved.txt"), "content").expect("Failed to write");
+    run_git(&repo_path, &["add", "."]);
+    run_git(&repo_path, &["commit", "-m", "feat: shelved"]);
+    let (_, tip) = run_git(&repo_path, &["rev-parse", "testuser/shelved"]);
+    run_git(&repo_path, &["checkout", "main"]);
+    let (success, _) = run_git(&repo_path, &["merge", "--ff-only", "testuser/shelved"]);
+    assert!(success, "Expected fast-forward merge to succeed");
+
+    let (success, stdout, stderr) = run_gg(&repo_path, &["clean", "--dry-run", "--archive"]);
+    assert!(success, "clean --dry-run --archive failed: {}", stderr);
+    assert!(stdout.contains("Would archive 'shelved'"), "{}", stdout);
+
+    let (success, stdout, stderr) = run_gg(&repo_path, &["clean", "--all", "--archive"]);
+    assert!(success, "clean --archive failed: {}", stderr);
+    assert!(
+        stdout.contains("Archived stack 'shelved' as refs/gg/archive/shelved/"),
+        "{}",
+        stdout
+    );
+    let (_, branches, _) = run_git_full(&repo_path, &["branch", "--list"]);
+    assert!(!branches.contains("testuser/shelved"), "{}", branches);
+
+    let (success, stdout, stderr) = run_gg(&repo_path, &["archive", "list", "--json"]);
+    assert!(success, "archive list failed: {}", stderr);
+    let parsed: Value = serde_json::from_str(&stdout).expect("stdout must be valid JSON");
+    let archives = parsed["archives"].as_array().expect("archives array");
+    assert_eq!(archives.len(), 1);
+    assert_eq!(archives[0]["stack"], "shelved");
+    assert_eq!(archives[0]["tip"], tip.trim());
+    let reference = archives[0]["ref"].as_str().unwrap().to_string();
+    let (success, archived_tip) = run_git(&repo_path, &["rev-parse", &reference]);
+    assert!(success);
+    assert_eq!(archived_tip.trim(), tip.trim());
+
+    let (success, stdout, stderr) = run_gg(&repo_path, &["archive", "restore", "shelved"]);
+    assert!(success, "archive restore failed: {}\n{}", stdout, stderr);
+    let (_, restored_tip) = run_git(&repo_path, &["rev-parse", "testuser/shelved"]);
+    assert_eq!(restored_tip.trim(), tip.trim());
+    let config: Value =
+        serde_json::from_str(&fs::read_to_string(gg_dir.join("config.json")).unwrap()).unwrap();
+    assert_eq!(
+        config["stacks"]["shelved"]["reviewers"],
+        serde_json::json!(["alice"])
+    );
+    let (success, _) = run_git(&repo_path, &["rev-parse", "--verify", &reference]);
+    assert!(!success, "archive ref should be gone after restore");
+
+    let (success, _, stderr) = run_gg(&repo_path, &["archive", "restore", "shelved"]);
+    assert!(!success);
+    assert!(stderr.contains("No archived stack 'shelved'"), "{}", stderr);
+}
+
+#[test]
+fn test_gg_clean_stale_removes_old_stacks_without_open_prs() {
+    let (_temp_dir, repo_path) = create_test_repo();
+
+    let gg_dir = repo_path.join(".git/gg");
+    fs::create_dir_all(&gg_dir).expect("Failed to create gg dir");
+    let config_path = gg_dir.join("config.json");
+    fs::write(
+        &config_path,
+        r#"{"defaults":{"branch_username":"testuser","base":"main"}}"#,
+    )
+    .expect("Failed to write config");
+
+    let now_secs = std::time::SystemTime::now()
+        .duration_since(std::time::UNIX_EPOCH)
+        .unwrap()
+        .as_secs();
+    let sixty_days_ago = now_secs - 60 * 24 * 60 * 60;
+
+    let (success, _, stderr) = run_gg(&repo_path, &["co", "old-one"]);
+    assert!(success, "Failed to create stack: {}", stderr);
+    fs::write(repo_path.join("old.txt"), "old").expect("Failed to write");
+    run_git(&repo_path, &["add", "."]);
+    let output = Command::new("git")
+        .args(["commit", "-m", "feat: old work"])
+        .env("GIT_AUTHOR_DATE", format!("@{} +0000", sixty_days_ago))
+        .env("GIT_COMMITTER_DATE", format!("@{} +0000", sixty_days_ago))
+        .current_dir(&repo_path)
+        .output()
+        .expect("Failed to run git");
+    assert!(output.status.success());
+    run_git(&repo_path, &["checkout", "main"]);
+
+    // `gg co` stamped the stack; pretend that was 60 days ago too
+    let mut config: Value =
+        serde_json::from_str(&fs::read_to_string(&config_path).unwrap()).unwrap();
+    assert!(
+        config["stacks"]["old-one"]["last_touched_ms"].is_u64(),
+        "gg co should stamp last_touched_ms: {}",
+        config
+    );
+    config["stacks"]["old-one"]["last_touched_ms"] = serde_json::json!(sixty_days_ago * 1000);
+    fs::write(&config_path, serde_json::to_string(&config).unwrap()).unwrap();
+
+    let (success, _, stderr) = run_gg(&repo_path, &["co", "fresh-one"]);
+    assert!(success, "Failed to create stack: {}", stderr);
+    fs::write(repo_path.join("fresh.txt"), "fresh").expect("Failed to write");
+    run_git(&repo_path, &["add", "."]);
+    run_git(&repo_path, &["commit", "-m", "feat: fresh work"]);
+    run_git(&repo_path, &["checkout", "main"]);
+
+    let (success, stdout, _) = run_gg(&repo_path, &["ls", "--all"]);
+    assert!(success);
+    let line = |name: &str| {
+        stdout
+            .lines()
+            .find(|l| l.contains(name))
+            .unwrap_or_default()
+            .to_string()
+    };
+    assert!(line("fresh-one").contains("touched just now"), "{}", stdout);
+    assert!(line("old-one").contains("touched "), "{}", stdout);
+
+    let (success, stdout, stderr) = run_gg(&repo_path, &["clean", "--dry-run", "--stale", "30d"]);
+    assert!(success, "clean --dry-run --stale failed: {}", stderr);
+    assert!(
+        stdout.contains("Would delete 'old-one': untouched for 60 days"),
+        "{}",
+        stdout
+    );
+    assert!(
+        stdout.contains("Would skip 'fresh-one': unmerged commits, touched"),
+        "{}",
+        stdout
+    );
+
+    // Without --stale, unmerged stacks are never cleaned
+    let (success, stdout, _) = run_gg(&repo_path, &["clean", "--dry-run"]);
+    assert!(success);
+    assert!(stdout.contains("Would skip 'old-one'"), "{}", stdout);
+
+    let (success, stdout, stderr) = run_gg(&repo_path, &["clean", "--all", "--stale", "30d"]);
+    assert!(success, "clean --stale failed: {}\n{}", stdout, stderr);
+    let (_, branches, _) = run_git_full(&repo_path, &["branch", "--list"]);
+    assert!(!branches.contains("testuser/old-one"), "{}", branches);
+    assert!(branches.contains("testuser/fresh-one"), "{}", branches);
+
+    let (success, _, stderr) = run_gg(&repo_path, &["clean", "--stale", "soon"]);
+    assert!(!success);
+    assert!(stderr.contains("invalid age 'soon'"), "{}", stderr);
+}
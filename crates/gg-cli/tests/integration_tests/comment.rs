@@ -0,0 +1,102 @@
+use crate::helpers::{create_test_repo, run_gg, run_gg_with_env, run_git};
+
+use std::fs;
+#[cfg(unix)]
+use std::os::unix::fs::PermissionsExt;
+
+#[test]
+fn test_gg_comment_requires_synced_entry() {
+    let (_temp_dir, repo_path) = create_test_repo();
+    let gg_dir = repo_path.join(".git/gg");
+    fs::create_dir_all(&gg_dir).expect("Failed to create gg dir");
+    fs::write(
+        gg_dir.join("config.json"),
+        r#"{"defaults":{"branch_username":"testuser","provider":"github"}}"#,
+    )
+    .expect("Failed to write config");
+
+    let (success, _stdout, stderr) = run_gg(&repo_path, &["co", "comment-stack"]);
+    assert!(success, "Failed to create stack: {}", stderr);
+    fs::write(repo_path.join("a.txt"), "a\n").expect("Failed to write file");
+    run_git(&repo_path, &["add", "a.txt"]);
+    run_git(&repo_path, &["commit", "-m", "Add a"]);
+
+    let (success, _stdout, stderr) = run_gg(&repo_path, &["comment", "1", "-m", "hi"]);
+    assert!(!success);
+    assert!(stderr.contains("has no PR"), "stderr: {stderr}");
+
+    let (success, _stdout, _stderr) = run_gg(&repo_path, &["comment", "1"]);
+    assert!(!success, "comment should require --message");
+}
+
+#[test]
+fn test_gg_comment_all_posts_on_every_pr() {
+    let (_temp_dir, repo_path) = create_test_repo();
+    let gg_dir = repo_path.join(".git/gg");
+    fs::create_dir_all(&gg_dir).expect("Failed to create gg dir");
+    fs::write(
+        gg_dir.join("config.json"),
+        r#"{"defaults":{"branch_username":"testuser","provider":"github"},"stacks":{"comment-all":{"mrs":{"c-aaaaaaa":101,"c-bbbbbbb":102}}}}"#,
+    )
+    .expect("Failed to write config");
+
+    let (success, _stdout, stderr) = run_gg(&repo_path, &["co", "comment-all"]);
+    assert!(success, "Failed to create stack: {}", stderr);
+    for (file, id) in [("a", "c-aaaaaaa"), ("b", "c-bbbbbbb")] {
+        fs::write(repo_path.join(format!("{file}.txt")), "x\n").expect("Failed to write");
+        run_git(&repo_path, &["add", &format!("{file}.txt")]);
+        run_git(
+            &repo_path,
+            &["commit", "-m", &format!("Add {file}\n\nGG-ID: {id}")],
+        );
+    }
+
+    let fake_bin = repo_path.join("fake-bin");
+    fs::create_dir_all(&fake_bin).expect("Failed to create fake bin dir");
+    let log = repo_path.join("gh.log");
+    fs::write(
+        fake_bin.join("gh"),
+        format!(
+            r#"#!/bin/sh
+echo "$@" >> "{log}"
+if [ "$1" = "--version" ]; then
+  echo "gh version 2.0.0"
+fi
+exit 0
+"#,
+            log = log.display()
+        ),
+    )
+    .expect("Failed to write fake gh");
+    #[cfg(unix)]
+    {
+        let mut perms = fs::metadata(fake_bin.join("gh"))
+            .expect("Failed to stat fake gh")
+            .permissions();
+        perms.set_mode(0o755);
+        fs::set_permissions(fake_bin.join("gh"), perms).expect("Failed to chmod fake gh");
+    }
+    let mut new_path = std::ffi::OsString::from(fake_bin.as_os_str());
+    new_path.push(":");
+    new_path.push(std::env::var_os("PATH").unwrap_or_default());
+
+    let (success, stdout, stderr) = run_gg_with_env(
+        &repo_path,
+        &["comment", "--all", "-m", "Rebased", "--json"],
+        &[("PATH", new_path.as_os_str())],
+    );
+    assert!(success, "comment failed: {}\n{}", stdout, stderr);
+
+    let parsed: serde_json::Value = serde_json::from_str(&stdout).expect("valid JSON");
+    let numbers: Vec<u64> = parsed["comments"]
+        .as_array()
+        .expect("comments array")
+        .iter()
+        .map(|c| c["pr_number"].as_u64().unwrap())
+        .collect();
+    assert_eq!(numbers, vec![101, 102]);
+
+    let gh_log = fs::read_to_string(&log).expect("gh log");
+    let comment_calls = gh_log.lines().filter(|l| l.contains("/comments")).count();
+    assert_eq!(comment_calls, 2, "{}", gh_log);
+}
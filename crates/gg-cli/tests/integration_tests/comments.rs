@@ -0,0 +1,32 @@
+use crate::helpers::{create_test_repo, run_gg};
+
+#[test]
+fn test_gg_comments_help_lists_resolve() {
+    let (_temp_dir, repo_path) = create_test_repo();
+    let (success, stdout, _stderr) = run_gg(&repo_path, &["comments", "--help"]);
+
+    assert!(success);
+    assert!(stdout.contains("resolve"));
+    assert!(stdout.contains("--unresolved"));
+}
+
+#[test]
+fn test_gg_comments_requires_stack() {
+    let (_temp_dir, repo_path) = create_test_repo();
+    let (success, _stdout, stderr) = run_gg(&repo_path, &["comments"]);
+
+    assert!(!success);
+    assert!(
+        stderr.contains("stack"),
+        "expected a not-on-stack error, got: {stderr}"
+    );
+}
+
+#[test]
+fn test_gg_comments_resolve_requires_id() {
+    let (_temp_dir, repo_path) = create_test_repo();
+    let (success, _stdout, stderr) = run_gg(&repo_path, &["comments", "resolve"]);
+
+    assert!(!success);
+    assert!(stderr.contains("<THREAD_ID>"));
+}
@@ -0,0 +1,333 @@
+use crate::helpers::{create_test_repo, run_gg, run_gg_with_env};
+
+use std::fs;
+use std::os::unix::fs::PermissionsExt;
+use std::path::{Path, PathBuf};
+
+/// Write an `$EDITOR` script that replaces the edited file with `contents`.
+fn fake_editor(dir: &Path, contents: &str) -> PathBuf {
+    let payload = dir.join("edited.json");
+    fs::write(&payload, contents).expect("Failed to write payload");
+    let script = dir.join("editor.sh");
+    fs::write(
+        &script,
+        format!("#!/bin/sh\ncp '{}' \"$1\"\n", payload.display()),
+    )
+    .expect("Failed to write editor");
+    fs::set_permissions(&script, fs::Permissions::from_mode(0o755)).expect("chmod");
+    script
+}
+
+#[test]
+fn test_gg_config_edit_saves_valid_config() {
+    let (temp_dir, repo_path) = create_test_repo();
+    let editor = fake_editor(
+        temp_dir.path(),
+        r#"{"defaults":{"branch_username":"testuser","sync_draft":true}}"#,
+    );
+
+    let (success, stdout, stderr) = run_gg_with_env(
+        &repo_path,
+        &["config", "edit"],
+        &[
+            ("EDITOR", editor.as_os_str()),
+            ("VISUAL", editor.as_os_str()),
+        ],
+    );
+    assert!(success, "gg config edit failed: {} {}", stdout, stderr);
+
+    let saved = fs::read_to_string(repo_path.join(".git/gg/config.json")).unwrap();
+    let parsed: serde_json::Value = serde_json::from_str(&saved).unwrap();
+    assert_eq!(parsed["defaults"]["sync_draft"], true);
+    assert_eq!(parsed["defaults"]["branch_username"], "testuser");
+}
+
+#[test]
+fn test_gg_config_edit_rejects_invalid_config() {
+    let (temp_dir, repo_path) = create_test_repo();
+    let config_path = repo_path.join(".git/gg/config.json");
+    fs::create_dir_all(config_path.parent().unwrap()).unwrap();
+    fs::write(
+        &config_path,
+        r#"{"defaults":{"branch_username":"testuser"}}"#,
+    )
+    .unwrap();
+
+    let editor = fake_editor(
+        temp_dir.path(),
+        r#"{"defaults":{"sync_drafts":true,"unstaged_action":"ask"}}"#,
+    );
+    let (success, _stdout, stderr) = run_gg_with_env(
+        &repo_path,
+        &["config", "edit"],
+        &[
+            ("EDITOR", editor.as_os_str()),
+            ("VISUAL", editor.as_os_str()),
+        ],
+    );
+    assert!(!success, "invalid config should be rejected");
+    assert!(
+        stderr.contains("unknown key `defaults.sync_drafts`"),
+        "stderr: {}",
+        stderr
+    );
+    assert_eq!(
+        fs::read_to_string(&config_path).unwrap(),
+        r#"{"defaults":{"branch_username":"testuser"}}"#,
+        "rejected edit must leave the config untouched"
+    );
+}
+
+#[test]
+fn test_gg_config_set_get_unset_with_origins() {
+    let (_temp_dir, repo_path) = create_test_repo();
+
+    // Global value, visible while the repo has no config of its own
+    let (success, _stdout, stderr) =
+        run_gg(&repo_path, &["config", "set", "--global", "sync_jobs", "6"]);
+    assert!(success, "gg config set --global failed: {}", stderr);
+    let (success, stdout, _stderr) =
+        run_gg(&repo_path, &["config", "get", "sync_jobs", "--origin"]);
+    assert!(success);
+    assert_eq!(stdout.trim(), "global\t6");
+
+    // Repo value wins; strings need no quoting
+    let (success, _stdout, stderr) = run_gg(&repo_path, &["config", "set", "base", "develop"]);
+    assert!(success, "gg config set failed: {}", stderr);
+    let (success, stdout, _stderr) = run_gg(&repo_path, &["config", "get", "base", "--json"]);
+    assert!(success);
+    let parsed: serde_json::Value = serde_json::from_str(&stdout).unwrap();
+    assert_eq!(parsed["key"], "defaults.base");
+    assert_eq!(parsed["value"], "develop");
+    assert_eq!(parsed["origin"], "repo");
+
+    // Per-stack values
+    let (success, _stdout, stderr) = run_gg(
+        &repo_path,
+        &[
+            "config",
+            "set",
+            "--stack",
+            "billing",
+            "reviewers",
+            r#"["alice"]"#,
+        ],
+    );
+    assert!(success, "gg config set --stack failed: {}", stderr);
+    let (success, stdout, _stderr) = run_gg(&repo_path, &["config", "list", "--stack", "billing"]);
+    assert!(success);
+    assert!(stdout.contains(r#"reviewers = ["alice"]"#), "{}", stdout);
+
+    // Schema validation
+    let (success, _stdout, stderr) = run_gg(&repo_path, &["config", "set", "sync_job", "8"]);
+    assert!(!success);
+    assert!(
+        stderr.contains("Unknown config key `defaults.sync_job`"),
+        "{}",
+        stderr
+    );
+    let (success, _stdout, stderr) = run_gg(&repo_path, &["config", "set", "sync_jobs", "lots"]);
+    assert!(!success);
+    assert!(
+        stderr.contains("Invalid value for `defaults.sync_jobs`"),
+        "{}",
+        stderr
+    );
+
+    let (success, stdout, _stderr) = run_gg(&repo_path, &["config", "list", "--origin"]);
+    assert!(success);
+    assert!(
+        stdout.contains("repo\tdefaults.base = develop"),
+        "{}",
+        stdout
+    );
+
+    let (success, _stdout, stderr) = run_gg(&repo_path, &["config", "unset", "base"]);
+    assert!(success, "gg config unset failed: {}", stderr);
+    let (success, _stdout, stderr) = run_gg(&repo_path, &["config", "get", "base"]);
+    assert!(!success);
+    assert!(stderr.contains("`defaults.base` is not set"), "{}", stderr);
+}
+
+#[test]
+fn test_gg_config_global_layer_merges_under_repo_key_by_key() {
+    let (temp_dir, repo_path) = create_test_repo();
+    let xdg = temp_dir.path().join("xdg");
+    fs::create_dir_all(xdg.join("gg")).unwrap();
+    fs::write(
+        xdg.join("gg/config.json"),
+        r#"{"defaults": {"sync_jobs": 6, "branch_username": "global-user"}}"#,
+    )
+    .unwrap();
+    let envs = [("XDG_CONFIG_HOME", xdg.as_os_str())];
+
+    // A repo value for one key keeps the global values of the others
+    let (success, _stdout, stderr) =
+        run_gg_with_env(&repo_path, &["config", "set", "base", "develop"], &envs);
+    assert!(success, "gg config set failed: {}", stderr);
+    let repo_config = fs::read_to_string(repo_path.join(".git/gg/config.json")).unwrap();
+    assert!(!repo_config.contains("sync_jobs"), "{}", repo_config);
+
+    let (success, stdout, _stderr) =
+        run_gg_with_env(&repo_path, &["config", "list", "--origin"], &envs);
+    assert!(success);
+    assert!(
+        stdout.contains("global\tdefaults.sync_jobs = 6"),
+        "{}",
+        stdout
+    );
+    assert!(
+        stdout.contains("repo\tdefaults.base = develop"),
+        "{}",
+        stdout
+    );
+
+    // Repo value overrides the global one
+    let (success, _stdout, stderr) = run_gg_with_env(
+        &repo_path,
+        &["config", "set", "branch_username", "repo-user"],
+        &envs,
+    );
+    assert!(success, "gg config set failed: {}", stderr);
+    let (success, stdout, _stderr) = run_gg_with_env(
+        &repo_path,
+        &["config", "get", "branch_username", "--origin"],
+        &envs,
+    );
+    assert!(success);
+    assert_eq!(stdout.trim(), "repo\trepo-user");
+}
+
+#[test]
+fn test_gg_config_global_layer_under_older_repo_config() {
+    let (temp_dir, repo_path) = create_test_repo();
+    let xdg = temp_dir.path().join("xdg");
+    fs::create_dir_all(xdg.join("gg")).unwrap();
+    fs::write(
+        xdg.join("gg/config.json"),
+        r#"{"defaults": {"branch_username": "global-user"}}"#,
+    )
+    .unwrap();
+    // Repo configs written before the global layer spell out every unset key
+    fs::create_dir_all(repo_path.join(".git/gg")).unwrap();
+    fs::write(
+        repo_path.join(".git/gg/config.json"),
+        r#"{"defaults": {"base": null, "branch_username": null, "provider": null}, "stacks": {}}"#,
+    )
+    .unwrap();
+    let envs = [("XDG_CONFIG_HOME", xdg.as_os_str())];
+
+    let (success, stdout, stderr) = run_gg_with_env(
+        &repo_path,
+        &["config", "get", "branch_username", "--origin"],
+        &envs,
+    );
+    assert!(success, "gg config get failed: {}", stderr);
+    assert_eq!(stdout.trim(), "global\tglobal-user");
+}
+
+#[test]
+fn test_gg_config_repo_value_matching_global_stays_pinned() {
+    let (temp_dir, repo_path) = create_test_repo();
+    let xdg = temp_dir.path().join("xdg");
+    fs::create_dir_all(xdg.join("gg")).unwrap();
+    fs::write(
+        xdg.join("gg/config.json"),
+        r#"{"defaults": {"base": "main", "branch_username": "testuser"}}"#,
+    )
+    .unwrap();
+    let envs = [("XDG_CONFIG_HOME", xdg.as_os_str())];
+
+    let (success, _stdout, stderr) = run_gg_with_env(
+        &repo_path,
+        &["config", "set", "--repo", "base", "main"],
+        &envs,
+    );
+    assert!(success, "gg config set failed: {}", stderr);
+    // Saving the config (here, to add the stack) keeps the repo's value
+    let (success, _stdout, stderr) = run_gg_with_env(&repo_path, &["co", "feat"], &envs);
+    assert!(success, "gg co failed: {}", stderr);
+
+    let (success, stdout, _stderr) =
+        run_gg_with_env(&repo_path, &["config", "get", "base", "--origin"], &envs);
+    assert!(success);
+    assert_eq!(stdout.trim(), "repo\tmain");
+}
+
+#[test]
+fn test_gg_config_team_layer_from_committed_file() {
+    let (_temp_dir, repo_path) = create_test_repo();
+    fs::create_dir_all(repo_path.join(".gg")).unwrap();
+    fs::write(
+        repo_path.join(".gg/config.json"),
+        r#"{"defaults": {"base": "develop", "lint": ["cargo fmt --check"], "labels": ["team"]}}"#,
+    )
+    .unwrap();
+
+    let (success, stdout, _stderr) = run_gg(&repo_path, &["config", "get", "base", "--origin"]);
+    assert!(success);
+    assert_eq!(stdout.trim(), "team\tdevelop");
+
+    // The personal config wins over the team's
+    let (success, _stdout, stderr) = run_gg(&repo_path, &["config", "set", "labels", r#"["me"]"#]);
+    assert!(success, "gg config set failed: {}", stderr);
+    let (success, stdout, _stderr) = run_gg(&repo_path, &["config", "list", "--origin"]);
+    assert!(success);
+    assert!(
+        stdout.contains("repo\tdefaults.labels = [\"me\"]"),
+        "{}",
+        stdout
+    );
+    assert!(
+        stdout.contains("team\tdefaults.lint = [\"cargo fmt --check\"]"),
+        "{}",
+        stdout
+    );
+    let repo_config = fs::read_to_string(repo_path.join(".git/gg/config.json")).unwrap();
+    assert!(!repo_config.contains("develop"), "{}", repo_config);
+
+    // --team edits the committed file
+    let (success, _stdout, stderr) =
+        run_gg(&repo_path, &["config", "set", "--team", "sync_jobs", "4"]);
+    assert!(success, "gg config set --team failed: {}", stderr);
+    let team_config = fs::read_to_string(repo_path.join(".gg/config.json")).unwrap();
+    assert!(team_config.contains(r#""sync_jobs": 4"#), "{}", team_config);
+    assert!(team_config.contains("develop"), "{}", team_config);
+}
+
+#[test]
+fn test_gg_config_team_layer_ignores_commands() {
+    let (_temp_dir, repo_path) = create_test_repo();
+    fs::create_dir_all(repo_path.join(".gg")).unwrap();
+    fs::write(
+        repo_path.join(".gg/config.json"),
+        r#"{"defaults": {"base": "develop", "notify_command": "touch pwned"}, "hooks": {"pre_sync": "touch pwned"}}"#,
+    )
+    .unwrap();
+
+    let (success, stdout, _stderr) = run_gg(&repo_path, &["config", "get", "base", "--origin"]);
+    assert!(success);
+    assert_eq!(stdout.trim(), "team\tdevelop");
+    for key in ["hooks.pre_sync", "notify_command"] {
+        let (success, stdout, _stderr) = run_gg(&repo_path, &["config", "get", key]);
+        assert!(!success, "{} should be ignored, got: {}", key, stdout);
+    }
+
+    let (success, _stdout, stderr) = run_gg(
+        &repo_path,
+        &["config", "set", "--team", "hooks.pre_land", "./check.sh"],
+    );
+    assert!(!success);
+    assert!(stderr.contains("team config cannot set"), "{}", stderr);
+
+    // Your own config can still set them
+    let (success, _stdout, stderr) = run_gg(
+        &repo_path,
+        &["config", "set", "hooks.pre_sync", "./check.sh"],
+    );
+    assert!(success, "gg config set failed: {}", stderr);
+    let (success, stdout, _stderr) =
+        run_gg(&repo_path, &["config", "get", "hooks.pre_sync", "--origin"]);
+    assert!(success);
+    assert_eq!(stdout.trim(), "repo\t./check.sh");
+}
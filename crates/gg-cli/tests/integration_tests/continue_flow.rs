@@ -329,4 +329,87 @@ fn test_continue_finalizes_interrupted_restack_for_undo() {
     assert_next_undo_targets(&repo_path, "restack", &head_before_restack);
 }
 
+#[test]
+fn test_continue_finishes_rebase_completed_with_git() {
+    let (_temp_dir, repo_path, _remote_path) = create_test_repo_with_remote();
+    write_test_config(&repo_path);
+
+    let (success, _, stderr) = run_gg(&repo_path, &["co", "continue-after-git"]);
+    assert!(success, "co failed: {stderr}");
+
+    fs::write(repo_path.join("README.md"), "stack version\n").unwrap();
+    run_git(&repo_path, &["add", "."]);
+    run_git(&repo_path, &["commit", "-m", "Stack update"]);
+    let head_before_rebase = head_sha(&repo_path);
+
+    run_git(&repo_path, &["checkout", "main"]);
+    fs::write(repo_path.join("README.md"), "main version\n").unwrap();
+    run_git(&repo_path, &["add", "."]);
+    run_git(&repo_path, &["commit", "-m", "Main update"]);
+    run_git(&repo_path, &["push", "origin", "main"]);
+    run_git(&repo_path, &["checkout", "testuser/continue-after-git"]);
+
+    let (success, _, _) = run_gg(&repo_path, &["rebase"]);
+    assert!(!success, "rebase should conflict");
+
+    // Finish the rebase with plain git: gg still owes the operation's cleanup
+    fs::write(repo_path.join("README.md"), "resolved version\n").unwrap();
+    run_git(&repo_path, &["add", "."]);
+    let (success, _) = run_git(
+        &repo_path,
+        &["-c", "core.editor=true", "rebase", "--continue"],
+    );
+    assert!(success, "git rebase --continue failed");
+
+    let (success, stdout, stderr) = run_gg(&repo_path, &["continue"]);
+    assert!(
+        success,
+        "continue should finish the operation: stdout={stdout} stderr={stderr}"
+    );
+    let (_, branch) = run_git(&repo_path, &["symbolic-ref", "--short", "HEAD"]);
+    assert_eq!(branch.trim(), "testuser/continue-after-git");
+
+    assert_next_undo_targets(&repo_path, "rebase", &head_before_rebase);
+}
+
+#[test]
+fn test_abort_returns_to_amended_entry_after_nav_conflict() {
+    let (_temp_dir, repo_path) = create_test_repo();
+    write_test_config(&repo_path);
+
+    let (success, _, stderr) = run_gg(&repo_path, &["co", "abort-nav"]);
+    assert!(success, "co failed: {stderr}");
+
+    fs::write(repo_path.join("shared.txt"), "one\n").unwrap();
+    run_git(&repo_path, &["add", "."]);
+    run_git(&repo_path, &["commit", "-m", "Commit 1"]);
+    fs::write(repo_path.join("shared.txt"), "two\n").unwrap();
+    run_git(&repo_path, &["add", "."]);
+    run_git(&repo_path, &["commit", "-m", "Commit 2"]);
+    let branch_tip = head_sha(&repo_path);
+
+    let (success, _, stderr) = run_gg(&repo_path, &["mv", "1"]);
+    assert!(success, "mv failed: {stderr}");
+    fs::write(repo_path.join("shared.txt"), "amended\n").unwrap();
+    run_git(&repo_path, &["add", "."]);
+    run_git(&repo_path, &["commit", "--amend", "--no-edit"]);
+    let amended = head_sha(&repo_path);
+
+    let (success, stdout, stderr) = run_gg(&repo_path, &["next"]);
+    assert!(
+        !success,
+        "next should stop on the conflict: stdout={stdout} stderr={stderr}"
+    );
+
+    let (success, stdout, stderr) = run_gg(&repo_path, &["abort"]);
+    assert!(success, "abort failed: stdout={stdout} stderr={stderr}");
+
+    // Back where the navigation started, not stranded on the branch tip
+    assert!(!repo_path.join(".git/rebase-merge").exists());
+    assert_eq!(head_sha(&repo_path), amended);
+    let (_, tip) = run_git(&repo_path, &["rev-parse", "testuser/abort-nav"]);
+    assert_eq!(tip.trim(), branch_tip);
+    assert!(!repo_path.join(".git/gg/operation-state.json").exists());
+}
+
 // ==================== gg reconcile tests ====================
@@ -0,0 +1,102 @@
+use crate::helpers::{create_test_repo, run_gg, run_git};
+
+use serde_json::Value;
+use std::fs;
+use std::path::Path;
+
+fn write_config(gg_dir: &Path) {
+    fs::create_dir_all(gg_dir).expect("Failed to create gg dir");
+    fs::write(
+        gg_dir.join("config.json"),
+        r#"{"defaults":{"branch_username":"testuser"}}"#,
+    )
+    .expect("Failed to write config");
+}
+
+#[test]
+fn test_gg_describe_sets_shows_and_clears_stack_description() {
+    let (_temp_dir, repo_path) = create_test_repo();
+
+    let gg_dir = repo_path.join(".git/gg");
+    write_config(&gg_dir);
+
+    let (success, stdout, stderr) = run_gg(
+        &repo_path,
+        &["co", "parser", "-d", "Speed up the config parser"],
+    );
+    assert!(success, "co -d failed: {} {}", stdout, stderr);
+
+    let config = fs::read_to_string(gg_dir.join("config.json")).expect("Failed to read config");
+    let config: Value = serde_json::from_str(&config).expect("valid config");
+    assert_eq!(
+        config["stacks"]["parser"]["description"],
+        "Speed up the config parser"
+    );
+
+    // Also in git notes, so it can be shared with the rest of the team
+    let (success, notes) = run_git(&repo_path, &["notes", "--ref=gg-stacks", "list"]);
+    assert!(success);
+    assert_eq!(notes.lines().count(), 1, "{}", notes);
+
+    let (success, stdout, _) = run_gg(&repo_path, &["describe"]);
+    assert!(success);
+    assert!(stdout.contains("Speed up the config parser"), "{}", stdout);
+
+    let (success, stdout, stderr) = run_gg(&repo_path, &["describe", "Parse configs lazily"]);
+    assert!(success, "describe failed: {} {}", stdout, stderr);
+
+    let (success, stdout, _) = run_gg(&repo_path, &["describe", "--json"]);
+    assert!(success);
+    let parsed: Value = serde_json::from_str(&stdout).expect("valid JSON");
+    assert_eq!(parsed["stack"], "parser");
+    assert_eq!(parsed["description"], "Parse configs lazily");
+
+    let (success, stdout, _) = run_gg(&repo_path, &["ls", "--all"]);
+    assert!(success);
+    assert!(stdout.contains("Parse configs lazily"), "{}", stdout);
+
+    let (success, stdout, _) = run_gg(&repo_path, &["ls", "--all", "--json"]);
+    assert!(success);
+    let parsed: Value = serde_json::from_str(&stdout).expect("valid JSON");
+    let stacks = parsed["stacks"].as_array().expect("stacks");
+    let parser = stacks
+        .iter()
+        .find(|s| s["name"] == "parser")
+        .expect("parser stack");
+    assert_eq!(parser["description"], "Parse configs lazily");
+
+    let (success, _, _) = run_gg(&repo_path, &["describe", "--clear"]);
+    assert!(success);
+    let (_, stdout, _) = run_gg(&repo_path, &["describe", "--json"]);
+    let parsed: Value = serde_json::from_str(&stdout).expect("valid JSON");
+    assert!(parsed["description"].is_null());
+    let (_, notes) = run_git(&repo_path, &["notes", "--ref=gg-stacks", "list"]);
+    assert!(notes.trim().is_empty(), "{}", notes);
+}
+
+#[test]
+fn test_gg_describe_reads_description_from_notes() {
+    let (_temp_dir, repo_path) = create_test_repo();
+    write_config(&repo_path.join(".git/gg"));
+
+    let (success, _, stderr) = run_gg(
+        &repo_path,
+        &["co", "shared", "--description", "Shared through notes"],
+    );
+    assert!(success, "co failed: {}", stderr);
+
+    // A clone without the local config still has the notes
+    let config_path = repo_path.join(".git/gg/config.json");
+    let config = fs::read_to_string(&config_path).expect("Failed to read config");
+    let mut config: Value = serde_json::from_str(&config).expect("valid config");
+    config["stacks"]["shared"]
+        .as_object_mut()
+        .expect("stack config")
+        .remove("description");
+    fs::write(&config_path, config.to_string()).expect("Failed to write config");
+
+    let (success, stdout, _) = run_gg(&repo_path, &["describe", "-s", "shared", "--json"]);
+    assert!(success);
+    let parsed: Value = serde_json::from_str(&stdout).expect("valid JSON");
+    assert_eq!(parsed["description"], "Shared through notes");
+}
@@ -0,0 +1,55 @@
+use crate::helpers::{create_test_repo, run_gg, run_git};
+
+use std::fs;
+
+fn setup_stack(repo_path: &std::path::Path) {
+    let gg_dir = repo_path.join(".git/gg");
+    fs::create_dir_all(&gg_dir).unwrap();
+    fs::write(
+        gg_dir.join("config.json"),
+        r#"{"defaults":{"branch_username":"testuser"}}"#,
+    )
+    .unwrap();
+
+    let (success, _, stderr) = run_gg(repo_path, &["co", "diff-test"]);
+    assert!(success, "Failed to create stack: {}", stderr);
+
+    for i in 1..=3 {
+        fs::write(
+            repo_path.join(format!("file{i}.txt")),
+            format!("line {i}\n"),
+        )
+        .unwrap();
+        run_git(repo_path, &["add", "."]);
+        run_git(repo_path, &["commit", "-m", &format!("Commit {i}")]);
+    }
+}
+
+#[test]
+fn test_diff_single_entry_range_and_stack() {
+    let (_temp_dir, repo_path) = create_test_repo();
+    setup_stack(&repo_path);
+
+    let (success, stdout, stderr) = run_gg(&repo_path, &["diff", "2"]);
+    assert!(success, "diff failed: {stderr}");
+    assert!(stdout.contains("+line 2"), "{stdout}");
+    assert!(!stdout.contains("+line 1"), "{stdout}");
+
+    let (success, stdout, _) = run_gg(&repo_path, &["diff", "2..3", "--name-only"]);
+    assert!(success);
+    assert_eq!(stdout.trim(), "file2.txt\nfile3.txt");
+
+    let (success, stdout, _) = run_gg(&repo_path, &["diff", "--stat"]);
+    assert!(success);
+    assert!(stdout.contains("3 files changed"), "{stdout}");
+}
+
+#[test]
+fn test_diff_rejects_reversed_range() {
+    let (_temp_dir, repo_path) = create_test_repo();
+    setup_stack(&repo_path);
+
+    let (success, _, stderr) = run_gg(&repo_path, &["diff", "3..1"]);
+    assert!(!success);
+    assert!(stderr.contains("Invalid range"), "{stderr}");
+}
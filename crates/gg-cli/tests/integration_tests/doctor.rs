@@ -0,0 +1,90 @@
+use crate::helpers::{create_test_repo, run_gg, run_git};
+
+use serde_json::Value;
+use std::fs;
+
+fn checks(parsed: &Value, area: &str) -> Vec<Value> {
+    parsed["doctor"]["checks"]
+        .as_array()
+        .unwrap()
+        .iter()
+        .filter(|c| c["area"] == area)
+        .cloned()
+        .collect()
+}
+
+#[test]
+fn test_doctor_reports_and_fixes_repo_state() {
+    let (_temp_dir, repo_path) = create_test_repo();
+    let gg_dir = repo_path.join(".git/gg");
+    fs::create_dir_all(&gg_dir).unwrap();
+    fs::write(
+        gg_dir.join("config.json"),
+        r#"{"defaults":{"branch_username":"testuser"}}"#,
+    )
+    .unwrap();
+
+    let (success, _, stderr) = run_gg(&repo_path, &["co", "doctor"]);
+    assert!(success, "Failed to create stack: {stderr}");
+    fs::write(repo_path.join("file.txt"), "v1\n").unwrap();
+    run_git(&repo_path, &["add", "."]);
+    run_git(&repo_path, &["commit", "-m", "Commit\n\nGG-ID: c-0000001"]);
+
+    let (success, stdout, _) = run_gg(&repo_path, &["doctor", "--json"]);
+    assert!(success, "healthy repo should pass: {stdout}");
+    let parsed: Value = serde_json::from_str(&stdout).expect("valid JSON");
+    assert_eq!(parsed["doctor"]["ok"], true);
+    assert_eq!(checks(&parsed, "git")[0]["status"], "pass");
+    assert_eq!(checks(&parsed, "config")[0]["status"], "pass");
+    // The test repository has no origin
+    assert_eq!(checks(&parsed, "remote")[0]["status"], "warn");
+
+    // A mapping to a commit that is gone, and navigation state left behind
+    // after HEAD went back to the branch
+    let mut config: Value =
+        serde_json::from_str(&fs::read_to_string(gg_dir.join("config.json")).unwrap()).unwrap();
+    config["stacks"]["doctor"]["mrs"] = serde_json::json!({ "c-dead000": 42 });
+    fs::write(gg_dir.join("config.json"), config.to_string()).unwrap();
+    let (_, head) = run_git(&repo_path, &["rev-parse", "HEAD"]);
+    fs::write(
+        gg_dir.join("current_stack"),
+        format!("testuser/doctor|1|{}", head.trim()),
+    )
+    .unwrap();
+
+    let (success, stdout, _) = run_gg(&repo_path, &["doctor", "--json"]);
+    assert!(!success, "stale mapping should fail doctor: {stdout}");
+    let parsed: Value = serde_json::from_str(&stdout).expect("valid JSON");
+    let stack = checks(&parsed, "stack");
+    assert_eq!(stack[0]["status"], "fail");
+    assert_eq!(stack[0]["fixable"], true);
+    assert_eq!(checks(&parsed, "navigation")[0]["status"], "warn");
+
+    let (success, stdout, stderr) = run_gg(&repo_path, &["doctor", "--fix", "--json"]);
+    assert!(success, "doctor --fix failed: {stdout} {stderr}");
+    let parsed: Value = serde_json::from_str(&stdout).expect("valid JSON");
+    assert_eq!(parsed["doctor"]["fixed"], 2);
+    assert!(!gg_dir.join("current_stack").exists());
+    let config: Value =
+        serde_json::from_str(&fs::read_to_string(gg_dir.join("config.json")).unwrap()).unwrap();
+    assert!(config["stacks"]["doctor"]["mrs"]
+        .as_object()
+        .is_none_or(|m| m.is_empty()));
+}
+
+#[test]
+fn test_doctor_reports_invalid_config() {
+    let (_temp_dir, repo_path) = create_test_repo();
+    let gg_dir = repo_path.join(".git/gg");
+    fs::create_dir_all(&gg_dir).unwrap();
+    fs::write(
+        gg_dir.join("config.json"),
+        r#"{"defaults":{"branch_username":"testuser","unstaged_action":"maybe"}}"#,
+    )
+    .unwrap();
+
+    let (success, stdout, _) = run_gg(&repo_path, &["doctor"]);
+    assert!(!success);
+    assert!(stdout.contains("repository config"), "stdout: {stdout}");
+    assert!(stdout.contains("gg config edit"), "stdout: {stdout}");
+}
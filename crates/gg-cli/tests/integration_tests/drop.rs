@@ -348,3 +348,49 @@ fn test_drop_last_commit() {
 // ==========================================================================
 // gg run tests
 // ==========================================================================
+
+#[test]
+fn test_drop_removes_mr_mapping() {
+    let (_temp_dir, repo_path) = create_test_repo();
+
+    let gg_dir = repo_path.join(".git/gg");
+    fs::create_dir_all(&gg_dir).unwrap();
+    fs::write(
+        gg_dir.join("config.json"),
+        r#"{"defaults":{"branch_username":"testuser"}}"#,
+    )
+    .unwrap();
+
+    let (success, _, stderr) = run_gg(&repo_path, &["co", "drop-mapping"]);
+    assert!(success, "Failed to create stack: {}", stderr);
+
+    for i in 1..=2 {
+        fs::write(repo_path.join(format!("file{}.txt", i)), "content").unwrap();
+        run_git(&repo_path, &["add", "."]);
+        run_git(
+            &repo_path,
+            &[
+                "commit",
+                "-m",
+                &format!("Commit {i}\n\nGG-ID: c-{i}{i}{i}{i}{i}{i}{i}"),
+            ],
+        );
+    }
+
+    let config_path = gg_dir.join("config.json");
+    let mut config: Value =
+        serde_json::from_str(&fs::read_to_string(&config_path).unwrap()).unwrap();
+    config["stacks"]["drop-mapping"]["mrs"] =
+        serde_json::json!({ "c-1111111": 11, "c-2222222": 22 });
+    fs::write(&config_path, config.to_string()).unwrap();
+
+    let (success, stdout, stderr) = run_gg(&repo_path, &["drop", "2", "--yes", "--json"]);
+    assert!(success, "drop failed: {stdout} {stderr}");
+    let json: Value = serde_json::from_str(&stdout).expect("valid JSON");
+    assert!(json["drop"].get("closed_prs").is_none());
+
+    let config: Value = serde_json::from_str(&fs::read_to_string(&config_path).unwrap()).unwrap();
+    let mrs = &config["stacks"]["drop-mapping"]["mrs"];
+    assert_eq!(mrs["c-1111111"], 11);
+    assert!(mrs.get("c-2222222").is_none(), "{mrs}");
+}
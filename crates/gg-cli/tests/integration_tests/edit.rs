@@ -0,0 +1,76 @@
+use crate::helpers::{create_test_repo, run_gg, run_git};
+
+use std::fs;
+
+fn setup_stack(repo_path: &std::path::Path) {
+    let gg_dir = repo_path.join(".git/gg");
+    fs::create_dir_all(&gg_dir).unwrap();
+    fs::write(
+        gg_dir.join("config.json"),
+        r#"{"defaults":{"branch_username":"testuser"}}"#,
+    )
+    .unwrap();
+
+    let (success, _, stderr) = run_gg(repo_path, &["co", "edit-test"]);
+    assert!(success, "Failed to create stack: {}", stderr);
+
+    for i in 1..=3 {
+        fs::write(repo_path.join(format!("file{i}.txt")), format!("{i}\n")).unwrap();
+        run_git(repo_path, &["add", "."]);
+        run_git(
+            repo_path,
+            &[
+                "commit",
+                "-m",
+                &format!("Commit {i}\n\nGG-ID: c-{i}{i}{i}{i}{i}{i}{i}"),
+            ],
+        );
+    }
+}
+
+#[test]
+fn test_edit_rewords_mid_stack_entry() {
+    let (_temp_dir, repo_path) = create_test_repo();
+    setup_stack(&repo_path);
+    let (_, tree_before) = run_git(&repo_path, &["rev-parse", "HEAD^{tree}"]);
+
+    let (success, stdout, stderr) = run_gg(
+        &repo_path,
+        &["edit", "2", "-m", "Better title\n\nWith a body"],
+    );
+    assert!(success, "edit failed: {stdout} {stderr}");
+    assert!(stdout.contains("Reworded #2"), "{stdout}");
+
+    let (_, message) = run_git(&repo_path, &["log", "-1", "--format=%B", "HEAD~1"]);
+    assert!(
+        message.starts_with("Better title\n\nWith a body"),
+        "{message}"
+    );
+    assert!(message.contains("GG-ID: c-2222222"), "{message}");
+
+    // Entries above are untouched apart from their parent.
+    let (_, message) = run_git(&repo_path, &["log", "-1", "--format=%B"]);
+    assert!(message.starts_with("Commit 3"), "{message}");
+    let (_, tree_after) = run_git(&repo_path, &["rev-parse", "HEAD^{tree}"]);
+    assert_eq!(tree_before, tree_after);
+    let (_, branch) = run_git(&repo_path, &["branch", "--show-current"]);
+    assert_eq!(branch.trim(), "testuser/edit-test");
+}
+
+#[test]
+fn test_edit_keeps_detached_head_on_its_entry() {
+    let (_temp_dir, repo_path) = create_test_repo();
+    setup_stack(&repo_path);
+
+    let (success, _, stderr) = run_gg(&repo_path, &["mv", "2"]);
+    assert!(success, "mv failed: {stderr}");
+
+    let (success, stdout, stderr) = run_gg(&repo_path, &["edit", "-m", "Reworded"]);
+    assert!(success, "edit failed: {stdout} {stderr}");
+
+    let (_, title) = run_git(&repo_path, &["log", "-1", "--format=%s"]);
+    assert_eq!(title.trim(), "Reworded");
+    let (_, tip) = run_git(&repo_path, &["rev-parse", "testuser/edit-test~1"]);
+    let (_, head) = run_git(&repo_path, &["rev-parse", "HEAD"]);
+    assert_eq!(tip, head);
+}
@@ -0,0 +1,75 @@
+use crate::helpers::{create_test_repo, run_gg, run_git};
+
+use std::fs;
+
+fn setup_stack() -> (tempfile::TempDir, std::path::PathBuf) {
+    let (temp_dir, repo_path) = create_test_repo();
+    let gg_dir = repo_path.join(".git/gg");
+    fs::create_dir_all(&gg_dir).unwrap();
+    fs::write(
+        gg_dir.join("config.json"),
+        r#"{"defaults":{"branch_username":"testuser"}}"#,
+    )
+    .unwrap();
+
+    let (success, _, stderr) = run_gg(&repo_path, &["co", "export-test"]);
+    assert!(success, "Failed to create stack: {}", stderr);
+    for i in 1..=2 {
+        fs::write(repo_path.join(format!("file{i}.txt")), "v1\n").unwrap();
+        run_git(&repo_path, &["add", "."]);
+        run_git(
+            &repo_path,
+            &["commit", "-m", &format!("Commit {i}\n\nGG-ID: c-{i:07}")],
+        );
+    }
+    (temp_dir, repo_path)
+}
+
+#[test]
+fn test_export_writes_numbered_patches() {
+    let (temp_dir, repo_path) = setup_stack();
+    let out = temp_dir.path().join("patches");
+
+    let (success, stdout, stderr) = run_gg(&repo_path, &["export", out.to_str().unwrap()]);
+    assert!(success, "export failed: {} {}", stdout, stderr);
+
+    let mut names: Vec<String> = fs::read_dir(&out)
+        .unwrap()
+        .map(|e| e.unwrap().file_name().to_string_lossy().into_owned())
+        .collect();
+    names.sort();
+    assert_eq!(names, ["0001-Commit-1.patch", "0002-Commit-2.patch"]);
+    let second = fs::read_to_string(out.join(&names[1])).unwrap();
+    assert!(second.contains("GG-ID: c-0000002"), "{}", second);
+}
+
+#[test]
+fn test_export_mbox_and_bundle() {
+    let (temp_dir, repo_path) = setup_stack();
+    let out = temp_dir.path().join("out");
+
+    let (success, _, stderr) = run_gg(
+        &repo_path,
+        &["export", out.to_str().unwrap(), "--format", "mbox"],
+    );
+    assert!(success, "mbox export failed: {}", stderr);
+    let mbox = fs::read_to_string(out.join("export-test.mbox")).unwrap();
+    assert_eq!(mbox.matches("\nSubject: ").count(), 2, "{}", mbox);
+
+    let (success, _, stderr) = run_gg(
+        &repo_path,
+        &["export", out.to_str().unwrap(), "--format", "bundle"],
+    );
+    assert!(success, "bundle export failed: {}", stderr);
+    let bundle = out.join("export-test.bundle");
+    let (valid, output) = run_git(
+        &repo_path,
+        &["bundle", "list-heads", bundle.to_str().unwrap()],
+    );
+    assert!(valid, "{}", output);
+    assert!(
+        output.contains("refs/heads/testuser/export-test"),
+        "{}",
+        output
+    );
+}
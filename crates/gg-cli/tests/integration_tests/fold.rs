@@ -0,0 +1,73 @@
+use crate::helpers::{create_test_repo, run_gg, run_git};
+
+use serde_json::Value;
+use std::fs;
+
+fn setup_stack(repo_path: &std::path::Path, commits: usize) {
+    let gg_dir = repo_path.join(".git/gg");
+    fs::create_dir_all(&gg_dir).unwrap();
+    fs::write(
+        gg_dir.join("config.json"),
+        r#"{"defaults":{"branch_username":"testuser"}}"#,
+    )
+    .unwrap();
+
+    let (success, _, stderr) = run_gg(repo_path, &["co", "fold-test"]);
+    assert!(success, "Failed to create stack: {}", stderr);
+
+    for i in 1..=commits {
+        fs::write(
+            repo_path.join(format!("file{}.txt", i)),
+            format!("content {}", i),
+        )
+        .unwrap();
+        run_git(repo_path, &["add", "."]);
+        run_git(
+            repo_path,
+            &[
+                "commit",
+                "-m",
+                &format!(
+                    "Commit {}\n\nGG-ID: c-{}{}{}{}{}{}{}",
+                    i, i, i, i, i, i, i, i
+                ),
+            ],
+        );
+    }
+}
+
+#[test]
+fn test_fold_merges_entry_into_parent() {
+    let (_temp_dir, repo_path) = create_test_repo();
+    setup_stack(&repo_path, 3);
+
+    let (success, stdout, stderr) = run_gg(&repo_path, &["fold", "2", "--json"]);
+    assert!(success, "fold failed: {} {}", stdout, stderr);
+    let json: Value = serde_json::from_str(&stdout).expect("valid JSON");
+    assert_eq!(json["folded"], 2);
+    assert_eq!(json["into"], 1);
+    assert_eq!(json["gg_id"], "c-1111111");
+    assert_eq!(json["remaining"], 2);
+
+    let (_, message) = run_git(&repo_path, &["log", "-1", "--format=%B", "HEAD~1"]);
+    assert!(message.starts_with("Commit 1\n\nCommit 2"), "{message}");
+    assert!(message.contains("GG-ID: c-1111111"), "{message}");
+    assert!(!message.contains("c-2222222"), "{message}");
+
+    // The entry above keeps its contents and GG-ID.
+    let (_, message) = run_git(&repo_path, &["log", "-1", "--format=%B"]);
+    assert!(message.contains("GG-ID: c-3333333"), "{message}");
+    for i in 1..=3 {
+        assert!(repo_path.join(format!("file{}.txt", i)).exists());
+    }
+}
+
+#[test]
+fn test_fold_rejects_first_entry() {
+    let (_temp_dir, repo_path) = create_test_repo();
+    setup_stack(&repo_path, 2);
+
+    let (success, _stdout, stderr) = run_gg(&repo_path, &["fold", "1"]);
+    assert!(!success);
+    assert!(stderr.contains("Cannot fold the first entry"), "{stderr}");
+}
@@ -1,6 +1,10 @@
 use std::fs;
-use std::path::PathBuf;
+use std::io::{BufRead, BufReader, Read, Write};
+use std::net::TcpListener;
+use std::path::{Path, PathBuf};
 use std::process::Command;
+use std::sync::{Arc, Mutex};
+use std::thread;
 
 use tempfile::TempDir;
 
@@ -60,6 +64,7 @@ pub(crate) fn run_gg(repo_path: &std::path::Path, args: &[&str]) -> (bool, Strin
         .args(args)
         .current_dir(repo_path)
         .env("HOME", isolated_home(repo_path))
+        .env_remove("XDG_CONFIG_HOME")
         .output()
         .expect("Failed to run gg");
 
@@ -79,7 +84,8 @@ pub(crate) fn run_gg_with_env(
     let mut cmd = Command::new(gg_path);
     cmd.args(args)
         .current_dir(repo_path)
-        .env("HOME", isolated_home(repo_path));
+        .env("HOME", isolated_home(repo_path))
+        .env_remove("XDG_CONFIG_HOME");
     for (key, value) in envs {
         cmd.env(key, value);
     }
@@ -248,3 +254,72 @@ pub(crate) fn create_worktree(main_repo: &PathBuf, name: &str) -> PathBuf {
     );
     worktree_path
 }
+
+/// Requests the server has answered, as `METHOD /path`.
+pub(crate) type Seen = Arc<Mutex<Vec<String>>>;
+
+/// Serve HTTP on a free local port, answering each request with the status
+/// and JSON body `respond(method, path, body)` returns. Returns the server's
+/// base URL; point `GG_API_URL` at it to stand in for the provider API.
+pub(crate) fn serve(
+    respond: impl Fn(&str, &str, &str) -> (u16, String) + Send + 'static,
+) -> (String, Seen) {
+    let listener = TcpListener::bind("127.0.0.1:0").expect("Failed to bind mock server");
+    let url = format!("http://{}", listener.local_addr().unwrap());
+    let seen = Seen::default();
+    let log = seen.clone();
+
+    thread::spawn(move || {
+        for stream in listener.incoming() {
+            let Ok(mut stream) = stream else { continue };
+            let mut reader = BufReader::new(stream.try_clone().unwrap());
+
+            let mut request_line = String::new();
+            reader.read_line(&mut request_line).unwrap();
+            let mut parts = request_line.split_whitespace();
+            let method = parts.next().unwrap_or_default().to_string();
+            let path = parts.next().unwrap_or_default().to_string();
+
+            let mut content_length = 0;
+            loop {
+                let mut header = String::new();
+                reader.read_line(&mut header).unwrap();
+                if header.trim().is_empty() {
+                    break;
+                }
+                if let Some((name, value)) = header.split_once(':') {
+                    if name.eq_ignore_ascii_case("content-length") {
+                        content_length = value.trim().parse().unwrap_or(0);
+                    }
+                }
+            }
+            let mut body = vec![0; content_length];
+            reader.read_exact(&mut body).unwrap();
+
+            let (status, response) = respond(&method, &path, &String::from_utf8_lossy(&body));
+            log.lock().unwrap().push(format!("{} {}", method, path));
+            write!(
+                stream,
+                "HTTP/1.1 {} X\r\nContent-Type: application/json\r\nContent-Length: {}\r\nConnection: close\r\n\r\n{}",
+                status,
+                response.len(),
+                response
+            )
+            .unwrap();
+        }
+    });
+
+    (url, seen)
+}
+
+/// A directory holding only `git`, to use as the whole of PATH.
+pub(crate) fn path_without_provider_clis(repo_path: &Path) -> PathBuf {
+    let git = std::env::split_paths(&std::env::var_os("PATH").unwrap())
+        .map(|dir| dir.join("git"))
+        .find(|candidate| candidate.is_file())
+        .expect("git not found on PATH");
+    let bin = repo_path.join(".test-bin");
+    fs::create_dir_all(&bin).unwrap();
+    std::os::unix::fs::symlink(git, bin.join("git")).unwrap();
+    bin
+}
@@ -0,0 +1,88 @@
+use crate::helpers::{create_test_repo, run_gg, run_git};
+
+use std::fs;
+
+fn setup_feature_branch() -> (tempfile::TempDir, std::path::PathBuf) {
+    let (temp_dir, repo_path) = create_test_repo();
+    let gg_dir = repo_path.join(".git/gg");
+    fs::create_dir_all(&gg_dir).unwrap();
+    fs::write(
+        gg_dir.join("config.json"),
+        r#"{"defaults":{"branch_username":"testuser"}}"#,
+    )
+    .unwrap();
+
+    run_git(&repo_path, &["checkout", "-b", "feature/login-form"]);
+    for i in 1..=2 {
+        fs::write(repo_path.join(format!("file{i}.txt")), "v1\n").unwrap();
+        run_git(&repo_path, &["add", "."]);
+        run_git(&repo_path, &["commit", "-m", &format!("Commit {i}")]);
+    }
+    run_git(&repo_path, &["checkout", "main"]);
+    (temp_dir, repo_path)
+}
+
+#[test]
+fn test_import_adopts_branch_as_stack() {
+    let (_temp_dir, repo_path) = setup_feature_branch();
+
+    let (success, stdout, stderr) = run_gg(&repo_path, &["import", "feature/login-form"]);
+    assert!(success, "import failed: {} {}", stdout, stderr);
+    assert!(stdout.contains("login-form"), "{}", stdout);
+
+    // Both commits got GG-IDs on the new stack branch
+    let (_, messages) = run_git(
+        &repo_path,
+        &["log", "--format=%B", "main..testuser/login-form"],
+    );
+    assert_eq!(messages.matches("GG-ID:").count(), 2, "{}", messages);
+
+    // The original branch is untouched
+    let (_, original) = run_git(
+        &repo_path,
+        &["log", "--format=%B", "main..feature/login-form"],
+    );
+    assert!(!original.contains("GG-ID:"), "{}", original);
+
+    let (success, _, stderr) = run_gg(&repo_path, &["co", "login-form"]);
+    assert!(success, "co failed: {}", stderr);
+    let (success, stdout, _) = run_gg(&repo_path, &["ls", "--json"]);
+    assert!(success);
+    let parsed: serde_json::Value = serde_json::from_str(&stdout).expect("valid JSON");
+    assert_eq!(parsed["stack"]["total_commits"], 2);
+}
+
+#[test]
+fn test_import_rejects_existing_stack_name() {
+    let (_temp_dir, repo_path) = setup_feature_branch();
+
+    let (success, _, stderr) = run_gg(&repo_path, &["import", "feature/login-form"]);
+    assert!(success, "import failed: {}", stderr);
+
+    let (success, _, stderr) = run_gg(&repo_path, &["import", "feature/login-form"]);
+    assert!(!success);
+    assert!(stderr.contains("already exists"), "{}", stderr);
+
+    let (success, stdout, stderr) = run_gg(
+        &repo_path,
+        &["import", "feature/login-form", "--name", "login-v2"],
+    );
+    assert!(success, "import --name failed: {} {}", stdout, stderr);
+    let (exists, _) = run_git(&repo_path, &["rev-parse", "--verify", "testuser/login-v2"]);
+    assert!(exists);
+}
+
+#[test]
+fn test_import_branch_without_commits_fails() {
+    let (_temp_dir, repo_path) = setup_feature_branch();
+    run_git(&repo_path, &["branch", "empty-branch", "main"]);
+
+    let (success, _, stderr) = run_gg(&repo_path, &["import", "empty-branch"]);
+    assert!(!success);
+    assert!(stderr.contains("no commits"), "{}", stderr);
+    let (exists, _) = run_git(
+        &repo_path,
+        &["rev-parse", "--verify", "testuser/empty-branch"],
+    );
+    assert!(!exists);
+}
@@ -0,0 +1,75 @@
+use crate::helpers::{create_test_repo, run_gg};
+
+use serde_json::Value;
+use std::fs;
+
+#[test]
+fn test_gg_insights_json_empty_log() {
+    let (_temp_dir, repo_path) = create_test_repo();
+
+    let (success, stdout, stderr) = run_gg(&repo_path, &["insights", "--json"]);
+    assert!(success, "gg insights --json failed: {}", stderr);
+
+    let parsed: Value = serde_json::from_str(&stdout).expect("stdout must be valid JSON");
+    assert_eq!(parsed["version"], 1);
+    assert_eq!(parsed["insights"]["operations"], 0);
+    assert!(parsed["insights"]["stacks"].as_array().unwrap().is_empty());
+    assert!(parsed["insights"]["median_sync_to_land_ms"].is_null());
+}
+
+#[test]
+fn test_gg_insights_human_empty_log() {
+    let (_temp_dir, repo_path) = create_test_repo();
+
+    let (success, stdout, stderr) = run_gg(&repo_path, &["insights"]);
+    assert!(success, "gg insights failed: {}", stderr);
+    assert!(stdout.contains("No operations recorded yet."));
+}
+
+#[test]
+fn test_gg_insights_reports_sync_to_land_from_op_log() {
+    let (_temp_dir, repo_path) = create_test_repo();
+
+    let ops_dir = repo_path.join(".git/gg/operations");
+    fs::create_dir_all(&ops_dir).unwrap();
+    let hour = 60 * 60 * 1000u64;
+    let records = [
+        (
+            hour,
+            "sync",
+            r#"[{"kind":"pr_created","number":7,"url":"https://example.com/7"}]"#,
+            false,
+        ),
+        (2 * hour, "rebase", "[]", true),
+        (
+            5 * hour,
+            "land",
+            r#"[{"kind":"pr_merged","number":7,"url":"https://example.com/7"}]"#,
+            false,
+        ),
+    ];
+    for (ts, kind, effects, conflicted) in records {
+        let id = format!("op_{ts:013}_fixture{kind}");
+        let json = format!(
+            r#"{{"id":"{id}","schema_version":2,"kind":"{kind}","status":"committed","created_at_ms":{ts},"args":[],"stack_name":"auth","refs_before":[],"remote_effects":{effects},"touched_remote":true,"conflicted":{conflicted}}}"#
+        );
+        fs::write(ops_dir.join(format!("{id}.json")), json).unwrap();
+    }
+
+    let (success, stdout, stderr) = run_gg(&repo_path, &["insights", "--json"]);
+    assert!(success, "gg insights --json failed: {}", stderr);
+
+    let parsed: Value = serde_json::from_str(&stdout).expect("stdout must be valid JSON");
+    let insights = &parsed["insights"];
+    assert_eq!(insights["operations"], 3);
+    assert_eq!(insights["syncs"], 1);
+    assert_eq!(insights["conflicts"], 1);
+    assert_eq!(insights["prs_created"], 1);
+    assert_eq!(insights["prs_merged"], 1);
+    assert_eq!(insights["stacks_landed"], 1);
+    assert_eq!(insights["median_sync_to_land_ms"], 4 * hour);
+
+    let stack = &insights["stacks"][0];
+    assert_eq!(stack["name"], "auth");
+    assert_eq!(stack["sync_to_land_ms"], 4 * hour);
+}
@@ -0,0 +1,38 @@
+use crate::helpers::{create_test_repo, run_gg, run_git};
+
+use std::fs;
+
+#[test]
+fn test_gg_label_help() {
+    let (_temp_dir, repo_path) = create_test_repo();
+    let (success, stdout, _stderr) = run_gg(&repo_path, &["label", "--help"]);
+
+    assert!(success);
+    assert!(stdout.contains("--remove"));
+    assert!(stdout.contains("<LABELS>"));
+}
+
+#[test]
+fn test_gg_label_requires_synced_entry() {
+    let (_temp_dir, repo_path) = create_test_repo();
+    let gg_dir = repo_path.join(".git/gg");
+    fs::create_dir_all(&gg_dir).expect("Failed to create gg dir");
+    fs::write(
+        gg_dir.join("config.json"),
+        r#"{"defaults":{"branch_username":"testuser","provider":"github"}}"#,
+    )
+    .expect("Failed to write config");
+
+    let (success, _stdout, stderr) = run_gg(&repo_path, &["co", "label-stack"]);
+    assert!(success, "Failed to create stack: {}", stderr);
+    fs::write(repo_path.join("a.txt"), "a\n").expect("Failed to write file");
+    run_git(&repo_path, &["add", "a.txt"]);
+    run_git(&repo_path, &["commit", "-m", "Add a"]);
+
+    let (success, _stdout, stderr) = run_gg(&repo_path, &["label", "1", "bug"]);
+    assert!(!success);
+    assert!(stderr.contains("has no PR"), "stderr: {stderr}");
+
+    let (success, _stdout, _stderr) = run_gg(&repo_path, &["label", "1"]);
+    assert!(!success, "label should require at least one label name");
+}
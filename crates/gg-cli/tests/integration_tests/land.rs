@@ -86,6 +86,111 @@ fn test_land_help_shows_admin_option() {
     );
 }
 
+#[test]
+fn test_land_strategy_values() {
+    let (_temp_dir, repo_path) = create_test_repo();
+
+    let (success, _stdout, stderr) = run_gg(&repo_path, &["land", "--strategy", "octopus"]);
+    assert!(!success, "Unknown strategies should be rejected");
+    assert!(
+        stderr.contains("squash") && stderr.contains("merge") && stderr.contains("rebase"),
+        "Should list the valid strategies: {}",
+        stderr
+    );
+
+    let (success, _stdout, stderr) =
+        run_gg(&repo_path, &["land", "--strategy", "rebase", "--no-squash"]);
+    assert!(!success, "--strategy and --no-squash should conflict");
+    assert!(
+        stderr.contains("cannot be used with"),
+        "Should report the conflict: {}",
+        stderr
+    );
+}
+
+#[test]
+fn test_land_auto_resolve_restack_is_interactive_only() {
+    let (_temp_dir, repo_path) = create_test_repo();
+
+    let (success, stdout, _stderr) = run_gg(&repo_path, &["land", "--help"]);
+    assert!(success);
+    assert!(
+        stdout.contains("--auto-resolve-restack"),
+        "Should show --auto-resolve-restack: {}",
+        stdout
+    );
+
+    let (success, _stdout, stderr) = run_gg(
+        &repo_path,
+        &["land", "--all", "--auto-resolve-restack", "--json"],
+    );
+    assert!(
+        !success,
+        "--auto-resolve-restack should conflict with --json"
+    );
+    assert!(
+        stderr.contains("cannot be used with"),
+        "Should report the conflict: {}",
+        stderr
+    );
+}
+
+#[test]
+fn test_land_resume_without_session() {
+    let (_temp_dir, repo_path) = create_test_repo();
+
+    let (success, _stdout, stderr) = run_gg(&repo_path, &["land", "--resume"]);
+    assert!(!success, "--resume needs a saved session");
+    assert!(
+        stderr.contains("No land session to resume"),
+        "Should explain there is nothing to resume: {}",
+        stderr
+    );
+
+    let (success, _stdout, stderr) = run_gg(&repo_path, &["land", "--resume", "--all"]);
+    assert!(!success, "--resume reuses the saved flags");
+    assert!(
+        stderr.contains("cannot be used with"),
+        "Should report the conflict: {}",
+        stderr
+    );
+}
+
+#[test]
+fn test_land_retrigger_ci_requires_wait() {
+    let (_temp_dir, repo_path) = create_test_repo();
+
+    let (success, _stdout, stderr) = run_gg(&repo_path, &["land", "--retrigger-ci"]);
+    assert!(!success, "--retrigger-ci without --wait should be rejected");
+    assert!(
+        stderr.contains("--wait"),
+        "Should point at the missing --wait flag: {}",
+        stderr
+    );
+}
+
+#[test]
+fn test_land_listen_requires_wait() {
+    let (_temp_dir, repo_path) = create_test_repo();
+
+    let (success, _stdout, stderr) = run_gg(&repo_path, &["land", "--listen"]);
+
+    assert!(!success, "--listen without --wait should be rejected");
+    assert!(
+        stderr.contains("--wait"),
+        "Should point at the missing --wait flag: {}",
+        stderr
+    );
+
+    let (success, stdout, _stderr) = run_gg(&repo_path, &["land", "--help"]);
+    assert!(success);
+    assert!(
+        stdout.contains("--listen"),
+        "Should show --listen: {}",
+        stdout
+    );
+}
+
 #[test]
 fn test_land_admin_flag_accepted() {
     // Test that the --admin flag is recognized and doesn't cause a clap error
@@ -1053,3 +1053,241 @@ fn test_lint_position_clamped_to_stack_size() {
     );
 }
 // ========== Split command tests ==========
+
+#[test]
+fn test_lint_jobs_replays_fixes_in_stack_order() {
+    let (_temp_dir, repo_path) = create_test_repo();
+
+    let gg_dir = repo_path.join(".git/gg");
+    fs::create_dir_all(&gg_dir).expect("Failed to create gg dir");
+    fs::write(
+        gg_dir.join("config.json"),
+        r#"{"defaults":{"branch_username":"testuser","lint":["./lint-fix.py"]}}"#,
+    )
+    .expect("Failed to write config");
+
+    // Replaces BAD with GOOD in every file2/file3 present in the commit
+    let lint_script = repo_path.join("lint-fix.py");
+    fs::write(
+        &lint_script,
+        r#"#!/usr/bin/env python3
+from pathlib import Path
+for name in ("file2.txt", "file3.txt"):
+    path = Path(name)
+    if path.exists():
+        path.write_text(path.read_text().replace("BAD", "GOOD"))
+"#,
+    )
+    .expect("Failed to write lint script");
+
+    #[cfg(unix)]
+    {
+        let mut perms = fs::metadata(&lint_script)
+            .expect("Failed to read lint script metadata")
+            .permissions();
+        perms.set_mode(0o755);
+        fs::set_permissions(&lint_script, perms).expect("Failed to chmod lint script");
+    }
+
+    let (success, _stdout, stderr) = run_gg(&repo_path, &["co", "lint-jobs-stack"]);
+    assert!(success, "Failed to create stack: {}", stderr);
+
+    // Commit 1 needs a fix that commit 2 inherits; commit 3 needs its own
+    fs::write(repo_path.join("file2.txt"), "BAD").expect("Failed to write file2");
+    run_git(&repo_path, &["add", "."]);
+    run_git(&repo_path, &["commit", "-m", "Commit 1"]);
+
+    fs::write(repo_path.join("file1.txt"), "one").expect("Failed to write file1");
+    run_git(&repo_path, &["add", "."]);
+    run_git(&repo_path, &["commit", "-m", "Commit 2"]);
+
+    fs::write(repo_path.join("file3.txt"), "BAD").expect("Failed to write file3");
+    run_git(&repo_path, &["add", "."]);
+    run_git(&repo_path, &["commit", "-m", "Commit 3"]);
+
+    let (success, stdout, stderr) = run_gg(&repo_path, &["lint", "--jobs", "3", "--json"]);
+    assert!(success, "gg lint --jobs failed: {}", stderr);
+    let parsed: Value = serde_json::from_str(&stdout).expect("stdout must be valid JSON");
+    assert_eq!(parsed["lint"]["all_passed"], true);
+    assert_eq!(parsed["lint"]["results"].as_array().unwrap().len(), 3);
+
+    let (_ok, branch) = run_git(&repo_path, &["rev-parse", "--abbrev-ref", "HEAD"]);
+    assert_eq!(branch.trim(), "testuser/lint-jobs-stack");
+    let (_ok, status) = run_git(&repo_path, &["status", "--porcelain"]);
+    assert!(status.trim().is_empty(), "worktree not clean: {}", status);
+
+    let (_ok, log) = run_git(&repo_path, &["log", "--format=%s", "-n", "3"]);
+    assert_eq!(
+        log.lines().collect::<Vec<_>>(),
+        ["Commit 3", "Commit 2", "Commit 1"]
+    );
+    for (rev, file) in [
+        ("HEAD~2", "file2.txt"),
+        ("HEAD~1", "file2.txt"),
+        ("HEAD", "file2.txt"),
+        ("HEAD", "file3.txt"),
+    ] {
+        let (_ok, content) = run_git(&repo_path, &["show", &format!("{}:{}", rev, file)]);
+        assert_eq!(content.trim(), "GOOD", "{}:{}", rev, file);
+    }
+    // Commit 2 only keeps its own change; the fix lives in commit 1
+    let (_ok, files) = run_git(&repo_path, &["show", "--name-only", "--format=", "HEAD~1"]);
+    assert_eq!(files.trim(), "file1.txt");
+}
+
+#[test]
+fn test_lint_skips_commits_outside_command_paths() {
+    let (_temp_dir, repo_path) = create_test_repo();
+
+    let gg_dir = repo_path.join(".git/gg");
+    fs::create_dir_all(&gg_dir).expect("Failed to create gg dir");
+    fs::write(
+        gg_dir.join("config.json"),
+        r#"{"defaults":{"branch_username":"testuser","lint":["true",{"cmd":"false","paths":["**/*.kt"]}]}}"#,
+    )
+    .expect("Failed to write config");
+
+    let (success, _stdout, stderr) = run_gg(&repo_path, &["co", "lint-paths-stack"]);
+    assert!(success, "Failed to create stack: {}", stderr);
+
+    fs::create_dir_all(repo_path.join("src")).expect("Failed to create src");
+    fs::write(repo_path.join("src/Main.kt"), "fun main() {}").expect("Failed to write kt");
+    run_git(&repo_path, &["add", "."]);
+    run_git(&repo_path, &["commit", "-m", "Kotlin change"]);
+
+    fs::write(repo_path.join("notes.txt"), "notes").expect("Failed to write notes");
+    run_git(&repo_path, &["add", "."]);
+    run_git(&repo_path, &["commit", "-m", "Docs change"]);
+
+    for jobs in ["1", "2"] {
+        let (_success, stdout, stderr) = run_gg(&repo_path, &["lint", "--jobs", jobs, "--json"]);
+        let parsed: Value = serde_json::from_str(&stdout)
+            .unwrap_or_else(|e| panic!("invalid JSON ({}): {}\n{}", e, stdout, stderr));
+        let results = parsed["lint"]["results"].as_array().unwrap();
+        assert_eq!(results.len(), 2, "jobs={}", jobs);
+
+        // The .kt commit runs both commands, and `false` fails it
+        assert_eq!(results[0]["passed"], false, "jobs={}", jobs);
+        assert_eq!(results[0]["commands"].as_array().unwrap().len(), 2);
+        assert!(results[0].get("skipped").is_none());
+
+        // The .txt commit only runs the unscoped command
+        assert_eq!(results[1]["passed"], true, "jobs={}", jobs);
+        assert_eq!(results[1]["commands"].as_array().unwrap().len(), 1);
+        assert_eq!(results[1]["commands"][0]["command"], "true");
+        assert_eq!(results[1]["skipped"], serde_json::json!(["false"]));
+    }
+}
+
+#[test]
+fn test_lint_from_and_range_select_positions() {
+    let (_temp_dir, repo_path) = create_test_repo();
+
+    let gg_dir = repo_path.join(".git/gg");
+    fs::create_dir_all(&gg_dir).expect("Failed to create gg dir");
+    fs::write(
+        gg_dir.join("config.json"),
+        r#"{"defaults":{"branch_username":"testuser","lint":["true"]}}"#,
+    )
+    .expect("Failed to write config");
+
+    let (success, _stdout, stderr) = run_gg(&repo_path, &["co", "lint-range-stack"]);
+    assert!(success, "Failed to create stack: {}", stderr);
+
+    for n in 1..=4 {
+        fs::write(repo_path.join(format!("file{}.txt", n)), "content")
+            .expect("Failed to write file");
+        run_git(&repo_path, &["add", "."]);
+        run_git(&repo_path, &["commit", "-m", &format!("Commit {}", n)]);
+    }
+
+    let positions = |args: &[&str]| -> Vec<u64> {
+        let mut full = vec!["lint", "--json"];
+        full.extend_from_slice(args);
+        let (success, stdout, stderr) = run_gg(&repo_path, &full);
+        assert!(success, "gg {:?} failed: {}", full, stderr);
+        let parsed: Value = serde_json::from_str(&stdout).expect("stdout must be valid JSON");
+        parsed["lint"]["results"]
+            .as_array()
+            .unwrap()
+            .iter()
+            .map(|r| r["position"].as_u64().unwrap())
+            .collect()
+    };
+
+    assert_eq!(positions(&["--from", "3"]), vec![3, 4]);
+    assert_eq!(positions(&["--from", "2", "--until", "3"]), vec![2, 3]);
+    assert_eq!(positions(&["--range", "2..3"]), vec![2, 3]);
+    assert_eq!(positions(&["--range", "..2"]), vec![1, 2]);
+    assert_eq!(positions(&["--range", "2..3", "--jobs", "2"]), vec![2, 3]);
+
+    let (success, _stdout, stderr) = run_gg(&repo_path, &["lint", "--from", "3", "--until", "2"]);
+    assert!(!success);
+    assert!(
+        stderr.contains("after the end position"),
+        "stderr: {}",
+        stderr
+    );
+
+    let (success, _stdout, stderr) = run_gg(&repo_path, &["lint", "--range", "2"]);
+    assert!(!success);
+    assert!(stderr.contains("Invalid range"), "stderr: {}", stderr);
+}
+
+#[test]
+fn test_lint_commit_overrides_configured_commands() {
+    let (_temp_dir, repo_path) = create_test_repo();
+
+    let gg_dir = repo_path.join(".git/gg");
+    fs::create_dir_all(&gg_dir).expect("Failed to create gg dir");
+    fs::write(
+        gg_dir.join("config.json"),
+        r#"{"defaults":{"branch_username":"testuser","lint":["true"]}}"#,
+    )
+    .expect("Failed to write config");
+
+    let (success, _stdout, stderr) = run_gg(&repo_path, &["co", "lint-override-stack"]);
+    assert!(success, "Failed to create stack: {}", stderr);
+
+    fs::write(repo_path.join("a.txt"), "a").expect("Failed to write file");
+    run_git(&repo_path, &["add", "."]);
+    run_git(&repo_path, &["commit", "-m", "Plain change"]);
+
+    fs::write(repo_path.join("b.txt"), "b").expect("Failed to write file");
+    run_git(&repo_path, &["add", "."]);
+    run_git(
+        &repo_path,
+        &["commit", "-m", "Trailer change\n\nGG-Lint: false"],
+    );
+
+    fs::create_dir_all(repo_path.join(".gg")).expect("Failed to create .gg");
+    fs::write(
+        repo_path.join(".gg/lint.json"),
+        r#"[{"cmd":"false","paths":["**/*.kt"]}]"#,
+    )
+    .expect("Failed to write lint.json");
+    run_git(&repo_path, &["add", "."]);
+    run_git(&repo_path, &["commit", "-m", "Lint file change"]);
+
+    for jobs in ["1", "2"] {
+        let (_success, stdout, stderr) = run_gg(&repo_path, &["lint", "--jobs", jobs, "--json"]);
+        let parsed: Value = serde_json::from_str(&stdout)
+            .unwrap_or_else(|e| panic!("invalid JSON ({}): {}\n{}", e, stdout, stderr));
+        let results = parsed["lint"]["results"].as_array().unwrap();
+        assert_eq!(results.len(), 3, "jobs={}", jobs);
+
+        // No override: the configured command runs
+        assert_eq!(results[0]["commands"][0]["command"], "true");
+        assert_eq!(results[0]["passed"], true, "jobs={}", jobs);
+
+        // The trailer replaces the configured command
+        assert_eq!(results[1]["commands"].as_array().unwrap().len(), 1);
+        assert_eq!(results[1]["commands"][0]["command"], "false");
+        assert_eq!(results[1]["passed"], false, "jobs={}", jobs);
+
+        // .gg/lint.json replaces it too, path filters included
+        assert!(results[2]["commands"].as_array().unwrap().is_empty());
+        assert_eq!(results[2]["skipped"], serde_json::json!(["false"]));
+        assert_eq!(results[2]["passed"], true, "jobs={}", jobs);
+    }
+}
@@ -198,6 +198,10 @@ fn test_gg_log_not_on_stack() {
         stderr.contains("not a stack branch"),
         "stderr should explain we are not on a stack: {stderr}"
     );
+    assert!(
+        stderr.contains("error[GG_NOT_ON_STACK]:"),
+        "stderr should carry the error code: {stderr}"
+    );
 
     // JSON mode surfaces the same condition as a structured error on stdout.
     let (success, stdout, _stderr) = run_gg(&repo_path, &["log", "--json"]);
@@ -205,6 +209,7 @@ fn test_gg_log_not_on_stack() {
     let parsed: Value = serde_json::from_str(&stdout).expect("stdout must be valid JSON");
     assert_eq!(parsed["version"], 1);
     assert!(parsed["error"].is_string(), "error field must be string");
+    assert_eq!(parsed["code"], "GG_NOT_ON_STACK");
 }
 
 #[test]
@@ -1,6 +1,6 @@
 use crate::helpers::{
     create_test_repo, create_test_repo_with_remote, create_test_repo_with_worktree_support,
-    create_worktree, run_gg, run_gg_with_env, run_git,
+    create_worktree, path_without_provider_clis, run_gg, run_gg_with_env, run_git,
 };
 
 use serde_json::Value;
@@ -321,9 +321,7 @@ fn test_gg_ls_json_omits_stale_interrupted_rebase_operation_id() {
         "stale operation markers must be omitted: {parsed:?}"
     );
     assert!(
-        !repo_path
-            .join(".git/gg/interrupted-rebase-operation.json")
-            .exists(),
+        !repo_path.join(".git/gg/operation-state.json").exists(),
         "validating a stale marker must clear it"
     );
 }
@@ -393,6 +391,7 @@ fn write_interrupted_rebase_marker(
 ) {
     let marker = serde_json::json!({
         "operation_id": operation_id,
+        "kind": "rebase",
         "rebase_state": {
             "head_name": head_name,
             "orig_head": orig_head,
@@ -406,11 +405,8 @@ fn write_interrupted_rebase_marker(
 }
 
 fn write_raw_interrupted_rebase_marker(repo_path: &std::path::Path, bytes: Vec<u8>) {
-    fs::write(
-        repo_path.join(".git/gg/interrupted-rebase-operation.json"),
-        bytes,
-    )
-    .expect("Failed to write interrupted rebase marker");
+    fs::write(repo_path.join(".git/gg/operation-state.json"), bytes)
+        .expect("Failed to write interrupted rebase marker");
 }
 
 fn assert_stale_marker_is_omitted_and_cleared(repo_path: &std::path::Path) {
@@ -423,9 +419,7 @@ fn assert_stale_marker_is_omitted_and_cleared(repo_path: &std::path::Path) {
         "stale operation markers must be omitted: {parsed:?}"
     );
     assert!(
-        !repo_path
-            .join(".git/gg/interrupted-rebase-operation.json")
-            .exists(),
+        !repo_path.join(".git/gg/operation-state.json").exists(),
         "validating a stale marker must clear it"
     );
 }
@@ -478,6 +472,78 @@ fn test_gg_ls_all_json() {
     );
 }
 
+#[test]
+fn test_gg_ls_filter_and_sort_all_stacks() {
+    let (_temp_dir, repo_path) = create_test_repo();
+
+    let gg_dir = repo_path.join(".git/gg");
+    fs::create_dir_all(&gg_dir).expect("Failed to create gg dir");
+    fs::write(
+        gg_dir.join("config.json"),
+        r#"{"defaults":{"branch_username":"testuser"}}"#,
+    )
+    .expect("Failed to write config");
+
+    for (stack, commits) in [("api-small", 1), ("api-large", 3), ("docs", 2)] {
+        run_git(&repo_path, &["checkout", "main"]);
+        let (success, _stdout, stderr) = run_gg(&repo_path, &["co", stack]);
+        assert!(success, "Failed to create {}: {}", stack, stderr);
+        for i in 0..commits {
+            fs::write(repo_path.join(format!("{}-{}.txt", stack, i)), "x")
+                .expect("Failed to write file");
+            run_git(&repo_path, &["add", "."]);
+            run_git(
+                &repo_path,
+                &[
+                    "-c",
+                    "user.name=Alice Example",
+                    "commit",
+                    "-m",
+                    &format!("{} {}", stack, i),
+                ],
+            );
+        }
+    }
+    run_git(&repo_path, &["checkout", "main"]);
+
+    let (success, stdout, stderr) = run_gg(
+        &repo_path,
+        &["ls", "--filter", "API", "--sort", "size", "--json"],
+    );
+    assert!(success, "gg ls --filter failed: {}", stderr);
+    let parsed: Value = serde_json::from_str(&stdout).expect("stdout must be valid JSON");
+    assert_eq!(parsed["filter"], "API");
+    assert_eq!(parsed["sort"], "size");
+    assert_eq!(parsed["total_stacks"], 3);
+    let names: Vec<&str> = parsed["stacks"]
+        .as_array()
+        .expect("stacks must be an array")
+        .iter()
+        .map(|s| s["name"].as_str().unwrap())
+        .collect();
+    assert_eq!(names, vec!["api-large", "api-small"]);
+
+    let (success, stdout, stderr) = run_gg(&repo_path, &["ls", "--filter", "author:alice"]);
+    assert!(success, "gg ls --filter author failed: {}", stderr);
+    assert!(
+        stdout.contains("(3 of 3 matching 'author:alice')"),
+        "{}",
+        stdout
+    );
+
+    let (success, stdout, stderr) = run_gg(&repo_path, &["ls", "--filter", "author:bob"]);
+    assert!(success, "gg ls --filter author failed: {}", stderr);
+    assert!(
+        stdout.contains("No stacks match 'author:bob' (3 stacks in total)."),
+        "{}",
+        stdout
+    );
+
+    let (success, _stdout, stderr) = run_gg(&repo_path, &["ls", "--filter", "state:pending"]);
+    assert!(!success);
+    assert!(stderr.contains("invalid state 'pending'"), "{}", stderr);
+}
+
 #[test]
 fn test_gg_ls_shows_behind_indicator_when_base_is_behind_origin() {
     let (_temp_dir, repo_path, _remote_path) = create_test_repo_with_remote();
@@ -520,6 +586,61 @@ fn test_gg_ls_shows_behind_indicator_when_base_is_behind_origin() {
     );
 }
 
+#[test]
+fn test_gg_ls_all_shows_last_push_time() {
+    let (_temp_dir, repo_path, _remote_path) = create_test_repo_with_remote();
+
+    let gg_dir = repo_path.join(".git/gg");
+    fs::create_dir_all(&gg_dir).expect("Failed to create gg dir");
+    fs::write(
+        gg_dir.join("config.json"),
+        r#"{"defaults":{"branch_username":"testuser"}}"#,
+    )
+    .expect("Failed to write config");
+
+    let (success, _stdout, stderr) = run_gg(&repo_path, &["co", "activity"]);
+    assert!(success, "Failed to create stack: {}", stderr);
+
+    fs::write(repo_path.join("pushed.txt"), "pushed").expect("Failed to write file");
+    run_git(&repo_path, &["add", "."]);
+    run_git(
+        &repo_path,
+        &["commit", "-m", "Pushed entry\n\nGG-ID: c-1234567"],
+    );
+    run_git(
+        &repo_path,
+        &[
+            "push",
+            "origin",
+            "HEAD:refs/heads/testuser/activity--c-1234567",
+        ],
+    );
+
+    fs::write(repo_path.join("local.txt"), "local").expect("Failed to write file");
+    run_git(&repo_path, &["add", "."]);
+    run_git(
+        &repo_path,
+        &["commit", "-m", "Local entry\n\nGG-ID: c-7654321"],
+    );
+
+    let (success, stdout, stderr) = run_gg(&repo_path, &["ls", "--all"]);
+    assert!(success, "ls --all failed: {}", stderr);
+    assert!(
+        stdout.contains("Pushed entry  (pushed just now)"),
+        "Expected push time in output: {}",
+        stdout
+    );
+    assert!(stdout.contains("updated just now"), "stdout: {}", stdout);
+    assert!(!stdout.contains("Local entry  ("), "stdout: {}", stdout);
+
+    let (success, stdout, stderr) = run_gg(&repo_path, &["ls", "--all", "--json"]);
+    assert!(success, "ls --all --json failed: {}", stderr);
+    let parsed: Value = serde_json::from_str(&stdout).expect("valid JSON");
+    let commits = &parsed["stacks"][0]["commits"];
+    assert!(commits[0]["pushed_at_ms"].as_u64().is_some());
+    assert!(commits[1].get("pushed_at_ms").is_none());
+}
+
 #[test]
 fn test_gg_ls_remote_no_stacks() {
     let (_temp_dir, repo_path, _remote_path) = create_test_repo_with_remote();
@@ -784,3 +905,215 @@ fn test_ls_json_includes_unintegrated_commits() {
         "no entry should be current while an orphan exists: {out}"
     );
 }
+
+#[test]
+fn test_gg_ls_remote_user_shows_teammate_stack() {
+    let (_temp_dir, repo_path, _remote_path) = create_test_repo_with_remote();
+
+    let gg_dir = repo_path.join(".git/gg");
+    fs::create_dir_all(&gg_dir).expect("Failed to create gg dir");
+    fs::write(
+        gg_dir.join("config.json"),
+        r#"{"defaults":{"branch_username":"testuser"}}"#,
+    )
+    .expect("Failed to write config");
+
+    // Simulate a teammate's synced stack: two entry branches, no stack branch.
+    run_git(&repo_path, &["checkout", "-b", "scratch"]);
+    fs::write(repo_path.join("a.txt"), "a").expect("Failed to write file");
+    run_git(&repo_path, &["add", "."]);
+    run_git(&repo_path, &["commit", "-m", "Add a\n\nGG-ID: c-aaaaaaa"]);
+    fs::write(repo_path.join("b.txt"), "b").expect("Failed to write file");
+    run_git(&repo_path, &["add", "."]);
+    run_git(
+        &repo_path,
+        &[
+            "commit",
+            "-m",
+            "Add b\n\nGG-ID: c-bbbbbbb\nGG-Parent: c-aaaaaaa",
+        ],
+    );
+    run_git(
+        &repo_path,
+        &["push", "origin", "HEAD~1:refs/heads/alice/feat--c-aaaaaaa"],
+    );
+    run_git(
+        &repo_path,
+        &["push", "origin", "HEAD:refs/heads/alice/feat--c-bbbbbbb"],
+    );
+    run_git(&repo_path, &["checkout", "main"]);
+    run_git(&repo_path, &["branch", "-D", "scratch"]);
+
+    let (success, stdout, stderr) =
+        run_gg(&repo_path, &["ls", "--remote", "--user", "alice", "--json"]);
+    assert!(success, "ls --remote --user failed: {stderr}");
+
+    let parsed: serde_json::Value = serde_json::from_str(&stdout).expect("valid JSON");
+    assert_eq!(parsed["user"], "alice");
+    let stacks = parsed["stacks"].as_array().expect("stacks array");
+    assert_eq!(stacks.len(), 1);
+    assert_eq!(stacks[0]["name"], "feat");
+    let entries = stacks[0]["entries"].as_array().expect("entries array");
+    assert_eq!(entries.len(), 2);
+    assert_eq!(entries[0]["title"], "Add a");
+    assert_eq!(entries[0]["gg_id"], "c-aaaaaaa");
+    assert_eq!(entries[1]["title"], "Add b");
+
+    // Own remote stacks are not mixed in, and nothing gets checked out.
+    let (success, stdout, _) = run_gg(&repo_path, &["ls", "--remote", "--user", "alice"]);
+    assert!(success);
+    assert!(stdout.contains("alice/feat"), "stdout={stdout}");
+    assert!(stdout.contains("Add b"), "stdout={stdout}");
+    let (_, branches) = run_git(&repo_path, &["branch", "--list", "alice/*"]);
+    assert!(branches.trim().is_empty(), "must not create local branches");
+}
+
+#[test]
+fn test_gg_ls_remote_user_uses_stack_remote_and_reports_failures() {
+    let (temp_dir, repo_path, _remote_path) = create_test_repo_with_remote();
+    let upstream_path = temp_dir.path().join("upstream.git");
+    run_git(
+        temp_dir.path(),
+        &["init", "--bare", upstream_path.to_str().unwrap()],
+    );
+    run_git(
+        &repo_path,
+        &["remote", "add", "upstream", upstream_path.to_str().unwrap()],
+    );
+    run_git(&repo_path, &["push", "upstream", "main"]);
+
+    let gg_dir = repo_path.join(".git/gg");
+    fs::create_dir_all(&gg_dir).expect("Failed to create gg dir");
+    fs::write(
+        gg_dir.join("config.json"),
+        r#"{"defaults":{"branch_username":"testuser","provider":"github"}}"#,
+    )
+    .expect("Failed to write config");
+
+    // alice pushed her stack to upstream only
+    run_git(&repo_path, &["checkout", "-b", "scratch"]);
+    fs::write(repo_path.join("a.txt"), "a").expect("Failed to write file");
+    run_git(&repo_path, &["add", "."]);
+    run_git(&repo_path, &["commit", "-m", "Add a\n\nGG-ID: c-aaaaaaa"]);
+    run_git(
+        &repo_path,
+        &["push", "upstream", "HEAD:refs/heads/alice/feat--c-aaaaaaa"],
+    );
+    run_git(&repo_path, &["checkout", "main"]);
+    run_git(&repo_path, &["branch", "-D", "scratch"]);
+
+    let (success, _stdout, stderr) = run_gg(&repo_path, &["co", "mine", "--remote", "upstream"]);
+    assert!(success, "Failed to create stack: {stderr}");
+
+    // Without gh the PR/MR lookups fail: a warning, not a failed listing
+    let bin = path_without_provider_clis(&repo_path);
+    let env = [("PATH", bin.as_os_str())];
+    let (success, stdout, stderr) = run_gg_with_env(
+        &repo_path,
+        &["ls", "--remote", "--user", "alice", "--json"],
+        &env,
+    );
+    assert!(success, "ls --remote --user failed: {stderr}");
+    let parsed: Value = serde_json::from_str(&stdout).expect("valid JSON");
+    assert_eq!(parsed["stacks"][0]["name"], "feat");
+    assert_eq!(parsed["stacks"][0]["entries"][0]["title"], "Add a");
+    let warnings = parsed["warnings"].as_array().expect("warnings array");
+    assert!(
+        warnings.iter().any(|w| w
+            .as_str()
+            .unwrap()
+            .contains("Could not look up the PR of alice/feat--c-aaaaaaa")),
+        "{warnings:?}"
+    );
+
+    // A failed fetch is reported too
+    fs::rename(&upstream_path, temp_dir.path().join("gone.git")).expect("Failed to move");
+    let (success, stdout, stderr) = run_gg_with_env(
+        &repo_path,
+        &["ls", "--remote", "--user", "alice", "--json"],
+        &env,
+    );
+    assert!(success, "ls --remote --user failed: {stderr}");
+    let parsed: Value = serde_json::from_str(&stdout).expect("valid JSON");
+    assert_eq!(parsed["stacks"][0]["name"], "feat");
+    assert!(
+        parsed["warnings"][0]
+            .as_str()
+            .unwrap()
+            .starts_with("Could not fetch from upstream"),
+        "{}",
+        parsed["warnings"]
+    );
+}
+
+#[test]
+fn test_gg_ls_user_requires_remote() {
+    let (_temp_dir, repo_path) = create_test_repo();
+
+    let (success, _, stderr) = run_gg(&repo_path, &["ls", "--user", "alice"]);
+    assert!(!success);
+    assert!(stderr.contains("--remote"), "stderr={stderr}");
+}
+
+#[test]
+fn test_gg_ls_global_lists_stacks_of_registered_repos() {
+    let home = tempfile::TempDir::new().expect("Failed to create home");
+    let env = [("HOME", home.path().as_os_str())];
+    let (_dir_a, repo_a) = create_test_repo();
+    let (_dir_b, repo_b) = create_test_repo();
+
+    for (repo_path, stack) in [(&repo_a, "api"), (&repo_b, "web")] {
+        let gg_dir = repo_path.join(".git/gg");
+        fs::create_dir_all(&gg_dir).expect("Failed to create gg dir");
+        fs::write(
+            gg_dir.join("config.json"),
+            r#"{"defaults":{"branch_username":"testuser"}}"#,
+        )
+        .expect("Failed to write config");
+        let (success, _stdout, stderr) = run_gg_with_env(repo_path, &["co", stack], &env);
+        assert!(success, "Failed to create {}: {}", stack, stderr);
+        fs::write(repo_path.join(format!("{}.txt", stack)), stack).expect("Failed to write");
+        run_git(repo_path, &["add", "."]);
+        run_git(repo_path, &["commit", "-m", stack]);
+
+        let (success, stdout, stderr) = run_gg_with_env(repo_path, &["setup", "--register"], &env);
+        assert!(success, "gg setup --register failed: {}", stderr);
+        assert!(stdout.contains("Registered"), "{}", stdout);
+    }
+    let (success, stdout, _stderr) = run_gg_with_env(&repo_a, &["setup", "--register"], &env);
+    assert!(success);
+    assert!(stdout.contains("Already registered"), "{}", stdout);
+
+    // Works outside any repository
+    let (success, stdout, stderr) =
+        run_gg_with_env(home.path(), &["ls", "--global", "--json"], &env);
+    assert!(success, "gg ls --global failed: {}", stderr);
+    let parsed: Value = serde_json::from_str(&stdout).expect("stdout must be valid JSON");
+    let repos = parsed["repos"].as_array().expect("repos must be an array");
+    assert_eq!(repos.len(), 2);
+    assert_eq!(repos[0]["stacks"][0]["name"], "api");
+    assert_eq!(repos[0]["stacks"][0]["commit_count"], 1);
+    assert_eq!(repos[0]["current_stack"], "api");
+    assert_eq!(repos[1]["stacks"][0]["name"], "web");
+
+    let (success, stdout, stderr) = run_gg_with_env(home.path(), &["ls", "--global"], &env);
+    assert!(success, "gg ls --global failed: {}", stderr);
+    assert!(stdout.contains("api (1 commit)"), "{}", stdout);
+    assert!(stdout.contains("web (1 commit)"), "{}", stdout);
+
+    // A repository that went away is reported, not fatal
+    fs::remove_dir_all(&repo_b).expect("Failed to remove repo");
+    let (success, stdout, stderr) =
+        run_gg_with_env(home.path(), &["ls", "--global", "--json"], &env);
+    assert!(success, "gg ls --global failed: {}", stderr);
+    let parsed: Value = serde_json::from_str(&stdout).expect("stdout must be valid JSON");
+    assert!(parsed["repos"][1]["error"].is_string(), "{}", stdout);
+
+    let (success, stdout, _stderr) = run_gg_with_env(&repo_a, &["setup", "--unregister"], &env);
+    assert!(success);
+    assert!(stdout.contains("Unregistered"), "{}", stdout);
+    let (_success, stdout, _stderr) =
+        run_gg_with_env(home.path(), &["ls", "--global", "--json"], &env);
+    let parsed: Value = serde_json::from_str(&stdout).expect("stdout must be valid JSON");
+    assert_eq!(parsed["repos"].as_array().unwrap().len(), 1);
+}
@@ -5,25 +5,57 @@
 mod helpers;
 
 mod absorb;
+mod annotate;
+mod check_msgs;
 mod checkout;
+mod ci;
 mod clean;
+mod comment;
+mod comments;
+mod config;
 mod continue_flow;
+mod describe;
+mod diff;
+mod doctor;
 mod drop;
+mod edit;
+mod export;
+mod fold;
+mod import;
 mod inbox;
+mod insights;
+mod label;
 mod land;
 mod lint;
 mod log;
 mod ls;
 mod misc;
+mod move_commit;
+mod native_api;
 mod navigation;
+mod open;
+mod plain;
+mod protected_branches;
+mod ready;
 mod rebase;
 mod reconcile;
 mod reorder;
 mod restack;
+mod retarget;
+mod review;
 mod run;
+mod scope;
+mod signing;
+mod snapshot;
 mod split;
 mod squash;
+mod squash_all;
+mod stash;
+mod status;
 mod sync;
 mod undo;
 mod unstack;
+mod use_stack;
+mod verify;
+mod watch;
 mod worktree;
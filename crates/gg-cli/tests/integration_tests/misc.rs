@@ -190,6 +190,23 @@ fn test_init_rejects_unsupported_shell() {
     assert!(stderr.contains("invalid value") || stderr.contains("possible values"));
 }
 
+#[test]
+fn test_init_requires_shell_or_migrate() {
+    let (_temp_dir, repo_path) = create_test_repo();
+
+    let (success, _stdout, stderr) = run_gg(&repo_path, &["init"]);
+    assert!(!success);
+    assert!(stderr.contains("<SHELL>"), "stderr: {}", stderr);
+
+    let (success, _stdout, stderr) = run_gg(&repo_path, &["init", "bash", "--migrate"]);
+    assert!(!success);
+    assert!(stderr.contains("cannot be used with"), "stderr: {}", stderr);
+
+    let (success, _stdout, stderr) = run_gg(&repo_path, &["init", "bash", "--dry-run"]);
+    assert!(!success);
+    assert!(stderr.contains("--dry-run"), "stderr: {}", stderr);
+}
+
 #[test]
 fn test_stack_name_sanitization_spaces_to_kebab() {
     let (_temp_dir, repo_path) = create_test_repo();
@@ -0,0 +1,80 @@
+use crate::helpers::{create_test_repo, run_gg, run_git};
+
+use std::fs;
+
+fn setup_two_stacks() -> (tempfile::TempDir, std::path::PathBuf) {
+    let (temp_dir, repo_path) = create_test_repo();
+    let gg_dir = repo_path.join(".git/gg");
+    fs::create_dir_all(&gg_dir).unwrap();
+    fs::write(
+        gg_dir.join("config.json"),
+        r#"{"defaults":{"branch_username":"testuser"}}"#,
+    )
+    .unwrap();
+
+    let (success, _, stderr) = run_gg(&repo_path, &["co", "other"]);
+    assert!(success, "Failed to create stack: {}", stderr);
+    fs::write(repo_path.join("other.txt"), "other\n").unwrap();
+    run_git(&repo_path, &["add", "."]);
+    run_git(&repo_path, &["commit", "-m", "Other commit"]);
+
+    let (success, _, stderr) = run_gg(&repo_path, &["co", "source"]);
+    assert!(success, "Failed to create stack: {}", stderr);
+    for i in 1..=3 {
+        fs::write(repo_path.join(format!("file{i}.txt")), "v1\n").unwrap();
+        run_git(&repo_path, &["add", "."]);
+        run_git(
+            &repo_path,
+            &["commit", "-m", &format!("Commit {i}\n\nGG-ID: c-{i:07}")],
+        );
+    }
+    (temp_dir, repo_path)
+}
+
+#[test]
+fn test_mv_to_moves_commit_and_pr_mapping() {
+    let (_temp_dir, repo_path) = setup_two_stacks();
+    let config_path = repo_path.join(".git/gg/config.json");
+    let mut config: serde_json::Value =
+        serde_json::from_str(&fs::read_to_string(&config_path).unwrap()).unwrap();
+    config["stacks"]["source"]["mrs"] = serde_json::json!({ "c-0000002": 42 });
+    fs::write(&config_path, config.to_string()).unwrap();
+
+    let (success, stdout, stderr) = run_gg(&repo_path, &["mv", "2", "--to", "other"]);
+    assert!(success, "mv --to failed: {} {}", stdout, stderr);
+    assert!(stdout.contains("Moved #2"), "{}", stdout);
+
+    let (_, source_log) = run_git(&repo_path, &["log", "--format=%s", "main..testuser/source"]);
+    assert_eq!(
+        source_log.lines().collect::<Vec<_>>(),
+        ["Commit 3", "Commit 1"]
+    );
+    let (_, other_log) = run_git(&repo_path, &["log", "--format=%s", "main..testuser/other"]);
+    assert_eq!(
+        other_log.lines().collect::<Vec<_>>(),
+        ["Commit 2", "Other commit"]
+    );
+    let (_, message) = run_git(&repo_path, &["log", "-1", "--format=%B", "testuser/other"]);
+    assert!(message.contains("GG-ID: c-0000002"), "{}", message);
+
+    let config: serde_json::Value =
+        serde_json::from_str(&fs::read_to_string(&config_path).unwrap()).unwrap();
+    assert_eq!(config["stacks"]["other"]["mrs"]["c-0000002"], 42);
+    assert!(config["stacks"]["source"]["mrs"]["c-0000002"].is_null());
+
+    let (_, branch) = run_git(&repo_path, &["rev-parse", "--abbrev-ref", "HEAD"]);
+    assert_eq!(branch.trim(), "testuser/source");
+}
+
+#[test]
+fn test_mv_to_unknown_stack_fails() {
+    let (_temp_dir, repo_path) = setup_two_stacks();
+    let (_, before) = run_git(&repo_path, &["rev-parse", "HEAD"]);
+
+    let (success, _, stderr) = run_gg(&repo_path, &["mv", "2", "--to", "missing"]);
+    assert!(!success);
+    assert!(stderr.contains("missing"), "{}", stderr);
+
+    let (_, after) = run_git(&repo_path, &["rev-parse", "HEAD"]);
+    assert_eq!(before, after);
+}
@@ -0,0 +1,163 @@
+//! `api_mode: native` against a local stand-in for the provider API, with
+//! neither gh nor glab on PATH.
+
+use std::ffi::OsStr;
+use std::fs;
+use std::path::PathBuf;
+
+use crate::helpers::{
+    create_test_repo, path_without_provider_clis, run_gg_with_env, run_git, serve,
+};
+
+const DISCUSSIONS: &str = r#"[{"id":"d1f2","notes":[{"body":"Rename this","author":{"username":"rev"},"resolvable":true,"resolved":false,"position":{"new_path":"feature.txt","new_line":1}}]}]"#;
+
+const REVIEW_THREADS: &str = r#"{"data":{"repository":{"pullRequest":{"reviewThreads":{"nodes":[{"id":"PRRT_abc","isResolved":false,"isOutdated":false,"path":"feature.txt","line":1,"originalLine":1,"comments":{"nodes":[{"author":{"login":"rev"},"body":"Rename this","createdAt":"2026-01-01T00:00:00Z"}]}}]}}}}}"#;
+
+/// A repo on stack `feat` whose one commit has MR/PR #7.
+fn native_repo(provider: &str, origin: &str) -> (tempfile::TempDir, PathBuf) {
+    let (temp_dir, repo_path) = create_test_repo();
+    run_git(&repo_path, &["remote", "add", "origin", origin]);
+
+    let gg_dir = repo_path.join(".git/gg");
+    fs::create_dir_all(&gg_dir).unwrap();
+    fs::write(
+        gg_dir.join("config.json"),
+        format!(
+            r#"{{"defaults":{{"branch_username":"testuser","base":"main","provider":"{}"}},"stacks":{{"feat":{{"mrs":{{"c-abc1234":7}}}}}}}}"#,
+            provider
+        ),
+    )
+    .unwrap();
+
+    run_git(&repo_path, &["checkout", "-b", "testuser/feat"]);
+    fs::write(repo_path.join("feature.txt"), "feature\n").unwrap();
+    run_git(&repo_path, &["add", "."]);
+    run_git(
+        &repo_path,
+        &["commit", "-m", "feat: add feature\n\nGG-ID: c-abc1234"],
+    );
+
+    (temp_dir, repo_path)
+}
+
+#[test]
+fn test_native_gitlab_comments_without_glab() {
+    let (url, seen) = serve(|method, path, _body| match (method, path) {
+        ("GET", "/api/v4/user") => (200, r#"{"username":"testuser"}"#.to_string()),
+        ("GET", p) if p.starts_with("/api/v4/projects/g%2Fp/merge_requests/7/discussions") => {
+            (200, DISCUSSIONS.to_string())
+        }
+        ("PUT", "/api/v4/projects/g%2Fp/merge_requests/7/discussions/d1f2") => {
+            (200, r#"{"id":"d1f2"}"#.to_string())
+        }
+        _ => (200, "{}".to_string()),
+    });
+    let (_temp_dir, repo_path) = native_repo("gitlab", "https://gitlab.example.com/g/p.git");
+    let bin = path_without_provider_clis(&repo_path);
+    let api_url = format!("{}/api/v4", url);
+    let envs: [(&str, &OsStr); 4] = [
+        ("PATH", bin.as_os_str()),
+        ("GG_API_MODE", OsStr::new("native")),
+        ("GG_API_URL", OsStr::new(&api_url)),
+        ("GITLAB_TOKEN", OsStr::new("test-token")),
+    ];
+
+    let (success, stdout, stderr) = run_gg_with_env(&repo_path, &["comments", "--json"], &envs);
+    assert!(success, "comments failed: {}", stderr);
+    assert!(stdout.contains("Rename this"), "stdout: {}", stdout);
+
+    let (success, _stdout, stderr) =
+        run_gg_with_env(&repo_path, &["comments", "resolve", "d1f2"], &envs);
+    assert!(success, "comments resolve failed: {}", stderr);
+
+    let seen = seen.lock().unwrap();
+    assert!(
+        seen.contains(&"PUT /api/v4/projects/g%2Fp/merge_requests/7/discussions/d1f2".to_string()),
+        "requests: {:?}",
+        seen
+    );
+}
+
+#[test]
+fn test_native_github_comments_without_gh() {
+    let (url, seen) = serve(|method, path, body| match (method, path) {
+        ("GET", "/api/v3/user") => (200, r#"{"login":"testuser"}"#.to_string()),
+        ("POST", "/api/graphql") if body.contains("resolveReviewThread") => (
+            200,
+            r#"{"data":{"resolveReviewThread":{"thread":{"isResolved":true}}}}"#.to_string(),
+        ),
+        ("POST", "/api/graphql") => (200, REVIEW_THREADS.to_string()),
+        _ => (200, "{}".to_string()),
+    });
+    let (_temp_dir, repo_path) = native_repo("github", "https://github.example.com/o/r.git");
+    let bin = path_without_provider_clis(&repo_path);
+    let api_url = format!("{}/api/v3", url);
+    let envs: [(&str, &OsStr); 4] = [
+        ("PATH", bin.as_os_str()),
+        ("GG_API_MODE", OsStr::new("native")),
+        ("GG_API_URL", OsStr::new(&api_url)),
+        ("GH_TOKEN", OsStr::new("test-token")),
+    ];
+
+    let (success, stdout, stderr) = run_gg_with_env(&repo_path, &["comments", "--json"], &envs);
+    assert!(success, "comments failed: {}", stderr);
+    assert!(stdout.contains("Rename this"), "stdout: {}", stdout);
+
+    let (success, _stdout, stderr) =
+        run_gg_with_env(&repo_path, &["comments", "resolve", "PRRT_abc"], &envs);
+    assert!(success, "comments resolve failed: {}", stderr);
+
+    assert_eq!(
+        seen.lock()
+            .unwrap()
+            .iter()
+            .filter(|r| *r == "POST /api/graphql")
+            .count(),
+        3,
+        "list, list again to match the id, then resolve"
+    );
+}
+
+#[test]
+fn test_native_gitlab_uses_the_stack_remote_project() {
+    let (url, seen) = serve(|method, path, _body| match (method, path) {
+        ("GET", "/api/v4/user") => (200, r#"{"username":"testuser"}"#.to_string()),
+        ("GET", p) if p.starts_with("/api/v4/projects/up%2Fp/merge_requests/7/discussions") => {
+            (200, DISCUSSIONS.to_string())
+        }
+        _ => (404, r#"{"message":"404 Not Found"}"#.to_string()),
+    });
+    let (_temp_dir, repo_path) = native_repo("gitlab", "https://gitlab.example.com/me/p.git");
+    run_git(
+        &repo_path,
+        &[
+            "remote",
+            "add",
+            "upstream",
+            "https://gitlab.example.com/up/p.git",
+        ],
+    );
+    fs::write(
+        repo_path.join(".git/gg/config.json"),
+        r#"{"defaults":{"branch_username":"testuser","base":"main","provider":"gitlab"},"stacks":{"feat":{"remote":"upstream","mrs":{"c-abc1234":7}}}}"#,
+    )
+    .unwrap();
+    let bin = path_without_provider_clis(&repo_path);
+    let api_url = format!("{}/api/v4", url);
+    let envs: [(&str, &OsStr); 4] = [
+        ("PATH", bin.as_os_str()),
+        ("GG_API_MODE", OsStr::new("native")),
+        ("GG_API_URL", OsStr::new(&api_url)),
+        ("GITLAB_TOKEN", OsStr::new("test-token")),
+    ];
+
+    let (success, stdout, stderr) = run_gg_with_env(&repo_path, &["comments", "--json"], &envs);
+    assert!(success, "comments failed: {}", stderr);
+    assert!(stdout.contains("Rename this"), "stdout: {}", stdout);
+    let seen = seen.lock().unwrap();
+    assert!(
+        !seen.iter().any(|r| r.contains("me%2Fp")),
+        "requests: {:?}",
+        seen
+    );
+}
@@ -390,3 +390,70 @@ fn test_nav_requires_stack() {
     assert!(!success, "Nav prev should fail when not on a stack");
     assert!(stderr.contains("not a stack branch"));
 }
+
+#[test]
+fn test_gg_mv_by_gg_id_and_pr_number() {
+    let (_temp_dir, repo_path) = create_test_repo();
+    let gg_dir = repo_path.join(".git/gg");
+    fs::create_dir_all(&gg_dir).expect("Failed to create gg dir");
+    fs::write(
+        gg_dir.join("config.json"),
+        r#"{"defaults":{"branch_username":"testuser"},"stacks":{"mv-ids":{"mrs":{"c-bbbbbbb":42}}}}"#,
+    )
+    .expect("Failed to write config");
+
+    let (success, _, stderr) = run_gg(&repo_path, &["co", "mv-ids"]);
+    assert!(success, "Failed to create stack: {stderr}");
+    for (file, id) in [("a", "c-aaaaaaa"), ("b", "c-bbbbbbb"), ("c", "c-ccccccc")] {
+        fs::write(repo_path.join(format!("{file}.txt")), "x\n").expect("Failed to write");
+        run_git(&repo_path, &["add", &format!("{file}.txt")]);
+        run_git(
+            &repo_path,
+            &["commit", "-m", &format!("Add {file}\n\nGG-ID: {id}")],
+        );
+    }
+
+    let (success, _, stderr) = run_gg(&repo_path, &["mv", "c-aaaaaaa"]);
+    assert!(success, "mv by GG-ID failed: {stderr}");
+    let (_, title) = run_git(&repo_path, &["log", "-1", "--format=%s"]);
+    assert_eq!(title.trim(), "Add a");
+
+    let (success, _, stderr) = run_gg(&repo_path, &["mv", "#42"]);
+    assert!(success, "mv by PR number failed: {stderr}");
+    let (_, title) = run_git(&repo_path, &["log", "-1", "--format=%s"]);
+    assert_eq!(title.trim(), "Add b");
+
+    let (success, _, stderr) = run_gg(&repo_path, &["mv", "#7"]);
+    assert!(!success);
+    assert!(stderr.contains("PR/MR #7"), "{stderr}");
+}
+
+#[test]
+fn test_gg_mv_and_co_without_target_need_a_terminal() {
+    let (_temp_dir, repo_path) = create_test_repo();
+    let gg_dir = repo_path.join(".git/gg");
+    fs::create_dir_all(&gg_dir).expect("Failed to create gg dir");
+    fs::write(
+        gg_dir.join("config.json"),
+        r#"{"defaults":{"branch_username":"testuser"}}"#,
+    )
+    .expect("Failed to write config");
+
+    let (success, _, stderr) = run_gg(&repo_path, &["co", "picker"]);
+    assert!(success, "Failed to create stack: {stderr}");
+    fs::write(repo_path.join("a.txt"), "a\n").expect("Failed to write");
+    run_git(&repo_path, &["add", "a.txt"]);
+    run_git(&repo_path, &["commit", "-m", "Add a"]);
+
+    let (success, _, stderr) = run_gg(&repo_path, &["mv"]);
+    assert!(!success);
+    assert!(stderr.contains("gg mv <target>"), "{stderr}");
+
+    let (success, _, stderr) = run_gg(&repo_path, &["co"]);
+    assert!(!success);
+    assert!(stderr.contains("gg co <stack-name>"), "{stderr}");
+
+    let (success, _, stderr) = run_gg(&repo_path, &["mv", "--to", "other"]);
+    assert!(!success);
+    assert!(stderr.contains("TARGET"), "{stderr}");
+}
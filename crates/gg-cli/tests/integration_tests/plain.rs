@@ -0,0 +1,57 @@
+use crate::helpers::{create_test_repo, run_gg, run_git};
+
+use std::fs;
+
+fn setup_stack(repo_path: &std::path::Path, plain_output: bool) {
+    let gg_dir = repo_path.join(".git/gg");
+    fs::create_dir_all(&gg_dir).expect("Failed to create gg dir");
+    fs::write(
+        gg_dir.join("config.json"),
+        format!(
+            r#"{{"defaults":{{"branch_username":"testuser","plain_output":{}}}}}"#,
+            plain_output
+        ),
+    )
+    .expect("Failed to write config");
+
+    let (success, _stdout, stderr) = run_gg(repo_path, &["co", "plain-stack"]);
+    assert!(success, "Failed to create stack: {}", stderr);
+
+    for name in ["a", "b"] {
+        fs::write(repo_path.join(format!("{}.txt", name)), name).expect("Failed to write file");
+        run_git(repo_path, &["add", "."]);
+        run_git(repo_path, &["commit", "-m", &format!("Add {}", name)]);
+    }
+}
+
+#[test]
+fn test_gg_plain_flag_uses_ascii_markers() {
+    let (_temp_dir, repo_path) = create_test_repo();
+    setup_stack(&repo_path, false);
+
+    let (success, stdout, stderr) = run_gg(&repo_path, &["log"]);
+    assert!(success, "gg log failed: {}", stderr);
+    assert!(stdout.contains("├──"), "stdout: {}", stdout);
+
+    let (success, stdout, stderr) = run_gg(&repo_path, &["--plain", "log"]);
+    assert!(success, "gg --plain log failed: {}", stderr);
+    assert!(stdout.is_ascii(), "stdout: {}", stdout);
+    assert!(stdout.contains("|--"), "stdout: {}", stdout);
+    assert!(stdout.contains("`--"), "stdout: {}", stdout);
+
+    let (success, stdout, stderr) = run_gg(&repo_path, &["ls", "--all", "--plain"]);
+    assert!(success, "gg ls --all --plain failed: {}", stderr);
+    assert!(stdout.is_ascii(), "stdout: {}", stdout);
+    assert!(stdout.contains("> plain-stack"), "stdout: {}", stdout);
+}
+
+#[test]
+fn test_gg_plain_output_config() {
+    let (_temp_dir, repo_path) = create_test_repo();
+    setup_stack(&repo_path, true);
+
+    let (success, stdout, stderr) = run_gg(&repo_path, &["ls", "--all"]);
+    assert!(success, "gg ls --all failed: {}", stderr);
+    assert!(stdout.is_ascii(), "stdout: {}", stdout);
+    assert!(stdout.contains("`--"), "stdout: {}", stdout);
+}
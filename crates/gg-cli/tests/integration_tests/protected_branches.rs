@@ -0,0 +1,90 @@
+use crate::helpers::{
+    create_test_repo, create_test_repo_with_remote, run_gg, run_gg_with_env, run_git,
+};
+
+use std::fs;
+
+#[cfg(unix)]
+use std::os::unix::fs::PermissionsExt;
+
+fn setup_stack(repo_path: &std::path::Path, protected: &str) {
+    let gg_dir = repo_path.join(".git/gg");
+    fs::create_dir_all(&gg_dir).expect("Failed to create gg dir");
+    fs::write(
+        gg_dir.join("config.json"),
+        format!(
+            r#"{{"defaults":{{"branch_username":"testuser","provider":"github","base":"main","protected_branches":["{}"]}}}}"#,
+            protected
+        ),
+    )
+    .expect("Failed to write config");
+
+    let (success, _stdout, stderr) = run_gg(repo_path, &["co", "guarded"]);
+    assert!(success, "Failed to create stack: {}", stderr);
+
+    fs::write(repo_path.join("entry.txt"), "a\n").expect("Failed to write file");
+    run_git(repo_path, &["add", "entry.txt"]);
+    run_git(repo_path, &["commit", "-m", "Entry\n\nGG-ID: c-1a2b3c4"]);
+}
+
+#[test]
+fn test_protected_branch_refuses_rewrite() {
+    let (_temp_dir, repo_path) = create_test_repo();
+    setup_stack(&repo_path, "testuser/guarded");
+
+    fs::write(repo_path.join("entry.txt"), "b\n").expect("Failed to write file");
+    run_git(&repo_path, &["add", "entry.txt"]);
+
+    let (success, _stdout, stderr) = run_gg(&repo_path, &["sc"]);
+    assert!(!success, "sc should refuse to rewrite a protected branch");
+    assert!(
+        stderr.contains("protected branch 'testuser/guarded'"),
+        "stderr: {}",
+        stderr
+    );
+
+    let (_, log) = run_git(&repo_path, &["log", "-1", "--format=%B"]);
+    assert!(log.contains("GG-ID: c-1a2b3c4"));
+}
+
+#[test]
+fn test_protected_branch_refuses_push() {
+    let (_temp_dir, repo_path, _remote_path) = create_test_repo_with_remote();
+    setup_stack(&repo_path, "testuser/guarded--*");
+
+    let fake_bin = repo_path.join("fake-bin");
+    fs::create_dir_all(&fake_bin).expect("Failed to create fake bin dir");
+    fs::write(
+        fake_bin.join("gh"),
+        "#!/bin/sh\n[ \"$1\" = \"--version\" ] && echo 'gh version 2.0.0' && exit 0\n[ \"$1\" = \"auth\" ] && exit 0\nexit 1\n",
+    )
+    .expect("Failed to write fake gh");
+    #[cfg(unix)]
+    {
+        let mut perms = fs::metadata(fake_bin.join("gh"))
+            .expect("Failed to stat fake gh")
+            .permissions();
+        perms.set_mode(0o755);
+        fs::set_permissions(fake_bin.join("gh"), perms).expect("Failed to chmod fake gh");
+    }
+
+    let old_path = std::env::var_os("PATH").unwrap_or_default();
+    let mut new_path = std::ffi::OsString::from(fake_bin.as_os_str());
+    new_path.push(":");
+    new_path.push(old_path);
+
+    let (success, stdout, stderr) = run_gg_with_env(
+        &repo_path,
+        &["sync", "--no-rebase-check"],
+        &[("PATH", new_path.as_os_str())],
+    );
+    assert!(!success, "sync should refuse to push: {}", stdout);
+    assert!(
+        stderr.contains("protected branch 'testuser/guarded--c-1a2b3c4'"),
+        "stderr: {}",
+        stderr
+    );
+
+    let (_, remote) = run_git(&repo_path, &["ls-remote", "--heads", "origin"]);
+    assert!(!remote.contains("guarded"), "remote: {}", remote);
+}
@@ -0,0 +1,197 @@
+use crate::helpers::{create_test_repo, run_gg, run_gg_with_env, run_git};
+
+use std::fs;
+#[cfg(unix)]
+use std::os::unix::fs::PermissionsExt;
+
+#[test]
+fn test_gg_ready_requires_synced_entry() {
+    let (_temp_dir, repo_path) = create_test_repo();
+    let gg_dir = repo_path.join(".git/gg");
+    fs::create_dir_all(&gg_dir).expect("Failed to create gg dir");
+    fs::write(
+        gg_dir.join("config.json"),
+        r#"{"defaults":{"branch_username":"testuser","provider":"github"}}"#,
+    )
+    .expect("Failed to write config");
+
+    let (success, _stdout, stderr) = run_gg(&repo_path, &["co", "ready-stack"]);
+    assert!(success, "Failed to create stack: {}", stderr);
+    fs::write(repo_path.join("a.txt"), "a\n").expect("Failed to write file");
+    run_git(&repo_path, &["add", "a.txt"]);
+    run_git(&repo_path, &["commit", "-m", "Add a"]);
+
+    let (success, _stdout, stderr) = run_gg(&repo_path, &["ready", "1"]);
+    assert!(!success);
+    assert!(stderr.contains("has no PR"), "stderr: {stderr}");
+
+    let (success, _stdout, stderr) = run_gg(&repo_path, &["ready", "--all"]);
+    assert!(!success);
+    assert!(stderr.contains("No entry has a PR"), "stderr: {stderr}");
+}
+
+#[test]
+fn test_gg_ready_all_promotes_only_drafts() {
+    let (_temp_dir, repo_path) = create_test_repo();
+    let gg_dir = repo_path.join(".git/gg");
+    fs::create_dir_all(&gg_dir).expect("Failed to create gg dir");
+    fs::write(
+        gg_dir.join("config.json"),
+        r#"{"defaults":{"branch_username":"testuser","provider":"github"},"stacks":{"ready-all":{"mrs":{"c-aaaaaaa":101,"c-bbbbbbb":102}}}}"#,
+    )
+    .expect("Failed to write config");
+
+    let (success, _stdout, stderr) = run_gg(&repo_path, &["co", "ready-all"]);
+    assert!(success, "Failed to create stack: {}", stderr);
+    for (file, id) in [("a", "c-aaaaaaa"), ("b", "c-bbbbbbb")] {
+        fs::write(repo_path.join(format!("{file}.txt")), "x\n").expect("Failed to write");
+        run_git(&repo_path, &["add", &format!("{file}.txt")]);
+        run_git(
+            &repo_path,
+            &["commit", "-m", &format!("Add {file}\n\nGG-ID: {id}")],
+        );
+    }
+
+    // #101 is open, #102 is a draft
+    let fake_bin = repo_path.join("fake-bin");
+    fs::create_dir_all(&fake_bin).expect("Failed to create fake bin dir");
+    let log = repo_path.join("gh.log");
+    fs::write(
+        fake_bin.join("gh"),
+        format!(
+            r#"#!/bin/sh
+echo "$@" >> "{log}"
+if [ "$1" = "--version" ]; then
+  echo "gh version 2.0.0"
+  exit 0
+fi
+if [ "$1" = "pr" ] && [ "$2" = "view" ]; then
+  draft=false
+  [ "$3" = "102" ] && draft=true
+  echo '{{"number":'"$3"',"title":"x","state":"OPEN","url":"https://github.com/test/repo/pull/'"$3"'","isDraft":'"$draft"',"mergeable":"MERGEABLE","reviews":[]}}'
+  exit 0
+fi
+exit 0
+"#,
+            log = log.display()
+        ),
+    )
+    .expect("Failed to write fake gh");
+    #[cfg(unix)]
+    {
+        let mut perms = fs::metadata(fake_bin.join("gh"))
+            .expect("Failed to stat fake gh")
+            .permissions();
+        perms.set_mode(0o755);
+        fs::set_permissions(fake_bin.join("gh"), perms).expect("Failed to chmod fake gh");
+    }
+    let mut new_path = std::ffi::OsString::from(fake_bin.as_os_str());
+    new_path.push(":");
+    new_path.push(std::env::var_os("PATH").unwrap_or_default());
+
+    let (success, stdout, stderr) = run_gg_with_env(
+        &repo_path,
+        &["ready", "--all"],
+        &[("PATH", new_path.as_os_str())],
+    );
+    assert!(success, "ready failed: {}\n{}", stdout, stderr);
+    assert!(
+        stdout.contains("#2 PR #102 is ready for review"),
+        "{stdout}"
+    );
+
+    let gh_log = fs::read_to_string(&log).expect("gh log");
+    let ready_calls: Vec<&str> = gh_log
+        .lines()
+        .filter(|l| l.starts_with("pr ready"))
+        .collect();
+    assert_eq!(ready_calls, vec!["pr ready 102"], "{}", gh_log);
+}
+
+#[test]
+fn test_gg_ready_targets_the_stack_remote_repo() {
+    let (_temp_dir, repo_path) = create_test_repo();
+    run_git(
+        &repo_path,
+        &["remote", "add", "origin", "https://github.com/me/fork.git"],
+    );
+    run_git(
+        &repo_path,
+        &[
+            "remote",
+            "add",
+            "upstream",
+            "https://github.com/org/proj.git",
+        ],
+    );
+    let gg_dir = repo_path.join(".git/gg");
+    fs::create_dir_all(&gg_dir).expect("Failed to create gg dir");
+    fs::write(
+        gg_dir.join("config.json"),
+        r#"{"defaults":{"branch_username":"testuser"}}"#,
+    )
+    .expect("Failed to write config");
+
+    let (success, _stdout, stderr) = run_gg(&repo_path, &["co", "ready-up"]);
+    assert!(success, "Failed to create stack: {}", stderr);
+    fs::write(repo_path.join("a.txt"), "a\n").expect("Failed to write file");
+    run_git(&repo_path, &["add", "a.txt"]);
+    run_git(&repo_path, &["commit", "-m", "Add a\n\nGG-ID: c-aaaaaaa"]);
+    fs::write(
+        gg_dir.join("config.json"),
+        r#"{"defaults":{"branch_username":"testuser"},"stacks":{"ready-up":{"remote":"upstream","mrs":{"c-aaaaaaa":101}}}}"#,
+    )
+    .expect("Failed to write config");
+
+    let fake_bin = repo_path.join("fake-bin");
+    fs::create_dir_all(&fake_bin).expect("Failed to create fake bin dir");
+    let log = repo_path.join("gh.log");
+    fs::write(
+        fake_bin.join("gh"),
+        format!(
+            r#"#!/bin/sh
+echo "${{GH_REPO:-none}} $@" >> "{log}"
+if [ "$1" = "--version" ]; then
+  echo "gh version 2.0.0"
+  exit 0
+fi
+if [ "$1" = "pr" ] && [ "$2" = "view" ]; then
+  echo '{{"number":101,"title":"x","state":"OPEN","url":"https://github.com/org/proj/pull/101","isDraft":true,"mergeable":"MERGEABLE","reviews":[]}}'
+  exit 0
+fi
+exit 0
+"#,
+            log = log.display()
+        ),
+    )
+    .expect("Failed to write fake gh");
+    #[cfg(unix)]
+    {
+        let mut perms = fs::metadata(fake_bin.join("gh"))
+            .expect("Failed to stat fake gh")
+            .permissions();
+        perms.set_mode(0o755);
+        fs::set_permissions(fake_bin.join("gh"), perms).expect("Failed to chmod fake gh");
+    }
+    let mut new_path = std::ffi::OsString::from(fake_bin.as_os_str());
+    new_path.push(":");
+    new_path.push(std::env::var_os("PATH").unwrap_or_default());
+
+    let (success, stdout, stderr) = run_gg_with_env(
+        &repo_path,
+        &["ready", "1"],
+        &[("PATH", new_path.as_os_str())],
+    );
+    assert!(success, "ready failed: {}\n{}", stdout, stderr);
+
+    // The provider comes from upstream's URL, and gh is pointed at it
+    let gh_log = fs::read_to_string(&log).expect("gh log");
+    assert!(
+        gh_log
+            .lines()
+            .any(|l| l == "github.com/org/proj pr ready 101"),
+        "{}",
+        gh_log
+    );
+    assert!(!gh_log.contains("me/fork"), "{}", gh_log);
+}
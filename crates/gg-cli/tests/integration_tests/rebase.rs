@@ -707,3 +707,206 @@ fn test_rebase_chain_after_multiple_squash_merges() {
     );
     assert!(log.contains("commit C"), "Should be commit C: {}", log);
 }
+
+#[test]
+fn test_rebase_restacks_dependent_stacks() {
+    let (_temp_dir, repo_path, _remote_path) = create_test_repo_with_remote();
+
+    let gg_dir = repo_path.join(".git/gg");
+    fs::create_dir_all(&gg_dir).expect("Failed to create gg dir");
+    fs::write(
+        gg_dir.join("config.json"),
+        r#"{"defaults":{"branch_username":"testuser"}}"#,
+    )
+    .expect("Failed to write config");
+
+    let (success, _, stderr) = run_gg(&repo_path, &["co", "parent"]);
+    assert!(success, "Failed to create parent stack: {}", stderr);
+    fs::write(repo_path.join("parent.txt"), "parent").expect("Failed to write file");
+    run_git(&repo_path, &["add", "."]);
+    run_git(&repo_path, &["commit", "-m", "Parent commit"]);
+
+    let (success, _, stderr) = run_gg(&repo_path, &["co", "child", "--base", "parent"]);
+    assert!(success, "Failed to create child stack: {}", stderr);
+    fs::write(repo_path.join("child.txt"), "child").expect("Failed to write file");
+    run_git(&repo_path, &["add", "."]);
+    run_git(&repo_path, &["commit", "-m", "Child commit"]);
+
+    // Advance origin/main so the parent has something to rebase onto
+    run_git(&repo_path, &["checkout", "main"]);
+    fs::write(repo_path.join("merged.txt"), "merged").expect("Failed to write file");
+    run_git(&repo_path, &["add", "."]);
+    run_git(&repo_path, &["commit", "-m", "Merged PR"]);
+    run_git(&repo_path, &["push", "origin", "main"]);
+
+    run_git(&repo_path, &["checkout", "testuser/parent"]);
+    let (success, stdout, stderr) = run_gg(&repo_path, &["rebase"]);
+    assert!(success, "rebase failed: {} {}", stdout, stderr);
+    assert!(stdout.contains("Restacked"), "{}", stdout);
+
+    let (_, branch) = run_git(&repo_path, &["rev-parse", "--abbrev-ref", "HEAD"]);
+    assert_eq!(branch.trim(), "testuser/parent");
+
+    let (is_ancestor, _) = run_git(
+        &repo_path,
+        &[
+            "merge-base",
+            "--is-ancestor",
+            "testuser/parent",
+            "testuser/child",
+        ],
+    );
+    assert!(is_ancestor, "child should sit on top of the rebased parent");
+    let (_, own) = run_git(
+        &repo_path,
+        &["rev-list", "--count", "testuser/parent..testuser/child"],
+    );
+    assert_eq!(own.trim(), "1");
+}
+
+#[test]
+fn test_rebase_stack_on_custom_remote_and_base() {
+    let (temp_dir, repo_path, _remote_path) = create_test_repo_with_remote();
+    let upstream_path = temp_dir.path().join("upstream.git");
+    run_git(
+        temp_dir.path(),
+        &["init", "--bare", upstream_path.to_str().unwrap()],
+    );
+    run_git(
+        &repo_path,
+        &["remote", "add", "upstream", upstream_path.to_str().unwrap()],
+    );
+    run_git(
+        &repo_path,
+        &["push", "upstream", "main:refs/heads/release/1.2"],
+    );
+
+    let gg_dir = repo_path.join(".git/gg");
+    fs::create_dir_all(&gg_dir).expect("Failed to create gg dir");
+    fs::write(
+        gg_dir.join("config.json"),
+        r#"{"defaults":{"branch_username":"testuser"}}"#,
+    )
+    .expect("Failed to write config");
+
+    let (success, _stdout, stderr) = run_gg(
+        &repo_path,
+        &["co", "fix", "--base", "release/1.2", "--remote", "nowhere"],
+    );
+    assert!(!success);
+    assert!(
+        stderr.contains("Remote 'nowhere' does not exist"),
+        "{}",
+        stderr
+    );
+
+    let (success, stdout, stderr) = run_gg(
+        &repo_path,
+        &["co", "fix", "--base", "release/1.2", "--remote", "upstream"],
+    );
+    assert!(success, "Failed to create stack: {}", stderr);
+    assert!(stdout.contains("upstream/release/1.2"), "{}", stdout);
+    let config: serde_json::Value =
+        serde_json::from_str(&fs::read_to_string(gg_dir.join("config.json")).unwrap()).unwrap();
+    assert_eq!(config["stacks"]["fix"]["base"], "release/1.2");
+    assert_eq!(config["stacks"]["fix"]["remote"], "upstream");
+
+    fs::write(repo_path.join("fix.txt"), "fix").expect("Failed to write file");
+    run_git(&repo_path, &["add", "."]);
+    run_git(&repo_path, &["commit", "-m", "Fix the release"]);
+
+    // The release branch moves on upstream only
+    run_git(&repo_path, &["checkout", "-b", "hotfix", "main"]);
+    fs::write(repo_path.join("hotfix.txt"), "hotfix").expect("Failed to write file");
+    run_git(&repo_path, &["add", "."]);
+    run_git(&repo_path, &["commit", "-m", "Hotfix"]);
+    run_git(
+        &repo_path,
+        &["push", "upstream", "hotfix:refs/heads/release/1.2"],
+    );
+    run_git(&repo_path, &["checkout", "testuser/fix"]);
+    run_git(&repo_path, &["branch", "-D", "hotfix"]);
+
+    let (success, stdout, stderr) = run_gg(&repo_path, &["rebase"]);
+    assert!(success, "gg rebase failed: {}{}", stdout, stderr);
+    let (on_upstream, _) = run_git(
+        &repo_path,
+        &[
+            "merge-base",
+            "--is-ancestor",
+            "upstream/release/1.2",
+            "HEAD",
+        ],
+    );
+    assert!(on_upstream, "stack should sit on upstream/release/1.2");
+
+    let (success, stdout, _stderr) = run_gg(&repo_path, &["ls", "--json"]);
+    assert!(success);
+    let parsed: serde_json::Value = serde_json::from_str(&stdout).unwrap();
+    assert_eq!(parsed["stack"]["base"], "release/1.2");
+    assert_eq!(parsed["stack"]["total_commits"], 1);
+}
+
+#[test]
+fn test_rewrites_use_the_stack_remote_base() {
+    let (temp_dir, repo_path, _remote_path) = create_test_repo_with_remote();
+    let upstream_path = temp_dir.path().join("upstream.git");
+    run_git(
+        temp_dir.path(),
+        &["init", "--bare", upstream_path.to_str().unwrap()],
+    );
+    run_git(
+        &repo_path,
+        &["remote", "add", "upstream", upstream_path.to_str().unwrap()],
+    );
+    run_git(
+        &repo_path,
+        &["push", "upstream", "main:refs/heads/release/1.2"],
+    );
+
+    let gg_dir = repo_path.join(".git/gg");
+    fs::create_dir_all(&gg_dir).expect("Failed to create gg dir");
+    fs::write(
+        gg_dir.join("config.json"),
+        r#"{"defaults":{"branch_username":"testuser"}}"#,
+    )
+    .expect("Failed to write config");
+
+    // Only upstream has release/1.2: no local branch, nothing on origin
+    let (success, _stdout, stderr) = run_gg(
+        &repo_path,
+        &["co", "fix", "--base", "release/1.2", "--remote", "upstream"],
+    );
+    assert!(success, "Failed to create stack: {}", stderr);
+    for name in ["one", "two", "three"] {
+        fs::write(repo_path.join(format!("{}.txt", name)), name).expect("Failed to write file");
+        run_git(&repo_path, &["add", "."]);
+        run_git(&repo_path, &["commit", "-m", &format!("Add {}", name)]);
+    }
+
+    let (success, stdout, stderr) = run_gg(&repo_path, &["reorder", "--order", "3,1,2"]);
+    assert!(success, "gg reorder failed: {}{}", stdout, stderr);
+    let (_, subjects) = run_git(
+        &repo_path,
+        &["log", "--format=%s", "upstream/release/1.2..HEAD"],
+    );
+    assert_eq!(
+        subjects.lines().collect::<Vec<_>>(),
+        vec!["Add two", "Add one", "Add three"]
+    );
+
+    let (success, stdout, stderr) = run_gg(&repo_path, &["drop", "1", "--force"]);
+    assert!(success, "gg drop failed: {}{}", stdout, stderr);
+
+    let (success, stdout, stderr) = run_gg(&repo_path, &["restack"]);
+    assert!(success, "gg restack failed: {}{}", stdout, stderr);
+
+    let (_, subjects) = run_git(
+        &repo_path,
+        &["log", "--format=%s", "upstream/release/1.2..HEAD"],
+    );
+    assert_eq!(
+        subjects.lines().collect::<Vec<_>>(),
+        vec!["Add two", "Add one"]
+    );
+}
@@ -1,6 +1,7 @@
-use crate::helpers::{create_test_repo, run_gg, run_git};
+use crate::helpers::{create_test_repo, run_gg, run_gg_with_env, run_git};
 
 use std::fs;
+use std::os::unix::fs::PermissionsExt;
 use std::process::Command;
 
 #[test]
@@ -361,3 +362,119 @@ fn test_arrange_is_alias_for_reorder() {
 // ============================================================
 // gg drop tests
 // ============================================================
+
+#[test]
+fn test_gg_reorder_interactive_editor_applies_and_validates() {
+    let (temp_dir, repo_path) = create_test_repo();
+    let gg_dir = repo_path.join(".git/gg");
+    fs::create_dir_all(&gg_dir).expect("Failed to create gg dir");
+    fs::write(
+        gg_dir.join("config.json"),
+        r#"{"defaults":{"branch_username":"testuser"}}"#,
+    )
+    .expect("Failed to write config");
+
+    let (success, _, stderr) = run_gg(&repo_path, &["co", "test-reorder-i"]);
+    assert!(success, "Failed to checkout: {}", stderr);
+    for name in ["A", "B", "C"] {
+        fs::write(repo_path.join(format!("{name}.txt")), name).expect("Failed to write file");
+        run_git(&repo_path, &["add", "."]);
+        run_git(&repo_path, &["commit", "-m", &format!("Add {name}")]);
+    }
+
+    // An editor that repeats the first entry is rejected before rewriting
+    let duplicate = temp_dir.path().join("duplicate.sh");
+    fs::write(&duplicate, "#!/bin/sh\nline=$(grep -v '^#' \"$1\" | head -1)\nprintf '%s\\n%s\\n' \"$line\" \"$line\" > \"$1\"\n")
+        .expect("Failed to write editor");
+    fs::set_permissions(&duplicate, fs::Permissions::from_mode(0o755)).expect("chmod");
+    let (_, before) = run_git(&repo_path, &["rev-parse", "HEAD"]);
+    let (success, _, stderr) = run_gg_with_env(
+        &repo_path,
+        &["reorder", "-i", "--no-tui"],
+        &[
+            ("EDITOR", duplicate.as_os_str()),
+            ("VISUAL", duplicate.as_os_str()),
+        ],
+    );
+    assert!(!success);
+    assert!(stderr.contains("Duplicate"), "stderr: {}", stderr);
+    let (_, after) = run_git(&repo_path, &["rev-parse", "HEAD"]);
+    assert_eq!(before, after);
+
+    // An editor that reverses the entries
+    let reverse = temp_dir.path().join("reverse.sh");
+    fs::write(
+        &reverse,
+        "#!/bin/sh\ngrep -v '^#' \"$1\" | sed '1!G;h;$!d' > \"$1.new\"\nmv \"$1.new\" \"$1\"\n",
+    )
+    .expect("Failed to write editor");
+    fs::set_permissions(&reverse, fs::Permissions::from_mode(0o755)).expect("chmod");
+    let (success, stdout, stderr) = run_gg_with_env(
+        &repo_path,
+        &["reorder", "-i", "--no-tui"],
+        &[
+            ("EDITOR", reverse.as_os_str()),
+            ("VISUAL", reverse.as_os_str()),
+        ],
+    );
+    assert!(success, "reorder -i failed: {} {}", stdout, stderr);
+    let (_, log) = run_git(&repo_path, &["log", "--format=%s", "-3"]);
+    assert_eq!(log.trim(), "Add A\nAdd B\nAdd C");
+
+    let (success, _, stderr) = run_gg(&repo_path, &["reorder", "-i", "--order", "1,2,3"]);
+    assert!(!success);
+    assert!(stderr.contains("--order"), "stderr: {}", stderr);
+}
+
+#[test]
+fn test_gg_reorder_refuses_order_that_breaks_line_dependency() {
+    let (_temp_dir, repo_path) = create_test_repo();
+    let gg_dir = repo_path.join(".git/gg");
+    fs::create_dir_all(&gg_dir).expect("Failed to create gg dir");
+    fs::write(
+        gg_dir.join("config.json"),
+        r#"{"defaults":{"branch_username":"testuser"}}"#,
+    )
+    .expect("Failed to write config");
+
+    let (success, _, stderr) = run_gg(&repo_path, &["co", "test-reorder-deps"]);
+    assert!(success, "Failed to checkout: {}", stderr);
+    for (file, content, message) in [
+        ("a.txt", "one\n", "Add a"),
+        ("b.txt", "b\n", "Add b"),
+        ("a.txt", "ONE\n", "Change a"),
+    ] {
+        fs::write(repo_path.join(file), content).expect("Failed to write file");
+        run_git(&repo_path, &["add", "."]);
+        run_git(&repo_path, &["commit", "-m", message]);
+    }
+    let (_, before) = run_git(&repo_path, &["rev-parse", "HEAD"]);
+
+    let (success, stdout, stderr) = run_gg(&repo_path, &["reorder", "--order", "3,1,2", "-n"]);
+    assert!(success, "dry run failed: {}", stderr);
+    assert!(
+        stdout.contains("conflict #3 changes lines from #1 (a.txt)"),
+        "stdout: {}",
+        stdout
+    );
+
+    let (success, stdout, stderr) = run_gg(&repo_path, &["reorder", "--order", "3,1,2", "--json"]);
+    assert!(success, "json failed: {}", stderr);
+    let parsed: serde_json::Value = serde_json::from_str(&stdout).expect("stdout must be JSON");
+    assert_eq!(parsed["reorder"]["order"], serde_json::json!([3, 1, 2]));
+    assert_eq!(
+        parsed["reorder"]["dependencies"],
+        serde_json::json!([{"position": 3, "depends_on": 1, "kind": "lines", "paths": ["a.txt"]}])
+    );
+    assert_eq!(parsed["reorder"]["conflicts"].as_array().unwrap().len(), 1);
+
+    let (success, _, stderr) = run_gg(&repo_path, &["reorder", "--order", "3,1,2"]);
+    assert!(!success);
+    assert!(stderr.contains("will conflict"), "stderr: {}", stderr);
+    let (_, after) = run_git(&repo_path, &["rev-parse", "HEAD"]);
+    assert_eq!(before, after);
+
+    // Moving the independent entry is fine
+    let (success, _, stderr) = run_gg(&repo_path, &["reorder", "--order", "2,1,3"]);
+    assert!(success, "reorder failed: {}", stderr);
+}
@@ -0,0 +1,87 @@
+use std::ffi::OsStr;
+use std::fs;
+
+use crate::helpers::{
+    create_test_repo, path_without_provider_clis, run_gg, run_gg_with_env, run_git, serve,
+};
+
+#[test]
+fn test_gg_retarget_prs_help() {
+    let (_temp_dir, repo_path) = create_test_repo();
+    let (success, stdout, _stderr) = run_gg(&repo_path, &["retarget-prs", "--help"]);
+
+    assert!(success);
+    assert!(stdout.contains("--dry-run"));
+    assert!(stdout.contains("--json"));
+}
+
+#[test]
+fn test_gg_retarget_prs_requires_stack() {
+    let (_temp_dir, repo_path) = create_test_repo();
+    let (success, _stdout, stderr) = run_gg(&repo_path, &["retarget-prs", "--dry-run"]);
+
+    assert!(!success);
+    assert!(
+        stderr.contains("stack"),
+        "expected a not-on-stack error, got: {stderr}"
+    );
+}
+
+#[test]
+fn test_gg_retarget_prs_json_fails_when_a_retarget_fails() {
+    let (url, _seen) = serve(|method, path, _body| {
+        match (method, path) {
+        ("GET", "/api/v3/user") => (200, r#"{"login":"testuser"}"#.to_string()),
+        ("GET", "/api/v3/repos/o/r/pulls/7") => (
+            200,
+            r#"{"number":7,"title":"Add feature","state":"open","mergeable":true,"html_url":"https://github.example.com/o/r/pull/7","head":{"ref":"testuser/feat--c-abc1234"},"base":{"ref":"old-base"},"updated_at":null}"#
+                .to_string(),
+        ),
+        ("GET", p) if p.starts_with("/api/v3/repos/o/r/pulls/7/reviews") => (200, "[]".to_string()),
+        ("PATCH", "/api/v3/repos/o/r/pulls/7") => {
+            (422, r#"{"message":"Validation Failed"}"#.to_string())
+        }
+        _ => (404, r#"{"message":"Not Found"}"#.to_string()),
+    }
+    });
+
+    let (_temp_dir, repo_path) = create_test_repo();
+    run_git(
+        &repo_path,
+        &[
+            "remote",
+            "add",
+            "origin",
+            "https://github.example.com/o/r.git",
+        ],
+    );
+    let gg_dir = repo_path.join(".git/gg");
+    fs::create_dir_all(&gg_dir).unwrap();
+    fs::write(
+        gg_dir.join("config.json"),
+        r#"{"defaults":{"branch_username":"testuser","base":"main","provider":"github"},"stacks":{"feat":{"mrs":{"c-abc1234":7}}}}"#,
+    )
+    .unwrap();
+    run_git(&repo_path, &["checkout", "-b", "testuser/feat"]);
+    fs::write(repo_path.join("feature.txt"), "feature\n").unwrap();
+    run_git(&repo_path, &["add", "."]);
+    run_git(
+        &repo_path,
+        &["commit", "-m", "Add feature\n\nGG-ID: c-abc1234"],
+    );
+
+    let bin = path_without_provider_clis(&repo_path);
+    let api_url = format!("{}/api/v3", url);
+    let envs: [(&str, &OsStr); 4] = [
+        ("PATH", bin.as_os_str()),
+        ("GG_API_MODE", OsStr::new("native")),
+        ("GG_API_URL", OsStr::new(&api_url)),
+        ("GH_TOKEN", OsStr::new("test-token")),
+    ];
+    let (success, stdout, stderr) = run_gg_with_env(&repo_path, &["retarget-prs", "--json"], &envs);
+
+    assert!(!success, "a failed retarget must exit non-zero");
+    let parsed: serde_json::Value = serde_json::from_str(&stdout)
+        .unwrap_or_else(|e| panic!("stdout must be JSON ({e}): {stdout} {stderr}"));
+    assert_eq!(parsed["entries"][0]["action"], "error", "{stdout}");
+}
@@ -0,0 +1,97 @@
+use crate::helpers::{create_test_repo, run_gg, run_gg_with_env, run_git};
+
+use std::fs;
+#[cfg(unix)]
+use std::os::unix::fs::PermissionsExt;
+
+#[test]
+fn test_gg_review_requires_synced_entry() {
+    let (_temp_dir, repo_path) = create_test_repo();
+    let gg_dir = repo_path.join(".git/gg");
+    fs::create_dir_all(&gg_dir).expect("Failed to create gg dir");
+    fs::write(
+        gg_dir.join("config.json"),
+        r#"{"defaults":{"branch_username":"testuser","provider":"github"}}"#,
+    )
+    .expect("Failed to write config");
+
+    let (success, _stdout, stderr) = run_gg(&repo_path, &["co", "review-stack"]);
+    assert!(success, "Failed to create stack: {}", stderr);
+    fs::write(repo_path.join("a.txt"), "a\n").expect("Failed to write file");
+    run_git(&repo_path, &["add", "a.txt"]);
+    run_git(&repo_path, &["commit", "-m", "Add a"]);
+
+    let fake_bin = repo_path.join("fake-bin");
+    fs::create_dir_all(&fake_bin).expect("Failed to create fake bin dir");
+    fs::write(fake_bin.join("gh"), "#!/bin/sh\nexit 0\n").expect("Failed to write fake gh");
+    #[cfg(unix)]
+    fs::set_permissions(fake_bin.join("gh"), fs::Permissions::from_mode(0o755))
+        .expect("Failed to chmod fake gh");
+    let mut new_path = std::ffi::OsString::from(fake_bin.as_os_str());
+    new_path.push(":");
+    new_path.push(std::env::var_os("PATH").unwrap_or_default());
+
+    let (success, _stdout, stderr) =
+        run_gg_with_env(&repo_path, &["review"], &[("PATH", new_path.as_os_str())]);
+    assert!(!success);
+    assert!(stderr.contains("has no PR"), "stderr: {stderr}");
+}
+
+#[test]
+fn test_gg_review_json_groups_unresolved_threads_by_file() {
+    let (_temp_dir, repo_path) = create_test_repo();
+    let gg_dir = repo_path.join(".git/gg");
+    fs::create_dir_all(&gg_dir).expect("Failed to create gg dir");
+    fs::write(
+        gg_dir.join("config.json"),
+        r#"{"defaults":{"branch_username":"testuser","provider":"github"},"stacks":{"review-json":{"mrs":{"c-aaaaaaa":101}}}}"#,
+    )
+    .expect("Failed to write config");
+
+    let (success, _stdout, stderr) = run_gg(&repo_path, &["co", "review-json"]);
+    assert!(success, "Failed to create stack: {}", stderr);
+    fs::write(repo_path.join("a.txt"), "a\n").expect("Failed to write file");
+    run_git(&repo_path, &["add", "a.txt"]);
+    run_git(&repo_path, &["commit", "-m", "Add a\n\nGG-ID: c-aaaaaaa"]);
+
+    let threads = r#"{"data":{"repository":{"pullRequest":{"reviewThreads":{"nodes":[
+{"id":"T3","isResolved":false,"isOutdated":false,"path":"src/b.rs","line":40,"originalLine":40,"comments":{"nodes":[{"author":{"login":"alice"},"body":"later","createdAt":"2026-01-01T00:00:00Z"}]}},
+{"id":"T1","isResolved":true,"isOutdated":false,"path":"src/a.rs","line":1,"originalLine":1,"comments":{"nodes":[]}},
+{"id":"T2","isResolved":false,"isOutdated":false,"path":"src/b.rs","line":3,"originalLine":3,"comments":{"nodes":[{"author":{"login":"bob"},"body":"first","createdAt":"2026-01-01T00:00:00Z"}]}}
+]}}}}}"#;
+    let fake_bin = repo_path.join("fake-bin");
+    fs::create_dir_all(&fake_bin).expect("Failed to create fake bin dir");
+    fs::write(
+        fake_bin.join("gh"),
+        format!(
+            "#!/bin/sh\nif [ \"$1\" = \"api\" ] && [ \"$2\" = \"graphql\" ]; then\ncat <<'JSON'\n{threads}\nJSON\nfi\nexit 0\n"
+        ),
+    )
+    .expect("Failed to write fake gh");
+    #[cfg(unix)]
+    fs::set_permissions(fake_bin.join("gh"), fs::Permissions::from_mode(0o755))
+        .expect("Failed to chmod fake gh");
+    let mut new_path = std::ffi::OsString::from(fake_bin.as_os_str());
+    new_path.push(":");
+    new_path.push(std::env::var_os("PATH").unwrap_or_default());
+
+    let (success, stdout, stderr) = run_gg_with_env(
+        &repo_path,
+        &["review", "1", "--json"],
+        &[("PATH", new_path.as_os_str())],
+    );
+    assert!(success, "review failed: {}\n{}", stdout, stderr);
+
+    let parsed: serde_json::Value = serde_json::from_str(&stdout).expect("valid JSON");
+    assert_eq!(parsed["pr_number"], 101);
+    let files = parsed["files"].as_array().expect("files array");
+    assert_eq!(files.len(), 1, "resolved threads are dropped: {stdout}");
+    assert_eq!(files[0]["path"], "src/b.rs");
+    let ids: Vec<&str> = files[0]["threads"]
+        .as_array()
+        .unwrap()
+        .iter()
+        .map(|t| t["id"].as_str().unwrap())
+        .collect();
+    assert_eq!(ids, vec!["T2", "T3"]);
+}
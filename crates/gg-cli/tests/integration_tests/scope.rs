@@ -0,0 +1,84 @@
+use crate::helpers::{create_test_repo, run_gg, run_git};
+
+use serde_json::Value;
+use std::fs;
+
+fn setup_scoped_stack(repo_path: &std::path::Path, lint: &str) {
+    let gg_dir = repo_path.join(".git/gg");
+    fs::create_dir_all(&gg_dir).expect("Failed to create gg dir");
+    fs::write(
+        gg_dir.join("config.json"),
+        format!(
+            r#"{{"defaults":{{"branch_username":"testuser","lint":[{}]}}}}"#,
+            serde_json::to_string(lint).unwrap()
+        ),
+    )
+    .expect("Failed to write config");
+
+    let (success, _stdout, stderr) = run_gg(repo_path, &["co", "payments"]);
+    assert!(success, "Failed to create stack: {}", stderr);
+
+    fs::create_dir_all(repo_path.join("svc/payments")).expect("Failed to create dir");
+    fs::write(repo_path.join("svc/payments/a.txt"), "one\n").expect("Failed to write file");
+    run_git(repo_path, &["add", "."]);
+    run_git(repo_path, &["commit", "-m", "Add payments"]);
+
+    let (success, _stdout, stderr) = run_gg(repo_path, &["scope", "svc/payments/**"]);
+    assert!(success, "gg scope failed: {}", stderr);
+}
+
+#[test]
+fn test_gg_scope_is_shown_by_ls() {
+    let (_temp_dir, repo_path) = create_test_repo();
+    setup_scoped_stack(&repo_path, "true");
+
+    let (success, stdout, _stderr) = run_gg(&repo_path, &["scope"]);
+    assert!(success);
+    assert!(stdout.contains("svc/payments/**"), "stdout: {}", stdout);
+
+    let (success, stdout, stderr) = run_gg(&repo_path, &["ls", "--json"]);
+    assert!(success, "gg ls failed: {}", stderr);
+    let parsed: Value = serde_json::from_str(&stdout).expect("valid JSON");
+    assert_eq!(parsed["stack"]["scope"][0], "svc/payments/**");
+
+    let (success, _stdout, stderr) = run_gg(&repo_path, &["scope", "--clear"]);
+    assert!(success, "gg scope --clear failed: {}", stderr);
+    let (_, stdout, _) = run_gg(&repo_path, &["ls", "--json"]);
+    let parsed: Value = serde_json::from_str(&stdout).expect("valid JSON");
+    assert!(parsed["stack"].get("scope").is_none());
+}
+
+#[test]
+fn test_gg_absorb_refuses_changes_outside_scope() {
+    let (_temp_dir, repo_path) = create_test_repo();
+    setup_scoped_stack(&repo_path, "true");
+
+    fs::write(repo_path.join("README.md"), "changed\n").expect("Failed to write file");
+    run_git(&repo_path, &["add", "README.md"]);
+
+    let (success, _stdout, stderr) = run_gg(&repo_path, &["absorb"]);
+    assert!(!success);
+    assert!(
+        stderr.contains("outside the stack scope") && stderr.contains("README.md"),
+        "stderr: {}",
+        stderr
+    );
+}
+
+#[test]
+fn test_gg_lint_keeps_fixes_inside_scope() {
+    let (_temp_dir, repo_path) = create_test_repo();
+    setup_scoped_stack(
+        &repo_path,
+        "echo fixed >> svc/payments/a.txt; echo junk > outside.txt",
+    );
+
+    let (success, stdout, stderr) = run_gg(&repo_path, &["lint"]);
+    assert!(success, "gg lint failed: {}\n{}", stdout, stderr);
+    assert!(stdout.contains("outside.txt"), "stdout: {}", stdout);
+
+    let (_, files) = run_git(&repo_path, &["show", "--name-only", "--format=", "HEAD"]);
+    assert!(files.contains("svc/payments/a.txt"), "files: {}", files);
+    assert!(!files.contains("outside.txt"), "files: {}", files);
+    assert!(!repo_path.join("outside.txt").exists());
+}
@@ -0,0 +1,116 @@
+use crate::helpers::{create_test_repo, run_gg, run_git};
+
+use serde_json::Value;
+use std::fs;
+use std::process::Command;
+
+/// Configure SSH commit signing with a fresh key. Returns false when
+/// ssh-keygen is not available.
+fn setup_ssh_signing(repo_path: &std::path::Path) -> bool {
+    let key = repo_path.join(".git/test-signing-key");
+    let generated = Command::new("ssh-keygen")
+        .args(["-q", "-t", "ed25519", "-N", "", "-f"])
+        .arg(&key)
+        .status()
+        .is_ok_and(|s| s.success());
+    if !generated {
+        return false;
+    }
+    run_git(repo_path, &["config", "gpg.format", "ssh"]);
+    run_git(
+        repo_path,
+        &["config", "user.signingkey", key.to_str().unwrap()],
+    );
+    run_git(repo_path, &["config", "commit.gpgsign", "true"]);
+    true
+}
+
+fn signed_entries(repo_path: &std::path::Path) -> Vec<bool> {
+    let (success, stdout, stderr) = run_gg(repo_path, &["ls", "--json"]);
+    assert!(success, "ls failed: {stderr}");
+    let parsed: Value = serde_json::from_str(&stdout).expect("stdout must be valid JSON");
+    parsed["stack"]["entries"]
+        .as_array()
+        .expect("entries array")
+        .iter()
+        .map(|e| e["signed"].as_bool().unwrap())
+        .collect()
+}
+
+#[test]
+fn test_reorder_re_signs_rewritten_commits() {
+    let (_temp_dir, repo_path) = create_test_repo();
+    if !setup_ssh_signing(&repo_path) {
+        return;
+    }
+    let gg_dir = repo_path.join(".git/gg");
+    fs::create_dir_all(&gg_dir).unwrap();
+    fs::write(
+        gg_dir.join("config.json"),
+        r#"{"defaults":{"branch_username":"testuser"}}"#,
+    )
+    .unwrap();
+
+    let (success, _, stderr) = run_gg(&repo_path, &["co", "signed"]);
+    assert!(success, "co failed: {stderr}");
+    for name in ["a", "b"] {
+        fs::write(repo_path.join(format!("{name}.txt")), name).unwrap();
+        run_git(&repo_path, &["add", "."]);
+        run_git(&repo_path, &["commit", "-m", &format!("Add {name}")]);
+    }
+    assert_eq!(signed_entries(&repo_path), vec![true, true]);
+
+    let (success, _, stderr) = run_gg(&repo_path, &["reorder", "--order", "2,1"]);
+    assert!(success, "reorder failed: {stderr}");
+    assert_eq!(signed_entries(&repo_path), vec![true, true]);
+    let (success, commit) = run_git(&repo_path, &["cat-file", "commit", "HEAD"]);
+    assert!(success);
+    assert!(commit.contains("-----BEGIN SSH SIGNATURE-----"), "{commit}");
+
+    let (success, stdout, _) = run_gg(&repo_path, &["--plain", "ls"]);
+    assert!(success);
+    assert!(stdout.contains("[signed]"), "{stdout}");
+
+    // defaults.sign_commits: false wins over commit.gpgsign
+    fs::write(
+        gg_dir.join("config.json"),
+        r#"{"defaults":{"branch_username":"testuser","sign_commits":false}}"#,
+    )
+    .unwrap();
+    let (success, _, stderr) = run_gg(&repo_path, &["reorder", "--order", "2,1"]);
+    assert!(success, "reorder failed: {stderr}");
+    assert_eq!(signed_entries(&repo_path), vec![false, false]);
+}
+
+#[test]
+fn test_squash_amend_follows_sign_commits() {
+    let (_temp_dir, repo_path) = create_test_repo();
+    if !setup_ssh_signing(&repo_path) {
+        return;
+    }
+    let gg_dir = repo_path.join(".git/gg");
+    fs::create_dir_all(&gg_dir).unwrap();
+    fs::write(
+        gg_dir.join("config.json"),
+        r#"{"defaults":{"branch_username":"testuser","sign_commits":false}}"#,
+    )
+    .unwrap();
+
+    let (success, _, stderr) = run_gg(&repo_path, &["co", "signed"]);
+    assert!(success, "co failed: {stderr}");
+    fs::write(repo_path.join("a.txt"), "a").unwrap();
+    run_git(&repo_path, &["add", "."]);
+    run_git(&repo_path, &["commit", "-m", "Add a"]);
+    assert_eq!(signed_entries(&repo_path), vec![true]);
+
+    // `git commit --amend` runs with -c commit.gpgsign=false
+    fs::write(repo_path.join("a.txt"), "aa").unwrap();
+    run_git(&repo_path, &["add", "."]);
+    let (success, _, stderr) = run_gg(&repo_path, &["sc"]);
+    assert!(success, "sc failed: {stderr}");
+    assert_eq!(signed_entries(&repo_path), vec![false]);
+
+    // ...without leaking into git's own config
+    let (_, gpgsign) = run_git(&repo_path, &["config", "commit.gpgsign"]);
+    assert_eq!(gpgsign.trim(), "true");
+}
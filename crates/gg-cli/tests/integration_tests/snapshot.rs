@@ -0,0 +1,77 @@
+use crate::helpers::{create_test_repo_with_remote, run_gg, run_git};
+
+use serde_json::Value;
+use std::fs;
+
+fn rev_parse(repo_path: &std::path::Path, rev: &str) -> String {
+    let (success, stdout) = run_git(repo_path, &["rev-parse", rev]);
+    assert!(success, "git rev-parse {rev} failed");
+    stdout.trim().to_string()
+}
+
+#[test]
+fn test_rebase_snapshot_can_be_restored() {
+    let (_temp_dir, repo_path, _remote_path) = create_test_repo_with_remote();
+    let gg_dir = repo_path.join(".git/gg");
+    fs::create_dir_all(&gg_dir).unwrap();
+    fs::write(
+        gg_dir.join("config.json"),
+        r#"{"defaults":{"branch_username":"testuser"}}"#,
+    )
+    .unwrap();
+
+    let (success, _, stderr) = run_gg(&repo_path, &["co", "snap"]);
+    assert!(success, "co failed: {stderr}");
+    fs::write(repo_path.join("feature.txt"), "feature\n").unwrap();
+    run_git(&repo_path, &["add", "."]);
+    run_git(&repo_path, &["commit", "-m", "Add feature"]);
+    let before_rebase = rev_parse(&repo_path, "HEAD");
+
+    run_git(&repo_path, &["checkout", "main"]);
+    fs::write(repo_path.join("main.txt"), "main\n").unwrap();
+    run_git(&repo_path, &["add", "."]);
+    run_git(&repo_path, &["commit", "-m", "Main update"]);
+    run_git(&repo_path, &["push", "origin", "main"]);
+    run_git(&repo_path, &["checkout", "testuser/snap"]);
+
+    let (success, stdout, stderr) = run_gg(&repo_path, &["rebase"]);
+    assert!(success, "rebase failed: stdout={stdout} stderr={stderr}");
+    let after_rebase = rev_parse(&repo_path, "HEAD");
+    assert_ne!(after_rebase, before_rebase);
+
+    let (success, stdout, stderr) = run_gg(&repo_path, &["snapshot", "list", "--json"]);
+    assert!(success, "snapshot list failed: {stderr}");
+    let parsed: Value = serde_json::from_str(&stdout).expect("stdout must be valid JSON");
+    let snapshots = parsed["snapshots"].as_array().unwrap();
+    assert_eq!(snapshots.len(), 1, "{parsed:?}");
+    assert_eq!(snapshots[0]["stack"], "snap");
+    assert_eq!(snapshots[0]["tip"], before_rebase.as_str());
+    assert!(snapshots[0]["ref"]
+        .as_str()
+        .unwrap()
+        .starts_with("refs/gg/backup/snap/"));
+
+    let (success, stdout, stderr) = run_gg(&repo_path, &["snapshot", "restore", "--json"]);
+    assert!(success, "restore failed: stdout={stdout} stderr={stderr}");
+    let parsed: Value = serde_json::from_str(&stdout).expect("stdout must be valid JSON");
+    assert_eq!(parsed["branch"], "testuser/snap");
+    assert_eq!(parsed["previous_tip"], after_rebase.as_str());
+    assert_eq!(rev_parse(&repo_path, "HEAD"), before_rebase);
+    assert_eq!(rev_parse(&repo_path, "testuser/snap"), before_rebase);
+
+    // The restore itself is undoable
+    let (success, _, stderr) = run_gg(&repo_path, &["undo"]);
+    assert!(success, "undo failed: {stderr}");
+    assert_eq!(rev_parse(&repo_path, "testuser/snap"), after_rebase);
+}
+
+#[test]
+fn test_snapshot_restore_without_snapshots_fails() {
+    let (_temp_dir, repo_path, _remote_path) = create_test_repo_with_remote();
+    let (success, stdout, stderr) = run_gg(&repo_path, &["snapshot", "restore", "nothing"]);
+    assert!(!success);
+    assert!(
+        format!("{stdout}{stderr}").contains("No snapshot 'nothing'"),
+        "stdout={stdout} stderr={stderr}"
+    );
+}
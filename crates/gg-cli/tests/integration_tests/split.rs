@@ -1986,6 +1986,106 @@ fn test_split_file_args_auto_selects_hunks() {
     );
 }
 
+#[test]
+fn test_split_by_file_creates_commit_per_file() {
+    let (_temp_dir, repo_path) = create_test_repo();
+
+    let gg_dir = repo_path.join(".git/gg");
+    fs::create_dir_all(&gg_dir).expect("Failed to create gg dir");
+    fs::write(
+        gg_dir.join("config.json"),
+        r#"{"defaults":{"branch_username":"testuser"}}"#,
+    )
+    .expect("Failed to write config");
+
+    let (success, _, stderr) = run_gg(&repo_path, &["co", "test-by-file"]);
+    assert!(success, "Failed to create stack: {}", stderr);
+
+    fs::write(repo_path.join("alpha.txt"), "alpha\n").expect("write");
+    fs::write(repo_path.join("beta.txt"), "beta\n").expect("write");
+    fs::write(repo_path.join("gamma.txt"), "gamma\n").expect("write");
+    run_git(&repo_path, &["add", "."]);
+    run_git(
+        &repo_path,
+        &["commit", "-m", "Add three files\n\nGG-ID: c-1111111"],
+    );
+    fs::write(repo_path.join("delta.txt"), "delta\n").expect("write");
+    run_git(&repo_path, &["add", "delta.txt"]);
+    run_git(&repo_path, &["commit", "-m", "Add delta"]);
+
+    let (success, stdout, stderr) = run_gg(&repo_path, &["split", "-c", "1", "--by-file"]);
+    assert!(success, "split --by-file failed: {stdout} {stderr}");
+    assert!(stdout.contains("into 3 commits"), "{stdout}");
+
+    let (_, log, _) = run_git_full(
+        &repo_path,
+        &["log", "--format=%s", "-4", "testuser/test-by-file"],
+    );
+    let titles: Vec<&str> = log.lines().collect();
+    assert_eq!(
+        titles,
+        vec![
+            "Add delta",
+            "Add three files",
+            "Add three files (beta.txt)",
+            "Add three files (alpha.txt)",
+        ]
+    );
+
+    // HEAD is left on the remainder, which keeps the original GG-ID and
+    // only gamma.txt.
+    let (_, body, _) = run_git_full(&repo_path, &["log", "-1", "--format=%B", "HEAD"]);
+    assert!(body.contains("GG-ID: c-1111111"), "{body}");
+    let (_, files, _) = run_git_full(&repo_path, &["diff", "--name-only", "HEAD~1", "HEAD"]);
+    assert_eq!(files.trim(), "gamma.txt");
+    let (_, files, _) = run_git_full(&repo_path, &["diff", "--name-only", "HEAD~3", "HEAD~2"]);
+    assert_eq!(files.trim(), "alpha.txt");
+}
+
+#[test]
+fn test_split_by_hunk_creates_commit_per_hunk() {
+    let (_temp_dir, repo_path) = create_two_hunk_split_commit();
+
+    let (success, stdout, stderr) = run_gg(&repo_path, &["split", "--by-hunk"]);
+    assert!(success, "split --by-hunk failed: {stdout} {stderr}");
+
+    // Two textual hunks plus the binary file, which is its own group.
+    let (_, log, _) = run_git_full(&repo_path, &["log", "--format=%s", "-3"]);
+    let titles: Vec<&str> = log.lines().collect();
+    assert_eq!(titles[0], "Two separated hunks");
+    assert!(titles[1].starts_with("Two separated hunks ("), "{log}");
+    assert!(titles[2].starts_with("Two separated hunks ("), "{log}");
+
+    let (_, body, _) = run_git_full(&repo_path, &["log", "-1", "--format=%B"]);
+    assert!(body.contains("GG-ID: c-abc1234"), "{body}");
+    let (_, tree_diff, _) = run_git_full(&repo_path, &["diff", "--stat", "HEAD~3", "HEAD"]);
+    assert!(tree_diff.contains("multi_hunk.txt"), "{tree_diff}");
+}
+
+#[test]
+fn test_split_by_file_rejects_single_file_commit() {
+    let (_temp_dir, repo_path) = create_test_repo();
+
+    let gg_dir = repo_path.join(".git/gg");
+    fs::create_dir_all(&gg_dir).expect("Failed to create gg dir");
+    fs::write(
+        gg_dir.join("config.json"),
+        r#"{"defaults":{"branch_username":"testuser"}}"#,
+    )
+    .expect("Failed to write config");
+
+    let (success, _, stderr) = run_gg(&repo_path, &["co", "test-by-file-single"]);
+    assert!(success, "Failed to create stack: {}", stderr);
+
+    fs::write(repo_path.join("only.txt"), "only\n").expect("write");
+    run_git(&repo_path, &["add", "only.txt"]);
+    run_git(&repo_path, &["commit", "-m", "Add only"]);
+
+    let (success, _, stderr) = run_gg(&repo_path, &["split", "--by-file"]);
+    assert!(!success);
+    assert!(stderr.contains("single changed file"), "{stderr}");
+}
+
 #[test]
 fn test_split_binary_file_with_file_args() {
     // Verify that `gg split <binary_file>` succeeds for a commit containing
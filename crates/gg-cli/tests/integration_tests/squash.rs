@@ -570,3 +570,203 @@ fn test_squash_requires_stack() {
         stderr
     );
 }
+
+#[test]
+fn test_gg_amend_target_amends_lower_entry_and_keeps_head() {
+    let (_temp_dir, repo_path) = create_test_repo();
+    let gg_dir = repo_path.join(".git/gg");
+    fs::create_dir_all(&gg_dir).expect("Failed to create gg dir");
+    fs::write(
+        gg_dir.join("config.json"),
+        r#"{"defaults":{"branch_username":"testuser"}}"#,
+    )
+    .expect("Failed to write config");
+
+    let (success, _, stderr) = run_gg(&repo_path, &["co", "amend-target"]);
+    assert!(success, "Failed to create stack: {stderr}");
+    for i in 1..=3 {
+        fs::write(repo_path.join(format!("file{i}.txt")), format!("v1 {i}\n")).unwrap();
+        run_git(&repo_path, &["add", "."]);
+        run_git(&repo_path, &["commit", "-m", &format!("Commit {i}")]);
+    }
+
+    // Stand on entry 2, then amend a change into entry 1.
+    let (success, _, stderr) = run_gg(&repo_path, &["mv", "2"]);
+    assert!(success, "mv failed: {stderr}");
+    fs::write(repo_path.join("file1.txt"), "v2 1\n").unwrap();
+    run_git(&repo_path, &["add", "file1.txt"]);
+
+    let (success, stdout, stderr) = run_gg(&repo_path, &["amend", "1"]);
+    assert!(success, "amend failed: {stdout} {stderr}");
+
+    let (_, content) = run_git(&repo_path, &["show", "testuser/amend-target~2:file1.txt"]);
+    assert_eq!(content, "v2 1\n");
+    let (_, log) = run_git(&repo_path, &["log", "--format=%s", "testuser/amend-target"]);
+    assert!(!log.contains("fixup!"), "{log}");
+    assert!(log.starts_with("Commit 3\nCommit 2\nCommit 1\n"), "{log}");
+
+    // HEAD is back on entry 2, which now sits on the amended entry 1.
+    let (_, title) = run_git(&repo_path, &["log", "-1", "--format=%s"]);
+    assert_eq!(title.trim(), "Commit 2");
+    let (_, content) = run_git(&repo_path, &["show", "HEAD:file1.txt"]);
+    assert_eq!(content, "v2 1\n");
+    let (_, status) = run_git(&repo_path, &["status", "--porcelain"]);
+    assert!(status.trim().is_empty(), "{status}");
+}
+
+#[test]
+fn test_gg_sc_into_amends_lower_entry_from_stack_head() {
+    let (_temp_dir, repo_path) = create_test_repo();
+    let gg_dir = repo_path.join(".git/gg");
+    fs::create_dir_all(&gg_dir).expect("Failed to create gg dir");
+    fs::write(
+        gg_dir.join("config.json"),
+        r#"{"defaults":{"branch_username":"testuser"}}"#,
+    )
+    .expect("Failed to write config");
+
+    let (success, _, stderr) = run_gg(&repo_path, &["co", "sc-into"]);
+    assert!(success, "Failed to create stack: {stderr}");
+    for i in 1..=2 {
+        fs::write(repo_path.join(format!("file{i}.txt")), format!("v1 {i}\n")).unwrap();
+        run_git(&repo_path, &["add", "."]);
+        run_git(&repo_path, &["commit", "-m", &format!("Commit {i}")]);
+    }
+
+    fs::write(repo_path.join("file1.txt"), "v2 1\n").unwrap();
+    run_git(&repo_path, &["add", "file1.txt"]);
+
+    let (success, _, stderr) = run_gg(&repo_path, &["sc", "1", "--into", "1"]);
+    assert!(!success, "TARGET and --into must conflict");
+    assert!(stderr.contains("--into"), "{stderr}");
+
+    let (success, stdout, stderr) = run_gg(&repo_path, &["sc", "--into", "1"]);
+    assert!(success, "sc --into failed: {stdout} {stderr}");
+
+    let (_, content) = run_git(&repo_path, &["show", "HEAD~1:file1.txt"]);
+    assert_eq!(content, "v2 1\n");
+    let (_, log) = run_git(&repo_path, &["log", "--format=%s", "-2"]);
+    assert_eq!(log.trim(), "Commit 2\nCommit 1");
+    let (_, status) = run_git(&repo_path, &["status", "--porcelain"]);
+    assert!(status.trim().is_empty(), "{status}");
+}
+
+#[cfg(unix)]
+fn write_git_hook(repo_path: &std::path::Path, name: &str, body: &str) {
+    use std::os::unix::fs::PermissionsExt;
+    let path = repo_path.join(".git/hooks").join(name);
+    fs::create_dir_all(path.parent().unwrap()).unwrap();
+    fs::write(&path, format!("#!/bin/sh\n{body}\n")).unwrap();
+    fs::set_permissions(&path, fs::Permissions::from_mode(0o755)).unwrap();
+}
+
+#[cfg(unix)]
+#[test]
+fn test_gg_sc_no_verify_skips_failing_pre_commit_hook() {
+    let (_temp_dir, repo_path) = create_test_repo();
+    let gg_dir = repo_path.join(".git/gg");
+    fs::create_dir_all(&gg_dir).unwrap();
+    fs::write(
+        gg_dir.join("config.json"),
+        r#"{"defaults":{"branch_username":"testuser"}}"#,
+    )
+    .unwrap();
+    run_gg(&repo_path, &["co", "hooks"]);
+    fs::write(repo_path.join("a.txt"), "a\n").unwrap();
+    run_git(&repo_path, &["add", "."]);
+    run_git(&repo_path, &["commit", "-m", "Add a"]);
+    write_git_hook(
+        &repo_path,
+        "pre-commit",
+        "echo 'pre-commit says no' >&2; exit 1",
+    );
+
+    fs::write(repo_path.join("a.txt"), "a2\n").unwrap();
+    run_git(&repo_path, &["add", "."]);
+    let head_before = head_sha(&repo_path);
+    let (success, _, stderr) = run_gg(&repo_path, &["sc"]);
+    assert!(!success, "pre-commit must block the amend");
+    assert!(stderr.contains("pre-commit says no"), "stderr={stderr}");
+    assert_eq!(head_sha(&repo_path), head_before);
+
+    let (success, _, stderr) = run_gg(&repo_path, &["sc", "--no-verify"]);
+    assert!(success, "--no-verify must skip the hook: {stderr}");
+    assert_ne!(head_sha(&repo_path), head_before);
+}
+
+#[cfg(unix)]
+#[test]
+fn test_gg_sc_into_runs_pre_commit_hook_unless_no_verify() {
+    let (_temp_dir, repo_path) = create_test_repo();
+    let gg_dir = repo_path.join(".git/gg");
+    fs::create_dir_all(&gg_dir).unwrap();
+    fs::write(
+        gg_dir.join("config.json"),
+        r#"{"defaults":{"branch_username":"testuser"}}"#,
+    )
+    .unwrap();
+    run_gg(&repo_path, &["co", "hooks"]);
+    for name in ["first", "second"] {
+        fs::write(repo_path.join(format!("{name}.txt")), name).unwrap();
+        run_git(&repo_path, &["add", "."]);
+        run_git(&repo_path, &["commit", "-m", name]);
+    }
+    write_git_hook(
+        &repo_path,
+        "pre-commit",
+        "echo 'pre-commit says no' >&2; exit 1",
+    );
+
+    fs::write(repo_path.join("first.txt"), "first, amended").unwrap();
+    run_git(&repo_path, &["add", "."]);
+    let head_before = head_sha(&repo_path);
+    let (success, _, stderr) = run_gg(&repo_path, &["sc", "--into", "1"]);
+    assert!(!success, "pre-commit must block the amend");
+    assert!(stderr.contains("pre-commit says no"), "stderr={stderr}");
+    assert_eq!(head_sha(&repo_path), head_before);
+
+    let (success, _, stderr) = run_gg(&repo_path, &["sc", "--into", "1", "--no-verify"]);
+    assert!(success, "--no-verify must skip the hook: {stderr}");
+    let (_, content) = run_git(&repo_path, &["show", "HEAD~1:first.txt"]);
+    assert_eq!(content, "first, amended");
+}
+
+#[cfg(unix)]
+#[test]
+fn test_run_hooks_rewrite_only_runs_hooks_for_the_amend_but_not_the_rebase() {
+    for (run_hooks, expected) in [
+        ("always", "amend\nrebase\n"),
+        ("rewrite-only", "amend\n"),
+        ("never", ""),
+    ] {
+        let (_temp_dir, repo_path) = create_test_repo();
+        let gg_dir = repo_path.join(".git/gg");
+        fs::create_dir_all(&gg_dir).unwrap();
+        fs::write(
+            gg_dir.join("config.json"),
+            format!(r#"{{"defaults":{{"branch_username":"testuser","run_hooks":"{run_hooks}"}}}}"#),
+        )
+        .unwrap();
+        run_gg(&repo_path, &["co", "hooks"]);
+        for name in ["first", "second"] {
+            fs::write(repo_path.join(format!("{name}.txt")), name).unwrap();
+            run_git(&repo_path, &["add", "."]);
+            run_git(&repo_path, &["commit", "-m", name]);
+        }
+        let (success, _, stderr) = run_gg(&repo_path, &["mv", "1"]);
+        assert!(success, "move failed: {stderr}");
+        write_git_hook(
+            &repo_path,
+            "post-rewrite",
+            "echo \"$1\" >> .git/rewrites.log",
+        );
+
+        fs::write(repo_path.join("first.txt"), "first, amended").unwrap();
+        run_git(&repo_path, &["add", "."]);
+        let (success, _, stderr) = run_gg(&repo_path, &["sc"]);
+        assert!(success, "sc failed with run_hooks={run_hooks}: {stderr}");
+
+        let log = fs::read_to_string(repo_path.join(".git/rewrites.log")).unwrap_or_default();
+        assert_eq!(log, expected, "run_hooks={run_hooks}");
+    }
+}
@@ -0,0 +1,80 @@
+use crate::helpers::{create_test_repo, run_gg, run_git};
+
+use serde_json::Value;
+use std::fs;
+
+fn setup_stack(repo_path: &std::path::Path, commits: usize) {
+    let gg_dir = repo_path.join(".git/gg");
+    fs::create_dir_all(&gg_dir).unwrap();
+    fs::write(
+        gg_dir.join("config.json"),
+        r#"{"defaults":{"branch_username":"testuser"}}"#,
+    )
+    .unwrap();
+
+    let (success, _, stderr) = run_gg(repo_path, &["co", "squash-all-test"]);
+    assert!(success, "Failed to create stack: {}", stderr);
+
+    for i in 1..=commits {
+        fs::write(
+            repo_path.join(format!("file{}.txt", i)),
+            format!("content {}", i),
+        )
+        .unwrap();
+        run_git(repo_path, &["add", "."]);
+        run_git(repo_path, &["commit", "-m", &format!("Commit {}", i)]);
+    }
+}
+
+#[test]
+fn test_squash_all_collapses_stack() {
+    let (_temp_dir, repo_path) = create_test_repo();
+    setup_stack(&repo_path, 3);
+
+    let (success, stdout, stderr) = run_gg(&repo_path, &["squash-all", "--yes", "--json"]);
+    assert!(success, "squash-all failed: {} {}", stdout, stderr);
+    let json: Value = serde_json::from_str(&stdout).expect("valid JSON");
+    assert_eq!(json["squashed"], 3);
+    assert_eq!(json["remaining"], 1);
+
+    let (_, message) = run_git(&repo_path, &["log", "-1", "--format=%B"]);
+    assert!(
+        message.starts_with("Commit 1\n\nCommit 2\n\nCommit 3"),
+        "{message}"
+    );
+    assert_eq!(message.matches("GG-ID:").count(), 1, "{message}");
+
+    // All files are still there.
+    for i in 1..=3 {
+        assert!(repo_path.join(format!("file{}.txt", i)).exists());
+    }
+}
+
+#[test]
+fn test_squash_all_until_keeps_upper_entries() {
+    let (_temp_dir, repo_path) = create_test_repo();
+    setup_stack(&repo_path, 3);
+
+    let (success, stdout, stderr) = run_gg(
+        &repo_path,
+        &["squash-all", "--until", "2", "-m", "Combined", "--yes"],
+    );
+    assert!(success, "squash-all failed: {} {}", stdout, stderr);
+    assert!(stdout.contains("2 remaining"), "{stdout}");
+
+    let (success, stdout, _) = run_gg(&repo_path, &["ls"]);
+    assert!(success);
+    assert!(stdout.contains("Combined"));
+    assert!(stdout.contains("Commit 3"));
+    assert!(!stdout.contains("Commit 1"));
+}
+
+#[test]
+fn test_squash_all_needs_two_commits() {
+    let (_temp_dir, repo_path) = create_test_repo();
+    setup_stack(&repo_path, 1);
+
+    let (success, _stdout, stderr) = run_gg(&repo_path, &["squash-all", "--yes"]);
+    assert!(!success);
+    assert!(stderr.contains("at least two commits"), "{stderr}");
+}
@@ -0,0 +1,109 @@
+use crate::helpers::{create_test_repo, run_gg, run_git};
+
+use serde_json::Value;
+use std::fs;
+use std::path::PathBuf;
+
+use tempfile::TempDir;
+
+fn setup_stash_stack(stack_name: &str) -> (TempDir, PathBuf) {
+    let (temp_dir, repo_path) = create_test_repo();
+
+    let gg_dir = repo_path.join(".git/gg");
+    fs::create_dir_all(&gg_dir).unwrap();
+    fs::write(
+        gg_dir.join("config.json"),
+        r#"{"defaults":{"branch_username":"testuser"}}"#,
+    )
+    .unwrap();
+
+    let (success, _, stderr) = run_gg(&repo_path, &["co", stack_name]);
+    assert!(success, "Failed to create stack {stack_name}: {stderr}");
+
+    for i in 1..=2 {
+        fs::write(repo_path.join(format!("file{i}.txt")), format!("v{i}\n")).unwrap();
+        run_git(&repo_path, &["add", "."]);
+        run_git(&repo_path, &["commit", "-m", &format!("Commit {i}")]);
+    }
+    (temp_dir, repo_path)
+}
+
+#[test]
+fn test_stash_push_tags_stack_and_position() {
+    let (_temp_dir, repo_path) = setup_stash_stack("stash-tag");
+
+    fs::write(repo_path.join("file1.txt"), "dirty\n").unwrap();
+    let (success, stdout, stderr) =
+        run_gg(&repo_path, &["stash", "push", "-m", "wip change", "--json"]);
+    assert!(success, "stash push failed: {stdout} {stderr}");
+    let parsed: Value = serde_json::from_str(&stdout).expect("valid JSON");
+    assert_eq!(parsed["stash"]["entry"]["stack"], "stash-tag");
+    assert_eq!(parsed["stash"]["entry"]["position"], 2);
+
+    let (_, status) = run_git(&repo_path, &["status", "--porcelain"]);
+    assert!(status.trim().is_empty(), "working tree should be clean");
+
+    let (success, stdout, _) = run_gg(&repo_path, &["stash", "list", "--json"]);
+    assert!(success);
+    let parsed: Value = serde_json::from_str(&stdout).expect("valid JSON");
+    let stashes = parsed["stashes"].as_array().unwrap();
+    assert_eq!(stashes.len(), 1);
+    assert_eq!(stashes[0]["reference"], "stash@{0}");
+    assert_eq!(stashes[0]["message"], "wip change");
+}
+
+#[test]
+fn test_stash_list_hides_untagged_unless_all() {
+    let (_temp_dir, repo_path) = setup_stash_stack("stash-untagged");
+
+    fs::write(repo_path.join("file1.txt"), "dirty\n").unwrap();
+    run_git(&repo_path, &["stash", "push", "-m", "plain"]);
+
+    let (success, stdout, _) = run_gg(&repo_path, &["stash", "list", "--json"]);
+    assert!(success);
+    let parsed: Value = serde_json::from_str(&stdout).unwrap();
+    assert!(parsed["stashes"].as_array().unwrap().is_empty());
+
+    let (success, stdout, _) = run_gg(&repo_path, &["stash", "list", "--all", "--json"]);
+    assert!(success);
+    let parsed: Value = serde_json::from_str(&stdout).unwrap();
+    assert_eq!(parsed["stashes"].as_array().unwrap().len(), 1);
+    assert!(parsed["stashes"][0]["stack"].is_null());
+}
+
+#[test]
+fn test_stash_pop_warns_on_different_position() {
+    let (_temp_dir, repo_path) = setup_stash_stack("stash-pop");
+
+    fs::write(repo_path.join("file1.txt"), "dirty\n").unwrap();
+    let (success, _, stderr) = run_gg(&repo_path, &["stash", "push"]);
+    assert!(success, "stash push failed: {stderr}");
+
+    let (success, _, stderr) = run_gg(&repo_path, &["first"]);
+    assert!(success, "gg first failed: {stderr}");
+
+    let (success, stdout, stderr) = run_gg(&repo_path, &["stash", "pop"]);
+    assert!(success, "stash pop failed: {stdout} {stderr}");
+    assert!(
+        stdout.contains("position 2") && stdout.contains("position 1"),
+        "expected position mismatch warning: {stdout}"
+    );
+
+    let (_, content) = run_git(&repo_path, &["show", ":file1.txt"]);
+    assert_eq!(content, "v1\n");
+    assert_eq!(
+        fs::read_to_string(repo_path.join("file1.txt")).unwrap(),
+        "dirty\n"
+    );
+    let (_, stash_list) = run_git(&repo_path, &["stash", "list"]);
+    assert!(stash_list.trim().is_empty());
+}
+
+#[test]
+fn test_stash_push_without_changes_fails() {
+    let (_temp_dir, repo_path) = setup_stash_stack("stash-clean");
+
+    let (success, _, stderr) = run_gg(&repo_path, &["stash", "push"]);
+    assert!(!success);
+    assert!(stderr.contains("No local changes"), "stderr={stderr}");
+}
@@ -0,0 +1,64 @@
+use crate::helpers::{create_test_repo, run_gg, run_git};
+
+use serde_json::Value;
+use std::fs;
+
+#[test]
+fn test_status_reports_position_and_changes() {
+    let (_temp_dir, repo_path) = create_test_repo();
+    let gg_dir = repo_path.join(".git/gg");
+    fs::create_dir_all(&gg_dir).unwrap();
+    fs::write(
+        gg_dir.join("config.json"),
+        r#"{"defaults":{"branch_username":"testuser"}}"#,
+    )
+    .unwrap();
+
+    let (success, _, stderr) = run_gg(&repo_path, &["co", "status-test"]);
+    assert!(success, "Failed to create stack: {}", stderr);
+    for i in 1..=2 {
+        fs::write(repo_path.join(format!("file{i}.txt")), "v1\n").unwrap();
+        run_git(&repo_path, &["add", "."]);
+        run_git(&repo_path, &["commit", "-m", &format!("Commit {i}")]);
+    }
+
+    fs::write(repo_path.join("file1.txt"), "v2\n").unwrap();
+    fs::write(repo_path.join("file2.txt"), "v2\n").unwrap();
+    run_git(&repo_path, &["add", "file2.txt"]);
+    fs::write(repo_path.join("notes.txt"), "scratch\n").unwrap();
+
+    let (success, stdout, stderr) = run_gg(&repo_path, &["status", "--json"]);
+    assert!(success, "status failed: {stdout} {stderr}");
+    let parsed: Value = serde_json::from_str(&stdout).expect("valid JSON");
+    let status = &parsed["status"];
+    assert_eq!(status["branch"], "testuser/status-test");
+    assert_eq!(status["stack"]["name"], "status-test");
+    assert_eq!(status["stack"]["current_position"], 2);
+    assert_eq!(status["stack"]["total_commits"], 2);
+    assert_eq!(status["current_entry"]["title"], "Commit 2");
+    assert_eq!(status["staged"], serde_json::json!(["file2.txt"]));
+    assert_eq!(status["unstaged"], serde_json::json!(["file1.txt"]));
+    assert_eq!(status["untracked"], serde_json::json!(["notes.txt"]));
+    assert_eq!(status["rebase_in_progress"], false);
+
+    let (success, stdout, _) = run_gg(&repo_path, &["status"]);
+    assert!(success);
+    assert!(stdout.contains("At [2/2]"), "{stdout}");
+    assert!(stdout.contains("Staged (1)"), "{stdout}");
+}
+
+#[test]
+fn test_status_outside_a_stack() {
+    let (_temp_dir, repo_path) = create_test_repo();
+
+    let (success, stdout, stderr) = run_gg(&repo_path, &["status", "--json"]);
+    assert!(success, "status failed: {stdout} {stderr}");
+    let parsed: Value = serde_json::from_str(&stdout).expect("valid JSON");
+    assert!(parsed["status"]["stack"].is_null());
+    assert!(parsed["status"]["current_entry"].is_null());
+
+    let (success, stdout, _) = run_gg(&repo_path, &["status"]);
+    assert!(success);
+    assert!(stdout.contains("not a stack"), "{stdout}");
+    assert!(stdout.contains("Working tree clean"), "{stdout}");
+}
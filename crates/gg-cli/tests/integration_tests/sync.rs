@@ -1205,3 +1205,550 @@ fn git_current_branch(repo_path: &std::path::Path) -> String {
         .expect("git rev-parse failed");
     String::from_utf8_lossy(&output.stdout).trim().to_string()
 }
+
+#[test]
+fn test_sync_dry_run_json_reports_plan_without_changes() {
+    let (_temp_dir, repo_path, remote_path) = create_test_repo_with_remote();
+
+    let gg_dir = repo_path.join(".git/gg");
+    fs::create_dir_all(&gg_dir).expect("Failed to create gg dir");
+    fs::write(
+        gg_dir.join("config.json"),
+        r#"{"defaults":{"branch_username":"testuser","provider":"github","base":"main","sync_behind_threshold":0}}"#,
+    )
+    .expect("Failed to write config");
+
+    let (success, _, stderr) = run_gg(&repo_path, &["co", "dry-run"]);
+    assert!(success, "Failed to create stack: {}", stderr);
+
+    fs::write(repo_path.join("a.txt"), "a\n").expect("Failed to write a");
+    run_git(&repo_path, &["add", "a.txt"]);
+    run_git(&repo_path, &["commit", "-m", "First\n\nGG-ID: c-1111111"]);
+    fs::write(repo_path.join("b.txt"), "b\n").expect("Failed to write b");
+    run_git(&repo_path, &["add", "b.txt"]);
+    run_git(&repo_path, &["commit", "-m", "Second"]);
+    let (_, head_before) = run_git(&repo_path, &["rev-parse", "HEAD"]);
+
+    // Any call that would change the remote fails the fake gh.
+    let fake_bin = repo_path.join("fake-bin");
+    fs::create_dir_all(&fake_bin).expect("Failed to create fake bin dir");
+    fs::write(
+        fake_bin.join("gh"),
+        r#"#!/bin/sh
+if [ "$1" = "--version" ]; then
+  echo "gh version 2.0.0"
+  exit 0
+fi
+if [ "$1" = "auth" ] && [ "$2" = "status" ]; then
+  exit 0
+fi
+echo "unexpected gh invocation: $@" >&2
+exit 1
+"#,
+    )
+    .expect("Failed to write fake gh");
+    #[cfg(unix)]
+    {
+        let mut perms = fs::metadata(fake_bin.join("gh"))
+            .expect("Failed to stat fake gh")
+            .permissions();
+        perms.set_mode(0o755);
+        fs::set_permissions(fake_bin.join("gh"), perms).expect("Failed to chmod fake gh");
+    }
+    let mut new_path = std::ffi::OsString::from(fake_bin.as_os_str());
+    new_path.push(":");
+    new_path.push(std::env::var_os("PATH").unwrap_or_default());
+
+    let (success, stdout, stderr) = run_gg_with_env(
+        &repo_path,
+        &["sync", "--dry-run", "--json"],
+        &[("PATH", new_path.as_os_str())],
+    );
+    assert!(success, "sync --dry-run failed: {}\n{}", stdout, stderr);
+
+    let parsed: Value = serde_json::from_str(&stdout).expect("valid JSON");
+    let plan = &parsed["sync_plan"];
+    assert_eq!(plan["stack"], "dry-run");
+    assert_eq!(plan["metadata"]["gg_ids_added"], 1);
+    let entries = plan["entries"].as_array().unwrap();
+    assert_eq!(entries.len(), 2);
+
+    assert_eq!(entries[0]["gg_id"], "c-1111111");
+    assert_eq!(entries[0]["branch"], "testuser/dry-run--c-1111111");
+    assert_eq!(entries[0]["push"], "create");
+    assert_eq!(entries[0]["pr_action"], "create");
+    assert_eq!(entries[0]["target_branch"], "main");
+
+    assert!(entries[1]["gg_id"].is_null());
+    assert!(entries[1]["branch"].is_null());
+    assert_eq!(entries[1]["rewritten"], true);
+    assert_eq!(entries[1]["pr_action"], "create");
+    assert_eq!(entries[1]["target_branch"], "testuser/dry-run--c-1111111");
+
+    // Nothing was rewritten or pushed.
+    let (_, head_after) = run_git(&repo_path, &["rev-parse", "HEAD"]);
+    assert_eq!(head_before, head_after);
+    let (_, remote_branches) = run_git(&remote_path, &["branch", "--list", "testuser/*"]);
+    assert!(remote_branches.trim().is_empty(), "{}", remote_branches);
+}
+
+#[test]
+fn test_sync_only_pushes_single_entry() {
+    let (_temp_dir, repo_path, remote_path) = create_test_repo_with_remote();
+
+    let gg_dir = repo_path.join(".git/gg");
+    fs::create_dir_all(&gg_dir).expect("Failed to create gg dir");
+    fs::write(
+        gg_dir.join("config.json"),
+        r#"{"defaults":{"branch_username":"testuser","provider":"github","base":"main","sync_behind_threshold":0}}"#,
+    )
+    .expect("Failed to write config");
+
+    let (success, _, stderr) = run_gg(&repo_path, &["co", "only"]);
+    assert!(success, "Failed to create stack: {}", stderr);
+    for (file, id) in [
+        ("a.txt", "c-aaaaaaa"),
+        ("b.txt", "c-bbbbbbb"),
+        ("c.txt", "c-ccccccc"),
+    ] {
+        fs::write(repo_path.join(file), "x\n").expect("Failed to write file");
+        run_git(&repo_path, &["add", file]);
+        run_git(
+            &repo_path,
+            &["commit", "-m", &format!("Add {}\n\nGG-ID: {}", file, id)],
+        );
+    }
+
+    // Fake gh: every `pr create` gets the next number, `pr view N` reports
+    // it open on the branch it was created from.
+    let fake_bin = repo_path.join("fake-bin");
+    fs::create_dir_all(&fake_bin).expect("Failed to create fake bin dir");
+    let log = repo_path.join("gh.log");
+    fs::write(
+        fake_bin.join("gh"),
+        format!(
+            r#"#!/bin/sh
+echo "$@" >> "{log}"
+if [ "$1" = "--version" ]; then
+  echo "gh version 2.0.0"
+  exit 0
+fi
+if [ "$1" = "auth" ] && [ "$2" = "status" ]; then
+  exit 0
+fi
+if [ "$1" = "pr" ] && [ "$2" = "create" ]; then
+  n=$(grep -c '^pr create' "{log}")
+  echo "https://github.com/test/repo/pull/10$n"
+  exit 0
+fi
+if [ "$1" = "pr" ] && [ "$2" = "view" ]; then
+  echo '{{"number":'"$3"',"title":"Entry","state":"OPEN","url":"https://github.com/test/repo/pull/'"$3"'","isDraft":false,"mergeable":"MERGEABLE","reviews":[]}}'
+  exit 0
+fi
+exit 0
+"#,
+            log = log.display()
+        ),
+    )
+    .expect("Failed to write fake gh");
+    #[cfg(unix)]
+    {
+        let mut perms = fs::metadata(fake_bin.join("gh"))
+            .expect("Failed to stat fake gh")
+            .permissions();
+        perms.set_mode(0o755);
+        fs::set_permissions(fake_bin.join("gh"), perms).expect("Failed to chmod fake gh");
+    }
+    let mut new_path = std::ffi::OsString::from(fake_bin.as_os_str());
+    new_path.push(":");
+    new_path.push(std::env::var_os("PATH").unwrap_or_default());
+    let env = [("PATH", new_path.as_os_str())];
+
+    // The entry below has never been pushed, so there is nothing to target.
+    let (success, _stdout, stderr) = run_gg_with_env(
+        &repo_path,
+        &["sync", "--only", "2", "--no-rebase-check"],
+        &env,
+    );
+    assert!(!success, "--only should refuse an unsynced predecessor");
+    assert!(stderr.contains("gg sync --until 1"), "stderr: {}", stderr);
+
+    let (success, stdout, stderr) = run_gg_with_env(
+        &repo_path,
+        &["sync", "--until", "1", "--no-rebase-check"],
+        &env,
+    );
+    assert!(success, "sync --until 1 failed: {}\n{}", stdout, stderr);
+
+    let (success, stdout, stderr) = run_gg_with_env(
+        &repo_path,
+        &["sync", "--only", "2", "--no-rebase-check", "--json"],
+        &env,
+    );
+    assert!(success, "sync --only 2 failed: {}\n{}", stdout, stderr);
+    let parsed: Value = serde_json::from_str(&stdout).expect("valid JSON");
+    let entries = parsed["sync"]["entries"].as_array().unwrap();
+    assert_eq!(entries.len(), 1);
+    assert_eq!(entries[0]["position"], 2);
+    assert_eq!(entries[0]["action"], "created");
+
+    let (_, remote_branches) = run_git(&remote_path, &["branch", "--list", "testuser/*"]);
+    assert!(remote_branches.contains("testuser/only--c-aaaaaaa"));
+    assert!(remote_branches.contains("testuser/only--c-bbbbbbb"));
+    assert!(
+        !remote_branches.contains("testuser/only--c-ccccccc"),
+        "entry 3 must not be pushed: {}",
+        remote_branches
+    );
+
+    let gh_log = fs::read_to_string(&log).expect("gh log");
+    let creates: Vec<&str> = gh_log
+        .lines()
+        .filter(|l| l.starts_with("pr create"))
+        .collect();
+    assert_eq!(creates.len(), 2, "{}", gh_log);
+    assert!(
+        creates[1].contains("--base testuser/only--c-aaaaaaa"),
+        "{}",
+        creates[1]
+    );
+}
+
+#[test]
+fn test_sync_requests_reviewers_on_new_prs() {
+    let (_temp_dir, repo_path, _remote_path) = create_test_repo_with_remote();
+
+    let gg_dir = repo_path.join(".git/gg");
+    fs::create_dir_all(&gg_dir).expect("Failed to create gg dir");
+    fs::write(
+        gg_dir.join("config.json"),
+        r#"{"defaults":{"branch_username":"testuser","provider":"github","base":"main","sync_behind_threshold":0,"reviewers":["alice"],"labels":["stacked"],"milestone":"v2"}}"#,
+    )
+    .expect("Failed to write config");
+
+    let (success, _, stderr) = run_gg(&repo_path, &["co", "reviewers"]);
+    assert!(success, "Failed to create stack: {}", stderr);
+    fs::write(repo_path.join("a.txt"), "a\n").expect("Failed to write a");
+    run_git(&repo_path, &["add", "a.txt"]);
+    run_git(&repo_path, &["commit", "-m", "Add a\n\nGG-ID: c-aaaaaaa"]);
+
+    let fake_bin = repo_path.join("fake-bin");
+    fs::create_dir_all(&fake_bin).expect("Failed to create fake bin dir");
+    let log = repo_path.join("gh.log");
+    fs::write(
+        fake_bin.join("gh"),
+        format!(
+            r#"#!/bin/sh
+echo "$@" >> "{log}"
+if [ "$1" = "--version" ]; then
+  echo "gh version 2.0.0"
+  exit 0
+fi
+if [ "$1" = "pr" ] && [ "$2" = "create" ]; then
+  echo "https://github.com/test/repo/pull/101"
+  exit 0
+fi
+if [ "$1" = "pr" ] && [ "$2" = "view" ]; then
+  echo '{{"number":101,"title":"Add a","state":"OPEN","url":"https://github.com/test/repo/pull/101","isDraft":false,"mergeable":"MERGEABLE","reviews":[]}}'
+  exit 0
+fi
+exit 0
+"#,
+            log = log.display()
+        ),
+    )
+    .expect("Failed to write fake gh");
+    #[cfg(unix)]
+    {
+        let mut perms = fs::metadata(fake_bin.join("gh"))
+            .expect("Failed to stat fake gh")
+            .permissions();
+        perms.set_mode(0o755);
+        fs::set_permissions(fake_bin.join("gh"), perms).expect("Failed to chmod fake gh");
+    }
+    let mut new_path = std::ffi::OsString::from(fake_bin.as_os_str());
+    new_path.push(":");
+    new_path.push(std::env::var_os("PATH").unwrap_or_default());
+
+    let (success, stdout, stderr) = run_gg_with_env(
+        &repo_path,
+        &["sync", "--no-rebase-check", "--reviewer", "@bob"],
+        &[("PATH", new_path.as_os_str())],
+    );
+    assert!(success, "sync failed: {}\n{}", stdout, stderr);
+
+    let gh_log = fs::read_to_string(&log).expect("gh log");
+    assert!(
+        gh_log
+            .lines()
+            .any(|l| l == "pr edit 101 --add-reviewer alice,bob"),
+        "{}",
+        gh_log
+    );
+    assert!(
+        gh_log
+            .lines()
+            .any(|l| l == "pr edit 101 --add-label stacked"),
+        "{}",
+        gh_log
+    );
+    assert!(
+        gh_log.lines().any(|l| l == "pr edit 101 --milestone v2"),
+        "{}",
+        gh_log
+    );
+}
+
+#[test]
+fn test_sync_links_issue_keys_in_pr_description() {
+    let (_temp_dir, repo_path, _remote_path) = create_test_repo_with_remote();
+
+    let gg_dir = repo_path.join(".git/gg");
+    fs::create_dir_all(&gg_dir).expect("Failed to create gg dir");
+    fs::write(
+        gg_dir.join("config.json"),
+        r#"{"defaults":{"branch_username":"testuser","provider":"github","base":"main","sync_behind_threshold":0,"issue_pattern":"PAY-\\d+","issue_link":"Fixes {key}"}}"#,
+    )
+    .expect("Failed to write config");
+
+    let (success, _, stderr) = run_gg(&repo_path, &["co", "refunds"]);
+    assert!(success, "Failed to create stack: {}", stderr);
+    fs::write(repo_path.join("a.txt"), "a\n").expect("Failed to write a");
+    run_git(&repo_path, &["add", "a.txt"]);
+    run_git(
+        &repo_path,
+        &[
+            "commit",
+            "-m",
+            "Retry refunds\n\nSee PAY-42.\n\nGG-ID: c-aaaaaaa",
+        ],
+    );
+
+    let fake_bin = repo_path.join("fake-bin");
+    fs::create_dir_all(&fake_bin).expect("Failed to create fake bin dir");
+    let log = repo_path.join("gh.log");
+    fs::write(
+        fake_bin.join("gh"),
+        format!(
+            r#"#!/bin/sh
+echo "$@" >> "{log}"
+if [ "$1" = "--version" ]; then
+  echo "gh version 2.0.0"
+  exit 0
+fi
+if [ "$1" = "pr" ] && [ "$2" = "create" ]; then
+  echo "https://github.com/test/repo/pull/101"
+  exit 0
+fi
+if [ "$1" = "pr" ] && [ "$2" = "view" ]; then
+  echo '{{"number":101,"title":"Retry refunds","state":"OPEN","url":"https://github.com/test/repo/pull/101","isDraft":false,"mergeable":"MERGEABLE","reviews":[]}}'
+  exit 0
+fi
+exit 0
+"#,
+            log = log.display()
+        ),
+    )
+    .expect("Failed to write fake gh");
+    #[cfg(unix)]
+    {
+        let mut perms = fs::metadata(fake_bin.join("gh"))
+            .expect("Failed to stat fake gh")
+            .permissions();
+        perms.set_mode(0o755);
+        fs::set_permissions(fake_bin.join("gh"), perms).expect("Failed to chmod fake gh");
+    }
+    let mut new_path = std::ffi::OsString::from(fake_bin.as_os_str());
+    new_path.push(":");
+    new_path.push(std::env::var_os("PATH").unwrap_or_default());
+
+    let (success, stdout, stderr) = run_gg_with_env(
+        &repo_path,
+        &["sync", "--no-rebase-check"],
+        &[("PATH", new_path.as_os_str())],
+    );
+    assert!(success, "sync failed: {}\n{}", stdout, stderr);
+
+    let gh_log = fs::read_to_string(&log).expect("gh log");
+    assert!(gh_log.contains("pr create"), "{}", gh_log);
+    assert_eq!(gh_log.matches("Fixes PAY-42").count(), 1, "{}", gh_log);
+}
+
+#[test]
+fn test_sync_template_stack_table_links_later_prs() {
+    let (_temp_dir, repo_path, _remote_path) = create_test_repo_with_remote();
+
+    let gg_dir = repo_path.join(".git/gg");
+    fs::create_dir_all(&gg_dir).expect("Failed to create gg dir");
+    fs::write(
+        gg_dir.join("config.json"),
+        r#"{"defaults":{"branch_username":"testuser","provider":"github","base":"main","sync_behind_threshold":0}}"#,
+    )
+    .expect("Failed to write config");
+    fs::create_dir_all(repo_path.join(".gg")).expect("Failed to create .gg dir");
+    fs::write(
+        repo_path.join(".gg/pr_template.md"),
+        "{{title}}\n\n{{part}}\n\n{{stack_table}}",
+    )
+    .expect("Failed to write template");
+
+    let (success, _, stderr) = run_gg(&repo_path, &["co", "templated"]);
+    assert!(success, "Failed to create stack: {}", stderr);
+    for (file, id) in [("a", "c-aaaaaaa"), ("b", "c-bbbbbbb")] {
+        fs::write(repo_path.join(format!("{file}.txt")), "x\n").expect("Failed to write");
+        run_git(&repo_path, &["add", &format!("{file}.txt")]);
+        run_git(
+            &repo_path,
+            &["commit", "-m", &format!("Add {file}\n\nGG-ID: {id}")],
+        );
+    }
+
+    // Stores PR bodies as files so `pr view --json body` and `pr edit --body`
+    // round-trip like the real API.
+    let fake_bin = repo_path.join("fake-bin");
+    fs::create_dir_all(&fake_bin).expect("Failed to create fake bin dir");
+    let state = repo_path.join("gh-state");
+    fs::create_dir_all(&state).expect("Failed to create state dir");
+    fs::write(
+        fake_bin.join("gh"),
+        format!(
+            r#"#!/bin/sh
+state="{state}"
+if [ "$1" = "--version" ]; then
+  echo "gh version 2.0.0"
+  exit 0
+fi
+body_arg() {{
+  while [ $# -gt 0 ]; do
+    if [ "$1" = "--body" ]; then printf '%s' "$2"; return; fi
+    shift
+  done
+}}
+if [ "$1" = "pr" ] && [ "$2" = "create" ]; then
+  n=$(( $(cat "$state/next" 2>/dev/null || echo 101) ))
+  echo $((n + 1)) > "$state/next"
+  body_arg "$@" > "$state/body-$n"
+  echo "https://github.com/test/repo/pull/$n"
+  exit 0
+fi
+if [ "$1" = "pr" ] && [ "$2" = "edit" ]; then
+  body_arg "$@" > "$state/body-$3"
+  exit 0
+fi
+if [ "$1" = "pr" ] && [ "$2" = "view" ] && [ "$5" = "body" ]; then
+  cat "$state/body-$3"
+  exit 0
+fi
+if [ "$1" = "pr" ] && [ "$2" = "view" ]; then
+  echo '{{"number":'"$3"',"title":"x","state":"OPEN","url":"https://github.com/test/repo/pull/'"$3"'","isDraft":false,"mergeable":"MERGEABLE","reviews":[]}}'
+  exit 0
+fi
+exit 0
+"#,
+            state = state.display()
+        ),
+    )
+    .expect("Failed to write fake gh");
+    #[cfg(unix)]
+    {
+        let mut perms = fs::metadata(fake_bin.join("gh"))
+            .expect("Failed to stat fake gh")
+            .permissions();
+        perms.set_mode(0o755);
+        fs::set_permissions(fake_bin.join("gh"), perms).expect("Failed to chmod fake gh");
+    }
+    let mut new_path = std::ffi::OsString::from(fake_bin.as_os_str());
+    new_path.push(":");
+    new_path.push(std::env::var_os("PATH").unwrap_or_default());
+
+    let (success, stdout, stderr) = run_gg_with_env(
+        &repo_path,
+        &["sync", "--no-rebase-check"],
+        &[("PATH", new_path.as_os_str())],
+    );
+    assert!(success, "sync failed: {}\n{}", stdout, stderr);
+
+    let first = fs::read_to_string(state.join("body-101")).expect("body of #101");
+    assert!(first.contains("Part 1 of 2 · next: #102"), "{}", first);
+    assert!(first.contains("| 👉 | 1 | #101 | Add a |"), "{}", first);
+    assert!(first.contains("|  | 2 | #102 | Add b |"), "{}", first);
+    let second = fs::read_to_string(state.join("body-102")).expect("body of #102");
+    assert!(
+        second.contains("Part 2 of 2 · previous: #101"),
+        "{}",
+        second
+    );
+}
+
+#[cfg(unix)]
+#[test]
+fn test_gg_sync_pre_sync_hook_failure_aborts_before_push() {
+    let (_temp_dir, repo_path, remote_path) = create_test_repo_with_remote();
+
+    let gg_dir = repo_path.join(".git/gg");
+    fs::create_dir_all(gg_dir.join("hooks")).expect("Failed to create hooks dir");
+    fs::write(
+        gg_dir.join("config.json"),
+        r#"{"defaults":{"branch_username":"testuser","provider":"github","base":"main"}}"#,
+    )
+    .expect("Failed to write config");
+
+    let hook_out = repo_path.join("hook-env.txt");
+    let hook = gg_dir.join("hooks/pre-sync");
+    fs::write(
+        &hook,
+        format!(
+            "#!/bin/sh\necho \"$GG_HOOK $GG_STACK $GG_ENTRY_COUNT $GG_IDS\" > {}\nexit 1\n",
+            hook_out.display()
+        ),
+    )
+    .expect("Failed to write hook");
+    fs::set_permissions(&hook, fs::Permissions::from_mode(0o755)).expect("Failed to chmod hook");
+
+    let (success, _, stderr) = run_gg(&repo_path, &["co", "hooked"]);
+    assert!(success, "Failed to create stack: {}", stderr);
+    fs::write(repo_path.join("entry.txt"), "a\n").expect("Failed to write entry");
+    run_git(&repo_path, &["add", "entry.txt"]);
+    run_git(&repo_path, &["commit", "-m", "Entry\n\nGG-ID: c-1a2b3c4"]);
+
+    let fake_bin = repo_path.join("fake-bin");
+    fs::create_dir_all(&fake_bin).expect("Failed to create fake bin dir");
+    fs::write(
+        fake_bin.join("gh"),
+        "#!/bin/sh\n[ \"$1\" = \"--version\" ] && echo \"gh version 2.0.0\"\n[ \"$1\" = \"auth\" ] && exit 0\nexit 0\n",
+    )
+    .expect("Failed to write fake gh");
+    fs::set_permissions(fake_bin.join("gh"), fs::Permissions::from_mode(0o755))
+        .expect("Failed to chmod fake gh");
+    let mut path = std::ffi::OsString::from(fake_bin.as_os_str());
+    path.push(":");
+    path.push(std::env::var_os("PATH").unwrap_or_default());
+
+    let (success, _stdout, stderr) = run_gg_with_env(
+        &repo_path,
+        &["sync", "--no-rebase-check"],
+        &[("PATH", path.as_os_str())],
+    );
+    assert!(!success, "sync should abort when pre-sync fails");
+    assert!(
+        stderr.contains("pre-sync hook failed"),
+        "stderr: {}",
+        stderr
+    );
+    assert_eq!(
+        fs::read_to_string(&hook_out).expect("hook did not run"),
+        "pre-sync hooked 1 c-1a2b3c4\n"
+    );
+
+    let remote_branches = std::process::Command::new("git")
+        .args(["branch", "--list", "testuser/*"])
+        .current_dir(&remote_path)
+        .output()
+        .expect("Failed to list remote branches");
+    assert!(
+        String::from_utf8_lossy(&remote_branches.stdout)
+            .trim()
+            .is_empty(),
+        "nothing should be pushed"
+    );
+}
@@ -360,3 +360,41 @@ fn test_undo_roundtrip_from_worktree() {
         "undo from worktree should restore pre-drop HEAD"
     );
 }
+
+#[test]
+fn test_redo_reapplies_undone_operation_once() {
+    let (_temp_dir, repo_path) = setup_undo_test_repo("redo");
+
+    for name in ["a", "b"] {
+        fs::write(repo_path.join(format!("{name}.txt")), "v1").unwrap();
+        run_git(&repo_path, &["add", "."]);
+        run_git(&repo_path, &["commit", "-m", &format!("Commit {name}")]);
+    }
+    let head_with_both = head_sha(&repo_path);
+
+    // Nothing was undone yet.
+    let (success, _, stderr) = run_gg(&repo_path, &["redo"]);
+    assert!(!success);
+    assert!(stderr.contains("Nothing to redo"), "{stderr}");
+
+    let (success, _, stderr) = run_gg(&repo_path, &["drop", "1", "--force"]);
+    assert!(success, "drop failed: {stderr}");
+    let head_after_drop = head_sha(&repo_path);
+
+    let (success, _, stderr) = run_gg(&repo_path, &["undo"]);
+    assert!(success, "undo failed: {stderr}");
+    assert_eq!(head_sha(&repo_path), head_with_both);
+
+    let (success, stdout, stderr) = run_gg(&repo_path, &["redo", "--json"]);
+    assert!(success, "redo failed: {stdout} {stderr}");
+    let parsed: Value = serde_json::from_str(&stdout).expect("valid JSON");
+    assert_eq!(parsed["status"], "succeeded");
+    assert_eq!(parsed["undone"]["kind"], "undo");
+    assert_eq!(head_sha(&repo_path), head_after_drop);
+
+    // A second redo does not flip back.
+    let (success, _, stderr) = run_gg(&repo_path, &["redo"]);
+    assert!(!success);
+    assert!(stderr.contains("Nothing to redo"), "{stderr}");
+    assert_eq!(head_sha(&repo_path), head_after_drop);
+}
@@ -0,0 +1,86 @@
+use crate::helpers::{create_test_repo, run_gg, run_git};
+
+use std::fs;
+
+fn setup_two_stacks(repo_path: &std::path::Path) {
+    let gg_dir = repo_path.join(".git/gg");
+    fs::create_dir_all(&gg_dir).expect("Failed to create gg dir");
+    fs::write(
+        gg_dir.join("config.json"),
+        r#"{"defaults":{"branch_username":"testuser"}}"#,
+    )
+    .expect("Failed to write config");
+
+    for (stack, file) in [("alpha", "a.txt"), ("beta", "b.txt")] {
+        let (success, _stdout, stderr) = run_gg(repo_path, &["co", stack]);
+        assert!(success, "Failed to create stack {}: {}", stack, stderr);
+        fs::write(repo_path.join(file), "content\n").expect("Failed to write file");
+        run_git(repo_path, &["add", file]);
+        run_git(repo_path, &["commit", "-m", &format!("Work on {}", stack)]);
+    }
+}
+
+#[test]
+fn test_gg_use_selects_stack_without_checkout() {
+    let (_temp_dir, repo_path) = create_test_repo();
+    setup_two_stacks(&repo_path);
+
+    let (success, stdout, stderr) = run_gg(&repo_path, &["use", "alpha"]);
+    assert!(success, "gg use failed: {}", stderr);
+    assert!(stdout.contains("alpha"));
+
+    // The checked-out branch is untouched...
+    let (_, branch) = run_git(&repo_path, &["branch", "--show-current"]);
+    assert_eq!(branch.trim(), "testuser/beta");
+
+    // ...but read-only commands follow the selection.
+    let (success, stdout, stderr) = run_gg(&repo_path, &["ls"]);
+    assert!(success, "gg ls failed: {}", stderr);
+    assert!(stdout.contains("Work on alpha"), "stdout: {}", stdout);
+    assert!(!stdout.contains("Work on beta"), "stdout: {}", stdout);
+
+    let (success, stdout, _stderr) = run_gg(&repo_path, &["use"]);
+    assert!(success);
+    assert!(stdout.contains("Active stack: alpha"));
+}
+
+#[test]
+fn test_gg_use_blocks_history_edits_until_checked_out() {
+    let (_temp_dir, repo_path) = create_test_repo();
+    setup_two_stacks(&repo_path);
+
+    let (success, _stdout, stderr) = run_gg(&repo_path, &["use", "alpha"]);
+    assert!(success, "gg use failed: {}", stderr);
+
+    let (success, _stdout, stderr) = run_gg(&repo_path, &["first"]);
+    assert!(!success);
+    assert!(
+        stderr.contains("selected with `gg use`"),
+        "stderr: {}",
+        stderr
+    );
+
+    // Checking out a stack replaces the selection.
+    let (success, _stdout, stderr) = run_gg(&repo_path, &["co", "alpha"]);
+    assert!(success, "gg co failed: {}", stderr);
+    let (success, _stdout, stderr) = run_gg(&repo_path, &["first"]);
+    assert!(success, "gg first failed: {}", stderr);
+}
+
+#[test]
+fn test_gg_use_clear_and_unknown_stack() {
+    let (_temp_dir, repo_path) = create_test_repo();
+    setup_two_stacks(&repo_path);
+
+    let (success, _stdout, stderr) = run_gg(&repo_path, &["use", "missing"]);
+    assert!(!success);
+    assert!(stderr.contains("not found"), "stderr: {}", stderr);
+
+    run_gg(&repo_path, &["use", "alpha"]);
+    let (success, _stdout, stderr) = run_gg(&repo_path, &["use", "--clear"]);
+    assert!(success, "gg use --clear failed: {}", stderr);
+
+    let (success, stdout, _stderr) = run_gg(&repo_path, &["ls"]);
+    assert!(success);
+    assert!(stdout.contains("Work on beta"), "stdout: {}", stdout);
+}
@@ -0,0 +1,130 @@
+use crate::helpers::{create_test_repo, run_gg, run_git};
+
+use serde_json::Value;
+use std::fs;
+use std::path::PathBuf;
+
+use tempfile::TempDir;
+
+fn setup_verify_stack(stack_name: &str) -> (TempDir, PathBuf) {
+    let (temp_dir, repo_path) = create_test_repo();
+
+    let gg_dir = repo_path.join(".git/gg");
+    fs::create_dir_all(&gg_dir).unwrap();
+    fs::write(
+        gg_dir.join("config.json"),
+        r#"{"defaults":{"branch_username":"testuser"}}"#,
+    )
+    .unwrap();
+
+    let (success, _, stderr) = run_gg(&repo_path, &["co", stack_name]);
+    assert!(success, "Failed to create stack {stack_name}: {stderr}");
+
+    for i in 1..=2 {
+        fs::write(repo_path.join(format!("file{i}.txt")), format!("v{i}\n")).unwrap();
+        run_git(&repo_path, &["add", "."]);
+        run_git(
+            &repo_path,
+            &["commit", "-m", &format!("Commit {i}\n\nGG-ID: c-000000{i}")],
+        );
+    }
+    (temp_dir, repo_path)
+}
+
+fn read_config(repo_path: &std::path::Path) -> Value {
+    let raw = fs::read_to_string(repo_path.join(".git/gg/config.json")).unwrap();
+    serde_json::from_str(&raw).unwrap()
+}
+
+fn write_config(repo_path: &std::path::Path, config: &Value) {
+    fs::write(
+        repo_path.join(".git/gg/config.json"),
+        serde_json::to_string_pretty(config).unwrap(),
+    )
+    .unwrap();
+}
+
+fn issue_kinds(parsed: &Value) -> Vec<String> {
+    parsed["verify"]["issues"]
+        .as_array()
+        .unwrap()
+        .iter()
+        .map(|i| i["kind"].as_str().unwrap().to_string())
+        .collect()
+}
+
+#[test]
+fn test_verify_healthy_stack() {
+    let (_temp_dir, repo_path) = setup_verify_stack("verify-ok");
+
+    let (success, stdout, stderr) = run_gg(&repo_path, &["verify", "--json"]);
+    assert!(success, "verify failed: {stdout} {stderr}");
+    let parsed: Value = serde_json::from_str(&stdout).expect("valid JSON");
+    assert_eq!(parsed["verify"]["ok"], true);
+    assert!(issue_kinds(&parsed).is_empty());
+}
+
+#[test]
+fn test_verify_fix_repairs_config() {
+    let (_temp_dir, repo_path) = setup_verify_stack("verify-config");
+
+    let mut config = read_config(&repo_path);
+    let stack = &mut config["stacks"]["verify-config"];
+    stack["mrs"] = serde_json::json!({ "c-dead000": 42 });
+    stack["worktree_path"] = Value::String("/nonexistent/gg-verify-worktree".into());
+    write_config(&repo_path, &config);
+
+    let (success, stdout, _) = run_gg(&repo_path, &["verify", "--json"]);
+    assert!(!success, "verify should fail with broken invariants");
+    let parsed: Value = serde_json::from_str(&stdout).expect("valid JSON");
+    let kinds = issue_kinds(&parsed);
+    assert!(kinds.contains(&"stale_mr_mapping".to_string()));
+    assert!(kinds.contains(&"missing_worktree".to_string()));
+
+    let (success, stdout, stderr) = run_gg(&repo_path, &["verify", "--fix", "--json"]);
+    assert!(success, "verify --fix failed: {stdout} {stderr}");
+    let parsed: Value = serde_json::from_str(&stdout).expect("valid JSON");
+    assert_eq!(parsed["verify"]["fixed"], 2);
+    assert_eq!(parsed["verify"]["remaining"], 0);
+
+    let config = read_config(&repo_path);
+    let stack = &config["stacks"]["verify-config"];
+    assert!(stack["mrs"].get("c-dead000").is_none());
+    assert!(stack.get("worktree_path").is_none_or(Value::is_null));
+}
+
+#[test]
+fn test_verify_fix_realigns_entry_branch() {
+    let (_temp_dir, repo_path) = setup_verify_stack("verify-branch");
+
+    let (_, tip) = run_git(&repo_path, &["rev-parse", "HEAD"]);
+    let entry_branch = "testuser/verify-branch--c-0000002";
+    run_git(&repo_path, &["branch", entry_branch, "HEAD~1"]);
+
+    let (success, stdout, _) = run_gg(&repo_path, &["verify", "--json"]);
+    assert!(!success);
+    let parsed: Value = serde_json::from_str(&stdout).expect("valid JSON");
+    assert_eq!(issue_kinds(&parsed), vec!["stale_entry_branch".to_string()]);
+
+    let (success, stdout, stderr) = run_gg(&repo_path, &["verify", "--fix"]);
+    assert!(success, "verify --fix failed: {stdout} {stderr}");
+
+    let (_, target) = run_git(&repo_path, &["rev-parse", entry_branch]);
+    assert_eq!(target.trim(), tip.trim());
+}
+
+#[test]
+fn test_verify_missing_gg_id_is_a_warning() {
+    let (_temp_dir, repo_path) = setup_verify_stack("verify-unsynced");
+
+    fs::write(repo_path.join("file3.txt"), "v3\n").unwrap();
+    run_git(&repo_path, &["add", "."]);
+    run_git(&repo_path, &["commit", "-m", "Not synced yet"]);
+
+    let (success, stdout, stderr) = run_gg(&repo_path, &["verify", "--json"]);
+    assert!(success, "verify failed: {stdout} {stderr}");
+    let parsed: Value = serde_json::from_str(&stdout).expect("valid JSON");
+    assert_eq!(parsed["verify"]["ok"], true);
+    assert_eq!(issue_kinds(&parsed), vec!["missing_gg_id".to_string()]);
+    assert_eq!(parsed["verify"]["issues"][0]["warning"], true);
+}
@@ -0,0 +1,28 @@
+use crate::helpers::{create_test_repo, run_gg, run_git};
+
+use std::fs;
+
+#[test]
+fn test_watch_requires_a_pr() {
+    let (_temp_dir, repo_path) = create_test_repo();
+    let gg_dir = repo_path.join(".git/gg");
+    fs::create_dir_all(&gg_dir).unwrap();
+    fs::write(
+        gg_dir.join("config.json"),
+        r#"{"defaults":{"branch_username":"testuser"}}"#,
+    )
+    .unwrap();
+
+    let (success, _, stderr) = run_gg(&repo_path, &["co", "watch-test"]);
+    assert!(success, "Failed to create stack: {}", stderr);
+    fs::write(repo_path.join("file.txt"), "content\n").unwrap();
+    run_git(&repo_path, &["add", "."]);
+    run_git(&repo_path, &["commit", "-m", "Commit 1"]);
+
+    let (success, stdout, stderr) = run_gg(&repo_path, &["watch"]);
+    assert!(!success);
+    assert!(
+        stderr.contains("No entry has a PR/MR yet"),
+        "{stdout} {stderr}"
+    );
+}
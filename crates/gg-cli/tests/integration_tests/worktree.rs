@@ -217,3 +217,117 @@ fn test_amend_in_worktree_does_not_leave_detached_head() {
 //
 // The original bug was that lint would try to run on position 3 of a 2-commit
 // stack after rebase dropped a landed commit.
+
+fn stack_worktrees(repo_path: &std::path::Path) -> Vec<serde_json::Value> {
+    let (success, stdout, stderr) = run_gg(repo_path, &["worktree", "list", "--json"]);
+    assert!(success, "worktree list failed: {stderr}");
+    let parsed: serde_json::Value =
+        serde_json::from_str(&stdout).expect("stdout must be valid JSON");
+    parsed["worktrees"].as_array().unwrap().clone()
+}
+
+#[test]
+fn test_worktree_list_move_and_prune() {
+    let (_parent_dir, repo_path) = create_test_repo_with_worktree_support();
+    let gg_dir = repo_path.join(".git/gg");
+    fs::create_dir_all(&gg_dir).unwrap();
+    fs::write(
+        gg_dir.join("config.json"),
+        r#"{"defaults":{"branch_username":"testuser"}}"#,
+    )
+    .unwrap();
+    for stack in ["kept", "gone"] {
+        let (success, _, stderr) = run_gg(&repo_path, &["co", stack, "--worktree"]);
+        assert!(success, "co {stack} --worktree failed: {stderr}");
+    }
+
+    let worktrees = stack_worktrees(&repo_path);
+    assert_eq!(worktrees.len(), 2, "{worktrees:?}");
+    assert_eq!(worktrees[0]["stack"], "gone");
+    assert_eq!(worktrees[1]["stack"], "kept");
+    assert!(worktrees.iter().all(|w| w["status"] == "active"));
+    assert_eq!(worktrees[1]["branch"], "testuser/kept");
+
+    // Move one worktree; the config follows
+    let destination = repo_path.parent().unwrap().join("moved/kept");
+    let (success, _, stderr) = run_gg(
+        &repo_path,
+        &["worktree", "move", "kept", destination.to_str().unwrap()],
+    );
+    assert!(success, "worktree move failed: {stderr}");
+    assert!(destination.join("README.md").exists());
+    let worktrees = stack_worktrees(&repo_path);
+    assert_eq!(worktrees[1]["path"], destination.to_str().unwrap());
+    assert_eq!(worktrees[1]["status"], "active");
+    let (_, porcelain) = run_git(&repo_path, &["worktree", "list", "--porcelain"]);
+    assert!(porcelain.contains("moved/kept"), "{porcelain}");
+
+    // Delete the other stack's branch behind gg's back: its worktree is orphaned
+    let gone_path = worktrees[0]["path"].as_str().unwrap().to_string();
+    run_git(std::path::Path::new(&gone_path), &["checkout", "--detach"]);
+    let (success, _) = run_git(&repo_path, &["branch", "-D", "testuser/gone"]);
+    assert!(success);
+    assert_eq!(stack_worktrees(&repo_path)[0]["status"], "orphaned");
+
+    let (success, stdout, stderr) = run_gg(&repo_path, &["worktree", "prune", "--dry-run"]);
+    assert!(success, "prune --dry-run failed: {stderr}");
+    assert!(stdout.contains("Would remove"), "{stdout}");
+    assert!(std::path::Path::new(&gone_path).exists());
+
+    let (success, stdout, stderr) = run_gg(&repo_path, &["worktree", "prune", "--json"]);
+    assert!(success, "prune failed: {stderr}");
+    let parsed: serde_json::Value = serde_json::from_str(&stdout).unwrap();
+    assert_eq!(parsed["pruned"][0]["stack"], "gone");
+    assert!(!std::path::Path::new(&gone_path).exists());
+    let worktrees = stack_worktrees(&repo_path);
+    assert_eq!(worktrees.len(), 1);
+    assert_eq!(worktrees[0]["stack"], "kept");
+}
+
+#[test]
+fn test_always_worktree_opens_stacks_in_worktrees_and_clean_removes_them() {
+    let (parent_dir, repo_path) = create_test_repo_with_worktree_support();
+    let base_dir = parent_dir.path().join("worktrees");
+    let gg_dir = repo_path.join(".git/gg");
+    fs::create_dir_all(&gg_dir).unwrap();
+    fs::write(
+        gg_dir.join("config.json"),
+        format!(
+            r#"{{"defaults":{{"branch_username":"testuser","always_worktree":true,"worktree_base_path":"{}"}}}}"#,
+            base_dir.display()
+        ),
+    )
+    .unwrap();
+
+    // A directory (no {stack}) gets one {repo}.{stack} worktree per stack
+    let (success, _, stderr) = run_gg(&repo_path, &["co", "feat"]);
+    assert!(success, "co feat failed: {stderr}");
+    let repo_name = repo_path.file_name().unwrap().to_str().unwrap();
+    let feat_path = base_dir.join(format!("{repo_name}.feat"));
+    assert!(feat_path.join("README.md").exists());
+    let (_, head) = run_git(&repo_path, &["branch", "--show-current"]);
+    assert_eq!(head.trim(), "main");
+
+    // --no-worktree checks the stack out here
+    let (success, _, stderr) = run_gg(&repo_path, &["co", "here", "--no-worktree"]);
+    assert!(success, "co here --no-worktree failed: {stderr}");
+    let (_, head) = run_git(&repo_path, &["branch", "--show-current"]);
+    assert_eq!(head.trim(), "testuser/here");
+    assert!(!base_dir.join(format!("{repo_name}.here")).exists());
+    run_git(&repo_path, &["checkout", "main"]);
+
+    // Land feat by hand; clean removes its worktree without asking
+    fs::write(feat_path.join("feat.txt"), "feat\n").unwrap();
+    run_git(&feat_path, &["add", "."]);
+    run_git(&feat_path, &["commit", "-m", "Add feat"]);
+    let (success, _) = run_git(&repo_path, &["merge", "--ff-only", "testuser/feat"]);
+    assert!(success);
+
+    let (success, stdout, stderr) = run_gg(&repo_path, &["clean", "--all", "--json"]);
+    assert!(success, "clean failed: {stderr}");
+    let parsed: serde_json::Value = serde_json::from_str(&stdout).unwrap();
+    let cleaned = parsed["clean"]["cleaned"].as_array().unwrap();
+    assert!(cleaned.iter().any(|s| s == "feat"), "{stdout}");
+    assert!(!feat_path.exists());
+    assert!(stack_worktrees(&repo_path).is_empty());
+}
@@ -0,0 +1,289 @@
+//! Direct HTTP transport for provider REST APIs.
+//!
+//! Gitea always goes through here. GitHub and GitLab do too when
+//! `defaults.api_mode` is `"native"` (or `GG_API_MODE=native`): `gh.rs` and
+//! `glab.rs` then hand their core operations to `gh_api.rs` / `glab_api.rs`
+//! instead of spawning the CLIs, so gg works where neither is installed.
+
+use std::sync::OnceLock;
+
+use serde_json::Value;
+
+use crate::config::{ApiMode, Config};
+use crate::error::{GgError, Result};
+use crate::git;
+
+/// Why a request failed.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum HttpError {
+    /// The server answered with a non-success status and this message
+    Status(u16, String),
+    /// The server could not be reached
+    Transport(String),
+    /// The response body was not valid JSON
+    Parse(String),
+}
+
+/// Whether GitHub/GitLab calls should bypass the CLIs.
+///
+/// Resolved once per process from `GG_API_MODE`, then the repository's
+/// config.
+pub fn native_mode() -> bool {
+    static NATIVE: OnceLock<bool> = OnceLock::new();
+    *NATIVE.get_or_init(|| {
+        let configured = git::open_repo()
+            .ok()
+            .and_then(|repo| Config::load(repo.commondir()).ok())
+            .map(|config| config.get_api_mode())
+            .unwrap_or_default();
+        resolve_mode(std::env::var("GG_API_MODE").ok().as_deref(), configured)
+    })
+}
+
+fn resolve_mode(env: Option<&str>, configured: ApiMode) -> bool {
+    match env.map(str::trim).filter(|v| !v.is_empty()) {
+        Some(value) => value.eq_ignore_ascii_case("native"),
+        None => configured == ApiMode::Native,
+    }
+}
+
+/// REST root from `GG_API_URL`, used instead of the one derived from the
+/// stack's remote (e.g. for an instance behind a proxy).
+pub fn api_url_override() -> Option<String> {
+    env_token(&["GG_API_URL"]).map(|url| url.trim_end_matches('/').to_string())
+}
+
+/// URL of the remote PRs/MRs live behind ([`git::provider_remote`]).
+pub fn remote_url() -> Result<String> {
+    git::provider_remote_url(&git::open_repo()?)
+}
+
+/// `(host, path)` of the stack's remote when it is not `origin`, for pointing
+/// gh/glab at it (they pick `origin` on their own).
+pub fn remote_override() -> Option<(String, String)> {
+    if git::provider_remote() == git::DEFAULT_REMOTE {
+        return None;
+    }
+    remote_url().ok().and_then(|url| split_remote(&url))
+}
+
+/// Split a remote URL into its host and repository path
+/// (`git@host:group/sub/repo.git` → `("host", "group/sub/repo")`).
+pub fn split_remote(url: &str) -> Option<(String, String)> {
+    let url = url.trim().trim_end_matches('/');
+    let host = git::remote_url_host(url)?.to_string();
+    let path = if let Some((_, rest)) = url.split_once("://") {
+        rest.split_once('/')?.1
+    } else {
+        url.split_once(':')?.1
+    };
+    let path = path.trim_start_matches('/').trim_end_matches(".git");
+    if !path.contains('/') {
+        return None;
+    }
+    Some((host, path.to_string()))
+}
+
+/// Percent-encode a path segment (branch names, GitLab project paths).
+pub fn encode(segment: &str) -> String {
+    let mut out = String::with_capacity(segment.len());
+    for byte in segment.bytes() {
+        match byte {
+            b'A'..=b'Z' | b'a'..=b'z' | b'0'..=b'9' | b'-' | b'_' | b'.' | b'~' => {
+                out.push(byte as char)
+            }
+            _ => out.push_str(&format!("%{:02X}", byte)),
+        }
+    }
+    out
+}
+
+/// Run `command args...` and return its trimmed stdout, if it succeeds and
+/// prints something. Used to borrow a token from an installed CLI.
+pub fn command_output(command: &str, args: &[&str]) -> Option<String> {
+    let output = std::process::Command::new(command)
+        .args(args)
+        .output()
+        .ok()?;
+    let stdout = String::from_utf8_lossy(&output.stdout).trim().to_string();
+    (output.status.success() && !stdout.is_empty()).then_some(stdout)
+}
+
+/// First non-empty value among the environment variables `vars`.
+pub fn env_token(vars: &[&str]) -> Option<String> {
+    vars.iter()
+        .filter_map(|var| std::env::var(var).ok())
+        .map(|value| value.trim().to_string())
+        .find(|value| !value.is_empty())
+}
+
+/// Send a JSON request and return the response body (`Value::Null` when
+/// empty).
+pub fn request(
+    method: &str,
+    url: &str,
+    headers: &[(&str, &str)],
+    body: Option<Value>,
+) -> std::result::Result<Value, HttpError> {
+    let req = build_request(method, url, headers);
+    let response = match body {
+        Some(body) => req
+            .set("Content-Type", "application/json")
+            .send_string(&body.to_string()),
+        None => req.call(),
+    };
+    read_response(response)
+}
+
+/// POST `content` as the file `file_name` of multipart form field `field`
+/// and return the response body.
+pub fn upload(
+    url: &str,
+    headers: &[(&str, &str)],
+    field: &str,
+    file_name: &str,
+    content: &[u8],
+) -> std::result::Result<Value, HttpError> {
+    let boundary = format!(
+        "gg-upload-{}",
+        std::time::SystemTime::now()
+            .duration_since(std::time::UNIX_EPOCH)
+            .map_or(0, |d| d.as_nanos())
+    );
+    let mut body = format!(
+        "--{}\r\nContent-Disposition: form-data; name=\"{}\"; filename=\"{}\"\r\n\
+         Content-Type: application/octet-stream\r\n\r\n",
+        boundary,
+        field,
+        file_name.replace('"', "")
+    )
+    .into_bytes();
+    body.extend_from_slice(content);
+    body.extend_from_slice(format!("\r\n--{}--\r\n", boundary).as_bytes());
+
+    let response = build_request("POST", url, headers)
+        .set(
+            "Content-Type",
+            &format!("multipart/form-data; boundary={}", boundary),
+        )
+        .send_bytes(&body);
+    read_response(response)
+}
+
+fn build_request(method: &str, url: &str, headers: &[(&str, &str)]) -> ureq::Request {
+    let mut req = ureq::request(method, url)
+        .set("Accept", "application/json")
+        .set("User-Agent", concat!("git-gud/", env!("CARGO_PKG_VERSION")));
+    for (name, value) in headers {
+        req = req.set(name, value);
+    }
+    req
+}
+
+/// The JSON body of `response` (`Value::Null` when empty), or why it failed.
+fn read_response(
+    response: std::result::Result<ureq::Response, ureq::Error>,
+) -> std::result::Result<Value, HttpError> {
+    match response {
+        Ok(response) => {
+            let text = response
+                .into_string()
+                .map_err(|e| HttpError::Transport(e.to_string()))?;
+            if text.trim().is_empty() {
+                return Ok(Value::Null);
+            }
+            serde_json::from_str(&text).map_err(|e| HttpError::Parse(e.to_string()))
+        }
+        Err(ureq::Error::Status(code, response)) => {
+            let text = response.into_string().unwrap_or_default();
+            Err(HttpError::Status(code, error_message(&text)))
+        }
+        Err(ureq::Error::Transport(e)) => Err(HttpError::Transport(e.to_string())),
+    }
+}
+
+/// The `message` (or `error`) of a JSON error body, else the body itself.
+fn error_message(body: &str) -> String {
+    let message = serde_json::from_str::<Value>(body).ok().and_then(|v| {
+        match v.get("message").or_else(|| v.get("error")) {
+            Some(Value::String(s)) => Some(s.clone()),
+            Some(other) if !other.is_null() => Some(other.to_string()),
+            _ => None,
+        }
+    });
+    message.unwrap_or_else(|| body.trim().to_string())
+}
+
+impl HttpError {
+    /// Convert into a `GgError`, wrapping server errors with `wrap` and
+    /// mapping 401 to `unauthorized`.
+    pub fn into_gg(
+        self,
+        context: &str,
+        wrap: fn(String) -> GgError,
+        unauthorized: fn() -> GgError,
+    ) -> GgError {
+        match self {
+            HttpError::Status(401, _) => unauthorized(),
+            HttpError::Status(code, message) => {
+                wrap(format!("{} (HTTP {}): {}", context, code, message))
+            }
+            HttpError::Transport(message) => {
+                GgError::NetworkError(format!("{}: {}", context, message))
+            }
+            HttpError::Parse(message) => {
+                wrap(format!("{}: invalid response: {}", context, message))
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn env_overrides_configured_mode() {
+        assert!(!resolve_mode(None, ApiMode::Cli));
+        assert!(resolve_mode(None, ApiMode::Native));
+        assert!(resolve_mode(Some("NATIVE"), ApiMode::Cli));
+        assert!(!resolve_mode(Some("cli"), ApiMode::Native));
+        assert!(resolve_mode(Some(" "), ApiMode::Native));
+    }
+
+    #[test]
+    fn splits_remote_urls() {
+        assert_eq!(
+            split_remote("git@gitlab.com:group/sub/repo.git"),
+            Some(("gitlab.com".to_string(), "group/sub/repo".to_string()))
+        );
+        assert_eq!(
+            split_remote("https://github.com/owner/repo"),
+            Some(("github.com".to_string(), "owner/repo".to_string()))
+        );
+        assert_eq!(
+            split_remote("ssh://git@ghe.example.com:2222/owner/repo.git"),
+            Some(("ghe.example.com".to_string(), "owner/repo".to_string()))
+        );
+        assert_eq!(split_remote("https://github.com/repo"), None);
+    }
+
+    #[test]
+    fn encodes_path_segments() {
+        assert_eq!(encode("group/sub repo"), "group%2Fsub%20repo");
+        assert_eq!(encode("nacho/stack--c-ab12"), "nacho%2Fstack--c-ab12");
+    }
+
+    #[test]
+    fn extracts_error_messages() {
+        assert_eq!(
+            error_message(r#"{"message": "Not Found"}"#),
+            "Not Found".to_string()
+        );
+        assert_eq!(
+            error_message(r#"{"message": ["is invalid"]}"#),
+            r#"["is invalid"]"#.to_string()
+        );
+        assert_eq!(error_message(" oops "), "oops".to_string());
+    }
+}
@@ -0,0 +1,360 @@
+//! Branch naming templates (`defaults.branch_template` /
+//! `defaults.entry_branch_template`).
+//!
+//! Stack branches default to `{user}/{stack}` and the per-entry branches
+//! pushed for PRs/MRs to `{user}/{stack}--{id}`. Teams whose branch-name
+//! policy forbids `--` or requires a prefix such as `feature/` set their own
+//! templates. Like plain mode, the naming is a process-wide setting chosen
+//! once at startup from the config; the `git::format_*` / `git::parse_*`
+//! helpers read it through [`current`].
+
+use std::sync::{Arc, OnceLock, RwLock};
+
+use regex::Regex;
+
+use crate::config::Config;
+
+/// Default stack branch template.
+pub const DEFAULT_STACK_TEMPLATE: &str = "{user}/{stack}";
+/// Default entry branch template.
+pub const DEFAULT_ENTRY_TEMPLATE: &str = "{user}/{stack}--{id}";
+
+const PLACEHOLDERS: [&str; 3] = ["user", "stack", "id"];
+
+/// One piece of a parsed template.
+#[derive(Debug, Clone, PartialEq, Eq)]
+enum Part {
+    Literal(String),
+    Placeholder(&'static str),
+}
+
+/// A parsed branch template.
+#[derive(Debug, Clone)]
+struct Template {
+    parts: Vec<Part>,
+    regex: Regex,
+}
+
+impl Template {
+    fn parse(template: &str, required: &[&'static str]) -> Result<Self, String> {
+        let mut parts = Vec::new();
+        let mut rest = template;
+        while !rest.is_empty() {
+            let Some(open) = rest.find('{') else {
+                parts.push(Part::Literal(rest.to_string()));
+                break;
+            };
+            if open > 0 {
+                parts.push(Part::Literal(rest[..open].to_string()));
+            }
+            let close = rest[open..]
+                .find('}')
+                .map(|i| open + i)
+                .ok_or_else(|| format!("unclosed `{{` in '{}'", template))?;
+            let name = &rest[open + 1..close];
+            let placeholder = PLACEHOLDERS
+                .into_iter()
+                .find(|p| *p == name)
+                .filter(|p| required.contains(p))
+                .ok_or_else(|| {
+                    format!(
+                        "unknown placeholder `{{{}}}` in '{}' (expected {})",
+                        name,
+                        template,
+                        list(required)
+                    )
+                })?;
+            if parts.contains(&Part::Placeholder(placeholder)) {
+                return Err(format!(
+                    "`{{{}}}` appears more than once in '{}'",
+                    placeholder, template
+                ));
+            }
+            if matches!(parts.last(), Some(Part::Placeholder(_))) {
+                return Err(format!(
+                    "placeholders in '{}' must be separated by literal text",
+                    template
+                ));
+            }
+            parts.push(Part::Placeholder(placeholder));
+            rest = &rest[close + 1..];
+        }
+
+        if let Some(missing) = required
+            .iter()
+            .find(|p| !parts.contains(&Part::Placeholder(p)))
+        {
+            return Err(format!("'{}' must contain `{{{}}}`", template, missing));
+        }
+
+        let mut pattern = String::from("^");
+        for part in &parts {
+            match part {
+                Part::Literal(text) => pattern.push_str(&regex::escape(text)),
+                // Stack names are the only free-form part: let them take
+                // as much as they can (`a-` + `--c-1a2b3c4`)
+                Part::Placeholder("stack") => pattern.push_str("(?P<stack>[^/]+)"),
+                Part::Placeholder(name) => pattern.push_str(&format!("(?P<{}>[^/]+?)", name)),
+            }
+        }
+        pattern.push('$');
+        let regex = Regex::new(&pattern).map_err(|e| e.to_string())?;
+        Ok(Self { parts, regex })
+    }
+
+    fn render(&self, user: &str, stack: &str, id: &str) -> String {
+        self.parts
+            .iter()
+            .map(|part| match part {
+                Part::Literal(text) => text.as_str(),
+                Part::Placeholder("user") => user,
+                Part::Placeholder("stack") => stack,
+                Part::Placeholder(_) => id,
+            })
+            .collect()
+    }
+
+    fn captures<'a>(&self, branch: &'a str) -> Option<regex::Captures<'a>> {
+        self.regex.captures(branch)
+    }
+}
+
+fn list(placeholders: &[&str]) -> String {
+    placeholders
+        .iter()
+        .map(|p| format!("`{{{}}}`", p))
+        .collect::<Vec<_>>()
+        .join(", ")
+}
+
+/// Stack and entry branch templates.
+#[derive(Debug, Clone)]
+pub struct BranchNaming {
+    stack: Template,
+    entry: Template,
+}
+
+impl Default for BranchNaming {
+    fn default() -> Self {
+        Self::new(DEFAULT_STACK_TEMPLATE, DEFAULT_ENTRY_TEMPLATE)
+            .expect("default branch templates are valid")
+    }
+}
+
+impl BranchNaming {
+    /// Parse and validate a pair of templates.
+    ///
+    /// The stack template needs `{user}` and `{stack}`, the entry template
+    /// also `{id}`. Rendered names must be valid branch names, stack
+    /// branches must not read as entry branches, and entry branches cannot
+    /// live under their stack branch.
+    pub fn new(stack_template: &str, entry_template: &str) -> Result<Self, String> {
+        let stack = Template::parse(stack_template, &["user", "stack"])
+            .map_err(|e| format!("defaults.branch_template: {}", e))?;
+        let entry = Template::parse(entry_template, &["user", "stack", "id"])
+            .map_err(|e| format!("defaults.entry_branch_template: {}", e))?;
+        let naming = Self { stack, entry };
+
+        let stack_sample = naming.format_stack("user", "my-stack");
+        let entry_sample = naming.format_entry("user", "my-stack", "c-1a2b3c4");
+        for (key, sample) in [
+            ("branch_template", &stack_sample),
+            ("entry_branch_template", &entry_sample),
+        ] {
+            if !git2::Reference::is_valid_name(&format!("refs/heads/{}", sample)) {
+                return Err(format!(
+                    "defaults.{}: '{}' is not a valid branch name",
+                    key, sample
+                ));
+            }
+        }
+        if naming.entry.captures(&stack_sample).is_some() {
+            return Err(format!(
+                "defaults.branch_template and defaults.entry_branch_template are ambiguous: \
+                 stack branch '{}' reads as an entry branch",
+                stack_sample
+            ));
+        }
+        // Git cannot hold both `a/b` and `a/b/c` as branches
+        if entry_sample.starts_with(&format!("{}/", stack_sample)) {
+            return Err(format!(
+                "defaults.entry_branch_template: entry branches cannot live under the stack \
+                 branch ('{}' is inside '{}')",
+                entry_sample, stack_sample
+            ));
+        }
+        Ok(naming)
+    }
+
+    /// Templates from `config`, falling back to the defaults.
+    pub fn from_config(config: &Config) -> Result<Self, String> {
+        Self::new(
+            config
+                .defaults
+                .branch_template
+                .as_deref()
+                .unwrap_or(DEFAULT_STACK_TEMPLATE),
+            config
+                .defaults
+                .entry_branch_template
+                .as_deref()
+                .unwrap_or(DEFAULT_ENTRY_TEMPLATE),
+        )
+    }
+
+    /// Stack branch name for `user`'s `stack`.
+    pub fn format_stack(&self, user: &str, stack: &str) -> String {
+        self.stack.render(user, stack, "")
+    }
+
+    /// Entry branch name for entry `id` of `user`'s `stack`.
+    pub fn format_entry(&self, user: &str, stack: &str, id: &str) -> String {
+        self.entry.render(user, stack, id)
+    }
+
+    /// `(user, stack)` of a stack branch. Entry branches are not stack
+    /// branches, even when the stack template would match them.
+    pub fn parse_stack(&self, branch: &str) -> Option<(String, String)> {
+        if self.entry.captures(branch).is_some() {
+            return None;
+        }
+        let caps = self.stack.captures(branch)?;
+        Some((caps["user"].to_string(), caps["stack"].to_string()))
+    }
+
+    /// `(user, stack, id)` of an entry branch.
+    pub fn parse_entry(&self, branch: &str) -> Option<(String, String, String)> {
+        let caps = self.entry.captures(branch)?;
+        Some((
+            caps["user"].to_string(),
+            caps["stack"].to_string(),
+            caps["id"].to_string(),
+        ))
+    }
+
+    /// Why `stack` cannot be used as a stack name under these templates, if
+    /// it cannot: its branches would not read back as the same stack.
+    pub fn check_stack_name(&self, stack: &str) -> Option<String> {
+        let branch = self.format_stack("user", stack);
+        if self.parse_stack(&branch) != Some(("user".to_string(), stack.to_string())) {
+            return Some(format!(
+                "its branch '{}' would be read as an entry branch",
+                branch
+            ));
+        }
+        let entry = self.format_entry("user", stack, "c-1a2b3c4");
+        if self.parse_entry(&entry)
+            != Some((
+                "user".to_string(),
+                stack.to_string(),
+                "c-1a2b3c4".to_string(),
+            ))
+        {
+            return Some(format!(
+                "its entry branches (e.g. '{}') would not read back as the same stack",
+                entry
+            ));
+        }
+        None
+    }
+}
+
+fn naming() -> &'static RwLock<Arc<BranchNaming>> {
+    static NAMING: OnceLock<RwLock<Arc<BranchNaming>>> = OnceLock::new();
+    NAMING.get_or_init(|| RwLock::new(Arc::new(BranchNaming::default())))
+}
+
+/// Use `naming` for every branch gg formats or parses from now on.
+pub fn set(naming_: BranchNaming) {
+    *naming().write().unwrap_or_else(|e| e.into_inner()) = Arc::new(naming_);
+}
+
+/// The naming in effect (the defaults until [`set`] is called).
+pub fn current() -> Arc<BranchNaming> {
+    naming().read().unwrap_or_else(|e| e.into_inner()).clone()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn custom_templates_round_trip() {
+        let naming = BranchNaming::new("feature/{user}/{stack}", "feature/{user}/{stack}/{id}");
+        // Entry branches inside the stack branch's directory cannot coexist
+        assert!(naming.unwrap_err().contains("cannot live under"));
+
+        let naming =
+            BranchNaming::new("feature/{user}/{stack}", "review/{user}/{stack}/{id}").unwrap();
+        assert_eq!(naming.format_stack("nacho", "auth"), "feature/nacho/auth");
+        assert_eq!(
+            naming.format_entry("nacho", "auth", "c-abc1234"),
+            "review/nacho/auth/c-abc1234"
+        );
+        assert_eq!(
+            naming.parse_stack("feature/nacho/auth"),
+            Some(("nacho".to_string(), "auth".to_string()))
+        );
+        assert_eq!(
+            naming.parse_entry("review/nacho/auth/c-abc1234"),
+            Some((
+                "nacho".to_string(),
+                "auth".to_string(),
+                "c-abc1234".to_string()
+            ))
+        );
+        assert_eq!(naming.parse_stack("nacho/auth"), None);
+        assert_eq!(naming.parse_entry("feature/nacho/auth"), None);
+        // `--` is fine in stack names once entry branches do not use it
+        assert_eq!(naming.check_stack_name("my--feature"), None);
+    }
+
+    #[test]
+    fn entry_suffix_template_rejects_ambiguous_stack_names() {
+        let naming = BranchNaming::new("{user}/{stack}", "{user}/{stack}_{id}").unwrap();
+        assert_eq!(
+            naming.parse_entry("nacho/auth_c-abc1234"),
+            Some((
+                "nacho".to_string(),
+                "auth".to_string(),
+                "c-abc1234".to_string()
+            ))
+        );
+        assert!(naming.check_stack_name("my_feature").is_some());
+        assert_eq!(naming.check_stack_name("my-feature"), None);
+    }
+
+    #[test]
+    fn invalid_templates_are_rejected() {
+        let cases = [
+            ("{user}/{stack}", "{user}/{stack}-{id}", "ambiguous"),
+            ("{stack}", DEFAULT_ENTRY_TEMPLATE, "must contain `{user}`"),
+            (
+                "{user}/{stack}/{id}",
+                DEFAULT_ENTRY_TEMPLATE,
+                "unknown placeholder",
+            ),
+            ("{user}/{stack}", "{user}/{stack}", "must contain `{id}`"),
+            (
+                "{user}/{stack}",
+                "{user}/{stack}{id}",
+                "separated by literal",
+            ),
+            (
+                "{user}/{stack}",
+                "{user}/{stack}/{id}/{id}",
+                "more than once",
+            ),
+            ("{user}/{stack", DEFAULT_ENTRY_TEMPLATE, "unclosed"),
+            (
+                "{user}..{stack}",
+                DEFAULT_ENTRY_TEMPLATE,
+                "not a valid branch name",
+            ),
+        ];
+        for (stack, entry, error) in cases {
+            let err = BranchNaming::new(stack, entry).unwrap_err();
+            assert!(err.contains(error), "{} / {}: {}", stack, entry, err);
+        }
+    }
+}
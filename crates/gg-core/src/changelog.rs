@@ -0,0 +1,364 @@
+//! Changelog entries for landed PRs/MRs (`defaults.changelog`).
+//!
+//! `gg land` renders one entry per PR/MR it merges from `template`. Besides
+//! `{title}`, `{number}`, `{ref}` (`#12` or `!12`), `{url}` and `{stack}`,
+//! templates get `{type}`, `{scope}`, `{breaking}` and `{description}` from
+//! the commit subject (see [`crate::commit_messages`]); `{type}` is `misc`
+//! when the subject has none. Depending on `mode`, the entries are only
+//! reported (`output`), or committed to the base branch as towncrier-style
+//! fragment files (`fragments`) or under a heading of a changelog file
+//! (`file`).
+//!
+//! The commit is made on top of the remote base branch without touching the
+//! working tree, and pushed without force: a protected or moving base
+//! branch only costs a warning.
+
+use git2::{Index, IndexEntry, IndexTime, Oid, Repository};
+
+use crate::commit_messages::{Policy, Subject};
+use crate::config::{ChangelogDefaults, ChangelogMode, Config};
+use crate::error::{GgError, Result};
+use crate::git;
+use crate::output::ChangelogEntryJson;
+use crate::signing;
+
+const DEFAULT_TEMPLATE: &str = "{description} ({ref})";
+const DEFAULT_FRAGMENT: &str = "{number}.{type}.md";
+const DEFAULT_FRAGMENT_DIR: &str = "changelog.d";
+const DEFAULT_FILE: &str = "CHANGELOG.md";
+const DEFAULT_SECTION: &str = "## Unreleased";
+
+/// A PR/MR `gg land` merged.
+#[derive(Debug, Clone)]
+pub struct MergedPr {
+    pub number: u64,
+    pub title: String,
+    /// Full message of the entry's commit
+    pub message: String,
+    pub url: String,
+}
+
+/// The changelog entries for `merged`, with the path each one goes to in
+/// `fragments` and `file` mode. Empty when the changelog is off.
+pub fn render(
+    config: &Config,
+    stack: &str,
+    ref_prefix: &str,
+    merged: &[MergedPr],
+) -> Result<Vec<ChangelogEntryJson>> {
+    let changelog = &config.defaults.changelog;
+    if changelog.mode == ChangelogMode::Off {
+        return Ok(Vec::new());
+    }
+    let policy = Policy::from_config(&config.defaults.commit_messages)?;
+    let template = changelog.template.as_deref().unwrap_or(DEFAULT_TEMPLATE);
+
+    Ok(merged
+        .iter()
+        .map(|pr| {
+            let subject = policy.parse(&pr.message).unwrap_or_default();
+            let fill = |template: &str| fill(template, pr, &subject, stack, ref_prefix);
+            let path = match changelog.mode {
+                ChangelogMode::Fragments => Some(format!(
+                    "{}/{}",
+                    changelog
+                        .path
+                        .as_deref()
+                        .unwrap_or(DEFAULT_FRAGMENT_DIR)
+                        .trim_end_matches('/'),
+                    fill(changelog.fragment.as_deref().unwrap_or(DEFAULT_FRAGMENT))
+                )),
+                ChangelogMode::File => Some(
+                    changelog
+                        .path
+                        .as_deref()
+                        .unwrap_or(DEFAULT_FILE)
+                        .to_string(),
+                ),
+                ChangelogMode::Off | ChangelogMode::Output => None,
+            };
+            ChangelogEntryJson {
+                pr_number: pr.number,
+                title: pr.title.clone(),
+                text: fill(template).trim().to_string(),
+                path,
+            }
+        })
+        .collect())
+}
+
+fn fill(template: &str, pr: &MergedPr, subject: &Subject, stack: &str, ref_prefix: &str) -> String {
+    template
+        .replace("{title}", &pr.title)
+        .replace(
+            "{description}",
+            subject.description.as_deref().unwrap_or(&pr.title),
+        )
+        .replace("{type}", subject.kind.as_deref().unwrap_or("misc"))
+        .replace("{scope}", subject.scope.as_deref().unwrap_or(""))
+        .replace("{breaking}", if subject.breaking { "!" } else { "" })
+        .replace("{number}", &pr.number.to_string())
+        .replace("{ref}", &format!("{}{}", ref_prefix, pr.number))
+        .replace("{url}", &pr.url)
+        .replace("{stack}", stack)
+}
+
+/// Commit `entries` on top of `base` on `remote` and push the commit there.
+/// Returns the new commit.
+pub fn commit_to_base(
+    repo: &Repository,
+    config: &ChangelogDefaults,
+    remote: &str,
+    base: &str,
+    ref_prefix: &str,
+    entries: &[ChangelogEntryJson],
+) -> Result<Oid> {
+    let git_dir = repo.path().to_string_lossy().into_owned();
+    git::run_git_command(&["--git-dir", &git_dir, "fetch", remote, base])?;
+    let parent = repo
+        .revparse_single(&format!("refs/remotes/{}/{}", remote, base))
+        .and_then(|object| object.peel_to_commit())
+        .map_err(|e| GgError::Other(format!("Could not find {}/{}: {}", remote, base, e)))?;
+    let parent_tree = parent.tree()?;
+
+    let mut files: Vec<(String, String)> = Vec::new();
+    for entry in entries {
+        let Some(path) = &entry.path else { continue };
+        if config.mode == ChangelogMode::Fragments {
+            files.push((path.clone(), format!("{}\n", entry.text)));
+        } else if !files.iter().any(|(p, _)| p == path) {
+            let current = parent_tree
+                .get_path(std::path::Path::new(path))
+                .ok()
+                .and_then(|tree_entry| repo.find_blob(tree_entry.id()).ok())
+                .map(|blob| String::from_utf8_lossy(blob.content()).into_owned())
+                .unwrap_or_default();
+            let items: Vec<String> = entries
+                .iter()
+                .filter(|e| e.path.as_ref() == Some(path))
+                .map(|e| format!("- {}", e.text))
+                .collect();
+            let section = config.section.as_deref().unwrap_or(DEFAULT_SECTION);
+            files.push((
+                path.clone(),
+                insert_entries(&current, section, &items.join("\n")),
+            ));
+        }
+    }
+
+    let mut index = Index::new()?;
+    index.read_tree(&parent_tree)?;
+    for (path, content) in &files {
+        index.add(&IndexEntry {
+            ctime: IndexTime::new(0, 0),
+            mtime: IndexTime::new(0, 0),
+            dev: 0,
+            ino: 0,
+            mode: 0o100644,
+            uid: 0,
+            gid: 0,
+            file_size: content.len() as u32,
+            id: repo.blob(content.as_bytes())?,
+            flags: 0,
+            flags_extended: 0,
+            path: path.as_bytes().to_vec(),
+        })?;
+    }
+    let tree = repo.find_tree(index.write_tree_to(repo)?)?;
+
+    let refs: Vec<String> = entries
+        .iter()
+        .map(|e| format!("{}{}", ref_prefix, e.pr_number))
+        .collect();
+    let message = format!("Add changelog entries for {}", refs.join(", "));
+    let signature = git::get_signature(repo)?;
+    let oid = signing::create_commit(repo, &signature, &signature, &message, &tree, &[&parent])?;
+
+    git::run_git_command(&[
+        "--git-dir",
+        &git_dir,
+        "push",
+        remote,
+        &format!("{}:refs/heads/{}", oid, base),
+    ])?;
+    Ok(oid)
+}
+
+/// `content` with `items` added at the top of `section`. A missing section
+/// is created below the file's `# ` title, or at the top of the file.
+fn insert_entries(content: &str, section: &str, items: &str) -> String {
+    let lines: Vec<&str> = content.lines().collect();
+    let skip_blank = |mut at: usize| {
+        while at < lines.len() && lines[at].trim().is_empty() {
+            at += 1;
+        }
+        at
+    };
+
+    let (head, rest) = match lines.iter().position(|l| l.trim_end() == section) {
+        Some(at) => (
+            lines[..=at].join("\n"),
+            lines[skip_blank(at + 1)..].to_vec(),
+        ),
+        None => {
+            let title = lines.first().filter(|l| l.starts_with("# "));
+            let head = match title {
+                Some(title) => format!("{}\n\n{}", title, section),
+                None => section.to_string(),
+            };
+            // The rest of the file starts a new section
+            let rest = lines[skip_blank(usize::from(title.is_some()))..].to_vec();
+            let mut out = format!("{}\n\n{}\n", head, items);
+            if !rest.is_empty() {
+                out.push('\n');
+                out.push_str(&rest.join("\n"));
+                out.push('\n');
+            }
+            return out;
+        }
+    };
+
+    let mut out = format!("{}\n\n{}\n", head, items);
+    if let Some(first) = rest.first() {
+        // Keep the new items in the same list as the section's current ones
+        if !(first.starts_with("- ") || first.starts_with("* ")) {
+            out.push('\n');
+        }
+        out.push_str(&rest.join("\n"));
+        out.push('\n');
+    }
+    out
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn merged(title: &str) -> MergedPr {
+        MergedPr {
+            number: 12,
+            title: title.to_string(),
+            message: format!("{}\n\nGG-ID: c-1234567", title),
+            url: "https://github.com/acme/app/pull/12".to_string(),
+        }
+    }
+
+    #[test]
+    fn renders_entries_from_the_commit_subject() {
+        let mut config = Config::default();
+        config.defaults.changelog.mode = ChangelogMode::Fragments;
+        let entries = render(
+            &config,
+            "refunds",
+            "#",
+            &[merged("feat(api): Retry refunds"), merged("Fix typo")],
+        )
+        .unwrap();
+        assert_eq!(entries[0].text, "Retry refunds (#12)");
+        assert_eq!(entries[0].path.as_deref(), Some("changelog.d/12.feat.md"));
+        assert_eq!(entries[1].text, "Fix typo (#12)");
+        assert_eq!(entries[1].path.as_deref(), Some("changelog.d/12.misc.md"));
+
+        config.defaults.changelog.mode = ChangelogMode::Output;
+        config.defaults.changelog.template = Some("[{title}]({url}) in {stack}".to_string());
+        let entries = render(&config, "refunds", "#", &[merged("Fix typo")]).unwrap();
+        assert_eq!(
+            entries[0].text,
+            "[Fix typo](https://github.com/acme/app/pull/12) in refunds"
+        );
+        assert_eq!(entries[0].path, None);
+
+        config.defaults.changelog.mode = ChangelogMode::Off;
+        assert!(render(&config, "refunds", "#", &[merged("Fix typo")])
+            .unwrap()
+            .is_empty());
+    }
+
+    #[test]
+    fn commit_to_base_pushes_fragments() {
+        let dir = tempfile::tempdir().unwrap();
+        let remote_path = dir.path().join("remote.git");
+        Repository::init_bare(&remote_path).unwrap();
+        let repo = Repository::init(dir.path().join("work")).unwrap();
+        let signature = git2::Signature::now("Test", "test@example.com").unwrap();
+        let tree = repo
+            .find_tree(repo.index().unwrap().write_tree().unwrap())
+            .unwrap();
+        let root = repo
+            .commit(None, &signature, &signature, "Initial", &tree, &[])
+            .unwrap();
+        repo.remote("origin", remote_path.to_str().unwrap())
+            .unwrap();
+        git::run_git_command(&[
+            "--git-dir",
+            repo.path().to_str().unwrap(),
+            "push",
+            "origin",
+            &format!("{}:refs/heads/main", root),
+        ])
+        .unwrap();
+        let mut config = repo.config().unwrap();
+        config.set_str("user.name", "Test").unwrap();
+        config.set_str("user.email", "test@example.com").unwrap();
+
+        let mut gg_config = Config::default();
+        gg_config.defaults.changelog.mode = ChangelogMode::Fragments;
+        let entries = render(&gg_config, "refunds", "#", &[merged("fix: Retry refunds")]).unwrap();
+        let oid = commit_to_base(
+            &repo,
+            &gg_config.defaults.changelog,
+            "origin",
+            "main",
+            "#",
+            &entries,
+        )
+        .unwrap();
+
+        let remote = Repository::open_bare(&remote_path).unwrap();
+        let tip = remote
+            .find_reference("refs/heads/main")
+            .unwrap()
+            .peel_to_commit()
+            .unwrap();
+        assert_eq!(tip.id(), oid);
+        assert_eq!(tip.parent_id(0).unwrap(), root);
+        assert_eq!(
+            tip.summary().unwrap(),
+            Some("Add changelog entries for #12")
+        );
+        let blob = tip
+            .tree()
+            .unwrap()
+            .get_path(std::path::Path::new("changelog.d/12.fix.md"))
+            .unwrap()
+            .to_object(&remote)
+            .unwrap()
+            .peel_to_blob()
+            .unwrap();
+        assert_eq!(blob.content(), b"Retry refunds (#12)\n");
+    }
+
+    #[test]
+    fn insert_entries_adds_to_the_section() {
+        assert_eq!(
+            insert_entries(
+                "# Changelog\n\n## Unreleased\n\n- Older\n\n## 1.0.0\n\n- First\n",
+                "## Unreleased",
+                "- Newer"
+            ),
+            "# Changelog\n\n## Unreleased\n\n- Newer\n- Older\n\n## 1.0.0\n\n- First\n"
+        );
+        assert_eq!(
+            insert_entries(
+                "# Changelog\n\n## 1.0.0\n\n- First\n",
+                "## Unreleased",
+                "- New"
+            ),
+            "# Changelog\n\n## Unreleased\n\n- New\n\n## 1.0.0\n\n- First\n"
+        );
+        assert_eq!(
+            insert_entries("", "## Unreleased", "- New"),
+            "## Unreleased\n\n- New\n"
+        );
+    }
+}
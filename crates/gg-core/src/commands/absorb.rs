@@ -2,18 +2,30 @@
 //!
 //! Uses the git-absorb library to automatically determine which commits
 //! staged changes should be absorbed into, then creates fixup commits
-//! and optionally rebases them.
+//! and optionally rebases them. `-i` and `--json` use gg's own
+//! hunk attribution (see [`super::absorb_plan`]) instead.
 
 use std::ffi::OsString;
+use std::process::Command;
 
-use console::style;
+use console::{style, Term};
+use dialoguer::{Input, Select};
 use slog::{o, Drain, Logger};
 
-use crate::config::Config;
+use super::absorb_plan::{self, AbsorbPlan, Attribution, CreatedFixup, PlannedHunk};
+use crate::config::{Config, UnstagedAction};
 use crate::error::{GgError, Result};
 use crate::git;
+use crate::hooks::{self, GitRewrite};
 use crate::immutability::{self, ImmutabilityPolicy};
-use crate::operations::{OperationKind, SnapshotScope};
+use crate::operations::{self, OperationKind, SnapshotScope};
+use crate::output::{
+    self, AbsorbFixupJson, AbsorbHunkJson, AbsorbResponse, AbsorbResultJson, AbsorbTargetJson,
+    OUTPUT_VERSION,
+};
+use crate::plain;
+use crate::replay;
+use crate::scope;
 use crate::stack::Stack;
 
 /// Options for the absorb command
@@ -34,18 +46,42 @@ pub struct AbsorbOptions {
     /// Override the immutability check (any stack commit being flagged as
     /// merged or base-ancestor would otherwise abort the operation).
     pub force: bool,
+    /// Review each hunk and its target before creating fixup commits
+    pub interactive: bool,
+    /// Print the hunk-to-commit mapping (and the fixups created) as JSON.
+    /// Without `dry_run`, absorbs every attributed hunk non-interactively.
+    pub json: bool,
+    /// Amend every staged change into this entry (position, short SHA or
+    /// GG-ID) instead of attributing hunks
+    pub into: Option<String>,
+    /// Stage unstaged modifications of tracked files before absorbing
+    pub all: bool,
+    /// With `all`, stage untracked files too
+    pub include_untracked: bool,
 }
 
 /// Run the absorb command
 pub fn run(options: AbsorbOptions) -> Result<()> {
+    if options.interactive && !atty::is(atty::Stream::Stdin) {
+        return Err(GgError::Other(
+            "`gg absorb -i` needs an interactive terminal. Use `gg absorb --dry-run --json` to inspect the mapping instead."
+                .to_string(),
+        ));
+    }
+
     let repo = git::open_repo()?;
-    let gg_config = Config::load_with_global(repo.commondir())?;
+    let gg_config = Config::load(repo.commondir())?;
 
     // Acquire the operation lock for validation, but defer writing the
     // op-log record until after the immutability guard passes so refused
     // operations never pollute `gg undo --list` (design §4.6).
     // NOTE: behaviour change — absorb previously had no lock.
-    let _lock = git::acquire_operation_lock(&repo, "absorb")?;
+    let lock = git::acquire_operation_lock(&repo, "absorb")?;
+
+    // Dry-run only reports; it never stages anything nor prompts.
+    if !options.dry_run && !include_unstaged(&repo, &gg_config, &options)? {
+        return Ok(());
+    }
 
     // Check if there are staged changes
     let statuses = repo.statuses(None)?;
@@ -59,6 +95,10 @@ pub fn run(options: AbsorbOptions) -> Result<()> {
     });
 
     if !has_staged {
+        if options.json {
+            print_json(&AbsorbPlan::default(), None, options.dry_run, &[], false);
+            return Ok(());
+        }
         // Check for unstaged changes
         let has_unstaged = statuses.iter().any(|s| {
             let status = s.status();
@@ -93,6 +133,27 @@ pub fn run(options: AbsorbOptions) -> Result<()> {
         ));
     }
 
+    // Only absorb changes that belong to this stack's path scope.
+    let outside = scope::out_of_scope(
+        gg_config.get_scope_for_stack(&stack.name),
+        scope::staged_paths(&repo)?,
+    );
+    if !outside.is_empty() {
+        return Err(GgError::Other(format!(
+            "Staged changes are outside the stack scope ({}):\n  {}\n\
+             Unstage them with `git restore --staged <path>` and retry.",
+            gg_config.get_scope_for_stack(&stack.name).join(", "),
+            outside.join("\n  ")
+        )));
+    }
+
+    // `--into` skips attribution entirely: it is `gg amend <target>`, which
+    // takes its own lock and records its own operation.
+    if let Some(target) = options.into.as_deref() {
+        drop(lock);
+        return super::squash::run_into(target, false, options.force);
+    }
+
     // Immutability pre-flight: enumerating exactly which commits git-absorb
     // will target requires re-running its scoring logic. v1 is conservative:
     // if any commit in the stack is immutable, refuse unless --force. Skip
@@ -103,6 +164,11 @@ pub fn run(options: AbsorbOptions) -> Result<()> {
         immutability::guard(report, options.force)?;
     }
 
+    // The rebase rewrites the stack: keep its tip around first
+    if !options.dry_run && options.and_rebase {
+        super::snapshot::backup_stack(&repo, &stack)?;
+    }
+
     // All validation passed and we are about to mutate: write the Pending
     // op-log record now so a failure beyond this point leaves a record the
     // sweep can promote to Interrupted. Dry-run mutates nothing, so skip
@@ -120,6 +186,54 @@ pub fn run(options: AbsorbOptions) -> Result<()> {
         )?)
     };
 
+    if options.json {
+        let plan = absorb_plan::plan(&repo, &stack)?;
+        if options.dry_run {
+            print_json(&plan, Some(&stack), true, &[], false);
+            return Ok(());
+        }
+
+        // Absorb exactly the attributed hunks; ambiguous and unattributed
+        // ones stay staged and are reported as such.
+        let targets: Vec<Option<usize>> = plan
+            .hunks
+            .iter()
+            .map(|hunk| hunk.attribution.target())
+            .collect();
+        let verb = if options.squash { "squash" } else { "fixup" };
+        let fixups = absorb_plan::create_fixups(&repo, &stack, &plan, &targets, verb)?;
+        let rebased = options.and_rebase && !fixups.is_empty();
+        if rebased {
+            let earliest = fixups.iter().map(|f| f.target).min().unwrap_or(1);
+            rebase_autosquash(&repo, &stack, earliest, guard.as_ref().map(|g| g.id()))?;
+        }
+        if let Some(guard) = guard {
+            guard.finalize_with_scope(
+                &repo,
+                &gg_config,
+                SnapshotScope::AllUserBranches,
+                vec![],
+                false,
+            )?;
+        }
+        print_json(&plan, Some(&stack), false, &fixups, rebased);
+        return Ok(());
+    }
+
+    if options.interactive {
+        run_interactive(&repo, &stack, &options, guard.as_ref().map(|g| g.id()))?;
+        if let Some(guard) = guard {
+            guard.finalize_with_scope(
+                &repo,
+                &gg_config,
+                SnapshotScope::AllUserBranches,
+                vec![],
+                false,
+            )?;
+        }
+        return Ok(());
+    }
+
     // Determine the base reference for absorb
     // We want to absorb into commits between base and HEAD
     let base_ref = stack.base.clone();
@@ -144,7 +258,8 @@ pub fn run(options: AbsorbOptions) -> Result<()> {
         force_author: false,
         force_detach: false,
         base: Some(&base_ref),
-        and_rebase: options.and_rebase,
+        // gg folds the fixups itself, in memory when it can
+        and_rebase: false,
         rebase_options: &rebase_options,
         whole_file: options.whole_file,
         one_fixup_per_commit: options.one_fixup_per_commit,
@@ -163,6 +278,9 @@ pub fn run(options: AbsorbOptions) -> Result<()> {
                     style("OK").green().bold()
                 );
             } else {
+                if options.and_rebase {
+                    rebase_autosquash(&repo, &stack, 1, guard.as_ref().map(|g| g.id()))?;
+                }
                 println!("{} Changes absorbed into stack", style("OK").green().bold());
                 if !options.and_rebase {
                     println!(
@@ -194,9 +312,18 @@ pub fn run(options: AbsorbOptions) -> Result<()> {
                 println!("  The staged changes may not match any existing commit hunks.");
                 println!();
                 println!("  Suggestions:");
-                println!("    • Use `gg mv <pos>` to navigate to a commit, then `gg sc` to squash");
-                println!("    • Create a new commit with `git commit`");
-                println!("    • Try `gg absorb --whole-file` to match by file instead of hunk");
+                println!(
+                    "    {} Use `gg mv <pos>` to navigate to a commit, then `gg sc` to squash",
+                    plain::bullet()
+                );
+                println!(
+                    "    {} Create a new commit with `git commit`",
+                    plain::bullet()
+                );
+                println!(
+                    "    {} Try `gg absorb --whole-file` to match by file instead of hunk",
+                    plain::bullet()
+                );
                 Ok(())
             } else if error_msg.contains("uncommitted changes") {
                 Err(GgError::Other(
@@ -222,6 +349,425 @@ pub fn run(options: AbsorbOptions) -> Result<()> {
     Ok(())
 }
 
+/// Print the hunk-to-commit mapping, and the fixup commits created from it.
+fn print_json(
+    plan: &AbsorbPlan,
+    stack: Option<&Stack>,
+    dry_run: bool,
+    fixups: &[CreatedFixup],
+    rebased: bool,
+) {
+    let target_json = |position: usize| {
+        stack
+            .and_then(|s| s.get_entry_by_position(position))
+            .map(|entry| AbsorbTargetJson {
+                position,
+                sha: entry.short_sha.clone(),
+                gg_id: entry.gg_id.clone(),
+                title: entry.title.clone(),
+            })
+    };
+    output::print_json(&AbsorbResponse {
+        version: OUTPUT_VERSION,
+        absorb: AbsorbResultJson {
+            dry_run,
+            hunks: plan
+                .hunks
+                .iter()
+                .map(|hunk| AbsorbHunkJson {
+                    path: plan.path(hunk).to_string(),
+                    old_start: hunk.old_start,
+                    old_lines: hunk.old_lines,
+                    new_start: hunk.new_start,
+                    new_lines: hunk.new_lines,
+                    status: hunk.attribution.status().to_string(),
+                    target: hunk.attribution.target().and_then(target_json),
+                    candidates: hunk.attribution.candidates(),
+                })
+                .collect(),
+            unsupported_paths: plan.unsupported_paths.clone(),
+            fixups: fixups
+                .iter()
+                .filter_map(|fixup| {
+                    Some(AbsorbFixupJson {
+                        sha: fixup.oid.to_string()[..7].to_string(),
+                        target: target_json(fixup.target)?,
+                        hunks: fixup.hunks,
+                    })
+                })
+                .collect(),
+            rebased,
+        },
+    });
+}
+
+/// Bring unstaged work into the absorption pass.
+///
+/// `--all` (and `--include-untracked`) stage it unconditionally. Otherwise,
+/// when tracked files have unstaged modifications, `defaults.unstaged_action`
+/// decides as it does for `gg amend`: `add` behaves like `--all`, `stash` and
+/// `continue` absorb the staged changes only (absorb never touches the
+/// working tree, and `--and-rebase` autostashes it), `abort` refuses, and
+/// `ask` prompts. Returns `false` when the user aborted at the prompt.
+fn include_unstaged(
+    repo: &git2::Repository,
+    config: &Config,
+    options: &AbsorbOptions,
+) -> Result<bool> {
+    let stage = if options.all {
+        true
+    } else {
+        let has_unstaged = repo.statuses(None)?.iter().any(|s| {
+            let status = s.status();
+            status.is_wt_modified()
+                || status.is_wt_deleted()
+                || status.is_wt_renamed()
+                || status.is_wt_typechange()
+        });
+        if !has_unstaged {
+            return Ok(true);
+        }
+        match config.get_unstaged_action() {
+            UnstagedAction::Add => true,
+            UnstagedAction::Stash | UnstagedAction::Continue => false,
+            UnstagedAction::Abort => {
+                return Err(GgError::Other(
+                    "Unstaged changes detected and defaults.unstaged_action is set to \"abort\". \
+                     Stage them, or pass --all to absorb them too."
+                        .to_string(),
+                ));
+            }
+            UnstagedAction::Ask => {
+                if options.json || !Term::stderr().is_term() {
+                    false
+                } else {
+                    println!(
+                        "{}",
+                        style(format!(
+                            "{} You have unstaged changes that won't be absorbed.",
+                            plain::warn()
+                        ))
+                        .yellow()
+                        .bold()
+                    );
+                    println!();
+                    let selection = Select::new()
+                        .items(["Stage them and continue", "Continue anyway", "Abort"])
+                        .default(0)
+                        .interact()
+                        .map_err(|e| GgError::Other(format!("Failed to read selection: {}", e)))?;
+                    match selection {
+                        0 => true,
+                        1 => false,
+                        _ => return Ok(false),
+                    }
+                }
+            }
+        }
+    };
+
+    if stage {
+        let args: &[&str] = if options.include_untracked {
+            &["add", "--all"]
+        } else {
+            &["add", "--update"]
+        };
+        let output = Command::new("git").args(args).output()?;
+        if !output.status.success() {
+            return Err(GgError::Other(format!(
+                "Failed to stage changes: {}",
+                String::from_utf8_lossy(&output.stderr).trim()
+            )));
+        }
+    }
+    Ok(true)
+}
+
+/// `gg absorb -i`: confirm or re-target each hunk, then create the fixups.
+fn run_interactive(
+    repo: &git2::Repository,
+    stack: &Stack,
+    options: &AbsorbOptions,
+    op_id: Option<&str>,
+) -> Result<()> {
+    let plan = absorb_plan::plan(repo, stack)?;
+    if plan.hunks.is_empty() {
+        println!(
+            "{}",
+            style("No staged hunks to absorb (only added, deleted, renamed or binary files).")
+                .dim()
+        );
+        return Ok(());
+    }
+
+    let Some(targets) = choose_targets(stack, &plan)? else {
+        println!("{}", style("Quit. Nothing absorbed.").dim());
+        return Ok(());
+    };
+    let verb = if options.squash { "squash" } else { "fixup" };
+    let fixups = absorb_plan::create_fixups(repo, stack, &plan, &targets, verb)?;
+    if fixups.is_empty() {
+        println!("{}", style("No hunks selected. Nothing absorbed.").dim());
+        return Ok(());
+    }
+
+    for fixup in &fixups {
+        let title = stack
+            .get_entry_by_position(fixup.target)
+            .map(|entry| entry.title.as_str())
+            .unwrap_or_default();
+        println!(
+            "{} {} hunk(s) {} {} {}",
+            style("OK").green().bold(),
+            fixup.hunks,
+            plain::arrow(),
+            style(format!("#{}", fixup.target)).dim(),
+            title
+        );
+    }
+    let left = targets.iter().filter(|t| t.is_none()).count();
+    if left > 0 {
+        println!("{}", style(format!("  {} hunk(s) left staged", left)).dim());
+    }
+
+    if options.and_rebase {
+        let earliest = fixups.iter().map(|f| f.target).min().unwrap_or(1);
+        rebase_autosquash(repo, stack, earliest, op_id)?;
+        println!(
+            "{}",
+            style("  Run `gg ls` to review and `gg sync --force` to push changes.").dim()
+        );
+    } else {
+        println!(
+            "{}",
+            style("  Fixup commits created. Run `git rebase -i --autosquash` or use `gg absorb --and-rebase` to automatically rebase.").dim()
+        );
+    }
+    Ok(())
+}
+
+/// Ask where each hunk goes, like `git add -p`. Returns `None` on quit.
+fn choose_targets(stack: &Stack, plan: &AbsorbPlan) -> Result<Option<Vec<Option<usize>>>> {
+    let describe = |position: usize| {
+        stack
+            .get_entry_by_position(position)
+            .map(|entry| format!("#{} {} {}", position, entry.short_sha, entry.title))
+            .unwrap_or_else(|| format!("#{}", position))
+    };
+
+    let mut targets: Vec<Option<usize>> = Vec::with_capacity(plan.hunks.len());
+    let mut rest: Option<bool> = None;
+    for (i, hunk) in plan.hunks.iter().enumerate() {
+        let proposed = hunk.attribution.target();
+        if let Some(accept) = rest {
+            targets.push(proposed.filter(|_| accept));
+            continue;
+        }
+
+        println!();
+        print_hunk(plan, hunk, i + 1, plan.hunks.len());
+        let (question, keys) = match &hunk.attribution {
+            Attribution::Target(position) => (
+                format!("Absorb into {}?", describe(*position)),
+                "y,n,t,a,d,q,?",
+            ),
+            Attribution::Ambiguous(positions) => (
+                format!(
+                    "Ambiguous: touched by {}. Skip?",
+                    positions
+                        .iter()
+                        .map(|p| format!("#{}", p))
+                        .collect::<Vec<_>>()
+                        .join(", ")
+                ),
+                "n,t,a,d,q,?",
+            ),
+            Attribution::Unattributed => (
+                "No stack commit touched these lines. Skip?".to_string(),
+                "n,t,a,d,q,?",
+            ),
+        };
+
+        loop {
+            let answer: String = Input::new()
+                .with_prompt(format!("{} [{}]", question, keys))
+                .interact_text()
+                .map_err(|e| GgError::Other(format!("Prompt failed: {}", e)))?;
+            match answer.trim() {
+                "y" if proposed.is_some() => targets.push(proposed),
+                "n" => targets.push(None),
+                "t" => match pick_target(stack, &hunk.attribution)? {
+                    Some(position) => targets.push(Some(position)),
+                    None => continue,
+                },
+                "a" => {
+                    targets.push(proposed);
+                    rest = Some(true);
+                }
+                "d" => {
+                    targets.push(None);
+                    rest = Some(false);
+                }
+                "q" => return Ok(None),
+                _ => {
+                    println!("y - absorb this hunk into the proposed commit");
+                    println!("n - leave this hunk staged");
+                    println!("t - absorb this hunk into another commit");
+                    println!("a - accept the proposal for this and all remaining hunks");
+                    println!("d - leave this and all remaining hunks staged");
+                    println!("q - quit without absorbing anything");
+                    continue;
+                }
+            }
+            break;
+        }
+    }
+    Ok(Some(targets))
+}
+
+/// Let the user pick any stack entry, candidates first.
+fn pick_target(stack: &Stack, attribution: &Attribution) -> Result<Option<usize>> {
+    let candidates = attribution.candidates();
+    let mut positions: Vec<usize> = candidates.clone();
+    positions.extend(
+        stack
+            .entries
+            .iter()
+            .map(|entry| entry.position)
+            .filter(|p| !candidates.contains(p)),
+    );
+    let items: Vec<String> = positions
+        .iter()
+        .filter_map(|&p| stack.get_entry_by_position(p))
+        .map(|entry| {
+            format!(
+                "#{} {} {}{}",
+                entry.position,
+                entry.short_sha,
+                entry.title,
+                if candidates.contains(&entry.position) {
+                    " (touched these lines)"
+                } else {
+                    ""
+                }
+            )
+        })
+        .collect();
+    let choice = Select::new()
+        .with_prompt("Absorb into (Esc to go back)")
+        .items(&items)
+        .default(0)
+        .interact_opt()
+        .map_err(|e| GgError::Other(format!("Prompt failed: {}", e)))?;
+    Ok(choice.map(|i| positions[i]))
+}
+
+fn print_hunk(plan: &AbsorbPlan, hunk: &PlannedHunk, index: usize, total: usize) {
+    println!(
+        "{} {}",
+        style(format!(
+            "({}/{}) {} @@ -{},{} +{},{} @@",
+            index,
+            total,
+            plan.path(hunk),
+            hunk.old_start,
+            hunk.old_lines,
+            hunk.new_start,
+            hunk.new_lines
+        ))
+        .cyan(),
+        style(hunk.attribution.status()).dim()
+    );
+    for line in &hunk.removed {
+        println!("{}", style(format!("-{}", line)).red());
+    }
+    for line in &hunk.added {
+        println!("{}", style(format!("+{}", line)).green());
+    }
+}
+
+/// Fold the new fixup commits into their targets with an autosquash
+/// rebase of everything above `earliest`'s parent.
+fn rebase_autosquash(
+    repo: &git2::Repository,
+    stack: &Stack,
+    earliest: usize,
+    op_id: Option<&str>,
+) -> Result<()> {
+    let entry = stack
+        .get_entry_by_position(earliest)
+        .ok_or_else(|| GgError::Other(format!("Position {} not found in stack", earliest)))?;
+    let onto = repo
+        .find_commit(entry.oid)?
+        .parent_id(0)
+        .map_err(|_| GgError::Other("Cannot absorb into the root commit".to_string()))?;
+
+    if autosquash_in_memory(repo, onto)? {
+        return Ok(());
+    }
+
+    let mut command = hooks::git_command(GitRewrite::Replay);
+    command
+        .env("GIT_SEQUENCE_EDITOR", "true")
+        .env("GIT_EDITOR", "true")
+        .args([
+            "rebase",
+            "--interactive",
+            "--autosquash",
+            "--autostash",
+            &onto.to_string(),
+        ]);
+    if let Some(workdir) = repo.workdir() {
+        command.current_dir(workdir);
+    }
+    let output = command.output()?;
+    if output.status.success() {
+        return Ok(());
+    }
+
+    if git::is_rebase_in_progress(repo) {
+        eprintln!("{}", style("Rebase conflict detected.").yellow().bold());
+        eprintln!("  Resolve conflicts, stage the changes with `git add`, then run `gg continue`");
+        eprintln!("  Or run `gg abort` to cancel the rebase");
+        if let Some(op_id) = op_id {
+            let _ = operations::remember_interrupted_rebase_operation(repo, op_id);
+        }
+        return Err(GgError::RebaseConflict);
+    }
+    Err(GgError::Other(format!(
+        "Rebase failed: {}",
+        String::from_utf8_lossy(&output.stderr).trim()
+    )))
+}
+
+/// Autosquash without touching the working tree or index. Only when every
+/// fixup folds in cleanly and the stack ends up with HEAD's tree, so
+/// whatever is still staged or modified stays valid on top of it. Returns
+/// whether HEAD was rewritten.
+fn autosquash_in_memory(repo: &git2::Repository, onto: git2::Oid) -> Result<bool> {
+    let head = repo.head()?;
+    let tip = head.peel_to_commit()?;
+    let commits = replay::commits_between(repo, onto, tip.id())?;
+    let Some(steps) = replay::autosquash_steps(repo, &commits)? else {
+        return Ok(false);
+    };
+    let Some(new_tip) = replay::replay(repo, onto, &steps)? else {
+        return Ok(false);
+    };
+    if repo.find_commit(new_tip)?.tree_id() != tip.tree_id() {
+        return Ok(false);
+    }
+    if new_tip != tip.id() {
+        match head.name().ok().filter(|_| head.is_branch()) {
+            Some(name) => {
+                repo.reference(name, new_tip, true, "gg absorb: autosquash")?;
+            }
+            None => repo.set_head_detached(new_tip)?,
+        }
+    }
+    Ok(true)
+}
+
 /// Create a slog logger for git-absorb output
 fn create_logger(verbose: bool) -> Logger {
     let decorator = slog_term::TermDecorator::new().build();
@@ -288,5 +834,8 @@ mod tests {
         assert!(!opts.one_fixup_per_commit);
         assert!(!opts.no_limit);
         assert!(!opts.squash);
+        assert!(opts.into.is_none());
+        assert!(!opts.all);
+        assert!(!opts.include_untracked);
     }
 }
@@ -0,0 +1,459 @@
+//! Hunk attribution for `gg absorb -i` and `gg absorb --dry-run --json`
+//!
+//! Splits the staged changes into zero-context hunks and blames the lines
+//! each one replaces (or, for pure insertions, the lines around it) to find
+//! the stack entry that last touched them. Chosen hunks are then committed
+//! as `fixup!` commits on top of HEAD without touching the index or the
+//! working tree, so skipped hunks simply stay staged.
+
+use std::collections::{BTreeMap, HashMap};
+use std::path::Path;
+
+use git2::build::TreeUpdateBuilder;
+use git2::{BlameOptions, Delta, DiffOptions, FileMode, Oid, Patch, Repository};
+
+use crate::error::{GgError, Result};
+use crate::git;
+use crate::signing;
+use crate::stack::Stack;
+
+/// Where a staged hunk would be absorbed.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Attribution {
+    /// Exactly one stack entry touched the hunk's lines (1-indexed position)
+    Target(usize),
+    /// Several stack entries touched them
+    Ambiguous(Vec<usize>),
+    /// Only commits below the stack touched them
+    Unattributed,
+}
+
+impl Attribution {
+    pub fn target(&self) -> Option<usize> {
+        match self {
+            Attribution::Target(position) => Some(*position),
+            _ => None,
+        }
+    }
+
+    /// Positions that could receive the hunk
+    pub fn candidates(&self) -> Vec<usize> {
+        match self {
+            Attribution::Target(position) => vec![*position],
+            Attribution::Ambiguous(positions) => positions.clone(),
+            Attribution::Unattributed => Vec::new(),
+        }
+    }
+
+    /// Short name used in JSON output
+    pub fn status(&self) -> &'static str {
+        match self {
+            Attribution::Target(_) => "attributed",
+            Attribution::Ambiguous(_) => "ambiguous",
+            Attribution::Unattributed => "unattributed",
+        }
+    }
+}
+
+/// A modified file with staged hunks.
+#[derive(Debug, Clone)]
+pub struct PlannedFile {
+    pub path: String,
+    old_blob: Oid,
+    new_blob: Oid,
+    mode: FileMode,
+}
+
+/// One zero-context hunk of the staged diff.
+#[derive(Debug, Clone)]
+pub struct PlannedHunk {
+    /// Index into [`AbsorbPlan::files`]
+    pub file: usize,
+    pub old_start: u32,
+    pub old_lines: u32,
+    pub new_start: u32,
+    pub new_lines: u32,
+    /// Lines the hunk removes, without the `-` marker
+    pub removed: Vec<String>,
+    /// Lines the hunk adds, without the `+` marker
+    pub added: Vec<String>,
+    pub attribution: Attribution,
+}
+
+/// Attribution of every staged hunk.
+#[derive(Debug, Clone, Default)]
+pub struct AbsorbPlan {
+    pub files: Vec<PlannedFile>,
+    pub hunks: Vec<PlannedHunk>,
+    /// Staged paths not split into hunks (added, deleted, renamed or binary
+    /// files); absorb leaves them staged
+    pub unsupported_paths: Vec<String>,
+}
+
+impl AbsorbPlan {
+    pub fn path(&self, hunk: &PlannedHunk) -> &str {
+        &self.files[hunk.file].path
+    }
+}
+
+/// A fixup commit created by [`create_fixups`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct CreatedFixup {
+    pub oid: Oid,
+    /// Position of the entry it fixes up
+    pub target: usize,
+    /// Number of hunks it carries
+    pub hunks: usize,
+}
+
+/// Attribute the staged changes (HEAD vs index) to entries of `stack`.
+pub fn plan(repo: &Repository, stack: &Stack) -> Result<AbsorbPlan> {
+    let head = repo.head()?.peel_to_commit()?;
+    let index = repo.index()?;
+    let mut opts = DiffOptions::new();
+    opts.context_lines(0).interhunk_lines(0);
+    let diff = repo.diff_tree_to_index(Some(&head.tree()?), Some(&index), Some(&mut opts))?;
+
+    let positions: HashMap<Oid, usize> = stack
+        .entries
+        .iter()
+        .map(|entry| (entry.oid, entry.position))
+        .collect();
+    let oldest = stack
+        .entries
+        .first()
+        .and_then(|entry| repo.find_commit(entry.oid).ok())
+        .and_then(|commit| commit.parent_id(0).ok());
+
+    let mut plan = AbsorbPlan::default();
+    for (idx, delta) in diff.deltas().enumerate() {
+        let path = delta
+            .new_file()
+            .path()
+            .or_else(|| delta.old_file().path())
+            .map(|p| p.to_string_lossy().to_string())
+            .unwrap_or_default();
+        let patch = Patch::from_diff(&diff, idx)?;
+        let binary = delta.flags().is_binary();
+        let (Delta::Modified, Some(patch), false) = (delta.status(), patch, binary) else {
+            plan.unsupported_paths.push(path);
+            continue;
+        };
+
+        let mut blame_opts = BlameOptions::new();
+        blame_opts.newest_commit(head.id());
+        if let Some(oldest) = oldest {
+            blame_opts.oldest_commit(oldest);
+        }
+        let blame = repo.blame_file(Path::new(&path), Some(&mut blame_opts))?;
+        let owner = |line: u32| -> Option<usize> {
+            let hunk = blame.get_line(line as usize)?;
+            positions.get(&hunk.final_commit_id()).copied()
+        };
+
+        let file = plan.files.len();
+        plan.files.push(PlannedFile {
+            path,
+            old_blob: delta.old_file().id(),
+            new_blob: delta.new_file().id(),
+            mode: delta.new_file().mode(),
+        });
+
+        for h in 0..patch.num_hunks() {
+            let (hunk, line_count) = patch.hunk(h)?;
+            let mut removed = Vec::new();
+            let mut added = Vec::new();
+            for l in 0..line_count {
+                let line = patch.line_in_hunk(h, l)?;
+                let text = String::from_utf8_lossy(line.content())
+                    .trim_end_matches(['\n', '\r'])
+                    .to_string();
+                match line.origin() {
+                    '-' => removed.push(text),
+                    '+' => added.push(text),
+                    _ => {}
+                }
+            }
+
+            // Blame the replaced lines; an insertion has none, so blame
+            // the lines on either side of it instead.
+            let blamed: Vec<u32> = if hunk.old_lines() > 0 {
+                (hunk.old_start()..hunk.old_start() + hunk.old_lines()).collect()
+            } else {
+                vec![hunk.old_start(), hunk.old_start() + 1]
+            };
+            let mut owners: Vec<usize> = blamed
+                .into_iter()
+                .filter(|&line| line > 0)
+                .filter_map(owner)
+                .collect();
+            owners.sort_unstable();
+            owners.dedup();
+
+            plan.hunks.push(PlannedHunk {
+                file,
+                old_start: hunk.old_start(),
+                old_lines: hunk.old_lines(),
+                new_start: hunk.new_start(),
+                new_lines: hunk.new_lines(),
+                removed,
+                added,
+                attribution: match owners.as_slice() {
+                    [] => Attribution::Unattributed,
+                    [position] => Attribution::Target(*position),
+                    _ => Attribution::Ambiguous(owners),
+                },
+            });
+        }
+    }
+    Ok(plan)
+}
+
+/// Commit the hunks with a target in `targets` (parallel to `plan.hunks`)
+/// as one `<verb>! <title>` commit per target entry on top of HEAD.
+///
+/// Trees are built from the HEAD and index blobs, so the index and the
+/// working tree are left alone and skipped hunks stay staged.
+pub fn create_fixups(
+    repo: &Repository,
+    stack: &Stack,
+    plan: &AbsorbPlan,
+    targets: &[Option<usize>],
+    verb: &str,
+) -> Result<Vec<CreatedFixup>> {
+    let mut by_target: BTreeMap<usize, Vec<usize>> = BTreeMap::new();
+    for (hunk, target) in targets.iter().enumerate() {
+        if let Some(position) = target {
+            by_target.entry(*position).or_default().push(hunk);
+        }
+    }
+    if by_target.is_empty() {
+        return Ok(Vec::new());
+    }
+
+    let signature = git::get_signature(repo)?;
+    let mut parent = repo.head()?.peel_to_commit()?;
+    let base_tree = parent.tree()?;
+    let mut applied: Vec<usize> = Vec::new();
+    let mut created = Vec::new();
+
+    for (position, hunks) in by_target {
+        let entry = stack
+            .get_entry_by_position(position)
+            .ok_or_else(|| GgError::Other(format!("Position {} not found in stack", position)))?;
+        applied.extend(&hunks);
+
+        // Each fixup tree holds every hunk chosen so far, so the commits
+        // chain on top of each other.
+        let mut builder = TreeUpdateBuilder::new();
+        let mut touched: Vec<usize> = applied.iter().map(|&h| plan.hunks[h].file).collect();
+        touched.sort_unstable();
+        touched.dedup();
+        for file in touched {
+            let planned = &plan.files[file];
+            let file_hunks: Vec<&PlannedHunk> = applied
+                .iter()
+                .map(|&h| &plan.hunks[h])
+                .filter(|h| h.file == file)
+                .collect();
+            let old = repo.find_blob(planned.old_blob)?;
+            let new = repo.find_blob(planned.new_blob)?;
+            let content = apply_hunks(old.content(), new.content(), &file_hunks);
+            let blob = repo.blob(&content)?;
+            builder.upsert(&planned.path, blob, planned.mode);
+        }
+        let tree = repo.find_tree(builder.create_updated(repo, &base_tree)?)?;
+
+        let message = format!("{}! {}", verb, entry.title);
+        let oid =
+            signing::create_commit(repo, &signature, &signature, &message, &tree, &[&parent])?;
+        created.push(CreatedFixup {
+            oid,
+            target: position,
+            hunks: hunks.len(),
+        });
+        parent = repo.find_commit(oid)?;
+    }
+
+    repo.head()?
+        .set_target(parent.id(), "gg absorb: create fixup commits")?;
+    Ok(created)
+}
+
+/// `old` with the given zero-context hunks of the `old` -> `new` diff applied.
+fn apply_hunks(old: &[u8], new: &[u8], hunks: &[&PlannedHunk]) -> Vec<u8> {
+    let old_lines: Vec<&[u8]> = old.split_inclusive(|&b| b == b'\n').collect();
+    let new_lines: Vec<&[u8]> = new.split_inclusive(|&b| b == b'\n').collect();
+    let mut hunks = hunks.to_vec();
+    hunks.sort_by_key(|h| h.old_start);
+
+    let mut out = Vec::with_capacity(new.len());
+    let mut cursor = 0;
+    for hunk in hunks {
+        // Zero-context hunks that only insert point at the line before the
+        // insertion; the others at their first replaced line.
+        let start = if hunk.old_lines == 0 {
+            hunk.old_start as usize
+        } else {
+            hunk.old_start as usize - 1
+        };
+        for line in &old_lines[cursor..start] {
+            out.extend_from_slice(line);
+        }
+        if hunk.new_lines > 0 {
+            let first = hunk.new_start as usize - 1;
+            for line in &new_lines[first..first + hunk.new_lines as usize] {
+                out.extend_from_slice(line);
+            }
+        }
+        cursor = start + hunk.old_lines as usize;
+    }
+    for line in &old_lines[cursor..] {
+        out.extend_from_slice(line);
+    }
+    out
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn hunk(old_start: u32, old_lines: u32, new_start: u32, new_lines: u32) -> PlannedHunk {
+        PlannedHunk {
+            file: 0,
+            old_start,
+            old_lines,
+            new_start,
+            new_lines,
+            removed: vec![],
+            added: vec![],
+            attribution: Attribution::Unattributed,
+        }
+    }
+
+    #[test]
+    fn apply_hunks_applies_only_the_chosen_ones() {
+        let old = b"a\nb\nc\nd\n";
+        let new = b"A\nb\nc\nc2\nd\n";
+        // -1,1 +1,1 replaces a; -3,0 +4,1 inserts c2 after c
+        let replace = hunk(1, 1, 1, 1);
+        let insert = hunk(3, 0, 4, 1);
+
+        assert_eq!(apply_hunks(old, new, &[&replace, &insert]), new.to_vec());
+        assert_eq!(apply_hunks(old, new, &[&replace]), b"A\nb\nc\nd\n".to_vec());
+        assert_eq!(
+            apply_hunks(old, new, &[&insert]),
+            b"a\nb\nc\nc2\nd\n".to_vec()
+        );
+        assert_eq!(apply_hunks(old, new, &[]), old.to_vec());
+    }
+
+    #[test]
+    fn apply_hunks_handles_deletions() {
+        let old = b"a\nb\nc\n";
+        let new = b"a\nc\n";
+        // -2,1 +1,0 deletes b
+        assert_eq!(apply_hunks(old, new, &[&hunk(2, 1, 1, 0)]), new.to_vec());
+    }
+
+    fn commit_file(repo: &Repository, content: &str, message: &str) -> Oid {
+        std::fs::write(repo.workdir().unwrap().join("file.txt"), content).unwrap();
+        let mut index = repo.index().unwrap();
+        index.add_path(Path::new("file.txt")).unwrap();
+        index.write().unwrap();
+        let tree = repo.find_tree(index.write_tree().unwrap()).unwrap();
+        let sig = repo.signature().unwrap();
+        let parent = repo.head().ok().map(|h| h.peel_to_commit().unwrap());
+        let parents: Vec<&git2::Commit> = parent.iter().collect();
+        repo.commit(Some("HEAD"), &sig, &sig, message, &tree, &parents)
+            .unwrap()
+    }
+
+    #[test]
+    fn plan_and_fixups_follow_blame() {
+        let dir = tempfile::tempdir().unwrap();
+        let repo = Repository::init(dir.path()).unwrap();
+        let mut config = repo.config().unwrap();
+        config.set_str("user.name", "Test").unwrap();
+        config.set_str("user.email", "test@example.com").unwrap();
+
+        commit_file(&repo, "base\nkeep1\nkeep2\n", "Base");
+        let first = commit_file(&repo, "base\nkeep1\none\nkeep2\n", "Add one");
+        let second = commit_file(&repo, "base\nkeep1\none\nkeep2\ntwo\n", "Add two");
+        let first = repo.find_commit(first).unwrap();
+        let second = repo.find_commit(second).unwrap();
+        let stack = Stack {
+            name: "stack".to_string(),
+            username: "me".to_string(),
+            base: "main".to_string(),
+            entries: vec![
+                crate::stack::StackEntry::from_commit(&first, 1),
+                crate::stack::StackEntry::from_commit(&second, 2),
+            ],
+            current_position: None,
+        };
+
+        // Base line, line from #1, line from #2
+        std::fs::write(
+            dir.path().join("file.txt"),
+            "BASE\nkeep1\nONE\nkeep2\nTWO\n",
+        )
+        .unwrap();
+        let mut index = repo.index().unwrap();
+        index.add_path(Path::new("file.txt")).unwrap();
+        index.write().unwrap();
+
+        let plan = plan(&repo, &stack).unwrap();
+        let attributions: Vec<&Attribution> = plan.hunks.iter().map(|h| &h.attribution).collect();
+        assert_eq!(
+            attributions,
+            vec![
+                &Attribution::Unattributed,
+                &Attribution::Target(1),
+                &Attribution::Target(2)
+            ]
+        );
+        assert_eq!(plan.path(&plan.hunks[1]), "file.txt");
+        assert_eq!(plan.hunks[1].removed, vec!["one"]);
+        assert_eq!(plan.hunks[1].added, vec!["ONE"]);
+
+        // Absorb only the #1 hunk; the others stay staged.
+        let created = create_fixups(&repo, &stack, &plan, &[None, Some(1), None], "fixup").unwrap();
+        assert_eq!(created.len(), 1);
+        assert_eq!(created[0].target, 1);
+        let head = repo.head().unwrap().peel_to_commit().unwrap();
+        assert_eq!(head.id(), created[0].oid);
+        assert_eq!(head.summary().unwrap(), Some("fixup! Add one"));
+        let blob = head
+            .tree()
+            .unwrap()
+            .get_path(Path::new("file.txt"))
+            .unwrap()
+            .to_object(&repo)
+            .unwrap()
+            .peel_to_blob()
+            .unwrap();
+        assert_eq!(blob.content(), b"base\nkeep1\nONE\nkeep2\ntwo\n");
+
+        let remaining = plan_hunk_count(&repo);
+        assert_eq!(remaining, 2);
+    }
+
+    fn plan_hunk_count(repo: &Repository) -> usize {
+        let head = repo.head().unwrap().peel_to_commit().unwrap();
+        let mut opts = DiffOptions::new();
+        opts.context_lines(0);
+        let diff = repo
+            .diff_tree_to_index(Some(&head.tree().unwrap()), None, Some(&mut opts))
+            .unwrap();
+        Patch::from_diff(&diff, 0).unwrap().unwrap().num_hunks()
+    }
+
+    #[test]
+    fn attribution_candidates_and_status() {
+        assert_eq!(Attribution::Target(2).target(), Some(2));
+        assert_eq!(Attribution::Ambiguous(vec![1, 3]).target(), None);
+        assert_eq!(Attribution::Ambiguous(vec![1, 3]).candidates(), vec![1, 3]);
+        assert_eq!(Attribution::Unattributed.status(), "unattributed");
+    }
+}
@@ -0,0 +1,146 @@
+//! `gg annotate` - Attach key/value metadata to a stack entry
+//!
+//! Annotations live in git notes under `refs/notes/gg` (see
+//! [`crate::notes`]), so they never touch commit messages or PR/MR
+//! descriptions. `gg ls` and `gg log` show them, including in JSON output.
+
+use console::style;
+
+use crate::config::Config;
+use crate::error::{GgError, Result};
+use crate::git;
+use crate::notes::{self, Annotations};
+use crate::output::{print_json, AnnotateResponse, OUTPUT_VERSION};
+use crate::stack::{self, Stack};
+
+/// Options for the annotate command
+#[derive(Debug, Default)]
+pub struct AnnotateOptions {
+    /// Entry to annotate (position, GG-ID, or SHA)
+    pub target: String,
+    /// `key=value` pairs to set, or bare `key` flags
+    pub pairs: Vec<String>,
+    /// Keys to remove
+    pub remove: Vec<String>,
+    /// Remove every annotation from the entry
+    pub clear: bool,
+    /// Output as JSON
+    pub json: bool,
+}
+
+/// Apply `pairs` and `remove` to `annotations`.
+pub fn apply(annotations: &mut Annotations, pairs: &[String], remove: &[String]) -> Result<()> {
+    for key in remove {
+        annotations.remove(key.trim());
+    }
+    for pair in pairs {
+        let (key, value) = match pair.split_once('=') {
+            Some((key, value)) => (key.trim(), value.trim()),
+            None => (pair.trim(), ""),
+        };
+        if key.is_empty() || key.contains(char::is_whitespace) {
+            return Err(GgError::Other(format!(
+                "Invalid annotation '{}': expected key=value or key",
+                pair
+            )));
+        }
+        if value.contains('\n') {
+            return Err(GgError::Other(format!(
+                "Annotation '{}' must fit on one line",
+                key
+            )));
+        }
+        annotations.insert(key.to_string(), value.to_string());
+    }
+    Ok(())
+}
+
+/// Run the annotate command
+pub fn run(options: AnnotateOptions) -> Result<()> {
+    let repo = git::open_repo()?;
+    let config = Config::load(repo.commondir())?;
+    let stack = Stack::load_active(&repo, &config)?;
+
+    let pos = stack::resolve_target(&stack, &options.target)?;
+    let entry = stack
+        .get_entry_by_position(pos)
+        .ok_or_else(|| GgError::Other(format!("No entry at position {}", pos)))?;
+
+    let mut annotations = entry.annotations.clone();
+    let changed = options.clear || !options.pairs.is_empty() || !options.remove.is_empty();
+    if options.clear {
+        annotations.clear();
+    }
+    apply(&mut annotations, &options.pairs, &options.remove)?;
+
+    if changed {
+        notes::write(&repo, entry.oid, &annotations)?;
+        // Keep notes attached when gg amends or rebases the entry.
+        notes::track_rewrites(&repo)?;
+    }
+
+    if options.json {
+        print_json(&AnnotateResponse {
+            version: OUTPUT_VERSION,
+            position: pos,
+            sha: entry.short_sha.clone(),
+            gg_id: entry.gg_id.clone(),
+            annotations,
+        });
+        return Ok(());
+    }
+
+    if annotations.is_empty() {
+        println!(
+            "{}",
+            style(format!("[{}] {} has no annotations.", pos, entry.title)).dim()
+        );
+    } else {
+        if changed {
+            print!("{} ", style("OK").green().bold());
+        }
+        println!(
+            "[{}] {} {}",
+            pos,
+            style(&entry.short_sha).yellow(),
+            entry.title
+        );
+        for (key, value) in &annotations {
+            if value.is_empty() {
+                println!("  {}", key);
+            } else {
+                println!("  {} = {}", key, value);
+            }
+        }
+    }
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn apply_sets_flags_and_removes_keys() {
+        let mut annotations = Annotations::new();
+        annotations.insert("risk".to_string(), "low".to_string());
+        apply(
+            &mut annotations,
+            &["risk=high".to_string(), "needs-qa".to_string()],
+            &[],
+        )
+        .unwrap();
+        assert_eq!(notes::render(&annotations, " "), "needs-qa risk=high");
+
+        apply(&mut annotations, &[], &["needs-qa".to_string()]).unwrap();
+        assert_eq!(notes::render(&annotations, " "), "risk=high");
+    }
+
+    #[test]
+    fn apply_rejects_invalid_keys() {
+        let mut annotations = Annotations::new();
+        assert!(apply(&mut annotations, &["=value".to_string()], &[]).is_err());
+        assert!(apply(&mut annotations, &["two words".to_string()], &[]).is_err());
+    }
+}
@@ -0,0 +1,264 @@
+//! `gg archive` - Stacks put aside by `gg clean --archive`
+//!
+//! Archiving keeps a cleaned stack's tip under
+//! `refs/gg/archive/<stack>/<date>` instead of deleting it, and records the
+//! stack's config (base, PR/MR mappings, ...) in `<commondir>/gg/archive.json`
+//! so `gg archive restore` can bring the stack back as it was.
+
+use std::fs;
+use std::path::PathBuf;
+
+use console::style;
+use git2::{BranchType, Oid, Repository};
+use serde::{Deserialize, Serialize};
+
+use crate::config::{Config, StackConfig};
+use crate::error::{GgError, Result};
+use crate::git;
+use crate::operations::now_ms;
+use crate::output::{
+    print_json, ArchiveEntryJson, ArchiveListResponse, ArchiveRestoreResponse, OUTPUT_VERSION,
+};
+use crate::relative_time::civil_from_days;
+
+const ARCHIVE_REF_PREFIX: &str = "refs/gg/archive/";
+const ARCHIVE_FILE: &str = "archive.json";
+
+/// One archived stack.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ArchivedStack {
+    pub stack: String,
+    /// Branch username at archive time, so the branch comes back under the
+    /// same name
+    pub username: String,
+    /// `refs/gg/archive/<stack>/<date>`
+    #[serde(rename = "ref")]
+    pub reference: String,
+    pub tip: String,
+    pub archived_at_ms: u64,
+    /// Stack config when it was archived (without its worktree)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub config: Option<StackConfig>,
+}
+
+impl From<&ArchivedStack> for ArchiveEntryJson {
+    fn from(archived: &ArchivedStack) -> Self {
+        ArchiveEntryJson {
+            stack: archived.stack.clone(),
+            reference: archived.reference.clone(),
+            tip: archived.tip.clone(),
+            archived_at_ms: archived.archived_at_ms,
+        }
+    }
+}
+
+#[derive(Debug, Default, Serialize, Deserialize)]
+struct ArchiveIndex {
+    #[serde(default)]
+    archives: Vec<ArchivedStack>,
+}
+
+impl ArchiveIndex {
+    fn path(repo: &Repository) -> PathBuf {
+        git::gg_dir(repo).join(ARCHIVE_FILE)
+    }
+
+    fn load(repo: &Repository) -> Result<Self> {
+        let path = Self::path(repo);
+        if !path.exists() {
+            return Ok(Self::default());
+        }
+        Ok(serde_json::from_str(&fs::read_to_string(path)?)?)
+    }
+
+    fn save(&self, repo: &Repository) -> Result<()> {
+        let path = Self::path(repo);
+        if let Some(parent) = path.parent() {
+            fs::create_dir_all(parent)?;
+        }
+        let temp_path = path.with_extension("tmp");
+        fs::write(&temp_path, serde_json::to_string_pretty(self)?)?;
+        fs::rename(&temp_path, &path)?;
+        Ok(())
+    }
+}
+
+/// Keep the tip of `stack_name`'s branch under
+/// `refs/gg/archive/<stack>/<date>` and record its config, before `gg clean`
+/// deletes the branch. Returns the archive ref, or `None` when the branch
+/// does not exist.
+pub(crate) fn archive_stack(
+    repo: &Repository,
+    config: &Config,
+    stack_name: &str,
+    username: &str,
+) -> Result<Option<String>> {
+    let branch_name = git::format_stack_branch(username, stack_name);
+    let Some(tip) = repo
+        .find_branch(&branch_name, BranchType::Local)
+        .ok()
+        .and_then(|b| b.get().target())
+    else {
+        return Ok(None);
+    };
+
+    let archived_at_ms = now_ms();
+    let date = format_date(archived_at_ms / 1000);
+    let mut reference = format!("{}{}/{}", ARCHIVE_REF_PREFIX, stack_name, date);
+    let mut n = 2;
+    while repo.find_reference(&reference).is_ok() {
+        reference = format!("{}{}/{}-{}", ARCHIVE_REF_PREFIX, stack_name, date, n);
+        n += 1;
+    }
+    repo.reference(
+        &reference,
+        tip,
+        false,
+        &format!("gg clean: archive {}", branch_name),
+    )?;
+
+    let mut index = ArchiveIndex::load(repo)?;
+    index.archives.push(ArchivedStack {
+        stack: stack_name.to_string(),
+        username: username.to_string(),
+        reference: reference.clone(),
+        tip: tip.to_string(),
+        archived_at_ms,
+        config: config.get_stack(stack_name).cloned().map(|mut cfg| {
+            cfg.worktree_path = None;
+            cfg
+        }),
+    });
+    index.save(repo)?;
+    Ok(Some(reference))
+}
+
+/// Run `gg archive list`
+pub fn run_list(json: bool) -> Result<()> {
+    let repo = git::open_repo()?;
+    let mut archives = ArchiveIndex::load(&repo)?.archives;
+    archives.sort_by(|a, b| {
+        a.stack
+            .cmp(&b.stack)
+            .then(b.archived_at_ms.cmp(&a.archived_at_ms))
+    });
+
+    if json {
+        print_json(&ArchiveListResponse {
+            version: OUTPUT_VERSION,
+            archives: archives.iter().map(Into::into).collect(),
+        });
+        return Ok(());
+    }
+
+    if archives.is_empty() {
+        println!("{}", style("No archived stacks.").dim());
+        return Ok(());
+    }
+    for archived in &archives {
+        let title = Oid::from_str(&archived.tip)
+            .ok()
+            .and_then(|oid| repo.find_commit(oid).ok())
+            .and_then(|c| c.summary().ok().flatten().map(str::to_string))
+            .unwrap_or_default();
+        println!(
+            "{}  {}  {} {}  {}",
+            style(&archived.stack).cyan(),
+            format_date(archived.archived_at_ms / 1000),
+            style(&archived.tip[..archived.tip.len().min(7)]).yellow(),
+            title,
+            style(&archived.reference).dim()
+        );
+    }
+    Ok(())
+}
+
+/// Run `gg archive restore`: recreate the stack branch and its config from
+/// the newest archive of `target` (a stack name, or an archive ref).
+pub fn run_restore(target: &str, json: bool) -> Result<()> {
+    let repo = git::open_repo()?;
+    let git_dir = repo.commondir();
+    let mut config = Config::load(git_dir)?;
+    let mut index = ArchiveIndex::load(&repo)?;
+
+    let found = index
+        .archives
+        .iter()
+        .enumerate()
+        .filter(|(_, a)| a.stack == target || a.reference == target)
+        .max_by_key(|(_, a)| a.archived_at_ms)
+        .map(|(i, _)| i)
+        .ok_or_else(|| {
+            GgError::Other(format!(
+                "No archived stack '{}'. See `gg archive list`.",
+                target
+            ))
+        })?;
+    let archived = index.archives[found].clone();
+
+    let tip = repo
+        .find_reference(&archived.reference)
+        .ok()
+        .and_then(|r| r.target())
+        .ok_or_else(|| {
+            GgError::Other(format!(
+                "Archive ref '{}' no longer exists.",
+                archived.reference
+            ))
+        })?;
+    let branch_name = git::format_stack_branch(&archived.username, &archived.stack);
+    if repo.find_branch(&branch_name, BranchType::Local).is_ok() {
+        return Err(GgError::Other(format!(
+            "Branch '{}' already exists. Delete or rename it before restoring '{}'.",
+            branch_name, archived.stack
+        )));
+    }
+
+    repo.branch(&branch_name, &repo.find_commit(tip)?, false)?;
+    if let Some(stack_config) = archived.config.clone() {
+        config
+            .stacks
+            .entry(archived.stack.clone())
+            .or_insert(stack_config);
+        config.save(git_dir)?;
+    }
+    repo.find_reference(&archived.reference)?.delete()?;
+    index.archives.remove(found);
+    index.save(&repo)?;
+
+    if json {
+        print_json(&ArchiveRestoreResponse {
+            version: OUTPUT_VERSION,
+            restored: (&archived).into(),
+            branch: branch_name,
+        });
+    } else {
+        println!(
+            "{} Restored stack '{}' as {} from {}",
+            style("OK").green().bold(),
+            archived.stack,
+            style(&branch_name).cyan(),
+            style(&archived.reference).dim()
+        );
+        println!("  Check it out with `gg co {}`.", archived.stack);
+    }
+    Ok(())
+}
+
+/// `YYYY-MM-DD` (UTC) for seconds since the Unix epoch.
+fn format_date(secs: u64) -> String {
+    let (year, month, day) = civil_from_days((secs / 86_400) as i64);
+    format!("{:04}-{:02}-{:02}", year, month, day)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn format_date_handles_epoch_and_leap_days() {
+        assert_eq!(format_date(0), "1970-01-01");
+        assert_eq!(format_date(951_782_400), "2000-02-29");
+        assert_eq!(format_date(1_792_108_800), "2026-10-16");
+    }
+}
@@ -0,0 +1,114 @@
+//! `gg check-msgs` - Check commit subjects against the commit message policy
+//!
+//! Checks the current stack, or any range of commits with `--range` (for
+//! CI, where there is no stack checked out), against
+//! `defaults.commit_messages` (see [`crate::commit_messages`]). Fails when a
+//! subject doesn't follow it, whatever `defaults.commit_messages.check` says.
+
+use console::style;
+use git2::{Oid, Sort};
+
+use crate::commit_messages::{self, MessageViolation, Policy};
+use crate::config::Config;
+use crate::error::{GgError, Result};
+use crate::git;
+use crate::output::{
+    print_json, CheckMsgJson, CheckMsgsResponse, CheckMsgsResultJson, OUTPUT_VERSION,
+};
+use crate::stack::Stack;
+
+/// Run the check-msgs command. `range` is `<from>..<to>`, or a single ref
+/// meaning `<ref>..HEAD`.
+pub fn run(range: Option<String>, json: bool) -> Result<()> {
+    let repo = git::open_repo()?;
+    let config = Config::load(repo.commondir())?;
+    let policy = Policy::from_config(&config.defaults.commit_messages)?;
+
+    let oids: Vec<Oid> = match range {
+        Some(range) => {
+            let range = if range.contains("..") {
+                range
+            } else {
+                format!("{}..HEAD", range)
+            };
+            let mut walk = repo.revwalk()?;
+            walk.set_sorting(Sort::TOPOLOGICAL | Sort::REVERSE)?;
+            walk.push_range(&range)
+                .map_err(|e| GgError::Other(format!("Invalid range '{}': {}", range, e)))?;
+            walk.collect::<std::result::Result<_, _>>()?
+        }
+        None => Stack::load_active(&repo, &config)?
+            .entries
+            .iter()
+            .map(|e| e.oid)
+            .collect(),
+    };
+
+    let mut commits = Vec::new();
+    let mut violations = Vec::new();
+    for (i, oid) in oids.iter().enumerate() {
+        let commit = repo.find_commit(*oid)?;
+        let message = commit.message().unwrap_or("");
+        let subject = message.lines().next().unwrap_or("").to_string();
+        let parsed = policy.parse(message);
+        let ok = parsed.is_some();
+        if !ok {
+            violations.push(MessageViolation {
+                position: i + 1,
+                short_sha: git::short_sha(&commit),
+                title: subject.clone(),
+            });
+        }
+        let parsed = parsed.unwrap_or_default();
+        commits.push(CheckMsgJson {
+            position: i + 1,
+            sha: git::short_sha(&commit),
+            subject,
+            ok,
+            kind: parsed.kind,
+            scope: parsed.scope,
+            breaking: parsed.breaking,
+        });
+    }
+
+    if json {
+        print_json(&CheckMsgsResponse {
+            version: OUTPUT_VERSION,
+            check_msgs: CheckMsgsResultJson {
+                ok: violations.is_empty(),
+                pattern: policy.pattern().to_string(),
+                violations: violations.len(),
+                commits,
+            },
+        });
+    } else if violations.is_empty() {
+        println!(
+            "{} {} commit subject(s) follow the commit message policy",
+            style("OK").green().bold(),
+            commits.len()
+        );
+    } else {
+        commit_messages::print_violations(
+            &format!(
+                "{} of {} commit subject(s) don't follow the commit message policy:",
+                violations.len(),
+                commits.len()
+            ),
+            &violations,
+        );
+        println!();
+        println!(
+            "{}",
+            style(format!(
+                "Subjects must match {}. Reword them with `gg edit <position> -m <message>`.",
+                policy.pattern()
+            ))
+            .dim()
+        );
+    }
+
+    if !violations.is_empty() {
+        return Err(GgError::Silenced);
+    }
+    Ok(())
+}
@@ -1,13 +1,13 @@
 //! `gg co` / `gg sw` - Create or switch to a stack
 
 use console::style;
-use dialoguer::FuzzySelect;
 use git2::BranchType;
 
 use crate::config::Config;
 use crate::error::{GgError, Result};
 use crate::git;
 use crate::operations::{OperationKind, SnapshotScope};
+use crate::plain;
 use crate::provider::Provider;
 use crate::stack;
 
@@ -15,11 +15,29 @@ use std::collections::HashSet;
 use std::path::Path;
 use std::process::Command;
 
-/// Run the checkout command
-pub fn run(stack_name: Option<String>, base: Option<String>, use_worktree: bool) -> Result<()> {
+/// Run the checkout command. `base`, `remote` and `from` only apply to a new
+/// stack; `from` is the tag, commit or branch it starts from. `description`
+/// sets the stack's description (`gg describe`). `worktree` forces (`--worktree`) or skips (`--no-worktree`) opening the
+/// stack in a linked worktree; `None` follows `defaults.always_worktree`.
+pub fn run(
+    stack_name: Option<String>,
+    base: Option<String>,
+    remote: Option<String>,
+    from: Option<String>,
+    description: Option<String>,
+    worktree: Option<bool>,
+) -> Result<()> {
     let repo = git::open_repo()?;
     let git_dir = repo.commondir();
-    let mut config = Config::load_with_global(git_dir)?;
+    let mut config = Config::load(git_dir)?;
+    if let Some(remote) = &remote {
+        if repo.find_remote(remote).is_err() {
+            return Err(GgError::Other(format!(
+                "Remote '{}' does not exist. Add it with `git remote add {} <url>`.",
+                remote, remote
+            )));
+        }
+    }
 
     // Acquire operation lock + record a Pending op for the undo log.
     let (_lock, guard) = git::acquire_operation_lock_and_record(
@@ -56,40 +74,31 @@ pub fn run(stack_name: Option<String>, base: Option<String>, use_worktree: bool)
             if sanitized != name {
                 println!(
                     "{} Converted stack name to: {}",
-                    style("→").cyan(),
+                    style(plain::arrow()).cyan(),
                     style(&sanitized).cyan()
                 );
             }
             sanitized
         }
-        None => {
-            // Get list of existing stacks
-            let stacks = stack::list_all_stacks(&repo, &config, &username)?;
-
-            if stacks.is_empty() {
-                return Err(GgError::Other(
-                    "No stacks found. Use `gg co <stack-name>` to create one.".to_string(),
-                ));
-            }
-
-            let selection = FuzzySelect::new()
-                .with_prompt("Select a stack")
-                .items(&stacks)
-                .interact()
-                .map_err(|e| GgError::Other(format!("Selection cancelled: {}", e)))?;
-
-            stacks[selection].clone()
-        }
+        None => super::picker::pick_stack(&repo, &config, &username)?,
     };
 
+    // Checking out a stack replaces any `gg use` selection in this worktree
+    stack::clear_active_stack(repo.path())?;
+
     // Format the branch name
     let branch_name = git::format_stack_branch(&username, &stack_name);
 
     // Check if main stack branch exists
     let branch_exists = repo.find_branch(&branch_name, BranchType::Local).is_ok();
 
+    // A stack already checked out right here stays where it is
+    let use_worktree = worktree.unwrap_or(config.defaults.always_worktree);
+    let current_branch = git::current_branch_name(&repo);
+    let opens_worktree = |branch: &str| use_worktree && current_branch.as_deref() != Some(branch);
+
     if branch_exists {
-        if use_worktree {
+        if opens_worktree(&branch_name) {
             let worktree_path =
                 ensure_stack_worktree(&repo, &mut config, &stack_name, &branch_name)?;
             request_shell_cd(&worktree_path);
@@ -112,7 +121,7 @@ pub fn run(stack_name: Option<String>, base: Option<String>, use_worktree: bool)
         git::find_entry_branch_for_stack(&repo, &username, &stack_name)
     {
         // Main stack branch doesn't exist, but an entry branch does - use that
-        if use_worktree {
+        if opens_worktree(&entry_branch) {
             let worktree_path =
                 ensure_stack_worktree(&repo, &mut config, &stack_name, &entry_branch)?;
             request_shell_cd(&worktree_path);
@@ -137,7 +146,7 @@ pub fn run(stack_name: Option<String>, base: Option<String>, use_worktree: bool)
         // complex auth callback setup, while git CLI uses system credentials
         println!(
             "{} Checking remote for stack {}...",
-            style("→").cyan(),
+            style(plain::arrow()).cyan(),
             style(&stack_name).cyan()
         );
         let _ = std::process::Command::new("git")
@@ -147,7 +156,10 @@ pub fn run(stack_name: Option<String>, base: Option<String>, use_worktree: bool)
         if check_remote_stack_exists(&repo, &username, &stack_name) {
             // Stack exists on remote - checkout
             // Try to find either the main stack branch or an entry branch
-            let remote_stack_branch = format!("origin/{}/{}", username, stack_name);
+            let remote_stack_branch = format!(
+                "origin/{}",
+                git::format_stack_branch(&username, &stack_name)
+            );
             let target_branch = if repo.revparse_single(&remote_stack_branch).is_ok() {
                 // Main stack branch exists
                 remote_stack_branch
@@ -222,18 +234,60 @@ pub fn run(stack_name: Option<String>, base: Option<String>, use_worktree: bool)
                 );
             }
         } else {
-            // Create new stack
-            let base_branch = base
+            // Create new stack. `--base <stack>` stacks it on top of another
+            // of the user's stacks instead of a plain branch. `--from <branch>`
+            // is the same as `--base <branch>`; another ref (tag, commit) is
+            // where the stack starts and is kept in the stack's config.
+            let (base, from) = match from
+                .as_deref()
+                .filter(|_| base.is_none())
+                .and_then(|f| from_branch(&repo, f, remote.as_deref()))
+            {
+                Some(branch) => (Some(branch), None),
+                None => (base, from),
+            };
+            let parent = base
+                .as_deref()
+                .and_then(|b| parent_stack_name(&repo, &username, b));
+            if parent.is_some() && from.is_some() {
+                return Err(GgError::Other(
+                    "`--from` can't be combined with a `--base` stack: a dependent stack starts from its parent".to_string(),
+                ));
+            }
+            let base_branch = parent
+                .as_deref()
+                .map(|p| git::format_stack_branch(&username, p))
+                .or(base)
                 .or_else(|| config.defaults.base.clone())
                 .or_else(|| git::find_base_branch(&repo).ok())
                 .ok_or(GgError::NoBaseBranch)?;
 
-            // Find the base commit
-            let base_ref = repo
-                .revparse_single(&base_branch)
-                .or_else(|_| repo.revparse_single(&format!("origin/{}", base_branch)))
-                .map_err(|_| GgError::NoBaseBranch)?;
-            let base_commit = base_ref.peel_to_commit()?;
+            // Find the base commit. A stack on another remote starts from
+            // that remote's base, fetched fresh.
+            let base_commit = match &from {
+                Some(from) => repo
+                    .revparse_single(from)
+                    .and_then(|o| o.peel_to_commit())
+                    .map_err(|_| {
+                        GgError::Other(format!("Cannot find '{}' to start the stack from", from))
+                    })?,
+                None => match remote.as_deref().filter(|_| parent.is_none()) {
+                    Some(remote) => {
+                        let _ = git::fetch_and_prune(remote);
+                        repo.revparse_single(&format!("{}/{}", remote, base_branch))
+                            .or_else(|_| repo.revparse_single(&base_branch))
+                    }
+                    None => git::resolve_base(
+                        &repo,
+                        parent
+                            .as_deref()
+                            .map_or(git::DEFAULT_REMOTE, |p| config.get_remote_for_stack(p)),
+                        &base_branch,
+                    ),
+                }
+                .map_err(|_| GgError::NoBaseBranch)?
+                .peel_to_commit()?,
+            };
 
             // Create the branch
             repo.branch(&branch_name, &base_commit, false)?;
@@ -251,9 +305,20 @@ pub fn run(stack_name: Option<String>, base: Option<String>, use_worktree: bool)
                 .unwrap_or("main")
                 .to_string();
             let stack_config = config.get_or_create_stack(&stack_name);
-            if base_branch != default_base {
+            if parent.is_some() {
+                stack_config.parent = parent.clone();
+            } else if base_branch != default_base {
                 stack_config.base = Some(base_branch.clone());
             }
+            stack_config.remote = remote.filter(|r| r != git::DEFAULT_REMOTE);
+            // Refs (tags) are kept by name, commits by their full SHA
+            stack_config.from = from.as_deref().map(|from| {
+                if repo.resolve_reference_from_short_name(from).is_ok() {
+                    from.to_string()
+                } else {
+                    base_commit.id().to_string()
+                }
+            });
 
             // Save username if not already set
             if config.defaults.branch_username.is_none() {
@@ -262,28 +327,51 @@ pub fn run(stack_name: Option<String>, base: Option<String>, use_worktree: bool)
 
             config.save(git_dir)?;
 
+            let based_on = match &parent {
+                Some(parent) => format!("on top of stack {}", style(parent).cyan()),
+                None if from.is_some() => format!(
+                    "from {} (PRs/MRs target {})",
+                    style(from.as_deref().unwrap_or_default()).yellow(),
+                    style(&base_branch).yellow()
+                ),
+                None => match config
+                    .get_stack(&stack_name)
+                    .and_then(|s| s.remote.as_deref())
+                {
+                    Some(remote) => format!(
+                        "based on {}",
+                        style(format!("{}/{}", remote, base_branch)).yellow()
+                    ),
+                    None => format!("based on {}", style(&base_branch).yellow()),
+                },
+            };
             if use_worktree {
                 let worktree_path =
                     ensure_stack_worktree(&repo, &mut config, &stack_name, &branch_name)?;
                 request_shell_cd(&worktree_path);
                 println!(
-                    "{} Created stack {} based on {} in worktree {}",
+                    "{} Created stack {} {} in worktree {}",
                     style("OK").green().bold(),
                     style(&stack_name).cyan(),
-                    style(&base_branch).yellow(),
+                    based_on,
                     style(worktree_path.display()).yellow()
                 );
             } else {
                 println!(
-                    "{} Created stack {} based on {}",
+                    "{} Created stack {} {}",
                     style("OK").green().bold(),
                     style(&stack_name).cyan(),
-                    style(&base_branch).yellow()
+                    based_on
                 );
             }
         }
     }
 
+    if description.is_some() {
+        super::describe::set_description(&repo, &mut config, &stack_name, description.as_deref())?;
+        config.save(git_dir)?;
+    }
+
     guard.finalize_with_scope(
         &repo,
         &config,
@@ -302,9 +390,8 @@ pub(crate) fn ensure_stack_worktree(
     branch_name: &str,
 ) -> Result<std::path::PathBuf> {
     let git_dir = repo.commondir();
-    let repo_root = repo
-        .workdir()
-        .ok_or_else(|| GgError::Other("Repository has no working directory".to_string()))?;
+    // Paths are relative to the main worktree, also when run from a linked one
+    let repo_root = &super::worktree::main_worktree_root(repo)?;
 
     let maybe_existing = config
         .get_stack(stack_name)
@@ -344,7 +431,31 @@ pub(crate) fn ensure_stack_worktree(
     Ok(target_path)
 }
 
-fn request_shell_cd(path: &Path) {
+/// Branch `from` names: a local branch, or one of `remote` (default
+/// `origin`) as `<remote>/<branch>`.
+fn from_branch(repo: &git2::Repository, from: &str, remote: Option<&str>) -> Option<String> {
+    if repo.find_branch(from, BranchType::Local).is_ok() {
+        return Some(from.to_string());
+    }
+    let remote = remote.unwrap_or(git::DEFAULT_REMOTE);
+    from.strip_prefix(&format!("{}/", remote))
+        .filter(|_| repo.find_branch(from, BranchType::Remote).is_ok())
+        .map(str::to_string)
+}
+
+/// Name of the user's stack `base` refers to (`name` or `user/name`), if any.
+fn parent_stack_name(repo: &git2::Repository, username: &str, base: &str) -> Option<String> {
+    let name = match git::parse_stack_branch(base) {
+        Some((user, name)) if user == username => name,
+        _ => base.to_string(),
+    };
+    let branch = git::format_stack_branch(username, &name);
+    repo.find_branch(&branch, BranchType::Local)
+        .is_ok()
+        .then_some(name)
+}
+
+pub(crate) fn request_shell_cd(path: &Path) {
     let Ok(cd_file) = std::env::var("GG_CD_FILE") else {
         return;
     };
@@ -414,7 +525,7 @@ fn find_remote_entry_branch(
 /// Check if a stack exists on remote (either main branch or entry branches)
 fn check_remote_stack_exists(repo: &git2::Repository, username: &str, stack_name: &str) -> bool {
     // Check for main stack branch
-    let remote_branch = format!("origin/{}/{}", username, stack_name);
+    let remote_branch = format!("origin/{}", git::format_stack_branch(username, stack_name));
     if repo.revparse_single(&remote_branch).is_ok() {
         return true;
     }
@@ -449,7 +560,7 @@ fn import_pr_mappings_for_remote_stack(
     stack_name: &str,
 ) -> Result<()> {
     // Detect and check provider
-    let provider = Provider::detect(repo)?;
+    let provider = Provider::for_stack(repo, config, stack_name)?;
 
     // Only attempt to import if provider tools are installed and authenticated
     // If they're not, just skip silently - sync will handle it later
@@ -528,7 +639,7 @@ fn import_pr_mappings_for_remote_stack(
         config.save(git_dir)?;
         println!(
             "{} Imported {} {} mapping(s) for stack {}",
-            style("→").cyan(),
+            style(plain::arrow()).cyan(),
             imported_count,
             provider.pr_label(),
             style(stack_name).cyan()
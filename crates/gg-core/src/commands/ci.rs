@@ -0,0 +1,77 @@
+//! `gg ci` - CI helpers for stack entries
+
+use console::style;
+
+use crate::config::Config;
+use crate::error::{GgError, Result};
+use crate::git;
+use crate::output::{print_json, CiRerunResponse, OUTPUT_VERSION};
+use crate::provider::Provider;
+use crate::stack::{self, Stack};
+
+/// Run `gg ci rerun <target>`: re-run CI for the entry's PR/MR.
+pub fn run_rerun(target: String, failed_only: bool, json: bool) -> Result<()> {
+    let repo = git::open_repo()?;
+    let config = Config::load(repo.commondir())?;
+    let stack = Stack::load_active(&repo, &config)?;
+
+    let pos = stack::resolve_target(&stack, &target)?;
+    let entry = stack
+        .get_entry_by_position(pos)
+        .ok_or_else(|| GgError::Other(format!("No entry at position {}", pos)))?;
+
+    let provider = Provider::for_stack(&repo, &config, &stack.name)?;
+    let pr_number = entry.mr_number.ok_or_else(|| {
+        GgError::Other(format!(
+            "Entry {} has no {}. Run `gg sync` first.",
+            pos,
+            provider.pr_label()
+        ))
+    })?;
+    provider.check_installed()?;
+    provider.check_auth()?;
+
+    let restarted = provider.rerun_ci(pr_number, failed_only)?;
+
+    if json {
+        print_json(&CiRerunResponse {
+            version: OUTPUT_VERSION,
+            position: pos,
+            pr_number,
+            failed_only,
+            restarted,
+        });
+        return Ok(());
+    }
+
+    let pr = format!(
+        "{} {}{}",
+        provider.pr_label(),
+        provider.pr_number_prefix(),
+        pr_number
+    );
+    if restarted.is_empty() {
+        println!(
+            "{}",
+            style(format!(
+                "Nothing to re-run for {} ({}).",
+                pr,
+                if failed_only {
+                    "no failed runs"
+                } else {
+                    "no finished runs"
+                }
+            ))
+            .dim()
+        );
+    } else {
+        println!(
+            "{} Re-running CI for {}: {}",
+            style("OK").green().bold(),
+            pr,
+            restarted.join(", ")
+        );
+    }
+
+    Ok(())
+}
@@ -2,22 +2,27 @@
 
 use console::style;
 use dialoguer::Confirm;
-use git2::{BranchType, Repository};
+use git2::{BranchType, Oid, Repository};
 use std::path::Path;
 
-use crate::config::Config;
+use crate::commands::archive::archive_stack;
+use crate::config::{CleanMode, Config};
 use crate::error::{GgError, Result};
 use crate::git;
+use crate::hooks::GitRewrite;
+use crate::notes;
 use crate::operations::{OperationKind, RemoteEffect, SnapshotScope};
-use crate::output::{print_json, CleanResponse, CleanResultJson, OUTPUT_VERSION};
+use crate::output::{print_json, CleanResponse, CleanResultJson, CleanStackJson, OUTPUT_VERSION};
+use crate::plain;
 use crate::provider::{PrState, Provider};
+use crate::relative_time;
 use crate::stack;
 
 /// Run the clean command for a specific stack (used by auto-clean)
 #[allow(dead_code)]
 pub fn run_for_stack(stack_name: &str, force: bool) -> Result<()> {
     let repo = git::open_repo()?;
-    let config = Config::load_with_global(repo.commondir())?;
+    let config = Config::load(repo.commondir())?;
 
     // Acquire operation lock + record a Pending op for the undo log.
     let (_lock, mut guard) = git::acquire_operation_lock_and_record(
@@ -74,10 +79,10 @@ fn run_for_stack_with_repo_options(
     record_remote_effect: &mut dyn FnMut(RemoteEffect),
 ) -> Result<()> {
     let git_dir = repo.commondir();
-    let mut config = Config::load_with_global(git_dir)?;
+    let mut config = Config::load(git_dir)?;
 
     // Detect provider (best-effort)
-    let provider = Provider::detect(repo).ok();
+    let provider = Provider::for_stack(repo, &config, stack_name).ok();
 
     // Get username
     let username = config
@@ -103,6 +108,17 @@ fn run_for_stack_with_repo_options(
     }
 
     let _ = maybe_remove_configured_worktree(repo, &mut config, stack_name, false)?;
+    let old_tip = stack_tip(repo, &branch_name);
+    if config.get_clean_mode() == CleanMode::Archive {
+        if let Some(reference) = archive_stack(repo, &config, stack_name, &username)? {
+            println!(
+                "{} Archived stack '{}' as {}",
+                style("OK").green().bold(),
+                stack_name,
+                style(reference).dim()
+            );
+        }
+    }
 
     // Delete local branch
     if let Ok(mut branch) = repo.find_branch(&branch_name, BranchType::Local) {
@@ -189,7 +205,9 @@ fn run_for_stack_with_repo_options(
     );
 
     // Remove from config
+    reparent_dependents(repo, &mut config, &username, stack_name, old_tip, false);
     config.remove_stack(stack_name);
+    let _ = notes::write_stack_description(repo, stack_name, None);
 
     // Save updated config
     config.save(git_dir)?;
@@ -197,29 +215,43 @@ fn run_for_stack_with_repo_options(
     Ok(())
 }
 
+/// Options for `gg clean`
+#[derive(Debug, Default)]
+pub struct CleanOptions {
+    /// Clean without prompting
+    pub all: bool,
+    pub json: bool,
+    /// Only report what would be deleted or skipped and why
+    pub dry_run: bool,
+    /// Keep stack branches under `refs/gg/archive/` instead of deleting
+    /// them (also `defaults.clean_mode: archive`)
+    pub archive: bool,
+    /// Also clean unmerged stacks untouched for this many days whose
+    /// PRs/MRs are all closed, or that have none
+    pub stale_days: Option<u64>,
+}
+
 /// Run the clean command
-pub fn run(clean_all: bool, json: bool) -> Result<()> {
+pub fn run(options: CleanOptions) -> Result<()> {
+    let CleanOptions {
+        all: clean_all,
+        json,
+        dry_run,
+        archive,
+        stale_days,
+    } = options;
     let repo = git::open_repo()?;
 
-    if json && !clean_all {
-        crate::output::print_json_error(
-            "--json requires --all (cannot show interactive prompts in JSON mode)",
-        );
+    if json && !clean_all && !dry_run {
+        crate::output::print_json_error(&GgError::Other(
+            "--json requires --all (cannot show interactive prompts in JSON mode)".to_string(),
+        ));
         std::process::exit(1);
     }
 
     let git_dir = repo.commondir();
-    let mut config = Config::load_with_global(git_dir)?;
-
-    // Acquire operation lock + record a Pending op for the undo log.
-    let (_lock, mut guard) = git::acquire_operation_lock_and_record(
-        &repo,
-        &config,
-        OperationKind::Clean,
-        std::env::args().skip(1).collect(),
-        None,
-        SnapshotScope::AllUserBranches,
-    )?;
+    let mut config = Config::load(git_dir)?;
+    let archive = archive || config.get_clean_mode() == CleanMode::Archive;
 
     // Detect provider (best-effort).
     // Some repos (e.g. local remotes in tests) won't match GitHub/GitLab.
@@ -238,15 +270,50 @@ pub fn run(clean_all: bool, json: bool) -> Result<()> {
     // Get all stacks
     let stacks = stack::list_all_stacks(&repo, &config, &username)?;
 
+    if dry_run {
+        let mut decisions = Vec::with_capacity(stacks.len());
+        for stack_name in &stacks {
+            decisions.push((
+                stack_name.as_str(),
+                plan_stack(
+                    &repo,
+                    &config,
+                    stack_name,
+                    &username,
+                    provider.as_ref(),
+                    stale_days,
+                )?,
+            ));
+        }
+        print_dry_run(&decisions, json, archive);
+        return Ok(());
+    }
+
+    // Acquire operation lock + record a Pending op for the undo log.
+    let (_lock, mut guard) = git::acquire_operation_lock_and_record(
+        &repo,
+        &config,
+        OperationKind::Clean,
+        std::env::args().skip(1).collect(),
+        None,
+        SnapshotScope::AllUserBranches,
+    )?;
+
     let mut cleaned: Vec<String> = Vec::new();
     let mut skipped: Vec<String> = Vec::new();
+    let mut report: Vec<CleanStackJson> = Vec::new();
     let mut remote_effects = Vec::new();
 
     if stacks.is_empty() {
         if json {
             print_json(&CleanResponse {
                 version: OUTPUT_VERSION,
-                clean: CleanResultJson { cleaned, skipped },
+                clean: CleanResultJson {
+                    cleaned,
+                    skipped,
+                    dry_run: false,
+                    stacks: report,
+                },
             });
         } else {
             println!("{}", style("No stacks to clean.").dim());
@@ -262,11 +329,17 @@ pub fn run(clean_all: bool, json: bool) -> Result<()> {
     }
 
     for stack_name in &stacks {
-        // Try to determine if stack is fully merged
         let branch_name = git::format_stack_branch(&username, stack_name);
+        let decision = plan_stack(
+            &repo,
+            &config,
+            stack_name,
+            &username,
+            provider.as_ref(),
+            stale_days,
+        )?;
 
-        // Check if the branch exists
-        if repo.find_branch(&branch_name, BranchType::Local).is_err() {
+        if decision.reason == CleanReason::BranchMissing {
             // Branch doesn't exist: clean LOCAL orphan entry branches and config.
             // Be conservative: do NOT delete remote branches here because we can't
             // reliably verify merge status without the main stack branch.
@@ -279,24 +352,36 @@ pub fn run(clean_all: bool, json: bool) -> Result<()> {
                 /*silent=*/ json,
                 &mut |_| {},
             );
+            reparent_dependents(&repo, &mut config, &username, stack_name, None, json);
             config.remove_stack(stack_name);
+            let _ = notes::write_stack_description(&repo, stack_name, None);
+            report.push(decision.to_json(stack_name, archive));
             cleaned.push(stack_name.clone());
             continue;
         }
 
-        // Load the stack to check MR status
-        let merge_status =
-            check_stack_merged(&repo, &config, stack_name, &username, provider.as_ref())?;
-
-        if merge_status.merged {
+        if decision.delete {
             if !clean_all && !json {
+                let stale = decision.reason == CleanReason::Stale;
+                let prompt = if stale {
+                    format!(
+                        "Delete stale stack '{}' ({})? ",
+                        stack_name, decision.detail
+                    )
+                } else {
+                    format!("Delete merged stack '{}'? ", stack_name)
+                };
                 let confirm = Confirm::new()
-                    .with_prompt(format!("Delete merged stack '{}'? ", stack_name))
-                    .default(true)
+                    .with_prompt(prompt)
+                    .default(!stale)
                     .interact()
                     .unwrap_or(false);
 
                 if !confirm {
+                    report.push(
+                        CleanDecision::skip(CleanReason::Declined, "declined at the prompt")
+                            .to_json(stack_name, archive),
+                    );
                     skipped.push(stack_name.clone());
                     continue;
                 }
@@ -305,12 +390,20 @@ pub fn run(clean_all: bool, json: bool) -> Result<()> {
             let removed_or_not_configured =
                 maybe_remove_configured_worktree(&repo, &mut config, stack_name, json)?;
             if json && !removed_or_not_configured {
-                skipped.push(format!(
-                    "{} (worktree not removed: confirmation defaults to false in --json mode)",
-                    stack_name
-                ));
+                let detail = "worktree not removed: confirmation defaults to false in --json mode";
+                report.push(
+                    CleanDecision::skip(CleanReason::WorktreeKept, detail)
+                        .to_json(stack_name, archive),
+                );
+                skipped.push(format!("{} ({})", stack_name, detail));
                 continue;
             }
+            let old_tip = stack_tip(&repo, &branch_name);
+            let archive_ref = if archive {
+                archive_stack(&repo, &config, stack_name, &username)?
+            } else {
+                None
+            };
 
             // Delete local branch
             if let Ok(mut branch) = repo.find_branch(&branch_name, BranchType::Local) {
@@ -379,7 +472,7 @@ pub fn run(clean_all: bool, json: bool) -> Result<()> {
                 }
             }
 
-            let allow_remote_delete = merge_status.verified;
+            let allow_remote_delete = decision.verified;
             if !allow_remote_delete && !json {
                 println!(
                     "{} Skipping remote branch deletion for '{}' because merge verification is unavailable.",
@@ -403,24 +496,40 @@ pub fn run(clean_all: bool, json: bool) -> Result<()> {
             );
 
             // Remove from config
+            reparent_dependents(&repo, &mut config, &username, stack_name, old_tip, json);
             config.remove_stack(stack_name);
+            let _ = notes::write_stack_description(&repo, stack_name, None);
 
             if !json {
-                println!(
-                    "{} Deleted stack '{}'",
-                    style("OK").green().bold(),
-                    stack_name
-                );
+                match &archive_ref {
+                    Some(reference) => println!(
+                        "{} Archived stack '{}' as {}",
+                        style("OK").green().bold(),
+                        stack_name,
+                        style(reference).dim()
+                    ),
+                    None => println!(
+                        "{} Deleted stack '{}'",
+                        style("OK").green().bold(),
+                        stack_name
+                    ),
+                }
             }
+            report.push(CleanStackJson {
+                archive_ref,
+                ..decision.to_json(stack_name, archive)
+            });
             cleaned.push(stack_name.clone());
         } else {
             if !json {
                 println!(
-                    "{} Stack '{}' has unmerged commits, skipping",
-                    style("○").yellow(),
-                    stack_name
+                    "{} Stack '{}' skipped: {}",
+                    style(plain::pending()).yellow(),
+                    stack_name,
+                    decision.detail
                 );
             }
+            report.push(decision.to_json(stack_name, archive));
             skipped.push(stack_name.clone());
         }
     }
@@ -431,7 +540,12 @@ pub fn run(clean_all: bool, json: bool) -> Result<()> {
     if json {
         print_json(&CleanResponse {
             version: OUTPUT_VERSION,
-            clean: CleanResultJson { cleaned, skipped },
+            clean: CleanResultJson {
+                cleaned,
+                skipped,
+                dry_run: false,
+                stacks: report,
+            },
         });
     } else if !cleaned.is_empty() {
         println!();
@@ -456,6 +570,165 @@ pub fn run(clean_all: bool, json: bool) -> Result<()> {
     Ok(())
 }
 
+/// Report what `gg clean` would delete or skip, and why, without touching
+/// branches, worktrees or config.
+fn print_dry_run(decisions: &[(&str, CleanDecision)], json: bool, archive: bool) {
+    if json {
+        let names = |delete: bool| {
+            decisions
+                .iter()
+                .filter(|(_, d)| d.delete == delete)
+                .map(|(name, _)| name.to_string())
+                .collect()
+        };
+        print_json(&CleanResponse {
+            version: OUTPUT_VERSION,
+            clean: CleanResultJson {
+                cleaned: names(true),
+                skipped: names(false),
+                dry_run: true,
+                stacks: decisions
+                    .iter()
+                    .map(|(name, d)| d.to_json(name, archive))
+                    .collect(),
+            },
+        });
+        return;
+    }
+
+    if decisions.is_empty() {
+        println!("{}", style("No stacks to clean.").dim());
+        return;
+    }
+    for (stack_name, decision) in decisions {
+        if decision.delete {
+            println!(
+                "{} Would {} '{}': {}",
+                style(plain::fail()).red(),
+                if decision.archives(archive) {
+                    "archive"
+                } else {
+                    "delete"
+                },
+                stack_name,
+                decision.detail
+            );
+        } else {
+            println!(
+                "{} Would skip '{}': {}",
+                style(plain::pending()).yellow(),
+                stack_name,
+                decision.detail
+            );
+        }
+    }
+    println!();
+    println!("{}", style("Dry run: nothing was deleted.").dim());
+}
+
+fn stack_tip(repo: &Repository, branch_name: &str) -> Option<Oid> {
+    repo.find_branch(branch_name, BranchType::Local)
+        .ok()
+        .and_then(|b| b.get().target())
+}
+
+/// Hand the stacks stacked on `stack_name` over to its own parent/base before
+/// it is removed, and move their commits off the parent's old commits
+/// (`old_tip`) onto that new base. Failed restacks are reported with the
+/// command to finish them by hand.
+fn reparent_dependents(
+    repo: &Repository,
+    config: &mut Config,
+    username: &str,
+    stack_name: &str,
+    old_tip: Option<Oid>,
+    silent: bool,
+) {
+    let children = config.dependent_stacks(stack_name);
+    if children.is_empty() {
+        return;
+    }
+    let (new_parent, new_base) = config
+        .get_stack(stack_name)
+        .map(|s| (s.parent.clone(), s.base.clone()))
+        .unwrap_or_default();
+    let original_branch = git::current_branch_name(repo);
+
+    for child in children {
+        let child_config = config.get_or_create_stack(&child);
+        child_config.parent = new_parent.clone();
+        if child_config.base.is_none() {
+            child_config.base = new_base.clone();
+        }
+
+        let child_branch = git::format_stack_branch(username, &child);
+        let Some(old_tip) = old_tip.map(|oid| oid.to_string()) else {
+            continue;
+        };
+        if git::run_git_command(&["merge-base", "--is-ancestor", &old_tip, &child_branch]).is_err()
+        {
+            continue;
+        }
+        let Ok(base) = stack::resolve_stack_base(repo, config, username, &child) else {
+            continue;
+        };
+        let remote_base = format!("{}/{}", config.get_remote_for_stack(&child), base);
+        let onto = if git::parse_stack_branch(&base).is_none()
+            && repo.revparse_single(&remote_base).is_ok()
+        {
+            remote_base
+        } else {
+            base
+        };
+
+        match git::run_rewrite_command(
+            GitRewrite::Replay,
+            &["rebase", "--onto", &onto, &old_tip, &child_branch],
+        ) {
+            Ok(_) => {
+                if !silent {
+                    println!(
+                        "{} Restacked {} onto {}",
+                        style("OK").green().bold(),
+                        style(&child).cyan(),
+                        onto
+                    );
+                }
+            }
+            Err(e) => {
+                if git::is_rebase_in_progress(repo) {
+                    let _ = git::run_git_command(&["rebase", "--abort"]);
+                }
+                if !silent {
+                    println!(
+                        "{} Could not restack {} onto {}: {}",
+                        style("Warning:").yellow(),
+                        child,
+                        onto,
+                        e
+                    );
+                    println!(
+                        "  Finish it with: git rebase --onto {} {} {}",
+                        onto, old_tip, child_branch
+                    );
+                }
+            }
+        }
+    }
+
+    if let Some(branch) = original_branch {
+        if git::current_branch_name(repo).as_ref() != Some(&branch) {
+            let _ = git::run_git_command(&["checkout", &branch]);
+        }
+    }
+}
+
+/// Remove the worktree configured for a cleaned stack. Returns false when
+/// it is kept.
+///
+/// With `defaults.always_worktree` the worktree was gg's to create, so a
+/// clean one goes without asking; otherwise (and for worktrees with
+/// uncommitted changes) the user confirms, which `silent` declines.
 fn maybe_remove_configured_worktree(
     repo: &Repository,
     config: &mut Config,
@@ -469,12 +742,37 @@ fn maybe_remove_configured_worktree(
         return Ok(true);
     };
 
-    let confirm = if silent {
+    let repo_root = super::worktree::main_worktree_root(repo)?;
+
+    // Already deleted by hand: just forget it
+    if !Path::new(&worktree_path).exists() {
+        let _ = std::process::Command::new("git")
+            .args(["worktree", "prune"])
+            .current_dir(&repo_root)
+            .output();
+        if let Some(stack_cfg_mut) = config.stacks.get_mut(stack_name) {
+            stack_cfg_mut.worktree_path = None;
+        }
+        return Ok(true);
+    }
+
+    let dirty = Repository::open(&worktree_path)
+        .ok()
+        .is_some_and(|r| !git::is_working_directory_clean(&r).unwrap_or(true));
+    let confirm = if config.defaults.always_worktree && !dirty {
+        true
+    } else if silent {
         false
     } else {
         let prompt = format!(
-            "Stack '{}' has an associated worktree at '{}'. Remove it?",
-            stack_name, worktree_path
+            "Stack '{}' has an associated worktree at '{}'{}. Remove it?",
+            stack_name,
+            worktree_path,
+            if dirty {
+                " with uncommitted changes"
+            } else {
+                ""
+            }
         );
         Confirm::new()
             .with_prompt(prompt)
@@ -494,16 +792,13 @@ fn maybe_remove_configured_worktree(
         return Ok(false);
     }
 
-    let repo_root = repo
-        .workdir()
-        .ok_or_else(|| GgError::Other("Repository has no working directory".to_string()))?;
-
+    let worktree_dir = std::fs::canonicalize(&worktree_path)?;
     let output = std::process::Command::new("git")
         .arg("worktree")
         .arg("remove")
         .arg(&worktree_path)
         .arg("--force")
-        .current_dir(repo_root)
+        .current_dir(&repo_root)
         .output()?;
 
     if !output.status.success() {
@@ -519,7 +814,12 @@ fn maybe_remove_configured_worktree(
         stack_cfg_mut.worktree_path = None;
     }
 
-    if !silent && !Path::new(&worktree_path).exists() {
+    // Don't leave the shell in a directory that no longer exists
+    if std::env::current_dir().is_ok_and(|cwd| cwd.starts_with(&worktree_dir)) {
+        super::checkout::request_shell_cd(&repo_root);
+    }
+
+    if !silent && !worktree_dir.exists() {
         println!(
             "{} Removed worktree '{}'.",
             style("OK").green().bold(),
@@ -535,6 +835,208 @@ struct MergeStatus {
     merged: bool,
     /// True when merge state was verified via provider checks.
     verified: bool,
+    reason: CleanReason,
+}
+
+/// Why `gg clean` deletes or keeps a stack.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum CleanReason {
+    /// The stack branch is gone; only entry branches and config are left
+    BranchMissing,
+    /// The provider reports every PR/MR as merged
+    AllPrsMerged,
+    /// The stack branch is an ancestor of the base branch
+    MergedLocally,
+    /// A PR/MR could not be fetched; the stack is only deleted when it is
+    /// merged locally, and its remote branches are kept
+    ProviderCheckFailed,
+    OpenPrsRemain,
+    Unmerged,
+    /// The stack's worktree has uncommitted changes
+    WorktreeDirty,
+    /// Unmerged, but untouched for the `--stale` age with no open PRs/MRs
+    Stale,
+    /// Answered no at the prompt
+    Declined,
+    /// `--json` cannot confirm removing the stack's worktree
+    WorktreeKept,
+}
+
+impl CleanReason {
+    fn as_str(self) -> &'static str {
+        match self {
+            CleanReason::BranchMissing => "branch_missing",
+            CleanReason::AllPrsMerged => "all_prs_merged",
+            CleanReason::MergedLocally => "merged_locally",
+            CleanReason::ProviderCheckFailed => "provider_check_failed",
+            CleanReason::OpenPrsRemain => "open_prs_remain",
+            CleanReason::Unmerged => "unmerged",
+            CleanReason::WorktreeDirty => "worktree_dirty",
+            CleanReason::Stale => "stale",
+            CleanReason::Declined => "declined",
+            CleanReason::WorktreeKept => "worktree_kept",
+        }
+    }
+}
+
+/// What `gg clean` does with one stack, and why.
+#[derive(Debug, Clone)]
+struct CleanDecision {
+    delete: bool,
+    reason: CleanReason,
+    detail: String,
+    /// Merge verified, so remote entry branches may be deleted too
+    verified: bool,
+}
+
+impl CleanDecision {
+    fn skip(reason: CleanReason, detail: impl Into<String>) -> Self {
+        CleanDecision {
+            delete: false,
+            reason,
+            detail: detail.into(),
+            verified: false,
+        }
+    }
+
+    /// The stack branch is kept under `refs/gg/archive/` rather than deleted
+    fn archives(&self, archive: bool) -> bool {
+        archive && self.delete && self.reason != CleanReason::BranchMissing
+    }
+
+    fn to_json(&self, stack_name: &str, archive: bool) -> CleanStackJson {
+        let action = if self.archives(archive) {
+            "archive"
+        } else if self.delete {
+            "delete"
+        } else {
+            "skip"
+        };
+        CleanStackJson {
+            name: stack_name.to_string(),
+            action: action.to_string(),
+            reason: self.reason.as_str().to_string(),
+            detail: self.detail.clone(),
+            archive_ref: None,
+        }
+    }
+}
+
+/// Decide whether `stack_name` is deleted, without touching anything.
+///
+/// With `stale_days`, an unmerged stack without open PRs/MRs is deleted too
+/// once it has gone that many days without changes.
+fn plan_stack(
+    repo: &Repository,
+    config: &Config,
+    stack_name: &str,
+    username: &str,
+    provider: Option<&Provider>,
+    stale_days: Option<u64>,
+) -> Result<CleanDecision> {
+    let branch_name = git::format_stack_branch(username, stack_name);
+    if repo.find_branch(&branch_name, BranchType::Local).is_err() {
+        return Ok(CleanDecision {
+            delete: true,
+            reason: CleanReason::BranchMissing,
+            detail: format!(
+                "branch '{}' is gone; only its local entry branches and config are left",
+                branch_name
+            ),
+            verified: false,
+        });
+    }
+
+    let merge_status = check_stack_merged(repo, config, stack_name, username, provider)?;
+    let remote_note = if merge_status.verified {
+        ""
+    } else {
+        "; remote branches kept (merge not verified)"
+    };
+    let detail = match (merge_status.reason, merge_status.merged) {
+        (CleanReason::AllPrsMerged, _) => "all PRs/MRs merged".to_string(),
+        (CleanReason::MergedLocally, _) => format!("branch merged locally{}", remote_note),
+        (CleanReason::ProviderCheckFailed, true) => {
+            "provider check failed, but the branch is merged locally; remote branches kept"
+                .to_string()
+        }
+        (CleanReason::ProviderCheckFailed, false) => {
+            "provider check failed and the branch is not merged locally".to_string()
+        }
+        (CleanReason::OpenPrsRemain, _) => "open PRs/MRs remain".to_string(),
+        _ => "unmerged commits".to_string(),
+    };
+    let mut decision = CleanDecision {
+        delete: merge_status.merged,
+        reason: merge_status.reason,
+        detail,
+        verified: merge_status.verified,
+    };
+    // Only stacks whose PRs/MRs are all closed, or that have none, can go
+    // stale; a failed provider check proves neither
+    if let (Some(days), false, CleanReason::Unmerged) =
+        (stale_days, merge_status.merged, merge_status.reason)
+    {
+        if let Some(touched) = stack::last_touched_ms(repo, config, username, stack_name) {
+            let now = relative_time::now_ms();
+            let age_days = now.saturating_sub(touched) / DAY_MS;
+            if age_days >= days {
+                decision = CleanDecision {
+                    delete: true,
+                    reason: CleanReason::Stale,
+                    detail: format!(
+                        "untouched for {} days and no open PRs/MRs; remote branches kept",
+                        age_days
+                    ),
+                    verified: false,
+                };
+            } else {
+                decision.detail = format!(
+                    "{}, touched {}",
+                    decision.detail,
+                    relative_time::format(touched, now)
+                );
+            }
+        }
+    }
+    if !decision.delete {
+        return Ok(decision);
+    }
+
+    // `git worktree remove --force` would throw uncommitted work away
+    let dirty_worktree = config
+        .get_stack(stack_name)
+        .and_then(|s| s.worktree_path.as_deref())
+        .filter(|path| {
+            Repository::open(path)
+                .ok()
+                .and_then(|wt| git::is_working_directory_clean(&wt).ok())
+                == Some(false)
+        });
+    if let Some(path) = dirty_worktree {
+        return Ok(CleanDecision::skip(
+            CleanReason::WorktreeDirty,
+            format!("worktree '{}' has uncommitted changes", path),
+        ));
+    }
+
+    Ok(decision)
+}
+
+const DAY_MS: u64 = 24 * 60 * 60 * 1000;
+
+/// Parse a `--stale` age: `30d`, `2w`, or a plain number of days.
+pub fn parse_age_days(value: &str) -> std::result::Result<u64, String> {
+    let value = value.trim();
+    let (number, unit_days) = match value.chars().last() {
+        Some('d') => (&value[..value.len() - 1], 1),
+        Some('w') => (&value[..value.len() - 1], 7),
+        _ => (value, 1),
+    };
+    number
+        .parse::<u64>()
+        .map(|n| n * unit_days)
+        .map_err(|_| format!("invalid age '{}': use e.g. 30d, 2w or 30", value))
 }
 
 fn should_delete_remote_branches(merge_status: MergeStatus, merge_verified_by_land: bool) -> bool {
@@ -567,6 +1069,8 @@ fn check_stack_merged(
     if let Some(stack_config) = config.get_stack(stack_name) {
         if !stack_config.mrs.is_empty() {
             if let Some(provider) = provider {
+                // The stack's PRs/MRs live behind its own remote
+                git::set_provider_remote(config.get_remote_for_stack(stack_name));
                 let mut all_provider_merged = true;
 
                 for (gg_id, mr_num) in &stack_config.mrs {
@@ -616,6 +1120,7 @@ fn check_stack_merged(
                     return Ok(MergeStatus {
                         merged: false,
                         verified: true,
+                        reason: CleanReason::OpenPrsRemain,
                     });
                 }
 
@@ -629,6 +1134,7 @@ fn check_stack_merged(
                     return Ok(MergeStatus {
                         merged: true,
                         verified: true,
+                        reason: CleanReason::AllPrsMerged,
                     });
                 }
 
@@ -649,6 +1155,11 @@ fn check_stack_merged(
         return Ok(MergeStatus {
             merged: false,
             verified: false,
+            reason: if had_provider_error {
+                CleanReason::ProviderCheckFailed
+            } else {
+                CleanReason::Unmerged
+            },
         });
     }
 
@@ -661,6 +1172,7 @@ fn check_stack_merged(
         return Ok(MergeStatus {
             merged: true,
             verified: false,
+            reason: CleanReason::ProviderCheckFailed,
         });
     }
 
@@ -672,6 +1184,7 @@ fn check_stack_merged(
         return Ok(MergeStatus {
             merged: true,
             verified: false,
+            reason: CleanReason::MergedLocally,
         });
     }
 
@@ -702,6 +1215,7 @@ fn check_stack_merged(
     Ok(MergeStatus {
         merged: true,
         verified: remote_verified,
+        reason: CleanReason::MergedLocally,
     })
 }
 
@@ -722,9 +1236,13 @@ fn is_stack_branch_ancestor_of_base(
 
     // Check if stack branch is ancestor of base (fully merged)
     let stack_ref = repo.revparse_single(&branch_name)?;
-    let base_ref = repo
-        .revparse_single(&base)
-        .or_else(|_| repo.revparse_single(&format!("origin/{}", base)))?;
+    let base_ref = repo.revparse_single(&base).or_else(|_| {
+        repo.revparse_single(&format!(
+            "{}/{}",
+            config.get_remote_for_stack(stack_name),
+            base
+        ))
+    })?;
 
     let stack_oid = stack_ref.id();
     let base_oid = base_ref.id();
@@ -732,7 +1250,7 @@ fn is_stack_branch_ancestor_of_base(
     Ok(repo.merge_base(stack_oid, base_oid)? == stack_oid)
 }
 
-/// Check if stack branch is an ancestor of the remote base branch (origin/<base>).
+/// Check if stack branch is an ancestor of the remote base branch (`<remote>/<base>`).
 /// This provides stronger verification that the merge is complete on the server.
 fn is_stack_branch_ancestor_of_remote_base(
     repo: &git2::Repository,
@@ -753,8 +1271,8 @@ fn is_stack_branch_ancestor_of_remote_base(
     let stack_ref = repo.revparse_single(&branch_name)?;
     let stack_oid = stack_ref.id();
 
-    // Try to get origin/<base> - if it doesn't exist, we can't verify
-    let remote_base = format!("origin/{}", base);
+    // Try to get <remote>/<base> - if it doesn't exist, we can't verify
+    let remote_base = format!("{}/{}", config.get_remote_for_stack(stack_name), base);
     let remote_ref = match repo.revparse_single(&remote_base) {
         Ok(r) => r,
         Err(_) => {
@@ -828,7 +1346,7 @@ fn are_all_entry_branches_merged_to_remote_base(
         .ok_or(GgError::NoBaseBranch)?;
 
     // Get remote base commit
-    let remote_base = format!("origin/{}", base);
+    let remote_base = format!("{}/{}", config.get_remote_for_stack(stack_name), base);
     let remote_ref = match repo.revparse_single(&remote_base) {
         Ok(r) => r,
         Err(_) => {
@@ -884,9 +1402,13 @@ fn verify_commits_reachable(
         .or_else(|| git::find_base_branch(repo).ok())
         .ok_or(GgError::NoBaseBranch)?;
 
-    // Get base commit (prefer origin/<base> for most up-to-date state)
+    // Get base commit (prefer <remote>/<base> for most up-to-date state)
     let base_ref = repo
-        .revparse_single(&format!("origin/{}", base))
+        .revparse_single(&format!(
+            "{}/{}",
+            config.get_remote_for_stack(stack_name),
+            base
+        ))
         .or_else(|_| repo.revparse_single(&base))?;
     let base_commit = base_ref.peel_to_commit()?;
 
@@ -922,6 +1444,7 @@ fn delete_entry_branches(
     silent: bool,
     record_remote_effect: &mut dyn FnMut(RemoteEffect),
 ) {
+    let remote = config.get_remote_for_stack(stack_name);
     // First, delete entry branches from config (if any)
     if let Some(stack_config) = config.get_stack(stack_name) {
         for entry_id in stack_config.mrs.keys() {
@@ -954,7 +1477,7 @@ fn delete_entry_branches(
             }
             // Delete remote entry branch
             if delete_remote {
-                if let Some(effect) = delete_remote_branch(repo, &entry_branch) {
+                if let Some(effect) = delete_remote_branch(repo, remote, &entry_branch) {
                     record_remote_effect(effect);
                 }
             }
@@ -997,19 +1520,19 @@ fn delete_entry_branches(
         }
         // Also try to delete from remote
         if delete_remote {
-            if let Some(effect) = delete_remote_branch(repo, &branch_name) {
+            if let Some(effect) = delete_remote_branch(repo, remote, &branch_name) {
                 record_remote_effect(effect);
             }
         }
     }
 }
 
-fn delete_remote_branch(repo: &Repository, branch: &str) -> Option<RemoteEffect> {
-    git::delete_remote_branch(repo, branch)
+fn delete_remote_branch(repo: &Repository, remote: &str, branch: &str) -> Option<RemoteEffect> {
+    git::delete_remote_branch(repo, remote, branch)
         .ok()
         .flatten()
         .map(|prior_oid| RemoteEffect::BranchDeleted {
-            remote: "origin".to_string(),
+            remote: remote.to_string(),
             branch: branch.to_string(),
             prior_oid: Some(prior_oid.to_string()),
         })
@@ -1019,12 +1542,22 @@ fn delete_remote_branch(repo: &Repository, branch: &str) -> Option<RemoteEffect>
 mod tests {
     use super::*;
 
+    #[test]
+    fn parse_age_days_accepts_days_weeks_and_plain_numbers() {
+        assert_eq!(parse_age_days("30d"), Ok(30));
+        assert_eq!(parse_age_days("2w"), Ok(14));
+        assert_eq!(parse_age_days("7"), Ok(7));
+        assert!(parse_age_days("soon").is_err());
+        assert!(parse_age_days("3m").is_err());
+    }
+
     #[test]
     fn normal_clean_deletes_remote_branches_only_when_verified() {
         assert!(should_delete_remote_branches(
             MergeStatus {
                 merged: true,
                 verified: true,
+                reason: CleanReason::AllPrsMerged,
             },
             false,
         ));
@@ -1033,6 +1566,7 @@ mod tests {
             MergeStatus {
                 merged: true,
                 verified: false,
+                reason: CleanReason::MergedLocally,
             },
             false,
         ));
@@ -1044,6 +1578,7 @@ mod tests {
             MergeStatus {
                 merged: true,
                 verified: false,
+                reason: CleanReason::MergedLocally,
             },
             true,
         ));
@@ -1055,6 +1590,7 @@ mod tests {
             MergeStatus {
                 merged: false,
                 verified: false,
+                reason: CleanReason::Unmerged,
             },
             true,
         ));
@@ -0,0 +1,99 @@
+//! `gg comment` - Post a comment on stack entries' PRs/MRs
+
+use console::style;
+
+use crate::config::Config;
+use crate::error::{GgError, Result};
+use crate::git;
+use crate::output::{print_json, CommentResponse, PostedCommentJson, OUTPUT_VERSION};
+use crate::provider::Provider;
+use crate::stack::{self, Stack};
+
+/// Options for the comment command
+#[derive(Debug, Default)]
+pub struct CommentOptions {
+    /// Entry to comment on: position (1-indexed), short SHA, or GG-ID. None = current.
+    pub target: Option<String>,
+    /// Comment on every entry that has a PR/MR
+    pub all: bool,
+    /// Comment body (Markdown)
+    pub message: String,
+    /// Print the result as JSON
+    pub json: bool,
+}
+
+/// Run the comment command
+pub fn run(options: CommentOptions) -> Result<()> {
+    if options.message.trim().is_empty() {
+        return Err(GgError::Other("Comment message is empty".to_string()));
+    }
+
+    let repo = git::open_repo()?;
+    let config = Config::load(repo.commondir())?;
+
+    let stack = Stack::load(&repo, &config)?;
+    if stack.is_empty() {
+        return Err(GgError::Other("Stack is empty".to_string()));
+    }
+
+    let provider = Provider::for_stack(&repo, &config, &stack.name)?;
+    let targets: Vec<(usize, u64)> = if options.all {
+        let prs: Vec<(usize, u64)> = stack
+            .entries
+            .iter()
+            .filter_map(|e| Some((e.position, e.mr_number?)))
+            .collect();
+        if prs.is_empty() {
+            return Err(GgError::Other(format!(
+                "No entry has a {} yet. Run `gg sync` first.",
+                provider.pr_label()
+            )));
+        }
+        prs
+    } else {
+        let position = match options.target.as_deref() {
+            Some(target) => stack::resolve_target(&stack, target)?,
+            None => stack.current_position.map(|p| p + 1).unwrap_or(stack.len()),
+        };
+        match stack.entries[position - 1].mr_number {
+            Some(pr) => vec![(position, pr)],
+            None => {
+                return Err(GgError::Other(format!(
+                    "#{} has no {} yet. Run `gg sync` first.",
+                    position,
+                    provider.pr_label()
+                )))
+            }
+        }
+    };
+    provider.check_installed()?;
+    provider.check_auth()?;
+
+    let mut comments = Vec::with_capacity(targets.len());
+    for (position, pr_number) in targets {
+        provider.create_pr_comment(pr_number, &options.message)?;
+        if !options.json {
+            println!(
+                "{} Commented on #{} {} {}{}",
+                style("OK").green().bold(),
+                position,
+                provider.pr_label(),
+                provider.pr_number_prefix(),
+                pr_number
+            );
+        }
+        comments.push(PostedCommentJson {
+            position,
+            pr_number,
+        });
+    }
+
+    if options.json {
+        print_json(&CommentResponse {
+            version: OUTPUT_VERSION,
+            comments,
+        });
+    }
+
+    Ok(())
+}
@@ -0,0 +1,311 @@
+//! `gg comments` - Inline review threads for the current stack
+//!
+//! Lists review threads per stack entry, grouped by file, with their
+//! resolved/unresolved state, and resolves threads with
+//! `gg comments resolve <id>` so the review loop can stay in the terminal.
+
+use std::collections::BTreeMap;
+
+use console::style;
+
+use crate::config::Config;
+use crate::error::{GgError, Result};
+use crate::git;
+use crate::output::{
+    print_json, CommentsEntryJson, CommentsResolveResponse, CommentsResponse, ResolvedThreadJson,
+    ReviewCommentJson, ReviewThreadJson, OUTPUT_VERSION,
+};
+use crate::plain;
+use crate::provider::{Provider, ReviewThread};
+use crate::stack::{self, Stack, StackEntry};
+
+/// Bucket used for threads that are not attached to a file.
+pub(super) const GENERAL_THREADS: &str = "(general)";
+
+/// Group threads by file path, keeping provider order within each file.
+pub fn group_by_file(threads: &[ReviewThread]) -> BTreeMap<&str, Vec<&ReviewThread>> {
+    let mut groups: BTreeMap<&str, Vec<&ReviewThread>> = BTreeMap::new();
+    for thread in threads {
+        groups
+            .entry(thread.path.as_deref().unwrap_or(GENERAL_THREADS))
+            .or_default()
+            .push(thread);
+    }
+    groups
+}
+
+/// Find a thread by exact id or unique id prefix.
+pub fn match_thread<'a>(
+    threads: &'a [(u64, ReviewThread)],
+    id: &str,
+) -> Result<&'a (u64, ReviewThread)> {
+    if let Some(exact) = threads.iter().find(|(_, t)| t.id == id) {
+        return Ok(exact);
+    }
+
+    let matches: Vec<_> = threads
+        .iter()
+        .filter(|(_, t)| t.id.starts_with(id))
+        .collect();
+    match matches.as_slice() {
+        [single] => Ok(single),
+        [] => Err(GgError::Other(format!(
+            "No review thread matching '{}' in this stack",
+            id
+        ))),
+        _ => Err(GgError::Other(format!(
+            "Thread id '{}' is ambiguous ({} matches); use a longer prefix",
+            id,
+            matches.len()
+        ))),
+    }
+}
+
+pub(super) fn load_stack_and_provider() -> Result<(Stack, Provider)> {
+    let repo = git::open_repo()?;
+    let config = Config::load(repo.commondir())?;
+    let stack = Stack::load_active(&repo, &config)?;
+
+    let provider = Provider::for_stack(&repo, &config, &stack.name)?;
+    provider.check_installed()?;
+    provider.check_auth()?;
+
+    Ok((stack, provider))
+}
+
+/// Run `gg comments [target]`
+pub fn run(target: Option<String>, unresolved_only: bool, json: bool) -> Result<()> {
+    let (stack, provider) = load_stack_and_provider()?;
+
+    let entries: Vec<&StackEntry> = match target.as_deref() {
+        Some(target) => {
+            let pos = stack::resolve_target(&stack, target)?;
+            stack.get_entry_by_position(pos).into_iter().collect()
+        }
+        None => stack.entries.iter().collect(),
+    };
+
+    let mut results = Vec::new();
+    for entry in entries {
+        let Some(pr_number) = entry.mr_number else {
+            continue;
+        };
+        let mut threads = provider.list_review_threads(pr_number)?;
+        if unresolved_only {
+            threads.retain(|t| !t.resolved);
+        }
+        results.push((entry, pr_number, threads));
+    }
+
+    if json {
+        print_json(&CommentsResponse {
+            version: OUTPUT_VERSION,
+            stack: stack.name.clone(),
+            entries: results
+                .iter()
+                .map(|(entry, pr_number, threads)| CommentsEntryJson {
+                    position: entry.position,
+                    sha: entry.short_sha.clone(),
+                    title: entry.title.clone(),
+                    gg_id: entry.gg_id.clone(),
+                    pr_number: *pr_number,
+                    threads: threads.iter().map(thread_json).collect(),
+                })
+                .collect(),
+        });
+        return Ok(());
+    }
+
+    if results.is_empty() {
+        println!(
+            "{}",
+            style(format!(
+                "No synced {}s in this stack. Run `gg sync` first.",
+                provider.pr_label()
+            ))
+            .dim()
+        );
+        return Ok(());
+    }
+
+    let mut any_threads = false;
+    for (entry, pr_number, threads) in &results {
+        if threads.is_empty() {
+            continue;
+        }
+        any_threads = true;
+        print_entry_threads(entry, &provider, *pr_number, threads);
+    }
+
+    if !any_threads {
+        let what = if unresolved_only {
+            "No unresolved review threads."
+        } else {
+            "No review threads."
+        };
+        println!("{}", style(what).dim());
+    }
+
+    Ok(())
+}
+
+fn print_entry_threads(
+    entry: &StackEntry,
+    provider: &Provider,
+    pr_number: u64,
+    threads: &[ReviewThread],
+) {
+    let unresolved = threads.iter().filter(|t| !t.resolved).count();
+    println!(
+        "[{}] {} {} ({}{}) {}",
+        entry.position,
+        style(&entry.short_sha).yellow(),
+        entry.title,
+        provider.pr_number_prefix(),
+        pr_number,
+        style(format!(
+            "{} unresolved, {} resolved",
+            unresolved,
+            threads.len() - unresolved
+        ))
+        .dim()
+    );
+
+    for (path, file_threads) in group_by_file(threads) {
+        println!("    {}", style(path).cyan());
+        for thread in file_threads {
+            let marker = if thread.resolved {
+                style(plain::ok()).green()
+            } else {
+                style(plain::running()).yellow()
+            };
+            let line = thread.line.map(|l| format!("L{} ", l)).unwrap_or_default();
+            let outdated = if thread.outdated {
+                style(" (outdated)").dim().to_string()
+            } else {
+                String::new()
+            };
+            println!(
+                "      {} {} {}{}",
+                marker,
+                style(&thread.id).dim(),
+                line,
+                outdated
+            );
+            for comment in &thread.comments {
+                let author = comment.author.as_deref().unwrap_or("ghost");
+                let first_line = comment.body.lines().next().unwrap_or("");
+                println!("          {}: {}", style(author).bold(), first_line);
+            }
+        }
+    }
+    println!();
+}
+
+pub(super) fn thread_json(thread: &ReviewThread) -> ReviewThreadJson {
+    ReviewThreadJson {
+        id: thread.id.clone(),
+        resolved: thread.resolved,
+        outdated: thread.outdated,
+        path: thread.path.clone(),
+        line: thread.line,
+        comments: thread
+            .comments
+            .iter()
+            .map(|c| ReviewCommentJson {
+                author: c.author.clone(),
+                body: c.body.clone(),
+                created_at: c.created_at.clone(),
+            })
+            .collect(),
+    }
+}
+
+/// Run `gg comments resolve <id>`
+pub fn run_resolve(thread_id: String, json: bool) -> Result<()> {
+    let (stack, provider) = load_stack_and_provider()?;
+
+    let mut threads = Vec::new();
+    for pr_number in stack.entries.iter().filter_map(|e| e.mr_number) {
+        for thread in provider.list_review_threads(pr_number)? {
+            threads.push((pr_number, thread));
+        }
+    }
+
+    let (pr_number, thread) = match_thread(&threads, &thread_id)?;
+    let already_resolved = thread.resolved;
+    if !already_resolved {
+        provider.resolve_review_thread(*pr_number, &thread.id)?;
+    }
+
+    if json {
+        print_json(&CommentsResolveResponse {
+            version: OUTPUT_VERSION,
+            resolved: ResolvedThreadJson {
+                id: thread.id.clone(),
+                pr_number: *pr_number,
+                already_resolved,
+            },
+        });
+    } else if already_resolved {
+        println!(
+            "{}",
+            style(format!("Thread {} was already resolved", thread.id)).dim()
+        );
+    } else {
+        println!(
+            "{} Resolved thread {} on {} {}{}",
+            style("OK").green().bold(),
+            thread.id,
+            provider.pr_label(),
+            provider.pr_number_prefix(),
+            pr_number
+        );
+    }
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn thread(id: &str, path: Option<&str>, resolved: bool) -> ReviewThread {
+        ReviewThread {
+            id: id.to_string(),
+            resolved,
+            outdated: false,
+            path: path.map(str::to_string),
+            line: None,
+            comments: vec![],
+        }
+    }
+
+    #[test]
+    fn groups_threads_by_file() {
+        let threads = vec![
+            thread("t1", Some("src/b.rs"), false),
+            thread("t2", None, true),
+            thread("t3", Some("src/a.rs"), false),
+            thread("t4", Some("src/b.rs"), true),
+        ];
+        let groups = group_by_file(&threads);
+        let keys: Vec<_> = groups.keys().copied().collect();
+        assert_eq!(keys, vec![GENERAL_THREADS, "src/a.rs", "src/b.rs"]);
+        let b: Vec<_> = groups["src/b.rs"].iter().map(|t| t.id.as_str()).collect();
+        assert_eq!(b, vec!["t1", "t4"]);
+    }
+
+    #[test]
+    fn match_thread_prefers_exact_then_unique_prefix() {
+        let threads = vec![
+            (1, thread("PRRT_abc", None, false)),
+            (2, thread("PRRT_abd", None, false)),
+            (3, thread("PRRT_ab", None, false)),
+        ];
+        assert_eq!(match_thread(&threads, "PRRT_ab").unwrap().0, 3);
+        assert_eq!(match_thread(&threads, "PRRT_abd").unwrap().0, 2);
+        assert!(match_thread(&threads, "PRRT_a").is_err());
+        assert!(match_thread(&threads, "nope").is_err());
+    }
+}
@@ -38,15 +38,29 @@ enum Commands {
         #[arg(short, long)]
         refresh: bool,
     },
-    #[command(name = "sync", alias = "diff")]
+    #[command(name = "sync")]
     Sync {
         #[arg(short, long)]
         draft: bool,
         #[arg(short, long)]
         force: bool,
     },
+    #[command(name = "diff")]
+    Diff {
+        target: Option<String>,
+        #[arg(long)]
+        stat: bool,
+        #[arg(long)]
+        name_only: bool,
+    },
     #[command(name = "mv", alias = "move")]
-    Move { target: String },
+    Move {
+        target: String,
+        #[arg(long)]
+        to: Option<String>,
+        #[arg(short, long, requires = "to")]
+        force: bool,
+    },
     #[command(name = "first")]
     First,
     #[command(name = "last")]
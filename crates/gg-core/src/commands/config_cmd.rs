@@ -0,0 +1,539 @@
+//! `gg config` - Inspect and edit the gg config
+//!
+//! `gg config edit` opens the effective config in `$EDITOR` and only saves
+//! it once it passes [`Config::parse_strict`], so a typo can't leave a
+//! broken `config.json` behind. `get`/`set`/`unset`/`list` work on single
+//! keys, written as dotted paths (`defaults.sync_jobs`), and go through the
+//! same validation.
+
+use std::fs;
+use std::path::{Path, PathBuf};
+
+use console::style;
+use dialoguer::{Confirm, Editor};
+use serde_json::{Map, Value};
+
+use crate::config::{ignored_in_team_config, Config, ConfigLayers, ConfigOrigin};
+use crate::error::{GgError, Result};
+use crate::git;
+use crate::output::{
+    print_json, ConfigGetResponse, ConfigListResponse, ConfigValueJson, OUTPUT_VERSION,
+};
+use crate::plain;
+
+/// Keys at the top of `config.json`. Any other first segment is taken as a
+/// key of `defaults`, so `sync_jobs` means `defaults.sync_jobs`.
+const TOP_LEVEL_KEYS: &[&str] = &["defaults", "stacks", "hooks"];
+
+/// Which config `gg config get|set|unset|list` works on.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ConfigScope {
+    /// `~/.config/gg/config.json`
+    Global,
+    /// `.gg/config.json`, committed to the repository
+    Team,
+    /// `.git/gg/config.json`
+    Repo,
+    /// One stack's entry in `.git/gg/config.json`; keys are relative to it
+    Stack(String),
+}
+
+impl ConfigScope {
+    /// Origin of the values this scope reads.
+    fn origin(&self) -> ConfigOrigin {
+        match self {
+            ConfigScope::Global => ConfigOrigin::Global,
+            ConfigScope::Team => ConfigOrigin::Team,
+            ConfigScope::Repo | ConfigScope::Stack(_) => ConfigOrigin::Repo,
+        }
+    }
+}
+
+/// The config files behind the effective config.
+struct Layers {
+    git_dir: Option<PathBuf>,
+    files: ConfigLayers,
+    effective: Value,
+}
+
+impl Layers {
+    /// Load every layer. Outside a repository only the global one exists.
+    fn load() -> Result<Self> {
+        let git_dir = git::open_repo()
+            .ok()
+            .map(|repo| repo.commondir().to_path_buf());
+        let files = ConfigLayers::read(git_dir.as_deref())?;
+        Ok(Layers {
+            effective: serde_json::to_value(files.effective()?)?,
+            git_dir,
+            files,
+        })
+    }
+
+    fn git_dir(&self) -> Result<&Path> {
+        self.git_dir.as_deref().ok_or(GgError::NotInRepo)
+    }
+
+    fn team_path(&self) -> Result<PathBuf> {
+        Config::team_config_path(self.git_dir()?).ok_or_else(|| {
+            GgError::Other("A bare repository has no team config (.gg/config.json)".to_string())
+        })
+    }
+
+    /// The config as the given scope sees it: the effective config without
+    /// a scope, one file otherwise.
+    fn value(&self, scope: Option<&ConfigScope>) -> Option<&Value> {
+        match scope {
+            None | Some(ConfigScope::Stack(_)) => Some(&self.effective),
+            Some(ConfigScope::Global) => self.files.global.as_ref(),
+            Some(ConfigScope::Team) => self.files.team.as_ref(),
+            Some(ConfigScope::Repo) => self.files.repo.as_ref(),
+        }
+    }
+
+    fn origin(&self, path: &[String]) -> ConfigOrigin {
+        self.files.origin(path)
+    }
+}
+
+/// Run `gg config get`
+pub fn run_get(key: &str, scope: Option<ConfigScope>, origin: bool, json: bool) -> Result<()> {
+    let path = key_path(key, scope.as_ref())?;
+    let layers = Layers::load()?;
+    let value = layers
+        .value(scope.as_ref())
+        .and_then(|v| lookup(v, &path))
+        .ok_or_else(|| GgError::Other(format!("`{}` is not set", path.join("."))))?;
+    let source = match &scope {
+        None => layers.origin(&path),
+        Some(scope) => scope.origin(),
+    };
+
+    if json {
+        print_json(&ConfigGetResponse {
+            version: OUTPUT_VERSION,
+            key: path.join("."),
+            value: value.clone(),
+            origin: source.as_str().to_string(),
+        });
+    } else if origin {
+        println!("{}\t{}", source.as_str(), display_value(value));
+    } else {
+        println!("{}", display_value(value));
+    }
+    Ok(())
+}
+
+/// Run `gg config list`
+pub fn run_list(scope: Option<ConfigScope>, origin: bool, json: bool) -> Result<()> {
+    let layers = Layers::load()?;
+    let prefix = match &scope {
+        Some(ConfigScope::Stack(name)) => vec!["stacks".to_string(), name.clone()],
+        _ => Vec::new(),
+    };
+    let mut values = Vec::new();
+    if let Some(root) = layers
+        .value(scope.as_ref())
+        .and_then(|v| lookup(v, &prefix))
+    {
+        flatten(root, &mut prefix.clone(), &mut values);
+    }
+
+    let entries: Vec<ConfigValueJson> = values
+        .into_iter()
+        .map(|(path, value)| {
+            let source = match &scope {
+                None => layers.origin(&path),
+                Some(scope) => scope.origin(),
+            };
+            ConfigValueJson {
+                key: path[prefix.len()..].join("."),
+                value,
+                origin: source.as_str().to_string(),
+            }
+        })
+        .collect();
+
+    if json {
+        print_json(&ConfigListResponse {
+            version: OUTPUT_VERSION,
+            values: entries,
+        });
+        return Ok(());
+    }
+    for entry in &entries {
+        if origin {
+            println!(
+                "{}\t{} = {}",
+                style(&entry.origin).dim(),
+                entry.key,
+                display_value(&entry.value)
+            );
+        } else {
+            println!("{} = {}", entry.key, display_value(&entry.value));
+        }
+    }
+    Ok(())
+}
+
+/// Run `gg config set`. `value` is read as JSON when it parses as JSON
+/// that fits the key (numbers, booleans, lists), as a string otherwise.
+pub fn run_set(key: &str, value: &str, scope: Option<ConfigScope>) -> Result<()> {
+    let scope = scope.unwrap_or(ConfigScope::Repo);
+    let path = key_path(key, Some(&scope))?;
+    require_known_key(&path)?;
+    if scope == ConfigScope::Team && ignored_in_team_config(&path) {
+        return Err(GgError::Other(format!(
+            "The team config cannot set `{}`: gg does not run commands from a committed file. \
+             Set it in your own config instead (`gg config set {} <value>`)",
+            path.join("."),
+            key
+        )));
+    }
+    let layers = Layers::load()?;
+    let base = scope_base(&layers, &scope)?;
+
+    let mut candidates: Vec<Value> = serde_json::from_str(value).into_iter().collect();
+    if candidates.first() != Some(&Value::String(value.to_string())) {
+        candidates.push(Value::String(value.to_string()));
+    }
+    let mut first_errors = None;
+    for candidate in candidates {
+        let mut updated = base.clone();
+        insert(&mut updated, &path, candidate.clone())?;
+        match Config::parse_strict(&updated.to_string()) {
+            Ok(_) => {
+                let file = write_scope(&layers, &scope, &updated)?;
+                println!(
+                    "{} Set {} = {} in {}",
+                    style("OK").green().bold(),
+                    path.join("."),
+                    display_value(&candidate),
+                    style(file.display()).cyan()
+                );
+                return Ok(());
+            }
+            Err(errors) => {
+                first_errors.get_or_insert(errors);
+            }
+        }
+    }
+    Err(invalid_value(&path, first_errors.unwrap_or_default()))
+}
+
+/// Run `gg config unset`: drop the key from the file, so the next layer
+/// (or the built-in default) applies again.
+pub fn run_unset(key: &str, scope: Option<ConfigScope>) -> Result<()> {
+    let scope = scope.unwrap_or(ConfigScope::Repo);
+    let path = key_path(key, Some(&scope))?;
+    require_known_key(&path)?;
+    let layers = Layers::load()?;
+    let file_value = match scope {
+        ConfigScope::Global => layers.files.global.clone(),
+        ConfigScope::Team => layers.files.team.clone(),
+        ConfigScope::Repo | ConfigScope::Stack(_) => layers.files.repo.clone(),
+    };
+    let mut updated = file_value
+        .filter(|v| lookup(v, &path).is_some())
+        .ok_or_else(|| GgError::Other(format!("`{}` is not set there", path.join("."))))?;
+    remove(&mut updated, &path);
+    Config::parse_strict(&updated.to_string()).map_err(|errors| invalid_value(&path, errors))?;
+    let file = write_scope(&layers, &scope, &updated)?;
+    println!(
+        "{} Unset {} in {}",
+        style("OK").green().bold(),
+        path.join("."),
+        style(file.display()).cyan()
+    );
+    Ok(())
+}
+
+/// The file `set` changes, as it is now.
+fn scope_base(layers: &Layers, scope: &ConfigScope) -> Result<Value> {
+    let file = match scope {
+        ConfigScope::Global => &layers.files.global,
+        ConfigScope::Team => {
+            layers.team_path()?;
+            &layers.files.team
+        }
+        ConfigScope::Repo | ConfigScope::Stack(_) => {
+            layers.git_dir()?;
+            &layers.files.repo
+        }
+    };
+    Ok(file.clone().unwrap_or_else(|| Value::Object(Map::new())))
+}
+
+/// Write the updated file of `scope`, keeping only what the user set so
+/// everything else follows the layers below. Returns the file written.
+fn write_scope(layers: &Layers, scope: &ConfigScope, updated: &Value) -> Result<PathBuf> {
+    match scope {
+        ConfigScope::Global => {
+            let path = Config::global_config_path()
+                .ok_or_else(|| GgError::Other("Could not determine home directory".to_string()))?;
+            if let Some(parent) = path.parent() {
+                fs::create_dir_all(parent)?;
+            }
+            let temp_path = path.with_extension("tmp");
+            fs::write(&temp_path, serde_json::to_string_pretty(updated)?)?;
+            fs::rename(&temp_path, &path)?;
+            Ok(path)
+        }
+        ConfigScope::Team => {
+            let path = layers.team_path()?;
+            if let Some(parent) = path.parent() {
+                fs::create_dir_all(parent)?;
+            }
+            // Committed file: end with a newline like the rest of the tree
+            fs::write(&path, serde_json::to_string_pretty(updated)? + "\n")?;
+            Ok(path)
+        }
+        ConfigScope::Repo | ConfigScope::Stack(_) => {
+            let git_dir = layers.git_dir()?;
+            Config::write_repo_json(git_dir, updated)?;
+            Ok(Config::config_path(git_dir))
+        }
+    }
+}
+
+/// Split a dotted key into a path from the root of `config.json`.
+fn key_path(key: &str, scope: Option<&ConfigScope>) -> Result<Vec<String>> {
+    let segments: Vec<String> = key.split('.').map(str::to_string).collect();
+    if segments.iter().any(String::is_empty) {
+        return Err(GgError::Other(format!("Invalid config key `{}`", key)));
+    }
+    Ok(match scope {
+        Some(ConfigScope::Stack(name)) => ["stacks".to_string(), name.clone()]
+            .into_iter()
+            .chain(segments)
+            .collect(),
+        _ if TOP_LEVEL_KEYS.contains(&segments[0].as_str()) => segments,
+        _ => std::iter::once("defaults".to_string())
+            .chain(segments)
+            .collect(),
+    })
+}
+
+/// Reject keys outside the config schema, so `set sync_job 8` fails instead
+/// of being silently ignored.
+fn require_known_key(path: &[String]) -> Result<()> {
+    let mut probe = Value::Object(Map::new());
+    insert(&mut probe, path, Value::Null)?;
+    match Config::parse_strict(&probe.to_string()) {
+        Err(errors) if errors.iter().any(|e| e.starts_with("unknown key")) => Err(GgError::Other(
+            format!("Unknown config key `{}`", path.join(".")),
+        )),
+        _ => Ok(()),
+    }
+}
+
+fn invalid_value(path: &[String], errors: Vec<String>) -> GgError {
+    // Line and column point into the generated JSON, not at anything the
+    // user typed.
+    let errors: Vec<&str> = errors
+        .iter()
+        .map(|e| e.rfind(" at line ").map_or(e.as_str(), |i| &e[..i]))
+        .collect();
+    GgError::Other(format!(
+        "Invalid value for `{}`: {}",
+        path.join("."),
+        errors.join("; ")
+    ))
+}
+
+/// The value at `path`; unset (`null`) values count as missing.
+fn lookup<'a>(value: &'a Value, path: &[String]) -> Option<&'a Value> {
+    path.iter()
+        .try_fold(value, |v, key| v.get(key))
+        .filter(|v| !v.is_null())
+}
+
+fn insert(value: &mut Value, path: &[String], new: Value) -> Result<()> {
+    let Some((last, parents)) = path.split_last() else {
+        *value = new;
+        return Ok(());
+    };
+    let mut current = value;
+    for key in parents {
+        let Value::Object(map) = current else {
+            return Err(GgError::Other(format!(
+                "`{}` is not a section",
+                path.join(".")
+            )));
+        };
+        current = map
+            .entry(key.clone())
+            .or_insert_with(|| Value::Object(Map::new()));
+        if current.is_null() {
+            *current = Value::Object(Map::new());
+        }
+    }
+    match current {
+        Value::Object(map) => {
+            map.insert(last.clone(), new);
+            Ok(())
+        }
+        _ => Err(GgError::Other(format!(
+            "`{}` is not a section",
+            path.join(".")
+        ))),
+    }
+}
+
+fn remove(value: &mut Value, path: &[String]) {
+    let Some((last, parents)) = path.split_last() else {
+        return;
+    };
+    if let Some(Value::Object(map)) = parents.iter().try_fold(value, |v, key| v.get_mut(key)) {
+        map.remove(last);
+    }
+}
+
+/// Every set leaf under `value` with its path. Lists are leaves.
+fn flatten(value: &Value, path: &mut Vec<String>, out: &mut Vec<(Vec<String>, Value)>) {
+    match value {
+        Value::Object(map) => {
+            let mut keys: Vec<&String> = map.keys().collect();
+            keys.sort();
+            for key in keys {
+                path.push(key.clone());
+                flatten(&map[key], path, out);
+                path.pop();
+            }
+        }
+        Value::Null => {}
+        leaf => out.push((path.clone(), leaf.clone())),
+    }
+}
+
+/// Strings as-is, everything else as JSON.
+fn display_value(value: &Value) -> String {
+    match value {
+        Value::String(s) => s.clone(),
+        other => other.to_string(),
+    }
+}
+
+/// Run `gg config edit`
+pub fn run_edit() -> Result<()> {
+    let repo = git::open_repo()?;
+    let git_dir = repo.commondir();
+    let config_path = Config::config_path(git_dir);
+
+    let mut text = match Config::load(git_dir) {
+        Ok(config) => format!("{}\n", serde_json::to_string_pretty(&config)?),
+        // A config that no longer parses is exactly what this command is for:
+        // hand the raw file to the editor so it can be fixed.
+        Err(_) if config_path.exists() => fs::read_to_string(&config_path)?,
+        Err(e) => return Err(e),
+    };
+
+    loop {
+        let edited = Editor::new()
+            .extension(".json")
+            .edit(&text)
+            .map_err(|e| GgError::Other(format!("Editor failed: {}", e)))?;
+        let Some(edited) = edited else {
+            println!(
+                "{}",
+                style("No changes saved; config left untouched.").dim()
+            );
+            return Ok(());
+        };
+
+        match Config::parse_strict(&edited) {
+            Ok(config) => {
+                // Config::save writes to a temp file and renames it into place.
+                config.save(git_dir)?;
+                println!(
+                    "{} Wrote config to {}",
+                    style("OK").green().bold(),
+                    style(config_path.display()).cyan()
+                );
+                return Ok(());
+            }
+            Err(errors) => {
+                eprintln!(
+                    "{} The edited config is invalid:",
+                    style("error:").red().bold()
+                );
+                for error in &errors {
+                    eprintln!("  {} {}", plain::bullet(), error);
+                }
+
+                let reopen = atty::is(atty::Stream::Stdin)
+                    && Confirm::new()
+                        .with_prompt("Re-open the editor?")
+                        .default(true)
+                        .interact()
+                        .unwrap_or(false);
+                if !reopen {
+                    return Err(GgError::Other(format!(
+                        "Config not saved; {} is unchanged",
+                        config_path.display()
+                    )));
+                }
+                text = edited;
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn path(key: &str) -> Vec<String> {
+        key.split('.').map(str::to_string).collect()
+    }
+
+    #[test]
+    fn key_path_defaults_to_defaults_section() {
+        assert_eq!(
+            key_path("sync_jobs", None).unwrap(),
+            path("defaults.sync_jobs")
+        );
+        assert_eq!(
+            key_path("hooks.pre_sync", None).unwrap(),
+            path("hooks.pre_sync")
+        );
+        assert_eq!(
+            key_path("base", Some(&ConfigScope::Stack("billing".to_string()))).unwrap(),
+            path("stacks.billing.base")
+        );
+        assert!(key_path("defaults..base", None).is_err());
+    }
+
+    #[test]
+    fn known_keys_follow_the_schema() {
+        assert!(require_known_key(&path("defaults.sync_jobs")).is_ok());
+        assert!(require_known_key(&path("stacks.any.reviewers")).is_ok());
+        assert!(require_known_key(&path("defaults.sync_job")).is_err());
+        assert!(require_known_key(&path("stacks.any.nope")).is_err());
+    }
+
+    #[test]
+    fn insert_remove_and_flatten_round_trip() {
+        let mut value = Value::Object(Map::new());
+        insert(&mut value, &path("defaults.sync_jobs"), Value::from(8)).unwrap();
+        insert(&mut value, &path("defaults.base"), Value::from("main")).unwrap();
+        assert_eq!(
+            lookup(&value, &path("defaults.sync_jobs")),
+            Some(&Value::from(8))
+        );
+
+        let mut out = Vec::new();
+        flatten(&value, &mut Vec::new(), &mut out);
+        assert_eq!(
+            out,
+            vec![
+                (path("defaults.base"), Value::from("main")),
+                (path("defaults.sync_jobs"), Value::from(8)),
+            ]
+        );
+
+        remove(&mut value, &path("defaults.base"));
+        assert_eq!(lookup(&value, &path("defaults.base")), None);
+        assert!(insert(&mut value, &path("defaults.sync_jobs.x"), Value::Null).is_err());
+    }
+}
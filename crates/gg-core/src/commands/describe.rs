@@ -0,0 +1,86 @@
+//! `gg describe` - Show or set the description of a stack
+//!
+//! The description says what a stack is for. It is stored in the stack's
+//! config and in git notes (see [`crate::notes`]), shown by `gg ls` and
+//! available to PR/MR templates as `{{stack_description}}`.
+
+use console::style;
+use git2::Repository;
+
+use crate::config::Config;
+use crate::error::Result;
+use crate::git;
+use crate::notes;
+use crate::output::{print_json, DescribeResponse, OUTPUT_VERSION};
+use crate::stack::{self, Stack};
+
+/// Run the describe command for `stack_name`, or the current stack
+pub fn run(
+    stack_name: Option<String>,
+    description: Option<String>,
+    clear: bool,
+    json: bool,
+) -> Result<()> {
+    let repo = git::open_repo()?;
+    let git_dir = repo.commondir();
+    let mut config = Config::load(git_dir)?;
+    let name = match stack_name {
+        Some(name) => name,
+        None => Stack::load_active(&repo, &config)?.name,
+    };
+
+    let changed = clear || description.is_some();
+    if changed {
+        set_description(&repo, &mut config, &name, description.as_deref())?;
+        config.save(git_dir)?;
+    }
+    let description = stack::stack_description(&repo, &config, &name);
+
+    if json {
+        print_json(&DescribeResponse {
+            version: OUTPUT_VERSION,
+            stack: name,
+            description,
+        });
+        return Ok(());
+    }
+
+    match description {
+        None if changed => println!(
+            "{} Cleared the description of {}",
+            style("OK").green().bold(),
+            style(&name).cyan()
+        ),
+        None => println!(
+            "{}",
+            style(format!(
+                "{} has no description. Set one with `gg describe \"...\"`.",
+                name
+            ))
+            .dim()
+        ),
+        Some(description) if changed => println!(
+            "{} Described {}: {}",
+            style("OK").green().bold(),
+            style(&name).cyan(),
+            description.lines().next().unwrap_or_default()
+        ),
+        Some(description) => println!("{}\n{}", style(&name).cyan().bold(), description),
+    }
+    Ok(())
+}
+
+/// Set (or with `None`, remove) the description of `stack` in its config
+/// and in git notes. The caller saves the config.
+pub(crate) fn set_description(
+    repo: &Repository,
+    config: &mut Config,
+    stack: &str,
+    description: Option<&str>,
+) -> Result<()> {
+    let description = description.map(str::trim).filter(|d| !d.is_empty());
+    if description.is_some() || config.get_stack(stack).is_some() {
+        config.get_or_create_stack(stack).description = description.map(str::to_string);
+    }
+    notes::write_stack_description(repo, stack, description)
+}
@@ -0,0 +1,100 @@
+//! `gg diff` - Show the patch of a stack entry, a range, or the whole stack
+//!
+//! The diff runs from the parent of the first selected entry to the last
+//! one, so it shows exactly what the matching PR/MRs will contain.
+
+use std::process::Command;
+
+use crate::config::Config;
+use crate::error::{GgError, Result};
+use crate::git;
+use crate::stack::{self, Stack};
+
+/// Options for the diff command
+#[derive(Debug, Default)]
+pub struct DiffOptions {
+    /// Entry (position, short SHA, or GG-ID) or range `A..B`. None = whole stack.
+    pub target: Option<String>,
+    /// Show a diffstat instead of the patch
+    pub stat: bool,
+    /// Show only the names of changed files
+    pub name_only: bool,
+}
+
+/// Run the diff command
+pub fn run(options: DiffOptions) -> Result<()> {
+    let repo = git::open_repo()?;
+    let git_dir = repo.commondir();
+    let config = Config::load(git_dir)?;
+
+    let stack = Stack::load(&repo, &config)?;
+    if stack.is_empty() {
+        return Err(GgError::Other("Stack is empty".to_string()));
+    }
+
+    let (first, last) = match options.target.as_deref() {
+        None => (1, stack.len()),
+        Some(spec) => match split_range(spec) {
+            Some((from, to)) => {
+                let first = from.map_or(Ok(1), |t| stack::resolve_target(&stack, t))?;
+                let last = to.map_or(Ok(stack.len()), |t| stack::resolve_target(&stack, t))?;
+                if first > last {
+                    return Err(GgError::Other(format!(
+                        "Invalid range '{}': #{} comes after #{}",
+                        spec, first, last
+                    )));
+                }
+                (first, last)
+            }
+            None => {
+                let position = stack::resolve_target(&stack, spec)?;
+                (position, position)
+            }
+        },
+    };
+
+    let from = repo
+        .find_commit(stack.entries[first - 1].oid)?
+        .parent_id(0)?;
+    let to = stack.entries[last - 1].oid;
+
+    let mut cmd = Command::new("git");
+    cmd.arg("diff");
+    if options.stat {
+        cmd.arg("--stat");
+    }
+    if options.name_only {
+        cmd.arg("--name-only");
+    }
+    cmd.arg(from.to_string()).arg(to.to_string());
+
+    let status = cmd.status()?;
+    if !status.success() {
+        return Err(GgError::Other("git diff failed".to_string()));
+    }
+    Ok(())
+}
+
+/// Split `A..B` into its ends; either end may be omitted. Returns None when
+/// `spec` is not a range.
+fn split_range(spec: &str) -> Option<(Option<&str>, Option<&str>)> {
+    let (from, to) = spec.split_once("..")?;
+    fn end(s: &str) -> Option<&str> {
+        Some(s.trim()).filter(|s| !s.is_empty())
+    }
+    Some((end(from), end(to)))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn split_range_handles_open_ends() {
+        assert_eq!(split_range("2..4"), Some((Some("2"), Some("4"))));
+        assert_eq!(split_range("2.."), Some((Some("2"), None)));
+        assert_eq!(split_range("..c-abc1234"), Some((None, Some("c-abc1234"))));
+        assert_eq!(split_range(".."), Some((None, None)));
+        assert_eq!(split_range("3"), None);
+    }
+}
@@ -0,0 +1,598 @@
+//! `gg doctor` - Diagnose the environment and the repository's gg state
+//!
+//! Checks, in order:
+//! - git is installed and recent enough
+//! - the config files parse and hold valid values
+//! - the provider CLI (`gh`/`glab`) is installed and authenticated
+//! - the remotes gg pushes to are reachable
+//! - every local stack passes `gg verify`'s local checks (stale PR/MR
+//!   mappings, orphan entry branches, ...)
+//! - no navigation context is left over from `gg mv`/`gg prev`/... after
+//!   HEAD moved back onto a branch
+//! - no rebase or merge is half-way done
+//!
+//! `--fix` only performs repairs that cannot lose work: the ones
+//! `gg verify --fix` makes, and clearing a dangling navigation context.
+
+use std::collections::BTreeSet;
+use std::process::Command;
+
+use console::style;
+use git2::{BranchType, Repository, RepositoryState};
+use serde::Serialize;
+
+use crate::branch_naming::BranchNaming;
+use crate::commands::verify::{self, Repair};
+use crate::config::{Config, ConfigLayers};
+use crate::error::{GgError, Result};
+use crate::git;
+use crate::gitea;
+use crate::operations::{OperationKind, SnapshotScope};
+use crate::output::{
+    print_json, DoctorCheckJson, DoctorResponse, DoctorResultJson, OUTPUT_VERSION,
+};
+use crate::provider::Provider;
+use crate::{gh, glab, stack};
+
+/// Oldest git gg supports.
+const MIN_GIT_VERSION: (u32, u32) = (2, 0);
+
+/// Outcome of a check. Serialized in lowercase in JSON output.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "lowercase")]
+pub enum CheckStatus {
+    Pass,
+    Warn,
+    Fail,
+}
+
+/// A repair `--fix` knows how to apply safely.
+#[derive(Debug, Clone, PartialEq, Eq)]
+enum Fix {
+    /// One of `gg verify --fix`'s repairs on a stack.
+    Verify { stack: String, repair: Repair },
+    /// Remove the navigation context of the current worktree.
+    ClearNavContext,
+}
+
+/// One diagnosed item.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Check {
+    /// Area checked (`git`, `config`, `provider`, `remote`, `stack`, ...)
+    pub area: &'static str,
+    pub status: CheckStatus,
+    pub message: String,
+    /// Suggested way to fix it by hand
+    pub hint: Option<String>,
+    fix: Option<Fix>,
+    pub fixed: bool,
+}
+
+impl Check {
+    fn pass(area: &'static str, message: impl Into<String>) -> Self {
+        Self::new(area, CheckStatus::Pass, message, None)
+    }
+
+    fn warn(area: &'static str, message: impl Into<String>, hint: impl Into<String>) -> Self {
+        Self::new(area, CheckStatus::Warn, message, Some(hint.into()))
+    }
+
+    fn fail(area: &'static str, message: impl Into<String>, hint: impl Into<String>) -> Self {
+        Self::new(area, CheckStatus::Fail, message, Some(hint.into()))
+    }
+
+    fn new(
+        area: &'static str,
+        status: CheckStatus,
+        message: impl Into<String>,
+        hint: Option<String>,
+    ) -> Self {
+        Self {
+            area,
+            status,
+            message: message.into(),
+            hint,
+            fix: None,
+            fixed: false,
+        }
+    }
+
+    fn with_fix(mut self, fix: Fix) -> Self {
+        self.fix = Some(fix);
+        self
+    }
+
+    pub fn is_fixable(&self) -> bool {
+        self.fix.is_some()
+    }
+
+    /// Whether this check still makes `gg doctor` fail.
+    pub fn is_blocking(&self) -> bool {
+        !self.fixed && self.status == CheckStatus::Fail
+    }
+}
+
+/// `(major, minor)` from `git --version` output (`git version 2.43.0`,
+/// `git version 2.39.3 (Apple Git-146)`).
+fn parse_git_version(output: &str) -> Option<(u32, u32)> {
+    let version = output.trim().strip_prefix("git version ")?;
+    let mut parts = version.split(|c: char| !c.is_ascii_digit());
+    let major = parts.next()?.parse().ok()?;
+    let minor = parts.next()?.parse().ok()?;
+    Some((major, minor))
+}
+
+fn check_git() -> Check {
+    let output = Command::new("git").arg("--version").output();
+    let text = match output {
+        Ok(o) if o.status.success() => String::from_utf8_lossy(&o.stdout).trim().to_string(),
+        _ => {
+            return Check::fail(
+                "git",
+                "git is not installed",
+                "Install git 2.x and make sure it is on your PATH",
+            )
+        }
+    };
+    match parse_git_version(&text) {
+        Some(version) if version < MIN_GIT_VERSION => Check::fail(
+            "git",
+            format!("{} is too old", text),
+            format!(
+                "Upgrade to git {}.{} or newer",
+                MIN_GIT_VERSION.0, MIN_GIT_VERSION.1
+            ),
+        ),
+        _ => Check::pass("git", text),
+    }
+}
+
+/// Parse every config layer on its own, then the merged result. Returns the
+/// effective config when it could be loaded.
+fn check_config(git_dir: &std::path::Path, checks: &mut Vec<Check>) -> Option<Config> {
+    let layers = [
+        ("global", Config::global_config_path()),
+        ("team", Config::team_config_path(git_dir)),
+        ("repository", Some(Config::config_path(git_dir))),
+    ];
+    let mut files = 0;
+    let mut ok = true;
+    for (layer, path) in layers {
+        let Some(path) = path.filter(|p| p.exists()) else {
+            continue;
+        };
+        files += 1;
+        let errors = match std::fs::read_to_string(&path) {
+            Ok(text) => Config::parse_strict(&text).err().unwrap_or_default(),
+            Err(e) => vec![e.to_string()],
+        };
+        if !errors.is_empty() {
+            ok = false;
+            checks.push(Check::fail(
+                "config",
+                format!(
+                    "{} config {} is invalid: {}",
+                    layer,
+                    path.display(),
+                    errors.join("; ")
+                ),
+                if layer == "repository" {
+                    "Fix it with `gg config edit`".to_string()
+                } else {
+                    format!("Fix {} by hand", path.display())
+                },
+            ));
+        }
+    }
+
+    let config = match Config::load(git_dir) {
+        Ok(config) => config,
+        Err(e) => {
+            if ok {
+                checks.push(Check::fail(
+                    "config",
+                    format!("config could not be loaded: {}", e),
+                    "Fix it with `gg config edit`",
+                ));
+            }
+            return None;
+        }
+    };
+    if let Ok(layers) = ConfigLayers::read(Some(git_dir)) {
+        let ignored = layers.ignored_team_keys();
+        if !ignored.is_empty() {
+            checks.push(Check::warn(
+                "config",
+                format!(
+                    "team config sets {}, which gg ignores: commands only run from your own config",
+                    ignored.join(", ")
+                ),
+                "Copy the ones you trust with `gg config set <key> <value>`",
+            ));
+        }
+    }
+    if let Err(e) = BranchNaming::from_config(&config) {
+        ok = false;
+        checks.push(Check::fail(
+            "config",
+            e,
+            "Fix the templates with `gg config set` or `gg config unset`",
+        ));
+    }
+    if ok {
+        checks.push(Check::pass(
+            "config",
+            format!("config is valid ({} file(s))", files),
+        ));
+    }
+    Some(config)
+}
+
+fn check_provider(repo: &Repository, checks: &mut Vec<Check>) {
+    let provider = match Provider::detect(repo) {
+        Ok(provider) => provider,
+        Err(e) => {
+            checks.push(Check::warn(
+                "provider",
+                format!("could not detect the provider: {}", e),
+                "Set it with `gg config set provider <github|gitlab|gitea>`",
+            ));
+            return;
+        }
+    };
+    if let Err(e) = provider.check_installed() {
+        let cli = match provider {
+            Provider::GitLab => "glab",
+            _ => "gh",
+        };
+        checks.push(Check::fail(
+            "provider",
+            e.to_string(),
+            format!(
+                "Install the `{}` CLI, or use `gg config set api_mode native`",
+                cli
+            ),
+        ));
+        return;
+    }
+    // Straight to the provider, without `check_auth`'s network fallback:
+    // a network error here is worth reporting
+    let auth = match provider {
+        Provider::GitHub => gh::check_gh_auth(),
+        Provider::GitLab => glab::check_glab_auth(),
+        Provider::Gitea => gitea::check_auth(),
+    };
+    checks.push(match auth {
+        Ok(()) => Check::pass(
+            "provider",
+            format!("authenticated with {}", provider.name()),
+        ),
+        Err(GgError::NetworkError(e)) => Check::warn(
+            "provider",
+            e,
+            "Check your connection and run `gg doctor` again",
+        ),
+        Err(e) => Check::fail(
+            "provider",
+            e.to_string(),
+            format!("Log in to {} and run `gg doctor` again", provider.name()),
+        ),
+    });
+}
+
+/// `origin` and every remote a stack is configured to use.
+fn check_remotes(repo: &Repository, config: Option<&Config>, checks: &mut Vec<Check>) {
+    let mut remotes = BTreeSet::from([git::DEFAULT_REMOTE.to_string()]);
+    if let Some(config) = config {
+        remotes.extend(config.stacks.values().filter_map(|s| s.remote.clone()));
+    }
+    for remote in remotes {
+        if repo.find_remote(&remote).is_err() {
+            checks.push(Check::warn(
+                "remote",
+                format!("remote '{}' does not exist", remote),
+                format!(
+                    "Add it with `git remote add {} <url>`; gg sync pushes there",
+                    remote
+                ),
+            ));
+            continue;
+        }
+        let output = Command::new("git")
+            .args(["ls-remote", "--heads", &remote])
+            .env("GIT_TERMINAL_PROMPT", "0")
+            .output();
+        checks.push(match output {
+            Ok(o) if o.status.success() => {
+                Check::pass("remote", format!("remote '{}' is reachable", remote))
+            }
+            Ok(o) => Check::warn(
+                "remote",
+                format!(
+                    "remote '{}' is unreachable: {}",
+                    remote,
+                    String::from_utf8_lossy(&o.stderr).trim()
+                ),
+                "Check the remote URL, your connection and your credentials",
+            ),
+            Err(e) => Check::warn(
+                "remote",
+                format!("could not reach remote '{}': {}", remote, e),
+                "Check that git is on your PATH",
+            ),
+        });
+    }
+}
+
+/// `gg verify`'s local checks on every stack.
+fn check_stacks(repo: &Repository, config: &Config, checks: &mut Vec<Check>) -> Result<()> {
+    let username = config
+        .defaults
+        .branch_username
+        .clone()
+        .or_else(|| Provider::detect(repo).ok().and_then(|p| p.whoami().ok()))
+        .unwrap_or_else(|| "unknown".to_string());
+    git::validate_branch_username(&username)?;
+
+    let stacks = stack::list_all_stacks(repo, config, &username)?;
+    let before = checks.len();
+    for stack_name in &stacks {
+        let facts = verify::gather_facts(repo, config, &username, stack_name)?;
+        for issue in verify::check_stack(&facts) {
+            let status = if issue.kind.is_warning() {
+                CheckStatus::Warn
+            } else {
+                CheckStatus::Fail
+            };
+            let hint = if issue.is_fixable() {
+                "Run `gg doctor --fix`".to_string()
+            } else {
+                "See `gg verify`".to_string()
+            };
+            let check = Check::new(
+                "stack",
+                status,
+                format!("{}: {}", issue.stack, issue.message),
+                Some(hint),
+            );
+            checks.push(match issue.repair {
+                Some(repair) => check.with_fix(Fix::Verify {
+                    stack: issue.stack,
+                    repair,
+                }),
+                None => check,
+            });
+        }
+    }
+    if checks.len() == before {
+        checks.push(Check::pass(
+            "stack",
+            format!("{} stack(s) are consistent", stacks.len()),
+        ));
+    }
+    Ok(())
+}
+
+/// Navigation context (`gg mv`, `gg prev`, ...) only means something while
+/// HEAD is detached on a stack that still exists.
+fn check_nav_context(repo: &Repository) -> Check {
+    let Some(branch) = stack::read_current_stack(repo.path()) else {
+        return Check::pass("navigation", "no navigation context");
+    };
+    let detached = repo.head_detached().unwrap_or(false);
+    let exists = repo.find_branch(&branch, BranchType::Local).is_ok();
+    if detached && exists {
+        return Check::pass("navigation", format!("navigating {}", branch));
+    }
+    let reason = if exists {
+        "HEAD is back on a branch"
+    } else {
+        "the branch no longer exists"
+    };
+    Check::warn(
+        "navigation",
+        format!("leftover navigation context for {} ({})", branch, reason),
+        "Run `gg doctor --fix` to clear it",
+    )
+    .with_fix(Fix::ClearNavContext)
+}
+
+fn check_repo_state(repo: &Repository) -> Check {
+    let operation = match repo.state() {
+        RepositoryState::Clean => return Check::pass("state", "no operation in progress"),
+        RepositoryState::Rebase
+        | RepositoryState::RebaseInteractive
+        | RepositoryState::RebaseMerge => "a rebase",
+        RepositoryState::Merge => "a merge",
+        RepositoryState::CherryPick | RepositoryState::CherryPickSequence => "a cherry-pick",
+        RepositoryState::Revert | RepositoryState::RevertSequence => "a revert",
+        _ => "a git operation",
+    };
+    Check::warn(
+        "state",
+        format!("{} is in progress", operation),
+        "Resolve the conflicts and run `gg continue`, or `gg abort`",
+    )
+}
+
+fn apply_fix(repo: &Repository, config: &mut Config, fix: &Fix) -> Result<()> {
+    match fix {
+        Fix::Verify { stack, repair } => verify::apply_repair(repo, config, stack, repair),
+        Fix::ClearNavContext => stack::clear_current_stack(repo.path()),
+    }
+}
+
+/// Run the doctor command
+pub fn run(fix: bool, json: bool) -> Result<()> {
+    let mut checks = vec![check_git()];
+
+    match git::open_repo() {
+        Ok(repo) => {
+            let git_dir = repo.commondir().to_path_buf();
+            let config = check_config(&git_dir, &mut checks);
+            check_provider(&repo, &mut checks);
+            check_remotes(&repo, config.as_ref(), &mut checks);
+            if let Some(config) = &config {
+                if let Err(e) = check_stacks(&repo, config, &mut checks) {
+                    checks.push(Check::fail(
+                        "stack",
+                        format!("could not check stacks: {}", e),
+                        "Run `gg verify` for details",
+                    ));
+                }
+            }
+            checks.push(check_nav_context(&repo));
+            checks.push(check_repo_state(&repo));
+
+            if let Some(mut config) = config.filter(|_| fix) {
+                if checks.iter().any(Check::is_fixable) {
+                    let (_lock, guard) = git::acquire_operation_lock_and_record(
+                        &repo,
+                        &config,
+                        OperationKind::Doctor,
+                        std::env::args().skip(1).collect(),
+                        None,
+                        SnapshotScope::AllUserBranches,
+                    )?;
+                    for check in checks.iter_mut() {
+                        if let Some(fix) = &check.fix {
+                            apply_fix(&repo, &mut config, fix)?;
+                            check.fixed = true;
+                        }
+                    }
+                    config.save(&git_dir)?;
+                    guard.finalize_with_scope(
+                        &repo,
+                        &config,
+                        SnapshotScope::AllUserBranches,
+                        vec![],
+                        false,
+                    )?;
+                }
+            }
+        }
+        Err(e) => checks.push(Check::fail(
+            "repository",
+            e.to_string(),
+            "Run `gg doctor` inside a git repository to check its gg state",
+        )),
+    }
+
+    let failed = checks.iter().filter(|c| c.is_blocking()).count();
+
+    if json {
+        print_json(&DoctorResponse {
+            version: OUTPUT_VERSION,
+            doctor: DoctorResultJson {
+                ok: failed == 0,
+                fixed: checks.iter().filter(|c| c.fixed).count(),
+                checks: checks
+                    .iter()
+                    .map(|c| DoctorCheckJson {
+                        area: c.area.to_string(),
+                        status: serde_json::to_value(c.status)
+                            .ok()
+                            .and_then(|v| v.as_str().map(str::to_string))
+                            .unwrap_or_default(),
+                        message: c.message.clone(),
+                        hint: c.hint.clone(),
+                        fixable: c.is_fixable(),
+                        fixed: c.fixed,
+                    })
+                    .collect(),
+            },
+        });
+    } else {
+        print_human(&checks, fix);
+    }
+
+    if failed > 0 {
+        return Err(GgError::Silenced);
+    }
+    Ok(())
+}
+
+fn print_human(checks: &[Check], fix: bool) {
+    for check in checks {
+        let marker = if check.fixed {
+            style("fixed").green().to_string()
+        } else {
+            match check.status {
+                CheckStatus::Pass => style("ok   ").green().to_string(),
+                CheckStatus::Warn => style("warn ").yellow().to_string(),
+                CheckStatus::Fail => style("fail ").red().to_string(),
+            }
+        };
+        println!(
+            "  {} {:<10} {}",
+            marker,
+            style(check.area).cyan(),
+            check.message
+        );
+        if let Some(hint) = check.hint.as_ref().filter(|_| !check.fixed) {
+            println!("  {:<16} {}", "", style(hint).dim());
+        }
+    }
+
+    let fixable = checks.iter().filter(|c| c.is_fixable() && !c.fixed).count();
+    if !fix && fixable > 0 {
+        println!();
+        println!(
+            "{}",
+            style(format!(
+                "Run `gg doctor --fix` to repair {} issue(s).",
+                fixable
+            ))
+            .dim()
+        );
+    }
+
+    if !checks.iter().any(Check::is_blocking) {
+        println!("{} No problems found", style("OK").green().bold());
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parses_git_versions() {
+        assert_eq!(parse_git_version("git version 2.43.0\n"), Some((2, 43)));
+        assert_eq!(
+            parse_git_version("git version 2.39.3 (Apple Git-146)"),
+            Some((2, 39))
+        );
+        assert_eq!(
+            parse_git_version("git version 2.45.1.windows.1"),
+            Some((2, 45))
+        );
+        assert_eq!(parse_git_version("not git"), None);
+    }
+
+    #[test]
+    fn nav_context_is_dangling_once_head_is_on_a_branch() {
+        let dir = tempfile::tempdir().unwrap();
+        let repo = Repository::init(dir.path()).unwrap();
+        let sig = git2::Signature::now("Test", "test@example.com").unwrap();
+        let tree = repo
+            .find_tree(repo.index().unwrap().write_tree().unwrap())
+            .unwrap();
+        let oid = repo
+            .commit(Some("HEAD"), &sig, &sig, "Initial", &tree, &[])
+            .unwrap();
+        let branch = git::format_stack_branch("me", "feat");
+        repo.branch(&branch, &repo.find_commit(oid).unwrap(), false)
+            .unwrap();
+
+        assert_eq!(check_nav_context(&repo).status, CheckStatus::Pass);
+
+        stack::save_nav_context(repo.path(), &branch, 1, oid).unwrap();
+        let check = check_nav_context(&repo);
+        assert_eq!(check.status, CheckStatus::Warn);
+        assert_eq!(check.fix, Some(Fix::ClearNavContext));
+
+        repo.set_head_detached(oid).unwrap();
+        assert_eq!(check_nav_context(&repo).status, CheckStatus::Pass);
+    }
+}
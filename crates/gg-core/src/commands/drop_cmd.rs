@@ -1,19 +1,26 @@
 //! `gg drop` - Remove commits from the stack
+//!
+//! Dropped entries lose their PR/MR mapping. With `--close-pr`, their open
+//! PRs/MRs are also closed on the provider with a comment saying why.
 
 use std::io::Write;
 
 use console::style;
 use dialoguer::Confirm;
+use git2::Repository;
 use serde_json::json;
 
 use crate::config::Config;
 use crate::error::{GgError, Result};
 use crate::git;
+use crate::hooks::{self, GitRewrite};
 use crate::immutability::{self, ImmutabilityPolicy};
 use crate::operations::{self, OperationKind, SnapshotScope};
 use crate::output::{print_json, DropResponse, DropResultJson, DroppedEntryJson, OUTPUT_VERSION};
 use crate::stack::{self, Stack};
 
+use super::squash_all::close_prs;
+
 /// Options for the drop command
 #[derive(Debug, Default)]
 pub struct DropOptions {
@@ -26,6 +33,8 @@ pub struct DropOptions {
     /// immutability guard. Use this for non-interactive callers (CI, MCP)
     /// that do not want to silently rewrite merged commits.
     pub yes: bool,
+    /// Close the dropped entries' open PRs/MRs on the provider
+    pub close_pr: bool,
     /// Output as JSON
     pub json: bool,
 }
@@ -33,7 +42,7 @@ pub struct DropOptions {
 /// Run the drop command
 pub fn run(options: DropOptions) -> Result<()> {
     let repo = git::open_repo()?;
-    let config = Config::load_with_global(repo.commondir())?;
+    let mut config = Config::load(repo.commondir())?;
 
     // Acquire the operation lock early so all validation runs under it, but
     // defer writing the op-log record until *after* the immutability guard
@@ -161,20 +170,122 @@ pub fn run(options: DropOptions) -> Result<()> {
         }
     }));
 
-    // Build the rebase todo list, omitting dropped commits
-    let drop_indices: Vec<usize> = drop_positions.iter().map(|p| p - 1).collect();
-    let kept_entries: Vec<&crate::stack::StackEntry> = stack_obj
+    match rebase_without(
+        &repo,
+        config.get_remote_for_stack(&stack_obj.name),
+        &stack_obj,
+        &drop_positions,
+    ) {
+        Ok(()) => {}
+        Err(GgError::RebaseConflict) => {
+            let _ = operations::remember_interrupted_rebase_operation(&repo, guard.id());
+            return Err(GgError::RebaseConflict);
+        }
+        Err(e) => return Err(e),
+    }
+
+    // Normalize GG metadata after stack shape change
+    let rewritten_stack = Stack::load(&repo, &config)?;
+    git::normalize_stack_metadata(&repo, &rewritten_stack)?;
+
+    // Clean up per-commit branches for dropped commits
+    for branch_name in &dropped_entry_branches {
+        // Delete local branch (ignore errors if it doesn't exist)
+        let _ = repo
+            .find_branch(branch_name, git2::BranchType::Local)
+            .and_then(|mut b| b.delete());
+    }
+
+    // Forget the dropped entries' PR/MR mappings, closing the PRs/MRs
+    // themselves when asked to.
+    let dropped_prs: Vec<u64> = drop_positions
+        .iter()
+        .filter_map(|&pos| stack_obj.get_entry_by_position(pos))
+        .filter_map(|entry| entry.mr_number)
+        .collect();
+    let closed_prs = if options.close_pr {
+        close_prs(
+            &repo,
+            config.get_remote_for_stack(&stack_obj.name),
+            &mut guard,
+            &dropped_prs,
+            |_| Some("Dropped from the stack by `gg drop`.".to_string()),
+            options.json,
+        )
+    } else {
+        Vec::new()
+    };
+    for &pos in &drop_positions {
+        if let Some(gg_id) = stack_obj
+            .get_entry_by_position(pos)
+            .and_then(|entry| entry.gg_id.as_deref())
+        {
+            config.remove_mr_for_entry(&stack_obj.name, gg_id);
+        }
+    }
+    config.save(repo.commondir())?;
+
+    let remaining = stack_obj.len() - dropped_entries.len();
+
+    // Finalize the op record with post-mutation refs. Closing PRs/MRs is
+    // the only remote effect.
+    let touched_remote = !closed_prs.is_empty();
+    guard.finalize_with_scope(
+        &repo,
+        &config,
+        SnapshotScope::AllUserBranches,
+        vec![],
+        touched_remote,
+    )?;
+
+    if options.json {
+        print_json(&DropResponse {
+            version: OUTPUT_VERSION,
+            drop: DropResultJson {
+                dropped: dropped_entries,
+                remaining,
+                closed_prs,
+            },
+        });
+    } else {
+        println!(
+            "{} Dropped {} commit(s), {} remaining",
+            style("OK").green().bold(),
+            drop_positions.len(),
+            remaining
+        );
+        if !closed_prs.is_empty() {
+            let list: Vec<String> = closed_prs.iter().map(|n| n.to_string()).collect();
+            println!("  Closed: {}", list.join(", "));
+        }
+    }
+
+    Ok(())
+}
+
+/// Rebase the checked-out stack without the entries at `positions`
+/// (1-indexed). `remote` is the stack's remote, where its base is looked up
+/// when there is no local branch. A conflict leaves the rebase paused for
+/// `gg continue`.
+pub(crate) fn rebase_without(
+    repo: &Repository,
+    remote: &str,
+    stack: &Stack,
+    positions: &[usize],
+) -> Result<()> {
+    let kept_entries: Vec<&crate::stack::StackEntry> = stack
         .entries
         .iter()
-        .enumerate()
-        .filter(|(i, _)| !drop_indices.contains(i))
-        .map(|(_, e)| e)
+        .filter(|e| !positions.contains(&e.position))
         .collect();
 
     // Perform rebase omitting dropped commits
-    let base_ref = repo
-        .revparse_single(&stack_obj.base)
-        .or_else(|_| repo.revparse_single(&format!("origin/{}", stack_obj.base)))?;
+    let base_ref = git::resolve_base(repo, remote, &stack.base)?;
+
+    if kept_entries.is_empty() {
+        git::run_git_command(&["reset", "--hard", &base_ref.id().to_string()])?;
+        return Ok(());
+    }
 
     let mut rebase_todo = String::new();
     for entry in &kept_entries {
@@ -200,7 +311,7 @@ pub fn run(options: DropOptions) -> Result<()> {
         std::fs::set_permissions(&script_file, perms)?;
     }
 
-    let output = std::process::Command::new("git")
+    let output = hooks::git_command(GitRewrite::Replay)
         .env("GIT_SEQUENCE_EDITOR", script_file.to_str().unwrap())
         .args(["rebase", "-i", &base_ref.id().to_string()])
         .output()?;
@@ -211,53 +322,10 @@ pub fn run(options: DropOptions) -> Result<()> {
     if !output.status.success() {
         let stderr = String::from_utf8_lossy(&output.stderr);
         if stderr.contains("CONFLICT") || stderr.contains("conflict") {
-            let _ = operations::remember_interrupted_rebase_operation(&repo, guard.id());
             return Err(GgError::RebaseConflict);
         }
         return Err(GgError::Other(format!("Rebase failed: {}", stderr)));
     }
-
-    // Normalize GG metadata after stack shape change
-    let rewritten_stack = Stack::load(&repo, &config)?;
-    git::normalize_stack_metadata(&repo, &rewritten_stack)?;
-
-    // Clean up per-commit branches for dropped commits
-    for branch_name in &dropped_entry_branches {
-        // Delete local branch (ignore errors if it doesn't exist)
-        let _ = repo
-            .find_branch(branch_name, git2::BranchType::Local)
-            .and_then(|mut b| b.delete());
-    }
-
-    let remaining = stack_obj.len() - dropped_entries.len();
-
-    // Finalize the op record with post-mutation refs. Drop is purely
-    // local; no remote effects.
-    guard.finalize_with_scope(
-        &repo,
-        &config,
-        SnapshotScope::AllUserBranches,
-        vec![],
-        false,
-    )?;
-
-    if options.json {
-        print_json(&DropResponse {
-            version: OUTPUT_VERSION,
-            drop: DropResultJson {
-                dropped: dropped_entries,
-                remaining,
-            },
-        });
-    } else {
-        println!(
-            "{} Dropped {} commit(s), {} remaining",
-            style("OK").green().bold(),
-            drop_positions.len(),
-            remaining
-        );
-    }
-
     Ok(())
 }
 
@@ -271,6 +339,7 @@ mod tests {
         assert!(opts.targets.is_empty());
         assert!(!opts.force);
         assert!(!opts.yes);
+        assert!(!opts.close_pr);
         assert!(!opts.json);
     }
 
@@ -280,6 +349,7 @@ mod tests {
             targets: vec!["1".to_string(), "c-abc1234".to_string()],
             force: true,
             yes: false,
+            close_pr: false,
             json: false,
         };
         assert_eq!(opts.targets.len(), 2);
@@ -294,6 +364,7 @@ mod tests {
             targets: vec!["2".to_string()],
             force: false,
             yes: true,
+            close_pr: false,
             json: true,
         };
         assert!(!opts.force);
@@ -0,0 +1,286 @@
+//! `gg edit` - Reword a commit anywhere in the stack
+//!
+//! The entry keeps its GG-ID, so its PR/MR stays mapped. Entries above are
+//! re-parented onto the reworded commit; trees never change, so neither a
+//! rebase nor a clean working directory is needed.
+
+use console::style;
+use dialoguer::Editor;
+use git2::Oid;
+
+use crate::commit_messages::Policy;
+use crate::config::Config;
+use crate::error::{GgError, Result};
+use crate::git;
+use crate::immutability::{self, ImmutabilityPolicy};
+use crate::issues;
+use crate::managed_body;
+use crate::operations::{OperationKind, SnapshotScope};
+use crate::provider::Provider;
+use crate::signing;
+use crate::stack::{self, Stack};
+use crate::template::{self, TemplateStack};
+
+use super::sync::{build_pr_payload, clean_title};
+
+/// Options for the edit command
+#[derive(Debug, Default)]
+pub struct EditOptions {
+    /// Entry to reword: position (1-indexed), short SHA, or GG-ID. None = current.
+    pub target: Option<String>,
+    /// New commit message. None = open $EDITOR.
+    pub message: Option<String>,
+    /// Also update the PR/MR title and description
+    pub update_pr: bool,
+    /// Override the immutability check for merged/base-ancestor commits
+    pub force: bool,
+}
+
+/// Run the edit command
+pub fn run(options: EditOptions) -> Result<()> {
+    let repo = git::open_repo()?;
+    let git_dir = repo.commondir();
+    let config = Config::load(git_dir)?;
+
+    let _lock = git::acquire_operation_lock(&repo, "edit")?;
+
+    let mut stack = Stack::load(&repo, &config)?;
+    immutability::refresh_mr_state_for_guard(&repo, &mut stack);
+    if stack.is_empty() {
+        return Err(GgError::Other("Stack is empty".to_string()));
+    }
+
+    let position = match options.target.as_deref() {
+        Some(target) => stack::resolve_target(&stack, target)?,
+        None => stack.current_position.map(|p| p + 1).unwrap_or(stack.len()),
+    };
+
+    // The entry and every entry above it get rewritten.
+    let positions: Vec<usize> = (position..=stack.len()).collect();
+    let policy = ImmutabilityPolicy::for_stack(&repo, &stack)?;
+    immutability::guard(policy.check_positions(&stack, &positions), options.force)?;
+
+    let entry = &stack.entries[position - 1];
+    let commit = repo.find_commit(entry.oid)?;
+    let current_message = git::strip_gg_parent_from_message(&git::strip_gg_id_from_message(
+        commit.message().unwrap_or(""),
+    ));
+
+    let message = match &options.message {
+        Some(msg) => msg.trim().to_string(),
+        None => Editor::new()
+            .extension(".txt")
+            .edit(&current_message)
+            .map_err(|e| GgError::Other(format!("Editor failed: {}", e)))?
+            .map(|msg| msg.trim().to_string())
+            .unwrap_or_else(|| current_message.clone()),
+    };
+    if message.is_empty() {
+        return Err(GgError::Other(
+            "Empty commit message, aborting edit".to_string(),
+        ));
+    }
+    if message == current_message.trim() {
+        println!("{}", style("Message unchanged.").dim());
+        return Ok(());
+    }
+
+    let mut guard = git::begin_recorded_op(
+        &repo,
+        &config,
+        OperationKind::Edit,
+        std::env::args().skip(1).collect(),
+        Some(stack.name.clone()),
+        SnapshotScope::AllUserBranches,
+    )?;
+
+    let new_message = match &entry.gg_id {
+        Some(gg_id) => git::set_gg_id_in_message(&message, gg_id),
+        None => message.clone(),
+    };
+    let parents: Vec<git2::Commit> = commit.parents().collect();
+    let parent_refs: Vec<&git2::Commit> = parents.iter().collect();
+    let mut tip: Oid = signing::create_commit(
+        &repo,
+        &commit.author(),
+        &git::get_signature(&repo)?,
+        &new_message,
+        &commit.tree()?,
+        &parent_refs,
+    )?;
+
+    for above in &stack.entries[position..] {
+        let commit = repo.find_commit(above.oid)?;
+        let parent = repo.find_commit(tip)?;
+        tip = signing::create_commit(
+            &repo,
+            &commit.author(),
+            &commit.committer(),
+            commit.message().unwrap_or(""),
+            &commit.tree()?,
+            &[&parent],
+        )?;
+    }
+
+    let branch_name = stack.branch_name();
+    repo.reference(&format!("refs/heads/{}", branch_name), tip, true, "gg edit")?;
+
+    // A detached HEAD at or above the entry points at a rewritten commit
+    // with the same tree, so moving HEAD leaves the working directory alone.
+    let rewritten = Stack::load(&repo, &config)?;
+    let detached_at = stack
+        .current_position
+        .filter(|p| p + 1 >= position && repo.head_detached().unwrap_or(false));
+    if let Some(current) = detached_at {
+        if let Some(moved) = rewritten.get_entry_by_position(current + 1) {
+            repo.set_head_detached(moved.oid)?;
+            stack::save_nav_context(repo.path(), &branch_name, current, moved.oid)?;
+        }
+    }
+    git::normalize_stack_metadata(&repo, &rewritten)?;
+
+    println!(
+        "{} Reworded #{}: {}",
+        style("OK").green().bold(),
+        position,
+        message.lines().next().unwrap_or("")
+    );
+
+    let mut touched_remote = false;
+    if options.update_pr {
+        match entry.mr_number {
+            Some(pr) => {
+                touched_remote = update_pr(&repo, &config, &rewritten, position, pr, &message);
+                if touched_remote {
+                    guard.mark_touched_remote();
+                }
+            }
+            None => println!(
+                "{}",
+                style("No PR/MR for this entry yet; `gg sync` will create it.").dim()
+            ),
+        }
+    } else if entry.mr_number.is_some() {
+        println!(
+            "{}",
+            style("Run `gg sync` to push the reworded commit.").dim()
+        );
+    }
+
+    guard.finalize_with_scope(
+        &repo,
+        &config,
+        SnapshotScope::AllUserBranches,
+        vec![],
+        touched_remote,
+    )?;
+
+    Ok(())
+}
+
+/// Update the PR/MR title and the gg-managed part of its description the
+/// way `gg sync` would. Failures are warnings. Returns whether anything
+/// changed remotely.
+fn update_pr(
+    repo: &git2::Repository,
+    config: &Config,
+    stack: &Stack,
+    position: usize,
+    pr: u64,
+    message: &str,
+) -> bool {
+    let provider = match Provider::for_stack(repo, config, &stack.name) {
+        Ok(provider) => provider,
+        Err(e) => {
+            println!(
+                "{} Could not update the PR/MR: {}",
+                style("Warning:").yellow(),
+                e
+            );
+            return false;
+        }
+    };
+    let label = format!(
+        "{} {}{}",
+        provider.pr_label(),
+        provider.pr_number_prefix(),
+        pr
+    );
+
+    let short_sha = stack
+        .get_entry_by_position(position)
+        .map(|e| e.short_sha.clone())
+        .unwrap_or_default();
+    let stack_entries = template::stack_entries(stack);
+    let stack_description = stack::stack_description(repo, config, &stack.name);
+    let mut title = clean_title(message.lines().next().unwrap_or(""));
+    if let Ok(policy) = Policy::from_config(&config.defaults.commit_messages) {
+        title = policy.pr_title(&title, message);
+    }
+    let mut body = git::extract_description_from_message(message);
+    if let Ok(Some(pattern)) = issues::issue_regex(config) {
+        let link = config
+            .defaults
+            .issue_link
+            .as_deref()
+            .unwrap_or(issues::DEFAULT_ISSUE_LINK);
+        body = issues::append_links(
+            body,
+            &issues::entry_keys(&pattern, &stack.name, message),
+            link,
+        );
+    }
+    let (title, description) = build_pr_payload(
+        &title,
+        body,
+        &stack.name,
+        &short_sha,
+        template::load_template(repo.commondir(), repo.workdir()).as_deref(),
+        Some(&TemplateStack {
+            position,
+            base: &stack.base,
+            description: stack_description.as_deref(),
+            number_prefix: provider.pr_number_prefix(),
+            entries: &stack_entries,
+        }),
+    );
+
+    let mut updated = false;
+    match provider.update_pr_title(pr, &title) {
+        Ok(()) => updated = true,
+        Err(e) => println!(
+            "{} Could not update {} title: {}",
+            style("Warning:").yellow(),
+            label,
+            e
+        ),
+    }
+    match provider.get_pr_body(pr) {
+        Ok(body) => match managed_body::replace_managed(&body, &description) {
+            Some(new_body) => match provider.update_pr_description(pr, &new_body) {
+                Ok(()) => updated = true,
+                Err(e) => println!(
+                    "{} Could not update {} description: {}",
+                    style("Warning:").yellow(),
+                    label,
+                    e
+                ),
+            },
+            None => println!(
+                "{} {} has no managed markers, skipping body update",
+                style("Warning:").yellow(),
+                label
+            ),
+        },
+        Err(e) => println!(
+            "{} Could not read {} body, skipping description update: {}",
+            style("Warning:").yellow(),
+            label,
+            e
+        ),
+    }
+    if updated {
+        println!("{} Updated {}", style("OK").green().bold(), label);
+    }
+    updated
+}
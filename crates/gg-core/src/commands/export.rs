@@ -0,0 +1,106 @@
+//! `gg export` - Write the stack out as patch files, an mbox, or a bundle
+//!
+//! Commit messages are exported as-is, GG-ID trailers included, so a stack
+//! applied elsewhere (`git am`, `git fetch <bundle>`) keeps its identity.
+
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::process::Command;
+
+use clap::ValueEnum;
+use console::style;
+
+use crate::config::Config;
+use crate::error::{GgError, Result};
+use crate::git;
+use crate::stack::Stack;
+
+/// Export format
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, ValueEnum)]
+pub enum ExportFormat {
+    /// One numbered patch per entry (`git format-patch`)
+    #[default]
+    Patch,
+    /// All entries in a single mbox file
+    Mbox,
+    /// A git bundle with the stack branch
+    Bundle,
+}
+
+/// Options for the export command
+#[derive(Debug, Default)]
+pub struct ExportOptions {
+    /// Output directory (created if missing)
+    pub dir: PathBuf,
+    /// What to write
+    pub format: ExportFormat,
+}
+
+/// Run the export command
+pub fn run(options: ExportOptions) -> Result<()> {
+    let repo = git::open_repo()?;
+    let config = Config::load(repo.commondir())?;
+
+    let stack = Stack::load_active(&repo, &config)?;
+    let (Some(first), Some(last)) = (stack.first(), stack.last()) else {
+        return Err(GgError::Other("Stack is empty".to_string()));
+    };
+    let base = repo.find_commit(first.oid)?.parent_id(0)?;
+    let range = format!("{}..{}", base, last.oid);
+
+    fs::create_dir_all(&options.dir)?;
+    let written = match options.format {
+        ExportFormat::Patch => {
+            let output =
+                git_output(&["format-patch", "-o", &options.dir.to_string_lossy(), &range])?;
+            output.lines().map(PathBuf::from).collect()
+        }
+        ExportFormat::Mbox => {
+            let path = options.dir.join(format!("{}.mbox", stack.name));
+            let mbox = git_output(&["format-patch", "--stdout", &range])?;
+            fs::write(&path, mbox)?;
+            vec![path]
+        }
+        ExportFormat::Bundle => {
+            let path = options.dir.join(format!("{}.bundle", stack.name));
+            git_output(&[
+                "bundle",
+                "create",
+                &path.to_string_lossy(),
+                &stack.branch_name(),
+                &format!("^{}", base),
+            ])?;
+            vec![path]
+        }
+    };
+
+    println!(
+        "{} Exported {} ({} commits) to {}",
+        style("OK").green().bold(),
+        style(&stack.name).cyan(),
+        stack.len(),
+        style(options.dir.display()).yellow()
+    );
+    for path in &written {
+        println!("  {}", display_name(path));
+    }
+    Ok(())
+}
+
+/// Run git and return its stdout, failing with its stderr.
+fn git_output(args: &[&str]) -> Result<String> {
+    let output = Command::new("git").args(args).output()?;
+    if !output.status.success() {
+        return Err(GgError::Command(
+            format!("git {}", args[0]),
+            String::from_utf8_lossy(&output.stderr).trim().to_string(),
+        ));
+    }
+    Ok(String::from_utf8_lossy(&output.stdout).into_owned())
+}
+
+fn display_name(path: &Path) -> String {
+    path.file_name()
+        .map(|name| name.to_string_lossy().into_owned())
+        .unwrap_or_else(|| path.display().to_string())
+}
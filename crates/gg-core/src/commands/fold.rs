@@ -0,0 +1,215 @@
+//! `gg fold` - Fold a commit into the one below it
+//!
+//! The inverse of `gg split`. The folded commit keeps the lower entry's
+//! GG-ID, so the lower PR/MR carries the combined change and the upper one
+//! is closed with a pointer to it. Entries above are re-parented onto the
+//! new commit; their trees are unchanged, so no rebase is involved.
+
+use console::style;
+use dialoguer::Editor;
+use git2::Oid;
+
+use crate::config::Config;
+use crate::error::{GgError, Result};
+use crate::git;
+use crate::immutability::{self, ImmutabilityPolicy};
+use crate::operations::{OperationKind, SnapshotScope};
+use crate::output::{print_json, FoldResponse, OUTPUT_VERSION};
+use crate::signing;
+use crate::stack::{self, Stack};
+
+use super::squash_all::{close_prs, combined_message, squashed_into_note};
+
+/// Options for the fold command
+#[derive(Debug, Default)]
+pub struct FoldOptions {
+    /// Entry to fold into its parent: position (1-indexed), short SHA, or GG-ID
+    pub target: String,
+    /// Commit message for the folded commit (skips the combined message)
+    pub message: Option<String>,
+    /// Edit the combined message in $EDITOR
+    pub edit: bool,
+    /// Leave the folded entry's PR/MR open
+    pub keep_pr: bool,
+    /// Override the immutability check for merged/base-ancestor commits
+    pub force: bool,
+    /// Output as JSON
+    pub json: bool,
+}
+
+/// Run the fold command
+pub fn run(options: FoldOptions) -> Result<()> {
+    let repo = git::open_repo()?;
+    let git_dir = repo.commondir();
+    let mut config = Config::load(git_dir)?;
+
+    let _lock = git::acquire_operation_lock(&repo, "fold")?;
+    git::require_clean_working_directory(&repo)?;
+
+    if git::current_branch_name(&repo).is_none() {
+        return Err(GgError::Other(
+            "fold must run from the stack branch. Run `gg last` first.".to_string(),
+        ));
+    }
+
+    let mut stack = Stack::load(&repo, &config)?;
+    immutability::refresh_mr_state_for_guard(&repo, &mut stack);
+
+    let position = stack::resolve_target(&stack, &options.target)?;
+    if position == 1 {
+        return Err(GgError::Other(
+            "Cannot fold the first entry: it has no parent in the stack".to_string(),
+        ));
+    }
+
+    // The parent entry and everything from the folded entry up are rewritten.
+    let positions: Vec<usize> = (position - 1..=stack.len()).collect();
+    let policy = ImmutabilityPolicy::for_stack(&repo, &stack)?;
+    immutability::guard(policy.check_positions(&stack, &positions), options.force)?;
+
+    let lower = &stack.entries[position - 2];
+    let upper = &stack.entries[position - 1];
+    let lower_commit = repo.find_commit(lower.oid)?;
+    let upper_commit = repo.find_commit(upper.oid)?;
+
+    let mut message = match &options.message {
+        Some(msg) => msg.clone(),
+        None => combined_message(&[
+            lower_commit.message().unwrap_or(""),
+            upper_commit.message().unwrap_or(""),
+        ]),
+    };
+    if options.edit && options.message.is_none() {
+        message = match Editor::new()
+            .extension(".txt")
+            .edit(&message)
+            .map_err(|e| GgError::Other(format!("Editor failed: {}", e)))?
+        {
+            Some(msg) if !msg.trim().is_empty() => msg.trim().to_string(),
+            None => message,
+            _ => {
+                return Err(GgError::Other(
+                    "Empty commit message, aborting fold".to_string(),
+                ))
+            }
+        };
+    }
+
+    let mut guard = git::begin_recorded_op(
+        &repo,
+        &config,
+        OperationKind::Fold,
+        std::env::args().skip(1).collect(),
+        Some(stack.name.clone()),
+        SnapshotScope::AllUserBranches,
+    )?;
+
+    // The lower entry's GG-ID survives so its PR/MR stays mapped.
+    let kept_gg_id = lower.gg_id.clone().unwrap_or_else(git::generate_gg_id);
+    let parents: Vec<git2::Commit> = lower_commit.parents().collect();
+    let parent_refs: Vec<&git2::Commit> = parents.iter().collect();
+    let folded_oid = signing::create_commit(
+        &repo,
+        &lower_commit.author(),
+        &git::get_signature(&repo)?,
+        &git::set_gg_id_in_message(&message, &kept_gg_id),
+        &upper_commit.tree()?,
+        &parent_refs,
+    )?;
+
+    let mut tip: Oid = folded_oid;
+    for entry in &stack.entries[position..] {
+        let commit = repo.find_commit(entry.oid)?;
+        let parent = repo.find_commit(tip)?;
+        tip = signing::create_commit(
+            &repo,
+            &commit.author(),
+            &commit.committer(),
+            commit.message().unwrap_or(""),
+            &commit.tree()?,
+            &[&parent],
+        )?;
+    }
+
+    repo.reference(
+        &format!("refs/heads/{}", stack.branch_name()),
+        tip,
+        true,
+        "gg fold",
+    )?;
+
+    let rewritten = Stack::load(&repo, &config)?;
+    git::normalize_stack_metadata(&repo, &rewritten)?;
+
+    let kept_pr = lower.mr_number;
+    let folded_pr = upper.mr_number;
+    let closed_prs = if options.keep_pr {
+        Vec::new()
+    } else {
+        let prs: Vec<u64> = folded_pr.into_iter().collect();
+        close_prs(
+            &repo,
+            config.get_remote_for_stack(&stack.name),
+            &mut guard,
+            &prs,
+            |provider| squashed_into_note(provider, kept_pr, "gg fold"),
+            options.json,
+        )
+    };
+    let touched_remote = !closed_prs.is_empty();
+
+    if let Some(gg_id) = &upper.gg_id {
+        if !options.keep_pr {
+            config.remove_mr_for_entry(&stack.name, gg_id);
+        }
+    }
+    if let Some(branch) = stack.entry_branch_name(upper) {
+        let _ = repo
+            .find_branch(&branch, git2::BranchType::Local)
+            .and_then(|mut b| b.delete());
+    }
+    config.save(git_dir)?;
+
+    let final_stack = Stack::load(&repo, &config)?;
+    let sha = final_stack.entries[position - 2].short_sha.clone();
+
+    guard.finalize_with_scope(
+        &repo,
+        &config,
+        SnapshotScope::AllUserBranches,
+        vec![],
+        touched_remote,
+    )?;
+
+    if options.json {
+        print_json(&FoldResponse {
+            version: OUTPUT_VERSION,
+            folded: position,
+            into: position - 1,
+            sha,
+            gg_id: kept_gg_id,
+            pr_number: kept_pr,
+            closed_prs,
+            remaining: final_stack.len(),
+        });
+    } else {
+        println!(
+            "{} Folded #{} into #{} ({}), {} remaining",
+            style("OK").green().bold(),
+            position,
+            position - 1,
+            style(&sha).yellow(),
+            final_stack.len()
+        );
+        if !closed_prs.is_empty() {
+            let list: Vec<String> = closed_prs.iter().map(|n| n.to_string()).collect();
+            println!("  Closed: {}", list.join(", "));
+        }
+        println!(
+            "{}",
+            style("Run `gg sync` to push the folded commit and retarget the PRs above.").dim()
+        );
+    }
+
+    Ok(())
+}
@@ -0,0 +1,240 @@
+//! `gg import` - Adopt an existing branch as a stack
+//!
+//! Every commit between the base and the branch tip becomes a stack entry:
+//! GG-ID trailers are added the same way `gg reconcile` does, the base is
+//! recorded, and an open PR/MR for the branch is mapped to the top entry.
+//! The original branch is left as it is.
+
+use console::style;
+use git2::BranchType;
+
+use crate::config::Config;
+use crate::error::{GgError, Result};
+use crate::git;
+use crate::operations::{OperationKind, SnapshotScope};
+use crate::plain;
+use crate::provider::Provider;
+use crate::stack::Stack;
+
+use super::migrate::stack_name_for;
+use super::reconcile::{find_unmapped_prs, map_prs};
+
+/// Options for the import command
+#[derive(Debug, Default)]
+pub struct ImportOptions {
+    /// Local branch (or `origin/<branch>`) to adopt
+    pub branch: String,
+    /// Stack name. None = derived from the branch name.
+    pub name: Option<String>,
+    /// Base branch. None = configured default or auto-detected.
+    pub base: Option<String>,
+}
+
+/// Run the import command
+pub fn run(options: ImportOptions) -> Result<()> {
+    let repo = git::open_repo()?;
+    let git_dir = repo.commondir();
+    let mut config = Config::load(git_dir)?;
+
+    let provider = Provider::detect(&repo).ok();
+    let username = config
+        .defaults
+        .branch_username
+        .clone()
+        .or_else(|| provider.as_ref().and_then(|p| p.whoami().ok()))
+        .ok_or_else(|| GgError::Command(
+            "git-provider".to_string(),
+            "Could not determine username. Set branch_username in config or authenticate with gh/glab".to_string()
+        ))?;
+    git::validate_branch_username(&username)?;
+
+    let branch = options
+        .branch
+        .strip_prefix("origin/")
+        .unwrap_or(&options.branch)
+        .to_string();
+    let tip = repo
+        .find_branch(&branch, BranchType::Local)
+        .ok()
+        .and_then(|b| b.get().target())
+        .or_else(|| git::get_remote_branch_oid(&repo, git::DEFAULT_REMOTE, &branch))
+        .ok_or_else(|| GgError::Other(format!("Branch '{}' not found", branch)))?;
+    if git::parse_stack_branch(&branch).is_some_and(|(user, _)| user == username) {
+        return Err(GgError::Other(format!(
+            "'{}' is already a stack branch",
+            branch
+        )));
+    }
+
+    let stack_name = match &options.name {
+        Some(name) => git::sanitize_stack_name(name)?,
+        None => stack_name_for(&branch.replace("--", "-"))
+            .map_err(|e| GgError::Other(format!("{} (pick a name with --name)", e)))?,
+    };
+    let stack_branch = git::format_stack_branch(&username, &stack_name);
+    if repo.find_branch(&stack_branch, BranchType::Local).is_ok()
+        || config.get_stack(&stack_name).is_some()
+    {
+        return Err(GgError::Other(format!(
+            "Stack '{}' already exists (pick another name with --name)",
+            stack_name
+        )));
+    }
+
+    let base = options
+        .base
+        .clone()
+        .or_else(|| config.defaults.base.clone())
+        .or_else(|| git::find_base_branch(&repo).ok())
+        .ok_or(GgError::NoBaseBranch)?;
+    let base_oid = git::resolve_base(&repo, git::DEFAULT_REMOTE, &base)
+        .map_err(|_| GgError::NoBaseBranch)?
+        .id();
+    if repo.graph_ahead_behind(tip, base_oid)?.0 == 0 {
+        return Err(GgError::Other(format!(
+            "'{}' has no commits on top of {}",
+            branch, base
+        )));
+    }
+
+    let (_lock, guard) = git::acquire_operation_lock_and_record(
+        &repo,
+        &config,
+        OperationKind::Import,
+        std::env::args().skip(1).collect(),
+        Some(stack_name.clone()),
+        SnapshotScope::AllUserBranches,
+    )?;
+
+    let default_base = config
+        .defaults
+        .base
+        .clone()
+        .unwrap_or_else(|| "main".to_string());
+    if base != default_base {
+        config.get_or_create_stack(&stack_name).base = Some(base.clone());
+    } else {
+        config.get_or_create_stack(&stack_name);
+    }
+
+    repo.branch(&stack_branch, &repo.find_commit(tip)?, false)?;
+    let stack = match Stack::load_named(&repo, &config, &username, &stack_name) {
+        Ok(stack) => stack,
+        Err(e) => {
+            if let Ok(mut created) = repo.find_branch(&stack_branch, BranchType::Local) {
+                let _ = created.delete();
+            }
+            return Err(e);
+        }
+    };
+
+    git::normalize_stack_metadata(&repo, &stack)?;
+    let stack = Stack::load_named(&repo, &config, &username, &stack_name)?;
+
+    println!(
+        "{} Imported {} as stack {} ({} commits on {})",
+        style("OK").green().bold(),
+        style(&branch).yellow(),
+        style(&stack_name).cyan(),
+        stack.len(),
+        style(&base).yellow()
+    );
+
+    let mut touched_remote = false;
+    match provider.filter(|p| p.check_auth().is_ok()) {
+        Some(provider) => {
+            touched_remote = map_branch_pr(&repo, &mut config, &provider, &stack, &branch)?;
+            let prs = find_unmapped_prs(&repo, &stack, &config, &provider)?;
+            map_prs(&mut config, &stack.name, &prs, &provider)?;
+        }
+        None => println!(
+            "{}",
+            style("  (Skipping PR/MR lookup - provider not available)").dim()
+        ),
+    }
+
+    if config.defaults.branch_username.is_none() {
+        config.defaults.branch_username = Some(username);
+    }
+    config.save(git_dir)?;
+
+    guard.finalize_with_scope(
+        &repo,
+        &config,
+        SnapshotScope::AllUserBranches,
+        vec![],
+        touched_remote,
+    )?;
+
+    println!(
+        "{}",
+        style(format!(
+            "Run `gg co {}` to switch to it, then `gg sync` to push it.",
+            stack_name
+        ))
+        .dim()
+    );
+    Ok(())
+}
+
+/// Map the branch's open PR/MR to the top entry, which has the same tip.
+/// Where the provider allows it, the remote branch is renamed to the entry
+/// branch so the PR/MR and its reviews carry over. Returns whether the
+/// remote was touched.
+fn map_branch_pr(
+    repo: &git2::Repository,
+    config: &mut Config,
+    provider: &Provider,
+    stack: &Stack,
+    branch: &str,
+) -> Result<bool> {
+    let Some(pr) = provider
+        .list_prs_for_branch(branch)
+        .ok()
+        .and_then(|prs| prs.first().copied())
+    else {
+        return Ok(false);
+    };
+    let Some(top) = stack.last() else {
+        return Ok(false);
+    };
+    let (Some(gg_id), Some(entry_branch)) = (&top.gg_id, stack.entry_branch_name(top)) else {
+        return Ok(false);
+    };
+
+    config.set_mr_for_entry(&stack.name, gg_id, pr);
+    println!(
+        "{} Mapped {} {}{} to #{}",
+        style("OK").green().bold(),
+        provider.pr_label(),
+        provider.pr_number_prefix(),
+        pr,
+        top.position
+    );
+
+    if !provider.supports_branch_rename()
+        || git::get_remote_branch_oid(repo, git::DEFAULT_REMOTE, branch).is_none()
+    {
+        println!(
+            "{}",
+            style(format!(
+                "  `gg sync` will replace {}{} with one for the entry branch.",
+                provider.pr_number_prefix(),
+                pr
+            ))
+            .dim()
+        );
+        return Ok(false);
+    }
+    git::guard_protected_branch(config, branch)?;
+    git::guard_protected_branch(config, &entry_branch)?;
+    provider.rename_branch(branch, &entry_branch)?;
+    let _ = git::fetch_and_prune(git::DEFAULT_REMOTE);
+    println!(
+        "{} Renamed origin/{} to {}",
+        style(plain::arrow()).cyan(),
+        branch,
+        entry_branch
+    );
+    Ok(true)
+}
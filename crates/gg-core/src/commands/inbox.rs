@@ -13,6 +13,7 @@ use crate::output::{
     print_json, InboxBucketsJson, InboxEntryJson, InboxResponse, InboxStackErrorJson,
     OUTPUT_VERSION,
 };
+use crate::plain;
 use crate::provider::{CiStatus, PrState, Provider};
 use crate::stack;
 
@@ -112,10 +113,15 @@ fn resolve_base_branch(
 
 fn load_stack_entries(
     repo: &git2::Repository,
+    remote: &str,
     base: &str,
     full_branch: &str,
 ) -> Result<Vec<stack::StackEntry>> {
-    let oids = git::get_stack_commit_oids(repo, base, Some(full_branch))?;
+    let base = git::resolve_base(repo, remote, base)
+        .map_err(|_| GgError::NoBaseBranch)?
+        .id()
+        .to_string();
+    let oids = git::get_stack_commit_oids(repo, &base, Some(full_branch))?;
 
     oids.iter()
         .enumerate()
@@ -182,7 +188,7 @@ fn infer_stack_usernames(repo: &git2::Repository, config: &Config) -> Result<Vec
 
 pub fn run(all: bool, json: bool) -> Result<()> {
     let repo = git::open_repo()?;
-    let config = Config::load_with_global(repo.commondir())?;
+    let config = Config::load(repo.commondir())?;
 
     let usernames = infer_stack_usernames(&repo, &config)?;
     if usernames.is_empty() {
@@ -272,7 +278,8 @@ pub fn run(all: bool, json: bool) -> Result<()> {
                 continue;
             }
         };
-        let mut entries = match load_stack_entries(&repo, &base, full_branch) {
+        let remote = config.get_remote_for_stack(stack_name);
+        let mut entries = match load_stack_entries(&repo, remote, &base, full_branch) {
             Ok(entries) => entries,
             Err(err) => {
                 stack_errors.push(StackLoadError {
@@ -294,7 +301,7 @@ pub fn run(all: bool, json: bool) -> Result<()> {
         }
 
         let provider = if entries.iter().any(|entry| entry.mr_number.is_some()) {
-            detected_provider
+            Provider::for_stack(&repo, &config, stack_name).ok()
         } else {
             None
         };
@@ -323,11 +330,11 @@ pub fn run(all: bool, json: bool) -> Result<()> {
 
         // Compute behind-base from the actual stack tip, not the local base branch.
         // This avoids false positives when local `<base>` is stale but the stack
-        // itself has already been rebased onto `origin/<base>`.
-        let behind =
-            git::count_branch_behind_upstream(&repo, full_branch, &format!("origin/{}", base))
-                .ok()
-                .filter(|&b| b > 0);
+        // itself has already been rebased onto `<remote>/<base>`.
+        let upstream = format!("{}/{}", remote, base);
+        let behind = git::count_branch_behind_upstream(&repo, full_branch, &upstream)
+            .ok()
+            .filter(|&b| b > 0);
 
         // Bucket each entry with a PR/MR
         for entry in &entries {
@@ -446,8 +453,10 @@ fn print_human_output(items: &[InboxItem], stack_errors: &[StackLoadError]) {
 
         for item in &group {
             let ci_icon = match &item.ci_status {
-                Some(CiStatus::Running) | Some(CiStatus::Pending) => " ⏳",
-                Some(CiStatus::Failed) => " ✗",
+                Some(CiStatus::Running) | Some(CiStatus::Pending) => {
+                    plain::sym(" ⏳", " [ci running]")
+                }
+                Some(CiStatus::Failed) => plain::sym(" ✗", " [ci failed]"),
                 _ => "",
             };
 
@@ -0,0 +1,509 @@
+//! Insights command — landing metrics derived from the operation log.
+//!
+//! Everything here is computed from `<commondir>/gg/operations/*.json`, so
+//! the numbers only cover the records still inside the op-log ring buffer
+//! (see [`crate::operations::OPERATION_LOG_CAP`]). PR/MR counts come from
+//! the `PrCreated` / `PrMerged` remote effects that `gg sync` and `gg land`
+//! record after talking to the provider.
+//!
+//! Review turnaround and time-to-merge are not reported: the op log only
+//! knows when `gg` itself ran, not when reviewers approved or when a PR was
+//! merged outside of `gg land`.
+
+use std::collections::BTreeMap;
+
+use console::style;
+
+use crate::error::Result;
+use crate::git;
+use crate::operations::{
+    OperationKind, OperationRecord, OperationStatus, OperationStore, RemoteEffect,
+    OPERATION_LOG_CAP,
+};
+use crate::output::{
+    print_json, InsightsResponse, InsightsStackJson, InsightsSummaryJson, OUTPUT_VERSION,
+};
+use crate::plain;
+
+const MS_PER_WEEK: u64 = 7 * 24 * 60 * 60 * 1000;
+
+/// Metrics for a single stack.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct StackInsights {
+    pub name: String,
+    /// Completed `gg sync` runs.
+    pub syncs: usize,
+    /// `gg land` runs that merged at least one PR/MR.
+    pub lands: usize,
+    /// Operations that stopped on a rebase conflict.
+    pub conflicts: usize,
+    pub prs_created: usize,
+    pub prs_merged: usize,
+    /// First sync of the most recent sync → land cycle.
+    pub first_sync_at_ms: Option<u64>,
+    pub last_land_at_ms: Option<u64>,
+    /// Sync → land duration of every completed cycle, oldest first. A land
+    /// closes the cycle, so a stack name reused afterwards starts a new one.
+    pub landed_cycles_ms: Vec<u64>,
+}
+
+impl StackInsights {
+    /// Sync → land duration of the most recent completed cycle.
+    pub fn sync_to_land_ms(&self) -> Option<u64> {
+        self.landed_cycles_ms.last().copied()
+    }
+}
+
+/// Aggregated metrics across every stack seen in the op log.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct Insights {
+    pub operations: usize,
+    pub window_start_ms: Option<u64>,
+    pub window_end_ms: Option<u64>,
+    pub stacks: Vec<StackInsights>,
+    pub syncs: usize,
+    pub lands: usize,
+    pub conflicts: usize,
+    pub prs_created: usize,
+    pub prs_merged: usize,
+}
+
+impl Insights {
+    /// Number of stacks with a measurable sync → land duration.
+    pub fn stacks_landed(&self) -> usize {
+        self.stacks
+            .iter()
+            .filter(|s| s.sync_to_land_ms().is_some())
+            .count()
+    }
+
+    /// Median sync → land duration across every completed cycle.
+    pub fn median_sync_to_land_ms(&self) -> Option<u64> {
+        let mut durations: Vec<u64> = self
+            .stacks
+            .iter()
+            .flat_map(|s| s.landed_cycles_ms.iter().copied())
+            .collect();
+        if durations.is_empty() {
+            return None;
+        }
+        durations.sort_unstable();
+        let mid = durations.len() / 2;
+        if durations.len().is_multiple_of(2) {
+            Some((durations[mid - 1] + durations[mid]) / 2)
+        } else {
+            Some(durations[mid])
+        }
+    }
+
+    /// Observed window length in weeks, floored at one week so a fresh op
+    /// log does not report inflated weekly rates.
+    pub fn weeks(&self) -> f64 {
+        let span = match (self.window_start_ms, self.window_end_ms) {
+            (Some(start), Some(end)) => end.saturating_sub(start),
+            _ => 0,
+        };
+        span.max(MS_PER_WEEK) as f64 / MS_PER_WEEK as f64
+    }
+
+    pub fn prs_created_per_week(&self) -> f64 {
+        self.prs_created as f64 / self.weeks()
+    }
+
+    pub fn prs_merged_per_week(&self) -> f64 {
+        self.prs_merged as f64 / self.weeks()
+    }
+}
+
+/// Attribute a record to a stack. Prefers the recorded `stack_name`, and
+/// falls back to the branches named in its remote effects so records written
+/// before sync/land started tagging their stack still count.
+fn record_stack_name(record: &OperationRecord) -> Option<String> {
+    if let Some(name) = &record.stack_name {
+        return Some(name.clone());
+    }
+    record.remote_effects.iter().find_map(|effect| {
+        let branch = match effect {
+            RemoteEffect::Pushed { branch, .. } | RemoteEffect::BranchDeleted { branch, .. } => {
+                branch
+            }
+            _ => return None,
+        };
+        git::parse_entry_branch(branch)
+            .map(|(_, stack, _)| stack)
+            .or_else(|| git::parse_stack_branch(branch).map(|(_, stack)| stack))
+    })
+}
+
+/// Compute insights from operation records (any order).
+pub fn compute(records: &[OperationRecord]) -> Insights {
+    let mut insights = Insights {
+        operations: records.len(),
+        window_start_ms: records.iter().map(|r| r.created_at_ms).min(),
+        window_end_ms: records.iter().map(|r| r.created_at_ms).max(),
+        ..Default::default()
+    };
+    let mut per_stack: BTreeMap<String, StackInsights> = BTreeMap::new();
+    // Start of the not-yet-landed cycle for each stack.
+    let mut open_cycles: BTreeMap<String, u64> = BTreeMap::new();
+
+    let mut ordered: Vec<&OperationRecord> = records.iter().collect();
+    ordered.sort_by_key(|r| r.created_at_ms);
+
+    for record in ordered {
+        let created = record
+            .remote_effects
+            .iter()
+            .filter(|e| matches!(e, RemoteEffect::PrCreated { .. }))
+            .count();
+        let merged = record
+            .remote_effects
+            .iter()
+            .filter(|e| matches!(e, RemoteEffect::PrMerged { .. }))
+            .count();
+        let is_sync =
+            record.kind == OperationKind::Sync && record.status == OperationStatus::Committed;
+        let is_land = record.kind == OperationKind::Land && merged > 0;
+
+        insights.prs_created += created;
+        insights.prs_merged += merged;
+        insights.conflicts += usize::from(record.conflicted);
+        insights.syncs += usize::from(is_sync);
+        insights.lands += usize::from(is_land);
+
+        let Some(name) = record_stack_name(record) else {
+            continue;
+        };
+        let stack = per_stack
+            .entry(name.clone())
+            .or_insert_with(|| StackInsights {
+                name: name.clone(),
+                ..Default::default()
+            });
+        stack.prs_created += created;
+        stack.prs_merged += merged;
+        stack.conflicts += usize::from(record.conflicted);
+        if is_sync {
+            stack.syncs += 1;
+            if !open_cycles.contains_key(&name) {
+                open_cycles.insert(name.clone(), record.created_at_ms);
+                stack.first_sync_at_ms = Some(record.created_at_ms);
+            }
+        }
+        if is_land {
+            stack.lands += 1;
+            stack.last_land_at_ms = Some(record.created_at_ms);
+            if let Some(start) = open_cycles.remove(&name) {
+                stack
+                    .landed_cycles_ms
+                    .push(record.created_at_ms.saturating_sub(start));
+            }
+        }
+    }
+
+    insights.stacks = per_stack.into_values().collect();
+    insights
+}
+
+/// Format a millisecond duration as e.g. "3d 4h", "2h 5m", "45m" or "<1m".
+pub fn format_duration_ms(ms: u64) -> String {
+    let minutes = ms / 60_000;
+    let hours = minutes / 60;
+    let days = hours / 24;
+    if days > 0 {
+        format!("{}d {}h", days, hours % 24)
+    } else if hours > 0 {
+        format!("{}h {}m", hours, minutes % 60)
+    } else if minutes > 0 {
+        format!("{}m", minutes)
+    } else {
+        "<1m".to_string()
+    }
+}
+
+fn plural(count: usize, singular: &str, plural: &str) -> String {
+    if count == 1 {
+        format!("{} {}", count, singular)
+    } else {
+        format!("{} {}", count, plural)
+    }
+}
+
+/// Run the insights command
+pub fn run(json: bool) -> Result<()> {
+    let repo = git::open_repo()?;
+    let store = OperationStore::new(&git::gg_dir(&repo));
+    let records = store.list(OPERATION_LOG_CAP)?;
+    let insights = compute(&records);
+
+    if json {
+        print_json(&InsightsResponse {
+            version: OUTPUT_VERSION,
+            insights: to_json(&insights),
+        });
+        return Ok(());
+    }
+
+    print_human(&insights);
+    Ok(())
+}
+
+fn to_json(insights: &Insights) -> InsightsSummaryJson {
+    InsightsSummaryJson {
+        operations: insights.operations,
+        window_start_ms: insights.window_start_ms,
+        window_end_ms: insights.window_end_ms,
+        syncs: insights.syncs,
+        lands: insights.lands,
+        conflicts: insights.conflicts,
+        prs_created: insights.prs_created,
+        prs_merged: insights.prs_merged,
+        prs_created_per_week: insights.prs_created_per_week(),
+        prs_merged_per_week: insights.prs_merged_per_week(),
+        stacks_landed: insights.stacks_landed(),
+        median_sync_to_land_ms: insights.median_sync_to_land_ms(),
+        stacks: insights
+            .stacks
+            .iter()
+            .map(|s| InsightsStackJson {
+                name: s.name.clone(),
+                syncs: s.syncs,
+                lands: s.lands,
+                conflicts: s.conflicts,
+                prs_created: s.prs_created,
+                prs_merged: s.prs_merged,
+                first_sync_at_ms: s.first_sync_at_ms,
+                last_land_at_ms: s.last_land_at_ms,
+                sync_to_land_ms: s.sync_to_land_ms(),
+            })
+            .collect(),
+    }
+}
+
+fn print_human(insights: &Insights) {
+    if insights.operations == 0 {
+        println!("{}", style("No operations recorded yet.").dim());
+        return;
+    }
+
+    println!(
+        "{} {}",
+        style("Insights").bold(),
+        style(format!(
+            "(last {} over {:.1} weeks)",
+            plural(insights.operations, "operation", "operations"),
+            insights.weeks()
+        ))
+        .dim()
+    );
+    println!();
+
+    let width = insights
+        .stacks
+        .iter()
+        .map(|s| s.name.len())
+        .max()
+        .unwrap_or(0);
+    for stack in &insights.stacks {
+        let landed = match stack.sync_to_land_ms() {
+            Some(ms) => style(format!(
+                "sync {} land {}",
+                plain::arrow(),
+                format_duration_ms(ms)
+            ))
+            .green(),
+            None => style("not landed".to_string()).dim(),
+        };
+        println!(
+            "  {}  {}  {}  {} created  {} merged  {}",
+            style(format!("{:<width$}", stack.name, width = width)).cyan(),
+            plural(stack.syncs, "sync", "syncs"),
+            plural(stack.conflicts, "conflict", "conflicts"),
+            stack.prs_created,
+            stack.prs_merged,
+            landed,
+        );
+    }
+    if !insights.stacks.is_empty() {
+        println!();
+    }
+
+    println!("{}", style("Overall").bold());
+    println!("  Stacks landed:       {}", insights.stacks_landed());
+    println!(
+        "  Median sync {} land:  {}",
+        plain::arrow(),
+        insights
+            .median_sync_to_land_ms()
+            .map(format_duration_ms)
+            .unwrap_or_else(|| "-".to_string())
+    );
+    println!("  Sync cycles:         {}", insights.syncs);
+    println!("  Conflicts hit:       {}", insights.conflicts);
+    println!(
+        "  PRs per week:        {:.1} created, {:.1} merged",
+        insights.prs_created_per_week(),
+        insights.prs_merged_per_week()
+    );
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::operations::tests::make_record;
+
+    const HOUR: u64 = 60 * 60 * 1000;
+
+    fn record(kind: OperationKind, ts: u64, stack: Option<&str>) -> OperationRecord {
+        let mut r = make_record(kind, ts);
+        r.stack_name = stack.map(str::to_string);
+        r
+    }
+
+    fn merged(number: u64) -> RemoteEffect {
+        RemoteEffect::PrMerged {
+            number,
+            url: format!("https://example.com/pr/{number}"),
+        }
+    }
+
+    fn created(number: u64) -> RemoteEffect {
+        RemoteEffect::PrCreated {
+            number,
+            url: format!("https://example.com/pr/{number}"),
+        }
+    }
+
+    #[test]
+    fn compute_empty_log() {
+        let insights = compute(&[]);
+        assert_eq!(insights.operations, 0);
+        assert!(insights.stacks.is_empty());
+        assert_eq!(insights.median_sync_to_land_ms(), None);
+        assert_eq!(insights.prs_created_per_week(), 0.0);
+    }
+
+    #[test]
+    fn compute_sync_to_land_per_stack() {
+        let mut s1 = record(OperationKind::Sync, HOUR, Some("auth"));
+        s1.remote_effects = vec![created(1), created(2)];
+        let s2 = record(OperationKind::Sync, 3 * HOUR, Some("auth"));
+        let mut land = record(OperationKind::Land, 10 * HOUR, Some("auth"));
+        land.remote_effects = vec![merged(1), merged(2)];
+
+        let insights = compute(&[land, s2, s1]);
+        assert_eq!(insights.stacks.len(), 1);
+        let auth = &insights.stacks[0];
+        assert_eq!(auth.syncs, 2);
+        assert_eq!(auth.lands, 1);
+        assert_eq!(auth.prs_created, 2);
+        assert_eq!(auth.prs_merged, 2);
+        assert_eq!(auth.sync_to_land_ms(), Some(9 * HOUR));
+        assert_eq!(insights.median_sync_to_land_ms(), Some(9 * HOUR));
+        assert_eq!(insights.stacks_landed(), 1);
+    }
+
+    #[test]
+    fn compute_restarts_cycle_when_stack_name_is_reused() {
+        let s1 = record(OperationKind::Sync, HOUR, Some("auth"));
+        let mut land1 = record(OperationKind::Land, 3 * HOUR, Some("auth"));
+        land1.remote_effects = vec![merged(1)];
+        let s2 = record(OperationKind::Sync, 100 * HOUR, Some("auth"));
+        let s3 = record(OperationKind::Sync, 101 * HOUR, Some("auth"));
+        let mut land2 = record(OperationKind::Land, 110 * HOUR, Some("auth"));
+        land2.remote_effects = vec![merged(2)];
+
+        let insights = compute(&[land2, s3, land1, s2, s1]);
+        let auth = &insights.stacks[0];
+        assert_eq!(auth.lands, 2);
+        assert_eq!(auth.landed_cycles_ms, vec![2 * HOUR, 10 * HOUR]);
+        assert_eq!(auth.first_sync_at_ms, Some(100 * HOUR));
+        assert_eq!(auth.last_land_at_ms, Some(110 * HOUR));
+        assert_eq!(auth.sync_to_land_ms(), Some(10 * HOUR));
+        assert_eq!(insights.median_sync_to_land_ms(), Some(6 * HOUR));
+        assert_eq!(insights.stacks_landed(), 1);
+    }
+
+    #[test]
+    fn compute_open_cycle_after_land_is_not_landed_yet() {
+        let s1 = record(OperationKind::Sync, HOUR, Some("auth"));
+        let mut land = record(OperationKind::Land, 2 * HOUR, Some("auth"));
+        land.remote_effects = vec![merged(1)];
+        let s2 = record(OperationKind::Sync, 50 * HOUR, Some("auth"));
+
+        let insights = compute(&[s1, land, s2]);
+        let auth = &insights.stacks[0];
+        assert_eq!(auth.landed_cycles_ms, vec![HOUR]);
+        assert_eq!(auth.first_sync_at_ms, Some(50 * HOUR));
+        assert_eq!(insights.median_sync_to_land_ms(), Some(HOUR));
+    }
+
+    #[test]
+    fn compute_ignores_land_without_merges_and_uncommitted_syncs() {
+        let mut sync = record(OperationKind::Sync, HOUR, Some("feat"));
+        sync.status = OperationStatus::Interrupted;
+        let land = record(OperationKind::Land, 2 * HOUR, Some("feat"));
+
+        let insights = compute(&[sync, land]);
+        assert_eq!(insights.syncs, 0);
+        assert_eq!(insights.lands, 0);
+        assert_eq!(insights.stacks[0].sync_to_land_ms(), None);
+    }
+
+    #[test]
+    fn compute_counts_conflicts() {
+        let mut rebase = record(OperationKind::Rebase, HOUR, Some("feat"));
+        rebase.conflicted = true;
+        let insights = compute(&[rebase, record(OperationKind::Sync, 0, Some("feat"))]);
+        assert_eq!(insights.conflicts, 1);
+        assert_eq!(insights.stacks[0].conflicts, 1);
+    }
+
+    #[test]
+    fn compute_attributes_untagged_records_by_pushed_branch() {
+        let mut sync = record(OperationKind::Sync, HOUR, None);
+        sync.remote_effects = vec![RemoteEffect::Pushed {
+            remote: "origin".into(),
+            branch: "nacho/billing--c-abc1234".into(),
+            force: true,
+        }];
+        let insights = compute(&[sync]);
+        assert_eq!(insights.stacks[0].name, "billing");
+        assert_eq!(insights.stacks[0].syncs, 1);
+    }
+
+    #[test]
+    fn median_averages_even_counts() {
+        let mut records = Vec::new();
+        for (stack, land_at) in [("a", 2 * HOUR), ("b", 4 * HOUR)] {
+            records.push(record(OperationKind::Sync, 0, Some(stack)));
+            let mut land = record(OperationKind::Land, land_at, Some(stack));
+            land.remote_effects = vec![merged(1)];
+            records.push(land);
+        }
+        assert_eq!(compute(&records).median_sync_to_land_ms(), Some(3 * HOUR));
+    }
+
+    #[test]
+    fn weekly_rates_use_at_least_one_week() {
+        let mut first = record(OperationKind::Sync, 0, Some("a"));
+        first.remote_effects = vec![created(1), created(2)];
+        let insights = compute(std::slice::from_ref(&first));
+        assert_eq!(insights.weeks(), 1.0);
+        assert_eq!(insights.prs_created_per_week(), 2.0);
+
+        let mut later = record(OperationKind::Sync, 2 * MS_PER_WEEK, Some("a"));
+        later.remote_effects = vec![created(3), created(4)];
+        let insights = compute(&[first, later]);
+        assert_eq!(insights.weeks(), 2.0);
+        assert_eq!(insights.prs_created_per_week(), 2.0);
+    }
+
+    #[test]
+    fn format_duration_ms_units() {
+        assert_eq!(format_duration_ms(30_000), "<1m");
+        assert_eq!(format_duration_ms(5 * 60_000), "5m");
+        assert_eq!(format_duration_ms(2 * HOUR + 5 * 60_000), "2h 5m");
+        assert_eq!(format_duration_ms(52 * HOUR), "2d 4h");
+    }
+}
@@ -0,0 +1,50 @@
+//! `gg label` - Add or remove labels on an entry's PR/MR
+
+use console::style;
+
+use crate::config::Config;
+use crate::error::{GgError, Result};
+use crate::git;
+use crate::provider::Provider;
+use crate::stack::{self, Stack};
+
+/// Run `gg label <target> <labels>...`
+pub fn run(target: String, labels: Vec<String>, remove: bool) -> Result<()> {
+    let repo = git::open_repo()?;
+    let config = Config::load(repo.commondir())?;
+    let stack = Stack::load_active(&repo, &config)?;
+
+    let pos = stack::resolve_target(&stack, &target)?;
+    let entry = stack
+        .get_entry_by_position(pos)
+        .ok_or_else(|| GgError::Other(format!("No entry at position {}", pos)))?;
+
+    let provider = Provider::for_stack(&repo, &config, &stack.name)?;
+    let pr_number = entry.mr_number.ok_or_else(|| {
+        GgError::Other(format!(
+            "Entry {} has no {}. Run `gg sync` first.",
+            pos,
+            provider.pr_label()
+        ))
+    })?;
+    provider.check_installed()?;
+    provider.check_auth()?;
+
+    if remove {
+        provider.remove_labels(pr_number, &labels)?;
+    } else {
+        provider.add_labels(pr_number, &labels)?;
+    }
+
+    println!(
+        "{} {} {} {} {} {}{}",
+        style("OK").green().bold(),
+        if remove { "Removed" } else { "Added" },
+        style(labels.join(", ")).cyan(),
+        if remove { "from" } else { "to" },
+        provider.pr_label(),
+        provider.pr_number_prefix(),
+        pr_number
+    );
+    Ok(())
+}
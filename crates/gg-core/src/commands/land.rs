@@ -1,6 +1,8 @@
 //! `gg land` - Merge approved PRs/MRs starting from the first commit
 
 use std::collections::HashSet;
+use std::fs;
+use std::path::Path;
 use std::sync::atomic::{AtomicBool, Ordering};
 use std::sync::Arc;
 use std::time::{Duration, Instant};
@@ -8,15 +10,25 @@ use std::time::{Duration, Instant};
 use console::style;
 use dialoguer::Confirm;
 use indicatif::{ProgressBar, ProgressStyle};
+use serde::{Deserialize, Serialize};
 
-use crate::config::Config;
+use crate::changelog::{self, MergedPr};
+use crate::config::{ChangelogMode, Config, MergeStrategy};
 use crate::error::{GgError, Result};
 use crate::git;
 use crate::glab::AutoMergeResult;
+use crate::hooks::{self, GitRewrite, Hook, HookContext, HookEntry};
+use crate::issues::{self, LandedIssue};
+use crate::notify::{self, Notification};
 use crate::operations::{OperationKind, RemoteEffect, SnapshotScope};
-use crate::output::{print_json, LandResponse, LandResultJson, LandedEntryJson, OUTPUT_VERSION};
+use crate::output::{
+    print_json, LandChangelogJson, LandResponse, LandResultJson, LandedEntryJson, OUTPUT_VERSION,
+};
+use crate::plain;
+use crate::protection::{self, Stage};
 use crate::provider::{CiStatus, PrState, Provider};
-use crate::stack::{resolve_target, Stack};
+use crate::stack::{resolve_target, Stack, StackEntry};
+use crate::webhook::WebhookListener;
 
 /// Format elapsed duration as human-readable string (e.g., "2m15s", "45s")
 fn format_duration(elapsed: Duration) -> String {
@@ -31,8 +43,16 @@ fn format_duration(elapsed: Duration) -> String {
     }
 }
 
-/// Create a spinner progress bar with elapsed time
+/// Create a spinner progress bar with elapsed time.
+///
+/// In plain mode the spinner is hidden and the message is printed once.
 fn create_spinner(message: &str) -> ProgressBar {
+    if plain::is_enabled() {
+        eprintln!("{} {}", plain::arrow(), message);
+        let spinner = ProgressBar::hidden();
+        spinner.set_message(message.to_string());
+        return spinner;
+    }
     let spinner = ProgressBar::new_spinner();
     spinner.set_style(
         ProgressStyle::default_spinner()
@@ -48,11 +68,15 @@ fn create_spinner(message: &str) -> ProgressBar {
 /// Finish a spinner with a success checkmark and elapsed time
 fn finish_spinner(spinner: &ProgressBar, message: &str, start_time: Instant) {
     let elapsed = format_duration(start_time.elapsed());
-    spinner.finish_with_message(format!("{} {} - {}", style("✓").green(), elapsed, message));
+    let line = format!("{} {} - {}", style(plain::ok()).green(), elapsed, message);
+    if plain::is_enabled() {
+        eprintln!("{}", line);
+    }
+    spinner.finish_with_message(line);
 }
 
 /// Sleep in small chunks so Ctrl+C interruption is handled promptly.
-fn interruptible_sleep(
+pub(crate) fn interruptible_sleep(
     duration: Duration,
     interrupted: Option<&Arc<AtomicBool>>,
     current_spinner: Option<&ProgressBar>,
@@ -78,12 +102,39 @@ fn interruptible_sleep(
     Ok(())
 }
 
+/// Wait before the next status check: until a webhook for `pr_num` arrives
+/// (or the fallback interval passes) when listening, else a plain sleep.
+fn wait_next_poll(
+    listener: Option<&WebhookListener>,
+    pr_num: u64,
+    poll_interval: Duration,
+    interrupted: Option<&Arc<AtomicBool>>,
+    current_spinner: Option<&ProgressBar>,
+) -> Result<()> {
+    let Some(listener) = listener else {
+        return interruptible_sleep(poll_interval, interrupted, current_spinner);
+    };
+    let fallback = Duration::from_secs(LISTEN_FALLBACK_POLL_SECS);
+    listener
+        .wait_for(pr_num, fallback, interrupted)
+        .map(|_| ())
+        .inspect_err(|_| {
+            if let Some(spinner) = current_spinner {
+                spinner.finish_and_clear();
+            }
+        })
+}
+
 /// Polling interval (10 seconds)
-const POLL_INTERVAL_SECS: u64 = 10;
+pub(crate) const POLL_INTERVAL_SECS: u64 = 10;
+
+/// With `--listen`, webhook deliveries trigger re-checks; this fallback poll
+/// covers missed or unforwarded events.
+const LISTEN_FALLBACK_POLL_SECS: u64 = 60;
 
 /// Maximum number of consecutive API-like failures (hard API errors or
 /// `MergeTrainStatus::Unknown` status responses caused by endpoint failures).
-const MAX_CONSECUTIVE_API_ERRORS: u32 = 5;
+pub(crate) const MAX_CONSECUTIVE_API_ERRORS: u32 = 5;
 
 /// Number of consecutive Idle polls after which we show an explicit
 /// "still waiting" state message while continuing to poll.
@@ -94,6 +145,35 @@ const MAX_CONSECUTIVE_API_ERRORS: u32 = 5;
 /// aggressive and can fail healthy `gg land --wait --all` flows.
 const IDLE_STILL_WAITING_POLLS: u32 = 6;
 
+/// Number of consecutive Stale polls after which `gg land --wait` rebases the
+/// MR and re-adds it to the merge train instead of waiting for the timeout.
+const STALE_REFRESH_POLLS: u32 = 6;
+
+/// Maximum number of stale refreshes per MR. Once exhausted we go back to
+/// plain polling so a persistently broken MR cannot loop forever.
+const MAX_STALE_REFRESHES: u32 = 3;
+
+fn should_refresh_stale_train_entry(stale_polls: u32, refreshes: u32) -> bool {
+    stale_polls >= STALE_REFRESH_POLLS && refreshes < MAX_STALE_REFRESHES
+}
+
+/// Rebase a stale MR onto its target and put it back on the merge train.
+fn refresh_stale_train_entry(provider: &Provider, pr_num: u64) -> Result<()> {
+    provider.rebase_pr(pr_num)?;
+    provider.add_to_merge_train(pr_num)?;
+    Ok(())
+}
+
+/// Capitalized merge queue name, used to prefix wait-loop states
+fn queue_title(provider: &Provider) -> String {
+    let name = provider.merge_queue_name();
+    let mut chars = name.chars();
+    match chars.next() {
+        Some(first) => first.to_uppercase().chain(chars).collect(),
+        None => String::new(),
+    }
+}
+
 fn merge_train_idle_state_message(idle_count: u32, seen_in_train: bool) -> &'static str {
     if seen_in_train {
         "MR was previously visible in the merge train but is not currently reported; still polling..."
@@ -132,6 +212,32 @@ fn should_count_merge_train_status_as_api_error(train_info: &crate::glab::MergeT
         && train_info.position.is_none()
 }
 
+/// Error message for an unapproved PR/MR, listing the unmet approval rules
+/// when the provider exposes them. A failed rules lookup falls back to the
+/// generic message.
+fn not_approved_message(provider: &Provider, pr_num: u64) -> String {
+    let requirements = provider
+        .unmet_approval_requirements(pr_num)
+        .unwrap_or_default();
+    format_not_approved(
+        &format!(
+            "{} {}{}",
+            provider.pr_label(),
+            provider.pr_number_prefix(),
+            pr_num
+        ),
+        &requirements,
+    )
+}
+
+fn format_not_approved(pr_ref: &str, requirements: &[String]) -> String {
+    if requirements.is_empty() {
+        format!("{} is not approved", pr_ref)
+    } else {
+        format!("{} is not approved: {}", pr_ref, requirements.join("; "))
+    }
+}
+
 /// Compute the range of entries that need retargeting after a merge.
 ///
 /// When `land_all` is true, returns all entries after `current_index`.
@@ -154,7 +260,12 @@ fn retarget_range(
 /// Cleanup after successfully merging a PR/MR:
 /// - Remove the PR/MR mapping from config
 /// - Update the base of remaining PRs/MRs (all for land_all, next entry for single land)
+/// - Delete the landed entry's remote branch when `delete_branch_on_land` is set
+///
+/// Returns the branch deletion effect, if a branch was actually removed.
+#[allow(clippy::too_many_arguments)]
 fn cleanup_after_merge(
+    repo: &git2::Repository,
     config: &mut Config,
     stack: &Stack,
     provider: &Provider,
@@ -162,7 +273,7 @@ fn cleanup_after_merge(
     pr_num: u64,
     land_all: bool,
     json: bool,
-) {
+) -> Option<RemoteEffect> {
     // Remove PR/MR mapping from config
     config.remove_mr_for_entry(&stack.name, gg_id);
 
@@ -195,7 +306,7 @@ fn cleanup_after_merge(
                 if !json {
                     println!(
                         "{} Warning: Failed to update {} {}{} base: {}",
-                        style("⚠").yellow(),
+                        style(plain::warn()).yellow(),
                         provider.pr_label(),
                         provider.pr_number_prefix(),
                         remaining_pr,
@@ -205,6 +316,44 @@ fn cleanup_after_merge(
             }
         }
     }
+
+    // Only now that nothing targets it any more can the branch go.
+    if !config.get_delete_branch_on_land() {
+        return None;
+    }
+    let branch = stack
+        .entries
+        .iter()
+        .find(|e| e.mr_number == Some(pr_num))
+        .and_then(|entry| stack.entry_branch_name(entry))?;
+    let remote = config.get_remote_for_stack(&stack.name);
+    match git::delete_remote_branch(repo, remote, &branch) {
+        Ok(Some(prior_oid)) => {
+            if !json {
+                println!(
+                    "{}",
+                    style(format!("  Deleted remote branch {}", branch)).dim()
+                );
+            }
+            Some(RemoteEffect::BranchDeleted {
+                remote: remote.to_string(),
+                branch,
+                prior_oid: Some(prior_oid.to_string()),
+            })
+        }
+        Ok(None) => None,
+        Err(e) => {
+            if !json {
+                println!(
+                    "{} Warning: Failed to delete remote branch {}: {}",
+                    style(plain::warn()).yellow(),
+                    branch,
+                    e
+                );
+            }
+            None
+        }
+    }
 }
 
 /// Rebase remaining PR branches onto the base branch after a merge
@@ -220,17 +369,20 @@ fn rebase_remaining_branches(
     start_index: usize,
     json: bool,
 ) -> Result<()> {
+    let config = Config::load(repo.commondir())?;
+    let remote = config.get_remote_for_stack(&stack.name);
+
     // Fetch the latest base branch
     if !json {
         println!(
             "{}",
-            style(format!("  Fetching origin/{}...", stack.base)).dim()
+            style(format!("  Fetching {}/{}...", remote, stack.base)).dim()
         );
     }
 
     let fetch_result = std::process::Command::new("git")
         .arg("fetch")
-        .arg("origin")
+        .arg(remote)
         .arg(&stack.base)
         .current_dir(
             repo.workdir()
@@ -242,8 +394,8 @@ fn rebase_remaining_branches(
     if !fetch_result.status.success() {
         let stderr = String::from_utf8_lossy(&fetch_result.stderr);
         return Err(GgError::Other(format!(
-            "Failed to fetch origin/{}: {}",
-            stack.base, stderr
+            "Failed to fetch {}/{}: {}",
+            remote, stack.base, stderr
         )));
     }
 
@@ -269,6 +421,7 @@ fn rebase_remaining_branches(
             Some(name) => name,
             None => continue,
         };
+        git::guard_protected_branch(&config, &branch_name)?;
 
         if !json {
             println!(
@@ -300,9 +453,9 @@ fn rebase_remaining_branches(
             )));
         }
 
-        // Rebase onto origin/base
-        let rebase_target = format!("origin/{}", stack.base);
-        let rebase_result = std::process::Command::new("git")
+        // Rebase onto <remote>/<base>
+        let rebase_target = format!("{}/{}", remote, stack.base);
+        let rebase_result = hooks::git_command(GitRewrite::Replay)
             .arg("rebase")
             .arg(&rebase_target)
             .current_dir(repo.workdir().unwrap())
@@ -336,7 +489,7 @@ fn rebase_remaining_branches(
         let push_result = std::process::Command::new("git")
             .arg("push")
             .arg("--force-with-lease")
-            .arg("origin")
+            .arg(remote)
             .arg(&branch_name)
             .current_dir(repo.workdir().unwrap())
             .output()
@@ -347,7 +500,7 @@ fn rebase_remaining_branches(
             if !json {
                 println!(
                     "{} Warning: Failed to push {}: {}",
-                    style("⚠").yellow(),
+                    style(plain::warn()).yellow(),
                     branch_name,
                     stderr
                 );
@@ -368,35 +521,238 @@ fn rebase_remaining_branches(
     Ok(())
 }
 
+const LAND_STATE_FILE: &str = "gg/land_state.json";
+
+/// Progress of a `gg land` session, saved as it goes so an interrupted,
+/// failed or conflicting land can be picked up with `gg land --resume`.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub(crate) struct LandState {
+    /// Stack branch being landed
+    pub branch: String,
+    pub land_all: bool,
+    /// GG-ID (or the original target) to land up to; positions shift as
+    /// entries land, so the GG-ID is preferred.
+    pub until: Option<String>,
+    pub strategy: MergeStrategy,
+    pub wait: bool,
+    pub auto_clean: bool,
+    pub auto_merge: bool,
+    pub admin: bool,
+    pub auto_resolve_restack: bool,
+    #[serde(default)]
+    pub retrigger_ci: bool,
+    /// PRs/MRs merged by this session so far
+    #[serde(default)]
+    pub merged: Vec<u64>,
+    /// PR/MR being landed when the state was last saved
+    #[serde(default)]
+    pub current: Option<u64>,
+    /// The stack is being rebased locally (`--auto-resolve-restack`);
+    /// `gg continue` resyncs and resumes.
+    #[serde(default)]
+    pub restack_pending: bool,
+}
+
+impl LandState {
+    fn into_options(self, json: bool) -> LandOptions {
+        LandOptions {
+            land_all: self.land_all,
+            json,
+            strategy: self.strategy,
+            wait: self.wait,
+            auto_clean: self.auto_clean,
+            auto_merge_flag: self.auto_merge,
+            until: self.until,
+            admin: self.admin,
+            auto_resolve_restack: self.auto_resolve_restack,
+            retrigger_ci: self.retrigger_ci,
+            resume: true,
+            ..Default::default()
+        }
+    }
+
+    /// Save, warning instead of failing: losing the resume point must not
+    /// stop a land that is already merging.
+    fn save_or_warn(&self, git_dir: &Path, json: bool) {
+        if let Err(e) = save_land_state(git_dir, self) {
+            if !json {
+                println!(
+                    "{} Could not save land progress: {}",
+                    style(plain::warn()).yellow(),
+                    e
+                );
+            }
+        }
+    }
+}
+
+pub(crate) fn save_land_state(git_dir: &Path, state: &LandState) -> Result<()> {
+    let file = git_dir.join(LAND_STATE_FILE);
+    if let Some(parent) = file.parent() {
+        fs::create_dir_all(parent)?;
+    }
+    fs::write(file, serde_json::to_string_pretty(state)?)?;
+    Ok(())
+}
+
+pub(crate) fn read_land_state(git_dir: &Path) -> Option<LandState> {
+    let content = fs::read_to_string(git_dir.join(LAND_STATE_FILE)).ok()?;
+    serde_json::from_str(&content).ok()
+}
+
+pub(crate) fn clear_land_state(git_dir: &Path) -> Result<()> {
+    let file = git_dir.join(LAND_STATE_FILE);
+    if file.exists() {
+        fs::remove_file(file)?;
+    }
+    Ok(())
+}
+
+/// Forget that a land is waiting on a local restack (`gg abort`). The
+/// session itself can still be picked up with `gg land --resume`.
+pub(crate) fn cancel_pending_restack(git_dir: &Path) -> Result<()> {
+    match read_land_state(git_dir) {
+        Some(mut state) if state.restack_pending => {
+            state.restack_pending = false;
+            save_land_state(git_dir, &state)
+        }
+        _ => Ok(()),
+    }
+}
+
+/// Fall back from a failed remote restack to the regular `gg rebase` flow
+/// on the stack branch. On a conflict the land state stays on disk for
+/// `gg continue`; the returned message tells the user what to do.
+fn restack_locally(
+    repo: &git2::Repository,
+    stack: &Stack,
+    state: &mut LandState,
+    error: &GgError,
+) -> std::result::Result<(), String> {
+    println!(
+        "{} Could not restack the remaining branches ({}); rebasing the stack locally...",
+        style(plain::warn()).yellow(),
+        error
+    );
+    if git::is_rebase_in_progress(repo) {
+        let _ = git::run_git_command(&["rebase", "--abort"]);
+    }
+    git::run_git_command(&["checkout", &stack.branch_name()]).map_err(|e| e.to_string())?;
+    state.restack_pending = true;
+    save_land_state(repo.path(), state).map_err(|e| e.to_string())?;
+
+    // Landed entries are merged by definition; rewriting them away is the
+    // point of this rebase, as in the post-land cleanup.
+    match crate::commands::rebase::run_with_repo(repo, Some(stack.base.clone()), false, true) {
+        Ok(()) => Ok(()),
+        Err(GgError::RebaseConflict) => Err(
+            "Restack stopped on a conflict. Resolve it, then run `gg continue` to resync and resume landing (or `gg abort`)."
+                .to_string(),
+        ),
+        Err(e) => {
+            state.restack_pending = false;
+            state.save_or_warn(repo.path(), false);
+            Err(e.to_string())
+        }
+    }
+}
+
+/// Resync the stack after a local restack and resume the land session.
+/// No-op unless a land is waiting on a restack.
+pub(crate) fn resume_pending_land(repo: &git2::Repository) -> Result<()> {
+    let Some(mut state) = read_land_state(repo.path()).filter(|s| s.restack_pending) else {
+        return Ok(());
+    };
+    state.restack_pending = false;
+    save_land_state(repo.path(), &state)?;
+
+    println!(
+        "{}",
+        style("Resyncing the restacked stack before resuming land...").dim()
+    );
+    crate::commands::sync::run(
+        false,
+        false,
+        false,
+        true,
+        false,
+        false,
+        false,
+        false,
+        None,
+        None,
+        vec![],
+        false,
+        false,
+    )?;
+    run(state.into_options(false))
+}
+
 /// Options for the land command
 #[derive(Debug, Default)]
 pub struct LandOptions {
     pub land_all: bool,
     pub json: bool,
-    pub squash: bool,
+    pub strategy: MergeStrategy,
     pub wait: bool,
     pub auto_clean: bool,
     pub auto_merge_flag: bool,
     pub until: Option<String>,
     pub admin: bool,
+    /// With `wait`, receive provider webhooks on this address instead of
+    /// polling every few seconds.
+    pub listen: Option<String>,
+    /// When restacking the remaining branches conflicts, rebase the stack
+    /// locally and resume after `gg continue` instead of stopping.
+    pub auto_resolve_restack: bool,
+    /// Pick up the saved land session (`.git/gg/land_state.json`) with its
+    /// flags instead of the ones passed in.
+    pub resume: bool,
+    /// With `wait`, re-run failed CI (up to [`MAX_CI_RETRIGGERS`] times)
+    /// without asking.
+    pub retrigger_ci: bool,
 }
 
 /// Run the land command
 pub fn run(opts: LandOptions) -> Result<()> {
+    let mut resumed_merges = Vec::new();
+    let opts = if opts.resume {
+        let repo = git::open_repo()?;
+        let state = read_land_state(repo.path()).ok_or_else(|| {
+            GgError::Other("No land session to resume. Run `gg land` to start one.".to_string())
+        })?;
+        if git::current_branch_name(&repo).as_deref() != Some(state.branch.as_str()) {
+            return Err(GgError::Other(format!(
+                "The saved land session is for {}. Check it out and run `gg land --resume` again.",
+                state.branch
+            )));
+        }
+        resumed_merges = state.merged.clone();
+        LandOptions {
+            listen: opts.listen,
+            ..state.into_options(opts.json)
+        }
+    } else {
+        opts
+    };
     let LandOptions {
         land_all,
         json,
-        squash,
+        strategy,
         wait,
         auto_clean,
         auto_merge_flag,
         until,
         admin,
+        listen,
+        auto_resolve_restack,
+        resume: _,
+        retrigger_ci,
     } = opts;
     let repo = git::open_repo()?;
 
     let git_dir = repo.commondir();
-    let mut config = Config::load_with_global(git_dir)?;
+    let mut config = Config::load(git_dir)?;
 
     // Acquire operation lock + record a Pending op for the undo log.
     let (_lock, mut guard) = git::acquire_operation_lock_and_record(
@@ -414,26 +770,55 @@ pub fn run(opts: LandOptions) -> Result<()> {
     let mut remote_effects: Vec<RemoteEffect> = Vec::new();
     let mut touched_remote = false;
 
-    let provider = Provider::detect(&repo)?;
+    let mut stack = Stack::load(&repo, &config)?;
+    guard.set_stack_name(&stack.name);
+
+    let provider = Provider::for_stack(&repo, &config, &stack.name)?;
     provider.check_installed()?;
     provider.check_auth()?;
 
-    let auto_merge_on_land =
-        provider == Provider::GitLab && (auto_merge_flag || config.get_gitlab_auto_merge_on_land());
+    let auto_merge_on_land = auto_merge_flag
+        || match provider {
+            Provider::GitHub => config.get_github_auto_merge_on_land(),
+            Provider::GitLab => config.get_gitlab_auto_merge_on_land(),
+            Provider::Gitea => false,
+        };
 
-    let merge_trains_enabled = provider.check_merge_trains_enabled().unwrap_or(false);
+    let merge_trains_enabled = provider
+        .check_merge_trains_enabled(&stack.base)
+        .unwrap_or(false);
     if merge_trains_enabled && !json {
         println!(
             "{}",
             style(format!(
-                "Merge trains enabled - {}s will be added to the merge train",
-                provider.pr_label()
+                "{} enabled - {}s will be added to the {}",
+                queue_title(&provider),
+                provider.pr_label(),
+                provider.merge_queue_name()
             ))
             .dim()
         );
     }
 
-    let mut stack = Stack::load(&repo, &config)?;
+    // A dependent stack lands after its parent: until the parent is landed
+    // and cleaned up, its first PR/MR targets the parent's branches.
+    if let Some(parent) = stack.parent_stack(&repo, &config) {
+        return Err(GgError::Other(if parent.is_empty() {
+            format!(
+                "Stack '{}' is stacked on '{}', which has fully landed. Run `gg clean` to remove '{}' first.",
+                stack.name, parent.name, parent.name
+            )
+        } else {
+            format!(
+                "Stack '{}' is stacked on '{}', which still has {} unlanded commit(s). Land '{}' first.",
+                stack.name,
+                parent.name,
+                parent.len(),
+                parent.name
+            )
+        }));
+    }
+
     if stack.is_empty() {
         if json {
             print_json(&LandResponse {
@@ -444,6 +829,8 @@ pub fn run(opts: LandOptions) -> Result<()> {
                     landed: vec![],
                     remaining: 0,
                     cleaned: false,
+                    deleted_branches: vec![],
+                    changelog: None,
                     warnings: vec![],
                     error: None,
                 },
@@ -475,6 +862,53 @@ pub fn run(opts: LandOptions) -> Result<()> {
         None
     };
     let land_multiple = land_all || land_until.is_some();
+    let mut land_state = LandState {
+        branch: stack.branch_name(),
+        land_all,
+        until: land_until
+            .and_then(|pos| stack.entries.get(pos - 1))
+            .and_then(|entry| entry.gg_id.clone())
+            .or_else(|| until.clone()),
+        strategy,
+        wait,
+        auto_clean,
+        auto_merge: auto_merge_flag,
+        admin,
+        auto_resolve_restack,
+        retrigger_ci,
+        merged: resumed_merges,
+        current: None,
+        restack_pending: false,
+    };
+    land_state.save_or_warn(repo.path(), json);
+    let mut resume_after_restack = false;
+
+    // Fail before merging anything if the base branch's signed-commit or
+    // linear-history protection would reject part of what we land.
+    let pending: Vec<&StackEntry> = stack
+        .entries
+        .iter()
+        .take(land_until.unwrap_or(stack.entries.len()))
+        .filter(|e| e.mr_state != Some(PrState::Merged))
+        .take(if land_multiple { usize::MAX } else { 1 })
+        .collect();
+    protection::preflight(
+        &repo,
+        &provider,
+        &stack.base,
+        &pending,
+        Stage::Land { strategy },
+    )?;
+    hooks::run(
+        &repo,
+        &config,
+        Hook::PreLand,
+        &HookContext {
+            stack: stack.name.clone(),
+            base: stack.base.clone(),
+            entries: pending.iter().map(|&e| e.into()).collect(),
+        },
+    )?;
 
     let interrupted = if wait {
         let flag = Arc::new(AtomicBool::new(false));
@@ -500,6 +934,25 @@ pub fn run(opts: LandOptions) -> Result<()> {
         None
     };
 
+    let listener = match listen.as_deref() {
+        Some(addr) if wait => {
+            let listener = WebhookListener::start(addr)?;
+            if !json {
+                println!(
+                    "{}",
+                    style(format!(
+                        "Listening for webhooks on http://{} (fallback poll every {}s)",
+                        listener.addr(),
+                        LISTEN_FALLBACK_POLL_SECS
+                    ))
+                    .dim()
+                );
+            }
+            Some(listener)
+        }
+        _ => None,
+    };
+
     let has_unsynced_commits_before_merge = stack.entries.iter().any(|e| !e.is_synced());
     let mut landed_count = 0usize;
     let mut landed_entries: Vec<LandedEntryJson> = vec![];
@@ -507,6 +960,7 @@ pub fn run(opts: LandOptions) -> Result<()> {
     let mut seen_closed: HashSet<String> = HashSet::new();
     let mut warnings: Vec<String> = vec![];
     let mut land_error: Option<String> = None;
+    let mut deleted_branches: Vec<String> = vec![];
 
     'landing_loop: loop {
         let entries_to_land = if let Some(end_pos) = land_until {
@@ -515,20 +969,37 @@ pub fn run(opts: LandOptions) -> Result<()> {
             &stack.entries[..]
         };
 
+        // One batched request for the whole stack; PRs it could not
+        // report on are asked about one at a time.
+        let unmerged: Vec<u64> = entries_to_land
+            .iter()
+            .filter_map(|entry| entry.mr_number)
+            .filter(|num| !land_state.merged.contains(num))
+            .collect();
+        let mut known = provider.get_prs_info(&unmerged).unwrap_or_default();
+
         let mut next_entry_idx = None;
         for (idx, entry) in entries_to_land.iter().enumerate() {
             if let Some(num) = entry.mr_number {
-                if let Ok(info) = provider.get_pr_info(num) {
-                    if info.state == PrState::Open || info.state == PrState::Draft {
+                // PRs merged earlier in this session are not asked about again.
+                let state = if land_state.merged.contains(&num) {
+                    Ok(PrState::Merged)
+                } else if let Some(info) = known.remove(&num) {
+                    Ok(info.state)
+                } else {
+                    provider.get_pr_info(num).map(|info| info.state)
+                };
+                if let Ok(state) = state {
+                    if state == PrState::Open || state == PrState::Draft {
                         next_entry_idx = Some(idx);
                         break;
-                    } else if info.state == PrState::Merged {
+                    } else if state == PrState::Merged {
                         if let Some(gg_id) = &entry.gg_id {
                             if seen_already_merged.insert(gg_id.clone()) {
                                 if !json {
                                     println!(
                                         "{} {} {}{} ({}) — already merged",
-                                        style("→").cyan(),
+                                        style(plain::arrow()).cyan(),
                                         provider.pr_label(),
                                         provider.pr_number_prefix(),
                                         num,
@@ -548,13 +1019,13 @@ pub fn run(opts: LandOptions) -> Result<()> {
                             }
                         }
                         continue;
-                    } else if info.state == PrState::Closed {
+                    } else if state == PrState::Closed {
                         if let Some(gg_id) = &entry.gg_id {
                             if seen_closed.insert(gg_id.clone()) {
                                 if !json {
                                     println!(
                                         "{} {} {}{} ({}) — closed, skipping",
-                                        style("⚠").yellow(),
+                                        style(plain::warn()).yellow(),
                                         provider.pr_label(),
                                         provider.pr_number_prefix(),
                                         num,
@@ -584,6 +1055,8 @@ pub fn run(opts: LandOptions) -> Result<()> {
         };
 
         let entry = &entries_to_land[entry_idx];
+        land_state.current = entry.mr_number;
+        land_state.save_or_warn(repo.path(), json);
         if let Some(ref flag) = interrupted {
             if flag.load(Ordering::SeqCst) {
                 land_error = Some("Interrupted by user".to_string());
@@ -621,7 +1094,7 @@ pub fn run(opts: LandOptions) -> Result<()> {
                     if !json {
                         println!(
                             "{} {} {}{} ({}) — already merged",
-                            style("→").cyan(),
+                            style(plain::arrow()).cyan(),
                             provider.pr_label(),
                             provider.pr_number_prefix(),
                             pr_num,
@@ -646,7 +1119,7 @@ pub fn run(opts: LandOptions) -> Result<()> {
                     if !json {
                         println!(
                             "{} {} {}{} ({}) — closed, skipping",
-                            style("⚠").yellow(),
+                            style(plain::warn()).yellow(),
                             provider.pr_label(),
                             provider.pr_number_prefix(),
                             pr_num,
@@ -676,10 +1149,11 @@ pub fn run(opts: LandOptions) -> Result<()> {
                     error: None,
                 });
                 land_error = Some(format!(
-                    "{} {}{} is a draft",
+                    "{} {}{} is a draft. Run `gg ready {}` to mark it ready for review, then land again.",
                     provider.pr_label(),
                     provider.pr_number_prefix(),
-                    pr_num
+                    pr_num,
+                    entry.position
                 ));
                 break 'landing_loop;
             }
@@ -692,7 +1166,9 @@ pub fn run(opts: LandOptions) -> Result<()> {
                         land_all || (admin && provider == Provider::GitHub),
                         timeout_minutes,
                         interrupted.as_ref(),
+                        listener.as_ref(),
                         &stack.base,
+                        retrigger_ci,
                         json,
                     ) {
                         landed_entries.push(LandedEntryJson {
@@ -710,12 +1186,7 @@ pub fn run(opts: LandOptions) -> Result<()> {
                 } else if !land_all && (!admin || provider != Provider::GitHub) {
                     let approved = provider.check_pr_approved(pr_num)?;
                     if !approved {
-                        land_error = Some(format!(
-                            "{} {}{} is not approved",
-                            provider.pr_label(),
-                            provider.pr_number_prefix(),
-                            pr_num
-                        ));
+                        land_error = Some(not_approved_message(&provider, pr_num));
                         break 'landing_loop;
                     }
                 }
@@ -770,6 +1241,7 @@ pub fn run(opts: LandOptions) -> Result<()> {
                             pr_num,
                             timeout_minutes,
                             interrupted.as_ref(),
+                            listener.as_ref(),
                             &stack.base,
                             json,
                         ) {
@@ -777,7 +1249,10 @@ pub fn run(opts: LandOptions) -> Result<()> {
                             break 'landing_loop;
                         }
                         landed_count += 1;
-                        cleanup_after_merge(
+                        land_state.merged.push(pr_num);
+                        land_state.save_or_warn(repo.path(), json);
+                        if let Some(effect) = cleanup_after_merge(
+                            &repo,
                             &mut config,
                             &stack,
                             &provider,
@@ -785,7 +1260,13 @@ pub fn run(opts: LandOptions) -> Result<()> {
                             pr_num,
                             land_multiple,
                             json,
-                        );
+                        ) {
+                            if let RemoteEffect::BranchDeleted { branch, .. } = &effect {
+                                deleted_branches.push(branch.clone());
+                            }
+                            guard.record_remote_effect(effect.clone());
+                            remote_effects.push(effect);
+                        }
                         if land_multiple {
                             let current_index = stack
                                 .entries
@@ -799,6 +1280,13 @@ pub fn run(opts: LandOptions) -> Result<()> {
                                 current_index,
                                 json,
                             ) {
+                                if auto_resolve_restack {
+                                    match restack_locally(&repo, &stack, &mut land_state, &e) {
+                                        Ok(()) => resume_after_restack = true,
+                                        Err(message) => land_error = Some(message),
+                                    }
+                                    break 'landing_loop;
+                                }
                                 warnings
                                     .push(format!("Failed to rebase remaining branches: {}", e));
                                 land_error = Some(e.to_string());
@@ -828,7 +1316,7 @@ pub fn run(opts: LandOptions) -> Result<()> {
                 }
             }
         } else if auto_merge_on_land {
-            match provider.auto_merge_pr_when_pipeline_succeeds(pr_num, squash, false) {
+            match provider.auto_merge_pr_when_pipeline_succeeds(pr_num, strategy, false) {
                 Ok(AutoMergeResult::Queued) => {
                     // Queuing for auto-merge mutates remote state even though
                     // the MR is not merged yet; mark the op as having touched
@@ -845,16 +1333,39 @@ pub fn run(opts: LandOptions) -> Result<()> {
                         action: "queued".to_string(),
                         error: None,
                     });
+                    if !json {
+                        println!(
+                            "{} Auto-merge enabled for {} {}{}; it merges once checks and approvals pass",
+                            style("OK").green().bold(),
+                            provider.pr_label(),
+                            provider.pr_number_prefix(),
+                            pr_num
+                        );
+                    }
+                }
+                Ok(AutoMergeResult::AlreadyQueued) => {
+                    landed_entries.push(LandedEntryJson {
+                        position: entry.position,
+                        sha: entry.short_sha.clone(),
+                        title: entry.title.clone(),
+                        gg_id: gg_id.clone(),
+                        pr_number: pr_num,
+                        action: "already_queued".to_string(),
+                        error: None,
+                    });
+                    if !json {
+                        println!(
+                            "{}",
+                            style(format!(
+                                "{} {}{} is already set to auto-merge",
+                                provider.pr_label(),
+                                provider.pr_number_prefix(),
+                                pr_num
+                            ))
+                            .dim()
+                        );
+                    }
                 }
-                Ok(AutoMergeResult::AlreadyQueued) => landed_entries.push(LandedEntryJson {
-                    position: entry.position,
-                    sha: entry.short_sha.clone(),
-                    title: entry.title.clone(),
-                    gg_id: gg_id.clone(),
-                    pr_number: pr_num,
-                    action: "already_queued".to_string(),
-                    error: None,
-                }),
                 Err(e) => {
                     landed_entries.push(LandedEntryJson {
                         position: entry.position,
@@ -871,9 +1382,12 @@ pub fn run(opts: LandOptions) -> Result<()> {
             break 'landing_loop;
         } else {
             if admin {
-                eprintln!("⚠ Merging with admin override — bypassing approval requirements");
+                eprintln!(
+                    "{} Merging with admin override — bypassing approval requirements",
+                    plain::warn()
+                );
             }
-            match provider.merge_pr(pr_num, squash, false, admin) {
+            match provider.merge_pr(pr_num, strategy, false, admin) {
                 Ok(()) => {
                     // Record the merge as a remote effect. Fetch the URL if we
                     // can; fall back to empty string if the info call fails.
@@ -899,7 +1413,10 @@ pub fn run(opts: LandOptions) -> Result<()> {
                         error: None,
                     });
                     landed_count += 1;
-                    cleanup_after_merge(
+                    land_state.merged.push(pr_num);
+                    land_state.save_or_warn(repo.path(), json);
+                    if let Some(effect) = cleanup_after_merge(
+                        &repo,
                         &mut config,
                         &stack,
                         &provider,
@@ -907,7 +1424,13 @@ pub fn run(opts: LandOptions) -> Result<()> {
                         pr_num,
                         land_multiple,
                         json,
-                    );
+                    ) {
+                        if let RemoteEffect::BranchDeleted { branch, .. } = &effect {
+                            deleted_branches.push(branch.clone());
+                        }
+                        guard.record_remote_effect(effect.clone());
+                        remote_effects.push(effect);
+                    }
                     if land_multiple {
                         let current_index = stack
                             .entries
@@ -917,6 +1440,13 @@ pub fn run(opts: LandOptions) -> Result<()> {
                         if let Err(e) =
                             rebase_remaining_branches(&repo, &stack, &provider, current_index, json)
                         {
+                            if auto_resolve_restack {
+                                match restack_locally(&repo, &stack, &mut land_state, &e) {
+                                    Ok(()) => resume_after_restack = true,
+                                    Err(message) => land_error = Some(message),
+                                }
+                                break 'landing_loop;
+                            }
                             warnings.push(format!("Failed to rebase remaining branches: {}", e));
                             land_error = Some(e.to_string());
                             break 'landing_loop;
@@ -928,6 +1458,14 @@ pub fn run(opts: LandOptions) -> Result<()> {
                     }
                 }
                 Err(e) => {
+                    // A merge refused by branch protection is usually a
+                    // failing required check; say which one.
+                    let mut error = e.to_string();
+                    if let Ok(failed_checks) = provider.get_failed_checks(pr_num) {
+                        if !failed_checks.required.is_empty() {
+                            error.push_str(&crate::gh::format_failed_checks(&failed_checks));
+                        }
+                    }
                     landed_entries.push(LandedEntryJson {
                         position: entry.position,
                         sha: entry.short_sha.clone(),
@@ -935,9 +1473,9 @@ pub fn run(opts: LandOptions) -> Result<()> {
                         gg_id: gg_id.clone(),
                         pr_number: pr_num,
                         action: "error".to_string(),
-                        error: Some(e.to_string()),
+                        error: Some(error.clone()),
                     });
-                    land_error = Some(e.to_string());
+                    land_error = Some(error);
                     break 'landing_loop;
                 }
             }
@@ -993,6 +1531,86 @@ pub fn run(opts: LandOptions) -> Result<()> {
         }
     }
 
+    let merged_prs: Vec<MergedPr> = landed_entries
+        .iter()
+        .filter(|e| e.action == "merged")
+        .map(|e| MergedPr {
+            number: e.pr_number,
+            title: e.title.clone(),
+            message: repo
+                .revparse_single(&e.sha)
+                .and_then(|object| object.peel_to_commit())
+                .map(|commit| commit.message().unwrap_or("").to_string())
+                .unwrap_or_default(),
+            url: remote_effects
+                .iter()
+                .find_map(|effect| match effect {
+                    RemoteEffect::PrMerged { number, url } if *number == e.pr_number => {
+                        Some(url.clone())
+                    }
+                    _ => None,
+                })
+                .unwrap_or_default(),
+        })
+        .collect();
+    let (changelog, pushed) = land_changelog(
+        &repo,
+        &config,
+        &stack,
+        &provider,
+        &merged_prs,
+        json,
+        &mut warnings,
+    );
+    if let Some(effect) = pushed {
+        guard.record_remote_effect(effect.clone());
+        remote_effects.push(effect);
+        touched_remote = true;
+    }
+
+    let merged: Vec<HookEntry> = landed_entries
+        .iter()
+        .filter(|e| e.action == "merged")
+        .map(|e| HookEntry {
+            position: e.position,
+            sha: repo
+                .revparse_single(&e.sha)
+                .map(|object| object.id().to_string())
+                .unwrap_or_else(|_| e.sha.clone()),
+            gg_id: Some(e.gg_id.clone()),
+            pr_number: Some(e.pr_number),
+        })
+        .collect();
+    if !merged.is_empty() {
+        let context = HookContext {
+            stack: stack.name.clone(),
+            base: stack.base.clone(),
+            entries: merged,
+        };
+        if let Err(e) = hooks::run(&repo, &config, Hook::PostLand, &context) {
+            if !json {
+                println!("{} {}", style(plain::warn()).yellow(), e);
+            }
+            warnings.push(e.to_string());
+        }
+    }
+    warnings.extend(resolve_linked_issues(
+        &repo,
+        &config,
+        &stack.name,
+        &landed_entries,
+        json,
+    ));
+
+    // Nobody may be watching a `--wait` land; tell them how it ended. A
+    // restack resume notifies from the land it starts.
+    if wait && !resume_after_restack {
+        notify::send(
+            &config,
+            &land_notification(&stack.name, &provider, landed_count, land_error.as_deref()),
+        );
+    }
+
     if json {
         let target_len = if let Some(end_pos) = land_until {
             end_pos.min(stack.entries.len())
@@ -1013,6 +1631,8 @@ pub fn run(opts: LandOptions) -> Result<()> {
                 landed: landed_entries,
                 remaining,
                 cleaned,
+                deleted_branches,
+                changelog,
                 warnings,
                 error: land_error.clone(),
             },
@@ -1023,15 +1643,21 @@ pub fn run(opts: LandOptions) -> Result<()> {
             println!();
             println!(
                 "{} Landed {} {}(s), but encountered an error:",
-                style("⚠").yellow().bold(),
+                style(plain::warn()).yellow().bold(),
                 landed_count,
                 provider.pr_label()
             );
         }
         eprintln!();
         eprintln!("{} {}", style("Error:").red().bold(), error);
+        if !land_state.restack_pending {
+            eprintln!(
+                "{}",
+                style("Run `gg land --resume` to pick up where this land stopped.").dim()
+            );
+        }
         for warning in &warnings {
-            println!("{} {}", style("⚠").yellow(), warning);
+            println!("{} {}", style(plain::warn()).yellow(), warning);
         }
     } else if landed_count > 0 {
         println!();
@@ -1057,6 +1683,16 @@ pub fn run(opts: LandOptions) -> Result<()> {
         touched_remote,
     )?;
 
+    // A conflict-free local restack resumes right away; the resync and the
+    // next land take the operation lock themselves.
+    if resume_after_restack {
+        drop(_lock);
+        return resume_pending_land(&repo);
+    }
+    if land_error.is_none() {
+        clear_land_state(repo.path())?;
+    }
+
     // In JSON mode, the error is already included in the LandResponse payload.
     // Returning Err would cause gg-cli to emit a second JSON error object,
     // breaking machine consumers that expect a single JSON document.
@@ -1069,24 +1705,255 @@ pub fn run(opts: LandOptions) -> Result<()> {
     }
 }
 
+/// Render the changelog entries of the PRs/MRs this land merged
+/// (`defaults.changelog`, see [`crate::changelog`]) and, unless the mode is
+/// `output`, commit them to the base branch. Failures are added to
+/// `warnings`; the push is returned as a remote effect.
+fn land_changelog(
+    repo: &git2::Repository,
+    config: &Config,
+    stack: &Stack,
+    provider: &Provider,
+    merged: &[MergedPr],
+    json: bool,
+    warnings: &mut Vec<String>,
+) -> (Option<LandChangelogJson>, Option<RemoteEffect>) {
+    let mode = config.defaults.changelog.mode;
+    if mode == ChangelogMode::Off || merged.is_empty() {
+        return (None, None);
+    }
+    let mut warn = |warning: String| {
+        if !json {
+            println!("{} {}", style(plain::warn()).yellow(), warning);
+        }
+        warnings.push(warning);
+    };
+
+    let entries = match changelog::render(config, &stack.name, provider.pr_number_prefix(), merged)
+    {
+        Ok(entries) => entries,
+        Err(e) => {
+            warn(format!("Could not write the changelog: {}", e));
+            return (None, None);
+        }
+    };
+
+    let mut commit = None;
+    let mut effect = None;
+    if mode == ChangelogMode::Output {
+        if !json {
+            println!("{}", style("Changelog:").bold());
+            for entry in &entries {
+                println!("  - {}", entry.text);
+            }
+        }
+    } else {
+        let remote = config.get_remote_for_stack(&stack.name);
+        match changelog::commit_to_base(
+            repo,
+            &config.defaults.changelog,
+            remote,
+            &stack.base,
+            provider.pr_number_prefix(),
+            &entries,
+        ) {
+            Ok(oid) => {
+                if !json {
+                    println!(
+                        "{} Added {} changelog entr{} to {}/{} ({})",
+                        style("OK").green().bold(),
+                        entries.len(),
+                        if entries.len() == 1 { "y" } else { "ies" },
+                        remote,
+                        stack.base,
+                        &oid.to_string()[..7]
+                    );
+                }
+                commit = Some(oid.to_string());
+                effect = Some(RemoteEffect::Pushed {
+                    remote: remote.to_string(),
+                    branch: stack.base.clone(),
+                    force: false,
+                });
+            }
+            Err(e) => warn(format!(
+                "Could not commit the changelog to {}/{}: {}",
+                remote, stack.base, e
+            )),
+        }
+    }
+
+    (
+        Some(LandChangelogJson {
+            mode,
+            entries,
+            commit,
+        }),
+        effect,
+    )
+}
+
+/// Close or transition the issues linked from the entries this land merged
+/// (`defaults.issue_tracker`, see [`crate::issues`]). Failures are returned
+/// as warnings: the PRs/MRs are landed either way.
+fn resolve_linked_issues(
+    repo: &git2::Repository,
+    config: &Config,
+    stack_name: &str,
+    landed: &[LandedEntryJson],
+    json: bool,
+) -> Vec<String> {
+    let (pattern, tracker) = match (
+        issues::issue_regex(config),
+        issues::tracker(&config.defaults.issue_tracker),
+    ) {
+        (Ok(Some(pattern)), Ok(Some(tracker))) => (pattern, tracker),
+        (Err(e), _) | (_, Err(e)) => {
+            if !json {
+                println!("{} {}", style(plain::warn()).yellow(), e);
+            }
+            return vec![e.to_string()];
+        }
+        _ => return Vec::new(),
+    };
+
+    let mut warnings = Vec::new();
+    let mut seen = HashSet::new();
+    for entry in landed.iter().filter(|e| e.action == "merged") {
+        let message = repo
+            .revparse_single(&entry.sha)
+            .and_then(|object| object.peel_to_commit())
+            .map(|commit| commit.message().unwrap_or("").to_string())
+            .unwrap_or_default();
+        for key in issues::entry_keys(&pattern, stack_name, &message) {
+            if !seen.insert(key.clone()) {
+                continue;
+            }
+            let issue = LandedIssue {
+                key,
+                pr_number: entry.pr_number,
+                stack: stack_name.to_string(),
+            };
+            match tracker.resolve(&issue) {
+                Ok(true) if !json => println!(
+                    "{} Resolved {} in {}",
+                    style("OK").green().bold(),
+                    issue.key,
+                    tracker.name()
+                ),
+                Ok(_) => {}
+                Err(e) => {
+                    let warning = format!(
+                        "Could not resolve {} in {}: {}",
+                        issue.key,
+                        tracker.name(),
+                        e
+                    );
+                    if !json {
+                        println!("{} {}", style(plain::warn()).yellow(), warning);
+                    }
+                    warnings.push(warning);
+                }
+            }
+        }
+    }
+    warnings
+}
+
+/// The `defaults.notify_command` report for a finished `gg land --wait`.
+fn land_notification(
+    stack_name: &str,
+    provider: &Provider,
+    landed_count: usize,
+    error: Option<&str>,
+) -> Notification {
+    let message = match error {
+        Some(error) if landed_count > 0 => format!(
+            "Landed {} {}(s) of {}, then failed: {}",
+            landed_count,
+            provider.pr_label(),
+            stack_name,
+            error
+        ),
+        Some(error) => format!("Landing {} failed: {}", stack_name, error),
+        None => format!(
+            "Landed {} {}(s) of {}",
+            landed_count,
+            provider.pr_label(),
+            stack_name
+        ),
+    };
+    Notification {
+        event: "land",
+        success: error.is_none(),
+        title: "gg land".to_string(),
+        message,
+    }
+}
+
+/// Maximum CI re-runs per PR/MR while waiting to land.
+const MAX_CI_RETRIGGERS: u32 = 2;
+
+/// How long a failure reported right after a re-run is treated as the old
+/// run's.
+const CI_RETRIGGER_GRACE_SECS: u64 = 30;
+
+/// Ask whether to re-run failed CI. Only interactive, non-JSON sessions are
+/// asked; everyone else needs `--retrigger-ci`.
+fn confirm_ci_retrigger(
+    provider: &Provider,
+    pr_num: u64,
+    json: bool,
+    spinner: &Option<ProgressBar>,
+) -> bool {
+    if json || !atty::is(atty::Stream::Stdout) || *provider == Provider::Gitea {
+        return false;
+    }
+    if let Some(spinner) = spinner {
+        spinner.suspend(|| prompt_ci_retrigger(provider, pr_num))
+    } else {
+        prompt_ci_retrigger(provider, pr_num)
+    }
+}
+
+fn prompt_ci_retrigger(provider: &Provider, pr_num: u64) -> bool {
+    Confirm::new()
+        .with_prompt(format!(
+            "CI failed on {} {}{}. Re-run the failed jobs?",
+            provider.pr_label(),
+            provider.pr_number_prefix(),
+            pr_num
+        ))
+        .default(false)
+        .interact()
+        .unwrap_or(false)
+}
+
 /// Wait for a PR/MR to be ready to merge (CI passes, approvals met)
 /// Also monitors merge train status if merge trains are enabled
+#[allow(clippy::too_many_arguments)]
 fn wait_for_pr_ready(
     provider: &Provider,
     pr_num: u64,
     skip_approval: bool,
     timeout_minutes: u64,
     interrupted: Option<&Arc<AtomicBool>>,
+    listener: Option<&WebhookListener>,
     target_branch: &str,
+    retrigger_ci: bool,
     json: bool,
 ) -> Result<()> {
     let start_time = Instant::now();
     let timeout = Duration::from_secs(timeout_minutes * 60);
     let poll_interval = Duration::from_secs(POLL_INTERVAL_SECS);
     let mut consecutive_errors: u32 = 0;
+    let mut ci_retriggers: u32 = 0;
+    let mut ci_retriggered_at: Option<Instant> = None;
 
-    // Check if merge trains are enabled
-    let merge_trains_enabled = provider.check_merge_trains_enabled().unwrap_or(false);
+    // Check if merge trains/queues are enabled
+    let merge_trains_enabled = provider
+        .check_merge_trains_enabled(target_branch)
+        .unwrap_or(false);
 
     if !json {
         println!(
@@ -1143,10 +2010,11 @@ fn wait_for_pr_ready(
                             finish_spinner(
                                 spinner,
                                 &format!(
-                                    "{} {}{} merged via merge train",
+                                    "{} {}{} merged via {}",
                                     provider.pr_label(),
                                     provider.pr_number_prefix(),
-                                    pr_num
+                                    pr_num,
+                                    provider.merge_queue_name()
                                 ),
                                 state_start_time,
                             );
@@ -1154,19 +2022,23 @@ fn wait_for_pr_ready(
                         return Ok(());
                     }
                     MergeTrainStatus::Merging => {
-                        new_state = "Merge train: merging now...".to_string();
+                        new_state = format!("{}: merging now...", queue_title(provider));
                     }
                     MergeTrainStatus::Fresh => {
                         if let Some(pos) = train_info.position {
-                            new_state = format!("Merge train: position {} (fresh, ready)", pos);
+                            new_state = format!(
+                                "{}: position {} (fresh, ready)",
+                                queue_title(provider),
+                                pos
+                            );
                         }
                     }
                     MergeTrainStatus::Stale => {
-                        new_state = "Merge train: stale (needs rebase)".to_string();
+                        new_state = format!("{}: stale (needs rebase)", queue_title(provider));
                     }
                     _ => {
                         if let Some(pos) = train_info.position {
-                            new_state = format!("Merge train: position {}", pos);
+                            new_state = format!("{}: position {}", queue_title(provider), pos);
                         }
                     }
                 }
@@ -1208,7 +2080,13 @@ fn wait_for_pr_ready(
                     current_state = Some(error_state);
                     state_start_time = Instant::now();
                 }
-                interruptible_sleep(poll_interval, interrupted, current_spinner.as_ref())?;
+                wait_next_poll(
+                    listener,
+                    pr_num,
+                    poll_interval,
+                    interrupted,
+                    current_spinner.as_ref(),
+                )?;
                 continue;
             }
         };
@@ -1226,6 +2104,35 @@ fn wait_for_pr_ready(
                     new_state = "CI running...".to_string();
                 }
             }
+            // Right after a re-run the provider may still report the old
+            // failure; give the new run a moment to register.
+            CiStatus::Failed
+                if ci_retriggered_at.is_some_and(|at| {
+                    at.elapsed() < Duration::from_secs(CI_RETRIGGER_GRACE_SECS)
+                }) =>
+            {
+                new_state = "Waiting for CI re-run to start...".to_string();
+            }
+            CiStatus::Failed
+                if ci_retriggers < MAX_CI_RETRIGGERS
+                    && (retrigger_ci
+                        || confirm_ci_retrigger(provider, pr_num, json, &current_spinner)) =>
+            {
+                ci_retriggers += 1;
+                match provider.rerun_ci(pr_num, true) {
+                    Ok(_) => {
+                        ci_retriggered_at = Some(Instant::now());
+                        new_state = format!(
+                            "CI failed; re-running failed jobs (retry {}/{})...",
+                            ci_retriggers, MAX_CI_RETRIGGERS
+                        );
+                    }
+                    Err(e) => {
+                        ci_retriggers = MAX_CI_RETRIGGERS;
+                        new_state = format!("Could not re-run CI: {}", e);
+                    }
+                }
+            }
             CiStatus::Failed => {
                 if let Some(ref spinner) = current_spinner {
                     spinner.finish_and_clear();
@@ -1244,6 +2151,9 @@ fn wait_for_pr_ready(
                         ));
                     }
                 }
+                if let Ok(failed_checks) = provider.get_failed_checks(pr_num) {
+                    msg.push_str(&crate::gh::format_failed_checks(&failed_checks));
+                }
                 return Err(GgError::Other(msg));
             }
             CiStatus::Canceled => {
@@ -1299,7 +2209,13 @@ fn wait_for_pr_ready(
                         current_state = Some(error_state);
                         state_start_time = Instant::now();
                     }
-                    interruptible_sleep(poll_interval, interrupted, current_spinner.as_ref())?;
+                    wait_next_poll(
+                        listener,
+                        pr_num,
+                        poll_interval,
+                        interrupted,
+                        current_spinner.as_ref(),
+                    )?;
                     continue;
                 }
             };
@@ -1348,17 +2264,24 @@ fn wait_for_pr_ready(
         }
 
         // Wait before next poll
-        interruptible_sleep(poll_interval, interrupted, current_spinner.as_ref())?;
+        wait_next_poll(
+            listener,
+            pr_num,
+            poll_interval,
+            interrupted,
+            current_spinner.as_ref(),
+        )?;
     }
 }
 
-/// Wait for an MR to complete merging through the merge train
+/// Wait for a PR/MR to complete merging through the merge train or queue
 /// Polls the merge train status until the MR is fully merged
 fn wait_for_merge_train_completion(
     provider: &Provider,
     pr_num: u64,
     timeout_minutes: u64,
     interrupted: Option<&Arc<AtomicBool>>,
+    listener: Option<&WebhookListener>,
     target_branch: &str,
     json: bool,
 ) -> Result<()> {
@@ -1373,14 +2296,20 @@ fn wait_for_merge_train_completion(
     let mut idle_count: u32 = 0;
     let mut seen_in_train = false;
 
+    // A Stale train entry usually needs a rebase GitLab will not do on its
+    // own; after STALE_REFRESH_POLLS we trigger one (bounded retries).
+    let mut stale_polls: u32 = 0;
+    let mut stale_refreshes: u32 = 0;
+
     if !json {
         println!(
             "{}",
             style(format!(
-                "Waiting for {} {}{} to merge through merge train (timeout: {}m)...",
+                "Waiting for {} {}{} to merge through {} (timeout: {}m)...",
                 provider.pr_label(),
                 provider.pr_number_prefix(),
                 pr_num,
+                provider.merge_queue_name(),
                 timeout_minutes
             ))
             .dim()
@@ -1398,10 +2327,11 @@ fn wait_for_merge_train_completion(
                 spinner.finish_and_clear();
             }
             return Err(GgError::Other(format!(
-                "Timeout waiting for {} {}{} to merge through train",
+                "Timeout waiting for {} {}{} to merge through {}",
                 provider.pr_label(),
                 provider.pr_number_prefix(),
-                pr_num
+                pr_num,
+                provider.merge_queue_name()
             )));
         }
 
@@ -1446,7 +2376,13 @@ fn wait_for_merge_train_completion(
                     current_state = Some(error_state);
                     state_start_time = Instant::now();
                 }
-                interruptible_sleep(poll_interval, interrupted, current_spinner.as_ref())?;
+                wait_next_poll(
+                    listener,
+                    pr_num,
+                    poll_interval,
+                    interrupted,
+                    current_spinner.as_ref(),
+                )?;
                 continue;
             }
         };
@@ -1472,10 +2408,11 @@ fn wait_for_merge_train_completion(
                 spinner.finish_and_clear();
             }
             return Err(GgError::Other(format!(
-                "{} {}{} was closed (may have been removed from merge train)",
+                "{} {}{} was closed (may have been removed from {})",
                 provider.pr_label(),
                 provider.pr_number_prefix(),
-                pr_num
+                pr_num,
+                provider.merge_queue_name()
             )));
         }
 
@@ -1486,16 +2423,20 @@ fn wait_for_merge_train_completion(
         match provider.get_merge_train_status(pr_num, target_branch) {
             Ok(Some(train_info)) => {
                 use crate::glab::MergeTrainStatus;
+                if train_info.status != MergeTrainStatus::Stale {
+                    stale_polls = 0;
+                }
                 match train_info.status {
                     MergeTrainStatus::Merged => {
                         if let Some(ref spinner) = current_spinner {
                             finish_spinner(
                                 spinner,
                                 &format!(
-                                    "{} {}{} merged via merge train",
+                                    "{} {}{} merged via {}",
                                     provider.pr_label(),
                                     provider.pr_number_prefix(),
-                                    pr_num
+                                    pr_num,
+                                    provider.merge_queue_name()
                                 ),
                                 state_start_time,
                             );
@@ -1505,29 +2446,56 @@ fn wait_for_merge_train_completion(
                     MergeTrainStatus::Merging => {
                         seen_in_train = true;
                         idle_count = 0; // MR is in train, reset grace counter
-                        new_state = "Merge train: merging now...".to_string();
+                        new_state = format!("{}: merging now...", queue_title(provider));
                     }
                     MergeTrainStatus::Fresh => {
                         seen_in_train = true;
                         idle_count = 0;
                         if let Some(pos) = train_info.position {
-                            new_state = format!("Merge train: position {} (fresh, ready)", pos);
+                            new_state = format!(
+                                "{}: position {} (fresh, ready)",
+                                queue_title(provider),
+                                pos
+                            );
                         }
                     }
                     MergeTrainStatus::Stale => {
                         seen_in_train = true;
                         idle_count = 0;
-                        new_state = "Merge train: stale (waiting for rebase/pipeline)".to_string();
+                        stale_polls += 1;
+                        if should_refresh_stale_train_entry(stale_polls, stale_refreshes) {
+                            stale_polls = 0;
+                            stale_refreshes += 1;
+                            new_state = match refresh_stale_train_entry(provider, pr_num) {
+                                Ok(()) => format!(
+                                    "Merge train: stale, rebased and re-queued (refresh {}/{})",
+                                    stale_refreshes, MAX_STALE_REFRESHES
+                                ),
+                                Err(e) => format!(
+                                    "Merge train: stale, refresh {}/{} failed: {}",
+                                    stale_refreshes, MAX_STALE_REFRESHES, e
+                                ),
+                            };
+                        } else if stale_refreshes >= MAX_STALE_REFRESHES {
+                            new_state = format!(
+                                "Merge train: still stale after {} refreshes (waiting for rebase/pipeline)",
+                                MAX_STALE_REFRESHES
+                            );
+                        } else {
+                            new_state =
+                                "Merge train: stale (waiting for rebase/pipeline)".to_string();
+                        }
                     }
                     MergeTrainStatus::SkipMerged => {
                         if let Some(ref spinner) = current_spinner {
                             spinner.finish_and_clear();
                         }
                         return Err(GgError::Other(format!(
-                            "{} {}{} was skipped from the merge train",
+                            "{} {}{} was skipped from the {}",
                             provider.pr_label(),
                             provider.pr_number_prefix(),
-                            pr_num
+                            pr_num,
+                            provider.merge_queue_name()
                         )));
                     }
                     MergeTrainStatus::Idle => {
@@ -1539,15 +2507,32 @@ fn wait_for_merge_train_completion(
                                 spinner.finish_and_clear();
                             }
                             return Err(GgError::Other(format!(
-                                "{} {}{} cannot continue in the merge train: {}",
+                                "{} {}{} cannot continue in the {}: {}",
                                 provider.pr_label(),
                                 provider.pr_number_prefix(),
                                 pr_num,
+                                provider.merge_queue_name(),
                                 message
                             )));
                         }
-                        new_state =
-                            merge_train_idle_state_message(idle_count, seen_in_train).to_string();
+                        // GitHub drops failed PRs from its merge queue; once
+                        // a PR has been seen queued, leaving it is final.
+                        if seen_in_train && *provider == Provider::GitHub {
+                            if let Some(ref spinner) = current_spinner {
+                                spinner.finish_and_clear();
+                            }
+                            return Err(GgError::Other(format!(
+                                "PR #{} was removed from the merge queue",
+                                pr_num
+                            )));
+                        }
+                        new_state = match provider {
+                            Provider::GitLab => {
+                                merge_train_idle_state_message(idle_count, seen_in_train)
+                                    .to_string()
+                            }
+                            _ => "Waiting for merge queue to pick up PR...".to_string(),
+                        };
                     }
                     MergeTrainStatus::Unknown => {
                         if should_count_merge_train_status_as_api_error(&train_info) {
@@ -1558,16 +2543,22 @@ fn wait_for_merge_train_completion(
                                     spinner.finish_and_clear();
                                 }
                                 return Err(GgError::Other(format!(
-                                    "Too many consecutive API errors ({}) while checking merge train status",
-                                    consecutive_errors
+                                    "Too many consecutive API errors ({}) while checking {} status",
+                                    consecutive_errors,
+                                    provider.merge_queue_name()
                                 )));
                             }
                             new_state = format!(
-                                "Merge train status unavailable (attempt {}/{}), retrying...",
-                                consecutive_errors, MAX_CONSECUTIVE_API_ERRORS
+                                "{} status unavailable (attempt {}/{}), retrying...",
+                                queue_title(provider),
+                                consecutive_errors,
+                                MAX_CONSECUTIVE_API_ERRORS
                             );
                         } else {
-                            new_state = "Merge train returned an unrecognized status; waiting conservatively...".to_string();
+                            new_state = format!(
+                                "{} returned an unrecognized status; waiting conservatively...",
+                                queue_title(provider)
+                            );
                         }
                     }
                 }
@@ -1592,13 +2583,18 @@ fn wait_for_merge_train_completion(
                         spinner.finish_and_clear();
                     }
                     return Err(GgError::Other(format!(
-                        "Too many consecutive API errors ({}) while checking merge train status: {}",
-                        consecutive_errors, e
+                        "Too many consecutive API errors ({}) while checking {} status: {}",
+                        consecutive_errors,
+                        provider.merge_queue_name(),
+                        e
                     )));
                 }
                 new_state = format!(
-                    "Merge train API error (attempt {}/{}): {}",
-                    consecutive_errors, MAX_CONSECUTIVE_API_ERRORS, e
+                    "{} API error (attempt {}/{}): {}",
+                    queue_title(provider),
+                    consecutive_errors,
+                    MAX_CONSECUTIVE_API_ERRORS,
+                    e
                 );
             }
         }
@@ -1627,7 +2623,13 @@ fn wait_for_merge_train_completion(
         }
 
         // Wait before next poll
-        interruptible_sleep(poll_interval, interrupted, current_spinner.as_ref())?;
+        wait_next_poll(
+            listener,
+            pr_num,
+            poll_interval,
+            interrupted,
+            current_spinner.as_ref(),
+        )?;
     }
 }
 
@@ -1642,6 +2644,47 @@ mod tests {
         assert_eq!(POLL_INTERVAL_SECS, 10);
     }
 
+    #[test]
+    fn test_stale_refresh_waits_for_persistent_stale_status() {
+        assert!(!should_refresh_stale_train_entry(0, 0));
+        assert!(!should_refresh_stale_train_entry(
+            STALE_REFRESH_POLLS - 1,
+            0
+        ));
+        assert!(should_refresh_stale_train_entry(STALE_REFRESH_POLLS, 0));
+        assert!(should_refresh_stale_train_entry(
+            STALE_REFRESH_POLLS + 1,
+            MAX_STALE_REFRESHES - 1
+        ));
+    }
+
+    #[test]
+    fn test_format_not_approved_lists_requirements() {
+        assert_eq!(format_not_approved("MR !7", &[]), "MR !7 is not approved");
+        assert_eq!(
+            format_not_approved(
+                "MR !7",
+                &[
+                    "needs 1 approval from @backend-owners (Backend)".to_string(),
+                    "needs 2 approvals (Any)".to_string(),
+                ]
+            ),
+            "MR !7 is not approved: needs 1 approval from @backend-owners (Backend); needs 2 approvals (Any)"
+        );
+    }
+
+    #[test]
+    fn test_stale_refresh_is_bounded() {
+        assert!(!should_refresh_stale_train_entry(
+            STALE_REFRESH_POLLS,
+            MAX_STALE_REFRESHES
+        ));
+        assert!(!should_refresh_stale_train_entry(
+            u32::MAX,
+            MAX_STALE_REFRESHES
+        ));
+    }
+
     #[test]
     fn test_poll_interval_is_reasonable() {
         // Poll interval should be between 1 and 60 seconds
@@ -1673,12 +2716,20 @@ mod tests {
             defaults: Defaults::default(),
             worktree_base_path: None,
             stacks: HashMap::new(),
+            hooks: Default::default(),
         };
 
         let mut stack_config = StackConfig {
             base: None,
+            remote: None,
+            from: None,
+            description: None,
             mrs: HashMap::new(),
             worktree_path: None,
+            scope: vec![],
+            parent: None,
+            reviewers: vec![],
+            last_touched_ms: None,
         };
         stack_config.mrs.insert("c-abc1234".to_string(), 123);
         stack_config.mrs.insert("c-def5678".to_string(), 456);
@@ -1703,12 +2754,20 @@ mod tests {
             defaults: Defaults::default(),
             worktree_base_path: None,
             stacks: HashMap::new(),
+            hooks: Default::default(),
         };
 
         let mut stack_config = StackConfig {
             base: None,
+            remote: None,
+            from: None,
+            description: None,
             mrs: HashMap::new(),
             worktree_path: None,
+            scope: vec![],
+            parent: None,
+            reviewers: vec![],
+            last_touched_ms: None,
         };
         stack_config.mrs.insert("c-abc1234".to_string(), 123);
         config.stacks.insert("test-stack".to_string(), stack_config);
@@ -1735,6 +2794,7 @@ mod tests {
             defaults: Defaults::default(),
             worktree_base_path: None,
             stacks: HashMap::new(),
+            hooks: Default::default(),
         };
 
         // Try to remove from non-existent stack - should not panic
@@ -1750,12 +2810,20 @@ mod tests {
             defaults: Defaults::default(),
             worktree_base_path: None,
             stacks: HashMap::new(),
+            hooks: Default::default(),
         };
 
         let mut stack_config = StackConfig {
             base: None,
+            remote: None,
+            from: None,
+            description: None,
             mrs: HashMap::new(),
             worktree_path: None,
+            scope: vec![],
+            parent: None,
+            reviewers: vec![],
+            last_touched_ms: None,
         };
         stack_config.mrs.insert("c-abc1234".to_string(), 123);
         stack_config.mrs.insert("c-def5678".to_string(), 456);
@@ -1793,10 +2861,19 @@ mod tests {
         // - gg_id: &str (commit id to clean up)
         // - pr_num: u64 (PR number that was merged)
         // - land_all: bool (whether to update remaining PR bases)
+        // - returns the remote branch deletion, if delete_branch_on_land removed one
 
         // Type-level assertion that cleanup_after_merge exists with the correct signature
-        let _fn_ptr: fn(&mut Config, &Stack, &Provider, &str, u64, bool, bool) =
-            cleanup_after_merge;
+        let _fn_ptr: fn(
+            &git2::Repository,
+            &mut Config,
+            &Stack,
+            &Provider,
+            &str,
+            u64,
+            bool,
+            bool,
+        ) -> Option<RemoteEffect> = cleanup_after_merge;
     }
 
     #[test]
@@ -1921,8 +2998,10 @@ mod tests {
             bool,
             u64,
             Option<&Arc<AtomicBool>>,
+            Option<&WebhookListener>,
             &str,
             bool,
+            bool,
         ) -> Result<()> = wait_for_pr_ready;
     }
 
@@ -2416,6 +3495,9 @@ mod tests {
             position: 1,
             in_merge_train: false,
             merge_train_position: None,
+            unresolved_threads: None,
+            annotations: Default::default(),
+            signed: false,
         };
 
         // Verify it's detected as unsynced
@@ -2437,6 +3519,9 @@ mod tests {
             position: 2,
             in_merge_train: false,
             merge_train_position: None,
+            unresolved_threads: None,
+            annotations: Default::default(),
+            signed: false,
         };
 
         // Verify it's detected as synced
@@ -2476,6 +3561,9 @@ mod tests {
                 position: 1,
                 in_merge_train: false,
                 merge_train_position: None,
+                unresolved_threads: None,
+                annotations: Default::default(),
+                signed: false,
             },
             StackEntry {
                 oid: git2::Oid::ZERO_SHA1,
@@ -2492,6 +3580,9 @@ mod tests {
                 position: 2,
                 in_merge_train: false,
                 merge_train_position: None,
+                unresolved_threads: None,
+                annotations: Default::default(),
+                signed: false,
             },
             StackEntry {
                 oid: git2::Oid::ZERO_SHA1,
@@ -2508,6 +3599,9 @@ mod tests {
                 position: 3,
                 in_merge_train: false,
                 merge_train_position: None,
+                unresolved_threads: None,
+                annotations: Default::default(),
+                signed: false,
             },
             StackEntry {
                 oid: git2::Oid::ZERO_SHA1,
@@ -2524,6 +3618,9 @@ mod tests {
                 position: 4,
                 in_merge_train: false,
                 merge_train_position: None,
+                unresolved_threads: None,
+                annotations: Default::default(),
+                signed: false,
             },
         ];
 
@@ -2572,6 +3669,8 @@ mod tests {
                 }],
                 remaining: 2,
                 cleaned: false,
+                deleted_branches: vec!["alice/feat-stack--c-abc1234".to_string()],
+                changelog: None,
                 warnings: vec!["warn".to_string()],
                 error: Some("stopped".to_string()),
             },
@@ -2583,11 +3682,52 @@ mod tests {
         assert_eq!(value["land"]["base"], "main");
         assert_eq!(value["land"]["remaining"], 2);
         assert_eq!(value["land"]["cleaned"], false);
+        assert_eq!(
+            value["land"]["deleted_branches"][0],
+            "alice/feat-stack--c-abc1234"
+        );
         assert_eq!(value["land"]["landed"][0]["action"], "merged");
         assert_eq!(value["land"]["landed"][0]["pr_number"], 42);
         assert_eq!(value["land"]["error"], "stopped");
     }
 
+    #[test]
+    fn test_land_state_round_trip() {
+        let dir = tempfile::tempdir().unwrap();
+        assert!(read_land_state(dir.path()).is_none());
+
+        let state = LandState {
+            branch: "alice/feature".to_string(),
+            land_all: true,
+            until: Some("c-abc1234".to_string()),
+            strategy: MergeStrategy::Rebase,
+            wait: true,
+            auto_clean: false,
+            auto_merge: false,
+            admin: false,
+            auto_resolve_restack: true,
+            retrigger_ci: false,
+            merged: vec![101],
+            current: Some(102),
+            restack_pending: true,
+        };
+        save_land_state(dir.path(), &state).unwrap();
+        assert_eq!(read_land_state(dir.path()), Some(state.clone()));
+
+        cancel_pending_restack(dir.path()).unwrap();
+        let saved = read_land_state(dir.path()).unwrap();
+        assert!(!saved.restack_pending);
+        assert_eq!(saved.merged, vec![101]);
+
+        let options = state.into_options(true);
+        assert!(options.resume && options.json && options.auto_resolve_restack);
+        assert_eq!(options.strategy, MergeStrategy::Rebase);
+        assert_eq!(options.until.as_deref(), Some("c-abc1234"));
+
+        clear_land_state(dir.path()).unwrap();
+        assert!(read_land_state(dir.path()).is_none());
+    }
+
     // ==========================================================================
     // Tests for merge train idle transition handling
     // ==========================================================================
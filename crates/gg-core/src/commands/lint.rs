@@ -1,25 +1,51 @@
 //! `gg lint` - Run configured lint commands on each commit in the stack
 //!
 //! Thin wrapper around `gg run` that reads commands from config
-//! and uses `ChangeMode::Amend`.
+//! and uses `ChangeMode::Amend`. With `--jobs`, commits are linted in
+//! parallel worktrees and their fixes replayed onto the stack in order.
+//! A commit can replace the configured commands with its own, through
+//! `GG-Lint:` trailers or a `.gg/lint.json` file in its tree.
 
 use console::style;
+use git2::{Oid, Repository};
+use regex::Regex;
 
-use crate::config::Config;
-use crate::error::Result;
+use crate::config::{Config, LintCommand};
+use crate::error::{GgError, Result};
 use crate::git;
 use crate::output::{
     self, LintCommandResult, LintCommitResult, LintResponse, LintResultJson, OUTPUT_VERSION,
 };
+use crate::stack::Stack;
 
 use super::run::{self, ChangeMode, RunOptions};
 
+/// Prefix for trailers that set a commit's own lint commands
+pub const GG_LINT_PREFIX: &str = "GG-Lint:";
+/// File in a commit's tree listing its own lint commands
+pub const LINT_FILE: &str = ".gg/lint.json";
+
+/// Options for the lint command
+#[derive(Debug, Default)]
+pub struct LintOptions {
+    /// First commit position to lint (default: 1)
+    pub from: Option<usize>,
+    /// Last commit position to lint (default: current)
+    pub until: Option<usize>,
+    /// Commits to lint in parallel, overriding `defaults.lint_jobs`
+    /// (0 = auto, 1 = sequential)
+    pub jobs: Option<usize>,
+    pub json: bool,
+    /// Whether to print the JSON response (false when called from sync)
+    pub emit_json_output: bool,
+}
+
 /// Run the lint command.
 ///
 /// Returns `Ok(true)` when all lint commands passed for all linted commits,
 /// `Ok(false)` when one or more commits had lint failures.
-pub fn run(until: Option<usize>, json: bool, emit_json_output: bool) -> Result<bool> {
-    run_with_no_commands_help(until, json, emit_json_output, NoCommandsHelp::Example)
+pub fn run(options: LintOptions) -> Result<bool> {
+    run_with_no_commands_help(options, NoCommandsHelp::Example)
 }
 
 /// Run the lint command with a shorter no-config message for parent commands.
@@ -31,7 +57,82 @@ pub fn run_brief_no_commands(
     json: bool,
     emit_json_output: bool,
 ) -> Result<bool> {
-    run_with_no_commands_help(until, json, emit_json_output, NoCommandsHelp::Brief)
+    run_with_no_commands_help(
+        LintOptions {
+            until,
+            json,
+            emit_json_output,
+            ..Default::default()
+        },
+        NoCommandsHelp::Brief,
+    )
+}
+
+/// Lint commands commit `oid` sets for itself, replacing `defaults.lint`.
+///
+/// Each `GG-Lint:` trailer adds one command (`GG-Lint: none` lints
+/// nothing); without trailers, a `.gg/lint.json` array in the commit's
+/// tree is used. Returns `None` when the commit sets neither.
+pub fn commit_lint_commands(repo: &Repository, oid: Oid) -> Result<Option<Vec<LintCommand>>> {
+    let commit = repo.find_commit(oid)?;
+    if let Some(commands) = commit.message().ok().and_then(parse_lint_trailers) {
+        return Ok(Some(commands));
+    }
+
+    let tree = commit.tree()?;
+    let Ok(entry) = tree.get_path(std::path::Path::new(LINT_FILE)) else {
+        return Ok(None);
+    };
+    let blob = entry.to_object(repo)?.peel_to_blob()?;
+    serde_json::from_slice(blob.content())
+        .map(Some)
+        .map_err(|e| {
+            GgError::Other(format!(
+                "Invalid {} in commit {}: {}",
+                LINT_FILE,
+                git::short_sha(&commit),
+                e
+            ))
+        })
+}
+
+fn parse_lint_trailers(message: &str) -> Option<Vec<LintCommand>> {
+    let re = Regex::new(r"(?i)^GG-Lint:\s*(.+)$").ok()?;
+    let commands: Vec<&str> = message
+        .lines()
+        .filter_map(|line| re.captures(line.trim()))
+        .filter_map(|captures| captures.get(1).map(|m| m.as_str().trim()))
+        .collect();
+    if commands.is_empty() {
+        return None;
+    }
+    Some(
+        commands
+            .into_iter()
+            .filter(|c| !c.eq_ignore_ascii_case("none"))
+            .map(LintCommand::from)
+            .collect(),
+    )
+}
+
+/// Parse a `--range` value of commit positions: `N..M` (inclusive), or
+/// `N..` / `..M` with the other end left open.
+pub fn parse_range(range: &str) -> Result<(Option<usize>, Option<usize>)> {
+    let invalid = || {
+        GgError::Other(format!(
+            "Invalid range '{}'. Use N..M, N.. or ..M with commit positions.",
+            range
+        ))
+    };
+    let (from, until) = range.trim().split_once("..").ok_or_else(invalid)?;
+    let parse = |s: &str| -> Result<Option<usize>> {
+        if s.is_empty() {
+            Ok(None)
+        } else {
+            s.parse().map(Some).map_err(|_| invalid())
+        }
+    };
+    Ok((parse(from)?, parse(until.trim_start_matches('='))?))
 }
 
 #[derive(Clone, Copy)]
@@ -41,16 +142,30 @@ enum NoCommandsHelp {
 }
 
 fn run_with_no_commands_help(
-    until: Option<usize>,
-    json: bool,
-    emit_json_output: bool,
+    options: LintOptions,
     no_commands_help: NoCommandsHelp,
 ) -> Result<bool> {
+    let LintOptions {
+        from,
+        until,
+        jobs,
+        json,
+        emit_json_output,
+    } = options;
     let repo = git::open_repo()?;
-    let config = Config::load_with_global(repo.commondir())?;
+    let config = Config::load(repo.commondir())?;
 
+    let stack = Stack::load(&repo, &config).ok();
     let lint_commands = &config.defaults.lint;
-    if lint_commands.is_empty() {
+    let has_commit_commands = || -> Result<bool> {
+        for entry in stack.iter().flat_map(|s| &s.entries) {
+            if commit_lint_commands(&repo, entry.oid)?.is_some() {
+                return Ok(true);
+            }
+        }
+        Ok(false)
+    };
+    if lint_commands.is_empty() && !has_commit_commands()? {
         if json && emit_json_output {
             print_empty_response();
         } else if !json {
@@ -72,18 +187,32 @@ fn run_with_no_commands_help(
         return Ok(true);
     }
 
+    // Lint fixes amend commits: keep the stack's tip around first
+    if let Some(stack) = &stack {
+        super::snapshot::backup_stack(&repo, stack)?;
+    }
+
+    // Lint fixes stay inside the stack's path scope, if it has one.
+    let scope = stack
+        .map(|stack| config.get_scope_for_stack(&stack.name).to_vec())
+        .unwrap_or_default();
+
     let result = run::execute_raw(RunOptions {
         commands: lint_commands
             .iter()
-            .map(|s| run::RunCommand::Shell(s.clone()))
+            .map(|c| run::RunCommand::Shell(c.cmd().to_string()))
             .collect(),
+        command_paths: lint_commands.iter().map(|c| c.paths().to_vec()).collect(),
+        commit_commands: Some(commit_lint_commands),
         change_mode: ChangeMode::Amend,
+        from,
         until,
         stop_on_error: false,
         json,
         emit_json_output,
         header_label: Some("lint".to_string()),
-        jobs: 1,
+        jobs: jobs.unwrap_or_else(|| config.get_lint_jobs()),
+        scope,
     })?;
 
     if json && emit_json_output {
@@ -105,6 +234,7 @@ fn run_with_no_commands_help(
                         output: c.output,
                     })
                     .collect(),
+                skipped: r.skipped,
             })
             .collect();
 
@@ -129,3 +259,35 @@ fn print_empty_response() {
         },
     });
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn lint_trailers_set_commit_commands() {
+        assert_eq!(parse_lint_trailers("Fix parser\n\nGG-ID: c-abc1234"), None);
+        assert_eq!(
+            parse_lint_trailers("Fix parser\n\nGG-Lint: cargo fmt\ngg-lint:  ruff check --fix \n")
+                .unwrap()
+                .iter()
+                .map(|c| c.cmd().to_string())
+                .collect::<Vec<_>>(),
+            vec!["cargo fmt", "ruff check --fix"]
+        );
+        assert_eq!(
+            parse_lint_trailers("Docs only\n\nGG-Lint: none"),
+            Some(vec![])
+        );
+    }
+
+    #[test]
+    fn parse_range_accepts_closed_and_open_ranges() {
+        assert_eq!(parse_range("2..4").unwrap(), (Some(2), Some(4)));
+        assert_eq!(parse_range("2..=4").unwrap(), (Some(2), Some(4)));
+        assert_eq!(parse_range("3..").unwrap(), (Some(3), None));
+        assert_eq!(parse_range("..2").unwrap(), (None, Some(2)));
+        assert!(parse_range("3").is_err());
+        assert!(parse_range("a..b").is_err());
+    }
+}
@@ -9,7 +9,9 @@ use console::style;
 use crate::config::Config;
 use crate::error::Result;
 use crate::git;
+use crate::notes;
 use crate::output::{print_json, LogJson, LogResponse, StackEntryJson, OUTPUT_VERSION};
+use crate::plain;
 use crate::provider::{CiStatus, PrState, Provider};
 use crate::stack::Stack;
 
@@ -17,9 +19,9 @@ use crate::stack::Stack;
 pub fn run(json: bool, refresh: bool) -> Result<()> {
     let repo = git::open_repo()?;
     let git_dir = repo.commondir();
-    let config = Config::load_with_global(git_dir)?;
+    let config = Config::load(git_dir)?;
 
-    let mut stack = Stack::load(&repo, &config)?;
+    let mut stack = Stack::load_active(&repo, &config)?;
     if !json {
         if let Some(mismatch) = stack.prefix_mismatch(&config) {
             println!(
@@ -33,7 +35,7 @@ pub fn run(json: bool, refresh: bool) -> Result<()> {
 
     if should_refresh_mr_info(refresh, json) {
         if refresh {
-            let provider = Provider::detect(&repo)?;
+            let provider = Provider::for_stack(&repo, &config, &stack.name)?;
             if !json {
                 print!("Refreshing {} status... ", provider.pr_label());
             }
@@ -41,7 +43,7 @@ pub fn run(json: bool, refresh: bool) -> Result<()> {
             if !json {
                 println!("{}", style("done").green());
             }
-        } else if let Ok(provider) = Provider::detect(&repo) {
+        } else if let Ok(provider) = Provider::for_stack(&repo, &config, &stack.name) {
             stack.refresh_mr_info(&provider)?;
         }
     }
@@ -79,10 +81,14 @@ fn build_log_response(stack: &Stack) -> LogResponse {
             pr_number: entry.mr_number,
             pr_state: entry.mr_state.as_ref().map(pr_state_to_json),
             approved: entry.approved,
+            changes_requested: entry.changes_requested,
             ci_status: entry.ci_status.as_ref().map(ci_status_to_json),
             is_current: current_pos_1based == Some(entry.position),
             in_merge_train: entry.in_merge_train,
             merge_train_position: entry.merge_train_position,
+            unresolved_threads: entry.unresolved_threads,
+            annotations: entry.annotations.clone(),
+            signed: entry.signed,
         })
         .collect();
 
@@ -109,7 +115,7 @@ fn render_text(stack: &Stack, repo: &git2::Repository) {
     if git::is_rebase_in_progress(repo) {
         println!(
             "{} {}",
-            style("⚠️").yellow(),
+            style(plain::warn()).yellow(),
             style("Rebase in progress. Run `gg continue` or `gg abort`")
                 .yellow()
                 .bold()
@@ -152,13 +158,30 @@ fn render_text(stack: &Stack, repo: &git2::Repository) {
                 }
             }
 
-            let continuation = if i + 1 < total { "│" } else { " " };
+            let continuation = if i + 1 < total {
+                plain::tree_pipe()
+            } else {
+                " "
+            };
             println!(
                 "  {}     {}",
                 style(continuation).dim(),
                 style(&mr_line).blue()
             );
         }
+
+        if !entry.annotations.is_empty() {
+            let continuation = if i + 1 < total {
+                plain::tree_pipe()
+            } else {
+                " "
+            };
+            println!(
+                "  {}     {}",
+                style(continuation).dim(),
+                style(notes::render(&entry.annotations, "  ")).magenta()
+            );
+        }
     }
 
     println!();
@@ -169,9 +192,9 @@ fn render_text(stack: &Stack, repo: &git2::Repository) {
 /// `├──` for all entries except the last (HEAD), which uses `└──`.
 fn glyph_for_position(i: usize, total: usize) -> &'static str {
     if i + 1 == total {
-        "└──"
+        plain::tree_last()
     } else {
-        "├──"
+        plain::tree_mid()
     }
 }
 
@@ -196,14 +219,18 @@ fn format_entry_line(
     };
 
     let ci = match &entry.ci_status {
-        Some(CiStatus::Success) => style("✓").green().to_string(),
-        Some(CiStatus::Failed) => style("✗").red().to_string(),
-        Some(CiStatus::Running) => style("●").yellow().to_string(),
-        Some(CiStatus::Pending) => style("○").dim().to_string(),
+        Some(CiStatus::Success) => style(plain::ok()).green().to_string(),
+        Some(CiStatus::Failed) => style(plain::fail()).red().to_string(),
+        Some(CiStatus::Running) => style(plain::running()).yellow().to_string(),
+        Some(CiStatus::Pending) => style(plain::pending()).dim().to_string(),
         _ => String::new(),
     };
 
-    let train = if entry.in_merge_train { " 🚂" } else { "" };
+    let train = if entry.in_merge_train {
+        plain::sym(" 🚂", " [train]")
+    } else {
+        ""
+    };
     let mr_display = entry
         .mr_number
         .map(|n| format!(" {}{}", pr_prefix, n))
@@ -281,6 +308,9 @@ mod tests {
             position,
             in_merge_train: false,
             merge_train_position: None,
+            unresolved_threads: None,
+            annotations: Default::default(),
+            signed: false,
         }
     }
 
@@ -1,23 +1,146 @@
 //! `gg ls` - List current stack or all stacks
 
+use std::collections::{BTreeMap, BTreeSet};
+use std::path::Path;
+
+use clap::ValueEnum;
 use console::style;
 
 use crate::config::Config;
 use crate::error::Result;
 use crate::git;
+use crate::notes;
 use crate::operations;
 use crate::output::{
-    print_json, AllStacksResponse, RemoteStackJson, RemoteStacksResponse, SingleStackResponse,
-    StackCommitJson, StackEntryJson, StackJson, StackSummaryJson, OUTPUT_VERSION,
+    print_json, AllStacksResponse, GlobalPrJson, GlobalRepoJson, GlobalStackJson,
+    GlobalStacksResponse, RemoteStackJson, RemoteStacksResponse, RemoteUserStacksResponse,
+    SingleStackResponse, StackCommitJson, StackEntryJson, StackJson, StackSummaryJson,
+    OUTPUT_VERSION,
 };
+use crate::parallel;
+use crate::plain;
 use crate::provider::{CiStatus, PrState, Provider};
-use crate::stack::{self, Stack};
+use crate::registry::Registry;
+use crate::relative_time;
+use crate::stack::{self, Stack, StackEntry};
+
+/// Options for `gg ls`
+#[derive(Debug, Default)]
+pub struct LsOptions {
+    /// Show all stacks, not just the current one
+    pub all: bool,
+    /// Refresh PR/MR status from the provider
+    pub refresh: bool,
+    /// List stacks on origin not checked out locally
+    pub remote: bool,
+    /// Show each entry's PR/MR review state and unresolved threads
+    /// (implies `refresh`)
+    pub prs: bool,
+    /// Only list stacks matching this filter (implies `all`)
+    pub filter: Option<StackFilter>,
+    /// Order of the all-stacks list (implies `all`)
+    pub sort: Option<StackSort>,
+    pub json: bool,
+}
+
+/// Which stacks `gg ls --filter` keeps.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum StackFilter {
+    /// Stack name contains the text (case-insensitive)
+    Name(String),
+    /// A commit's author name or email contains the text (case-insensitive)
+    Author(String),
+    /// A PR/MR of the stack is in this state; `None` for stacks without any
+    State(Option<PrState>),
+}
+
+impl std::fmt::Display for StackFilter {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            StackFilter::Name(text) => write!(f, "{}", text),
+            StackFilter::Author(text) => write!(f, "author:{}", text),
+            StackFilter::State(state) => write!(
+                f,
+                "state:{}",
+                state
+                    .as_ref()
+                    .map(pr_state_to_json)
+                    .as_deref()
+                    .unwrap_or("none")
+            ),
+        }
+    }
+}
+
+/// Parse a `--filter` value: `author:<text>`, `state:<state>`, or a stack
+/// name substring (optionally written `name:<text>`).
+pub fn parse_filter(value: &str) -> std::result::Result<StackFilter, String> {
+    let (kind, text) = value.split_once(':').unwrap_or(("name", value));
+    if text.is_empty() {
+        return Err(format!("empty filter '{}'", value));
+    }
+    match kind {
+        "name" => Ok(StackFilter::Name(text.to_string())),
+        "author" => Ok(StackFilter::Author(text.to_string())),
+        "state" => match text {
+            "open" => Ok(StackFilter::State(Some(PrState::Open))),
+            "draft" => Ok(StackFilter::State(Some(PrState::Draft))),
+            "merged" => Ok(StackFilter::State(Some(PrState::Merged))),
+            "closed" => Ok(StackFilter::State(Some(PrState::Closed))),
+            "none" => Ok(StackFilter::State(None)),
+            _ => Err(format!(
+                "invalid state '{}': use open, draft, merged, closed or none",
+                text
+            )),
+        },
+        // Not a known prefix: the colon is part of the name
+        _ => Ok(StackFilter::Name(value.to_string())),
+    }
+}
+
+/// Order of `gg ls --all`
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, ValueEnum)]
+pub enum StackSort {
+    /// Alphabetical
+    #[default]
+    Name,
+    /// Most recently touched or updated first
+    Updated,
+    /// Most commits first
+    Size,
+}
+
+impl StackSort {
+    fn as_str(self) -> &'static str {
+        match self {
+            StackSort::Name => "name",
+            StackSort::Updated => "updated",
+            StackSort::Size => "size",
+        }
+    }
+}
 
 /// Run the list command
-pub fn run(all: bool, refresh: bool, remote: bool, json: bool) -> Result<()> {
+pub fn run(options: LsOptions) -> Result<()> {
+    let LsOptions {
+        all,
+        refresh,
+        remote,
+        prs,
+        filter,
+        sort,
+        json,
+    } = options;
+    let refresh = refresh || prs;
+    let all = all || filter.is_some() || sort.is_some();
+    let listing = Listing {
+        refresh,
+        filter,
+        sort: sort.unwrap_or_default(),
+    };
     let repo = git::open_repo()?;
     let git_dir = repo.commondir();
-    let config = Config::load_with_global(git_dir)?;
+    let config = Config::load(git_dir)?;
 
     // Handle --remote flag
     if remote {
@@ -25,11 +148,11 @@ pub fn run(all: bool, refresh: bool, remote: bool, json: bool) -> Result<()> {
     }
 
     // Try to load current stack
-    let current_stack = Stack::load(&repo, &config).ok();
+    let current_stack = Stack::load_active(&repo, &config).ok();
 
     match current_stack {
         None => {
-            list_all_stacks(&repo, &config, json)?;
+            list_all_stacks(&repo, &config, &listing, json)?;
         }
         Some(mut stack) if !all => {
             if !json {
@@ -45,31 +168,117 @@ pub fn run(all: bool, refresh: bool, remote: bool, json: bool) -> Result<()> {
 
             if should_refresh_mr_info(refresh, json) {
                 if refresh {
-                    let provider = Provider::detect(&repo)?;
+                    let provider = Provider::for_stack(&repo, &config, &stack.name)?;
                     if !json {
                         print!("Refreshing {} status... ", provider.pr_label());
                     }
-                    stack.refresh_mr_info(&provider)?;
+                    stack.refresh_pr_details(&provider, config.get_sync_jobs(), prs);
                     if !json {
                         println!("{}", style("done").green());
                     }
-                } else if let Ok(provider) = Provider::detect(&repo) {
-                    stack.refresh_mr_info(&provider)?;
+                } else if let Ok(provider) = Provider::for_stack(&repo, &config, &stack.name) {
+                    stack.refresh_pr_details(&provider, config.get_sync_jobs(), false);
                 }
             }
 
-            show_stack(&stack, json)?;
+            show_stack(&stack, &config, prs, json)?;
         }
         Some(_) => {
-            list_all_stacks(&repo, &config, json)?;
+            list_all_stacks(&repo, &config, &listing, json)?;
         }
     }
 
     Ok(())
 }
 
-/// List all available stacks with their commits in a tree view
-fn list_all_stacks(repo: &git2::Repository, config: &Config, json: bool) -> Result<()> {
+/// Entries untouched for longer than this are highlighted in `gg ls --all`.
+const STALE_AFTER_MS: u64 = 7 * 24 * 60 * 60 * 1000;
+
+/// How `gg ls --all` gathers and orders stacks.
+#[derive(Debug, Default)]
+struct Listing {
+    /// Query each entry's PR/MR for its last activity
+    refresh: bool,
+    filter: Option<StackFilter>,
+    sort: StackSort,
+}
+
+/// A stack of the all-stacks view, before rendering.
+struct ListedStack {
+    name: String,
+    commits: Result<Vec<StackCommitInfo>>,
+    last_touched_ms: Option<u64>,
+}
+
+impl ListedStack {
+    fn commit_count(&self) -> usize {
+        self.commits.as_ref().map(|c| c.len()).unwrap_or(0)
+    }
+
+    /// Latest push or PR/MR activity of any entry
+    fn last_activity_ms(&self) -> Option<u64> {
+        self.commits
+            .as_ref()
+            .ok()?
+            .iter()
+            .filter_map(StackCommitInfo::last_activity_ms)
+            .max()
+    }
+
+    fn updated_ms(&self) -> Option<u64> {
+        self.last_touched_ms.max(self.last_activity_ms())
+    }
+}
+
+/// Keep the stacks matching `filter`. `pr_states` holds the states of each
+/// stack's PRs/MRs, only needed by state filters.
+fn filter_stacks(stacks: &mut Vec<ListedStack>, filter: &StackFilter, pr_states: &[Vec<PrState>]) {
+    let contains =
+        |haystack: &str, needle: &str| haystack.to_lowercase().contains(&needle.to_lowercase());
+    let mut index = 0;
+    stacks.retain(|stack| {
+        let states = pr_states.get(index);
+        index += 1;
+        match filter {
+            StackFilter::Name(text) => contains(&stack.name, text),
+            StackFilter::Author(text) => stack
+                .commits
+                .as_ref()
+                .is_ok_and(|commits| commits.iter().any(|c| contains(&c.author, text))),
+            StackFilter::State(Some(state)) => states.is_some_and(|s| s.contains(state)),
+            StackFilter::State(None) => stack
+                .commits
+                .as_ref()
+                .is_ok_and(|commits| commits.iter().all(|c| c.pr_number.is_none())),
+        }
+    });
+}
+
+fn sort_stacks(stacks: &mut [ListedStack], sort: StackSort) {
+    match sort {
+        StackSort::Name => stacks.sort_by(|a, b| a.name.cmp(&b.name)),
+        StackSort::Updated => stacks.sort_by(|a, b| {
+            b.updated_ms()
+                .cmp(&a.updated_ms())
+                .then_with(|| a.name.cmp(&b.name))
+        }),
+        StackSort::Size => stacks.sort_by(|a, b| {
+            b.commit_count()
+                .cmp(&a.commit_count())
+                .then_with(|| a.name.cmp(&b.name))
+        }),
+    }
+}
+
+/// List all available stacks with their commits in a tree view.
+///
+/// With `refresh`, each entry's PR/MR is queried for its last activity.
+fn list_all_stacks(
+    repo: &git2::Repository,
+    config: &Config,
+    listing: &Listing,
+    json: bool,
+) -> Result<()> {
     let username = config
         .defaults
         .branch_username
@@ -81,37 +290,81 @@ fn list_all_stacks(repo: &git2::Repository, config: &Config, json: bool) -> Resu
 
     let stacks = stack::list_all_stacks(repo, config, &username)?;
 
-    // Get current branch to highlight active stack
-    let current_branch = git::current_branch_name(repo);
-    let current_stack = current_branch
-        .as_ref()
-        .and_then(|b| git::parse_stack_branch(b))
+    // Highlight the stack selected with `gg use`, else the checked-out one
+    let current_stack = stack::read_active_stack(repo.path())
+        .or_else(|| git::current_branch_name(repo))
+        .as_deref()
+        .and_then(git::parse_stack_branch)
         .map(|(_, name)| name);
 
     // Get base branch for commit listing
     let base_branch = git::find_base_branch(repo).unwrap_or_else(|_| "main".to_string());
+    let provider = if listing.refresh {
+        Provider::detect(repo).ok()
+    } else {
+        None
+    };
+    let load_commits = |stack_name: &str| {
+        let full_branch = git::format_stack_branch(&username, stack_name);
+        let mut commits = get_stack_commits_info(
+            repo,
+            config.get_remote_for_stack(stack_name),
+            &full_branch,
+            &base_branch,
+        )?;
+        fill_activity(
+            repo,
+            config,
+            provider.as_ref(),
+            &username,
+            stack_name,
+            &mut commits,
+        );
+        Ok::<_, crate::error::GgError>(commits)
+    };
+    let now = relative_time::now_ms();
+
+    let total_stacks = stacks.len();
+    let mut stacks: Vec<ListedStack> = stacks
+        .into_iter()
+        .map(|name| ListedStack {
+            commits: load_commits(&name),
+            last_touched_ms: stack::last_touched_ms(repo, config, &username, &name),
+            name,
+        })
+        .collect();
+    if let Some(filter) = &listing.filter {
+        let pr_states = match filter {
+            StackFilter::State(Some(_)) => stack_pr_states(repo, config, &stacks)?,
+            _ => Vec::new(),
+        };
+        filter_stacks(&mut stacks, filter, &pr_states);
+    }
+    sort_stacks(&mut stacks, listing.sort);
 
     if json {
         let summaries = stacks
-            .iter()
-            .map(|stack_name| {
+            .into_iter()
+            .map(|listed| {
+                let stack_name = &listed.name;
                 let is_current = current_stack.as_deref() == Some(stack_name);
                 let has_worktree = config
                     .get_stack(stack_name)
                     .and_then(|s| s.worktree_path.as_ref())
                     .is_some();
 
-                let full_branch = git::format_stack_branch(&username, stack_name);
-                let commits =
-                    get_stack_commits_info(repo, &full_branch, &base_branch).unwrap_or_default();
+                let commits = listed.commits.unwrap_or_default();
                 let commit_count = commits.len();
                 let commits = commits
                     .into_iter()
                     .enumerate()
-                    .map(|(i, (sha, title))| StackCommitJson {
+                    .map(|(i, commit)| StackCommitJson {
                         position: i + 1,
-                        sha,
-                        title,
+                        sha: commit.sha,
+                        title: commit.title,
+                        pr_number: commit.pr_number,
+                        pushed_at_ms: commit.pushed_at_ms,
+                        pr_updated_at_ms: commit.pr_updated_at_ms,
                     })
                     .collect();
 
@@ -126,7 +379,14 @@ fn list_all_stacks(repo: &git2::Repository, config: &Config, json: bool) -> Resu
                     commit_count,
                     is_current,
                     has_worktree,
-                    behind_base: behind_count(repo, &stack_base),
+                    behind_base: behind_count(
+                        repo,
+                        config.get_remote_for_stack(stack_name),
+                        &stack_base,
+                    ),
+                    scope: config.get_scope_for_stack(stack_name).to_vec(),
+                    description: stack::stack_description(repo, config, stack_name),
+                    last_touched_ms: listed.last_touched_ms,
                     commits,
                 }
             })
@@ -136,24 +396,55 @@ fn list_all_stacks(repo: &git2::Repository, config: &Config, json: bool) -> Resu
             version: OUTPUT_VERSION,
             operation_id: operations::interrupted_rebase_operation(repo)?.map(|record| record.id),
             current_stack,
+            filter: listing.filter.as_ref().map(ToString::to_string),
+            sort: listing.sort.as_str().to_string(),
+            total_stacks,
             stacks: summaries,
         });
         return Ok(());
     }
 
     if stacks.is_empty() {
-        println!(
-            "{}",
-            style("No stacks found. Use `gg co <name>` to create one.").dim()
-        );
+        match &listing.filter {
+            Some(filter) if total_stacks > 0 => println!(
+                "{}",
+                style(format!(
+                    "No stacks match '{}' ({} stacks in total).",
+                    filter, total_stacks
+                ))
+                .dim()
+            ),
+            _ => println!(
+                "{}",
+                style("No stacks found. Use `gg co <name>` to create one.").dim()
+            ),
+        }
         return Ok(());
     }
 
-    println!("{}", style("Stacks:").bold());
+    match &listing.filter {
+        Some(filter) => println!(
+            "{} {}",
+            style("Stacks:").bold(),
+            style(format!(
+                "({} of {} matching '{}')",
+                stacks.len(),
+                total_stacks,
+                filter
+            ))
+            .dim()
+        ),
+        None => println!("{}", style("Stacks:").bold()),
+    }
 
-    for stack_name in &stacks {
+    for listed in &stacks {
+        let stack_name = &listed.name;
         let is_current = current_stack.as_deref() == Some(stack_name);
-        let marker = if is_current { "→ " } else { "  " };
+        let marker = if is_current {
+            plain::sym("→ ", "> ")
+        } else {
+            "  "
+        };
         let wt_indicator = if config
             .get_stack(stack_name)
             .and_then(|s| s.worktree_path.as_ref())
@@ -164,61 +455,112 @@ fn list_all_stacks(repo: &git2::Repository, config: &Config, json: bool) -> Resu
             ""
         };
 
-        let full_branch = git::format_stack_branch(&username, stack_name);
-        let commits = get_stack_commits_info(repo, &full_branch, &base_branch);
+        let commits = &listed.commits;
 
-        let commit_count = commits.as_ref().map(|c| c.len()).unwrap_or(0);
+        let commit_count = listed.commit_count();
         let commit_info = format!(" ({} commits)", commit_count);
         let stack_base = config
             .get_base_for_stack(stack_name)
             .unwrap_or(base_branch.as_str());
-        let behind_indicator = behind_indicator(repo, stack_base)
-            .map(|s| format!(" {}", style(s).yellow()))
+        let behind_indicator =
+            behind_indicator(repo, config.get_remote_for_stack(stack_name), stack_base)
+                .map(|s| format!(" {}", style(s).yellow()))
+                .unwrap_or_default();
+        let scope = config.get_scope_for_stack(stack_name);
+        let scope_info = if scope.is_empty() {
+            String::new()
+        } else {
+            format!(" [scope: {}]", scope.join(", "))
+        };
+        let last_activity = listed
+            .last_activity_ms()
+            .map(|ms| {
+                format!(
+                    " {}",
+                    activity_style(
+                        format!("updated {}", relative_time::format(ms, now)),
+                        ms,
+                        now
+                    )
+                )
+            })
+            .unwrap_or_default();
+        let touched = listed
+            .last_touched_ms
+            .map(|ms| {
+                format!(
+                    " {}",
+                    activity_style(
+                        format!("touched {}", relative_time::format(ms, now)),
+                        ms,
+                        now
+                    )
+                )
+            })
             .unwrap_or_default();
 
         println!();
         if is_current {
             println!(
-                "{}{}{}{}{}",
+                "{}{}{}{}{}{}{}{}",
                 style(marker).cyan().bold(),
                 style(stack_name).cyan().bold(),
                 style(wt_indicator).yellow(),
                 style(&commit_info).dim(),
-                behind_indicator
+                behind_indicator,
+                style(&scope_info).dim(),
+                touched,
+                last_activity
             );
         } else {
             println!(
-                "{}{}{}{}{}",
+                "{}{}{}{}{}{}{}{}",
                 marker,
                 stack_name,
                 style(wt_indicator).yellow(),
                 style(&commit_info).dim(),
-                behind_indicator
+                behind_indicator,
+                style(&scope_info).dim(),
+                touched,
+                last_activity
             );
         }
+        if let Some(description) = stack::stack_description(repo, config, stack_name) {
+            for line in description.lines() {
+                println!("    {}", style(line).dim().italic());
+            }
+        }
 
-        if let Ok(ref commits) = commits {
+        if let Ok(commits) = commits {
             let total = commits.len();
-            for (i, (sha, title)) in commits.iter().enumerate() {
+            for (i, commit) in commits.iter().enumerate() {
+                let (sha, title) = (&commit.sha, &commit.title);
+                let activity = commit_activity(commit, provider.as_ref(), now);
                 let is_last = i == total - 1;
-                let branch_char = if is_last { "└──" } else { "├──" };
+                let branch_char = if is_last {
+                    plain::tree_last()
+                } else {
+                    plain::tree_mid()
+                };
                 let position = i + 1;
 
                 if is_current {
                     println!(
-                        "    {} {} {} {}",
+                        "    {} {} {} {}{}",
                         style(branch_char).dim(),
                         style(format!("[{}]", position)).dim(),
                         style(sha).yellow(),
-                        title
+                        title,
+                        activity
                     );
                 } else {
                     println!(
-                        "    {} {} {} {}",
+                        "    {} {} {} {}{}",
                         style(branch_char).dim(),
                         style(format!("[{}]", position)).dim(),
                         style(sha).yellow().dim(),
-                        style(title).dim()
+                        style(title).dim(),
+                        activity
                     );
                 }
             }
@@ -235,17 +577,147 @@ fn list_all_stacks(repo: &git2::Repository, config: &Config, json: bool) -> Resu
     Ok(())
 }
 
+/// A commit of a stack, as shown by the all-stacks view
+#[derive(Debug, Default)]
+struct StackCommitInfo {
+    sha: String,
+    title: String,
+    gg_id: Option<String>,
+    /// `Name <email>` of the commit author
+    author: String,
+    pr_number: Option<u64>,
+    /// Last time the entry branch moved on origin (push or fetch)
+    pushed_at_ms: Option<u64>,
+    /// Last activity on the entry's PR/MR (only with `--refresh`)
+    pr_updated_at_ms: Option<u64>,
+}
+
+impl StackCommitInfo {
+    fn last_activity_ms(&self) -> Option<u64> {
+        self.pushed_at_ms.max(self.pr_updated_at_ms)
+    }
+}
+
+/// States of each stack's PRs/MRs, in the order of `stacks`, for
+/// `--filter state:<state>`.
+fn stack_pr_states(
+    repo: &git2::Repository,
+    config: &Config,
+    stacks: &[ListedStack],
+) -> Result<Vec<Vec<PrState>>> {
+    let mut states = vec![Vec::new(); stacks.len()];
+    // PRs/MRs live behind each stack's remote: look them up one remote at a
+    // time
+    let remotes: BTreeSet<&str> = stacks
+        .iter()
+        .map(|stack| config.get_remote_for_stack(&stack.name))
+        .collect();
+    for remote in remotes {
+        let provider = Provider::for_remote(repo, remote)?;
+        let numbers: Vec<(usize, u64)> = stacks
+            .iter()
+            .enumerate()
+            .filter(|(_, stack)| config.get_remote_for_stack(&stack.name) == remote)
+            .filter_map(|(i, stack)| stack.commits.as_ref().ok().map(|c| (i, c)))
+            .flat_map(|(i, commits)| {
+                commits
+                    .iter()
+                    .filter_map(move |c| c.pr_number.map(|n| (i, n)))
+            })
+            .collect();
+        let fetched = parallel::map(&numbers, config.get_sync_jobs(), |(_, number)| {
+            provider.get_pr_info(*number).ok().map(|info| info.state)
+        });
+        for ((i, _), state) in numbers.iter().zip(fetched) {
+            if let Some(state) = state {
+                states[*i].push(state);
+            }
+        }
+    }
+    Ok(states)
+}
+
+/// Fill in push times and, when a provider is given, PR/MR activity.
+fn fill_activity(
+    repo: &git2::Repository,
+    config: &Config,
+    provider: Option<&Provider>,
+    username: &str,
+    stack_name: &str,
+    commits: &mut [StackCommitInfo],
+) {
+    let remote = config.get_remote_for_stack(stack_name);
+    if provider.is_some() {
+        git::set_provider_remote(remote);
+    }
+    for commit in commits {
+        let Some(gg_id) = commit.gg_id.as_deref() else {
+            continue;
+        };
+        let entry_branch = git::format_entry_branch(username, stack_name, gg_id);
+        commit.pushed_at_ms = last_push_ms(repo, remote, &entry_branch);
+        commit.pr_number = config.get_mr_for_entry(stack_name, gg_id);
+        if let (Some(provider), Some(number)) = (provider, commit.pr_number) {
+            commit.pr_updated_at_ms = provider
+                .get_pr_info(number)
+                .ok()
+                .and_then(|info| info.updated_at_ms);
+        }
+    }
+}
+
+/// When `<remote>/<branch>` last moved, from its reflog.
+fn last_push_ms(repo: &git2::Repository, remote: &str, branch: &str) -> Option<u64> {
+    let reflog = repo
+        .reflog(&format!("refs/remotes/{}/{}", remote, branch))
+        .ok()?;
+    let seconds = reflog.get(0)?.committer().when().seconds();
+    u64::try_from(seconds).ok().map(|s| s * 1000)
+}
+
+/// Dim recent activity, highlight anything older than [`STALE_AFTER_MS`].
+fn activity_style(text: String, at_ms: u64, now_ms: u64) -> console::StyledObject<String> {
+    if now_ms.saturating_sub(at_ms) > STALE_AFTER_MS {
+        style(text).yellow()
+    } else {
+        style(text).dim()
+    }
+}
+
+/// Render "  (pushed 3h ago, #42 updated 20m ago)" for an entry line.
+fn commit_activity(commit: &StackCommitInfo, provider: Option<&Provider>, now_ms: u64) -> String {
+    let mut parts = Vec::new();
+    if let Some(ms) = commit.pushed_at_ms {
+        parts.push(format!("pushed {}", relative_time::format(ms, now_ms)));
+    }
+    if let (Some(ms), Some(number)) = (commit.pr_updated_at_ms, commit.pr_number) {
+        let prefix = provider.map(Provider::pr_number_prefix).unwrap_or("#");
+        parts.push(format!(
+            "{}{} updated {}",
+            prefix,
+            number,
+            relative_time::format(ms, now_ms)
+        ));
+    }
+    match commit.last_activity_ms() {
+        Some(ms) if !parts.is_empty() => format!(
+            "  {}",
+            activity_style(format!("({})", parts.join(", ")), ms, now_ms)
+        ),
+        _ => String::new(),
+    }
+}
+
 fn get_stack_commits_info(
     repo: &git2::Repository,
+    remote: &str,
     branch: &str,
     base: &str,
-) -> Result<Vec<(String, String)>> {
+) -> Result<Vec<StackCommitInfo>> {
     use git2::Sort;
 
     let head = repo.revparse_single(branch)?;
-    let base_ref = repo
-        .revparse_single(base)
-        .or_else(|_| repo.revparse_single(&format!("origin/{}", base)))?;
+    let base_ref = git::resolve_base(repo, remote, base)?;
 
     let mut revwalk = repo.revwalk()?;
     revwalk.set_sorting(Sort::TOPOLOGICAL | Sort::REVERSE)?;
@@ -263,7 +735,17 @@ fn get_stack_commits_info(
             .flatten()
             .unwrap_or("<no message>")
             .to_string();
-        commits.push((sha, title));
+        commits.push(StackCommitInfo {
+            sha,
+            title,
+            gg_id: git::get_gg_id(&commit),
+            author: format!(
+                "{} <{}>",
+                commit.author().name().unwrap_or_default(),
+                commit.author().email().unwrap_or_default()
+            ),
+            ..Default::default()
+        });
     }
 
     Ok(commits)
@@ -324,9 +806,11 @@ fn list_remote_stacks(repo: &git2::Repository, config: &Config, json: bool) -> R
         let stacks = remote_stacks
             .iter()
             .map(|stack_name| {
-                let remote_branch = format!("origin/{}/{}", username, stack_name);
+                let remote_branch =
+                    format!("origin/{}", git::format_stack_branch(&username, stack_name));
                 let commit_count =
-                    count_stack_commits(repo, &remote_branch, &base_branch).unwrap_or(0);
+                    count_stack_commits(repo, git::DEFAULT_REMOTE, &remote_branch, &base_branch)
+                        .unwrap_or(0);
 
                 let mut pr_numbers = config
                     .get_stack(stack_name)
@@ -436,6 +920,456 @@ fn list_remote_stacks(repo: &git2::Repository, config: &Config, json: bool) -> R
     Ok(())
 }
 
+/// Pushed branches of one remote stack: the stack branch tip (if pushed) and
+/// its entry branches keyed by entry id.
+#[derive(Debug, Default)]
+struct RemoteStackBranches {
+    stack_tip: Option<git2::Oid>,
+    entries: Vec<(String, git2::Oid)>,
+}
+
+/// Group `<remote>/<user>/...` branches by stack name.
+fn collect_user_remote_stacks(
+    repo: &git2::Repository,
+    remote: &str,
+    user: &str,
+) -> Result<BTreeMap<String, RemoteStackBranches>> {
+    let prefix = format!("{}/", remote);
+    let mut stacks: BTreeMap<String, RemoteStackBranches> = BTreeMap::new();
+    for branch_result in repo.branches(Some(git2::BranchType::Remote))? {
+        let (branch, _) = branch_result?;
+        let Some(oid) = branch.get().target() else {
+            continue;
+        };
+        let Some(name) = branch.name()? else {
+            continue;
+        };
+        let Some(branch_name) = name.strip_prefix(&prefix) else {
+            continue;
+        };
+        if let Some((branch_user, stack_name, entry_id)) = git::parse_entry_branch(branch_name) {
+            if branch_user == user {
+                stacks
+                    .entry(stack_name)
+                    .or_default()
+                    .entries
+                    .push((entry_id, oid));
+            }
+        } else if let Some((branch_user, stack_name)) = git::parse_stack_branch(branch_name) {
+            if branch_user == user {
+                stacks.entry(stack_name).or_default().stack_tip = Some(oid);
+            }
+        }
+    }
+    Ok(stacks)
+}
+
+/// Rebuild a read-only [`Stack`] from another user's pushed branches. The tip
+/// is the stack branch when pushed, otherwise the entry branch furthest from
+/// the base (sync only pushes entry branches).
+fn build_remote_user_stack(
+    repo: &git2::Repository,
+    remote: &str,
+    user: &str,
+    stack_name: &str,
+    branches: &RemoteStackBranches,
+    base: &str,
+) -> Result<Stack> {
+    let base_oid = repo
+        .revparse_single(&format!("{}/{}", remote, base))
+        .or_else(|_| repo.revparse_single(base))?
+        .id();
+
+    let ahead = |oid: git2::Oid| -> usize {
+        let Ok(mut revwalk) = repo.revwalk() else {
+            return 0;
+        };
+        if revwalk.push(oid).is_err() || revwalk.hide(base_oid).is_err() {
+            return 0;
+        }
+        revwalk.count()
+    };
+    let tip = branches.stack_tip.or_else(|| {
+        branches
+            .entries
+            .iter()
+            .max_by_key(|(_, oid)| ahead(*oid))
+            .map(|(_, oid)| *oid)
+    });
+
+    let mut entries = Vec::new();
+    if let Some(tip) = tip {
+        let mut revwalk = repo.revwalk()?;
+        revwalk.set_sorting(git2::Sort::TOPOLOGICAL | git2::Sort::REVERSE)?;
+        revwalk.push(tip)?;
+        revwalk.hide(base_oid)?;
+        for (i, oid) in revwalk.enumerate() {
+            let commit = repo.find_commit(oid?)?;
+            entries.push(StackEntry::from_commit(&commit, i + 1));
+        }
+    }
+
+    Ok(Stack {
+        name: stack_name.to_string(),
+        username: user.to_string(),
+        base: base.to_string(),
+        entries,
+        current_position: None,
+    })
+}
+
+/// Look up PR/MR numbers for entries whose branch was pushed (up to `jobs`
+/// at once), then refresh their state. Best-effort: a failed lookup leaves
+/// the entry without a PR/MR and is returned as a warning.
+fn attach_remote_user_prs(
+    stack: &mut Stack,
+    branches: &RemoteStackBranches,
+    provider: &Provider,
+    jobs: usize,
+) -> Vec<String> {
+    let pushed: Vec<(usize, String)> = stack
+        .entries
+        .iter()
+        .enumerate()
+        .filter_map(|(i, entry)| {
+            let gg_id = entry.gg_id.as_deref()?;
+            branches.entries.iter().any(|(id, _)| id == gg_id).then(|| {
+                (
+                    i,
+                    git::format_entry_branch(&stack.username, &stack.name, gg_id),
+                )
+            })
+        })
+        .collect();
+    let found = parallel::map(&pushed, jobs, |(_, branch)| {
+        provider.list_prs_for_branch(branch)
+    });
+
+    let mut warnings = Vec::new();
+    for ((i, branch), prs) in pushed.iter().zip(found) {
+        match prs {
+            Ok(prs) => stack.entries[*i].mr_number = prs.first().copied(),
+            Err(e) => warnings.push(format!(
+                "Could not look up the {} of {}: {}",
+                provider.pr_label(),
+                branch,
+                e
+            )),
+        }
+    }
+    stack.refresh_pr_details(provider, jobs, false);
+    warnings
+}
+
+/// Remote holding pushed stacks: the current stack's, else `origin`.
+fn listing_remote(repo: &git2::Repository, config: &Config) -> String {
+    stack::read_active_stack(repo.path())
+        .or_else(|| git::current_branch_name(repo))
+        .as_deref()
+        .and_then(git::parse_stack_branch)
+        .map(|(_, name)| config.get_remote_for_stack(&name).to_string())
+        .unwrap_or_else(|| git::DEFAULT_REMOTE.to_string())
+}
+
+/// `gg ls --remote --user <name>`: read-only view of another user's pushed
+/// stacks, rendered like `gg ls` without checking anything out.
+pub fn run_remote_for_user(user: &str, json: bool) -> Result<()> {
+    let repo = git::open_repo()?;
+    let config = Config::load(repo.commondir())?;
+    git::validate_branch_username(user)?;
+    let remote = listing_remote(&repo, &config);
+
+    if !json {
+        println!("{}", style(format!("Fetching from {}...", remote)).dim());
+    }
+    let mut warnings = Vec::new();
+    if let Err(e) = git::fetch_and_prune(&remote) {
+        warnings.push(format!("{} (showing the branches fetched before)", e));
+    }
+
+    let base = git::find_base_branch(&repo).unwrap_or_else(|_| "main".to_string());
+    let provider = Provider::for_remote(&repo, &remote).ok();
+
+    let mut stacks = Vec::new();
+    for (stack_name, branches) in collect_user_remote_stacks(&repo, &remote, user)? {
+        let mut stack =
+            build_remote_user_stack(&repo, &remote, user, &stack_name, &branches, &base)?;
+        if let Some(provider) = &provider {
+            warnings.extend(attach_remote_user_prs(
+                &mut stack,
+                &branches,
+                provider,
+                config.get_sync_jobs(),
+            ));
+        }
+        stacks.push(stack);
+    }
+
+    if json {
+        print_json(&RemoteUserStacksResponse {
+            version: OUTPUT_VERSION,
+            user: user.to_string(),
+            stacks: stacks
+                .iter()
+                .map(|stack| StackJson {
+                    name: stack.name.clone(),
+                    base: stack.base.clone(),
+                    total_commits: stack.len(),
+                    synced_commits: stack.synced_count(),
+                    current_position: None,
+                    behind_base: None,
+                    scope: vec![],
+                    description: None,
+                    entries: stack
+                        .entries
+                        .iter()
+                        .map(|entry| entry_json(entry, false))
+                        .collect(),
+                    unintegrated_commits: vec![],
+                })
+                .collect(),
+            warnings,
+        });
+        return Ok(());
+    }
+
+    for warning in &warnings {
+        println!("{} {}", style("Warning:").yellow(), warning);
+    }
+    if stacks.is_empty() {
+        println!(
+            "{}",
+            style(format!("No remote stacks found for user '{}'.", user)).dim()
+        );
+        return Ok(());
+    }
+
+    let pr_prefix = provider
+        .as_ref()
+        .map(|p| p.pr_number_prefix())
+        .unwrap_or("!");
+    println!("{}", style(format!("Remote stacks for {}:", user)).bold());
+    for stack in &stacks {
+        println!();
+        println!(
+            "{} ({} commits, {} synced)",
+            style(git::format_stack_branch(user, &stack.name))
+                .cyan()
+                .bold(),
+            stack.len(),
+            stack.synced_count()
+        );
+        for entry in &stack.entries {
+            print_entry(entry, pr_prefix, false, false);
+        }
+    }
+    println!();
+    println!(
+        "{}",
+        style("Read-only view: nothing was checked out.").dim()
+    );
+
+    Ok(())
+}
+
+/// Run `gg ls --global`: the stacks of every repository registered with
+/// `gg setup --register`, with the state of their PRs/MRs.
+pub fn run_global(json: bool) -> Result<()> {
+    let registry = Registry::load()?;
+    if registry.repos.is_empty() && !json {
+        println!(
+            "{}",
+            style("No repositories registered. Run `gg setup --register` in each repository.")
+                .dim()
+        );
+        return Ok(());
+    }
+
+    // Provider CLIs act on the repository of the current directory, so
+    // each repository is listed from inside it.
+    let cwd = std::env::current_dir()?;
+    let repos: Vec<GlobalRepoJson> = registry
+        .repos
+        .iter()
+        .map(|registered| {
+            let listed = std::env::set_current_dir(&registered.path)
+                .map_err(Into::into)
+                .and_then(|_| global_repo_stacks(&registered.path));
+            let path = registered.path.display().to_string();
+            match listed {
+                Ok((current_stack, stacks)) => GlobalRepoJson {
+                    path,
+                    error: None,
+                    current_stack,
+                    stacks,
+                },
+                Err(e) => GlobalRepoJson {
+                    path,
+                    error: Some(e.to_string()),
+                    current_stack: None,
+                    stacks: Vec::new(),
+                },
+            }
+        })
+        .collect();
+    std::env::set_current_dir(cwd)?;
+
+    if json {
+        print_json(&GlobalStacksResponse {
+            version: OUTPUT_VERSION,
+            repos,
+        });
+        return Ok(());
+    }
+
+    let home = dirs::home_dir();
+    for repo in &repos {
+        let path = match home
+            .as_deref()
+            .and_then(|h| Path::new(&repo.path).strip_prefix(h).ok())
+        {
+            Some(rest) => format!("~/{}", rest.display()),
+            None => repo.path.clone(),
+        };
+        println!();
+        if let Some(error) = &repo.error {
+            println!(
+                "{} {}",
+                style(path).bold(),
+                style(format!("{} {}", plain::fail(), error)).red()
+            );
+            continue;
+        }
+        println!(
+            "{} {}",
+            style(path).bold(),
+            style(format!(
+                "({} stack{})",
+                repo.stacks.len(),
+                if repo.stacks.len() == 1 { "" } else { "s" }
+            ))
+            .dim()
+        );
+        for stack in &repo.stacks {
+            let is_current = repo.current_stack.as_deref() == Some(stack.name.as_str());
+            let marker = if is_current {
+                plain::sym("→ ", "> ")
+            } else {
+                "  "
+            };
+            let prs = if stack.prs.is_empty() {
+                style("no PRs/MRs".to_string()).dim().to_string()
+            } else {
+                stack
+                    .prs
+                    .iter()
+                    .map(|pr| {
+                        let label = format!(
+                            "#{} {}",
+                            pr.number,
+                            pr.state.as_deref().unwrap_or("unknown")
+                        );
+                        match pr.state.as_deref() {
+                            Some("merged") => style(label).green(),
+                            Some("closed") => style(label).red(),
+                            Some("open") => style(label).yellow(),
+                            _ => style(label).dim(),
+                        }
+                        .to_string()
+                    })
+                    .collect::<Vec<_>>()
+                    .join(", ")
+            };
+            println!(
+                "  {}{} {}  {}",
+                marker,
+                if is_current {
+                    style(&stack.name).cyan().bold()
+                } else {
+                    style(&stack.name)
+                },
+                style(format!(
+                    "({} commit{})",
+                    stack.commit_count,
+                    if stack.commit_count == 1 { "" } else { "s" }
+                ))
+                .dim(),
+                prs
+            );
+        }
+    }
+    println!();
+    Ok(())
+}
+
+/// The current stack and all stacks of the repository at `path`, with the
+/// state of each PR/MR.
+fn global_repo_stacks(path: &Path) -> Result<(Option<String>, Vec<GlobalStackJson>)> {
+    let repo = git2::Repository::open(path)?;
+    let config = Config::load(repo.commondir())?;
+    let provider = Provider::detect(&repo).ok();
+    let username = config
+        .defaults
+        .branch_username
+        .clone()
+        .or_else(|| provider.and_then(|p| p.whoami().ok()))
+        .unwrap_or_else(|| "unknown".to_string());
+    let base_branch = git::find_base_branch(&repo).unwrap_or_else(|_| "main".to_string());
+    let current_stack = stack::read_active_stack(repo.path())
+        .or_else(|| git::current_branch_name(&repo))
+        .as_deref()
+        .and_then(git::parse_stack_branch)
+        .map(|(_, name)| name);
+
+    let mut stacks = Vec::new();
+    for name in stack::list_all_stacks(&repo, &config, &username)? {
+        let base = config
+            .get_base_for_stack(&name)
+            .unwrap_or(base_branch.as_str())
+            .to_string();
+        let commits = get_stack_commits_info(
+            &repo,
+            config.get_remote_for_stack(&name),
+            &git::format_stack_branch(&username, &name),
+            &base,
+        )
+        .unwrap_or_default();
+        let prs = commits
+            .iter()
+            .filter_map(|c| config.get_mr_for_entry(&name, c.gg_id.as_deref()?))
+            .map(|number| GlobalPrJson {
+                number,
+                state: None,
+            })
+            .collect();
+        stacks.push(GlobalStackJson {
+            name,
+            base,
+            commit_count: commits.len(),
+            prs,
+        });
+    }
+
+    if let Some(provider) = provider {
+        let numbers: Vec<(usize, usize)> = stacks
+            .iter()
+            .enumerate()
+            .flat_map(|(i, stack)| (0..stack.prs.len()).map(move |j| (i, j)))
+            .collect();
+        let states = parallel::map(&numbers, config.get_sync_jobs(), |(i, j)| {
+            provider
+                .get_pr_info(stacks[*i].prs[*j].number)
+                .ok()
+                .map(|info| pr_state_to_json(&info.state))
+        });
+        for ((i, j), state) in numbers.into_iter().zip(states) {
+            stacks[i].prs[j].state = state;
+        }
+    }
+
+    Ok((current_stack, stacks))
+}
+
 fn print_remote_stack_line(
     repo: &git2::Repository,
     config: &Config,
@@ -444,10 +1378,10 @@ fn print_remote_stack_line(
     provider: Option<&Provider>,
     is_merged: bool,
 ) {
-    let remote_branch = format!("origin/{}/{}", username, stack_name);
+    let remote_branch = format!("origin/{}", git::format_stack_branch(username, stack_name));
 
     let commit_info = if let Ok(base) = git::find_base_branch(repo) {
-        if let Ok(count) = count_stack_commits(repo, &remote_branch, &base) {
+        if let Ok(count) = count_stack_commits(repo, git::DEFAULT_REMOTE, &remote_branch, &base) {
             format!(" ({} commits)", count)
         } else {
             String::new()
@@ -478,7 +1412,7 @@ fn print_remote_stack_line(
     if is_merged {
         println!(
             "  {} {}{}{}",
-            style("✓").green(),
+            style(plain::ok()).green(),
             style(stack_name).dim(),
             style(&commit_info).dim(),
             style(&mr_info).dim()
@@ -486,7 +1420,7 @@ fn print_remote_stack_line(
     } else {
         println!(
             "  {} {}{}{}",
-            style("○").dim(),
+            style(plain::pending()).dim(),
             style(stack_name).cyan(),
             style(&commit_info).dim(),
             style(&mr_info).blue()
@@ -494,11 +1428,14 @@ fn print_remote_stack_line(
     }
 }
 
-fn count_stack_commits(repo: &git2::Repository, branch: &str, base: &str) -> Result<usize> {
+fn count_stack_commits(
+    repo: &git2::Repository,
+    remote: &str,
+    branch: &str,
+    base: &str,
+) -> Result<usize> {
     let head = repo.revparse_single(branch)?;
-    let base_ref = repo
-        .revparse_single(base)
-        .or_else(|_| repo.revparse_single(&format!("origin/{}", base)))?;
+    let base_ref = git::resolve_base(repo, remote, base)?;
 
     let mut revwalk = repo.revwalk()?;
     revwalk.push(head.id())?;
@@ -507,9 +1444,15 @@ fn count_stack_commits(repo: &git2::Repository, branch: &str, base: &str) -> Res
     Ok(revwalk.count())
 }
 
-fn behind_count(repo: &git2::Repository, base_branch: &str) -> Option<usize> {
+/// How far the local `base_branch` is behind `<remote>/<base_branch>`
+pub(crate) fn behind_count(
+    repo: &git2::Repository,
+    remote: &str,
+    base_branch: &str,
+) -> Option<usize> {
     let behind =
-        git::count_commits_behind(repo, base_branch, &format!("origin/{}", base_branch)).ok()?;
+        git::count_commits_behind(repo, base_branch, &format!("{}/{}", remote, base_branch))
+            .ok()?;
     if behind > 0 {
         Some(behind)
     } else {
@@ -517,12 +1460,15 @@ fn behind_count(repo: &git2::Repository, base_branch: &str) -> Option<usize> {
     }
 }
 
-fn behind_indicator(repo: &git2::Repository, base_branch: &str) -> Option<String> {
-    behind_count(repo, base_branch).map(|behind| format!("↓{}", behind))
+fn behind_indicator(repo: &git2::Repository, remote: &str, base_branch: &str) -> Option<String> {
+    behind_count(repo, remote, base_branch)
+        .map(|behind| format!("{}{}", plain::sym("↓", "behind "), behind))
 }
 
 /// Show detailed stack view
-fn show_stack(stack: &Stack, json: bool) -> Result<()> {
+fn show_stack(stack: &Stack, config: &Config, prs: bool, json: bool) -> Result<()> {
+    let scope = config.get_scope_for_stack(&stack.name);
+    let remote = config.get_remote_for_stack(&stack.name);
     let synced = stack.synced_count();
     let total = stack.len();
 
@@ -546,20 +1492,7 @@ fn show_stack(stack: &Stack, json: bool) -> Result<()> {
                     && (entry.position == current_pos + 1
                         || (stack.current_position.is_none() && entry.position == stack.len()));
 
-                StackEntryJson {
-                    position: entry.position,
-                    sha: entry.short_sha.clone(),
-                    title: entry.title.clone(),
-                    gg_id: entry.gg_id.clone(),
-                    gg_parent: entry.gg_parent.clone(),
-                    pr_number: entry.mr_number,
-                    pr_state: entry.mr_state.as_ref().map(pr_state_to_json),
-                    approved: entry.approved,
-                    ci_status: entry.ci_status.as_ref().map(ci_status_to_json),
-                    is_current,
-                    in_merge_train: entry.in_merge_train,
-                    merge_train_position: entry.merge_train_position,
-                }
+                entry_json(entry, is_current)
             })
             .collect();
 
@@ -572,7 +1505,9 @@ fn show_stack(stack: &Stack, json: bool) -> Result<()> {
                 total_commits: total,
                 synced_commits: synced,
                 current_position: stack.current_position.map(|p| p + 1),
-                behind_base: behind_count(&repo, &stack.base),
+                behind_base: behind_count(&repo, remote, &stack.base),
+                scope: scope.to_vec(),
+                description: stack::stack_description(&repo, config, &stack.name),
                 entries,
                 unintegrated_commits: unintegrated
                     .iter()
@@ -589,7 +1524,7 @@ fn show_stack(stack: &Stack, json: bool) -> Result<()> {
         return Ok(());
     }
 
-    let behind = behind_indicator(&repo, &stack.base)
+    let behind = behind_indicator(&repo, remote, &stack.base)
         .map(|s| format!(" {}", style(s).yellow()))
         .unwrap_or_default();
 
@@ -600,12 +1535,18 @@ fn show_stack(stack: &Stack, json: bool) -> Result<()> {
         synced,
         behind
     );
+    if !scope.is_empty() {
+        println!("{}", style(format!("scope: {}", scope.join(", "))).dim());
+    }
+    if let Some(description) = stack::stack_description(&repo, config, &stack.name) {
+        println!("{}", style(description).dim());
+    }
     println!();
 
     if git::is_rebase_in_progress(&repo) {
         println!(
             "{} {}",
-            style("⚠️").yellow(),
+            style(plain::warn()).yellow(),
             style("Rebase in progress. Run `gg continue` or `gg abort`")
                 .yellow()
                 .bold()
@@ -638,74 +1579,7 @@ fn show_stack(stack: &Stack, json: bool) -> Result<()> {
             && (entry.position == current_pos + 1
                 || (stack.current_position.is_none() && entry.position == stack.len()));
 
-        let position = format!("[{}]", entry.position);
-        let sha = &entry.short_sha;
-        let title = &entry.title;
-
-        let status = entry.status_display();
-        let status_styled = match &entry.mr_state {
-            Some(PrState::Merged) => style(&status).green(),
-            Some(PrState::Closed) => style(&status).red(),
-            Some(PrState::Draft) => style(&status).dim(),
-            Some(PrState::Open) if entry.approved => style(&status).green(),
-            Some(PrState::Open) => style(&status).yellow(),
-            None => style(&status).dim(),
-        };
-
-        let ci = match &entry.ci_status {
-            Some(CiStatus::Success) => style("✓").green().to_string(),
-            Some(CiStatus::Failed) => style("✗").red().to_string(),
-            Some(CiStatus::Running) => style("●").yellow().to_string(),
-            Some(CiStatus::Pending) => style("○").dim().to_string(),
-            _ => String::new(),
-        };
-
-        let train = if entry.in_merge_train { " 🚂" } else { "" };
-        let gg_id = entry.gg_id.as_deref().unwrap_or("-");
-        let mr_display = entry
-            .mr_number
-            .map(|n| format!("{}{}", pr_prefix, n))
-            .unwrap_or_default();
-        let head_marker = if is_current { " <- HEAD" } else { "" };
-
-        if is_current {
-            println!(
-                "  {} {} {} {} {}{} (id: {}){}",
-                style(&position).bold(),
-                style(sha).yellow().bold(),
-                style(title).bold(),
-                status_styled,
-                ci,
-                train,
-                style(gg_id).dim(),
-                style(head_marker).cyan().bold()
-            );
-        } else {
-            println!(
-                "  {} {} {} {} {}{} (id: {})",
-                style(&position).dim(),
-                style(sha).yellow(),
-                title,
-                status_styled,
-                ci,
-                train,
-                style(gg_id).dim()
-            );
-        }
-
-        if !mr_display.is_empty() {
-            let mut mr_line = mr_display.clone();
-
-            if entry.in_merge_train {
-                if let Some(pos) = entry.merge_train_position {
-                    mr_line.push_str(&format!(" [train pos {}]", pos));
-                } else {
-                    mr_line.push_str(" [train]");
-                }
-            }
-
-            println!("      {}", style(&mr_line).blue());
-        }
+        print_entry(entry, pr_prefix, is_current, prs);
     }
 
     if let Some(u) = &unintegrated {
@@ -717,7 +1591,7 @@ fn show_stack(stack: &Stack, json: bool) -> Result<()> {
         };
         println!(
             "  {} Un-integrated commit at HEAD (detached):",
-            style("⚠").yellow().bold()
+            style(plain::warn()).yellow().bold()
         );
         println!(
             "      {} {}{}  — sits on top of [{}]",
@@ -737,7 +1611,169 @@ fn show_stack(stack: &Stack, json: bool) -> Result<()> {
     Ok(())
 }
 
-fn pr_state_to_json(state: &PrState) -> String {
+pub(crate) fn entry_json(entry: &StackEntry, is_current: bool) -> StackEntryJson {
+    StackEntryJson {
+        position: entry.position,
+        sha: entry.short_sha.clone(),
+        title: entry.title.clone(),
+        gg_id: entry.gg_id.clone(),
+        gg_parent: entry.gg_parent.clone(),
+        pr_number: entry.mr_number,
+        pr_state: entry.mr_state.as_ref().map(pr_state_to_json),
+        approved: entry.approved,
+        changes_requested: entry.changes_requested,
+        ci_status: entry.ci_status.as_ref().map(ci_status_to_json),
+        is_current,
+        in_merge_train: entry.in_merge_train,
+        merge_train_position: entry.merge_train_position,
+        unresolved_threads: entry.unresolved_threads,
+        annotations: entry.annotations.clone(),
+        signed: entry.signed,
+    }
+}
+
+fn print_entry(entry: &StackEntry, pr_prefix: &str, is_current: bool, prs: bool) {
+    let position = format!("[{}]", entry.position);
+    let sha = &entry.short_sha;
+    let title = &entry.title;
+
+    let status = entry.status_display();
+    let status_styled = match &entry.mr_state {
+        Some(PrState::Merged) => style(&status).green(),
+        Some(PrState::Closed) => style(&status).red(),
+        Some(PrState::Draft) => style(&status).dim(),
+        Some(PrState::Open) if entry.approved => style(&status).green(),
+        Some(PrState::Open) => style(&status).yellow(),
+        None => style(&status).dim(),
+    };
+
+    let ci = match &entry.ci_status {
+        Some(CiStatus::Success) => style(plain::ok()).green().to_string(),
+        Some(CiStatus::Failed) => style(plain::fail()).red().to_string(),
+        Some(CiStatus::Running) => style(plain::running()).yellow().to_string(),
+        Some(CiStatus::Pending) => style(plain::pending()).dim().to_string(),
+        _ => String::new(),
+    };
+
+    let train = if entry.in_merge_train {
+        plain::sym(" 🚂", " [train]")
+    } else {
+        ""
+    };
+    let signed = if entry.signed {
+        plain::sym(" 🔏", " [signed]")
+    } else {
+        ""
+    };
+    let gg_id = entry.gg_id.as_deref().unwrap_or("-");
+    let mr_display = entry
+        .mr_number
+        .map(|n| format!("{}{}", pr_prefix, n))
+        .unwrap_or_default();
+    let head_marker = if is_current { " <- HEAD" } else { "" };
+
+    if is_current {
+        println!(
+            "  {} {} {} {} {}{}{} (id: {}){}",
+            style(&position).bold(),
+            style(sha).yellow().bold(),
+            style(title).bold(),
+            status_styled,
+            ci,
+            train,
+            style(signed).dim(),
+            style(gg_id).dim(),
+            style(head_marker).cyan().bold()
+        );
+    } else {
+        println!(
+            "  {} {} {} {} {}{}{} (id: {})",
+            style(&position).dim(),
+            style(sha).yellow(),
+            title,
+            status_styled,
+            ci,
+            train,
+            style(signed).dim(),
+            style(gg_id).dim()
+        );
+    }
+
+    if !mr_display.is_empty() {
+        let mut mr_line = mr_display.clone();
+
+        if entry.in_merge_train {
+            if let Some(pos) = entry.merge_train_position {
+                mr_line.push_str(&format!(" [train pos {}]", pos));
+            } else {
+                mr_line.push_str(" [train]");
+            }
+        }
+
+        if prs {
+            println!(
+                "      {}  {}",
+                style(&mr_line).blue(),
+                pr_details(entry).join(", ")
+            );
+        } else {
+            println!("      {}", style(&mr_line).blue());
+        }
+    }
+
+    if !entry.annotations.is_empty() {
+        println!(
+            "      {}",
+            style(notes::render(&entry.annotations, "  ")).magenta()
+        );
+    }
+}
+
+/// Review, CI and thread state of an entry's PR/MR for `gg ls --prs`.
+fn pr_details(entry: &StackEntry) -> Vec<String> {
+    let mut details = Vec::new();
+    let review = match &entry.mr_state {
+        Some(PrState::Open) | Some(PrState::Draft) if entry.changes_requested => {
+            Some(style("changes requested".to_string()).red())
+        }
+        Some(PrState::Open) | Some(PrState::Draft) if entry.approved => {
+            Some(style("approved".to_string()).green())
+        }
+        Some(PrState::Open) | Some(PrState::Draft) => {
+            Some(style("review pending".to_string()).yellow())
+        }
+        _ => None,
+    };
+    if let Some(review) = review {
+        details.push(review.to_string());
+    }
+    let ci = match &entry.ci_status {
+        Some(CiStatus::Success) => Some(style("CI passed".to_string()).green()),
+        Some(CiStatus::Failed) => Some(style("CI failed".to_string()).red()),
+        Some(CiStatus::Running) => Some(style("CI running".to_string()).yellow()),
+        Some(CiStatus::Pending) => Some(style("CI pending".to_string()).dim()),
+        Some(CiStatus::Canceled) => Some(style("CI canceled".to_string()).dim()),
+        Some(CiStatus::Unknown) | None => None,
+    };
+    if let Some(ci) = ci {
+        details.push(ci.to_string());
+    }
+    match entry.unresolved_threads {
+        Some(0) | None => {}
+        Some(n) => details.push(
+            style(format!(
+                "{} unresolved thread{}",
+                n,
+                if n == 1 { "" } else { "s" }
+            ))
+            .yellow()
+            .to_string(),
+        ),
+    }
+    details
+}
+
+pub(crate) fn pr_state_to_json(state: &PrState) -> String {
     match state {
         PrState::Open => "open".to_string(),
         PrState::Merged => "merged".to_string(),
@@ -746,7 +1782,7 @@ fn pr_state_to_json(state: &PrState) -> String {
     }
 }
 
-fn ci_status_to_json(status: &CiStatus) -> String {
+pub(crate) fn ci_status_to_json(status: &CiStatus) -> String {
     match status {
         CiStatus::Pending => "pending".to_string(),
         CiStatus::Running => "running".to_string(),
@@ -780,6 +1816,105 @@ mod tests {
         assert!(!should_refresh_mr_info(false, false));
     }
 
+    #[test]
+    fn parse_filter_understands_prefixes() {
+        use super::{parse_filter, StackFilter};
+        use crate::provider::PrState;
+
+        assert_eq!(
+            parse_filter("billing"),
+            Ok(StackFilter::Name("billing".to_string()))
+        );
+        assert_eq!(
+            parse_filter("author:alice"),
+            Ok(StackFilter::Author("alice".to_string()))
+        );
+        assert_eq!(
+            parse_filter("state:merged"),
+            Ok(StackFilter::State(Some(PrState::Merged)))
+        );
+        assert_eq!(parse_filter("state:none"), Ok(StackFilter::State(None)));
+        assert_eq!(
+            parse_filter("team:a"),
+            Ok(StackFilter::Name("team:a".to_string()))
+        );
+        assert!(parse_filter("state:pending").is_err());
+        assert!(parse_filter("author:").is_err());
+        assert_eq!(
+            parse_filter("state:open").unwrap().to_string(),
+            "state:open"
+        );
+    }
+
+    #[test]
+    fn sort_stacks_by_updated_and_size() {
+        use super::{sort_stacks, ListedStack, StackSort};
+
+        let listed = |name: &str, commits: usize, touched: Option<u64>| ListedStack {
+            name: name.to_string(),
+            commits: Ok((0..commits).map(|_| Default::default()).collect()),
+            last_touched_ms: touched,
+        };
+        let mut stacks = vec![
+            listed("a", 1, Some(10)),
+            listed("b", 3, None),
+            listed("c", 2, Some(30)),
+        ];
+        let names = |stacks: &[ListedStack]| -> Vec<String> {
+            stacks.iter().map(|s| s.name.clone()).collect()
+        };
+
+        sort_stacks(&mut stacks, StackSort::Updated);
+        assert_eq!(names(&stacks), vec!["c", "a", "b"]);
+        sort_stacks(&mut stacks, StackSort::Size);
+        assert_eq!(names(&stacks), vec!["b", "c", "a"]);
+        sort_stacks(&mut stacks, StackSort::Name);
+        assert_eq!(names(&stacks), vec!["a", "b", "c"]);
+    }
+
+    #[test]
+    fn pr_details_show_review_ci_and_threads() {
+        use super::pr_details;
+        use crate::provider::{CiStatus, PrState};
+        use crate::stack::StackEntry;
+
+        console::set_colors_enabled(false);
+        let mut entry = StackEntry {
+            oid: git2::Oid::ZERO_SHA1,
+            short_sha: "abc1234".to_string(),
+            title: "Add parser".to_string(),
+            gg_id: None,
+            gg_parent: None,
+            mr_number: Some(41),
+            mr_state: Some(PrState::Open),
+            approved: false,
+            changes_requested: true,
+            mergeable: false,
+            ci_status: Some(CiStatus::Failed),
+            position: 1,
+            in_merge_train: false,
+            merge_train_position: None,
+            unresolved_threads: Some(2),
+            annotations: Default::default(),
+            signed: false,
+        };
+        assert_eq!(
+            pr_details(&entry),
+            vec!["changes requested", "CI failed", "2 unresolved threads"]
+        );
+
+        entry.changes_requested = false;
+        entry.approved = true;
+        entry.ci_status = Some(CiStatus::Success);
+        entry.unresolved_threads = Some(0);
+        assert_eq!(pr_details(&entry), vec!["approved", "CI passed"]);
+
+        entry.mr_state = Some(PrState::Merged);
+        entry.ci_status = None;
+        entry.unresolved_threads = None;
+        assert!(pr_details(&entry).is_empty());
+    }
+
     // ==========================================================================
     // Tests for remote stack classification (active vs landed)
     // ==========================================================================
@@ -0,0 +1,471 @@
+//! `gg init --migrate` - Adopt existing PR branches as gg stacks
+//!
+//! Scans the current user's open PRs/MRs, groups chains where each PR
+//! targets the previous one's head branch, and converts every chain into a
+//! stack: commits get GG-ID trailers, the stack branch is created locally,
+//! PR mappings are recorded, and remote branches are renamed to gg's entry
+//! branch names so the existing reviews carry over.
+//!
+//! Only chains with exactly one commit per PR are migrated; anything else
+//! is reported and left untouched.
+
+use std::collections::{HashMap, HashSet};
+
+use console::style;
+use git2::{Oid, Repository};
+
+use crate::config::Config;
+use crate::error::{GgError, Result};
+use crate::git;
+use crate::operations::{OperationKind, SnapshotScope};
+use crate::provider::{OpenPr, Provider};
+use crate::signing;
+use crate::stack;
+
+/// A run of PRs where each one targets the previous one's head branch.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct PrChain {
+    /// Branch the first PR targets.
+    pub base: String,
+    /// PRs bottom-up.
+    pub prs: Vec<OpenPr>,
+}
+
+/// A PR left out of migration, and why.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SkippedPr {
+    pub number: u64,
+    pub reason: String,
+}
+
+/// Group PRs into linear chains.
+///
+/// A PR whose base is not another PR's head starts a chain; a PR targeting
+/// a branch that several PRs build on is skipped, since a stack cannot
+/// fork.
+pub fn group_chains(prs: &[OpenPr]) -> (Vec<PrChain>, Vec<SkippedPr>) {
+    let heads: HashSet<&str> = prs.iter().map(|pr| pr.head_branch.as_str()).collect();
+    let mut children: HashMap<&str, Vec<&OpenPr>> = HashMap::new();
+    for pr in prs {
+        children
+            .entry(pr.base_branch.as_str())
+            .or_default()
+            .push(pr);
+    }
+
+    let mut chains = Vec::new();
+    let mut skipped = Vec::new();
+    let mut visited = HashSet::new();
+
+    let mut roots: Vec<&OpenPr> = prs
+        .iter()
+        .filter(|pr| !heads.contains(pr.base_branch.as_str()))
+        .collect();
+    roots.sort_by_key(|pr| pr.number);
+
+    for root in roots {
+        let mut chain = vec![root.clone()];
+        visited.insert(root.number);
+        let mut head = root.head_branch.as_str();
+        while let Some(next) = children.get(head) {
+            if next.len() > 1 {
+                for pr in next {
+                    skip_subtree(pr, &children, &mut visited, &mut skipped, head);
+                }
+                break;
+            }
+            let pr = next[0];
+            if !visited.insert(pr.number) {
+                break;
+            }
+            chain.push(pr.clone());
+            head = pr.head_branch.as_str();
+        }
+        chains.push(PrChain {
+            base: root.base_branch.clone(),
+            prs: chain,
+        });
+    }
+
+    // Whatever is left only reaches itself (a cycle of base branches).
+    for pr in prs {
+        if !visited.contains(&pr.number) {
+            skipped.push(SkippedPr {
+                number: pr.number,
+                reason: "base branches form a cycle".to_string(),
+            });
+        }
+    }
+
+    (chains, skipped)
+}
+
+fn skip_subtree(
+    pr: &OpenPr,
+    children: &HashMap<&str, Vec<&OpenPr>>,
+    visited: &mut HashSet<u64>,
+    skipped: &mut Vec<SkippedPr>,
+    fork: &str,
+) {
+    if !visited.insert(pr.number) {
+        return;
+    }
+    skipped.push(SkippedPr {
+        number: pr.number,
+        reason: format!("{} has several PRs on top of it", fork),
+    });
+    for child in children.get(pr.head_branch.as_str()).into_iter().flatten() {
+        skip_subtree(child, children, visited, skipped, fork);
+    }
+}
+
+/// Stack name derived from the first PR's branch (its last path segment).
+pub fn stack_name_for(head_branch: &str) -> Result<String> {
+    let last = head_branch.rsplit('/').next().unwrap_or(head_branch);
+    git::sanitize_stack_name(last)
+}
+
+/// A chain that passed validation and can be converted.
+struct Plan {
+    stack_name: String,
+    base: String,
+    prs: Vec<OpenPr>,
+    /// Remote tip of each PR branch, bottom-up.
+    commits: Vec<Oid>,
+}
+
+/// Check that every PR in the chain is exactly one commit on top of its
+/// base. Returns the PR tips bottom-up.
+fn chain_commits(repo: &Repository, chain: &PrChain) -> std::result::Result<Vec<Oid>, String> {
+    let mut commits: Vec<Oid> = Vec::with_capacity(chain.prs.len());
+    for pr in &chain.prs {
+        let tip = git::get_remote_branch_oid(repo, git::DEFAULT_REMOTE, &pr.head_branch)
+            .ok_or_else(|| format!("origin/{} not found; fetch it first", pr.head_branch))?;
+        let commit = repo.find_commit(tip).map_err(|e| e.to_string())?;
+        if commit.parent_count() != 1 {
+            return Err(format!("#{} ends in a merge commit", pr.number));
+        }
+
+        let one_commit = match commits.last() {
+            Some(&parent_tip) => commit.parent_id(0).ok() == Some(parent_tip),
+            None => {
+                let base = git::get_remote_branch_oid(repo, git::DEFAULT_REMOTE, &chain.base)
+                    .or_else(|| repo.revparse_single(&chain.base).ok().map(|o| o.id()))
+                    .ok_or_else(|| format!("base branch {} not found", chain.base))?;
+                let mut walk = repo.revwalk().map_err(|e| e.to_string())?;
+                walk.push(tip).map_err(|e| e.to_string())?;
+                walk.hide(base).map_err(|e| e.to_string())?;
+                walk.count() == 1
+            }
+        };
+        if !one_commit {
+            return Err(format!(
+                "#{} is not a single commit on top of {}",
+                pr.number,
+                if commits.is_empty() {
+                    chain.base.as_str()
+                } else {
+                    "the previous PR"
+                }
+            ));
+        }
+        commits.push(tip);
+    }
+    Ok(commits)
+}
+
+/// Run the migration
+pub fn run(dry_run: bool) -> Result<()> {
+    let repo = git::open_repo()?;
+    let git_dir = repo.commondir();
+    let mut config = Config::load(git_dir)?;
+
+    let provider = Provider::detect(&repo)?;
+    provider.check_installed()?;
+    provider.check_auth()?;
+    if !dry_run && !provider.supports_branch_rename() {
+        return Err(GgError::Other(format!(
+            "{} cannot rename an {}'s source branch, so existing {}s cannot be adopted in place",
+            provider.name(),
+            provider.pr_label(),
+            provider.pr_label()
+        )));
+    }
+
+    let username = config
+        .defaults
+        .branch_username
+        .clone()
+        .or_else(|| provider.whoami().ok())
+        .ok_or_else(|| GgError::Command(
+            "git-provider".to_string(),
+            "Could not determine username. Set branch_username in config or authenticate with gh/glab".to_string()
+        ))?;
+    git::validate_branch_username(&username)?;
+
+    git::fetch_and_prune(git::DEFAULT_REMOTE)?;
+
+    // PRs gg already tracks, or whose branches already follow gg's naming,
+    // are not candidates.
+    let managed: HashSet<u64> = config
+        .stacks
+        .values()
+        .flat_map(|s| s.mrs.values().copied())
+        .collect();
+    let candidates: Vec<OpenPr> = provider
+        .list_my_open_prs()?
+        .into_iter()
+        .filter(|pr| !managed.contains(&pr.number))
+        .filter(|pr| git::parse_entry_branch(&pr.head_branch).is_none())
+        .collect();
+
+    if candidates.is_empty() {
+        println!(
+            "{}",
+            style(format!("No open {}s to migrate.", provider.pr_label())).dim()
+        );
+        return Ok(());
+    }
+
+    let (chains, mut skipped) = group_chains(&candidates);
+    let mut taken: HashSet<String> = stack::list_all_stacks(&repo, &config, &username)?
+        .into_iter()
+        .collect();
+
+    let mut plans = Vec::new();
+    for chain in chains {
+        let first = chain.prs[0].number;
+        let stack_name = match stack_name_for(&chain.prs[0].head_branch) {
+            Ok(name) => name,
+            Err(e) => {
+                skipped.push(SkippedPr {
+                    number: first,
+                    reason: e.to_string(),
+                });
+                continue;
+            }
+        };
+        if !taken.insert(stack_name.clone()) {
+            for pr in &chain.prs {
+                skipped.push(SkippedPr {
+                    number: pr.number,
+                    reason: format!("stack '{}' already exists", stack_name),
+                });
+            }
+            continue;
+        }
+        match chain_commits(&repo, &chain) {
+            Ok(commits) => plans.push(Plan {
+                stack_name,
+                base: chain.base,
+                prs: chain.prs,
+                commits,
+            }),
+            Err(reason) => {
+                for pr in &chain.prs {
+                    skipped.push(SkippedPr {
+                        number: pr.number,
+                        reason: reason.clone(),
+                    });
+                }
+            }
+        }
+    }
+
+    let prefix = provider.pr_number_prefix();
+    for plan in &plans {
+        println!(
+            "{} {} {} (base {})",
+            if dry_run {
+                style("Would create stack").yellow()
+            } else {
+                style("Creating stack").green()
+            },
+            style(&plan.stack_name).cyan(),
+            style(format!("[{} {}(s)]", plan.prs.len(), provider.pr_label())).dim(),
+            style(&plan.base).yellow()
+        );
+        for pr in &plan.prs {
+            println!(
+                "  {}{}  {}  {}",
+                prefix, pr.number, pr.head_branch, pr.title
+            );
+        }
+    }
+    skipped.sort_by_key(|s| s.number);
+    for s in &skipped {
+        println!(
+            "{} {}{}: {}",
+            style("Skipped").dim(),
+            prefix,
+            s.number,
+            s.reason
+        );
+    }
+
+    if dry_run || plans.is_empty() {
+        return Ok(());
+    }
+
+    let (_lock, mut guard) = git::acquire_operation_lock_and_record(
+        &repo,
+        &config,
+        OperationKind::Migrate,
+        std::env::args().skip(1).collect(),
+        None,
+        SnapshotScope::AllUserBranches,
+    )?;
+
+    let default_base = config
+        .defaults
+        .base
+        .clone()
+        .unwrap_or_else(|| "main".to_string());
+    let mut touched_remote = false;
+    let mut failed = false;
+
+    for plan in &plans {
+        // Rewrite the chain with GG-ID trailers; trees are unchanged.
+        let mut parent = repo.find_commit(plan.commits[0])?.parent(0)?;
+        let mut gg_ids = Vec::with_capacity(plan.commits.len());
+        for oid in &plan.commits {
+            let commit = repo.find_commit(*oid)?;
+            let gg_id = git::get_gg_id(&commit).unwrap_or_else(git::generate_gg_id);
+            let message = git::set_gg_id_in_message(commit.message().unwrap_or_default(), &gg_id);
+            let new_oid = signing::create_commit(
+                &repo,
+                &commit.author(),
+                &commit.committer(),
+                &message,
+                &commit.tree()?,
+                &[&parent],
+            )?;
+            parent = repo.find_commit(new_oid)?;
+            gg_ids.push(gg_id);
+        }
+
+        let stack_branch = git::format_stack_branch(&username, &plan.stack_name);
+        repo.branch(&stack_branch, &parent, false)?;
+
+        let stack_config = config.get_or_create_stack(&plan.stack_name);
+        if plan.base != default_base {
+            stack_config.base = Some(plan.base.clone());
+        }
+        for (pr, gg_id) in plan.prs.iter().zip(&gg_ids) {
+            config.set_mr_for_entry(&plan.stack_name, gg_id, pr.number);
+        }
+
+        for (pr, gg_id) in plan.prs.iter().zip(&gg_ids) {
+            let new_branch = git::format_entry_branch(&username, &plan.stack_name, gg_id);
+            let renamed = git::guard_protected_branch(&config, &pr.head_branch)
+                .and_then(|()| git::guard_protected_branch(&config, &new_branch))
+                .and_then(|()| provider.rename_branch(&pr.head_branch, &new_branch));
+            match renamed {
+                Ok(()) => {
+                    touched_remote = true;
+                    guard.mark_touched_remote();
+                }
+                Err(e) => {
+                    failed = true;
+                    println!(
+                        "{} {}{}: {}",
+                        style("Warning:").yellow(),
+                        prefix,
+                        pr.number,
+                        e
+                    );
+                }
+            }
+        }
+
+        println!(
+            "{} Created stack {}",
+            style("OK").green().bold(),
+            style(&plan.stack_name).cyan()
+        );
+    }
+
+    if config.defaults.branch_username.is_none() {
+        config.defaults.branch_username = Some(username);
+    }
+    config.save(git_dir)?;
+    git::fetch_and_prune(git::DEFAULT_REMOTE)?;
+
+    guard.finalize_with_scope(
+        &repo,
+        &config,
+        SnapshotScope::AllUserBranches,
+        vec![],
+        touched_remote,
+    )?;
+
+    println!();
+    println!(
+        "{}",
+        style(
+            "Run `gg co <stack>` to switch to a stack, then `gg sync` to push the GG-ID trailers."
+        )
+        .dim()
+    );
+    if failed {
+        println!(
+            "{}",
+            style("Some branches could not be renamed; `gg sync` will open new PRs for them.")
+                .yellow()
+        );
+    }
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn pr(number: u64, head: &str, base: &str) -> OpenPr {
+        OpenPr {
+            number,
+            title: format!("PR {}", number),
+            head_branch: head.to_string(),
+            base_branch: base.to_string(),
+        }
+    }
+
+    #[test]
+    fn groups_dependent_prs_into_chains() {
+        let prs = vec![
+            pr(3, "feat/c", "feat/b"),
+            pr(1, "feat/a", "main"),
+            pr(2, "feat/b", "feat/a"),
+            pr(7, "fix/typo", "main"),
+        ];
+        let (chains, skipped) = group_chains(&prs);
+        assert!(skipped.is_empty());
+        assert_eq!(chains.len(), 2);
+        assert_eq!(chains[0].base, "main");
+        let numbers: Vec<u64> = chains[0].prs.iter().map(|p| p.number).collect();
+        assert_eq!(numbers, vec![1, 2, 3]);
+        assert_eq!(chains[1].prs.len(), 1);
+    }
+
+    #[test]
+    fn forks_end_the_chain_and_skip_branches() {
+        let prs = vec![
+            pr(1, "a", "main"),
+            pr(2, "b", "a"),
+            pr(3, "c", "a"),
+            pr(4, "d", "c"),
+        ];
+        let (chains, skipped) = group_chains(&prs);
+        assert_eq!(chains.len(), 1);
+        assert_eq!(chains[0].prs.len(), 1);
+        let skipped: Vec<u64> = skipped.iter().map(|s| s.number).collect();
+        assert_eq!(skipped, vec![2, 3, 4]);
+    }
+
+    #[test]
+    fn stack_name_uses_last_branch_segment() {
+        assert_eq!(stack_name_for("nacho/login-form").unwrap(), "login-form");
+        assert_eq!(stack_name_for("login").unwrap(), "login");
+    }
+}
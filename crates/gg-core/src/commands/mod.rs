@@ -1,29 +1,65 @@
 //! Command implementations for git-gud
 
 pub mod absorb;
+pub mod absorb_plan;
+pub mod annotate;
+pub mod archive;
+pub mod check_msgs;
 pub mod checkout;
+pub mod ci;
 pub mod clean;
+pub mod comment;
+pub mod comments;
 pub mod completions;
+pub mod config_cmd;
+pub mod describe;
+pub mod diff;
+pub mod doctor;
 pub mod drop_cmd;
+pub mod edit;
+pub mod export;
+pub mod fold;
+pub mod import;
 pub mod inbox;
 pub mod init;
+pub mod insights;
+pub mod label;
 pub mod land;
 pub mod lint;
 pub mod log;
 pub mod ls;
+pub mod migrate;
+pub mod move_cmd;
 pub mod nav;
+pub mod open;
+pub mod picker;
+pub mod ready;
 pub mod rebase;
 pub mod reconcile;
 pub mod reorder;
+pub mod reorder_deps;
 pub mod reorder_tui;
 pub mod restack;
+pub mod retarget;
+pub mod review;
 pub mod run;
+pub mod scope;
 pub mod setup;
+pub mod snapshot;
 pub mod split;
 pub mod split_protocol;
 pub mod split_tui;
 pub mod squash;
+pub mod squash_all;
+pub mod stash;
+pub mod status;
 pub mod sync;
+pub mod ui;
+pub mod ui_tui;
 pub mod undo;
 pub mod unstack;
 pub mod unstack_tui;
+pub mod use_cmd;
+pub mod verify;
+pub mod watch;
+pub mod worktree;
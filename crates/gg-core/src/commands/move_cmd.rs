@@ -0,0 +1,172 @@
+//! `gg mv <target> --to <stack>` - Move a commit to another stack
+//!
+//! The commit is cherry-picked, GG-ID included, on top of the other stack
+//! and then dropped from this one. Its PR/MR mapping moves with it, so the
+//! next `gg sync` of the destination stack picks the PR/MR up.
+
+use console::style;
+use git2::BranchType;
+
+use crate::config::Config;
+use crate::error::{GgError, Result};
+use crate::git;
+use crate::immutability::{self, ImmutabilityPolicy};
+use crate::operations::{self, OperationKind, SnapshotScope};
+use crate::signing;
+use crate::stack::{self, Stack};
+
+use super::drop_cmd::rebase_without;
+
+/// Options for moving a commit to another stack
+#[derive(Debug, Default)]
+pub struct MoveOptions {
+    /// Entry to move: position (1-indexed), short SHA, or GG-ID
+    pub target: String,
+    /// Destination stack name
+    pub to: String,
+    /// Override the immutability check for merged/base-ancestor commits
+    pub force: bool,
+}
+
+/// Run the move command
+pub fn run(options: MoveOptions) -> Result<()> {
+    let repo = git::open_repo()?;
+    let git_dir = repo.commondir();
+    let mut config = Config::load(git_dir)?;
+
+    let _lock = git::acquire_operation_lock(&repo, "move")?;
+    git::require_clean_working_directory(&repo)?;
+
+    if git::current_branch_name(&repo).is_none() {
+        return Err(GgError::Other(
+            "Moving a commit must run from the stack branch. Run `gg last` first.".to_string(),
+        ));
+    }
+
+    let mut stack = Stack::load(&repo, &config)?;
+    immutability::refresh_mr_state_for_guard(&repo, &mut stack);
+    let position = stack::resolve_target(&stack, &options.target)?;
+
+    let dest = match git::parse_stack_branch(&options.to) {
+        Some((user, name)) if user == stack.username => name,
+        _ => options.to.clone(),
+    };
+    if dest == stack.name {
+        return Err(GgError::Other(format!(
+            "#{} is already in stack '{}'",
+            position, dest
+        )));
+    }
+    let dest_branch = git::format_stack_branch(&stack.username, &dest);
+    let dest_tip = repo
+        .find_branch(&dest_branch, BranchType::Local)
+        .map_err(|_| GgError::StackNotFound(dest.clone()))?
+        .get()
+        .peel_to_commit()?;
+    if let Some(worktree) = git::is_branch_checked_out_in_worktree(&repo, &dest_branch) {
+        return Err(GgError::Other(format!(
+            "Stack '{}' is checked out in worktree '{}'. Move the commit from there instead.",
+            dest, worktree
+        )));
+    }
+
+    // The moved entry and everything above it are rewritten.
+    let positions: Vec<usize> = (position..=stack.len()).collect();
+    let policy = ImmutabilityPolicy::for_stack(&repo, &stack)?;
+    immutability::guard(policy.check_positions(&stack, &positions), options.force)?;
+
+    let entry = &stack.entries[position - 1];
+    let commit = repo.find_commit(entry.oid)?;
+    let mut index = repo.cherrypick_commit(&commit, &dest_tip, 0, None)?;
+    if index.has_conflicts() {
+        return Err(GgError::Other(format!(
+            "#{} ({}) does not apply cleanly on top of stack '{}'",
+            position, entry.short_sha, dest
+        )));
+    }
+    let tree = repo.find_tree(index.write_tree_to(&repo)?)?;
+
+    let guard = git::begin_recorded_op(
+        &repo,
+        &config,
+        OperationKind::Move,
+        std::env::args().skip(1).collect(),
+        Some(stack.name.clone()),
+        SnapshotScope::AllUserBranches,
+    )?;
+
+    let moved_oid = signing::create_commit(
+        &repo,
+        &commit.author(),
+        &commit.committer(),
+        commit.message().unwrap_or(""),
+        &tree,
+        &[&dest_tip],
+    )?;
+    repo.reference(
+        &format!("refs/heads/{}", dest_branch),
+        moved_oid,
+        true,
+        "gg mv --to",
+    )?;
+    let dest_stack = Stack::load_named(&repo, &config, &stack.username, &dest)?;
+    git::normalize_stack_metadata(&repo, &dest_stack)?;
+
+    // The PR/MR follows the commit; its entry branch name changes with the
+    // stack, so the old local one goes away.
+    if let Some(gg_id) = &entry.gg_id {
+        if let Some(pr) = config.get_mr_for_entry(&stack.name, gg_id) {
+            config.remove_mr_for_entry(&stack.name, gg_id);
+            config.set_mr_for_entry(&dest, gg_id, pr);
+        }
+    }
+    if let Some(branch) = stack.entry_branch_name(entry) {
+        let _ = repo
+            .find_branch(&branch, BranchType::Local)
+            .and_then(|mut b| b.delete());
+    }
+    config.save(git_dir)?;
+
+    match rebase_without(
+        &repo,
+        config.get_remote_for_stack(&stack.name),
+        &stack,
+        &[position],
+    ) {
+        Ok(()) => {}
+        Err(GgError::RebaseConflict) => {
+            let _ = operations::remember_interrupted_rebase_operation(&repo, guard.id());
+            return Err(GgError::RebaseConflict);
+        }
+        Err(e) => return Err(e),
+    }
+    let rewritten = Stack::load(&repo, &config)?;
+    git::normalize_stack_metadata(&repo, &rewritten)?;
+
+    guard.finalize_with_scope(
+        &repo,
+        &config,
+        SnapshotScope::AllUserBranches,
+        vec![],
+        false,
+    )?;
+
+    println!(
+        "{} Moved #{} {} to stack {}, {} remaining",
+        style("OK").green().bold(),
+        position,
+        style(&entry.short_sha).yellow(),
+        style(&dest).cyan(),
+        rewritten.len()
+    );
+    println!(
+        "{}",
+        style(format!(
+            "Run `gg sync` on both '{}' and '{}' to update their PRs/MRs.",
+            stack.name, dest
+        ))
+        .dim()
+    );
+
+    Ok(())
+}
@@ -5,7 +5,9 @@ use console::style;
 use crate::config::Config;
 use crate::error::{GgError, Result};
 use crate::git;
+use crate::hooks::{self, GitRewrite};
 use crate::operations::{self, OperationKind, SnapshotScope};
+use crate::replay;
 use crate::stack::{self, Stack, StackEntry};
 
 /// Acquire the operation lock, record a Pending Nav op, run the given
@@ -17,7 +19,7 @@ where
     F: FnOnce(&git2::Repository, &Config) -> Result<()>,
 {
     let repo = git::open_repo()?;
-    let config = Config::load_with_global(repo.commondir())?;
+    let config = Config::load(repo.commondir())?;
     let (_lock, guard) = git::acquire_operation_lock_and_record(
         &repo,
         &config,
@@ -41,8 +43,9 @@ where
     )
 }
 
-/// Move to a specific position, entry ID, or SHA
-pub fn move_to(target: &str) -> Result<()> {
+/// Move to a specific position, entry ID, PR/MR number or SHA, or pick the
+/// entry interactively when `target` is `None`
+pub fn move_to(target: Option<&str>) -> Result<()> {
     let op_args = std::env::args().skip(1).collect();
     with_recorded_nav_lock(op_args, |repo, config| {
         if git::is_rebase_in_progress(repo) {
@@ -58,35 +61,15 @@ pub fn move_to(target: &str) -> Result<()> {
             return Err(GgError::Other("Stack is empty".to_string()));
         }
 
-        // Try to parse target as position (1-indexed number)
-        if let Ok(pos) = target.parse::<usize>() {
-            if let Some(entry) = stack.get_entry_by_position(pos) {
-                return checkout_entry(repo, &stack, entry);
-            } else {
-                return Err(GgError::Other(format!(
-                    "Position {} is out of range (1-{})",
-                    pos,
-                    stack.len()
-                )));
-            }
-        }
-
-        // Try to find by GG-ID
-        if let Some(entry) = stack.get_entry_by_gg_id(target) {
-            return checkout_entry(repo, &stack, entry);
-        }
-
-        // Try to find by SHA prefix
-        for entry in &stack.entries {
-            if entry.short_sha.starts_with(target) || entry.oid.to_string().starts_with(target) {
-                return checkout_entry(repo, &stack, entry);
-            }
-        }
-
-        Err(GgError::Other(format!(
-            "Could not find commit matching '{}' in stack",
-            target
-        )))
+        // Position, PR/MR number (`#123`), GG-ID or SHA prefix
+        let pos = match target {
+            Some(target) => stack::resolve_target(&stack, target)?,
+            None => super::picker::pick_entry(&stack)?,
+        };
+        let entry = stack
+            .get_entry_by_position(pos)
+            .ok_or_else(|| GgError::Other(format!("Position {} not found in stack", pos)))?;
+        checkout_entry(repo, &stack, entry)
     })
 }
 
@@ -288,8 +271,6 @@ fn checkout_entry(repo: &git2::Repository, stack: &Stack, entry: &StackEntry) ->
 /// If modified and there are commits after this one, rebase them onto the new HEAD
 /// Returns true if a rebase was performed
 fn check_and_rebase_if_modified(repo: &git2::Repository, stack: &Stack) -> Result<bool> {
-    use std::process::Command;
-
     // Don't try to rebase if a rebase is already in progress
     if git::is_rebase_in_progress(repo) {
         return Ok(false);
@@ -345,9 +326,34 @@ fn check_and_rebase_if_modified(repo: &git2::Repository, stack: &Stack) -> Resul
     // Get the branch name to rebase
     let branch_name = stack.branch_name();
 
+    // Replay the commits above in memory when they apply cleanly, so the
+    // working tree is left alone
+    let branch_tip = repo
+        .revparse_single(&format!("refs/heads/{}", branch_name))?
+        .id();
+    let steps: Vec<replay::Step> = replay::commits_between(repo, original_oid, branch_tip)?
+        .into_iter()
+        .map(replay::Step::Pick)
+        .collect();
+    if let Some(new_tip) = replay::replay(repo, current_oid, &steps)? {
+        repo.reference(
+            &format!("refs/heads/{}", branch_name),
+            new_tip,
+            true,
+            "gg: rebase onto modified commit",
+        )?;
+        // HEAD is now the entry at `saved_position` of the rewritten stack
+        stack::save_nav_context(git_dir, &branch_name, saved_position, current_oid)?;
+        println!(
+            "{} Successfully rebased stack onto modified commit",
+            style("OK").green().bold()
+        );
+        return Ok(true);
+    }
+
     // Use git rebase --onto to rebase the remaining commits
     // git rebase --onto <new_base> <old_base> <branch>
-    let rebase_result = Command::new("git")
+    let rebase_result = hooks::git_command(GitRewrite::Replay)
         .args([
             "rebase",
             "--onto",
@@ -0,0 +1,102 @@
+//! `gg open` - Open stack PRs/MRs in the browser
+
+use std::process::Command;
+
+use console::style;
+
+use crate::config::Config;
+use crate::error::{GgError, Result};
+use crate::git;
+use crate::provider::Provider;
+use crate::stack::{self, Stack};
+
+/// Options for the open command
+#[derive(Debug, Default)]
+pub struct OpenOptions {
+    /// Entry to open: position (1-indexed), short SHA, or GG-ID. None = current.
+    pub target: Option<String>,
+    /// Open every entry that has a PR/MR
+    pub all: bool,
+    /// Print the URLs instead of opening them
+    pub print: bool,
+}
+
+/// Run the open command
+pub fn run(options: OpenOptions) -> Result<()> {
+    let repo = git::open_repo()?;
+    let git_dir = repo.commondir();
+    let config = Config::load(git_dir)?;
+
+    let stack = Stack::load(&repo, &config)?;
+    if stack.is_empty() {
+        return Err(GgError::Other("Stack is empty".to_string()));
+    }
+
+    let prs: Vec<u64> = if options.all {
+        let prs: Vec<u64> = stack.entries.iter().filter_map(|e| e.mr_number).collect();
+        if prs.is_empty() {
+            return Err(GgError::Other(
+                "No entry has a PR/MR yet. Run `gg sync` first.".to_string(),
+            ));
+        }
+        prs
+    } else {
+        let position = match options.target.as_deref() {
+            Some(target) => stack::resolve_target(&stack, target)?,
+            None => stack.current_position.map(|p| p + 1).unwrap_or(stack.len()),
+        };
+        match stack.entries[position - 1].mr_number {
+            Some(pr) => vec![pr],
+            None => {
+                return Err(GgError::Other(format!(
+                    "#{} has no PR/MR yet. Run `gg sync` first.",
+                    position
+                )))
+            }
+        }
+    };
+
+    let provider = Provider::for_stack(&repo, &config, &stack.name)?;
+    for pr in prs {
+        let url = provider.get_pr_info(pr)?.url;
+        if options.print {
+            println!("{}", url);
+            continue;
+        }
+        open_url(&url)?;
+        println!(
+            "{} Opened {} {}{}",
+            style("OK").green().bold(),
+            provider.pr_label(),
+            provider.pr_number_prefix(),
+            pr
+        );
+    }
+
+    Ok(())
+}
+
+/// Open `url` with `$BROWSER` when set, otherwise the platform opener.
+fn open_url(url: &str) -> Result<()> {
+    let mut cmd = match std::env::var("BROWSER") {
+        Ok(browser) if !browser.trim().is_empty() => Command::new(browser.trim()),
+        _ if cfg!(target_os = "macos") => Command::new("open"),
+        _ if cfg!(windows) => {
+            let mut cmd = Command::new("cmd");
+            cmd.args(["/C", "start", ""]);
+            cmd
+        }
+        _ => Command::new("xdg-open"),
+    };
+    let status = cmd
+        .arg(url)
+        .status()
+        .map_err(|e| GgError::Other(format!("Could not launch a browser: {}", e)))?;
+    if !status.success() {
+        return Err(GgError::Other(format!(
+            "Could not open {} (use --print to get the URL)",
+            url
+        )));
+    }
+    Ok(())
+}
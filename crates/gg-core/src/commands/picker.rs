@@ -0,0 +1,266 @@
+//! Fuzzy pickers for commands run without a target: `gg co` lists local
+//! and remote stacks, `gg mv` the entries of the current stack.
+
+use std::collections::BTreeSet;
+
+use console::Term;
+use dialoguer::FuzzySelect;
+
+use crate::config::Config;
+use crate::error::{GgError, Result};
+use crate::git;
+use crate::parallel;
+use crate::plain;
+use crate::provider::{PrState, Provider};
+use crate::stack::{self, Stack};
+
+/// A stack offered by [`pick_stack`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+struct StackChoice {
+    name: String,
+    /// Only pushed to origin; checking it out creates the local branch
+    remote: bool,
+    commits: usize,
+    /// State of every mapped PR/MR, when the provider could be reached
+    pr_states: Vec<PrState>,
+}
+
+impl StackChoice {
+    fn label(&self) -> String {
+        let mut details = Vec::new();
+        if self.remote {
+            details.push("remote".to_string());
+        }
+        details.push(format!(
+            "{} commit{}",
+            self.commits,
+            if self.commits == 1 { "" } else { "s" }
+        ));
+        for (state, label) in [
+            (PrState::Open, "open"),
+            (PrState::Draft, "draft"),
+            (PrState::Merged, "merged"),
+            (PrState::Closed, "closed"),
+        ] {
+            let count = self.pr_states.iter().filter(|s| **s == state).count();
+            if count > 0 {
+                details.push(format!("{} {}", count, label));
+            }
+        }
+        format!("{}  ({})", self.name, details.join(", "))
+    }
+}
+
+/// Let the user pick one of their stacks, local or only on origin.
+pub(crate) fn pick_stack(
+    repo: &git2::Repository,
+    config: &Config,
+    username: &str,
+) -> Result<String> {
+    require_terminal("gg co <stack-name>")?;
+
+    let base_branch = git::find_base_branch(repo).unwrap_or_else(|_| "main".to_string());
+    let base_for = |name: &str| {
+        config
+            .get_base_for_stack(name)
+            .unwrap_or(base_branch.as_str())
+            .to_string()
+    };
+
+    let mut choices: Vec<StackChoice> = stack::list_all_stacks(repo, config, username)?
+        .into_iter()
+        .map(|name| {
+            let branch = git::format_stack_branch(username, &name);
+            let tip = if repo.revparse_single(&branch).is_ok() {
+                branch
+            } else {
+                git::find_entry_branch_for_stack(repo, username, &name).unwrap_or(branch)
+            };
+            StackChoice {
+                commits: count_ahead(
+                    repo,
+                    config.get_remote_for_stack(&name),
+                    &tip,
+                    &base_for(&name),
+                ),
+                name,
+                remote: false,
+                pr_states: Vec::new(),
+            }
+        })
+        .collect();
+    for (name, tips) in remote_only_stacks(repo, username, &choices)? {
+        let base = base_for(&name);
+        choices.push(StackChoice {
+            commits: tips
+                .iter()
+                .map(|tip| count_ahead(repo, git::DEFAULT_REMOTE, tip, &base))
+                .max()
+                .unwrap_or(0),
+            name,
+            remote: true,
+            pr_states: Vec::new(),
+        });
+    }
+
+    if choices.is_empty() {
+        return Err(GgError::Other(
+            "No stacks found. Use `gg co <stack-name>` to create one.".to_string(),
+        ));
+    }
+    choices.sort_by(|a, b| a.name.cmp(&b.name));
+
+    // PR/MR states are best-effort: without a reachable provider the list
+    // still shows names and commit counts.
+    // They live behind each stack's remote: look them up one remote at a time.
+    let remotes: BTreeSet<&str> = choices
+        .iter()
+        .map(|choice| config.get_remote_for_stack(&choice.name))
+        .collect();
+    for remote in remotes {
+        let Ok(provider) = Provider::for_remote(repo, remote) else {
+            continue;
+        };
+        let numbers: Vec<(usize, u64)> = choices
+            .iter()
+            .enumerate()
+            .filter(|(_, choice)| config.get_remote_for_stack(&choice.name) == remote)
+            .filter_map(|(i, choice)| config.get_stack(&choice.name).map(|s| (i, s)))
+            .flat_map(|(i, s)| s.mrs.values().map(move |n| (i, *n)))
+            .collect();
+        let states = parallel::map(&numbers, config.get_sync_jobs(), |(_, number)| {
+            provider.get_pr_info(*number).ok().map(|info| info.state)
+        });
+        for ((i, _), state) in numbers.iter().zip(states) {
+            if let Some(state) = state {
+                choices[*i].pr_states.push(state);
+            }
+        }
+    }
+
+    let labels: Vec<String> = choices.iter().map(StackChoice::label).collect();
+    let selection = FuzzySelect::new()
+        .with_prompt("Select a stack")
+        .items(&labels)
+        .interact()
+        .map_err(|e| GgError::Other(format!("Selection cancelled: {}", e)))?;
+    Ok(choices[selection].name.clone())
+}
+
+/// Let the user pick an entry of `stack`; returns its position.
+pub(crate) fn pick_entry(stack: &Stack) -> Result<usize> {
+    require_terminal("gg mv <target>")?;
+
+    let current = stack
+        .current_position
+        .unwrap_or(stack.len().saturating_sub(1));
+    let labels: Vec<String> = stack
+        .entries
+        .iter()
+        .map(|entry| {
+            let marker = if entry.position == current + 1 {
+                plain::sym("→ ", "> ")
+            } else {
+                "  "
+            };
+            let pr = entry
+                .mr_number
+                .map(|n| format!("  #{}", n))
+                .unwrap_or_default();
+            format!(
+                "{}[{}] {} {}{}",
+                marker, entry.position, entry.short_sha, entry.title, pr
+            )
+        })
+        .collect();
+    let selection = FuzzySelect::new()
+        .with_prompt("Move to")
+        .items(&labels)
+        .default(current)
+        .interact()
+        .map_err(|e| GgError::Other(format!("Selection cancelled: {}", e)))?;
+    Ok(selection + 1)
+}
+
+fn require_terminal(usage: &str) -> Result<()> {
+    if Term::stderr().is_term() {
+        Ok(())
+    } else {
+        Err(GgError::Other(format!(
+            "Not running in a terminal, so there is no picker. Use `{}`.",
+            usage
+        )))
+    }
+}
+
+/// Stacks of `username` on origin without a local branch, with the remote
+/// branches (stack or entry branches) that hold their commits.
+fn remote_only_stacks(
+    repo: &git2::Repository,
+    username: &str,
+    local: &[StackChoice],
+) -> Result<Vec<(String, Vec<String>)>> {
+    let mut stacks: Vec<(String, Vec<String>)> = Vec::new();
+    for branch_result in repo.branches(Some(git2::BranchType::Remote))? {
+        let (branch, _) = branch_result?;
+        let Some(name) = branch.name()? else {
+            continue;
+        };
+        let Some(branch_name) = name.strip_prefix("origin/") else {
+            continue;
+        };
+        let stack_name = match git::parse_entry_branch(branch_name) {
+            Some((user, stack_name, _)) if user == username => stack_name,
+            Some(_) => continue,
+            None => match git::parse_stack_branch(branch_name) {
+                Some((user, stack_name)) if user == username => stack_name,
+                _ => continue,
+            },
+        };
+        if local.iter().any(|c| c.name == stack_name) {
+            continue;
+        }
+        match stacks.iter_mut().find(|(n, _)| *n == stack_name) {
+            Some((_, tips)) => tips.push(name.to_string()),
+            None => stacks.push((stack_name, vec![name.to_string()])),
+        }
+    }
+    Ok(stacks)
+}
+
+/// Commits on `branch` that are not on `base` (or `<remote>/<base>`).
+fn count_ahead(repo: &git2::Repository, remote: &str, branch: &str, base: &str) -> usize {
+    let count = || -> std::result::Result<usize, git2::Error> {
+        let tip = repo.revparse_single(branch)?.id();
+        let base = git::resolve_base(repo, remote, base)?.id();
+        let mut revwalk = repo.revwalk()?;
+        revwalk.push(tip)?;
+        revwalk.hide(base)?;
+        Ok(revwalk.count())
+    };
+    count().unwrap_or(0)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn stack_label_summarizes_commits_and_pr_states() {
+        let choice = StackChoice {
+            name: "billing".to_string(),
+            remote: false,
+            commits: 3,
+            pr_states: vec![PrState::Open, PrState::Merged, PrState::Open],
+        };
+        assert_eq!(choice.label(), "billing  (3 commits, 2 open, 1 merged)");
+
+        let remote = StackChoice {
+            name: "docs".to_string(),
+            remote: true,
+            commits: 1,
+            pr_states: Vec::new(),
+        };
+        assert_eq!(remote.label(), "docs  (remote, 1 commit)");
+    }
+}
@@ -0,0 +1,96 @@
+//! `gg ready` - Mark draft PRs/MRs as ready for review
+
+use console::style;
+
+use crate::config::Config;
+use crate::error::{GgError, Result};
+use crate::git;
+use crate::provider::{PrState, Provider};
+use crate::stack::{self, Stack};
+
+/// Options for the ready command
+#[derive(Debug, Default)]
+pub struct ReadyOptions {
+    /// Entry to promote: position (1-indexed), short SHA, or GG-ID. None = current.
+    pub target: Option<String>,
+    /// Promote every draft PR/MR in the stack
+    pub all: bool,
+}
+
+/// Run the ready command
+pub fn run(options: ReadyOptions) -> Result<()> {
+    let repo = git::open_repo()?;
+    let config = Config::load(repo.commondir())?;
+
+    let stack = Stack::load(&repo, &config)?;
+    if stack.is_empty() {
+        return Err(GgError::Other("Stack is empty".to_string()));
+    }
+
+    let provider = Provider::for_stack(&repo, &config, &stack.name)?;
+    let prs: Vec<(usize, u64)> = if options.all {
+        let prs: Vec<(usize, u64)> = stack
+            .entries
+            .iter()
+            .filter_map(|e| Some((e.position, e.mr_number?)))
+            .collect();
+        if prs.is_empty() {
+            return Err(GgError::Other(format!(
+                "No entry has a {} yet. Run `gg sync` first.",
+                provider.pr_label()
+            )));
+        }
+        prs
+    } else {
+        let position = match options.target.as_deref() {
+            Some(target) => stack::resolve_target(&stack, target)?,
+            None => stack.current_position.map(|p| p + 1).unwrap_or(stack.len()),
+        };
+        match stack.entries[position - 1].mr_number {
+            Some(pr) => vec![(position, pr)],
+            None => {
+                return Err(GgError::Other(format!(
+                    "#{} has no {} yet. Run `gg sync` first.",
+                    position,
+                    provider.pr_label()
+                )))
+            }
+        }
+    };
+    provider.check_installed()?;
+    provider.check_auth()?;
+
+    let mut promoted = 0;
+    for (position, pr) in prs {
+        let label = format!(
+            "{} {}{}",
+            provider.pr_label(),
+            provider.pr_number_prefix(),
+            pr
+        );
+        match provider.get_pr_info(pr)?.state {
+            PrState::Draft => {
+                provider.mark_ready(pr)?;
+                promoted += 1;
+                println!(
+                    "{} #{} {} is ready for review",
+                    style("OK").green().bold(),
+                    position,
+                    label
+                );
+            }
+            // With --all, only drafts are interesting
+            _ if options.all => {}
+            PrState::Open => println!("#{} {} is already ready for review", position, label),
+            PrState::Merged | PrState::Closed => {
+                return Err(GgError::Other(format!("{} is already closed", label)))
+            }
+        }
+    }
+
+    if options.all && promoted == 0 {
+        println!("No draft {}s in the stack", provider.pr_label());
+    }
+
+    Ok(())
+}
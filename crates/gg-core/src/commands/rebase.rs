@@ -1,19 +1,22 @@
 //! `gg rebase` - Rebase the stack onto an updated base branch
 
 use console::style;
-use git2::Repository;
+use git2::{Oid, Repository};
 
+use super::stash;
 use crate::config::Config;
 use crate::error::{GgError, Result};
 use crate::git;
+use crate::hooks::GitRewrite;
 use crate::immutability::{self, ImmutabilityPolicy};
-use crate::operations::{self, OperationKind, SnapshotScope};
+use crate::operations::{self, OperationKind, OperationState, PendingStep, SnapshotScope};
+use crate::plain;
 use crate::stack::{self, Stack};
 
 /// Run the rebase command
 pub fn run(target: Option<String>, force: bool) -> Result<()> {
     let repo = git::open_repo()?;
-    let config = Config::load_with_global(repo.commondir())?;
+    let config = Config::load(repo.commondir())?;
 
     // Acquire the operation lock for validation, but defer writing the
     // op-log record until after the immutability guard passes so refused
@@ -25,6 +28,10 @@ pub fn run(target: Option<String>, force: bool) -> Result<()> {
     // and don't need undo coverage.
     let target_branch = prepare_rebase(&repo, &config, target.clone(), false, force)?;
 
+    if let Ok(stack) = Stack::load(&repo, &config) {
+        super::snapshot::backup_stack(&repo, &stack)?;
+    }
+
     // All validation passed — now write the Pending op-log record so a
     // failure beyond this point leaves a record the sweep can promote to
     // Interrupted.
@@ -37,14 +44,19 @@ pub fn run(target: Option<String>, force: bool) -> Result<()> {
         SnapshotScope::AllUserBranches,
     )?;
 
-    match execute_rebase(&repo, &target_branch, false) {
-        Ok(()) => guard.finalize_with_scope(
-            &repo,
-            &config,
-            SnapshotScope::AllUserBranches,
-            vec![],
-            false,
-        ),
+    match execute_rebase(&repo, &config, &target_branch, false) {
+        Ok(()) => {
+            if target.is_some() {
+                forget_from(&repo)?;
+            }
+            guard.finalize_with_scope(
+                &repo,
+                &config,
+                SnapshotScope::AllUserBranches,
+                vec![],
+                false,
+            )
+        }
         Err(GgError::RebaseConflict) => {
             let _ = operations::remember_interrupted_rebase_operation(&repo, guard.id());
             Err(GgError::RebaseConflict)
@@ -60,9 +72,9 @@ pub fn run_with_repo(
     json: bool,
     force: bool,
 ) -> Result<()> {
-    let config = Config::load_with_global(repo.commondir())?;
+    let config = Config::load(repo.commondir())?;
     let target_branch = prepare_rebase(repo, &config, target, json, force)?;
-    execute_rebase(repo, &target_branch, json)
+    execute_rebase(repo, &config, &target_branch, json)
 }
 
 /// Validation phase: resolve target, fetch, update local base, run the
@@ -75,59 +87,72 @@ fn prepare_rebase(
     force: bool,
 ) -> Result<String> {
     // Determine target branch. If no target provided, we need to be on a
-    // stack to get the base branch.
-    let target_branch = if let Some(t) = target {
-        t
+    // stack to get the base branch, or the ref it was started from.
+    let (target_branch, from) = if let Some(t) = target {
+        (t, false)
     } else {
         let stack = Stack::load(repo, config)?;
-        stack.base.clone()
+        match config.get_from_for_stack(&stack.name) {
+            Some(from) => (from.to_string(), true),
+            None => (stack.base.clone(), false),
+        }
     };
 
     // Remember current branch to return to after updating base
     let current_branch = git::current_branch_name(repo);
 
     if !json {
-        println!(
-            "{}",
-            style(format!("Updating {} and rebasing stack...", target_branch)).dim()
-        );
+        let message = if from {
+            format!("Rebasing stack onto {}...", target_branch)
+        } else {
+            format!("Updating {} and rebasing stack...", target_branch)
+        };
+        println!("{}", style(message).dim());
     }
 
-    // Fetch the latest from remote first. We want fresh origin/<base> for
+    // Fetch the latest from remote first. We want fresh <remote>/<base> for
     // both the immutability guard and the rebase itself — running the guard
     // against stale refs can silently pass on a newly-merged commit and
     // then rewrite it after the fetch updates the ref.
-    let fetch_result = git::run_git_command(&["fetch", "origin", "--prune"]);
+    let remote = stack_remote(repo, config);
+    let fetch_result = git::run_git_command(&["fetch", remote, "--prune"]);
     let fetch_succeeded = fetch_result.is_ok();
     if let Err(e) = fetch_result {
         if !json {
             println!(
-                "{} Could not fetch from origin: {}",
+                "{} Could not fetch from {}: {}",
                 style("Warning:").yellow(),
+                remote,
                 e
             );
         }
     }
 
     // Update local base branch to match remote (fast-forward)
-    // This ensures merged PRs are reflected in the local base
-    let update_result = update_local_branch(&target_branch);
-    if let Err(e) = update_result {
-        if !json {
+    // This ensures merged PRs are reflected in the local base. A
+    // `gg co --from` ref is rebased onto as it is.
+    if !from {
+        let update_result = update_local_branch(remote, &target_branch);
+        if let Err(e) = update_result {
+            if !json {
+                println!(
+                    "{} Could not update local {}: {}",
+                    style("Warning:").yellow(),
+                    target_branch,
+                    e
+                );
+                println!(
+                    "  Continuing with rebase onto {}/{}...",
+                    remote, target_branch
+                );
+            }
+        } else if !json {
             println!(
-                "{} Could not update local {}: {}",
-                style("Warning:").yellow(),
-                target_branch,
-                e
+                "{} Updated local {} to latest",
+                style(plain::arrow()).cyan(),
+                target_branch
             );
-            println!("  Continuing with rebase onto origin/{}...", target_branch);
         }
-    } else if !json {
-        println!(
-            "{} Updated local {} to latest",
-            style("→").cyan(),
-            target_branch
-        );
     }
 
     // Return to stack branch if we switched away
@@ -162,7 +187,7 @@ fn prepare_rebase(
             if dropped > 0 && !json {
                 println!(
                     "{} Skipping {} merged commit(s) already on {}",
-                    style("→").cyan(),
+                    style(plain::arrow()).cyan(),
                     dropped,
                     policy.base_ref()
                 );
@@ -176,7 +201,12 @@ fn prepare_rebase(
 
 /// Mutation phase: stash uncommitted changes, run `git rebase`, restore
 /// stash. Assumes validation (fetch + immutability guard) has already run.
-fn execute_rebase(repo: &Repository, target_branch: &str, json: bool) -> Result<()> {
+fn execute_rebase(
+    repo: &Repository,
+    config: &Config,
+    target_branch: &str,
+    json: bool,
+) -> Result<()> {
     let current_branch = git::current_branch_name(repo);
 
     // Auto-stash uncommitted changes if present. Done after the guard so we
@@ -186,12 +216,46 @@ fn execute_rebase(repo: &Repository, target_branch: &str, json: bool) -> Result<
         if !json {
             println!("{}", style("Auto-stashing uncommitted changes...").dim());
         }
-        git::run_git_command(&["stash", "push", "-m", "gg-rebase-autostash"])?;
+        stash::auto_stash(repo, config, "gg-rebase-autostash")?;
     }
 
+    // A dependent stack rebases onto its parent's local branch, which has
+    // no remote counterpart. If the parent was rewritten since this stack
+    // forked from it, replay only this stack's own commits.
+    let stack = Stack::load(repo, config).ok();
+    let on_parent = stack
+        .as_ref()
+        .and_then(|s| s.parent_stack(repo, config))
+        .is_some_and(|parent| parent.branch_name() == target_branch);
+    let rebase_target = if on_parent {
+        target_branch.to_string()
+    } else {
+        let remote = stack.as_ref().map_or(git::DEFAULT_REMOTE, |s| {
+            config.get_remote_for_stack(&s.name)
+        });
+        let remote_target = format!("{}/{}", remote, target_branch);
+        if repo.revparse_single(&remote_target).is_ok() {
+            remote_target
+        } else {
+            // A tag or commit (`gg co --from`), or a branch only kept locally
+            target_branch.to_string()
+        }
+    };
+    let stale_fork = stack
+        .as_ref()
+        .filter(|_| on_parent)
+        .and_then(|s| s.stale_parent_fork(repo, config))
+        .map(|oid| oid.to_string());
+    let old_tip = repo.head()?.peel_to_commit()?.id();
+
     // Perform the rebase
-    let rebase_target = format!("origin/{}", target_branch);
-    let rebase_result = git::run_git_command(&["rebase", &rebase_target]);
+    let rebase_result = match &stale_fork {
+        Some(fork) => git::run_rewrite_command(
+            GitRewrite::Replay,
+            &["rebase", "--onto", &rebase_target, fork],
+        ),
+        None => git::run_rewrite_command(GitRewrite::Replay, &["rebase", &rebase_target]),
+    };
 
     match rebase_result {
         Ok(_) => {
@@ -208,6 +272,16 @@ fn execute_rebase(repo: &Repository, target_branch: &str, json: bool) -> Result<
                 );
             }
 
+            // Stacks stacked on this one follow it to its new head.
+            if let (Some(stack), Some(branch)) = (&stack, &current_branch) {
+                if *branch == stack.branch_name() {
+                    restack_dependents(repo, config, stack, old_tip, json);
+                    if git::current_branch_name(repo).as_ref() != Some(branch) {
+                        git::run_git_command(&["checkout", branch])?;
+                    }
+                }
+            }
+
             // Restore stashed changes if we stashed earlier
             if needs_stash {
                 if !json {
@@ -216,7 +290,7 @@ fn execute_rebase(repo: &Repository, target_branch: &str, json: bool) -> Result<
                 match git::run_git_command(&["stash", "pop"]) {
                     Ok(_) => {
                         if !json {
-                            println!("{} Changes restored", style("→").cyan());
+                            println!("{} Changes restored", style(plain::arrow()).cyan());
                         }
                     }
                     Err(e) => {
@@ -270,8 +344,114 @@ fn execute_rebase(repo: &Repository, target_branch: &str, json: bool) -> Result<
     }
 }
 
+/// Rebase the stacks that depend on `parent` onto its new head, recursively.
+/// `old_tip` is where the parent's branch pointed before it was rewritten.
+/// Failures are reported and left for the user to fix with `gg rebase`.
+fn restack_dependents(
+    repo: &Repository,
+    config: &Config,
+    parent: &Stack,
+    old_tip: Oid,
+    json: bool,
+) {
+    let parent_branch = parent.branch_name();
+    if repo.revparse_single(&parent_branch).ok().map(|o| o.id()) == Some(old_tip) {
+        return;
+    }
+    for child_name in config.dependent_stacks(&parent.name) {
+        let Ok(child) = Stack::load_named(repo, config, &parent.username, &child_name) else {
+            continue;
+        };
+        let child_branch = child.branch_name();
+        let child_tip = git::run_git_command(&["rev-parse", &child_branch]);
+        let forked_from_old_tip = git::run_git_command(&[
+            "merge-base",
+            "--is-ancestor",
+            &old_tip.to_string(),
+            &child_branch,
+        ])
+        .is_ok();
+        let fork = if forked_from_old_tip {
+            Some(old_tip)
+        } else {
+            child.stale_parent_fork(repo, config)
+        };
+        let Some(fork) = fork else {
+            continue;
+        };
+        let child_old_tip = child_tip
+            .ok()
+            .and_then(|sha| Oid::from_str(sha.trim()).ok());
+
+        match git::run_rewrite_command(
+            GitRewrite::Replay,
+            &[
+                "rebase",
+                "--onto",
+                &parent_branch,
+                &fork.to_string(),
+                &child_branch,
+            ],
+        ) {
+            Ok(_) => {
+                if !json {
+                    println!(
+                        "{} Restacked {} onto {}",
+                        style("OK").green().bold(),
+                        style(&child_name).cyan(),
+                        parent.name
+                    );
+                }
+                if let (Ok(child), Some(child_old_tip)) = (
+                    Stack::load_named(repo, config, &parent.username, &child_name),
+                    child_old_tip,
+                ) {
+                    restack_dependents(repo, config, &child, child_old_tip, json);
+                }
+            }
+            Err(e) => {
+                if git::is_rebase_in_progress(repo) {
+                    let _ = git::run_git_command(&["rebase", "--abort"]);
+                }
+                if !json {
+                    println!(
+                        "{} Could not restack {} onto {}: {}",
+                        style("Warning:").yellow(),
+                        child_name,
+                        parent.name,
+                        e
+                    );
+                    println!("  Check it out and run `gg rebase` to resolve it manually.");
+                }
+            }
+        }
+    }
+}
+
+/// Remote of the checked-out stack (`origin` when not on a stack)
+/// An explicit `gg rebase <target>` moves a stack started with
+/// `gg co --from <ref>` off that ref, so it follows its base again.
+fn forget_from(repo: &Repository) -> Result<()> {
+    let mut config = Config::load(repo.commondir())?;
+    let Ok(stack) = Stack::load(repo, &config) else {
+        return Ok(());
+    };
+    if let Some(stack_config) = config.stacks.get_mut(&stack.name) {
+        if stack_config.from.take().is_some() {
+            config.save(repo.commondir())?;
+        }
+    }
+    Ok(())
+}
+
+fn stack_remote<'a>(repo: &Repository, config: &'a Config) -> &'a str {
+    Stack::load(repo, config)
+        .map(|stack| config.get_remote_for_stack(&stack.name))
+        .unwrap_or(git::DEFAULT_REMOTE)
+}
+
 /// Update a local branch to match its remote counterpart (fast-forward only)
-fn update_local_branch(branch: &str) -> Result<()> {
+fn update_local_branch(remote: &str, branch: &str) -> Result<()> {
     // Check if the local branch exists
     let local_exists = git::run_git_command(&["rev-parse", "--verify", branch]).is_ok();
 
@@ -281,7 +461,7 @@ fn update_local_branch(branch: &str) -> Result<()> {
     }
 
     // Check if remote branch exists
-    let remote_ref = format!("origin/{}", branch);
+    let remote_ref = format!("{}/{}", remote, branch);
     if git::run_git_command(&["rev-parse", "--verify", &remote_ref]).is_err() {
         // Remote branch doesn't exist
         return Ok(());
@@ -303,157 +483,192 @@ fn update_local_branch(branch: &str) -> Result<()> {
     Ok(())
 }
 
-/// Continue a paused rebase
+/// Continue a paused rebase, then finish the gg operation that stopped on it
 pub fn continue_rebase() -> Result<()> {
     let repo = git::open_repo()?;
-    let config = Config::load_with_global(repo.commondir())?;
+    let config = Config::load(repo.commondir())?;
 
-    if !git::is_rebase_in_progress(&repo) {
+    // A rebase finished with plain `git rebase --continue` still leaves the
+    // rest of the interrupted operation for `gg continue` to run
+    let rebasing = git::is_rebase_in_progress(&repo);
+    let interrupted = operations::interrupted_operation(&repo)?;
+    if !rebasing && interrupted.is_none() {
         return Err(GgError::NoRebaseInProgress);
     }
 
-    let _lock = git::acquire_operation_lock(&repo, "continue")?;
+    let lock = git::acquire_operation_lock(&repo, "continue")?;
 
-    // Check for unstaged changes before continuing
-    let statuses = repo.statuses(None)?;
-    let has_unstaged = statuses.iter().any(|s| {
-        let flags = s.status();
-        // Check for modified/deleted files that aren't staged
-        flags.is_wt_modified() || flags.is_wt_deleted()
-    });
-
-    if has_unstaged {
-        return Err(GgError::Other(
-            "You have unstaged changes. Stage them with `git add` before running `gg continue`."
-                .to_string(),
-        ));
-    }
-
-    // Check for unresolved conflicts
-    let has_conflicts = statuses.iter().any(|s| {
-        let flags = s.status();
-        flags.is_conflicted()
-    });
-
-    if has_conflicts {
-        return Err(GgError::Other(
-            "You have unresolved conflicts. Resolve them and stage with `git add` before running `gg continue`.".to_string()
-        ));
-    }
+    if rebasing {
+        // Check for unstaged changes before continuing
+        let statuses = repo.statuses(None)?;
+        let has_unstaged = statuses.iter().any(|s| {
+            let flags = s.status();
+            // Check for modified/deleted files that aren't staged
+            flags.is_wt_modified() || flags.is_wt_deleted()
+        });
 
-    let continued_operation = operations::interrupted_rebase_operation(&repo)?;
+        if has_unstaged {
+            return Err(GgError::Other(
+                "You have unstaged changes. Stage them with `git add` before running `gg continue`."
+                    .to_string(),
+            ));
+        }
 
-    match git::rebase_continue() {
-        Ok(_) => {
-            // If the paused rebase was a mid-stack integration (`gg restack`
-            // folding in a detached commit), finish the integration-specific
-            // cleanup that the conflict short-circuited: normalize GG metadata,
-            // land HEAD back on the inserted commit, and rewrite the nav context.
-            if let Some((branch_name, head_oid)) =
-                crate::stack::read_pending_integration(repo.path())
-            {
-                let config = Config::load_with_global(repo.commondir())?;
-                let (new_head_oid, stack_name) =
-                    crate::commands::restack::finalize_detached_integration(
-                        &repo,
-                        &config,
-                        &branch_name,
-                        head_oid,
-                    )?;
-                crate::stack::clear_pending_integration(repo.path())?;
-
-                let short = repo
-                    .find_object(new_head_oid, None)?
-                    .short_id()?
-                    .as_str()
-                    .unwrap_or("")
-                    .to_string();
-                println!(
-                    "{} Integrated mid-stack commit into stack {:?}; HEAD stays on {}",
-                    style("OK").green().bold(),
-                    stack_name,
-                    style(&short).yellow()
-                );
-                println!(
-                    "  {}",
-                    style("Run `gg sync` to push the updated stack.").dim()
-                );
-                finalize_continued_operation(&repo, &config, continued_operation)?;
-                return Ok(());
-            }
+        // Check for unresolved conflicts
+        let has_conflicts = statuses.iter().any(|s| {
+            let flags = s.status();
+            flags.is_conflicted()
+        });
 
-            finalize_continued_operation(&repo, &config, continued_operation)?;
-            println!(
-                "{} Rebase continued successfully",
-                style("OK").green().bold()
-            );
-            Ok(())
+        if has_conflicts {
+            return Err(GgError::Other(
+                "You have unresolved conflicts. Resolve them and stage with `git add` before running `gg continue`.".to_string()
+            ));
         }
-        Err(e) => {
+
+        if let Err(e) = git::rebase_continue() {
             let error_str = e.to_string();
             if error_str.contains("CONFLICT") || error_str.contains("conflict") {
                 println!(
                     "{} More conflicts detected. Resolve and run `gg continue` again.",
                     style("!").yellow().bold()
                 );
-                Err(GgError::RebaseConflict)
-            } else {
-                // Provide more helpful error message
-                eprintln!("{} Failed to continue rebase", style("Error:").red().bold());
-                eprintln!("  {}", error_str);
-                eprintln!();
-                eprintln!("{}", style("You are still in rebase state.").yellow());
-                eprintln!("  • Resolve any remaining issues");
-                eprintln!("  • Run `git rebase --continue` manually to continue");
-                eprintln!("  • Or run `gg abort` to cancel the rebase");
-                eprintln!();
-                eprintln!("  Hint: Run `git status` to see the current state");
-                Err(e)
+                return Err(GgError::RebaseConflict);
             }
+
+            // Provide more helpful error message
+            eprintln!("{} Failed to continue rebase", style("Error:").red().bold());
+            eprintln!("  {}", error_str);
+            eprintln!();
+            eprintln!("{}", style("You are still in rebase state.").yellow());
+            eprintln!("  {} Resolve any remaining issues", plain::bullet());
+            eprintln!(
+                "  {} Run `git rebase --continue` manually to continue",
+                plain::bullet()
+            );
+            eprintln!(
+                "  {} Or run `gg abort` to cancel the rebase",
+                plain::bullet()
+            );
+            eprintln!();
+            eprintln!("  Hint: Run `git status` to see the current state");
+            return Err(e);
         }
     }
+
+    // If the paused rebase was a mid-stack integration (`gg restack`
+    // folding in a detached commit), finish the integration-specific
+    // cleanup that the conflict short-circuited: normalize GG metadata,
+    // land HEAD back on the inserted commit, and rewrite the nav context.
+    if let Some((branch_name, head_oid)) = crate::stack::read_pending_integration(repo.path()) {
+        let (new_head_oid, stack_name) = crate::commands::restack::finalize_detached_integration(
+            &repo,
+            &config,
+            &branch_name,
+            head_oid,
+        )?;
+        crate::stack::clear_pending_integration(repo.path())?;
+
+        let short = repo
+            .find_object(new_head_oid, None)?
+            .short_id()?
+            .as_str()
+            .unwrap_or("")
+            .to_string();
+        println!(
+            "{} Integrated mid-stack commit into stack {:?}; HEAD stays on {}",
+            style("OK").green().bold(),
+            stack_name,
+            style(&short).yellow()
+        );
+        println!(
+            "  {}",
+            style("Run `gg sync` to push the updated stack.").dim()
+        );
+        finalize_continued_operation(&repo, &config, interrupted)?;
+        return Ok(());
+    }
+
+    finalize_continued_operation(&repo, &config, interrupted)?;
+    println!(
+        "{} Rebase continued successfully",
+        style("OK").green().bold()
+    );
+
+    // `gg land --auto-resolve-restack` stopped here; pick it back up
+    // once the lock is free for its resync and land.
+    drop(lock);
+    crate::commands::land::resume_pending_land(&repo)
 }
 
-/// Abort a paused rebase
+/// Abort a paused rebase and roll back the gg operation that stopped on it
 pub fn abort_rebase() -> Result<()> {
     let repo = git::open_repo()?;
 
-    if !git::is_rebase_in_progress(&repo) {
+    let rebasing = git::is_rebase_in_progress(&repo);
+    let interrupted = operations::interrupted_operation(&repo)?;
+    if !rebasing && interrupted.is_none() {
         return Err(GgError::NoRebaseInProgress);
     }
 
-    git::rebase_abort()?;
+    let _lock = git::acquire_operation_lock(&repo, "abort")?;
+
+    if rebasing {
+        git::rebase_abort()?;
+    }
+    // The rebase only covers the branch it rewrote: refs the operation moved
+    // before stopping, and HEAD, go back to where the operation found them
+    let restored = match &interrupted {
+        Some((_, record)) => operations::roll_back_interrupted_operation(&repo, record)?,
+        None => Vec::new(),
+    };
     operations::clear_interrupted_rebase_operation(&repo)?;
 
     // Discard any pending mid-stack integration: the fold-in is cancelled.
     crate::stack::clear_pending_integration(repo.path())?;
+    // Likewise a land waiting on this restack will not resume on its own.
+    crate::commands::land::cancel_pending_restack(repo.path())?;
 
     println!("{} Rebase aborted", style("OK").green().bold());
+    for name in &restored {
+        println!(
+            "  Restored {}",
+            style(name.strip_prefix("refs/heads/").unwrap_or(name)).cyan()
+        );
+    }
+    if let Some((state, _)) = &interrupted {
+        match &state.original_branch {
+            Some(branch) => println!("  HEAD is back on {}", style(branch).cyan()),
+            None => {
+                if let Some(head) = &state.original_head {
+                    println!(
+                        "  HEAD is back at {}",
+                        style(&head[..head.len().min(7)]).yellow()
+                    );
+                }
+            }
+        }
+    }
 
     Ok(())
 }
 
+/// Run the steps the interrupted operation still had left, then commit its
+/// record to the undo log. The state is saved after every step, so a step
+/// that fails can be retried with another `gg continue`.
 fn finalize_continued_operation(
     repo: &Repository,
     config: &Config,
-    operation: Option<operations::OperationRecord>,
+    interrupted: Option<(OperationState, operations::OperationRecord)>,
 ) -> Result<()> {
-    let Some(operation) = operation else {
+    let Some((mut state, operation)) = interrupted else {
         return Ok(());
     };
 
-    if operation_needs_metadata_normalization_after_continue(operation.kind) {
-        let rewritten_stack = Stack::load(repo, config)?;
-        git::normalize_stack_metadata(repo, &rewritten_stack)?;
-    }
-    if operation.kind == OperationKind::Drop {
-        cleanup_continued_drop_branches(repo, &operation);
-    }
-    if operation.kind == OperationKind::Split {
-        restore_continued_split_navigation(repo, config, &operation)?;
-    }
-    if operation.kind == OperationKind::Squash {
-        restore_continued_squash_navigation(repo, config, &operation)?;
+    while let Some(&step) = state.remaining.first() {
+        run_pending_step(repo, config, &state, &operation, step)?;
+        state.remaining.remove(0);
+        operations::save_operation_state(repo, &state)?;
     }
 
     operations::finalize_operation_by_id(
@@ -468,14 +683,47 @@ fn finalize_continued_operation(
     Ok(())
 }
 
-fn operation_needs_metadata_normalization_after_continue(kind: OperationKind) -> bool {
-    matches!(
-        kind,
-        OperationKind::Drop
-            | OperationKind::Reorder
-            | OperationKind::Restack
-            | OperationKind::Split
-    )
+fn run_pending_step(
+    repo: &Repository,
+    config: &Config,
+    state: &OperationState,
+    operation: &operations::OperationRecord,
+    step: PendingStep,
+) -> Result<()> {
+    match step {
+        PendingStep::ReturnToBranch => {
+            if let Some(branch_name) = &state.original_branch {
+                return_to_branch(repo, branch_name)?;
+            }
+        }
+        PendingStep::NormalizeMetadata => {
+            let rewritten_stack = Stack::load(repo, config)?;
+            git::normalize_stack_metadata(repo, &rewritten_stack)?;
+        }
+        PendingStep::DeleteDroppedBranches => cleanup_continued_drop_branches(repo, operation),
+        PendingStep::RestoreEntry if operation.kind == OperationKind::Split => {
+            restore_continued_split_navigation(repo, config, operation)?;
+        }
+        PendingStep::RestoreEntry => {
+            restore_continued_squash_navigation(repo, config, operation)?;
+        }
+        PendingStep::ClearNavContext => stack::clear_current_stack(repo.path())?,
+    }
+    Ok(())
+}
+
+/// Leave HEAD attached to `branch_name` after the rebase. `git rebase` can
+/// finish on a detached HEAD (in worktrees, or when the operation rebased
+/// from a detached position), which would strand the user off the stack.
+fn return_to_branch(repo: &Repository, branch_name: &str) -> Result<()> {
+    if !repo.head_detached()? {
+        return Ok(());
+    }
+    git::ensure_branch_attached(repo, branch_name)?;
+    if repo.head_detached()? {
+        git::checkout_branch(repo, branch_name)?;
+    }
+    Ok(())
 }
 
 fn restore_continued_split_navigation(
@@ -640,6 +888,9 @@ mod tests {
             position,
             in_merge_train: false,
             merge_train_position: None,
+            unresolved_threads: None,
+            annotations: Default::default(),
+            signed: false,
         }
     }
 
@@ -710,6 +961,7 @@ mod tests {
                     "remainder_gg_id": "c-two222",
                 }
             })),
+            conflicted: false,
         };
 
         let previous_dir = std::env::current_dir().unwrap();
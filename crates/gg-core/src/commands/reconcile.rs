@@ -15,6 +15,7 @@ use crate::config::Config;
 use crate::error::Result;
 use crate::git;
 use crate::operations::{OperationKind, SnapshotScope};
+use crate::plain;
 use crate::provider::Provider;
 use crate::stack::Stack;
 
@@ -34,7 +35,7 @@ struct CommitInfo {
 }
 
 #[derive(Debug)]
-struct PrMapping {
+pub(crate) struct PrMapping {
     gg_id: String,
     branch: String,
     pr_number: u64,
@@ -80,7 +81,7 @@ pub struct ReconcileOptions {
 pub fn run(options: ReconcileOptions) -> Result<()> {
     let repo = git::open_repo()?;
     let git_dir = repo.commondir();
-    let mut config = Config::load_with_global(git_dir)?;
+    let mut config = Config::load(git_dir)?;
 
     // Acquire operation lock + record a Pending op for the undo log.
     // NOTE: behaviour change — reconcile previously had no lock. Only the
@@ -95,8 +96,11 @@ pub fn run(options: ReconcileOptions) -> Result<()> {
         SnapshotScope::AllUserBranches,
     )?;
 
+    // Load current stack
+    let stack = Stack::load(&repo, &config)?;
+
     // Detect provider
-    let provider = Provider::detect(&repo)?;
+    let provider = Provider::for_stack(&repo, &config, &stack.name)?;
 
     // Only check installed/auth if not doing a dry run
     // (dry run can show what would be done without actual provider access)
@@ -105,9 +109,6 @@ pub fn run(options: ReconcileOptions) -> Result<()> {
         provider.check_auth()?;
     }
 
-    // Load current stack
-    let stack = Stack::load(&repo, &config)?;
-
     if stack.is_empty() {
         println!("{}", style("Stack is empty. Nothing to reconcile.").dim());
         guard.finalize_with_scope(
@@ -122,7 +123,7 @@ pub fn run(options: ReconcileOptions) -> Result<()> {
 
     println!(
         "{} Analyzing stack {} ({} commits)...",
-        style("→").cyan(),
+        style(plain::arrow()).cyan(),
         style(&stack.name).bold(),
         stack.len()
     );
@@ -172,7 +173,7 @@ pub fn run(options: ReconcileOptions) -> Result<()> {
     if actions.is_empty() {
         println!(
             "\n{} Stack is already reconciled. Nothing to do.",
-            style("✓").green().bold()
+            style(plain::ok()).green().bold()
         );
         guard.finalize_with_scope(
             &repo,
@@ -185,7 +186,10 @@ pub fn run(options: ReconcileOptions) -> Result<()> {
     }
 
     if options.dry_run {
-        println!("\n{} Dry run complete. No changes made.", style("→").cyan());
+        println!(
+            "\n{} Dry run complete. No changes made.",
+            style(plain::arrow()).cyan()
+        );
         guard.finalize_with_scope(
             &repo,
             &config,
@@ -232,7 +236,7 @@ pub fn run(options: ReconcileOptions) -> Result<()> {
 }
 
 /// Find PRs/MRs for entry branches that aren't mapped in config
-fn find_unmapped_prs(
+pub(crate) fn find_unmapped_prs(
     _repo: &Repository,
     stack: &Stack,
     config: &Config,
@@ -294,13 +298,13 @@ fn display_actions(actions: &ReconcileActions, provider: &Provider) {
     if !actions.commits_needing_ids.is_empty() {
         println!(
             "\n{} {} commits need GG-IDs:",
-            style("→").cyan(),
+            style(plain::arrow()).cyan(),
             actions.commits_needing_ids.len()
         );
         for commit in &actions.commits_needing_ids {
             println!(
                 "  {} {} {}",
-                style("•").dim(),
+                style(plain::bullet()).dim(),
                 style(&commit.short_sha).yellow(),
                 commit.title
             );
@@ -310,15 +314,16 @@ fn display_actions(actions: &ReconcileActions, provider: &Provider) {
     if !actions.prs_to_map.is_empty() {
         println!(
             "\n{} {} existing {}s found to map:",
-            style("→").cyan(),
+            style(plain::arrow()).cyan(),
             actions.prs_to_map.len(),
             provider.pr_label()
         );
         for mapping in &actions.prs_to_map {
             println!(
-                "  {} {} → {} {}{}",
-                style("•").dim(),
+                "  {} {} {} {} {}{}",
+                style(plain::bullet()).dim(),
                 style(&mapping.branch).cyan(),
+                plain::arrow(),
                 provider.pr_label(),
                 provider.pr_number_prefix(),
                 mapping.pr_number
@@ -328,7 +333,7 @@ fn display_actions(actions: &ReconcileActions, provider: &Provider) {
 }
 
 /// Map PRs/MRs to entries in config
-fn map_prs(
+pub(crate) fn map_prs(
     config: &mut Config,
     stack_name: &str,
     mappings: &[PrMapping],
@@ -337,9 +342,10 @@ fn map_prs(
     for mapping in mappings {
         config.set_mr_for_entry(stack_name, &mapping.gg_id, mapping.pr_number);
         println!(
-            "{} Mapped {} → {} {}{}",
+            "{} Mapped {} {} {} {}{}",
             style("OK").green().bold(),
             style(&mapping.gg_id).cyan(),
+            plain::arrow(),
             provider.pr_label(),
             provider.pr_number_prefix(),
             mapping.pr_number
@@ -5,13 +5,19 @@ use std::io::Write;
 use console::style;
 use dialoguer::Editor;
 
+use super::reorder_deps::{self, Dependency, DependencyKind};
 use super::reorder_tui::{self, ReorderEntry};
 use crate::config::Config;
 use crate::error::{GgError, Result};
 use crate::git;
+use crate::hooks::{self, GitRewrite};
 use crate::immutability::{self, ImmutabilityPolicy};
 use crate::operations::{self, OperationKind, SnapshotScope};
-use crate::stack::Stack;
+use crate::output::{
+    self, ReorderDependencyJson, ReorderResponse, ReorderResultJson, OUTPUT_VERSION,
+};
+use crate::plain;
+use crate::stack::{Stack, StackEntry};
 
 /// Options for the reorder command
 #[derive(Debug, Default)]
@@ -21,14 +27,24 @@ pub struct ReorderOptions {
     pub order: Option<String>,
     /// If true, disable TUI and use editor fallback
     pub no_tui: bool,
-    /// If true, override the immutability check
+    /// Pick the order interactively (TUI, or editor with `no_tui`). This is
+    /// also what happens when no `order` is given.
+    pub interactive: bool,
+    /// If true, override the immutability check and reorder even when the
+    /// new order breaks a line dependency
     pub force: bool,
+    /// Check the new order against the dependencies between entries
+    /// without rewriting anything
+    pub dry_run: bool,
+    /// Print the dependency graph (and what `order` breaks) as JSON. Never
+    /// prompts nor rewrites.
+    pub json: bool,
 }
 
 /// Run the reorder command
 pub fn run(options: ReorderOptions) -> Result<()> {
     let repo = git::open_repo()?;
-    let config = Config::load_with_global(repo.commondir())?;
+    let config = Config::load(repo.commondir())?;
 
     // Acquire the operation lock now but defer writing the op-log record
     // until all validation (including the immutability guard) passes so
@@ -37,7 +53,9 @@ pub fn run(options: ReorderOptions) -> Result<()> {
     let _lock = git::acquire_operation_lock(&repo, "reorder")?;
 
     // Require clean working directory
-    git::require_clean_working_directory(&repo)?;
+    if !options.dry_run && !options.json {
+        git::require_clean_working_directory(&repo)?;
+    }
 
     // Load stack
     let mut stack = Stack::load(&repo, &config)?;
@@ -45,13 +63,23 @@ pub fn run(options: ReorderOptions) -> Result<()> {
     // squash-merged PRs that base-ancestor would otherwise miss).
     immutability::refresh_mr_state_for_guard(&repo, &mut stack);
 
+    if options.json {
+        let new_order = match options.order.as_deref() {
+            Some(order_str) => parse_order_from_string(order_str, &stack)?,
+            None => None,
+        };
+        let dependencies = reorder_deps::analyze(&repo, &stack)?;
+        print_json(&stack, &dependencies, new_order.as_deref());
+        return Ok(());
+    }
+
     if stack.len() < 2 {
         println!("{}", style("Need at least 2 commits to reorder.").dim());
         return Ok(());
     }
 
     // Get the new order - from CLI, TUI, or editor
-    let new_order = if let Some(order_str) = options.order {
+    let new_order = if let Some(order_str) = options.order.filter(|_| !options.interactive) {
         parse_order_from_string(&order_str, &stack)?
     } else {
         let is_tty = atty::is(atty::Stream::Stdin) && atty::is(atty::Stream::Stdout);
@@ -88,6 +116,37 @@ pub fn run(options: ReorderOptions) -> Result<()> {
     // from the lowest changed position upward, so check that span. Dropped
     // commits count as "changes" too (anything missing from new_order needs
     // to be included in the check).
+    // Dependency check: an entry that ends up below (or without) the entry
+    // whose lines it changes cannot apply.
+    let dependencies = reorder_deps::analyze(&repo, &stack)?;
+    let broken = reorder_deps::broken(&dependencies, &stack, &new_order);
+    if options.dry_run {
+        print_dry_run(&broken);
+        return Ok(());
+    }
+    let conflicts: Vec<&Dependency> = broken
+        .iter()
+        .copied()
+        .filter(|dep| dep.kind == DependencyKind::Lines)
+        .collect();
+    if !conflicts.is_empty() && !options.force {
+        return Err(GgError::Other(format!(
+            "This order will conflict:\n{}\nUse --force to reorder anyway (and resolve the conflicts), or --dry-run to review the order.",
+            conflicts
+                .iter()
+                .map(|dep| format!("  {}", describe(dep)))
+                .collect::<Vec<_>>()
+                .join("\n")
+        )));
+    }
+    for dep in &broken {
+        println!(
+            "{} {}",
+            style(plain::warn()).yellow(),
+            style(describe(dep)).yellow()
+        );
+    }
+
     let min_change = lowest_change_position(&old_order, &new_order);
     if let Some(min) = min_change {
         let targets: Vec<usize> = (min..=stack.len()).collect();
@@ -96,6 +155,8 @@ pub fn run(options: ReorderOptions) -> Result<()> {
         immutability::guard(report, options.force)?;
     }
 
+    super::snapshot::backup_stack(&repo, &stack)?;
+
     // All validation passed — write the Pending op-log record immediately
     // before the actual rebase.
     let guard = git::begin_recorded_op(
@@ -123,7 +184,8 @@ pub fn run(options: ReorderOptions) -> Result<()> {
     }
 
     // Perform the rebase with the new order
-    if let Err(e) = perform_reorder(&repo, &stack, &new_order) {
+    let remote = config.get_remote_for_stack(&stack.name);
+    if let Err(e) = perform_reorder(&repo, remote, &stack, &new_order) {
         if matches!(e, GgError::RebaseConflict) {
             let _ = operations::remember_interrupted_rebase_operation(&repo, guard.id());
         }
@@ -160,6 +222,70 @@ pub fn run(options: ReorderOptions) -> Result<()> {
     Ok(())
 }
 
+/// One line about a broken dependency.
+fn describe(dep: &Dependency) -> String {
+    let what = match dep.kind {
+        DependencyKind::Lines => "changes lines from",
+        DependencyKind::Files => "edits the same files as",
+    };
+    format!(
+        "#{} {} #{} ({})",
+        dep.position,
+        what,
+        dep.depends_on,
+        dep.paths.join(", ")
+    )
+}
+
+/// `--dry-run`: report what the order breaks without rewriting.
+fn print_dry_run(broken: &[&Dependency]) {
+    if broken.is_empty() {
+        println!(
+            "{} The new order keeps every entry above the ones it builds on.",
+            style("OK").green().bold()
+        );
+        return;
+    }
+    for dep in broken {
+        let label = match dep.kind {
+            DependencyKind::Lines => style("conflict").red().bold(),
+            DependencyKind::Files => style("may conflict").yellow(),
+        };
+        println!("{} {}", label, describe(dep));
+    }
+}
+
+fn print_json(stack: &Stack, dependencies: &[Dependency], new_order: Option<&[String]>) {
+    let to_json = |dep: &Dependency| ReorderDependencyJson {
+        position: dep.position,
+        depends_on: dep.depends_on,
+        kind: dep.kind.as_str().to_string(),
+        paths: dep.paths.clone(),
+    };
+    let conflicts = new_order
+        .map(|order| reorder_deps::broken(dependencies, stack, order))
+        .unwrap_or_default();
+    output::print_json(&ReorderResponse {
+        version: OUTPUT_VERSION,
+        reorder: ReorderResultJson {
+            order: new_order.map(|order| {
+                order
+                    .iter()
+                    .filter_map(|sha| {
+                        stack
+                            .entries
+                            .iter()
+                            .find(|e| &e.short_sha == sha)
+                            .map(|e| e.position)
+                    })
+                    .collect()
+            }),
+            dependencies: dependencies.iter().map(to_json).collect(),
+            conflicts: conflicts.into_iter().map(to_json).collect(),
+        },
+    });
+}
+
 /// Find the lowest (1-indexed) stack position whose SHA differs between the
 /// old and new orderings. If positions match up to the shorter length but
 /// the new order is shorter (commits dropped from the tail), the first
@@ -221,21 +347,10 @@ fn parse_order_from_string(order_str: &str, stack: &Stack) -> Result<Option<Vec<
         result
     } else {
         // Parse as SHAs
-        let mut result = Vec::new();
-        for part in &parts {
-            // Find matching entry
-            let entry = stack
-                .entries
-                .iter()
-                .find(|e| {
-                    e.short_sha.starts_with(part)
-                        || part.starts_with(&e.short_sha)
-                        || e.gg_id.as_ref().map(|id| id == part).unwrap_or(false)
-                })
-                .ok_or_else(|| GgError::Other(format!("Unknown commit: '{}'", part)))?;
-            result.push(entry.short_sha.clone());
-        }
-        result
+        parts
+            .iter()
+            .map(|part| find_entry(stack, part).map(|entry| entry.short_sha.clone()))
+            .collect::<Result<_>>()?
     };
 
     // Validate all commits are accounted for
@@ -247,9 +362,40 @@ fn parse_order_from_string(order_str: &str, stack: &Stack) -> Result<Option<Vec<
         )));
     }
 
-    // Check for duplicates
+    check_duplicates(&new_order)?;
+    Ok(Some(new_order))
+}
+
+/// The entry named by a SHA prefix or GG-ID. A prefix shared by several
+/// entries is an error rather than a silent pick.
+fn find_entry<'a>(stack: &'a Stack, part: &str) -> Result<&'a StackEntry> {
+    let matches: Vec<&StackEntry> = stack
+        .entries
+        .iter()
+        .filter(|e| {
+            e.short_sha.starts_with(part)
+                || part.starts_with(&e.short_sha)
+                || e.gg_id.as_deref() == Some(part)
+        })
+        .collect();
+    match matches.as_slice() {
+        [entry] => Ok(entry),
+        [] => Err(GgError::Other(format!("Unknown commit: '{}'", part))),
+        _ => Err(GgError::Other(format!(
+            "Ambiguous commit: '{}' matches {}",
+            part,
+            matches
+                .iter()
+                .map(|e| e.short_sha.as_str())
+                .collect::<Vec<_>>()
+                .join(", ")
+        ))),
+    }
+}
+
+fn check_duplicates(order: &[String]) -> Result<()> {
     let mut seen = std::collections::HashSet::new();
-    for sha in &new_order {
+    for sha in order {
         if !seen.insert(sha) {
             return Err(GgError::Other(format!(
                 "Duplicate commit in order: {}",
@@ -257,8 +403,7 @@ fn parse_order_from_string(order_str: &str, stack: &Stack) -> Result<Option<Vec<
             )));
         }
     }
-
-    Ok(Some(new_order))
+    Ok(())
 }
 
 /// Get order from interactive TUI
@@ -297,57 +442,41 @@ fn get_order_from_editor(stack: &Stack) -> Result<Option<Vec<String>>> {
         .edit(&todo_content)
         .map_err(|e| GgError::Other(format!("Editor failed: {}", e)))?;
 
-    let edited = match edited {
-        Some(content) => content,
-        None => return Ok(None),
-    };
+    match edited {
+        Some(content) => parse_order_from_editor(&content, stack).map(Some),
+        None => Ok(None),
+    }
+}
 
-    // Parse the edited content
-    let new_order: Vec<String> = edited
+/// Parse the edited todo list: the first word of each non-comment line is
+/// a SHA prefix or GG-ID. Missing entries are dropped.
+fn parse_order_from_editor(content: &str, stack: &Stack) -> Result<Vec<String>> {
+    let new_order: Vec<String> = content
         .lines()
-        .filter(|line| !line.trim().starts_with('#') && !line.trim().is_empty())
+        .map(str::trim)
+        .filter(|line| !line.starts_with('#') && !line.is_empty())
         .filter_map(|line| line.split_whitespace().next())
-        .filter(|sha| !sha.is_empty())
-        .map(|s| s.to_string())
-        .collect();
+        .map(|part| find_entry(stack, part).map(|entry| entry.short_sha.clone()))
+        .collect::<Result<_>>()?;
 
     if new_order.is_empty() {
         return Err(GgError::Other(
             "Cannot drop all commits. At least one must remain.".to_string(),
         ));
     }
-
-    // Validate all SHAs from editor match stack entries
-    let valid_shas: Vec<&str> = stack.entries.iter().map(|e| e.short_sha.as_str()).collect();
-    for sha in &new_order {
-        let is_valid = valid_shas
-            .iter()
-            .any(|s| s.starts_with(sha.as_str()) || sha.starts_with(*s));
-        if !is_valid {
-            return Err(GgError::Other(format!("Unknown commit SHA: {}", sha)));
-        }
-    }
-
-    // Check for duplicates
-    let mut seen = std::collections::HashSet::new();
-    for sha in &new_order {
-        if !seen.insert(sha) {
-            return Err(GgError::Other(format!(
-                "Duplicate commit in order: {}",
-                sha
-            )));
-        }
-    }
-
-    Ok(Some(new_order))
+    check_duplicates(&new_order)?;
+    Ok(new_order)
 }
 
 /// Perform the actual reorder via git rebase
-fn perform_reorder(repo: &git2::Repository, stack: &Stack, new_order: &[String]) -> Result<()> {
+fn perform_reorder(
+    repo: &git2::Repository,
+    remote: &str,
+    stack: &Stack,
+    new_order: &[String],
+) -> Result<()> {
     // First, start a rebase
-    let base_ref = repo
-        .revparse_single(&stack.base)
-        .or_else(|_| repo.revparse_single(&format!("origin/{}", stack.base)))?;
+    let base_ref = git::resolve_base(repo, remote, &stack.base)?;
 
     // Build the rebase todo
     let mut rebase_todo = String::new();
@@ -386,7 +515,7 @@ fn perform_reorder(repo: &git2::Repository, stack: &Stack, new_order: &[String])
     }
 
     // Run the rebase
-    let output = std::process::Command::new("git")
+    let output = hooks::git_command(GitRewrite::Replay)
         .env("GIT_SEQUENCE_EDITOR", script_file.to_str().unwrap())
         .args(["rebase", "-i", &base_ref.id().to_string()])
         .output()?;
@@ -452,6 +581,9 @@ mod tests {
             position: pos,
             in_merge_train: false,
             merge_train_position: None,
+            unresolved_threads: None,
+            annotations: Default::default(),
+            signed: false,
         };
         Stack {
             name: "test".to_string(),
@@ -497,6 +629,24 @@ mod tests {
         );
     }
 
+    #[test]
+    fn test_parse_order_from_editor_canonicalizes_and_validates() {
+        let stack = make_test_stack();
+        let order = parse_order_from_editor(
+            "# comment\nccc3 c-0000003 commit 3\n\nc-0000001 commit 1\n",
+            &stack,
+        )
+        .unwrap();
+        assert_eq!(order, vec!["ccc3333".to_string(), "aaa1111".to_string()]);
+
+        let err = parse_order_from_editor("ccc3333\nccc3\n", &stack).unwrap_err();
+        assert!(err.to_string().contains("Duplicate"));
+        let err = parse_order_from_editor("zzz9999\n", &stack).unwrap_err();
+        assert!(err.to_string().contains("Unknown commit"));
+        let err = parse_order_from_editor("# all gone\n", &stack).unwrap_err();
+        assert!(err.to_string().contains("At least one"));
+    }
+
     #[test]
     fn lowest_change_position_returns_none_for_identical_orderings() {
         let old_order = ["aaa1111", "bbb2222", "ccc3333"];
@@ -0,0 +1,246 @@
+//! Dependencies between stack entries, so `gg reorder` can tell an order
+//! that will conflict before it rewrites anything.
+//!
+//! An entry depends on an earlier one when it changes lines that entry
+//! wrote (blame of the lines it replaces, or of the lines around a pure
+//! insertion, as for `gg absorb -i`): moving it below that entry, or
+//! dropping that entry, makes its patch conflict. Entries that only touch
+//! the same files may or may not conflict.
+
+use std::collections::{BTreeMap, BTreeSet, HashMap};
+use std::path::Path;
+
+use git2::{BlameOptions, Delta, DiffOptions, Oid, Patch, Repository};
+
+use crate::error::Result;
+use crate::stack::Stack;
+
+/// How sure a dependency is to cause a conflict when broken.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+pub enum DependencyKind {
+    /// Changes lines written by the other entry: always conflicts
+    Lines,
+    /// Only edits the same files: may conflict
+    Files,
+}
+
+impl DependencyKind {
+    pub fn as_str(self) -> &'static str {
+        match self {
+            DependencyKind::Lines => "lines",
+            DependencyKind::Files => "files",
+        }
+    }
+}
+
+/// Entry at `position` builds on the entry at `depends_on` (lower).
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Dependency {
+    pub position: usize,
+    pub depends_on: usize,
+    pub kind: DependencyKind,
+    pub paths: Vec<String>,
+}
+
+/// Every dependency between entries of `stack`, ordered by position.
+pub fn analyze(repo: &Repository, stack: &Stack) -> Result<Vec<Dependency>> {
+    let positions: HashMap<Oid, usize> = stack
+        .entries
+        .iter()
+        .map(|entry| (entry.oid, entry.position))
+        .collect();
+    let oldest = stack
+        .entries
+        .first()
+        .and_then(|entry| repo.find_commit(entry.oid).ok())
+        .and_then(|commit| commit.parent_id(0).ok());
+
+    let mut touched: Vec<BTreeSet<String>> = Vec::with_capacity(stack.len());
+    let mut lines: BTreeMap<(usize, usize), BTreeSet<String>> = BTreeMap::new();
+    for entry in &stack.entries {
+        let commit = repo.find_commit(entry.oid)?;
+        let Ok(parent) = commit.parent(0) else {
+            touched.push(BTreeSet::new());
+            continue;
+        };
+        let mut opts = DiffOptions::new();
+        opts.context_lines(0).interhunk_lines(0);
+        let diff = repo.diff_tree_to_tree(
+            Some(&parent.tree()?),
+            Some(&commit.tree()?),
+            Some(&mut opts),
+        )?;
+
+        let mut paths = BTreeSet::new();
+        for (idx, delta) in diff.deltas().enumerate() {
+            let path = delta
+                .old_file()
+                .path()
+                .or_else(|| delta.new_file().path())
+                .map(|p| p.to_string_lossy().to_string())
+                .unwrap_or_default();
+            paths.insert(path.clone());
+            // Added files have no earlier lines to depend on
+            if !matches!(delta.status(), Delta::Modified | Delta::Deleted) {
+                continue;
+            }
+            let Some(patch) = Patch::from_diff(&diff, idx)? else {
+                continue;
+            };
+
+            let mut blame_opts = BlameOptions::new();
+            blame_opts.newest_commit(parent.id());
+            if let Some(oldest) = oldest {
+                blame_opts.oldest_commit(oldest);
+            }
+            let Ok(blame) = repo.blame_file(Path::new(&path), Some(&mut blame_opts)) else {
+                continue;
+            };
+            for h in 0..patch.num_hunks() {
+                let (hunk, _) = patch.hunk(h)?;
+                let blamed: Vec<u32> = if hunk.old_lines() > 0 {
+                    (hunk.old_start()..hunk.old_start() + hunk.old_lines()).collect()
+                } else {
+                    vec![hunk.old_start(), hunk.old_start() + 1]
+                };
+                for line in blamed.into_iter().filter(|&line| line > 0) {
+                    let owner = blame
+                        .get_line(line as usize)
+                        .and_then(|b| positions.get(&b.final_commit_id()).copied());
+                    if let Some(owner) = owner.filter(|&owner| owner < entry.position) {
+                        lines
+                            .entry((entry.position, owner))
+                            .or_default()
+                            .insert(path.clone());
+                    }
+                }
+            }
+        }
+        touched.push(paths);
+    }
+
+    let mut dependencies = Vec::new();
+    for (i, later) in touched.iter().enumerate() {
+        for (j, earlier) in touched.iter().enumerate().take(i) {
+            let (position, depends_on) = (i + 1, j + 1);
+            if let Some(paths) = lines.remove(&(position, depends_on)) {
+                dependencies.push(Dependency {
+                    position,
+                    depends_on,
+                    kind: DependencyKind::Lines,
+                    paths: paths.into_iter().collect(),
+                });
+                continue;
+            }
+            let shared: Vec<String> = later.intersection(earlier).cloned().collect();
+            if !shared.is_empty() {
+                dependencies.push(Dependency {
+                    position,
+                    depends_on,
+                    kind: DependencyKind::Files,
+                    paths: shared,
+                });
+            }
+        }
+    }
+    Ok(dependencies)
+}
+
+/// Dependencies `new_order` (short SHAs, bottom first) breaks: the entry
+/// ends up below what it depends on, or what it depends on is dropped.
+pub fn broken<'a>(
+    dependencies: &'a [Dependency],
+    stack: &Stack,
+    new_order: &[String],
+) -> Vec<&'a Dependency> {
+    let index_of = |position: usize| {
+        let sha = &stack.get_entry_by_position(position)?.short_sha;
+        new_order.iter().position(|s| s == sha)
+    };
+    dependencies
+        .iter()
+        .filter(
+            |dep| match (index_of(dep.position), index_of(dep.depends_on)) {
+                (Some(at), Some(base)) => at < base,
+                (Some(_), None) => true,
+                (None, _) => false,
+            },
+        )
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn commit(repo: &Repository, files: &[(&str, &str)], message: &str) -> Oid {
+        let mut index = repo.index().unwrap();
+        for (path, content) in files {
+            std::fs::write(repo.workdir().unwrap().join(path), content).unwrap();
+            index.add_path(Path::new(path)).unwrap();
+        }
+        index.write().unwrap();
+        let tree = repo.find_tree(index.write_tree().unwrap()).unwrap();
+        let sig = git2::Signature::now("Test", "test@example.com").unwrap();
+        let parent = repo.head().ok().map(|h| h.peel_to_commit().unwrap());
+        let parents: Vec<&git2::Commit> = parent.iter().collect();
+        repo.commit(Some("HEAD"), &sig, &sig, message, &tree, &parents)
+            .unwrap()
+    }
+
+    #[test]
+    fn detects_line_and_file_dependencies() {
+        let dir = tempfile::tempdir().unwrap();
+        let repo = Repository::init(dir.path()).unwrap();
+        commit(&repo, &[("a.txt", "base\n"), ("b.txt", "b\n")], "Base");
+        let one = commit(&repo, &[("a.txt", "base\none\n")], "One");
+        let two = commit(&repo, &[("b.txt", "b\n\n\ntwo\n")], "Two");
+        let three = commit(&repo, &[("a.txt", "base\nONE\n")], "Three");
+        let four = commit(&repo, &[("b.txt", "B\n\n\ntwo\n")], "Four");
+        let entries = [one, two, three, four]
+            .iter()
+            .enumerate()
+            .map(|(i, oid)| {
+                crate::stack::StackEntry::from_commit(&repo.find_commit(*oid).unwrap(), i + 1)
+            })
+            .collect();
+        let stack = Stack {
+            name: "stack".to_string(),
+            username: "me".to_string(),
+            base: "main".to_string(),
+            entries,
+            current_position: None,
+        };
+
+        let deps = analyze(&repo, &stack).unwrap();
+        assert_eq!(
+            deps,
+            vec![
+                Dependency {
+                    position: 3,
+                    depends_on: 1,
+                    kind: DependencyKind::Lines,
+                    paths: vec!["a.txt".to_string()],
+                },
+                Dependency {
+                    position: 4,
+                    depends_on: 2,
+                    kind: DependencyKind::Files,
+                    paths: vec!["b.txt".to_string()],
+                },
+            ]
+        );
+
+        let sha = |p: usize| stack.entries[p - 1].short_sha.clone();
+        let swapped = vec![sha(3), sha(1), sha(4), sha(2)];
+        let broken_deps: Vec<usize> = broken(&deps, &stack, &swapped)
+            .iter()
+            .map(|d| d.position)
+            .collect();
+        assert_eq!(broken_deps, vec![3, 4]);
+
+        // Dropping #1 breaks #3; keeping it below #3 does not
+        assert_eq!(broken(&deps, &stack, &[sha(2), sha(3), sha(4)]).len(), 1);
+        assert!(broken(&deps, &stack, &[sha(2), sha(1), sha(3), sha(4)]).is_empty());
+    }
+}
@@ -7,10 +7,12 @@ use console::style;
 use crate::config::Config;
 use crate::error::{GgError, Result};
 use crate::git;
+use crate::hooks::{self, GitRewrite};
 use crate::operations::{self, OperationKind, SnapshotScope};
 use crate::output::{
     print_json, RestackResponse, RestackResultJson, RestackStepJson, OUTPUT_VERSION,
 };
+use crate::plain;
 use crate::stack::{self, Stack};
 
 /// Options for the restack command
@@ -203,7 +205,7 @@ fn integrate_unintegrated(
         } else {
             println!(
                 "{} Would integrate {} commit(s) at HEAD ({} {}) into the stack at position {}.",
-                style("→").cyan().bold(),
+                style(plain::arrow()).cyan().bold(),
                 unintegrated.count,
                 style(&unintegrated.short_sha).yellow(),
                 unintegrated.subject,
@@ -228,7 +230,7 @@ fn integrate_unintegrated(
     // whether `head_oid` descends from `original_oid` or merely replaces it, the
     // commits above `original_oid` are replayed onto `head_oid` identically.
     // git rebase --onto <new_base> <old_base> <branch>
-    let output = std::process::Command::new("git")
+    let output = hooks::git_command(GitRewrite::Replay)
         .args([
             "rebase",
             "--onto",
@@ -298,7 +300,7 @@ fn integrate_unintegrated(
     } else {
         println!(
             "{} Integrated {} commit(s) into stack {:?}; HEAD stays on {} {}",
-            style("✓").green().bold(),
+            style(plain::ok()).green().bold(),
             unintegrated.count,
             reloaded.name,
             style(&new_short_sha).yellow(),
@@ -320,7 +322,7 @@ fn integrate_unintegrated(
 /// Run the restack command.
 pub fn run(options: RestackOptions) -> Result<()> {
     let repo = git::open_repo()?;
-    let config = Config::load_with_global(repo.commondir())?;
+    let config = Config::load(repo.commondir())?;
 
     // Acquire operation lock (recording deferred until after validation)
     let _lock = git::acquire_operation_lock(&repo, "restack")?;
@@ -395,7 +397,7 @@ pub fn run(options: RestackOptions) -> Result<()> {
         } else {
             println!(
                 "{} Stack is already consistent ({} commits, no ancestry drift)",
-                style("✓").green().bold(),
+                style(plain::ok()).green().bold(),
                 total
             );
         }
@@ -419,7 +421,7 @@ pub fn run(options: RestackOptions) -> Result<()> {
         } else {
             println!(
                 "{} Restack plan for stack {:?} ({} commits):",
-                style("→").cyan().bold(),
+                style(plain::arrow()).cyan().bold(),
                 stack_name,
                 total
             );
@@ -430,7 +432,13 @@ pub fn run(options: RestackOptions) -> Result<()> {
                     RestackAction::Reattach => {
                         let cur = step.current_parent.as_deref().unwrap_or("(none)");
                         let exp = step.expected_parent.as_deref().unwrap_or("(none)");
-                        format!("{}    {} → {}", style("reattach").yellow(), cur, exp)
+                        format!(
+                            "{}    {} {} {}",
+                            style("reattach").yellow(),
+                            cur,
+                            plain::arrow(),
+                            exp
+                        )
                     }
                 };
                 println!(
@@ -465,12 +473,11 @@ pub fn run(options: RestackOptions) -> Result<()> {
 
     // Execute: single git rebase -i
     // Determine base ref for the rebase
+    let remote = config.get_remote_for_stack(&stack.name);
     let base_oid = if let Some(from_pos) = from_position {
         if from_pos <= 1 {
             // --from 1 is equivalent to full restack
-            let base_ref = repo
-                .revparse_single(&stack.base)
-                .or_else(|_| repo.revparse_single(&format!("origin/{}", stack.base)))?;
+            let base_ref = git::resolve_base(&repo, remote, &stack.base)?;
             base_ref.id()
         } else {
             // Use the commit at from_pos - 1 as the base
@@ -480,9 +487,7 @@ pub fn run(options: RestackOptions) -> Result<()> {
             base_entry.oid
         }
     } else {
-        let base_ref = repo
-            .revparse_single(&stack.base)
-            .or_else(|_| repo.revparse_single(&format!("origin/{}", stack.base)))?;
+        let base_ref = git::resolve_base(&repo, remote, &stack.base)?;
         base_ref.id()
     };
 
@@ -514,7 +519,7 @@ pub fn run(options: RestackOptions) -> Result<()> {
         std::fs::set_permissions(&script_file, perms)?;
     }
 
-    let output = std::process::Command::new("git")
+    let output = hooks::git_command(GitRewrite::Replay)
         .env("GIT_SEQUENCE_EDITOR", script_file.to_str().unwrap())
         .args(["rebase", "-i", &base_oid.to_string()])
         .output()?;
@@ -569,7 +574,7 @@ pub fn run(options: RestackOptions) -> Result<()> {
     } else {
         println!(
             "{} Restacked {} commit(s) in stack {:?}",
-            style("✓").green().bold(),
+            style(plain::ok()).green().bold(),
             reattach_count,
             stack_name
         );
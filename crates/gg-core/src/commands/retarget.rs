@@ -0,0 +1,284 @@
+//! `gg retarget-prs` - Validate and repair the PR/MR base-branch chain
+//!
+//! In a stack, PR N must target the entry branch of the nearest open
+//! predecessor (or the stack base for the first entry). Manual edits on the
+//! provider can break that chain; this command reports every PR/MR whose
+//! target drifted and retargets it.
+
+use console::style;
+
+use crate::config::Config;
+use crate::error::{GgError, Result};
+use crate::git;
+use crate::operations::{OperationKind, SnapshotScope};
+use crate::output::{print_json, RetargetEntryJson, RetargetResponse, OUTPUT_VERSION};
+use crate::plain;
+use crate::provider::{PrState, Provider};
+use crate::stack::Stack;
+
+use super::sync::compute_target_branch;
+
+/// What happened to one PR/MR.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum RetargetAction {
+    /// Already targets the expected branch.
+    Ok,
+    /// Target was wrong and has been fixed.
+    Retargeted,
+    /// Target is wrong; `--dry-run` left it alone.
+    WouldRetarget,
+    /// Merged/closed, or its expected parent has not been synced yet.
+    Skipped,
+    /// The provider refused the update.
+    Error,
+}
+
+impl RetargetAction {
+    pub fn as_str(self) -> &'static str {
+        match self {
+            RetargetAction::Ok => "ok",
+            RetargetAction::Retargeted => "retargeted",
+            RetargetAction::WouldRetarget => "would_retarget",
+            RetargetAction::Skipped => "skipped",
+            RetargetAction::Error => "error",
+        }
+    }
+}
+
+/// Decide what to do with a PR/MR given its current and expected target.
+pub fn plan_action(current: Option<&str>, expected: &str, dry_run: bool) -> RetargetAction {
+    if current == Some(expected) {
+        RetargetAction::Ok
+    } else if dry_run {
+        RetargetAction::WouldRetarget
+    } else {
+        RetargetAction::Retargeted
+    }
+}
+
+/// Run the retarget-prs command
+pub fn run(dry_run: bool, json: bool) -> Result<()> {
+    let repo = git::open_repo()?;
+    let config = Config::load(repo.commondir())?;
+    let stack = Stack::load_active(&repo, &config)?;
+
+    let provider = Provider::for_stack(&repo, &config, &stack.name)?;
+    provider.check_installed()?;
+    provider.check_auth()?;
+
+    let (_lock, mut guard) = if dry_run {
+        (None, None)
+    } else {
+        let (lock, guard) = git::acquire_operation_lock_and_record(
+            &repo,
+            &config,
+            OperationKind::Retarget,
+            std::env::args().skip(1).collect(),
+            Some(stack.name.clone()),
+            SnapshotScope::ActiveStack(&stack),
+        )?;
+        (Some(lock), Some(guard))
+    };
+
+    // Fetch every PR up front: the expected target of entry N depends on
+    // whether its predecessors are still open.
+    let mut infos = Vec::with_capacity(stack.entries.len());
+    for entry in &stack.entries {
+        infos.push(match entry.mr_number {
+            Some(pr_num) => Some(provider.get_pr_info(pr_num)?),
+            None => None,
+        });
+    }
+    let entry_is_closed: Vec<bool> = infos
+        .iter()
+        .map(|info| {
+            info.as_ref()
+                .is_some_and(|i| matches!(i.state, PrState::Merged | PrState::Closed))
+        })
+        .collect();
+
+    let pr_base = stack.pr_base(&repo, &config)?;
+    let mut results = Vec::new();
+    let mut touched_remote = false;
+    for (i, entry) in stack.entries.iter().enumerate() {
+        let (Some(pr_num), Some(info)) = (entry.mr_number, infos[i].as_ref()) else {
+            continue;
+        };
+        let expected = compute_target_branch(i, &pr_base, &stack.entries, &entry_is_closed, &stack);
+        let current = info.base_branch.clone();
+
+        let parent_unsynced = (0..i)
+            .rev()
+            .find(|&j| !entry_is_closed[j])
+            .is_some_and(|j| stack.entries[j].mr_number.is_none());
+
+        let mut error = None;
+        let action = if entry_is_closed[i] || parent_unsynced {
+            RetargetAction::Skipped
+        } else {
+            match plan_action(current.as_deref(), &expected, dry_run) {
+                RetargetAction::Retargeted => match provider.update_pr_base(pr_num, &expected) {
+                    Ok(()) => {
+                        touched_remote = true;
+                        if let Some(guard) = guard.as_mut() {
+                            guard.mark_touched_remote();
+                        }
+                        RetargetAction::Retargeted
+                    }
+                    Err(e) => {
+                        error = Some(e.to_string());
+                        RetargetAction::Error
+                    }
+                },
+                other => other,
+            }
+        };
+
+        results.push(RetargetEntryJson {
+            position: entry.position,
+            pr_number: pr_num,
+            title: entry.title.clone(),
+            current_base: current,
+            expected_base: expected,
+            action: action.as_str().to_string(),
+            error,
+        });
+    }
+
+    if let Some(guard) = guard {
+        guard.finalize_with_scope(
+            &repo,
+            &config,
+            SnapshotScope::ActiveStack(&stack),
+            vec![],
+            touched_remote,
+        )?;
+    }
+
+    let failed = results.iter().any(|r| r.action == "error");
+
+    if json {
+        print_json(&RetargetResponse {
+            version: OUTPUT_VERSION,
+            stack: stack.name.clone(),
+            dry_run,
+            entries: results,
+        });
+    } else {
+        print_human(&provider, &results, dry_run);
+    }
+
+    if failed {
+        return Err(GgError::Silenced);
+    }
+    Ok(())
+}
+
+fn print_human(provider: &Provider, results: &[RetargetEntryJson], dry_run: bool) {
+    if results.is_empty() {
+        println!(
+            "{}",
+            style(format!(
+                "No synced {}s in this stack. Run `gg sync` first.",
+                provider.pr_label()
+            ))
+            .dim()
+        );
+        return;
+    }
+
+    for r in results {
+        let pr = format!(
+            "{} {}{}",
+            provider.pr_label(),
+            provider.pr_number_prefix(),
+            r.pr_number
+        );
+        let current = r.current_base.as_deref().unwrap_or("?");
+        match r.action.as_str() {
+            "ok" => println!(
+                "  {} [{}] {} -> {}",
+                style(plain::ok()).green(),
+                r.position,
+                pr,
+                style(&r.expected_base).dim()
+            ),
+            "retargeted" => println!(
+                "  {} [{}] {} retargeted: {} -> {}",
+                style("OK").green().bold(),
+                r.position,
+                pr,
+                style(current).dim(),
+                style(&r.expected_base).cyan()
+            ),
+            "would_retarget" => println!(
+                "  {} [{}] {} targets {}, expected {}",
+                style(plain::fail()).yellow(),
+                r.position,
+                pr,
+                style(current).dim(),
+                style(&r.expected_base).cyan()
+            ),
+            "skipped" => println!(
+                "  {} [{}] {} skipped (closed, or parent not synced)",
+                style("-").dim(),
+                r.position,
+                pr
+            ),
+            _ => println!(
+                "  {} [{}] {}: {}",
+                style("Error:").red().bold(),
+                r.position,
+                pr,
+                r.error.as_deref().unwrap_or("unknown error")
+            ),
+        }
+    }
+
+    let pending = results
+        .iter()
+        .filter(|r| r.action == "would_retarget")
+        .count();
+    if dry_run && pending > 0 {
+        println!();
+        println!(
+            "{}",
+            style(format!(
+                "Run `gg retarget-prs` to fix {} {}(s).",
+                pending,
+                provider.pr_label()
+            ))
+            .dim()
+        );
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn plan_action_matches_expected_target() {
+        assert_eq!(plan_action(Some("main"), "main", false), RetargetAction::Ok);
+        assert_eq!(plan_action(Some("main"), "main", true), RetargetAction::Ok);
+    }
+
+    #[test]
+    fn plan_action_fixes_or_reports_drift() {
+        assert_eq!(
+            plan_action(Some("main"), "u/s--c-1", false),
+            RetargetAction::Retargeted
+        );
+        assert_eq!(
+            plan_action(Some("main"), "u/s--c-1", true),
+            RetargetAction::WouldRetarget
+        );
+        assert_eq!(plan_action(None, "main", false), RetargetAction::Retargeted);
+    }
+
+    #[test]
+    fn action_strings_are_snake_case() {
+        assert_eq!(RetargetAction::WouldRetarget.as_str(), "would_retarget");
+        assert_eq!(RetargetAction::Retargeted.as_str(), "retargeted");
+    }
+}
@@ -0,0 +1,145 @@
+//! `gg review` - Unresolved review feedback for one stack entry
+//!
+//! Shows the open review threads of an entry's PR/MR grouped by file and
+//! ordered by line, with every comment in full, so feedback can be read
+//! (or fed to an editor through `--json`) next to the code it is about.
+
+use console::style;
+
+use crate::error::{GgError, Result};
+use crate::output::{print_json, ReviewFileJson, ReviewResponse, OUTPUT_VERSION};
+use crate::provider::ReviewThread;
+use crate::stack;
+
+use super::comments::{group_by_file, load_stack_and_provider, thread_json, GENERAL_THREADS};
+
+/// Keep unresolved threads, ordered by file and then line. General threads
+/// (no line) come first within their file.
+pub fn unresolved_by_line(mut threads: Vec<ReviewThread>) -> Vec<ReviewThread> {
+    threads.retain(|t| !t.resolved);
+    threads.sort_by(|a, b| (&a.path, a.line).cmp(&(&b.path, b.line)));
+    threads
+}
+
+/// Run `gg review [target]`
+pub fn run(target: Option<String>, json: bool) -> Result<()> {
+    let (stack, provider) = load_stack_and_provider()?;
+
+    let position = match target.as_deref() {
+        Some(target) => stack::resolve_target(&stack, target)?,
+        None => stack.current_position.map(|p| p + 1).unwrap_or(stack.len()),
+    };
+    let entry = stack
+        .get_entry_by_position(position)
+        .ok_or_else(|| GgError::Other(format!("No entry at position {}", position)))?;
+    let pr_number = entry.mr_number.ok_or_else(|| {
+        GgError::Other(format!(
+            "#{} has no {} yet. Run `gg sync` first.",
+            position,
+            provider.pr_label()
+        ))
+    })?;
+
+    let threads = unresolved_by_line(provider.list_review_threads(pr_number)?);
+    let groups = group_by_file(&threads);
+
+    if json {
+        print_json(&ReviewResponse {
+            version: OUTPUT_VERSION,
+            position,
+            sha: entry.short_sha.clone(),
+            title: entry.title.clone(),
+            gg_id: entry.gg_id.clone(),
+            pr_number,
+            files: groups
+                .iter()
+                .map(|(path, threads)| ReviewFileJson {
+                    path: (*path != GENERAL_THREADS).then(|| path.to_string()),
+                    threads: threads.iter().map(|t| thread_json(t)).collect(),
+                })
+                .collect(),
+        });
+        return Ok(());
+    }
+
+    println!(
+        "[{}] {} {} ({}{})",
+        position,
+        style(&entry.short_sha).yellow(),
+        entry.title,
+        provider.pr_number_prefix(),
+        pr_number
+    );
+    if threads.is_empty() {
+        println!("{}", style("No unresolved review threads.").dim());
+        return Ok(());
+    }
+
+    for (path, file_threads) in groups {
+        println!();
+        println!("{}", style(path).cyan().bold());
+        for thread in file_threads {
+            let line = thread
+                .line
+                .map(|l| format!("L{}", l))
+                .unwrap_or_else(|| "—".to_string());
+            let outdated = if thread.outdated { " (outdated)" } else { "" };
+            println!(
+                "  {} {}{}",
+                style(line).bold(),
+                style(&thread.id).dim(),
+                style(outdated).dim()
+            );
+            for comment in &thread.comments {
+                let author = comment.author.as_deref().unwrap_or("ghost");
+                println!("    {}:", style(author).bold());
+                for body_line in comment.body.lines() {
+                    println!("      {}", body_line);
+                }
+            }
+        }
+    }
+    println!();
+    println!(
+        "{}",
+        style(format!(
+            "{} unresolved thread(s). Resolve with `gg comments resolve <id>`.",
+            threads.len()
+        ))
+        .dim()
+    );
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn thread(id: &str, path: Option<&str>, line: Option<u64>, resolved: bool) -> ReviewThread {
+        ReviewThread {
+            id: id.to_string(),
+            resolved,
+            outdated: false,
+            path: path.map(str::to_string),
+            line,
+            comments: vec![],
+        }
+    }
+
+    #[test]
+    fn keeps_unresolved_threads_sorted_by_file_and_line() {
+        let threads = vec![
+            thread("t1", Some("src/b.rs"), Some(40), false),
+            thread("t2", Some("src/a.rs"), Some(9), true),
+            thread("t3", Some("src/b.rs"), Some(3), false),
+            thread("t4", Some("src/a.rs"), None, false),
+            thread("t5", None, None, false),
+        ];
+        let ids: Vec<_> = unresolved_by_line(threads)
+            .into_iter()
+            .map(|t| t.id)
+            .collect();
+        assert_eq!(ids, vec!["t5", "t4", "t3", "t1"]);
+    }
+}
@@ -6,12 +6,16 @@ use std::process::{Command, Output, Stdio};
 use console::style;
 use git2::Oid;
 
+use crate::config::LintCommand;
 use crate::error::{GgError, Result};
 use crate::git;
+use crate::hooks::{self, GitRewrite};
 use crate::operations::{self, OperationKind, SnapshotScope};
 use crate::output::{
     self, RunCommandResult, RunCommitResult, RunResponse, RunResultJson, OUTPUT_VERSION,
 };
+use crate::plain;
+use crate::scope;
 use crate::stack::{Stack, StackEntry};
 
 /// How to handle working-tree changes after running commands on a commit.
@@ -87,6 +91,8 @@ pub struct RunOptions {
     pub commands: Vec<RunCommand>,
     /// How to handle file modifications.
     pub change_mode: ChangeMode,
+    /// Start at this commit position (1-indexed). None = the first commit.
+    pub from: Option<usize>,
     /// Stop at this commit position (1-indexed). None = current position or full stack.
     pub until: Option<usize>,
     /// Stop on first command failure instead of continuing.
@@ -98,10 +104,24 @@ pub struct RunOptions {
     /// Optional label for the header (e.g. "lint" instead of "run").
     pub header_label: Option<String>,
     /// Number of parallel jobs. 0 = auto (num CPUs), 1 = sequential.
-    /// Parallel only applies to ReadOnly mode.
+    /// Parallel applies to ReadOnly and Amend modes.
     pub jobs: usize,
+    /// Path globs amended changes are restricted to (empty = unrestricted).
+    /// Changes outside them are discarded instead of folded in.
+    pub scope: Vec<String>,
+    /// Path globs per command, in the order of `commands`. A command with
+    /// globs only runs on commits whose diff touches a matching file; a
+    /// missing or empty entry runs everywhere.
+    pub command_paths: Vec<Vec<String>>,
+    /// Reads commands a commit asks for itself (e.g. `GG-Lint:` trailers),
+    /// replacing `commands` and `command_paths` on that commit when it
+    /// returns some.
+    pub commit_commands: Option<CommitCommandsFn>,
 }
 
+/// See [`RunOptions::commit_commands`].
+pub type CommitCommandsFn = fn(&git2::Repository, Oid) -> Result<Option<Vec<LintCommand>>>;
+
 /// Raw result from running commands on the stack.
 pub struct RunResult {
     pub all_passed: bool,
@@ -123,7 +143,7 @@ pub fn execute(options: RunOptions) -> Result<bool> {
     let emit_json_output = options.emit_json_output;
 
     let repo = git::open_repo()?;
-    let config = crate::config::Config::load_with_global(repo.commondir())?;
+    let config = crate::config::Config::load(repo.commondir())?;
 
     // In Amend mode, `gg run` rewrites commits — acquire an operation lock
     // and record the op so `gg undo` can reverse it. Behaviour change: prior
@@ -190,7 +210,7 @@ pub fn execute_raw(options: RunOptions) -> Result<RunResult> {
     git::require_clean_working_directory(&repo)?;
 
     // Load stack
-    let config = crate::config::Config::load_with_global(repo.commondir())?;
+    let config = crate::config::Config::load(repo.commondir())?;
 
     execute_raw_body(&repo, &config, options)
 }
@@ -227,34 +247,71 @@ fn execute_raw_body(
         )));
     }
 
+    // Positions are resolved against the stack as it is now (after any
+    // rebase that ran before us), like `until` above.
+    let start_pos = options.from.unwrap_or(1);
+    if start_pos == 0 || start_pos > stack.len() {
+        return Err(GgError::Other(format!(
+            "Position {} is out of range (1-{})",
+            start_pos,
+            stack.len()
+        )));
+    }
+    if start_pos > end_pos {
+        return Err(GgError::Other(format!(
+            "Start position {} is after the end position {}",
+            start_pos, end_pos
+        )));
+    }
+
     // Determine whether to use parallel execution
-    let use_parallel = options.change_mode == ChangeMode::ReadOnly && options.jobs != 1;
+    let use_parallel = options.change_mode != ChangeMode::Discard && options.jobs != 1;
 
     if use_parallel {
         if !options.json {
             let jobs = effective_jobs(options.jobs);
-            let header = format!(
-                "Running {} command(s) on commits 1-{} (mode: read-only, jobs: {})",
-                options.commands.len(),
-                end_pos,
-                if options.jobs == 0 {
-                    format!("auto={}", jobs)
-                } else {
-                    jobs.to_string()
-                },
-            );
+            let jobs = if options.jobs == 0 {
+                format!("auto={}", jobs)
+            } else {
+                jobs.to_string()
+            };
+            let header = if let Some(ref label) = options.header_label {
+                format!(
+                    "Running {} on commits {}-{} ({} {} commands, jobs: {})",
+                    label,
+                    start_pos,
+                    end_pos,
+                    options.commands.len(),
+                    label,
+                    jobs,
+                )
+            } else {
+                let mode_label = match options.change_mode {
+                    ChangeMode::Amend => "amend",
+                    _ => "read-only",
+                };
+                format!(
+                    "Running {} command(s) on commits {}-{} (mode: {}, jobs: {})",
+                    options.commands.len(),
+                    start_pos,
+                    end_pos,
+                    mode_label,
+                    jobs,
+                )
+            };
             println!("{}", style(header).dim());
         }
 
-        return run_on_commits_parallel(repo, &stack, &options, end_pos);
+        return run_on_commits_parallel(repo, stack, &options, end_pos);
     }
 
     // --- Sequential path ---
     if !options.json {
         let header = if let Some(ref label) = options.header_label {
             format!(
-                "Running {} on commits 1-{} ({} {} commands)",
+                "Running {} on commits {}-{} ({} {} commands)",
                 label,
+                start_pos,
                 end_pos,
                 options.commands.len(),
                 label,
@@ -266,8 +323,9 @@ fn execute_raw_body(
                 ChangeMode::Discard => "discard",
             };
             format!(
-                "Running {} command(s) on commits 1-{} (mode: {})",
+                "Running {} command(s) on commits {}-{} (mode: {})",
                 options.commands.len(),
+                start_pos,
                 end_pos,
                 mode_label,
             )
@@ -275,11 +333,10 @@ fn execute_raw_body(
         println!("{}", style(header).dim());
     }
 
-    if options.jobs > 1 && options.change_mode != ChangeMode::ReadOnly && !options.json {
+    if options.jobs > 1 && options.change_mode == ChangeMode::Discard && !options.json {
         println!(
             "{}",
-            style("Note: --jobs is ignored in amend/discard mode (requires sequential execution)")
-                .dim()
+            style("Note: --jobs is ignored in discard mode (requires sequential execution)").dim()
         );
     }
 
@@ -318,7 +375,7 @@ fn run_on_commits(
     let mut run_results: Vec<RunCommitResult> = Vec::with_capacity(end_pos);
     let mut all_passed = true;
 
-    let mut i = 0;
+    let mut i = options.from.map_or(0, |from| from - 1);
     while i < end_pos {
         let entry = entries[i].clone();
         let mut had_changes_this_commit = false;
@@ -328,11 +385,35 @@ fn run_on_commits(
         // See Bug #3.
         let mut amended_oid: Option<Oid> = None;
 
+        let (commands, skipped) = commands_for_commit(repo, options, entry.oid)?;
+        if commands.is_empty() {
+            if !options.json {
+                println!();
+                println!(
+                    "{} Skipping [{}] {} {} (no commands apply to this commit)",
+                    style(plain::arrow()).dim(),
+                    entry.position,
+                    style(&entry.short_sha).yellow(),
+                    entry.title
+                );
+            }
+            run_results.push(RunCommitResult {
+                position: entry.position,
+                sha: entry.short_sha.clone(),
+                title: entry.title.clone(),
+                passed: true,
+                commands: vec![],
+                skipped,
+            });
+            i += 1;
+            continue;
+        }
+
         if !options.json {
             println!();
             println!(
                 "{} Running on [{}] {} {}",
-                style("→").cyan(),
+                style(plain::arrow()).cyan(),
                 entry.position,
                 style(&entry.short_sha).yellow(),
                 entry.title
@@ -345,9 +426,9 @@ fn run_on_commits(
 
         // Run commands
         let mut commit_passed = true;
-        let mut command_results = Vec::with_capacity(options.commands.len());
+        let mut command_results = Vec::with_capacity(commands.len());
 
-        for cmd in &options.commands {
+        for cmd in &commands {
             let cmd_display = cmd.display();
             if !options.json {
                 print!("  Running: {} ... ", style(&cmd_display).dim());
@@ -420,6 +501,18 @@ fn run_on_commits(
             });
         }
 
+        // Keep amended changes inside the stack scope
+        if options.change_mode == ChangeMode::Amend {
+            let discarded = scope::discard_out_of_scope_changes(repo, &options.scope)?;
+            if !discarded.is_empty() && !options.json {
+                println!(
+                    "  {} Discarded changes outside the stack scope: {}",
+                    style("!").yellow(),
+                    discarded.join(", ")
+                );
+            }
+        }
+
         // Handle file changes based on mode
         if !git::is_working_directory_clean(repo)? {
             match options.change_mode {
@@ -452,7 +545,7 @@ fn run_on_commits(
                     }
 
                     // Amend the commit
-                    let amend_output = Command::new("git")
+                    let amend_output = hooks::git_command(GitRewrite::Amend)
                         .args(["commit", "--amend", "--no-edit"])
                         .current_dir(repo_root)
                         .stdin(Stdio::null())
@@ -500,13 +593,10 @@ fn run_on_commits(
                         git::run_git_command(&["branch", "-f", target_branch, &old_tip])?;
                         git::checkout_branch(repo, target_branch)?;
 
-                        if let Err(e) = git::run_git_command(&[
-                            "rebase",
-                            "--onto",
-                            &new_commit,
-                            &old_commit,
-                            target_branch,
-                        ]) {
+                        if let Err(e) = git::run_rewrite_command(
+                            GitRewrite::Replay,
+                            &["rebase", "--onto", &new_commit, &old_commit, target_branch],
+                        ) {
                             if git::is_rebase_in_progress(repo) {
                                 print_rebase_conflict_help(repo_root, options.json);
                                 return Err(GgError::Other(
@@ -601,6 +691,7 @@ fn run_on_commits(
             title: entry.title.clone(),
             passed: commit_passed,
             commands: command_results,
+            skipped,
         });
 
         // Stop on first failure if requested
@@ -899,6 +990,7 @@ fn run_commands_in_worktree(
         title: entry.title.clone(),
         passed: commit_passed,
         commands: command_results,
+        skipped: vec![],
     }
 }
 
@@ -925,10 +1017,12 @@ fn is_worktree_dirty(wt_path: &Path) -> bool {
 }
 
 /// Parallel execution path: creates isolated worktrees, runs commands concurrently,
-/// collects results in commit order. Only valid for ReadOnly mode.
+/// collects results in commit order. Valid for ReadOnly and Amend modes; in
+/// Amend mode each worktree amends its own commit and the fixes are replayed
+/// onto the stack in order afterwards (see [`replay_amended_commits`]).
 fn run_on_commits_parallel(
     repo: &git2::Repository,
-    stack: &Stack,
+    stack: Stack,
     options: &RunOptions,
     end_pos: usize,
 ) -> Result<RunResult> {
@@ -936,37 +1030,47 @@ fn run_on_commits_parallel(
         .workdir()
         .ok_or_else(|| GgError::Other("Repository has no working directory".to_string()))?;
     let jobs = effective_jobs(options.jobs);
-    let entries = &stack.entries[..end_pos];
+    let offset = options.from.map_or(0, |from| from - 1);
+    let entries = &stack.entries[offset..end_pos];
 
-    let resolved_commands = pre_resolve_commands(&options.commands, repo);
-    let original_commands: &[RunCommand] = &options.commands;
-    // Parallelism is only valid for ReadOnly; we enforce the read-only contract
-    // (post-command dirty check) inside each worker just like the sequential path.
+    // Commands each commit needs; commits needing none get no worktree.
+    let selections = entries
+        .iter()
+        .map(|entry| commands_for_commit(repo, options, entry.oid))
+        .collect::<Result<Vec<_>>>()?;
+    let to_run = selections.iter().filter(|(run, _)| !run.is_empty()).count();
+    // We enforce the read-only contract (post-command dirty check) inside
+    // each worker just like the sequential path.
     let is_read_only = options.change_mode == ChangeMode::ReadOnly;
+    let is_amend = options.change_mode == ChangeMode::Amend;
 
     // Create worktrees (sequential — git requires this)
     let mut guard = WorktreeGuard::new(repo_root)?;
-    let mut worktree_paths: Vec<PathBuf> = Vec::with_capacity(end_pos);
+    let mut worktree_paths: Vec<Option<PathBuf>> = Vec::with_capacity(end_pos);
 
     if !options.json {
         println!(
             "{}",
             style(format!(
                 "Creating {} worktree(s) for parallel execution...",
-                end_pos
+                to_run
             ))
             .dim()
         );
     }
 
-    for (i, entry) in entries.iter().enumerate() {
-        let wt_path = guard.add(i, entry.oid)?;
+    for (i, (entry, (commands, _))) in entries.iter().zip(&selections).enumerate() {
+        let wt_path = if commands.is_empty() {
+            None
+        } else {
+            Some(guard.add(i, entry.oid)?)
+        };
         worktree_paths.push(wt_path);
     }
 
-    // Progress bar for non-JSON output
-    let pb = if !options.json {
-        let pb = indicatif::ProgressBar::new(end_pos as u64);
+    // Progress bar for non-JSON, non-plain output
+    let pb = if !options.json && !plain::is_enabled() {
+        let pb = indicatif::ProgressBar::new(to_run as u64);
         pb.set_style(
             indicatif::ProgressStyle::with_template(
                 "  {spinner:.cyan} [{bar:30.cyan/dim}] {pos}/{len} commits ({elapsed})",
@@ -979,24 +1083,65 @@ fn run_on_commits_parallel(
         None
     };
 
-    // Build work items: (index, entry, worktree_path, original_commands)
+    // Build work items: (index, entry, worktree_path, resolved_commands,
+    // original_commands) for the commits that have commands to run.
     // original_commands is threaded through so the resulting RunCommandResult.command
     // reflects the user's input (e.g. `.git/gg/lint.sh`), not the pre-resolved
     // absolute path used for execution inside the detached worktree.
-    let work_items: Vec<(usize, &StackEntry, &Path, &[RunCommand])> = entries
+    type WorkItem<'a> = (
+        usize,
+        &'a StackEntry,
+        &'a Path,
+        Vec<RunCommand>,
+        Vec<RunCommand>,
+    );
+    let work_items: Vec<WorkItem> = entries
         .iter()
         .zip(worktree_paths.iter())
+        .zip(&selections)
         .enumerate()
-        .map(|(i, (entry, path))| (i, entry, path.as_path(), original_commands))
+        .filter_map(|(i, ((entry, path), (commands, _)))| {
+            let path = path.as_deref()?;
+            Some((
+                i,
+                entry,
+                path,
+                pre_resolve_commands(commands, repo),
+                commands.clone(),
+            ))
+        })
         .collect();
 
-    // Run in parallel with bounded concurrency
+    // Run in parallel with bounded concurrency. Each result carries the
+    // amended commit, if the commands changed files in Amend mode. Commits
+    // with nothing to run pass as-is.
+    type WorkerResult = (
+        usize,
+        RunCommitResult,
+        std::result::Result<Option<Oid>, String>,
+    );
     let work = std::sync::Mutex::new(work_items.into_iter());
-    let collected: std::sync::Mutex<Vec<(usize, RunCommitResult)>> =
-        std::sync::Mutex::new(Vec::with_capacity(end_pos));
+    let skipped_results: Vec<WorkerResult> = entries
+        .iter()
+        .zip(&selections)
+        .enumerate()
+        .filter(|(_, (_, (commands, _)))| commands.is_empty())
+        .map(|(i, (entry, (_, skipped)))| {
+            let result = RunCommitResult {
+                position: entry.position,
+                sha: entry.short_sha.clone(),
+                title: entry.title.clone(),
+                passed: true,
+                commands: vec![],
+                skipped: skipped.clone(),
+            };
+            (i, result, Ok(None))
+        })
+        .collect();
+    let collected: std::sync::Mutex<Vec<WorkerResult>> = std::sync::Mutex::new(skipped_results);
 
     std::thread::scope(|s| {
-        let num_workers = jobs.min(end_pos);
+        let num_workers = jobs.min(to_run);
         for _ in 0..num_workers {
             s.spawn(|| {
                 loop {
@@ -1010,18 +1155,24 @@ fn run_on_commits_parallel(
                         guard.next()
                     };
                     match item {
-                        Some((idx, entry, wt_path, orig_cmds)) => {
-                            let result = run_commands_in_worktree(
-                                &resolved_commands,
-                                orig_cmds,
+                        Some((idx, entry, wt_path, cmds, orig_cmds)) => {
+                            let mut result = run_commands_in_worktree(
+                                &cmds,
+                                &orig_cmds,
                                 wt_path,
                                 entry,
                                 is_read_only,
                             );
+                            result.skipped = selections[idx].1.clone();
+                            let amended = if is_amend {
+                                amend_in_worktree(wt_path, &options.scope)
+                            } else {
+                                Ok(None)
+                            };
                             collected
                                 .lock()
                                 .unwrap_or_else(std::sync::PoisonError::into_inner)
-                                .push((idx, result));
+                                .push((idx, result, amended));
                             if let Some(ref pb) = pb {
                                 pb.inc(1);
                             }
@@ -1043,18 +1194,56 @@ fn run_on_commits_parallel(
     }
 
     // Sort results by commit position
-    let mut sorted_results: Vec<RunCommitResult> = {
+    let (mut sorted_results, mut amended): (Vec<RunCommitResult>, Vec<Option<Oid>>) = {
         let mut indexed = results;
-        indexed.sort_by_key(|(idx, _)| *idx);
-        indexed.into_iter().map(|(_, r)| r).collect()
+        indexed.sort_by_key(|(idx, _, _)| *idx);
+        let mut sorted = Vec::with_capacity(indexed.len());
+        let mut amended = Vec::with_capacity(indexed.len());
+        for (_, result, oid) in indexed {
+            let oid = oid.map_err(|e| {
+                GgError::Other(format!(
+                    "Failed to amend commit [{}] {}: {}",
+                    result.position, result.sha, e
+                ))
+            })?;
+            sorted.push(result);
+            amended.push(oid);
+        }
+        (sorted, amended)
     };
 
     let all_passed = sorted_results.iter().all(|r| r.passed);
 
-    // Apply stop_on_error: truncate after first failure
+    // Apply stop_on_error: truncate after first failure. Commits above it
+    // keep their original content, as in the sequential path.
     if options.stop_on_error && !all_passed {
         if let Some(first_fail) = sorted_results.iter().position(|r| !r.passed) {
             sorted_results.truncate(first_fail + 1);
+            amended.truncate(first_fail + 1);
+        }
+    }
+
+    drop(guard);
+
+    let had_changes = amended.iter().any(Option::is_some);
+    if had_changes {
+        let mut stack_amended = vec![None; offset];
+        stack_amended.extend(amended.iter().copied());
+        let Some(new_oids) = replay_amended_commits(repo, &stack, &stack_amended)? else {
+            if !options.json {
+                println!(
+                    "{}",
+                    style("Changes from parallel workers conflict when combined; re-running sequentially...")
+                        .yellow()
+                );
+            }
+            return run_on_commits(repo, stack, options, end_pos);
+        };
+        for (result, oid) in sorted_results.iter_mut().zip(&amended) {
+            if oid.is_some() {
+                let commit = repo.find_commit(new_oids[result.position - 1])?;
+                result.sha = git::short_sha(&commit);
+            }
         }
     }
 
@@ -1063,9 +1252,9 @@ fn run_on_commits_parallel(
         for result in &sorted_results {
             println!();
             let status_icon = if result.passed {
-                style("✓").green()
+                style(plain::ok()).green()
             } else {
-                style("✗").red()
+                style(plain::fail()).red()
             };
             println!(
                 "{} [{}] {} {}",
@@ -1074,6 +1263,13 @@ fn run_on_commits_parallel(
                 style(&result.sha).yellow(),
                 result.title,
             );
+            if result.commands.is_empty() {
+                println!(
+                    "  {} {}",
+                    style(plain::arrow()).dim(),
+                    style("skipped (no commands apply to this commit)").dim()
+                );
+            }
             for cmd_result in &result.commands {
                 let cmd_status = if cmd_result.passed {
                     style("OK").green().to_string()
@@ -1082,7 +1278,7 @@ fn run_on_commits_parallel(
                 };
                 println!(
                     "  {} {} {}",
-                    style("→").dim(),
+                    style(plain::arrow()).dim(),
                     cmd_result.command,
                     cmd_status
                 );
@@ -1094,32 +1290,213 @@ fn run_on_commits_parallel(
             }
         }
         println!();
+        if had_changes {
+            println!(
+                "{}",
+                style("Changes were made. Review with `gg ls` and sync with `gg sync`.").dim()
+            );
+        }
         let status_msg = if all_passed {
             format!(
                 "{} Ran on {} commit(s) across {} worker(s) — all passed",
                 style("OK").green().bold(),
                 sorted_results.len(),
-                jobs.min(end_pos),
+                jobs.min(entries.len()),
             )
         } else {
             format!(
                 "{} Ran on {} commit(s) across {} worker(s) — some failed",
                 style("FAIL").red().bold(),
                 sorted_results.len(),
-                jobs.min(end_pos),
+                jobs.min(entries.len()),
             )
         };
         println!("{}", status_msg);
     }
 
-    // guard is dropped here — worktrees cleaned up automatically
-
     Ok(RunResult {
         all_passed,
         results: sorted_results,
     })
 }
 
+/// Commands to run on commit `oid`, and the display form of those skipped.
+///
+/// Starts from the commit's own commands when `options.commit_commands`
+/// yields some, else from `options.commands`; keeps commands without path
+/// globs plus those with a glob matching a file the commit touches.
+fn commands_for_commit(
+    repo: &git2::Repository,
+    options: &RunOptions,
+    oid: Oid,
+) -> Result<(Vec<RunCommand>, Vec<String>)> {
+    let overridden = match options.commit_commands {
+        Some(commit_commands) => commit_commands(repo, oid)?,
+        None => None,
+    };
+    let candidates: Vec<(RunCommand, Vec<String>)> = match overridden {
+        Some(commands) => commands
+            .into_iter()
+            .map(|c| (RunCommand::Shell(c.cmd().to_string()), c.paths().to_vec()))
+            .collect(),
+        None => options
+            .commands
+            .iter()
+            .enumerate()
+            .map(|(i, cmd)| {
+                let paths = options.command_paths.get(i).cloned().unwrap_or_default();
+                (cmd.clone(), paths)
+            })
+            .collect(),
+    };
+
+    let changed = if candidates.iter().any(|(_, globs)| !globs.is_empty()) {
+        scope::commit_paths(repo, oid)?
+    } else {
+        Vec::new()
+    };
+    let (run, skipped): (Vec<_>, Vec<_>) = candidates.into_iter().partition(|(_, globs)| {
+        globs.is_empty() || changed.iter().any(|path| scope::contains(globs, path))
+    });
+    Ok((
+        run.into_iter().map(|(cmd, _)| cmd).collect(),
+        skipped.into_iter().map(|(cmd, _)| cmd.display()).collect(),
+    ))
+}
+
+/// Fold whatever the commands changed in a worker worktree into its commit,
+/// mirroring the sequential Amend path (scope filter, `git add -A`,
+/// `git commit --amend`). Returns the amended commit, or `None` when the
+/// commands left the tracked files untouched.
+fn amend_in_worktree(wt_path: &Path, scope: &[String]) -> std::result::Result<Option<Oid>, String> {
+    if !scope.is_empty() {
+        let wt_repo = git2::Repository::open(wt_path).map_err(|e| e.to_string())?;
+        scope::discard_out_of_scope_changes(&wt_repo, scope).map_err(|e| e.to_string())?;
+    }
+    if !is_worktree_dirty(wt_path) {
+        return Ok(None);
+    }
+
+    let git = |args: &[&str]| -> std::result::Result<String, String> {
+        let output = hooks::git_command(GitRewrite::Amend)
+            .args(args)
+            .current_dir(wt_path)
+            .stdin(Stdio::null())
+            .output()
+            .map_err(|e| e.to_string())?;
+        if output.status.success() {
+            Ok(String::from_utf8_lossy(&output.stdout).trim().to_string())
+        } else {
+            Err(String::from_utf8_lossy(&output.stderr).trim().to_string())
+        }
+    };
+    git(&["add", "-A"])?;
+    git(&["commit", "--amend", "--no-edit"])?;
+    let head = git(&["rev-parse", "HEAD"])?;
+    Oid::from_str(&head).map(Some).map_err(|e| e.to_string())
+}
+
+/// Rebuild the stack on top of commits amended in parallel worktrees.
+///
+/// Walks the whole stack in order, cherry-picking each entry (its amended
+/// version when there is one) onto its rewritten parent, so a fix made in
+/// one commit flows into the commits above it. Entries whose parent did not
+/// change are kept as-is. On success the stack branch points at the new tip
+/// and HEAD is back where it was (on the branch, or at the rewritten
+/// current entry). Returns `None`, with the repository left untouched, when
+/// the amended commits don't combine cleanly.
+fn replay_amended_commits(
+    repo: &git2::Repository,
+    stack: &Stack,
+    amended: &[Option<Oid>],
+) -> Result<Option<Vec<Oid>>> {
+    let repo_root = repo
+        .workdir()
+        .ok_or_else(|| GgError::Other("Repository has no working directory".to_string()))?;
+    let original_branch = git::current_branch_name(repo);
+    let original_head = repo.head()?.peel_to_commit()?.id();
+    let git = |args: &[&str]| -> Result<Output> {
+        Ok(hooks::git_command(GitRewrite::Replay)
+            .args(args)
+            .current_dir(repo_root)
+            .stdin(Stdio::null())
+            .output()?)
+    };
+    let git_ok = |args: &[&str]| -> Result<()> {
+        let output = git(args)?;
+        if output.status.success() {
+            Ok(())
+        } else {
+            Err(GgError::Other(format!(
+                "git {} failed: {}",
+                args.join(" "),
+                String::from_utf8_lossy(&output.stderr).trim()
+            )))
+        }
+    };
+
+    let mut new_oids: Vec<Oid> = Vec::with_capacity(stack.len());
+    let mut head = original_head;
+    let mut detached = original_branch.is_none();
+    for (i, entry) in stack.entries.iter().enumerate() {
+        let source = amended.get(i).copied().flatten().unwrap_or(entry.oid);
+        let original_parent = repo.find_commit(entry.oid)?.parent_id(0)?;
+        let parent = new_oids.last().copied().unwrap_or(original_parent);
+        if parent == original_parent {
+            new_oids.push(source);
+            continue;
+        }
+
+        if head != parent || !detached {
+            git_ok(&["checkout", "--quiet", "--detach", &parent.to_string()])?;
+            detached = true;
+        }
+        let picked = git(&[
+            "cherry-pick",
+            "--allow-empty",
+            "--keep-redundant-commits",
+            &source.to_string(),
+        ])?;
+        if !picked.status.success() {
+            let _ = git(&["cherry-pick", "--abort"]);
+            match original_branch.as_deref() {
+                Some(branch) => git::checkout_branch(repo, branch)?,
+                None => git::checkout_commit(repo, &repo.find_commit(original_head)?)?,
+            }
+            return Ok(None);
+        }
+        head = repo.head()?.peel_to_commit()?.id();
+        new_oids.push(head);
+    }
+
+    // Move the stack branch while detached so the working tree follows the
+    // checkout below rather than going stale under a moved ref.
+    if !detached {
+        git_ok(&["checkout", "--quiet", "--detach"])?;
+    }
+    let branch = original_branch
+        .clone()
+        .unwrap_or_else(|| stack.branch_name());
+    let tip = new_oids
+        .last()
+        .copied()
+        .ok_or_else(|| GgError::Other("Stack is empty".to_string()))?;
+    git_ok(&["branch", "-f", &branch, &tip.to_string()])?;
+
+    match original_branch.as_deref() {
+        Some(branch) => git::checkout_branch(repo, branch)?,
+        None => {
+            let position = stack
+                .entries
+                .iter()
+                .position(|e| e.oid == original_head)
+                .unwrap_or(new_oids.len() - 1);
+            git::checkout_commit(repo, &repo.find_commit(new_oids[position])?)?;
+        }
+    }
+    Ok(Some(new_oids))
+}
+
 // ---------------------------------------------------------------------------
 // Helpers
 // ---------------------------------------------------------------------------
@@ -1222,7 +1599,10 @@ fn restore_original_position(
 ) {
     if !json {
         println!();
-        println!("{} Restoring original position...", style("→").cyan());
+        println!(
+            "{} Restoring original position...",
+            style(plain::arrow()).cyan()
+        );
     }
 
     let restored = if let Some(branch) = original_branch {
@@ -1283,7 +1663,7 @@ fn print_rebase_conflict_help(repo_root: &Path, json: bool) {
     println!();
     println!(
         "{} Rebase conflict while rebasing after changes",
-        style("⚠️").yellow()
+        style(plain::warn()).yellow()
     );
     println!();
 
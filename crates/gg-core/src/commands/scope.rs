@@ -0,0 +1,61 @@
+//! `gg scope` - Show or set the path scope of the current stack
+//!
+//! The scope is a list of path globs (e.g. `services/payments/**`) stored
+//! in the stack's config. See [`crate::scope`] for how commands use it.
+
+use console::style;
+
+use crate::config::Config;
+use crate::error::Result;
+use crate::git;
+use crate::stack::Stack;
+
+/// Run the scope command
+pub fn run(patterns: Vec<String>, clear: bool) -> Result<()> {
+    let repo = git::open_repo()?;
+    let git_dir = repo.commondir();
+    let mut config = Config::load(git_dir)?;
+    let stack = Stack::load_active(&repo, &config)?;
+
+    if clear {
+        config.get_or_create_stack(&stack.name).scope.clear();
+        config.save(git_dir)?;
+        println!(
+            "{} Cleared the scope of {}",
+            style("OK").green().bold(),
+            style(&stack.name).cyan()
+        );
+        return Ok(());
+    }
+
+    if patterns.is_empty() {
+        let scope = config.get_scope_for_stack(&stack.name);
+        if scope.is_empty() {
+            println!(
+                "{}",
+                style(format!(
+                    "{} has no scope; it may touch any path.",
+                    stack.name
+                ))
+                .dim()
+            );
+        } else {
+            println!("Scope of {}:", style(&stack.name).cyan());
+            for pattern in scope {
+                println!("  {}", pattern);
+            }
+        }
+        return Ok(());
+    }
+
+    config.get_or_create_stack(&stack.name).scope = patterns;
+    config.save(git_dir)?;
+    println!(
+        "{} Scope of {}: {}",
+        style("OK").green().bold(),
+        style(&stack.name).cyan(),
+        config.get_scope_for_stack(&stack.name).join(", ")
+    );
+
+    Ok(())
+}
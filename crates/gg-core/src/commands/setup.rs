@@ -4,15 +4,22 @@ use console::style;
 use dialoguer::theme::ColorfulTheme;
 use dialoguer::{Confirm, Input, Select};
 
-use crate::config::{Config, Defaults, UnstagedAction};
+use crate::config::{CleanMode, Config, Defaults, LintCommand, UnstagedAction};
 use crate::error::{GgError, Result};
 use crate::git;
+use crate::plain;
 use crate::provider::Provider;
+use crate::registry::Registry;
 
 /// Print a styled group header for full setup mode
 fn print_group_header(name: &str) {
     println!();
-    println!("{}", style(format!("── {} ──", name)).cyan().bold());
+    println!(
+        "{}",
+        style(format!("{0} {1} {0}", plain::sym("──", "--"), name))
+            .cyan()
+            .bold()
+    );
 }
 
 /// Run the setup command
@@ -26,9 +33,6 @@ pub fn run(all: bool) -> Result<()> {
     let mut config = Config::load(git_dir)?;
     let theme = ColorfulTheme::default();
 
-    // Load global config to use as effective defaults
-    let global = Config::load_global()?.unwrap_or_default();
-
     if config_path.exists() {
         let proceed = Confirm::with_theme(&theme)
             .with_prompt(format!(
@@ -50,29 +54,29 @@ pub fn run(all: bool) -> Result<()> {
         );
     }
 
-    // Use global defaults as starting point when local config doesn't exist
-    let effective_defaults = if config_path.exists() {
-        &config.defaults
-    } else {
-        &global.defaults
-    };
-
+    // The loaded config already includes the global one
     let defaults = if all {
-        prompt_defaults_full(&repo, effective_defaults, &theme)?
+        prompt_defaults_full(&repo, &config.defaults, &theme)?
     } else {
-        prompt_defaults_quick(&repo, effective_defaults, &theme)?
+        prompt_defaults_quick(&repo, &config.defaults, &theme)?
     };
     config.defaults = defaults;
 
-    // worktree_base_path lives on Config, not Defaults
     if all {
         print_group_header("Worktrees");
-        let effective_worktree = if config_path.exists() {
-            config.worktree_base_path.as_deref()
-        } else {
-            global.worktree_base_path.as_deref()
-        };
-        config.worktree_base_path = prompt_worktree_base_path(effective_worktree, &theme)?;
+        // The top-level worktree_base_path moves into defaults
+        let existing = config
+            .defaults
+            .worktree_base_path
+            .take()
+            .or(config.worktree_base_path.take());
+        config.defaults.worktree_base_path =
+            prompt_worktree_base_path(existing.as_deref(), &theme)?;
+        config.defaults.always_worktree = Confirm::with_theme(&theme)
+            .with_prompt("Open every stack in its own worktree on `gg co`?")
+            .default(config.defaults.always_worktree)
+            .interact()
+            .map_err(|e| GgError::Other(format!("Prompt failed: {}", e)))?;
     }
 
     config.save(git_dir)?;
@@ -97,6 +101,36 @@ pub fn run(all: bool) -> Result<()> {
     Ok(())
 }
 
+/// Run `gg setup --register` (or `--unregister`): add the current
+/// repository to the registry read by `gg ls --global`, or remove it.
+pub fn run_register(unregister: bool) -> Result<()> {
+    let repo = git::open_repo()?;
+    let (path, changed) = if unregister {
+        Registry::unregister(&repo)?
+    } else {
+        Registry::register(&repo)?
+    };
+    let message = match (unregister, changed) {
+        (false, true) => "Registered",
+        (false, false) => "Already registered:",
+        (true, true) => "Unregistered",
+        (true, false) => "Not registered:",
+    };
+    println!(
+        "{} {} {}",
+        style("OK").green().bold(),
+        message,
+        style(path.display()).cyan()
+    );
+    if !unregister {
+        println!(
+            "{}",
+            style("Run `gg ls --global` to list stacks across registered repositories.").dim()
+        );
+    }
+    Ok(())
+}
+
 /// Quick mode: Only essential settings
 fn prompt_defaults_quick(
     repo: &git2::Repository,
@@ -179,6 +213,16 @@ fn prompt_defaults_full(
         .default(existing.land_auto_clean)
         .interact()
         .map_err(|e| GgError::Other(format!("Prompt failed: {}", e)))?;
+    let archive = Confirm::with_theme(theme)
+        .with_prompt("Archive cleaned stacks under refs/gg/archive instead of deleting them?")
+        .default(existing.clean_mode == CleanMode::Archive)
+        .interact()
+        .map_err(|e| GgError::Other(format!("Prompt failed: {}", e)))?;
+    defaults.clean_mode = if archive {
+        CleanMode::Archive
+    } else {
+        CleanMode::Delete
+    };
     let effective_timeout = existing.land_wait_timeout_minutes.unwrap_or(30);
     let timeout: u64 = Input::with_theme(theme)
         .with_prompt("Timeout in minutes for `gg land --wait`")
@@ -303,15 +347,17 @@ fn prompt_provider(
         git::detect_remote_provider_from_url(url).map(|provider| match provider {
             git::RemoteProvider::GitHub => 0usize,
             git::RemoteProvider::GitLab => 1usize,
+            git::RemoteProvider::Gitea => 2usize,
         })
     });
 
-    let providers = &["GitHub", "GitLab"];
+    let providers = &["GitHub", "GitLab", "Gitea/Forgejo"];
 
     // If we have an existing value, use that as default
     let existing_index = existing.and_then(|p| match p.to_lowercase().as_str() {
         "github" => Some(0),
         "gitlab" => Some(1),
+        "gitea" | "forgejo" => Some(2),
         _ => None,
     });
 
@@ -347,6 +393,7 @@ fn prompt_provider(
     let provider = match selection {
         0 => "github",
         1 => "gitlab",
+        2 => "gitea",
         _ => unreachable!(),
     };
 
@@ -389,9 +436,9 @@ fn prompt_branch_username(
 
 fn prompt_lint_commands(
     repo: &git2::Repository,
-    existing: &[String],
+    existing: &[LintCommand],
     theme: &ColorfulTheme,
-) -> Result<Vec<String>> {
+) -> Result<Vec<LintCommand>> {
     if !existing.is_empty() {
         println!("{}", style("Current lint commands:").dim());
         for cmd in existing {
@@ -425,7 +472,7 @@ fn prompt_lint_commands(
             .unwrap_or(false);
 
         if include {
-            lint.extend(suggestions);
+            lint.extend(suggestions.iter().map(|s| LintCommand::from(s.as_str())));
         }
     }
 
@@ -442,8 +489,8 @@ fn prompt_lint_commands(
             .map_err(|e| GgError::Other(format!("Prompt failed: {}", e)))?;
 
         let trimmed = cmd.trim();
-        if !trimmed.is_empty() && !lint.iter().any(|c| c == trimmed) {
-            lint.push(trimmed.to_string());
+        if !trimmed.is_empty() && !lint.iter().any(|c| c.cmd() == trimmed) {
+            lint.push(LintCommand::from(trimmed));
         }
 
         add_more = Confirm::with_theme(theme)
@@ -499,7 +546,7 @@ fn prompt_worktree_base_path(
 ) -> Result<Option<String>> {
     let input: String = Input::with_theme(theme)
         .with_prompt(
-            "Base path template for stack worktrees (variables: {repo}, {stack}, leave empty to disable)",
+            "Directory or path template for stack worktrees (variables: {repo}, {stack}, leave empty for the default)",
         )
         .default(existing.unwrap_or("").to_string())
         .allow_empty(true)
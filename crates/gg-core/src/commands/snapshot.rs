@@ -0,0 +1,355 @@
+//! `gg snapshot` - Automatic backups taken before destructive operations
+//!
+//! `gg rebase`, `gg reorder`, `gg lint` and `gg absorb --and-rebase` keep the
+//! stack's tip under `refs/gg/backup/<stack>/<timestamp>` (milliseconds since
+//! the Unix epoch) before rewriting anything. `gg snapshot list` shows them
+//! and `gg snapshot restore` puts a stack branch back on one.
+
+use console::style;
+use git2::{BranchType, Oid, Repository, ResetType};
+
+use crate::config::Config;
+use crate::error::{GgError, Result};
+use crate::git;
+use crate::operations::{now_ms, OperationKind, SnapshotScope};
+use crate::output::{
+    print_json, SnapshotJson, SnapshotListResponse, SnapshotRestoreResponse, OUTPUT_VERSION,
+};
+use crate::relative_time;
+use crate::stack::Stack;
+
+const BACKUP_REF_PREFIX: &str = "refs/gg/backup/";
+
+/// Snapshots kept per stack; older ones are deleted as new ones are taken.
+pub const SNAPSHOTS_PER_STACK: usize = 20;
+
+/// One `refs/gg/backup/<stack>/<timestamp>` ref.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Snapshot {
+    pub stack: String,
+    pub reference: String,
+    pub tip: Oid,
+    pub created_at_ms: u64,
+}
+
+impl From<&Snapshot> for SnapshotJson {
+    fn from(snapshot: &Snapshot) -> Self {
+        SnapshotJson {
+            stack: snapshot.stack.clone(),
+            reference: snapshot.reference.clone(),
+            tip: snapshot.tip.to_string(),
+            created_at_ms: snapshot.created_at_ms,
+        }
+    }
+}
+
+/// Every snapshot, grouped by stack and newest first within each stack.
+pub fn list_snapshots(repo: &Repository) -> Result<Vec<Snapshot>> {
+    let mut snapshots = Vec::new();
+    for reference in repo.references_glob(&format!("{}*", BACKUP_REF_PREFIX))? {
+        let reference = reference?;
+        let Ok(name) = reference.name() else {
+            continue;
+        };
+        let Some((stack, timestamp)) = name
+            .strip_prefix(BACKUP_REF_PREFIX)
+            .and_then(|rest| rest.rsplit_once('/'))
+        else {
+            continue;
+        };
+        let (Ok(created_at_ms), Some(tip)) = (timestamp.parse(), reference.target()) else {
+            continue;
+        };
+        snapshots.push(Snapshot {
+            stack: stack.to_string(),
+            reference: name.to_string(),
+            tip,
+            created_at_ms,
+        });
+    }
+    snapshots.sort_by(|a, b| {
+        a.stack
+            .cmp(&b.stack)
+            .then(b.created_at_ms.cmp(&a.created_at_ms))
+    });
+    Ok(snapshots)
+}
+
+/// Keep `stack`'s tip under `refs/gg/backup/<stack>/<timestamp>` before a
+/// destructive operation rewrites it, pruning the stack's oldest snapshots
+/// beyond [`SNAPSHOTS_PER_STACK`]. Returns the snapshot ref, or `None` when
+/// the branch does not exist or its newest snapshot already has this tip.
+pub(crate) fn backup_stack(repo: &Repository, stack: &Stack) -> Result<Option<String>> {
+    let branch_name = stack.branch_name();
+    let Some(tip) = repo
+        .find_branch(&branch_name, BranchType::Local)
+        .ok()
+        .and_then(|b| b.get().target())
+    else {
+        return Ok(None);
+    };
+
+    let existing: Vec<Snapshot> = list_snapshots(repo)?
+        .into_iter()
+        .filter(|s| s.stack == stack.name)
+        .collect();
+    if existing.first().is_some_and(|newest| newest.tip == tip) {
+        return Ok(None);
+    }
+
+    let mut created_at_ms = now_ms();
+    let mut reference = format!("{}{}/{}", BACKUP_REF_PREFIX, stack.name, created_at_ms);
+    while repo.find_reference(&reference).is_ok() {
+        created_at_ms += 1;
+        reference = format!("{}{}/{}", BACKUP_REF_PREFIX, stack.name, created_at_ms);
+    }
+    repo.reference(
+        &reference,
+        tip,
+        false,
+        &format!("gg snapshot: back up {}", branch_name),
+    )?;
+
+    for old in existing.iter().skip(SNAPSHOTS_PER_STACK - 1) {
+        if let Ok(mut r) = repo.find_reference(&old.reference) {
+            r.delete()?;
+        }
+    }
+    Ok(Some(reference))
+}
+
+/// Run `gg snapshot list`, optionally for one stack
+pub fn run_list(stack: Option<&str>, json: bool) -> Result<()> {
+    let repo = git::open_repo()?;
+    let snapshots: Vec<Snapshot> = list_snapshots(&repo)?
+        .into_iter()
+        .filter(|s| stack.is_none_or(|name| s.stack == name))
+        .collect();
+
+    if json {
+        print_json(&SnapshotListResponse {
+            version: OUTPUT_VERSION,
+            snapshots: snapshots.iter().map(Into::into).collect(),
+        });
+        return Ok(());
+    }
+
+    if snapshots.is_empty() {
+        println!("{}", style("No snapshots.").dim());
+        return Ok(());
+    }
+    let now = now_ms();
+    for snapshot in &snapshots {
+        let commit = repo.find_commit(snapshot.tip).ok();
+        let title = commit
+            .as_ref()
+            .and_then(|c| c.summary().ok().flatten().map(str::to_string))
+            .unwrap_or_default();
+        let short = commit
+            .as_ref()
+            .map(git::short_sha)
+            .unwrap_or_else(|| snapshot.tip.to_string()[..7].to_string());
+        println!(
+            "{}  {}  {} {}  {}",
+            style(&snapshot.stack).cyan(),
+            relative_time::format(snapshot.created_at_ms, now),
+            style(short).yellow(),
+            title,
+            style(&snapshot.reference).dim()
+        );
+    }
+    Ok(())
+}
+
+/// Run `gg snapshot restore`: move a stack branch back to a snapshot.
+///
+/// `target` is a snapshot ref (`refs/gg/backup/<stack>/<timestamp>` or just
+/// `<stack>/<timestamp>`) or a stack name for its newest snapshot; without
+/// it, the newest snapshot of the current stack. The restore is recorded,
+/// so `gg undo` reverses it.
+pub fn run_restore(target: Option<&str>, json: bool) -> Result<()> {
+    let repo = git::open_repo()?;
+    let config = Config::load(repo.commondir())?;
+
+    if git::is_rebase_in_progress(&repo) {
+        return Err(GgError::Other(
+            "A rebase is in progress. Run `gg continue` or `gg abort` first.".to_string(),
+        ));
+    }
+
+    let current_stack = Stack::load(&repo, &config).ok();
+    let stack_name = target
+        .map(str::to_string)
+        .or_else(|| current_stack.as_ref().map(|s| s.name.clone()))
+        .ok_or_else(|| {
+            GgError::Other(
+                "Not on a stack. Pass a stack name or snapshot ref to `gg snapshot restore`."
+                    .to_string(),
+            )
+        })?;
+    let snapshot = list_snapshots(&repo)?
+        .into_iter()
+        .find(|s| {
+            s.stack == stack_name
+                || s.reference == stack_name
+                || s.reference.strip_prefix(BACKUP_REF_PREFIX) == Some(stack_name.as_str())
+        })
+        .ok_or_else(|| {
+            GgError::Other(format!(
+                "No snapshot '{}'. See `gg snapshot list`.",
+                stack_name
+            ))
+        })?;
+
+    let branch_name = stack_branch(&repo, &config, current_stack.as_ref(), &snapshot.stack)
+        .ok_or_else(|| {
+            GgError::Other(format!(
+                "Stack '{}' has no branch to restore. Recreate it with `gg co {}` first.",
+                snapshot.stack, snapshot.stack
+            ))
+        })?;
+    let refname = format!("refs/heads/{}", branch_name);
+    let previous_tip = repo.refname_to_id(&refname)?;
+    let checked_out = git::current_branch_name(&repo).as_deref() == Some(branch_name.as_str());
+    if checked_out {
+        git::require_clean_working_directory(&repo)?;
+    }
+
+    let (_lock, guard) = git::acquire_operation_lock_and_record(
+        &repo,
+        &config,
+        OperationKind::Snapshot,
+        std::env::args().skip(1).collect(),
+        Some(snapshot.stack.clone()),
+        SnapshotScope::AllUserBranches,
+    )?;
+    let commit = repo.find_commit(snapshot.tip)?;
+    if checked_out {
+        repo.reset(commit.as_object(), ResetType::Hard, None)?;
+    } else {
+        repo.reference(
+            &refname,
+            snapshot.tip,
+            true,
+            &format!("gg snapshot: restore {}", snapshot.reference),
+        )?;
+    }
+    guard.finalize_with_scope(
+        &repo,
+        &config,
+        SnapshotScope::AllUserBranches,
+        vec![],
+        false,
+    )?;
+
+    if json {
+        print_json(&SnapshotRestoreResponse {
+            version: OUTPUT_VERSION,
+            restored: (&snapshot).into(),
+            branch: branch_name,
+            previous_tip: previous_tip.to_string(),
+        });
+    } else {
+        println!(
+            "{} Restored {} to {} ({})",
+            style("OK").green().bold(),
+            style(&branch_name).cyan(),
+            style(git::short_sha(&commit)).yellow(),
+            relative_time::format(snapshot.created_at_ms, now_ms())
+        );
+        println!(
+            "  {}",
+            style(format!(
+                "It was at {}; `gg undo` puts it back.",
+                &previous_tip.to_string()[..7]
+            ))
+            .dim()
+        );
+    }
+    Ok(())
+}
+
+/// Local branch of `stack`: the current stack's, or a stack branch with
+/// that name, preferring the configured branch username.
+fn stack_branch(
+    repo: &Repository,
+    config: &Config,
+    current: Option<&Stack>,
+    stack: &str,
+) -> Option<String> {
+    if let Some(current) = current.filter(|s| s.name == stack) {
+        return Some(current.branch_name());
+    }
+    let mut candidates: Vec<(String, String)> = repo
+        .branches(Some(BranchType::Local))
+        .ok()?
+        .filter_map(|b| b.ok())
+        .filter_map(|(b, _)| b.name().ok().flatten().map(str::to_string))
+        .filter_map(|name| {
+            git::parse_stack_branch(&name)
+                .filter(|(_, s)| s == stack)
+                .map(|(user, _)| (user, name))
+        })
+        .collect();
+    let preferred = config.defaults.branch_username.as_deref();
+    candidates.sort_by_key(|(user, _)| Some(user.as_str()) != preferred);
+    candidates.into_iter().next().map(|(_, name)| name)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn commit(repo: &Repository, message: &str) -> Oid {
+        let sig = git2::Signature::now("Test", "test@example.com").unwrap();
+        let tree = repo
+            .find_tree(repo.index().unwrap().write_tree().unwrap())
+            .unwrap();
+        let parent = repo.head().ok().map(|h| h.peel_to_commit().unwrap());
+        let parents: Vec<&git2::Commit> = parent.iter().collect();
+        repo.commit(Some("HEAD"), &sig, &sig, message, &tree, &parents)
+            .unwrap()
+    }
+
+    fn stack(name: &str) -> Stack {
+        Stack {
+            name: name.to_string(),
+            username: "nacho".to_string(),
+            base: "main".to_string(),
+            entries: vec![],
+            current_position: None,
+        }
+    }
+
+    #[test]
+    fn backup_skips_unchanged_tips_and_prunes_old_snapshots() {
+        let dir = tempfile::tempdir().unwrap();
+        let repo = Repository::init(dir.path()).unwrap();
+        let first = commit(&repo, "first");
+        repo.branch("nacho/feat", &repo.find_commit(first).unwrap(), true)
+            .unwrap();
+        let feat = stack("feat");
+
+        let reference = backup_stack(&repo, &feat).unwrap().unwrap();
+        assert!(reference.starts_with("refs/gg/backup/feat/"));
+        assert_eq!(backup_stack(&repo, &feat).unwrap(), None);
+
+        for i in 0..SNAPSHOTS_PER_STACK + 2 {
+            let oid = commit(&repo, &format!("commit {}", i));
+            repo.branch("nacho/feat", &repo.find_commit(oid).unwrap(), true)
+                .unwrap();
+            backup_stack(&repo, &feat).unwrap().unwrap();
+        }
+
+        let snapshots = list_snapshots(&repo).unwrap();
+        assert_eq!(snapshots.len(), SNAPSHOTS_PER_STACK);
+        assert_eq!(
+            snapshots[0].tip,
+            repo.refname_to_id("refs/heads/nacho/feat").unwrap()
+        );
+        assert!(snapshots
+            .windows(2)
+            .all(|w| w[0].created_at_ms > w[1].created_at_ms));
+        assert!(snapshots.iter().all(|s| s.tip != first));
+    }
+}
@@ -1,4 +1,5 @@
-//! `gg split` - Split a commit into two
+//! `gg split` - Split a commit into two (or, with `--by-file` / `--by-hunk`,
+//! into one commit per file or hunk)
 
 use std::collections::{BTreeMap, BTreeSet, HashMap, HashSet};
 use std::io::{self, Write};
@@ -12,9 +13,12 @@ use serde_json::json;
 use crate::config::Config;
 use crate::error::{GgError, Result};
 use crate::git;
+use crate::hooks::{self, GitRewrite};
 use crate::immutability::{self, ImmutabilityPolicy};
 use crate::operations::{self, OperationKind, SnapshotScope};
 use crate::output;
+use crate::plain;
+use crate::signing;
 use crate::stack::{self, Stack};
 
 use super::split_protocol::{
@@ -44,6 +48,10 @@ pub struct SplitOptions {
     pub plan_json: Option<PathBuf>,
     /// If true, emit machine-readable JSON output.
     pub json: bool,
+    /// Split into one commit per changed file, without prompting.
+    pub by_file: bool,
+    /// Split into one commit per hunk, without prompting.
+    pub by_hunk: bool,
 }
 
 struct ResolvedSplitTarget<'repo> {
@@ -102,7 +110,7 @@ pub fn run(options: SplitOptions) -> Result<()> {
     }
 
     let repo = git::open_repo()?;
-    let config = Config::load_with_global(repo.commondir())?;
+    let config = Config::load(repo.commondir())?;
     // Acquire the operation lock up-front but defer writing the op-log
     // record until after all validation (including the immutability guard)
     // so refused operations never leak into `gg undo --list` (design §4.6).
@@ -110,24 +118,30 @@ pub fn run(options: SplitOptions) -> Result<()> {
 
     git::require_clean_working_directory(&repo)?;
 
-    let ResolvedSplitTarget {
-        stack,
-        target_pos,
-        target_commit,
-        parent_commit,
-        original_gg_id,
-    } = resolve_target(&repo, &config, options.target.as_deref(), false)?;
+    let resolved = resolve_target(&repo, &config, options.target.as_deref(), false)?;
 
     // Immutability pre-flight: splitting rewrites the target commit and every
     // commit above it (they get a new parent). Guard against splitting a
     // merged or base-ancestor commit unless the user explicitly overrides.
     {
-        let targets: Vec<usize> = (target_pos..=stack.len()).collect();
-        let policy = ImmutabilityPolicy::for_stack(&repo, &stack)?;
-        let report = policy.check_positions(&stack, &targets);
+        let targets: Vec<usize> = (resolved.target_pos..=resolved.stack.len()).collect();
+        let policy = ImmutabilityPolicy::for_stack(&repo, &resolved.stack)?;
+        let report = policy.check_positions(&resolved.stack, &targets);
         immutability::guard(report, options.force)?;
     }
 
+    if options.by_file || options.by_hunk {
+        return run_multi_split(&repo, &config, resolved, options.by_hunk);
+    }
+
+    let ResolvedSplitTarget {
+        stack,
+        target_pos,
+        target_commit,
+        parent_commit,
+        original_gg_id,
+    } = resolved;
+
     println!(
         "Splitting commit {}: {} ({})",
         target_pos,
@@ -226,7 +240,11 @@ pub fn run(options: SplitOptions) -> Result<()> {
     if all_selected {
         println!(
             "{}",
-            style("⚠ All hunks selected — the original commit will become empty.").yellow()
+            style(format!(
+                "{} All hunks selected — the original commit will become empty.",
+                plain::warn()
+            ))
+            .yellow()
         );
     }
 
@@ -271,7 +289,7 @@ pub fn run(options: SplitOptions) -> Result<()> {
     let num_rebased = result.rewritten_descendants.len();
 
     // Print results
-    println!("{} Split complete!", style("✔").green().bold());
+    println!("{} Split complete!", style(plain::ok()).green().bold());
     println!(
         "  New commit {} (before): {} {}",
         target_pos,
@@ -299,7 +317,7 @@ pub fn run(options: SplitOptions) -> Result<()> {
 pub fn apply_plan(_options: &SplitOptions, path: &Path) -> Result<SplitApplyResponse> {
     let plan = read_plan(path)?;
     let repo = git::open_repo()?;
-    let config = Config::load_with_global(repo.commondir())?;
+    let config = Config::load(repo.commondir())?;
     let _lock = git::acquire_operation_lock(&repo, "split")?;
 
     git::require_clean_working_directory(&repo)?;
@@ -516,8 +534,8 @@ fn execute_split(
     let sig = git::get_signature(repo)?;
     let new_gg_id = git::generate_gg_id();
     let first_message = git::set_gg_id_in_message(&selection.first_message, &new_gg_id);
-    let first_oid = repo.commit(
-        None,
+    let first_oid = signing::create_commit(
+        repo,
         &sig,
         &sig,
         &first_message,
@@ -531,8 +549,8 @@ fn execute_split(
     } else {
         selection.remainder_message
     };
-    let second_oid = repo.commit(
-        None,
+    let second_oid = signing::create_commit(
+        repo,
         &sig,
         &sig,
         &remainder_message,
@@ -546,6 +564,7 @@ fn execute_split(
         &stack,
         config,
         target_pos,
+        target_pos + 1,
         &target_commit,
         &second_commit,
     ) {
@@ -585,6 +604,204 @@ fn split_identity_at(stack: &Stack, position: usize) -> Result<SplitCommitIdenti
     })
 }
 
+/// One commit of a `--by-file` / `--by-hunk` split.
+struct SplitGroup {
+    /// Suffix appended to the original title, e.g. `src/lib.rs` or `src/lib.rs, hunk 2`
+    label: String,
+    hunk_indices: Vec<usize>,
+    non_hunk_files: Vec<String>,
+}
+
+/// Group a commit's changes into one entry per file, or per hunk. Files
+/// without textual hunks (binary, rename-only, mode-only) always form a
+/// group of their own.
+fn split_groups(
+    changed_files: &[ChangedFile],
+    hunks: &[DiffHunk],
+    by_hunk: bool,
+) -> Vec<SplitGroup> {
+    let mut groups = Vec::new();
+    for file in changed_files {
+        let indices: Vec<usize> = hunks
+            .iter()
+            .enumerate()
+            .filter(|(_, hunk)| hunk.file_path == file.path)
+            .map(|(index, _)| index)
+            .collect();
+        if indices.is_empty() {
+            groups.push(SplitGroup {
+                label: file.path.clone(),
+                hunk_indices: vec![],
+                non_hunk_files: vec![file.path.clone()],
+            });
+        } else if by_hunk && indices.len() > 1 {
+            for (n, index) in indices.iter().enumerate() {
+                groups.push(SplitGroup {
+                    label: format!("{}, hunk {}", file.path, n + 1),
+                    hunk_indices: vec![*index],
+                    non_hunk_files: vec![],
+                });
+            }
+        } else {
+            groups.push(SplitGroup {
+                label: file.path.clone(),
+                hunk_indices: indices,
+                non_hunk_files: vec![],
+            });
+        }
+    }
+    groups
+}
+
+/// Split the target into one commit per file or hunk. The last commit keeps
+/// the original message and GG-ID; the others get fresh GG-IDs and the
+/// original title suffixed with what they contain.
+fn run_multi_split(
+    repo: &git2::Repository,
+    config: &Config,
+    resolved: ResolvedSplitTarget<'_>,
+    by_hunk: bool,
+) -> Result<()> {
+    let ResolvedSplitTarget {
+        stack,
+        target_pos,
+        target_commit,
+        parent_commit,
+        original_gg_id,
+    } = resolved;
+
+    let changed_files = get_changed_files(repo, &parent_commit, &target_commit)?;
+    let hunks = get_hunks(repo, &parent_commit, &target_commit)?;
+    let groups = split_groups(&changed_files, &hunks, by_hunk);
+    if groups.len() < 2 {
+        return Err(GgError::Other(format!(
+            "Nothing to split: the commit has a single {}",
+            if by_hunk { "hunk" } else { "changed file" }
+        )));
+    }
+
+    println!(
+        "Splitting commit {} into {} commits: {} ({})",
+        target_pos,
+        groups.len(),
+        style(git::get_commit_title(&target_commit)).bold(),
+        style(git::short_sha(&target_commit)).yellow()
+    );
+
+    let parent_tree = parent_commit.tree()?;
+    let target_tree = target_commit.tree()?;
+    let mut trees = Vec::with_capacity(groups.len());
+    let mut indices = Vec::new();
+    let mut non_hunk_files = Vec::new();
+    let mut selected_paths = HashSet::new();
+    for group in &groups[..groups.len() - 1] {
+        indices.extend_from_slice(&group.hunk_indices);
+        non_hunk_files.extend(group.non_hunk_files.iter().cloned());
+        selected_paths.extend(
+            group
+                .hunk_indices
+                .iter()
+                .map(|index| hunks[*index].file_path.as_str())
+                .chain(group.non_hunk_files.iter().map(String::as_str)),
+        );
+        validate_path_dependent_selection(&target_tree, &changed_files, &selected_paths)?;
+        trees.push(build_tree_from_hunks(
+            repo,
+            &parent_tree,
+            &target_tree,
+            &hunks,
+            &indices,
+            &non_hunk_files,
+        )?);
+    }
+
+    let remainder_pos = target_pos + groups.len() - 1;
+    let mut guard = git::begin_recorded_op(
+        repo,
+        config,
+        OperationKind::Split,
+        std::env::args().skip(1).collect(),
+        None,
+        SnapshotScope::AllUserBranches,
+    )?;
+    guard.set_pending_plan(json!({
+        "split": {
+            "branch_name": stack.branch_name(),
+            "remainder_position": remainder_pos,
+            "remainder_gg_id": original_gg_id.clone(),
+        }
+    }));
+
+    let sig = git::get_signature(repo)?;
+    let title = git::get_commit_title(&target_commit);
+    let mut parent = parent_commit;
+    for (group, tree) in groups.iter().zip(&trees) {
+        let message = git::set_gg_id_in_message(
+            &format!("{} ({})", title, group.label),
+            &git::generate_gg_id(),
+        );
+        let oid = signing::create_commit(repo, &sig, &sig, &message, tree, &[&parent])?;
+        parent = repo.find_commit(oid)?;
+    }
+
+    let original_message = target_commit.message().unwrap_or("");
+    let remainder_message = match &original_gg_id {
+        Some(gg_id) => git::set_gg_id_in_message(original_message, gg_id),
+        None => original_message.to_string(),
+    };
+    let remainder_oid = signing::create_commit(
+        repo,
+        &sig,
+        &sig,
+        &remainder_message,
+        &target_tree,
+        &[&parent],
+    )?;
+    let remainder_commit = repo.find_commit(remainder_oid)?;
+
+    let num_rebased = match rebase_descendants(
+        repo,
+        &stack,
+        config,
+        target_pos,
+        remainder_pos,
+        &target_commit,
+        &remainder_commit,
+    ) {
+        Ok(count) => count,
+        Err(error) => {
+            if matches!(error, GgError::RebaseConflict) {
+                let _ = operations::remember_interrupted_rebase_operation(repo, guard.id());
+            }
+            return Err(error);
+        }
+    };
+
+    let rewritten_stack = Stack::load(repo, config)?;
+    guard.finalize_with_scope(repo, config, SnapshotScope::AllUserBranches, vec![], false)?;
+
+    println!("{} Split complete!", style(plain::ok()).green().bold());
+    for position in target_pos..=remainder_pos {
+        if let Some(entry) = rewritten_stack.get_entry_by_position(position) {
+            println!(
+                "  {} {} {}",
+                position,
+                style(&entry.short_sha).yellow(),
+                style(&entry.title).dim()
+            );
+        }
+    }
+    if num_rebased > 0 {
+        println!(
+            "  Rebased {} descendant commit{}.",
+            num_rebased,
+            if num_rebased == 1 { "" } else { "s" }
+        );
+    }
+
+    Ok(())
+}
+
 fn resolve_target<'repo>(
     repo: &'repo git2::Repository,
     config: &Config,
@@ -635,7 +852,7 @@ fn resolve_target<'repo>(
 /// Describe a split target without changing repository state.
 pub fn describe(options: &SplitOptions) -> Result<SplitDescribeResponse> {
     let repo = git::open_repo()?;
-    let config = Config::load_with_global(repo.commondir())?;
+    let config = Config::load(repo.commondir())?;
     let resolved = resolve_target(&repo, &config, options.target.as_deref(), true)?;
     let hunks = get_hunks(&repo, &resolved.parent_commit, &resolved.target_commit)?;
     let described_hunks = hunks
@@ -865,6 +1082,7 @@ fn rebase_descendants(
     stack: &Stack,
     config: &Config,
     target_pos: usize,
+    remainder_pos: usize,
     original_commit: &git2::Commit,
     second_commit: &git2::Commit,
 ) -> Result<usize> {
@@ -879,7 +1097,7 @@ fn rebase_descendants(
 
     // Use git rebase --onto <second_commit> <original_commit> <branch>
     let branch_name = stack.branch_name();
-    let output = Command::new("git")
+    let output = hooks::git_command(GitRewrite::Replay)
         .args([
             "rebase",
             "--onto",
@@ -902,10 +1120,9 @@ fn rebase_descendants(
     let rewritten_stack = Stack::load(repo, config)?;
     git::normalize_stack_metadata(repo, &rewritten_stack)?;
 
-    // Navigate back to the position of the remainder commit (target_pos + 1 in new stack)
+    // Navigate back to the position of the remainder commit in the new (larger) stack
     let new_stack = Stack::load(repo, config)?;
-    let new_pos = target_pos; // The remainder is at target_pos + 1 in the new (larger) stack
-    if let Some(entry) = new_stack.get_entry_by_position(new_pos + 1) {
+    if let Some(entry) = new_stack.get_entry_by_position(remainder_pos) {
         let git_dir = repo.path();
         stack::save_nav_context(git_dir, &branch_name, entry.position - 1, entry.oid)?;
         let commit = repo.find_commit(entry.oid)?;
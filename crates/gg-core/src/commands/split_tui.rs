@@ -22,6 +22,7 @@ use ratatui::{
 
 use super::split::DiffHunk;
 use crate::error::{GgError, Result};
+use crate::plain;
 
 /// Result of the TUI hunk selection + inline commit message
 #[derive(Debug, Clone)]
@@ -787,7 +788,7 @@ fn draw_file_panel(f: &mut Frame, state: &SplitTuiState, area: Rect) {
 
             // Determine checkbox style
             let (checkbox, checkbox_style) = if selected == total && total > 0 {
-                ("[✓]", Style::default().fg(Color::Green))
+                (plain::sym("[✓]", "[x]"), Style::default().fg(Color::Green))
             } else if selected > 0 {
                 ("[~]", Style::default().fg(Color::Yellow))
             } else {
@@ -890,7 +891,7 @@ fn draw_diff_panel(f: &mut Frame, state: &SplitTuiState, area: Rect) {
 
                 // Checkbox for hunk
                 let (checkbox, checkbox_style) = if is_selected {
-                    ("[✓]", Style::default().fg(Color::Green))
+                    (plain::sym("[✓]", "[x]"), Style::default().fg(Color::Green))
                 } else {
                     ("[ ]", Style::default().fg(Color::DarkGray))
                 };
@@ -1,16 +1,22 @@
-//! `gg sc` / `gg squash` - Squash changes into the current commit
+//! `gg sc` / `gg squash` - Squash changes into the current commit, or into
+//! another entry of the stack with `gg amend <target>`
 
+use std::io::Write;
 use std::process::Command;
 
 use console::{style, Term};
 use dialoguer::Select;
 use serde_json::json;
 
+use super::stash::{self, StashTag};
 use crate::config::{Config, UnstagedAction};
 use crate::error::{GgError, Result};
 use crate::git;
+use crate::hooks::{self, GitRewrite};
 use crate::immutability::{self, ImmutabilityPolicy};
 use crate::operations::{self, OperationKind, SnapshotScope};
+use crate::plain;
+use crate::scope;
 use crate::stack;
 use crate::stack::Stack;
 
@@ -19,7 +25,10 @@ fn restore_auto_stash() {
         Ok(output) if !output.status.success() => {
             eprintln!(
                 "{}",
-                style("⚠ Could not restore stashed changes automatically. Run 'git stash pop' manually.")
+                style(format!(
+                    "{} Could not restore stashed changes automatically. Run 'git stash pop' manually.",
+                    plain::warn()
+                ))
                     .yellow()
                     .bold()
             );
@@ -27,7 +36,10 @@ fn restore_auto_stash() {
         Err(_) => {
             eprintln!(
                 "{}",
-                style("⚠ Could not restore stashed changes automatically. Run 'git stash pop' manually.")
+                style(format!(
+                    "{} Could not restore stashed changes automatically. Run 'git stash pop' manually.",
+                    plain::warn()
+                ))
                     .yellow()
                     .bold()
             );
@@ -82,7 +94,7 @@ fn has_untracked_files() -> Result<bool> {
 /// Run the squash command
 pub fn run(all: bool, staged_only: bool, force: bool) -> Result<()> {
     let repo = git::open_repo()?;
-    let config = Config::load_with_global(repo.commondir())?;
+    let config = Config::load(repo.commondir())?;
 
     // Acquire the operation lock for validation, but defer writing the
     // op-log record until after the immutability guard passes so refused
@@ -146,9 +158,12 @@ pub fn run(all: bool, staged_only: bool, force: bool) -> Result<()> {
             UnstagedAction::Ask => {
                 println!(
                     "{}",
-                    style("⚠ You have unstaged changes that won't be included in the amend.")
-                        .yellow()
-                        .bold()
+                    style(format!(
+                        "{} You have unstaged changes that won't be included in the amend.",
+                        plain::warn()
+                    ))
+                    .yellow()
+                    .bold()
                 );
                 println!();
 
@@ -173,18 +188,11 @@ pub fn run(all: bool, staged_only: bool, force: bool) -> Result<()> {
                         add_all_changes()?;
                     }
                     1 => {
-                        let stash_output = Command::new("git")
-                            .args(["stash", "push", "-m", "gg amend: auto-stash"])
-                            .output()?;
-
-                        if !stash_output.status.success() {
-                            let stderr = String::from_utf8_lossy(&stash_output.stderr);
-                            return Err(GgError::Other(format!(
-                                "Failed to stash changes: {}",
-                                stderr
-                            )));
-                        }
-
+                        stash::push_tagged(
+                            &StashTag::for_stack(&stack),
+                            "gg amend: auto-stash",
+                            false,
+                        )?;
                         auto_stashed = true;
                     }
                     2 => {}
@@ -200,18 +208,7 @@ pub fn run(all: bool, staged_only: bool, force: bool) -> Result<()> {
                 add_all_changes()?;
             }
             UnstagedAction::Stash => {
-                let stash_output = Command::new("git")
-                    .args(["stash", "push", "-m", "gg amend: auto-stash"])
-                    .output()?;
-
-                if !stash_output.status.success() {
-                    let stderr = String::from_utf8_lossy(&stash_output.stderr);
-                    return Err(GgError::Other(format!(
-                        "Failed to stash changes: {}",
-                        stderr
-                    )));
-                }
-
+                stash::push_tagged(&StashTag::for_stack(&stack), "gg amend: auto-stash", false)?;
                 auto_stashed = true;
             }
             UnstagedAction::Continue => {}
@@ -262,7 +259,7 @@ pub fn run(all: bool, staged_only: bool, force: bool) -> Result<()> {
         args.push("--all");
     }
 
-    let output = Command::new("git").args(&args).output()?;
+    let output = hooks::git_command(GitRewrite::Amend).args(&args).output()?;
 
     if !output.status.success() {
         if auto_stashed {
@@ -283,6 +280,21 @@ pub fn run(all: bool, staged_only: bool, force: bool) -> Result<()> {
         head_title
     );
 
+    // Warn (without blocking) when the amended commit strays outside the
+    // stack's path scope.
+    let amended = repo.head()?.peel_to_commit()?;
+    let outside = scope::out_of_scope(
+        config.get_scope_for_stack(&stack.name),
+        scope::commit_paths(&repo, amended.id())?,
+    );
+    if !outside.is_empty() {
+        println!(
+            "{} Commit touches files outside the stack scope: {}",
+            style("Warning:").yellow(),
+            outside.join(", ")
+        );
+    }
+
     // If we need to rebase remaining commits
     if needs_rebase {
         let remaining = stack.len() - stack.current_position.unwrap() - 1;
@@ -317,7 +329,7 @@ pub fn run(all: bool, staged_only: bool, force: bool) -> Result<()> {
 
         // Use git rebase to rebase remaining commits
         // git rebase --onto <new_head> <old_head> <branch>
-        let rebase_result = Command::new("git")
+        let rebase_result = hooks::git_command(GitRewrite::Replay)
             .args([
                 "rebase",
                 "--onto",
@@ -403,3 +415,181 @@ pub fn run(all: bool, staged_only: bool, force: bool) -> Result<()> {
 
     Ok(())
 }
+
+/// Amend staged changes into the entry at `target` without navigating to it.
+///
+/// The changes are committed as a temporary commit, folded into the target
+/// by an interactive rebase of the stack branch, and HEAD is put back on the
+/// entry it was on. Falls back to [`run`] when the target is the current
+/// entry.
+pub fn run_into(target: &str, all: bool, force: bool) -> Result<()> {
+    let repo = git::open_repo()?;
+    let config = Config::load(repo.commondir())?;
+
+    let stack = Stack::load(&repo, &config)?;
+    let target_pos = stack::resolve_target(&stack, target)?;
+    let current_pos = stack.current_position.map(|p| p + 1).unwrap_or(stack.len());
+    if target_pos == current_pos {
+        return run(all, false, force);
+    }
+
+    let _lock = git::acquire_operation_lock(&repo, "squash")?;
+    let mut stack = Stack::load(&repo, &config)?;
+    immutability::refresh_mr_state_for_guard(&repo, &mut stack);
+
+    // The target and every entry above it get rewritten.
+    let targets: Vec<usize> = (target_pos..=stack.len()).collect();
+    let policy = ImmutabilityPolicy::for_stack(&repo, &stack)?;
+    immutability::guard(policy.check_positions(&stack, &targets), force)?;
+
+    if all {
+        add_all_changes()?;
+    }
+    if !has_staged_changes()? {
+        println!("{}", style("No staged changes to amend.").dim());
+        return Ok(());
+    }
+    // Unstaged changes would be lost (or block) when the branch is rebased.
+    if has_unstaged_changes()? {
+        return Err(GgError::Other(
+            "Unstaged changes detected. Stage or stash them (or pass --all) before amending another commit."
+                .to_string(),
+        ));
+    }
+
+    let entry = stack
+        .get_entry_by_position(target_pos)
+        .ok_or_else(|| GgError::Other(format!("Position {} not found in stack", target_pos)))?;
+    let target_commit = repo.find_commit(entry.oid)?;
+    let onto = target_commit
+        .parent(0)
+        .map_err(|_| GgError::Other("Cannot amend the root commit".to_string()))?;
+    let branch_name = stack.branch_name();
+
+    let mut guard = git::begin_recorded_op(
+        &repo,
+        &config,
+        OperationKind::Squash,
+        std::env::args().skip(1).collect(),
+        None,
+        SnapshotScope::AllUserBranches,
+    )?;
+    // `gg continue` uses this to return to the entry HEAD was on.
+    if stack.current_position.is_some() {
+        guard.set_pending_plan(json!({
+            "squash": {
+                "branch_name": branch_name,
+                "target_position": current_pos,
+                "target_gg_id": stack
+                    .get_entry_by_position(current_pos)
+                    .and_then(|entry| entry.gg_id.clone()),
+            }
+        }));
+    }
+
+    let output = hooks::git_command(GitRewrite::Amend)
+        .args(["commit", "-m", &format!("fixup! {}", entry.title)])
+        .output()?;
+    if !output.status.success() {
+        let stderr = String::from_utf8_lossy(&output.stderr);
+        return Err(GgError::Other(format!(
+            "Failed to commit staged changes: {}",
+            stderr
+        )));
+    }
+    let fixup = repo.head()?.peel_to_commit()?.id();
+    git::checkout_branch(&repo, &branch_name)?;
+
+    let mut rebase_todo = String::new();
+    for stack_entry in &stack.entries[target_pos - 1..] {
+        rebase_todo.push_str(&format!("pick {}\n", stack_entry.oid));
+        if stack_entry.position == target_pos {
+            rebase_todo.push_str(&format!("fixup {}\n", fixup));
+        }
+    }
+
+    let unique_id = std::process::id();
+    let todo_file = std::env::temp_dir().join(format!("gg-amend-todo-{}", unique_id));
+    std::fs::write(&todo_file, &rebase_todo)?;
+
+    let editor_script = format!("#!/bin/sh\ncat {} > \"$1\"", todo_file.display());
+    let script_file = std::env::temp_dir().join(format!("gg-amend-editor-{}.sh", unique_id));
+    {
+        let mut f = std::fs::File::create(&script_file)?;
+        f.write_all(editor_script.as_bytes())?;
+    }
+
+    #[cfg(unix)]
+    {
+        use std::os::unix::fs::PermissionsExt;
+        let mut perms = std::fs::metadata(&script_file)?.permissions();
+        perms.set_mode(0o755);
+        std::fs::set_permissions(&script_file, perms)?;
+    }
+
+    let output = hooks::git_command(GitRewrite::Replay)
+        .env("GIT_SEQUENCE_EDITOR", script_file.to_str().unwrap())
+        .args(["rebase", "-i", &onto.id().to_string()])
+        .output()?;
+
+    let _ = std::fs::remove_file(&todo_file);
+    let _ = std::fs::remove_file(&script_file);
+
+    if !output.status.success() {
+        let stderr = String::from_utf8_lossy(&output.stderr);
+        let stdout = String::from_utf8_lossy(&output.stdout);
+        if stderr.contains("CONFLICT")
+            || stderr.contains("conflict")
+            || stdout.contains("CONFLICT")
+            || stdout.contains("conflict")
+        {
+            eprintln!("{}", style("Rebase conflict detected.").yellow().bold());
+            eprintln!(
+                "  Resolve conflicts, stage the changes with `git add`, then run `gg continue`"
+            );
+            eprintln!("  Or run `gg abort` to cancel the rebase");
+            let _ = operations::remember_interrupted_rebase_operation(&repo, guard.id());
+            return Err(GgError::RebaseConflict);
+        }
+        return Err(GgError::Other(format!("Rebase failed: {}", stderr)));
+    }
+
+    git::ensure_branch_attached(&repo, &branch_name)?;
+    let rewritten_stack = Stack::load(&repo, &config)?;
+    git::normalize_stack_metadata(&repo, &rewritten_stack)?;
+
+    println!(
+        "{} Amended {} {}",
+        style("OK").green().bold(),
+        style(format!("#{}", target_pos)).dim(),
+        entry.title
+    );
+    let rebased = stack.len() - target_pos;
+    if rebased > 0 {
+        println!(
+            "{} Rebased {} commits on top",
+            style("OK").green().bold(),
+            rebased
+        );
+    }
+
+    // Put HEAD back on the entry it was on.
+    if let Some(position) = stack.current_position {
+        let new_stack = Stack::load(&repo, &config)?;
+        if let Some(entry) = new_stack.get_entry_by_position(position + 1) {
+            stack::save_nav_context(repo.path(), &branch_name, position, entry.oid)?;
+            let commit = repo.find_commit(entry.oid)?;
+            git::checkout_commit(&repo, &commit)?;
+        }
+    }
+
+    guard.finalize_with_scope(
+        &repo,
+        &config,
+        SnapshotScope::AllUserBranches,
+        vec![],
+        false,
+    )?;
+
+    Ok(())
+}
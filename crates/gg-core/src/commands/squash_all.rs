@@ -0,0 +1,370 @@
+//! `gg squash-all` - Collapse the stack (or its lower part) into one commit
+//!
+//! The squashed commit keeps the bottom entry's GG-ID, so its PR/MR (which
+//! already targets the base branch) becomes the single consolidated review.
+//! The PRs/MRs of the other squashed entries are closed with a pointer to
+//! it. Entries above `--until` are re-parented onto the new commit; their
+//! trees are unchanged, so no rebase (and no conflict) is involved.
+
+use console::style;
+use dialoguer::{Confirm, Editor};
+use git2::Oid;
+
+use crate::config::Config;
+use crate::error::{GgError, Result};
+use crate::git;
+use crate::immutability::{self, ImmutabilityPolicy};
+use crate::operations::{OperationGuard, OperationKind, SnapshotScope};
+use crate::output::{print_json, SquashAllResponse, OUTPUT_VERSION};
+use crate::provider::{PrState, Provider};
+use crate::signing;
+use crate::stack::Stack;
+
+/// Options for the squash-all command
+#[derive(Debug, Default)]
+pub struct SquashAllOptions {
+    /// Squash only entries 1..=N (default: the whole stack)
+    pub until: Option<usize>,
+    /// Commit message for the squashed commit (skips the editor)
+    pub message: Option<String>,
+    /// Edit the combined message in $EDITOR
+    pub edit: bool,
+    /// Leave the PRs/MRs of the squashed entries open
+    pub keep_prs: bool,
+    /// Skip the confirmation prompt
+    pub yes: bool,
+    /// Override the immutability check for merged/base-ancestor commits
+    pub force: bool,
+    /// Output as JSON
+    pub json: bool,
+}
+
+/// Concatenate commit messages the way `git rebase` squashes do: the first
+/// message leads, the others follow separated by blank lines. gg trailers
+/// are dropped; the squashed commit gets its own.
+pub fn combined_message(messages: &[&str]) -> String {
+    messages
+        .iter()
+        .map(|m| git::strip_gg_parent_from_message(&git::strip_gg_id_from_message(m)))
+        .map(|m| m.trim().to_string())
+        .filter(|m| !m.is_empty())
+        .collect::<Vec<_>>()
+        .join("\n\n")
+}
+
+/// Close the still-open PRs/MRs in `prs`, first leaving the comment built
+/// by `note`, if any. Failures are reported as warnings (unless `json`),
+/// never as errors: the local rewrite has already happened. Returns the
+/// PRs/MRs that were closed.
+pub(crate) fn close_prs(
+    repo: &git2::Repository,
+    remote: &str,
+    guard: &mut OperationGuard,
+    prs: &[u64],
+    note: impl Fn(&Provider) -> Option<String>,
+    json: bool,
+) -> Vec<u64> {
+    let mut closed_prs = Vec::new();
+    if prs.is_empty() {
+        return closed_prs;
+    }
+    let provider = match Provider::for_remote(repo, remote) {
+        Ok(provider) => provider,
+        Err(e) => {
+            if !json {
+                println!(
+                    "{} Could not close the PRs/MRs: {}",
+                    style("Warning:").yellow(),
+                    e
+                );
+            }
+            return closed_prs;
+        }
+    };
+    for &pr in prs {
+        let open = provider
+            .get_pr_info(pr)
+            .map(|info| matches!(info.state, PrState::Open | PrState::Draft))
+            .unwrap_or(false);
+        if !open {
+            continue;
+        }
+        if let Some(note) = note(&provider) {
+            let _ = provider.create_pr_comment(pr, &note);
+        }
+        match provider.close_pr(pr) {
+            Ok(()) => {
+                closed_prs.push(pr);
+                guard.mark_touched_remote();
+            }
+            Err(e) => {
+                if !json {
+                    println!(
+                        "{} Could not close {} {}{}: {}",
+                        style("Warning:").yellow(),
+                        provider.pr_label(),
+                        provider.pr_number_prefix(),
+                        pr,
+                        e
+                    );
+                }
+            }
+        }
+    }
+    closed_prs
+}
+
+/// "Squashed into #N by `command`." for the PRs/MRs folded into `kept_pr`.
+pub(crate) fn squashed_into_note(
+    provider: &Provider,
+    kept_pr: Option<u64>,
+    command: &str,
+) -> Option<String> {
+    kept_pr.map(|kept| {
+        format!(
+            "Squashed into {}{} by `{}`.",
+            provider.pr_number_prefix(),
+            kept,
+            command
+        )
+    })
+}
+
+/// Run the squash-all command
+pub fn run(options: SquashAllOptions) -> Result<()> {
+    let repo = git::open_repo()?;
+    let git_dir = repo.commondir();
+    let mut config = Config::load(git_dir)?;
+
+    let _lock = git::acquire_operation_lock(&repo, "squash-all")?;
+    git::require_clean_working_directory(&repo)?;
+
+    if git::current_branch_name(&repo).is_none() {
+        return Err(GgError::Other(
+            "squash-all must run from the stack branch. Run `gg last` first.".to_string(),
+        ));
+    }
+
+    let mut stack = Stack::load(&repo, &config)?;
+    immutability::refresh_mr_state_for_guard(&repo, &mut stack);
+
+    let until = options.until.unwrap_or(stack.len()).min(stack.len());
+    if until < 2 {
+        return Err(GgError::Other(
+            "Need at least two commits to squash".to_string(),
+        ));
+    }
+
+    // Every entry is rewritten: the squashed ones, and the ones above that
+    // get a new parent.
+    let positions: Vec<usize> = (1..=stack.len()).collect();
+    let policy = ImmutabilityPolicy::for_stack(&repo, &stack)?;
+    immutability::guard(policy.check_positions(&stack, &positions), options.force)?;
+
+    let squashed = &stack.entries[..until];
+    let commits = squashed
+        .iter()
+        .map(|e| repo.find_commit(e.oid))
+        .collect::<std::result::Result<Vec<_>, _>>()?;
+
+    let mut message = match &options.message {
+        Some(msg) => msg.clone(),
+        None => {
+            let messages: Vec<&str> = commits.iter().map(|c| c.message().unwrap_or("")).collect();
+            combined_message(&messages)
+        }
+    };
+    if options.edit && options.message.is_none() {
+        message = match Editor::new()
+            .extension(".txt")
+            .edit(&message)
+            .map_err(|e| GgError::Other(format!("Editor failed: {}", e)))?
+        {
+            Some(msg) if !msg.trim().is_empty() => msg.trim().to_string(),
+            None => message,
+            _ => {
+                return Err(GgError::Other(
+                    "Empty commit message, aborting squash-all".to_string(),
+                ))
+            }
+        };
+    }
+
+    if !options.json && !options.yes && !options.force {
+        println!(
+            "{} Will squash {} commit(s) into one:",
+            style("Squash").yellow().bold(),
+            until
+        );
+        for entry in squashed {
+            println!(
+                "  {} {} {}",
+                style(format!("#{}", entry.position)).dim(),
+                style(&entry.short_sha).yellow(),
+                entry.title
+            );
+        }
+        println!();
+        let confirmed = Confirm::new()
+            .with_prompt("Proceed with squash-all?")
+            .default(false)
+            .interact()
+            .unwrap_or(false);
+        if !confirmed {
+            println!("{}", style("Squash cancelled.").dim());
+            return Ok(());
+        }
+    }
+
+    let mut guard = git::begin_recorded_op(
+        &repo,
+        &config,
+        OperationKind::SquashAll,
+        std::env::args().skip(1).collect(),
+        Some(stack.name.clone()),
+        SnapshotScope::AllUserBranches,
+    )?;
+
+    // The bottom entry's GG-ID survives so its PR/MR stays mapped.
+    let kept_gg_id = squashed[0]
+        .gg_id
+        .clone()
+        .unwrap_or_else(git::generate_gg_id);
+    let first = &commits[0];
+    let last = &commits[until - 1];
+    let parents: Vec<git2::Commit> = first.parents().collect();
+    let parent_refs: Vec<&git2::Commit> = parents.iter().collect();
+    let squashed_oid = signing::create_commit(
+        &repo,
+        &first.author(),
+        &git::get_signature(&repo)?,
+        &git::set_gg_id_in_message(&message, &kept_gg_id),
+        &last.tree()?,
+        &parent_refs,
+    )?;
+
+    // Re-parent the entries above the squashed range.
+    let mut tip: Oid = squashed_oid;
+    for entry in &stack.entries[until..] {
+        let commit = repo.find_commit(entry.oid)?;
+        let parent = repo.find_commit(tip)?;
+        tip = signing::create_commit(
+            &repo,
+            &commit.author(),
+            &commit.committer(),
+            commit.message().unwrap_or(""),
+            &commit.tree()?,
+            &[&parent],
+        )?;
+    }
+
+    repo.reference(
+        &format!("refs/heads/{}", stack.branch_name()),
+        tip,
+        true,
+        "gg squash-all",
+    )?;
+
+    let rewritten = Stack::load(&repo, &config)?;
+    git::normalize_stack_metadata(&repo, &rewritten)?;
+
+    // Fold the other squashed entries' PRs/MRs into the kept one.
+    let kept_pr = squashed[0].mr_number;
+    let folded: Vec<(String, Option<u64>, Option<String>)> = squashed[1..]
+        .iter()
+        .filter_map(|e| {
+            let gg_id = e.gg_id.clone()?;
+            Some((gg_id, e.mr_number, stack.entry_branch_name(e)))
+        })
+        .collect();
+
+    let open_prs: Vec<u64> = folded.iter().filter_map(|(_, pr, _)| *pr).collect();
+    let closed_prs = if options.keep_prs {
+        Vec::new()
+    } else {
+        close_prs(
+            &repo,
+            config.get_remote_for_stack(&stack.name),
+            &mut guard,
+            &open_prs,
+            |provider| squashed_into_note(provider, kept_pr, "gg squash-all"),
+            options.json,
+        )
+    };
+    let touched_remote = !closed_prs.is_empty();
+
+    for (gg_id, _, branch) in &folded {
+        if !options.keep_prs {
+            config.remove_mr_for_entry(&stack.name, gg_id);
+        }
+        if let Some(branch) = branch {
+            let _ = repo
+                .find_branch(branch, git2::BranchType::Local)
+                .and_then(|mut b| b.delete());
+        }
+    }
+    config.save(git_dir)?;
+
+    let final_stack = Stack::load(&repo, &config)?;
+    let sha = final_stack.entries[0].short_sha.clone();
+
+    guard.finalize_with_scope(
+        &repo,
+        &config,
+        SnapshotScope::AllUserBranches,
+        vec![],
+        touched_remote,
+    )?;
+
+    if options.json {
+        print_json(&SquashAllResponse {
+            version: OUTPUT_VERSION,
+            squashed: until,
+            sha,
+            gg_id: kept_gg_id,
+            pr_number: kept_pr,
+            closed_prs,
+            remaining: final_stack.len(),
+        });
+    } else {
+        println!(
+            "{} Squashed {} commit(s) into {}, {} remaining",
+            style("OK").green().bold(),
+            until,
+            style(&sha).yellow(),
+            final_stack.len()
+        );
+        if !closed_prs.is_empty() {
+            let list: Vec<String> = closed_prs.iter().map(|n| n.to_string()).collect();
+            println!("  Closed: {}", list.join(", "));
+        }
+        println!(
+            "{}",
+            style("Run `gg sync` to push the squashed commit.").dim()
+        );
+    }
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn combined_message_concatenates_and_drops_trailers() {
+        let messages = [
+            "Add parser\n\nParses things.\n\nGG-ID: c-1111111",
+            "Use parser\n\nGG-ID: c-2222222\nGG-Parent: c-1111111",
+        ];
+        assert_eq!(
+            combined_message(&messages),
+            "Add parser\n\nParses things.\n\nUse parser"
+        );
+    }
+
+    #[test]
+    fn combined_message_skips_empty_messages() {
+        assert_eq!(combined_message(&["", "Only one\n"]), "Only one");
+    }
+}
@@ -0,0 +1,402 @@
+//! `gg stash` - Stack-aware wrapper around `git stash`
+//!
+//! Stashes created through gg carry a `[gg:<stack>@<position>]` tag in their
+//! message so `gg stash pop` can warn when changes are restored onto a
+//! different stack or position than the one they were taken from. The
+//! auto-stash paths in `gg sc` and `gg rebase` use the same tagging.
+
+use std::process::Command;
+
+use console::style;
+use git2::Repository;
+
+use crate::config::Config;
+use crate::error::{GgError, Result};
+use crate::git;
+use crate::output::{
+    print_json, StashEntryJson, StashListResponse, StashResponse, StashResultJson, OUTPUT_VERSION,
+};
+use crate::stack::Stack;
+
+const TAG_PREFIX: &str = "[gg:";
+
+/// Where a stash was taken: stack name and 1-indexed stack position.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct StashTag {
+    pub stack: String,
+    /// 1-indexed position of HEAD in the stack (0 for an empty stack).
+    pub position: usize,
+}
+
+impl StashTag {
+    /// Tag for the current HEAD position in `stack`.
+    pub fn for_stack(stack: &Stack) -> Self {
+        let position = stack.current_position.map(|p| p + 1).unwrap_or(stack.len());
+        Self {
+            stack: stack.name.clone(),
+            position,
+        }
+    }
+
+    fn encode(&self) -> String {
+        format!("{}{}@{}]", TAG_PREFIX, self.stack, self.position)
+    }
+
+    /// Extract the tag and the remaining user message from a stash subject
+    /// such as `On nacho/auth: [gg:auth@2] wip`.
+    fn parse(subject: &str) -> Option<(Self, String)> {
+        let start = subject.find(TAG_PREFIX)?;
+        let rest = &subject[start + TAG_PREFIX.len()..];
+        let end = rest.find(']')?;
+        let (stack, position) = rest[..end].rsplit_once('@')?;
+        if stack.is_empty() {
+            return None;
+        }
+        let tag = Self {
+            stack: stack.to_string(),
+            position: position.parse().ok()?,
+        };
+        Some((tag, rest[end + 1..].trim().to_string()))
+    }
+}
+
+/// A single `git stash list` entry.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct StashEntry {
+    /// Stash index (`stash@{index}`).
+    pub index: usize,
+    /// gg tag, if the stash was created by gg.
+    pub tag: Option<StashTag>,
+    /// Message without the gg tag (or the raw subject for untagged stashes).
+    pub message: String,
+}
+
+impl StashEntry {
+    pub fn reference(&self) -> String {
+        format!("stash@{{{}}}", self.index)
+    }
+
+    fn from_subject(index: usize, subject: &str) -> Self {
+        match StashTag::parse(subject) {
+            Some((tag, message)) => Self {
+                index,
+                tag: Some(tag),
+                message,
+            },
+            None => Self {
+                index,
+                tag: None,
+                message: subject.to_string(),
+            },
+        }
+    }
+}
+
+impl From<&StashEntry> for StashEntryJson {
+    fn from(entry: &StashEntry) -> Self {
+        Self {
+            reference: entry.reference(),
+            index: entry.index,
+            stack: entry.tag.as_ref().map(|t| t.stack.clone()),
+            position: entry.tag.as_ref().map(|t| t.position),
+            message: entry.message.clone(),
+        }
+    }
+}
+
+fn run_stash(args: &[&str]) -> Result<String> {
+    let output = Command::new("git").arg("stash").args(args).output()?;
+    if !output.status.success() {
+        let stderr = String::from_utf8_lossy(&output.stderr);
+        return Err(GgError::Other(format!(
+            "git stash {} failed: {}",
+            args.first().copied().unwrap_or_default(),
+            stderr.trim()
+        )));
+    }
+    Ok(String::from_utf8_lossy(&output.stdout).trim().to_string())
+}
+
+/// Push a stash tagged with `tag`. Used by `gg stash push` and by the
+/// auto-stash paths of other commands.
+pub fn push_tagged(tag: &StashTag, message: &str, include_untracked: bool) -> Result<()> {
+    let full_message = format!("{} {}", tag.encode(), message);
+    let mut args = vec!["push", "-m", full_message.as_str()];
+    if include_untracked {
+        args.push("--include-untracked");
+    }
+    run_stash(&args)?;
+    Ok(())
+}
+
+/// Auto-stash helper for commands that only hold a repo + config. Tags the
+/// stash with the current stack when one can be loaded, and falls back to
+/// an untagged stash otherwise.
+pub(crate) fn auto_stash(repo: &Repository, config: &Config, message: &str) -> Result<()> {
+    match Stack::load(repo, config) {
+        Ok(stack) => push_tagged(&StashTag::for_stack(&stack), message, false),
+        Err(_) => {
+            run_stash(&["push", "-m", message])?;
+            Ok(())
+        }
+    }
+}
+
+/// List all stashes (newest first), gg-tagged or not.
+pub fn list_entries() -> Result<Vec<StashEntry>> {
+    let output = run_stash(&["list", "--format=%gs"])?;
+    Ok(output
+        .lines()
+        .enumerate()
+        .map(|(index, subject)| StashEntry::from_subject(index, subject))
+        .collect())
+}
+
+/// Parse `stash@{N}` or a bare `N` into a stash index.
+fn parse_stash_ref(target: &str) -> Option<usize> {
+    let inner = target
+        .strip_prefix("stash@{")
+        .and_then(|s| s.strip_suffix('}'))
+        .unwrap_or(target);
+    inner.parse().ok()
+}
+
+/// Pick the stash `gg stash pop` restores when no target is given: the
+/// newest gg stash for the current stack, else the newest gg stash.
+fn default_pop_target<'a>(
+    entries: &'a [StashEntry],
+    current: Option<&StashTag>,
+) -> Option<&'a StashEntry> {
+    let tagged = || entries.iter().filter(|e| e.tag.is_some());
+    current
+        .and_then(|cur| tagged().find(|e| e.tag.as_ref().map(|t| &t.stack) == Some(&cur.stack)))
+        .or_else(|| tagged().next())
+}
+
+/// Describe how the stash origin differs from where it is being restored.
+fn mismatch_warning(stash: &StashTag, current: Option<&StashTag>) -> Option<String> {
+    match current {
+        None => Some(format!(
+            "stash was created on stack '{}' at position {}, but you are not on a stack",
+            stash.stack, stash.position
+        )),
+        Some(cur) if cur.stack != stash.stack => Some(format!(
+            "stash was created on stack '{}' at position {}, but you are on stack '{}'",
+            stash.stack, stash.position, cur.stack
+        )),
+        Some(cur) if cur.position != stash.position => Some(format!(
+            "stash was created at position {} of '{}', but you are at position {}",
+            stash.position, stash.stack, cur.position
+        )),
+        Some(_) => None,
+    }
+}
+
+fn current_tag(repo: &Repository, config: &Config) -> Option<StashTag> {
+    Stack::load(repo, config)
+        .ok()
+        .map(|stack| StashTag::for_stack(&stack))
+}
+
+/// Run `gg stash push`
+pub fn run_push(message: Option<String>, include_untracked: bool, json: bool) -> Result<()> {
+    let repo = git::open_repo()?;
+    let config = Config::load(repo.commondir())?;
+    let stack = Stack::load(&repo, &config)?;
+    let tag = StashTag::for_stack(&stack);
+
+    // `git stash push` exits 0 when there is nothing to save, which would
+    // make us report a stash that was never created.
+    let has_changes = if include_untracked {
+        !repo.statuses(None)?.is_empty()
+    } else {
+        !git::is_working_directory_clean(&repo)?
+    };
+    if !has_changes {
+        return Err(GgError::Other("No local changes to stash.".to_string()));
+    }
+
+    let message = message.unwrap_or_else(|| "gg stash".to_string());
+    push_tagged(&tag, &message, include_untracked)?;
+
+    let entry = StashEntry {
+        index: 0,
+        tag: Some(tag.clone()),
+        message,
+    };
+    if json {
+        print_json(&StashResponse {
+            version: OUTPUT_VERSION,
+            stash: StashResultJson {
+                action: "push".to_string(),
+                entry: (&entry).into(),
+                warnings: vec![],
+            },
+        });
+    } else {
+        println!(
+            "{} Stashed changes from {} #{} ({})",
+            style("OK").green().bold(),
+            style(&tag.stack).cyan(),
+            tag.position,
+            entry.reference()
+        );
+    }
+    Ok(())
+}
+
+/// Run `gg stash pop`
+pub fn run_pop(target: Option<String>, json: bool) -> Result<()> {
+    let repo = git::open_repo()?;
+    let config = Config::load(repo.commondir())?;
+    let current = current_tag(&repo, &config);
+    let entries = list_entries()?;
+
+    let entry = match target {
+        Some(target) => {
+            let index = parse_stash_ref(&target)
+                .ok_or_else(|| GgError::Other(format!("Invalid stash reference: {}", target)))?;
+            entries
+                .iter()
+                .find(|e| e.index == index)
+                .ok_or_else(|| GgError::Other(format!("No stash found at stash@{{{}}}", index)))?
+        }
+        None => default_pop_target(&entries, current.as_ref())
+            .ok_or_else(|| GgError::Other("No gg stashes found.".to_string()))?,
+    };
+
+    let warnings: Vec<String> = entry
+        .tag
+        .as_ref()
+        .and_then(|tag| mismatch_warning(tag, current.as_ref()))
+        .into_iter()
+        .collect();
+    if !json {
+        for warning in &warnings {
+            println!("{} {}", style("Warning:").yellow(), warning);
+        }
+    }
+
+    run_stash(&["pop", &entry.reference()])?;
+
+    if json {
+        print_json(&StashResponse {
+            version: OUTPUT_VERSION,
+            stash: StashResultJson {
+                action: "pop".to_string(),
+                entry: entry.into(),
+                warnings,
+            },
+        });
+    } else {
+        println!(
+            "{} Restored {} {}",
+            style("OK").green().bold(),
+            entry.reference(),
+            style(&entry.message).dim()
+        );
+    }
+    Ok(())
+}
+
+/// Run `gg stash list`
+pub fn run_list(all: bool, json: bool) -> Result<()> {
+    git::open_repo()?;
+    let entries: Vec<StashEntry> = list_entries()?
+        .into_iter()
+        .filter(|e| all || e.tag.is_some())
+        .collect();
+
+    if json {
+        print_json(&StashListResponse {
+            version: OUTPUT_VERSION,
+            stashes: entries.iter().map(Into::into).collect(),
+        });
+        return Ok(());
+    }
+
+    if entries.is_empty() {
+        println!("{}", style("No gg stashes.").dim());
+        return Ok(());
+    }
+    for entry in &entries {
+        let origin = match &entry.tag {
+            Some(tag) => format!("{} #{}", style(&tag.stack).cyan(), tag.position),
+            None => style("(untagged)").dim().to_string(),
+        };
+        println!("{}  {}  {}", entry.reference(), origin, entry.message);
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn tag(stack: &str, position: usize) -> StashTag {
+        StashTag {
+            stack: stack.to_string(),
+            position,
+        }
+    }
+
+    #[test]
+    fn tag_round_trips_through_stash_subject() {
+        let t = tag("auth", 2);
+        let subject = format!("On nacho/auth: {} wip login", t.encode());
+        let (parsed, message) = StashTag::parse(&subject).unwrap();
+        assert_eq!(parsed, t);
+        assert_eq!(message, "wip login");
+    }
+
+    #[test]
+    fn tag_parse_keeps_at_signs_in_stack_name() {
+        let (parsed, _) = StashTag::parse("On x: [gg:a@b@3] m").unwrap();
+        assert_eq!(parsed, tag("a@b", 3));
+    }
+
+    #[test]
+    fn untagged_subjects_are_kept_verbatim() {
+        let entry = StashEntry::from_subject(1, "WIP on main: abc123 msg");
+        assert_eq!(entry.tag, None);
+        assert_eq!(entry.message, "WIP on main: abc123 msg");
+        assert_eq!(entry.reference(), "stash@{1}");
+    }
+
+    #[test]
+    fn parse_stash_ref_accepts_index_and_full_ref() {
+        assert_eq!(parse_stash_ref("stash@{3}"), Some(3));
+        assert_eq!(parse_stash_ref("2"), Some(2));
+        assert_eq!(parse_stash_ref("stash@{x}"), None);
+    }
+
+    #[test]
+    fn default_pop_prefers_current_stack() {
+        let entries = vec![
+            StashEntry::from_subject(0, "On a: plain"),
+            StashEntry::from_subject(1, "On a: [gg:other@1] x"),
+            StashEntry::from_subject(2, "On a: [gg:auth@2] y"),
+        ];
+        let current = tag("auth", 1);
+        assert_eq!(
+            default_pop_target(&entries, Some(&current)).unwrap().index,
+            2
+        );
+        assert_eq!(default_pop_target(&entries, None).unwrap().index, 1);
+    }
+
+    #[test]
+    fn mismatch_warning_covers_stack_and_position() {
+        let stash = tag("auth", 2);
+        assert!(mismatch_warning(&stash, Some(&tag("auth", 2))).is_none());
+        assert!(mismatch_warning(&stash, Some(&tag("auth", 1)))
+            .unwrap()
+            .contains("position 1"));
+        assert!(mismatch_warning(&stash, Some(&tag("billing", 2)))
+            .unwrap()
+            .contains("'billing'"));
+        assert!(mismatch_warning(&stash, None)
+            .unwrap()
+            .contains("not on a stack"));
+    }
+}
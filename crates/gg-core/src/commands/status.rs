@@ -0,0 +1,281 @@
+//! `gg status` - Stack-aware summary of the working state
+//!
+//! One screen with the current stack, the position of HEAD in it, the
+//! PR/MR and CI state of the current entry, working-tree changes, and
+//! whether a rebase is in progress or the base is behind origin.
+
+use console::style;
+use git2::{Repository, Status, StatusOptions};
+
+use crate::config::Config;
+use crate::error::Result;
+use crate::git;
+use crate::operations;
+use crate::output::{
+    print_json, StackEntryJson, StatusJson, StatusResponse, StatusStackJson, OUTPUT_VERSION,
+};
+use crate::plain;
+use crate::provider::Provider;
+use crate::stack::Stack;
+
+use super::ls::{behind_count, entry_json};
+
+/// Working-tree changes grouped the way `git status` presents them.
+/// A path with both staged and unstaged edits appears in both lists.
+#[derive(Debug, Default, PartialEq)]
+struct WorkingTree {
+    staged: Vec<String>,
+    unstaged: Vec<String>,
+    untracked: Vec<String>,
+    conflicted: Vec<String>,
+}
+
+impl WorkingTree {
+    fn is_clean(&self) -> bool {
+        self.staged.is_empty()
+            && self.unstaged.is_empty()
+            && self.untracked.is_empty()
+            && self.conflicted.is_empty()
+    }
+}
+
+/// Run the status command
+pub fn run(json: bool) -> Result<()> {
+    let repo = git::open_repo()?;
+    let git_dir = repo.commondir();
+    let config = Config::load(git_dir)?;
+
+    let branch = git::current_branch_name(&repo);
+    let mut stack = Stack::load_active(&repo, &config).ok();
+
+    // Only the current entry is queried, so this stays a single round-trip
+    // per call; without a provider (or offline) the local state is shown.
+    let current = stack
+        .as_ref()
+        .filter(|s| !s.is_empty())
+        .map(|s| s.current_position.map(|p| p + 1).unwrap_or(s.len()));
+    if let (Some(stack), Some(position)) = (stack.as_mut(), current) {
+        if stack.entries[position - 1].mr_number.is_some() {
+            if let Ok(provider) = Provider::for_stack(&repo, &config, &stack.name) {
+                stack.refresh_entry_mr_info(position, &provider);
+            }
+        }
+    }
+
+    let tree = working_tree(&repo)?;
+    let rebase_in_progress = git::is_rebase_in_progress(&repo);
+    let behind_base = stack
+        .as_ref()
+        .and_then(|s| behind_count(&repo, config.get_remote_for_stack(&s.name), &s.base));
+
+    if json {
+        let current_entry = stack
+            .as_ref()
+            .zip(current)
+            .map(|(s, position)| entry_json(&s.entries[position - 1], true));
+        print_json(&StatusResponse {
+            version: OUTPUT_VERSION,
+            operation_id: operations::interrupted_rebase_operation(&repo)?.map(|record| record.id),
+            status: StatusJson {
+                branch,
+                stack: stack.as_ref().map(|s| StatusStackJson {
+                    name: s.name.clone(),
+                    base: s.base.clone(),
+                    current_position: current,
+                    total_commits: s.len(),
+                    synced_commits: s.synced_count(),
+                    behind_base,
+                }),
+                current_entry,
+                staged: tree.staged,
+                unstaged: tree.unstaged,
+                untracked: tree.untracked,
+                conflicted: tree.conflicted,
+                rebase_in_progress,
+            },
+        });
+        return Ok(());
+    }
+
+    match &stack {
+        Some(stack) => {
+            let behind = behind_base
+                .map(|n| {
+                    format!(
+                        ", {}",
+                        style(format!("{} behind origin/{}", n, stack.base)).yellow()
+                    )
+                })
+                .unwrap_or_default();
+            println!(
+                "Stack {} (base {}{})",
+                style(&stack.name).cyan().bold(),
+                stack.base,
+                behind
+            );
+            match current {
+                Some(position) => {
+                    let entry = &stack.entries[position - 1];
+                    println!(
+                        "At [{}/{}] {} {}",
+                        position,
+                        stack.len(),
+                        style(&entry.short_sha).yellow(),
+                        entry.title
+                    );
+                    print_review_line(&repo, &entry_json(entry, true));
+                }
+                None => println!(
+                    "{}",
+                    style("No commits yet. Use `git commit` to add changes.").dim()
+                ),
+            }
+        }
+        None => {
+            let on = match &branch {
+                Some(branch) => format!("On branch {}", style(branch).cyan()),
+                None => "HEAD detached".to_string(),
+            };
+            println!(
+                "{} {}",
+                on,
+                style("(not a stack; `gg co <name>` starts one)").dim()
+            );
+        }
+    }
+
+    if rebase_in_progress {
+        println!(
+            "{} {}",
+            style(plain::warn()).yellow(),
+            style("Rebase in progress. Run `gg continue` or `gg abort`")
+                .yellow()
+                .bold()
+        );
+    }
+
+    println!();
+    if tree.is_clean() {
+        println!("{}", style("Working tree clean").dim());
+    } else {
+        print_paths("Conflicted", &tree.conflicted, |s| style(s).red().bold());
+        print_paths("Staged", &tree.staged, |s| style(s).green());
+        print_paths("Unstaged", &tree.unstaged, |s| style(s).red());
+        print_paths("Untracked", &tree.untracked, |s| style(s).dim());
+    }
+
+    Ok(())
+}
+
+/// Print the PR/MR line for the current entry, if it has one.
+fn print_review_line(repo: &Repository, entry: &StackEntryJson) {
+    let Some(number) = entry.pr_number else {
+        println!("  {}", style("Not pushed yet. Run `gg sync`.").dim());
+        return;
+    };
+    let provider = Provider::detect(repo).ok();
+    let (label, prefix) = provider
+        .as_ref()
+        .map(|p| (p.pr_label(), p.pr_number_prefix()))
+        .unwrap_or(("PR", "#"));
+
+    let mut parts = vec![format!("{} {}{}", label, prefix, number)];
+    if let Some(state) = &entry.pr_state {
+        parts.push(state.clone());
+    }
+    if entry.approved {
+        parts.push("approved".to_string());
+    }
+    if let Some(ci) = &entry.ci_status {
+        parts.push(format!("CI {}", ci));
+    }
+    println!("  {}", style(parts.join(", ")).blue());
+}
+
+fn print_paths(
+    heading: &str,
+    paths: &[String],
+    paint: impl Fn(&str) -> console::StyledObject<&str>,
+) {
+    if paths.is_empty() {
+        return;
+    }
+    println!("{} ({}):", heading, paths.len());
+    for path in paths {
+        println!("  {}", paint(path));
+    }
+}
+
+fn working_tree(repo: &Repository) -> Result<WorkingTree> {
+    let mut opts = StatusOptions::new();
+    opts.include_untracked(true)
+        .include_ignored(false)
+        .exclude_submodules(true);
+
+    let mut tree = WorkingTree::default();
+    for entry in repo.statuses(Some(&mut opts))?.iter() {
+        let Ok(path) = entry.path() else {
+            continue;
+        };
+        classify(&mut tree, path, entry.status());
+    }
+    Ok(tree)
+}
+
+fn classify(tree: &mut WorkingTree, path: &str, status: Status) {
+    if status.is_conflicted() {
+        tree.conflicted.push(path.to_string());
+        return;
+    }
+    if status.intersects(
+        Status::INDEX_NEW
+            | Status::INDEX_MODIFIED
+            | Status::INDEX_DELETED
+            | Status::INDEX_RENAMED
+            | Status::INDEX_TYPECHANGE,
+    ) {
+        tree.staged.push(path.to_string());
+    }
+    if status.intersects(
+        Status::WT_MODIFIED | Status::WT_DELETED | Status::WT_RENAMED | Status::WT_TYPECHANGE,
+    ) {
+        tree.unstaged.push(path.to_string());
+    }
+    if status.is_wt_new() {
+        tree.untracked.push(path.to_string());
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn classify_splits_staged_and_unstaged_edits_of_one_path() {
+        let mut tree = WorkingTree::default();
+        classify(
+            &mut tree,
+            "both.rs",
+            Status::INDEX_MODIFIED | Status::WT_MODIFIED,
+        );
+        classify(&mut tree, "new.rs", Status::WT_NEW);
+        classify(&mut tree, "added.rs", Status::INDEX_NEW);
+        classify(
+            &mut tree,
+            "clash.rs",
+            Status::CONFLICTED | Status::WT_MODIFIED,
+        );
+
+        assert_eq!(
+            tree,
+            WorkingTree {
+                staged: vec!["both.rs".into(), "added.rs".into()],
+                unstaged: vec!["both.rs".into()],
+                untracked: vec!["new.rs".into()],
+                conflicted: vec!["clash.rs".into()],
+            }
+        );
+        assert!(!tree.is_clean());
+        assert!(WorkingTree::default().is_clean());
+    }
+}
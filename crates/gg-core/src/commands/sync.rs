@@ -1,23 +1,34 @@
 //! `gg sync` - Sync stack with remote provider (push branches and create/update PRs/MRs)
 
+use std::collections::HashMap;
+
 use console::style;
 use dialoguer::Confirm;
 use git2::Repository;
 use indicatif::{ProgressBar, ProgressStyle};
 
-use crate::config::Config;
+use crate::commit_messages::{self, MessageViolation, Policy};
+use crate::config::{CommitMessageCheck, Config, StackGraphMode};
 use crate::error::{GgError, Result};
 use crate::git::{self, get_commit_description, strip_gg_id_from_message};
+use crate::hooks::{self, Hook, HookContext};
+use crate::issues;
 use crate::managed_body;
 use crate::operations::{OperationKind, RemoteEffect, SnapshotScope};
 use crate::output::{
-    print_json, StreamingJson, SyncEntryResultJson, SyncMetadataJson, SyncResponse, SyncResultJson,
-    SyncStreamingEvent, SyncStreamingResponse, OUTPUT_VERSION,
+    print_json, StreamingJson, SyncEntryResultJson, SyncMetadataJson, SyncPlanEntryJson,
+    SyncPlanJson, SyncPlanResponse, SyncResponse, SyncResultJson, SyncStreamingEvent,
+    SyncStreamingResponse, OUTPUT_VERSION,
 };
+use crate::parallel;
+use crate::plain;
+use crate::protection::{self, Stage};
 use crate::provider::Provider;
-use crate::stack::{resolve_target, Stack};
+use crate::scope;
+use crate::stack::{resolve_target, Stack, StackEntry};
+use crate::stack_graph;
 use crate::stack_nav;
-use crate::template::{self, TemplateContext};
+use crate::template::{self, TemplateContext, TemplateStack};
 
 /// Per-entry state captured during the main sync loop that the nav-comment
 /// reconcile pass needs. Populated only for entries whose PR exists.
@@ -28,11 +39,78 @@ struct NavEntrySnapshot {
     json_index: usize,
 }
 
+/// Render the stack graph for one PR/MR and write it into the graph block
+/// of its description. Image mode uploads an SVG (GitLab) and falls back to
+/// Mermaid when the provider has no upload API; an unchanged graph is
+/// neither re-uploaded nor re-written.
+fn upsert_stack_graph(
+    provider: &Provider,
+    git_dir: &std::path::Path,
+    base: &str,
+    pr_number: u64,
+    entries: &[stack_graph::GraphEntry],
+    mode: StackGraphMode,
+) -> Result<()> {
+    let prefix = provider.pr_number_prefix();
+    let body = provider.get_pr_body(pr_number)?;
+
+    let mermaid = || stack_graph::render_mermaid(base, entries, prefix);
+    let (source, markdown) = match mode {
+        StackGraphMode::Off => return Ok(()),
+        StackGraphMode::Mermaid => {
+            let graph = mermaid();
+            (graph.clone(), Some(graph))
+        }
+        StackGraphMode::Image => (stack_graph::render_svg(base, entries, prefix), None),
+    };
+    let fingerprint = stack_graph::fingerprint(&source);
+    if stack_graph::existing_fingerprint(&body) == Some(fingerprint.as_str()) {
+        return Ok(());
+    }
+
+    let markdown = match markdown {
+        Some(markdown) => markdown,
+        None => {
+            let path = git_dir
+                .join("gg")
+                .join(format!("stack-graph-{}.svg", fingerprint));
+            std::fs::write(&path, &source)?;
+            let uploaded = provider.upload_image(&path);
+            let _ = std::fs::remove_file(&path);
+            uploaded?.unwrap_or_else(mermaid)
+        }
+    };
+
+    let updated =
+        stack_graph::upsert_block(&body, &stack_graph::wrap_block(&markdown, &fingerprint));
+    if updated != body {
+        provider.update_pr_description(pr_number, &updated)?;
+    }
+    Ok(())
+}
+
+/// Find the PR/MR that entry `i` should be blocked by: its nearest
+/// predecessor that is still open. Returns `Some(None)` when there is none
+/// (the entry targets the base) and `None` when a predecessor has no PR/MR,
+/// in which case the dependency cannot be decided yet.
+fn dependency_blocker(i: usize, snapshots: &[Option<NavEntrySnapshot>]) -> Option<Option<u64>> {
+    for snap in snapshots[..i].iter().rev() {
+        let snap = snap.as_ref()?;
+        if !matches!(
+            snap.pr_state,
+            stack_nav::PrEntryState::Merged | stack_nav::PrEntryState::Closed
+        ) {
+            return Some(Some(snap.pr_number));
+        }
+    }
+    Some(None)
+}
+
 /// Format and display a push error with helpful context
 fn maybe_rebase_if_base_is_behind(
     repo: &Repository,
     config: &Config,
-    base_branch: &str,
+    upstream: &str,
     json: bool,
     jsonl: bool,
 ) -> Result<bool> {
@@ -41,14 +119,14 @@ fn maybe_rebase_if_base_is_behind(
         return Ok(false);
     }
 
-    // Use merge-base to find how many commits on origin/<base_branch> are not
-    // reachable from HEAD. This correctly detects when a branch needs rebasing
-    // regardless of what local <base_branch> looks like.
-    let behind =
-        match git::count_branch_behind_upstream(repo, "HEAD", &format!("origin/{}", base_branch)) {
-            Ok(count) => count,
-            Err(_) => return Ok(false),
-        };
+    // Use merge-base to find how many commits on the upstream (origin/<base>,
+    // or the parent stack's branch for dependent stacks) are not reachable
+    // from HEAD. This correctly detects when a branch needs rebasing
+    // regardless of what local <base> looks like.
+    let behind = match git::count_branch_behind_upstream(repo, "HEAD", upstream) {
+        Ok(count) => count,
+        Err(_) => return Ok(false),
+    };
 
     if behind < threshold {
         return Ok(false);
@@ -62,10 +140,10 @@ fn maybe_rebase_if_base_is_behind(
     if config.get_sync_auto_rebase() {
         if !json && !jsonl {
             println!(
-                "{} Your stack is {} commits behind origin/{}. {} may show unrelated changes. Auto-rebasing...",
-                style("⚠").yellow().bold(),
+                "{} Your stack is {} commits behind {}. {} may show unrelated changes. Auto-rebasing...",
+                style(plain::warn()).yellow().bold(),
                 behind,
-                base_branch,
+                upstream,
                 prs_label
             );
         }
@@ -83,10 +161,10 @@ fn maybe_rebase_if_base_is_behind(
 
     if !json && !jsonl {
         println!(
-            "{} Your stack is {} commits behind origin/{}. {} may show unrelated changes. Run 'gg rebase' first to update.",
-            style("⚠").yellow().bold(),
+            "{} Your stack is {} commits behind {}. {} may show unrelated changes. Run 'gg rebase' first to update.",
+            style(plain::warn()).yellow().bold(),
             behind,
-            base_branch,
+            upstream,
             prs_label
         );
     }
@@ -125,7 +203,7 @@ fn format_push_error(error: &GgError, branch_name: &str) {
             println!();
             println!(
                 "{} Push failed for {}",
-                style("✗").red().bold(),
+                style(plain::fail()).red().bold(),
                 style(branch).cyan()
             );
             println!();
@@ -168,7 +246,7 @@ fn format_push_error(error: &GgError, branch_name: &str) {
             println!();
             println!(
                 "{} Push failed for {}: {}",
-                style("✗").red().bold(),
+                style(plain::fail()).red().bold(),
                 style(branch_name).cyan(),
                 error
             );
@@ -183,7 +261,7 @@ fn format_push_error(error: &GgError, branch_name: &str) {
 /// that is not merged/closed. If all predecessors are merged, returns `base`.
 /// This ensures downstream MRs are retargeted away from merged intermediate
 /// branches (fixes GitLab stacked MR retargeting — see #297).
-fn compute_target_branch(
+pub(crate) fn compute_target_branch(
     i: usize,
     base: &str,
     entries: &[crate::stack::StackEntry],
@@ -201,6 +279,302 @@ fn compute_target_branch(
     base.to_string()
 }
 
+/// Report what `gg sync` would do without rewriting commits, pushing, or
+/// touching any PR/MR. Lint and the pre-sync rebase are not run; a stack
+/// behind its upstream is reported instead.
+#[allow(clippy::too_many_arguments)]
+fn run_dry_run(
+    draft: bool,
+    json: bool,
+    no_rebase_check: bool,
+    update_descriptions: bool,
+    update_title: bool,
+    until: Option<String>,
+    only: Option<String>,
+    reviewers: Vec<String>,
+) -> Result<()> {
+    let repo = git::open_repo()?;
+    let config = Config::load(repo.commondir())?;
+    let draft = draft || config.get_sync_draft();
+    let update_descriptions = update_descriptions || config.get_sync_update_descriptions();
+    let update_title = update_title || config.get_sync_update_title();
+
+    let stack = Stack::load(&repo, &config)?;
+    let reviewers = merge_reviewers(config.get_reviewers_for_stack(&stack.name), &reviewers);
+    let mut warnings: Vec<String> = stack
+        .prefix_mismatch(&config)
+        .map(|mismatch| mismatch.warning_message())
+        .into_iter()
+        .collect();
+    let end_pos = match until.as_ref().or(only.as_ref()) {
+        Some(target) => resolve_target(&stack, target)?,
+        None => stack.len(),
+    };
+    let policy = Policy::from_config(&config.defaults.commit_messages)?;
+    let checked: Vec<&StackEntry> = stack
+        .entries
+        .iter()
+        .filter(|e| e.position <= end_pos && (only.is_none() || e.position == end_pos))
+        .collect();
+    warnings.extend(
+        commit_message_violations(&repo, &config, &policy, &checked)?
+            .iter()
+            .map(violation_warning),
+    );
+
+    let provider = Provider::for_stack(&repo, &config, &stack.name)?;
+    provider.check_installed()?;
+    provider.check_auth()?;
+    let remote = config.get_remote_for_stack(&stack.name);
+    let _ = git::fetch_and_prune(remote);
+
+    let parent = config.get_parent_for_stack(&stack.name);
+    if let Some(parent) = parent {
+        if stack.stale_parent_fork(&repo, &config).is_some() {
+            return Err(GgError::Other(format!(
+                "Stack '{}' is out of date with its parent '{}'. Run `gg rebase` first.",
+                stack.name, parent
+            )));
+        }
+    }
+    let upstream = if parent.is_some() {
+        stack.base.clone()
+    } else if let Some(from) = config.get_from_for_stack(&stack.name) {
+        from.to_string()
+    } else {
+        format!("{}/{}", remote, stack.base)
+    };
+    let threshold = config.get_sync_behind_threshold();
+    let behind_base = if no_rebase_check || threshold == 0 {
+        None
+    } else {
+        git::count_branch_behind_upstream(&repo, "HEAD", &upstream)
+            .ok()
+            .filter(|&behind| behind >= threshold)
+    };
+
+    // Mirror normalize_stack_metadata: a commit is rewritten when its
+    // trailers change or when anything below it was rewritten. Commits
+    // without a GG-ID get a placeholder, which also flags their child's
+    // GG-Parent as changing.
+    let mut metadata = SyncMetadataJson::default();
+    let mut rewritten = Vec::with_capacity(stack.len());
+    let mut previous_gg_id: Option<String> = None;
+    let mut below_rewritten = false;
+    for entry in &stack.entries {
+        let commit = repo.find_commit(entry.oid)?;
+        let message = commit.message().unwrap_or("");
+        let gg_id = entry
+            .gg_id
+            .clone()
+            .unwrap_or_else(|| format!("c-pending{}", entry.position));
+        let (normalized, id_added, parent_updated, parent_removed) =
+            git::normalize_gg_metadata_in_message(message, &gg_id, previous_gg_id.as_deref());
+        metadata.gg_ids_added += usize::from(id_added);
+        metadata.gg_parents_updated += usize::from(parent_updated);
+        metadata.gg_parents_removed += usize::from(parent_removed);
+        below_rewritten = below_rewritten || normalized != message;
+        rewritten.push(below_rewritten);
+        previous_gg_id = Some(gg_id);
+    }
+
+    let pr_base = stack.pr_base(&repo, &config)?;
+    let mut force_draft = draft;
+    let mut entries: Vec<SyncPlanEntryJson> = Vec::with_capacity(end_pos);
+    for (i, entry) in stack.entries[..end_pos].iter().enumerate() {
+        let branch = stack.entry_branch_name(entry);
+        let push = match branch
+            .as_deref()
+            .and_then(|b| git::get_remote_branch_oid(&repo, remote, b))
+        {
+            None => "create",
+            Some(remote) if rewritten[i] || remote != entry.oid => "force_push",
+            Some(_) => "up_to_date",
+        };
+
+        // Same walk-back as compute_target_branch, over the planned state.
+        let target_branch = match entries.iter().rev().find(|e| e.pr_action != "skip_closed") {
+            Some(prev) => prev.branch.clone(),
+            None => Some(pr_base.clone()),
+        };
+
+        let raw_title = strip_gg_id_from_message(&entry.title);
+        if !force_draft && is_wip_or_draft_prefix(&raw_title) {
+            force_draft = true;
+        }
+
+        let pr_number = entry
+            .gg_id
+            .as_ref()
+            .and_then(|gg_id| config.get_mr_for_entry(&stack.name, gg_id));
+        let pr_info = pr_number.and_then(|pr| provider.get_pr_info(pr).ok());
+        let current_base = pr_info.as_ref().and_then(|info| info.base_branch.clone());
+        let mut entry_draft = force_draft;
+        let pr_action = match (pr_number, &pr_info) {
+            (None, _) => "create",
+            (Some(_), Some(info))
+                if matches!(
+                    info.state,
+                    crate::provider::PrState::Merged | crate::provider::PrState::Closed
+                ) =>
+            {
+                "skip_closed"
+            }
+            (Some(_), info) => {
+                entry_draft = info.as_ref().map(|i| i.draft).unwrap_or(entry_draft);
+                if branch
+                    .as_deref()
+                    .and_then(|b| mismatched_pr_head_branch(info.as_ref(), b))
+                    .is_some()
+                {
+                    "recreate"
+                } else if current_base.is_none() || current_base != target_branch {
+                    "retarget"
+                } else if push != "up_to_date" || update_descriptions || update_title {
+                    "update"
+                } else {
+                    "up_to_date"
+                }
+            }
+        };
+
+        entries.push(SyncPlanEntryJson {
+            position: entry.position,
+            sha: entry.short_sha.clone(),
+            title: entry.title.clone(),
+            gg_id: entry.gg_id.clone(),
+            rewritten: rewritten[i],
+            branch,
+            push: push.to_string(),
+            pr_number,
+            pr_action: pr_action.to_string(),
+            target_branch,
+            current_base,
+            draft: entry_draft,
+            reviewers: if matches!(pr_action, "create" | "recreate") {
+                reviewers.clone()
+            } else {
+                Vec::new()
+            },
+        });
+    }
+
+    if only.is_some() {
+        entries.retain(|entry| entry.position == end_pos);
+    }
+
+    let plan = SyncPlanJson {
+        stack: stack.name.clone(),
+        base: stack.base.clone(),
+        behind_base,
+        warnings,
+        metadata,
+        entries,
+    };
+    if json {
+        print_json(&SyncPlanResponse {
+            version: OUTPUT_VERSION,
+            sync_plan: plan,
+        });
+    } else {
+        print_dry_run(&provider, &config, &upstream, &plan);
+    }
+    Ok(())
+}
+
+fn print_dry_run(provider: &Provider, config: &Config, upstream: &str, plan: &SyncPlanJson) {
+    for warning in &plan.warnings {
+        println!("{} {}", style("Warning:").yellow(), warning);
+    }
+    println!(
+        "{} {}",
+        style("Dry run:").bold(),
+        style("nothing will be rewritten, pushed, or changed on the remote.").dim()
+    );
+    if plan.entries.is_empty() {
+        println!("{}", style("Stack is empty. Nothing to sync.").dim());
+        return;
+    }
+    if let Some(behind) = plan.behind_base {
+        let what = if config.get_sync_auto_rebase() {
+            "would rebase first"
+        } else {
+            "would offer to rebase first"
+        };
+        println!(
+            "{} {} commits behind {}, {}",
+            style(plain::warn()).yellow().bold(),
+            behind,
+            upstream,
+            what
+        );
+    }
+    let metadata = &plan.metadata;
+    if metadata.gg_ids_added + metadata.gg_parents_updated + metadata.gg_parents_removed > 0 {
+        println!(
+            "{} Would add {} GG-ID(s) and fix {} GG-Parent trailer(s)",
+            style(plain::arrow()).cyan(),
+            metadata.gg_ids_added,
+            metadata.gg_parents_updated + metadata.gg_parents_removed
+        );
+    }
+
+    let pr = |number: u64| {
+        format!(
+            "{} {}{}",
+            provider.pr_label(),
+            provider.pr_number_prefix(),
+            number
+        )
+    };
+    let new_branch = "(new entry branch)".to_string();
+    for entry in &plan.entries {
+        let branch = entry.branch.as_ref().unwrap_or(&new_branch);
+        let target = entry.target_branch.as_ref().unwrap_or(&new_branch);
+        let push = match entry.push.as_str() {
+            "create" => format!("push {}", style(branch).cyan()),
+            "force_push" => format!("force-push {}", style(branch).cyan()),
+            _ => format!("{} up to date", style(branch).cyan()),
+        };
+        let pr_action = match (entry.pr_action.as_str(), entry.pr_number) {
+            ("create", _) => format!(
+                "create {}{} -> {}{}",
+                provider.pr_label(),
+                if entry.draft { " (draft)" } else { "" },
+                target,
+                if entry.reviewers.is_empty() {
+                    String::new()
+                } else {
+                    format!(", review: {}", entry.reviewers.join(", "))
+                }
+            ),
+            ("skip_closed", Some(n)) => format!("{} closed/merged, skip", pr(n)),
+            ("recreate", Some(n)) => format!("replace {} (head branch changed)", pr(n)),
+            ("retarget", Some(n)) => format!(
+                "retarget {}: {} -> {}",
+                pr(n),
+                entry.current_base.as_deref().unwrap_or("?"),
+                target
+            ),
+            ("update", Some(n)) => format!("update {}", pr(n)),
+            (_, Some(n)) => format!("{} up to date", pr(n)),
+            (_, None) => String::new(),
+        };
+        println!(
+            "  {} {} {}",
+            style(format!("[{}]", entry.position)).dim(),
+            style(&entry.sha).yellow(),
+            entry.title
+        );
+        let rewrite = if entry.rewritten {
+            format!("{}, ", style("rewrite").magenta())
+        } else {
+            String::new()
+        };
+        println!("      {}{}, {}", rewrite, push, pr_action);
+    }
+}
+
 /// Run the sync command
 #[allow(clippy::too_many_arguments)]
 pub fn run(
@@ -213,12 +587,32 @@ pub fn run(
     update_title: bool,
     run_lint: bool,
     until: Option<String>,
+    only: Option<String>,
+    reviewers: Vec<String>,
     no_verify: bool,
+    dry_run: bool,
 ) -> Result<()> {
+    if dry_run {
+        return run_dry_run(
+            draft,
+            json,
+            no_rebase_check,
+            update_descriptions,
+            update_title,
+            until,
+            only,
+            reviewers,
+        );
+    }
+    // --only syncs the same range as --until, but skips every entry below
+    // the target. Both take the same position/GG-ID/SHA syntax.
+    let partial = until.is_some() || only.is_some();
+    let until = until.or_else(|| only.clone());
+
     let repo = git::open_repo()?;
 
     let git_dir = repo.commondir();
-    let mut config = Config::load_with_global(git_dir)?;
+    let mut config = Config::load(git_dir)?;
 
     // Acquire operation lock + record a Pending op for the undo log.
     let (_lock, mut guard) = git::acquire_operation_lock_and_record(
@@ -247,7 +641,8 @@ pub fn run(
 
     // Load stack early to validate --until
     let initial_stack = Stack::load(&repo, &config)?;
-    let warnings: Vec<String> = initial_stack
+    guard.set_stack_name(&initial_stack.name);
+    let mut warnings: Vec<String> = initial_stack
         .prefix_mismatch(&config)
         .map(|mismatch| mismatch.warning_message())
         .into_iter()
@@ -305,23 +700,82 @@ pub fn run(
         None
     };
 
+    // Likewise the commit message policy: a blocked sync pushes nothing
+    let message_policy = Policy::from_config(&config.defaults.commit_messages)?;
+    let issue_pattern = issues::issue_regex(&config)?;
+    let issue_link = config
+        .defaults
+        .issue_link
+        .clone()
+        .unwrap_or_else(|| issues::DEFAULT_ISSUE_LINK.to_string());
+    let checked: Vec<&StackEntry> = initial_stack
+        .entries
+        .iter()
+        .filter(|e| match lint_end_pos {
+            Some(end) if only.is_some() => e.position == end,
+            Some(end) => e.position <= end,
+            None => true,
+        })
+        .collect();
+    let violations = commit_message_violations(&repo, &config, &message_policy, &checked)?;
+    if !violations.is_empty() {
+        if config.defaults.commit_messages.check == CommitMessageCheck::Block {
+            return Err(GgError::Other(format!(
+                "Commit subjects don't follow the commit message policy:\n{}\n\
+                 Reword them with `gg edit <position> -m <message>`, or set \
+                 defaults.commit_messages.check to warn.",
+                violations
+                    .iter()
+                    .map(|v| format!("  #{} {} {}", v.position, v.short_sha, v.title))
+                    .collect::<Vec<_>>()
+                    .join("\n")
+            )));
+        }
+        if !json && !jsonl {
+            commit_messages::print_violations(
+                &format!(
+                    "{} Commit subjects don't follow the commit message policy:",
+                    style("Warning:").yellow()
+                ),
+                &violations,
+            );
+        }
+        warnings.extend(violations.iter().map(violation_warning));
+    }
+
     // Detect and check provider
-    let provider = Provider::detect(&repo)?;
+    let provider = Provider::for_stack(&repo, &config, &initial_stack.name)?;
     provider.check_installed()?;
     provider.check_auth()?;
 
     // Fetch from remote to ensure we have up-to-date refs
-    let _ = git::fetch_and_prune();
+    let remote = config.get_remote_for_stack(&initial_stack.name).to_string();
+    let _ = git::fetch_and_prune(&remote);
 
     let mut rebased_before_sync = false;
     if !no_rebase_check {
-        rebased_before_sync = maybe_rebase_if_base_is_behind(
-            &repo,
-            &config,
-            initial_stack.base.as_str(),
-            json,
-            jsonl,
-        )?;
+        let upstream = if initial_stack.parent_stack(&repo, &config).is_some() {
+            initial_stack.base.clone()
+        } else if let Some(from) = config.get_from_for_stack(&initial_stack.name) {
+            // Stacks started with `gg co --from` only follow that ref
+            from.to_string()
+        } else {
+            format!("{}/{}", remote, initial_stack.base)
+        };
+        rebased_before_sync =
+            maybe_rebase_if_base_is_behind(&repo, &config, &upstream, json, jsonl)?;
+    }
+
+    // A dependent stack must sit on its parent's current head, otherwise the
+    // parent's old commits would be pushed as part of this stack.
+    if let Some(parent) = config.get_parent_for_stack(&initial_stack.name) {
+        let stack = Stack::load(&repo, &config)?;
+        if stack.stale_parent_fork(&repo, &config).is_some() {
+            return Err(GgError::Other(format!(
+                "Stack '{}' is out of date with its parent '{}'. Run `gg rebase` first.",
+                stack.name, parent
+            )));
+        }
     }
 
     let streamer = if jsonl {
@@ -425,27 +879,73 @@ pub fn run(
     let metadata_counts = git::normalize_stack_metadata(&repo, &stack)?;
     stack = Stack::load(&repo, &config)?;
 
-    // Determine sync range based on --until flag
+    // Determine sync range based on --until / --only
     let sync_until = if let Some(ref target) = until {
         Some(resolve_target(&stack, target)?)
     } else {
         None
     };
+    let only_pos = sync_until.filter(|_| only.is_some());
 
     let entries_to_sync = if let Some(end_pos) = sync_until {
         &stack.entries[..end_pos]
     } else {
         &stack.entries[..]
     };
+    if let Some(pos) = only_pos {
+        check_only_predecessors(&repo, &config, &stack, pos, json || jsonl)?;
+    }
+
+    // Fail before pushing anything if a push rule (e.g. GitLab's "reject
+    // unsigned commits") would reject part of the stack.
+    let push_entries: Vec<&crate::stack::StackEntry> = entries_to_sync
+        .iter()
+        .filter(|entry| only_pos.is_none_or(|pos| entry.position == pos))
+        .collect();
+    protection::preflight(&repo, &provider, &stack.base, &push_entries, Stage::Push)?;
+    let pr_base = stack.pr_base(&repo, &config)?;
+    for entry in push_entries.iter() {
+        if let Some(branch) = stack.entry_branch_name(entry) {
+            git::guard_protected_branch(&config, &branch)?;
+        }
+    }
+
+    if !json && !jsonl {
+        scope::warn_entries(
+            &repo,
+            config.get_scope_for_stack(&stack.name),
+            &push_entries,
+        )?;
+    }
+
+    hooks::run(
+        &repo,
+        &config,
+        Hook::PreSync,
+        &HookContext {
+            stack: stack.name.clone(),
+            base: stack.base.clone(),
+            entries: push_entries.iter().map(|&e| e.into()).collect(),
+        },
+    )?;
 
     // Load optional PR template
-    let pr_template = template::load_template(git_dir);
+    let pr_template = template::load_template(git_dir, repo.workdir());
+    let stack_template = pr_template.as_deref().is_some_and(template::uses_stack);
+    let mut template_rows = template::stack_entries(&stack);
+    let stack_description = crate::stack::stack_description(&repo, &config, &stack.name);
+    let mut templated_bodies: Vec<TemplatedBody> = Vec::new();
+    let new_pr_settings = NewPrSettings {
+        reviewers: merge_reviewers(config.get_reviewers_for_stack(&stack.name), &reviewers),
+        labels: config.defaults.labels.clone(),
+        milestone: config.defaults.milestone.clone(),
+    };
 
     // Sync progress
-    let pb = if json || jsonl {
+    let pb = if json || jsonl || plain::is_enabled() {
         ProgressBar::hidden()
     } else if atty::is(atty::Stream::Stderr) {
-        let pb = ProgressBar::new(entries_to_sync.len() as u64);
+        let pb = ProgressBar::new(push_entries.len() as u64);
         pb.set_style(
             ProgressStyle::default_bar()
                 .template("{spinner:.green} [{bar:40.cyan/blue}] {pos}/{len} {msg}")
@@ -467,6 +967,51 @@ pub fn run(
     // when computing their target branch (walk-back algorithm for stacked MRs).
     let mut entry_is_closed: Vec<bool> = Vec::with_capacity(entries_to_sync.len());
 
+    // Push every outdated entry branch and look up every mapped PR/MR up
+    // front, `sync_jobs` at a time. The loop below reports the results in
+    // stack order and retries failed pushes one by one, so lease prompts and
+    // hook errors read the same as a sequential sync.
+    let jobs = config.get_sync_jobs();
+    let mut to_push: Vec<String> = Vec::new();
+    for entry in &push_entries {
+        let branch = stack.entry_branch_name(entry).unwrap();
+        create_entry_branch(&repo, &stack, entry, &branch)?;
+        if git::get_remote_branch_oid(&repo, &remote, &branch) != Some(entry.oid) {
+            to_push.push(branch);
+        }
+    }
+    pb.set_message("Pushing...");
+    let mut prepushed: HashMap<String, bool> = HashMap::new();
+    for (branch, result) in to_push.iter().zip(git::push_branches(
+        &remote, &to_push, true, force, no_verify, jobs,
+    )) {
+        if result.is_ok() {
+            let effect = RemoteEffect::Pushed {
+                remote: remote.clone(),
+                branch: branch.clone(),
+                force,
+            };
+            remote_effects.push(effect.clone());
+            touched_remote = true;
+            guard.record_remote_effect(effect);
+        }
+        prepushed.insert(branch.clone(), result.is_ok());
+    }
+    let mapped_prs: Vec<u64> = push_entries
+        .iter()
+        .filter_map(|entry| {
+            let gg_id = entry.gg_id.as_ref()?;
+            config.get_mr_for_entry(&stack.name, gg_id)
+        })
+        .collect();
+    let mut pr_infos: HashMap<u64, crate::provider::PrInfo> = mapped_prs
+        .iter()
+        .zip(parallel::map(&mapped_prs, jobs, |&pr| {
+            provider.get_pr_info(pr).ok()
+        }))
+        .filter_map(|(&pr, info)| Some((pr, info?)))
+        .collect();
+
     if let Some(s) = streamer.as_mut() {
         s.emit(&SyncStreamingResponse {
             version: OUTPUT_VERSION,
@@ -474,7 +1019,7 @@ pub fn run(
             event: SyncStreamingEvent::Start {
                 stack: stack.name.clone(),
                 base: stack.base.clone(),
-                total_entries: entries_to_sync.len(),
+                total_entries: push_entries.len(),
             },
         });
     }
@@ -485,6 +1030,34 @@ pub fn run(
         let commit = repo.find_commit(entry.oid)?;
         let raw_title = strip_gg_id_from_message(&entry.title);
 
+        // Under --only, entries below the target are neither pushed nor
+        // touched; only their PR/MR state feeds the target-branch walk-back.
+        if only_pos.is_some_and(|pos| entry.position != pos) {
+            if !force_draft && is_wip_or_draft_prefix(&raw_title) {
+                force_draft = true;
+            }
+            let pr_state = config
+                .get_mr_for_entry(&stack.name, gg_id)
+                .and_then(|pr| Some((pr, provider.get_pr_info(pr).ok()?.state)));
+            entry_is_closed.push(pr_state.as_ref().is_some_and(|(_, state)| {
+                matches!(
+                    state,
+                    crate::provider::PrState::Merged | crate::provider::PrState::Closed
+                )
+            }));
+            nav_snapshots.push(pr_state.map(|(pr_number, state)| NavEntrySnapshot {
+                pr_number,
+                pr_state: match state {
+                    crate::provider::PrState::Open => stack_nav::PrEntryState::Open,
+                    crate::provider::PrState::Draft => stack_nav::PrEntryState::Draft,
+                    crate::provider::PrState::Merged => stack_nav::PrEntryState::Merged,
+                    crate::provider::PrState::Closed => stack_nav::PrEntryState::Closed,
+                },
+                json_index: 0,
+            }));
+            continue;
+        }
+
         if let Some(s) = streamer.as_mut() {
             s.emit(&SyncStreamingResponse {
                 version: OUTPUT_VERSION,
@@ -504,7 +1077,12 @@ pub fn run(
         }
         let entry_draft = force_draft;
 
-        let title = clean_title(&raw_title);
+        let message = commit.message().unwrap_or("");
+        let title = message_policy.pr_title(&clean_title(&raw_title), message);
+        let type_labels = message_policy
+            .parse(message)
+            .map(|subject| message_policy.labels(&subject))
+            .unwrap_or_default();
 
         let mut action = "up_to_date".to_string();
         let mut pr_number: Option<u64> = None;
@@ -515,22 +1093,32 @@ pub fn run(
         let mut effective_draft = entry_draft;
         let mut is_entry_closed = false;
 
+        let mut commit_description = get_commit_description(&commit);
+        if let Some(pattern) = &issue_pattern {
+            let keys = issues::entry_keys(pattern, &stack.name, message);
+            commit_description = issues::append_links(commit_description, &keys, &issue_link);
+        }
         let (title, description) = build_pr_payload(
             &title,
-            get_commit_description(&commit),
+            commit_description.clone(),
             &stack.name,
             &entry.short_sha,
             pr_template.as_deref(),
+            Some(&TemplateStack {
+                position: entry.position,
+                base: &stack.base,
+                description: stack_description.as_deref(),
+                number_prefix: provider.pr_number_prefix(),
+                entries: &template_rows,
+            }),
         );
 
         pb.set_message(format!("Processing {}...", entry.short_sha));
 
-        // Create/update the remote branch for this commit
-        create_entry_branch(&repo, &stack, entry, &entry_branch)?;
-
-        // Check if remote branch exists and has the same OID as local
-        let remote_oid = git::get_remote_branch_oid(&repo, &entry_branch);
-        let needs_push = remote_oid != Some(entry.oid);
+        // Branches already pushed above only need reporting; failed ones
+        // are pushed again here.
+        let pushed_early = prepushed.get(&entry_branch).copied();
+        let needs_push = pushed_early.is_some();
 
         // Only push if the remote is different or doesn't exist
         if needs_push {
@@ -548,7 +1136,11 @@ pub fn run(
             // Push the branch (always force-push with lease because rebases change commit SHAs)
             // This is safe because each entry branch is owned by this stack
             // If --force is passed, use hard force as an escape hatch
-            let push_result = git::push_branch(&entry_branch, true, force, no_verify);
+            let push_result = if pushed_early == Some(true) {
+                Ok(())
+            } else {
+                git::push_branch(&remote, &entry_branch, true, force, no_verify)
+            };
             if let Err(e) = push_result {
                 pb.finish_and_clear();
                 if json || jsonl {
@@ -604,19 +1196,21 @@ pub fn run(
             // Record the push as a remote effect. `sync` always pushes with
             // force-with-lease because rebases rewrite entry-branch history;
             // the `force` field here reflects the hard --force escape hatch.
-            let effect = RemoteEffect::Pushed {
-                remote: "origin".to_string(),
-                branch: entry_branch.clone(),
-                force,
-            };
-            remote_effects.push(effect.clone());
-            touched_remote = true;
-            guard.record_remote_effect(effect);
+            if pushed_early != Some(true) {
+                let effect = RemoteEffect::Pushed {
+                    remote: remote.clone(),
+                    branch: entry_branch.clone(),
+                    force,
+                };
+                remote_effects.push(effect.clone());
+                touched_remote = true;
+                guard.record_remote_effect(effect);
+            }
         }
 
         // Determine target branch for MR — uses walk-back to skip merged predecessors.
         let target_branch =
-            compute_target_branch(i, &stack.base, entries_to_sync, &entry_is_closed, &stack);
+            compute_target_branch(i, &pr_base, entries_to_sync, &entry_is_closed, &stack);
 
         // Create or update PR
         let existing_pr = config.get_mr_for_entry(&stack.name, gg_id);
@@ -625,7 +1219,9 @@ pub fn run(
             Some(pr_num) => {
                 pr_number = Some(pr_num);
                 // Check if PR is still open before updating
-                let pr_info = provider.get_pr_info(pr_num).ok();
+                let pr_info = pr_infos
+                    .remove(&pr_num)
+                    .or_else(|| provider.get_pr_info(pr_num).ok());
                 pr_url = pr_info.as_ref().map(|info| info.url.clone());
                 // Cache the state so the nav reconcile pass can reuse it without
                 // a second network round-trip.
@@ -662,7 +1258,7 @@ pub fn run(
                     if !json && !jsonl {
                         pb.println(format!(
                             "{} {} {}{} already closed/merged, skipping",
-                            style("○").dim(),
+                            style(plain::pending()).dim(),
                             provider.pr_label(),
                             provider.pr_number_prefix(),
                             pr_num
@@ -717,6 +1313,16 @@ pub fn run(
                                 stack_nav::PrEntryState::Open
                             });
                             action = "recreated".to_string();
+                            if let Some(e) = apply_new_pr_settings(
+                                &provider,
+                                result.number,
+                                &new_pr_settings,
+                                &type_labels,
+                                &pb,
+                                json || jsonl,
+                            ) {
+                                entry_error.get_or_insert(e);
+                            }
 
                             let created_effect = RemoteEffect::PrCreated {
                                 number: result.number,
@@ -916,18 +1522,35 @@ pub fn run(
                         }
                     }
 
-                    // Update PR/MR base if needed. A successful `update_pr_base`
-                    // call mutates remote state — even if the new base matches
-                    // what the API already had, we've made a request that the
-                    // provider treats as an authoritative update. Mark
-                    // `touched_remote` so `gg undo` refuses to replay this sync
-                    // locally (there's no safe local inverse for a remote base
-                    // change).
-                    match provider.update_pr_base(pr_num, &target_branch) {
-                        Ok(()) => {
+                    // Update PR/MR base if it drifted from the stack chain (or
+                    // we could not read it). A successful `update_pr_base`
+                    // call mutates remote state; mark `touched_remote` so
+                    // `gg undo` refuses to replay this sync locally (there's
+                    // no safe local inverse for a remote base change).
+                    let current_base = pr_info.as_ref().and_then(|info| info.base_branch.clone());
+                    let base_update = if current_base.as_deref() == Some(target_branch.as_str()) {
+                        Ok(())
+                    } else {
+                        provider.update_pr_base(pr_num, &target_branch).map(|()| {
                             touched_remote = true;
                             guard.mark_touched_remote();
-                        }
+                            if let Some(old_base) = &current_base {
+                                if !json && !jsonl {
+                                    pb.println(format!(
+                                        "{} Retargeted {} {}{}: {} -> {}",
+                                        style("OK").green().bold(),
+                                        provider.pr_label(),
+                                        provider.pr_number_prefix(),
+                                        pr_num,
+                                        style(old_base).dim(),
+                                        style(&target_branch).cyan()
+                                    ));
+                                }
+                            }
+                        })
+                    };
+                    match base_update {
+                        Ok(()) => {}
                         Err(e) => {
                             if !json && !jsonl {
                                 pb.println(format!(
@@ -1013,6 +1636,16 @@ pub fn run(
                             Some(result.url.clone())
                         };
                         action = "created".to_string();
+                        if let Some(e) = apply_new_pr_settings(
+                            &provider,
+                            result.number,
+                            &new_pr_settings,
+                            &type_labels,
+                            &pb,
+                            json || jsonl,
+                        ) {
+                            entry_error = Some(e);
+                        }
 
                         // Record the PR creation as a remote effect so `gg undo`
                         // can surface a provider-specific revert hint. Persist
@@ -1065,6 +1698,24 @@ pub fn run(
             }
         }
 
+        if let Some(num) = pr_number {
+            if let Some(row) = template_rows.get_mut(entry.position - 1) {
+                row.pr_number = Some(num);
+            }
+            if stack_template
+                && (action == "created" || (action == "updated" && update_descriptions))
+            {
+                templated_bodies.push(TemplatedBody {
+                    position: entry.position,
+                    pr_number: num,
+                    title: title.clone(),
+                    description: commit_description,
+                    short_sha: entry.short_sha.clone(),
+                    rendered: description.clone(),
+                });
+            }
+        }
+
         if json || jsonl {
             json_entries.push(SyncEntryResultJson {
                 position: entry.position,
@@ -1130,14 +1781,14 @@ pub fn run(
 
     // --- Nav-comment reconcile pass ---
     //
-    // Skipped under --until to avoid inconsistent nav comments: a partial sync
+    // Skipped under --until/--only to avoid inconsistent nav comments: a partial sync
     // cannot vouch for all PRs in the stack, and the single-entry skip rule
     // would misfire for partial subsets. Full `gg sync` (no --until) will
     // reconcile navigation across the whole stack.
     // Skip nav reconcile if any entry failed during the sync — a partial set of
     // PR numbers would produce truncated stack navigation on every other PR in
     // the stack. The next full successful sync will reconcile.
-    if until.is_none() && nav_snapshots.iter().all(|s| s.is_some()) {
+    if !partial && nav_snapshots.iter().all(|s| s.is_some()) {
         // For each synced entry whose PR exists and is reachable, decide whether
         // to create/update/delete the managed nav comment based on:
         //   - the stack_nav_comments setting
@@ -1328,6 +1979,152 @@ pub fn run(
         }
     } // end nav-comment reconcile
 
+    // --- Stack-aware templates ---
+    //
+    // `{{stack_table}}` and `{{part}}` rendered in the loop only knew the
+    // PRs/MRs created before each entry. Now that every number is known,
+    // re-render those bodies and rewrite the managed block where it changed.
+    if let Some(tmpl) = pr_template.as_deref().filter(|_| stack_template) {
+        for body in &templated_bodies {
+            let (_, description) = build_pr_payload(
+                &body.title,
+                body.description.clone(),
+                &stack.name,
+                &body.short_sha,
+                Some(tmpl),
+                Some(&TemplateStack {
+                    position: body.position,
+                    base: &stack.base,
+                    description: stack_description.as_deref(),
+                    number_prefix: provider.pr_number_prefix(),
+                    entries: &template_rows,
+                }),
+            );
+            if description == body.rendered {
+                continue;
+            }
+            let result = provider.get_pr_body(body.pr_number).and_then(|remote| {
+                match managed_body::replace_managed(&remote, &description) {
+                    Some(new_body) => provider.update_pr_description(body.pr_number, &new_body),
+                    None => Ok(()),
+                }
+            });
+            if let Err(e) = result {
+                if !json && !jsonl {
+                    println!(
+                        "{} Could not refresh the stack in {} {}{} description: {}",
+                        style("Warning:").yellow(),
+                        provider.pr_label(),
+                        provider.pr_number_prefix(),
+                        body.pr_number,
+                        e
+                    );
+                }
+            }
+        }
+    }
+
+    // --- Stack graph in descriptions ---
+    //
+    // Like nav comments, this needs every PR number, so it runs after the
+    // main loop and is skipped for partial (--until/--only) or failed syncs.
+    let graph_mode = config.get_stack_graph();
+    if graph_mode != StackGraphMode::Off
+        && !partial
+        && entries_to_sync.len() >= 2
+        && nav_snapshots.iter().all(|s| s.is_some())
+    {
+        let snapshots: Vec<&NavEntrySnapshot> = nav_snapshots.iter().flatten().collect();
+        for (i, snap) in snapshots.iter().enumerate() {
+            if matches!(
+                snap.pr_state,
+                stack_nav::PrEntryState::Merged | stack_nav::PrEntryState::Closed
+            ) {
+                continue;
+            }
+            let graph_entries: Vec<stack_graph::GraphEntry> = snapshots
+                .iter()
+                .zip(entries_to_sync)
+                .enumerate()
+                .map(|(j, (s, entry))| stack_graph::GraphEntry {
+                    pr_number: s.pr_number,
+                    title: entry.title.clone(),
+                    merged: s.pr_state == stack_nav::PrEntryState::Merged,
+                    is_current: j == i,
+                })
+                .collect();
+            if let Err(e) = upsert_stack_graph(
+                &provider,
+                git_dir,
+                &stack.base,
+                snap.pr_number,
+                &graph_entries,
+                graph_mode,
+            ) {
+                if !json && !jsonl {
+                    println!(
+                        "{} Could not update stack graph on {} {}{}: {}",
+                        style("Warning:").yellow(),
+                        provider.pr_label(),
+                        provider.pr_number_prefix(),
+                        snap.pr_number,
+                        e
+                    );
+                }
+            }
+        }
+    }
+
+    // --- GitLab MR dependencies ---
+    //
+    // Mark each open MR as blocked by its nearest open predecessor so the
+    // ordering constraint shows up natively in GitLab. Failures are warnings:
+    // MR dependencies need a paid tier and must never fail a sync.
+    if matches!(provider, Provider::GitLab) && config.get_gitlab_sync_mr_dependencies() {
+        let stack_numbers: Vec<u64> = nav_snapshots
+            .iter()
+            .flatten()
+            .map(|snap| snap.pr_number)
+            .collect();
+        for (i, snap) in nav_snapshots.iter().enumerate() {
+            let Some(snap) = snap else { continue };
+            if only_pos.is_some_and(|pos| i + 1 != pos) {
+                continue;
+            }
+            if matches!(
+                snap.pr_state,
+                stack_nav::PrEntryState::Merged | stack_nav::PrEntryState::Closed
+            ) {
+                continue;
+            }
+            let Some(blocking) = dependency_blocker(i, &nav_snapshots) else {
+                continue;
+            };
+            match provider.sync_pr_dependency(snap.pr_number, blocking, &stack_numbers) {
+                Ok(true) => {
+                    touched_remote = true;
+                    guard.mark_touched_remote();
+                }
+                Ok(false) => {}
+                Err(e) => {
+                    if !json && !jsonl {
+                        println!(
+                            "{} Could not set dependencies on {} {}{}: {}",
+                            style("Warning:").yellow(),
+                            provider.pr_label(),
+                            provider.pr_number_prefix(),
+                            snap.pr_number,
+                            e
+                        );
+                    }
+                    // One failure usually means the feature is unavailable
+                    // for this project; don't repeat the warning per MR.
+                    break;
+                }
+            }
+        }
+    }
+
     // Save updated config
     config.save(git_dir)?;
 
@@ -1371,7 +2168,7 @@ pub fn run(
         println!(
             "{} Synced {} commits",
             style("OK").green().bold(),
-            entries_to_sync.len()
+            push_entries.len()
         );
     }
 
@@ -1386,6 +2183,149 @@ pub fn run(
     Ok(())
 }
 
+/// Configured reviewers plus the `--reviewer` ones, without `@` prefixes
+/// or duplicates.
+fn merge_reviewers(configured: &[String], extra: &[String]) -> Vec<String> {
+    let mut merged: Vec<String> = Vec::new();
+    for reviewer in configured.iter().chain(extra) {
+        let reviewer = reviewer.trim().trim_start_matches('@');
+        if !reviewer.is_empty() && !merged.iter().any(|r| r == reviewer) {
+            merged.push(reviewer.to_string());
+        }
+    }
+    merged
+}
+
+/// A PR/MR body rendered from a stack-aware template, kept so it can be
+/// re-rendered once every PR/MR in the stack has a number
+struct TemplatedBody {
+    position: usize,
+    pr_number: u64,
+    title: String,
+    description: Option<String>,
+    short_sha: String,
+    rendered: String,
+}
+
+/// Reviewers, labels and milestone applied to each PR/MR sync creates
+struct NewPrSettings {
+    reviewers: Vec<String>,
+    labels: Vec<String>,
+    milestone: Option<String>,
+}
+
+/// Apply [`NewPrSettings`], plus the labels derived from the entry's commit
+/// type, to a newly created PR/MR. Failures are warnings, not sync errors;
+/// the first one is returned for the JSON entry.
+fn apply_new_pr_settings(
+    provider: &Provider,
+    number: u64,
+    settings: &NewPrSettings,
+    type_labels: &[String],
+    pb: &ProgressBar,
+    quiet: bool,
+) -> Option<String> {
+    let mut results = Vec::new();
+    if !settings.reviewers.is_empty() {
+        results.push((
+            "request reviewers",
+            provider.request_reviewers(number, &settings.reviewers),
+        ));
+    }
+    let mut labels = settings.labels.clone();
+    for label in type_labels {
+        if !labels.contains(label) {
+            labels.push(label.clone());
+        }
+    }
+    if !labels.is_empty() {
+        results.push(("add labels", provider.add_labels(number, &labels)));
+    }
+    if let Some(milestone) = &settings.milestone {
+        results.push(("set milestone", provider.set_milestone(number, milestone)));
+    }
+
+    let mut first_error = None;
+    for (what, result) in results {
+        let Err(e) = result else { continue };
+        if !quiet {
+            pb.println(format!(
+                "{} Could not {} on {} {}{}: {}",
+                style("Warning:").yellow(),
+                what,
+                provider.pr_label(),
+                provider.pr_number_prefix(),
+                number,
+                e
+            ));
+        }
+        first_error.get_or_insert(format!("Could not {what}: {e}"));
+    }
+    first_error
+}
+
+/// Entries whose subject breaks `defaults.commit_messages`; none when the
+/// check is off.
+fn commit_message_violations(
+    repo: &Repository,
+    config: &Config,
+    policy: &Policy,
+    entries: &[&StackEntry],
+) -> Result<Vec<MessageViolation>> {
+    if config.defaults.commit_messages.check == CommitMessageCheck::Off {
+        return Ok(Vec::new());
+    }
+    commit_messages::check_entries(repo, policy, entries)
+}
+
+fn violation_warning(violation: &MessageViolation) -> String {
+    format!(
+        "#{} {} doesn't follow the commit message policy: {}",
+        violation.position, violation.short_sha, violation.title
+    )
+}
+
+/// Before a `--only` sync, make sure the entries below the target exist on
+/// the remote so its PR/MR has a branch to target. Entries whose remote
+/// branch is out of date are only warned about: the PR/MR diff will include
+/// their local changes until they are synced too.
+fn check_only_predecessors(
+    repo: &Repository,
+    config: &Config,
+    stack: &Stack,
+    position: usize,
+    quiet: bool,
+) -> Result<()> {
+    let remote = config.get_remote_for_stack(&stack.name);
+    for entry in &stack.entries[..position - 1] {
+        let Some(branch) = stack.entry_branch_name(entry) else {
+            continue;
+        };
+        let has_pr = entry
+            .gg_id
+            .as_ref()
+            .is_some_and(|gg_id| config.get_mr_for_entry(&stack.name, gg_id).is_some());
+        match git::get_remote_branch_oid(repo, remote, &branch) {
+            None if !has_pr => {
+                return Err(GgError::Other(format!(
+                    "#{} has not been synced yet. Run `gg sync --until {}` first.",
+                    entry.position, entry.position
+                )));
+            }
+            Some(remote) if remote != entry.oid && !quiet => {
+                println!(
+                    "{} #{} is out of date on the remote; #{}'s diff may include its changes",
+                    style("Warning:").yellow(),
+                    entry.position,
+                    position
+                );
+            }
+            _ => {}
+        }
+    }
+    Ok(())
+}
+
 fn restore_sync_start_position(
     repo: &Repository,
     start_branch: Option<&str>,
@@ -1396,7 +2336,7 @@ fn restore_sync_start_position(
     if !json && !jsonl {
         println!(
             "{} Restoring repository to pre-sync state...",
-            style("→").cyan()
+            style(plain::arrow()).cyan()
         );
     }
 
@@ -1417,12 +2357,13 @@ fn restore_sync_start_position(
     Ok(())
 }
 
-fn build_pr_payload(
+pub(crate) fn build_pr_payload(
     title: &str,
     description: Option<String>,
     stack_name: &str,
     short_sha: &str,
     template: Option<&str>,
+    stack: Option<&TemplateStack>,
 ) -> (String, String) {
     let body = match template {
         Some(tmpl) => {
@@ -1432,6 +2373,7 @@ fn build_pr_payload(
                 stack_name,
                 commit_sha: short_sha,
                 title,
+                stack,
             };
             template::render_template(tmpl, &ctx)
         }
@@ -1450,7 +2392,7 @@ fn is_wip_or_draft_prefix(title: &str) -> bool {
     lower.starts_with("wip:") || lower.starts_with("draft:")
 }
 
-fn clean_title(title: &str) -> String {
+pub(crate) fn clean_title(title: &str) -> String {
     let trimmed = title.trim();
     trimmed.strip_suffix('.').unwrap_or(trimmed).to_string()
 }
@@ -1550,14 +2492,45 @@ fn create_entry_branch(
 #[cfg(test)]
 mod tests {
     use super::{
-        build_pr_payload, clean_title, compute_target_branch, description_with_replacement_note,
-        ensure_draft_prefix_for_gitlab, is_wip_or_draft_prefix, mismatched_pr_head_branch,
-        replacement_closing_comment,
+        build_pr_payload, clean_title, compute_target_branch, dependency_blocker,
+        description_with_replacement_note, ensure_draft_prefix_for_gitlab, is_wip_or_draft_prefix,
+        merge_reviewers, mismatched_pr_head_branch, replacement_closing_comment, NavEntrySnapshot,
     };
     use crate::git;
     use crate::output::{
         SyncEntryResultJson, SyncMetadataJson, SyncResponse, SyncResultJson, OUTPUT_VERSION,
     };
+    use crate::stack_nav;
+
+    fn nav_snap(pr_number: u64, pr_state: stack_nav::PrEntryState) -> Option<NavEntrySnapshot> {
+        Some(NavEntrySnapshot {
+            pr_number,
+            pr_state,
+            json_index: 0,
+        })
+    }
+
+    #[test]
+    fn test_dependency_blocker_skips_merged_predecessors() {
+        use stack_nav::PrEntryState::*;
+        let snaps = vec![
+            nav_snap(10, Merged),
+            nav_snap(11, Open),
+            nav_snap(12, Merged),
+            nav_snap(13, Draft),
+        ];
+        assert_eq!(dependency_blocker(0, &snaps), Some(None));
+        assert_eq!(dependency_blocker(1, &snaps), Some(None));
+        assert_eq!(dependency_blocker(3, &snaps), Some(Some(11)));
+    }
+
+    #[test]
+    fn test_dependency_blocker_unknown_when_predecessor_has_no_pr() {
+        use stack_nav::PrEntryState::*;
+        let snaps = vec![nav_snap(10, Open), None, nav_snap(12, Open)];
+        assert_eq!(dependency_blocker(2, &snaps), None);
+        assert_eq!(dependency_blocker(1, &snaps), Some(Some(10)));
+    }
 
     #[test]
     fn test_get_remote_branch_oid() {
@@ -1570,7 +2543,7 @@ mod tests {
         let repo = Repository::init(temp_dir.path()).unwrap();
 
         // Non-existent remote branch should return None
-        let result = git::get_remote_branch_oid(&repo, "non-existent-branch");
+        let result = git::get_remote_branch_oid(&repo, "origin", "non-existent-branch");
         assert!(result.is_none());
     }
 
@@ -1582,6 +2555,7 @@ mod tests {
             "stack",
             "abc123",
             None,
+            None,
         );
         assert_eq!(title, "Add feature");
         assert_eq!(description, "Details here");
@@ -1589,7 +2563,8 @@ mod tests {
 
     #[test]
     fn test_build_pr_payload_falls_back_without_description() {
-        let (title, description) = build_pr_payload("Add feature", None, "stack", "abc123", None);
+        let (title, description) =
+            build_pr_payload("Add feature", None, "stack", "abc123", None, None);
         assert_eq!(title, "Add feature");
         assert_eq!(description, "Part of stack `stack`\n\nCommit: abc123");
     }
@@ -1601,6 +2576,16 @@ mod tests {
         assert_eq!(clean_title(" Add feature. "), "Add feature");
     }
 
+    #[test]
+    fn test_merge_reviewers_dedupes_and_strips_at() {
+        let configured = vec!["alice".to_string(), "acme/core".to_string()];
+        let extra = vec!["@bob".to_string(), "@alice".to_string(), " ".to_string()];
+        assert_eq!(
+            merge_reviewers(&configured, &extra),
+            vec!["alice", "acme/core", "bob"]
+        );
+    }
+
     #[test]
     fn test_is_wip_or_draft_prefix_case_insensitive() {
         assert!(is_wip_or_draft_prefix("WIP: something"));
@@ -1625,6 +2610,7 @@ mod tests {
             "stack",
             "abc123",
             None,
+            None,
         );
         // Verify the description is passed through unchanged
         assert_eq!(description, clean_description);
@@ -1642,6 +2628,7 @@ mod tests {
             "my-stack",
             "abc1234",
             Some(template),
+            None,
         );
         assert_eq!(title, "Add feature");
         assert_eq!(
@@ -1654,7 +2641,7 @@ mod tests {
     fn test_build_pr_payload_with_template_no_description() {
         let template = "## {{title}}\n\n{{description}}\n\nPart of `{{stack_name}}`";
         let (title, description) =
-            build_pr_payload("Fix bug", None, "bugfix", "def5678", Some(template));
+            build_pr_payload("Fix bug", None, "bugfix", "def5678", Some(template), None);
         assert_eq!(title, "Fix bug");
         // {{description}} should be replaced with empty string when None
         assert_eq!(description, "## Fix bug\n\n\n\nPart of `bugfix`");
@@ -1665,7 +2652,7 @@ mod tests {
         // When template is provided, it should be used even if description is None
         // (instead of the default fallback)
         let template = "Custom: {{title}}";
-        let (_, description) = build_pr_payload("Test", None, "stack", "abc", Some(template));
+        let (_, description) = build_pr_payload("Test", None, "stack", "abc", Some(template), None);
         assert_eq!(description, "Custom: Test");
         // Should NOT contain the default fallback
         assert!(!description.contains("Part of stack"));
@@ -1743,6 +2730,7 @@ mod tests {
             "stack",
             "abc123",
             None,
+            None,
         );
         let wrapped = managed_body::wrap(&description);
         assert!(wrapped.starts_with("<!-- gg:managed:start -->"));
@@ -1762,6 +2750,7 @@ mod tests {
             "stack",
             "abc123",
             None,
+            None,
         );
         let result = managed_body::replace_managed(&user_edited, &new_description).unwrap();
         assert!(result.contains("- [x] Tests pass"));
@@ -1791,11 +2780,13 @@ mod tests {
             state: crate::provider::PrState::Open,
             url: "https://example.com/pr/428".to_string(),
             head_branch: Some("testuser/old-stack--c-8b999da".to_string()),
+            base_branch: None,
             draft: false,
             approved: false,
             mergeable: true,
             changes_requested: false,
             detailed_merge_status: None,
+            updated_at_ms: None,
         };
 
         assert_eq!(
@@ -1812,14 +2803,17 @@ mod tests {
             state: crate::provider::PrState::Open,
             url: "https://example.com/pr/428".to_string(),
             head_branch: Some("testuser/new-stack--c-8b999da".to_string()),
+            base_branch: None,
             draft: false,
             approved: false,
             mergeable: true,
             changes_requested: false,
             detailed_merge_status: None,
+            updated_at_ms: None,
         };
         let unknown = crate::provider::PrInfo {
             head_branch: None,
+            base_branch: None,
             ..matching.clone()
         };
 
@@ -1862,6 +2856,7 @@ mod tests {
             "my-stack",
             "def456",
             Some(template),
+            None,
         );
         let wrapped = managed_body::wrap(&description);
 
@@ -1875,6 +2870,7 @@ mod tests {
             "my-stack",
             "def456",
             Some(template),
+            None,
         );
         let result = managed_body::replace_managed(&body, &new_desc).unwrap();
         assert!(result.contains("- [x] Review done"));
@@ -1963,6 +2959,9 @@ mod tests {
             position: 1,
             in_merge_train: false,
             merge_train_position: None,
+            unresolved_threads: None,
+            annotations: Default::default(),
+            signed: false,
             changes_requested: false,
             mergeable: false,
         }
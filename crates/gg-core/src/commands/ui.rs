@@ -0,0 +1,163 @@
+//! `gg ui` - Interactive stack dashboard
+//!
+//! Shows the stack in a TUI (see `ui_tui`) and runs the regular commands on
+//! the user's behalf: the TUI closes, the command prints as usual, and the
+//! dashboard reopens on the reloaded stack.
+
+use std::collections::HashMap;
+use std::io::{self, BufRead};
+
+use console::style;
+
+use crate::config::Config;
+use crate::error::{GgError, Result};
+use crate::git;
+use crate::provider::{CiStatus, PrState, Provider};
+use crate::stack::Stack;
+
+use super::land::{self, LandOptions};
+use super::ls::behind_count;
+use super::nav;
+use super::open::{self, OpenOptions};
+use super::reorder::{self, ReorderOptions};
+use super::sync;
+use super::ui_tui::{self, UiAction, UiEntry, UiView};
+
+/// PR/MR details kept between redraws so navigating doesn't re-query the
+/// provider; only the initial load, `R`, sync and land do.
+type PrCache = HashMap<u64, (Option<PrState>, Option<CiStatus>, bool)>;
+
+/// Run the ui command
+pub fn run() -> Result<()> {
+    if !(atty::is(atty::Stream::Stdin) && atty::is(atty::Stream::Stdout)) {
+        return Err(GgError::Other(
+            "gg ui needs an interactive terminal".to_string(),
+        ));
+    }
+
+    let mut cache = PrCache::new();
+    let mut refresh = true;
+    let mut cursor = None;
+    let mut message = None;
+
+    loop {
+        let repo = git::open_repo()?;
+        let config = Config::load(repo.commondir())?;
+        let mut stack = Stack::load(&repo, &config)?;
+        let provider = Provider::detect(&repo).ok();
+
+        if refresh {
+            if let Some(provider) = &provider {
+                stack.refresh_mr_info(provider)?;
+                cache = stack
+                    .entries
+                    .iter()
+                    .filter_map(|e| {
+                        e.mr_number
+                            .map(|n| (n, (e.mr_state.clone(), e.ci_status.clone(), e.approved)))
+                    })
+                    .collect();
+            }
+            refresh = false;
+        }
+
+        let view = UiView {
+            stack_name: stack.name.clone(),
+            base: stack.base.clone(),
+            behind_base: behind_count(&repo, config.get_remote_for_stack(&stack.name), &stack.base),
+            entries: ui_entries(&stack, &cache, provider.as_ref()),
+            message: message.take(),
+        };
+
+        let action = ui_tui::dashboard(view, cursor)?;
+        let (result, pause) = match &action {
+            UiAction::Quit => return Ok(()),
+            UiAction::Refresh => {
+                refresh = true;
+                continue;
+            }
+            UiAction::Checkout(position) => {
+                cursor = Some(*position);
+                (nav::move_to(Some(&position.to_string())), false)
+            }
+            UiAction::Sync => {
+                refresh = true;
+                let result = sync::run(
+                    false,
+                    false,
+                    false,
+                    false,
+                    false,
+                    false,
+                    false,
+                    config.get_sync_auto_lint(),
+                    None,
+                    None,
+                    Vec::new(),
+                    false,
+                    false,
+                );
+                (result, true)
+            }
+            UiAction::Land(position) => {
+                refresh = true;
+                let result = land::run(LandOptions {
+                    strategy: config.get_merge_strategy(),
+                    auto_clean: config.get_land_auto_clean(),
+                    admin: config.get_land_admin(),
+                    until: Some(position.to_string()),
+                    ..Default::default()
+                });
+                (result, true)
+            }
+            UiAction::Reorder => (reorder::run(ReorderOptions::default()), false),
+            UiAction::Open(position) => {
+                cursor = Some(*position);
+                let result = open::run(OpenOptions {
+                    target: Some(position.to_string()),
+                    ..Default::default()
+                });
+                (result, false)
+            }
+        };
+
+        if let Err(e) = result {
+            message = Some(format!("Error: {}", e));
+            if pause {
+                eprintln!("{} {}", style("error:").red().bold(), e);
+            }
+        }
+        if pause {
+            wait_for_enter();
+        }
+    }
+}
+
+fn ui_entries(stack: &Stack, cache: &PrCache, provider: Option<&Provider>) -> Vec<UiEntry> {
+    let prefix = provider.map(|p| p.pr_number_prefix()).unwrap_or("#");
+    let current = stack.current_position.map(|p| p + 1).unwrap_or(stack.len());
+
+    stack
+        .entries
+        .iter()
+        .map(|entry| {
+            let cached = entry.mr_number.and_then(|n| cache.get(&n));
+            UiEntry {
+                short_sha: entry.short_sha.clone(),
+                title: entry.title.clone(),
+                pr: entry.mr_number.map(|n| format!("{}{}", prefix, n)),
+                pr_state: cached.and_then(|c| c.0.clone()),
+                ci_status: cached.and_then(|c| c.1.clone()),
+                approved: cached.is_some_and(|c| c.2),
+                is_current: entry.position == current,
+            }
+        })
+        .collect()
+}
+
+fn wait_for_enter() {
+    println!();
+    println!("{}", style("Press Enter to return to gg ui").dim());
+    let mut line = String::new();
+    let _ = io::stdin().lock().read_line(&mut line);
+}
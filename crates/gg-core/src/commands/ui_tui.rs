@@ -0,0 +1,466 @@
+//! TUI for the `gg ui` stack dashboard
+//!
+//! A single-panel terminal UI (ratatui + crossterm) listing the stack with
+//! PR/MR state, CI and approvals. Each key that changes something returns an
+//! action to `gg ui`, which leaves the TUI, runs the command, and reopens:
+//! - Navigate with j/k or arrows, Enter/c to check out the selected entry
+//! - s sync, l land up to the selected entry, r reorder, o open the PR/MR
+//! - R refresh PR/MR state, q/Esc quit
+
+use std::io;
+
+use crossterm::{
+    event::{self, Event, KeyCode, KeyModifiers},
+    execute,
+    terminal::{disable_raw_mode, enable_raw_mode, EnterAlternateScreen, LeaveAlternateScreen},
+};
+use ratatui::{
+    backend::CrosstermBackend,
+    layout::{Constraint, Direction, Layout},
+    style::{Color, Modifier, Style},
+    text::{Line, Span},
+    widgets::{Block, Borders, List, ListItem, Paragraph, Wrap},
+    Frame, Terminal,
+};
+
+use crate::error::{GgError, Result};
+use crate::provider::{CiStatus, PrState};
+
+/// A stack entry for display in the dashboard
+#[derive(Debug, Clone)]
+pub struct UiEntry {
+    /// Short SHA for display
+    pub short_sha: String,
+    /// Commit title (first line)
+    pub title: String,
+    /// PR/MR reference as shown to the user (`#12`, `!12`)
+    pub pr: Option<String>,
+    pub pr_state: Option<PrState>,
+    pub ci_status: Option<CiStatus>,
+    pub approved: bool,
+    /// HEAD is on this entry
+    pub is_current: bool,
+}
+
+/// Everything the dashboard renders
+#[derive(Debug, Clone)]
+pub struct UiView {
+    pub stack_name: String,
+    pub base: String,
+    /// Commits origin/<base> is ahead of the local base
+    pub behind_base: Option<usize>,
+    /// Entries, index 0 = position 1 = bottom of stack
+    pub entries: Vec<UiEntry>,
+    /// Outcome of the previous action, shown above the key bar
+    pub message: Option<String>,
+}
+
+/// What the user asked for. Positions are 1-indexed.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum UiAction {
+    Quit,
+    Refresh,
+    Checkout(usize),
+    Sync,
+    Land(usize),
+    Reorder,
+    Open(usize),
+}
+
+/// State for the dashboard TUI
+struct UiTuiState {
+    view: UiView,
+    cursor: usize,
+    action: Option<UiAction>,
+}
+
+impl UiTuiState {
+    fn new(view: UiView, cursor: Option<usize>) -> Self {
+        let last = view.entries.len().saturating_sub(1);
+        let cursor = cursor
+            .map(|p| p.saturating_sub(1))
+            .or_else(|| view.entries.iter().position(|e| e.is_current))
+            .unwrap_or(last)
+            .min(last);
+        Self {
+            view,
+            cursor,
+            action: None,
+        }
+    }
+
+    /// Move cursor up
+    fn cursor_up(&mut self) {
+        if self.cursor > 0 {
+            self.cursor -= 1;
+        }
+    }
+
+    /// Move cursor down
+    fn cursor_down(&mut self) {
+        if self.cursor + 1 < self.view.entries.len() {
+            self.cursor += 1;
+        }
+    }
+
+    /// 1-indexed position under the cursor, if the stack has entries
+    fn selected(&self) -> Option<usize> {
+        (!self.view.entries.is_empty()).then_some(self.cursor + 1)
+    }
+}
+
+/// Terminal cleanup guard - restores terminal on drop
+struct TerminalGuard {
+    _private: (),
+}
+
+impl TerminalGuard {
+    fn new() -> Result<Self> {
+        enable_raw_mode()
+            .map_err(|e| GgError::Other(format!("Failed to enable raw mode: {}", e)))?;
+        if let Err(e) = execute!(io::stdout(), EnterAlternateScreen) {
+            let _ = disable_raw_mode();
+            return Err(GgError::Other(format!(
+                "Failed to enter alternate screen: {}",
+                e
+            )));
+        }
+        Ok(Self { _private: () })
+    }
+}
+
+impl Drop for TerminalGuard {
+    fn drop(&mut self) {
+        let _ = disable_raw_mode();
+        let _ = execute!(io::stdout(), LeaveAlternateScreen);
+    }
+}
+
+/// Show the dashboard until the user picks an action.
+///
+/// `cursor` is the 1-indexed position to select initially; None selects the
+/// current entry. Returns the action with the terminal already restored.
+pub fn dashboard(view: UiView, cursor: Option<usize>) -> Result<UiAction> {
+    let _guard = TerminalGuard::new()?;
+
+    let backend = CrosstermBackend::new(io::stdout());
+    let mut terminal = Terminal::new(backend)
+        .map_err(|e| GgError::Other(format!("Failed to create terminal: {}", e)))?;
+
+    let mut state = UiTuiState::new(view, cursor);
+
+    loop {
+        terminal
+            .draw(|f| draw(f, &state))
+            .map_err(|e| GgError::Other(format!("Failed to draw: {}", e)))?;
+
+        if event::poll(std::time::Duration::from_millis(100))
+            .map_err(|e| GgError::Other(format!("Event poll failed: {}", e)))?
+        {
+            if let Event::Key(key) =
+                event::read().map_err(|e| GgError::Other(format!("Event read failed: {}", e)))?
+            {
+                handle_key(&mut state, key.code, key.modifiers);
+
+                if let Some(action) = state.action.take() {
+                    return Ok(action);
+                }
+            }
+        }
+    }
+}
+
+/// Handle a key press
+fn handle_key(state: &mut UiTuiState, code: KeyCode, modifiers: KeyModifiers) {
+    // Ctrl+C always quits
+    if modifiers.contains(KeyModifiers::CONTROL) {
+        if let KeyCode::Char('c') = code {
+            state.action = Some(UiAction::Quit);
+            return;
+        }
+    }
+
+    let selected = state.selected();
+    state.action = match code {
+        KeyCode::Up | KeyCode::Char('k') => {
+            state.cursor_up();
+            None
+        }
+        KeyCode::Down | KeyCode::Char('j') => {
+            state.cursor_down();
+            None
+        }
+        KeyCode::Enter | KeyCode::Char('c') => selected.map(UiAction::Checkout),
+        KeyCode::Char('s') => Some(UiAction::Sync),
+        KeyCode::Char('l') => selected.map(UiAction::Land),
+        KeyCode::Char('r') if state.view.entries.len() > 1 => Some(UiAction::Reorder),
+        KeyCode::Char('o') => selected
+            .filter(|p| state.view.entries[p - 1].pr.is_some())
+            .map(UiAction::Open),
+        KeyCode::Char('R') | KeyCode::F(5) => Some(UiAction::Refresh),
+        KeyCode::Char('q') | KeyCode::Esc => Some(UiAction::Quit),
+        _ => None,
+    };
+}
+
+/// Draw the TUI
+fn draw(f: &mut Frame, state: &UiTuiState) {
+    let size = f.area();
+
+    // Layout: stack list + message line + status bar
+    let chunks = Layout::default()
+        .direction(Direction::Vertical)
+        .constraints([
+            Constraint::Min(1),
+            Constraint::Length(1),
+            Constraint::Length(1),
+        ])
+        .split(size);
+
+    draw_stack(f, state, chunks[0]);
+    if let Some(message) = &state.view.message {
+        f.render_widget(
+            Paragraph::new(Span::styled(
+                format!(" {}", message),
+                Style::default().fg(Color::Yellow),
+            )),
+            chunks[1],
+        );
+    }
+    draw_status_bar(f, chunks[2]);
+}
+
+fn pr_state_span(entry: &UiEntry) -> Span<'static> {
+    let (text, color) = match (&entry.pr_state, entry.approved) {
+        (Some(PrState::Merged), _) => ("merged", Color::Green),
+        (Some(PrState::Closed), _) => ("closed", Color::Red),
+        (Some(PrState::Draft), _) => ("draft", Color::DarkGray),
+        (Some(PrState::Open), true) => ("approved", Color::Green),
+        (Some(PrState::Open), false) => ("open", Color::Yellow),
+        (None, _) if entry.pr.is_some() => ("", Color::DarkGray),
+        (None, _) => ("not pushed", Color::DarkGray),
+    };
+    Span::styled(text, Style::default().fg(color))
+}
+
+fn ci_span(status: Option<&CiStatus>) -> Span<'static> {
+    match status {
+        Some(CiStatus::Success) => Span::styled(" ✓", Style::default().fg(Color::Green)),
+        Some(CiStatus::Failed) => Span::styled(" ✗", Style::default().fg(Color::Red)),
+        Some(CiStatus::Running) => Span::styled(" ●", Style::default().fg(Color::Yellow)),
+        Some(CiStatus::Pending) => Span::styled(" ○", Style::default().fg(Color::DarkGray)),
+        _ => Span::raw(""),
+    }
+}
+
+/// Draw the stack list
+fn draw_stack(f: &mut Frame, state: &UiTuiState, area: ratatui::layout::Rect) {
+    let items: Vec<ListItem> = if state.view.entries.is_empty() {
+        vec![ListItem::new(Span::styled(
+            "  No commits yet. Use `git commit` to add changes.",
+            Style::default().fg(Color::DarkGray),
+        ))]
+    } else {
+        state
+            .view
+            .entries
+            .iter()
+            .enumerate()
+            .map(|(idx, entry)| {
+                let is_selected = idx == state.cursor;
+                let cursor_marker = if is_selected { "▸" } else { " " };
+
+                let mut spans = vec![
+                    Span::raw(cursor_marker),
+                    Span::raw(" "),
+                    Span::styled(
+                        format!("{:<3}", idx + 1),
+                        Style::default().fg(Color::DarkGray),
+                    ),
+                    Span::styled(
+                        format!("{} ", entry.short_sha),
+                        Style::default().fg(Color::Yellow),
+                    ),
+                    Span::raw(entry.title.clone()),
+                    Span::raw("  "),
+                ];
+                if let Some(pr) = &entry.pr {
+                    spans.push(Span::styled(
+                        format!("{} ", pr),
+                        Style::default().fg(Color::Blue),
+                    ));
+                }
+                spans.push(pr_state_span(entry));
+                spans.push(ci_span(entry.ci_status.as_ref()));
+                if entry.is_current {
+                    spans.push(Span::styled(
+                        "  <- HEAD",
+                        Style::default()
+                            .fg(Color::Cyan)
+                            .add_modifier(Modifier::BOLD),
+                    ));
+                }
+
+                let mut item = ListItem::new(Line::from(spans));
+                if is_selected {
+                    item = item.style(
+                        Style::default()
+                            .bg(Color::DarkGray)
+                            .add_modifier(Modifier::BOLD),
+                    );
+                }
+                item
+            })
+            .collect()
+    };
+
+    let behind = state
+        .view
+        .behind_base
+        .map(|n| format!(", ↓{} behind origin/{}", n, state.view.base))
+        .unwrap_or_default();
+    let title = format!(
+        " {} (base {}{}) ",
+        state.view.stack_name, state.view.base, behind
+    );
+
+    let block = Block::default()
+        .title(title.as_str())
+        .borders(Borders::ALL)
+        .border_style(Style::default().fg(Color::Blue));
+
+    f.render_widget(List::new(items).block(block), area);
+}
+
+/// Draw the status bar
+fn draw_status_bar(f: &mut Frame, area: ratatui::layout::Rect) {
+    let key_style = Style::default()
+        .fg(Color::Cyan)
+        .add_modifier(Modifier::BOLD);
+    let desc_style = Style::default().fg(Color::Gray);
+    let sep_style = Style::default().fg(Color::DarkGray);
+
+    let mut spans = Vec::new();
+    for (key, desc) in [
+        ("j/k", "navigate"),
+        ("Enter", "checkout"),
+        ("s", "sync"),
+        ("l", "land to here"),
+        ("r", "reorder"),
+        ("o", "open PR"),
+        ("R", "refresh"),
+        ("q", "quit"),
+    ] {
+        spans.push(Span::styled(format!(" {}", key), key_style));
+        spans.push(Span::styled(format!(" {}", desc), desc_style));
+        spans.push(Span::styled(" ", sep_style));
+    }
+
+    let paragraph = Paragraph::new(Line::from(spans)).wrap(Wrap { trim: true });
+    f.render_widget(paragraph, area);
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn make_view(n: usize, current: usize) -> UiView {
+        UiView {
+            stack_name: "feat".to_string(),
+            base: "main".to_string(),
+            behind_base: None,
+            entries: (1..=n)
+                .map(|i| UiEntry {
+                    short_sha: format!("abc{:04}", i),
+                    title: format!("Commit {}", i),
+                    pr: (i == 1).then(|| "#10".to_string()),
+                    pr_state: None,
+                    ci_status: None,
+                    approved: false,
+                    is_current: i == current,
+                })
+                .collect(),
+            message: None,
+        }
+    }
+
+    fn press(state: &mut UiTuiState, code: KeyCode) -> Option<UiAction> {
+        handle_key(state, code, KeyModifiers::NONE);
+        state.action.take()
+    }
+
+    #[test]
+    fn test_cursor_starts_on_current_entry() {
+        let state = UiTuiState::new(make_view(3, 2), None);
+        assert_eq!(state.selected(), Some(2));
+
+        let state = UiTuiState::new(make_view(3, 2), Some(3));
+        assert_eq!(state.selected(), Some(3));
+
+        // A stale cursor past the top is clamped.
+        let state = UiTuiState::new(make_view(3, 2), Some(9));
+        assert_eq!(state.selected(), Some(3));
+    }
+
+    #[test]
+    fn test_navigation_stays_in_bounds() {
+        let mut state = UiTuiState::new(make_view(2, 1), None);
+        assert_eq!(press(&mut state, KeyCode::Char('k')), None);
+        assert_eq!(state.selected(), Some(1));
+        press(&mut state, KeyCode::Down);
+        press(&mut state, KeyCode::Char('j'));
+        assert_eq!(state.selected(), Some(2));
+    }
+
+    #[test]
+    fn test_keys_map_to_actions_on_selected_entry() {
+        let mut state = UiTuiState::new(make_view(3, 3), None);
+        assert_eq!(
+            press(&mut state, KeyCode::Char('l')),
+            Some(UiAction::Land(3))
+        );
+        assert_eq!(press(&mut state, KeyCode::Up), None);
+        assert_eq!(
+            press(&mut state, KeyCode::Enter),
+            Some(UiAction::Checkout(2))
+        );
+        assert_eq!(press(&mut state, KeyCode::Char('s')), Some(UiAction::Sync));
+        assert_eq!(
+            press(&mut state, KeyCode::Char('r')),
+            Some(UiAction::Reorder)
+        );
+        assert_eq!(
+            press(&mut state, KeyCode::Char('R')),
+            Some(UiAction::Refresh)
+        );
+        assert_eq!(press(&mut state, KeyCode::Esc), Some(UiAction::Quit));
+    }
+
+    #[test]
+    fn test_open_needs_a_pr() {
+        let mut state = UiTuiState::new(make_view(2, 2), None);
+        assert_eq!(press(&mut state, KeyCode::Char('o')), None);
+        press(&mut state, KeyCode::Up);
+        assert_eq!(
+            press(&mut state, KeyCode::Char('o')),
+            Some(UiAction::Open(1))
+        );
+    }
+
+    #[test]
+    fn test_empty_stack_only_allows_global_actions() {
+        let mut state = UiTuiState::new(make_view(0, 0), None);
+        assert_eq!(state.selected(), None);
+        assert_eq!(press(&mut state, KeyCode::Enter), None);
+        assert_eq!(press(&mut state, KeyCode::Char('l')), None);
+        assert_eq!(press(&mut state, KeyCode::Char('r')), None);
+        assert_eq!(press(&mut state, KeyCode::Char('s')), Some(UiAction::Sync));
+    }
+
+    #[test]
+    fn test_ctrl_c_quits() {
+        let mut state = UiTuiState::new(make_view(2, 2), None);
+        handle_key(&mut state, KeyCode::Char('c'), KeyModifiers::CONTROL);
+        assert_eq!(state.action, Some(UiAction::Quit));
+    }
+}
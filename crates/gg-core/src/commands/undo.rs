@@ -1,8 +1,10 @@
-//! `gg undo` command handler. See design §2.4.
+//! `gg undo` / `gg redo` command handlers. See design §2.4.
 //!
 //! Wraps [`crate::operations::run_undo`] with the record-itself pattern: an
 //! `Undo` operation is itself recorded in the op log (D5), so the user can
-//! run `gg undo; gg undo` to redo the first op.
+//! run `gg undo; gg undo` to redo the first op. `gg redo` does the same but
+//! only when the latest operation is an undo, and records itself as `Redo`
+//! so repeating it does not flip back and forth.
 //!
 //! Refusal modes (remote/interrupted/stale/unsupported-schema) intentionally
 //! drop the guard without calling `finalize`, which leaves the record as
@@ -15,7 +17,8 @@ use crate::config::Config;
 use crate::error::{GgError, Result};
 use crate::git;
 use crate::operations::{
-    self, OperationKind, OperationRecord, SnapshotScope, UndoOptions, UndoOutcome,
+    self, OperationGuard, OperationKind, OperationRecord, OperationStatus, SnapshotScope,
+    UndoOptions, UndoOutcome,
 };
 use crate::output::{
     print_json, OperationSummaryJson, UndoJsonStatus, UndoListResponse, UndoRefusalJson,
@@ -38,7 +41,7 @@ pub struct UndoCliOptions {
 /// Run the undo command.
 pub fn run(options: UndoCliOptions) -> Result<()> {
     let repo = git::open_repo()?;
-    let config = Config::load_with_global(repo.commondir())?;
+    let config = Config::load(repo.commondir())?;
 
     if options.list {
         return run_list(&repo, &options);
@@ -66,6 +69,39 @@ fn run_list(repo: &git2::Repository, options: &UndoCliOptions) -> Result<()> {
     Ok(())
 }
 
+/// Run the redo command: reverse the latest operation if it was `gg undo`.
+pub fn run_redo(json: bool) -> Result<()> {
+    let repo = git::open_repo()?;
+    let config = Config::load(repo.commondir())?;
+
+    let _lock = git::acquire_operation_lock(&repo, "redo")?;
+    let target = operations::list(&repo, usize::MAX)?
+        .into_iter()
+        .find(|r| r.status == OperationStatus::Committed && r.kind != OperationKind::Nav)
+        .filter(|r| r.kind == OperationKind::Undo)
+        .ok_or_else(|| {
+            GgError::Other("Nothing to redo: the last operation was not `gg undo`".to_string())
+        })?;
+
+    let guard = git::begin_recorded_op(
+        &repo,
+        &config,
+        OperationKind::Redo,
+        std::env::args().skip(1).collect(),
+        None,
+        SnapshotScope::AllUserBranches,
+    )?;
+    let outcome = operations::run_undo(
+        &repo,
+        &config,
+        UndoOptions {
+            operation_id: Some(target.id.clone()),
+            json,
+        },
+    )?;
+    finish(&repo, &config, guard, outcome, json, "Redid")
+}
+
 fn run_undo(repo: &git2::Repository, config: &Config, options: &UndoCliOptions) -> Result<()> {
     // Undo itself takes a lock and records itself (D5). Use AllUserBranches
     // scope so the undo record's refs_before captures whatever user-owned
@@ -84,7 +120,17 @@ fn run_undo(repo: &git2::Repository, config: &Config, options: &UndoCliOptions)
         json: options.json,
     };
     let outcome = operations::run_undo(repo, config, undo_opts)?;
+    finish(repo, config, guard, outcome, options.json, "Undid")
+}
 
+fn finish(
+    repo: &git2::Repository,
+    config: &Config,
+    guard: OperationGuard,
+    outcome: UndoOutcome,
+    json: bool,
+    verb: &str,
+) -> Result<()> {
     // On success we capture the post-replay snapshot and finalize. On any
     // refusal we drop the guard without finalize (see module docs).
     match &outcome {
@@ -99,7 +145,7 @@ fn run_undo(repo: &git2::Repository, config: &Config, options: &UndoCliOptions)
         }
     }
 
-    emit_response(&outcome, options.json)?;
+    emit_response(&outcome, json, verb)?;
 
     // `emit_response` already printed a detailed human-readable diagnostic
     // (or the structured JSON refusal). For refusals we return the marker
@@ -114,7 +160,7 @@ fn run_undo(repo: &git2::Repository, config: &Config, options: &UndoCliOptions)
     }
 }
 
-fn emit_response(outcome: &UndoOutcome, json: bool) -> Result<()> {
+fn emit_response(outcome: &UndoOutcome, json: bool, verb: &str) -> Result<()> {
     if json {
         let response = build_json_response(outcome);
         print_json(&response);
@@ -124,8 +170,9 @@ fn emit_response(outcome: &UndoOutcome, json: bool) -> Result<()> {
     match outcome {
         UndoOutcome::Succeeded(target) => {
             println!(
-                "{} Undid {} ({})",
+                "{} {} {} ({})",
                 style("OK").green().bold(),
+                verb,
                 short_id(&target.id),
                 short_args(&target.args),
             );
@@ -356,6 +403,7 @@ mod tests {
             touched_remote,
             undoes: None,
             pending_plan: None,
+            conflicted: false,
         }
     }
 
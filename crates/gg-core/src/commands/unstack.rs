@@ -12,11 +12,13 @@ use super::unstack_tui::{self, UnstackEntry};
 use crate::config::{Config, StackConfig};
 use crate::error::{GgError, Result};
 use crate::git;
+use crate::hooks::{self, GitRewrite};
 use crate::immutability::{self, ImmutabilityPolicy};
 use crate::operations::{OperationKind, SnapshotScope};
 use crate::output::{
     print_json, UnstackEntryJson, UnstackResponse, UnstackResultJson, OUTPUT_VERSION,
 };
+use crate::plain;
 use crate::stack::{self, Stack, StackEntry};
 
 const AUTO_NAME_ATTEMPT_LIMIT: usize = 100;
@@ -43,7 +45,7 @@ pub struct UnstackOptions {
 /// Run the unstack command.
 pub fn run(options: UnstackOptions) -> Result<()> {
     let repo = git::open_repo()?;
-    let mut config = Config::load_with_global(repo.commondir())?;
+    let mut config = Config::load(repo.commondir())?;
 
     let _lock = git::acquire_operation_lock(&repo, "unstack")?;
     git::require_clean_working_directory(&repo)?;
@@ -108,14 +110,26 @@ pub fn run(options: UnstackOptions) -> Result<()> {
     if !options.json {
         println!(
             "{} Unstacking {} at position #{}...",
-            style("→").cyan(),
+            style(plain::arrow()).cyan(),
             style(&original_stack).cyan(),
             split_position
         );
     }
 
-    // Create the new stack by rebasing upper commits onto base
-    let new_tip = rebase_upper_stack(&repo, &stack_obj, &new_branch, split_position)?;
+    // Create the new stack by rebasing upper commits onto base (or the
+    // ref the stack was started from)
+    let onto = config
+        .get_from_for_stack(&original_stack)
+        .unwrap_or(&stack_obj.base)
+        .to_string();
+    let new_tip = rebase_upper_stack(
+        &repo,
+        config.get_remote_for_stack(&stack_obj.name),
+        &stack_obj,
+        &onto,
+        &new_branch,
+        split_position,
+    )?;
 
     // In worktree mode, create the managed worktree before mutating the
     // original stack branch or config. If worktree creation fails, the
@@ -381,20 +395,21 @@ fn generate_new_stack_name(repo: &Repository, config: &Config, stack: &Stack) ->
     )))
 }
 
-/// Rebase the upper portion of the stack onto the base branch.
+/// Rebase the upper portion of the stack onto `onto` (its base branch).
 ///
 /// Uses `git rebase --onto <base> <lower-tip> <new-branch>` so the upper
 /// commits are replayed cleanly onto the base.
 fn rebase_upper_stack(
     repo: &Repository,
+    remote: &str,
     stack: &Stack,
+    onto: &str,
     new_branch: &str,
     split_position: usize,
 ) -> Result<Oid> {
     let original_branch = stack.branch_name();
-    let base_ref = repo
-        .revparse_single(&stack.base)
-        .or_else(|_| repo.revparse_single(&format!("origin/{}", stack.base)))
+    let base_ref = git::resolve_base(repo, remote, onto)
+        .and_then(|r| r.peel_to_commit())
         .map_err(|_| GgError::NoBaseBranch)?;
 
     let stack_tip = stack.last().expect("stack is non-empty").oid;
@@ -434,7 +449,7 @@ fn rebase_upper_stack(
         std::fs::set_permissions(&script_file, perms)?;
     }
 
-    let output = std::process::Command::new("git")
+    let output = hooks::git_command(GitRewrite::Replay)
         .env("GIT_SEQUENCE_EDITOR", script_file.to_str().unwrap())
         .args([
             "rebase",
@@ -550,6 +565,23 @@ fn migrate_config(
 
     let mut new_config = StackConfig {
         base: original_base,
+        remote: config
+            .get_stack(original_stack)
+            .and_then(|s| s.remote.clone()),
+        from: config
+            .get_stack(original_stack)
+            .and_then(|s| s.from.clone()),
+        description: config
+            .get_stack(original_stack)
+            .and_then(|s| s.description.clone()),
+        parent: config
+            .get_stack(original_stack)
+            .and_then(|s| s.parent.clone()),
+        scope: config.get_scope_for_stack(original_stack).to_vec(),
+        reviewers: config
+            .get_stack(original_stack)
+            .map(|s| s.reviewers.clone())
+            .unwrap_or_default(),
         ..StackConfig::default()
     };
 
@@ -707,8 +739,15 @@ mod tests {
             "feature".to_string(),
             StackConfig {
                 base: None,
+                remote: None,
+                from: None,
+                description: None,
                 mrs: HashMap::from([("c-abc1234".to_string(), 42)]),
                 worktree_path: None,
+                scope: vec![],
+                parent: None,
+                reviewers: vec![],
+                last_touched_ms: None,
             },
         );
         let moved_entries = vec![UnstackEntryJson {
@@ -0,0 +1,101 @@
+//! `gg use` - Select the active stack without checking it out
+//!
+//! A worktree can hold several stacks; `gg use <stack>` picks which one
+//! read-only and provider commands (`ls`, `log`, `comments`, `ci`,
+//! `retarget-prs`) act on, leaving the checked-out branch alone. Commands
+//! that rewrite history still need the stack checked out with `gg co`.
+
+use console::style;
+use git2::BranchType;
+
+use crate::config::Config;
+use crate::error::{GgError, Result};
+use crate::git;
+use crate::stack;
+
+/// Find the local stack branch for `stack_name`, preferring `username`'s.
+fn find_stack_branch(
+    repo: &git2::Repository,
+    stack_name: &str,
+    username: Option<&str>,
+) -> Result<Option<String>> {
+    let mut found = None;
+    for branch in repo.branches(Some(BranchType::Local))? {
+        let (branch, _) = branch?;
+        let Some(name) = branch.name()? else {
+            continue;
+        };
+        if let Some((user, stack)) = git::parse_stack_branch(name) {
+            if stack != stack_name {
+                continue;
+            }
+            if username == Some(user.as_str()) {
+                return Ok(Some(name.to_string()));
+            }
+            found.get_or_insert_with(|| name.to_string());
+        }
+    }
+    Ok(found)
+}
+
+/// Run the use command
+pub fn run(stack_name: Option<String>, clear: bool) -> Result<()> {
+    let repo = git::open_repo()?;
+    let config = Config::load(repo.commondir())?;
+    let git_dir = repo.path();
+
+    if clear {
+        stack::clear_active_stack(git_dir)?;
+        println!(
+            "{} Cleared the active stack; gg follows the checked-out branch again",
+            style("OK").green().bold()
+        );
+        return Ok(());
+    }
+
+    let Some(stack_name) = stack_name else {
+        match stack::read_active_stack(git_dir)
+            .as_deref()
+            .and_then(git::parse_stack_branch)
+        {
+            Some((_, name)) => println!("Active stack: {}", style(name).cyan()),
+            None => println!(
+                "{}",
+                style("No stack selected; gg follows the checked-out branch.").dim()
+            ),
+        }
+        return Ok(());
+    };
+
+    let branch = find_stack_branch(
+        &repo,
+        &stack_name,
+        config.defaults.branch_username.as_deref(),
+    )?
+    .ok_or_else(|| GgError::StackNotFound(stack_name.clone()))?;
+
+    // Selecting the checked-out stack is the default anyway
+    if git::current_branch_name(&repo).as_deref() == Some(branch.as_str()) {
+        stack::clear_active_stack(git_dir)?;
+    } else {
+        stack::save_active_stack(git_dir, &branch)?;
+    }
+
+    println!(
+        "{} Active stack: {}",
+        style("OK").green().bold(),
+        style(&stack_name).cyan()
+    );
+    if git::current_branch_name(&repo).as_deref() != Some(branch.as_str()) {
+        println!(
+            "{}",
+            style(format!(
+                "ls, log, comments, ci and retarget-prs now use it. Run `gg co {}` to edit it.",
+                stack_name
+            ))
+            .dim()
+        );
+    }
+
+    Ok(())
+}
@@ -0,0 +1,542 @@
+//! `gg verify` - Check gg's internal invariants and optionally repair them
+//!
+//! Checks, for every local stack:
+//! - each commit has a GG-ID, and GG-IDs are unique within the stack
+//! - every `mrs` mapping points at a commit in the stack (and, when a
+//!   provider is reachable, at an existing PR/MR)
+//! - local entry branches point at the commit carrying their GG-ID
+//! - configured worktree paths exist on disk
+//!
+//! `--fix` only performs repairs that cannot lose work: dropping stale
+//! config mappings, clearing missing worktree paths, and moving local entry
+//! branches back onto their commit. Everything else is reported with a hint.
+
+use std::collections::{BTreeMap, HashMap};
+use std::path::Path;
+
+use console::style;
+use git2::{BranchType, Oid, Repository};
+use serde::Serialize;
+
+use crate::config::Config;
+use crate::error::{GgError, Result};
+use crate::git;
+use crate::operations::{OperationKind, SnapshotScope};
+use crate::output::{
+    print_json, VerifyIssueJson, VerifyResponse, VerifyResultJson, OUTPUT_VERSION,
+};
+use crate::provider::Provider;
+use crate::stack;
+
+/// Category of a broken invariant. Serialized as snake_case in JSON output.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum IssueKind {
+    MissingGgId,
+    DuplicateGgId,
+    StaleMrMapping,
+    DuplicateMrMapping,
+    MissingPr,
+    StaleEntryBranch,
+    OrphanEntryBranch,
+    MissingWorktree,
+}
+
+impl IssueKind {
+    /// Warnings are reported but do not make `gg verify` fail. Commits
+    /// legitimately lack a GG-ID until their first `gg sync`.
+    pub fn is_warning(self) -> bool {
+        matches!(self, IssueKind::MissingGgId)
+    }
+}
+
+/// A single broken invariant.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Issue {
+    pub kind: IssueKind,
+    pub stack: String,
+    pub message: String,
+    pub repair: Option<Repair>,
+    pub fixed: bool,
+}
+
+/// A repair `--fix` knows how to apply safely.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Repair {
+    /// Remove `stacks.<stack>.mrs.<gg_id>` from the config.
+    RemoveMrMapping { gg_id: String },
+    /// Clear `stacks.<stack>.worktree_path`.
+    ClearWorktreePath,
+    /// Point a local entry branch at the commit carrying its GG-ID.
+    MoveEntryBranch { branch: String, target: Oid },
+}
+
+impl Issue {
+    fn new(kind: IssueKind, stack: &str, message: String, repair: Option<Repair>) -> Self {
+        Self {
+            kind,
+            stack: stack.to_string(),
+            message,
+            repair,
+            fixed: false,
+        }
+    }
+
+    pub fn is_fixable(&self) -> bool {
+        self.repair.is_some()
+    }
+
+    /// Whether this issue still makes the check fail.
+    pub fn is_blocking(&self) -> bool {
+        !self.fixed && !self.kind.is_warning()
+    }
+}
+
+/// Local facts about one stack, gathered before checking invariants.
+#[derive(Debug, Default)]
+pub struct StackFacts {
+    pub name: String,
+    /// Commits base → tip as (oid, short sha, GG-ID). Empty when the stack
+    /// branch does not exist locally.
+    pub commits: Vec<(Oid, String, Option<String>)>,
+    pub has_branch: bool,
+    /// gg_id → PR/MR number from config.
+    pub mrs: BTreeMap<String, u64>,
+    /// Local entry branches as (branch name, gg_id, target).
+    pub entry_branches: Vec<(String, String, Oid)>,
+    pub worktree_path: Option<String>,
+}
+
+/// Check the local invariants of one stack. Pure so it can be unit tested.
+pub fn check_stack(facts: &StackFacts) -> Vec<Issue> {
+    let name = facts.name.as_str();
+    let mut issues = Vec::new();
+
+    let mut by_id: HashMap<&str, Oid> = HashMap::new();
+    for (oid, sha, gg_id) in &facts.commits {
+        match gg_id {
+            None => issues.push(Issue::new(
+                IssueKind::MissingGgId,
+                name,
+                format!("commit {} has no GG-ID (run `gg reconcile`)", sha),
+                None,
+            )),
+            Some(id) => {
+                if by_id.insert(id.as_str(), *oid).is_some() {
+                    issues.push(Issue::new(
+                        IssueKind::DuplicateGgId,
+                        name,
+                        format!("GG-ID {} appears on more than one commit ({})", id, sha),
+                        None,
+                    ));
+                }
+            }
+        }
+    }
+
+    if facts.has_branch {
+        for gg_id in facts.mrs.keys() {
+            if !by_id.contains_key(gg_id.as_str()) {
+                issues.push(Issue::new(
+                    IssueKind::StaleMrMapping,
+                    name,
+                    format!(
+                        "mapping {} -> #{} points at no commit in the stack",
+                        gg_id, facts.mrs[gg_id]
+                    ),
+                    Some(Repair::RemoveMrMapping {
+                        gg_id: gg_id.clone(),
+                    }),
+                ));
+            }
+        }
+    }
+
+    let mut by_pr: BTreeMap<u64, Vec<&str>> = BTreeMap::new();
+    for (gg_id, pr) in &facts.mrs {
+        by_pr.entry(*pr).or_default().push(gg_id);
+    }
+    for (pr, ids) in by_pr.into_iter().filter(|(_, ids)| ids.len() > 1) {
+        issues.push(Issue::new(
+            IssueKind::DuplicateMrMapping,
+            name,
+            format!(
+                "#{} is mapped from several entries ({})",
+                pr,
+                ids.join(", ")
+            ),
+            None,
+        ));
+    }
+
+    if facts.has_branch {
+        for (branch, gg_id, target) in &facts.entry_branches {
+            match by_id.get(gg_id.as_str()) {
+                Some(expected) if expected != target => issues.push(Issue::new(
+                    IssueKind::StaleEntryBranch,
+                    name,
+                    format!(
+                        "{} points at {:.7}, expected {:.7}",
+                        branch,
+                        target.to_string(),
+                        expected.to_string()
+                    ),
+                    Some(Repair::MoveEntryBranch {
+                        branch: branch.clone(),
+                        target: *expected,
+                    }),
+                )),
+                Some(_) => {}
+                None => issues.push(Issue::new(
+                    IssueKind::OrphanEntryBranch,
+                    name,
+                    format!(
+                        "{} has no matching commit in the stack (run `gg clean`)",
+                        branch
+                    ),
+                    None,
+                )),
+            }
+        }
+    }
+
+    if let Some(path) = &facts.worktree_path {
+        if !Path::new(path).exists() {
+            issues.push(Issue::new(
+                IssueKind::MissingWorktree,
+                name,
+                format!("worktree path {} does not exist", path),
+                Some(Repair::ClearWorktreePath),
+            ));
+        }
+    }
+
+    issues
+}
+
+pub(crate) fn gather_facts(
+    repo: &Repository,
+    config: &Config,
+    username: &str,
+    stack_name: &str,
+) -> Result<StackFacts> {
+    let stack_config = config.get_stack(stack_name);
+    let mut facts = StackFacts {
+        name: stack_name.to_string(),
+        mrs: stack_config
+            .map(|c| c.mrs.iter().map(|(k, v)| (k.clone(), *v)).collect())
+            .unwrap_or_default(),
+        worktree_path: stack_config.and_then(|c| c.worktree_path.clone()),
+        ..Default::default()
+    };
+
+    let branch = git::format_stack_branch(username, stack_name);
+    facts.has_branch = repo.find_branch(&branch, BranchType::Local).is_ok();
+    if facts.has_branch {
+        let base = config
+            .get_base_for_stack(stack_name)
+            .map(str::to_string)
+            .or_else(|| git::find_base_branch(repo).ok())
+            .ok_or(GgError::NoBaseBranch)?;
+        let base = git::resolve_base(repo, config.get_remote_for_stack(stack_name), &base)
+            .map_err(|_| GgError::NoBaseBranch)?
+            .id()
+            .to_string();
+        for oid in git::get_stack_commit_oids(repo, &base, Some(&branch))? {
+            let commit = repo.find_commit(oid)?;
+            facts
+                .commits
+                .push((oid, git::short_sha(&commit), git::get_gg_id(&commit)));
+        }
+    }
+
+    for branch_result in repo.branches(Some(BranchType::Local))? {
+        let (branch, _) = branch_result?;
+        let (Some(name), Some(target)) = (branch.name()?, branch.get().target()) else {
+            continue;
+        };
+        if let Some((user, stack, gg_id)) = git::parse_entry_branch(name) {
+            if user == username && stack == stack_name {
+                facts.entry_branches.push((name.to_string(), gg_id, target));
+            }
+        }
+    }
+
+    Ok(facts)
+}
+
+/// Remote check: every mapped PR/MR must exist. Never auto-fixed — a lookup
+/// failure can be transient, so the mapping is left for the user to judge.
+fn check_prs(facts: &StackFacts, provider: &Provider) -> Vec<Issue> {
+    facts
+        .mrs
+        .iter()
+        .filter(|(_, pr)| provider.get_pr_info(**pr).is_err())
+        .map(|(gg_id, pr)| {
+            Issue::new(
+                IssueKind::MissingPr,
+                &facts.name,
+                format!(
+                    "{} {}{} (mapped from {}) could not be found",
+                    provider.pr_label(),
+                    provider.pr_number_prefix(),
+                    pr,
+                    gg_id
+                ),
+                None,
+            )
+        })
+        .collect()
+}
+
+pub(crate) fn apply_repair(
+    repo: &Repository,
+    config: &mut Config,
+    stack_name: &str,
+    repair: &Repair,
+) -> Result<()> {
+    match repair {
+        Repair::RemoveMrMapping { gg_id } => config.remove_mr_for_entry(stack_name, gg_id),
+        Repair::ClearWorktreePath => {
+            config.get_or_create_stack(stack_name).worktree_path = None;
+            let _ = git::run_git_command(&["worktree", "prune"]);
+        }
+        Repair::MoveEntryBranch { branch, target } => {
+            let mut reference = repo.find_reference(&format!("refs/heads/{}", branch))?;
+            reference.set_target(*target, "gg verify --fix: realign entry branch")?;
+        }
+    }
+    Ok(())
+}
+
+/// Run the verify command
+pub fn run(fix: bool, json: bool) -> Result<()> {
+    let repo = git::open_repo()?;
+    let git_dir = repo.commondir();
+    let mut config = Config::load(git_dir)?;
+
+    let username = config
+        .defaults
+        .branch_username
+        .clone()
+        .or_else(|| Provider::detect(&repo).ok().and_then(|p| p.whoami().ok()))
+        .unwrap_or_else(|| "unknown".to_string());
+    git::validate_branch_username(&username)?;
+
+    let provider = Provider::detect(&repo)
+        .ok()
+        .filter(|p| p.check_installed().is_ok());
+
+    let mut issues = Vec::new();
+    for stack_name in stack::list_all_stacks(&repo, &config, &username)? {
+        let facts = gather_facts(&repo, &config, &username, &stack_name)?;
+        issues.extend(check_stack(&facts));
+        if let Some(provider) = &provider {
+            issues.extend(check_prs(&facts, provider));
+        }
+    }
+
+    if fix && issues.iter().any(Issue::is_fixable) {
+        let (_lock, guard) = git::acquire_operation_lock_and_record(
+            &repo,
+            &config,
+            OperationKind::Verify,
+            std::env::args().skip(1).collect(),
+            None,
+            SnapshotScope::AllUserBranches,
+        )?;
+        for issue in issues.iter_mut() {
+            if let Some(repair) = &issue.repair {
+                apply_repair(&repo, &mut config, &issue.stack, repair)?;
+                issue.fixed = true;
+            }
+        }
+        config.save(git_dir)?;
+        guard.finalize_with_scope(
+            &repo,
+            &config,
+            SnapshotScope::AllUserBranches,
+            vec![],
+            false,
+        )?;
+    }
+
+    let remaining = issues.iter().filter(|i| i.is_blocking()).count();
+
+    if json {
+        print_json(&VerifyResponse {
+            version: OUTPUT_VERSION,
+            verify: VerifyResultJson {
+                ok: remaining == 0,
+                fixed: issues.iter().filter(|i| i.fixed).count(),
+                remaining,
+                issues: issues
+                    .iter()
+                    .map(|i| VerifyIssueJson {
+                        kind: serde_json::to_value(i.kind)
+                            .ok()
+                            .and_then(|v| v.as_str().map(str::to_string))
+                            .unwrap_or_default(),
+                        stack: i.stack.clone(),
+                        message: i.message.clone(),
+                        warning: i.kind.is_warning(),
+                        fixable: i.is_fixable(),
+                        fixed: i.fixed,
+                    })
+                    .collect(),
+            },
+        });
+    } else {
+        print_human(&issues, fix);
+    }
+
+    if remaining > 0 {
+        return Err(GgError::Silenced);
+    }
+    Ok(())
+}
+
+fn print_human(issues: &[Issue], fix: bool) {
+    for issue in issues {
+        let marker = if issue.fixed {
+            style("fixed").green().to_string()
+        } else if issue.kind.is_warning() {
+            style("warning").yellow().to_string()
+        } else if issue.is_fixable() {
+            style("fixable").yellow().to_string()
+        } else {
+            style("error").red().to_string()
+        };
+        println!(
+            "  {} {} {}",
+            marker,
+            style(&issue.stack).cyan(),
+            issue.message
+        );
+    }
+
+    let fixable = issues.iter().filter(|i| i.is_fixable() && !i.fixed).count();
+    if !fix && fixable > 0 {
+        println!();
+        println!(
+            "{}",
+            style(format!(
+                "Run `gg verify --fix` to repair {} issue(s).",
+                fixable
+            ))
+            .dim()
+        );
+    }
+
+    if !issues.iter().any(Issue::is_blocking) {
+        println!("{} All invariants hold", style("OK").green().bold());
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn oid(n: u8) -> Oid {
+        Oid::from_bytes(&[n; 20]).unwrap()
+    }
+
+    fn facts() -> StackFacts {
+        StackFacts {
+            name: "feat".into(),
+            has_branch: true,
+            commits: vec![
+                (oid(1), "1111111".into(), Some("c-aaaaaaa".into())),
+                (oid(2), "2222222".into(), Some("c-bbbbbbb".into())),
+            ],
+            ..Default::default()
+        }
+    }
+
+    fn kinds(issues: &[Issue]) -> Vec<IssueKind> {
+        issues.iter().map(|i| i.kind).collect()
+    }
+
+    #[test]
+    fn healthy_stack_has_no_issues() {
+        let mut f = facts();
+        f.mrs.insert("c-aaaaaaa".into(), 1);
+        f.entry_branches
+            .push(("u/feat--c-aaaaaaa".into(), "c-aaaaaaa".into(), oid(1)));
+        assert!(check_stack(&f).is_empty());
+    }
+
+    #[test]
+    fn detects_missing_and_duplicate_gg_ids() {
+        let mut f = facts();
+        f.commits.push((oid(3), "3333333".into(), None));
+        f.commits
+            .push((oid(4), "4444444".into(), Some("c-aaaaaaa".into())));
+        assert_eq!(
+            kinds(&check_stack(&f)),
+            vec![IssueKind::MissingGgId, IssueKind::DuplicateGgId]
+        );
+    }
+
+    #[test]
+    fn stale_mapping_is_fixable() {
+        let mut f = facts();
+        f.mrs.insert("c-gone000".into(), 9);
+        let issues = check_stack(&f);
+        assert_eq!(kinds(&issues), vec![IssueKind::StaleMrMapping]);
+        assert_eq!(
+            issues[0].repair,
+            Some(Repair::RemoveMrMapping {
+                gg_id: "c-gone000".into()
+            })
+        );
+    }
+
+    #[test]
+    fn mappings_are_not_judged_without_a_local_branch() {
+        let mut f = facts();
+        f.has_branch = false;
+        f.commits.clear();
+        f.mrs.insert("c-gone000".into(), 9);
+        assert!(check_stack(&f).is_empty());
+    }
+
+    #[test]
+    fn detects_duplicate_pr_mappings() {
+        let mut f = facts();
+        f.mrs.insert("c-aaaaaaa".into(), 5);
+        f.mrs.insert("c-bbbbbbb".into(), 5);
+        assert_eq!(kinds(&check_stack(&f)), vec![IssueKind::DuplicateMrMapping]);
+    }
+
+    #[test]
+    fn entry_branch_checks() {
+        let mut f = facts();
+        f.entry_branches
+            .push(("u/feat--c-bbbbbbb".into(), "c-bbbbbbb".into(), oid(9)));
+        f.entry_branches
+            .push(("u/feat--c-zzzzzzz".into(), "c-zzzzzzz".into(), oid(9)));
+        let issues = check_stack(&f);
+        assert_eq!(
+            kinds(&issues),
+            vec![IssueKind::StaleEntryBranch, IssueKind::OrphanEntryBranch]
+        );
+        assert_eq!(
+            issues[0].repair,
+            Some(Repair::MoveEntryBranch {
+                branch: "u/feat--c-bbbbbbb".into(),
+                target: oid(2)
+            })
+        );
+        assert!(!issues[1].is_fixable());
+    }
+
+    #[test]
+    fn missing_worktree_is_fixable() {
+        let mut f = facts();
+        f.worktree_path = Some("/definitely/not/here/gg-verify".into());
+        let issues = check_stack(&f);
+        assert_eq!(kinds(&issues), vec![IssueKind::MissingWorktree]);
+        assert_eq!(issues[0].repair, Some(Repair::ClearWorktreePath));
+    }
+}
@@ -0,0 +1,331 @@
+//! `gg watch` - Live CI and approval monitoring for the whole stack
+//!
+//! Polls the provider for every entry with a PR/MR on the same cadence as
+//! `gg land --wait`, redrawing a table in the terminal or streaming one
+//! NDJSON event per change with `--json`. Nothing is ever merged; the watch
+//! ends on Ctrl+C or once every PR/MR is merged or closed; the latter (and
+//! giving up on API errors) is reported through `defaults.notify_command`.
+
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::Arc;
+use std::time::Duration;
+
+use console::{style, Term};
+
+use crate::config::Config;
+use crate::error::{GgError, Result};
+use crate::git;
+use crate::notify::{self, Notification};
+use crate::output::{
+    StreamingJson, WatchEntryJson, WatchEvent, WatchStreamingResponse, OUTPUT_VERSION,
+};
+use crate::plain;
+use crate::provider::{CiStatus, PrState, Provider};
+use crate::stack::Stack;
+
+use super::land::{interruptible_sleep, MAX_CONSECUTIVE_API_ERRORS, POLL_INTERVAL_SECS};
+use super::ls::{ci_status_to_json, pr_state_to_json};
+
+/// Options for the watch command
+#[derive(Debug, Default)]
+pub struct WatchOptions {
+    /// Seconds between polls. None = the `gg land --wait` interval.
+    pub interval: Option<u64>,
+    /// Stream NDJSON events instead of drawing a table
+    pub json: bool,
+}
+
+/// Provider state of one entry at one poll
+#[derive(Debug, Clone, PartialEq)]
+struct WatchRow {
+    position: usize,
+    short_sha: String,
+    title: String,
+    pr_number: u64,
+    pr_state: Option<PrState>,
+    ci_status: Option<CiStatus>,
+    approved: bool,
+}
+
+impl WatchRow {
+    fn is_finished(&self) -> bool {
+        matches!(self.pr_state, Some(PrState::Merged | PrState::Closed))
+    }
+
+    fn to_json(&self) -> WatchEntryJson {
+        WatchEntryJson {
+            position: self.position,
+            sha: self.short_sha.clone(),
+            title: self.title.clone(),
+            pr_number: self.pr_number,
+            pr_state: self.pr_state.as_ref().map(pr_state_to_json),
+            ci_status: self.ci_status.as_ref().map(ci_status_to_json),
+            approved: self.approved,
+        }
+    }
+}
+
+/// Run the watch command
+pub fn run(options: WatchOptions) -> Result<()> {
+    let repo = git::open_repo()?;
+    let config = Config::load(repo.commondir())?;
+    let stack = Stack::load(&repo, &config)?;
+
+    let mut rows: Vec<WatchRow> = stack
+        .entries
+        .iter()
+        .filter_map(|entry| {
+            entry.mr_number.map(|pr_number| WatchRow {
+                position: entry.position,
+                short_sha: entry.short_sha.clone(),
+                title: entry.title.clone(),
+                pr_number,
+                pr_state: None,
+                ci_status: None,
+                approved: false,
+            })
+        })
+        .collect();
+    if rows.is_empty() {
+        return Err(GgError::Other(
+            "No entry has a PR/MR yet. Run `gg sync` first.".to_string(),
+        ));
+    }
+    let provider = Provider::for_stack(&repo, &config, &stack.name)?;
+
+    let interval = Duration::from_secs(options.interval.unwrap_or(POLL_INTERVAL_SECS).max(1));
+    let interrupted = Arc::new(AtomicBool::new(false));
+    let flag = Arc::clone(&interrupted);
+    ctrlc::set_handler(move || {
+        if flag.load(Ordering::SeqCst) {
+            std::process::abort();
+        }
+        flag.store(true, Ordering::SeqCst);
+    })
+    .map_err(|e| GgError::Other(format!("Failed to set Ctrl+C handler: {}", e)))?;
+
+    let mut streamer = options.json.then(StreamingJson::new);
+    let emit = |streamer: &mut Option<StreamingJson>, event: WatchEvent| {
+        if let Some(streamer) = streamer {
+            streamer.emit(&WatchStreamingResponse {
+                version: OUTPUT_VERSION,
+                event,
+            });
+        }
+    };
+    emit(
+        &mut streamer,
+        WatchEvent::Start {
+            stack: stack.name.clone(),
+            base: stack.base.clone(),
+            interval_secs: interval.as_secs(),
+        },
+    );
+
+    let term = Term::stdout();
+    let mut drawn_lines = 0;
+    let mut consecutive_errors: u32 = 0;
+    let mut first = true;
+
+    loop {
+        let mut next = rows.clone();
+        let mut error = None;
+        for row in next.iter_mut().filter(|row| !row.is_finished()) {
+            match poll_row(&provider, row) {
+                Ok(()) => {}
+                Err(e) => error = Some(e),
+            }
+        }
+
+        match error {
+            Some(e) => {
+                consecutive_errors += 1;
+                emit(
+                    &mut streamer,
+                    WatchEvent::Error {
+                        message: e.to_string(),
+                    },
+                );
+                if consecutive_errors >= MAX_CONSECUTIVE_API_ERRORS {
+                    let error = format!(
+                        "Too many consecutive API errors ({}): {}",
+                        consecutive_errors, e
+                    );
+                    notify::send(
+                        &config,
+                        &Notification {
+                            event: "watch",
+                            success: false,
+                            title: "gg watch".to_string(),
+                            message: format!("Watching {} stopped: {}", stack.name, error),
+                        },
+                    );
+                    return Err(GgError::Other(error));
+                }
+            }
+            None => consecutive_errors = 0,
+        }
+
+        let changed: Vec<&WatchRow> = if first {
+            next.iter().collect()
+        } else {
+            changed_rows(&rows, &next)
+        };
+        let any_changed = !changed.is_empty();
+        for row in changed {
+            emit(&mut streamer, WatchEvent::Entry(row.to_json()));
+        }
+        first = false;
+        rows = next;
+
+        // A terminal gets the table redrawn in place; pipes only get a new
+        // copy when something changed.
+        if !options.json && (term.is_term() || any_changed) {
+            if drawn_lines > 0 && term.is_term() {
+                let _ = term.clear_last_lines(drawn_lines);
+            }
+            drawn_lines = render(&stack.name, &provider, &rows, interval);
+        }
+
+        if rows.iter().all(WatchRow::is_finished) {
+            emit(&mut streamer, WatchEvent::Done);
+            notify::send(
+                &config,
+                &Notification {
+                    event: "watch",
+                    success: true,
+                    title: "gg watch".to_string(),
+                    message: format!(
+                        "All {}s of {} are merged or closed",
+                        provider.pr_label(),
+                        stack.name
+                    ),
+                },
+            );
+            if !options.json {
+                println!(
+                    "{}",
+                    style(format!(
+                        "All {}s are merged or closed.",
+                        provider.pr_label()
+                    ))
+                    .green()
+                );
+            }
+            return Ok(());
+        }
+
+        if interruptible_sleep(interval, Some(&interrupted), None).is_err() {
+            emit(&mut streamer, WatchEvent::Done);
+            return Ok(());
+        }
+    }
+}
+
+fn poll_row(provider: &Provider, row: &mut WatchRow) -> Result<()> {
+    let info = provider.get_pr_info(row.pr_number)?;
+    row.pr_state = Some(info.state);
+    row.approved = provider
+        .check_pr_approved(row.pr_number)
+        .unwrap_or(info.approved);
+    row.ci_status = Some(provider.get_pr_ci_status(row.pr_number)?);
+    Ok(())
+}
+
+/// Rows whose provider state differs from the previous poll.
+fn changed_rows<'a>(prev: &[WatchRow], next: &'a [WatchRow]) -> Vec<&'a WatchRow> {
+    next.iter()
+        .filter(|row| {
+            prev.iter()
+                .find(|p| p.pr_number == row.pr_number)
+                .is_none_or(|p| {
+                    p.pr_state != row.pr_state
+                        || p.ci_status != row.ci_status
+                        || p.approved != row.approved
+                })
+        })
+        .collect()
+}
+
+/// Draw the table and return how many lines it took.
+fn render(stack_name: &str, provider: &Provider, rows: &[WatchRow], interval: Duration) -> usize {
+    println!(
+        "{} {}",
+        style(stack_name).cyan().bold(),
+        style(format!("(every {}s, Ctrl+C to stop)", interval.as_secs())).dim()
+    );
+    for row in rows {
+        let state = match (&row.pr_state, row.approved) {
+            (Some(PrState::Merged), _) => style("merged".to_string()).green(),
+            (Some(PrState::Closed), _) => style("closed".to_string()).red(),
+            (Some(PrState::Draft), _) => style("draft".to_string()).dim(),
+            (Some(PrState::Open), true) => style("approved".to_string()).green(),
+            (Some(PrState::Open), false) => style("open".to_string()).yellow(),
+            (None, _) => style("?".to_string()).dim(),
+        };
+        let ci = match &row.ci_status {
+            Some(CiStatus::Success) => style(plain::ok()).green().to_string(),
+            Some(CiStatus::Failed) => style(plain::fail()).red().to_string(),
+            Some(CiStatus::Running) => style(plain::running()).yellow().to_string(),
+            Some(CiStatus::Pending) => style(plain::pending()).dim().to_string(),
+            _ => String::new(),
+        };
+        println!(
+            "  {} {} {} {}{} {} {}",
+            style(format!("[{}]", row.position)).dim(),
+            style(&row.short_sha).yellow(),
+            row.title,
+            style(provider.pr_number_prefix()).blue(),
+            style(row.pr_number).blue(),
+            state,
+            ci
+        );
+    }
+    rows.len() + 1
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn row(pr_number: u64, ci_status: Option<CiStatus>, approved: bool) -> WatchRow {
+        WatchRow {
+            position: pr_number as usize,
+            short_sha: "abc1234".to_string(),
+            title: "Title".to_string(),
+            pr_number,
+            pr_state: Some(PrState::Open),
+            ci_status,
+            approved,
+        }
+    }
+
+    #[test]
+    fn changed_rows_reports_only_state_changes() {
+        let prev = vec![
+            row(1, Some(CiStatus::Running), false),
+            row(2, Some(CiStatus::Running), false),
+            row(3, Some(CiStatus::Success), false),
+        ];
+        let next = vec![
+            row(1, Some(CiStatus::Running), false),
+            row(2, Some(CiStatus::Success), false),
+            row(3, Some(CiStatus::Success), true),
+        ];
+        let changed: Vec<u64> = changed_rows(&prev, &next)
+            .iter()
+            .map(|r| r.pr_number)
+            .collect();
+        assert_eq!(changed, vec![2, 3]);
+    }
+
+    #[test]
+    fn finished_means_merged_or_closed() {
+        let mut r = row(1, None, false);
+        assert!(!r.is_finished());
+        r.pr_state = Some(PrState::Merged);
+        assert!(r.is_finished());
+        r.pr_state = Some(PrState::Closed);
+        assert!(r.is_finished());
+    }
+}
@@ -0,0 +1,407 @@
+//! `gg worktree` - Manage the linked worktrees of stacks
+//!
+//! `gg co --worktree` and `gg unstack --worktree` record each stack's
+//! worktree under `stacks.<stack>.worktree_path`. `gg worktree list` shows
+//! them, `gg worktree prune` removes the ones whose stack is gone, and
+//! `gg worktree move` relocates one, keeping the config in sync.
+
+use std::collections::HashSet;
+use std::path::{Path, PathBuf};
+use std::process::Command;
+
+use console::style;
+use git2::{BranchType, Repository};
+
+use crate::config::Config;
+use crate::error::{GgError, Result};
+use crate::git;
+use crate::output::{
+    print_json, WorktreeJson, WorktreeListResponse, WorktreeMoveResponse, WorktreePruneResponse,
+    OUTPUT_VERSION,
+};
+
+use super::checkout::request_shell_cd;
+
+/// State of a stack's configured worktree.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum WorktreeStatus {
+    /// On disk, for a stack that still has its branch
+    Active,
+    /// The directory no longer exists
+    Missing,
+    /// On disk, but the stack's branch is gone
+    Orphaned,
+}
+
+impl WorktreeStatus {
+    pub fn as_str(self) -> &'static str {
+        match self {
+            WorktreeStatus::Active => "active",
+            WorktreeStatus::Missing => "missing",
+            WorktreeStatus::Orphaned => "orphaned",
+        }
+    }
+}
+
+/// A `stacks.<stack>.worktree_path` entry.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct StackWorktree {
+    pub stack: String,
+    pub path: PathBuf,
+    pub status: WorktreeStatus,
+    /// Branch checked out in the worktree
+    pub branch: Option<String>,
+    /// Uncommitted changes to tracked files
+    pub dirty: bool,
+}
+
+impl From<&StackWorktree> for WorktreeJson {
+    fn from(worktree: &StackWorktree) -> Self {
+        WorktreeJson {
+            stack: worktree.stack.clone(),
+            path: worktree.path.display().to_string(),
+            status: worktree.status.as_str().to_string(),
+            branch: worktree.branch.clone(),
+            dirty: worktree.dirty,
+        }
+    }
+}
+
+/// Every stack with a configured worktree, by stack name.
+pub fn stack_worktrees(repo: &Repository, config: &Config) -> Result<Vec<StackWorktree>> {
+    let stacks_with_branch: HashSet<String> = repo
+        .branches(Some(BranchType::Local))?
+        .filter_map(|b| b.ok())
+        .filter_map(|(b, _)| b.name().ok().flatten().map(str::to_string))
+        .filter_map(|name| git::parse_stack_branch(&name).map(|(_, stack)| stack))
+        .collect();
+
+    let mut worktrees: Vec<StackWorktree> = config
+        .stacks
+        .iter()
+        .filter_map(|(stack, cfg)| Some((stack, cfg.worktree_path.as_ref()?)))
+        .map(|(stack, path)| {
+            let path = PathBuf::from(path);
+            let worktree_repo = path
+                .exists()
+                .then(|| Repository::open(&path).ok())
+                .flatten();
+            let status = if !path.exists() {
+                WorktreeStatus::Missing
+            } else if stacks_with_branch.contains(stack) {
+                WorktreeStatus::Active
+            } else {
+                WorktreeStatus::Orphaned
+            };
+            StackWorktree {
+                stack: stack.clone(),
+                branch: worktree_repo.as_ref().and_then(git::current_branch_name),
+                dirty: worktree_repo
+                    .as_ref()
+                    .is_some_and(|r| !git::is_working_directory_clean(r).unwrap_or(true)),
+                path,
+                status,
+            }
+        })
+        .collect();
+    worktrees.sort_by(|a, b| a.stack.cmp(&b.stack));
+    Ok(worktrees)
+}
+
+/// Run `gg worktree list`
+pub fn run_list(json: bool) -> Result<()> {
+    let repo = git::open_repo()?;
+    let config = Config::load(repo.commondir())?;
+    let worktrees = stack_worktrees(&repo, &config)?;
+
+    if json {
+        print_json(&WorktreeListResponse {
+            version: OUTPUT_VERSION,
+            worktrees: worktrees.iter().map(Into::into).collect(),
+        });
+        return Ok(());
+    }
+
+    if worktrees.is_empty() {
+        println!("{}", style("No stack worktrees.").dim());
+        return Ok(());
+    }
+    for worktree in &worktrees {
+        print_worktree(worktree);
+    }
+    if worktrees.iter().any(|w| w.status != WorktreeStatus::Active) {
+        println!();
+        println!(
+            "  {}",
+            style("Run `gg worktree prune` to remove the stale ones.").dim()
+        );
+    }
+    Ok(())
+}
+
+fn print_worktree(worktree: &StackWorktree) {
+    let status = match worktree.status {
+        WorktreeStatus::Active => style(worktree.status.as_str()).green(),
+        WorktreeStatus::Missing | WorktreeStatus::Orphaned => {
+            style(worktree.status.as_str()).yellow()
+        }
+    };
+    let branch = worktree
+        .branch
+        .as_deref()
+        .map(|b| format!(" ({})", b))
+        .unwrap_or_default();
+    let dirty = if worktree.dirty { " *" } else { "" };
+    println!(
+        "{}  {}  {}{}{}",
+        style(&worktree.stack).cyan(),
+        status,
+        worktree.path.display(),
+        style(branch).dim(),
+        style(dirty).red()
+    );
+}
+
+/// Run `gg worktree prune`: remove the worktrees of stacks whose branch is
+/// gone and forget the ones whose directory is. Worktrees with uncommitted
+/// changes are kept unless `force`.
+pub fn run_prune(dry_run: bool, force: bool, json: bool) -> Result<()> {
+    let repo = git::open_repo()?;
+    let git_dir = repo.commondir().to_path_buf();
+    let _lock = git::acquire_operation_lock(&repo, "worktree")?;
+    let mut config = Config::load(&git_dir)?;
+    let repo_root = main_worktree_root(&repo)?;
+
+    let (stale, kept): (Vec<StackWorktree>, Vec<StackWorktree>) = stack_worktrees(&repo, &config)?
+        .into_iter()
+        .filter(|w| w.status != WorktreeStatus::Active)
+        .partition(|w| force || !w.dirty);
+
+    if !dry_run {
+        for worktree in &stale {
+            if worktree.status == WorktreeStatus::Orphaned {
+                let mut args = vec!["worktree", "remove"];
+                if force {
+                    args.push("--force");
+                }
+                let path = worktree.path.to_string_lossy();
+                args.push(&path);
+                run_git_in(&repo_root, &args).map_err(|e| {
+                    GgError::Other(format!(
+                        "Failed to remove worktree '{}': {}",
+                        worktree.path.display(),
+                        e
+                    ))
+                })?;
+            }
+            if let Some(stack_cfg) = config.stacks.get_mut(&worktree.stack) {
+                stack_cfg.worktree_path = None;
+            }
+        }
+        if !stale.is_empty() {
+            let _ = run_git_in(&repo_root, &["worktree", "prune"]);
+            config.save(&git_dir)?;
+        }
+    }
+
+    if json {
+        print_json(&WorktreePruneResponse {
+            version: OUTPUT_VERSION,
+            dry_run,
+            pruned: stale.iter().map(Into::into).collect(),
+            kept: kept.iter().map(Into::into).collect(),
+        });
+        return Ok(());
+    }
+
+    if stale.is_empty() && kept.is_empty() {
+        println!("{}", style("No stale worktrees.").dim());
+        return Ok(());
+    }
+    for worktree in &stale {
+        let action = match (dry_run, worktree.status) {
+            (true, _) => "Would remove",
+            (false, WorktreeStatus::Missing) => "Forgot",
+            (false, _) => "Removed",
+        };
+        println!(
+            "{} {} worktree of {} at {}",
+            style("OK").green().bold(),
+            action,
+            style(&worktree.stack).cyan(),
+            worktree.path.display()
+        );
+    }
+    for worktree in &kept {
+        println!(
+            "{} Kept worktree of {} at {}: it has uncommitted changes (use --force to remove it anyway)",
+            style("Note:").cyan(),
+            style(&worktree.stack).cyan(),
+            worktree.path.display()
+        );
+    }
+    Ok(())
+}
+
+/// Run `gg worktree move`: relocate `stack`'s worktree to `destination`
+/// and record the new path.
+pub fn run_move(stack: &str, destination: &Path, json: bool) -> Result<()> {
+    let repo = git::open_repo()?;
+    let git_dir = repo.commondir().to_path_buf();
+    let _lock = git::acquire_operation_lock(&repo, "worktree")?;
+    let mut config = Config::load(&git_dir)?;
+    let repo_root = main_worktree_root(&repo)?;
+
+    let from = config
+        .get_stack(stack)
+        .and_then(|c| c.worktree_path.clone())
+        .map(PathBuf::from)
+        .ok_or_else(|| {
+            GgError::Other(format!(
+                "Stack '{}' has no worktree. See `gg worktree list`.",
+                stack
+            ))
+        })?;
+    if !from.exists() {
+        return Err(GgError::Other(format!(
+            "The worktree of '{}' at '{}' no longer exists. Run `gg worktree prune` to forget it.",
+            stack,
+            from.display()
+        )));
+    }
+    let to = std::path::absolute(destination)?;
+    if to.exists() {
+        return Err(GgError::Other(format!(
+            "'{}' already exists. Pick a path that does not.",
+            to.display()
+        )));
+    }
+    if let Some(parent) = to.parent() {
+        std::fs::create_dir_all(parent)?;
+    }
+
+    // Moving the worktree the shell is in: follow it
+    let inside = std::env::current_dir()
+        .ok()
+        .zip(from.canonicalize().ok())
+        .is_some_and(|(cwd, from)| cwd.starts_with(from));
+    run_git_in(
+        &repo_root,
+        &[
+            "worktree",
+            "move",
+            &from.to_string_lossy(),
+            &to.to_string_lossy(),
+        ],
+    )
+    .map_err(|e| {
+        GgError::Other(format!(
+            "Failed to move worktree '{}': {}",
+            from.display(),
+            e
+        ))
+    })?;
+    config.get_or_create_stack(stack).worktree_path = Some(to.to_string_lossy().to_string());
+    config.save(&git_dir)?;
+    if inside {
+        request_shell_cd(&to);
+    }
+
+    if json {
+        print_json(&WorktreeMoveResponse {
+            version: OUTPUT_VERSION,
+            stack: stack.to_string(),
+            from: from.display().to_string(),
+            to: to.display().to_string(),
+        });
+    } else {
+        println!(
+            "{} Moved worktree of {} to {}",
+            style("OK").green().bold(),
+            style(stack).cyan(),
+            style(to.display()).yellow()
+        );
+    }
+    Ok(())
+}
+
+/// Working directory of the main worktree, where `git worktree` commands
+/// are run from (so they also work from inside the worktree being changed).
+pub(crate) fn main_worktree_root(repo: &Repository) -> Result<PathBuf> {
+    let commondir = repo.commondir();
+    match Repository::open(commondir)?.workdir() {
+        Some(workdir) => Ok(workdir.to_path_buf()),
+        None => Ok(commondir.parent().unwrap_or(commondir).to_path_buf()),
+    }
+}
+
+fn run_git_in(dir: &Path, args: &[&str]) -> std::result::Result<(), String> {
+    let output = Command::new("git")
+        .args(args)
+        .current_dir(dir)
+        .output()
+        .map_err(|e| e.to_string())?;
+    if output.status.success() {
+        Ok(())
+    } else {
+        Err(String::from_utf8_lossy(&output.stderr).trim().to_string())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::config::StackConfig;
+
+    #[test]
+    fn stack_worktrees_classifies_missing_and_orphaned_paths() {
+        let dir = tempfile::tempdir().unwrap();
+        let repo = Repository::init(dir.path().join("repo")).unwrap();
+        let sig = git2::Signature::now("Test", "test@example.com").unwrap();
+        let tree = repo
+            .find_tree(repo.index().unwrap().write_tree().unwrap())
+            .unwrap();
+        let oid = repo
+            .commit(Some("HEAD"), &sig, &sig, "initial", &tree, &[])
+            .unwrap();
+        repo.branch("nacho/alive", &repo.find_commit(oid).unwrap(), false)
+            .unwrap();
+
+        let alive = dir.path().join("alive");
+        let orphan = dir.path().join("orphan");
+        std::fs::create_dir_all(&alive).unwrap();
+        std::fs::create_dir_all(&orphan).unwrap();
+        let mut config = Config::default();
+        for (stack, path) in [
+            ("alive", alive.clone()),
+            ("gone", dir.path().join("gone")),
+            ("orphan", orphan.clone()),
+        ] {
+            config.stacks.insert(
+                stack.to_string(),
+                StackConfig {
+                    worktree_path: Some(path.to_string_lossy().to_string()),
+                    ..Default::default()
+                },
+            );
+        }
+        config
+            .stacks
+            .insert("no-worktree".to_string(), StackConfig::default());
+
+        let worktrees = stack_worktrees(&repo, &config).unwrap();
+        let statuses: Vec<(&str, WorktreeStatus)> = worktrees
+            .iter()
+            .map(|w| (w.stack.as_str(), w.status))
+            .collect();
+        assert_eq!(
+            statuses,
+            vec![
+                ("alive", WorktreeStatus::Active),
+                ("gone", WorktreeStatus::Missing),
+                ("orphan", WorktreeStatus::Orphaned),
+            ]
+        );
+        assert_eq!(worktrees[0].path, alive);
+    }
+}
@@ -0,0 +1,270 @@
+//! Commit message policy (`defaults.commit_messages`).
+//!
+//! Commit subjects are checked against a regex, Conventional Commits
+//! (`feat(parser)!: Add streaming`) unless the config says otherwise.
+//! `gg check-msgs` reports subjects that don't match, and `gg sync` warns
+//! about or refuses to push them. The regex's named groups (`type`, `scope`,
+//! `breaking`, `description`) also shape the PR/MR: its title can be
+//! rebuilt from them, and new PRs/MRs get a label for the commit type.
+//!
+//! Subjects are checked without gg's `WIP:`/`Draft:` prefix, and
+//! `fixup!`/`squash!`/`amend!` commits are left alone: they are folded into
+//! another commit before they land.
+
+use std::collections::BTreeMap;
+
+use console::style;
+use git2::Repository;
+use regex::Regex;
+
+use crate::config::CommitMessageDefaults;
+use crate::error::{GgError, Result};
+use crate::stack::StackEntry;
+
+/// Conventional Commits: `type(scope)!: description`, scope and `!` optional.
+pub const CONVENTIONAL_PATTERN: &str =
+    r"^(?P<type>[a-z][a-z0-9-]*)(?:\((?P<scope>[^()]+)\))?(?P<breaking>!)?: (?P<description>\S.*)$";
+
+/// What a subject says about its change, from the pattern's named groups.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct Subject {
+    pub kind: Option<String>,
+    pub scope: Option<String>,
+    pub breaking: bool,
+    pub description: Option<String>,
+}
+
+/// A compiled `defaults.commit_messages`.
+#[derive(Debug, Clone)]
+pub struct Policy {
+    pattern: Regex,
+    pr_title: Option<String>,
+    labels: BTreeMap<String, String>,
+}
+
+impl Policy {
+    pub fn from_config(config: &CommitMessageDefaults) -> Result<Self> {
+        let source = config.pattern.as_deref().unwrap_or(CONVENTIONAL_PATTERN);
+        let pattern = Regex::new(source).map_err(|e| {
+            GgError::Other(format!(
+                "Invalid defaults.commit_messages.pattern `{}`: {}",
+                source, e
+            ))
+        })?;
+        Ok(Self {
+            pattern,
+            pr_title: config.pr_title.clone(),
+            labels: config.labels.clone(),
+        })
+    }
+
+    /// The regex subjects are checked against.
+    pub fn pattern(&self) -> &str {
+        self.pattern.as_str()
+    }
+
+    /// Parse the subject of `message`; `None` when it breaks the policy.
+    /// `BREAKING CHANGE:` footers count as breaking too.
+    pub fn parse(&self, message: &str) -> Option<Subject> {
+        let subject = message.lines().next().unwrap_or("").trim_end();
+        let (_, subject) = split_draft_prefix(subject);
+        if ["fixup! ", "squash! ", "amend! "]
+            .iter()
+            .any(|p| subject.starts_with(p))
+        {
+            return Some(Subject::default());
+        }
+        let mut parsed = self.captures(subject)?;
+        parsed.breaking |= message.lines().skip(1).any(|line| {
+            line.starts_with("BREAKING CHANGE:") || line.starts_with("BREAKING-CHANGE:")
+        });
+        Some(parsed)
+    }
+
+    /// The PR/MR title for a commit titled `title`: `pr_title` filled in from
+    /// the subject, keeping any `WIP:`/`Draft:` prefix. `title` is returned
+    /// as-is without a template, or when it lacks a type or description.
+    pub fn pr_title(&self, title: &str, message: &str) -> String {
+        let Some(template) = &self.pr_title else {
+            return title.to_string();
+        };
+        let (prefix, rest) = split_draft_prefix(title);
+        let Some(subject) = self.captures(rest) else {
+            return title.to_string();
+        };
+        let (Some(kind), Some(description)) = (&subject.kind, &subject.description) else {
+            return title.to_string();
+        };
+        let breaking = subject.breaking || self.parse(message).is_some_and(|s| s.breaking);
+        let rendered = template
+            .replace("{type}", kind)
+            .replace("{scope}", subject.scope.as_deref().unwrap_or(""))
+            .replace("{breaking}", if breaking { "!" } else { "" })
+            .replace("{description}", description);
+        format!("{}{}", prefix, rendered)
+    }
+
+    /// Labels for a new PR/MR whose commit has `subject`.
+    pub fn labels(&self, subject: &Subject) -> Vec<String> {
+        let mut labels = Vec::new();
+        if let Some(label) = subject.kind.as_ref().and_then(|k| self.labels.get(k)) {
+            labels.push(label.clone());
+        }
+        if let Some(label) = self.labels.get("breaking").filter(|_| subject.breaking) {
+            if !labels.contains(label) {
+                labels.push(label.clone());
+            }
+        }
+        labels
+    }
+
+    fn captures(&self, subject: &str) -> Option<Subject> {
+        let captures = self.pattern.captures(subject)?;
+        let group = |name: &str| {
+            captures
+                .name(name)
+                .map(|m| m.as_str().to_string())
+                .filter(|s| !s.is_empty())
+        };
+        Some(Subject {
+            kind: group("type"),
+            scope: group("scope"),
+            breaking: group("breaking").is_some(),
+            description: group("description"),
+        })
+    }
+}
+
+/// Split gg's `WIP:`/`Draft:` marker (and the spaces after it) off `subject`.
+fn split_draft_prefix(subject: &str) -> (&str, &str) {
+    let trimmed = subject.trim_start();
+    for marker in ["wip:", "draft:"] {
+        if trimmed
+            .get(..marker.len())
+            .is_some_and(|p| p.eq_ignore_ascii_case(marker))
+        {
+            let rest = trimmed[marker.len()..].trim_start();
+            return (&subject[..subject.len() - rest.len()], rest);
+        }
+    }
+    ("", subject)
+}
+
+/// A stack entry whose subject breaks the policy.
+#[derive(Debug, Clone)]
+pub struct MessageViolation {
+    pub position: usize,
+    pub short_sha: String,
+    pub title: String,
+}
+
+/// Check the subjects of `entries` against `policy`.
+pub fn check_entries(
+    repo: &Repository,
+    policy: &Policy,
+    entries: &[&StackEntry],
+) -> Result<Vec<MessageViolation>> {
+    let mut found = Vec::new();
+    for entry in entries {
+        let commit = repo.find_commit(entry.oid)?;
+        if policy.parse(commit.message().unwrap_or("")).is_none() {
+            found.push(MessageViolation {
+                position: entry.position,
+                short_sha: entry.short_sha.clone(),
+                title: entry.title.clone(),
+            });
+        }
+    }
+    Ok(found)
+}
+
+/// Print the entries of `violations`, one per line, under `heading`.
+pub fn print_violations(heading: &str, violations: &[MessageViolation]) {
+    println!("{}", heading);
+    for v in violations {
+        println!(
+            "  {} {} {}",
+            style(format!("#{}", v.position)).dim(),
+            style(&v.short_sha).yellow(),
+            v.title
+        );
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn policy(config: CommitMessageDefaults) -> Policy {
+        Policy::from_config(&config).unwrap()
+    }
+
+    #[test]
+    fn parses_conventional_subjects() {
+        let policy = policy(CommitMessageDefaults::default());
+        assert_eq!(
+            policy.parse("feat(parser)!: Stream large files\n\nGG-ID: c-1234567"),
+            Some(Subject {
+                kind: Some("feat".to_string()),
+                scope: Some("parser".to_string()),
+                breaking: true,
+                description: Some("Stream large files".to_string()),
+            })
+        );
+        let fix = policy
+            .parse("WIP: fix: Handle empty input\n\nBREAKING CHANGE: errors on empty input")
+            .unwrap();
+        assert_eq!(fix.kind.as_deref(), Some("fix"));
+        assert!(fix.breaking);
+
+        assert_eq!(policy.parse("Add parser"), None);
+        assert_eq!(policy.parse("feat:missing space"), None);
+        assert_eq!(policy.parse("fixup! Add parser"), Some(Subject::default()));
+    }
+
+    #[test]
+    fn custom_pattern_replaces_conventional_commits() {
+        let policy = policy(CommitMessageDefaults {
+            pattern: Some(r"^\[(?P<type>[A-Z]+-\d+)\] ".to_string()),
+            ..Default::default()
+        });
+        assert!(policy.parse("[PAY-12] Retry refunds").is_some());
+        assert!(policy.parse("fix: Retry refunds").is_none());
+
+        let invalid = Policy::from_config(&CommitMessageDefaults {
+            pattern: Some("(".to_string()),
+            ..Default::default()
+        });
+        assert!(invalid.is_err());
+    }
+
+    #[test]
+    fn pr_title_and_labels_come_from_the_type() {
+        let policy = policy(CommitMessageDefaults {
+            pr_title: Some("[{type}{breaking}] {description}".to_string()),
+            labels: BTreeMap::from([
+                ("feat".to_string(), "enhancement".to_string()),
+                ("fix".to_string(), "bug".to_string()),
+                ("breaking".to_string(), "breaking-change".to_string()),
+            ]),
+            ..Default::default()
+        });
+        assert_eq!(
+            policy.pr_title("feat(parser)!: Stream large files", ""),
+            "[feat!] Stream large files"
+        );
+        assert_eq!(
+            policy.pr_title("Draft: fix: Handle empty input", ""),
+            "Draft: [fix] Handle empty input"
+        );
+        assert_eq!(policy.pr_title("Add parser", ""), "Add parser");
+
+        let subject = policy.parse("feat!: Stream large files").unwrap();
+        assert_eq!(
+            policy.labels(&subject),
+            vec!["enhancement", "breaking-change"]
+        );
+        let subject = policy.parse("docs: Explain streaming").unwrap();
+        assert!(policy.labels(&subject).is_empty());
+    }
+}
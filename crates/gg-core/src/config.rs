@@ -3,21 +3,26 @@
 //! Config is stored in `.git/gg/config.json` and contains:
 //! - Default settings (base branch, username, lint commands)
 //! - Per-stack settings and MR mappings
+//!
+//! A global config (`~/.config/gg/config.json`, or under
+//! `$XDG_CONFIG_HOME`) sits underneath it: each key the repo config doesn't
+//! set falls through to the global one, then to the built-in default.
 
-use std::collections::HashMap;
+use std::collections::{BTreeMap, HashMap};
 use std::fs::{self, File};
 use std::path::{Path, PathBuf};
 use std::time::Duration;
 
 use fs2::FileExt;
 use serde::{Deserialize, Serialize};
+use serde_json::{Map, Value};
 
 use crate::error::{GgError, Result};
 
 /// Default configuration values
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct Defaults {
-    /// Git hosting provider ("github" or "gitlab")
+    /// Git hosting provider ("github", "gitlab" or "gitea")
     /// Used for self-hosted instances where URL detection fails
     pub provider: Option<String>,
 
@@ -25,15 +30,83 @@ pub struct Defaults {
     #[serde(default)]
     pub gitlab: GitLabDefaults,
 
+    /// GitHub-specific defaults
+    #[serde(default, skip_serializing_if = "GitHubDefaults::is_default")]
+    pub github: GitHubDefaults,
+
+    /// Gitea/Forgejo-specific defaults
+    #[serde(default, skip_serializing_if = "GiteaDefaults::is_default")]
+    pub gitea: GiteaDefaults,
+
+    /// Talk to GitHub/GitLab through their CLIs or directly over HTTP
+    /// (default: cli)
+    #[serde(default)]
+    pub api_mode: ApiMode,
+
     /// Base branch name (default: auto-detect main/master/trunk)
     pub base: Option<String>,
 
     /// Username for branch naming (default: glab whoami)
     pub branch_username: Option<String>,
 
+    /// Stack branch name template with `{user}` and `{stack}`
+    /// (default: `{user}/{stack}`)
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub branch_template: Option<String>,
+
+    /// Entry branch name template with `{user}`, `{stack}` and `{id}`
+    /// (default: `{user}/{stack}--{id}`)
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub entry_branch_template: Option<String>,
+
     /// Lint commands to run per commit
     #[serde(default)]
-    pub lint: Vec<String>,
+    pub lint: Vec<LintCommand>,
+
+    /// Commits `gg lint` checks at once in temporary worktrees
+    /// (default: 1 = sequential, 0 = one per CPU)
+    #[serde(default = "default_lint_jobs")]
+    pub lint_jobs: usize,
+
+    /// Branch patterns gg never pushes to or rewrites (e.g. `main`,
+    /// `release/*`), whatever the base or branch naming says
+    #[serde(default)]
+    pub protected_branches: Vec<String>,
+
+    /// Users (or `org/team` teams) asked to review every new PR/MR
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub reviewers: Vec<String>,
+
+    /// Labels added to every new PR/MR
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub labels: Vec<String>,
+
+    /// Milestone (by title) every new PR/MR is put in
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub milestone: Option<String>,
+
+    /// Commit message policy: which subjects are valid, and the PR/MR
+    /// titles and labels derived from them
+    #[serde(default, skip_serializing_if = "CommitMessageDefaults::is_default")]
+    pub commit_messages: CommitMessageDefaults,
+
+    /// Regex finding issue keys (`#123`, `PAY-42`) in stack names and commit
+    /// messages; a `key` group narrows each match
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub issue_pattern: Option<String>,
+
+    /// Line added to PR/MR descriptions for each issue key, with `{key}`
+    /// (default: `Closes {key}`)
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub issue_link: Option<String>,
+
+    /// Tracker whose linked issues `gg land` closes or transitions
+    #[serde(default, skip_serializing_if = "IssueTrackerDefaults::is_default")]
+    pub issue_tracker: IssueTrackerDefaults,
+
+    /// Changelog entries `gg land` writes for the PRs/MRs it merges
+    #[serde(default, skip_serializing_if = "ChangelogDefaults::is_default")]
+    pub changelog: ChangelogDefaults,
 
     /// Deprecated: kept for backward compatibility with existing config files.
     /// Runtime behavior always enforces GG-ID metadata normalization.
@@ -47,10 +120,28 @@ pub struct Defaults {
     #[serde(default)]
     pub land_auto_clean: bool,
 
+    /// What `gg clean` does with a merged stack's branch: delete it, or
+    /// keep it under `refs/gg/archive/` (default: delete)
+    #[serde(default)]
+    pub clean_mode: CleanMode,
+
     /// Use admin privileges to bypass approval requirements on land (default: false)
     #[serde(default)]
     pub land_admin: bool,
 
+    /// How `gg land` merges PRs/MRs: squash, merge or rebase (default: squash)
+    #[serde(default)]
+    pub merge_strategy: MergeStrategy,
+
+    /// Delete each entry's remote branch once its PR/MR is landed (default: false)
+    #[serde(default)]
+    pub delete_branch_on_land: bool,
+
+    /// Command (or http(s) webhook URL) notified when `gg land --wait` or
+    /// `gg watch` finishes
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub notify_command: Option<String>,
+
     /// Automatically run lint before sync (default: false)
     #[serde(default)]
     pub sync_auto_lint: bool,
@@ -67,8 +158,9 @@ pub struct Defaults {
     #[serde(default)]
     pub unstaged_action: UnstagedAction,
 
-    /// Create new PRs/MRs as drafts by default during sync (default: false)
-    #[serde(default)]
+    /// Create new PRs/MRs as drafts by default during sync (default: false).
+    /// Also accepted as `create_as_draft`.
+    #[serde(default, alias = "create_as_draft")]
     pub sync_draft: bool,
 
     /// Update PR/MR descriptions on re-sync (default: true)
@@ -79,21 +171,191 @@ pub struct Defaults {
     #[serde(default)]
     pub sync_update_title: bool,
 
+    /// Concurrent pushes and PR/MR lookups during sync (default: 4, 1 = sequential)
+    #[serde(default = "default_sync_jobs")]
+    pub sync_jobs: usize,
+
     /// Post and maintain a managed navigation comment on each PR/MR in a
     /// multi-entry stack. Default: false (opt-in).
     #[serde(default)]
     pub stack_nav_comments: bool,
+
+    /// Embed a graph of the stack in each PR/MR description during sync
+    /// (default: off)
+    #[serde(default)]
+    pub stack_graph: StackGraphMode,
+
+    /// Use ASCII markers instead of emoji, box-drawing and spinners, and
+    /// disable color, as with `--plain` (default: false)
+    #[serde(default)]
+    pub plain_output: bool,
+
+    /// Sign the commits gg rewrites (`true`/`false`); unset follows git's
+    /// `commit.gpgsign`
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub sign_commits: Option<bool>,
+
+    /// Which git hooks run during gg's amends and rebases: always,
+    /// rewrite-only or never (default: always)
+    #[serde(default)]
+    pub run_hooks: RunHooks,
+
+    /// Open every stack checked out with `gg co` in its own linked worktree,
+    /// as if `--worktree` was passed (default: false)
+    #[serde(default)]
+    pub always_worktree: bool,
+
+    /// Where stack worktrees go: a template using `{repo}` and `{stack}`, or
+    /// a directory that gets one `{repo}.{stack}` worktree per stack
+    /// (default: `../{repo}.{stack}`)
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub worktree_base_path: Option<String>,
 }
 
 fn default_sync_behind_threshold() -> usize {
     1
 }
 
+fn default_sync_jobs() -> usize {
+    4
+}
+
+fn default_lint_jobs() -> usize {
+    1
+}
+
 fn default_true() -> bool {
     true
 }
 
-/// Behavior for `gg amend` when unstaged changes are detected.
+/// How `gg sync` embeds the stack graph in PR/MR descriptions.
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, Default, PartialEq, Eq)]
+#[serde(rename_all = "lowercase")]
+pub enum StackGraphMode {
+    /// No graph.
+    #[default]
+    Off,
+    /// A Mermaid diagram, rendered natively by GitHub and GitLab.
+    Mermaid,
+    /// An uploaded SVG image (GitLab uploads); falls back to Mermaid on
+    /// GitHub, which has no upload API.
+    Image,
+}
+
+/// A lint command from `defaults.lint`: either a plain command string, or
+/// `{"cmd": ..., "paths": [...]}` to run it only on commits that touch a
+/// file matching one of the `paths` globs.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+#[serde(untagged)]
+pub enum LintCommand {
+    /// Runs on every commit.
+    Plain(String),
+    /// Runs on commits whose diff touches a file matching `paths` (every
+    /// commit when `paths` is empty).
+    Scoped {
+        cmd: String,
+        #[serde(default)]
+        paths: Vec<String>,
+    },
+}
+
+impl LintCommand {
+    /// The shell command to run.
+    pub fn cmd(&self) -> &str {
+        match self {
+            LintCommand::Plain(cmd) | LintCommand::Scoped { cmd, .. } => cmd,
+        }
+    }
+
+    /// Path globs limiting which commits the command runs on (empty = all).
+    pub fn paths(&self) -> &[String] {
+        match self {
+            LintCommand::Plain(_) => &[],
+            LintCommand::Scoped { paths, .. } => paths,
+        }
+    }
+}
+
+impl From<&str> for LintCommand {
+    fn from(cmd: &str) -> Self {
+        LintCommand::Plain(cmd.to_string())
+    }
+}
+
+impl std::fmt::Display for LintCommand {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self.paths() {
+            [] => write!(f, "{}", self.cmd()),
+            paths => write!(f, "{} ({})", self.cmd(), paths.join(", ")),
+        }
+    }
+}
+
+/// How gg talks to GitHub and GitLab.
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, Default, PartialEq, Eq)]
+#[serde(rename_all = "lowercase")]
+pub enum ApiMode {
+    /// Shell out to `gh` / `glab`.
+    #[default]
+    Cli,
+    /// Call the REST APIs directly with a token, so neither CLI has to be
+    /// installed (e.g. in CI containers).
+    Native,
+}
+
+/// How `gg land` merges a PR/MR into its base.
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, Default, PartialEq, Eq, clap::ValueEnum)]
+#[serde(rename_all = "lowercase")]
+pub enum MergeStrategy {
+    /// Squash the PR/MR into a single commit.
+    #[default]
+    Squash,
+    /// Create a merge commit.
+    Merge,
+    /// Rebase the commits onto the base without a merge commit.
+    Rebase,
+}
+
+impl MergeStrategy {
+    /// Name as used by `gh pr merge --<name>`, GitHub's `merge_method` and
+    /// Gitea's `Do`
+    pub fn as_str(self) -> &'static str {
+        match self {
+            MergeStrategy::Squash => "squash",
+            MergeStrategy::Merge => "merge",
+            MergeStrategy::Rebase => "rebase",
+        }
+    }
+}
+
+/// What `gg clean` does with the branch of a merged stack.
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, Default, PartialEq, Eq)]
+#[serde(rename_all = "lowercase")]
+pub enum CleanMode {
+    /// Delete the branch.
+    #[default]
+    Delete,
+    /// Keep the tip under `refs/gg/archive/<stack>/<date>`, restorable with
+    /// `gg archive restore`.
+    Archive,
+}
+
+/// Which git hooks run when gg amends or rebases (`defaults.run_hooks`).
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, Default, PartialEq, Eq)]
+#[serde(rename_all = "kebab-case")]
+pub enum RunHooks {
+    /// Whatever git runs: `pre-commit`/`commit-msg` on amends, `pre-rebase`,
+    /// `post-rewrite` and `post-checkout` on rebases.
+    #[default]
+    Always,
+    /// Only for amends that change a commit's content, not for rebases that
+    /// just move commits around.
+    RewriteOnly,
+    /// None.
+    Never,
+}
+
+/// Behavior for `gg amend` and `gg absorb` when unstaged changes are detected.
 #[derive(Debug, Clone, Copy, Serialize, Deserialize, Default, PartialEq, Eq)]
 #[serde(rename_all = "lowercase")]
 pub enum UnstagedAction {
@@ -115,13 +377,32 @@ impl Default for Defaults {
         Self {
             provider: None,
             gitlab: GitLabDefaults::default(),
+            github: GitHubDefaults::default(),
+            gitea: GiteaDefaults::default(),
+            api_mode: ApiMode::Cli,
             base: None,
             branch_username: None,
+            branch_template: None,
+            entry_branch_template: None,
             lint: Vec::new(),
+            lint_jobs: default_lint_jobs(),
+            protected_branches: Vec::new(),
+            reviewers: Vec::new(),
+            labels: Vec::new(),
+            milestone: None,
+            commit_messages: CommitMessageDefaults::default(),
+            issue_pattern: None,
+            issue_link: None,
+            issue_tracker: IssueTrackerDefaults::default(),
+            changelog: ChangelogDefaults::default(),
             auto_add_gg_ids: true,
             land_wait_timeout_minutes: None,
             land_auto_clean: false,
+            clean_mode: CleanMode::Delete,
             land_admin: false,
+            merge_strategy: MergeStrategy::Squash,
+            delete_branch_on_land: false,
+            notify_command: None,
             sync_auto_lint: false,
             sync_auto_rebase: false,
             sync_behind_threshold: default_sync_behind_threshold(),
@@ -129,18 +410,204 @@ impl Default for Defaults {
             sync_draft: false,
             sync_update_descriptions: true,
             sync_update_title: false,
+            sync_jobs: default_sync_jobs(),
             stack_nav_comments: false,
+            stack_graph: StackGraphMode::Off,
+            plain_output: false,
+            sign_commits: None,
+            run_hooks: RunHooks::Always,
+            always_worktree: false,
+            worktree_base_path: None,
         }
     }
 }
 
 /// GitLab-specific default settings
-#[derive(Debug, Clone, Serialize, Deserialize, Default, PartialEq, Eq)]
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
 pub struct GitLabDefaults {
     /// When landing, request GitLab to auto-merge the MR when the pipeline succeeds
     /// ("merge when pipeline succeeds") instead of attempting an immediate merge.
     #[serde(default)]
     pub auto_merge_on_land: bool,
+
+    /// Mark each MR as blocked by its predecessor (MR dependencies) during
+    /// sync (default: true)
+    #[serde(default = "default_true")]
+    pub sync_mr_dependencies: bool,
+}
+
+impl Default for GitLabDefaults {
+    fn default() -> Self {
+        Self {
+            auto_merge_on_land: false,
+            sync_mr_dependencies: true,
+        }
+    }
+}
+
+/// GitHub-specific default settings
+#[derive(Debug, Clone, Serialize, Deserialize, Default, PartialEq, Eq)]
+pub struct GitHubDefaults {
+    /// When landing, enable GitHub's native auto-merge on the PR instead of
+    /// merging it from the client.
+    #[serde(default)]
+    pub auto_merge_on_land: bool,
+}
+
+impl GitHubDefaults {
+    fn is_default(&self) -> bool {
+        self == &Self::default()
+    }
+}
+
+/// Gitea/Forgejo-specific default settings
+#[derive(Debug, Clone, Serialize, Deserialize, Default, PartialEq, Eq)]
+pub struct GiteaDefaults {
+    /// Web URL of the instance (e.g. `https://git.example.com`). Defaults to
+    /// `https://<host>` of the `origin` remote; set it when the SSH host or
+    /// port differs from the web one.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub url: Option<String>,
+}
+
+impl GiteaDefaults {
+    fn is_default(&self) -> bool {
+        self == &Self::default()
+    }
+}
+
+/// Commit message policy (`defaults.commit_messages`). Subjects are
+/// checked against `pattern`, Conventional Commits by default.
+#[derive(Debug, Clone, Serialize, Deserialize, Default, PartialEq, Eq)]
+pub struct CommitMessageDefaults {
+    /// What `gg sync` does with subjects that don't match: off, warn or
+    /// block (default: off)
+    #[serde(default)]
+    pub check: CommitMessageCheck,
+
+    /// Regex every subject must match. Named groups `type`, `scope`,
+    /// `breaking` and `description` feed `pr_title` and `labels`
+    /// (default: Conventional Commits)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub pattern: Option<String>,
+
+    /// PR/MR title template with `{type}`, `{scope}`, `{breaking}` and
+    /// `{description}` (default: the commit subject)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub pr_title: Option<String>,
+
+    /// Label added to new PRs/MRs by commit type (e.g. `"feat": "enhancement"`);
+    /// the `breaking` key labels breaking changes
+    #[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
+    pub labels: BTreeMap<String, String>,
+}
+
+impl CommitMessageDefaults {
+    fn is_default(&self) -> bool {
+        self == &Self::default()
+    }
+}
+
+/// What `gg sync` does with commit subjects that break the policy.
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, Default, PartialEq, Eq)]
+#[serde(rename_all = "lowercase")]
+pub enum CommitMessageCheck {
+    /// Don't check.
+    #[default]
+    Off,
+    /// Print a warning and sync anyway.
+    Warn,
+    /// Refuse to sync until the commits are reworded.
+    Block,
+}
+
+/// Issue tracker `gg land` resolves linked issues in
+/// (`defaults.issue_tracker`)
+#[derive(Debug, Clone, Serialize, Deserialize, Default, PartialEq, Eq)]
+pub struct IssueTrackerDefaults {
+    /// github, jira, linear or command (default: none)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub kind: Option<IssueTrackerKind>,
+
+    /// Base URL of the Jira instance (e.g. `https://acme.atlassian.net`)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub url: Option<String>,
+
+    /// Jira transition or Linear workflow state landed issues move to
+    /// (default: `Done`)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub transition: Option<String>,
+
+    /// Shell command run per landed issue when `kind` is `command`
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub command: Option<String>,
+}
+
+impl IssueTrackerDefaults {
+    fn is_default(&self) -> bool {
+        self == &Self::default()
+    }
+}
+
+/// Issue trackers gg can resolve issues in.
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq)]
+#[serde(rename_all = "lowercase")]
+pub enum IssueTrackerKind {
+    /// GitHub issues of the repository, closed by number.
+    Github,
+    /// Jira issues, moved through a transition.
+    Jira,
+    /// Linear issues, moved to a workflow state.
+    Linear,
+    /// A shell command, for any other tracker.
+    Command,
+}
+
+/// Changelog entries `gg land` writes for the PRs/MRs it merges
+/// (`defaults.changelog`)
+#[derive(Debug, Clone, Serialize, Deserialize, Default, PartialEq, Eq)]
+pub struct ChangelogDefaults {
+    /// off, output, fragments or file (default: off)
+    #[serde(default)]
+    pub mode: ChangelogMode,
+
+    /// Fragment directory (default: `changelog.d`) or changelog file
+    /// (default: `CHANGELOG.md`)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub path: Option<String>,
+
+    /// Entry text (default: `{description} ({ref})`)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub template: Option<String>,
+
+    /// Fragment file name (default: `{number}.{type}.md`)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub fragment: Option<String>,
+
+    /// Heading `file` mode adds entries under (default: `## Unreleased`)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub section: Option<String>,
+}
+
+impl ChangelogDefaults {
+    fn is_default(&self) -> bool {
+        self == &Self::default()
+    }
+}
+
+/// Where `gg land` puts changelog entries.
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, Default, PartialEq, Eq)]
+#[serde(rename_all = "lowercase")]
+pub enum ChangelogMode {
+    /// No changelog entries.
+    #[default]
+    Off,
+    /// Only in the land output (`--json` included).
+    Output,
+    /// One fragment file per PR/MR, committed to the base branch.
+    Fragments,
+    /// A section of a changelog file, committed to the base branch.
+    File,
 }
 
 /// Per-stack configuration
@@ -150,6 +617,15 @@ pub struct StackConfig {
     #[serde(skip_serializing_if = "Option::is_none")]
     pub base: Option<String>,
 
+    /// Remote the stack pushes to and rebases from (default: `origin`)
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub remote: Option<String>,
+
+    /// Tag, commit or branch the stack starts from instead of its base
+    /// (`gg co <name> --from <ref>`); `gg rebase` replays it onto this ref
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub from: Option<String>,
+
     /// Mapping from entry-id to MR number
     #[serde(default, skip_serializing_if = "HashMap::is_empty")]
     pub mrs: HashMap<String, u64>,
@@ -157,6 +633,26 @@ pub struct StackConfig {
     /// Absolute path to a linked worktree for this stack
     #[serde(skip_serializing_if = "Option::is_none")]
     pub worktree_path: Option<String>,
+
+    /// Path globs this stack is expected to stay within (monorepos)
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub scope: Vec<String>,
+
+    /// Stack this one is stacked on (`gg co <name> --base <stack>`)
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub parent: Option<String>,
+
+    /// What the stack is for (`gg describe`), also kept in git notes
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub description: Option<String>,
+
+    /// Reviewers for new PRs/MRs of this stack (replaces `defaults.reviewers`)
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub reviewers: Vec<String>,
+
+    /// When a gg command last changed this stack (ms since the Unix epoch)
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub last_touched_ms: Option<u64>,
 }
 
 /// Root configuration structure
@@ -168,38 +664,310 @@ pub struct Config {
 
     /// Template for stack worktree path.
     /// Variables: {repo} and {stack}
+    ///
+    /// Superseded by `defaults.worktree_base_path`, which wins when both
+    /// are set.
     #[serde(skip_serializing_if = "Option::is_none")]
     pub worktree_base_path: Option<String>,
 
     /// Per-stack configurations
     #[serde(default, skip_serializing_if = "HashMap::is_empty")]
     pub stacks: HashMap<String, StackConfig>,
+
+    /// Lifecycle hook commands (see [`crate::hooks`])
+    #[serde(default, skip_serializing_if = "HooksConfig::is_default")]
+    pub hooks: HooksConfig,
 }
 
-impl Config {
-    /// Load config from the given git directory
-    /// Uses file locking to prevent race conditions with concurrent operations
-    pub fn load(git_dir: &Path) -> Result<Self> {
-        let config_path = Self::config_path(git_dir);
+/// Shell commands run at lifecycle points. Each one replaces the matching
+/// `.git/gg/hooks/` script.
+#[derive(Debug, Clone, Serialize, Deserialize, Default, PartialEq, Eq)]
+pub struct HooksConfig {
+    /// Before `gg sync` pushes; a non-zero exit aborts the sync
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub pre_sync: Option<String>,
+
+    /// Before `gg land` merges; a non-zero exit aborts the land
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub pre_land: Option<String>,
+
+    /// After `gg land` merged at least one PR/MR
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub post_land: Option<String>,
+}
+
+impl HooksConfig {
+    fn is_default(&self) -> bool {
+        self == &Self::default()
+    }
+}
+
+/// Where an effective config value comes from.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ConfigOrigin {
+    /// Built-in default
+    Default,
+    /// The global config
+    Global,
+    /// `.gg/config.json`, committed to the repository
+    Team,
+    /// `.git/gg/config.json`
+    Repo,
+}
 
-        if !config_path.exists() {
-            return Ok(Config::default());
+impl ConfigOrigin {
+    pub fn as_str(self) -> &'static str {
+        match self {
+            ConfigOrigin::Default => "default",
+            ConfigOrigin::Global => "global",
+            ConfigOrigin::Team => "team",
+            ConfigOrigin::Repo => "repo",
         }
+    }
+}
 
-        // Acquire shared lock for reading (multiple readers allowed)
-        let lock = Self::acquire_lock(git_dir, /*exclusive=*/ false)?;
+/// Keys that make gg run a shell command. Anyone who can get a commit in
+/// sets the team config, so it may not set these; they only count from the
+/// global and repo configs.
+const TEAM_IGNORED_KEYS: &[&[&str]] = &[
+    &["hooks"],
+    &["defaults", "notify_command"],
+    &["defaults", "issue_tracker", "command"],
+];
+
+/// Whether the team config may not set `path` (e.g. `["hooks", "pre_sync"]`).
+pub fn ignored_in_team_config(path: &[String]) -> bool {
+    TEAM_IGNORED_KEYS
+        .iter()
+        .any(|key| path.len() >= key.len() && key.iter().zip(path).all(|(k, p)| k == p))
+}
 
-        let contents = fs::read_to_string(&config_path)?;
-        let config: Config = serde_json::from_str(&contents)?;
+/// The config files behind the effective config, as raw JSON, so each key
+/// can be resolved (and traced) layer by layer.
+#[derive(Debug, Clone, Default)]
+pub struct ConfigLayers {
+    /// The global config, without stacks (those are always per repo)
+    pub global: Option<Value>,
+    /// `.gg/config.json` in the working tree, without stacks
+    pub team: Option<Value>,
+    /// `.git/gg/config.json`
+    pub repo: Option<Value>,
+}
 
-        // Lock automatically released when dropped
-        drop(lock);
-        Ok(config)
+impl ConfigLayers {
+    /// Read the global config and, given a git directory, the team and
+    /// repo configs.
+    pub fn read(git_dir: Option<&Path>) -> Result<Self> {
+        let global = read_shared_layer(Config::global_config_path())?;
+        let team = read_shared_layer(git_dir.and_then(Config::team_config_path))?;
+        let repo = match git_dir {
+            Some(git_dir) => read_repo_layer(git_dir)?,
+            None => None,
+        };
+        Ok(ConfigLayers { global, team, repo })
+    }
+
+    /// Every layer merged, key by key.
+    pub fn effective(&self) -> Result<Config> {
+        let team = self.team_settings();
+        let mut merged = Value::Object(Map::new());
+        for layer in [&self.global, &team, &self.repo].into_iter().flatten() {
+            merge_value(&mut merged, layer);
+        }
+        Ok(serde_json::from_value(merged)?)
+    }
+
+    /// The team config without the keys it may not set.
+    fn team_settings(&self) -> Option<Value> {
+        let mut team = self.team.clone()?;
+        for key in TEAM_IGNORED_KEYS {
+            let (last, parents) = key.split_last().expect("keys are never empty");
+            if let Some(Value::Object(map)) =
+                parents.iter().try_fold(&mut team, |v, k| v.get_mut(*k))
+            {
+                map.remove(*last);
+            }
+        }
+        Some(team)
+    }
+
+    /// The command keys the team config sets, which gg ignores, as dotted
+    /// paths.
+    pub fn ignored_team_keys(&self) -> Vec<String> {
+        let Some(team) = &self.team else {
+            return Vec::new();
+        };
+        let mut keys = Vec::new();
+        for key in TEAM_IGNORED_KEYS {
+            match key.iter().try_fold(team, |v, k| v.get(*k)) {
+                Some(Value::Object(map)) => keys.extend(
+                    map.iter()
+                        .filter(|(_, v)| !v.is_null())
+                        .map(|(k, _)| format!("{}.{}", key.join("."), k)),
+                ),
+                Some(Value::Null) | None => {}
+                Some(_) => keys.push(key.join(".")),
+            }
+        }
+        keys
+    }
+
+    /// What the repo config is layered on: the team config over the global
+    /// config over the built-in defaults.
+    fn below_repo(&self) -> Result<Value> {
+        let below = ConfigLayers {
+            repo: None,
+            ..self.clone()
+        };
+        Ok(serde_json::to_value(below.effective()?)?)
+    }
+
+    /// Which layer the effective value at `path` (e.g. `["defaults",
+    /// "sync_jobs"]`) comes from.
+    pub fn origin(&self, path: &[String]) -> ConfigOrigin {
+        let has = |layer: &Option<Value>| {
+            layer
+                .as_ref()
+                .and_then(|v| path.iter().try_fold(v, |v, key| v.get(key)))
+                .is_some_and(|v| !v.is_null())
+        };
+        if has(&self.repo) {
+            ConfigOrigin::Repo
+        } else if has(&self.team_settings()) {
+            ConfigOrigin::Team
+        } else if has(&self.global) {
+            ConfigOrigin::Global
+        } else {
+            ConfigOrigin::Default
+        }
+    }
+}
+
+/// Read `.git/gg/config.json`, if it exists.
+fn read_repo_layer(git_dir: &Path) -> Result<Option<Value>> {
+    let path = Config::config_path(git_dir);
+    if !path.exists() {
+        return Ok(None);
+    }
+    // Shared lock: concurrent readers are fine, writers wait
+    let lock = Config::acquire_lock(git_dir, /*exclusive=*/ false)?;
+    let contents = fs::read_to_string(&path)?;
+    drop(lock);
+    Ok(Some(serde_json::from_str(&contents)?))
+}
+
+/// Read a config layer shared beyond this clone, if it exists. Stacks are
+/// dropped: they only ever live in `.git/gg/config.json`.
+fn read_shared_layer(path: Option<PathBuf>) -> Result<Option<Value>> {
+    let Some(path) = path.filter(|p| p.exists()) else {
+        return Ok(None);
+    };
+    let mut value: Value = serde_json::from_str(&fs::read_to_string(&path)?)?;
+    if let Value::Object(map) = &mut value {
+        map.remove("stacks");
+    }
+    Ok(Some(value))
+}
+
+/// Lay `upper` over `base`: objects merge key by key, anything else in
+/// `upper` replaces what `base` has. A `null` in `upper` is an unset key
+/// (older repo configs are full of them) and leaves `base` alone.
+fn merge_value(base: &mut Value, upper: &Value) {
+    match (base, upper) {
+        (_, Value::Null) => {}
+        (Value::Object(base), Value::Object(upper)) => {
+            for (key, value) in upper.iter().filter(|(_, v)| !v.is_null()) {
+                match base.get_mut(key) {
+                    Some(existing) => merge_value(existing, value),
+                    None => {
+                        base.insert(key.clone(), value.clone());
+                    }
+                }
+            }
+        }
+        (base, upper) => *base = upper.clone(),
+    }
+}
+
+/// `value` as the repo config, given what the repo config has now (`kept`):
+/// the keys it sets take their new values, even when those match `below`,
+/// and the other keys are only added where they differ from `below`.
+fn repo_layer(value: &Value, kept: Option<&Value>, below: &Value) -> Option<Value> {
+    match (value, kept) {
+        (Value::Object(value), Some(Value::Object(kept))) => {
+            let layer: Map<String, Value> = value
+                .iter()
+                .filter_map(|(key, v)| {
+                    let b = below.get(key).unwrap_or(&Value::Null);
+                    repo_layer(v, kept.get(key), b).map(|layer| (key.clone(), layer))
+                })
+                .collect();
+            (!layer.is_empty()).then_some(Value::Object(layer))
+        }
+        (value, Some(kept)) if !kept.is_null() => Some(value.clone()),
+        _ => diff_value(value, below),
+    }
+}
+
+/// The parts of `value` that differ from `base`; `None` when nothing does.
+fn diff_value(value: &Value, base: &Value) -> Option<Value> {
+    match (value, base) {
+        (Value::Object(value), Value::Object(base)) => {
+            let changed: Map<String, Value> = value
+                .iter()
+                .filter_map(|(key, v)| match base.get(key) {
+                    Some(b) => diff_value(v, b).map(|d| (key.clone(), d)),
+                    None if v.is_null() => None,
+                    None => Some((key.clone(), v.clone())),
+                })
+                .collect();
+            (!changed.is_empty()).then_some(Value::Object(changed))
+        }
+        _ if value == base => None,
+        _ => Some(value.clone()),
     }
+}
 
-    /// Save config to the given git directory
-    /// Uses file locking and atomic write to prevent corruption
+impl Config {
+    /// Load the effective config for the given git directory: the repo
+    /// config on top of the team config, on top of the global one, key by
+    /// key, on top of the built-in defaults. Uses file locking to prevent race conditions with
+    /// concurrent operations.
+    pub fn load(git_dir: &Path) -> Result<Self> {
+        ConfigLayers::read(Some(git_dir))?.effective()
+    }
+
+    /// Save config to the given git directory.
+    ///
+    /// Keys the repo config already sets stay in it, so a value pinned to
+    /// this repo doesn't start following the global config just because
+    /// they match today. Of the others, only stacks and the values that
+    /// differ from the team and global configs (or the built-in defaults)
+    /// are written. Uses file locking and atomic write to prevent
+    /// corruption.
     pub fn save(&self, git_dir: &Path) -> Result<()> {
+        let kept = read_repo_layer(git_dir)?;
+        // The shared layers only decide which new keys are worth writing:
+        // one that can't be read counts as absent rather than failing the save
+        let below = ConfigLayers {
+            global: read_shared_layer(Self::global_config_path()).unwrap_or_default(),
+            team: read_shared_layer(Self::team_config_path(git_dir)).unwrap_or_default(),
+            repo: None,
+        }
+        .below_repo()
+        .or_else(|_| ConfigLayers::default().below_repo())?;
+        let mut local = repo_layer(&serde_json::to_value(self)?, kept.as_ref(), &below)
+            .unwrap_or(Value::Object(Map::new()));
+        // Stacks only exist in the repo config
+        if let (Value::Object(map), false) = (&mut local, self.stacks.is_empty()) {
+            map.insert("stacks".to_string(), serde_json::to_value(&self.stacks)?);
+        }
+        Self::write_repo_json(git_dir, &local)
+    }
+
+    /// Write `value` as the repo config as it is, with the same locking and
+    /// atomic rename as [`Config::save`].
+    pub(crate) fn write_repo_json(git_dir: &Path, value: &Value) -> Result<()> {
         let config_path = Self::config_path(git_dir);
 
         // Ensure the gg directory exists
@@ -212,7 +980,7 @@ impl Config {
 
         // Atomic write: write to temp file, then rename
         let temp_path = config_path.with_extension("tmp");
-        let contents = serde_json::to_string_pretty(self)?;
+        let contents = serde_json::to_string_pretty(value)?;
         fs::write(&temp_path, contents)?;
 
         // Atomic rename (overwrites existing file)
@@ -223,6 +991,37 @@ impl Config {
         Ok(())
     }
 
+    /// Parse config JSON, rejecting anything outside the schema.
+    ///
+    /// Unlike a plain deserialize, unknown keys (usually typos) are errors
+    /// too. Every problem found is returned, each with its location:
+    /// `line N column M` for syntax and type errors, the key path otherwise.
+    pub fn parse_strict(text: &str) -> std::result::Result<Config, Vec<String>> {
+        let mut unknown = Vec::new();
+        let mut de = serde_json::Deserializer::from_str(text);
+        let config: Config = serde_ignored::deserialize(&mut de, |path| {
+            unknown.push(format!("unknown key `{}`", path))
+        })
+        .and_then(|config| de.end().map(|()| config))
+        .map_err(|e| vec![e.to_string()])?;
+
+        let mut errors = unknown;
+        if let Some(provider) = &config.defaults.provider {
+            if let Err(e) = crate::provider::Provider::from_name(provider) {
+                errors.push(format!("defaults.provider: {}", e));
+            }
+        }
+        if let Err(e) = crate::branch_naming::BranchNaming::from_config(&config) {
+            errors.push(e);
+        }
+
+        if errors.is_empty() {
+            Ok(config)
+        } else {
+            Err(errors)
+        }
+    }
+
     /// Acquire a file lock on the config file
     /// Returns a File handle that holds the lock until dropped
     fn acquire_lock(git_dir: &Path, exclusive: bool) -> Result<File> {
@@ -275,6 +1074,14 @@ impl Config {
         git_dir.join("gg").join("config.json")
     }
 
+    /// Path of the team config, `.gg/config.json` in the main working tree
+    /// of the repository whose common git directory is `git_dir`. `None`
+    /// for bare repositories.
+    pub fn team_config_path(git_dir: &Path) -> Option<PathBuf> {
+        let workdir = git_dir.parent().filter(|_| git_dir.ends_with(".git"))?;
+        Some(workdir.join(".gg").join("config.json"))
+    }
+
     /// Get or create stack config
     pub fn get_or_create_stack(&mut self, stack_name: &str) -> &mut StackConfig {
         self.stacks.entry(stack_name.to_string()).or_default()
@@ -298,6 +1105,67 @@ impl Config {
             .or(self.defaults.base.as_deref())
     }
 
+    /// Get the remote of a stack: its own, or the one of the stack it is
+    /// stacked on, or `origin`
+    pub fn get_remote_for_stack(&self, stack_name: &str) -> &str {
+        let mut name = stack_name;
+        for _ in 0..=self.stacks.len() {
+            let Some(stack) = self.stacks.get(name) else {
+                break;
+            };
+            if let Some(remote) = stack.remote.as_deref() {
+                return remote;
+            }
+            match stack.parent.as_deref() {
+                Some(parent) => name = parent,
+                None => break,
+            }
+        }
+        crate::git::DEFAULT_REMOTE
+    }
+
+    /// Get the ref a stack was started from with `gg co --from`, if any
+    pub fn get_from_for_stack(&self, stack_name: &str) -> Option<&str> {
+        self.stacks.get(stack_name).and_then(|s| s.from.as_deref())
+    }
+
+    /// Get the parent stack of a dependent stack
+    pub fn get_parent_for_stack(&self, stack_name: &str) -> Option<&str> {
+        self.stacks
+            .get(stack_name)
+            .and_then(|s| s.parent.as_deref())
+    }
+
+    /// Get the stacks stacked directly on top of `stack_name`
+    pub fn dependent_stacks(&self, stack_name: &str) -> Vec<String> {
+        let mut children: Vec<String> = self
+            .stacks
+            .iter()
+            .filter(|(_, s)| s.parent.as_deref() == Some(stack_name))
+            .map(|(name, _)| name.clone())
+            .collect();
+        children.sort();
+        children
+    }
+
+    /// Get the path scope for a stack (empty = unrestricted)
+    pub fn get_scope_for_stack(&self, stack_name: &str) -> &[String] {
+        self.stacks
+            .get(stack_name)
+            .map(|s| s.scope.as_slice())
+            .unwrap_or(&[])
+    }
+
+    /// Reviewers for new PRs/MRs of a stack: its own list when it has one,
+    /// `defaults.reviewers` otherwise
+    pub fn get_reviewers_for_stack(&self, stack_name: &str) -> &[String] {
+        self.stacks
+            .get(stack_name)
+            .map(|s| s.reviewers.as_slice())
+            .filter(|reviewers| !reviewers.is_empty())
+            .unwrap_or(&self.defaults.reviewers)
+    }
+
     /// Get the MR number for an entry ID in a stack
     pub fn get_mr_for_entry(&self, stack_name: &str, entry_id: &str) -> Option<u64> {
         self.stacks
@@ -343,11 +1211,40 @@ impl Config {
         self.defaults.land_admin
     }
 
+    /// Get the default merge strategy for `gg land` (default: squash)
+    pub fn get_merge_strategy(&self) -> MergeStrategy {
+        self.defaults.merge_strategy
+    }
+
+    /// Get whether `gg land` deletes landed entries' remote branches (default: false)
+    pub fn get_delete_branch_on_land(&self) -> bool {
+        self.defaults.delete_branch_on_land
+    }
+
+    /// Get the completion notification command or webhook URL, if any
+    pub fn get_notify_command(&self) -> Option<&str> {
+        self.defaults
+            .notify_command
+            .as_deref()
+            .map(str::trim)
+            .filter(|s| !s.is_empty())
+    }
+
     /// Get whether GitLab auto-merge-on-land is enabled by default (default: false)
     pub fn get_gitlab_auto_merge_on_land(&self) -> bool {
         self.defaults.gitlab.auto_merge_on_land
     }
 
+    /// Get whether sync sets GitLab MR dependencies (default: true)
+    pub fn get_gitlab_sync_mr_dependencies(&self) -> bool {
+        self.defaults.gitlab.sync_mr_dependencies
+    }
+
+    /// Get whether GitHub auto-merge-on-land is enabled by default (default: false)
+    pub fn get_github_auto_merge_on_land(&self) -> bool {
+        self.defaults.github.auto_merge_on_land
+    }
+
     /// Get whether to auto-lint before sync (default: false)
     pub fn get_sync_auto_lint(&self) -> bool {
         self.defaults.sync_auto_lint
@@ -363,6 +1260,11 @@ impl Config {
         self.defaults.sync_behind_threshold
     }
 
+    /// Get what `gg clean` does with merged stack branches (default: delete)
+    pub fn get_clean_mode(&self) -> CleanMode {
+        self.defaults.clean_mode
+    }
+
     /// Get the default action for `gg amend` when unstaged changes are present.
     pub fn get_unstaged_action(&self) -> UnstagedAction {
         self.defaults.unstaged_action
@@ -378,6 +1280,16 @@ impl Config {
         self.defaults.sync_update_descriptions
     }
 
+    /// Get how many commits `gg lint` checks at once (default: 1, 0 = auto)
+    pub fn get_lint_jobs(&self) -> usize {
+        self.defaults.lint_jobs
+    }
+
+    /// Get how many pushes/lookups sync runs at once (default: 4, minimum 1)
+    pub fn get_sync_jobs(&self) -> usize {
+        self.defaults.sync_jobs.max(1)
+    }
+
     /// Get whether to update PR/MR titles on re-sync (default: false)
     pub fn get_sync_update_title(&self) -> bool {
         self.defaults.sync_update_title
@@ -388,89 +1300,74 @@ impl Config {
         self.defaults.stack_nav_comments
     }
 
+    /// How to embed the stack graph in PR/MR descriptions (default: off).
+    pub fn get_stack_graph(&self) -> StackGraphMode {
+        self.defaults.stack_graph
+    }
+
+    /// How to reach GitHub/GitLab (default: through their CLIs).
+    pub fn get_api_mode(&self) -> ApiMode {
+        self.defaults.api_mode
+    }
+
     // ============ Global config loading ============
 
-    /// Get the global config directory path (~/.config/gg)
-    /// Uses home directory to ensure consistent cross-platform behavior
+    /// Get the global config directory: `$XDG_CONFIG_HOME/gg` when set,
+    /// `~/.config/gg` otherwise (on every platform, for consistency)
     pub fn global_config_dir() -> Option<PathBuf> {
-        dirs::home_dir().map(|h| h.join(".config").join("gg"))
+        std::env::var_os("XDG_CONFIG_HOME")
+            .map(PathBuf::from)
+            .filter(|dir| dir.is_absolute())
+            .or_else(|| dirs::home_dir().map(|h| h.join(".config")))
+            .map(|dir| dir.join("gg"))
     }
 
-    /// Get the global config file path (~/.config/gg/config.json)
+    /// Get the global config file path (`<global config dir>/config.json`)
     pub fn global_config_path() -> Option<PathBuf> {
         Self::global_config_dir().map(|d| d.join("config.json"))
     }
 
-    /// Load global config from ~/.config/gg/config.json
+    /// Load the global config on its own.
     /// Returns None if the file doesn't exist
     pub fn load_global() -> Result<Option<Config>> {
-        let Some(path) = Self::global_config_path() else {
-            return Ok(None);
-        };
-        if !path.exists() {
-            return Ok(None);
-        }
-        let contents = fs::read_to_string(&path)?;
-        let config: Config = serde_json::from_str(&contents)?;
-        Ok(Some(config))
-    }
-
-    /// Load config with global defaults applied first, then repo-local on top.
-    /// Resolution: hardcoded defaults → global config → repo-local config
-    pub fn load_with_global(git_dir: &Path) -> Result<Self> {
-        // Start with global (or default if no global exists)
-        let mut config: Config = Self::load_global()?.unwrap_or_default();
-
-        let local_path = Self::config_path(git_dir);
-        if local_path.exists() {
-            let lock = Self::acquire_lock(git_dir, /*exclusive=*/ false)?;
-            let contents = fs::read_to_string(&local_path)?;
-            let local: Config = serde_json::from_str(&contents)?;
-            drop(lock);
-
-            // Local overrides global
-            config.merge_local(local);
-        }
-
-        Ok(config)
-    }
-
-    /// Merge a local config on top of self (global).
-    /// Local stacks always replace. Local defaults override global defaults.
-    /// worktree_base_path from local overrides global if set.
-    fn merge_local(&mut self, local: Config) {
-        // Stacks are always local
-        self.stacks = local.stacks;
-
-        // worktree_base_path: local wins if present
-        if local.worktree_base_path.is_some() {
-            self.worktree_base_path = local.worktree_base_path;
+        match ConfigLayers::read(None)?.global {
+            Some(global) => Ok(Some(serde_json::from_value(global)?)),
+            None => Ok(None),
         }
-
-        // Defaults: local wins entirely (since we serialize all fields,
-        // the local JSON will have explicit values for every field)
-        self.defaults = local.defaults;
     }
 
     /// Render the target worktree path for a stack.
     ///
-    /// Default template: ../{repo}.{stack}
+    /// `defaults.worktree_base_path` (or the older top-level
+    /// `worktree_base_path`) is a template when it mentions `{stack}` and a
+    /// directory for `{repo}.{stack}` otherwise. Default: ../{repo}.{stack}
     pub fn render_worktree_path(&self, repo_root: &Path, stack_name: &str) -> PathBuf {
         let repo_name = repo_root
             .file_name()
             .and_then(|s| s.to_str())
             .unwrap_or("repo");
 
-        let template = self
+        let configured = self
+            .defaults
             .worktree_base_path
             .as_deref()
-            .unwrap_or("../{repo}.{stack}");
+            .or(self.worktree_base_path.as_deref())
+            .map(str::trim)
+            .filter(|s| !s.is_empty());
+        let template = match configured {
+            Some(template) if template.contains("{stack}") => template.to_string(),
+            Some(dir) => format!("{}/{{repo}}.{{stack}}", dir.trim_end_matches('/')),
+            None => "../{repo}.{stack}".to_string(),
+        };
 
         let rendered = template
             .replace("{repo}", repo_name)
             .replace("{stack}", stack_name);
 
-        let path = PathBuf::from(rendered);
+        let path = match (rendered.strip_prefix("~/"), std::env::var_os("HOME")) {
+            (Some(rest), Some(home)) => PathBuf::from(home).join(rest),
+            _ => PathBuf::from(rendered),
+        };
         if path.is_absolute() {
             path
         } else {
@@ -484,6 +1381,30 @@ mod tests {
     use super::*;
     use tempfile::TempDir;
 
+    #[test]
+    fn test_parse_strict_reports_unknown_keys_and_bad_values() {
+        let config = Config::parse_strict(r#"{"defaults":{"sync_draft":true}}"#).unwrap();
+        assert!(config.defaults.sync_draft);
+
+        let errors = Config::parse_strict(
+            r#"{"defaults":{"provider":"bitbucket","gitlab":{"auto_merge":true}},"stacks":{"a":{"scopes":[]}}}"#,
+        )
+        .unwrap_err();
+        assert!(errors.contains(&"unknown key `defaults.gitlab.auto_merge`".to_string()));
+        assert!(errors.contains(&"unknown key `stacks.a.scopes`".to_string()));
+        assert!(errors.iter().any(|e| e.starts_with("defaults.provider:")));
+
+        let errors = Config::parse_strict("{\n  \"defaults\": {\"unstaged_action\": \"maybe\"}\n}")
+            .unwrap_err();
+        assert_eq!(errors.len(), 1);
+        assert!(
+            errors[0].contains("unknown variant `maybe`"),
+            "{:?}",
+            errors
+        );
+        assert!(errors[0].contains("line 2"), "{:?}", errors);
+    }
+
     #[test]
     fn test_config_roundtrip() {
         let temp_dir = TempDir::new().unwrap();
@@ -492,7 +1413,7 @@ mod tests {
         let mut config = Config::default();
         config.defaults.base = Some("main".to_string());
         config.defaults.branch_username = Some("nacho".to_string());
-        config.defaults.lint = vec!["cargo fmt".to_string(), "cargo clippy".to_string()];
+        config.defaults.lint = vec!["cargo fmt".into(), "cargo clippy".into()];
 
         let stack = config.get_or_create_stack("my-feature");
         stack.mrs.insert("c-abc123".to_string(), 1234);
@@ -508,6 +1429,36 @@ mod tests {
         );
     }
 
+    #[test]
+    fn test_dependent_stacks() {
+        let mut config = Config::default();
+        config.get_or_create_stack("base-stack");
+        config.get_or_create_stack("b-child").parent = Some("base-stack".to_string());
+        config.get_or_create_stack("a-child").parent = Some("base-stack".to_string());
+        config.get_or_create_stack("grandchild").parent = Some("a-child".to_string());
+
+        assert_eq!(config.get_parent_for_stack("grandchild"), Some("a-child"));
+        assert_eq!(config.get_parent_for_stack("base-stack"), None);
+        assert_eq!(
+            config.dependent_stacks("base-stack"),
+            vec!["a-child".to_string(), "b-child".to_string()]
+        );
+        assert!(config.dependent_stacks("grandchild").is_empty());
+    }
+
+    #[test]
+    fn test_remote_for_stack_follows_parents() {
+        let mut config = Config::default();
+        config.get_or_create_stack("release").remote = Some("upstream".to_string());
+        config.get_or_create_stack("fix").parent = Some("release".to_string());
+        config.get_or_create_stack("other");
+
+        assert_eq!(config.get_remote_for_stack("release"), "upstream");
+        assert_eq!(config.get_remote_for_stack("fix"), "upstream");
+        assert_eq!(config.get_remote_for_stack("other"), "origin");
+        assert_eq!(config.get_remote_for_stack("unknown"), "origin");
+    }
+
     #[test]
     fn test_missing_config_returns_default() {
         let temp_dir = TempDir::new().unwrap();
@@ -571,6 +1522,25 @@ mod tests {
         assert!(!config.get_land_admin());
     }
 
+    #[test]
+    fn test_merge_strategy_default_and_parse() {
+        assert_eq!(
+            Config::default().get_merge_strategy(),
+            MergeStrategy::Squash
+        );
+        let parsed: Config =
+            serde_json::from_str(r#"{"defaults": {"merge_strategy": "rebase"}}"#).unwrap();
+        assert_eq!(parsed.get_merge_strategy(), MergeStrategy::Rebase);
+    }
+
+    #[test]
+    fn test_delete_branch_on_land_default() {
+        assert!(!Config::default().get_delete_branch_on_land());
+        let parsed: Config =
+            serde_json::from_str(r#"{"defaults": {"delete_branch_on_land": true}}"#).unwrap();
+        assert!(parsed.get_delete_branch_on_land());
+    }
+
     #[test]
     fn test_land_admin_enabled() {
         let mut config = Config::default();
@@ -635,19 +1605,20 @@ mod tests {
     }
 
     #[test]
-    fn test_provider_config_serialized_as_null_when_none() {
+    fn test_unset_provider_not_written_to_repo_config() {
         let temp_dir = TempDir::new().unwrap();
         let git_dir = temp_dir.path();
 
         let config = Config::default();
         config.save(git_dir).unwrap();
 
-        // All Defaults fields are always serialized
+        // Values matching the layers below stay out of the repo config
         let contents = std::fs::read_to_string(Config::config_path(git_dir)).unwrap();
         assert!(
-            contents.contains("provider"),
-            "provider should always be serialized"
+            !contents.contains("provider"),
+            "unset provider should not be written"
         );
+        assert_eq!(Config::load(git_dir).unwrap().defaults.provider, None);
     }
 
     #[test]
@@ -656,6 +1627,17 @@ mod tests {
         assert!(!config.get_gitlab_auto_merge_on_land());
     }
 
+    #[test]
+    fn test_gitlab_sync_mr_dependencies_defaults_to_true() {
+        let config = Config::default();
+        assert!(config.get_gitlab_sync_mr_dependencies());
+
+        let parsed: Config =
+            serde_json::from_str(r#"{"defaults": {"gitlab": {"auto_merge_on_land": true}}}"#)
+                .unwrap();
+        assert!(parsed.get_gitlab_sync_mr_dependencies());
+    }
+
     #[test]
     fn test_gitlab_auto_merge_on_land_roundtrip() {
         let temp_dir = TempDir::new().unwrap();
@@ -670,18 +1652,33 @@ mod tests {
     }
 
     #[test]
-    fn test_gitlab_defaults_always_serialized() {
+    fn test_github_auto_merge_on_land_roundtrip() {
         let temp_dir = TempDir::new().unwrap();
         let git_dir = temp_dir.path();
 
-        let config = Config::default();
+        let mut config = Config::default();
+        assert!(!config.get_github_auto_merge_on_land());
+        config.defaults.github.auto_merge_on_land = true;
         config.save(git_dir).unwrap();
 
-        let contents = std::fs::read_to_string(Config::config_path(git_dir)).unwrap();
-        assert!(
-            contents.contains("gitlab"),
-            "gitlab defaults should always be serialized"
-        );
+        let loaded = Config::load(git_dir).unwrap();
+        assert!(loaded.get_github_auto_merge_on_land());
+    }
+
+    #[test]
+    fn test_stack_graph_mode_parsing() {
+        assert_eq!(Config::default().get_stack_graph(), StackGraphMode::Off);
+        let parsed: Config =
+            serde_json::from_str(r#"{"defaults": {"stack_graph": "mermaid"}}"#).unwrap();
+        assert_eq!(parsed.get_stack_graph(), StackGraphMode::Mermaid);
+    }
+
+    #[test]
+    fn test_api_mode_parsing() {
+        assert_eq!(Config::default().get_api_mode(), ApiMode::Cli);
+        let parsed: Config =
+            serde_json::from_str(r#"{"defaults": {"api_mode": "native"}}"#).unwrap();
+        assert_eq!(parsed.get_api_mode(), ApiMode::Native);
     }
 
     #[test]
@@ -763,6 +1760,31 @@ mod tests {
         assert_eq!(path, Path::new("/tmp/wt/my-repo-feature-a"));
     }
 
+    #[test]
+    fn test_render_worktree_path_defaults_setting_wins_and_accepts_a_directory() {
+        let mut config = Config {
+            worktree_base_path: Some("/tmp/wt/{repo}-{stack}".to_string()),
+            ..Config::default()
+        };
+        config.defaults.worktree_base_path = Some("/tmp/gg-worktrees/".to_string());
+        let repo_root = Path::new("/workspace/my-repo");
+        let path = config.render_worktree_path(repo_root, "feature-a");
+        assert_eq!(path, Path::new("/tmp/gg-worktrees/my-repo.feature-a"));
+
+        config.defaults.worktree_base_path = Some("worktrees/{stack}".to_string());
+        let path = config.render_worktree_path(repo_root, "feature-a");
+        assert_eq!(path, Path::new("/workspace/my-repo/worktrees/feature-a"));
+    }
+
+    #[test]
+    fn test_clean_mode_defaults_to_delete_and_accepts_archive() {
+        let config: Config = serde_json::from_str(r#"{"defaults":{}}"#).unwrap();
+        assert_eq!(config.get_clean_mode(), CleanMode::Delete);
+        let config: Config =
+            serde_json::from_str(r#"{"defaults":{"clean_mode":"archive"}}"#).unwrap();
+        assert_eq!(config.get_clean_mode(), CleanMode::Archive);
+    }
+
     #[test]
     fn test_unstaged_action_deserializes_to_default_when_missing() {
         let config: Config = serde_json::from_str(r#"{"defaults":{"base":"main"}}"#).unwrap();
@@ -783,6 +1805,16 @@ mod tests {
         assert_eq!(config.get_unstaged_action(), UnstagedAction::Add);
     }
 
+    #[test]
+    fn test_run_hooks_defaults_to_always_and_accepts_kebab_case() {
+        assert_eq!(Config::default().defaults.run_hooks, RunHooks::Always);
+        let config: Config =
+            serde_json::from_str(r#"{"defaults":{"run_hooks":"rewrite-only"}}"#).unwrap();
+        assert_eq!(config.defaults.run_hooks, RunHooks::RewriteOnly);
+        let config: Config = serde_json::from_str(r#"{"defaults":{"run_hooks":"never"}}"#).unwrap();
+        assert_eq!(config.defaults.run_hooks, RunHooks::Never);
+    }
+
     // ============ Tests for sync_draft and sync_update_descriptions ============
 
     #[test]
@@ -839,6 +1871,12 @@ mod tests {
         assert!(!loaded.get_sync_update_descriptions());
     }
 
+    #[test]
+    fn test_create_as_draft_is_an_alias_of_sync_draft() {
+        let config = Config::parse_strict(r#"{"defaults":{"create_as_draft":true}}"#).unwrap();
+        assert!(config.get_sync_draft());
+    }
+
     #[test]
     fn test_sync_draft_deserializes_to_default_when_missing() {
         let config: Config = serde_json::from_str(r#"{"defaults":{"base":"main"}}"#).unwrap();
@@ -886,6 +1924,65 @@ mod tests {
         assert!(!config.get_sync_update_title());
     }
 
+    // ============ Tests for reviewers ============
+
+    #[test]
+    fn test_reviewers_stack_list_replaces_defaults() {
+        let mut config = Config::default();
+        assert!(config.get_reviewers_for_stack("feature").is_empty());
+
+        config.defaults.reviewers = vec!["alice".to_string()];
+        assert_eq!(config.get_reviewers_for_stack("feature"), ["alice"]);
+
+        config.get_or_create_stack("feature").reviewers = vec!["acme/core".to_string()];
+        assert_eq!(config.get_reviewers_for_stack("feature"), ["acme/core"]);
+        assert_eq!(config.get_reviewers_for_stack("other"), ["alice"]);
+    }
+
+    // ============ Tests for sync_jobs ============
+
+    #[test]
+    fn test_sync_jobs_defaults_to_four_and_is_at_least_one() {
+        let config: Config = serde_json::from_str(r#"{"defaults":{"base":"main"}}"#).unwrap();
+        assert_eq!(config.get_sync_jobs(), 4);
+
+        let config: Config = serde_json::from_str(r#"{"defaults":{"sync_jobs":0}}"#).unwrap();
+        assert_eq!(config.get_sync_jobs(), 1);
+    }
+
+    #[test]
+    fn test_lint_accepts_plain_and_scoped_commands() {
+        let config: Config = serde_json::from_str(
+            r#"{"defaults":{"lint":["cargo fmt",{"cmd":"ktfmt","paths":["**/*.kt"]}]}}"#,
+        )
+        .unwrap();
+        assert_eq!(
+            config.defaults.lint,
+            vec![
+                LintCommand::Plain("cargo fmt".to_string()),
+                LintCommand::Scoped {
+                    cmd: "ktfmt".to_string(),
+                    paths: vec!["**/*.kt".to_string()],
+                },
+            ]
+        );
+        assert!(config.defaults.lint[0].paths().is_empty());
+        assert_eq!(config.defaults.lint[1].cmd(), "ktfmt");
+
+        // Plain commands keep serializing as strings
+        let json = serde_json::to_string(&config.defaults.lint).unwrap();
+        assert_eq!(json, r#"["cargo fmt",{"cmd":"ktfmt","paths":["**/*.kt"]}]"#);
+    }
+
+    #[test]
+    fn test_lint_jobs_defaults_to_sequential() {
+        let config: Config = serde_json::from_str(r#"{"defaults":{"base":"main"}}"#).unwrap();
+        assert_eq!(config.get_lint_jobs(), 1);
+
+        let config: Config = serde_json::from_str(r#"{"defaults":{"lint_jobs":0}}"#).unwrap();
+        assert_eq!(config.get_lint_jobs(), 0);
+    }
+
     // ============ Tests for stack_nav_comments ============
 
     #[test]
@@ -936,7 +2033,7 @@ mod tests {
     }
 
     #[test]
-    fn test_load_with_global_uses_local_when_present() {
+    fn test_load_uses_local_when_present() {
         let temp_dir = TempDir::new().unwrap();
         let git_dir = temp_dir.path();
 
@@ -946,14 +2043,212 @@ mod tests {
         config.defaults.sync_draft = true;
         config.save(git_dir).unwrap();
 
-        // Load with global should use local values
-        let loaded = Config::load_with_global(git_dir).unwrap();
+        let loaded = Config::load(git_dir).unwrap();
         assert_eq!(loaded.defaults.base, Some("develop".to_string()));
         assert!(loaded.get_sync_draft());
     }
 
+    fn layers(global: &str, repo: &str) -> ConfigLayers {
+        ConfigLayers {
+            global: Some(serde_json::from_str(global).unwrap()),
+            team: None,
+            repo: Some(serde_json::from_str(repo).unwrap()),
+        }
+    }
+
+    fn path(key: &str) -> Vec<String> {
+        key.split('.').map(str::to_string).collect()
+    }
+
     #[test]
-    fn test_load_with_global_uses_global_or_default_when_no_local_config() {
+    fn test_layers_merge_repo_over_global_key_by_key() {
+        let layers = layers(
+            r#"{"defaults": {"base": "main", "sync_jobs": 8, "lint": ["make lint"]},
+                "hooks": {"pre_sync": "./global-pre-sync", "post_land": "./notify"}}"#,
+            r#"{"defaults": {"base": "develop"}, "hooks": {"pre_sync": "./local-pre-sync"}}"#,
+        );
+        let config = layers.effective().unwrap();
+        assert_eq!(config.defaults.base.as_deref(), Some("develop"));
+        assert_eq!(config.get_sync_jobs(), 8);
+        assert_eq!(config.defaults.lint.len(), 1);
+        assert_eq!(config.hooks.pre_sync.as_deref(), Some("./local-pre-sync"));
+        assert_eq!(config.hooks.pre_land, None);
+        assert_eq!(config.hooks.post_land.as_deref(), Some("./notify"));
+
+        assert_eq!(layers.origin(&path("defaults.base")), ConfigOrigin::Repo);
+        assert_eq!(
+            layers.origin(&path("defaults.sync_jobs")),
+            ConfigOrigin::Global
+        );
+        assert_eq!(
+            layers.origin(&path("defaults.sync_draft")),
+            ConfigOrigin::Default
+        );
+    }
+
+    #[test]
+    fn test_nulls_in_repo_config_leave_global_values_alone() {
+        // What `save` wrote before the global layer existed
+        let layers = layers(
+            r#"{"defaults": {"base": "main", "branch_username": "global-user"}}"#,
+            r#"{"defaults": {"base": null, "branch_username": null, "provider": null,
+                "sync_draft": null}, "stacks": {}}"#,
+        );
+        let config = layers.effective().unwrap();
+        assert_eq!(config.defaults.base.as_deref(), Some("main"));
+        assert_eq!(
+            config.defaults.branch_username.as_deref(),
+            Some("global-user")
+        );
+        assert!(!config.get_sync_draft());
+        assert_eq!(
+            layers.origin(&path("defaults.branch_username")),
+            ConfigOrigin::Global
+        );
+    }
+
+    #[test]
+    fn test_team_layer_sits_between_global_and_repo() {
+        let layers = ConfigLayers {
+            global: Some(serde_json::json!({"defaults": {"base": "main", "sync_jobs": 8}})),
+            team: Some(serde_json::json!({
+                "defaults": {"base": "develop", "lint": ["make lint"]}
+            })),
+            repo: Some(serde_json::json!({"defaults": {"lint": []}})),
+        };
+        let config = layers.effective().unwrap();
+        assert_eq!(config.defaults.base.as_deref(), Some("develop"));
+        assert_eq!(config.get_sync_jobs(), 8);
+        assert!(config.defaults.lint.is_empty());
+
+        assert_eq!(layers.origin(&path("defaults.base")), ConfigOrigin::Team);
+        assert_eq!(layers.origin(&path("defaults.lint")), ConfigOrigin::Repo);
+        assert_eq!(
+            layers.origin(&path("defaults.sync_jobs")),
+            ConfigOrigin::Global
+        );
+
+        // Saving the team's values keeps them out of the repo config
+        assert_eq!(
+            diff_value(
+                &serde_json::to_value(
+                    ConfigLayers {
+                        repo: None,
+                        ..layers.clone()
+                    }
+                    .effective()
+                    .unwrap()
+                )
+                .unwrap(),
+                &layers.below_repo().unwrap()
+            ),
+            None
+        );
+    }
+
+    #[test]
+    fn test_team_layer_cannot_set_commands() {
+        let layers = ConfigLayers {
+            global: Some(serde_json::json!({"hooks": {"post_land": "./deploy.sh"}})),
+            team: Some(serde_json::json!({
+                "defaults": {
+                    "base": "develop",
+                    "notify_command": "curl evil.example",
+                    "issue_tracker": {"kind": "command", "command": "./lookup.sh"}
+                },
+                "hooks": {"pre_sync": "rm -rf ~", "post_land": "./other.sh"}
+            })),
+            repo: None,
+        };
+        let config = layers.effective().unwrap();
+        assert_eq!(config.defaults.base.as_deref(), Some("develop"));
+        assert_eq!(config.get_notify_command(), None);
+        assert_eq!(config.defaults.issue_tracker.command, None);
+        assert_eq!(config.hooks.pre_sync, None);
+        assert_eq!(config.hooks.post_land.as_deref(), Some("./deploy.sh"));
+
+        assert_eq!(
+            layers.origin(&path("hooks.pre_sync")),
+            ConfigOrigin::Default
+        );
+        assert_eq!(
+            layers.origin(&path("hooks.post_land")),
+            ConfigOrigin::Global
+        );
+        assert_eq!(
+            layers.ignored_team_keys(),
+            [
+                "hooks.post_land",
+                "hooks.pre_sync",
+                "defaults.notify_command",
+                "defaults.issue_tracker.command"
+            ]
+        );
+        assert!(ignored_in_team_config(&path("hooks.pre_land")));
+        assert!(!ignored_in_team_config(&path(
+            "defaults.issue_tracker.kind"
+        )));
+    }
+
+    #[test]
+    fn test_team_config_path_is_in_the_main_working_tree() {
+        assert_eq!(
+            Config::team_config_path(Path::new("/work/app/.git")),
+            Some(PathBuf::from("/work/app/.gg/config.json"))
+        );
+        assert_eq!(Config::team_config_path(Path::new("/srv/app.git")), None);
+    }
+
+    #[test]
+    fn test_repo_config_only_keeps_values_that_differ_from_global() {
+        let layers = layers(r#"{"defaults": {"sync_jobs": 8}}"#, "{}");
+        let mut config = layers.effective().unwrap();
+        config.defaults.base = Some("develop".to_string());
+
+        let local = diff_value(
+            &serde_json::to_value(&config).unwrap(),
+            &layers.below_repo().unwrap(),
+        )
+        .unwrap();
+        assert_eq!(local, serde_json::json!({"defaults": {"base": "develop"}}));
+
+        // Nothing changed: nothing to write
+        let unchanged = layers.effective().unwrap();
+        assert_eq!(
+            diff_value(
+                &serde_json::to_value(&unchanged).unwrap(),
+                &layers.below_repo().unwrap()
+            ),
+            None
+        );
+    }
+
+    #[test]
+    fn test_repo_config_keeps_the_keys_it_already_sets() {
+        let layers = layers(
+            r#"{"defaults": {"base": "main", "sync_jobs": 8}}"#,
+            r#"{"defaults": {"base": "main", "branch_username": null}}"#,
+        );
+        let mut config = layers.effective().unwrap();
+        config.defaults.sync_draft = true;
+
+        let below = layers.below_repo().unwrap();
+        let local = repo_layer(
+            &serde_json::to_value(&config).unwrap(),
+            layers.repo.as_ref(),
+            &below,
+        )
+        .unwrap();
+        // `base` matches the global value but stays pinned; the null
+        // `branch_username` was never set
+        assert_eq!(
+            local,
+            serde_json::json!({"defaults": {"base": "main", "sync_draft": true}})
+        );
+    }
+
+    #[test]
+    fn test_load_uses_global_or_default_when_no_local_config() {
         let temp_dir = TempDir::new().unwrap();
         let git_dir = temp_dir.path();
 
@@ -961,7 +2256,7 @@ mod tests {
         let expected = Config::load_global()
             .unwrap_or_default()
             .unwrap_or_default();
-        let loaded = Config::load_with_global(git_dir).unwrap();
+        let loaded = Config::load(git_dir).unwrap();
         assert_eq!(loaded.get_sync_draft(), expected.get_sync_draft());
         assert_eq!(
             loaded.get_sync_update_descriptions(),
@@ -0,0 +1,439 @@
+//! Conflicts of a paused rebase (or merge, cherry-pick, revert), for tools
+//! that resolve them without a terminal, such as the `gg-mcp` conflict tools.
+//!
+//! [`status`] lists the conflicted files, [`show`] splits a file into its
+//! conflict hunks, and [`resolve`] writes a resolution and stages it; once
+//! no file is left, `gg continue` picks the operation back up. During a
+//! rebase "ours" is the branch being rebased onto, with the commits replayed
+//! so far, and "theirs" is the commit being replayed.
+
+use std::fs;
+use std::path::Path;
+
+use git2::{IndexConflict, Repository, RepositoryState};
+use serde::Serialize;
+
+use crate::error::{GgError, Result};
+use crate::git;
+use crate::operations::{self, OperationKind};
+
+/// What is paused, and on which files.
+#[derive(Debug, Clone, Serialize)]
+pub struct ConflictStatus {
+    /// `rebase`, `merge`, `cherry_pick` or `revert`; `None` when nothing is
+    /// in progress
+    pub operation: Option<&'static str>,
+    /// The gg command that started it, when one did
+    pub gg_operation: Option<OperationKind>,
+    /// The commit being replayed or merged
+    pub stopped_at: Option<StoppedAt>,
+    pub files: Vec<ConflictFile>,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct StoppedAt {
+    pub sha: String,
+    pub title: String,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct ConflictFile {
+    pub path: String,
+    pub kind: ConflictKind,
+    /// Conflict hunks left in the working tree file
+    pub hunks: usize,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ConflictKind {
+    BothModified,
+    BothAdded,
+    DeletedByUs,
+    DeletedByThem,
+}
+
+/// A conflicted file, hunk by hunk.
+#[derive(Debug, Clone, Serialize)]
+pub struct ConflictDetails {
+    pub path: String,
+    pub kind: ConflictKind,
+    /// Labels of the conflict markers (e.g. `HEAD` and `abc1234 (Add parser)`)
+    pub ours_label: Option<String>,
+    pub theirs_label: Option<String>,
+    /// Whether the file is not text, so it has no hunks
+    pub binary: bool,
+    pub hunks: Vec<ConflictHunk>,
+}
+
+/// One `<<<<<<<` ... `>>>>>>>` block.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct ConflictHunk {
+    /// 1-based line of the `<<<<<<<` marker
+    pub line: usize,
+    pub ours: String,
+    /// Common ancestor, with `merge.conflictStyle` `diff3` or `zdiff3`
+    pub base: Option<String>,
+    pub theirs: String,
+}
+
+/// How to resolve a file.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Resolution {
+    /// Our side of every hunk, keeping what merged cleanly
+    Ours,
+    /// Their side of every hunk, keeping what merged cleanly
+    Theirs,
+    /// The whole resolved file
+    Content(String),
+    /// Remove the file
+    Delete,
+}
+
+/// The paused operation and its conflicted files.
+pub fn status(repo: &Repository) -> Result<ConflictStatus> {
+    let operation = match repo.state() {
+        RepositoryState::Rebase
+        | RepositoryState::RebaseInteractive
+        | RepositoryState::RebaseMerge
+        | RepositoryState::ApplyMailboxOrRebase => Some("rebase"),
+        RepositoryState::Merge => Some("merge"),
+        RepositoryState::CherryPick | RepositoryState::CherryPickSequence => Some("cherry_pick"),
+        RepositoryState::Revert | RepositoryState::RevertSequence => Some("revert"),
+        _ => None,
+    };
+    let gg_operation = operations::interrupted_operation(repo)?.map(|(state, _)| state.kind);
+    let stopped_at = [
+        "REBASE_HEAD",
+        "CHERRY_PICK_HEAD",
+        "REVERT_HEAD",
+        "MERGE_HEAD",
+    ]
+    .iter()
+    .find_map(|name| repo.revparse_single(name).ok()?.peel_to_commit().ok())
+    .map(|commit| StoppedAt {
+        sha: git::short_sha(&commit),
+        title: commit.summary().ok().flatten().unwrap_or("").to_string(),
+    });
+
+    let mut files = Vec::new();
+    for conflict in repo.index()?.conflicts()? {
+        let conflict = conflict?;
+        let Some(path) = conflict_path(&conflict) else {
+            continue;
+        };
+        let hunks = read_text(repo, &path)?
+            .map(|content| parse(&content).1.len())
+            .unwrap_or(0);
+        files.push(ConflictFile {
+            kind: kind(&conflict),
+            path,
+            hunks,
+        });
+    }
+
+    Ok(ConflictStatus {
+        operation,
+        gg_operation,
+        stopped_at,
+        files,
+    })
+}
+
+/// The hunks of conflicted file `path`.
+pub fn show(repo: &Repository, path: &str) -> Result<ConflictDetails> {
+    let conflict = find_conflict(repo, path)?;
+    let content = read_text(repo, path)?;
+    let (labels, hunks) = content.as_deref().map(parse).unwrap_or_default();
+    Ok(ConflictDetails {
+        path: path.to_string(),
+        kind: kind(&conflict),
+        ours_label: labels.0,
+        theirs_label: labels.1,
+        binary: content.is_none() && workdir(repo)?.join(path).exists(),
+        hunks,
+    })
+}
+
+/// Resolve conflicted file `path` and stage it.
+pub fn resolve(repo: &Repository, path: &str, resolution: Resolution) -> Result<()> {
+    let conflict = find_conflict(repo, path)?;
+    let file = workdir(repo)?.join(path);
+
+    let content: Option<Vec<u8>> = match resolution {
+        Resolution::Content(content) => {
+            if !parse(&content).1.is_empty() {
+                return Err(GgError::Other(format!(
+                    "The resolution of {} still has conflict markers",
+                    path
+                )));
+            }
+            Some(content.into_bytes())
+        }
+        Resolution::Delete => None,
+        Resolution::Ours | Resolution::Theirs => {
+            let ours = resolution == Resolution::Ours;
+            match read_text(repo, path)? {
+                Some(current) if !parse(&current).1.is_empty() => {
+                    Some(pick(&current, ours).into_bytes())
+                }
+                // Binary files and delete/modify conflicts have no hunks:
+                // take that side's whole version
+                _ => {
+                    let side = if ours { &conflict.our } else { &conflict.their };
+                    match side {
+                        Some(entry) => Some(repo.find_blob(entry.id)?.content().to_vec()),
+                        None => None,
+                    }
+                }
+            }
+        }
+    };
+
+    let mut index = repo.index()?;
+    match content {
+        Some(content) => {
+            if let Some(parent) = file.parent() {
+                fs::create_dir_all(parent)?;
+            }
+            fs::write(&file, content)?;
+            index.add_path(Path::new(path))?;
+        }
+        None => {
+            if file.exists() {
+                fs::remove_file(&file)?;
+            }
+            index.remove_path(Path::new(path))?;
+        }
+    }
+    index.write()?;
+    Ok(())
+}
+
+fn workdir(repo: &Repository) -> Result<&Path> {
+    repo.workdir()
+        .ok_or_else(|| GgError::Other("Repository has no working directory".to_string()))
+}
+
+fn find_conflict(repo: &Repository, path: &str) -> Result<IndexConflict> {
+    for conflict in repo.index()?.conflicts()? {
+        let conflict = conflict?;
+        if conflict_path(&conflict).as_deref() == Some(path) {
+            return Ok(conflict);
+        }
+    }
+    Err(GgError::Other(format!("{} has no conflict", path)))
+}
+
+fn conflict_path(conflict: &IndexConflict) -> Option<String> {
+    [&conflict.our, &conflict.their, &conflict.ancestor]
+        .into_iter()
+        .flatten()
+        .next()
+        .map(|entry| String::from_utf8_lossy(&entry.path).into_owned())
+}
+
+fn kind(conflict: &IndexConflict) -> ConflictKind {
+    match (&conflict.ancestor, &conflict.our, &conflict.their) {
+        (_, None, _) => ConflictKind::DeletedByUs,
+        (_, _, None) => ConflictKind::DeletedByThem,
+        (None, _, _) => ConflictKind::BothAdded,
+        _ => ConflictKind::BothModified,
+    }
+}
+
+/// The working tree file at `path`; `None` when missing or not UTF-8.
+fn read_text(repo: &Repository, path: &str) -> Result<Option<String>> {
+    match fs::read(workdir(repo)?.join(path)) {
+        Ok(bytes) => Ok(String::from_utf8(bytes).ok()),
+        Err(e) if e.kind() == std::io::ErrorKind::NotFound => Ok(None),
+        Err(e) => Err(e.into()),
+    }
+}
+
+/// Part of a file with conflict markers.
+enum Segment {
+    Text(String),
+    Conflict(ConflictHunk),
+}
+
+type Labels = (Option<String>, Option<String>);
+
+fn marker<'a>(line: &'a str, marker: &str) -> Option<&'a str> {
+    let rest = line.trim_end_matches(['\n', '\r']).strip_prefix(marker)?;
+    if rest.is_empty() {
+        Some("")
+    } else {
+        rest.strip_prefix(' ')
+    }
+}
+
+/// Split `content` into text and conflict hunks. Markers that never close
+/// are left as text.
+fn segments(content: &str) -> (Labels, Vec<Segment>) {
+    enum Part {
+        Ours,
+        Base,
+        Theirs,
+    }
+    let mut labels: Labels = (None, None);
+    let mut out = Vec::new();
+    let mut text = String::new();
+    let mut open: Option<(ConflictHunk, Part, String)> = None;
+
+    for (i, line) in content.split_inclusive('\n').enumerate() {
+        let Some((hunk, part, raw)) = &mut open else {
+            match marker(line, "<<<<<<<") {
+                Some(label) => {
+                    if labels.0.is_none() && !label.is_empty() {
+                        labels.0 = Some(label.to_string());
+                    }
+                    let hunk = ConflictHunk {
+                        line: i + 1,
+                        ours: String::new(),
+                        base: None,
+                        theirs: String::new(),
+                    };
+                    open = Some((hunk, Part::Ours, line.to_string()));
+                }
+                None => text.push_str(line),
+            }
+            continue;
+        };
+        raw.push_str(line);
+        match part {
+            Part::Ours | Part::Base if marker(line, "|||||||").is_some() => {
+                hunk.base = Some(String::new());
+                *part = Part::Base;
+            }
+            Part::Ours | Part::Base if marker(line, "=======") == Some("") => {
+                *part = Part::Theirs;
+            }
+            Part::Theirs if marker(line, ">>>>>>>").is_some() => {
+                if let Some(label) = marker(line, ">>>>>>>").filter(|l| !l.is_empty()) {
+                    labels.1.get_or_insert_with(|| label.to_string());
+                }
+                let (hunk, _, _) = open.take().expect("conflict is open");
+                if !text.is_empty() {
+                    out.push(Segment::Text(std::mem::take(&mut text)));
+                }
+                out.push(Segment::Conflict(hunk));
+            }
+            Part::Ours => hunk.ours.push_str(line),
+            Part::Base => hunk.base.get_or_insert_with(String::new).push_str(line),
+            Part::Theirs => hunk.theirs.push_str(line),
+        }
+    }
+    if let Some((_, _, raw)) = open {
+        text.push_str(&raw);
+    }
+    if !text.is_empty() {
+        out.push(Segment::Text(text));
+    }
+    (labels, out)
+}
+
+/// The marker labels and conflict hunks of `content`.
+fn parse(content: &str) -> (Labels, Vec<ConflictHunk>) {
+    let (labels, segments) = segments(content);
+    let hunks = segments
+        .into_iter()
+        .filter_map(|segment| match segment {
+            Segment::Conflict(hunk) => Some(hunk),
+            Segment::Text(_) => None,
+        })
+        .collect();
+    (labels, hunks)
+}
+
+/// `content` with each conflict hunk replaced by one of its sides.
+fn pick(content: &str, ours: bool) -> String {
+    segments(content)
+        .1
+        .into_iter()
+        .map(|segment| match segment {
+            Segment::Text(text) => text,
+            Segment::Conflict(hunk) if ours => hunk.ours,
+            Segment::Conflict(hunk) => hunk.theirs,
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const CONFLICTED: &str = "fn main() {\n<<<<<<< HEAD\n    run(1);\n||||||| base\n    run();\n=======\n    run(2);\n>>>>>>> abc1234 (Tune run)\n}\n";
+
+    #[test]
+    fn parses_hunks_and_labels() {
+        let ((ours, theirs), hunks) = parse(CONFLICTED);
+        assert_eq!(ours.as_deref(), Some("HEAD"));
+        assert_eq!(theirs.as_deref(), Some("abc1234 (Tune run)"));
+        assert_eq!(
+            hunks,
+            vec![ConflictHunk {
+                line: 2,
+                ours: "    run(1);\n".to_string(),
+                base: Some("    run();\n".to_string()),
+                theirs: "    run(2);\n".to_string(),
+            }]
+        );
+
+        // A marker that never closes is just text
+        assert!(parse("<<<<<<< HEAD\nfoo\n").1.is_empty());
+    }
+
+    #[test]
+    fn resolves_a_cherry_pick_conflict() {
+        let dir = tempfile::tempdir().unwrap();
+        let git = |args: &[&str]| {
+            let output = std::process::Command::new("git")
+                .args(args)
+                .current_dir(dir.path())
+                .output()
+                .unwrap();
+            String::from_utf8_lossy(&output.stdout).trim().to_string()
+        };
+        git(&["init", "-q", "-b", "main"]);
+        git(&["config", "user.name", "Test"]);
+        git(&["config", "user.email", "test@example.com"]);
+        fs::write(dir.path().join("a.txt"), "one\n").unwrap();
+        git(&["add", "a.txt"]);
+        git(&["commit", "-qm", "Add a"]);
+        git(&["checkout", "-qb", "other"]);
+        fs::write(dir.path().join("a.txt"), "theirs\n").unwrap();
+        git(&["commit", "-qam", "Change a"]);
+        let theirs = git(&["rev-parse", "HEAD"]);
+        git(&["checkout", "-q", "main"]);
+        fs::write(dir.path().join("a.txt"), "ours\n").unwrap();
+        git(&["commit", "-qam", "Change a too"]);
+        git(&["cherry-pick", &theirs]);
+
+        let repo = Repository::open(dir.path()).unwrap();
+        let paused = status(&repo).unwrap();
+        assert_eq!(paused.operation, Some("cherry_pick"));
+        assert_eq!(paused.stopped_at.unwrap().title, "Change a");
+        assert_eq!(paused.files.len(), 1);
+        assert_eq!(paused.files[0].path, "a.txt");
+        assert_eq!(paused.files[0].kind, ConflictKind::BothModified);
+        assert_eq!(paused.files[0].hunks, 1);
+        assert_eq!(show(&repo, "a.txt").unwrap().hunks[0].theirs, "theirs\n");
+
+        let still_conflicted = Resolution::Content(CONFLICTED.to_string());
+        assert!(resolve(&repo, "a.txt", still_conflicted).is_err());
+        resolve(&repo, "a.txt", Resolution::Theirs).unwrap();
+        assert_eq!(
+            fs::read_to_string(dir.path().join("a.txt")).unwrap(),
+            "theirs\n"
+        );
+        assert!(status(&repo).unwrap().files.is_empty());
+        assert!(resolve(&repo, "a.txt", Resolution::Ours).is_err());
+    }
+
+    #[test]
+    fn pick_keeps_one_side_and_the_clean_text() {
+        assert_eq!(pick(CONFLICTED, true), "fn main() {\n    run(1);\n}\n");
+        assert_eq!(pick(CONFLICTED, false), "fn main() {\n    run(2);\n}\n");
+    }
+}
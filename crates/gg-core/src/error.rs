@@ -26,6 +26,9 @@ pub enum GgError {
     #[error("{0}")]
     NotOnStackBranch(String),
 
+    #[error("Stack '{0}' is selected with `gg use` but not checked out. Run `gg co {0}` to edit it, or `gg use --clear`.")]
+    ActiveStackNotCheckedOut(String),
+
     #[error("Stack '{0}' not found")]
     StackNotFound(String),
 
@@ -38,6 +41,12 @@ pub enum GgError {
     #[error("Commit {0} is missing a GG-ID trailer. Run `gg sync` to add one.")]
     MissingGgId(String),
 
+    #[error("gh CLI not installed")]
+    GhNotInstalled,
+
+    #[error("{0}")]
+    GhNotAuthenticated(String),
+
     #[error("glab is not installed. Please install it from https://gitlab.com/gitlab-org/cli")]
     GlabNotInstalled,
 
@@ -47,6 +56,14 @@ pub enum GgError {
     #[error("glab command failed: {0}")]
     GlabError(String),
 
+    #[error(
+        "Not authenticated with Gitea. Set GITEA_TOKEN (or FORGEJO_TOKEN), or run `tea login add`."
+    )]
+    GiteaNotAuthenticated,
+
+    #[error("Gitea API request failed: {0}")]
+    GiteaError(String),
+
     #[error("Invalid PR number: {0}")]
     InvalidPrNumber(String),
 
@@ -76,6 +93,12 @@ pub enum GgError {
     )]
     ImmutableTargetsDuringSync(String),
 
+    #[error("branch protection on {0} would reject this stack:\n{1}")]
+    ProtectionViolation(String, String),
+
+    #[error("Refusing to push to or rewrite protected branch '{0}' (matches `defaults.protected_branches`)")]
+    ProtectedBranch(String),
+
     #[error("Git error: {0}")]
     Git(#[from] git2::Error),
 
@@ -104,6 +127,9 @@ pub enum GgError {
     #[error("Cannot locally undo '{kind}': it touched a remote.\n{hint}")]
     RemoteUndoUnsupported { kind: String, hint: String },
 
+    #[error("{0} hook failed: {1}")]
+    HookFailed(String, String),
+
     #[error("{0}")]
     Other(String),
 
@@ -118,6 +144,53 @@ pub enum GgError {
     Silenced,
 }
 
+impl GgError {
+    /// Stable machine-readable code for this kind of failure, printed as
+    /// `error[GG_...]:` on stderr and as `code` in `--json` errors. Codes
+    /// never change once released, even when messages do.
+    pub fn code(&self) -> &'static str {
+        match self {
+            GgError::NetworkError(_) => "GG_NETWORK",
+            GgError::InvalidStackName(_) => "GG_INVALID_STACK_NAME",
+            GgError::InvalidBranchUsername(_) => "GG_INVALID_BRANCH_USERNAME",
+            GgError::NotInRepo => "GG_NOT_IN_REPO",
+            GgError::NoBaseBranch => "GG_NO_BASE_BRANCH",
+            GgError::NotOnStack | GgError::NotOnStackBranch(_) => "GG_NOT_ON_STACK",
+            GgError::ActiveStackNotCheckedOut(_) => "GG_STACK_NOT_CHECKED_OUT",
+            GgError::StackNotFound(_) => "GG_STACK_NOT_FOUND",
+            GgError::DirtyWorkingDirectory => "GG_DIRTY_WORKING_DIRECTORY",
+            GgError::MergeCommitInStack => "GG_MERGE_COMMIT_IN_STACK",
+            GgError::MissingGgId(_) => "GG_MISSING_GG_ID",
+            GgError::GhNotInstalled | GgError::GlabNotInstalled => "GG_PROVIDER_CLI_MISSING",
+            GgError::GhNotAuthenticated(_)
+            | GgError::GlabNotAuthenticated
+            | GgError::GiteaNotAuthenticated => "GG_AUTH_REQUIRED",
+            GgError::GlabError(_) | GgError::GiteaError(_) => "GG_PROVIDER",
+            GgError::InvalidPrNumber(_) => "GG_INVALID_PR_NUMBER",
+            GgError::PushFailed { .. } => "GG_PUSH_FAILED",
+            GgError::Command(_, _) => "GG_COMMAND_FAILED",
+            GgError::RebaseConflict => "GG_REBASE_CONFLICT",
+            GgError::NoRebaseInProgress => "GG_NO_REBASE_IN_PROGRESS",
+            GgError::ImmutableTargets(_) | GgError::ImmutableTargetsDuringSync(_) => {
+                "GG_IMMUTABLE_COMMITS"
+            }
+            GgError::ProtectionViolation(_, _) => "GG_BRANCH_PROTECTION",
+            GgError::ProtectedBranch(_) => "GG_PROTECTED_BRANCH",
+            GgError::Git(_) => "GG_GIT",
+            GgError::Config(_) => "GG_CONFIG",
+            GgError::Io(_) => "GG_IO",
+            GgError::Json(_) => "GG_JSON",
+            GgError::OperationRecordNotFound(_) => "GG_OPERATION_NOT_FOUND",
+            GgError::OperationNotUndoable { .. } => "GG_NOT_UNDOABLE",
+            GgError::StaleUndo { .. } => "GG_STALE_UNDO",
+            GgError::RemoteUndoUnsupported { .. } => "GG_REMOTE_UNDO_UNSUPPORTED",
+            GgError::HookFailed(_, _) => "GG_HOOK_FAILED",
+            GgError::GitOperationInProgress(_, _) => "GG_OPERATION_IN_PROGRESS",
+            GgError::Other(_) | GgError::Silenced => "GG_ERROR",
+        }
+    }
+}
+
 pub type Result<T> = std::result::Result<T, GgError>;
 
 /// Check if an error message indicates a network problem rather than an auth failure.
@@ -167,6 +240,32 @@ pub fn is_network_error(output: &str) -> bool {
 mod tests {
     use super::*;
 
+    #[test]
+    fn test_error_codes_are_stable_identifiers() {
+        let errors = [
+            GgError::NotOnStack,
+            GgError::NotOnStackBranch("main".to_string()),
+            GgError::RebaseConflict,
+            GgError::NetworkError("timed out".to_string()),
+            GgError::Other("boom".to_string()),
+        ];
+        let codes: Vec<&str> = errors.iter().map(GgError::code).collect();
+        assert_eq!(
+            codes,
+            [
+                "GG_NOT_ON_STACK",
+                "GG_NOT_ON_STACK",
+                "GG_REBASE_CONFLICT",
+                "GG_NETWORK",
+                "GG_ERROR"
+            ]
+        );
+        assert!(codes
+            .iter()
+            .all(|c| c.starts_with("GG_")
+                && c.chars().all(|ch| ch.is_ascii_uppercase() || ch == '_')));
+    }
+
     #[test]
     fn test_is_network_error_detects_dns_errors() {
         assert!(is_network_error("Could not resolve host: github.com"));
@@ -1,12 +1,31 @@
 //! GitHub CLI (gh) integration
 //!
-//! Wraps gh subprocess calls for PR management.
+//! Wraps gh subprocess calls for PR management. With `api_mode: native`,
+//! the core operations are served by [`crate::gh_api`] instead.
 
+use std::collections::HashMap;
 use std::process::Command;
 
 use serde::Deserialize;
 
+use crate::api;
+use crate::config::MergeStrategy;
 use crate::error::{GgError, Result};
+use crate::gh_api;
+use crate::relative_time;
+
+/// A `gh` invocation aimed at the repository of the stack's remote.
+///
+/// gh resolves the repository from the git remotes by itself; when the stack
+/// lives on a remote other than `origin`, `GH_REPO` points it there instead.
+fn gh_command() -> Command {
+    let mut cmd = Command::new("gh");
+    if let Some((host, path)) = api::remote_override() {
+        cmd.env("GH_REPO", format!("{}/{}", host, path))
+            .env("GH_HOST", host);
+    }
+    cmd
+}
 
 /// PR state from GitHub
 #[derive(Debug, Clone, PartialEq, Eq)]
@@ -26,13 +45,17 @@ pub struct PrInfo {
     pub state: PrState,
     pub url: String,
     pub head_branch: Option<String>,
+    pub base_branch: Option<String>,
     pub draft: bool,
     pub approved: bool,
     pub mergeable: bool,
     pub changes_requested: bool,
+    /// Last activity on the PR, in milliseconds since the epoch
+    pub updated_at_ms: Option<u64>,
 }
 
-/// JSON response from `gh pr view --json`
+/// JSON response from `gh pr view --json`, or one PR of a
+/// [`prs_status_query`] response
 #[derive(Debug, Deserialize)]
 #[serde(rename_all = "camelCase")]
 #[allow(dead_code)]
@@ -42,12 +65,20 @@ struct GhPrJson {
     state: String,
     url: String,
     head_ref_name: Option<String>,
+    base_ref_name: Option<String>,
     #[serde(default)]
     is_draft: bool,
     mergeable: Option<String>,
     #[serde(default)]
     reviews: Vec<GhReview>,
     review_decision: Option<String>,
+    updated_at: Option<String>,
+    /// GraphQL only
+    #[serde(default)]
+    merge_queue_entry: Option<MergeQueueEntry>,
+    /// GraphQL only: `{ nodes: [{ commit: { statusCheckRollup } }] }`
+    #[serde(default)]
+    commits: serde_json::Value,
 }
 
 #[derive(Debug, Deserialize)]
@@ -58,11 +89,15 @@ struct GhReview {
 
 /// Check if gh is installed
 pub fn check_gh_installed() -> Result<()> {
-    let output = Command::new("gh").arg("--version").output();
+    if api::native_mode() {
+        return Ok(());
+    }
+
+    let output = gh_command().arg("--version").output();
 
     match output {
         Ok(o) if o.status.success() => Ok(()),
-        _ => Err(GgError::Other("gh CLI not installed".to_string())),
+        _ => Err(GgError::GhNotInstalled),
     }
 }
 
@@ -71,9 +106,13 @@ pub fn check_gh_installed() -> Result<()> {
 /// Distinguishes between actual auth failures and network errors:
 /// - Returns `Ok(())` if authenticated
 /// - Returns `Err(GgError::NetworkError(...))` if a network error is detected
-/// - Returns `Err(GgError::Other(...))` for actual auth failures
+/// - Returns `Err(GgError::GhNotAuthenticated(...))` for actual auth failures
 pub fn check_gh_auth() -> Result<()> {
-    let output = Command::new("gh").args(["auth", "status"]).output()?;
+    if api::native_mode() {
+        return gh_api::check_auth();
+    }
+
+    let output = gh_command().args(["auth", "status"]).output()?;
 
     if output.status.success() {
         return Ok(());
@@ -90,14 +129,18 @@ pub fn check_gh_auth() -> Result<()> {
         ));
     }
 
-    Err(GgError::Other(
+    Err(GgError::GhNotAuthenticated(
         "Not authenticated with GitHub. Run `gh auth login` first.".to_string(),
     ))
 }
 
 /// Get the current GitHub username
 pub fn whoami() -> Result<String> {
-    let output = Command::new("gh")
+    if api::native_mode() {
+        return gh_api::whoami();
+    }
+
+    let output = gh_command()
         .args(["api", "user", "--jq", ".login"])
         .output()?;
 
@@ -133,6 +176,10 @@ pub fn create_pr(
     description: &str,
     draft: bool,
 ) -> Result<PrCreationResult> {
+    if api::native_mode() {
+        return gh_api::create_pr(source_branch, target_branch, title, description, draft);
+    }
+
     let mut args = vec![
         "pr",
         "create",
@@ -150,7 +197,7 @@ pub fn create_pr(
         args.push("--draft");
     }
 
-    let output = Command::new("gh").args(&args).output()?;
+    let output = gh_command().args(&args).output()?;
 
     if !output.status.success() {
         let stderr = String::from_utf8_lossy(&output.stderr);
@@ -180,13 +227,17 @@ pub fn create_pr(
 
 /// View PR information
 pub fn view_pr(pr_number: u64) -> Result<PrInfo> {
-    let output = Command::new("gh")
+    if api::native_mode() {
+        return gh_api::view_pr(pr_number);
+    }
+
+    let output = gh_command()
         .args([
             "pr",
             "view",
             &pr_number.to_string(),
             "--json",
-            "number,title,state,url,headRefName,isDraft,mergeable,reviews,reviewDecision",
+            "number,title,state,url,headRefName,baseRefName,isDraft,mergeable,reviews,reviewDecision,updatedAt",
         ])
         .output()?;
 
@@ -201,7 +252,10 @@ pub fn view_pr(pr_number: u64) -> Result<PrInfo> {
     let stdout = String::from_utf8_lossy(&output.stdout);
     let pr_json: GhPrJson = serde_json::from_str(&stdout)
         .map_err(|e| GgError::Other(format!("Failed to parse PR JSON: {}", e)))?;
+    Ok(convert_pr_json(pr_json))
+}
 
+fn convert_pr_json(pr_json: GhPrJson) -> PrInfo {
     let state = match pr_json.state.to_uppercase().as_str() {
         "MERGED" => PrState::Merged,
         "CLOSED" => PrState::Closed,
@@ -214,22 +268,31 @@ pub fn view_pr(pr_number: u64) -> Result<PrInfo> {
 
     let mergeable = pr_json.mergeable.as_deref() == Some("MERGEABLE");
 
-    Ok(PrInfo {
+    PrInfo {
         number: pr_json.number,
         title: pr_json.title,
         state,
         url: pr_json.url,
         head_branch: pr_json.head_ref_name,
+        base_branch: pr_json.base_ref_name,
         draft: pr_json.is_draft,
         approved,
         mergeable,
         changes_requested,
-    })
+        updated_at_ms: pr_json
+            .updated_at
+            .as_deref()
+            .and_then(relative_time::parse_rfc3339_ms),
+    }
 }
 
 /// Close a PR without merging.
 pub fn close_pr(pr_number: u64) -> Result<()> {
-    let output = Command::new("gh")
+    if api::native_mode() {
+        return gh_api::close_pr(pr_number);
+    }
+
+    let output = gh_command()
         .args(["pr", "close", &pr_number.to_string()])
         .output()?;
 
@@ -244,6 +307,33 @@ pub fn close_pr(pr_number: u64) -> Result<()> {
     Ok(())
 }
 
+/// Close an issue of the current repository as completed.
+pub fn close_issue(number: u64) -> Result<()> {
+    if api::native_mode() {
+        return gh_api::close_issue(number);
+    }
+
+    let output = gh_command()
+        .args([
+            "issue",
+            "close",
+            &number.to_string(),
+            "--reason",
+            "completed",
+        ])
+        .output()?;
+
+    if !output.status.success() {
+        let stderr = String::from_utf8_lossy(&output.stderr);
+        return Err(GgError::Other(format!(
+            "Failed to close issue #{}: {}",
+            number, stderr
+        )));
+    }
+
+    Ok(())
+}
+
 /// Alias for view_pr for compatibility
 pub fn get_pr_info(pr_number: u64) -> Result<PrInfo> {
     view_pr(pr_number)
@@ -251,7 +341,11 @@ pub fn get_pr_info(pr_number: u64) -> Result<PrInfo> {
 
 /// Convert an existing PR to draft (GitHub only)
 pub fn convert_pr_to_draft(pr_number: u64) -> Result<()> {
-    let output = Command::new("gh")
+    if api::native_mode() {
+        return gh_api::convert_pr_to_draft(pr_number);
+    }
+
+    let output = gh_command()
         .args(["pr", "ready", "--undo", &pr_number.to_string()])
         .output()?;
 
@@ -273,7 +367,11 @@ pub fn convert_pr_to_draft(pr_number: u64) -> Result<()> {
 
 /// Update PR base branch
 pub fn update_pr_base(pr_number: u64, base_branch: &str) -> Result<()> {
-    let output = Command::new("gh")
+    if api::native_mode() {
+        return gh_api::update_pr_base(pr_number, base_branch);
+    }
+
+    let output = gh_command()
         .args(["pr", "edit", &pr_number.to_string(), "--base", base_branch])
         .output()?;
 
@@ -288,9 +386,72 @@ pub fn update_pr_base(pr_number: u64, base_branch: &str) -> Result<()> {
     Ok(())
 }
 
+/// Run `gh pr edit <pr> <flag> <value>`; `what` names the change in errors.
+fn edit_pr(pr_number: u64, flag: &str, value: &str, what: &str) -> Result<()> {
+    let output = gh_command()
+        .args(["pr", "edit", &pr_number.to_string(), flag, value])
+        .output()?;
+
+    if !output.status.success() {
+        let stderr = String::from_utf8_lossy(&output.stderr);
+        return Err(GgError::Other(format!(
+            "Failed to {} on PR #{}: {}",
+            what, pr_number, stderr
+        )));
+    }
+
+    Ok(())
+}
+
+/// Request reviews from users and teams (`org/team`)
+pub fn request_reviewers(pr_number: u64, reviewers: &[String]) -> Result<()> {
+    if api::native_mode() {
+        return gh_api::request_reviewers(pr_number, reviewers);
+    }
+    edit_pr(
+        pr_number,
+        "--add-reviewer",
+        &reviewers.join(","),
+        "request reviewers",
+    )
+}
+
+/// Add labels to a PR
+pub fn add_labels(pr_number: u64, labels: &[String]) -> Result<()> {
+    if api::native_mode() {
+        return gh_api::add_labels(pr_number, labels);
+    }
+    edit_pr(pr_number, "--add-label", &labels.join(","), "add labels")
+}
+
+/// Remove labels from a PR
+pub fn remove_labels(pr_number: u64, labels: &[String]) -> Result<()> {
+    if api::native_mode() {
+        return gh_api::remove_labels(pr_number, labels);
+    }
+    edit_pr(
+        pr_number,
+        "--remove-label",
+        &labels.join(","),
+        "remove labels",
+    )
+}
+
+/// Set the milestone of a PR by title
+pub fn set_milestone(pr_number: u64, milestone: &str) -> Result<()> {
+    if api::native_mode() {
+        return gh_api::set_milestone(pr_number, milestone);
+    }
+    edit_pr(pr_number, "--milestone", milestone, "set the milestone")
+}
+
 /// Get PR body text
 pub fn get_pr_body(pr_number: u64) -> Result<String> {
-    let output = Command::new("gh")
+    if api::native_mode() {
+        return gh_api::get_pr_body(pr_number);
+    }
+
+    let output = gh_command()
         .args([
             "pr",
             "view",
@@ -319,7 +480,11 @@ pub fn get_pr_body(pr_number: u64) -> Result<String> {
 
 /// Update PR description/body
 pub fn update_pr_description(pr_number: u64, description: &str) -> Result<()> {
-    let output = Command::new("gh")
+    if api::native_mode() {
+        return gh_api::update_pr_description(pr_number, description);
+    }
+
+    let output = gh_command()
         .args(["pr", "edit", &pr_number.to_string(), "--body", description])
         .output()?;
 
@@ -336,7 +501,11 @@ pub fn update_pr_description(pr_number: u64, description: &str) -> Result<()> {
 
 /// Update PR title
 pub fn update_pr_title(pr_number: u64, title: &str) -> Result<()> {
-    let output = Command::new("gh")
+    if api::native_mode() {
+        return gh_api::update_pr_title(pr_number, title);
+    }
+
+    let output = gh_command()
         .args(["pr", "edit", &pr_number.to_string(), "--title", title])
         .output()?;
 
@@ -352,16 +521,20 @@ pub fn update_pr_title(pr_number: u64, title: &str) -> Result<()> {
 }
 
 /// Merge a PR
-pub fn merge_pr(pr_number: u64, squash: bool, delete_branch: bool, admin: bool) -> Result<()> {
-    let pr_num_str = pr_number.to_string();
-    let mut args = vec!["pr", "merge", &pr_num_str];
-
-    if squash {
-        args.push("--squash");
-    } else {
-        args.push("--merge");
+pub fn merge_pr(
+    pr_number: u64,
+    strategy: MergeStrategy,
+    delete_branch: bool,
+    admin: bool,
+) -> Result<()> {
+    if api::native_mode() {
+        return gh_api::merge_pr(pr_number, strategy, delete_branch);
     }
 
+    let pr_num_str = pr_number.to_string();
+    let strategy_flag = format!("--{}", strategy.as_str());
+    let mut args = vec!["pr", "merge", &pr_num_str, &strategy_flag];
+
     if delete_branch {
         args.push("--delete-branch");
     }
@@ -370,7 +543,7 @@ pub fn merge_pr(pr_number: u64, squash: bool, delete_branch: bool, admin: bool)
         args.push("--admin");
     }
 
-    let output = Command::new("gh").args(&args).output()?;
+    let output = gh_command().args(&args).output()?;
 
     if !output.status.success() {
         let stderr = String::from_utf8_lossy(&output.stderr);
@@ -383,10 +556,96 @@ pub fn merge_pr(pr_number: u64, squash: bool, delete_branch: bool, admin: bool)
     Ok(())
 }
 
+/// Whether GitHub auto-merge is already enabled on a PR.
+fn auto_merge_enabled(pr_number: u64) -> Result<bool> {
+    let output = gh_command()
+        .args([
+            "pr",
+            "view",
+            &pr_number.to_string(),
+            "--json",
+            "autoMergeRequest",
+        ])
+        .output()?;
+
+    if !output.status.success() {
+        let stderr = String::from_utf8_lossy(&output.stderr);
+        return Err(GgError::Other(format!(
+            "Failed to read auto-merge state for PR #{}: {}",
+            pr_number, stderr
+        )));
+    }
+
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    let json: serde_json::Value = serde_json::from_str(&stdout)?;
+    Ok(json.get("autoMergeRequest").is_some_and(|v| !v.is_null()))
+}
+
+/// Enable GitHub's native auto-merge on a PR.
+///
+/// GitHub merges the PR once required checks and reviews pass. This only
+/// queues the merge: the PR is still open when this returns.
+pub fn enable_auto_merge(
+    pr_number: u64,
+    strategy: MergeStrategy,
+    delete_branch: bool,
+) -> Result<crate::glab::AutoMergeResult> {
+    use crate::glab::AutoMergeResult;
+
+    if api::native_mode() {
+        return gh_api::enable_auto_merge(pr_number, strategy).map_err(|e| match e {
+            GgError::Other(message) => GgError::Other(format!(
+                "Failed to enable auto-merge for PR #{}: {}{}",
+                pr_number,
+                message,
+                auto_merge_error_hint(&message)
+            )),
+            e => e,
+        });
+    }
+
+    if auto_merge_enabled(pr_number)? {
+        return Ok(AutoMergeResult::AlreadyQueued);
+    }
+
+    let number = pr_number.to_string();
+    let strategy_flag = format!("--{}", strategy.as_str());
+    let mut args = vec!["pr", "merge", &number, "--auto", &strategy_flag];
+    if delete_branch {
+        args.push("--delete-branch");
+    }
+    let output = gh_command().args(&args).output()?;
+
+    if !output.status.success() {
+        let stderr = String::from_utf8_lossy(&output.stderr);
+        return Err(GgError::Other(format!(
+            "Failed to enable auto-merge for PR #{}: {}{}",
+            pr_number,
+            stderr.trim(),
+            auto_merge_error_hint(&stderr)
+        )));
+    }
+
+    Ok(AutoMergeResult::Queued)
+}
+
+fn auto_merge_error_hint(stderr: &str) -> &'static str {
+    if stderr.contains("clean status") {
+        " (the PR is already mergeable; run `gg land` without --auto-merge)"
+    } else if stderr.to_lowercase().contains("auto merge is not allowed") {
+        " (enable \"Allow auto-merge\" in the repository settings)"
+    } else {
+        ""
+    }
+}
+
 /// Mark PR as ready for review (convert from draft)
-#[allow(dead_code)]
 pub fn mark_ready_for_review(pr_number: u64) -> Result<()> {
-    let output = Command::new("gh")
+    if api::native_mode() {
+        return gh_api::mark_ready_for_review(pr_number);
+    }
+
+    let output = gh_command()
         .args(["pr", "ready", &pr_number.to_string()])
         .output()?;
 
@@ -404,7 +663,11 @@ pub fn mark_ready_for_review(pr_number: u64) -> Result<()> {
 /// Approve a PR
 #[allow(dead_code)]
 pub fn approve_pr(pr_number: u64) -> Result<()> {
-    let output = Command::new("gh")
+    if api::native_mode() {
+        return gh_api::approve_pr(pr_number);
+    }
+
+    let output = gh_command()
         .args(["pr", "review", &pr_number.to_string(), "--approve"])
         .output()?;
 
@@ -421,7 +684,11 @@ pub fn approve_pr(pr_number: u64) -> Result<()> {
 
 /// Check if PR has required approvals
 pub fn check_pr_approved(pr_number: u64) -> Result<bool> {
-    let output = Command::new("gh")
+    if api::native_mode() {
+        return gh_api::check_pr_approved(pr_number);
+    }
+
+    let output = gh_command()
         .args([
             "pr",
             "view",
@@ -457,7 +724,11 @@ pub enum CiStatus {
 }
 
 pub fn get_pr_ci_status(pr_number: u64) -> Result<CiStatus> {
-    let output = Command::new("gh")
+    if api::native_mode() {
+        return gh_api::get_pr_ci_status(pr_number);
+    }
+
+    let output = gh_command()
         .args([
             "pr",
             "view",
@@ -498,10 +769,249 @@ pub fn get_pr_ci_status(pr_number: u64) -> Result<CiStatus> {
     }
 }
 
+/// A single check on a PR, from `gh pr checks --json`.
+#[derive(Debug, Clone, Deserialize)]
+pub struct PrCheck {
+    pub name: String,
+    /// `pass`, `fail`, `pending`, `skipping`, or `cancel`
+    #[serde(default)]
+    pub bucket: String,
+    #[serde(default)]
+    pub workflow: String,
+    pub link: Option<String>,
+}
+
+impl PrCheck {
+    fn is_failure(&self) -> bool {
+        matches!(self.bucket.as_str(), "fail" | "cancel")
+    }
+}
+
+/// Failed checks on a PR, split by whether branch protection requires them.
+#[derive(Debug, Clone, Default)]
+pub struct FailedChecks {
+    pub required: Vec<PrCheck>,
+    pub non_required: Vec<PrCheck>,
+}
+
+impl FailedChecks {
+    pub fn is_empty(&self) -> bool {
+        self.required.is_empty() && self.non_required.is_empty()
+    }
+}
+
+/// List checks on a PR, optionally only the ones required by branch protection.
+///
+/// `gh pr checks` exits non-zero when checks are failing or pending, so the
+/// JSON on stdout is parsed regardless of the exit status.
+pub fn list_pr_checks(pr_number: u64, required_only: bool) -> Result<Vec<PrCheck>> {
+    if api::native_mode() {
+        return gh_api::list_pr_checks(pr_number, required_only);
+    }
+
+    let number = pr_number.to_string();
+    let mut args = vec![
+        "pr",
+        "checks",
+        &number,
+        "--json",
+        "name,bucket,workflow,link",
+    ];
+    if required_only {
+        args.push("--required");
+    }
+    let output = gh_command().args(&args).output()?;
+
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    if stdout.trim().is_empty() {
+        let stderr = String::from_utf8_lossy(&output.stderr);
+        if output.status.success() || stderr.contains("no required checks") {
+            return Ok(vec![]);
+        }
+        return Err(GgError::Other(format!(
+            "Failed to list checks for PR #{}: {}",
+            pr_number, stderr
+        )));
+    }
+
+    serde_json::from_str(&stdout).map_err(|e| {
+        GgError::Other(format!(
+            "Failed to parse checks for PR #{}: {}",
+            pr_number, e
+        ))
+    })
+}
+
+/// Split failed checks into required and non-required ones.
+pub fn classify_failed_checks(checks: Vec<PrCheck>, required: &[PrCheck]) -> FailedChecks {
+    let mut failed = FailedChecks::default();
+    for check in checks.into_iter().filter(PrCheck::is_failure) {
+        if required.iter().any(|r| r.name == check.name) {
+            failed.required.push(check);
+        } else {
+            failed.non_required.push(check);
+        }
+    }
+    failed
+}
+
+/// Get the failed checks on a PR, split by required/non-required.
+pub fn get_failed_checks(pr_number: u64) -> Result<FailedChecks> {
+    let checks = list_pr_checks(pr_number, false)?;
+    if !checks.iter().any(PrCheck::is_failure) {
+        return Ok(FailedChecks::default());
+    }
+    let required = list_pr_checks(pr_number, true)?;
+    Ok(classify_failed_checks(checks, &required))
+}
+
+/// Format failed checks for an error message, one line per category.
+pub fn format_failed_checks(failed: &FailedChecks) -> String {
+    let names = |checks: &[PrCheck]| {
+        checks
+            .iter()
+            .map(|c| {
+                if c.workflow.is_empty() {
+                    c.name.clone()
+                } else {
+                    format!("{} ({})", c.name, c.workflow)
+                }
+            })
+            .collect::<Vec<_>>()
+            .join(", ")
+    };
+
+    let mut out = String::new();
+    if !failed.required.is_empty() {
+        out.push_str(&format!(
+            "\n  Failed required checks: {}",
+            names(&failed.required)
+        ));
+    }
+    if !failed.non_required.is_empty() {
+        out.push_str(&format!(
+            "\n  Failed non-required checks (do not block merging): {}",
+            names(&failed.non_required)
+        ));
+    }
+    out
+}
+
+/// A GitHub Actions workflow run, from `gh run list --json`.
+#[derive(Debug, Clone, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct WorkflowRun {
+    pub database_id: u64,
+    pub name: String,
+    /// `queued`, `in_progress`, `completed`, ...
+    #[serde(default)]
+    pub status: String,
+    /// `success`, `failure`, `cancelled`, `timed_out`, ... (empty while running)
+    #[serde(default)]
+    pub conclusion: String,
+}
+
+impl WorkflowRun {
+    fn is_failure(&self) -> bool {
+        matches!(
+            self.conclusion.as_str(),
+            "failure" | "cancelled" | "timed_out" | "startup_failure"
+        )
+    }
+}
+
+/// Pick the runs to re-run. Runs still in progress cannot be re-run; with
+/// `failed_only`, successful runs are left alone too.
+pub fn select_runs_to_rerun(runs: &[WorkflowRun], failed_only: bool) -> Vec<&WorkflowRun> {
+    runs.iter()
+        .filter(|r| r.status == "completed")
+        .filter(|r| !failed_only || r.is_failure())
+        .collect()
+}
+
+/// Re-run the GitHub Actions workflow runs for a PR's head commit.
+///
+/// With `failed_only`, only failed runs are re-run, and only their failed
+/// jobs. Checks from external CI services are not covered. Returns the
+/// names of the runs that were restarted.
+pub fn rerun_pr_workflows(pr_number: u64, failed_only: bool) -> Result<Vec<String>> {
+    if api::native_mode() {
+        return gh_api::rerun_pr_workflows(pr_number, failed_only);
+    }
+
+    let output = gh_command()
+        .args([
+            "pr",
+            "view",
+            &pr_number.to_string(),
+            "--json",
+            "headRefOid",
+            "--jq",
+            ".headRefOid",
+        ])
+        .output()?;
+    if !output.status.success() {
+        let stderr = String::from_utf8_lossy(&output.stderr);
+        return Err(GgError::Other(format!(
+            "Failed to get head commit of PR #{}: {}",
+            pr_number, stderr
+        )));
+    }
+    let head_sha = String::from_utf8_lossy(&output.stdout).trim().to_string();
+
+    let output = gh_command()
+        .args([
+            "run",
+            "list",
+            "--commit",
+            &head_sha,
+            "--json",
+            "databaseId,name,status,conclusion",
+        ])
+        .output()?;
+    if !output.status.success() {
+        let stderr = String::from_utf8_lossy(&output.stderr);
+        return Err(GgError::Other(format!(
+            "Failed to list workflow runs for PR #{}: {}",
+            pr_number, stderr
+        )));
+    }
+    let runs: Vec<WorkflowRun> = serde_json::from_slice(&output.stdout).map_err(|e| {
+        GgError::Other(format!(
+            "Failed to parse workflow runs for PR #{}: {}",
+            pr_number, e
+        ))
+    })?;
+
+    let mut restarted = Vec::new();
+    for run in select_runs_to_rerun(&runs, failed_only) {
+        let id = run.database_id.to_string();
+        let mut args = vec!["run", "rerun", id.as_str()];
+        if failed_only {
+            args.push("--failed");
+        }
+        let output = gh_command().args(&args).output()?;
+        if !output.status.success() {
+            let stderr = String::from_utf8_lossy(&output.stderr);
+            return Err(GgError::Other(format!(
+                "Failed to re-run workflow '{}' for PR #{}: {}",
+                run.name, pr_number, stderr
+            )));
+        }
+        restarted.push(run.name.clone());
+    }
+
+    Ok(restarted)
+}
+
 /// List PRs for a specific head branch
 /// Returns a list of PR numbers for open PRs with the given head branch
 pub fn list_prs_for_branch(branch: &str) -> Result<Vec<u64>> {
-    let output = Command::new("gh")
+    if api::native_mode() {
+        return gh_api::list_prs_for_branch(branch);
+    }
+
+    let output = gh_command()
         .args([
             "pr",
             "list",
@@ -542,34 +1052,110 @@ pub fn list_prs_for_branch(branch: &str) -> Result<Vec<u64>> {
     Ok(prs)
 }
 
-/// A GitHub issue comment (which includes PR comments on the Conversation tab).
+/// An open PR authored by the current user, as listed by `gh pr list`.
 #[derive(Debug, Clone, Deserialize)]
-pub struct IssueComment {
-    pub id: u64,
-    pub body: String,
+#[serde(rename_all = "camelCase")]
+pub struct OpenPr {
+    pub number: u64,
+    pub title: String,
+    pub head_ref_name: String,
+    pub base_ref_name: String,
 }
 
-/// List all comments on a PR (issue comments, i.e. Conversation-tab comments).
-///
-/// Paginates manually (100 per page) because `gh api --paginate` without
-/// `--slurp` concatenates raw JSON arrays, which is not valid JSON — parsing
-/// would fail as soon as a PR has more than one page of comments. We iterate
-/// pages until an empty array comes back.
-pub fn list_issue_comments(pr_number: u64) -> Result<Vec<IssueComment>> {
-    let mut all = Vec::new();
-    let mut page = 1u32;
+/// List open PRs authored by the authenticated user.
+pub fn list_my_open_prs() -> Result<Vec<OpenPr>> {
+    if api::native_mode() {
+        return gh_api::list_my_open_prs();
+    }
 
-    loop {
-        let endpoint = format!(
-            "repos/{{owner}}/{{repo}}/issues/{}/comments?per_page=100&page={}",
-            pr_number, page
-        );
-        let output = Command::new("gh").args(["api", &endpoint]).output()?;
+    let output = gh_command()
+        .args([
+            "pr",
+            "list",
+            "--author",
+            "@me",
+            "--state",
+            "open",
+            "--limit",
+            "200",
+            "--json",
+            "number,title,headRefName,baseRefName",
+        ])
+        .output()?;
 
-        if !output.status.success() {
-            let stderr = String::from_utf8_lossy(&output.stderr);
-            return Err(GgError::Other(format!(
-                "Failed to list comments for PR #{} (page {}): {}",
+    if !output.status.success() {
+        let stderr = String::from_utf8_lossy(&output.stderr);
+        return Err(GgError::Other(format!(
+            "Failed to list open PRs: {}",
+            stderr
+        )));
+    }
+
+    serde_json::from_slice(&output.stdout)
+        .map_err(|e| GgError::Other(format!("Failed to parse PR list: {}", e)))
+}
+
+/// Rename a branch on GitHub. Open PRs whose head or base is the branch
+/// follow the rename.
+pub fn rename_branch(branch: &str, new_name: &str) -> Result<()> {
+    if api::native_mode() {
+        return gh_api::rename_branch(branch, new_name);
+    }
+
+    let output = gh_command()
+        .args([
+            "api",
+            "-X",
+            "POST",
+            &format!("repos/{{owner}}/{{repo}}/branches/{}/rename", branch),
+            "-f",
+            &format!("new_name={}", new_name),
+        ])
+        .output()?;
+
+    if !output.status.success() {
+        let stderr = String::from_utf8_lossy(&output.stderr);
+        return Err(GgError::Other(format!(
+            "Failed to rename branch {} to {}: {}",
+            branch, new_name, stderr
+        )));
+    }
+
+    Ok(())
+}
+
+/// A GitHub issue comment (which includes PR comments on the Conversation tab).
+#[derive(Debug, Clone, Deserialize)]
+pub struct IssueComment {
+    pub id: u64,
+    pub body: String,
+}
+
+/// List all comments on a PR (issue comments, i.e. Conversation-tab comments).
+///
+/// Paginates manually (100 per page) because `gh api --paginate` without
+/// `--slurp` concatenates raw JSON arrays, which is not valid JSON — parsing
+/// would fail as soon as a PR has more than one page of comments. We iterate
+/// pages until an empty array comes back.
+pub fn list_issue_comments(pr_number: u64) -> Result<Vec<IssueComment>> {
+    if api::native_mode() {
+        return gh_api::list_issue_comments(pr_number);
+    }
+
+    let mut all = Vec::new();
+    let mut page = 1u32;
+
+    loop {
+        let endpoint = format!(
+            "repos/{{owner}}/{{repo}}/issues/{}/comments?per_page=100&page={}",
+            pr_number, page
+        );
+        let output = gh_command().args(["api", &endpoint]).output()?;
+
+        if !output.status.success() {
+            let stderr = String::from_utf8_lossy(&output.stderr);
+            return Err(GgError::Other(format!(
+                "Failed to list comments for PR #{} (page {}): {}",
                 pr_number, page, stderr
             )));
         }
@@ -596,10 +1182,101 @@ pub fn list_issue_comments(pr_number: u64) -> Result<Vec<IssueComment>> {
     Ok(all)
 }
 
+/// Branch protection settings relevant to what gg pushes and lands.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct BranchRules {
+    pub required_signatures: bool,
+    pub required_linear_history: bool,
+}
+
+impl BranchRules {
+    pub(crate) fn merge(self, other: BranchRules) -> BranchRules {
+        BranchRules {
+            required_signatures: self.required_signatures || other.required_signatures,
+            required_linear_history: self.required_linear_history || other.required_linear_history,
+        }
+    }
+}
+
+/// Parse the active rules for a branch (`rules/branches/{branch}`), which
+/// covers repository and organization rulesets.
+pub(crate) fn parse_ruleset_rules(json: &str) -> BranchRules {
+    #[derive(Deserialize)]
+    struct Rule {
+        #[serde(rename = "type")]
+        rule_type: String,
+    }
+
+    let rules: Vec<Rule> = serde_json::from_str(json).unwrap_or_default();
+    BranchRules {
+        required_signatures: rules.iter().any(|r| r.rule_type == "required_signatures"),
+        required_linear_history: rules
+            .iter()
+            .any(|r| r.rule_type == "required_linear_history"),
+    }
+}
+
+/// Parse classic branch protection (`branches/{branch}/protection`).
+pub(crate) fn parse_classic_protection(json: &str) -> BranchRules {
+    let value: serde_json::Value = serde_json::from_str(json).unwrap_or_default();
+    let enabled = |key: &str| value[key]["enabled"].as_bool().unwrap_or(false);
+    BranchRules {
+        required_signatures: enabled("required_signatures"),
+        required_linear_history: enabled("required_linear_history"),
+    }
+}
+
+/// Get signed-commit and linear-history requirements for `branch`, from
+/// both rulesets and classic branch protection.
+///
+/// Classic protection is only readable by admins; a failed lookup there is
+/// treated as "no classic protection" rather than an error.
+pub fn get_branch_rules(branch: &str) -> Result<BranchRules> {
+    if api::native_mode() {
+        return gh_api::get_branch_rules(branch);
+    }
+
+    let output = gh_command()
+        .args([
+            "api",
+            &format!("repos/{{owner}}/{{repo}}/rules/branches/{}", branch),
+        ])
+        .output()?;
+
+    if !output.status.success() {
+        let stderr = String::from_utf8_lossy(&output.stderr);
+        return Err(GgError::Other(format!(
+            "Failed to read rules for branch {}: {}",
+            branch, stderr
+        )));
+    }
+    let rules = parse_ruleset_rules(&String::from_utf8_lossy(&output.stdout));
+
+    let classic = gh_command()
+        .args([
+            "api",
+            &format!("repos/{{owner}}/{{repo}}/branches/{}/protection", branch),
+        ])
+        .output()?;
+    if classic.status.success() {
+        return Ok(
+            rules.merge(parse_classic_protection(&String::from_utf8_lossy(
+                &classic.stdout,
+            ))),
+        );
+    }
+
+    Ok(rules)
+}
+
 /// Post a new comment on a PR.
 pub fn create_issue_comment(pr_number: u64, body: &str) -> Result<()> {
+    if api::native_mode() {
+        return gh_api::create_issue_comment(pr_number, body);
+    }
+
     let endpoint = format!("repos/{{owner}}/{{repo}}/issues/{}/comments", pr_number);
-    let output = Command::new("gh")
+    let output = gh_command()
         .args([
             "api",
             "-X",
@@ -622,8 +1299,12 @@ pub fn create_issue_comment(pr_number: u64, body: &str) -> Result<()> {
 
 /// Edit an existing PR comment by its comment id.
 pub fn update_issue_comment(comment_id: u64, body: &str) -> Result<()> {
+    if api::native_mode() {
+        return gh_api::update_issue_comment(comment_id, body);
+    }
+
     let endpoint = format!("repos/{{owner}}/{{repo}}/issues/comments/{}", comment_id);
-    let output = Command::new("gh")
+    let output = gh_command()
         .args([
             "api",
             "-X",
@@ -646,8 +1327,12 @@ pub fn update_issue_comment(comment_id: u64, body: &str) -> Result<()> {
 
 /// Delete a PR comment by its comment id.
 pub fn delete_issue_comment(comment_id: u64) -> Result<()> {
+    if api::native_mode() {
+        return gh_api::delete_issue_comment(comment_id);
+    }
+
     let endpoint = format!("repos/{{owner}}/{{repo}}/issues/comments/{}", comment_id);
-    let output = Command::new("gh")
+    let output = gh_command()
         .args(["api", "-X", "DELETE", &endpoint])
         .output()?;
 
@@ -661,10 +1346,499 @@ pub fn delete_issue_comment(comment_id: u64) -> Result<()> {
     Ok(())
 }
 
+/// An inline review thread on a PR, from the GraphQL `reviewThreads` field.
+#[derive(Debug, Clone, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ReviewThread {
+    pub id: String,
+    pub is_resolved: bool,
+    #[serde(default)]
+    pub is_outdated: bool,
+    pub path: Option<String>,
+    pub line: Option<u64>,
+    pub original_line: Option<u64>,
+    pub comments: ReviewCommentConnection,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+pub struct ReviewCommentConnection {
+    pub nodes: Vec<ReviewComment>,
+}
+
+/// A single comment inside a review thread.
+#[derive(Debug, Clone, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ReviewComment {
+    pub author: Option<ReviewAuthor>,
+    pub body: String,
+    pub created_at: String,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+pub struct ReviewAuthor {
+    pub login: String,
+}
+
+#[derive(Debug, Deserialize)]
+struct ReviewThreadsResponse {
+    data: ReviewThreadsData,
+}
+
+#[derive(Debug, Deserialize)]
+struct ReviewThreadsData {
+    repository: ReviewThreadsRepository,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct ReviewThreadsRepository {
+    pull_request: ReviewThreadsPullRequest,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct ReviewThreadsPullRequest {
+    review_threads: ReviewThreadConnection,
+}
+
+#[derive(Debug, Deserialize)]
+struct ReviewThreadConnection {
+    nodes: Vec<ReviewThread>,
+}
+
+pub(crate) const REVIEW_THREADS_QUERY: &str =
+    "query($owner: String!, $repo: String!, $number: Int!) { \
+repository(owner: $owner, name: $repo) { pullRequest(number: $number) { \
+reviewThreads(first: 100) { nodes { id isResolved isOutdated path line originalLine \
+comments(first: 100) { nodes { author { login } body createdAt } } } } } } }";
+
+pub(crate) const RESOLVE_THREAD_MUTATION: &str = "mutation($id: ID!) { \
+resolveReviewThread(input: { threadId: $id }) { thread { isResolved } } }";
+
+/// List inline review threads on a PR.
+///
+/// Review threads are only exposed through GraphQL; the REST review-comment
+/// endpoint has no notion of resolution. `{owner}`/`{repo}` are expanded by
+/// `gh api` from the current repository.
+pub fn list_review_threads(pr_number: u64) -> Result<Vec<ReviewThread>> {
+    if api::native_mode() {
+        return gh_api::list_review_threads(pr_number);
+    }
+
+    let output = gh_command()
+        .args([
+            "api",
+            "graphql",
+            "-F",
+            "owner={owner}",
+            "-F",
+            "repo={repo}",
+            "-F",
+            &format!("number={}", pr_number),
+            "-f",
+            &format!("query={}", REVIEW_THREADS_QUERY),
+        ])
+        .output()?;
+
+    if !output.status.success() {
+        let stderr = String::from_utf8_lossy(&output.stderr);
+        return Err(GgError::Other(format!(
+            "Failed to list review threads for PR #{}: {}",
+            pr_number, stderr
+        )));
+    }
+
+    let response = serde_json::from_slice(&output.stdout).map_err(|e| {
+        GgError::Other(format!(
+            "Failed to parse review threads for PR #{}: {}",
+            pr_number, e
+        ))
+    })?;
+    parse_review_threads(response, pr_number)
+}
+
+/// The threads of a [`REVIEW_THREADS_QUERY`] response.
+pub(crate) fn parse_review_threads(
+    response: serde_json::Value,
+    pr_number: u64,
+) -> Result<Vec<ReviewThread>> {
+    let response: ReviewThreadsResponse = serde_json::from_value(response).map_err(|e| {
+        GgError::Other(format!(
+            "Failed to parse review threads for PR #{}: {}",
+            pr_number, e
+        ))
+    })?;
+    Ok(response.data.repository.pull_request.review_threads.nodes)
+}
+
+/// Mark a review thread as resolved.
+pub fn resolve_review_thread(thread_id: &str) -> Result<()> {
+    if api::native_mode() {
+        return gh_api::resolve_review_thread(thread_id);
+    }
+
+    let output = gh_command()
+        .args([
+            "api",
+            "graphql",
+            "-f",
+            &format!("id={}", thread_id),
+            "-f",
+            &format!("query={}", RESOLVE_THREAD_MUTATION),
+        ])
+        .output()?;
+
+    if !output.status.success() {
+        let stderr = String::from_utf8_lossy(&output.stderr);
+        return Err(GgError::Other(format!(
+            "Failed to resolve review thread {}: {}",
+            thread_id, stderr
+        )));
+    }
+    Ok(())
+}
+
+pub(crate) const MERGE_QUEUE_QUERY: &str =
+    "query($owner: String!, $repo: String!, $branch: String!) { \
+repository(owner: $owner, name: $repo) { mergeQueue(branch: $branch) { id } } }";
+
+pub(crate) const MERGE_QUEUE_ENTRY_QUERY: &str =
+    "query($owner: String!, $repo: String!, $number: Int!) { \
+repository(owner: $owner, name: $repo) { pullRequest(number: $number) { \
+id state mergeQueueEntry { position state } } } }";
+
+pub(crate) const ENQUEUE_MUTATION: &str = "mutation($id: ID!) { \
+enqueuePullRequest(input: { pullRequestId: $id }) { mergeQueueEntry { position } } }";
+
+/// A PR with its merge queue entry, as returned by [`MERGE_QUEUE_ENTRY_QUERY`]
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub(crate) struct MergeQueuePull {
+    pub id: String,
+    pub state: String,
+    pub merge_queue_entry: Option<MergeQueueEntry>,
+}
+
+#[derive(Debug, Deserialize)]
+pub(crate) struct MergeQueueEntry {
+    pub position: Option<usize>,
+    pub state: String,
+}
+
+/// Extract `data.repository.pullRequest` from a [`MERGE_QUEUE_ENTRY_QUERY`]
+/// response.
+pub(crate) fn parse_merge_queue_pull(response: &serde_json::Value) -> Result<MergeQueuePull> {
+    serde_json::from_value(response["data"]["repository"]["pullRequest"].clone())
+        .map_err(|e| GgError::Other(format!("Failed to parse merge queue status: {}", e)))
+}
+
+/// Map a PR's merge queue entry onto the merge train states `gg land`
+/// already understands. GitHub positions are 0-based; trains count from 1.
+pub(crate) fn merge_queue_info(pull: &MergeQueuePull) -> crate::glab::MergeTrainInfo {
+    merge_queue_entry_info(&pull.state, pull.merge_queue_entry.as_ref())
+}
+
+fn merge_queue_entry_info(
+    pr_state: &str,
+    entry: Option<&MergeQueueEntry>,
+) -> crate::glab::MergeTrainInfo {
+    use crate::glab::{MergeTrainInfo, MergeTrainStatus};
+
+    let Some(entry) = entry else {
+        return MergeTrainInfo {
+            status: if pr_state == "MERGED" {
+                MergeTrainStatus::Merged
+            } else {
+                MergeTrainStatus::Idle
+            },
+            position: None,
+            pipeline_running: false,
+        };
+    };
+    let status = match entry.state.as_str() {
+        "QUEUED" | "AWAITING_CHECKS" => MergeTrainStatus::Fresh,
+        "MERGEABLE" | "LOCKED" => MergeTrainStatus::Merging,
+        "UNMERGEABLE" => MergeTrainStatus::SkipMerged,
+        _ => MergeTrainStatus::Unknown,
+    };
+    MergeTrainInfo {
+        status,
+        position: Some(entry.position.unwrap_or(0) + 1),
+        pipeline_running: entry.state == "AWAITING_CHECKS",
+    }
+}
+
+/// Run a GraphQL document through `gh api graphql`. `{owner}`/`{repo}` in
+/// `fields` are expanded by gh from the current repository.
+fn gh_graphql(query: &str, fields: &[(&str, String)], what: &str) -> Result<serde_json::Value> {
+    let mut args = vec!["api".to_string(), "graphql".to_string()];
+    for (name, value) in fields {
+        args.push("-F".to_string());
+        args.push(format!("{}={}", name, value));
+    }
+    args.push("-f".to_string());
+    args.push(format!("query={}", query));
+
+    let output = gh_command().args(&args).output()?;
+    if !output.status.success() {
+        let stderr = String::from_utf8_lossy(&output.stderr);
+        return Err(GgError::Other(format!("Failed to {}: {}", what, stderr)));
+    }
+    serde_json::from_slice(&output.stdout)
+        .map_err(|e| GgError::Other(format!("Failed to {}: {}", what, e)))
+}
+
+fn repo_fields() -> Vec<(&'static str, String)> {
+    vec![
+        ("owner", "{owner}".to_string()),
+        ("repo", "{repo}".to_string()),
+    ]
+}
+
+/// Whether `branch` requires a merge queue
+pub fn check_merge_queue_enabled(branch: &str) -> Result<bool> {
+    if api::native_mode() {
+        return gh_api::check_merge_queue_enabled(branch);
+    }
+
+    let mut fields = repo_fields();
+    fields.push(("branch", branch.to_string()));
+    let response = gh_graphql(MERGE_QUEUE_QUERY, &fields, "check the merge queue")?;
+    Ok(!response["data"]["repository"]["mergeQueue"].is_null())
+}
+
+fn merge_queue_pull(pr_number: u64) -> Result<MergeQueuePull> {
+    let mut fields = repo_fields();
+    fields.push(("number", pr_number.to_string()));
+    let response = gh_graphql(
+        MERGE_QUEUE_ENTRY_QUERY,
+        &fields,
+        &format!("get merge queue status for PR #{}", pr_number),
+    )?;
+    parse_merge_queue_pull(&response)
+}
+
+/// Where a PR is in its base branch's merge queue
+pub fn get_merge_queue_status(pr_number: u64) -> Result<crate::glab::MergeTrainInfo> {
+    if api::native_mode() {
+        return gh_api::get_merge_queue_status(pr_number);
+    }
+    Ok(merge_queue_info(&merge_queue_pull(pr_number)?))
+}
+
+/// Most PRs asked about in one [`prs_status_query`], to stay well within
+/// GitHub's GraphQL node limits
+pub(crate) const PRS_PER_QUERY: usize = 50;
+
+/// A PR's state, CI, approval and merge queue status, as fetched in bulk
+/// by [`get_prs_status`]
+#[derive(Debug, Clone)]
+pub struct PrStatus {
+    pub info: PrInfo,
+    pub ci_status: CiStatus,
+    /// Same rule as [`check_pr_approved`]: approved, or no review required
+    pub approved: bool,
+    pub merge_queue: crate::glab::MergeTrainInfo,
+}
+
+/// GraphQL document asking for every PR in `pr_numbers` at once, one
+/// `pr<N>: pullRequest(number: N)` alias each.
+pub(crate) fn prs_status_query(pr_numbers: &[u64]) -> String {
+    let mut query = String::from(
+        "query($owner: String!, $repo: String!) { repository(owner: $owner, name: $repo) { ",
+    );
+    for number in pr_numbers {
+        query.push_str(&format!(
+            "pr{n}: pullRequest(number: {n}) {{ number title state url headRefName baseRefName \
+             isDraft mergeable reviewDecision updatedAt mergeQueueEntry {{ position state }} \
+             commits(last: 1) {{ nodes {{ commit {{ statusCheckRollup {{ state }} }} }} }} }} ",
+            n = number
+        ));
+    }
+    query.push_str("} }");
+    query
+}
+
+/// Extract every PR of a [`prs_status_query`] response, keyed by number.
+pub(crate) fn parse_prs_status(response: &serde_json::Value) -> Result<HashMap<u64, PrStatus>> {
+    let pulls = response["data"]["repository"]
+        .as_object()
+        .ok_or_else(|| GgError::Other("Failed to parse PR status: no repository".to_string()))?;
+    let mut statuses = HashMap::new();
+    for pull in pulls.values().filter(|pull| !pull.is_null()) {
+        let mut pr_json: GhPrJson = serde_json::from_value(pull.clone())
+            .map_err(|e| GgError::Other(format!("Failed to parse PR status: {}", e)))?;
+        let ci_status = rollup_ci_status(
+            pr_json.commits["nodes"][0]["commit"]["statusCheckRollup"]["state"].as_str(),
+        );
+        let approved = matches!(
+            pr_json.review_decision.as_deref(),
+            None | Some("") | Some("APPROVED")
+        );
+        let merge_queue = merge_queue_entry_info(
+            &pr_json.state.to_uppercase(),
+            pr_json.merge_queue_entry.take().as_ref(),
+        );
+        let info = convert_pr_json(pr_json);
+        statuses.insert(
+            info.number,
+            PrStatus {
+                info,
+                ci_status,
+                approved,
+                merge_queue,
+            },
+        );
+    }
+    Ok(statuses)
+}
+
+/// CI status from a commit's `statusCheckRollup.state`
+fn rollup_ci_status(state: Option<&str>) -> CiStatus {
+    match state {
+        Some("SUCCESS") => CiStatus::Success,
+        Some("FAILURE") | Some("ERROR") => CiStatus::Failed,
+        Some("PENDING") | Some("EXPECTED") => CiStatus::Pending,
+        _ => CiStatus::Unknown,
+    }
+}
+
+/// State, CI, approval and merge queue status of several PRs, with one
+/// GraphQL request per [`PRS_PER_QUERY`] PRs instead of four calls per PR.
+/// Fails as a whole when any PR cannot be resolved.
+pub fn get_prs_status(pr_numbers: &[u64]) -> Result<HashMap<u64, PrStatus>> {
+    if api::native_mode() {
+        return gh_api::get_prs_status(pr_numbers);
+    }
+
+    let mut statuses = HashMap::new();
+    for chunk in pr_numbers.chunks(PRS_PER_QUERY) {
+        let response = gh_graphql(&prs_status_query(chunk), &repo_fields(), "get PR status")?;
+        statuses.extend(parse_prs_status(&response)?);
+    }
+    Ok(statuses)
+}
+
+/// Add a PR to its base branch's merge queue
+pub fn add_to_merge_queue(pr_number: u64) -> Result<crate::glab::AutoMergeResult> {
+    use crate::glab::AutoMergeResult;
+
+    if api::native_mode() {
+        return gh_api::add_to_merge_queue(pr_number);
+    }
+
+    let pull = merge_queue_pull(pr_number)?;
+    if pull.merge_queue_entry.is_some() {
+        return Ok(AutoMergeResult::AlreadyQueued);
+    }
+    gh_graphql(
+        ENQUEUE_MUTATION,
+        &[("id", pull.id)],
+        &format!("add PR #{} to the merge queue", pr_number),
+    )?;
+    Ok(AutoMergeResult::Queued)
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
 
+    #[test]
+    fn test_merge_queue_info_maps_entry_states() {
+        use crate::glab::MergeTrainStatus;
+
+        let pull = |state: &str, entry: Option<(usize, &str)>| MergeQueuePull {
+            id: "PR_1".to_string(),
+            state: state.to_string(),
+            merge_queue_entry: entry.map(|(position, state)| MergeQueueEntry {
+                position: Some(position),
+                state: state.to_string(),
+            }),
+        };
+
+        let info = merge_queue_info(&pull("OPEN", Some((0, "AWAITING_CHECKS"))));
+        assert_eq!(info.status, MergeTrainStatus::Fresh);
+        assert_eq!(info.position, Some(1));
+        assert!(info.pipeline_running);
+
+        let info = merge_queue_info(&pull("OPEN", Some((2, "MERGEABLE"))));
+        assert_eq!(info.status, MergeTrainStatus::Merging);
+        assert_eq!(info.position, Some(3));
+
+        let info = merge_queue_info(&pull("OPEN", Some((0, "UNMERGEABLE"))));
+        assert_eq!(info.status, MergeTrainStatus::SkipMerged);
+
+        assert_eq!(
+            merge_queue_info(&pull("MERGED", None)).status,
+            MergeTrainStatus::Merged
+        );
+        assert_eq!(
+            merge_queue_info(&pull("OPEN", None)).status,
+            MergeTrainStatus::Idle
+        );
+    }
+
+    #[test]
+    fn test_parse_merge_queue_pull() {
+        let response = serde_json::json!({"data": {"repository": {"pullRequest": {
+            "id": "PR_kw", "state": "OPEN",
+            "mergeQueueEntry": {"position": 1, "state": "QUEUED"}
+        }}}});
+        let pull = parse_merge_queue_pull(&response).unwrap();
+        assert_eq!(pull.id, "PR_kw");
+        assert_eq!(pull.merge_queue_entry.unwrap().position, Some(1));
+    }
+
+    #[test]
+    fn test_prs_status_query_aliases_each_pr() {
+        let query = prs_status_query(&[12, 13]);
+        assert!(query.contains("pr12: pullRequest(number: 12) {"));
+        assert!(query.contains("pr13: pullRequest(number: 13) {"));
+        assert_eq!(query.matches('{').count(), query.matches('}').count());
+    }
+
+    #[test]
+    fn test_parse_prs_status() {
+        let response = serde_json::json!({"data": {"repository": {
+            "pr12": {
+                "number": 12, "title": "Add parser", "state": "OPEN",
+                "url": "https://github.com/o/r/pull/12",
+                "headRefName": "nacho/parser--c-abc1234", "baseRefName": "main",
+                "isDraft": false, "mergeable": "MERGEABLE", "reviewDecision": null,
+                "updatedAt": "2026-10-16T10:00:00Z",
+                "mergeQueueEntry": {"position": 0, "state": "QUEUED"},
+                "commits": {"nodes": [{"commit": {"statusCheckRollup": {"state": "SUCCESS"}}}]}
+            },
+            "pr13": {
+                "number": 13, "title": "Use parser", "state": "OPEN",
+                "url": "https://github.com/o/r/pull/13",
+                "headRefName": "nacho/parser--c-def5678", "baseRefName": "nacho/parser--c-abc1234",
+                "isDraft": true, "mergeable": "UNKNOWN", "reviewDecision": "REVIEW_REQUIRED",
+                "updatedAt": null, "mergeQueueEntry": null,
+                "commits": {"nodes": [{"commit": {"statusCheckRollup": null}}]}
+            }
+        }}});
+        let statuses = parse_prs_status(&response).unwrap();
+        assert_eq!(statuses.len(), 2);
+
+        let first = &statuses[&12];
+        assert_eq!(first.info.state, PrState::Open);
+        assert!(first.info.mergeable);
+        assert_eq!(first.ci_status, CiStatus::Success);
+        // No review required counts as approved, as in check_pr_approved
+        assert!(first.approved);
+        assert_eq!(first.merge_queue.position, Some(1));
+
+        let second = &statuses[&13];
+        assert_eq!(second.info.state, PrState::Draft);
+        assert_eq!(second.ci_status, CiStatus::Unknown);
+        assert!(!second.approved);
+        assert_eq!(
+            second.merge_queue.status,
+            crate::glab::MergeTrainStatus::Idle
+        );
+    }
+
     #[test]
     fn test_pr_state_equality() {
         assert_eq!(PrState::Open, PrState::Open);
@@ -690,10 +1864,12 @@ mod tests {
             state: PrState::Open,
             url: "https://github.com/test/repo/pull/42".to_string(),
             head_branch: Some("user/stack--c-abc1234".to_string()),
+            base_branch: None,
             draft: false,
             approved: true,
             mergeable: true,
             changes_requested: false,
+            updated_at_ms: None,
         };
         assert_eq!(info.number, 42);
         assert_eq!(info.title, "Test PR");
@@ -796,4 +1972,141 @@ mod tests {
         assert_eq!(comments[0].id, 1);
         assert_eq!(comments[1].body, "second <!-- gg:stack-nav -->");
     }
+
+    #[test]
+    fn test_review_threads_response_deserialization() {
+        let json = r#"{"data": {"repository": {"pullRequest": {"reviewThreads": {"nodes": [
+            {
+                "id": "PRRT_abc",
+                "isResolved": false,
+                "isOutdated": true,
+                "path": "src/lib.rs",
+                "line": null,
+                "originalLine": 12,
+                "comments": {"nodes": [
+                    {"author": {"login": "alice"}, "body": "nit", "createdAt": "2026-01-01T00:00:00Z"},
+                    {"author": null, "body": "ghost", "createdAt": "2026-01-02T00:00:00Z"}
+                ]}
+            }
+        ]}}}}}"#;
+        let parsed: ReviewThreadsResponse = serde_json::from_str(json).expect("should deserialize");
+        let threads = parsed.data.repository.pull_request.review_threads.nodes;
+        assert_eq!(threads.len(), 1);
+        assert_eq!(threads[0].id, "PRRT_abc");
+        assert!(!threads[0].is_resolved);
+        assert!(threads[0].is_outdated);
+        assert_eq!(threads[0].line, None);
+        assert_eq!(threads[0].original_line, Some(12));
+        assert_eq!(threads[0].comments.nodes.len(), 2);
+        assert!(threads[0].comments.nodes[1].author.is_none());
+    }
+
+    fn check(name: &str, bucket: &str) -> PrCheck {
+        PrCheck {
+            name: name.to_string(),
+            bucket: bucket.to_string(),
+            workflow: String::new(),
+            link: None,
+        }
+    }
+
+    #[test]
+    fn test_pr_check_deserialization() {
+        let json = r#"[{"name": "build", "bucket": "fail", "workflow": "CI", "link": "https://example.com/1"}]"#;
+        let checks: Vec<PrCheck> = serde_json::from_str(json).expect("should deserialize");
+        assert_eq!(checks[0].name, "build");
+        assert_eq!(checks[0].workflow, "CI");
+        assert!(checks[0].is_failure());
+    }
+
+    #[test]
+    fn test_classify_failed_checks() {
+        let all = vec![
+            check("build", "fail"),
+            check("lint", "fail"),
+            check("docs", "cancel"),
+            check("test", "pass"),
+            check("optional-e2e", "skipping"),
+        ];
+        let required = vec![check("build", "fail"), check("test", "pass")];
+        let failed = classify_failed_checks(all, &required);
+        let names = |c: &[PrCheck]| c.iter().map(|c| c.name.clone()).collect::<Vec<_>>();
+        assert_eq!(names(&failed.required), vec!["build"]);
+        assert_eq!(names(&failed.non_required), vec!["lint", "docs"]);
+    }
+
+    #[test]
+    fn test_format_failed_checks() {
+        let failed = FailedChecks {
+            required: vec![PrCheck {
+                workflow: "CI".to_string(),
+                ..check("build", "fail")
+            }],
+            non_required: vec![check("lint", "fail")],
+        };
+        assert_eq!(
+            format_failed_checks(&failed),
+            "\n  Failed required checks: build (CI)\n  Failed non-required checks (do not block merging): lint"
+        );
+        assert_eq!(format_failed_checks(&FailedChecks::default()), "");
+    }
+
+    #[test]
+    fn test_auto_merge_error_hint() {
+        assert!(
+            auto_merge_error_hint("Pull request is in clean status").contains("already mergeable")
+        );
+        assert!(
+            auto_merge_error_hint("Auto merge is not allowed for this repository")
+                .contains("Allow auto-merge")
+        );
+        assert_eq!(auto_merge_error_hint("something else"), "");
+    }
+
+    #[test]
+    fn test_parse_branch_rules() {
+        let rulesets =
+            r#"[{"type": "required_signatures", "ruleset_id": 1}, {"type": "pull_request"}]"#;
+        assert_eq!(
+            parse_ruleset_rules(rulesets),
+            BranchRules {
+                required_signatures: true,
+                required_linear_history: false,
+            }
+        );
+
+        let classic = r#"{"required_signatures": {"enabled": false}, "required_linear_history": {"enabled": true}}"#;
+        assert_eq!(
+            parse_classic_protection(classic),
+            BranchRules {
+                required_signatures: false,
+                required_linear_history: true,
+            }
+        );
+
+        assert_eq!(
+            parse_ruleset_rules(rulesets).merge(parse_classic_protection(classic)),
+            BranchRules {
+                required_signatures: true,
+                required_linear_history: true,
+            }
+        );
+        assert_eq!(parse_ruleset_rules("not json"), BranchRules::default());
+    }
+
+    #[test]
+    fn select_runs_to_rerun_skips_running_and_passing() {
+        let runs: Vec<WorkflowRun> = serde_json::from_str(
+            r#"[
+                {"databaseId": 1, "name": "ci", "status": "completed", "conclusion": "failure"},
+                {"databaseId": 2, "name": "lint", "status": "completed", "conclusion": "success"},
+                {"databaseId": 3, "name": "e2e", "status": "in_progress", "conclusion": ""}
+            ]"#,
+        )
+        .unwrap();
+
+        let ids = |runs: Vec<&WorkflowRun>| runs.iter().map(|r| r.database_id).collect::<Vec<_>>();
+        assert_eq!(ids(select_runs_to_rerun(&runs, true)), vec![1]);
+        assert_eq!(ids(select_runs_to_rerun(&runs, false)), vec![1, 2]);
+    }
 }
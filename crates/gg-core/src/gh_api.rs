@@ -0,0 +1,947 @@
+//! GitHub REST backend for `api_mode: native`.
+//!
+//! Mirrors the `gh.rs` functions, returning the same types, so no gg
+//! command needs gh. The token comes from `GH_TOKEN` / `GITHUB_TOKEN`,
+//! falling back to `gh auth token` when gh happens to be installed.
+
+use std::collections::HashMap;
+
+use serde::Deserialize;
+use serde_json::{json, Value};
+
+use crate::api;
+use crate::config::MergeStrategy;
+use crate::error::{GgError, Result};
+use crate::gh::{
+    self, BranchRules, CiStatus, IssueComment, OpenPr, PrCheck, PrCreationResult, PrInfo, PrState,
+    ReviewThread, WorkflowRun,
+};
+use crate::glab::{AutoMergeResult, MergeTrainInfo};
+use crate::relative_time;
+
+const NOT_AUTHENTICATED: &str =
+    "Not authenticated with GitHub. Set GH_TOKEN or GITHUB_TOKEN, or run `gh auth login` first.";
+
+struct Context {
+    /// REST root (`https://api.github.com` or `https://<host>/api/v3`)
+    api: String,
+    owner: String,
+    repo: String,
+    token: String,
+}
+
+#[derive(Debug, Deserialize)]
+struct PullJson {
+    number: u64,
+    #[serde(default)]
+    node_id: String,
+    title: String,
+    #[serde(default)]
+    body: Option<String>,
+    state: String,
+    #[serde(default)]
+    merged: bool,
+    #[serde(default)]
+    merged_at: Option<String>,
+    #[serde(default)]
+    draft: bool,
+    mergeable: Option<bool>,
+    #[serde(default)]
+    auto_merge: Option<Value>,
+    html_url: String,
+    head: RefJson,
+    base: RefJson,
+    updated_at: Option<String>,
+}
+
+#[derive(Debug, Deserialize)]
+struct RefJson {
+    #[serde(rename = "ref")]
+    ref_name: String,
+    #[serde(default)]
+    sha: String,
+}
+
+#[derive(Debug, Deserialize)]
+struct ReviewJson {
+    state: String,
+    user: Option<UserJson>,
+}
+
+#[derive(Debug, Deserialize)]
+struct UserJson {
+    login: String,
+}
+
+fn api_root(host: &str) -> String {
+    if host.eq_ignore_ascii_case("github.com") {
+        "https://api.github.com".to_string()
+    } else {
+        format!("https://{}/api/v3", host)
+    }
+}
+
+fn context() -> Result<Context> {
+    let url = api::remote_url()?;
+    let (host, path) = api::split_remote(&url).ok_or_else(|| {
+        GgError::Other(format!("Could not parse owner/repo from remote: {}", url))
+    })?;
+    let (owner, repo) = path
+        .split_once('/')
+        .ok_or_else(|| GgError::Other(format!("Unexpected GitHub repository path: {}", path)))?;
+    let token = api::env_token(&["GH_TOKEN", "GITHUB_TOKEN"])
+        .or_else(|| api::command_output("gh", &["auth", "token", "--hostname", &host]))
+        .ok_or_else(|| GgError::GhNotAuthenticated(NOT_AUTHENTICATED.to_string()))?;
+    Ok(Context {
+        api: api::api_url_override().unwrap_or_else(|| api_root(&host)),
+        owner: owner.to_string(),
+        repo: repo.to_string(),
+        token,
+    })
+}
+
+fn request(ctx: &Context, method: &str, path: &str, body: Option<Value>) -> Result<Value> {
+    let url = format!("{}{}", ctx.api, path);
+    let auth = format!("Bearer {}", ctx.token);
+    api::request(
+        method,
+        &url,
+        &[
+            ("Authorization", &auth),
+            ("Accept", "application/vnd.github+json"),
+        ],
+        body,
+    )
+    .map_err(|e| {
+        e.into_gg(
+            &format!("GitHub {} {}", method, path),
+            GgError::Other,
+            || GgError::GhNotAuthenticated(NOT_AUTHENTICATED.to_string()),
+        )
+    })
+}
+
+/// Run a GraphQL query. GitHub Enterprise serves GraphQL at `/api/graphql`
+/// rather than under the `/api/v3` REST root.
+fn graphql(ctx: &Context, query: &str, variables: Value) -> Result<Value> {
+    let url = match ctx.api.strip_suffix("/v3") {
+        Some(root) => format!("{}/graphql", root),
+        None => format!("{}/graphql", ctx.api),
+    };
+    let auth = format!("Bearer {}", ctx.token);
+    let response = api::request(
+        "POST",
+        &url,
+        &[("Authorization", &auth)],
+        Some(json!({ "query": query, "variables": variables })),
+    )
+    .map_err(|e| {
+        e.into_gg("GitHub GraphQL", GgError::Other, || {
+            GgError::GhNotAuthenticated(NOT_AUTHENTICATED.to_string())
+        })
+    })?;
+    if let Some(message) = response["errors"][0]["message"].as_str() {
+        return Err(GgError::Other(format!("GitHub GraphQL: {}", message)));
+    }
+    Ok(response)
+}
+
+fn repo_request(ctx: &Context, method: &str, path: &str, body: Option<Value>) -> Result<Value> {
+    let path = format!("/repos/{}/{}{}", ctx.owner, ctx.repo, path);
+    request(ctx, method, &path, body)
+}
+
+fn parse<T: for<'de> Deserialize<'de>>(value: Value) -> Result<T> {
+    serde_json::from_value(value)
+        .map_err(|e| GgError::Other(format!("Failed to parse GitHub response: {}", e)))
+}
+
+/// Fetch every page of a list endpoint (`path` must not have a query).
+fn paginate<T: for<'de> Deserialize<'de>>(
+    ctx: &Context,
+    path: &str,
+    query: &str,
+) -> Result<Vec<T>> {
+    let mut all = Vec::new();
+    for page in 1.. {
+        let sep = if query.is_empty() { "" } else { "&" };
+        let items: Vec<T> = parse(repo_request(
+            ctx,
+            "GET",
+            &format!("{}?{}{}per_page=100&page={}", path, query, sep, page),
+            None,
+        )?)?;
+        let full_page = items.len() == 100;
+        all.extend(items);
+        if !full_page {
+            break;
+        }
+    }
+    Ok(all)
+}
+
+/// `(approved, changes_requested)` from the latest approving or blocking
+/// review of each reviewer, like GitHub's review decision.
+fn review_decision(reviews: &[ReviewJson]) -> (bool, bool) {
+    let mut latest = std::collections::BTreeMap::new();
+    for review in reviews {
+        if matches!(
+            review.state.as_str(),
+            "APPROVED" | "CHANGES_REQUESTED" | "DISMISSED"
+        ) {
+            let user = review.user.as_ref().map_or("", |u| u.login.as_str());
+            latest.insert(user, review.state.as_str());
+        }
+    }
+    let changes_requested = latest.values().any(|s| *s == "CHANGES_REQUESTED");
+    let approved = !changes_requested && latest.values().any(|s| *s == "APPROVED");
+    (approved, changes_requested)
+}
+
+fn convert_pull(pull: PullJson, reviews: &[ReviewJson]) -> PrInfo {
+    let state = if pull.merged || pull.merged_at.is_some() {
+        PrState::Merged
+    } else if pull.state == "closed" {
+        PrState::Closed
+    } else if pull.draft {
+        PrState::Draft
+    } else {
+        PrState::Open
+    };
+    let (approved, changes_requested) = review_decision(reviews);
+    PrInfo {
+        number: pull.number,
+        title: pull.title,
+        state,
+        url: pull.html_url,
+        head_branch: Some(pull.head.ref_name),
+        base_branch: Some(pull.base.ref_name),
+        draft: pull.draft,
+        approved,
+        mergeable: pull.mergeable.unwrap_or(false),
+        changes_requested,
+        updated_at_ms: pull
+            .updated_at
+            .as_deref()
+            .and_then(relative_time::parse_rfc3339_ms),
+    }
+}
+
+/// Combine check-run conclusions and the legacy commit status into one
+/// CI status.
+fn ci_status(check_runs: &Value, combined: &Value) -> CiStatus {
+    let mut has_success = false;
+    let mut has_pending = false;
+
+    for run in check_runs["check_runs"].as_array().into_iter().flatten() {
+        if run["status"].as_str() != Some("completed") {
+            has_pending = true;
+            continue;
+        }
+        match run["conclusion"].as_str().unwrap_or("") {
+            "failure" | "timed_out" | "action_required" | "startup_failure" => {
+                return CiStatus::Failed
+            }
+            "cancelled" => return CiStatus::Canceled,
+            "success" => has_success = true,
+            _ => {}
+        }
+    }
+
+    if combined["total_count"].as_u64().unwrap_or(0) > 0 {
+        match combined["state"].as_str().unwrap_or("") {
+            "failure" | "error" => return CiStatus::Failed,
+            "pending" => has_pending = true,
+            "success" => has_success = true,
+            _ => {}
+        }
+    }
+
+    if has_pending {
+        CiStatus::Pending
+    } else if has_success {
+        CiStatus::Success
+    } else {
+        CiStatus::Unknown
+    }
+}
+
+/// Check that the token is accepted
+pub fn check_auth() -> Result<()> {
+    whoami().map(|_| ())
+}
+
+/// Get the login of the authenticated user
+pub fn whoami() -> Result<String> {
+    let ctx = context()?;
+    let user: UserJson = parse(request(&ctx, "GET", "/user", None)?)?;
+    Ok(user.login)
+}
+
+/// Create a new PR
+pub fn create_pr(
+    source_branch: &str,
+    target_branch: &str,
+    title: &str,
+    description: &str,
+    draft: bool,
+) -> Result<PrCreationResult> {
+    let ctx = context()?;
+    let pull: PullJson = parse(repo_request(
+        &ctx,
+        "POST",
+        "/pulls",
+        Some(json!({
+            "head": source_branch,
+            "base": target_branch,
+            "title": title,
+            "body": description,
+            "draft": draft,
+        })),
+    )?)?;
+    Ok(PrCreationResult {
+        number: pull.number,
+        url: pull.html_url,
+    })
+}
+
+/// View PR information
+pub fn view_pr(pr_number: u64) -> Result<PrInfo> {
+    let ctx = context()?;
+    let pull: PullJson = parse(repo_request(
+        &ctx,
+        "GET",
+        &format!("/pulls/{}", pr_number),
+        None,
+    )?)?;
+    let reviews: Vec<ReviewJson> = paginate(&ctx, &format!("/pulls/{}/reviews", pr_number), "")?;
+    Ok(convert_pull(pull, &reviews))
+}
+
+fn edit_pull(pr_number: u64, fields: Value) -> Result<()> {
+    let ctx = context()?;
+    repo_request(
+        &ctx,
+        "PATCH",
+        &format!("/pulls/{}", pr_number),
+        Some(fields),
+    )?;
+    Ok(())
+}
+
+/// Close a PR without merging.
+pub fn close_pr(pr_number: u64) -> Result<()> {
+    edit_pull(pr_number, json!({ "state": "closed" }))
+}
+
+/// Close an issue as completed.
+pub fn close_issue(number: u64) -> Result<()> {
+    let ctx = context()?;
+    repo_request(
+        &ctx,
+        "PATCH",
+        &format!("/issues/{}", number),
+        Some(json!({ "state": "closed", "state_reason": "completed" })),
+    )?;
+    Ok(())
+}
+
+/// Update PR base branch
+pub fn update_pr_base(pr_number: u64, base_branch: &str) -> Result<()> {
+    edit_pull(pr_number, json!({ "base": base_branch }))
+}
+
+/// Update PR description/body
+pub fn update_pr_description(pr_number: u64, description: &str) -> Result<()> {
+    edit_pull(pr_number, json!({ "body": description }))
+}
+
+/// Update PR title
+pub fn update_pr_title(pr_number: u64, title: &str) -> Result<()> {
+    edit_pull(pr_number, json!({ "title": title }))
+}
+
+fn graphql_repo_vars(ctx: &Context) -> Value {
+    json!({ "owner": ctx.owner, "repo": ctx.repo })
+}
+
+/// Whether `branch` requires a merge queue
+pub fn check_merge_queue_enabled(branch: &str) -> Result<bool> {
+    let ctx = context()?;
+    let mut vars = graphql_repo_vars(&ctx);
+    vars["branch"] = json!(branch);
+    let response = graphql(&ctx, gh::MERGE_QUEUE_QUERY, vars)?;
+    Ok(!response["data"]["repository"]["mergeQueue"].is_null())
+}
+
+fn merge_queue_pull(ctx: &Context, pr_number: u64) -> Result<gh::MergeQueuePull> {
+    let mut vars = graphql_repo_vars(ctx);
+    vars["number"] = json!(pr_number);
+    gh::parse_merge_queue_pull(&graphql(ctx, gh::MERGE_QUEUE_ENTRY_QUERY, vars)?)
+}
+
+/// Where a PR is in its base branch's merge queue
+pub fn get_merge_queue_status(pr_number: u64) -> Result<MergeTrainInfo> {
+    let ctx = context()?;
+    Ok(gh::merge_queue_info(&merge_queue_pull(&ctx, pr_number)?))
+}
+
+/// State, CI, approval and merge queue status of several PRs, one GraphQL
+/// request per [`gh::PRS_PER_QUERY`] PRs
+pub fn get_prs_status(pr_numbers: &[u64]) -> Result<HashMap<u64, gh::PrStatus>> {
+    let ctx = context()?;
+    let mut statuses = HashMap::new();
+    for chunk in pr_numbers.chunks(gh::PRS_PER_QUERY) {
+        let response = graphql(&ctx, &gh::prs_status_query(chunk), graphql_repo_vars(&ctx))?;
+        statuses.extend(gh::parse_prs_status(&response)?);
+    }
+    Ok(statuses)
+}
+
+/// Add a PR to its base branch's merge queue
+pub fn add_to_merge_queue(pr_number: u64) -> Result<AutoMergeResult> {
+    let ctx = context()?;
+    let pull = merge_queue_pull(&ctx, pr_number)?;
+    if pull.merge_queue_entry.is_some() {
+        return Ok(AutoMergeResult::AlreadyQueued);
+    }
+    graphql(&ctx, gh::ENQUEUE_MUTATION, json!({ "id": pull.id }))?;
+    Ok(AutoMergeResult::Queued)
+}
+
+/// Mark a draft PR as ready for review. REST cannot change draft state, so
+/// this goes through the GraphQL mutation with the PR's node id.
+pub fn mark_ready_for_review(pr_number: u64) -> Result<()> {
+    let ctx = context()?;
+    let pull: PullJson = parse(repo_request(
+        &ctx,
+        "GET",
+        &format!("/pulls/{}", pr_number),
+        None,
+    )?)?;
+    graphql(
+        &ctx,
+        "mutation($id: ID!) { markPullRequestReadyForReview(input: { pullRequestId: $id }) \
+         { pullRequest { isDraft } } }",
+        json!({ "id": pull.node_id }),
+    )?;
+    Ok(())
+}
+
+/// Request reviews from users and teams (`org/team`)
+pub fn request_reviewers(pr_number: u64, reviewers: &[String]) -> Result<()> {
+    let (teams, users): (Vec<&String>, Vec<&String>) =
+        reviewers.iter().partition(|r| r.contains('/'));
+    let team_slugs: Vec<&str> = teams
+        .iter()
+        .filter_map(|team| team.rsplit('/').next())
+        .collect();
+    let ctx = context()?;
+    repo_request(
+        &ctx,
+        "POST",
+        &format!("/pulls/{}/requested_reviewers", pr_number),
+        Some(json!({ "reviewers": users, "team_reviewers": team_slugs })),
+    )?;
+    Ok(())
+}
+
+/// Add labels to a PR
+pub fn add_labels(pr_number: u64, labels: &[String]) -> Result<()> {
+    let ctx = context()?;
+    repo_request(
+        &ctx,
+        "POST",
+        &format!("/issues/{}/labels", pr_number),
+        Some(json!({ "labels": labels })),
+    )?;
+    Ok(())
+}
+
+/// Remove labels from a PR
+pub fn remove_labels(pr_number: u64, labels: &[String]) -> Result<()> {
+    let ctx = context()?;
+    for label in labels {
+        repo_request(
+            &ctx,
+            "DELETE",
+            &format!("/issues/{}/labels/{}", pr_number, api::encode(label)),
+            None,
+        )?;
+    }
+    Ok(())
+}
+
+#[derive(Debug, Deserialize)]
+struct MilestoneJson {
+    number: u64,
+    title: String,
+}
+
+/// Set the milestone of a PR by title (open milestones only)
+pub fn set_milestone(pr_number: u64, milestone: &str) -> Result<()> {
+    let ctx = context()?;
+    let milestones: Vec<MilestoneJson> = paginate(&ctx, "/milestones", "state=open")?;
+    let number = milestones
+        .iter()
+        .find(|m| m.title == milestone)
+        .map(|m| m.number)
+        .ok_or_else(|| GgError::Other(format!("No open milestone named '{}'", milestone)))?;
+    repo_request(
+        &ctx,
+        "PATCH",
+        &format!("/issues/{}", pr_number),
+        Some(json!({ "milestone": number })),
+    )?;
+    Ok(())
+}
+
+/// Get PR body text
+pub fn get_pr_body(pr_number: u64) -> Result<String> {
+    let ctx = context()?;
+    let pull: PullJson = parse(repo_request(
+        &ctx,
+        "GET",
+        &format!("/pulls/{}", pr_number),
+        None,
+    )?)?;
+    Ok(pull.body.unwrap_or_default())
+}
+
+/// Merge a PR, then delete its head branch if asked to
+pub fn merge_pr(pr_number: u64, strategy: MergeStrategy, delete_branch: bool) -> Result<()> {
+    let ctx = context()?;
+    let pull: PullJson = parse(repo_request(
+        &ctx,
+        "GET",
+        &format!("/pulls/{}", pr_number),
+        None,
+    )?)?;
+    repo_request(
+        &ctx,
+        "PUT",
+        &format!("/pulls/{}/merge", pr_number),
+        Some(json!({ "merge_method": strategy.as_str() })),
+    )?;
+    if delete_branch {
+        // Best effort, like `gh pr merge --delete-branch`: the branch may be
+        // gone already (auto-delete) or still be the base of another PR.
+        let _ = repo_request(
+            &ctx,
+            "DELETE",
+            &format!("/git/refs/heads/{}", pull.head.ref_name),
+            None,
+        );
+    }
+    Ok(())
+}
+
+/// Whether a PR has an approval and no outstanding change requests.
+/// A PR nobody reviewed counts as approved, as with an empty
+/// `reviewDecision`; branch protection still decides at merge time.
+pub fn check_pr_approved(pr_number: u64) -> Result<bool> {
+    let ctx = context()?;
+    let reviews: Vec<ReviewJson> = paginate(&ctx, &format!("/pulls/{}/reviews", pr_number), "")?;
+    let (approved, changes_requested) = review_decision(&reviews);
+    Ok(approved || (!changes_requested && reviews.is_empty()))
+}
+
+/// Get CI status for a PR's head commit
+pub fn get_pr_ci_status(pr_number: u64) -> Result<CiStatus> {
+    let ctx = context()?;
+    let pull: PullJson = parse(repo_request(
+        &ctx,
+        "GET",
+        &format!("/pulls/{}", pr_number),
+        None,
+    )?)?;
+    let sha = pull.head.sha;
+    let check_runs = repo_request(
+        &ctx,
+        "GET",
+        &format!("/commits/{}/check-runs?per_page=100", sha),
+        None,
+    )?;
+    let combined = repo_request(&ctx, "GET", &format!("/commits/{}/status", sha), None)?;
+    Ok(ci_status(&check_runs, &combined))
+}
+
+/// List open PRs whose head is `branch`
+pub fn list_prs_for_branch(branch: &str) -> Result<Vec<u64>> {
+    let ctx = context()?;
+    let query = format!("state=open&head={}:{}", ctx.owner, api::encode(branch));
+    let pulls: Vec<PullJson> = paginate(&ctx, "/pulls", &query)?;
+    Ok(pulls.into_iter().map(|p| p.number).collect())
+}
+
+/// List open PRs authored by the authenticated user.
+pub fn list_my_open_prs() -> Result<Vec<OpenPr>> {
+    #[derive(Deserialize)]
+    struct Item {
+        number: u64,
+        title: String,
+        head: RefJson,
+        base: RefJson,
+        user: Option<UserJson>,
+    }
+
+    let me = whoami()?;
+    let ctx = context()?;
+    let pulls: Vec<Item> = paginate(&ctx, "/pulls", "state=open")?;
+    Ok(pulls
+        .into_iter()
+        .filter(|p| p.user.as_ref().is_some_and(|u| u.login == me))
+        .map(|p| OpenPr {
+            number: p.number,
+            title: p.title,
+            head_ref_name: p.head.ref_name,
+            base_ref_name: p.base.ref_name,
+        })
+        .collect())
+}
+
+/// List all conversation comments on a PR.
+pub fn list_issue_comments(pr_number: u64) -> Result<Vec<IssueComment>> {
+    let ctx = context()?;
+    paginate(&ctx, &format!("/issues/{}/comments", pr_number), "")
+}
+
+/// Post a new comment on a PR.
+pub fn create_issue_comment(pr_number: u64, body: &str) -> Result<()> {
+    let ctx = context()?;
+    repo_request(
+        &ctx,
+        "POST",
+        &format!("/issues/{}/comments", pr_number),
+        Some(json!({ "body": body })),
+    )?;
+    Ok(())
+}
+
+/// Edit an existing PR comment by its comment id.
+pub fn update_issue_comment(comment_id: u64, body: &str) -> Result<()> {
+    let ctx = context()?;
+    repo_request(
+        &ctx,
+        "PATCH",
+        &format!("/issues/comments/{}", comment_id),
+        Some(json!({ "body": body })),
+    )?;
+    Ok(())
+}
+
+/// Delete a PR comment by its comment id.
+pub fn delete_issue_comment(comment_id: u64) -> Result<()> {
+    let ctx = context()?;
+    repo_request(
+        &ctx,
+        "DELETE",
+        &format!("/issues/comments/{}", comment_id),
+        None,
+    )?;
+    Ok(())
+}
+
+fn get_pull(ctx: &Context, pr_number: u64) -> Result<PullJson> {
+    parse(repo_request(
+        ctx,
+        "GET",
+        &format!("/pulls/{}", pr_number),
+        None,
+    )?)
+}
+
+/// Convert a PR back to draft, through GraphQL like [`mark_ready_for_review`].
+pub fn convert_pr_to_draft(pr_number: u64) -> Result<()> {
+    let ctx = context()?;
+    let pull = get_pull(&ctx, pr_number)?;
+    if pull.draft {
+        return Ok(());
+    }
+    graphql(
+        &ctx,
+        "mutation($id: ID!) { convertPullRequestToDraft(input: { pullRequestId: $id }) \
+         { pullRequest { isDraft } } }",
+        json!({ "id": pull.node_id }),
+    )?;
+    Ok(())
+}
+
+/// Approve a PR
+pub fn approve_pr(pr_number: u64) -> Result<()> {
+    let ctx = context()?;
+    repo_request(
+        &ctx,
+        "POST",
+        &format!("/pulls/{}/reviews", pr_number),
+        Some(json!({ "event": "APPROVE" })),
+    )?;
+    Ok(())
+}
+
+/// Enable GitHub's auto-merge on a PR. Whether the head branch is deleted
+/// afterwards is up to the repository's settings.
+pub fn enable_auto_merge(pr_number: u64, strategy: MergeStrategy) -> Result<AutoMergeResult> {
+    let ctx = context()?;
+    let pull = get_pull(&ctx, pr_number)?;
+    if pull.auto_merge.is_some() {
+        return Ok(AutoMergeResult::AlreadyQueued);
+    }
+    graphql(
+        &ctx,
+        "mutation($id: ID!, $method: PullRequestMergeMethod!) { enablePullRequestAutoMerge(\
+         input: { pullRequestId: $id, mergeMethod: $method }) { clientMutationId } }",
+        json!({ "id": pull.node_id, "method": strategy.as_str().to_uppercase() }),
+    )?;
+    Ok(AutoMergeResult::Queued)
+}
+
+const CHECKS_QUERY: &str = "query($owner: String!, $repo: String!, $number: Int!) { \
+repository(owner: $owner, name: $repo) { pullRequest(number: $number) { \
+commits(last: 1) { nodes { commit { statusCheckRollup { contexts(first: 100) { nodes { \
+__typename \
+... on CheckRun { name status conclusion detailsUrl isRequired(pullRequestNumber: $number) \
+checkSuite { workflowRun { workflow { name } } } } \
+... on StatusContext { context state targetUrl isRequired(pullRequestNumber: $number) } \
+} } } } } } } } }";
+
+/// The `gh pr checks` bucket of a check run conclusion (or status, while it
+/// runs) or a commit status state.
+fn check_bucket(state: &str) -> &'static str {
+    match state {
+        "SUCCESS" => "pass",
+        "SKIPPED" | "NEUTRAL" => "skipping",
+        "ERROR" | "FAILURE" | "TIMED_OUT" | "ACTION_REQUIRED" | "STARTUP_FAILURE" => "fail",
+        "CANCELLED" => "cancel",
+        _ => "pending",
+    }
+}
+
+fn parse_checks(response: &Value, required_only: bool) -> Vec<PrCheck> {
+    let contexts = &response["data"]["repository"]["pullRequest"]["commits"]["nodes"][0]["commit"]
+        ["statusCheckRollup"]["contexts"]["nodes"];
+    contexts
+        .as_array()
+        .into_iter()
+        .flatten()
+        .filter(|check| !required_only || check["isRequired"].as_bool() == Some(true))
+        .map(|check| {
+            let text = |value: &Value| value.as_str().unwrap_or_default().to_string();
+            if check["__typename"] == "StatusContext" {
+                PrCheck {
+                    name: text(&check["context"]),
+                    bucket: check_bucket(&text(&check["state"])).to_string(),
+                    workflow: String::new(),
+                    link: check["targetUrl"].as_str().map(str::to_string),
+                }
+            } else {
+                let state = if check["status"] == "COMPLETED" {
+                    &check["conclusion"]
+                } else {
+                    &check["status"]
+                };
+                PrCheck {
+                    name: text(&check["name"]),
+                    bucket: check_bucket(&text(state)).to_string(),
+                    workflow: text(&check["checkSuite"]["workflowRun"]["workflow"]["name"]),
+                    link: check["detailsUrl"].as_str().map(str::to_string),
+                }
+            }
+        })
+        .collect()
+}
+
+/// List checks on a PR's head commit, optionally only the required ones.
+pub fn list_pr_checks(pr_number: u64, required_only: bool) -> Result<Vec<PrCheck>> {
+    let ctx = context()?;
+    let mut vars = graphql_repo_vars(&ctx);
+    vars["number"] = json!(pr_number);
+    let response = graphql(&ctx, CHECKS_QUERY, vars)?;
+    Ok(parse_checks(&response, required_only))
+}
+
+/// Re-run the GitHub Actions workflow runs of a PR's head commit; with
+/// `failed_only`, only the failed jobs of failed runs.
+pub fn rerun_pr_workflows(pr_number: u64, failed_only: bool) -> Result<Vec<String>> {
+    let ctx = context()?;
+    let pull = get_pull(&ctx, pr_number)?;
+    let response = repo_request(
+        &ctx,
+        "GET",
+        &format!("/actions/runs?head_sha={}&per_page=100", pull.head.sha),
+        None,
+    )?;
+    let runs: Vec<WorkflowRun> = response["workflow_runs"]
+        .as_array()
+        .into_iter()
+        .flatten()
+        .map(|run| WorkflowRun {
+            database_id: run["id"].as_u64().unwrap_or_default(),
+            name: run["name"].as_str().unwrap_or_default().to_string(),
+            status: run["status"].as_str().unwrap_or_default().to_string(),
+            conclusion: run["conclusion"].as_str().unwrap_or_default().to_string(),
+        })
+        .collect();
+
+    let action = if failed_only {
+        "rerun-failed-jobs"
+    } else {
+        "rerun"
+    };
+    let mut restarted = Vec::new();
+    for run in gh::select_runs_to_rerun(&runs, failed_only) {
+        repo_request(
+            &ctx,
+            "POST",
+            &format!("/actions/runs/{}/{}", run.database_id, action),
+            None,
+        )?;
+        restarted.push(run.name.clone());
+    }
+    Ok(restarted)
+}
+
+/// Rename a branch. Open PRs whose head or base is the branch follow it.
+pub fn rename_branch(branch: &str, new_name: &str) -> Result<()> {
+    let ctx = context()?;
+    repo_request(
+        &ctx,
+        "POST",
+        &format!("/branches/{}/rename", branch),
+        Some(json!({ "new_name": new_name })),
+    )?;
+    Ok(())
+}
+
+/// Signed-commit and linear-history requirements for `branch`. Classic
+/// protection is only readable by admins: a failed lookup there counts as
+/// none.
+pub fn get_branch_rules(branch: &str) -> Result<BranchRules> {
+    let ctx = context()?;
+    let rules = repo_request(&ctx, "GET", &format!("/rules/branches/{}", branch), None)?;
+    let rules = gh::parse_ruleset_rules(&rules.to_string());
+    Ok(
+        match repo_request(
+            &ctx,
+            "GET",
+            &format!("/branches/{}/protection", branch),
+            None,
+        ) {
+            Ok(classic) => rules.merge(gh::parse_classic_protection(&classic.to_string())),
+            Err(_) => rules,
+        },
+    )
+}
+
+/// List inline review threads on a PR.
+pub fn list_review_threads(pr_number: u64) -> Result<Vec<ReviewThread>> {
+    let ctx = context()?;
+    let mut vars = graphql_repo_vars(&ctx);
+    vars["number"] = json!(pr_number);
+    let response = graphql(&ctx, gh::REVIEW_THREADS_QUERY, vars)?;
+    gh::parse_review_threads(response, pr_number)
+}
+
+/// Mark a review thread as resolved.
+pub fn resolve_review_thread(thread_id: &str) -> Result<()> {
+    let ctx = context()?;
+    graphql(
+        &ctx,
+        gh::RESOLVE_THREAD_MUTATION,
+        json!({ "id": thread_id }),
+    )?;
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parses_check_rollup_like_gh_pr_checks() {
+        let response = json!({"data": {"repository": {"pullRequest": {"commits": {"nodes": [
+            {"commit": {"statusCheckRollup": {"contexts": {"nodes": [
+                {"__typename": "CheckRun", "name": "test", "status": "COMPLETED",
+                 "conclusion": "FAILURE", "detailsUrl": "https://ci/1", "isRequired": true,
+                 "checkSuite": {"workflowRun": {"workflow": {"name": "CI"}}}},
+                {"__typename": "CheckRun", "name": "lint", "status": "IN_PROGRESS",
+                 "conclusion": null, "isRequired": false, "checkSuite": null},
+                {"__typename": "StatusContext", "context": "deploy", "state": "SUCCESS",
+                 "targetUrl": null, "isRequired": false}
+            ]}}}}
+        ]}}}}});
+
+        let checks = parse_checks(&response, false);
+        let summary: Vec<(&str, &str, &str)> = checks
+            .iter()
+            .map(|c| (c.name.as_str(), c.bucket.as_str(), c.workflow.as_str()))
+            .collect();
+        assert_eq!(
+            summary,
+            [
+                ("test", "fail", "CI"),
+                ("lint", "pending", ""),
+                ("deploy", "pass", "")
+            ]
+        );
+        assert_eq!(checks[0].link.as_deref(), Some("https://ci/1"));
+
+        let required = parse_checks(&response, true);
+        assert_eq!(required.len(), 1);
+        assert_eq!(required[0].name, "test");
+    }
+
+    #[test]
+    fn api_root_for_enterprise_hosts() {
+        assert_eq!(api_root("github.com"), "https://api.github.com");
+        assert_eq!(
+            api_root("ghe.example.com"),
+            "https://ghe.example.com/api/v3"
+        );
+    }
+
+    #[test]
+    fn converts_pull_and_reviews() {
+        let pull: PullJson = serde_json::from_str(
+            r#"{"number": 12, "title": "Add parser", "state": "open", "draft": false,
+                "mergeable": true, "html_url": "https://github.com/o/r/pull/12",
+                "head": {"ref": "nacho/parser--c-abc1234", "sha": "abc"},
+                "base": {"ref": "main", "sha": "def"},
+                "updated_at": "2024-01-15T10:30:00Z"}"#,
+        )
+        .unwrap();
+        let reviews: Vec<ReviewJson> = serde_json::from_str(
+            r#"[{"state": "CHANGES_REQUESTED", "user": {"login": "a"}},
+                {"state": "COMMENTED", "user": {"login": "a"}},
+                {"state": "APPROVED", "user": {"login": "b"}}]"#,
+        )
+        .unwrap();
+        let info = convert_pull(pull, &reviews);
+        assert_eq!(info.state, PrState::Open);
+        assert!(info.changes_requested);
+        assert!(!info.approved);
+        assert!(info.mergeable);
+        assert_eq!(info.base_branch.as_deref(), Some("main"));
+    }
+
+    #[test]
+    fn combines_check_runs_and_statuses() {
+        let runs = json!({"check_runs": [
+            {"status": "completed", "conclusion": "success"},
+            {"status": "in_progress", "conclusion": null}
+        ]});
+        let no_status = json!({"state": "pending", "total_count": 0});
+        assert_eq!(ci_status(&runs, &no_status), CiStatus::Pending);
+
+        let runs = json!({"check_runs": [{"status": "completed", "conclusion": "success"}]});
+        assert_eq!(ci_status(&runs, &no_status), CiStatus::Success);
+
+        let failed = json!({"state": "failure", "total_count": 1});
+        assert_eq!(ci_status(&runs, &failed), CiStatus::Failed);
+
+        assert_eq!(
+            ci_status(&json!({"check_runs": []}), &no_status),
+            CiStatus::Unknown
+        );
+    }
+}
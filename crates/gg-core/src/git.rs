@@ -5,6 +5,7 @@
 
 use std::fs::{self, File};
 use std::process::Command;
+use std::sync::{OnceLock, RwLock};
 use std::time::Duration;
 
 /// Default timeout in seconds for acquiring the index.lock
@@ -16,10 +17,50 @@ use git2::Branch;
 use git2::{BranchType, Commit, Oid, Repository, Signature, Sort};
 use regex::Regex;
 
+use crate::branch_naming;
 use crate::error::{GgError, Result};
+use crate::hooks::{self, GitRewrite};
+use crate::signing;
 
 /// Prefix for GG-ID trailers in commit messages
 pub const GG_ID_PREFIX: &str = "GG-ID:";
+
+/// Remote stacks push to unless their config names another one
+pub const DEFAULT_REMOTE: &str = "origin";
+
+fn provider_remote_cell() -> &'static RwLock<String> {
+    static REMOTE: OnceLock<RwLock<String>> = OnceLock::new();
+    REMOTE.get_or_init(|| RwLock::new(DEFAULT_REMOTE.to_string()))
+}
+
+/// Look PRs/MRs up in the repository behind `remote` from now on (the
+/// remote of the stack a command works on).
+pub fn set_provider_remote(remote: &str) {
+    *provider_remote_cell()
+        .write()
+        .unwrap_or_else(|e| e.into_inner()) = remote.to_string();
+}
+
+/// The remote whose repository holds the PRs/MRs: `origin` unless a
+/// command selected the remote of its stack.
+pub fn provider_remote() -> String {
+    provider_remote_cell()
+        .read()
+        .unwrap_or_else(|e| e.into_inner())
+        .clone()
+}
+
+/// URL of [`provider_remote`] in `repo`.
+pub fn provider_remote_url(repo: &Repository) -> Result<String> {
+    let name = provider_remote();
+    let remote = repo
+        .find_remote(&name)
+        .map_err(|_| GgError::Other(format!("No {} remote found", name)))?;
+    remote
+        .url()
+        .map(str::to_string)
+        .map_err(|_| GgError::Other(format!("Remote {} has no URL", name)))
+}
 /// Prefix for GG-Parent trailers in commit messages
 pub const GG_PARENT_PREFIX: &str = "GG-Parent:";
 
@@ -210,6 +251,13 @@ pub fn begin_recorded_op(
         SCHEMA_VERSION,
     };
 
+    // 0. Never rewrite a protected branch, even if it is checked out.
+    if kind.rewrites_history() {
+        if let Some(branch) = current_branch_name(repo) {
+            guard_protected_branch(config, &branch)?;
+        }
+    }
+
     // 1. Sweep stale Pending records. Swallows all errors.
     let gg_dir_path = gg_dir(repo);
     let store = OperationStore::new(&gg_dir_path);
@@ -234,6 +282,7 @@ pub fn begin_recorded_op(
         touched_remote: false,
         undoes: None,
         pending_plan: None,
+        conflicted: false,
     };
     store.save(&record)?;
 
@@ -262,6 +311,16 @@ pub fn find_base_branch(repo: &Repository) -> Result<String> {
     Err(GgError::NoBaseBranch)
 }
 
+/// `base` as a local branch, falling back to `<remote>/<base>`.
+pub fn resolve_base<'r>(
+    repo: &'r Repository,
+    remote: &str,
+    base: &str,
+) -> std::result::Result<git2::Object<'r>, git2::Error> {
+    repo.revparse_single(base)
+        .or_else(|_| repo.revparse_single(&format!("{}/{}", remote, base)))
+}
+
 /// Get the current branch name, if on a branch
 pub fn current_branch_name(repo: &Repository) -> Option<String> {
     let head = repo.head().ok()?;
@@ -275,46 +334,24 @@ pub fn current_branch_name(repo: &Repository) -> Option<String> {
 /// Parse a stack branch name into (username, stack_name)
 /// Note: Entry branches (username/stack--entry_id) should NOT be parsed as stack branches
 pub fn parse_stack_branch(branch_name: &str) -> Option<(String, String)> {
-    let parts: Vec<&str> = branch_name.split('/').collect();
-    if parts.len() == 2 {
-        // Exclude entry branches which have "--" in the stack name portion
-        if parts[1].contains("--") {
-            return None;
-        }
-        Some((parts[0].to_string(), parts[1].to_string()))
-    } else {
-        None
-    }
+    branch_naming::current().parse_stack(branch_name)
 }
 
 /// Parse an entry branch name into (username, stack_name, entry_id)
-/// Format: username/stack_name--entry_id
+/// Format: `defaults.entry_branch_template` (username/stack_name--entry_id)
 pub fn parse_entry_branch(branch_name: &str) -> Option<(String, String, String)> {
-    let parts: Vec<&str> = branch_name.split('/').collect();
-    if parts.len() == 2 && parts[1].contains("--") {
-        let stack_parts: Vec<&str> = parts[1].split("--").collect();
-        if stack_parts.len() == 2 {
-            Some((
-                parts[0].to_string(),
-                stack_parts[0].to_string(),
-                stack_parts[1].to_string(),
-            ))
-        } else {
-            None
-        }
-    } else {
-        None
-    }
+    branch_naming::current().parse_entry(branch_name)
 }
 
-/// Format a stack branch name
+/// Format a stack branch name (`defaults.branch_template`)
 pub fn format_stack_branch(username: &str, stack_name: &str) -> String {
-    format!("{}/{}", username, stack_name)
+    branch_naming::current().format_stack(username, stack_name)
 }
 
 /// Format a remote branch name for a specific entry
+/// (`defaults.entry_branch_template`)
 pub fn format_entry_branch(username: &str, stack_name: &str, entry_id: &str) -> String {
-    format!("{}/{}--{}", username, stack_name, entry_id)
+    branch_naming::current().format_entry(username, stack_name, entry_id)
 }
 
 /// Find the first entry branch for a stack (username/stack_name--*)
@@ -430,7 +467,7 @@ pub fn get_stack_commit_oids(
         .or_else(|_| repo.revparse_single(&format!("origin/{}", base_branch)))
         .map_err(|_| GgError::NoBaseBranch)?;
 
-    let base_oid = base_ref.id();
+    let base_oid = base_ref.peel_to_commit()?.id();
 
     let mut revwalk = repo.revwalk()?;
     revwalk.set_sorting(Sort::TOPOLOGICAL | Sort::REVERSE)?;
@@ -621,8 +658,8 @@ pub fn normalize_stack_metadata(
             let parents = parents?;
             let parent_refs: Vec<&Commit> = parents.iter().collect();
 
-            repo.commit(
-                None,
+            signing::create_commit(
+                repo,
                 &original_commit.author(),
                 &original_commit.committer(),
                 &new_message,
@@ -639,7 +676,10 @@ pub fn normalize_stack_metadata(
     }
 
     let target_refname = {
-        let stack_refname = format!("refs/heads/{}/{}", stack.username, stack.name);
+        let stack_refname = format!(
+            "refs/heads/{}",
+            format_stack_branch(&stack.username, &stack.name)
+        );
         if repo.find_reference(&stack_refname).is_ok() {
             stack_refname
         } else {
@@ -668,7 +708,7 @@ pub fn normalize_stack_metadata(
     Ok(counts)
 }
 
-fn extract_description_from_message(message: &str) -> Option<String> {
+pub(crate) fn extract_description_from_message(message: &str) -> Option<String> {
     let stripped = strip_gg_parent_from_message(&strip_gg_id_from_message(message));
     let newline_idx = stripped.find('\n')?;
     let description = stripped[newline_idx + 1..].trim();
@@ -757,7 +797,17 @@ pub fn get_signature(repo: &Repository) -> Result<Signature<'static>> {
 
 /// Run git command as subprocess (for operations git2 doesn't support well)
 pub fn run_git_command(args: &[&str]) -> Result<String> {
-    let output = Command::new("git").args(args).output()?;
+    run_command(Command::new("git"), args)
+}
+
+/// [`run_git_command`] for an amend or rebase gg makes, with git hooks
+/// following `defaults.run_hooks` (see [`hooks::git_command`])
+pub fn run_rewrite_command(rewrite: GitRewrite, args: &[&str]) -> Result<String> {
+    run_command(hooks::git_command(rewrite), args)
+}
+
+fn run_command(mut command: Command, args: &[&str]) -> Result<String> {
+    let output = command.args(args).output()?;
 
     if output.status.success() {
         Ok(String::from_utf8_lossy(&output.stdout).trim().to_string())
@@ -771,20 +821,27 @@ pub fn run_git_command(args: &[&str]) -> Result<String> {
     }
 }
 
-/// Fetch from origin and prune stale remote-tracking refs
+/// Fetch from `remote` and prune stale remote-tracking refs
 /// This ensures we have up-to-date remote state before operations like sync
-pub fn fetch_and_prune() -> Result<()> {
+pub fn fetch_and_prune(remote: &str) -> Result<()> {
     // Using subprocess because git2's fetch requires complex auth callback setup
-    let _ = std::process::Command::new("git")
-        .args(["fetch", "origin", "--prune"])
-        .output();
+    let output = std::process::Command::new("git")
+        .args(["fetch", remote, "--prune"])
+        .output()?;
+    if !output.status.success() {
+        return Err(GgError::Other(format!(
+            "Could not fetch from {}: {}",
+            remote,
+            String::from_utf8_lossy(&output.stderr).trim()
+        )));
+    }
     Ok(())
 }
 
-/// Get the OID of a remote branch, if it exists
+/// Get the OID of a branch on `remote`, if it exists
 /// Returns None if the remote branch doesn't exist
-pub fn get_remote_branch_oid(repo: &Repository, branch_name: &str) -> Option<Oid> {
-    let remote_ref = format!("refs/remotes/origin/{}", branch_name);
+pub fn get_remote_branch_oid(repo: &Repository, remote: &str, branch_name: &str) -> Option<Oid> {
+    let remote_ref = format!("refs/remotes/{}/{}", remote, branch_name);
     repo.revparse_single(&remote_ref).ok().map(|obj| obj.id())
 }
 
@@ -828,15 +885,16 @@ pub fn count_branch_behind_upstream(
 
 /// Build the argv passed to `git push`.
 ///
-/// Order: `push [--force | --force-with-lease] [--no-verify] origin <branch>`.
+/// Order: `push [--force | --force-with-lease] [--no-verify] <remote> <branch>`.
 /// `hard_force` wins over `force_with_lease` when both are true, matching
 /// `push_branch`'s existing contract.
-fn build_push_args(
-    branch_name: &str,
+fn build_push_args<'a>(
+    remote: &'a str,
+    branch_name: &'a str,
     force_with_lease: bool,
     hard_force: bool,
     no_verify: bool,
-) -> Vec<&str> {
+) -> Vec<&'a str> {
     let mut args: Vec<&str> = vec!["push"];
     if hard_force {
         args.push("--force");
@@ -846,11 +904,34 @@ fn build_push_args(
     if no_verify {
         args.push("--no-verify");
     }
-    args.push("origin");
+    args.push(remote);
     args.push(branch_name);
     args
 }
 
+/// Whether `branch` matches one of the protected branch `patterns`
+/// (globs: `*` within a path segment, `**` across segments).
+pub fn is_protected_branch(patterns: &[String], branch: &str) -> bool {
+    patterns
+        .iter()
+        .any(|pattern| crate::scope::glob_match(pattern, branch))
+}
+
+/// Fail with [`GgError::ProtectedBranch`] if `config` protects `branch`.
+pub fn guard_protected_branch(config: &crate::config::Config, branch: &str) -> Result<()> {
+    if is_protected_branch(&config.defaults.protected_branches, branch) {
+        return Err(GgError::ProtectedBranch(branch.to_string()));
+    }
+    Ok(())
+}
+
+/// [`guard_protected_branch`] for the repository in the current directory.
+fn guard_protected_push(branch: &str) -> Result<()> {
+    let repo = open_repo()?;
+    let config = crate::config::Config::load(repo.commondir())?;
+    guard_protected_branch(&config, branch)
+}
+
 /// Push a branch to origin
 ///
 /// - `force_with_lease`: Use --force-with-lease (safe force, recommended for stacked diffs)
@@ -861,12 +942,17 @@ fn build_push_args(
 /// the remote branch may have been deleted (e.g., after a PR was merged).
 /// The retry path honors `no_verify` the same way.
 pub fn push_branch(
+    remote: &str,
     branch_name: &str,
     force_with_lease: bool,
     hard_force: bool,
     no_verify: bool,
 ) -> Result<()> {
-    let args = build_push_args(branch_name, force_with_lease, hard_force, no_verify);
+    // Last line of defense against a base or branch template pointing at a
+    // branch nobody should push to.
+    guard_protected_push(branch_name)?;
+
+    let args = build_push_args(remote, branch_name, force_with_lease, hard_force, no_verify);
 
     let output = Command::new("git").args(&args).output()?;
 
@@ -887,12 +973,12 @@ pub fn push_branch(
                  This could mean someone else has pushed changes.\n\
                  \n\
                  To proceed safely:\n\
-                 1. Run 'git fetch origin'\n\
+                 1. Run 'git fetch {}'\n\
                  2. Review the changes\n\
                  3. Run 'gg sync' again\n\
                  \n\
                  If you're certain you want to overwrite remote changes, run with --force flag.",
-                branch_name
+                branch_name, remote
             )));
         }
 
@@ -923,7 +1009,7 @@ pub fn push_branch(
 
         // User confirmed, proceed with force push
         eprintln!("{}", console::style("Force-pushing...").dim());
-        let retry_args = build_push_args(branch_name, false, true, no_verify);
+        let retry_args = build_push_args(remote, branch_name, false, true, no_verify);
         return run_git_command(&retry_args).map(|_| ());
     }
 
@@ -937,6 +1023,36 @@ pub fn push_branch(
     })
 }
 
+/// Push several branches to origin, at most `jobs` at a time.
+///
+/// Results come back in the order of `branches`. Unlike [`push_branch`], a
+/// stale lease is never prompted for: every failure is returned as an error,
+/// and callers retry those branches with [`push_branch`] one by one.
+pub fn push_branches(
+    remote: &str,
+    branches: &[String],
+    force_with_lease: bool,
+    hard_force: bool,
+    no_verify: bool,
+    jobs: usize,
+) -> Vec<Result<()>> {
+    crate::parallel::map(branches, jobs, |branch| {
+        guard_protected_push(branch)?;
+        let args = build_push_args(remote, branch, force_with_lease, hard_force, no_verify);
+        let output = Command::new("git").args(&args).output()?;
+        if output.status.success() {
+            return Ok(());
+        }
+        let (hook_error, git_error) =
+            parse_push_error(String::from_utf8_lossy(&output.stderr).trim());
+        Err(GgError::PushFailed {
+            branch: branch.clone(),
+            hook_error,
+            git_error,
+        })
+    })
+}
+
 /// Parse git push stderr to separate hook errors from git errors
 fn parse_push_error(stderr: &str) -> (Option<String>, Option<String>) {
     let lines: Vec<&str> = stderr.lines().collect();
@@ -984,7 +1100,14 @@ fn parse_push_error(stderr: &str) -> (Option<String>, Option<String>) {
 /// Delete a remote branch only if its server tip still matches the tip resolved
 /// immediately before deletion. Returns the exact deleted OID, or `None` when
 /// the branch does not exist on the server.
-pub fn delete_remote_branch(repo: &Repository, branch_name: &str) -> Result<Option<Oid>> {
+pub fn delete_remote_branch(
+    repo: &Repository,
+    remote: &str,
+    branch_name: &str,
+) -> Result<Option<Oid>> {
+    let config = crate::config::Config::load(repo.commondir())?;
+    guard_protected_branch(&config, branch_name)?;
+
     let workdir = repo
         .workdir()
         .ok_or_else(|| GgError::Other("Cannot delete a remote branch from a bare repo".into()))?;
@@ -992,7 +1115,7 @@ pub fn delete_remote_branch(repo: &Repository, branch_name: &str) -> Result<Opti
     let lookup = Command::new("git")
         .args(["-C"])
         .arg(workdir)
-        .args(["ls-remote", "--heads", "origin", &branch_ref])
+        .args(["ls-remote", "--heads", remote, &branch_ref])
         .output()?;
     if !lookup.status.success() {
         return Err(GgError::Other(format!(
@@ -1017,7 +1140,7 @@ pub fn delete_remote_branch(repo: &Repository, branch_name: &str) -> Result<Opti
     let deletion = Command::new("git")
         .args(["-C"])
         .arg(workdir)
-        .args(["push", &lease, "origin", &delete_refspec])
+        .args(["push", &lease, remote, &delete_refspec])
         .output()?;
     if !deletion.status.success() {
         return Err(GgError::Other(format!(
@@ -1033,7 +1156,7 @@ pub fn delete_remote_branch(repo: &Repository, branch_name: &str) -> Result<Opti
 pub fn rebase_continue() -> Result<()> {
     // Set GIT_EDITOR=true to avoid "Terminal is dumb, but EDITOR unset" errors
     // This allows rebase to continue without requiring an interactive editor
-    let output = Command::new("git")
+    let output = hooks::git_command(GitRewrite::Replay)
         .args(["rebase", "--continue"])
         .env("GIT_EDITOR", "true")
         .output()?;
@@ -1070,7 +1193,8 @@ pub fn short_sha(commit: &Commit) -> String {
 ///
 /// - Converts spaces to hyphens (kebab-case)
 /// - Rejects names containing `/` (conflicts with branch format)
-/// - Rejects names containing `--` (conflicts with entry branch format)
+/// - Rejects names whose branches would not parse back as the same stack
+///   (e.g. `--` with the default entry branch format)
 /// - Rejects names with other invalid git ref characters
 ///
 /// Returns the sanitized name or an error
@@ -1085,10 +1209,15 @@ pub fn sanitize_stack_name(name: &str) -> Result<String> {
         ));
     }
 
-    if sanitized.contains("--") {
-        return Err(GgError::InvalidStackName(
-            "Stack name cannot contain '--' (conflicts with entry branch format)".to_string(),
-        ));
+    if let Some(reason) = branch_naming::current().check_stack_name(&sanitized) {
+        return Err(GgError::InvalidStackName(if sanitized.contains("--") {
+            "Stack name cannot contain '--' (conflicts with entry branch format)".to_string()
+        } else {
+            format!(
+                "Stack name '{}' conflicts with branch naming: {}",
+                sanitized, reason
+            )
+        }));
     }
 
     // Check for other invalid git ref characters
@@ -2231,6 +2360,16 @@ mod tests {
         std::fs::remove_file(&index_lock).ok();
     }
 
+    #[test]
+    fn test_is_protected_branch() {
+        let patterns = vec!["main".to_string(), "release/*".to_string()];
+        assert!(is_protected_branch(&patterns, "main"));
+        assert!(is_protected_branch(&patterns, "release/1.2"));
+        assert!(!is_protected_branch(&patterns, "maintenance"));
+        assert!(!is_protected_branch(&patterns, "nacho/release--c-1234567"));
+        assert!(!is_protected_branch(&[], "main"));
+    }
+
     #[test]
     #[allow(clippy::type_complexity)]
     fn test_build_push_args_matrix() {
@@ -2265,7 +2404,7 @@ mod tests {
         ];
 
         for ((fwl, hard, no_verify), expected) in cases {
-            let got = build_push_args("feat/x", *fwl, *hard, *no_verify);
+            let got = build_push_args("origin", "feat/x", *fwl, *hard, *no_verify);
             assert_eq!(
                 got.as_slice(),
                 *expected,
@@ -2315,7 +2454,7 @@ mod tests {
             &["update-ref", "-d", "refs/remotes/origin/feature"],
         );
 
-        let deleted = delete_remote_branch(&repo, "feature").unwrap();
+        let deleted = delete_remote_branch(&repo, "origin", "feature").unwrap();
         assert_eq!(deleted, Some(server_oid));
         let output = Command::new("git")
             .args([
@@ -2346,7 +2485,7 @@ mod tests {
             ],
         );
 
-        let deleted = delete_remote_branch(&repo, "feature").unwrap();
+        let deleted = delete_remote_branch(&repo, "origin", "feature").unwrap();
         assert_eq!(deleted, Some(server_oid));
         let output = Command::new("git")
             .args([
@@ -2391,7 +2530,7 @@ mod tests {
             std::fs::set_permissions(&hook, permissions).unwrap();
         }
 
-        let error = delete_remote_branch(&repo, "feature").unwrap_err();
+        let error = delete_remote_branch(&repo, "origin", "feature").unwrap_err();
         assert!(error.to_string().contains("server tip changed"));
         let output = Command::new("git")
             .args([
@@ -2417,6 +2556,7 @@ mod tests {
 pub enum RemoteProvider {
     GitHub,
     GitLab,
+    Gitea,
 }
 
 /// Detect the remote provider based on a remote URL
@@ -2425,11 +2565,20 @@ pub fn detect_remote_provider_from_url(url: &str) -> Option<RemoteProvider> {
     match remote_url_host(url)?.to_ascii_lowercase().as_str() {
         "github.com" => Some(RemoteProvider::GitHub),
         "gitlab.com" => Some(RemoteProvider::GitLab),
+        "gitea.com" | "codeberg.org" => Some(RemoteProvider::Gitea),
+        // Self-hosted instances are commonly served from a `gitea.` or
+        // `forgejo.` subdomain (or a host named after them).
+        host if host
+            .split('.')
+            .any(|label| matches!(label, "gitea" | "forgejo")) =>
+        {
+            Some(RemoteProvider::Gitea)
+        }
         _ => None,
     }
 }
 
-fn remote_url_host(url: &str) -> Option<&str> {
+pub(crate) fn remote_url_host(url: &str) -> Option<&str> {
     let url = url.trim();
     if url.is_empty() {
         return None;
@@ -2458,20 +2607,14 @@ fn remote_url_host(url: &str) -> Option<&str> {
     }
 }
 
-/// Detect the remote provider based on the remote URL
+/// Detect the remote provider based on the URL of [`provider_remote`]
 #[allow(dead_code)]
 pub fn detect_remote_provider(repo: &Repository) -> Result<RemoteProvider> {
-    let remote = repo
-        .find_remote("origin")
-        .map_err(|_| GgError::Other("No origin remote found".to_string()))?;
+    let url = provider_remote_url(repo)?;
 
-    let url = remote
-        .url()
-        .map_err(|_| GgError::Other("Origin remote has no URL".to_string()))?;
-
-    detect_remote_provider_from_url(url).ok_or_else(|| {
+    detect_remote_provider_from_url(&url).ok_or_else(|| {
         GgError::Other(format!(
-            "Could not detect remote provider from URL: {}. Supported: github.com, gitlab.com",
+            "Could not detect remote provider from URL: {}. Supported: github.com, gitlab.com, Gitea/Forgejo hosts. Set `defaults.provider` for other self-hosted instances",
             url
         ))
     })
@@ -2517,6 +2660,26 @@ mod remote_provider_tests {
         }
     }
 
+    #[test]
+    fn detects_gitea_and_forgejo_remotes() {
+        for url in [
+            "git@codeberg.org:user/repo.git",
+            "https://gitea.com/user/repo.git",
+            "https://gitea.mycompany.com/user/repo.git",
+            "ssh://git@forgejo.example.org:2222/user/repo.git",
+        ] {
+            assert_eq!(
+                detect_remote_provider_from_url(url),
+                Some(RemoteProvider::Gitea),
+                "url: {url}"
+            );
+        }
+        assert_eq!(
+            detect_remote_provider_from_url("git@mygitea.example.com:user/repo.git"),
+            None
+        );
+    }
+
     #[test]
     fn ignores_unknown_self_hosted_and_false_positive_remotes() {
         for url in [
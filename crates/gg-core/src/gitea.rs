@@ -0,0 +1,835 @@
+//! Gitea / Forgejo integration
+//!
+//! Talks to the REST API (`/api/v1`) directly, so no CLI has to be
+//! installed. The instance and repository come from the stack's remote;
+//! `defaults.gitea.url` overrides the web URL when the SSH host differs from
+//! it. The token is read from `GITEA_TOKEN` / `FORGEJO_TOKEN`, falling back
+//! to a matching `tea` login.
+
+use serde::Deserialize;
+use serde_json::{json, Value};
+
+use crate::api;
+use crate::config::{Config, MergeStrategy};
+use crate::error::{GgError, Result};
+use crate::git;
+use crate::glab::AutoMergeResult;
+use crate::relative_time;
+
+/// Pull request state from Gitea
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum PullState {
+    Open,
+    Merged,
+    Closed,
+    Draft,
+}
+
+/// Combined commit status from Gitea
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum CiStatus {
+    Pending,
+    Success,
+    Failed,
+    Unknown,
+}
+
+/// Pull request information
+#[derive(Debug, Clone)]
+pub struct PullInfo {
+    pub number: u64,
+    pub title: String,
+    pub state: PullState,
+    pub url: String,
+    pub head_branch: Option<String>,
+    pub base_branch: Option<String>,
+    pub draft: bool,
+    pub mergeable: bool,
+    /// Last activity on the PR, in milliseconds since the epoch
+    pub updated_at_ms: Option<u64>,
+}
+
+/// An open PR authored by the current user.
+#[derive(Debug, Clone)]
+pub struct OpenPull {
+    pub number: u64,
+    pub title: String,
+    pub head_branch: String,
+    pub base_branch: String,
+}
+
+/// A comment on a PR's conversation.
+#[derive(Debug, Clone)]
+pub struct Comment {
+    pub id: u64,
+    pub body: String,
+}
+
+#[derive(Debug, Deserialize)]
+struct PullJson {
+    number: u64,
+    title: String,
+    #[serde(default)]
+    body: Option<String>,
+    state: String,
+    #[serde(default)]
+    merged: bool,
+    #[serde(default)]
+    draft: Option<bool>,
+    #[serde(default)]
+    mergeable: bool,
+    html_url: String,
+    head: Option<BranchJson>,
+    base: Option<BranchJson>,
+    user: Option<UserJson>,
+    updated_at: Option<String>,
+}
+
+#[derive(Debug, Deserialize)]
+struct BranchJson {
+    #[serde(rename = "ref")]
+    ref_name: String,
+}
+
+#[derive(Debug, Deserialize)]
+struct UserJson {
+    login: String,
+}
+
+#[derive(Debug, Deserialize)]
+struct ReviewJson {
+    state: String,
+    #[serde(default)]
+    dismissed: bool,
+    #[serde(default)]
+    stale: bool,
+    user: Option<UserJson>,
+}
+
+#[derive(Debug, Deserialize)]
+struct CommentJson {
+    id: u64,
+    body: String,
+}
+
+/// Where the repository lives and how to authenticate against it.
+#[derive(Debug, Clone, PartialEq, Eq)]
+struct Context {
+    /// Web URL of the instance, without a trailing slash
+    base_url: String,
+    owner: String,
+    repo: String,
+    token: String,
+}
+
+impl Context {
+    fn repo_path(&self, path: &str) -> String {
+        format!("/repos/{}/{}{}", self.owner, self.repo, path)
+    }
+}
+
+/// Split a remote URL into the instance's web URL, owner and repository.
+///
+/// HTTP(S) remotes keep their scheme, port and any sub-path the instance is
+/// served from; SSH remotes map to `https://<host>`.
+fn parse_remote(url: &str) -> Option<(String, String, String)> {
+    let url = url.trim().trim_end_matches('/');
+    let (web_prefix, path) = if let Some(rest) = url
+        .strip_prefix("https://")
+        .or_else(|| url.strip_prefix("http://"))
+    {
+        let scheme = if url.starts_with("https://") {
+            "https"
+        } else {
+            "http"
+        };
+        let rest = rest.rsplit_once('@').map_or(rest, |(_, rest)| rest);
+        let mut segments: Vec<&str> = rest.split('/').filter(|s| !s.is_empty()).collect();
+        if segments.len() < 3 {
+            return None;
+        }
+        let repo = segments.pop()?;
+        let owner = segments.pop()?;
+        (
+            format!("{}://{}", scheme, segments.join("/")),
+            format!("{}/{}", owner, repo),
+        )
+    } else {
+        let host = git::remote_url_host(url)?;
+        let path = if let Some((_, rest)) = url.split_once("://") {
+            rest.split_once('/')?.1
+        } else {
+            url.split_once(':')?.1
+        };
+        (format!("https://{}", host), path.to_string())
+    };
+
+    let path = path.trim_end_matches(".git");
+    let (owner, repo) = path.rsplit_once('/')?;
+    let owner = owner.rsplit('/').next()?;
+    if owner.is_empty() || repo.is_empty() {
+        return None;
+    }
+    Some((web_prefix, owner.to_string(), repo.to_string()))
+}
+
+/// Find the token of the `tea` login whose URL matches `base_url` in the
+/// contents of tea's `config.yml`.
+fn parse_tea_token(config: &str, base_url: &str) -> Option<String> {
+    let mut logins: Vec<(Option<String>, Option<String>)> = Vec::new();
+    for line in config.lines() {
+        let trimmed = line.trim();
+        let entry = if let Some(rest) = trimmed.strip_prefix("- ") {
+            logins.push((None, None));
+            rest
+        } else {
+            trimmed
+        };
+        let Some((key, value)) = entry.split_once(':') else {
+            continue;
+        };
+        let value = value.trim().trim_matches(['"', '\'']).to_string();
+        if let Some(login) = logins.last_mut() {
+            match key.trim() {
+                "url" => login.0 = Some(value),
+                "token" => login.1 = Some(value),
+                _ => {}
+            }
+        }
+    }
+
+    let wanted = base_url.trim_end_matches('/');
+    logins.into_iter().find_map(|(url, token)| {
+        let url = url?;
+        let token = token.filter(|t| !t.is_empty())?;
+        (url.trim_end_matches('/') == wanted).then_some(token)
+    })
+}
+
+fn find_token(base_url: &str) -> Option<String> {
+    for var in ["GITEA_TOKEN", "FORGEJO_TOKEN"] {
+        if let Ok(token) = std::env::var(var) {
+            if !token.trim().is_empty() {
+                return Some(token.trim().to_string());
+            }
+        }
+    }
+    let path = dirs::home_dir()?
+        .join(".config")
+        .join("tea")
+        .join("config.yml");
+    let contents = std::fs::read_to_string(path).ok()?;
+    parse_tea_token(&contents, base_url)
+}
+
+fn context() -> Result<Context> {
+    let repo = git::open_repo()?;
+    let url = api::remote_url()?;
+    let (detected_url, owner, name) = parse_remote(&url).ok_or_else(|| {
+        GgError::GiteaError(format!("Could not parse owner/repo from remote: {}", url))
+    })?;
+
+    let base_url = Config::load(repo.commondir())
+        .ok()
+        .and_then(|config| config.defaults.gitea.url)
+        .unwrap_or(detected_url)
+        .trim_end_matches('/')
+        .to_string();
+    let token = find_token(&base_url).ok_or(GgError::GiteaNotAuthenticated)?;
+
+    Ok(Context {
+        base_url,
+        owner,
+        repo: name,
+        token,
+    })
+}
+
+/// Send an API request and return the response body (`Value::Null` when
+/// empty).
+fn request(ctx: &Context, method: &str, path: &str, body: Option<Value>) -> Result<Value> {
+    let url = format!("{}/api/v1{}", ctx.base_url, path);
+    let auth = format!("token {}", ctx.token);
+    api::request(method, &url, &[("Authorization", &auth)], body).map_err(|e| {
+        e.into_gg(&format!("{} {}", method, path), GgError::GiteaError, || {
+            GgError::GiteaNotAuthenticated
+        })
+    })
+}
+
+fn parse<T: for<'de> Deserialize<'de>>(value: Value) -> Result<T> {
+    serde_json::from_value(value).map_err(|e| GgError::GiteaError(e.to_string()))
+}
+
+/// Gitea marks drafts with a work-in-progress title prefix.
+fn is_wip_title(title: &str) -> bool {
+    let lower = title.to_lowercase();
+    ["wip:", "[wip]", "draft:", "[draft]"]
+        .iter()
+        .any(|prefix| lower.starts_with(prefix))
+}
+
+/// Remove work-in-progress prefixes recognised by [`is_wip_title`].
+fn strip_wip_prefix(title: &str) -> &str {
+    let mut rest = title.trim_start();
+    while is_wip_title(rest) {
+        let len = if rest.starts_with('[') {
+            rest.find(']').map_or(rest.len(), |i| i + 1)
+        } else {
+            rest.find(':').map_or(rest.len(), |i| i + 1)
+        };
+        rest = rest[len..].trim_start();
+    }
+    rest
+}
+
+fn convert_pull(pull: PullJson) -> PullInfo {
+    let draft = pull.draft.unwrap_or(false) || is_wip_title(&pull.title);
+    let state = if pull.merged {
+        PullState::Merged
+    } else if pull.state == "closed" {
+        PullState::Closed
+    } else if draft {
+        PullState::Draft
+    } else {
+        PullState::Open
+    };
+    PullInfo {
+        number: pull.number,
+        state,
+        url: pull.html_url,
+        head_branch: pull.head.map(|b| b.ref_name),
+        base_branch: pull.base.map(|b| b.ref_name),
+        draft,
+        mergeable: pull.mergeable,
+        updated_at_ms: pull
+            .updated_at
+            .as_deref()
+            .and_then(relative_time::parse_rfc3339_ms),
+        title: pull.title,
+    }
+}
+
+/// `(approved, changes_requested)` from a PR's reviews, counting only the
+/// latest non-dismissed review of each reviewer.
+fn review_decision(reviews: &[ReviewJson]) -> (bool, bool) {
+    let mut latest: std::collections::BTreeMap<&str, &str> = std::collections::BTreeMap::new();
+    for review in reviews {
+        if review.dismissed || review.stale {
+            continue;
+        }
+        if !matches!(review.state.as_str(), "APPROVED" | "REQUEST_CHANGES") {
+            continue;
+        }
+        let user = review.user.as_ref().map_or("", |u| u.login.as_str());
+        latest.insert(user, review.state.as_str());
+    }
+    let changes_requested = latest.values().any(|s| *s == "REQUEST_CHANGES");
+    let approved = latest.values().any(|s| *s == "APPROVED");
+    (approved, changes_requested)
+}
+
+fn convert_ci_state(state: &str) -> CiStatus {
+    match state {
+        "pending" => CiStatus::Pending,
+        "success" | "warning" => CiStatus::Success,
+        "failure" | "error" => CiStatus::Failed,
+        _ => CiStatus::Unknown,
+    }
+}
+
+/// Check that a token is configured and accepted.
+pub fn check_auth() -> Result<()> {
+    whoami().map(|_| ())
+}
+
+/// Get the username of the authenticated user
+pub fn whoami() -> Result<String> {
+    let ctx = context()?;
+    let user: UserJson = parse(request(&ctx, "GET", "/user", None)?)?;
+    Ok(user.login)
+}
+
+/// Create a pull request. Drafts get a `WIP: ` title prefix.
+pub fn create_pr(
+    head: &str,
+    base: &str,
+    title: &str,
+    body: &str,
+    draft: bool,
+) -> Result<(u64, String)> {
+    let ctx = context()?;
+    let title = if draft && !is_wip_title(title) {
+        format!("WIP: {}", title)
+    } else {
+        title.to_string()
+    };
+    let pull: PullJson = parse(request(
+        &ctx,
+        "POST",
+        &ctx.repo_path("/pulls"),
+        Some(json!({ "head": head, "base": base, "title": title, "body": body })),
+    )?)?;
+    Ok((pull.number, pull.html_url))
+}
+
+fn get_pull(ctx: &Context, number: u64) -> Result<PullJson> {
+    parse(request(
+        ctx,
+        "GET",
+        &ctx.repo_path(&format!("/pulls/{}", number)),
+        None,
+    )?)
+}
+
+/// Get pull request information
+pub fn get_pr_info(number: u64) -> Result<PullInfo> {
+    let ctx = context()?;
+    Ok(convert_pull(get_pull(&ctx, number)?))
+}
+
+/// Get the body of a pull request
+pub fn get_pr_body(number: u64) -> Result<String> {
+    let ctx = context()?;
+    Ok(get_pull(&ctx, number)?.body.unwrap_or_default())
+}
+
+fn edit_pull(number: u64, fields: Value) -> Result<()> {
+    let ctx = context()?;
+    request(
+        &ctx,
+        "PATCH",
+        &ctx.repo_path(&format!("/pulls/{}", number)),
+        Some(fields),
+    )?;
+    Ok(())
+}
+
+/// Change the base branch of a pull request
+pub fn update_pr_base(number: u64, base: &str) -> Result<()> {
+    edit_pull(number, json!({ "base": base }))
+}
+
+/// Request reviews from users and teams (`org/team`)
+pub fn request_reviewers(number: u64, reviewers: &[String]) -> Result<()> {
+    let (teams, users): (Vec<&String>, Vec<&String>) =
+        reviewers.iter().partition(|r| r.contains('/'));
+    let team_names: Vec<&str> = teams
+        .iter()
+        .filter_map(|team| team.rsplit('/').next())
+        .collect();
+    let ctx = context()?;
+    request(
+        &ctx,
+        "POST",
+        &ctx.repo_path(&format!("/pulls/{}/requested_reviewers", number)),
+        Some(json!({ "reviewers": users, "team_reviewers": team_names })),
+    )?;
+    Ok(())
+}
+
+#[derive(Debug, Deserialize)]
+struct NamedJson {
+    id: u64,
+    #[serde(alias = "title")]
+    name: String,
+}
+
+/// Look up the IDs of repository labels by name.
+fn label_ids(ctx: &Context, labels: &[String]) -> Result<Vec<u64>> {
+    let known: Vec<NamedJson> = parse(request(
+        ctx,
+        "GET",
+        &ctx.repo_path("/labels?limit=100"),
+        None,
+    )?)?;
+    labels
+        .iter()
+        .map(|label| {
+            known
+                .iter()
+                .find(|k| &k.name == label)
+                .map(|k| k.id)
+                .ok_or_else(|| GgError::GiteaError(format!("No label named '{}'", label)))
+        })
+        .collect()
+}
+
+/// Add labels to a pull request
+pub fn add_labels(number: u64, labels: &[String]) -> Result<()> {
+    let ctx = context()?;
+    let ids = label_ids(&ctx, labels)?;
+    request(
+        &ctx,
+        "POST",
+        &ctx.repo_path(&format!("/issues/{}/labels", number)),
+        Some(json!({ "labels": ids })),
+    )?;
+    Ok(())
+}
+
+/// Remove labels from a pull request
+pub fn remove_labels(number: u64, labels: &[String]) -> Result<()> {
+    let ctx = context()?;
+    for id in label_ids(&ctx, labels)? {
+        request(
+            &ctx,
+            "DELETE",
+            &ctx.repo_path(&format!("/issues/{}/labels/{}", number, id)),
+            None,
+        )?;
+    }
+    Ok(())
+}
+
+/// Set the milestone of a pull request by title
+pub fn set_milestone(number: u64, milestone: &str) -> Result<()> {
+    let ctx = context()?;
+    let milestones: Vec<NamedJson> = parse(request(
+        &ctx,
+        "GET",
+        &ctx.repo_path(&format!("/milestones?name={}", api::encode(milestone))),
+        None,
+    )?)?;
+    let id = milestones
+        .iter()
+        .find(|m| m.name == milestone)
+        .map(|m| m.id)
+        .ok_or_else(|| GgError::GiteaError(format!("No milestone named '{}'", milestone)))?;
+    edit_pull(number, json!({ "milestone": id }))
+}
+
+/// Mark a work-in-progress pull request as ready by dropping its WIP prefix
+pub fn mark_ready(number: u64) -> Result<()> {
+    let ctx = context()?;
+    let pull = get_pull(&ctx, number)?;
+    edit_pull(number, json!({ "title": strip_wip_prefix(&pull.title) }))
+}
+
+/// Change the title of a pull request
+pub fn update_pr_title(number: u64, title: &str) -> Result<()> {
+    edit_pull(number, json!({ "title": title }))
+}
+
+/// Change the body of a pull request
+pub fn update_pr_body(number: u64, body: &str) -> Result<()> {
+    edit_pull(number, json!({ "body": body }))
+}
+
+/// Close a pull request without merging
+pub fn close_pr(number: u64) -> Result<()> {
+    edit_pull(number, json!({ "state": "closed" }))
+}
+
+/// List the conversation comments of a pull request
+pub fn list_comments(number: u64) -> Result<Vec<Comment>> {
+    let ctx = context()?;
+    let comments: Vec<CommentJson> = parse(request(
+        &ctx,
+        "GET",
+        &ctx.repo_path(&format!("/issues/{}/comments", number)),
+        None,
+    )?)?;
+    Ok(comments
+        .into_iter()
+        .map(|c| Comment {
+            id: c.id,
+            body: c.body,
+        })
+        .collect())
+}
+
+/// Add a comment to a pull request
+pub fn create_comment(number: u64, body: &str) -> Result<()> {
+    let ctx = context()?;
+    request(
+        &ctx,
+        "POST",
+        &ctx.repo_path(&format!("/issues/{}/comments", number)),
+        Some(json!({ "body": body })),
+    )?;
+    Ok(())
+}
+
+/// Replace the body of a comment
+pub fn update_comment(comment_id: u64, body: &str) -> Result<()> {
+    let ctx = context()?;
+    request(
+        &ctx,
+        "PATCH",
+        &ctx.repo_path(&format!("/issues/comments/{}", comment_id)),
+        Some(json!({ "body": body })),
+    )?;
+    Ok(())
+}
+
+/// Delete a comment
+pub fn delete_comment(comment_id: u64) -> Result<()> {
+    let ctx = context()?;
+    request(
+        &ctx,
+        "DELETE",
+        &ctx.repo_path(&format!("/issues/comments/{}", comment_id)),
+        None,
+    )?;
+    Ok(())
+}
+
+fn reviews(ctx: &Context, number: u64) -> Result<(bool, bool)> {
+    let reviews: Vec<ReviewJson> = parse(request(
+        ctx,
+        "GET",
+        &ctx.repo_path(&format!("/pulls/{}/reviews", number)),
+        None,
+    )?)?;
+    Ok(review_decision(&reviews))
+}
+
+/// `(approved, changes_requested)` for a pull request
+pub fn get_review_decision(number: u64) -> Result<(bool, bool)> {
+    let ctx = context()?;
+    reviews(&ctx, number)
+}
+
+/// Whether a pull request is approved with no outstanding change requests
+pub fn check_pr_approved(number: u64) -> Result<bool> {
+    let (approved, changes_requested) = get_review_decision(number)?;
+    Ok(approved && !changes_requested)
+}
+
+fn merge_body(strategy: MergeStrategy, delete_branch: bool, when_checks_succeed: bool) -> Value {
+    json!({
+        "Do": strategy.as_str(),
+        "delete_branch_after_merge": delete_branch,
+        "merge_when_checks_succeed": when_checks_succeed,
+    })
+}
+
+/// Merge a pull request now
+pub fn merge_pr(number: u64, strategy: MergeStrategy, delete_branch: bool) -> Result<()> {
+    let ctx = context()?;
+    request(
+        &ctx,
+        "POST",
+        &ctx.repo_path(&format!("/pulls/{}/merge", number)),
+        Some(merge_body(strategy, delete_branch, false)),
+    )?;
+    Ok(())
+}
+
+/// Schedule a pull request to merge once its checks succeed
+pub fn auto_merge_pr(
+    number: u64,
+    strategy: MergeStrategy,
+    delete_branch: bool,
+) -> Result<AutoMergeResult> {
+    let ctx = context()?;
+    match request(
+        &ctx,
+        "POST",
+        &ctx.repo_path(&format!("/pulls/{}/merge", number)),
+        Some(merge_body(strategy, delete_branch, true)),
+    ) {
+        Ok(_) => Ok(AutoMergeResult::Queued),
+        Err(GgError::GiteaError(msg)) if msg.to_lowercase().contains("already scheduled") => {
+            Ok(AutoMergeResult::AlreadyQueued)
+        }
+        Err(e) => Err(e),
+    }
+}
+
+/// Combined commit status of a pull request's head
+pub fn get_pr_ci_status(number: u64) -> Result<CiStatus> {
+    let ctx = context()?;
+    let pull: Value = request(
+        &ctx,
+        "GET",
+        &ctx.repo_path(&format!("/pulls/{}", number)),
+        None,
+    )?;
+    let Some(sha) = pull["head"]["sha"].as_str() else {
+        return Ok(CiStatus::Unknown);
+    };
+    let status = request(
+        &ctx,
+        "GET",
+        &ctx.repo_path(&format!("/commits/{}/status", sha)),
+        None,
+    )?;
+    if status["total_count"].as_u64() == Some(0) {
+        return Ok(CiStatus::Unknown);
+    }
+    Ok(convert_ci_state(status["state"].as_str().unwrap_or("")))
+}
+
+fn list_open_pulls(ctx: &Context) -> Result<Vec<PullJson>> {
+    let mut all = Vec::new();
+    for page in 1.. {
+        let pulls: Vec<PullJson> = parse(request(
+            ctx,
+            "GET",
+            &ctx.repo_path(&format!("/pulls?state=open&limit=50&page={}", page)),
+            None,
+        )?)?;
+        let done = pulls.len() < 50;
+        all.extend(pulls);
+        if done {
+            break;
+        }
+    }
+    Ok(all)
+}
+
+/// Numbers of open pull requests whose head is `branch`
+pub fn list_prs_for_branch(branch: &str) -> Result<Vec<u64>> {
+    let ctx = context()?;
+    Ok(list_open_pulls(&ctx)?
+        .into_iter()
+        .filter(|p| p.head.as_ref().is_some_and(|h| h.ref_name == branch))
+        .map(|p| p.number)
+        .collect())
+}
+
+/// Open pull requests authored by the authenticated user
+pub fn list_my_open_prs() -> Result<Vec<OpenPull>> {
+    let me = whoami()?;
+    let ctx = context()?;
+    Ok(list_open_pulls(&ctx)?
+        .into_iter()
+        .filter(|p| p.user.as_ref().is_some_and(|u| u.login == me))
+        .filter_map(|p| {
+            Some(OpenPull {
+                number: p.number,
+                title: p.title,
+                head_branch: p.head?.ref_name,
+                base_branch: p.base?.ref_name,
+            })
+        })
+        .collect())
+}
+
+/// Rebase a pull request's head onto its base on the server
+pub fn rebase_pr(number: u64) -> Result<()> {
+    let ctx = context()?;
+    request(
+        &ctx,
+        "POST",
+        &ctx.repo_path(&format!("/pulls/{}/update?style=rebase", number)),
+        None,
+    )?;
+    Ok(())
+}
+
+/// Whether the protection rule of `branch` requires signed commits.
+/// Unprotected branches (404) require nothing.
+pub fn requires_signed_commits(branch: &str) -> Result<bool> {
+    let ctx = context()?;
+    match request(
+        &ctx,
+        "GET",
+        &ctx.repo_path(&format!("/branch_protections/{}", branch)),
+        None,
+    ) {
+        Ok(rule) => Ok(rule["require_signed_commits"].as_bool().unwrap_or(false)),
+        Err(GgError::GiteaError(msg))
+            if msg.contains("(HTTP 404)") || msg.contains("(HTTP 403)") =>
+        {
+            Ok(false)
+        }
+        Err(e) => Err(e),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parses_remote_urls() {
+        assert_eq!(
+            parse_remote("git@codeberg.org:owner/project.git"),
+            Some((
+                "https://codeberg.org".to_string(),
+                "owner".to_string(),
+                "project".to_string()
+            ))
+        );
+        assert_eq!(
+            parse_remote("ssh://git@gitea.example.com:2222/team/app.git"),
+            Some((
+                "https://gitea.example.com".to_string(),
+                "team".to_string(),
+                "app".to_string()
+            ))
+        );
+        assert_eq!(
+            parse_remote("http://localhost:3000/git/team/app.git/"),
+            Some((
+                "http://localhost:3000/git".to_string(),
+                "team".to_string(),
+                "app".to_string()
+            ))
+        );
+        assert_eq!(parse_remote("https://gitea.com/app"), None);
+    }
+
+    #[test]
+    fn finds_tea_token_for_instance() {
+        let config = "logins:\n  - name: work\n    url: https://git.work.example\n    token: abc\n  - name: codeberg\n    url: \"https://codeberg.org/\"\n    token: def\n";
+        assert_eq!(
+            parse_tea_token(config, "https://codeberg.org"),
+            Some("def".to_string())
+        );
+        assert_eq!(parse_tea_token(config, "https://gitea.com"), None);
+    }
+
+    #[test]
+    fn converts_pull_json() {
+        let pull: PullJson = serde_json::from_str(
+            r#"{"number": 7, "title": "WIP: Add parser", "state": "open", "merged": false,
+                "mergeable": true, "html_url": "https://codeberg.org/o/r/pulls/7",
+                "head": {"ref": "nacho/parser--c-abc1234"}, "base": {"ref": "main"},
+                "updated_at": "2024-01-15T10:30:00Z"}"#,
+        )
+        .unwrap();
+        let info = convert_pull(pull);
+        assert_eq!(info.state, PullState::Draft);
+        assert!(info.draft);
+        assert_eq!(info.head_branch.as_deref(), Some("nacho/parser--c-abc1234"));
+        assert_eq!(info.base_branch.as_deref(), Some("main"));
+        assert!(info.updated_at_ms.is_some());
+
+        let merged: PullJson = serde_json::from_str(
+            r#"{"number": 8, "title": "Done", "state": "closed", "merged": true,
+                "html_url": "u", "head": null, "base": null}"#,
+        )
+        .unwrap();
+        assert_eq!(convert_pull(merged).state, PullState::Merged);
+    }
+
+    #[test]
+    fn latest_review_per_user_wins() {
+        let reviews: Vec<ReviewJson> = serde_json::from_str(
+            r#"[{"state": "REQUEST_CHANGES", "user": {"login": "a"}},
+                {"state": "COMMENT", "user": {"login": "b"}},
+                {"state": "APPROVED", "user": {"login": "a"}}]"#,
+        )
+        .unwrap();
+        assert_eq!(review_decision(&reviews), (true, false));
+
+        let reviews: Vec<ReviewJson> = serde_json::from_str(
+            r#"[{"state": "APPROVED", "user": {"login": "a"}},
+                {"state": "REQUEST_CHANGES", "user": {"login": "b"}},
+                {"state": "REQUEST_CHANGES", "dismissed": true, "user": {"login": "c"}}]"#,
+        )
+        .unwrap();
+        assert_eq!(review_decision(&reviews), (true, true));
+    }
+
+    #[test]
+    fn strips_wip_prefix() {
+        assert_eq!(strip_wip_prefix("WIP: Add parser"), "Add parser");
+        assert_eq!(strip_wip_prefix("[wip] Draft: Add parser"), "Add parser");
+        assert_eq!(strip_wip_prefix("Add parser"), "Add parser");
+    }
+}
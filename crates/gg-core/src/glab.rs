@@ -1,6 +1,7 @@
 //! GitLab CLI (glab) integration
 //!
-//! Wraps glab subprocess calls for MR management.
+//! Wraps glab subprocess calls for MR management. With `api_mode: native`,
+//! the core operations are served by [`crate::glab_api`] instead.
 //! NOTE: This module is kept for future GitLab support but is not currently used.
 
 #![allow(dead_code)]
@@ -9,7 +10,11 @@ use std::process::Command;
 
 use serde::Deserialize;
 
+use crate::api;
+use crate::config::MergeStrategy;
 use crate::error::{GgError, Result};
+use crate::glab_api;
+use crate::relative_time;
 
 /// MR state from GitLab
 #[derive(Debug, Clone, PartialEq, Eq)]
@@ -29,11 +34,14 @@ pub struct MrInfo {
     pub state: MrState,
     pub web_url: String,
     pub head_branch: Option<String>,
+    pub base_branch: Option<String>,
     pub draft: bool,
     pub approved: bool,
     pub mergeable: bool,
     pub changes_requested: bool,
     pub detailed_merge_status: Option<String>,
+    /// Last activity on the MR, in milliseconds since the epoch
+    pub updated_at_ms: Option<u64>,
 }
 
 /// JSON response from `glab mr view --json`
@@ -44,14 +52,20 @@ struct GlabMrJson {
     state: String,
     web_url: String,
     source_branch: Option<String>,
+    target_branch: Option<String>,
     draft: Option<bool>,
     work_in_progress: Option<bool>,
     detailed_merge_status: Option<String>,
+    updated_at: Option<String>,
 }
 
 /// Check if glab is installed
 pub fn check_glab_installed() -> Result<()> {
-    let output = Command::new("glab").arg("--version").output();
+    if api::native_mode() {
+        return Ok(());
+    }
+
+    let output = glab_command().arg("--version").output();
 
     match output {
         Ok(o) if o.status.success() => Ok(()),
@@ -66,7 +80,11 @@ pub fn check_glab_installed() -> Result<()> {
 /// - Returns `Err(GgError::NetworkError(...))` if a network error is detected
 /// - Returns `Err(GgError::GlabNotAuthenticated)` for actual auth failures
 pub fn check_glab_auth() -> Result<()> {
-    let output = Command::new("glab").args(["auth", "status"]).output()?;
+    if api::native_mode() {
+        return glab_api::check_auth();
+    }
+
+    let output = glab_command().args(["auth", "status"]).output()?;
 
     if output.status.success() {
         return Ok(());
@@ -88,9 +106,11 @@ pub fn check_glab_auth() -> Result<()> {
 
 /// Get the current GitLab username
 pub fn whoami() -> Result<String> {
-    let output = Command::new("glab")
-        .args(["auth", "status", "-t"])
-        .output()?;
+    if api::native_mode() {
+        return glab_api::whoami();
+    }
+
+    let output = glab_command().args(["auth", "status", "-t"]).output()?;
 
     if !output.status.success() {
         return Err(GgError::GlabNotAuthenticated);
@@ -115,7 +135,7 @@ pub fn whoami() -> Result<String> {
 
     // Fallback: try `glab api user`
     // Note: We don't use --jq flag as it's not available in all glab versions
-    let api_output = Command::new("glab").args(["api", "user"]).output()?;
+    let api_output = glab_command().args(["api", "user"]).output()?;
 
     if api_output.status.success() {
         let stdout = String::from_utf8_lossy(&api_output.stdout);
@@ -148,6 +168,10 @@ pub fn create_mr(
     description: &str,
     draft: bool,
 ) -> Result<MrCreationResult> {
+    if api::native_mode() {
+        return glab_api::create_mr(source_branch, target_branch, title, description, draft);
+    }
+
     let mut args = vec![
         "mr",
         "create",
@@ -166,7 +190,7 @@ pub fn create_mr(
         args.push("--draft");
     }
 
-    let output = Command::new("glab").args(&args).output()?;
+    let output = glab_command().args(&args).output()?;
 
     if !output.status.success() {
         let stderr = String::from_utf8_lossy(&output.stderr);
@@ -306,7 +330,11 @@ fn parse_mr_number_from_output(stdout: &str, stderr: &str) -> Result<u64> {
 
 /// View MR information
 pub fn view_mr(mr_number: u64) -> Result<MrInfo> {
-    let output = Command::new("glab")
+    if api::native_mode() {
+        return glab_api::view_mr(mr_number);
+    }
+
+    let output = glab_command()
         .args(["mr", "view", &mr_number.to_string(), "--output", "json"])
         .output()?;
 
@@ -318,10 +346,25 @@ pub fn view_mr(mr_number: u64) -> Result<MrInfo> {
         )));
     }
 
-    let stdout = String::from_utf8_lossy(&output.stdout);
-    let mr_json: GlabMrJson = serde_json::from_str(&stdout)
+    parse_mr_info(&String::from_utf8_lossy(&output.stdout))
+}
+
+/// Parse an MR as returned by `glab mr view --output json` or the
+/// `merge_requests/:iid` API.
+pub(crate) fn parse_mr_info(json: &str) -> Result<MrInfo> {
+    let mr_json: GlabMrJson = serde_json::from_str(json)
         .map_err(|e| GgError::GlabError(format!("Failed to parse MR JSON: {}", e)))?;
+    Ok(convert_mr_json(mr_json))
+}
 
+/// Parse a `merge_requests` list response.
+pub(crate) fn parse_mr_list(json: &str) -> Result<Vec<MrInfo>> {
+    let mrs: Vec<GlabMrJson> = serde_json::from_str(json)
+        .map_err(|e| GgError::GlabError(format!("Failed to parse MR list JSON: {}", e)))?;
+    Ok(mrs.into_iter().map(convert_mr_json).collect())
+}
+
+fn convert_mr_json(mr_json: GlabMrJson) -> MrInfo {
     let draft = mr_json.draft.unwrap_or(false) || mr_json.work_in_progress.unwrap_or(false);
 
     let state = match mr_json.state.as_str() {
@@ -333,28 +376,81 @@ pub fn view_mr(mr_number: u64) -> Result<MrInfo> {
 
     let mergeable = state == MrState::Open && !draft;
 
-    Ok(MrInfo {
+    MrInfo {
         iid: mr_json.iid,
         title: mr_json.title,
         state,
         web_url: mr_json.web_url,
         head_branch: mr_json.source_branch,
+        base_branch: mr_json.target_branch,
         draft,
         approved: false, // Would need additional API call
         mergeable,
         changes_requested: false, // GitLab doesn't expose this directly
         detailed_merge_status: mr_json.detailed_merge_status,
-    })
+        updated_at_ms: mr_json
+            .updated_at
+            .as_deref()
+            .and_then(relative_time::parse_rfc3339_ms),
+    }
+}
+
+/// Most MRs asked about in one list request (GitLab's page size limit)
+pub(crate) const MRS_PER_REQUEST: usize = 100;
+
+/// `merge_requests` list query selecting exactly `mr_numbers`
+pub(crate) fn mr_iids_query(mr_numbers: &[u64]) -> String {
+    let mut query: Vec<String> = mr_numbers
+        .iter()
+        .map(|iid| format!("iids[]={}", iid))
+        .collect();
+    query.push(format!("per_page={}", MRS_PER_REQUEST));
+    query.join("&")
+}
+
+/// Several MRs with one `merge_requests?iids[]=…` list request per
+/// [`MRS_PER_REQUEST`] MRs. MRs that do not exist are left out.
+pub fn get_mrs_info(mr_numbers: &[u64]) -> Result<Vec<MrInfo>> {
+    if api::native_mode() {
+        return glab_api::get_mrs_info(mr_numbers);
+    }
+
+    let mut mrs = Vec::new();
+    for chunk in mr_numbers.chunks(MRS_PER_REQUEST) {
+        let endpoint = format!(
+            "projects/{}/merge_requests?{}",
+            glab_project_prefix(),
+            mr_iids_query(chunk)
+        );
+        let output = glab_command().args(["api", &endpoint]).output()?;
+        if !output.status.success() {
+            let stderr = String::from_utf8_lossy(&output.stderr);
+            return Err(GgError::GlabError(format!(
+                "Failed to list MRs: {}",
+                stderr
+            )));
+        }
+        mrs.extend(parse_mr_list(&String::from_utf8_lossy(&output.stdout))?);
+    }
+    Ok(mrs)
 }
 
 /// Close an MR without merging.
 pub fn close_mr(mr_number: u64) -> Result<()> {
-    let output = Command::new("glab")
+    if api::native_mode() {
+        return glab_api::close_mr(mr_number);
+    }
+
+    let output = glab_command()
         .args([
             "api",
             "--method",
             "PUT",
-            &format!("projects/:id/merge_requests/{}", mr_number),
+            &format!(
+                "projects/{}/merge_requests/{}",
+                glab_project_prefix(),
+                mr_number
+            ),
             "-f",
             "state_event=close",
         ])
@@ -378,7 +474,11 @@ pub fn get_mr_info(mr_number: u64) -> Result<MrInfo> {
 
 /// Update MR target branch
 pub fn update_mr_target(mr_number: u64, target_branch: &str) -> Result<()> {
-    let output = Command::new("glab")
+    if api::native_mode() {
+        return glab_api::update_mr_target(mr_number, target_branch);
+    }
+
+    let output = glab_command()
         .args([
             "mr",
             "update",
@@ -399,6 +499,81 @@ pub fn update_mr_target(mr_number: u64, target_branch: &str) -> Result<()> {
     Ok(())
 }
 
+/// Run `glab mr update <mr> <flag> <value>`; `what` names the change in errors.
+fn update_mr(mr_number: u64, flag: &str, value: &str, what: &str) -> Result<()> {
+    let output = glab_command()
+        .args(["mr", "update", &mr_number.to_string(), flag, value])
+        .output()?;
+
+    if !output.status.success() {
+        let stderr = String::from_utf8_lossy(&output.stderr);
+        return Err(GgError::GlabError(format!(
+            "Failed to {} on MR !{}: {}",
+            what, mr_number, stderr
+        )));
+    }
+
+    Ok(())
+}
+
+/// Request reviews from users
+pub fn request_reviewers(mr_number: u64, reviewers: &[String]) -> Result<()> {
+    if api::native_mode() {
+        return glab_api::request_reviewers(mr_number, reviewers);
+    }
+    update_mr(
+        mr_number,
+        "--reviewer",
+        &reviewers.join(","),
+        "request reviewers",
+    )
+}
+
+/// Add labels to an MR
+pub fn add_labels(mr_number: u64, labels: &[String]) -> Result<()> {
+    if api::native_mode() {
+        return glab_api::add_labels(mr_number, labels);
+    }
+    update_mr(mr_number, "--label", &labels.join(","), "add labels")
+}
+
+/// Remove labels from an MR
+pub fn remove_labels(mr_number: u64, labels: &[String]) -> Result<()> {
+    if api::native_mode() {
+        return glab_api::remove_labels(mr_number, labels);
+    }
+    update_mr(mr_number, "--unlabel", &labels.join(","), "remove labels")
+}
+
+/// Set the milestone of an MR by title
+pub fn set_milestone(mr_number: u64, milestone: &str) -> Result<()> {
+    if api::native_mode() {
+        return glab_api::set_milestone(mr_number, milestone);
+    }
+    update_mr(mr_number, "--milestone", milestone, "set the milestone")
+}
+
+/// Mark a draft MR as ready
+pub fn mark_mr_ready(mr_number: u64) -> Result<()> {
+    if api::native_mode() {
+        return glab_api::mark_mr_ready(mr_number);
+    }
+
+    let output = glab_command()
+        .args(["mr", "update", &mr_number.to_string(), "--ready"])
+        .output()?;
+
+    if !output.status.success() {
+        let stderr = String::from_utf8_lossy(&output.stderr);
+        return Err(GgError::GlabError(format!(
+            "Failed to mark MR !{} as ready: {}",
+            mr_number, stderr
+        )));
+    }
+
+    Ok(())
+}
+
 /// JSON response for getting MR body only
 #[derive(Debug, Deserialize)]
 struct GlabMrBodyJson {
@@ -407,7 +582,11 @@ struct GlabMrBodyJson {
 
 /// Get MR description/body text
 pub fn get_mr_body(mr_number: u64) -> Result<String> {
-    let output = Command::new("glab")
+    if api::native_mode() {
+        return glab_api::get_mr_body(mr_number);
+    }
+
+    let output = glab_command()
         .args(["mr", "view", &mr_number.to_string(), "--output", "json"])
         .output()?;
 
@@ -428,7 +607,11 @@ pub fn get_mr_body(mr_number: u64) -> Result<String> {
 
 /// Update MR description/body
 pub fn update_mr_description(mr_number: u64, description: &str) -> Result<()> {
-    let output = Command::new("glab")
+    if api::native_mode() {
+        return glab_api::update_mr_description(mr_number, description);
+    }
+
+    let output = glab_command()
         .args([
             "mr",
             "update",
@@ -451,7 +634,11 @@ pub fn update_mr_description(mr_number: u64, description: &str) -> Result<()> {
 
 /// Update MR title
 pub fn update_mr_title(mr_number: u64, title: &str) -> Result<()> {
-    let output = Command::new("glab")
+    if api::native_mode() {
+        return glab_api::update_mr_title(mr_number, title);
+    }
+
+    let output = glab_command()
         .args(["mr", "update", &mr_number.to_string(), "--title", title])
         .output()?;
 
@@ -466,19 +653,26 @@ pub fn update_mr_title(mr_number: u64, title: &str) -> Result<()> {
     Ok(())
 }
 
-/// Merge an MR
-pub fn merge_mr(mr_number: u64, squash: bool, delete_branch: bool) -> Result<()> {
+/// Merge an MR. GitLab's merge method (merge commit or fast-forward) is a
+/// project setting; `Rebase` rebases the MR onto its target first.
+pub fn merge_mr(mr_number: u64, strategy: MergeStrategy, delete_branch: bool) -> Result<()> {
+    if api::native_mode() {
+        return glab_api::merge_mr(mr_number, strategy, delete_branch);
+    }
+
     let mr_num_str = mr_number.to_string();
     let mut args = vec!["mr", "merge", &mr_num_str, "--yes"];
 
-    if squash {
-        args.push("--squash");
+    match strategy {
+        MergeStrategy::Squash => args.push("--squash"),
+        MergeStrategy::Rebase => args.push("--rebase"),
+        MergeStrategy::Merge => {}
     }
     if delete_branch {
         args.push("--remove-source-branch");
     }
 
-    let output = Command::new("glab").args(&args).output()?;
+    let output = glab_command().args(&args).output()?;
 
     if !output.status.success() {
         let stderr = String::from_utf8_lossy(&output.stderr);
@@ -503,15 +697,24 @@ pub fn merge_mr(mr_number: u64, squash: bool, delete_branch: bool) -> Result<()>
 /// - `Err(...)` for other errors
 pub fn auto_merge_mr_when_pipeline_succeeds(
     mr_number: u64,
-    squash: bool,
+    strategy: MergeStrategy,
     delete_branch: bool,
 ) -> Result<AutoMergeResult> {
-    let output = Command::new("glab")
+    if api::native_mode() {
+        return glab_api::auto_merge_mr_when_pipeline_succeeds(mr_number, strategy, delete_branch);
+    }
+
+    let squash = strategy == MergeStrategy::Squash;
+    let output = glab_command()
         .args([
             "api",
             "--method",
             "PUT",
-            &format!("projects/:id/merge_requests/{}/merge", mr_number),
+            &format!(
+                "projects/{}/merge_requests/{}/merge",
+                glab_project_prefix(),
+                mr_number
+            ),
             "-f",
             "merge_when_pipeline_succeeds=true",
             "-f",
@@ -544,12 +747,20 @@ pub fn auto_merge_mr_when_pipeline_succeeds(
 
 /// Check approvals for an MR
 pub fn check_mr_approved(mr_number: u64) -> Result<bool> {
+    if api::native_mode() {
+        return glab_api::check_mr_approved(mr_number);
+    }
+
     // Use glab api to check approvals
     // Note: We don't use --jq flag as it's not available in all glab versions
-    let output = Command::new("glab")
+    let output = glab_command()
         .args([
             "api",
-            &format!("projects/:id/merge_requests/{}/approvals", mr_number),
+            &format!(
+                "projects/{}/merge_requests/{}/approvals",
+                glab_project_prefix(),
+                mr_number
+            ),
         ])
         .output()?;
 
@@ -571,6 +782,112 @@ pub fn check_mr_approved(mr_number: u64) -> Result<bool> {
     Ok(false)
 }
 
+/// An approval rule from `merge_requests/:iid/approval_state`.
+#[derive(Debug, Clone, Deserialize)]
+pub struct ApprovalRule {
+    pub name: String,
+    #[serde(default)]
+    pub approvals_required: u32,
+    #[serde(default)]
+    pub approved: bool,
+    #[serde(default)]
+    pub approved_by: Vec<ApprovalUser>,
+    #[serde(default)]
+    pub users: Vec<ApprovalUser>,
+    #[serde(default)]
+    pub groups: Vec<ApprovalGroup>,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+pub struct ApprovalUser {
+    pub username: String,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+pub struct ApprovalGroup {
+    pub full_path: String,
+}
+
+#[derive(Debug, Deserialize)]
+struct ApprovalStateJson {
+    #[serde(default)]
+    rules: Vec<ApprovalRule>,
+}
+
+impl ApprovalRule {
+    /// Human-readable description of what this rule still needs, e.g.
+    /// `needs 1 approval from @backend-owners`. `None` once satisfied.
+    pub fn unmet_description(&self) -> Option<String> {
+        if self.approved {
+            return None;
+        }
+        let remaining = self
+            .approvals_required
+            .saturating_sub(self.approved_by.len() as u32)
+            .max(1);
+        let plural = if remaining == 1 { "" } else { "s" };
+
+        let mut approvers: Vec<String> = self
+            .groups
+            .iter()
+            .map(|g| format!("@{}", g.full_path))
+            .collect();
+        if approvers.is_empty() {
+            approvers = self
+                .users
+                .iter()
+                .map(|u| format!("@{}", u.username))
+                .collect();
+        }
+
+        Some(if approvers.is_empty() {
+            format!("needs {} approval{} ({})", remaining, plural, self.name)
+        } else {
+            format!(
+                "needs {} approval{} from {} ({})",
+                remaining,
+                plural,
+                approvers.join(", "),
+                self.name
+            )
+        })
+    }
+}
+
+/// Get the approval rules GitLab still considers unmet for an MR.
+pub fn get_unmet_approval_rules(mr_number: u64) -> Result<Vec<ApprovalRule>> {
+    if api::native_mode() {
+        return glab_api::get_unmet_approval_rules(mr_number);
+    }
+
+    let output = glab_command()
+        .args([
+            "api",
+            &format!(
+                "projects/{}/merge_requests/{}/approval_state",
+                glab_project_prefix(),
+                mr_number
+            ),
+        ])
+        .output()?;
+
+    if !output.status.success() {
+        let stderr = String::from_utf8_lossy(&output.stderr);
+        return Err(GgError::GlabError(format!(
+            "Failed to get approval rules for MR !{}: {}",
+            mr_number, stderr
+        )));
+    }
+
+    parse_unmet_approval_rules(&String::from_utf8_lossy(&output.stdout))
+}
+
+/// The unmet rules of an `approval_state` response.
+pub(crate) fn parse_unmet_approval_rules(json: &str) -> Result<Vec<ApprovalRule>> {
+    let state: ApprovalStateJson = serde_json::from_str(json)?;
+    Ok(state.rules.into_iter().filter(|r| !r.approved).collect())
+}
+
 /// Get CI status for an MR
 #[derive(Debug, Clone, PartialEq, Eq)]
 pub enum CiStatus {
@@ -619,7 +936,11 @@ pub struct MergeTrainInfo {
 }
 
 pub fn get_mr_ci_status(mr_number: u64) -> Result<CiStatus> {
-    let output = Command::new("glab")
+    if api::native_mode() {
+        return glab_api::get_mr_ci_status(mr_number);
+    }
+
+    let output = glab_command()
         .args(["mr", "view", &mr_number.to_string(), "--output", "json"])
         .output()?;
 
@@ -651,7 +972,11 @@ pub fn get_mr_ci_status(mr_number: u64) -> Result<CiStatus> {
 /// List MRs for a specific source branch
 /// Returns a list of MR numbers (iids) for open MRs with the given source branch
 pub fn list_mrs_for_branch(branch: &str) -> Result<Vec<u64>> {
-    let output = Command::new("glab")
+    if api::native_mode() {
+        return glab_api::list_mrs_for_branch(branch);
+    }
+
+    let output = glab_command()
         .args(["mr", "list", "--source-branch", branch, "--output", "json"])
         .output()?;
 
@@ -683,14 +1008,51 @@ pub fn list_mrs_for_branch(branch: &str) -> Result<Vec<u64>> {
     Ok(mrs.into_iter().map(|mr| mr.iid).collect())
 }
 
+/// An open MR authored by the current user.
+#[derive(Debug, Clone, Deserialize)]
+pub struct OpenMr {
+    pub iid: u64,
+    pub title: String,
+    pub source_branch: String,
+    pub target_branch: String,
+}
+
+/// List open MRs in this project authored by the authenticated user.
+pub fn list_my_open_mrs() -> Result<Vec<OpenMr>> {
+    if api::native_mode() {
+        return glab_api::list_my_open_mrs();
+    }
+
+    let endpoint = format!(
+        "projects/{}/merge_requests?state=opened&scope=created_by_me&per_page=100",
+        glab_project_prefix()
+    );
+    let output = glab_command().args(["api", &endpoint]).output()?;
+
+    if !output.status.success() {
+        let stderr = String::from_utf8_lossy(&output.stderr);
+        return Err(GgError::GlabError(format!(
+            "Failed to list open MRs: {}",
+            stderr
+        )));
+    }
+
+    serde_json::from_slice(&output.stdout)
+        .map_err(|e| GgError::GlabError(format!("Failed to parse MR list: {}", e)))
+}
+
 /// Check if merge trains are enabled for the current project
 /// Returns true if merge trains are enabled, false otherwise
 /// Uses caching to avoid repeated API calls (stored in memory)
 pub fn check_merge_trains_enabled() -> Result<bool> {
+    if api::native_mode() {
+        return glab_api::check_merge_trains_enabled();
+    }
+
     // Use glab api to check project settings
     // Note: We don't use --jq flag as it's not available in all glab versions
-    let output = Command::new("glab")
-        .args(["api", "projects/:id"])
+    let output = glab_command()
+        .args(["api", &format!("projects/{}", glab_project_prefix())])
         .output()?;
 
     if !output.status.success() {
@@ -719,12 +1081,20 @@ pub fn check_merge_trains_enabled() -> Result<bool> {
 /// - `Ok(AutoMergeResult::AlreadyQueued)` if already in the merge train (HTTP 409)
 /// - `Err(...)` for other errors
 pub fn add_to_merge_train(mr_number: u64) -> Result<AutoMergeResult> {
-    let output = Command::new("glab")
+    if api::native_mode() {
+        return glab_api::add_to_merge_train(mr_number);
+    }
+
+    let output = glab_command()
         .args([
             "api",
             "-X",
             "POST",
-            &format!("projects/:id/merge_trains/merge_requests/{}", mr_number),
+            &format!(
+                "projects/{}/merge_trains/merge_requests/{}",
+                glab_project_prefix(),
+                mr_number
+            ),
         ])
         .output()?;
 
@@ -746,6 +1116,102 @@ pub fn add_to_merge_train(mr_number: u64) -> Result<AutoMergeResult> {
     Ok(AutoMergeResult::Queued)
 }
 
+/// Ask GitLab to rebase an MR's source branch onto its target branch.
+///
+/// The rebase runs asynchronously on the GitLab side; the API call returns
+/// as soon as it is scheduled.
+pub fn rebase_mr(mr_number: u64) -> Result<()> {
+    if api::native_mode() {
+        return glab_api::rebase_mr(mr_number);
+    }
+
+    let output = glab_command()
+        .args([
+            "api",
+            "-X",
+            "PUT",
+            &format!(
+                "projects/{}/merge_requests/{}/rebase",
+                glab_project_prefix(),
+                mr_number
+            ),
+        ])
+        .output()?;
+
+    if !output.status.success() {
+        let stderr = String::from_utf8_lossy(&output.stderr);
+        return Err(GgError::GlabError(format!(
+            "Failed to rebase MR !{}: {}",
+            mr_number, stderr
+        )));
+    }
+
+    Ok(())
+}
+
+/// ID of an MR's head pipeline, if it has one and it can be read.
+fn get_mr_head_pipeline_id(mr_number: u64) -> Option<u64> {
+    let output = glab_command()
+        .args(["mr", "view", &mr_number.to_string(), "--output", "json"])
+        .output()
+        .ok()?;
+
+    if !output.status.success() {
+        return None;
+    }
+
+    let mr_json: serde_json::Value = serde_json::from_slice(&output.stdout).ok()?;
+    mr_json
+        .get("head_pipeline")
+        .and_then(|p| p.get("id"))
+        .and_then(|id| id.as_u64())
+}
+
+/// Re-run CI for an MR.
+///
+/// With `failed_only`, retries the failed and canceled jobs of the head
+/// pipeline; otherwise starts a new MR pipeline. Returns a description of
+/// the pipeline that was (re)started.
+pub fn retry_mr_pipeline(mr_number: u64, failed_only: bool) -> Result<Vec<String>> {
+    if api::native_mode() {
+        return glab_api::retry_mr_pipeline(mr_number, failed_only);
+    }
+
+    let endpoint = if failed_only {
+        let pipeline_id = get_mr_head_pipeline_id(mr_number).ok_or_else(|| {
+            GgError::GlabError(format!("MR !{} has no pipeline to retry", mr_number))
+        })?;
+        format!(
+            "projects/{}/pipelines/{}/retry",
+            glab_project_prefix(),
+            pipeline_id
+        )
+    } else {
+        format!(
+            "projects/{}/merge_requests/{}/pipelines",
+            glab_project_prefix(),
+            mr_number
+        )
+    };
+
+    let output = glab_command()
+        .args(["api", "-X", "POST", &endpoint])
+        .output()?;
+    if !output.status.success() {
+        let stderr = String::from_utf8_lossy(&output.stderr);
+        return Err(GgError::GlabError(format!(
+            "Failed to re-run pipeline for MR !{}: {}",
+            mr_number, stderr
+        )));
+    }
+
+    let pipeline: serde_json::Value = serde_json::from_slice(&output.stdout).unwrap_or_default();
+    Ok(vec![match pipeline.get("id").and_then(|id| id.as_u64()) {
+        Some(id) => format!("pipeline {}", id),
+        None => "pipeline".to_string(),
+    }])
+}
+
 /// A failed CI job with its name, stage, and optional URL
 #[derive(Debug, Clone)]
 pub struct FailedJob {
@@ -760,36 +1226,25 @@ pub struct FailedJob {
 /// 1. `glab mr view <mr_number> --output json` → extract `head_pipeline.id`
 /// 2. `glab api "projects/:id/pipelines/<id>/jobs"` → find jobs with status "failed"
 pub fn get_mr_failed_ci_jobs(mr_number: u64) -> Result<Vec<FailedJob>> {
-    // Step 1: Get MR details to find the head pipeline ID
-    let output = Command::new("glab")
-        .args(["mr", "view", &mr_number.to_string(), "--output", "json"])
-        .output()?;
-
-    if !output.status.success() {
-        return Ok(vec![]);
+    if api::native_mode() {
+        return glab_api::get_mr_failed_ci_jobs(mr_number);
     }
 
-    let stdout = String::from_utf8_lossy(&output.stdout);
-    let mr_json: serde_json::Value = match serde_json::from_str(&stdout) {
-        Ok(v) => v,
-        Err(_) => return Ok(vec![]),
-    };
-
-    let pipeline_id = mr_json
-        .get("head_pipeline")
-        .and_then(|p| p.get("id"))
-        .and_then(|id| id.as_u64());
-
-    let pipeline_id = match pipeline_id {
+    // Step 1: Get MR details to find the head pipeline ID
+    let pipeline_id = match get_mr_head_pipeline_id(mr_number) {
         Some(id) => id,
         None => return Ok(vec![]),
     };
 
     // Step 2: Get jobs for this pipeline
-    let jobs_output = Command::new("glab")
+    let jobs_output = glab_command()
         .args([
             "api",
-            &format!("projects/:id/pipelines/{}/jobs", pipeline_id),
+            &format!(
+                "projects/{}/pipelines/{}/jobs",
+                glab_project_prefix(),
+                pipeline_id
+            ),
         ])
         .output()?;
 
@@ -797,8 +1252,13 @@ pub fn get_mr_failed_ci_jobs(mr_number: u64) -> Result<Vec<FailedJob>> {
         return Ok(vec![]);
     }
 
-    let jobs_stdout = String::from_utf8_lossy(&jobs_output.stdout);
+    Ok(parse_failed_jobs(&String::from_utf8_lossy(
+        &jobs_output.stdout,
+    )))
+}
 
+/// The failed jobs of a pipeline jobs list; empty when it can't be parsed.
+pub(crate) fn parse_failed_jobs(json: &str) -> Vec<FailedJob> {
     #[derive(Deserialize)]
     struct JobJson {
         name: String,
@@ -809,22 +1269,15 @@ pub fn get_mr_failed_ci_jobs(mr_number: u64) -> Result<Vec<FailedJob>> {
         web_url: Option<String>,
     }
 
-    let jobs: Vec<JobJson> = match serde_json::from_str(&jobs_stdout) {
-        Ok(v) => v,
-        Err(_) => return Ok(vec![]),
-    };
-
-    let failed_jobs = jobs
-        .into_iter()
+    let jobs: Vec<JobJson> = serde_json::from_str(json).unwrap_or_default();
+    jobs.into_iter()
         .filter(|j| j.status == "failed")
         .map(|j| FailedJob {
             name: j.name,
             stage: j.stage,
             web_url: j.web_url,
         })
-        .collect();
-
-    Ok(failed_jobs)
+        .collect()
 }
 
 /// Format failed jobs as a compact string for error messages
@@ -858,8 +1311,13 @@ fn merge_train_endpoint(
         None => String::new(),
     };
     format!(
-        "projects/:id/merge_trains/{}?{}sort={}&per_page={}&page={}",
-        target_branch, scope_query, sort, per_page, page
+        "projects/{}/merge_trains/{}?{}sort={}&per_page={}&page={}",
+        glab_project_prefix(),
+        target_branch,
+        scope_query,
+        sort,
+        per_page,
+        page
     )
 }
 
@@ -912,29 +1370,29 @@ fn query_merge_train(
     };
 
     loop {
-        let output = Command::new("glab")
-            .args([
-                "api",
-                &merge_train_endpoint(target_branch, per_page, page, scope, sort),
-            ])
-            .output()?;
-
-        if !output.status.success() {
-            let stderr = String::from_utf8_lossy(&output.stderr);
-            return Err(GgError::GlabError(format!(
-                "Failed to query merge train for target branch {}: {}",
-                target_branch,
-                stderr.trim()
-            )));
-        }
-
-        let stdout = String::from_utf8_lossy(&output.stdout);
-        let page_entries = parse_merge_train_page(&stdout);
-        if let Some(info) =
-            find_mr_in_merge_train_page(mr_number, &page_entries, position_offset, include_position)
-        {
-            return Ok(Some(info));
-        }
+        let endpoint = merge_train_endpoint(target_branch, per_page, page, scope, sort);
+        let stdout = if api::native_mode() {
+            let query = endpoint.split_once('?').map_or("", |(_, query)| query);
+            glab_api::merge_train_page(target_branch, query)?
+        } else {
+            let output = glab_command().args(["api", &endpoint]).output()?;
+
+            if !output.status.success() {
+                let stderr = String::from_utf8_lossy(&output.stderr);
+                return Err(GgError::GlabError(format!(
+                    "Failed to query merge train for target branch {}: {}",
+                    target_branch,
+                    stderr.trim()
+                )));
+            }
+            String::from_utf8_lossy(&output.stdout).into_owned()
+        };
+        let page_entries = parse_merge_train_page(&stdout);
+        if let Some(info) =
+            find_mr_in_merge_train_page(mr_number, &page_entries, position_offset, include_position)
+        {
+            return Ok(Some(info));
+        }
 
         if page_entries.len() < per_page {
             break;
@@ -1015,9 +1473,27 @@ pub struct MrNote {
 ///
 /// `glab api` expands `:id` to the numeric project ID of the current repo,
 /// resolved from the git remote.  This matches the pattern used throughout
-/// this file (e.g. `projects/:id/merge_requests/…`).
-fn glab_project_prefix() -> &'static str {
-    ":id"
+/// this file (e.g. `projects/:id/merge_requests/…`). When the stack lives on
+/// another remote, its encoded project path is used instead.
+fn glab_project_prefix() -> String {
+    match api::remote_override() {
+        Some((_, path)) => api::encode(&path),
+        None => ":id".to_string(),
+    }
+}
+
+/// A `glab` invocation aimed at the project of the stack's remote.
+///
+/// glab resolves the project from the git remotes by itself; when the stack
+/// lives on a remote other than `origin`, `GITLAB_REPO` and `GITLAB_HOST`
+/// point it there instead.
+fn glab_command() -> Command {
+    let mut cmd = Command::new("glab");
+    if let Some((host, path)) = api::remote_override() {
+        cmd.env("GITLAB_REPO", format!("https://{}/{}", host, path))
+            .env("GITLAB_HOST", host);
+    }
+    cmd
 }
 
 /// List all notes on an MR.
@@ -1026,6 +1502,10 @@ fn glab_project_prefix() -> &'static str {
 /// manually (100 per page) until a short page is returned, so MRs with
 /// many notes still surface the managed nav note reliably.
 pub fn list_mr_notes(mr_iid: u64) -> Result<Vec<MrNote>> {
+    if api::native_mode() {
+        return glab_api::list_mr_notes(mr_iid);
+    }
+
     let mut all = Vec::new();
     let mut page = 1u32;
 
@@ -1036,7 +1516,7 @@ pub fn list_mr_notes(mr_iid: u64) -> Result<Vec<MrNote>> {
             mr_iid,
             page
         );
-        let output = Command::new("glab").args(["api", &endpoint]).output()?;
+        let output = glab_command().args(["api", &endpoint]).output()?;
 
         if !output.status.success() {
             let stderr = String::from_utf8_lossy(&output.stderr);
@@ -1070,12 +1550,16 @@ pub fn list_mr_notes(mr_iid: u64) -> Result<Vec<MrNote>> {
 
 /// Create a new note on an MR.
 pub fn create_mr_note(mr_iid: u64, body: &str) -> Result<()> {
+    if api::native_mode() {
+        return glab_api::create_mr_note(mr_iid, body);
+    }
+
     let endpoint = format!(
         "projects/{}/merge_requests/{}/notes",
         glab_project_prefix(),
         mr_iid
     );
-    let output = Command::new("glab")
+    let output = glab_command()
         .args([
             "api",
             "-X",
@@ -1098,13 +1582,17 @@ pub fn create_mr_note(mr_iid: u64, body: &str) -> Result<()> {
 
 /// Update an existing note on an MR.
 pub fn update_mr_note(mr_iid: u64, note_id: u64, body: &str) -> Result<()> {
+    if api::native_mode() {
+        return glab_api::update_mr_note(mr_iid, note_id, body);
+    }
+
     let endpoint = format!(
         "projects/{}/merge_requests/{}/notes/{}",
         glab_project_prefix(),
         mr_iid,
         note_id
     );
-    let output = Command::new("glab")
+    let output = glab_command()
         .args([
             "api",
             "-X",
@@ -1127,13 +1615,17 @@ pub fn update_mr_note(mr_iid: u64, note_id: u64, body: &str) -> Result<()> {
 
 /// Delete a note from an MR.
 pub fn delete_mr_note(mr_iid: u64, note_id: u64) -> Result<()> {
+    if api::native_mode() {
+        return glab_api::delete_mr_note(mr_iid, note_id);
+    }
+
     let endpoint = format!(
         "projects/{}/merge_requests/{}/notes/{}",
         glab_project_prefix(),
         mr_iid,
         note_id
     );
-    let output = Command::new("glab")
+    let output = glab_command()
         .args(["api", "-X", "DELETE", &endpoint])
         .output()?;
 
@@ -1147,10 +1639,430 @@ pub fn delete_mr_note(mr_iid: u64, note_id: u64) -> Result<()> {
     Ok(())
 }
 
+/// A GitLab MR discussion (a thread of notes).
+#[derive(Debug, Clone, Deserialize)]
+pub struct MrDiscussion {
+    pub id: String,
+    #[serde(default)]
+    pub notes: Vec<DiscussionNote>,
+}
+
+/// A note inside an MR discussion.
+#[derive(Debug, Clone, Deserialize)]
+pub struct DiscussionNote {
+    pub body: String,
+    pub author: Option<NoteAuthor>,
+    #[serde(default)]
+    pub created_at: String,
+    #[serde(default)]
+    pub system: bool,
+    #[serde(default)]
+    pub resolvable: bool,
+    #[serde(default)]
+    pub resolved: bool,
+    pub position: Option<NotePosition>,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+pub struct NoteAuthor {
+    pub username: String,
+}
+
+/// Diff position of an inline note.
+#[derive(Debug, Clone, Deserialize)]
+pub struct NotePosition {
+    pub new_path: Option<String>,
+    pub old_path: Option<String>,
+    pub new_line: Option<u64>,
+    pub old_line: Option<u64>,
+}
+
+impl MrDiscussion {
+    /// Whether this discussion is a resolvable review thread (as opposed to
+    /// a plain comment or a system note).
+    pub fn is_resolvable(&self) -> bool {
+        self.notes
+            .first()
+            .is_some_and(|n| n.resolvable && !n.system)
+    }
+
+    /// A discussion is resolved once every resolvable note in it is.
+    pub fn is_resolved(&self) -> bool {
+        self.notes
+            .iter()
+            .filter(|n| n.resolvable)
+            .all(|n| n.resolved)
+    }
+}
+
+/// List all discussions on an MR. Paginates like [`list_mr_notes`].
+pub fn list_mr_discussions(mr_iid: u64) -> Result<Vec<MrDiscussion>> {
+    if api::native_mode() {
+        return glab_api::list_mr_discussions(mr_iid);
+    }
+
+    let mut all = Vec::new();
+    let mut page = 1u32;
+
+    loop {
+        let endpoint = format!(
+            "projects/{}/merge_requests/{}/discussions?per_page=100&page={}",
+            glab_project_prefix(),
+            mr_iid,
+            page
+        );
+        let output = glab_command().args(["api", &endpoint]).output()?;
+
+        if !output.status.success() {
+            let stderr = String::from_utf8_lossy(&output.stderr);
+            return Err(GgError::GlabError(format!(
+                "Failed to list discussions for MR !{} (page {}): {}",
+                mr_iid, page, stderr
+            )));
+        }
+
+        let stdout = String::from_utf8_lossy(&output.stdout);
+        let page_discussions: Vec<MrDiscussion> = serde_json::from_str(&stdout).map_err(|e| {
+            GgError::GlabError(format!(
+                "Failed to parse discussions JSON for MR !{} (page {}): {}",
+                mr_iid, page, e
+            ))
+        })?;
+
+        if page_discussions.is_empty() {
+            break;
+        }
+        let full_page = page_discussions.len() == 100;
+        all.extend(page_discussions);
+        if !full_page {
+            break;
+        }
+        page += 1;
+    }
+
+    Ok(all)
+}
+
+/// Resolve a discussion on an MR.
+pub fn resolve_mr_discussion(mr_iid: u64, discussion_id: &str) -> Result<()> {
+    if api::native_mode() {
+        return glab_api::resolve_mr_discussion(mr_iid, discussion_id);
+    }
+
+    let endpoint = format!(
+        "projects/{}/merge_requests/{}/discussions/{}",
+        glab_project_prefix(),
+        mr_iid,
+        discussion_id
+    );
+    let output = glab_command()
+        .args(["api", "-X", "PUT", &endpoint, "-f", "resolved=true"])
+        .output()?;
+
+    if !output.status.success() {
+        let stderr = String::from_utf8_lossy(&output.stderr);
+        return Err(GgError::GlabError(format!(
+            "Failed to resolve discussion {} on MR !{}: {}",
+            discussion_id, mr_iid, stderr
+        )));
+    }
+    Ok(())
+}
+
+/// Project settings that constrain the commits gg pushes and merges.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct ProjectRules {
+    /// Push rule rejecting unsigned commits (GitLab Premium).
+    pub reject_unsigned_commits: bool,
+    /// The project merges with fast-forward only (`merge_method: ff`).
+    pub fast_forward_only: bool,
+}
+
+fn parse_push_rule(json: &str) -> bool {
+    let value: serde_json::Value = serde_json::from_str(json).unwrap_or_default();
+    value["reject_unsigned_commits"].as_bool().unwrap_or(false)
+}
+
+fn parse_merge_method(json: &str) -> Option<String> {
+    let value: serde_json::Value = serde_json::from_str(json).unwrap_or_default();
+    value["merge_method"].as_str().map(str::to_string)
+}
+
+/// Get the project's signed-commit push rule and merge method.
+///
+/// Push rules are a paid feature; a failed lookup is treated as "no push
+/// rule" rather than an error.
+pub fn get_project_rules() -> Result<ProjectRules> {
+    if api::native_mode() {
+        return glab_api::get_project_rules();
+    }
+
+    let project = format!("projects/{}", glab_project_prefix());
+    let output = glab_command().args(["api", &project]).output()?;
+    if !output.status.success() {
+        let stderr = String::from_utf8_lossy(&output.stderr);
+        return Err(GgError::GlabError(format!(
+            "Failed to read project settings: {}",
+            stderr
+        )));
+    }
+    let merge_method = parse_merge_method(&String::from_utf8_lossy(&output.stdout));
+
+    let push_rule = glab_command()
+        .args(["api", &format!("{}/push_rule", project)])
+        .output()?;
+    let reject_unsigned_commits =
+        push_rule.status.success() && parse_push_rule(&String::from_utf8_lossy(&push_rule.stdout));
+
+    Ok(ProjectRules {
+        reject_unsigned_commits,
+        fast_forward_only: merge_method.as_deref() == Some("ff"),
+    })
+}
+
+/// Upload a file to the project and return the Markdown that embeds it.
+pub fn upload_file(path: &std::path::Path) -> Result<String> {
+    if api::native_mode() {
+        return glab_api::upload_file(path);
+    }
+
+    #[derive(Deserialize)]
+    struct UploadJson {
+        markdown: String,
+    }
+
+    let output = glab_command()
+        .args([
+            "api",
+            "-X",
+            "POST",
+            &format!("projects/{}/uploads", glab_project_prefix()),
+            "-F",
+            &format!("file=@{}", path.display()),
+        ])
+        .output()?;
+
+    if !output.status.success() {
+        let stderr = String::from_utf8_lossy(&output.stderr);
+        return Err(GgError::GlabError(format!(
+            "Failed to upload {}: {}",
+            path.display(),
+            stderr
+        )));
+    }
+
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    let json: UploadJson = serde_json::from_str(&stdout)
+        .map_err(|e| GgError::GlabError(format!("Failed to parse upload JSON: {}", e)))?;
+    Ok(json.markdown)
+}
+
+/// An MR dependency ("blocked by") as returned by the MR blocks API.
+#[derive(Debug, Clone, Deserialize)]
+pub struct MrBlock {
+    pub id: u64,
+    pub blocking_merge_request: BlockingMr,
+}
+
+/// The blocking side of an [`MrBlock`].
+#[derive(Debug, Clone, Deserialize)]
+pub struct BlockingMr {
+    pub id: u64,
+    pub iid: u64,
+}
+
+/// List the MRs blocking `mr_iid`.
+pub fn list_mr_blocks(mr_iid: u64) -> Result<Vec<MrBlock>> {
+    if api::native_mode() {
+        return glab_api::list_mr_blocks(mr_iid);
+    }
+
+    let endpoint = format!(
+        "projects/{}/merge_requests/{}/blocks",
+        glab_project_prefix(),
+        mr_iid
+    );
+    let output = glab_command().args(["api", &endpoint]).output()?;
+
+    if !output.status.success() {
+        let stderr = String::from_utf8_lossy(&output.stderr);
+        return Err(GgError::GlabError(format!(
+            "Failed to list dependencies for MR !{}: {}",
+            mr_iid, stderr
+        )));
+    }
+
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    serde_json::from_str(&stdout).map_err(|e| {
+        GgError::GlabError(format!(
+            "Failed to parse dependencies JSON for MR !{}: {}",
+            mr_iid, e
+        ))
+    })
+}
+
+/// Look up the global (instance-wide) id of an MR. The blocks API takes
+/// global ids rather than project-scoped iids.
+fn get_mr_global_id(mr_iid: u64) -> Result<u64> {
+    #[derive(Deserialize)]
+    struct MrIdJson {
+        id: u64,
+    }
+
+    let endpoint = format!(
+        "projects/{}/merge_requests/{}",
+        glab_project_prefix(),
+        mr_iid
+    );
+    let output = glab_command().args(["api", &endpoint]).output()?;
+
+    if !output.status.success() {
+        let stderr = String::from_utf8_lossy(&output.stderr);
+        return Err(GgError::GlabError(format!(
+            "Failed to view MR !{}: {}",
+            mr_iid, stderr
+        )));
+    }
+
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    let json: MrIdJson = serde_json::from_str(&stdout)
+        .map_err(|e| GgError::GlabError(format!("Failed to parse MR JSON: {}", e)))?;
+    Ok(json.id)
+}
+
+/// Mark `mr_iid` as blocked by `blocking_iid`.
+pub fn create_mr_block(mr_iid: u64, blocking_iid: u64) -> Result<()> {
+    if api::native_mode() {
+        return glab_api::create_mr_block(mr_iid, blocking_iid);
+    }
+
+    let blocking_id = get_mr_global_id(blocking_iid)?;
+    let endpoint = format!(
+        "projects/{}/merge_requests/{}/blocks",
+        glab_project_prefix(),
+        mr_iid
+    );
+    let output = glab_command()
+        .args([
+            "api",
+            "-X",
+            "POST",
+            &endpoint,
+            "-F",
+            &format!("blocking_merge_request_id={}", blocking_id),
+        ])
+        .output()?;
+
+    if !output.status.success() {
+        let stderr = String::from_utf8_lossy(&output.stderr);
+        return Err(GgError::GlabError(format!(
+            "Failed to mark MR !{} as blocked by !{}: {}",
+            mr_iid, blocking_iid, stderr
+        )));
+    }
+    Ok(())
+}
+
+/// Remove a dependency from `mr_iid`.
+pub fn delete_mr_block(mr_iid: u64, block_id: u64) -> Result<()> {
+    if api::native_mode() {
+        return glab_api::delete_mr_block(mr_iid, block_id);
+    }
+
+    let endpoint = format!(
+        "projects/{}/merge_requests/{}/blocks/{}",
+        glab_project_prefix(),
+        mr_iid,
+        block_id
+    );
+    let output = glab_command()
+        .args(["api", "-X", "DELETE", &endpoint])
+        .output()?;
+
+    if !output.status.success() {
+        let stderr = String::from_utf8_lossy(&output.stderr);
+        return Err(GgError::GlabError(format!(
+            "Failed to remove dependency {} from MR !{}: {}",
+            block_id, mr_iid, stderr
+        )));
+    }
+    Ok(())
+}
+
+/// Changes needed to make an MR's dependencies match the stack order.
+#[derive(Debug, Default, PartialEq, Eq)]
+pub struct MrBlockPlan {
+    /// Block ids to delete: dependencies on other MRs of the same stack that
+    /// are no longer the predecessor (e.g. after a reorder).
+    pub remove: Vec<u64>,
+    /// Blocking MR iid to add, if the dependency is missing.
+    pub add: Option<u64>,
+}
+
+/// Plan dependency changes for one MR. Dependencies on MRs outside the stack
+/// are never touched.
+pub fn plan_mr_blocks(
+    existing: &[MrBlock],
+    blocking_iid: Option<u64>,
+    stack_iids: &[u64],
+) -> MrBlockPlan {
+    let remove = existing
+        .iter()
+        .filter(|b| {
+            let iid = b.blocking_merge_request.iid;
+            stack_iids.contains(&iid) && Some(iid) != blocking_iid
+        })
+        .map(|b| b.id)
+        .collect();
+    let add = blocking_iid.filter(|iid| {
+        !existing
+            .iter()
+            .any(|b| b.blocking_merge_request.iid == *iid)
+    });
+    MrBlockPlan { remove, add }
+}
+
+/// Make `mr_iid` blocked by exactly `blocking_iid` among the stack's MRs.
+/// Returns whether anything changed.
+pub fn sync_mr_dependency(
+    mr_iid: u64,
+    blocking_iid: Option<u64>,
+    stack_iids: &[u64],
+) -> Result<bool> {
+    let existing = list_mr_blocks(mr_iid)?;
+    let plan = plan_mr_blocks(&existing, blocking_iid, stack_iids);
+    for block_id in &plan.remove {
+        delete_mr_block(mr_iid, *block_id)?;
+    }
+    if let Some(blocking) = plan.add {
+        create_mr_block(mr_iid, blocking)?;
+    }
+    Ok(!plan.remove.is_empty() || plan.add.is_some())
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
 
+    #[test]
+    fn test_mr_list_by_iids() {
+        assert_eq!(mr_iids_query(&[4, 7]), "iids[]=4&iids[]=7&per_page=100");
+        let mrs = parse_mr_list(
+            r#"[
+                {"iid": 7, "title": "Use parser", "state": "opened", "draft": true,
+                 "web_url": "https://gitlab.com/g/p/-/merge_requests/7"},
+                {"iid": 4, "title": "Add parser", "state": "merged",
+                 "web_url": "https://gitlab.com/g/p/-/merge_requests/4",
+                 "source_branch": "nacho/parser--c-abc1234", "target_branch": "main"}
+            ]"#,
+        )
+        .unwrap();
+        assert_eq!(mrs.len(), 2);
+        assert_eq!((mrs[0].iid, &mrs[0].state), (7, &MrState::Draft));
+        assert_eq!((mrs[1].iid, &mrs[1].state), (4, &MrState::Merged));
+        assert_eq!(mrs[1].base_branch.as_deref(), Some("main"));
+    }
+
     #[test]
     fn test_parse_mr_number_exclamation_format() {
         // Standard format: !123
@@ -1790,4 +2702,120 @@ mod tests {
         assert_eq!(notes[0].id, 1);
         assert_eq!(notes[1].body, "nav <!-- gg:stack-nav -->");
     }
+
+    #[test]
+    fn test_mr_discussion_deserialization() {
+        let json = r#"[
+            {"id": "abc123", "individual_note": false, "notes": [
+                {"id": 1, "body": "rename this", "author": {"username": "bob"},
+                 "created_at": "2026-01-01T00:00:00Z", "system": false,
+                 "resolvable": true, "resolved": false,
+                 "position": {"new_path": "src/main.rs", "old_path": "src/main.rs", "new_line": 7, "old_line": null}},
+                {"id": 2, "body": "done", "author": {"username": "me"},
+                 "created_at": "2026-01-02T00:00:00Z", "system": false,
+                 "resolvable": true, "resolved": true}
+            ]},
+            {"id": "def456", "individual_note": true, "notes": [
+                {"id": 3, "body": "added 1 commit", "system": true, "resolvable": false}
+            ]}
+        ]"#;
+        let discussions: Vec<MrDiscussion> =
+            serde_json::from_str(json).expect("should deserialize list");
+        assert_eq!(discussions.len(), 2);
+        assert!(discussions[0].is_resolvable());
+        assert!(!discussions[0].is_resolved());
+        let position = discussions[0].notes[0].position.as_ref().unwrap();
+        assert_eq!(position.new_path.as_deref(), Some("src/main.rs"));
+        assert_eq!(position.new_line, Some(7));
+        assert!(!discussions[1].is_resolvable());
+    }
+
+    #[test]
+    fn test_approval_rule_unmet_description() {
+        let json = r#"{"rules": [
+            {"name": "Backend", "approvals_required": 1, "approved": false,
+             "approved_by": [], "users": [{"username": "alice"}],
+             "groups": [{"full_path": "backend-owners"}]},
+            {"name": "Security", "approvals_required": 2, "approved": false,
+             "approved_by": [{"username": "carol"}], "users": [{"username": "dave"}], "groups": []},
+            {"name": "Any", "approvals_required": 3, "approved": false},
+            {"name": "Done", "approvals_required": 1, "approved": true}
+        ]}"#;
+        let state: ApprovalStateJson = serde_json::from_str(json).expect("should deserialize");
+        let descriptions: Vec<_> = state
+            .rules
+            .iter()
+            .map(ApprovalRule::unmet_description)
+            .collect();
+        assert_eq!(
+            descriptions,
+            vec![
+                Some("needs 1 approval from @backend-owners (Backend)".to_string()),
+                Some("needs 1 approval from @dave (Security)".to_string()),
+                Some("needs 3 approvals (Any)".to_string()),
+                None,
+            ]
+        );
+    }
+
+    fn block(id: u64, blocking_iid: u64) -> MrBlock {
+        MrBlock {
+            id,
+            blocking_merge_request: BlockingMr {
+                id: blocking_iid + 1000,
+                iid: blocking_iid,
+            },
+        }
+    }
+
+    #[test]
+    fn test_plan_mr_blocks_adds_missing_dependency() {
+        let plan = plan_mr_blocks(&[], Some(10), &[10, 11]);
+        assert_eq!(
+            plan,
+            MrBlockPlan {
+                remove: vec![],
+                add: Some(10)
+            }
+        );
+        assert_eq!(
+            plan_mr_blocks(&[block(1, 10)], Some(10), &[10, 11]),
+            MrBlockPlan::default()
+        );
+    }
+
+    #[test]
+    fn test_plan_mr_blocks_replaces_stale_stack_dependency_only() {
+        // Blocked by !12 (same stack, no longer the predecessor) and !99
+        // (outside the stack, left alone).
+        let existing = vec![block(1, 12), block(2, 99)];
+        let plan = plan_mr_blocks(&existing, Some(10), &[10, 11, 12]);
+        assert_eq!(plan.remove, vec![1]);
+        assert_eq!(plan.add, Some(10));
+
+        let first = plan_mr_blocks(&existing, None, &[10, 11, 12]);
+        assert_eq!(first.remove, vec![1]);
+        assert_eq!(first.add, None);
+    }
+
+    #[test]
+    fn test_parse_mr_blocks() {
+        let json = r#"[{"id": 7, "blocking_merge_request": {"id": 1234, "iid": 42, "title": "x"}, "blocked_merge_request": {"id": 1235, "iid": 43}}]"#;
+        let blocks: Vec<MrBlock> = serde_json::from_str(json).unwrap();
+        assert_eq!(blocks[0].id, 7);
+        assert_eq!(blocks[0].blocking_merge_request.iid, 42);
+    }
+
+    #[test]
+    fn test_parse_project_rules() {
+        assert!(parse_push_rule(
+            r#"{"id": 1, "reject_unsigned_commits": true}"#
+        ));
+        assert!(!parse_push_rule("null"));
+        assert_eq!(
+            parse_merge_method(r#"{"id": 3, "merge_method": "ff"}"#).as_deref(),
+            Some("ff")
+        );
+        assert_eq!(parse_merge_method("{}"), None);
+    }
 }
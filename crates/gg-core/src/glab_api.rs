@@ -0,0 +1,698 @@
+//! GitLab REST backend for `api_mode: native`.
+//!
+//! Mirrors the `glab.rs` functions, returning the same types, so no gg
+//! command needs glab. The token comes from `GITLAB_TOKEN` / `GL_TOKEN`,
+//! falling back to glab's stored token when glab happens to be installed.
+
+use serde::Deserialize;
+use serde_json::{json, Value};
+
+use crate::api;
+use crate::config::MergeStrategy;
+use crate::error::{GgError, Result};
+use crate::glab::{
+    self, ApprovalRule, AutoMergeResult, CiStatus, FailedJob, MrBlock, MrCreationResult,
+    MrDiscussion, MrInfo, MrNote, OpenMr, ProjectRules,
+};
+
+struct Context {
+    /// REST root (`https://<host>/api/v4`)
+    api: String,
+    /// URL-encoded project path, usable as `:id`
+    project: String,
+    token: String,
+}
+
+fn context() -> Result<Context> {
+    let url = api::remote_url()?;
+    let (host, path) = api::split_remote(&url).ok_or_else(|| {
+        GgError::GlabError(format!("Could not parse project path from remote: {}", url))
+    })?;
+    let token = api::env_token(&["GITLAB_TOKEN", "GL_TOKEN"])
+        .or_else(|| api::command_output("glab", &["config", "get", "token", "--host", &host]))
+        .ok_or(GgError::GlabNotAuthenticated)?;
+    Ok(Context {
+        api: api::api_url_override().unwrap_or_else(|| format!("https://{}/api/v4", host)),
+        project: api::encode(&path),
+        token,
+    })
+}
+
+fn request(ctx: &Context, method: &str, path: &str, body: Option<Value>) -> Result<Value> {
+    let url = format!("{}{}", ctx.api, path);
+    api::request(method, &url, &[("PRIVATE-TOKEN", &ctx.token)], body).map_err(|e| {
+        e.into_gg(
+            &format!("GitLab {} {}", method, path),
+            GgError::GlabError,
+            || GgError::GlabNotAuthenticated,
+        )
+    })
+}
+
+fn project_request(ctx: &Context, method: &str, path: &str, body: Option<Value>) -> Result<Value> {
+    let path = format!("/projects/{}{}", ctx.project, path);
+    request(ctx, method, &path, body)
+}
+
+fn mr_request(
+    ctx: &Context,
+    method: &str,
+    mr_iid: u64,
+    path: &str,
+    body: Option<Value>,
+) -> Result<Value> {
+    let path = format!(
+        "/projects/{}/merge_requests/{}{}",
+        ctx.project, mr_iid, path
+    );
+    request(ctx, method, &path, body)
+}
+
+fn parse<T: for<'de> Deserialize<'de>>(value: Value) -> Result<T> {
+    serde_json::from_value(value)
+        .map_err(|e| GgError::GlabError(format!("Failed to parse GitLab response: {}", e)))
+}
+
+fn convert_pipeline_status(status: &str) -> CiStatus {
+    match status {
+        "success" => CiStatus::Success,
+        "failed" => CiStatus::Failed,
+        "running" => CiStatus::Running,
+        "pending" | "created" | "waiting_for_resource" | "preparing" | "scheduled" | "manual" => {
+            CiStatus::Pending
+        }
+        "canceled" => CiStatus::Canceled,
+        _ => CiStatus::Unknown,
+    }
+}
+
+/// Check that the token is accepted
+pub fn check_auth() -> Result<()> {
+    whoami().map(|_| ())
+}
+
+/// Get the username of the authenticated user
+pub fn whoami() -> Result<String> {
+    let ctx = context()?;
+    let user = request(&ctx, "GET", "/user", None)?;
+    user["username"]
+        .as_str()
+        .map(str::to_string)
+        .ok_or_else(|| GgError::GlabError("Could not determine GitLab username".to_string()))
+}
+
+/// Create a new MR. Drafts get GitLab's `Draft: ` title prefix.
+pub fn create_mr(
+    source_branch: &str,
+    target_branch: &str,
+    title: &str,
+    description: &str,
+    draft: bool,
+) -> Result<MrCreationResult> {
+    let ctx = context()?;
+    let title = if draft {
+        format!("Draft: {}", title)
+    } else {
+        title.to_string()
+    };
+    let mr = request(
+        &ctx,
+        "POST",
+        &format!("/projects/{}/merge_requests", ctx.project),
+        Some(json!({
+            "source_branch": source_branch,
+            "target_branch": target_branch,
+            "title": title,
+            "description": description,
+        })),
+    )?;
+    Ok(MrCreationResult {
+        number: mr["iid"].as_u64().unwrap_or(0),
+        url: mr["web_url"].as_str().unwrap_or_default().to_string(),
+    })
+}
+
+/// View MR information
+pub fn view_mr(mr_number: u64) -> Result<MrInfo> {
+    let ctx = context()?;
+    let mr = mr_request(&ctx, "GET", mr_number, "", None)?;
+    glab::parse_mr_info(&mr.to_string())
+}
+
+/// Several MRs with one list request per [`glab::MRS_PER_REQUEST`] MRs
+pub fn get_mrs_info(mr_numbers: &[u64]) -> Result<Vec<MrInfo>> {
+    let ctx = context()?;
+    let mut mrs = Vec::new();
+    for chunk in mr_numbers.chunks(glab::MRS_PER_REQUEST) {
+        let list = request(
+            &ctx,
+            "GET",
+            &format!(
+                "/projects/{}/merge_requests?{}",
+                ctx.project,
+                glab::mr_iids_query(chunk)
+            ),
+            None,
+        )?;
+        mrs.extend(glab::parse_mr_list(&list.to_string())?);
+    }
+    Ok(mrs)
+}
+
+fn update_mr(mr_number: u64, fields: Value) -> Result<()> {
+    let ctx = context()?;
+    mr_request(&ctx, "PUT", mr_number, "", Some(fields))?;
+    Ok(())
+}
+
+/// Close an MR without merging.
+pub fn close_mr(mr_number: u64) -> Result<()> {
+    update_mr(mr_number, json!({ "state_event": "close" }))
+}
+
+/// Update MR target branch
+pub fn update_mr_target(mr_number: u64, target_branch: &str) -> Result<()> {
+    update_mr(mr_number, json!({ "target_branch": target_branch }))
+}
+
+/// Request reviews from users. GitLab wants user IDs, so each username is
+/// looked up first.
+pub fn request_reviewers(mr_number: u64, reviewers: &[String]) -> Result<()> {
+    let ctx = context()?;
+    let mut ids = Vec::with_capacity(reviewers.len());
+    for username in reviewers {
+        let users = request(
+            &ctx,
+            "GET",
+            &format!("/users?username={}", api::encode(username)),
+            None,
+        )?;
+        let id = users[0]["id"]
+            .as_u64()
+            .ok_or_else(|| GgError::GlabError(format!("Unknown GitLab user '{}'", username)))?;
+        ids.push(id);
+    }
+    update_mr(mr_number, json!({ "reviewer_ids": ids }))
+}
+
+/// Add labels to an MR
+pub fn add_labels(mr_number: u64, labels: &[String]) -> Result<()> {
+    update_mr(mr_number, json!({ "add_labels": labels.join(",") }))
+}
+
+/// Remove labels from an MR
+pub fn remove_labels(mr_number: u64, labels: &[String]) -> Result<()> {
+    update_mr(mr_number, json!({ "remove_labels": labels.join(",") }))
+}
+
+/// Set the milestone of an MR by title
+pub fn set_milestone(mr_number: u64, milestone: &str) -> Result<()> {
+    let ctx = context()?;
+    let milestones = request(
+        &ctx,
+        "GET",
+        &format!(
+            "/projects/{}/milestones?title={}",
+            ctx.project,
+            api::encode(milestone)
+        ),
+        None,
+    )?;
+    let id = milestones[0]["id"]
+        .as_u64()
+        .ok_or_else(|| GgError::GlabError(format!("No milestone named '{}'", milestone)))?;
+    update_mr(mr_number, json!({ "milestone_id": id }))
+}
+
+/// Update MR description/body
+pub fn update_mr_description(mr_number: u64, description: &str) -> Result<()> {
+    update_mr(mr_number, json!({ "description": description }))
+}
+
+/// Update MR title
+pub fn update_mr_title(mr_number: u64, title: &str) -> Result<()> {
+    update_mr(mr_number, json!({ "title": title }))
+}
+
+/// Strip GitLab's draft markers (`Draft:`, `[Draft]`, `(Draft)`, and the
+/// legacy `WIP:`/`[WIP]`) from the start of an MR title.
+fn strip_draft_prefix(title: &str) -> &str {
+    const PREFIXES: &[&str] = &["draft:", "[draft]", "(draft)", "wip:", "[wip]"];
+    let mut rest = title.trim_start();
+    while let Some(prefix) = PREFIXES.iter().find(|p| {
+        rest.get(..p.len())
+            .is_some_and(|s| s.eq_ignore_ascii_case(p))
+    }) {
+        rest = rest[prefix.len()..].trim_start();
+    }
+    rest
+}
+
+/// Mark a draft MR as ready. GitLab tracks drafts by title prefix, so this
+/// rewrites the title without it.
+pub fn mark_mr_ready(mr_number: u64) -> Result<()> {
+    let ctx = context()?;
+    let mr = mr_request(&ctx, "GET", mr_number, "", None)?;
+    let title = mr["title"].as_str().unwrap_or_default();
+    update_mr(mr_number, json!({ "title": strip_draft_prefix(title) }))
+}
+
+/// Get MR description/body text
+pub fn get_mr_body(mr_number: u64) -> Result<String> {
+    let ctx = context()?;
+    let mr = mr_request(&ctx, "GET", mr_number, "", None)?;
+    Ok(mr["description"].as_str().unwrap_or_default().to_string())
+}
+
+/// How long to wait for a server-side rebase before merging anyway
+const REBASE_POLLS: u32 = 30;
+
+/// Rebase an MR onto its target and wait for GitLab to finish, like
+/// `glab mr merge --rebase`.
+fn rebase_and_wait(ctx: &Context, mr_number: u64) -> Result<()> {
+    mr_request(ctx, "PUT", mr_number, "/rebase", None)?;
+    for _ in 0..REBASE_POLLS {
+        let mr = mr_request(
+            ctx,
+            "GET",
+            mr_number,
+            "?include_rebase_in_progress=true",
+            None,
+        )?;
+        if !mr["rebase_in_progress"].as_bool().unwrap_or(false) {
+            return match mr["merge_error"].as_str() {
+                Some(error) if !error.is_empty() => Err(GgError::GlabError(format!(
+                    "Failed to rebase MR !{}: {}",
+                    mr_number, error
+                ))),
+                _ => Ok(()),
+            };
+        }
+        std::thread::sleep(std::time::Duration::from_secs(1));
+    }
+    Ok(())
+}
+
+/// Merge an MR. `Rebase` rebases it onto its target first.
+pub fn merge_mr(mr_number: u64, strategy: MergeStrategy, delete_branch: bool) -> Result<()> {
+    let ctx = context()?;
+    if strategy == MergeStrategy::Rebase {
+        rebase_and_wait(&ctx, mr_number)?;
+    }
+    mr_request(
+        &ctx,
+        "PUT",
+        mr_number,
+        "/merge",
+        Some(json!({
+            "squash": strategy == MergeStrategy::Squash,
+            "should_remove_source_branch": delete_branch,
+        })),
+    )?;
+    Ok(())
+}
+
+/// Request GitLab to auto-merge an MR when the pipeline succeeds.
+pub fn auto_merge_mr_when_pipeline_succeeds(
+    mr_number: u64,
+    strategy: MergeStrategy,
+    delete_branch: bool,
+) -> Result<AutoMergeResult> {
+    let ctx = context()?;
+    match mr_request(
+        &ctx,
+        "PUT",
+        mr_number,
+        "/merge",
+        Some(json!({
+            "merge_when_pipeline_succeeds": true,
+            "should_remove_source_branch": delete_branch,
+            "squash": strategy == MergeStrategy::Squash,
+        })),
+    ) {
+        Ok(_) => Ok(AutoMergeResult::Queued),
+        Err(GgError::GlabError(msg)) if msg.contains("(HTTP 409)") && msg.contains("already") => {
+            Ok(AutoMergeResult::AlreadyQueued)
+        }
+        Err(e) => Err(e),
+    }
+}
+
+/// Check approvals for an MR
+pub fn check_mr_approved(mr_number: u64) -> Result<bool> {
+    let ctx = context()?;
+    let approvals = mr_request(&ctx, "GET", mr_number, "/approvals", None)?;
+    Ok(approvals["approved"].as_bool().unwrap_or(false))
+}
+
+/// Get the head pipeline status of an MR
+pub fn get_mr_ci_status(mr_number: u64) -> Result<CiStatus> {
+    let ctx = context()?;
+    let mr = mr_request(&ctx, "GET", mr_number, "", None)?;
+    Ok(mr["head_pipeline"]["status"]
+        .as_str()
+        .map_or(CiStatus::Unknown, convert_pipeline_status))
+}
+
+/// List open MRs for a specific source branch
+pub fn list_mrs_for_branch(branch: &str) -> Result<Vec<u64>> {
+    let ctx = context()?;
+    let mrs = request(
+        &ctx,
+        "GET",
+        &format!(
+            "/projects/{}/merge_requests?state=opened&source_branch={}&per_page=100",
+            ctx.project,
+            api::encode(branch)
+        ),
+        None,
+    )?;
+    Ok(mrs
+        .as_array()
+        .into_iter()
+        .flatten()
+        .filter_map(|mr| mr["iid"].as_u64())
+        .collect())
+}
+
+/// List open MRs in this project authored by the authenticated user.
+pub fn list_my_open_mrs() -> Result<Vec<OpenMr>> {
+    let ctx = context()?;
+    parse(request(
+        &ctx,
+        "GET",
+        &format!(
+            "/projects/{}/merge_requests?state=opened&scope=created_by_me&per_page=100",
+            ctx.project
+        ),
+        None,
+    )?)
+}
+
+/// List all notes on an MR.
+pub fn list_mr_notes(mr_iid: u64) -> Result<Vec<MrNote>> {
+    let ctx = context()?;
+    let mut all = Vec::new();
+    for page in 1.. {
+        let notes: Vec<MrNote> = parse(mr_request(
+            &ctx,
+            "GET",
+            mr_iid,
+            &format!("/notes?per_page=100&page={}", page),
+            None,
+        )?)?;
+        let full_page = notes.len() == 100;
+        all.extend(notes);
+        if !full_page {
+            break;
+        }
+    }
+    Ok(all)
+}
+
+/// Create a new note on an MR.
+pub fn create_mr_note(mr_iid: u64, body: &str) -> Result<()> {
+    let ctx = context()?;
+    mr_request(
+        &ctx,
+        "POST",
+        mr_iid,
+        "/notes",
+        Some(json!({ "body": body })),
+    )?;
+    Ok(())
+}
+
+/// Update an existing note on an MR.
+pub fn update_mr_note(mr_iid: u64, note_id: u64, body: &str) -> Result<()> {
+    let ctx = context()?;
+    mr_request(
+        &ctx,
+        "PUT",
+        mr_iid,
+        &format!("/notes/{}", note_id),
+        Some(json!({ "body": body })),
+    )?;
+    Ok(())
+}
+
+/// Delete a note from an MR.
+pub fn delete_mr_note(mr_iid: u64, note_id: u64) -> Result<()> {
+    let ctx = context()?;
+    mr_request(&ctx, "DELETE", mr_iid, &format!("/notes/{}", note_id), None)?;
+    Ok(())
+}
+
+/// Get the approval rules GitLab still considers unmet for an MR.
+pub fn get_unmet_approval_rules(mr_number: u64) -> Result<Vec<ApprovalRule>> {
+    let ctx = context()?;
+    let state = mr_request(&ctx, "GET", mr_number, "/approval_state", None)?;
+    glab::parse_unmet_approval_rules(&state.to_string())
+}
+
+/// Whether merge trains are enabled for the project; `false` when the
+/// project settings can't be read.
+pub fn check_merge_trains_enabled() -> Result<bool> {
+    let ctx = context()?;
+    Ok(project_request(&ctx, "GET", "", None)
+        .map(|project| project["merge_trains_enabled"].as_bool().unwrap_or(false))
+        .unwrap_or(false))
+}
+
+/// Add an MR to the merge train
+pub fn add_to_merge_train(mr_number: u64) -> Result<AutoMergeResult> {
+    let ctx = context()?;
+    match project_request(
+        &ctx,
+        "POST",
+        &format!("/merge_trains/merge_requests/{}", mr_number),
+        None,
+    ) {
+        Ok(_) => Ok(AutoMergeResult::Queued),
+        Err(GgError::GlabError(msg)) if msg.contains("(HTTP 409)") && msg.contains("already") => {
+            Ok(AutoMergeResult::AlreadyQueued)
+        }
+        Err(e) => Err(e),
+    }
+}
+
+/// One page of the merge train of `target_branch`, as JSON text.
+pub fn merge_train_page(target_branch: &str, query: &str) -> Result<String> {
+    let ctx = context()?;
+    let page = project_request(
+        &ctx,
+        "GET",
+        &format!("/merge_trains/{}?{}", api::encode(target_branch), query),
+        None,
+    )?;
+    Ok(page.to_string())
+}
+
+/// Ask GitLab to rebase an MR; the rebase itself runs asynchronously.
+pub fn rebase_mr(mr_number: u64) -> Result<()> {
+    let ctx = context()?;
+    mr_request(&ctx, "PUT", mr_number, "/rebase", None)?;
+    Ok(())
+}
+
+fn head_pipeline_id(ctx: &Context, mr_number: u64) -> Result<Option<u64>> {
+    let mr = mr_request(ctx, "GET", mr_number, "", None)?;
+    Ok(mr["head_pipeline"]["id"].as_u64())
+}
+
+/// Re-run CI for an MR: retry the failed jobs of the head pipeline, or
+/// start a new MR pipeline.
+pub fn retry_mr_pipeline(mr_number: u64, failed_only: bool) -> Result<Vec<String>> {
+    let ctx = context()?;
+    let pipeline = if failed_only {
+        let pipeline_id = head_pipeline_id(&ctx, mr_number)?.ok_or_else(|| {
+            GgError::GlabError(format!("MR !{} has no pipeline to retry", mr_number))
+        })?;
+        project_request(
+            &ctx,
+            "POST",
+            &format!("/pipelines/{}/retry", pipeline_id),
+            None,
+        )?
+    } else {
+        mr_request(&ctx, "POST", mr_number, "/pipelines", None)?
+    };
+    Ok(vec![match pipeline["id"].as_u64() {
+        Some(id) => format!("pipeline {}", id),
+        None => "pipeline".to_string(),
+    }])
+}
+
+/// Failed jobs of an MR's head pipeline; empty when they can't be read.
+pub fn get_mr_failed_ci_jobs(mr_number: u64) -> Result<Vec<FailedJob>> {
+    let ctx = context()?;
+    let Some(pipeline_id) = head_pipeline_id(&ctx, mr_number).ok().flatten() else {
+        return Ok(vec![]);
+    };
+    Ok(project_request(
+        &ctx,
+        "GET",
+        &format!("/pipelines/{}/jobs?per_page=100", pipeline_id),
+        None,
+    )
+    .map(|jobs| glab::parse_failed_jobs(&jobs.to_string()))
+    .unwrap_or_default())
+}
+
+/// List all discussions on an MR.
+pub fn list_mr_discussions(mr_iid: u64) -> Result<Vec<MrDiscussion>> {
+    let ctx = context()?;
+    let mut all = Vec::new();
+    for page in 1.. {
+        let discussions: Vec<MrDiscussion> = parse(mr_request(
+            &ctx,
+            "GET",
+            mr_iid,
+            &format!("/discussions?per_page=100&page={}", page),
+            None,
+        )?)?;
+        let full_page = discussions.len() == 100;
+        all.extend(discussions);
+        if !full_page {
+            break;
+        }
+    }
+    Ok(all)
+}
+
+/// Resolve a discussion on an MR.
+pub fn resolve_mr_discussion(mr_iid: u64, discussion_id: &str) -> Result<()> {
+    let ctx = context()?;
+    mr_request(
+        &ctx,
+        "PUT",
+        mr_iid,
+        &format!("/discussions/{}", discussion_id),
+        Some(json!({ "resolved": true })),
+    )?;
+    Ok(())
+}
+
+/// Get the project's signed-commit push rule and merge method. Push rules
+/// are a paid feature: a failed lookup means "no push rule".
+pub fn get_project_rules() -> Result<ProjectRules> {
+    let ctx = context()?;
+    let project = project_request(&ctx, "GET", "", None)?;
+    let reject_unsigned_commits = project_request(&ctx, "GET", "/push_rule", None)
+        .map(|rule| rule["reject_unsigned_commits"].as_bool().unwrap_or(false))
+        .unwrap_or(false);
+    Ok(ProjectRules {
+        reject_unsigned_commits,
+        fast_forward_only: project["merge_method"].as_str() == Some("ff"),
+    })
+}
+
+/// Upload a file to the project and return the Markdown that embeds it.
+pub fn upload_file(path: &std::path::Path) -> Result<String> {
+    let ctx = context()?;
+    let content = std::fs::read(path)?;
+    let file_name = path
+        .file_name()
+        .map(|name| name.to_string_lossy().into_owned())
+        .unwrap_or_default();
+    let upload = api::upload(
+        &format!("{}/projects/{}/uploads", ctx.api, ctx.project),
+        &[("PRIVATE-TOKEN", &ctx.token)],
+        "file",
+        &file_name,
+        &content,
+    )
+    .map_err(|e| {
+        e.into_gg(
+            &format!("GitLab upload of {}", path.display()),
+            GgError::GlabError,
+            || GgError::GlabNotAuthenticated,
+        )
+    })?;
+    upload["markdown"]
+        .as_str()
+        .map(str::to_string)
+        .ok_or_else(|| GgError::GlabError(format!("Failed to upload {}", path.display())))
+}
+
+/// List the MRs blocking `mr_iid`.
+pub fn list_mr_blocks(mr_iid: u64) -> Result<Vec<MrBlock>> {
+    let ctx = context()?;
+    parse(mr_request(&ctx, "GET", mr_iid, "/blocks", None)?)
+}
+
+/// Mark `mr_iid` as blocked by `blocking_iid`. The blocks API takes the
+/// blocking MR's global id rather than its iid.
+pub fn create_mr_block(mr_iid: u64, blocking_iid: u64) -> Result<()> {
+    let ctx = context()?;
+    let blocking = mr_request(&ctx, "GET", blocking_iid, "", None)?;
+    let blocking_id = blocking["id"].as_u64().ok_or_else(|| {
+        GgError::GlabError(format!("Could not read the id of MR !{}", blocking_iid))
+    })?;
+    mr_request(
+        &ctx,
+        "POST",
+        mr_iid,
+        "/blocks",
+        Some(json!({ "blocking_merge_request_id": blocking_id })),
+    )?;
+    Ok(())
+}
+
+/// Remove a dependency from `mr_iid`.
+pub fn delete_mr_block(mr_iid: u64, block_id: u64) -> Result<()> {
+    let ctx = context()?;
+    mr_request(
+        &ctx,
+        "DELETE",
+        mr_iid,
+        &format!("/blocks/{}", block_id),
+        None,
+    )?;
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn converts_pipeline_statuses() {
+        assert_eq!(convert_pipeline_status("success"), CiStatus::Success);
+        assert_eq!(convert_pipeline_status("failed"), CiStatus::Failed);
+        assert_eq!(convert_pipeline_status("running"), CiStatus::Running);
+        assert_eq!(
+            convert_pipeline_status("waiting_for_resource"),
+            CiStatus::Pending
+        );
+        assert_eq!(convert_pipeline_status("canceled"), CiStatus::Canceled);
+        assert_eq!(convert_pipeline_status("skipped"), CiStatus::Unknown);
+    }
+
+    #[test]
+    fn parses_api_merge_request() {
+        let mr = json!({
+            "iid": 5, "title": "Draft: Add parser", "state": "opened",
+            "web_url": "https://gitlab.com/g/p/-/merge_requests/5",
+            "source_branch": "nacho/parser--c-abc1234", "target_branch": "main",
+            "draft": true, "detailed_merge_status": "draft_status",
+            "updated_at": "2024-01-15T10:30:00.000Z"
+        });
+        let info = glab::parse_mr_info(&mr.to_string()).unwrap();
+        assert_eq!(info.iid, 5);
+        assert!(info.draft);
+        assert_eq!(info.base_branch.as_deref(), Some("main"));
+        assert!(info.updated_at_ms.is_some());
+    }
+
+    #[test]
+    fn strips_draft_prefix() {
+        assert_eq!(strip_draft_prefix("Draft: Add parser"), "Add parser");
+        assert_eq!(
+            strip_draft_prefix("[Draft] (draft) Add parser"),
+            "Add parser"
+        );
+        assert_eq!(strip_draft_prefix("WIP: Add parser"), "Add parser");
+        assert_eq!(strip_draft_prefix("Drafting rules"), "Drafting rules");
+    }
+}
@@ -0,0 +1,274 @@
+//! Lifecycle hooks: user scripts run at key points of `gg sync` and `gg land`.
+//!
+//! A hook is the `hooks.<name>` config command when set, otherwise an
+//! executable `.git/gg/hooks/<file>` script:
+//!
+//! | Hook | Config key | Script | When |
+//! |------|------------|--------|------|
+//! | pre-sync | `hooks.pre_sync` | `pre-sync` | before `gg sync` pushes anything |
+//! | pre-land | `hooks.pre_land` | `pre-land` | before `gg land` merges anything |
+//! | post-land | `hooks.post_land` | `post-land` | after `gg land` merged at least one PR/MR |
+//!
+//! Hooks run from the repository root and read the stack from `GG_*`
+//! environment variables. A pre-hook exiting non-zero aborts the command;
+//! a failing post-hook only produces a warning. Hook output goes to stderr
+//! so `--json` output stays parseable.
+//!
+//! git's own hooks (`pre-commit`, `commit-msg`, `post-rewrite`, ...) are a
+//! separate matter: [`git_command`] decides, per `defaults.run_hooks`,
+//! whether they run for the amends and rebases gg makes.
+
+use std::path::{Path, PathBuf};
+use std::process::{Command, Stdio};
+use std::sync::{OnceLock, RwLock};
+
+use git2::Repository;
+
+use crate::config::{Config, RunHooks};
+use crate::error::{GgError, Result};
+use crate::signing;
+use crate::stack::StackEntry;
+
+/// Lifecycle points where hooks run.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Hook {
+    PreSync,
+    PreLand,
+    PostLand,
+}
+
+impl Hook {
+    /// Script name under `.git/gg/hooks/`, also exported as `GG_HOOK`
+    pub fn name(self) -> &'static str {
+        match self {
+            Hook::PreSync => "pre-sync",
+            Hook::PreLand => "pre-land",
+            Hook::PostLand => "post-land",
+        }
+    }
+
+    fn configured(self, config: &Config) -> Option<&str> {
+        let command = match self {
+            Hook::PreSync => &config.hooks.pre_sync,
+            Hook::PreLand => &config.hooks.pre_land,
+            Hook::PostLand => &config.hooks.post_land,
+        };
+        command.as_deref().map(str::trim).filter(|s| !s.is_empty())
+    }
+}
+
+/// A stack entry as seen by a hook.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct HookEntry {
+    pub position: usize,
+    pub sha: String,
+    pub gg_id: Option<String>,
+    pub pr_number: Option<u64>,
+}
+
+impl From<&StackEntry> for HookEntry {
+    fn from(entry: &StackEntry) -> Self {
+        Self {
+            position: entry.position,
+            sha: entry.oid.to_string(),
+            gg_id: entry.gg_id.clone(),
+            pr_number: entry.mr_number,
+        }
+    }
+}
+
+/// What a hook is told about the operation.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct HookContext {
+    pub stack: String,
+    pub base: String,
+    /// Entries the operation acts on (for post-land, the ones it merged)
+    pub entries: Vec<HookEntry>,
+}
+
+impl HookContext {
+    fn env(&self, hook: Hook) -> Vec<(&'static str, String)> {
+        let join = |f: &dyn Fn(&HookEntry) -> Option<String>| {
+            self.entries
+                .iter()
+                .filter_map(f)
+                .collect::<Vec<_>>()
+                .join(" ")
+        };
+        vec![
+            ("GG_HOOK", hook.name().to_string()),
+            ("GG_STACK", self.stack.clone()),
+            ("GG_BASE", self.base.clone()),
+            ("GG_ENTRY_COUNT", self.entries.len().to_string()),
+            ("GG_POSITIONS", join(&|e| Some(e.position.to_string()))),
+            ("GG_SHAS", join(&|e| Some(e.sha.clone()))),
+            ("GG_IDS", join(&|e| e.gg_id.clone())),
+            (
+                "GG_PR_NUMBERS",
+                join(&|e| e.pr_number.map(|n| n.to_string())),
+            ),
+        ]
+    }
+}
+
+/// Run `hook` if one is configured.
+///
+/// Returns [`GgError::HookFailed`] when it exits non-zero; callers abort on
+/// pre-hooks and warn on post-hooks.
+pub fn run(repo: &Repository, config: &Config, hook: Hook, context: &HookContext) -> Result<()> {
+    let mut command = if let Some(configured) = hook.configured(config) {
+        let mut command = Command::new("sh");
+        command.arg("-c").arg(configured);
+        command
+    } else if let Some(script) = script_path(repo.commondir(), hook) {
+        Command::new(script)
+    } else {
+        return Ok(());
+    };
+
+    if let Some(workdir) = repo.workdir() {
+        command.current_dir(workdir);
+    }
+    let status = command
+        .envs(context.env(hook))
+        .stdin(Stdio::null())
+        .stdout(std::io::stderr())
+        .status()
+        .map_err(|e| GgError::HookFailed(hook.name().to_string(), e.to_string()))?;
+    if status.success() {
+        Ok(())
+    } else {
+        Err(GgError::HookFailed(
+            hook.name().to_string(),
+            format!("exited with {}", status),
+        ))
+    }
+}
+
+/// Executable `.git/gg/hooks/<name>` script for `hook`, if present.
+fn script_path(git_dir: &Path, hook: Hook) -> Option<PathBuf> {
+    let path = git_dir.join("gg").join("hooks").join(hook.name());
+    let metadata = path.metadata().ok()?;
+    #[cfg(unix)]
+    {
+        use std::os::unix::fs::PermissionsExt;
+        if metadata.permissions().mode() & 0o111 == 0 {
+            return None;
+        }
+    }
+    metadata.is_file().then_some(path)
+}
+
+/// What a `git` subprocess gg runs does to history.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum GitRewrite {
+    /// Changes what a commit contains (`gg sc`, `gg run --amend`)
+    Amend,
+    /// Moves commits without changing them (rebase, reorder, drop, ...)
+    Replay,
+}
+
+fn git_hooks_mode() -> &'static RwLock<RunHooks> {
+    static MODE: OnceLock<RwLock<RunHooks>> = OnceLock::new();
+    MODE.get_or_init(|| RwLock::new(RunHooks::default()))
+}
+
+/// Use `mode` for git hooks from now on: `defaults.run_hooks` at startup,
+/// [`RunHooks::Never`] for `--no-verify`.
+pub fn set_git_hooks(mode: RunHooks) {
+    *git_hooks_mode().write().unwrap_or_else(|e| e.into_inner()) = mode;
+}
+
+/// Whether git hooks run for `rewrite`.
+pub fn git_hooks_run(rewrite: GitRewrite) -> bool {
+    match *git_hooks_mode().read().unwrap_or_else(|e| e.into_inner()) {
+        RunHooks::Always => true,
+        RunHooks::RewriteOnly => rewrite == GitRewrite::Amend,
+        RunHooks::Never => false,
+    }
+}
+
+/// `git`, for a `rewrite` gg makes itself; every git hook is turned off for
+/// it (`core.hooksPath` pointed at nothing) when [`git_hooks_run`] says no,
+/// and the commits it writes are signed as `defaults.sign_commits` says.
+pub fn git_command(rewrite: GitRewrite) -> Command {
+    let mut command = Command::new("git");
+    if !git_hooks_run(rewrite) {
+        command.args(["-c", "core.hooksPath=/dev/null"]);
+    }
+    if let Some(sign) = signing::sign_commits() {
+        command.args(["-c", &format!("commit.gpgsign={}", sign)]);
+    }
+    command
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn context() -> HookContext {
+        HookContext {
+            stack: "feature".to_string(),
+            base: "main".to_string(),
+            entries: vec![
+                HookEntry {
+                    position: 1,
+                    sha: "abc1234".to_string(),
+                    gg_id: Some("c-1111111".to_string()),
+                    pr_number: Some(41),
+                },
+                HookEntry {
+                    position: 2,
+                    sha: "def5678".to_string(),
+                    gg_id: None,
+                    pr_number: None,
+                },
+            ],
+        }
+    }
+
+    #[test]
+    fn env_describes_stack_and_entries() {
+        let env = context().env(Hook::PreSync);
+        let get = |key: &str| env.iter().find(|(k, _)| *k == key).unwrap().1.clone();
+        assert_eq!(get("GG_HOOK"), "pre-sync");
+        assert_eq!(get("GG_STACK"), "feature");
+        assert_eq!(get("GG_BASE"), "main");
+        assert_eq!(get("GG_ENTRY_COUNT"), "2");
+        assert_eq!(get("GG_POSITIONS"), "1 2");
+        assert_eq!(get("GG_SHAS"), "abc1234 def5678");
+        assert_eq!(get("GG_IDS"), "c-1111111");
+        assert_eq!(get("GG_PR_NUMBERS"), "41");
+    }
+
+    #[test]
+    fn configured_command_wins_and_failure_is_an_error() {
+        let dir = tempfile::tempdir().unwrap();
+        let repo = Repository::init(dir.path()).unwrap();
+        let mut config = Config::default();
+        assert!(run(&repo, &config, Hook::PreLand, &context()).is_ok());
+
+        config.hooks.pre_land = Some("test \"$GG_PR_NUMBERS\" = 41".to_string());
+        assert!(run(&repo, &config, Hook::PreLand, &context()).is_ok());
+
+        config.hooks.pre_land = Some("exit 3".to_string());
+        let err = run(&repo, &config, Hook::PreLand, &context()).unwrap_err();
+        assert!(matches!(err, GgError::HookFailed(ref name, _) if name == "pre-land"));
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn script_must_be_executable() {
+        use std::os::unix::fs::PermissionsExt;
+
+        let dir = tempfile::tempdir().unwrap();
+        let hooks = dir.path().join("gg").join("hooks");
+        std::fs::create_dir_all(&hooks).unwrap();
+        let script = hooks.join("post-land");
+        std::fs::write(&script, "#!/bin/sh\nexit 1\n").unwrap();
+        assert_eq!(script_path(dir.path(), Hook::PostLand), None);
+
+        std::fs::set_permissions(&script, std::fs::Permissions::from_mode(0o755)).unwrap();
+        assert_eq!(script_path(dir.path(), Hook::PostLand), Some(script));
+    }
+}
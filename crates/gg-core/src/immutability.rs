@@ -190,7 +190,10 @@ impl<'a> ImmutabilityPolicy<'a> {
     /// Construct a policy for the given stack, resolving the remote base ref
     /// (falling back to the local base if the remote ref is not available).
     pub fn for_stack(repo: &'a Repository, stack: &Stack) -> Result<Self> {
-        let remote_ref = format!("origin/{}", stack.base);
+        let remote = crate::config::Config::load(repo.commondir())
+            .map(|config| config.get_remote_for_stack(&stack.name).to_string())
+            .unwrap_or_else(|_| crate::git::DEFAULT_REMOTE.to_string());
+        let remote_ref = format!("{}/{}", remote, stack.base);
         let (base_ref, base_oid) =
             if let Ok(obj) = repo.revparse_single(&format!("refs/remotes/{}", remote_ref)) {
                 (remote_ref, Some(obj.id()))
@@ -325,7 +328,10 @@ pub fn guard(report: ImmutabilityReport, force: bool) -> Result<()> {
 /// Cost: O(entries with `mr_number`) network round-trips, executed serially.
 /// For typical stacks (a handful of open PRs) this is well below a second.
 pub fn refresh_mr_state_for_guard(repo: &Repository, stack: &mut Stack) {
-    let Ok(provider) = Provider::detect(repo) else {
+    let remote = crate::config::Config::load(repo.commondir())
+        .map(|config| config.get_remote_for_stack(&stack.name).to_string())
+        .unwrap_or_else(|_| crate::git::DEFAULT_REMOTE.to_string());
+    let Ok(provider) = Provider::for_remote(repo, &remote) else {
         // No provider configured — offline or non-GitHub/GitLab repo. The
         // base-ancestor rule remains in effect.
         return;
@@ -363,6 +369,9 @@ mod tests {
             position: pos,
             in_merge_train: false,
             merge_train_position: None,
+            unresolved_threads: None,
+            annotations: Default::default(),
+            signed: false,
         }
     }
 
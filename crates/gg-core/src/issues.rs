@@ -0,0 +1,410 @@
+//! Issue tracker linking (`defaults.issue_pattern`, `defaults.issue_tracker`).
+//!
+//! `defaults.issue_pattern` finds issue keys (`#123`, `PAY-42`, `ENG-7`) in
+//! the stack name and in commit messages. `gg sync` adds one
+//! `defaults.issue_link` line per key (`Closes {key}` by default) to the
+//! PR/MR description of each entry, and `gg land` hands the keys of every
+//! merged entry to the configured tracker:
+//!
+//! - `github`: closes the issue (`#123`) in the repository's GitHub project
+//! - `jira`: applies the `transition` (default `Done`) on the Jira instance
+//!   at `url`, with `JIRA_API_TOKEN` (and `JIRA_EMAIL` for Jira Cloud)
+//! - `linear`: moves the issue to the workflow state named `transition`
+//!   (default `Done`), with `LINEAR_API_KEY`
+//! - `command`: runs `command` through `sh -c` with `GG_ISSUE_KEY`,
+//!   `GG_ISSUE_PR` and `GG_ISSUE_STACK` set, for any other tracker
+//!
+//! A tracker that fails only produces a warning; the PRs/MRs stay landed.
+
+use std::process::{Command, Stdio};
+
+use base64::Engine;
+use regex::Regex;
+use serde_json::{json, Value};
+
+use crate::api::{self, HttpError};
+use crate::config::{Config, IssueTrackerDefaults, IssueTrackerKind};
+use crate::error::{GgError, Result};
+use crate::gh;
+use crate::git;
+
+/// Link line used when `defaults.issue_link` is unset.
+pub const DEFAULT_ISSUE_LINK: &str = "Closes {key}";
+
+/// Workflow state landed issues move to when `transition` is unset.
+const DEFAULT_TRANSITION: &str = "Done";
+
+/// `defaults.issue_pattern`, compiled; `None` when unset.
+pub fn issue_regex(config: &Config) -> Result<Option<Regex>> {
+    config
+        .defaults
+        .issue_pattern
+        .as_deref()
+        .map(|source| {
+            Regex::new(source).map_err(|e| {
+                GgError::Other(format!(
+                    "Invalid defaults.issue_pattern `{}`: {}",
+                    source, e
+                ))
+            })
+        })
+        .transpose()
+}
+
+/// Issue keys matched by `pattern` in `text`, first occurrence first. A
+/// `key` group narrows a match to the key itself.
+pub fn extract_keys(pattern: &Regex, text: &str) -> Vec<String> {
+    let mut keys: Vec<String> = Vec::new();
+    for captures in pattern.captures_iter(text) {
+        let Some(key) = captures.name("key").or_else(|| captures.get(0)) else {
+            continue;
+        };
+        let key = key.as_str().to_string();
+        if !key.is_empty() && !keys.contains(&key) {
+            keys.push(key);
+        }
+    }
+    keys
+}
+
+/// Issue keys of a stack entry: those in the stack name, then those in its
+/// commit message (without gg's trailers).
+pub fn entry_keys(pattern: &Regex, stack_name: &str, message: &str) -> Vec<String> {
+    let message = git::strip_gg_parent_from_message(&git::strip_gg_id_from_message(message));
+    let mut keys = extract_keys(pattern, stack_name);
+    for key in extract_keys(pattern, &message) {
+        if !keys.contains(&key) {
+            keys.push(key);
+        }
+    }
+    keys
+}
+
+/// `description` with a `link` line (`{key}` filled in) for each of `keys`
+/// it doesn't already contain.
+pub fn append_links(description: Option<String>, keys: &[String], link: &str) -> Option<String> {
+    let body = description.unwrap_or_default();
+    let lines: Vec<String> = keys
+        .iter()
+        .map(|key| link.replace("{key}", key))
+        .filter(|line| !body.contains(line.as_str()))
+        .collect();
+    if lines.is_empty() {
+        return (!body.is_empty()).then_some(body);
+    }
+    let links = lines.join("\n");
+    Some(if body.trim().is_empty() {
+        links
+    } else {
+        format!("{}\n\n{}", body.trim_end(), links)
+    })
+}
+
+/// An issue whose PR/MR was just landed.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct LandedIssue {
+    pub key: String,
+    pub pr_number: u64,
+    pub stack: String,
+}
+
+/// A tracker `gg land` resolves linked issues in.
+pub trait IssueTracker {
+    /// Name shown in messages, e.g. `Jira`
+    fn name(&self) -> &'static str;
+
+    /// Close or transition the issue; `Ok(false)` when the key is not one
+    /// of this tracker's.
+    fn resolve(&self, issue: &LandedIssue) -> Result<bool>;
+}
+
+/// The tracker configured in `defaults.issue_tracker`, if any.
+pub fn tracker(config: &IssueTrackerDefaults) -> Result<Option<Box<dyn IssueTracker>>> {
+    let transition = config
+        .transition
+        .clone()
+        .unwrap_or_else(|| DEFAULT_TRANSITION.to_string());
+    let missing = |field: &str| {
+        GgError::Other(format!(
+            "defaults.issue_tracker.{} is required for this tracker",
+            field
+        ))
+    };
+    Ok(match config.kind {
+        None => None,
+        Some(IssueTrackerKind::Github) => Some(Box::new(GitHubIssues)),
+        Some(IssueTrackerKind::Jira) => Some(Box::new(Jira {
+            url: config
+                .url
+                .clone()
+                .ok_or_else(|| missing("url"))?
+                .trim_end_matches('/')
+                .to_string(),
+            transition,
+        })),
+        Some(IssueTrackerKind::Linear) => Some(Box::new(Linear { state: transition })),
+        Some(IssueTrackerKind::Command) => Some(Box::new(CommandTracker {
+            command: config.command.clone().ok_or_else(|| missing("command"))?,
+        })),
+    })
+}
+
+fn http_error(tracker: &str, error: HttpError) -> GgError {
+    GgError::Other(match error {
+        HttpError::Status(code, message) => {
+            format!("{} returned HTTP {}: {}", tracker, code, message)
+        }
+        HttpError::Transport(message) | HttpError::Parse(message) => {
+            format!("{} request failed: {}", tracker, message)
+        }
+    })
+}
+
+/// Closes GitHub issues of the current repository.
+struct GitHubIssues;
+
+impl IssueTracker for GitHubIssues {
+    fn name(&self) -> &'static str {
+        "GitHub"
+    }
+
+    fn resolve(&self, issue: &LandedIssue) -> Result<bool> {
+        let Ok(number) = issue.key.trim_start_matches('#').parse::<u64>() else {
+            return Ok(false);
+        };
+        gh::close_issue(number)?;
+        Ok(true)
+    }
+}
+
+/// Applies a transition to Jira issues.
+struct Jira {
+    url: String,
+    transition: String,
+}
+
+impl Jira {
+    fn auth_header() -> Result<String> {
+        let token = api::env_token(&["JIRA_API_TOKEN"]).ok_or_else(|| {
+            GgError::Other("Set JIRA_API_TOKEN to transition Jira issues".to_string())
+        })?;
+        Ok(match api::env_token(&["JIRA_EMAIL"]) {
+            // Jira Cloud: API tokens go with the account's email
+            Some(email) => format!(
+                "Basic {}",
+                base64::engine::general_purpose::STANDARD.encode(format!("{}:{}", email, token))
+            ),
+            None => format!("Bearer {}", token),
+        })
+    }
+}
+
+impl IssueTracker for Jira {
+    fn name(&self) -> &'static str {
+        "Jira"
+    }
+
+    fn resolve(&self, issue: &LandedIssue) -> Result<bool> {
+        let auth = Self::auth_header()?;
+        let headers = [("Authorization", auth.as_str())];
+        let url = format!(
+            "{}/rest/api/2/issue/{}/transitions",
+            self.url,
+            api::encode(&issue.key)
+        );
+        let available =
+            api::request("GET", &url, &headers, None).map_err(|e| http_error(self.name(), e))?;
+        let matches = |t: &&Value, field: &str| {
+            t.pointer(field)
+                .and_then(Value::as_str)
+                .is_some_and(|name| name.eq_ignore_ascii_case(&self.transition))
+        };
+        let transitions = available["transitions"]
+            .as_array()
+            .cloned()
+            .unwrap_or_default();
+        let Some(id) = transitions
+            .iter()
+            .find(|t| matches(t, "/name") || matches(t, "/to/name"))
+            .and_then(|t| t["id"].as_str())
+        else {
+            return Err(GgError::Other(format!(
+                "{} has no '{}' transition",
+                issue.key, self.transition
+            )));
+        };
+        api::request(
+            "POST",
+            &url,
+            &headers,
+            Some(json!({ "transition": { "id": id } })),
+        )
+        .map_err(|e| http_error(self.name(), e))?;
+        Ok(true)
+    }
+}
+
+/// Moves Linear issues to a workflow state.
+struct Linear {
+    state: String,
+}
+
+impl Linear {
+    const URL: &'static str = "https://api.linear.app/graphql";
+
+    fn query(&self, key: &str, query: &str, variables: Value) -> Result<Value> {
+        let token = api::env_token(&["LINEAR_API_KEY"]).ok_or_else(|| {
+            GgError::Other("Set LINEAR_API_KEY to update Linear issues".to_string())
+        })?;
+        let response = api::request(
+            "POST",
+            Self::URL,
+            &[("Authorization", token.as_str())],
+            Some(json!({ "query": query, "variables": variables })),
+        )
+        .map_err(|e| http_error(self.name(), e))?;
+        if let Some(message) = response["errors"][0]["message"].as_str() {
+            return Err(GgError::Other(format!("Linear: {}: {}", key, message)));
+        }
+        Ok(response["data"].clone())
+    }
+}
+
+impl IssueTracker for Linear {
+    fn name(&self) -> &'static str {
+        "Linear"
+    }
+
+    fn resolve(&self, issue: &LandedIssue) -> Result<bool> {
+        let data = self.query(
+            &issue.key,
+            "query($id: String!) { issue(id: $id) { id team { states { nodes { id name } } } } }",
+            json!({ "id": issue.key }),
+        )?;
+        let Some(id) = data["issue"]["id"].as_str() else {
+            return Ok(false);
+        };
+        let states = data["issue"]["team"]["states"]["nodes"]
+            .as_array()
+            .cloned()
+            .unwrap_or_default();
+        let Some(state) = states
+            .iter()
+            .find(|s| {
+                s["name"]
+                    .as_str()
+                    .is_some_and(|name| name.eq_ignore_ascii_case(&self.state))
+            })
+            .and_then(|s| s["id"].as_str())
+        else {
+            return Err(GgError::Other(format!(
+                "{}'s team has no '{}' state",
+                issue.key, self.state
+            )));
+        };
+        self.query(
+            &issue.key,
+            "mutation($id: String!, $state: String!) { issueUpdate(id: $id, input: { stateId: $state }) { success } }",
+            json!({ "id": id, "state": state }),
+        )?;
+        Ok(true)
+    }
+}
+
+/// Hands issues to a user-provided command.
+struct CommandTracker {
+    command: String,
+}
+
+impl IssueTracker for CommandTracker {
+    fn name(&self) -> &'static str {
+        "issue_tracker.command"
+    }
+
+    fn resolve(&self, issue: &LandedIssue) -> Result<bool> {
+        let output = Command::new("sh")
+            .arg("-c")
+            .arg(&self.command)
+            .env("GG_ISSUE_KEY", &issue.key)
+            .env("GG_ISSUE_PR", issue.pr_number.to_string())
+            .env("GG_ISSUE_STACK", &issue.stack)
+            .stdin(Stdio::null())
+            .output()?;
+        if !output.status.success() {
+            let stderr = String::from_utf8_lossy(&output.stderr).trim().to_string();
+            return Err(GgError::Other(if stderr.is_empty() {
+                format!("`{}` exited with {}", self.command, output.status)
+            } else {
+                stderr
+            }));
+        }
+        Ok(true)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn extracts_keys_from_stack_name_and_message() {
+        let pattern = Regex::new(r"[A-Z]+-\d+").unwrap();
+        assert_eq!(
+            entry_keys(
+                &pattern,
+                "PAY-12-retry-refunds",
+                "Retry refunds\n\nAlso fixes PAY-40 and PAY-12.\n\nGG-ID: c-1234567"
+            ),
+            vec!["PAY-12", "PAY-40"]
+        );
+
+        let github = Regex::new(r"(?:^|\s)(?P<key>#\d+)").unwrap();
+        assert_eq!(
+            extract_keys(&github, "Fix crash #42 (see #7, not a#1)"),
+            vec!["#42", "#7"]
+        );
+    }
+
+    #[test]
+    fn append_links_skips_links_already_present() {
+        let keys = vec!["#42".to_string(), "#7".to_string()];
+        assert_eq!(
+            append_links(
+                Some("Details.\n\nCloses #7".to_string()),
+                &keys,
+                DEFAULT_ISSUE_LINK
+            ),
+            Some("Details.\n\nCloses #7\n\nCloses #42".to_string())
+        );
+        assert_eq!(
+            append_links(None, &keys, "Refs {key}"),
+            Some("Refs #42\nRefs #7".to_string())
+        );
+        assert_eq!(append_links(None, &[], DEFAULT_ISSUE_LINK), None);
+    }
+
+    #[test]
+    fn command_tracker_gets_issue_env() {
+        let dir = tempfile::tempdir().unwrap();
+        let out = dir.path().join("out");
+        let tracker = tracker(&IssueTrackerDefaults {
+            kind: Some(IssueTrackerKind::Command),
+            command: Some(format!(
+                "printf '%s|%s|%s' \"$GG_ISSUE_KEY\" \"$GG_ISSUE_PR\" \"$GG_ISSUE_STACK\" > {}",
+                out.display()
+            )),
+            ..Default::default()
+        })
+        .unwrap()
+        .unwrap();
+        let resolved = tracker
+            .resolve(&LandedIssue {
+                key: "PAY-12".to_string(),
+                pr_number: 41,
+                stack: "refunds".to_string(),
+            })
+            .unwrap();
+        assert!(resolved);
+        assert_eq!(std::fs::read_to_string(out).unwrap(), "PAY-12|41|refunds");
+    }
+}
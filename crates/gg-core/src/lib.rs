@@ -3,18 +3,40 @@
 //! This crate contains all the business logic for git-gud operations,
 //! separated from the CLI and MCP server entry points.
 
+pub mod api;
+pub mod branch_naming;
+pub mod changelog;
 pub mod commands;
+pub mod commit_messages;
 pub mod config;
+pub mod conflicts;
 pub mod context;
 pub mod error;
 pub mod gh;
+pub mod gh_api;
 pub mod git;
+pub mod gitea;
 pub mod glab;
+pub mod glab_api;
+pub mod hooks;
 pub mod immutability;
+pub mod issues;
 pub mod managed_body;
+pub mod notes;
+pub mod notify;
 pub mod operations;
 pub mod output;
+pub mod parallel;
+pub mod plain;
+pub mod protection;
 pub mod provider;
+pub mod registry;
+pub mod relative_time;
+pub mod replay;
+pub mod scope;
+pub mod signing;
 pub mod stack;
+pub mod stack_graph;
 pub mod stack_nav;
 pub mod template;
+pub mod webhook;
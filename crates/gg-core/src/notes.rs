@@ -0,0 +1,196 @@
+//! Per-entry annotations stored as git notes under `refs/notes/gg`.
+//!
+//! A note holds one annotation per line: `key=value`, or a bare `key` for
+//! flag-style tags such as `needs-qa`. Notes hang off the commit, so
+//! [`track_rewrites`] configures git to carry them across the amends and
+//! rebases gg runs.
+//!
+//! Stack descriptions live under `refs/notes/gg-stacks`, each on a blob
+//! naming its stack, so they survive rewrites and can be pushed like any
+//! other notes ref.
+
+use std::collections::BTreeMap;
+
+use git2::{ObjectType, Oid, Repository};
+
+use crate::error::Result;
+use crate::git;
+
+/// Notes ref holding gg annotations.
+pub const NOTES_REF: &str = "refs/notes/gg";
+
+/// Notes ref holding stack descriptions.
+pub const STACK_NOTES_REF: &str = "refs/notes/gg-stacks";
+
+/// Annotations of one entry, sorted by key. Flag-style tags have an empty
+/// value.
+pub type Annotations = BTreeMap<String, String>;
+
+/// Parse the text of a note.
+pub fn parse(text: &str) -> Annotations {
+    text.lines()
+        .map(str::trim)
+        .filter(|line| !line.is_empty())
+        .map(|line| match line.split_once('=') {
+            Some((key, value)) => (key.trim().to_string(), value.trim().to_string()),
+            None => (line.to_string(), String::new()),
+        })
+        .collect()
+}
+
+/// Render annotations as note text (or inline, joined by `separator`).
+pub fn render(annotations: &Annotations, separator: &str) -> String {
+    annotations
+        .iter()
+        .map(|(key, value)| {
+            if value.is_empty() {
+                key.clone()
+            } else {
+                format!("{}={}", key, value)
+            }
+        })
+        .collect::<Vec<_>>()
+        .join(separator)
+}
+
+/// Annotations attached to `oid` (empty when it has no note).
+pub fn read(repo: &Repository, oid: Oid) -> Annotations {
+    repo.find_note(Some(NOTES_REF), oid)
+        .ok()
+        .and_then(|note| note.message().ok().map(parse))
+        .unwrap_or_default()
+}
+
+/// Replace the annotations of `oid`. Empty annotations remove the note.
+pub fn write(repo: &Repository, oid: Oid, annotations: &Annotations) -> Result<()> {
+    if annotations.is_empty() {
+        if repo.find_note(Some(NOTES_REF), oid).is_ok() {
+            let sig = git::get_signature(repo)?;
+            repo.note_delete(oid, Some(NOTES_REF), &sig, &sig)?;
+        }
+        return Ok(());
+    }
+    let sig = git::get_signature(repo)?;
+    let text = format!("{}\n", render(annotations, "\n"));
+    repo.note(&sig, &sig, Some(NOTES_REF), oid, &text, true)?;
+    Ok(())
+}
+
+/// Content of the blob a stack's description note hangs off.
+fn stack_key(stack: &str) -> String {
+    format!("gg-stack {}\n", stack)
+}
+
+/// Description of `stack` kept in git notes, if any.
+pub fn read_stack_description(repo: &Repository, stack: &str) -> Option<String> {
+    let key = Oid::hash_object(ObjectType::Blob, stack_key(stack).as_bytes()).ok()?;
+    repo.find_note(Some(STACK_NOTES_REF), key)
+        .ok()
+        .and_then(|note| note.message().ok().map(|m| m.trim_end().to_string()))
+        .filter(|m| !m.is_empty())
+}
+
+/// Replace the description of `stack` in git notes. `None` removes it.
+pub fn write_stack_description(
+    repo: &Repository,
+    stack: &str,
+    description: Option<&str>,
+) -> Result<()> {
+    let key = repo.blob(stack_key(stack).as_bytes())?;
+    let sig = git::get_signature(repo)?;
+    match description {
+        Some(description) => {
+            let text = format!("{}\n", description.trim_end());
+            repo.note(&sig, &sig, Some(STACK_NOTES_REF), key, &text, true)?;
+        }
+        None => {
+            if repo.find_note(Some(STACK_NOTES_REF), key).is_ok() {
+                repo.note_delete(key, Some(STACK_NOTES_REF), &sig, &sig)?;
+            }
+        }
+    }
+    Ok(())
+}
+
+/// Make `git commit --amend` and `git rebase` copy gg notes to the
+/// rewritten commits (`notes.rewriteRef`). Keeps any existing values.
+pub fn track_rewrites(repo: &Repository) -> Result<()> {
+    let config = repo.config()?;
+    let mut tracked = false;
+    if let Ok(mut entries) = config.multivar("notes.rewriteRef", None) {
+        while let Some(entry) = entries.next() {
+            if entry?.value().ok() == Some(NOTES_REF) {
+                tracked = true;
+            }
+        }
+    }
+    if !tracked {
+        repo.config()?
+            .open_level(git2::ConfigLevel::Local)?
+            .set_multivar("notes.rewriteRef", "^$", NOTES_REF)?;
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parse_and_render_roundtrip() {
+        let annotations = parse("risk=high\n\nneeds-qa\n owner = payments \n");
+        assert_eq!(annotations.get("risk").map(String::as_str), Some("high"));
+        assert_eq!(annotations.get("needs-qa").map(String::as_str), Some(""));
+        assert_eq!(
+            annotations.get("owner").map(String::as_str),
+            Some("payments")
+        );
+        assert_eq!(
+            render(&annotations, " "),
+            "needs-qa owner=payments risk=high"
+        );
+    }
+
+    #[test]
+    fn write_read_and_remove_note() {
+        let dir = tempfile::TempDir::new().unwrap();
+        let repo = Repository::init(dir.path()).unwrap();
+        let mut cfg = repo.config().unwrap();
+        cfg.set_str("user.name", "Test").unwrap();
+        cfg.set_str("user.email", "test@example.com").unwrap();
+        let sig = git2::Signature::now("Test", "test@example.com").unwrap();
+        let tree_id = repo.index().unwrap().write_tree().unwrap();
+        let tree = repo.find_tree(tree_id).unwrap();
+        let oid = repo
+            .commit(Some("HEAD"), &sig, &sig, "Initial", &tree, &[])
+            .unwrap();
+
+        let mut annotations = Annotations::new();
+        annotations.insert("risk".to_string(), "high".to_string());
+        write(&repo, oid, &annotations).unwrap();
+        assert_eq!(read(&repo, oid), annotations);
+
+        write(&repo, oid, &Annotations::new()).unwrap();
+        assert!(read(&repo, oid).is_empty());
+    }
+
+    #[test]
+    fn write_read_and_remove_stack_description() {
+        let dir = tempfile::TempDir::new().unwrap();
+        let repo = Repository::init(dir.path()).unwrap();
+        let mut cfg = repo.config().unwrap();
+        cfg.set_str("user.name", "Test").unwrap();
+        cfg.set_str("user.email", "test@example.com").unwrap();
+
+        assert_eq!(read_stack_description(&repo, "auth"), None);
+        write_stack_description(&repo, "auth", Some("Login flow\n\nSplit from #12\n")).unwrap();
+        assert_eq!(
+            read_stack_description(&repo, "auth").as_deref(),
+            Some("Login flow\n\nSplit from #12")
+        );
+        assert_eq!(read_stack_description(&repo, "billing"), None);
+
+        write_stack_description(&repo, "auth", None).unwrap();
+        assert_eq!(read_stack_description(&repo, "auth"), None);
+    }
+}
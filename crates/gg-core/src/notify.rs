@@ -0,0 +1,150 @@
+//! Completion notifications for long-running commands.
+//!
+//! `gg land --wait` and `gg watch` can run unattended for half an hour or
+//! more. When `defaults.notify_command` is set, they report how they ended:
+//!
+//! - an `http://` / `https://` value receives a JSON POST
+//!   (`{"event", "status", "title", "message", "text"}`; `text` makes it
+//!   usable as a Slack/Mattermost incoming webhook as-is)
+//! - anything else runs through `sh -c` with the title and message appended
+//!   as arguments (so `notify-send` works unchanged) and exposed as
+//!   `GG_NOTIFY_TITLE` / `GG_NOTIFY_MESSAGE`, next to `GG_NOTIFY_EVENT` and
+//!   `GG_NOTIFY_STATUS` (`success` or `failure`)
+//!
+//! A failing notification only prints a warning; it never changes the
+//! outcome of the command that triggered it.
+
+use std::process::{Command, Stdio};
+
+use console::style;
+use serde_json::json;
+
+use crate::api::{self, HttpError};
+use crate::config::Config;
+use crate::plain;
+
+/// How a long-running command ended.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Notification {
+    /// Command that finished, e.g. `land` or `watch`
+    pub event: &'static str,
+    pub success: bool,
+    pub title: String,
+    pub message: String,
+}
+
+impl Notification {
+    fn status(&self) -> &'static str {
+        if self.success {
+            "success"
+        } else {
+            "failure"
+        }
+    }
+}
+
+/// Deliver `notification` through `defaults.notify_command`, if configured.
+pub fn send(config: &Config, notification: &Notification) {
+    let Some(target) = config.get_notify_command() else {
+        return;
+    };
+    let result = if is_webhook(target) {
+        post(target, notification)
+    } else {
+        run_command(target, notification)
+    };
+    if let Err(e) = result {
+        eprintln!(
+            "{} Notification failed: {}",
+            style(plain::warn()).yellow(),
+            e
+        );
+    }
+}
+
+fn is_webhook(target: &str) -> bool {
+    target.starts_with("http://") || target.starts_with("https://")
+}
+
+fn post(url: &str, notification: &Notification) -> Result<(), String> {
+    let body = json!({
+        "event": notification.event,
+        "status": notification.status(),
+        "title": notification.title,
+        "message": notification.message,
+        "text": format!("{}: {}", notification.title, notification.message),
+    });
+    match api::request("POST", url, &[], Some(body)) {
+        // Chat webhooks commonly answer with a plain-text "ok"
+        Ok(_) | Err(HttpError::Parse(_)) => Ok(()),
+        Err(HttpError::Status(code, message)) => Err(format!("HTTP {}: {}", code, message)),
+        Err(HttpError::Transport(message)) => Err(message),
+    }
+}
+
+fn run_command(command: &str, notification: &Notification) -> Result<(), String> {
+    let output = Command::new("sh")
+        .arg("-c")
+        .arg(format!("{} \"$@\"", command))
+        .arg("gg")
+        .arg(&notification.title)
+        .arg(&notification.message)
+        .env("GG_NOTIFY_EVENT", notification.event)
+        .env("GG_NOTIFY_STATUS", notification.status())
+        .env("GG_NOTIFY_TITLE", &notification.title)
+        .env("GG_NOTIFY_MESSAGE", &notification.message)
+        .stdin(Stdio::null())
+        .output()
+        .map_err(|e| e.to_string())?;
+    if output.status.success() {
+        Ok(())
+    } else {
+        let stderr = String::from_utf8_lossy(&output.stderr).trim().to_string();
+        Err(if stderr.is_empty() {
+            format!("`{}` exited with {}", command, output.status)
+        } else {
+            stderr
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn notification(success: bool) -> Notification {
+        Notification {
+            event: "land",
+            success,
+            title: "gg land".to_string(),
+            message: "Landed 2 PR(s)".to_string(),
+        }
+    }
+
+    #[test]
+    fn webhook_targets_are_urls() {
+        assert!(is_webhook("https://hooks.example.com/abc"));
+        assert!(is_webhook("http://localhost:9000"));
+        assert!(!is_webhook("notify-send"));
+    }
+
+    #[test]
+    fn command_receives_title_message_and_env() {
+        let dir = tempfile::tempdir().unwrap();
+        let out = dir.path().join("out");
+        let command = format!(
+            "sh -c 'printf \"%s|%s|$GG_NOTIFY_EVENT|$GG_NOTIFY_STATUS\" \"$0\" \"$1\" > {}'",
+            out.display()
+        );
+        run_command(&command, &notification(false)).unwrap();
+        assert_eq!(
+            std::fs::read_to_string(out).unwrap(),
+            "gg land|Landed 2 PR(s)|land|failure"
+        );
+    }
+
+    #[test]
+    fn failing_command_is_an_error() {
+        assert!(run_command("false", &notification(true)).is_err());
+    }
+}
@@ -44,7 +44,9 @@ pub(crate) const PENDING_STALENESS_MS: u64 = 30_000;
 /// Reserved top-level branch names that never belong to a user namespace,
 /// excluded from `SnapshotScope::AllUserBranches`.
 const TRUNK_EXCLUSIONS: &[&str] = &["main", "master", "trunk"];
-const INTERRUPTED_REBASE_MARKER: &str = "interrupted-rebase-operation.json";
+const OPERATION_STATE_FILE: &str = "operation-state.json";
+/// Marker older gg versions wrote instead of [`OPERATION_STATE_FILE`]
+const LEGACY_REBASE_MARKER: &str = "interrupted-rebase-operation.json";
 
 // ---------------------------------------------------------------------------
 // Record schema
@@ -70,6 +72,46 @@ pub enum OperationKind {
     Restack,
     Run,
     Undo,
+    Verify,
+    Doctor,
+    Retarget,
+    Migrate,
+    SquashAll,
+    Fold,
+    Edit,
+    Redo,
+    Move,
+    Import,
+    Snapshot,
+}
+
+impl OperationKind {
+    /// Whether the operation rewrites the commits of the checked-out branch.
+    pub fn rewrites_history(self) -> bool {
+        matches!(
+            self,
+            OperationKind::Drop
+                | OperationKind::Squash
+                | OperationKind::Split
+                | OperationKind::Unstack
+                | OperationKind::Rebase
+                | OperationKind::Reorder
+                | OperationKind::Absorb
+                | OperationKind::Reconcile
+                | OperationKind::Restack
+                | OperationKind::Run
+                | OperationKind::SquashAll
+                | OperationKind::Fold
+                | OperationKind::Edit
+                | OperationKind::Move
+        )
+    }
+
+    /// Whether finishing the operation counts as activity on its stack for
+    /// `gg clean --stale`. Cleaning a stack or moving around in it does not.
+    pub fn touches_stack(self) -> bool {
+        !matches!(self, OperationKind::Clean | OperationKind::Nav)
+    }
 }
 
 /// Lifecycle status of an operation record.
@@ -164,13 +206,112 @@ pub struct OperationRecord {
     /// use (e.g. partial-rebase state).
     #[serde(default, skip_serializing_if = "Option::is_none")]
     pub pending_plan: Option<serde_json::Value>,
+    /// True iff the operation stopped on a rebase conflict at least once.
+    /// Set by [`remember_interrupted_rebase_operation`]; read by `gg insights`.
+    #[serde(default, skip_serializing_if = "std::ops::Not::not")]
+    pub conflicted: bool,
 }
 
-#[derive(Debug, Serialize, Deserialize)]
-struct InterruptedRebaseMarker {
-    operation_id: String,
+/// Persisted state of a recorded operation that stopped on a rebase
+/// conflict, stored per worktree at `<git-dir>/gg/operation-state.json`.
+///
+/// `gg continue` runs the remaining steps once the rebase completes and
+/// `gg abort` rolls the operation's refs back to `refs_before`, so every
+/// interrupted operation ends on a well-defined branch state.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct OperationState {
+    pub operation_id: String,
+    pub kind: OperationKind,
+    /// Branch HEAD was attached to when the operation started, if any
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub original_branch: Option<String>,
+    /// Commit HEAD pointed at when the operation started
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub original_head: Option<String>,
+    /// The git rebase the operation is waiting on (branch, original tip and
+    /// target OIDs)
     #[serde(default, skip_serializing_if = "Option::is_none")]
     rebase_state: Option<RebaseStateMarker>,
+    /// Steps left to run once the rebase completes, in order
+    #[serde(default)]
+    pub remaining: Vec<PendingStep>,
+}
+
+/// A step `gg continue` still has to run after the rebase completes.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum PendingStep {
+    /// Rewrite the GG-ID/GG-Parent trailers of the rewritten stack
+    NormalizeMetadata,
+    /// Delete the entry branches of dropped commits
+    DeleteDroppedBranches,
+    /// Check out the entry `gg split`/`gg squash` was editing
+    RestoreEntry,
+    /// Forget the navigation position the rebase started from
+    ClearNavContext,
+    /// Re-attach HEAD to the branch the operation started on
+    ReturnToBranch,
+}
+
+impl OperationState {
+    fn new(record: &OperationRecord, rebase_state: Option<RebaseStateMarker>) -> Self {
+        let head = record.refs_before.iter().find(|snap| snap.is_head);
+        let original_branch = head
+            .and_then(|snap| snap.head_symbolic.as_deref())
+            .and_then(|name| name.strip_prefix("refs/heads/"))
+            .map(str::to_string);
+        let plan = |key: &str| {
+            record
+                .pending_plan
+                .as_ref()
+                .and_then(|plan| plan.get(key))
+                .is_some()
+        };
+
+        let restores_entry = (record.kind == OperationKind::Split && plan("split"))
+            || (record.kind == OperationKind::Squash && plan("squash"));
+
+        let mut remaining = Vec::new();
+        // Back on the branch first, so the steps below load the stack from it
+        if original_branch.is_some() && !restores_entry {
+            remaining.push(PendingStep::ReturnToBranch);
+        }
+        if matches!(
+            record.kind,
+            OperationKind::Drop
+                | OperationKind::Reorder
+                | OperationKind::Restack
+                | OperationKind::Split
+        ) {
+            remaining.push(PendingStep::NormalizeMetadata);
+        }
+        if record.kind == OperationKind::Drop && plan("drop") {
+            remaining.push(PendingStep::DeleteDroppedBranches);
+        }
+        if restores_entry {
+            remaining.push(PendingStep::RestoreEntry);
+        }
+        if record.kind == OperationKind::Nav {
+            remaining.push(PendingStep::ClearNavContext);
+        }
+
+        Self {
+            operation_id: record.id.clone(),
+            kind: record.kind,
+            original_branch,
+            original_head: head.and_then(|snap| snap.target.clone()),
+            rebase_state,
+            remaining,
+        }
+    }
+}
+
+/// Contents of [`LEGACY_REBASE_MARKER`].
+#[derive(Debug, Deserialize)]
+struct LegacyRebaseMarker {
+    operation_id: String,
+    #[serde(default)]
+    rebase_state: Option<RebaseStateMarker>,
 }
 
 #[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
@@ -386,8 +527,40 @@ impl OperationStore {
 // Cross-invocation finalization for interrupted rebases
 // ---------------------------------------------------------------------------
 
-fn interrupted_rebase_marker_path(repo: &Repository) -> PathBuf {
-    repo.path().join("gg").join(INTERRUPTED_REBASE_MARKER)
+fn operation_state_path(repo: &Repository) -> PathBuf {
+    repo.path().join("gg").join(OPERATION_STATE_FILE)
+}
+
+fn legacy_marker_path(repo: &Repository) -> PathBuf {
+    repo.path().join("gg").join(LEGACY_REBASE_MARKER)
+}
+
+/// The state of a conflict an older gg stopped on, rebuilt from its marker
+/// and the operation record and moved to [`OPERATION_STATE_FILE`].
+fn migrate_legacy_marker(repo: &Repository) -> Result<Option<OperationState>> {
+    let path = legacy_marker_path(repo);
+    let bytes = match fs::read(&path) {
+        Ok(bytes) => bytes,
+        Err(e) if e.kind() == std::io::ErrorKind::NotFound => return Ok(None),
+        Err(e) => return Err(GgError::Io(e)),
+    };
+    let marker: Option<LegacyRebaseMarker> = serde_json::from_slice(&bytes).ok();
+    let record = marker
+        .as_ref()
+        .filter(|marker| is_valid_operation_id(&marker.operation_id))
+        .and_then(|marker| {
+            OperationStore::new(&crate::git::gg_dir(repo))
+                .load(&marker.operation_id)
+                .ok()
+        });
+    let state = marker
+        .zip(record)
+        .map(|(marker, record)| OperationState::new(&record, marker.rebase_state));
+    if let Some(state) = &state {
+        save_operation_state(repo, state)?;
+    }
+    fs::remove_file(&path)?;
+    Ok(state)
 }
 
 fn current_rebase_state(repo: &Repository) -> Option<RebaseStateMarker> {
@@ -420,23 +593,37 @@ fn read_trimmed_file(path: &Path) -> Option<String> {
 /// Remember which operation owns the current Git rebase state.
 ///
 /// Commands call this when a recorded operation stops on a rebase conflict.
-/// A later `gg continue` invocation uses the marker to finalize the original
-/// operation record after `git rebase --continue` completes.
+/// It writes the [`OperationState`] a later `gg continue` or `gg abort`
+/// consumes to finish or roll back the original operation.
 pub fn remember_interrupted_rebase_operation(repo: &Repository, operation_id: &str) -> Result<()> {
     if !is_valid_operation_id(operation_id) {
         return Err(GgError::OperationRecordNotFound(operation_id.to_string()));
     }
 
-    let path = interrupted_rebase_marker_path(repo);
+    let store = OperationStore::new(&crate::git::gg_dir(repo));
+    let mut record = store.load(operation_id)?;
+    save_operation_state(
+        repo,
+        &OperationState::new(&record, current_rebase_state(repo)),
+    )?;
+
+    // Flag the record itself so the conflict survives after `gg continue`
+    // clears the state. Best-effort: the state file is what `continue` needs.
+    if !record.conflicted {
+        record.conflicted = true;
+        let _ = store.save(&record);
+    }
+    Ok(())
+}
+
+/// Persist `state`, e.g. after `gg continue` completed one of its steps.
+pub fn save_operation_state(repo: &Repository, state: &OperationState) -> Result<()> {
+    let path = operation_state_path(repo);
     if let Some(parent) = path.parent() {
         fs::create_dir_all(parent)?;
     }
     let tmp = path.with_extension("json.tmp");
-    let marker = InterruptedRebaseMarker {
-        operation_id: operation_id.to_string(),
-        rebase_state: current_rebase_state(repo),
-    };
-    let json = serde_json::to_vec_pretty(&marker)?;
+    let json = serde_json::to_vec_pretty(state)?;
     {
         let mut f = fs::File::create(&tmp)?;
         f.write_all(&json)?;
@@ -446,32 +633,46 @@ pub fn remember_interrupted_rebase_operation(repo: &Repository, operation_id: &s
     Ok(())
 }
 
-/// Load the operation record associated with the current interrupted rebase.
-pub fn interrupted_rebase_operation(repo: &Repository) -> Result<Option<OperationRecord>> {
-    let path = interrupted_rebase_marker_path(repo);
-    if !path.exists() {
-        return Ok(None);
-    }
-
-    let bytes = fs::read(&path)?;
-    let marker: InterruptedRebaseMarker = match serde_json::from_slice(&bytes) {
-        Ok(marker) => marker,
-        Err(_) => {
-            clear_interrupted_rebase_operation(repo)?;
-            return Ok(None);
+/// Load the state and record of the operation waiting on `gg continue`.
+///
+/// The state is still valid while its rebase is in progress, and after the
+/// rebase was completed with plain `git rebase --continue` (its branch moved
+/// off the original tip). A rebase aborted or replaced outside gg discards
+/// the state.
+pub fn interrupted_operation(
+    repo: &Repository,
+) -> Result<Option<(OperationState, OperationRecord)>> {
+    let path = operation_state_path(repo);
+    let state: OperationState = if path.exists() {
+        let bytes = fs::read(&path)?;
+        match serde_json::from_slice(&bytes) {
+            Ok(state) => state,
+            Err(_) => {
+                clear_interrupted_rebase_operation(repo)?;
+                return Ok(None);
+            }
+        }
+    } else {
+        match migrate_legacy_marker(repo)? {
+            Some(state) => state,
+            None => return Ok(None),
         }
     };
-    let Some(expected) = &marker.rebase_state else {
+    let Some(expected) = &state.rebase_state else {
         clear_interrupted_rebase_operation(repo)?;
         return Ok(None);
     };
-    if current_rebase_state(repo).as_ref() != Some(expected) {
+    let still_valid = match current_rebase_state(repo) {
+        Some(current) => &current == expected,
+        None => rebase_completed(repo, expected),
+    };
+    if !still_valid {
         clear_interrupted_rebase_operation(repo)?;
         return Ok(None);
     }
 
     let store = OperationStore::new(&crate::git::gg_dir(repo));
-    let record = match store.load(&marker.operation_id) {
+    let record = match store.load(&state.operation_id) {
         Ok(record) => record,
         Err(_) => {
             clear_interrupted_rebase_operation(repo)?;
@@ -488,18 +689,85 @@ pub fn interrupted_rebase_operation(repo: &Repository) -> Result<Option<Operatio
         return Ok(None);
     }
 
-    Ok(Some(record))
+    Ok(Some((state, record)))
+}
+
+/// Whether the rebase described by `rebase_state` finished: its branch no
+/// longer points at the tip it started from.
+fn rebase_completed(repo: &Repository, rebase_state: &RebaseStateMarker) -> bool {
+    let (Some(head_name), Some(orig_head)) = (&rebase_state.head_name, &rebase_state.orig_head)
+    else {
+        return false;
+    };
+    repo.find_reference(head_name)
+        .ok()
+        .and_then(|r| r.target())
+        .is_some_and(|oid| oid.to_string() != *orig_head)
 }
 
-/// Remove the interrupted-rebase marker.
+/// Load the operation record associated with the current interrupted rebase.
+pub fn interrupted_rebase_operation(repo: &Repository) -> Result<Option<OperationRecord>> {
+    Ok(interrupted_operation(repo)?.map(|(_, record)| record))
+}
+
+/// Remove the interrupted operation's state file.
 pub fn clear_interrupted_rebase_operation(repo: &Repository) -> Result<()> {
-    match fs::remove_file(interrupted_rebase_marker_path(repo)) {
+    match fs::remove_file(operation_state_path(repo)) {
         Ok(()) => Ok(()),
         Err(e) if e.kind() == std::io::ErrorKind::NotFound => Ok(()),
         Err(e) => Err(GgError::Io(e)),
     }
 }
 
+/// Roll an interrupted operation back for `gg abort`: every ref the
+/// operation moved returns to its `refs_before` target and HEAD to where it
+/// was, checking out that commit. Run after `git rebase --abort`. Returns
+/// the names of the refs that were restored.
+pub fn roll_back_interrupted_operation(
+    repo: &Repository,
+    record: &OperationRecord,
+) -> Result<Vec<String>> {
+    let mut restored = Vec::new();
+    for snap in record.refs_before.iter().filter(|snap| !snap.is_head) {
+        let current = match repo.find_reference(&snap.name) {
+            Ok(r) => r.target().map(|o| o.to_string()),
+            Err(e) if e.code() == git2::ErrorCode::NotFound => None,
+            Err(e) => return Err(GgError::Git(e)),
+        };
+        if current == snap.target {
+            continue;
+        }
+        match &snap.target {
+            Some(oid_str) => {
+                let oid = git2::Oid::from_str(oid_str)?;
+                repo.reference(&snap.name, oid, true, "gg abort")?;
+            }
+            None => {
+                if let Ok(mut r) = repo.find_reference(&snap.name) {
+                    r.delete()?;
+                }
+            }
+        }
+        restored.push(snap.name.clone());
+    }
+
+    if let Some(head) = record.refs_before.iter().find(|snap| snap.is_head) {
+        let target = match &head.head_symbolic {
+            Some(sym) => repo.find_reference(sym).ok().and_then(|r| r.target()),
+            None => head
+                .target
+                .as_deref()
+                .and_then(|oid| git2::Oid::from_str(oid).ok()),
+        };
+        if let Some(target) = target {
+            let commit = repo.find_commit(target)?;
+            repo.checkout_tree(commit.as_object(), None)?;
+        }
+        apply_head_snapshot(repo, head)?;
+    }
+    Ok(restored)
+}
+
 /// Finalize an existing Pending/Interrupted operation record by id.
 ///
 /// This is used by `gg continue`: the operation guard that wrote the record
@@ -708,6 +976,17 @@ impl OperationGuard {
         let _ = self.store.save(&self.record);
     }
 
+    /// Attach the stack name once it is known. Commands that acquire the
+    /// lock before loading the stack (sync, land) call this afterwards so
+    /// the record is still attributable to a stack.
+    pub fn set_stack_name(&mut self, stack_name: &str) {
+        if self.record.stack_name.as_deref() == Some(stack_name) {
+            return;
+        }
+        self.record.stack_name = Some(stack_name.to_string());
+        let _ = self.store.save(&self.record);
+    }
+
     /// Persist command-specific state needed to finish a conflict-resumed
     /// operation from a later `gg continue` invocation.
     pub fn set_pending_plan(&mut self, pending_plan: serde_json::Value) {
@@ -758,10 +1037,50 @@ impl OperationGuard {
         touched_remote: bool,
     ) -> Result<()> {
         let refs_after = snapshot_refs(repo, config, scope)?;
-        self.finalize(refs_after, remote_effects, touched_remote)
+        let touched = (self.record.kind.touches_stack())
+            .then(|| self.record.stack_name.clone())
+            .flatten();
+        self.finalize(refs_after, remote_effects, touched_remote)?;
+        if let Some(stack_name) = touched {
+            touch_stack(repo, config, &stack_name);
+        }
+        Ok(())
     }
 }
 
+/// Stamp `last_touched_ms` on the stack's config, for `gg clean --stale`
+/// and `gg ls --all`.
+///
+/// Only done when the repo already has a local config (creating one would
+/// shadow the global defaults) and the stack is configured or its branch
+/// exists (so a cleaned stack is not brought back). Errors are swallowed:
+/// the command that just succeeded must not fail on bookkeeping.
+fn touch_stack(repo: &Repository, config: &Config, stack_name: &str) {
+    let git_dir = repo.commondir();
+    if !Config::config_path(git_dir).exists() {
+        return;
+    }
+    let Ok(mut local) = Config::load(git_dir) else {
+        return;
+    };
+    let branch_exists = config
+        .defaults
+        .branch_username
+        .as_deref()
+        .is_some_and(|user| {
+            repo.find_branch(
+                &crate::git::format_stack_branch(user, stack_name),
+                BranchType::Local,
+            )
+            .is_ok()
+        });
+    if !branch_exists && local.get_stack(stack_name).is_none() {
+        return;
+    }
+    local.get_or_create_stack(stack_name).last_touched_ms = Some(now_ms());
+    let _ = local.save(git_dir);
+}
+
 impl Drop for OperationGuard {
     fn drop(&mut self) {
         // Intentional: do nothing. See doc comment above and design §2.2.
@@ -958,6 +1277,7 @@ pub(crate) mod tests {
             touched_remote: false,
             undoes: None,
             pending_plan: None,
+            conflicted: false,
         }
     }
 
@@ -990,6 +1310,7 @@ pub(crate) mod tests {
             touched_remote: false,
             undoes: None,
             pending_plan: None,
+            conflicted: false,
         };
         let json = serde_json::to_string(&record).unwrap();
         let back: OperationRecord = serde_json::from_str(&json).unwrap();
@@ -1202,6 +1523,7 @@ pub(crate) mod tests {
             touched_remote: false,
             undoes: None,
             pending_plan: None,
+            conflicted: false,
         };
         store.save(&rec).unwrap();
         let guard = OperationGuard {
@@ -1234,6 +1556,7 @@ pub(crate) mod tests {
                 touched_remote: false,
                 undoes: None,
                 pending_plan: None,
+                conflicted: false,
             };
             store.save(&rec).unwrap();
             let _guard = OperationGuard {
@@ -1271,7 +1594,21 @@ pub(crate) mod tests {
     }
 
     #[test]
-    fn interrupted_rebase_marker_is_scoped_to_worktree_git_dir() {
+    fn remember_interrupted_rebase_flags_record_as_conflicted() {
+        let dir = tempfile::tempdir().unwrap();
+        let repo = Repository::init(dir.path()).unwrap();
+        let store = OperationStore::new(&crate::git::gg_dir(&repo));
+        let mut record = make_record(OperationKind::Rebase, 10);
+        record.status = OperationStatus::Pending;
+        store.save(&record).unwrap();
+
+        remember_interrupted_rebase_operation(&repo, &record.id).unwrap();
+
+        assert!(store.load(&record.id).unwrap().conflicted);
+    }
+
+    #[test]
+    fn operation_state_is_scoped_to_worktree_git_dir() {
         let parent = tempfile::tempdir().unwrap();
         let repo_path = parent.path().join("repo");
         let worktree_path = parent.path().join("linked");
@@ -1319,8 +1656,8 @@ pub(crate) mod tests {
         remember_interrupted_rebase_operation(&repo, &main_record.id).unwrap();
         remember_interrupted_rebase_operation(&worktree_repo, &worktree_record.id).unwrap();
 
-        let main_marker = interrupted_rebase_marker_path(&repo);
-        let worktree_marker = interrupted_rebase_marker_path(&worktree_repo);
+        let main_marker = operation_state_path(&repo);
+        let worktree_marker = operation_state_path(&worktree_repo);
         assert_ne!(main_marker, worktree_marker);
         assert!(main_marker.exists());
         assert!(worktree_marker.exists());
@@ -1333,6 +1670,168 @@ pub(crate) mod tests {
         assert_eq!(loaded_worktree.id, worktree_record.id);
     }
 
+    #[test]
+    fn operation_state_plans_remaining_steps() {
+        let mut drop = make_record(OperationKind::Drop, 13);
+        drop.refs_before = vec![RefSnapshot {
+            name: "HEAD".into(),
+            target: Some("a".repeat(40)),
+            is_head: true,
+            head_symbolic: Some("refs/heads/nacho/feat".into()),
+        }];
+        drop.pending_plan = Some(serde_json::json!({ "drop": { "entry_branches": [] } }));
+        let state = OperationState::new(&drop, None);
+        assert_eq!(state.original_branch.as_deref(), Some("nacho/feat"));
+        assert_eq!(state.original_head, Some("a".repeat(40)));
+        assert_eq!(
+            state.remaining,
+            vec![
+                PendingStep::ReturnToBranch,
+                PendingStep::NormalizeMetadata,
+                PendingStep::DeleteDroppedBranches
+            ]
+        );
+
+        // Split/squash put HEAD back on the edited entry instead
+        let mut squash = drop.clone();
+        squash.kind = OperationKind::Squash;
+        squash.pending_plan = Some(serde_json::json!({ "squash": {} }));
+        assert_eq!(
+            OperationState::new(&squash, None).remaining,
+            vec![PendingStep::RestoreEntry]
+        );
+
+        let mut nav = make_record(OperationKind::Nav, 14);
+        nav.refs_before = vec![RefSnapshot {
+            name: "HEAD".into(),
+            target: Some("b".repeat(40)),
+            is_head: true,
+            head_symbolic: None,
+        }];
+        let state = OperationState::new(&nav, None);
+        assert_eq!(state.original_branch, None);
+        assert_eq!(state.remaining, vec![PendingStep::ClearNavContext]);
+    }
+
+    #[test]
+    fn interrupted_operation_outlives_rebase_finished_by_hand() {
+        let (_dir, repo, c1, c2) = crate::operations::undo_tests::setup_repo_with_two_commits();
+        let c1_commit = repo.find_commit(c1).unwrap();
+        repo.branch("nacho/feat", &c1_commit, true).unwrap();
+        let store = OperationStore::new(&crate::git::gg_dir(&repo));
+        let mut record = make_record(OperationKind::Rebase, 15);
+        record.status = OperationStatus::Pending;
+        store.save(&record).unwrap();
+
+        write_rebase_state(&repo, "refs/heads/nacho/feat", &c1.to_string(), "cccccccc");
+        remember_interrupted_rebase_operation(&repo, &record.id).unwrap();
+        fs::remove_dir_all(repo.path().join("rebase-merge")).unwrap();
+
+        // Branch still at its original tip: the rebase was aborted
+        assert!(interrupted_operation(&repo).unwrap().is_none());
+        assert!(!operation_state_path(&repo).exists());
+
+        write_rebase_state(&repo, "refs/heads/nacho/feat", &c1.to_string(), "cccccccc");
+        remember_interrupted_rebase_operation(&repo, &record.id).unwrap();
+        fs::remove_dir_all(repo.path().join("rebase-merge")).unwrap();
+        repo.reference("refs/heads/nacho/feat", c2, true, "test")
+            .unwrap();
+
+        let (state, loaded) = interrupted_operation(&repo).unwrap().unwrap();
+        assert_eq!(state.operation_id, record.id);
+        assert_eq!(loaded.id, record.id);
+    }
+
+    #[test]
+    fn interrupted_operation_reads_legacy_marker() {
+        let dir = tempfile::tempdir().unwrap();
+        let repo = Repository::init(dir.path()).unwrap();
+        let store = OperationStore::new(&crate::git::gg_dir(&repo));
+        let mut record = make_record(OperationKind::Drop, 16);
+        record.status = OperationStatus::Interrupted;
+        record.refs_before = vec![RefSnapshot {
+            name: "HEAD".into(),
+            target: Some("a".repeat(40)),
+            is_head: true,
+            head_symbolic: Some("refs/heads/nacho/feat".into()),
+        }];
+        store.save(&record).unwrap();
+
+        // What an older gg left behind when the drop stopped on a conflict
+        write_rebase_state(&repo, "refs/heads/nacho/feat", "aaaaaaaa", "bbbbbbbb");
+        fs::write(
+            legacy_marker_path(&repo),
+            serde_json::json!({
+                "operation_id": record.id,
+                "rebase_state": {
+                    "head_name": "refs/heads/nacho/feat",
+                    "orig_head": "aaaaaaaa",
+                    "onto": "bbbbbbbb"
+                }
+            })
+            .to_string(),
+        )
+        .unwrap();
+
+        let (state, loaded) = interrupted_operation(&repo).unwrap().unwrap();
+        assert_eq!(loaded.id, record.id);
+        assert_eq!(state.kind, OperationKind::Drop);
+        assert_eq!(state.original_branch.as_deref(), Some("nacho/feat"));
+        assert!(state.remaining.contains(&PendingStep::ReturnToBranch));
+        assert!(!legacy_marker_path(&repo).exists());
+        assert!(operation_state_path(&repo).exists());
+
+        // A marker naming no known operation is discarded
+        clear_interrupted_rebase_operation(&repo).unwrap();
+        fs::write(legacy_marker_path(&repo), r#"{"operation_id":"gone"}"#).unwrap();
+        assert!(interrupted_operation(&repo).unwrap().is_none());
+        assert!(!legacy_marker_path(&repo).exists());
+    }
+
+    #[test]
+    fn roll_back_interrupted_operation_restores_refs_and_head() {
+        let (_dir, repo, c1, c2) = crate::operations::undo_tests::setup_repo_with_two_commits();
+        let c1_commit = repo.find_commit(c1).unwrap();
+        let c2_commit = repo.find_commit(c2).unwrap();
+        repo.branch("nacho/feat", &c1_commit, true).unwrap();
+        repo.branch("nacho/other", &c2_commit, true).unwrap();
+
+        let mut record = make_record(OperationKind::Move, 16);
+        record.refs_before = vec![
+            RefSnapshot {
+                name: "HEAD".into(),
+                target: Some(c1.to_string()),
+                is_head: true,
+                head_symbolic: Some("refs/heads/nacho/feat".into()),
+            },
+            RefSnapshot {
+                name: "refs/heads/nacho/feat".into(),
+                target: Some(c1.to_string()),
+                is_head: false,
+                head_symbolic: None,
+            },
+            RefSnapshot {
+                name: "refs/heads/nacho/other".into(),
+                target: Some(c1.to_string()),
+                is_head: false,
+                head_symbolic: None,
+            },
+        ];
+        // The operation left HEAD detached somewhere else
+        repo.set_head_detached(c2).unwrap();
+
+        let restored = roll_back_interrupted_operation(&repo, &record).unwrap();
+
+        assert_eq!(restored, vec!["refs/heads/nacho/other".to_string()]);
+        let other = repo.find_reference("refs/heads/nacho/other").unwrap();
+        assert_eq!(other.target(), Some(c1));
+        assert!(!repo.head_detached().unwrap());
+        assert_eq!(
+            repo.head().unwrap().name().unwrap(),
+            "refs/heads/nacho/feat"
+        );
+    }
+
     fn write_rebase_state(repo: &Repository, head_name: &str, orig_head: &str, onto: &str) {
         let rebase_dir = repo.path().join("rebase-merge");
         fs::create_dir_all(&rebase_dir).unwrap();
@@ -1436,7 +1935,8 @@ mod undo_tests {
     use crate::config::Config;
     use git2::Repository;
 
-    fn setup_repo_with_two_commits() -> (tempfile::TempDir, Repository, git2::Oid, git2::Oid) {
+    pub(crate) fn setup_repo_with_two_commits(
+    ) -> (tempfile::TempDir, Repository, git2::Oid, git2::Oid) {
         let dir = tempfile::tempdir().unwrap();
         let repo = Repository::init(dir.path()).unwrap();
         let sig = git2::Signature::now("t", "t@t").unwrap();
@@ -1494,6 +1994,7 @@ mod undo_tests {
             touched_remote: false,
             undoes: None,
             pending_plan: None,
+            conflicted: false,
         };
         store.save(&rec).unwrap();
 
@@ -1547,6 +2048,7 @@ mod undo_tests {
             touched_remote: false,
             undoes: None,
             pending_plan: None,
+            conflicted: false,
         };
         store.save(&rec).unwrap();
 
@@ -1586,6 +2088,7 @@ mod undo_tests {
             touched_remote: true,
             undoes: None,
             pending_plan: None,
+            conflicted: false,
         };
         store.save(&rec).unwrap();
         let out = run_undo(
@@ -1,9 +1,13 @@
 //! Structured output helpers.
 
+use std::collections::BTreeMap;
 use std::io::{self, Write};
 
 use serde::{Serialize, Serializer};
 
+use crate::config::ChangelogMode;
+use crate::error::GgError;
+
 pub const OUTPUT_VERSION: u32 = 1;
 const STREAMING_ABORT_EXIT_CODE: i32 = 1;
 
@@ -73,12 +77,15 @@ fn handle_streaming_write_error(error: io::Error, code: i32) -> ! {
 pub struct ErrorJson<'a> {
     pub version: u32,
     pub error: &'a str,
+    /// Stable code of the failure (see [`GgError::code`])
+    pub code: &'a str,
 }
 
-pub fn print_json_error(message: &str) {
+pub fn print_json_error(error: &GgError) {
     print_json(&ErrorJson {
         version: OUTPUT_VERSION,
-        error: message,
+        error: &error.to_string(),
+        code: error.code(),
     });
 }
 
@@ -98,6 +105,11 @@ pub struct StackJson {
     pub synced_commits: usize,
     pub current_position: Option<usize>,
     pub behind_base: Option<usize>,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub scope: Vec<String>,
+    /// The stack's description (`gg describe`)
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub description: Option<String>,
     pub entries: Vec<StackEntryJson>,
     #[serde(default, skip_serializing_if = "Vec::is_empty")]
     pub unintegrated_commits: Vec<UnintegratedCommitJson>,
@@ -121,10 +133,18 @@ pub struct StackEntryJson {
     pub pr_number: Option<u64>,
     pub pr_state: Option<String>,
     pub approved: bool,
+    pub changes_requested: bool,
     pub ci_status: Option<String>,
     pub is_current: bool,
     pub in_merge_train: bool,
     pub merge_train_position: Option<usize>,
+    /// Unresolved review threads (only with `gg ls --prs`)
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub unresolved_threads: Option<usize>,
+    #[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
+    pub annotations: BTreeMap<String, String>,
+    /// Whether the commit is signed
+    pub signed: bool,
 }
 
 #[derive(Serialize)]
@@ -133,9 +153,69 @@ pub struct AllStacksResponse {
     #[serde(default, skip_serializing_if = "Option::is_none")]
     pub operation_id: Option<String>,
     pub current_stack: Option<String>,
+    /// `--filter` applied to the list, as given
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub filter: Option<String>,
+    /// Order of `stacks`: `name`, `updated` or `size`
+    pub sort: String,
+    /// Stacks before filtering
+    pub total_stacks: usize,
     pub stacks: Vec<StackSummaryJson>,
 }
 
+#[derive(Serialize)]
+pub struct ConfigGetResponse {
+    pub version: u32,
+    pub key: String,
+    pub value: serde_json::Value,
+    /// `repo`, `global` or `default`
+    pub origin: String,
+}
+
+#[derive(Serialize)]
+pub struct ConfigListResponse {
+    pub version: u32,
+    pub values: Vec<ConfigValueJson>,
+}
+
+#[derive(Serialize)]
+pub struct ConfigValueJson {
+    pub key: String,
+    pub value: serde_json::Value,
+    pub origin: String,
+}
+
+#[derive(Serialize)]
+pub struct GlobalStacksResponse {
+    pub version: u32,
+    pub repos: Vec<GlobalRepoJson>,
+}
+
+#[derive(Serialize)]
+pub struct GlobalRepoJson {
+    pub path: String,
+    /// Why the repository could not be listed (moved, deleted, ...)
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub error: Option<String>,
+    pub current_stack: Option<String>,
+    pub stacks: Vec<GlobalStackJson>,
+}
+
+#[derive(Serialize)]
+pub struct GlobalStackJson {
+    pub name: String,
+    pub base: String,
+    pub commit_count: usize,
+    pub prs: Vec<GlobalPrJson>,
+}
+
+#[derive(Serialize)]
+pub struct GlobalPrJson {
+    pub number: u64,
+    /// `None` when the provider could not be reached
+    pub state: Option<String>,
+}
+
 #[derive(Serialize)]
 pub struct StackSummaryJson {
     pub name: String,
@@ -144,6 +224,14 @@ pub struct StackSummaryJson {
     pub is_current: bool,
     pub has_worktree: bool,
     pub behind_base: Option<usize>,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub scope: Vec<String>,
+    /// The stack's description (`gg describe`)
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub description: Option<String>,
+    /// Last change to the stack, by gg or to its tip commit
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub last_touched_ms: Option<u64>,
     pub commits: Vec<StackCommitJson>,
 }
 
@@ -152,6 +240,14 @@ pub struct StackCommitJson {
     pub position: usize,
     pub sha: String,
     pub title: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub pr_number: Option<u64>,
+    /// Last time the entry branch moved on origin
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub pushed_at_ms: Option<u64>,
+    /// Last PR/MR activity (`gg ls --all --refresh`)
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub pr_updated_at_ms: Option<u64>,
 }
 
 #[derive(Serialize)]
@@ -167,6 +263,16 @@ pub struct RemoteStackJson {
     pub pr_numbers: Vec<u64>,
 }
 
+#[derive(Serialize)]
+pub struct RemoteUserStacksResponse {
+    pub version: u32,
+    pub user: String,
+    pub stacks: Vec<StackJson>,
+    /// Fetch and PR/MR lookup failures; the stacks are shown regardless
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub warnings: Vec<String>,
+}
+
 #[derive(Serialize)]
 pub struct SyncResponse {
     pub version: u32,
@@ -250,6 +356,7 @@ pub enum SyncStreamingEvent {
         error: Option<String>,
     },
     Error {
+        code: String,
         message: String,
     },
     Summary {
@@ -299,6 +406,48 @@ pub struct SyncEntryResultJson {
     pub nav_comment_action: Option<String>,
 }
 
+#[derive(Serialize)]
+pub struct SyncPlanResponse {
+    pub version: u32,
+    pub sync_plan: SyncPlanJson,
+}
+
+#[derive(Serialize)]
+pub struct SyncPlanJson {
+    pub stack: String,
+    pub base: String,
+    /// Commits the stack is behind its upstream, when past the rebase threshold.
+    pub behind_base: Option<usize>,
+    pub warnings: Vec<String>,
+    pub metadata: SyncMetadataJson,
+    pub entries: Vec<SyncPlanEntryJson>,
+}
+
+#[derive(Serialize)]
+pub struct SyncPlanEntryJson {
+    pub position: usize,
+    pub sha: String,
+    pub title: String,
+    /// None when sync would generate a new GG-ID for this commit.
+    pub gg_id: Option<String>,
+    /// Whether normalizing GG metadata would rewrite this commit.
+    pub rewritten: bool,
+    /// None until the commit has a GG-ID.
+    pub branch: Option<String>,
+    /// One of `create`, `force_push`, or `up_to_date`.
+    pub push: String,
+    pub pr_number: Option<u64>,
+    /// One of `create`, `update`, `retarget`, `recreate`, `skip_closed`, or `up_to_date`.
+    pub pr_action: String,
+    /// None when the target is an entry branch that does not exist yet.
+    pub target_branch: Option<String>,
+    pub current_base: Option<String>,
+    pub draft: bool,
+    /// Reviewers requested on a new PR/MR
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub reviewers: Vec<String>,
+}
+
 #[derive(Serialize)]
 pub struct LintResponse {
     pub version: u32,
@@ -318,6 +467,9 @@ pub struct LintCommitResult {
     pub title: String,
     pub passed: bool,
     pub commands: Vec<LintCommandResult>,
+    /// Commands not run because the commit touches none of their `paths`
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub skipped: Vec<String>,
 }
 
 #[derive(Serialize)]
@@ -327,6 +479,80 @@ pub struct LintCommandResult {
     pub output: Option<String>,
 }
 
+#[derive(Serialize)]
+pub struct ReorderResponse {
+    pub version: u32,
+    pub reorder: ReorderResultJson,
+}
+
+#[derive(Serialize)]
+pub struct ReorderResultJson {
+    /// Requested order as current positions, bottom first (absent without
+    /// `--order`)
+    pub order: Option<Vec<usize>>,
+    pub dependencies: Vec<ReorderDependencyJson>,
+    /// Dependencies the requested order breaks
+    pub conflicts: Vec<ReorderDependencyJson>,
+}
+
+#[derive(Serialize)]
+pub struct ReorderDependencyJson {
+    pub position: usize,
+    pub depends_on: usize,
+    /// `lines` (always conflicts when broken) or `files` (may conflict)
+    pub kind: String,
+    pub paths: Vec<String>,
+}
+
+#[derive(Serialize)]
+pub struct AbsorbResponse {
+    pub version: u32,
+    pub absorb: AbsorbResultJson,
+}
+
+#[derive(Serialize)]
+pub struct AbsorbResultJson {
+    pub dry_run: bool,
+    pub hunks: Vec<AbsorbHunkJson>,
+    /// Staged paths absorb leaves alone (added, deleted, renamed or binary)
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub unsupported_paths: Vec<String>,
+    /// Fixup commits created for the attributed hunks (empty on dry run)
+    pub fixups: Vec<AbsorbFixupJson>,
+    /// Whether the fixups were already folded into their targets
+    pub rebased: bool,
+}
+
+#[derive(Serialize)]
+pub struct AbsorbHunkJson {
+    pub path: String,
+    pub old_start: u32,
+    pub old_lines: u32,
+    pub new_start: u32,
+    pub new_lines: u32,
+    /// `attributed`, `ambiguous` or `unattributed`
+    pub status: String,
+    pub target: Option<AbsorbTargetJson>,
+    /// Positions of every entry that touched the hunk's lines
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub candidates: Vec<usize>,
+}
+
+#[derive(Serialize)]
+pub struct AbsorbFixupJson {
+    pub sha: String,
+    pub target: AbsorbTargetJson,
+    pub hunks: usize,
+}
+
+#[derive(Serialize)]
+pub struct AbsorbTargetJson {
+    pub position: usize,
+    pub sha: String,
+    pub gg_id: Option<String>,
+    pub title: String,
+}
+
 #[derive(Serialize)]
 pub struct RunResponse {
     pub version: u32,
@@ -346,6 +572,9 @@ pub struct RunCommitResult {
     pub title: String,
     pub passed: bool,
     pub commands: Vec<RunCommandResult>,
+    /// Commands not run because the commit touches none of their path globs
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub skipped: Vec<String>,
 }
 
 #[derive(Serialize)]
@@ -368,6 +597,11 @@ pub struct LandResultJson {
     pub landed: Vec<LandedEntryJson>,
     pub remaining: usize,
     pub cleaned: bool,
+    /// Remote entry branches deleted after landing (`delete_branch_on_land`)
+    pub deleted_branches: Vec<String>,
+    /// Changelog entries for the merged PRs/MRs (`defaults.changelog`)
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub changelog: Option<LandChangelogJson>,
     pub warnings: Vec<String>,
     pub error: Option<String>,
 }
@@ -383,6 +617,23 @@ pub struct LandedEntryJson {
     pub error: Option<String>,
 }
 
+#[derive(Serialize)]
+pub struct LandChangelogJson {
+    pub mode: ChangelogMode,
+    pub entries: Vec<ChangelogEntryJson>,
+    /// Commit that added the entries to the base branch
+    pub commit: Option<String>,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct ChangelogEntryJson {
+    pub pr_number: u64,
+    pub title: String,
+    pub text: String,
+    /// File the entry was written to
+    pub path: Option<String>,
+}
+
 // ---------------------------------------------------------------------------
 // Inbox responses
 // ---------------------------------------------------------------------------
@@ -425,6 +676,414 @@ pub struct InboxStackErrorJson {
     pub error: String,
 }
 
+// ---------------------------------------------------------------------------
+// Insights responses
+// ---------------------------------------------------------------------------
+
+#[derive(Serialize)]
+pub struct InsightsResponse {
+    pub version: u32,
+    pub insights: InsightsSummaryJson,
+}
+
+#[derive(Serialize)]
+pub struct InsightsSummaryJson {
+    pub operations: usize,
+    pub window_start_ms: Option<u64>,
+    pub window_end_ms: Option<u64>,
+    pub syncs: usize,
+    pub lands: usize,
+    pub conflicts: usize,
+    pub prs_created: usize,
+    pub prs_merged: usize,
+    pub prs_created_per_week: f64,
+    pub prs_merged_per_week: f64,
+    pub stacks_landed: usize,
+    pub median_sync_to_land_ms: Option<u64>,
+    pub stacks: Vec<InsightsStackJson>,
+}
+
+#[derive(Serialize)]
+pub struct InsightsStackJson {
+    pub name: String,
+    pub syncs: usize,
+    pub lands: usize,
+    pub conflicts: usize,
+    pub prs_created: usize,
+    pub prs_merged: usize,
+    pub first_sync_at_ms: Option<u64>,
+    pub last_land_at_ms: Option<u64>,
+    pub sync_to_land_ms: Option<u64>,
+}
+
+// ---------------------------------------------------------------------------
+// Stash responses
+// ---------------------------------------------------------------------------
+
+#[derive(Serialize)]
+pub struct StashResponse {
+    pub version: u32,
+    pub stash: StashResultJson,
+}
+
+#[derive(Serialize)]
+pub struct StashResultJson {
+    pub action: String,
+    pub entry: StashEntryJson,
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub warnings: Vec<String>,
+}
+
+#[derive(Serialize)]
+pub struct StashListResponse {
+    pub version: u32,
+    pub stashes: Vec<StashEntryJson>,
+}
+
+#[derive(Serialize)]
+pub struct StashEntryJson {
+    pub reference: String,
+    pub index: usize,
+    pub stack: Option<String>,
+    pub position: Option<usize>,
+    pub message: String,
+}
+
+// ---------------------------------------------------------------------------
+// Archive responses
+// ---------------------------------------------------------------------------
+
+#[derive(Serialize)]
+pub struct ArchiveListResponse {
+    pub version: u32,
+    pub archives: Vec<ArchiveEntryJson>,
+}
+
+#[derive(Serialize)]
+pub struct ArchiveRestoreResponse {
+    pub version: u32,
+    pub restored: ArchiveEntryJson,
+    /// Local stack branch recreated from the archive
+    pub branch: String,
+}
+
+#[derive(Serialize)]
+pub struct ArchiveEntryJson {
+    pub stack: String,
+    /// `refs/gg/archive/<stack>/<date>`
+    #[serde(rename = "ref")]
+    pub reference: String,
+    pub tip: String,
+    pub archived_at_ms: u64,
+}
+
+// ---------------------------------------------------------------------------
+// Snapshot responses
+// ---------------------------------------------------------------------------
+
+#[derive(Serialize)]
+pub struct SnapshotListResponse {
+    pub version: u32,
+    pub snapshots: Vec<SnapshotJson>,
+}
+
+#[derive(Serialize)]
+pub struct SnapshotRestoreResponse {
+    pub version: u32,
+    pub restored: SnapshotJson,
+    /// Stack branch moved to the snapshot
+    pub branch: String,
+    /// Where the branch pointed before the restore
+    pub previous_tip: String,
+}
+
+#[derive(Serialize)]
+pub struct SnapshotJson {
+    pub stack: String,
+    /// `refs/gg/backup/<stack>/<timestamp>`
+    #[serde(rename = "ref")]
+    pub reference: String,
+    pub tip: String,
+    pub created_at_ms: u64,
+}
+
+// ---------------------------------------------------------------------------
+// Worktree responses
+// ---------------------------------------------------------------------------
+
+#[derive(Serialize)]
+pub struct WorktreeListResponse {
+    pub version: u32,
+    pub worktrees: Vec<WorktreeJson>,
+}
+
+#[derive(Serialize)]
+pub struct WorktreePruneResponse {
+    pub version: u32,
+    pub dry_run: bool,
+    pub pruned: Vec<WorktreeJson>,
+    /// Stale worktrees left alone because they have uncommitted changes
+    pub kept: Vec<WorktreeJson>,
+}
+
+#[derive(Serialize)]
+pub struct WorktreeMoveResponse {
+    pub version: u32,
+    pub stack: String,
+    pub from: String,
+    pub to: String,
+}
+
+#[derive(Serialize)]
+pub struct WorktreeJson {
+    pub stack: String,
+    pub path: String,
+    /// `active`, `missing` (the directory is gone) or `orphaned` (the
+    /// stack's branch is gone)
+    pub status: String,
+    /// Branch checked out in the worktree
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub branch: Option<String>,
+    pub dirty: bool,
+}
+
+// ---------------------------------------------------------------------------
+// Describe responses
+// ---------------------------------------------------------------------------
+
+#[derive(Serialize)]
+pub struct DescribeResponse {
+    pub version: u32,
+    pub stack: String,
+    pub description: Option<String>,
+}
+
+// ---------------------------------------------------------------------------
+// Check-msgs responses
+// ---------------------------------------------------------------------------
+
+#[derive(Serialize)]
+pub struct CheckMsgsResponse {
+    pub version: u32,
+    pub check_msgs: CheckMsgsResultJson,
+}
+
+#[derive(Serialize)]
+pub struct CheckMsgsResultJson {
+    pub ok: bool,
+    pub pattern: String,
+    pub violations: usize,
+    pub commits: Vec<CheckMsgJson>,
+}
+
+#[derive(Serialize)]
+pub struct CheckMsgJson {
+    pub position: usize,
+    pub sha: String,
+    pub subject: String,
+    pub ok: bool,
+    #[serde(rename = "type", skip_serializing_if = "Option::is_none")]
+    pub kind: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub scope: Option<String>,
+    pub breaking: bool,
+}
+
+// ---------------------------------------------------------------------------
+// Verify responses
+// ---------------------------------------------------------------------------
+
+#[derive(Serialize)]
+pub struct VerifyResponse {
+    pub version: u32,
+    pub verify: VerifyResultJson,
+}
+
+#[derive(Serialize)]
+pub struct VerifyResultJson {
+    pub ok: bool,
+    pub fixed: usize,
+    pub remaining: usize,
+    pub issues: Vec<VerifyIssueJson>,
+}
+
+#[derive(Serialize)]
+pub struct VerifyIssueJson {
+    pub kind: String,
+    pub stack: String,
+    pub message: String,
+    pub warning: bool,
+    pub fixable: bool,
+    pub fixed: bool,
+}
+
+// ---------------------------------------------------------------------------
+// Doctor responses
+// ---------------------------------------------------------------------------
+
+#[derive(Serialize)]
+pub struct DoctorResponse {
+    pub version: u32,
+    pub doctor: DoctorResultJson,
+}
+
+#[derive(Serialize)]
+pub struct DoctorResultJson {
+    pub ok: bool,
+    pub fixed: usize,
+    pub checks: Vec<DoctorCheckJson>,
+}
+
+#[derive(Serialize)]
+pub struct DoctorCheckJson {
+    pub area: String,
+    /// `pass`, `warn` or `fail`
+    pub status: String,
+    pub message: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub hint: Option<String>,
+    pub fixable: bool,
+    pub fixed: bool,
+}
+
+// ---------------------------------------------------------------------------
+// Comments responses
+// ---------------------------------------------------------------------------
+
+#[derive(Serialize)]
+pub struct CommentsResponse {
+    pub version: u32,
+    pub stack: String,
+    pub entries: Vec<CommentsEntryJson>,
+}
+
+#[derive(Serialize)]
+pub struct CommentsEntryJson {
+    pub position: usize,
+    pub sha: String,
+    pub title: String,
+    pub gg_id: Option<String>,
+    pub pr_number: u64,
+    pub threads: Vec<ReviewThreadJson>,
+}
+
+#[derive(Serialize)]
+pub struct ReviewThreadJson {
+    pub id: String,
+    pub resolved: bool,
+    pub outdated: bool,
+    pub path: Option<String>,
+    pub line: Option<u64>,
+    pub comments: Vec<ReviewCommentJson>,
+}
+
+#[derive(Serialize)]
+pub struct ReviewCommentJson {
+    pub author: Option<String>,
+    pub body: String,
+    pub created_at: String,
+}
+
+#[derive(Serialize)]
+pub struct ReviewResponse {
+    pub version: u32,
+    pub position: usize,
+    pub sha: String,
+    pub title: String,
+    pub gg_id: Option<String>,
+    pub pr_number: u64,
+    /// Unresolved threads grouped by file (`path: null` for general threads),
+    /// ordered by line within each file.
+    pub files: Vec<ReviewFileJson>,
+}
+
+#[derive(Serialize)]
+pub struct ReviewFileJson {
+    pub path: Option<String>,
+    pub threads: Vec<ReviewThreadJson>,
+}
+
+#[derive(Serialize)]
+pub struct CommentsResolveResponse {
+    pub version: u32,
+    pub resolved: ResolvedThreadJson,
+}
+
+#[derive(Serialize)]
+pub struct ResolvedThreadJson {
+    pub id: String,
+    pub pr_number: u64,
+    pub already_resolved: bool,
+}
+
+// ---------------------------------------------------------------------------
+// Retarget responses
+// ---------------------------------------------------------------------------
+
+#[derive(Serialize)]
+pub struct RetargetResponse {
+    pub version: u32,
+    pub stack: String,
+    pub dry_run: bool,
+    pub entries: Vec<RetargetEntryJson>,
+}
+
+#[derive(Serialize)]
+pub struct RetargetEntryJson {
+    pub position: usize,
+    pub pr_number: u64,
+    pub title: String,
+    pub current_base: Option<String>,
+    pub expected_base: String,
+    /// One of `ok`, `retargeted`, `would_retarget`, `skipped`, `error`.
+    pub action: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub error: Option<String>,
+}
+
+// ---------------------------------------------------------------------------
+// CI responses
+// ---------------------------------------------------------------------------
+
+#[derive(Serialize)]
+pub struct CiRerunResponse {
+    pub version: u32,
+    pub position: usize,
+    pub pr_number: u64,
+    pub failed_only: bool,
+    /// Workflow runs (GitHub) or pipelines (GitLab) that were restarted.
+    pub restarted: Vec<String>,
+}
+
+// ---------------------------------------------------------------------------
+// Comment response
+// ---------------------------------------------------------------------------
+
+#[derive(Serialize)]
+pub struct CommentResponse {
+    pub version: u32,
+    pub comments: Vec<PostedCommentJson>,
+}
+
+#[derive(Serialize)]
+pub struct PostedCommentJson {
+    pub position: usize,
+    pub pr_number: u64,
+}
+
+// ---------------------------------------------------------------------------
+// Annotate response
+// ---------------------------------------------------------------------------
+
+#[derive(Serialize)]
+pub struct AnnotateResponse {
+    pub version: u32,
+    pub position: usize,
+    pub sha: String,
+    pub gg_id: Option<String>,
+    pub annotations: BTreeMap<String, String>,
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -475,6 +1134,7 @@ mod tests {
                         passed: false,
                         output: Some("test failed".to_string()),
                     }],
+                    skipped: vec![],
                 }],
             },
         };
@@ -506,6 +1166,7 @@ mod tests {
                         passed: false,
                         output: Some("error: warning denied".to_string()),
                     }],
+                    skipped: vec!["ktfmt".to_string()],
                 }],
             },
         };
@@ -515,6 +1176,7 @@ mod tests {
         assert_eq!(value["lint"]["all_passed"], false);
         assert_eq!(value["lint"]["results"][0]["position"], 1);
         assert_eq!(value["lint"]["results"][0]["commands"][0]["passed"], false);
+        assert_eq!(value["lint"]["results"][0]["skipped"][0], "ktfmt");
         assert_eq!(
             value["lint"]["results"][0]["commands"][0]["output"],
             "error: warning denied"
@@ -693,12 +1355,14 @@ mod tests {
             version: OUTPUT_VERSION,
             command: "sync".to_string(),
             event: SyncStreamingEvent::Error {
+                code: "GG_ERROR".to_string(),
                 message: "boom".to_string(),
             },
         };
         let v = serde_json::to_value(&response).unwrap();
         assert_eq!(v["event"], "error");
         assert_eq!(v["status"], "error");
+        assert_eq!(v["code"], "GG_ERROR");
     }
 
     #[test]
@@ -742,6 +1406,22 @@ pub struct CleanResponse {
 pub struct CleanResultJson {
     pub cleaned: Vec<String>,
     pub skipped: Vec<String>,
+    pub dry_run: bool,
+    /// Every stack considered, with what happened (or would) and why
+    pub stacks: Vec<CleanStackJson>,
+}
+
+#[derive(Serialize)]
+pub struct CleanStackJson {
+    pub name: String,
+    /// `delete`, `archive` or `skip`
+    pub action: String,
+    /// e.g. `all_prs_merged`, `open_prs_remain`, `worktree_dirty`
+    pub reason: String,
+    pub detail: String,
+    /// Ref the stack branch was archived under (`gg clean --archive`)
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub archive_ref: Option<String>,
 }
 
 #[derive(Serialize)]
@@ -754,6 +1434,9 @@ pub struct DropResponse {
 pub struct DropResultJson {
     pub dropped: Vec<DroppedEntryJson>,
     pub remaining: usize,
+    /// PRs/MRs closed because of `--close-pr`
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub closed_prs: Vec<u64>,
 }
 
 #[derive(Serialize)]
@@ -763,6 +1446,34 @@ pub struct DroppedEntryJson {
     pub title: String,
 }
 
+#[derive(Serialize)]
+pub struct SquashAllResponse {
+    pub version: u32,
+    /// Number of entries folded into the squashed commit
+    pub squashed: usize,
+    pub sha: String,
+    pub gg_id: String,
+    /// PR/MR that now carries the squashed commit
+    pub pr_number: Option<u64>,
+    pub closed_prs: Vec<u64>,
+    pub remaining: usize,
+}
+
+#[derive(Serialize)]
+pub struct FoldResponse {
+    pub version: u32,
+    /// Position of the entry that was folded away
+    pub folded: usize,
+    /// Position of the entry it was folded into
+    pub into: usize,
+    pub sha: String,
+    pub gg_id: String,
+    /// PR/MR that now carries the folded commit
+    pub pr_number: Option<u64>,
+    pub closed_prs: Vec<u64>,
+    pub remaining: usize,
+}
+
 #[derive(Serialize)]
 pub struct UnstackResponse {
     pub version: u32,
@@ -832,6 +1543,75 @@ pub struct LogJson {
     pub entries: Vec<StackEntryJson>,
 }
 
+#[derive(Serialize)]
+pub struct WatchStreamingResponse {
+    pub version: u32,
+    #[serde(flatten)]
+    pub event: WatchEvent,
+}
+
+#[derive(Serialize)]
+#[serde(rename_all = "snake_case", tag = "event")]
+pub enum WatchEvent {
+    Start {
+        stack: String,
+        base: String,
+        interval_secs: u64,
+    },
+    /// First state of an entry, then every change to it
+    Entry(WatchEntryJson),
+    Error {
+        message: String,
+    },
+    /// Every PR/MR is merged or closed, or the watch was interrupted
+    Done,
+}
+
+#[derive(Serialize)]
+pub struct WatchEntryJson {
+    pub position: usize,
+    pub sha: String,
+    pub title: String,
+    pub pr_number: u64,
+    pub pr_state: Option<String>,
+    pub ci_status: Option<String>,
+    pub approved: bool,
+}
+
+#[derive(Serialize)]
+pub struct StatusResponse {
+    pub version: u32,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub operation_id: Option<String>,
+    pub status: StatusJson,
+}
+
+#[derive(Serialize)]
+pub struct StatusJson {
+    /// Checked-out branch; None when HEAD is detached
+    pub branch: Option<String>,
+    /// None when HEAD is not on a stack
+    pub stack: Option<StatusStackJson>,
+    /// The entry HEAD is on, with PR/MR and CI state when available
+    pub current_entry: Option<StackEntryJson>,
+    pub staged: Vec<String>,
+    pub unstaged: Vec<String>,
+    pub untracked: Vec<String>,
+    pub conflicted: Vec<String>,
+    pub rebase_in_progress: bool,
+}
+
+#[derive(Serialize)]
+pub struct StatusStackJson {
+    pub name: String,
+    pub base: String,
+    pub current_position: Option<usize>,
+    pub total_commits: usize,
+    pub synced_commits: usize,
+    /// Commits on origin/<base> missing from the local base branch
+    pub behind_base: Option<usize>,
+}
+
 // ---------------------------------------------------------------------------
 // Undo responses (task #5)
 // ---------------------------------------------------------------------------
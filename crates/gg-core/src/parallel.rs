@@ -0,0 +1,62 @@
+//! Bounded parallelism for independent subprocess work (pushes, provider
+//! lookups), keeping results in input order so output stays readable.
+
+use std::sync::{Mutex, PoisonError};
+
+/// Apply `f` to every item with at most `jobs` threads and return the
+/// results in the same order as `items`.
+pub fn map<T, R, F>(items: &[T], jobs: usize, f: F) -> Vec<R>
+where
+    T: Sync,
+    R: Send,
+    F: Fn(&T) -> R + Sync,
+{
+    let jobs = jobs.max(1).min(items.len());
+    if jobs <= 1 {
+        return items.iter().map(f).collect();
+    }
+
+    let work = Mutex::new(items.iter().enumerate());
+    let results: Mutex<Vec<Option<R>>> = Mutex::new(items.iter().map(|_| None).collect());
+    std::thread::scope(|s| {
+        for _ in 0..jobs {
+            s.spawn(|| loop {
+                let item = work.lock().unwrap_or_else(PoisonError::into_inner).next();
+                let Some((i, item)) = item else { break };
+                let result = f(item);
+                results.lock().unwrap_or_else(PoisonError::into_inner)[i] = Some(result);
+            });
+        }
+    });
+
+    results
+        .into_inner()
+        .unwrap_or_else(PoisonError::into_inner)
+        .into_iter()
+        .map(|r| r.expect("every item is processed"))
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::time::Duration;
+
+    #[test]
+    fn map_keeps_input_order() {
+        let items: Vec<u64> = (0..20).collect();
+        let out = map(&items, 4, |&n| {
+            // Later items finish first.
+            std::thread::sleep(Duration::from_millis(20 - n));
+            n * 2
+        });
+        assert_eq!(out, (0..20).map(|n| n * 2).collect::<Vec<_>>());
+    }
+
+    #[test]
+    fn map_handles_empty_and_single_job() {
+        let empty: Vec<u32> = vec![];
+        assert!(map(&empty, 4, |n| *n).is_empty());
+        assert_eq!(map(&[1, 2, 3], 1, |n| n + 1), vec![2, 3, 4]);
+    }
+}
@@ -0,0 +1,99 @@
+//! Plain output mode (`--plain` / `defaults.plain_output`).
+//!
+//! Plain mode swaps emoji, box-drawing characters and spinners for stable
+//! ASCII markers and turns off color, so output reads well in screen
+//! readers, dumb terminals and log files. It is a process-wide switch set
+//! once at startup; rendering code asks for markers through [`sym`] or the
+//! named helpers below.
+
+use std::sync::atomic::{AtomicBool, Ordering};
+
+static PLAIN: AtomicBool = AtomicBool::new(false);
+
+/// Turn plain mode on or off, disabling color along with it.
+pub fn set_enabled(enabled: bool) {
+    PLAIN.store(enabled, Ordering::Relaxed);
+    if enabled {
+        console::set_colors_enabled(false);
+        console::set_colors_enabled_stderr(false);
+    }
+}
+
+/// Whether plain mode is on.
+pub fn is_enabled() -> bool {
+    PLAIN.load(Ordering::Relaxed)
+}
+
+/// `fancy` normally, `ascii` in plain mode.
+pub fn sym(fancy: &'static str, ascii: &'static str) -> &'static str {
+    pick(is_enabled(), fancy, ascii)
+}
+
+fn pick(plain: bool, fancy: &'static str, ascii: &'static str) -> &'static str {
+    if plain {
+        ascii
+    } else {
+        fancy
+    }
+}
+
+/// Success marker (`✓` / `ok`).
+pub fn ok() -> &'static str {
+    sym("✓", "ok")
+}
+
+/// Failure marker (`✗` / `x`).
+pub fn fail() -> &'static str {
+    sym("✗", "x")
+}
+
+/// Warning marker (`⚠` / `!`).
+pub fn warn() -> &'static str {
+    sym("⚠", "!")
+}
+
+/// Step/progress arrow (`→` / `->`).
+pub fn arrow() -> &'static str {
+    sym("→", "->")
+}
+
+/// List bullet (`•` / `-`).
+pub fn bullet() -> &'static str {
+    sym("•", "-")
+}
+
+/// Running/in-progress marker (`●` / `*`).
+pub fn running() -> &'static str {
+    sym("●", "*")
+}
+
+/// Pending/idle marker (`○` / `o`).
+pub fn pending() -> &'static str {
+    sym("○", "o")
+}
+
+/// Tree branch for a middle child (`├──` / `|--`).
+pub fn tree_mid() -> &'static str {
+    sym("├──", "|--")
+}
+
+/// Tree branch for the last child (`└──` / `` `-- ``).
+pub fn tree_last() -> &'static str {
+    sym("└──", "`--")
+}
+
+/// Vertical tree continuation (`│` / `|`).
+pub fn tree_pipe() -> &'static str {
+    sym("│", "|")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn pick_uses_ascii_only_in_plain_mode() {
+        assert_eq!(pick(false, "✓", "ok"), "✓");
+        assert_eq!(pick(true, "✓", "ok"), "ok");
+    }
+}
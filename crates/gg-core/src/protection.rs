@@ -0,0 +1,311 @@
+//! Pre-flight checks for signed-commit and linear-history protections.
+//!
+//! Providers reject pushes or merges that break branch protection only after
+//! the work is done: a half-pushed stack, or a land that stops midway. This
+//! module reads the protection settings up front and checks the stack
+//! locally, so `gg sync` and `gg land` can fail early and name the commits
+//! that violate a rule.
+//!
+//! Which rules apply depends on the stage:
+//! - **Push** (`gg sync`): only rules the provider enforces on every push —
+//!   GitLab's "reject unsigned commits" push rule.
+//! - **Land** (`gg land`): rules enforced when merging into the base. A
+//!   GitHub squash merge is created (and signed) by GitHub, so only
+//!   merge and rebase strategies need signed commits, and only the merge
+//!   strategy creates a merge commit that a linear-history branch rejects.
+
+use git2::Repository;
+
+use crate::config::MergeStrategy;
+use crate::error::{GgError, Result};
+use crate::provider::{BranchProtections, Provider};
+use crate::stack::StackEntry;
+
+/// When the check runs.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Stage {
+    Push,
+    Land { strategy: MergeStrategy },
+}
+
+/// What the stack must satisfy at a given stage.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct Requirements {
+    /// Every commit must carry a signature.
+    pub signed_commits: bool,
+    /// No commit may be a merge commit.
+    pub no_merge_commits: bool,
+    /// The merge itself would create a merge commit the branch rejects.
+    pub merge_strategy_rejected: bool,
+}
+
+impl Requirements {
+    pub fn is_empty(&self) -> bool {
+        *self == Self::default()
+    }
+}
+
+/// Work out which rules apply to the stack at `stage`.
+pub fn requirements(protections: &BranchProtections, stage: Stage) -> Requirements {
+    match stage {
+        Stage::Push => Requirements {
+            signed_commits: protections.require_signed_commits
+                && protections.signatures_enforced_on_push,
+            ..Requirements::default()
+        },
+        // Push-enforced signatures were already checked when syncing, and
+        // squash commits are created by the provider.
+        Stage::Land {
+            strategy: MergeStrategy::Squash,
+        } => Requirements::default(),
+        Stage::Land { strategy } => Requirements {
+            signed_commits: protections.require_signed_commits
+                && !protections.signatures_enforced_on_push,
+            no_merge_commits: protections.require_linear_history,
+            merge_strategy_rejected: strategy == MergeStrategy::Merge
+                && protections.require_linear_history
+                && !protections.signatures_enforced_on_push,
+        },
+    }
+}
+
+/// A rule a commit breaks.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Violation {
+    Unsigned,
+    MergeCommit,
+}
+
+impl Violation {
+    pub fn describe(self) -> &'static str {
+        match self {
+            Violation::Unsigned => "not signed",
+            Violation::MergeCommit => "merge commit",
+        }
+    }
+}
+
+/// A stack entry and the rules it breaks.
+#[derive(Debug, Clone)]
+pub struct EntryViolation {
+    pub position: usize,
+    pub short_sha: String,
+    pub title: String,
+    pub violations: Vec<Violation>,
+}
+
+/// Check `entries` against `reqs`.
+pub fn check_entries(
+    repo: &Repository,
+    entries: &[&StackEntry],
+    reqs: &Requirements,
+) -> Result<Vec<EntryViolation>> {
+    let mut found = Vec::new();
+    for entry in entries {
+        let mut violations = Vec::new();
+        if reqs.signed_commits && repo.extract_signature(&entry.oid, None).is_err() {
+            violations.push(Violation::Unsigned);
+        }
+        if reqs.no_merge_commits && repo.find_commit(entry.oid)?.parent_count() > 1 {
+            violations.push(Violation::MergeCommit);
+        }
+        if !violations.is_empty() {
+            found.push(EntryViolation {
+                position: entry.position,
+                short_sha: entry.short_sha.clone(),
+                title: entry.title.clone(),
+                violations,
+            });
+        }
+    }
+    Ok(found)
+}
+
+/// Fail with [`GgError::ProtectionViolation`] if anything breaks the rules
+/// of `branch`.
+pub fn guard(branch: &str, reqs: &Requirements, violations: &[EntryViolation]) -> Result<()> {
+    if violations.is_empty() && !reqs.merge_strategy_rejected {
+        return Ok(());
+    }
+
+    let mut lines = Vec::new();
+    if reqs.merge_strategy_rejected {
+        lines.push(format!(
+            "  {} requires a linear history; land with `--strategy squash` or `--strategy rebase`",
+            branch
+        ));
+    }
+    for v in violations {
+        let reasons: Vec<&str> = v.violations.iter().map(|r| r.describe()).collect();
+        lines.push(format!(
+            "  #{}  {}  {}  ({})",
+            v.position,
+            v.short_sha,
+            v.title,
+            reasons.join(", ")
+        ));
+    }
+    if violations
+        .iter()
+        .any(|v| v.violations.contains(&Violation::Unsigned))
+    {
+        lines.push(
+            "Sign the commits (e.g. `git rebase --exec 'git commit --amend --no-edit -S' <base>`) and retry."
+                .to_string(),
+        );
+    }
+
+    Err(GgError::ProtectionViolation(
+        branch.to_string(),
+        lines.join("\n"),
+    ))
+}
+
+/// Read `branch`'s protections and fail if `entries` cannot be pushed or
+/// landed at `stage`.
+///
+/// Best-effort: if the settings cannot be read (missing permissions, paid
+/// feature, network), the provider stays the final authority and the check
+/// passes.
+pub fn preflight(
+    repo: &Repository,
+    provider: &Provider,
+    branch: &str,
+    entries: &[&StackEntry],
+    stage: Stage,
+) -> Result<()> {
+    // GitHub enforces nothing relevant on push to entry branches.
+    if stage == Stage::Push && *provider == Provider::GitHub {
+        return Ok(());
+    }
+    let Ok(protections) = provider.branch_protections(branch) else {
+        return Ok(());
+    };
+    let reqs = requirements(&protections, stage);
+    if reqs.is_empty() {
+        return Ok(());
+    }
+    let violations = check_entries(repo, entries, &reqs)?;
+    guard(branch, &reqs, &violations)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn protections(signed: bool, linear: bool, on_push: bool) -> BranchProtections {
+        BranchProtections {
+            require_signed_commits: signed,
+            require_linear_history: linear,
+            signatures_enforced_on_push: on_push,
+        }
+    }
+
+    #[test]
+    fn push_only_checks_push_enforced_signatures() {
+        assert!(requirements(&protections(true, true, false), Stage::Push).is_empty());
+        assert_eq!(
+            requirements(&protections(true, true, true), Stage::Push),
+            Requirements {
+                signed_commits: true,
+                ..Requirements::default()
+            }
+        );
+    }
+
+    #[test]
+    fn squash_land_has_no_requirements() {
+        let p = protections(true, true, false);
+        let stage = Stage::Land {
+            strategy: MergeStrategy::Squash,
+        };
+        assert!(requirements(&p, stage).is_empty());
+    }
+
+    #[test]
+    fn github_merge_land_needs_signatures_and_rejects_merge_strategy() {
+        let reqs = requirements(
+            &protections(true, true, false),
+            Stage::Land {
+                strategy: MergeStrategy::Merge,
+            },
+        );
+        assert!(reqs.signed_commits);
+        assert!(reqs.no_merge_commits);
+        assert!(reqs.merge_strategy_rejected);
+    }
+
+    #[test]
+    fn github_rebase_land_keeps_linear_history() {
+        let reqs = requirements(
+            &protections(true, true, false),
+            Stage::Land {
+                strategy: MergeStrategy::Rebase,
+            },
+        );
+        assert!(reqs.signed_commits);
+        assert!(reqs.no_merge_commits);
+        assert!(!reqs.merge_strategy_rejected);
+    }
+
+    #[test]
+    fn gitlab_fast_forward_land_only_forbids_merge_commits() {
+        let reqs = requirements(
+            &protections(true, true, true),
+            Stage::Land {
+                strategy: MergeStrategy::Merge,
+            },
+        );
+        assert_eq!(
+            reqs,
+            Requirements {
+                no_merge_commits: true,
+                ..Requirements::default()
+            }
+        );
+    }
+
+    #[test]
+    fn guard_lists_violating_commits() {
+        let violations = vec![EntryViolation {
+            position: 2,
+            short_sha: "abc1234".to_string(),
+            title: "Add thing".to_string(),
+            violations: vec![Violation::Unsigned],
+        }];
+        let err = guard("main", &Requirements::default(), &violations).unwrap_err();
+        let msg = err.to_string();
+        assert!(
+            msg.contains("#2  abc1234  Add thing  (not signed)"),
+            "{msg}"
+        );
+        assert!(msg.contains("main"), "{msg}");
+
+        assert!(guard("main", &Requirements::default(), &[]).is_ok());
+    }
+
+    #[test]
+    fn unsigned_commit_is_detected() {
+        let dir = tempfile::TempDir::new().unwrap();
+        let repo = Repository::init(dir.path()).unwrap();
+        let sig = git2::Signature::now("Test", "test@example.com").unwrap();
+        let tree_id = repo.index().unwrap().write_tree().unwrap();
+        let tree = repo.find_tree(tree_id).unwrap();
+        let oid = repo
+            .commit(Some("HEAD"), &sig, &sig, "Initial", &tree, &[])
+            .unwrap();
+
+        let entry = StackEntry::from_commit(&repo.find_commit(oid).unwrap(), 1);
+        let reqs = Requirements {
+            signed_commits: true,
+            ..Requirements::default()
+        };
+        let found = check_entries(&repo, &[&entry], &reqs).unwrap();
+        assert_eq!(found.len(), 1);
+        assert_eq!(found[0].violations, vec![Violation::Unsigned]);
+
+        assert!(check_entries(&repo, &[&entry], &Requirements::default())
+            .unwrap()
+            .is_empty());
+    }
+}
@@ -1,16 +1,21 @@
-//! Provider abstraction for GitHub and GitLab
+//! Provider abstraction for GitHub, GitLab and Gitea/Forgejo
 //!
 //! Provides a unified interface for working with different git hosting providers.
 
+use std::collections::HashMap;
+
 use git2::Repository;
 
-use crate::config::Config;
+use crate::config::{Config, MergeStrategy};
 use crate::error::{GgError, Result};
 use crate::gh::{self, CiStatus as GhCiStatus, PrState as GhPrState};
 use crate::git;
+use crate::gitea::{self, CiStatus as GiteaCiStatus, PullState as GiteaPullState};
 use crate::glab::{self, AutoMergeResult, CiStatus as GlabCiStatus, MrState as GlabMrState};
+use crate::plain;
 use crate::stack_nav;
 
+pub use crate::gh::FailedChecks;
 pub use crate::glab::FailedJob;
 
 /// Handle auth check result with network error fallback.
@@ -33,6 +38,7 @@ fn check_auth_with_network_fallback(result: Result<()>) -> Result<()> {
 pub enum Provider {
     GitHub,
     GitLab,
+    Gitea,
 }
 
 /// Unified PR/MR state across providers
@@ -44,6 +50,18 @@ pub enum PrState {
     Draft,
 }
 
+/// Commit requirements a provider enforces for a target branch.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct BranchProtections {
+    /// Commits must be signed.
+    pub require_signed_commits: bool,
+    /// The branch history must stay linear (no merge commits).
+    pub require_linear_history: bool,
+    /// Signatures are enforced when pushing any branch (GitLab push rules),
+    /// not only when merging into the protected branch.
+    pub signatures_enforced_on_push: bool,
+}
+
 /// Unified CI status across providers
 #[derive(Debug, Clone, PartialEq, Eq)]
 pub enum CiStatus {
@@ -64,11 +82,34 @@ pub struct PrInfo {
     pub state: PrState,
     pub url: String,
     pub head_branch: Option<String>,
+    pub base_branch: Option<String>,
     pub draft: bool,
     pub approved: bool,
     pub mergeable: bool,
     pub changes_requested: bool,
     pub detailed_merge_status: Option<String>,
+    /// Last activity on the PR/MR, in milliseconds since the epoch
+    pub updated_at_ms: Option<u64>,
+}
+
+/// A PR/MR as fetched in bulk by [`Provider::get_prs_status`]: its
+/// [`PrInfo`], plus whatever else the provider returned in the same
+/// request. `None` fields need a call per PR/MR.
+#[derive(Debug, Clone)]
+pub struct PrStatus {
+    pub info: PrInfo,
+    pub ci_status: Option<CiStatus>,
+    pub approved: Option<bool>,
+    pub merge_train: Option<glab::MergeTrainInfo>,
+}
+
+/// An open PR/MR authored by the current user.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct OpenPr {
+    pub number: u64,
+    pub title: String,
+    pub head_branch: String,
+    pub base_branch: String,
 }
 
 /// Result of creating a PR/MR
@@ -78,6 +119,27 @@ pub struct PrCreationResult {
     pub url: String,
 }
 
+/// An inline review thread on a PR/MR.
+#[derive(Debug, Clone)]
+pub struct ReviewThread {
+    /// Provider thread id (GitHub node id / GitLab discussion id)
+    pub id: String,
+    pub resolved: bool,
+    /// The code the thread points at has changed since (GitHub only)
+    pub outdated: bool,
+    pub path: Option<String>,
+    pub line: Option<u64>,
+    pub comments: Vec<ReviewComment>,
+}
+
+/// A single comment inside a review thread.
+#[derive(Debug, Clone)]
+pub struct ReviewComment {
+    pub author: Option<String>,
+    pub body: String,
+    pub created_at: String,
+}
+
 /// A PR/MR comment that git-gud manages (identified by marker).
 #[derive(Debug, Clone)]
 pub struct ManagedComment {
@@ -89,12 +151,12 @@ impl Provider {
     /// Detect provider from config or repository URL
     ///
     /// Priority:
-    /// 1. Config `defaults.provider` if set ("github" or "gitlab")
-    /// 2. Auto-detect from remote URL (github.com, gitlab.com)
+    /// 1. Config `defaults.provider` if set ("github", "gitlab" or "gitea")
+    /// 2. Auto-detect from remote URL (github.com, gitlab.com, Gitea/Forgejo hosts)
     pub fn detect(repo: &Repository) -> Result<Self> {
         // Try to load config and check for explicit provider setting
         let common_dir = repo.commondir();
-        if let Ok(config) = Config::load_with_global(common_dir) {
+        if let Ok(config) = Config::load(common_dir) {
             if let Some(provider) = config.defaults.provider.as_deref() {
                 return Self::from_name(provider);
             }
@@ -104,17 +166,32 @@ impl Provider {
         match git::detect_remote_provider(repo) {
             Ok(git::RemoteProvider::GitHub) => Ok(Provider::GitHub),
             Ok(git::RemoteProvider::GitLab) => Ok(Provider::GitLab),
+            Ok(git::RemoteProvider::Gitea) => Ok(Provider::Gitea),
             Err(e) => Err(e),
         }
     }
 
-    /// Create provider from string ("github" or "gitlab")
+    /// Detect the provider of a stack's remote, and look PRs/MRs up in that
+    /// remote's repository from now on
+    pub fn for_stack(repo: &Repository, config: &Config, stack_name: &str) -> Result<Self> {
+        Self::for_remote(repo, config.get_remote_for_stack(stack_name))
+    }
+
+    /// Detect the provider of `remote`, and look PRs/MRs up in its
+    /// repository from now on
+    pub fn for_remote(repo: &Repository, remote: &str) -> Result<Self> {
+        git::set_provider_remote(remote);
+        Self::detect(repo)
+    }
+
+    /// Create provider from string ("github", "gitlab", "gitea" or "forgejo")
     pub fn from_name(s: &str) -> Result<Self> {
         match s.to_lowercase().as_str() {
             "github" => Ok(Provider::GitHub),
             "gitlab" => Ok(Provider::GitLab),
+            "gitea" | "forgejo" => Ok(Provider::Gitea),
             _ => Err(GgError::Other(format!(
-                "Unknown provider '{}'. Supported: github, gitlab",
+                "Unknown provider '{}'. Supported: github, gitlab, gitea",
                 s
             ))),
         }
@@ -126,6 +203,7 @@ impl Provider {
         match self {
             Provider::GitHub => "github",
             Provider::GitLab => "gitlab",
+            Provider::Gitea => "gitea",
         }
     }
 
@@ -134,6 +212,8 @@ impl Provider {
         match self {
             Provider::GitHub => gh::check_gh_installed(),
             Provider::GitLab => glab::check_glab_installed(),
+            // Talks to the REST API directly; nothing to install.
+            Provider::Gitea => Ok(()),
         }
     }
 
@@ -145,6 +225,7 @@ impl Provider {
         match self {
             Provider::GitHub => check_auth_with_network_fallback(gh::check_gh_auth()),
             Provider::GitLab => check_auth_with_network_fallback(glab::check_glab_auth()),
+            Provider::Gitea => check_auth_with_network_fallback(gitea::check_auth()),
         }
     }
 
@@ -153,6 +234,7 @@ impl Provider {
         match self {
             Provider::GitHub => gh::whoami(),
             Provider::GitLab => glab::whoami(),
+            Provider::Gitea => gitea::whoami(),
         }
     }
 
@@ -182,42 +264,96 @@ impl Provider {
                     url: result.url,
                 })
             }
+            Provider::Gitea => {
+                let (number, url) =
+                    gitea::create_pr(source_branch, target_branch, title, description, draft)?;
+                Ok(PrCreationResult { number, url })
+            }
         }
     }
 
     /// Get PR/MR information
     pub fn get_pr_info(&self, number: u64) -> Result<PrInfo> {
         match self {
-            Provider::GitHub => {
-                let info = gh::get_pr_info(number)?;
+            Provider::GitHub => Ok(convert_gh_pr_info(gh::get_pr_info(number)?)),
+            Provider::GitLab => Ok(convert_glab_mr_info(glab::get_mr_info(number)?)),
+            Provider::Gitea => {
+                let info = gitea::get_pr_info(number)?;
+                let (approved, changes_requested) = gitea::get_review_decision(number)?;
                 Ok(PrInfo {
                     number: info.number,
                     title: info.title,
-                    state: convert_gh_state(info.state),
+                    state: convert_gitea_state(info.state),
                     url: info.url,
                     head_branch: info.head_branch,
+                    base_branch: info.base_branch,
                     draft: info.draft,
-                    approved: info.approved,
+                    approved,
                     mergeable: info.mergeable,
-                    changes_requested: info.changes_requested,
+                    changes_requested,
                     detailed_merge_status: None,
+                    updated_at_ms: info.updated_at_ms,
                 })
             }
-            Provider::GitLab => {
-                let info = glab::get_mr_info(number)?;
-                Ok(PrInfo {
-                    number: info.iid,
-                    title: info.title,
-                    state: convert_glab_state(info.state),
-                    url: info.web_url,
-                    head_branch: info.head_branch,
-                    draft: info.draft,
-                    approved: info.approved,
-                    mergeable: info.mergeable,
-                    changes_requested: info.changes_requested,
-                    detailed_merge_status: info.detailed_merge_status,
+        }
+    }
+
+    /// Get PR/MR information for several PRs/MRs at once: one GraphQL query
+    /// on GitHub, one merge request list on GitLab. Gitea has no bulk API
+    /// and is asked one PR at a time. PRs/MRs missing from the map could
+    /// not be fetched.
+    pub fn get_prs_info(&self, numbers: &[u64]) -> Result<HashMap<u64, PrInfo>> {
+        match self {
+            Provider::GitHub => Ok(self
+                .get_prs_status(numbers)?
+                .into_iter()
+                .map(|(number, status)| (number, status.info))
+                .collect()),
+            Provider::GitLab => Ok(glab::get_mrs_info(numbers)?
+                .into_iter()
+                .map(|info| (info.iid, convert_glab_mr_info(info)))
+                .collect()),
+            Provider::Gitea => Ok(numbers
+                .iter()
+                .filter_map(|&number| Some((number, self.get_pr_info(number).ok()?)))
+                .collect()),
+        }
+    }
+
+    /// Like [`get_prs_info`](Self::get_prs_info), with the CI, approval and
+    /// merge queue status GitHub returns in the same GraphQL query. GitLab
+    /// and Gitea leave those to the per-PR/MR calls.
+    pub fn get_prs_status(&self, numbers: &[u64]) -> Result<HashMap<u64, PrStatus>> {
+        match self {
+            Provider::GitHub => Ok(gh::get_prs_status(numbers)?
+                .into_iter()
+                .map(|(number, status)| {
+                    (
+                        number,
+                        PrStatus {
+                            info: convert_gh_pr_info(status.info),
+                            ci_status: Some(convert_gh_ci_status(status.ci_status)),
+                            approved: Some(status.approved),
+                            merge_train: Some(status.merge_queue),
+                        },
+                    )
                 })
-            }
+                .collect()),
+            Provider::GitLab | Provider::Gitea => Ok(self
+                .get_prs_info(numbers)?
+                .into_iter()
+                .map(|(number, info)| {
+                    (
+                        number,
+                        PrStatus {
+                            info,
+                            ci_status: None,
+                            approved: None,
+                            merge_train: None,
+                        },
+                    )
+                })
+                .collect()),
         }
     }
 
@@ -226,6 +362,80 @@ impl Provider {
         match self {
             Provider::GitHub => gh::update_pr_base(number, base_branch),
             Provider::GitLab => glab::update_mr_target(number, base_branch),
+            Provider::Gitea => gitea::update_pr_base(number, base_branch),
+        }
+    }
+
+    /// Make `number` depend on `blocking` (its predecessor in the stack),
+    /// dropping dependencies on other PRs/MRs of the same stack. GitLab only
+    /// (MR dependencies); a no-op elsewhere. Returns whether anything changed.
+    pub fn sync_pr_dependency(
+        &self,
+        number: u64,
+        blocking: Option<u64>,
+        stack_numbers: &[u64],
+    ) -> Result<bool> {
+        match self {
+            Provider::GitHub | Provider::Gitea => Ok(false),
+            Provider::GitLab => glab::sync_mr_dependency(number, blocking, stack_numbers),
+        }
+    }
+
+    /// Upload an image and return Markdown embedding it, or `None` when the
+    /// provider has no upload API (GitHub, Gitea).
+    pub fn upload_image(&self, path: &std::path::Path) -> Result<Option<String>> {
+        match self {
+            Provider::GitHub | Provider::Gitea => Ok(None),
+            Provider::GitLab => glab::upload_file(path).map(Some),
+        }
+    }
+
+    /// Ask `reviewers` to review a PR/MR. Entries containing a `/` are teams
+    /// (`org/team`) on GitHub and Gitea; GitLab takes usernames only.
+    pub fn request_reviewers(&self, number: u64, reviewers: &[String]) -> Result<()> {
+        if reviewers.is_empty() {
+            return Ok(());
+        }
+        match self {
+            Provider::GitHub => gh::request_reviewers(number, reviewers),
+            Provider::GitLab => glab::request_reviewers(number, reviewers),
+            Provider::Gitea => gitea::request_reviewers(number, reviewers),
+        }
+    }
+
+    /// Add labels to a PR/MR.
+    pub fn add_labels(&self, number: u64, labels: &[String]) -> Result<()> {
+        match self {
+            Provider::GitHub => gh::add_labels(number, labels),
+            Provider::GitLab => glab::add_labels(number, labels),
+            Provider::Gitea => gitea::add_labels(number, labels),
+        }
+    }
+
+    /// Remove labels from a PR/MR.
+    pub fn remove_labels(&self, number: u64, labels: &[String]) -> Result<()> {
+        match self {
+            Provider::GitHub => gh::remove_labels(number, labels),
+            Provider::GitLab => glab::remove_labels(number, labels),
+            Provider::Gitea => gitea::remove_labels(number, labels),
+        }
+    }
+
+    /// Put a PR/MR in the milestone with this title.
+    pub fn set_milestone(&self, number: u64, milestone: &str) -> Result<()> {
+        match self {
+            Provider::GitHub => gh::set_milestone(number, milestone),
+            Provider::GitLab => glab::set_milestone(number, milestone),
+            Provider::Gitea => gitea::set_milestone(number, milestone),
+        }
+    }
+
+    /// Mark a draft PR/MR as ready for review
+    pub fn mark_ready(&self, number: u64) -> Result<()> {
+        match self {
+            Provider::GitHub => gh::mark_ready_for_review(number),
+            Provider::GitLab => glab::mark_mr_ready(number),
+            Provider::Gitea => gitea::mark_ready(number),
         }
     }
 
@@ -234,6 +444,7 @@ impl Provider {
         match self {
             Provider::GitHub => gh::close_pr(number),
             Provider::GitLab => glab::close_mr(number),
+            Provider::Gitea => gitea::close_pr(number),
         }
     }
 
@@ -242,6 +453,7 @@ impl Provider {
         match self {
             Provider::GitHub => gh::get_pr_body(number),
             Provider::GitLab => glab::get_mr_body(number),
+            Provider::Gitea => gitea::get_pr_body(number),
         }
     }
 
@@ -250,6 +462,7 @@ impl Provider {
         match self {
             Provider::GitHub => gh::update_pr_description(number, description),
             Provider::GitLab => glab::update_mr_description(number, description),
+            Provider::Gitea => gitea::update_pr_body(number, description),
         }
     }
 
@@ -278,6 +491,13 @@ impl Provider {
                         body: n.body,
                     }))
             }
+            Provider::Gitea => Ok(gitea::list_comments(pr_number)?
+                .into_iter()
+                .find(|c| stack_nav::is_managed_comment(&c.body))
+                .map(|c| ManagedComment {
+                    id: c.id,
+                    body: c.body,
+                })),
         }
     }
 
@@ -286,6 +506,39 @@ impl Provider {
         match self {
             Provider::GitHub => gh::create_issue_comment(pr_number, body),
             Provider::GitLab => glab::create_mr_note(pr_number, body),
+            Provider::Gitea => gitea::create_comment(pr_number, body),
+        }
+    }
+
+    /// List inline review threads on a PR/MR, resolved or not.
+    ///
+    /// Not available on Gitea, which has no thread resolution API.
+    pub fn list_review_threads(&self, pr_number: u64) -> Result<Vec<ReviewThread>> {
+        match self {
+            Provider::GitHub => Ok(gh::list_review_threads(pr_number)?
+                .into_iter()
+                .map(convert_gh_review_thread)
+                .collect()),
+            Provider::GitLab => Ok(glab::list_mr_discussions(pr_number)?
+                .into_iter()
+                .filter(|d| d.is_resolvable())
+                .map(convert_glab_discussion)
+                .collect()),
+            Provider::Gitea => Ok(vec![]),
+        }
+    }
+
+    /// Mark a review thread as resolved.
+    ///
+    /// `pr_number` is required for GitLab (discussions are per-MR); GitHub
+    /// ignores it because thread node ids are global.
+    pub fn resolve_review_thread(&self, pr_number: u64, thread_id: &str) -> Result<()> {
+        match self {
+            Provider::GitHub => gh::resolve_review_thread(thread_id),
+            Provider::GitLab => glab::resolve_mr_discussion(pr_number, thread_id),
+            Provider::Gitea => Err(GgError::GiteaError(
+                "Resolving review threads is not supported on Gitea".to_string(),
+            )),
         }
     }
 
@@ -297,6 +550,7 @@ impl Provider {
         match self {
             Provider::GitHub => gh::update_issue_comment(comment_id, body),
             Provider::GitLab => glab::update_mr_note(pr_number, comment_id, body),
+            Provider::Gitea => gitea::update_comment(comment_id, body),
         }
     }
 
@@ -305,6 +559,7 @@ impl Provider {
         match self {
             Provider::GitHub => gh::delete_issue_comment(comment_id),
             Provider::GitLab => glab::delete_mr_note(pr_number, comment_id),
+            Provider::Gitea => gitea::delete_comment(comment_id),
         }
     }
 
@@ -313,6 +568,7 @@ impl Provider {
         match self {
             Provider::GitHub => gh::update_pr_title(number, title),
             Provider::GitLab => glab::update_mr_title(number, title),
+            Provider::Gitea => gitea::update_pr_title(number, title),
         }
     }
 
@@ -320,42 +576,52 @@ impl Provider {
     pub fn merge_pr(
         &self,
         number: u64,
-        squash: bool,
+        strategy: MergeStrategy,
         delete_branch: bool,
         admin: bool,
     ) -> Result<()> {
         match self {
-            Provider::GitHub => gh::merge_pr(number, squash, delete_branch, admin),
+            Provider::GitHub => gh::merge_pr(number, strategy, delete_branch, admin),
             Provider::GitLab => {
                 if admin {
-                    eprintln!("⚠ --admin is not supported on GitLab; ignoring flag");
+                    eprintln!(
+                        "{} --admin is not supported on GitLab; ignoring flag",
+                        plain::warn()
+                    );
                 }
-                glab::merge_mr(number, squash, delete_branch)
+                glab::merge_mr(number, strategy, delete_branch)
+            }
+            Provider::Gitea => {
+                if admin {
+                    eprintln!(
+                        "{} --admin is not supported on Gitea; ignoring flag",
+                        plain::warn()
+                    );
+                }
+                gitea::merge_pr(number, strategy, delete_branch)
             }
         }
     }
 
-    /// Request auto-merge ("merge when pipeline succeeds").
-    ///
-    /// GitLab only.
+    /// Request auto-merge: GitLab "merge when pipeline succeeds", GitHub's
+    /// native auto-merge, or Gitea "merge when checks succeed".
     ///
     /// Returns:
     /// - `Ok(AutoMergeResult::Queued)` if successfully queued for auto-merge
     /// - `Ok(AutoMergeResult::AlreadyQueued)` if already set to auto-merge
-    /// - `Err(...)` for other errors or if provider is GitHub
+    /// - `Err(...)` for other errors
     pub fn auto_merge_pr_when_pipeline_succeeds(
         &self,
         number: u64,
-        squash: bool,
+        strategy: MergeStrategy,
         delete_branch: bool,
     ) -> Result<AutoMergeResult> {
         match self {
-            Provider::GitHub => Err(GgError::Other(
-                "Auto-merge-on-land is only supported for GitLab".to_string(),
-            )),
+            Provider::GitHub => gh::enable_auto_merge(number, strategy, delete_branch),
             Provider::GitLab => {
-                glab::auto_merge_mr_when_pipeline_succeeds(number, squash, delete_branch)
+                glab::auto_merge_mr_when_pipeline_succeeds(number, strategy, delete_branch)
             }
+            Provider::Gitea => gitea::auto_merge_pr(number, strategy, delete_branch),
         }
     }
 
@@ -364,6 +630,21 @@ impl Provider {
         match self {
             Provider::GitHub => gh::check_pr_approved(number),
             Provider::GitLab => glab::check_mr_approved(number),
+            Provider::Gitea => gitea::check_pr_approved(number),
+        }
+    }
+
+    /// Describe the approval requirements still blocking a PR/MR
+    /// (e.g. `needs 1 approval from @backend-owners`).
+    ///
+    /// GitLab only — returns an empty vec for other providers.
+    pub fn unmet_approval_requirements(&self, number: u64) -> Result<Vec<String>> {
+        match self {
+            Provider::GitHub | Provider::Gitea => Ok(vec![]),
+            Provider::GitLab => Ok(glab::get_unmet_approval_rules(number)?
+                .iter()
+                .filter_map(glab::ApprovalRule::unmet_description)
+                .collect()),
         }
     }
 
@@ -378,6 +659,10 @@ impl Provider {
                 let status = glab::get_mr_ci_status(number)?;
                 Ok(convert_glab_ci_status(status))
             }
+            Provider::Gitea => {
+                let status = gitea::get_pr_ci_status(number)?;
+                Ok(convert_gitea_ci_status(status))
+            }
         }
     }
 
@@ -387,6 +672,7 @@ impl Provider {
         match self {
             Provider::GitHub => "GitHub",
             Provider::GitLab => "GitLab",
+            Provider::Gitea => "Gitea",
         }
     }
 
@@ -396,72 +682,206 @@ impl Provider {
         match self {
             Provider::GitHub => gh::list_prs_for_branch(branch),
             Provider::GitLab => glab::list_mrs_for_branch(branch),
+            Provider::Gitea => gitea::list_prs_for_branch(branch),
+        }
+    }
+
+    /// List open PRs/MRs authored by the current user.
+    pub fn list_my_open_prs(&self) -> Result<Vec<OpenPr>> {
+        match self {
+            Provider::GitHub => Ok(gh::list_my_open_prs()?
+                .into_iter()
+                .map(|pr| OpenPr {
+                    number: pr.number,
+                    title: pr.title,
+                    head_branch: pr.head_ref_name,
+                    base_branch: pr.base_ref_name,
+                })
+                .collect()),
+            Provider::GitLab => Ok(glab::list_my_open_mrs()?
+                .into_iter()
+                .map(|mr| OpenPr {
+                    number: mr.iid,
+                    title: mr.title,
+                    head_branch: mr.source_branch,
+                    base_branch: mr.target_branch,
+                })
+                .collect()),
+            Provider::Gitea => Ok(gitea::list_my_open_prs()?
+                .into_iter()
+                .map(|pr| OpenPr {
+                    number: pr.number,
+                    title: pr.title,
+                    head_branch: pr.head_branch,
+                    base_branch: pr.base_branch,
+                })
+                .collect()),
+        }
+    }
+
+    /// Whether a remote branch can be renamed with its open PRs/MRs
+    /// following it. GitLab and Gitea cannot change a PR/MR's source branch.
+    pub fn supports_branch_rename(&self) -> bool {
+        matches!(self, Provider::GitHub)
+    }
+
+    /// Rename a remote branch, carrying open PRs along (GitHub only).
+    pub fn rename_branch(&self, branch: &str, new_name: &str) -> Result<()> {
+        match self {
+            Provider::GitHub => gh::rename_branch(branch, new_name),
+            Provider::GitLab => Err(GgError::GlabError(
+                "GitLab cannot rename a merge request's source branch".to_string(),
+            )),
+            Provider::Gitea => Err(GgError::GiteaError(
+                "Gitea cannot rename a pull request's head branch".to_string(),
+            )),
         }
     }
 
     /// Get PR/MR label (PR or MR)
     pub fn pr_label(&self) -> &'static str {
         match self {
-            Provider::GitHub => "PR",
+            Provider::GitHub | Provider::Gitea => "PR",
             Provider::GitLab => "MR",
         }
     }
 
-    /// Get PR/MR number prefix (# for GitHub, ! for GitLab)
+    /// Get PR/MR number prefix (# for GitHub and Gitea, ! for GitLab)
     pub fn pr_number_prefix(&self) -> &'static str {
         match self {
-            Provider::GitHub => "#",
+            Provider::GitHub | Provider::Gitea => "#",
             Provider::GitLab => "!",
         }
     }
 
-    /// Check if merge trains are enabled (GitLab only)
-    /// Returns false for GitHub (not supported)
-    pub fn check_merge_trains_enabled(&self) -> Result<bool> {
+    /// What the provider calls its merge queue ("merge train" on GitLab,
+    /// "merge queue" on GitHub)
+    pub fn merge_queue_name(&self) -> &'static str {
+        match self {
+            Provider::GitLab => "merge train",
+            Provider::GitHub | Provider::Gitea => "merge queue",
+        }
+    }
+
+    /// Check if merges into `target_branch` go through a merge train (GitLab,
+    /// project-wide) or merge queue (GitHub, per branch).
+    /// Returns false for Gitea (not supported)
+    pub fn check_merge_trains_enabled(&self, target_branch: &str) -> Result<bool> {
         match self {
-            Provider::GitHub => Ok(false),
+            Provider::GitHub => gh::check_merge_queue_enabled(target_branch),
             Provider::GitLab => glab::check_merge_trains_enabled(),
+            Provider::Gitea => Ok(false),
         }
     }
 
-    /// Add PR/MR to merge train (GitLab only)
-    /// Falls back to regular merge for GitHub
+    /// Add PR/MR to the merge train (GitLab) or merge queue (GitHub)
     ///
     /// Returns:
-    /// - `Ok(AutoMergeResult::Queued)` if successfully added to the merge train
-    /// - `Ok(AutoMergeResult::AlreadyQueued)` if already in the merge train
-    /// - `Err(...)` for other errors or if provider is GitHub
+    /// - `Ok(AutoMergeResult::Queued)` if successfully added to the queue
+    /// - `Ok(AutoMergeResult::AlreadyQueued)` if already queued
+    /// - `Err(...)` for other errors or if provider is Gitea
     pub fn add_to_merge_train(&self, number: u64) -> Result<AutoMergeResult> {
         match self {
-            Provider::GitHub => {
-                // GitHub doesn't support merge trains, fallback to regular merge
-                Err(GgError::Other(
-                    "Merge trains are not supported on GitHub".to_string(),
-                ))
-            }
+            Provider::GitHub => gh::add_to_merge_queue(number),
             Provider::GitLab => glab::add_to_merge_train(number),
+            Provider::Gitea => Err(GgError::GiteaError(
+                "Merge trains are not supported on Gitea".to_string(),
+            )),
+        }
+    }
+
+    /// Rebase a PR/MR onto its target branch on the server (GitLab and Gitea)
+    pub fn rebase_pr(&self, number: u64) -> Result<()> {
+        match self {
+            Provider::GitHub => Err(GgError::Other(
+                "Server-side rebase is not supported on GitHub".to_string(),
+            )),
+            Provider::GitLab => glab::rebase_mr(number),
+            Provider::Gitea => gitea::rebase_pr(number),
         }
     }
 
-    /// Get merge train status (GitLab only)
-    /// Returns None for GitHub (not supported)
+    /// Get merge train (GitLab) or merge queue (GitHub) status
+    /// Returns None for Gitea (not supported)
     pub fn get_merge_train_status(
         &self,
         number: u64,
         target_branch: &str,
     ) -> Result<Option<glab::MergeTrainInfo>> {
         match self {
-            Provider::GitHub => Ok(None),
+            Provider::GitHub => Ok(Some(gh::get_merge_queue_status(number)?)),
+            Provider::Gitea => Ok(None),
             Provider::GitLab => Ok(Some(glab::get_merge_train_status(number, target_branch)?)),
         }
     }
 
+    /// Get failed checks on a PR, split by whether branch protection
+    /// requires them.
+    ///
+    /// GitHub only — returns no checks for GitLab (see `get_failed_ci_jobs`)
+    /// and Gitea.
+    pub fn get_failed_checks(&self, number: u64) -> Result<FailedChecks> {
+        match self {
+            Provider::GitHub => gh::get_failed_checks(number),
+            Provider::GitLab | Provider::Gitea => Ok(FailedChecks::default()),
+        }
+    }
+
+    /// Re-run CI for a PR/MR, returning the names of the runs/pipelines
+    /// that were restarted.
+    ///
+    /// GitHub re-runs the Actions workflow runs of the head commit (only the
+    /// failed jobs of failed runs with `failed_only`). GitLab retries the
+    /// failed jobs of the head pipeline, or starts a new MR pipeline. Gitea
+    /// has no API to re-run Actions.
+    pub fn rerun_ci(&self, number: u64, failed_only: bool) -> Result<Vec<String>> {
+        match self {
+            Provider::GitHub => gh::rerun_pr_workflows(number, failed_only),
+            Provider::GitLab => glab::retry_mr_pipeline(number, failed_only),
+            Provider::Gitea => Err(GgError::GiteaError(
+                "Re-running CI is not supported on Gitea".to_string(),
+            )),
+        }
+    }
+
+    /// Get signed-commit and linear-history requirements for `branch`.
+    ///
+    /// GitHub reads rulesets and classic branch protection. GitLab reads the
+    /// project's push rule and merge method (fast-forward merges require a
+    /// linear history); those apply to every target branch. Gitea reads the
+    /// branch protection rule's signed-commit requirement.
+    pub fn branch_protections(&self, branch: &str) -> Result<BranchProtections> {
+        match self {
+            Provider::GitHub => {
+                let rules = gh::get_branch_rules(branch)?;
+                Ok(BranchProtections {
+                    require_signed_commits: rules.required_signatures,
+                    require_linear_history: rules.required_linear_history,
+                    signatures_enforced_on_push: false,
+                })
+            }
+            Provider::GitLab => {
+                let rules = glab::get_project_rules()?;
+                Ok(BranchProtections {
+                    require_signed_commits: rules.reject_unsigned_commits,
+                    require_linear_history: rules.fast_forward_only,
+                    signatures_enforced_on_push: true,
+                })
+            }
+            Provider::Gitea => Ok(BranchProtections {
+                require_signed_commits: gitea::requires_signed_commits(branch)?,
+                require_linear_history: false,
+                signatures_enforced_on_push: false,
+            }),
+        }
+    }
+
     /// Get failed CI jobs for a PR/MR's head pipeline.
     ///
-    /// GitLab only — returns empty vec for GitHub (not yet implemented).
+    /// GitLab only — returns empty vec for other providers.
     pub fn get_failed_ci_jobs(&self, number: u64) -> Result<Vec<FailedJob>> {
         match self {
-            Provider::GitHub => Ok(vec![]),
+            Provider::GitHub | Provider::Gitea => Ok(vec![]),
             Provider::GitLab => glab::get_mr_failed_ci_jobs(number),
         }
     }
@@ -469,6 +889,40 @@ impl Provider {
 
 // Conversion helpers
 
+fn convert_gh_pr_info(info: gh::PrInfo) -> PrInfo {
+    PrInfo {
+        number: info.number,
+        title: info.title,
+        state: convert_gh_state(info.state),
+        url: info.url,
+        head_branch: info.head_branch,
+        base_branch: info.base_branch,
+        draft: info.draft,
+        approved: info.approved,
+        mergeable: info.mergeable,
+        changes_requested: info.changes_requested,
+        detailed_merge_status: None,
+        updated_at_ms: info.updated_at_ms,
+    }
+}
+
+fn convert_glab_mr_info(info: glab::MrInfo) -> PrInfo {
+    PrInfo {
+        number: info.iid,
+        title: info.title,
+        state: convert_glab_state(info.state),
+        url: info.web_url,
+        head_branch: info.head_branch,
+        base_branch: info.base_branch,
+        draft: info.draft,
+        approved: info.approved,
+        mergeable: info.mergeable,
+        changes_requested: info.changes_requested,
+        detailed_merge_status: info.detailed_merge_status,
+        updated_at_ms: info.updated_at_ms,
+    }
+}
+
 fn convert_gh_state(state: GhPrState) -> PrState {
     match state {
         GhPrState::Open => PrState::Open,
@@ -487,6 +941,15 @@ fn convert_glab_state(state: GlabMrState) -> PrState {
     }
 }
 
+fn convert_gitea_state(state: GiteaPullState) -> PrState {
+    match state {
+        GiteaPullState::Open => PrState::Open,
+        GiteaPullState::Merged => PrState::Merged,
+        GiteaPullState::Closed => PrState::Closed,
+        GiteaPullState::Draft => PrState::Draft,
+    }
+}
+
 fn convert_gh_ci_status(status: GhCiStatus) -> CiStatus {
     match status {
         GhCiStatus::Pending => CiStatus::Pending,
@@ -509,6 +972,59 @@ fn convert_glab_ci_status(status: GlabCiStatus) -> CiStatus {
     }
 }
 
+fn convert_gitea_ci_status(status: GiteaCiStatus) -> CiStatus {
+    match status {
+        GiteaCiStatus::Pending => CiStatus::Pending,
+        GiteaCiStatus::Success => CiStatus::Success,
+        GiteaCiStatus::Failed => CiStatus::Failed,
+        GiteaCiStatus::Unknown => CiStatus::Unknown,
+    }
+}
+
+fn convert_gh_review_thread(thread: gh::ReviewThread) -> ReviewThread {
+    ReviewThread {
+        id: thread.id,
+        resolved: thread.is_resolved,
+        outdated: thread.is_outdated,
+        path: thread.path,
+        line: thread.line.or(thread.original_line),
+        comments: thread
+            .comments
+            .nodes
+            .into_iter()
+            .map(|c| ReviewComment {
+                author: c.author.map(|a| a.login),
+                body: c.body,
+                created_at: c.created_at,
+            })
+            .collect(),
+    }
+}
+
+fn convert_glab_discussion(discussion: glab::MrDiscussion) -> ReviewThread {
+    let resolved = discussion.is_resolved();
+    let position = discussion.notes.first().and_then(|n| n.position.clone());
+    ReviewThread {
+        id: discussion.id,
+        resolved,
+        outdated: false,
+        path: position
+            .as_ref()
+            .and_then(|p| p.new_path.clone().or_else(|| p.old_path.clone())),
+        line: position.as_ref().and_then(|p| p.new_line.or(p.old_line)),
+        comments: discussion
+            .notes
+            .into_iter()
+            .filter(|n| !n.system)
+            .map(|n| ReviewComment {
+                author: n.author.map(|a| a.username),
+                body: n.body,
+                created_at: n.created_at,
+            })
+            .collect(),
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -544,6 +1060,7 @@ mod tests {
     fn test_provider_name() {
         assert_eq!(Provider::GitHub.name(), "GitHub");
         assert_eq!(Provider::GitLab.name(), "GitLab");
+        assert_eq!(Provider::Gitea.name(), "Gitea");
     }
 
     #[test]
@@ -566,11 +1083,13 @@ mod tests {
             state: PrState::Open,
             url: "https://example.com/pr/42".to_string(),
             head_branch: Some("user/stack--c-abc1234".to_string()),
+            base_branch: None,
             draft: false,
             approved: true,
             mergeable: true,
             changes_requested: false,
             detailed_merge_status: None,
+            updated_at_ms: None,
         };
         assert_eq!(info.number, 42);
         assert_eq!(info.title, "Test PR");
@@ -667,6 +1186,8 @@ mod tests {
         assert_eq!(Provider::from_name("gitlab").unwrap(), Provider::GitLab);
         assert_eq!(Provider::from_name("GitLab").unwrap(), Provider::GitLab);
         assert_eq!(Provider::from_name("GITLAB").unwrap(), Provider::GitLab);
+        assert_eq!(Provider::from_name("gitea").unwrap(), Provider::Gitea);
+        assert_eq!(Provider::from_name("Forgejo").unwrap(), Provider::Gitea);
 
         // Invalid providers
         assert!(Provider::from_name("bitbucket").is_err());
@@ -678,6 +1199,7 @@ mod tests {
     fn test_provider_as_config_str() {
         assert_eq!(Provider::GitHub.as_config_str(), "github");
         assert_eq!(Provider::GitLab.as_config_str(), "gitlab");
+        assert_eq!(Provider::Gitea.as_config_str(), "gitea");
     }
 
     #[test]
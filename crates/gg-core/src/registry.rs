@@ -0,0 +1,105 @@
+//! Registry of repositories known to gg, for `gg ls --global`
+//!
+//! `gg setup --register` records a repository in
+//! `~/.config/gg/repos.json`, next to the global config, so stacks can be
+//! listed across every registered repository at once.
+
+use std::fs;
+use std::path::{Path, PathBuf};
+
+use git2::Repository;
+use serde::{Deserialize, Serialize};
+
+use crate::config::Config;
+use crate::error::{GgError, Result};
+use crate::operations::now_ms;
+
+const REGISTRY_FILE: &str = "repos.json";
+
+/// A repository recorded by `gg setup --register`.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct RegisteredRepo {
+    /// Main working copy (or the git dir of a bare repository)
+    pub path: PathBuf,
+    pub registered_at_ms: u64,
+}
+
+#[derive(Debug, Default, Serialize, Deserialize)]
+pub struct Registry {
+    #[serde(default)]
+    pub repos: Vec<RegisteredRepo>,
+}
+
+impl Registry {
+    /// `~/.config/gg/repos.json`
+    pub fn path() -> Option<PathBuf> {
+        Config::global_config_dir().map(|d| d.join(REGISTRY_FILE))
+    }
+
+    /// Load the registry; empty when it does not exist yet.
+    pub fn load() -> Result<Self> {
+        let Some(path) = Self::path() else {
+            return Ok(Self::default());
+        };
+        if !path.exists() {
+            return Ok(Self::default());
+        }
+        Ok(serde_json::from_str(&fs::read_to_string(path)?)?)
+    }
+
+    fn save(&self) -> Result<()> {
+        let path = Self::path()
+            .ok_or_else(|| GgError::Other("Could not determine home directory".to_string()))?;
+        if let Some(parent) = path.parent() {
+            fs::create_dir_all(parent)?;
+        }
+        let temp_path = path.with_extension("tmp");
+        fs::write(&temp_path, serde_json::to_string_pretty(self)?)?;
+        fs::rename(&temp_path, &path)?;
+        Ok(())
+    }
+
+    /// Record `repo`. Returns its registered path and whether it was new.
+    pub fn register(repo: &Repository) -> Result<(PathBuf, bool)> {
+        let path = repo_path(repo);
+        let mut registry = Self::load()?;
+        if registry.repos.iter().any(|r| r.path == path) {
+            return Ok((path, false));
+        }
+        registry.repos.push(RegisteredRepo {
+            path: path.clone(),
+            registered_at_ms: now_ms(),
+        });
+        registry.save()?;
+        Ok((path, true))
+    }
+
+    /// Forget `repo`. Returns its path and whether it was registered.
+    pub fn unregister(repo: &Repository) -> Result<(PathBuf, bool)> {
+        let path = repo_path(repo);
+        let mut registry = Self::load()?;
+        let before = registry.repos.len();
+        registry.repos.retain(|r| r.path != path);
+        if registry.repos.len() == before {
+            return Ok((path, false));
+        }
+        registry.save()?;
+        Ok((path, true))
+    }
+}
+
+/// Path a repository is registered under: its main working copy, so every
+/// worktree of a clone maps to the same entry.
+fn repo_path(repo: &Repository) -> PathBuf {
+    let common = repo.commondir();
+    let path = if common.file_name().is_some_and(|n| n == ".git") {
+        common.parent().unwrap_or(common)
+    } else {
+        common
+    };
+    canonical(path)
+}
+
+fn canonical(path: &Path) -> PathBuf {
+    fs::canonicalize(path).unwrap_or_else(|_| path.to_path_buf())
+}
@@ -0,0 +1,178 @@
+//! Relative timestamps ("3h ago") for human-readable output.
+//!
+//! Recent times are shown relative to now; anything older than
+//! [`RELATIVE_LIMIT_DAYS`] falls back to a calendar date written in the
+//! order the user's locale (`LC_ALL` / `LC_TIME` / `LANG`) expects. JSON
+//! output keeps raw `*_at_ms` values instead.
+
+use std::time::{SystemTime, UNIX_EPOCH};
+
+const MINUTE_MS: u64 = 60 * 1000;
+const HOUR_MS: u64 = 60 * MINUTE_MS;
+const DAY_MS: u64 = 24 * HOUR_MS;
+
+/// Times older than this many days are shown as a date instead.
+pub const RELATIVE_LIMIT_DAYS: u64 = 30;
+
+/// Current time in milliseconds since the Unix epoch.
+pub fn now_ms() -> u64 {
+    SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map(|d| d.as_millis() as u64)
+        .unwrap_or(0)
+}
+
+/// Format `then_ms` relative to `now_ms` using the current locale.
+pub fn format(then_ms: u64, now_ms: u64) -> String {
+    format_with_locale(then_ms, now_ms, &current_locale())
+}
+
+/// Format `then_ms` relative to `now_ms` for `locale` (e.g. `en_US.UTF-8`).
+pub fn format_with_locale(then_ms: u64, now_ms: u64, locale: &str) -> String {
+    let elapsed = now_ms.saturating_sub(then_ms);
+    if elapsed < MINUTE_MS {
+        "just now".to_string()
+    } else if elapsed < HOUR_MS {
+        format!("{}m ago", elapsed / MINUTE_MS)
+    } else if elapsed < DAY_MS {
+        format!("{}h ago", elapsed / HOUR_MS)
+    } else if elapsed < RELATIVE_LIMIT_DAYS * DAY_MS {
+        format!("{}d ago", elapsed / DAY_MS)
+    } else {
+        format_date(then_ms, locale)
+    }
+}
+
+/// The locale governing date formatting, empty when unset.
+fn current_locale() -> String {
+    ["LC_ALL", "LC_TIME", "LANG"]
+        .iter()
+        .filter_map(|var| std::env::var(var).ok())
+        .find(|value| !value.is_empty())
+        .unwrap_or_default()
+}
+
+/// Write the calendar date of `ms` in the order `locale` expects.
+fn format_date(ms: u64, locale: &str) -> String {
+    let (year, month, day) = civil_from_days((ms / DAY_MS) as i64);
+    let lang = locale.split(['.', '@']).next().unwrap_or("");
+    let language = lang.split('_').next().unwrap_or("");
+
+    match (lang, language) {
+        ("en_US" | "en_PH", _) => format!("{:02}/{:02}/{}", month, day, year),
+        ("" | "C" | "POSIX", _) | (_, "ja" | "zh" | "ko" | "sv" | "lt" | "hu") => {
+            format!("{}-{:02}-{:02}", year, month, day)
+        }
+        (
+            _,
+            "de" | "ru" | "pl" | "cs" | "sk" | "fi" | "nb" | "nn" | "da" | "tr" | "uk" | "ro"
+            | "bg" | "hr" | "sl" | "sr" | "et" | "lv" | "is",
+        ) => format!("{:02}.{:02}.{}", day, month, year),
+        _ => format!("{:02}/{:02}/{}", day, month, year),
+    }
+}
+
+/// Parse an RFC 3339 timestamp (`2024-01-15T10:30:00Z`,
+/// `2024-01-15T10:30:00.123+02:00`) into milliseconds since the epoch.
+pub fn parse_rfc3339_ms(value: &str) -> Option<u64> {
+    let value = value.trim();
+    let (date, time) = value.split_once(['T', 't', ' '])?;
+
+    let mut date_parts = date.splitn(3, '-');
+    let year: i64 = date_parts.next()?.parse().ok()?;
+    let month: u32 = date_parts.next()?.parse().ok()?;
+    let day: u32 = date_parts.next()?.parse().ok()?;
+    if !(1..=12).contains(&month) || !(1..=31).contains(&day) {
+        return None;
+    }
+
+    // Split off the zone: `Z`, `+HH:MM` or `-HH:MM`.
+    let (clock, offset_secs) = if let Some(clock) = time.strip_suffix(['Z', 'z']) {
+        (clock, 0i64)
+    } else {
+        let idx = time.rfind(['+', '-'])?;
+        let (clock, zone) = time.split_at(idx);
+        let sign = if zone.starts_with('-') { -1 } else { 1 };
+        let (hours, minutes) = zone[1..].split_once(':')?;
+        let secs = hours.parse::<i64>().ok()? * 3600 + minutes.parse::<i64>().ok()? * 60;
+        (clock, sign * secs)
+    };
+
+    let (clock, fraction) = clock.split_once('.').unwrap_or((clock, ""));
+    let mut clock_parts = clock.splitn(3, ':');
+    let hour: i64 = clock_parts.next()?.parse().ok()?;
+    let minute: i64 = clock_parts.next()?.parse().ok()?;
+    let second: i64 = clock_parts.next()?.parse().ok()?;
+    let millis: i64 = format!("{:0<3}", fraction).get(..3)?.parse().ok()?;
+
+    let days = days_from_civil(year, month, day);
+    let secs = days * 86_400 + hour * 3600 + minute * 60 + second - offset_secs;
+    u64::try_from(secs * 1000 + millis).ok()
+}
+
+/// Days since 1970-01-01 of a proleptic Gregorian date.
+fn days_from_civil(year: i64, month: u32, day: u32) -> i64 {
+    let year = if month <= 2 { year - 1 } else { year };
+    let era = year.div_euclid(400);
+    let yoe = year - era * 400;
+    let month = month as i64;
+    let doy = (153 * (if month > 2 { month - 3 } else { month + 9 }) + 2) / 5 + day as i64 - 1;
+    let doe = yoe * 365 + yoe / 4 - yoe / 100 + doy;
+    era * 146_097 + doe - 719_468
+}
+
+/// Inverse of [`days_from_civil`]: `(year, month, day)`.
+pub(crate) fn civil_from_days(days: i64) -> (i64, u32, u32) {
+    let z = days + 719_468;
+    let era = z.div_euclid(146_097);
+    let doe = z - era * 146_097;
+    let yoe = (doe - doe / 1460 + doe / 36_524 - doe / 146_096) / 365;
+    let doy = doe - (365 * yoe + yoe / 4 - yoe / 100);
+    let mp = (5 * doy + 2) / 153;
+    let day = (doy - (153 * mp + 2) / 5 + 1) as u32;
+    let month = if mp < 10 { mp + 3 } else { mp - 9 } as u32;
+    let year = yoe + era * 400 + i64::from(month <= 2);
+    (year, month, day)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn relative_units() {
+        let now = 1_700_000_000_000;
+        assert_eq!(format_with_locale(now - 5_000, now, ""), "just now");
+        assert_eq!(format_with_locale(now - 5 * MINUTE_MS, now, ""), "5m ago");
+        assert_eq!(format_with_locale(now - 3 * HOUR_MS, now, ""), "3h ago");
+        assert_eq!(format_with_locale(now - 12 * DAY_MS, now, ""), "12d ago");
+        // Clock skew: a time in the future is "just now", not a panic.
+        assert_eq!(format_with_locale(now + HOUR_MS, now, ""), "just now");
+    }
+
+    #[test]
+    fn old_times_use_locale_date_order() {
+        let then = parse_rfc3339_ms("2024-03-05T10:00:00Z").unwrap();
+        let now = then + 90 * DAY_MS;
+        assert_eq!(format_with_locale(then, now, "C.UTF-8"), "2024-03-05");
+        assert_eq!(format_with_locale(then, now, "en_US.UTF-8"), "03/05/2024");
+        assert_eq!(format_with_locale(then, now, "en_GB.UTF-8"), "05/03/2024");
+        assert_eq!(format_with_locale(then, now, "de_DE.UTF-8"), "05.03.2024");
+        assert_eq!(format_with_locale(then, now, "ja_JP.UTF-8"), "2024-03-05");
+    }
+
+    #[test]
+    fn parses_rfc3339_variants() {
+        assert_eq!(parse_rfc3339_ms("1970-01-01T00:00:00Z"), Some(0));
+        assert_eq!(
+            parse_rfc3339_ms("2023-11-14T22:13:20.5Z"),
+            Some(1_700_000_000_500)
+        );
+        assert_eq!(
+            parse_rfc3339_ms("2023-11-15T00:13:20+02:00"),
+            Some(1_700_000_000_000)
+        );
+        assert_eq!(parse_rfc3339_ms("not a date"), None);
+        assert_eq!(parse_rfc3339_ms("2023-13-01T00:00:00Z"), None);
+    }
+}
@@ -0,0 +1,264 @@
+//! In-memory rebases
+//!
+//! Rewrites commits onto new parents with git2's merge machinery, writing
+//! only new objects: the working tree, the index and every ref are left
+//! alone. A step that conflicts stops the replay with nothing to clean up,
+//! so callers fall back to `git rebase`, which stops for the user to
+//! resolve the conflict.
+
+use git2::{Oid, Repository, Sort};
+
+use crate::error::Result;
+use crate::signing;
+
+/// One step of a replay.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Step {
+    /// Cherry-pick the commit on top of the previous result
+    Pick(Oid),
+    /// Fold the commit's changes into the previous result, keeping that
+    /// result's message (`fixup` in a rebase todo list)
+    Fixup(Oid),
+}
+
+/// Commits reachable from `to` but not from `from`, oldest first.
+pub fn commits_between(repo: &Repository, from: Oid, to: Oid) -> Result<Vec<Oid>> {
+    let mut walk = repo.revwalk()?;
+    walk.set_sorting(Sort::TOPOLOGICAL | Sort::REVERSE)?;
+    walk.push(to)?;
+    walk.hide(from)?;
+    Ok(walk.collect::<std::result::Result<Vec<_>, _>>()?)
+}
+
+/// Replay `steps` onto `onto` and return the new tip. Commits keep their
+/// author and message; the committer is the current user, as with
+/// `git rebase`. Returns `None` when a step conflicts, a commit is a
+/// merge, or a `Fixup` has nothing before it to fold into.
+pub fn replay(repo: &Repository, onto: Oid, steps: &[Step]) -> Result<Option<Oid>> {
+    let mut tip = repo.find_commit(onto)?;
+    let mut picked_any = false;
+    for step in steps {
+        let (oid, fixup) = match *step {
+            Step::Pick(oid) => (oid, false),
+            Step::Fixup(oid) => (oid, true),
+        };
+        let commit = repo.find_commit(oid)?;
+        if commit.parent_count() != 1 || (fixup && !picked_any) {
+            return Ok(None);
+        }
+
+        // Already in place: keep it, like `git rebase` does
+        if !fixup && commit.parent_id(0)? == tip.id() {
+            tip = commit;
+            picked_any = true;
+            continue;
+        }
+
+        let mut index = repo.cherrypick_commit(&commit, &tip, 0, None)?;
+        if index.has_conflicts() {
+            return Ok(None);
+        }
+        let tree = repo.find_tree(index.write_tree_to(repo)?)?;
+        let committer = repo
+            .signature()
+            .unwrap_or_else(|_| commit.committer().to_owned());
+
+        let new_oid = if fixup {
+            let parents: Vec<git2::Commit> = tip.parents().collect();
+            let parents: Vec<&git2::Commit> = parents.iter().collect();
+            signing::create_commit(
+                repo,
+                &tip.author(),
+                &committer,
+                tip.message_raw().unwrap_or(""),
+                &tree,
+                &parents,
+            )?
+        } else {
+            signing::create_commit(
+                repo,
+                &commit.author(),
+                &committer,
+                commit.message_raw().unwrap_or(""),
+                &tree,
+                &[&tip],
+            )?
+        };
+        tip = repo.find_commit(new_oid)?;
+        picked_any = true;
+    }
+    Ok(Some(tip.id()))
+}
+
+/// The todo list `git rebase --autosquash` would build for `commits`
+/// (oldest first): each `fixup! <subject>` commit moves right after the
+/// commit it fixes. `None` when it takes more than picks and fixups
+/// (`squash!` and `amend!` commits edit messages).
+pub fn autosquash_steps(repo: &Repository, commits: &[Oid]) -> Result<Option<Vec<Step>>> {
+    // (target, its subject, fixups folded into it)
+    let mut groups: Vec<(Oid, String, Vec<Oid>)> = Vec::new();
+    for &oid in commits {
+        let commit = repo.find_commit(oid)?;
+        let subject = commit.summary().ok().flatten().unwrap_or("").to_string();
+        if subject.starts_with("squash! ") || subject.starts_with("amend! ") {
+            return Ok(None);
+        }
+
+        let mut key = subject.as_str();
+        while let Some(rest) = key.strip_prefix("fixup! ") {
+            key = rest;
+        }
+        let target = (key.len() != subject.len())
+            .then(|| {
+                groups.iter().position(|(target, target_subject, _)| {
+                    target_subject == key || (key.len() >= 4 && target.to_string().starts_with(key))
+                })
+            })
+            .flatten();
+        match target {
+            Some(i) => groups[i].2.push(oid),
+            None => groups.push((oid, subject, Vec::new())),
+        }
+    }
+
+    Ok(Some(
+        groups
+            .into_iter()
+            .flat_map(|(target, _, fixups)| {
+                std::iter::once(Step::Pick(target)).chain(fixups.into_iter().map(Step::Fixup))
+            })
+            .collect(),
+    ))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::path::Path;
+
+    fn commit_files(repo: &Repository, files: &[(&str, &str)], message: &str) -> Oid {
+        let mut index = repo.index().unwrap();
+        for (name, content) in files {
+            std::fs::write(repo.workdir().unwrap().join(name), content).unwrap();
+            index.add_path(Path::new(name)).unwrap();
+        }
+        index.write().unwrap();
+        let tree = repo.find_tree(index.write_tree().unwrap()).unwrap();
+        let sig = repo.signature().unwrap();
+        let parent = repo.head().ok().map(|h| h.peel_to_commit().unwrap());
+        let parents: Vec<&git2::Commit> = parent.iter().collect();
+        repo.commit(Some("HEAD"), &sig, &sig, message, &tree, &parents)
+            .unwrap()
+    }
+
+    fn file_at(repo: &Repository, oid: Oid, name: &str) -> Option<String> {
+        let entry = repo
+            .find_commit(oid)
+            .unwrap()
+            .tree()
+            .unwrap()
+            .get_path(Path::new(name))
+            .ok()?;
+        let blob = entry.to_object(repo).unwrap().peel_to_blob().unwrap();
+        Some(String::from_utf8_lossy(blob.content()).to_string())
+    }
+
+    fn test_repo() -> (tempfile::TempDir, Repository) {
+        let dir = tempfile::tempdir().unwrap();
+        let repo = Repository::init(dir.path()).unwrap();
+        let mut config = repo.config().unwrap();
+        config.set_str("user.name", "Test").unwrap();
+        config.set_str("user.email", "test@example.com").unwrap();
+        (dir, repo)
+    }
+
+    #[test]
+    fn replay_picks_onto_a_rewritten_commit() {
+        let (_dir, repo) = test_repo();
+        let base = commit_files(&repo, &[("a.txt", "a\n")], "Base");
+        let first = commit_files(&repo, &[("a.txt", "a1\n")], "Change a");
+        let second = commit_files(&repo, &[("b.txt", "b\n")], "Add b");
+        let head_before = repo.head().unwrap().target().unwrap();
+
+        // An amended #1, as left behind by `gg sc` in the middle of a stack
+        let sig = repo.signature().unwrap();
+        let amended_tree = {
+            let mut index = repo
+                .cherrypick_commit(
+                    &repo.find_commit(first).unwrap(),
+                    &repo.find_commit(base).unwrap(),
+                    0,
+                    None,
+                )
+                .unwrap();
+            repo.find_tree(index.write_tree_to(&repo).unwrap()).unwrap()
+        };
+        let amended = repo
+            .commit(
+                None,
+                &sig,
+                &sig,
+                "Change a (amended)",
+                &amended_tree,
+                &[&repo.find_commit(base).unwrap()],
+            )
+            .unwrap();
+
+        let commits = commits_between(&repo, first, second).unwrap();
+        assert_eq!(commits, vec![second]);
+        let steps: Vec<Step> = commits.into_iter().map(Step::Pick).collect();
+        let tip = replay(&repo, amended, &steps).unwrap().unwrap();
+
+        let tip_commit = repo.find_commit(tip).unwrap();
+        assert_eq!(tip_commit.parent_id(0).unwrap(), amended);
+        assert_eq!(tip_commit.summary().unwrap(), Some("Add b"));
+        assert_eq!(file_at(&repo, tip, "b.txt").as_deref(), Some("b\n"));
+        // Nothing but new objects was written
+        assert_eq!(repo.head().unwrap().target().unwrap(), head_before);
+    }
+
+    #[test]
+    fn replay_stops_on_conflicts() {
+        let (_dir, repo) = test_repo();
+        let base = commit_files(&repo, &[("a.txt", "a\n")], "Base");
+        let ours = commit_files(&repo, &[("a.txt", "ours\n")], "Ours");
+        repo.set_head_detached(base).unwrap();
+        let theirs = commit_files(&repo, &[("a.txt", "theirs\n")], "Theirs");
+
+        assert_eq!(replay(&repo, ours, &[Step::Pick(theirs)]).unwrap(), None);
+    }
+
+    #[test]
+    fn autosquash_folds_fixups_into_their_targets() {
+        let (_dir, repo) = test_repo();
+        let base = commit_files(&repo, &[("a.txt", "a\n")], "Base");
+        let first = commit_files(&repo, &[("a.txt", "a\none\n")], "Add one");
+        let second = commit_files(&repo, &[("b.txt", "two\n")], "Add two");
+        let fixup = commit_files(&repo, &[("a.txt", "a\nONE\n")], "fixup! Add one");
+
+        let commits = commits_between(&repo, base, fixup).unwrap();
+        let steps = autosquash_steps(&repo, &commits).unwrap().unwrap();
+        assert_eq!(
+            steps,
+            vec![Step::Pick(first), Step::Fixup(fixup), Step::Pick(second)]
+        );
+
+        let tip = replay(&repo, base, &steps).unwrap().unwrap();
+        let tip_commit = repo.find_commit(tip).unwrap();
+        assert_eq!(tip_commit.summary().unwrap(), Some("Add two"));
+        let folded = tip_commit.parent_id(0).unwrap();
+        assert_eq!(
+            repo.find_commit(folded).unwrap().summary().unwrap(),
+            Some("Add one")
+        );
+        assert_eq!(file_at(&repo, folded, "a.txt").as_deref(), Some("a\nONE\n"));
+        assert_eq!(
+            repo.find_commit(tip).unwrap().tree_id(),
+            repo.find_commit(fixup).unwrap().tree_id()
+        );
+
+        let squash = commit_files(&repo, &[("a.txt", "a\n1\n")], "squash! Add one");
+        let commits = commits_between(&repo, base, squash).unwrap();
+        assert_eq!(autosquash_steps(&repo, &commits).unwrap(), None);
+    }
+}
@@ -0,0 +1,263 @@
+//! Per-stack path scopes for monorepos.
+//!
+//! A stack may declare the paths it is allowed to touch (e.g.
+//! `services/payments/**`). `gg lint` keeps its automatic fixes inside the
+//! scope, `gg absorb` refuses staged changes outside it, and `gg sc` /
+//! `gg sync` warn when a commit strays. An empty scope means "everything".
+//!
+//! Patterns are matched against repository-relative paths with `/`
+//! separators: `*` and `?` stay within one path segment, `**` spans any
+//! number of segments, and a pattern without wildcards also matches
+//! everything below it as a directory.
+
+use std::process::Command;
+
+use console::style;
+use git2::{Oid, Repository, Status};
+
+use crate::error::{GgError, Result};
+use crate::stack::StackEntry;
+
+/// Whether `path` matches the glob `pattern`.
+pub fn glob_match(pattern: &str, path: &str) -> bool {
+    let pattern = pattern.trim_start_matches("./").trim_end_matches('/');
+    let pattern_segments: Vec<&str> = pattern.split('/').filter(|s| !s.is_empty()).collect();
+    let path_segments: Vec<&str> = path.split('/').filter(|s| !s.is_empty()).collect();
+
+    if !pattern.contains(['*', '?']) {
+        // Plain paths name a file or a whole directory.
+        return path_segments.starts_with(&pattern_segments);
+    }
+    match_segments(&pattern_segments, &path_segments)
+}
+
+fn match_segments(pattern: &[&str], path: &[&str]) -> bool {
+    match pattern.split_first() {
+        None => path.is_empty(),
+        Some((&"**", rest)) => (0..=path.len()).any(|skip| match_segments(rest, &path[skip..])),
+        Some((segment, rest)) => match path.split_first() {
+            Some((name, path_rest)) => {
+                match_segment(segment.as_bytes(), name.as_bytes())
+                    && match_segments(rest, path_rest)
+            }
+            None => false,
+        },
+    }
+}
+
+fn match_segment(pattern: &[u8], name: &[u8]) -> bool {
+    match pattern.split_first() {
+        None => name.is_empty(),
+        Some((b'*', rest)) => (0..=name.len()).any(|skip| match_segment(rest, &name[skip..])),
+        Some((b'?', rest)) => !name.is_empty() && match_segment(rest, &name[1..]),
+        Some((c, rest)) => name.first() == Some(c) && match_segment(rest, &name[1..]),
+    }
+}
+
+/// Whether `path` is inside `scope`. An empty scope contains every path.
+pub fn contains(scope: &[String], path: &str) -> bool {
+    scope.is_empty() || scope.iter().any(|pattern| glob_match(pattern, path))
+}
+
+/// The paths of `paths` that fall outside `scope`.
+pub fn out_of_scope<I, S>(scope: &[String], paths: I) -> Vec<String>
+where
+    I: IntoIterator<Item = S>,
+    S: AsRef<str>,
+{
+    paths
+        .into_iter()
+        .filter(|p| !contains(scope, p.as_ref()))
+        .map(|p| p.as_ref().to_string())
+        .collect()
+}
+
+/// Paths changed by `commit` relative to its first parent.
+pub fn commit_paths(repo: &Repository, commit: Oid) -> Result<Vec<String>> {
+    let commit = repo.find_commit(commit)?;
+    let tree = commit.tree()?;
+    let parent_tree = match commit.parent(0) {
+        Ok(parent) => Some(parent.tree()?),
+        Err(_) => None,
+    };
+    let diff = repo.diff_tree_to_tree(parent_tree.as_ref(), Some(&tree), None)?;
+
+    let mut paths = Vec::new();
+    for delta in diff.deltas() {
+        for file in [delta.old_file(), delta.new_file()] {
+            if let Some(path) = file.path().and_then(|p| p.to_str()) {
+                if !paths.iter().any(|p| p == path) {
+                    paths.push(path.to_string());
+                }
+            }
+        }
+    }
+    Ok(paths)
+}
+
+/// Paths with staged changes in the index.
+pub fn staged_paths(repo: &Repository) -> Result<Vec<String>> {
+    let staged = Status::INDEX_NEW
+        | Status::INDEX_MODIFIED
+        | Status::INDEX_DELETED
+        | Status::INDEX_RENAMED
+        | Status::INDEX_TYPECHANGE;
+    Ok(repo
+        .statuses(None)?
+        .iter()
+        .filter(|s| s.status().intersects(staged))
+        .filter_map(|s| s.path().ok().map(str::to_string))
+        .collect())
+}
+
+/// Throw away working-tree changes outside `scope`, leaving in-scope ones
+/// in place. Returns the discarded paths.
+pub fn discard_out_of_scope_changes(repo: &Repository, scope: &[String]) -> Result<Vec<String>> {
+    if scope.is_empty() {
+        return Ok(Vec::new());
+    }
+    let repo_root = repo
+        .workdir()
+        .ok_or_else(|| GgError::Other("Repository has no working directory".to_string()))?;
+
+    let mut tracked = Vec::new();
+    let mut discarded = Vec::new();
+    for status in repo.statuses(None)?.iter() {
+        let Ok(path) = status.path() else {
+            continue;
+        };
+        if contains(scope, path) {
+            continue;
+        }
+        if status.status().contains(Status::WT_NEW) {
+            let full = repo_root.join(path);
+            if full.is_dir() {
+                std::fs::remove_dir_all(&full)?;
+            } else {
+                std::fs::remove_file(&full)?;
+            }
+        } else {
+            tracked.push(path.to_string());
+        }
+        discarded.push(path.to_string());
+    }
+
+    if !tracked.is_empty() {
+        let output = Command::new("git")
+            .args(["checkout", "HEAD", "--"])
+            .args(&tracked)
+            .current_dir(repo_root)
+            .output()?;
+        if !output.status.success() {
+            return Err(GgError::Other(format!(
+                "Failed to discard changes outside the stack scope: {}",
+                String::from_utf8_lossy(&output.stderr).trim()
+            )));
+        }
+    }
+    Ok(discarded)
+}
+
+/// A stack entry that touches files outside the stack scope.
+#[derive(Debug, Clone)]
+pub struct ScopeViolation {
+    pub position: usize,
+    pub short_sha: String,
+    pub title: String,
+    pub paths: Vec<String>,
+}
+
+/// Check `entries` against `scope`.
+pub fn check_entries(
+    repo: &Repository,
+    scope: &[String],
+    entries: &[&StackEntry],
+) -> Result<Vec<ScopeViolation>> {
+    if scope.is_empty() {
+        return Ok(Vec::new());
+    }
+    let mut found = Vec::new();
+    for entry in entries {
+        let paths = out_of_scope(scope, commit_paths(repo, entry.oid)?);
+        if !paths.is_empty() {
+            found.push(ScopeViolation {
+                position: entry.position,
+                short_sha: entry.short_sha.clone(),
+                title: entry.title.clone(),
+                paths,
+            });
+        }
+    }
+    Ok(found)
+}
+
+/// Print a warning for each entry touching files outside `scope`.
+pub fn warn_entries(repo: &Repository, scope: &[String], entries: &[&StackEntry]) -> Result<()> {
+    let violations = check_entries(repo, scope, entries)?;
+    if violations.is_empty() {
+        return Ok(());
+    }
+    println!(
+        "{} Commits touch files outside the stack scope ({}):",
+        style("Warning:").yellow(),
+        scope.join(", ")
+    );
+    for v in &violations {
+        println!(
+            "  {} {} {}",
+            style(format!("#{}", v.position)).dim(),
+            style(&v.short_sha).yellow(),
+            v.title
+        );
+        for path in &v.paths {
+            println!("      {}", style(path).dim());
+        }
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn double_star_spans_directories() {
+        assert!(glob_match("services/payments/**", "services/payments/a.rs"));
+        assert!(glob_match(
+            "services/payments/**",
+            "services/payments/src/deep/b.rs"
+        ));
+        assert!(!glob_match("services/payments/**", "services/billing/a.rs"));
+        assert!(glob_match("**/*.md", "README.md"));
+        assert!(glob_match("**/*.md", "docs/src/intro.md"));
+    }
+
+    #[test]
+    fn single_star_stays_within_a_segment() {
+        assert!(glob_match(
+            "services/*/Cargo.toml",
+            "services/api/Cargo.toml"
+        ));
+        assert!(!glob_match("services/*", "services/api/Cargo.toml"));
+        assert!(glob_match("lib?.rs", "lib1.rs"));
+        assert!(!glob_match("lib?.rs", "lib.rs"));
+    }
+
+    #[test]
+    fn plain_path_matches_directory_contents() {
+        assert!(glob_match("services/payments", "services/payments/a.rs"));
+        assert!(glob_match("./services/payments/", "services/payments/a.rs"));
+        assert!(glob_match("Cargo.toml", "Cargo.toml"));
+        assert!(!glob_match("services/pay", "services/payments/a.rs"));
+    }
+
+    #[test]
+    fn empty_scope_contains_everything() {
+        assert!(contains(&[], "anything/at/all"));
+        let scope = vec!["web/**".to_string(), "shared/*.ts".to_string()];
+        assert_eq!(
+            out_of_scope(&scope, ["web/app.ts", "shared/x.ts", "api/main.go"]),
+            vec!["api/main.go".to_string()]
+        );
+    }
+}
@@ -0,0 +1,215 @@
+//! Commit signing (`defaults.sign_commits`).
+//!
+//! Rewrites that gg performs itself through git2 (metadata normalization,
+//! in-memory rebases, split, fold, move, ...) create commits the way
+//! `git commit` would: signed with the `gpg.format` program and
+//! `user.signingkey` when signing is on. Signing follows git's
+//! `commit.gpgsign` unless `defaults.sign_commits` overrides it; the override
+//! is passed as `-c commit.gpgsign=<bool>` to the `git` subprocesses gg runs
+//! (`git rebase`, `git commit --amend`) as well, so every rewrite agrees.
+//! Like plain mode, the setting is process-wide and chosen once at startup.
+
+use std::io::Write;
+use std::path::PathBuf;
+use std::process::{Command, Stdio};
+use std::sync::{OnceLock, RwLock};
+
+use git2::{Commit, Oid, Repository, Signature, Tree};
+
+use crate::error::{GgError, Result};
+
+fn sign_override() -> &'static RwLock<Option<bool>> {
+    static OVERRIDE: OnceLock<RwLock<Option<bool>>> = OnceLock::new();
+    OVERRIDE.get_or_init(|| RwLock::new(None))
+}
+
+/// Use `defaults.sign_commits` for every commit gg writes from now on
+/// (`None`: follow `commit.gpgsign`).
+pub fn set(sign_commits: Option<bool>) {
+    *sign_override().write().unwrap_or_else(|e| e.into_inner()) = sign_commits;
+}
+
+/// `defaults.sign_commits`, when set: the `commit.gpgsign` value for the
+/// `git` subprocesses gg runs (see [`crate::hooks::git_command`]).
+pub fn sign_commits() -> Option<bool> {
+    *sign_override().read().unwrap_or_else(|e| e.into_inner())
+}
+
+/// Whether commits written in `repo` get signed.
+pub fn enabled(repo: &Repository) -> bool {
+    if let Some(value) = sign_commits() {
+        return value;
+    }
+    repo.config()
+        .and_then(|c| c.get_bool("commit.gpgsign"))
+        .unwrap_or(false)
+}
+
+/// Write a commit without moving any ref, signing it when [`enabled`].
+/// Drop-in for `repo.commit(None, ...)`.
+pub fn create_commit(
+    repo: &Repository,
+    author: &Signature<'_>,
+    committer: &Signature<'_>,
+    message: &str,
+    tree: &Tree<'_>,
+    parents: &[&Commit<'_>],
+) -> Result<Oid> {
+    if !enabled(repo) {
+        return Ok(repo.commit(None, author, committer, message, tree, parents)?);
+    }
+    let buffer = repo.commit_create_buffer(author, committer, message, tree, parents)?;
+    let content = std::str::from_utf8(&buffer)
+        .map_err(|e| GgError::Other(format!("Commit is not valid UTF-8: {}", e)))?;
+    let signature = sign(repo, content, committer)?;
+    Ok(repo.commit_signed(content, &signature, None)?)
+}
+
+/// Sign commit `content` like `git commit -S` does.
+fn sign(repo: &Repository, content: &str, committer: &Signature<'_>) -> Result<String> {
+    let config = repo.config()?;
+    let get = |key: &str| config.get_string(key).ok();
+    let format = get("gpg.format").unwrap_or_else(|| "openpgp".to_string());
+    let key = get("user.signingkey");
+
+    // Literal SSH keys (`ssh-ed25519 AAAA...`, `key::...`) are in the agent;
+    // ssh-keygen still wants them in a file
+    let mut literal_key_file = None;
+    let (program, args) = match format.as_str() {
+        "ssh" => {
+            let key = key.ok_or_else(|| {
+                GgError::Other(
+                    "Signing commits with gpg.format=ssh needs user.signingkey".to_string(),
+                )
+            })?;
+            let mut args = vec!["-Y".to_string(), "sign".into(), "-n".into(), "git".into()];
+            match key.strip_prefix("key::").or_else(|| {
+                (key.starts_with("ssh-") || key.starts_with("ecdsa-")).then_some(key.as_str())
+            }) {
+                Some(literal) => {
+                    let path = repo.path().join("gg").join(".signing-key.pub");
+                    if let Some(parent) = path.parent() {
+                        std::fs::create_dir_all(parent)?;
+                    }
+                    std::fs::write(&path, literal)?;
+                    args.extend(["-f".to_string(), path.display().to_string(), "-U".into()]);
+                    literal_key_file = Some(path);
+                }
+                None => args.extend(["-f".to_string(), expand_home(&key)]),
+            }
+            (
+                get("gpg.ssh.program").unwrap_or_else(|| "ssh-keygen".to_string()),
+                args,
+            )
+        }
+        "x509" => (
+            get("gpg.x509.program").unwrap_or_else(|| "gpgsm".to_string()),
+            gpg_args(key, committer),
+        ),
+        _ => (
+            get("gpg.openpgp.program")
+                .or_else(|| get("gpg.program"))
+                .unwrap_or_else(|| "gpg".to_string()),
+            gpg_args(key, committer),
+        ),
+    };
+
+    let result = run_signer(&program, &args, content);
+    if let Some(path) = literal_key_file {
+        let _ = std::fs::remove_file(path);
+    }
+    result.map_err(|e| {
+        GgError::Other(format!(
+            "Failed to sign commit with {}: {}\n\
+             Fix the signing setup, or set defaults.sign_commits to false to skip signing.",
+            program, e
+        ))
+    })
+}
+
+fn gpg_args(key: Option<String>, committer: &Signature<'_>) -> Vec<String> {
+    let key = key.unwrap_or_else(|| {
+        format!(
+            "{} <{}>",
+            committer.name().unwrap_or(""),
+            committer.email().unwrap_or("")
+        )
+    });
+    vec!["--status-fd=2".to_string(), "-bsau".into(), key]
+}
+
+fn run_signer(
+    program: &str,
+    args: &[String],
+    content: &str,
+) -> std::result::Result<String, String> {
+    let mut child = Command::new(program)
+        .args(args)
+        .stdin(Stdio::piped())
+        .stdout(Stdio::piped())
+        .stderr(Stdio::piped())
+        .spawn()
+        .map_err(|e| e.to_string())?;
+    child
+        .stdin
+        .take()
+        .expect("stdin is piped")
+        .write_all(content.as_bytes())
+        .map_err(|e| e.to_string())?;
+    let output = child.wait_with_output().map_err(|e| e.to_string())?;
+    let signature = String::from_utf8_lossy(&output.stdout).to_string();
+    if !output.status.success() || signature.trim().is_empty() {
+        return Err(String::from_utf8_lossy(&output.stderr).trim().to_string());
+    }
+    Ok(signature)
+}
+
+fn expand_home(path: &str) -> String {
+    match (path.strip_prefix("~/"), std::env::var_os("HOME")) {
+        (Some(rest), Some(home)) => PathBuf::from(home).join(rest).display().to_string(),
+        _ => path.to_string(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn create_commit_signs_with_ssh_key_when_gpgsign_is_set() {
+        if Command::new("ssh-keygen").arg("-?").output().is_err() {
+            return;
+        }
+        let dir = tempfile::tempdir().unwrap();
+        let key = dir.path().join("key");
+        let status = Command::new("ssh-keygen")
+            .args(["-q", "-t", "ed25519", "-N", "", "-f"])
+            .arg(&key)
+            .status()
+            .unwrap();
+        assert!(status.success());
+
+        let repo = Repository::init(dir.path().join("repo")).unwrap();
+        let mut config = repo.config().unwrap();
+        config.set_str("gpg.format", "ssh").unwrap();
+        config
+            .set_str("user.signingkey", key.to_str().unwrap())
+            .unwrap();
+        let sig = Signature::now("Test", "test@example.com").unwrap();
+        let tree = repo
+            .find_tree(repo.index().unwrap().write_tree().unwrap())
+            .unwrap();
+
+        let unsigned = create_commit(&repo, &sig, &sig, "unsigned", &tree, &[]).unwrap();
+        assert!(repo.extract_signature(&unsigned, None).is_err());
+
+        config.set_bool("commit.gpgsign", true).unwrap();
+        let signed = create_commit(&repo, &sig, &sig, "signed", &tree, &[]).unwrap();
+        let (signature, _) = repo.extract_signature(&signed, None).unwrap();
+        assert!(String::from_utf8_lossy(&signature).contains("BEGIN SSH SIGNATURE"));
+        assert_eq!(
+            repo.find_commit(signed).unwrap().message().unwrap(),
+            "signed"
+        );
+    }
+}
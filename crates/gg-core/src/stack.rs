@@ -3,19 +3,27 @@
 //! A stack is a linear sequence of commits on a branch, each identified
 //! by a stable GG-ID trailer that persists across rebases.
 
+use std::collections::HashSet;
 use std::fs;
 use std::path::Path;
 
-use git2::{Commit, Repository};
+use git2::{BranchType, Commit, Oid, Repository};
 
 use crate::config::Config;
 use crate::error::{GgError, Result};
 use crate::git::{self, get_gg_id, get_gg_parent, short_sha};
-use crate::provider::{CiStatus, PrState, Provider};
+use crate::glab::{MergeTrainInfo, MergeTrainStatus};
+use crate::notes;
+use crate::parallel;
+use crate::provider::{CiStatus, PrInfo, PrState, PrStatus, Provider};
 
 /// File to store the current stack when in detached HEAD mode
 const CURRENT_STACK_FILE: &str = "gg/current_stack";
 
+/// File holding the stack selected with `gg use`. Lives in the worktree's
+/// own git dir, so each worktree has its own selection.
+const ACTIVE_STACK_FILE: &str = "gg/active_stack";
+
 /// File marking a mid-stack integration whose rebase paused on a conflict, so
 /// `gg continue` can finish the integration-specific cleanup. Format:
 /// `branch_name|head_oid`.
@@ -52,6 +60,12 @@ pub struct StackEntry {
     pub in_merge_train: bool,
     /// Position in merge train if applicable
     pub merge_train_position: Option<usize>,
+    /// Unresolved review threads on the PR/MR (only fetched by `gg ls --prs`)
+    pub unresolved_threads: Option<usize>,
+    /// Annotations from `gg annotate` (git notes under `refs/notes/gg`)
+    pub annotations: notes::Annotations,
+    /// Whether the commit carries a GPG/SSH/X.509 signature
+    pub signed: bool,
 }
 
 impl StackEntry {
@@ -72,6 +86,9 @@ impl StackEntry {
             position,
             in_merge_train: false,
             merge_train_position: None,
+            unresolved_threads: None,
+            annotations: notes::Annotations::new(),
+            signed: commit.header_field_bytes("gpgsig").is_ok(),
         }
     }
 
@@ -136,8 +153,12 @@ impl StackPrefixMismatch {
 
 impl Stack {
     /// Load stack from the current branch (or stored stack if in detached HEAD)
+    ///
+    /// Fails if another stack was selected with `gg use`: commands that
+    /// rewrite history need the selected stack checked out.
     pub fn load(repo: &Repository, config: &Config) -> Result<Self> {
         let git_dir = repo.path();
+        let active = read_active_stack(git_dir);
 
         // First, try to get branch name from HEAD
         let on_branch = git::current_branch_name(repo).is_some();
@@ -146,32 +167,77 @@ impl Stack {
                 // If in detached HEAD, try to read stored stack
                 read_current_stack(git_dir)
             })
-            .ok_or(GgError::NotOnStack)?;
+            .ok_or(GgError::NotOnStack);
+
+        let parsed = branch_name
+            .as_deref()
+            .ok()
+            .and_then(git::parse_stack_branch);
+        if let Some(active) = active {
+            let active_name = git::parse_stack_branch(&active).map(|(_, name)| name);
+            if parsed.as_ref().map(|(_, name)| name) != active_name.as_ref() {
+                return Err(GgError::ActiveStackNotCheckedOut(
+                    active_name.unwrap_or(active),
+                ));
+            }
+        }
 
-        let (username, name) = git::parse_stack_branch(&branch_name).ok_or_else(|| {
+        let branch_name = branch_name?;
+        let (username, name) = parsed.ok_or_else(|| {
             GgError::NotOnStackBranch(format_not_stack_branch_error(&branch_name, config))
         })?;
+        Self::load_branch(repo, config, &branch_name, username, name, on_branch)
+    }
+
+    /// Load the stack selected with `gg use`, or the checked-out one if
+    /// none is selected. For commands that only read the stack or talk to
+    /// the provider, so they work without checking the stack out.
+    pub fn load_active(repo: &Repository, config: &Config) -> Result<Self> {
+        let Some(branch_name) = read_active_stack(repo.path()) else {
+            return Self::load(repo, config);
+        };
+        let (username, name) = git::parse_stack_branch(&branch_name)
+            .ok_or_else(|| GgError::StackNotFound(branch_name.clone()))?;
+        let checked_out = git::current_branch_name(repo).as_deref() == Some(branch_name.as_str());
+        Self::load_branch(repo, config, &branch_name, username, name, checked_out)
+    }
 
+    fn load_branch(
+        repo: &Repository,
+        config: &Config,
+        branch_name: &str,
+        username: String,
+        name: String,
+        on_branch: bool,
+    ) -> Result<Self> {
         // Determine base branch
-        let base = config
-            .get_base_for_stack(&name)
-            .map(|s| s.to_string())
-            .or_else(|| git::find_base_branch(repo).ok())
-            .ok_or(GgError::NoBaseBranch)?;
-
-        // Get commit OIDs - use branch ref if in detached HEAD mode
-        let stack_ref = if on_branch {
-            None
+        let base = resolve_stack_base(repo, config, &username, &name)?;
+
+        // Get commit OIDs - use branch ref if in detached HEAD mode. Without
+        // a local base branch, the stack's remote has the base.
+        let stack_ref = if on_branch { None } else { Some(branch_name) };
+        let base_spec = if repo.revparse_single(&base).is_ok() {
+            base.clone()
         } else {
-            Some(branch_name.clone())
+            format!("{}/{}", config.get_remote_for_stack(&name), base)
         };
-        let oids = git::get_stack_commit_oids(repo, &base, stack_ref.as_deref())?;
+        let mut oids = git::get_stack_commit_oids(repo, &base_spec, stack_ref)?;
+        // A stack started with `gg co --from <ref>` also begins after that ref
+        if let Some(Ok(since_from)) = config
+            .get_from_for_stack(&name)
+            .map(|from| git::get_stack_commit_oids(repo, from, stack_ref))
+        {
+            let since_from: HashSet<Oid> = since_from.into_iter().collect();
+            oids.retain(|oid| since_from.contains(oid));
+        }
 
         // Build entries
         let mut entries: Vec<StackEntry> = Vec::with_capacity(oids.len());
         for (i, oid) in oids.iter().enumerate() {
             let commit = repo.find_commit(*oid)?;
-            entries.push(StackEntry::from_commit(&commit, i + 1));
+            let mut entry = StackEntry::from_commit(&commit, i + 1);
+            entry.annotations = notes::read(repo, *oid);
+            entries.push(entry);
         }
 
         // Enrich with MR info from config
@@ -198,6 +264,80 @@ impl Stack {
         })
     }
 
+    /// Load another of the user's stacks by name, without checking it out
+    pub fn load_named(
+        repo: &Repository,
+        config: &Config,
+        username: &str,
+        name: &str,
+    ) -> Result<Self> {
+        let branch_name = git::format_stack_branch(username, name);
+        if repo.find_branch(&branch_name, BranchType::Local).is_err() {
+            return Err(GgError::StackNotFound(name.to_string()));
+        }
+        Self::load_branch(
+            repo,
+            config,
+            &branch_name,
+            username.to_string(),
+            name.to_string(),
+            false,
+        )
+    }
+
+    /// The parent stack this one is stacked on, when its branch still exists
+    pub fn parent_stack(&self, repo: &Repository, config: &Config) -> Option<Stack> {
+        let parent = config.get_parent_for_stack(&self.name)?;
+        if self.base != git::format_stack_branch(&self.username, parent) {
+            return None;
+        }
+        Self::load_named(repo, config, &self.username, parent).ok()
+    }
+
+    /// Branch the first entry's PR/MR targets. Plain stacks target their
+    /// base; dependent stacks target the top entry branch of their parent,
+    /// which therefore has to be synced first.
+    pub fn pr_base(&self, repo: &Repository, config: &Config) -> Result<String> {
+        let Some(parent) = self.parent_stack(repo, config) else {
+            return Ok(self.base.clone());
+        };
+        let Some(top) = parent.last() else {
+            return parent.pr_base(repo, config);
+        };
+        match parent.entry_branch_name(top) {
+            Some(branch) if top.mr_number.is_some() => Ok(branch),
+            _ => Err(GgError::Other(format!(
+                "Stack '{}' is stacked on '{}', which is not fully synced. Run `gg sync` on '{}' first.",
+                self.name, parent.name, parent.name
+            ))),
+        }
+    }
+
+    /// Detect a parent stack that was rewritten (amended, rebased, ...) after
+    /// this stack forked from it. The old copies of the parent's commits then
+    /// show up at the bottom of this stack; returns the last of them, which is
+    /// the fork point to rebase from.
+    pub fn stale_parent_fork(&self, repo: &Repository, config: &Config) -> Option<git2::Oid> {
+        let parent = self.parent_stack(repo, config)?;
+        let parent_ids: HashSet<&str> = parent
+            .entries
+            .iter()
+            .filter_map(|e| e.gg_id.as_deref())
+            .chain(
+                config
+                    .get_stack(&parent.name)
+                    .into_iter()
+                    .flat_map(|s| s.mrs.keys().map(String::as_str)),
+            )
+            .collect();
+        let stale = self
+            .entries
+            .iter()
+            .take_while(|e| e.gg_id.as_deref().is_some_and(|id| parent_ids.contains(id)))
+            .count();
+        (stale > 0).then(|| self.entries[stale - 1].oid)
+    }
+
     pub fn prefix_mismatch(&self, config: &Config) -> Option<StackPrefixMismatch> {
         let expected_prefix = config.defaults.branch_username.as_deref()?;
         if expected_prefix.is_empty() || expected_prefix == self.username {
@@ -318,44 +458,120 @@ impl Stack {
 
     /// Refresh PR/MR info for all entries from provider
     pub fn refresh_mr_info(&mut self, provider: &Provider) -> Result<()> {
-        for entry in &mut self.entries {
-            if let Some(pr_num) = entry.mr_number {
-                match provider.get_pr_info(pr_num) {
-                    Ok(info) => {
-                        entry.mr_state = Some(info.state);
-                        entry.approved = info.approved;
-                        entry.changes_requested = info.changes_requested;
-                        entry.mergeable = info.mergeable;
-                    }
-                    Err(_) => {
-                        // PR/MR might have been deleted
-                        entry.mr_state = None;
-                    }
-                }
+        self.refresh_pr_details(provider, 1, false);
+        Ok(())
+    }
 
-                // Get CI status
-                if let Ok(ci) = provider.get_pr_ci_status(pr_num) {
-                    entry.ci_status = Some(ci);
-                }
+    /// Refresh PR/MR info for all entries. Whatever the provider can report
+    /// in bulk comes from one batched request; the rest is queried for up
+    /// to `jobs` PRs/MRs at once. With `threads`, also count each PR/MR's
+    /// unresolved review threads.
+    pub fn refresh_pr_details(&mut self, provider: &Provider, jobs: usize, threads: bool) {
+        let numbers: Vec<(usize, u64)> = self
+            .entries
+            .iter()
+            .enumerate()
+            .filter_map(|(i, entry)| entry.mr_number.map(|n| (i, n)))
+            .collect();
+        let base = self.base.clone();
+        // A failed batch (e.g. one PR was deleted) falls back to per-PR calls
+        let batch = provider
+            .get_prs_status(&numbers.iter().map(|(_, n)| *n).collect::<Vec<_>>())
+            .unwrap_or_default();
+        let fetched = parallel::map(&numbers, jobs, |(_, number)| {
+            MrDetails::fetch(provider, *number, &base, threads, batch.get(number))
+        });
+        for ((i, _), details) in numbers.iter().zip(fetched) {
+            details.apply(&mut self.entries[*i]);
+        }
+    }
 
-                // Check approval status
-                if let Ok(approved) = provider.check_pr_approved(pr_num) {
-                    entry.approved = approved;
-                }
+    /// Refresh PR/MR state, CI and merge-train info for a single entry
+    /// (1-indexed). Entries without a PR/MR are left untouched.
+    pub fn refresh_entry_mr_info(&mut self, position: usize, provider: &Provider) {
+        let base = self.base.clone();
+        let Some(entry) = self.entries.get_mut(position.wrapping_sub(1)) else {
+            return;
+        };
+        let Some(pr_num) = entry.mr_number else {
+            return;
+        };
+        MrDetails::fetch(provider, pr_num, &base, false, None).apply(entry);
+    }
+}
 
-                // Check merge train status (GitLab only)
-                if let Ok(Some(train_info)) = provider.get_merge_train_status(pr_num, &self.base) {
-                    use crate::glab::MergeTrainStatus;
-                    entry.in_merge_train = !matches!(train_info.status, MergeTrainStatus::Idle);
-                    entry.merge_train_position = train_info.position;
-                }
+/// Everything the provider reported about one PR/MR. Fetched without
+/// touching the entry so several PRs/MRs can be queried at once.
+struct MrDetails {
+    info: Option<PrInfo>,
+    ci_status: Option<CiStatus>,
+    approved: Option<bool>,
+    merge_train: Option<MergeTrainInfo>,
+    unresolved_threads: Option<usize>,
+}
+
+impl MrDetails {
+    /// Fetch what `known` (from a batched request) does not already tell.
+    fn fetch(
+        provider: &Provider,
+        pr_num: u64,
+        base: &str,
+        threads: bool,
+        known: Option<&PrStatus>,
+    ) -> Self {
+        let known = known.cloned();
+        let (info, ci_status, approved, merge_train) = match known {
+            Some(status) => (
+                Some(status.info),
+                status.ci_status,
+                status.approved,
+                status.merge_train,
+            ),
+            None => (None, None, None, None),
+        };
+        MrDetails {
+            info: info.or_else(|| provider.get_pr_info(pr_num).ok()),
+            ci_status: ci_status.or_else(|| provider.get_pr_ci_status(pr_num).ok()),
+            approved: approved.or_else(|| provider.check_pr_approved(pr_num).ok()),
+            merge_train: merge_train
+                .or_else(|| provider.get_merge_train_status(pr_num, base).ok().flatten()),
+            unresolved_threads: threads
+                .then(|| provider.list_review_threads(pr_num).ok())
+                .flatten()
+                .map(|t| t.iter().filter(|thread| !thread.resolved).count()),
+        }
+    }
+
+    fn apply(self, entry: &mut StackEntry) {
+        match self.info {
+            Some(info) => {
+                entry.mr_state = Some(info.state);
+                entry.approved = info.approved;
+                entry.changes_requested = info.changes_requested;
+                entry.mergeable = info.mergeable;
             }
+            // PR/MR might have been deleted
+            None => entry.mr_state = None,
+        }
+        if let Some(ci) = self.ci_status {
+            entry.ci_status = Some(ci);
+        }
+        if let Some(approved) = self.approved {
+            entry.approved = approved;
+        }
+        // Merge train status is GitLab only
+        if let Some(train_info) = self.merge_train {
+            entry.in_merge_train = !matches!(train_info.status, MergeTrainStatus::Idle);
+            entry.merge_train_position = train_info.position;
+        }
+        if self.unresolved_threads.is_some() {
+            entry.unresolved_threads = self.unresolved_threads;
         }
-        Ok(())
     }
 }
 
 fn format_not_stack_branch_error(branch_name: &str, config: &Config) -> String {
+    let expected = git::format_stack_branch("<prefix>", "<stack-name>");
     if let Some(expected_prefix) = config
         .defaults
         .branch_username
@@ -365,18 +581,58 @@ fn format_not_stack_branch_error(branch_name: &str, config: &Config) -> String {
     {
         let suggested_branch = git::format_stack_branch(expected_prefix, branch_name);
         format!(
-            "Current branch '{}' is not a stack branch. Expected format: '<prefix>/<stack-name>', for example '{}'. Rename it with: git branch -m {}",
-            branch_name, suggested_branch, suggested_branch
+            "Current branch '{}' is not a stack branch. Expected format: '{}', for example '{}'. Rename it with: git branch -m {}",
+            branch_name, expected, suggested_branch, suggested_branch
         )
     } else {
         format!(
-            "Current branch '{}' is not a stack branch. Expected format: '<prefix>/<stack-name>'. Use `gg co <stack-name>` to create or switch to a stack.",
-            branch_name
+            "Current branch '{}' is not a stack branch. Expected format: '{}'. Use `gg co <stack-name>` to create or switch to a stack.",
+            branch_name, expected
         )
     }
 }
 
-/// Resolve a target string (position, GG-ID, or SHA) to a position in the stack
+/// Description of a stack (`gg describe`): from its config, or from git
+/// notes when the config doesn't have it (e.g. a stack checked out from a
+/// remote).
+pub fn stack_description(repo: &Repository, config: &Config, name: &str) -> Option<String> {
+    config
+        .get_stack(name)
+        .and_then(|s| s.description.clone())
+        .or_else(|| notes::read_stack_description(repo, name))
+}
+
+/// Resolve the base branch of a stack. A dependent stack is based on its
+/// parent's branch; once the parent is gone (landed and cleaned) it falls
+/// through to whatever the parent was based on.
+pub fn resolve_stack_base(
+    repo: &Repository,
+    config: &Config,
+    username: &str,
+    name: &str,
+) -> Result<String> {
+    let mut name = name.to_string();
+    let mut seen = HashSet::new();
+    while let Some(parent) = config.get_parent_for_stack(&name) {
+        if !seen.insert(name.clone()) {
+            break;
+        }
+        let parent_branch = git::format_stack_branch(username, parent);
+        if repo.find_branch(&parent_branch, BranchType::Local).is_ok() {
+            return Ok(parent_branch);
+        }
+        name = parent.to_string();
+    }
+
+    config
+        .get_base_for_stack(&name)
+        .map(|s| s.to_string())
+        .or_else(|| git::find_base_branch(repo).ok())
+        .ok_or(GgError::NoBaseBranch)
+}
+
+/// Resolve a target string (position, GG-ID, SHA, or `#123`/`!123` PR/MR
+/// number) to a position in the stack
 pub fn resolve_target(stack: &Stack, target: &str) -> Result<usize> {
     // Try to parse target as position (1-indexed number)
     if let Ok(pos) = target.parse::<usize>() {
@@ -390,6 +646,20 @@ pub fn resolve_target(stack: &Stack, target: &str) -> Result<usize> {
         return Ok(pos);
     }
 
+    // Try to parse target as a PR/MR number (#123 or !123)
+    if let Some(number) = target
+        .strip_prefix('#')
+        .or_else(|| target.strip_prefix('!'))
+        .and_then(|n| n.parse::<u64>().ok())
+    {
+        return stack
+            .entries
+            .iter()
+            .find(|entry| entry.mr_number == Some(number))
+            .map(|entry| entry.position)
+            .ok_or_else(|| GgError::Other(format!("No entry in the stack has PR/MR {}", target)));
+    }
+
     // Try to find by GG-ID
     if let Some(entry) = stack.get_entry_by_gg_id(target) {
         return Ok(entry.position);
@@ -408,6 +678,32 @@ pub fn resolve_target(stack: &Stack, target: &str) -> Result<usize> {
     )))
 }
 
+/// Select `branch_name` as this worktree's active stack (`gg use`)
+pub fn save_active_stack(git_dir: &Path, branch_name: &str) -> Result<()> {
+    let stack_file = git_dir.join(ACTIVE_STACK_FILE);
+    if let Some(parent) = stack_file.parent() {
+        fs::create_dir_all(parent)?;
+    }
+    fs::write(stack_file, branch_name)?;
+    Ok(())
+}
+
+/// Read this worktree's active stack selection (stack branch name)
+pub fn read_active_stack(git_dir: &Path) -> Option<String> {
+    let content = fs::read_to_string(git_dir.join(ACTIVE_STACK_FILE)).ok()?;
+    let trimmed = content.trim();
+    (!trimmed.is_empty()).then(|| trimmed.to_string())
+}
+
+/// Clear this worktree's active stack selection
+pub fn clear_active_stack(git_dir: &Path) -> Result<()> {
+    let stack_file = git_dir.join(ACTIVE_STACK_FILE);
+    if stack_file.exists() {
+        fs::remove_file(stack_file)?;
+    }
+    Ok(())
+}
+
 /// Store the current stack branch for use in detached HEAD mode
 #[allow(dead_code)]
 pub fn save_current_stack(git_dir: &Path, branch_name: &str) -> Result<()> {
@@ -605,6 +901,25 @@ pub fn detect_unintegrated(repo: &Repository, stack: &Stack) -> Result<Option<Un
     }))
 }
 
+/// When `stack_name` last changed: the `last_touched_ms` gg stamps on its
+/// config, or its tip commit's committer date when that is later (stacks
+/// changed outside gg, or never stamped).
+pub fn last_touched_ms(
+    repo: &Repository,
+    config: &Config,
+    username: &str,
+    stack_name: &str,
+) -> Option<u64> {
+    let stamped = config.get_stack(stack_name).and_then(|s| s.last_touched_ms);
+    let committed = repo
+        .revparse_single(&git::format_stack_branch(username, stack_name))
+        .ok()
+        .and_then(|obj| obj.peel_to_commit().ok())
+        .and_then(|commit| u64::try_from(commit.committer().when().seconds()).ok())
+        .map(|s| s * 1000);
+    stamped.max(committed)
+}
+
 /// List all stacks in the repository
 pub fn list_all_stacks(repo: &Repository, config: &Config, username: &str) -> Result<Vec<String>> {
     let mut stacks = Vec::new();
@@ -660,9 +975,31 @@ mod tests {
             position: pos,
             in_merge_train: false,
             merge_train_position: None,
+            unresolved_threads: None,
+            annotations: Default::default(),
+            signed: false,
         }
     }
 
+    #[test]
+    fn resolve_target_accepts_pr_numbers() {
+        let mut first = mk_entry(1, Some("c-a"));
+        first.mr_number = Some(41);
+        let mut second = mk_entry(2, Some("c-b"));
+        second.mr_number = Some(42);
+        let stack = Stack {
+            name: "s".to_string(),
+            username: "u".to_string(),
+            base: "main".to_string(),
+            entries: vec![first, second],
+            current_position: None,
+        };
+
+        assert_eq!(resolve_target(&stack, "#42").unwrap(), 2);
+        assert_eq!(resolve_target(&stack, "!41").unwrap(), 1);
+        assert!(resolve_target(&stack, "#7").is_err());
+    }
+
     #[test]
     fn expected_parent_first_entry_is_none() {
         let stack = Stack {
@@ -752,4 +1089,20 @@ mod tests {
         assert!(message.contains("testuser/feature"));
         assert!(message.contains("git branch -m testuser/feature"));
     }
+
+    #[test]
+    fn active_stack_selection_round_trips() {
+        let dir = tempfile::TempDir::new().unwrap();
+        assert_eq!(read_active_stack(dir.path()), None);
+
+        save_active_stack(dir.path(), "testuser/alpha").unwrap();
+        assert_eq!(
+            read_active_stack(dir.path()).as_deref(),
+            Some("testuser/alpha")
+        );
+
+        clear_active_stack(dir.path()).unwrap();
+        assert_eq!(read_active_stack(dir.path()), None);
+        clear_active_stack(dir.path()).unwrap();
+    }
 }
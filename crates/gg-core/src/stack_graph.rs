@@ -0,0 +1,255 @@
+//! Stack graph rendering for PR/MR descriptions.
+//!
+//! Renders the stack as a Mermaid flowchart or a standalone SVG, and keeps a
+//! marker-delimited graph block at the end of each PR/MR description. Pure —
+//! no I/O, no provider calls; `gg sync` does the uploading and updating.
+
+use std::collections::hash_map::DefaultHasher;
+use std::fmt::Write as _;
+use std::hash::{Hash, Hasher};
+
+/// Start marker for the graph block in a PR/MR description.
+const GRAPH_START: &str = "<!-- gg:stack-graph:start -->";
+/// End marker for the graph block in a PR/MR description.
+const GRAPH_END: &str = "<!-- gg:stack-graph:end -->";
+
+/// One node of the graph, bottom-up (index 0 sits on the base branch).
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub(crate) struct GraphEntry {
+    pub pr_number: u64,
+    pub title: String,
+    pub merged: bool,
+    pub is_current: bool,
+}
+
+fn node_label(entry: &GraphEntry, number_prefix: &str) -> String {
+    let mut label = format!("{}{} {}", number_prefix, entry.pr_number, entry.title);
+    if entry.merged {
+        label.push_str(" (merged)");
+    }
+    label
+}
+
+/// Render the stack as a Mermaid flowchart with the base at the bottom and
+/// the current entry highlighted.
+pub(crate) fn render_mermaid(base: &str, entries: &[GraphEntry], number_prefix: &str) -> String {
+    // Mermaid labels are quoted; `#` and `"` need entity escapes (`#` first,
+    // since the entities themselves start with one).
+    let escape = |s: &str| s.replace('#', "#35;").replace('"', "#quot;");
+
+    let mut out = String::new();
+    writeln!(out, "```mermaid").unwrap();
+    writeln!(out, "flowchart BT").unwrap();
+    writeln!(out, "  base[\"{}\"]", escape(base)).unwrap();
+    for (i, entry) in entries.iter().enumerate() {
+        writeln!(
+            out,
+            "  pr{}[\"{}\"]",
+            i,
+            escape(&node_label(entry, number_prefix))
+        )
+        .unwrap();
+    }
+    for i in 0..entries.len() {
+        let parent = if i == 0 {
+            "base".to_string()
+        } else {
+            format!("pr{}", i - 1)
+        };
+        writeln!(out, "  pr{} --> {}", i, parent).unwrap();
+    }
+    if let Some(i) = entries.iter().position(|e| e.is_current) {
+        writeln!(out, "  classDef current stroke-width:3px,font-weight:bold").unwrap();
+        writeln!(out, "  class pr{} current", i).unwrap();
+    }
+    out.push_str("```");
+    out
+}
+
+/// Render the stack as a self-contained SVG (top of the stack first, base
+/// at the bottom).
+pub(crate) fn render_svg(base: &str, entries: &[GraphEntry], number_prefix: &str) -> String {
+    const WIDTH: usize = 480;
+    const BOX_HEIGHT: usize = 32;
+    const GAP: usize = 16;
+    const MAX_CHARS: usize = 60;
+
+    let escape = |s: &str| {
+        s.replace('&', "&amp;")
+            .replace('<', "&lt;")
+            .replace('>', "&gt;")
+            .replace('"', "&quot;")
+    };
+    let truncate = |s: String| {
+        if s.chars().count() > MAX_CHARS {
+            format!("{}…", s.chars().take(MAX_CHARS - 1).collect::<String>())
+        } else {
+            s
+        }
+    };
+
+    // Top-down rows: tip of the stack first, base last.
+    let mut rows: Vec<(String, bool, bool)> = entries
+        .iter()
+        .rev()
+        .map(|e| {
+            (
+                truncate(node_label(e, number_prefix)),
+                e.is_current,
+                e.merged,
+            )
+        })
+        .collect();
+    rows.push((base.to_string(), false, false));
+
+    let height = rows.len() * (BOX_HEIGHT + GAP) - GAP + 2;
+    let mut out = String::new();
+    writeln!(
+        out,
+        r#"<svg xmlns="http://www.w3.org/2000/svg" width="{w}" height="{h}" viewBox="0 0 {w} {h}" font-family="sans-serif" font-size="13">"#,
+        w = WIDTH + 2,
+        h = height
+    )
+    .unwrap();
+    for (i, (label, current, merged)) in rows.iter().enumerate() {
+        let y = 1 + i * (BOX_HEIGHT + GAP);
+        let is_base = i == rows.len() - 1;
+        let fill = if *current {
+            "#ddf4ff"
+        } else if *merged {
+            "#f0e6ff"
+        } else if is_base {
+            "#eeeeee"
+        } else {
+            "#ffffff"
+        };
+        let stroke_width = if *current { 3 } else { 1 };
+        writeln!(
+            out,
+            r##"  <rect x="1" y="{y}" width="{WIDTH}" height="{BOX_HEIGHT}" rx="6" fill="{fill}" stroke="#57606a" stroke-width="{stroke_width}"/>"##
+        )
+        .unwrap();
+        writeln!(
+            out,
+            r##"  <text x="12" y="{}" fill="#24292f">{}</text>"##,
+            y + BOX_HEIGHT / 2 + 5,
+            escape(label)
+        )
+        .unwrap();
+        if !is_base {
+            let x = WIDTH / 2;
+            writeln!(
+                out,
+                r##"  <line x1="{x}" y1="{}" x2="{x}" y2="{}" stroke="#57606a"/>"##,
+                y + BOX_HEIGHT,
+                y + BOX_HEIGHT + GAP
+            )
+            .unwrap();
+        }
+    }
+    out.push_str("</svg>\n");
+    out
+}
+
+/// Short stable-enough fingerprint of rendered graph content, stored in the
+/// block so unchanged images are not re-uploaded on every sync.
+pub(crate) fn fingerprint(content: &str) -> String {
+    let mut hasher = DefaultHasher::new();
+    content.hash(&mut hasher);
+    format!("{:016x}", hasher.finish())
+}
+
+/// Wrap graph markdown in the block markers, tagging it with `fingerprint`.
+pub(crate) fn wrap_block(markdown: &str, fingerprint: &str) -> String {
+    format!(
+        "{}\n<!-- gg:stack-graph:{} -->\n{}\n{}",
+        GRAPH_START, fingerprint, markdown, GRAPH_END
+    )
+}
+
+/// Fingerprint recorded in an existing graph block, if any.
+pub(crate) fn existing_fingerprint(body: &str) -> Option<&str> {
+    let start = body.find(GRAPH_START)? + GRAPH_START.len();
+    let rest = body[start..]
+        .trim_start()
+        .strip_prefix("<!-- gg:stack-graph:")?;
+    let end = rest.find(" -->")?;
+    Some(&rest[..end])
+}
+
+/// Replace the graph block in `body`, or append it if there is none.
+pub(crate) fn upsert_block(body: &str, block: &str) -> String {
+    if let Some(start) = body.find(GRAPH_START) {
+        if let Some(end_rel) = body[start..].find(GRAPH_END) {
+            let end = start + end_rel + GRAPH_END.len();
+            return format!("{}{}{}", &body[..start], block, &body[end..]);
+        }
+    }
+    let trimmed = body.trim_end();
+    if trimmed.is_empty() {
+        block.to_string()
+    } else {
+        format!("{}\n\n{}", trimmed, block)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn entries() -> Vec<GraphEntry> {
+        vec![
+            GraphEntry {
+                pr_number: 1,
+                title: "Add \"quoted\" API".to_string(),
+                merged: true,
+                is_current: false,
+            },
+            GraphEntry {
+                pr_number: 2,
+                title: "Use API".to_string(),
+                merged: false,
+                is_current: true,
+            },
+        ]
+    }
+
+    #[test]
+    fn mermaid_links_entries_to_their_parent() {
+        let out = render_mermaid("main", &entries(), "#");
+        assert!(out.starts_with("```mermaid\nflowchart BT\n"), "{out}");
+        assert!(out.contains("  base[\"main\"]\n"));
+        assert!(
+            out.contains("pr0[\"#35;1 Add #quot;quoted#quot; API (merged)\"]"),
+            "{out}"
+        );
+        assert!(out.contains("  pr0 --> base\n"));
+        assert!(out.contains("  pr1 --> pr0\n"));
+        assert!(out.contains("  class pr1 current\n"));
+        assert!(out.ends_with("```"));
+    }
+
+    #[test]
+    fn svg_lists_tip_first_and_escapes_labels() {
+        let out = render_svg("main", &entries(), "!");
+        let tip = out.find("!2 Use API").unwrap();
+        let bottom = out.find("!1 Add &quot;quoted&quot; API").unwrap();
+        let base = out.find(">main<").unwrap();
+        assert!(tip < bottom && bottom < base);
+        assert!(out.starts_with("<svg"));
+    }
+
+    #[test]
+    fn upsert_appends_then_replaces_block() {
+        let block = wrap_block("graph v1", "aaaa");
+        let body = upsert_block("Description\n", &block);
+        assert_eq!(body, format!("Description\n\n{}", block));
+        assert_eq!(existing_fingerprint(&body), Some("aaaa"));
+
+        let block2 = wrap_block("graph v2", "bbbb");
+        let updated = upsert_block(&body, &block2);
+        assert!(updated.contains("graph v2"));
+        assert!(!updated.contains("graph v1"));
+        assert_eq!(existing_fingerprint(&updated), Some("bbbb"));
+    }
+}
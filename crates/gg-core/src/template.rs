@@ -1,36 +1,140 @@
 //! PR/MR description template support
 //!
-//! Templates are stored in `.git/gg/pr_template.md` and support placeholders:
+//! Templates are stored in `.git/gg/pr_template.md` (local) or
+//! `.gg/pr_template.md` (committed to the repository) and support placeholders:
 //! - `{{description}}` - the commit description
 //! - `{{stack_name}}` - name of the current stack
 //! - `{{commit_sha}}` - short SHA of the commit
 //! - `{{title}}` - the PR/MR title
+//! - `{{position}}` / `{{total}}` - the entry's position and the stack size
+//! - `{{base}}` - the stack's base branch
+//! - `{{stack_description}}` - the stack's description (`gg describe`)
+//! - `{{stack_table}}` - a table of every entry in the stack and its PR/MR
+//! - `{{part}}` - "Part X of Y" with links to the previous and next PR/MR
 
+use std::fmt::Write as _;
 use std::fs;
 use std::path::Path;
 
+use crate::stack::Stack;
+
 /// Default template filename
 const TEMPLATE_FILENAME: &str = "pr_template.md";
 
+/// Placeholders whose value depends on the other PRs/MRs in the stack
+const STACK_PLACEHOLDERS: &[&str] = &["{{stack_table}}", "{{part}}"];
+
 /// Context for template rendering
 pub struct TemplateContext<'a> {
     pub description: Option<&'a str>,
     pub stack_name: &'a str,
     pub commit_sha: &'a str,
     pub title: &'a str,
+    /// Stack-wide values; stack placeholders render empty without it
+    pub stack: Option<&'a TemplateStack<'a>>,
+}
+
+/// Where the rendered entry sits in its stack
+pub struct TemplateStack<'a> {
+    /// Position of the entry being rendered (1-indexed)
+    pub position: usize,
+    pub base: &'a str,
+    /// The stack's description (`gg describe`)
+    pub description: Option<&'a str>,
+    /// `#` for GitHub/Gitea, `!` for GitLab
+    pub number_prefix: &'a str,
+    /// Every entry in the stack, bottom-up
+    pub entries: &'a [TemplateStackEntry],
+}
+
+/// One row of `{{stack_table}}`
+#[derive(Debug, Clone)]
+pub struct TemplateStackEntry {
+    pub position: usize,
+    pub title: String,
+    pub pr_number: Option<u64>,
+}
+
+/// Rows for every entry of `stack`, with the PR/MR numbers known so far
+pub fn stack_entries(stack: &Stack) -> Vec<TemplateStackEntry> {
+    stack
+        .entries
+        .iter()
+        .map(|entry| TemplateStackEntry {
+            position: entry.position,
+            title: entry.title.clone(),
+            pr_number: entry.mr_number,
+        })
+        .collect()
+}
+
+/// Load the PR template, preferring the local `.git/gg/pr_template.md` over
+/// the repository's `.gg/pr_template.md`
+pub fn load_template(git_dir: &Path, workdir: Option<&Path>) -> Option<String> {
+    let local = git_dir.join("gg").join(TEMPLATE_FILENAME);
+    let shared = workdir.map(|dir| dir.join(".gg").join(TEMPLATE_FILENAME));
+
+    std::iter::once(local)
+        .chain(shared)
+        .find(|path| path.exists())
+        .and_then(|path| fs::read_to_string(path).ok())
 }
 
-/// Load the PR template from `.git/gg/pr_template.md` if it exists
-pub fn load_template(git_dir: &Path) -> Option<String> {
-    let template_path = git_dir.join("gg").join(TEMPLATE_FILENAME);
+/// Whether `template` uses placeholders that change as PRs/MRs are created
+/// elsewhere in the stack
+pub fn uses_stack(template: &str) -> bool {
+    STACK_PLACEHOLDERS.iter().any(|p| template.contains(p))
+}
 
-    if template_path.exists() {
-        fs::read_to_string(&template_path).ok()
-    } else {
-        None
+fn pr_ref(stack: &TemplateStack, pr_number: Option<u64>) -> String {
+    match pr_number {
+        Some(n) => format!("{}{}", stack.number_prefix, n),
+        None => "—".to_string(),
     }
 }
 
+/// Render `{{stack_table}}`: one row per entry, the current one marked
+fn render_stack_table(stack: &TemplateStack) -> String {
+    let mut out = String::from("| | # | PR | Title |\n|---|---|---|---|\n");
+    for entry in stack.entries {
+        let marker = if entry.position == stack.position {
+            "👉"
+        } else {
+            ""
+        };
+        writeln!(
+            out,
+            "| {} | {} | {} | {} |",
+            marker,
+            entry.position,
+            pr_ref(stack, entry.pr_number),
+            entry.title.replace('|', "\\|")
+        )
+        .unwrap();
+    }
+    out.truncate(out.trim_end().len());
+    out
+}
+
+/// Render `{{part}}`: "Part X of Y" plus links to the neighbouring PRs/MRs
+fn render_part(stack: &TemplateStack) -> String {
+    let mut out = format!("Part {} of {}", stack.position, stack.entries.len());
+    let neighbour = |position: usize| {
+        stack
+            .entries
+            .iter()
+            .find(|e| e.position == position)
+            .and_then(|e| e.pr_number)
+    };
+    if let Some(prev) = stack.position.checked_sub(1).and_then(neighbour) {
+        write!(out, " · previous: {}", pr_ref(stack, Some(prev))).unwrap();
+    }
+    if let Some(next) = neighbour(stack.position + 1) {
+        write!(out, " · next: {}", pr_ref(stack, Some(next))).unwrap();
+    }
+    out
+}
+
 /// Render a template with the given context
 ///
 /// Replaces placeholders:
@@ -38,14 +142,34 @@ pub fn load_template(git_dir: &Path) -> Option<String> {
 /// - `{{stack_name}}` - stack name
 /// - `{{commit_sha}}` - short commit SHA
 /// - `{{title}}` - PR/MR title
+/// - `{{position}}`, `{{total}}`, `{{base}}`, `{{stack_description}}`,
+///   `{{stack_table}}`, `{{part}}` - stack placeholders (empty string without
+///   [`TemplateContext::stack`])
 pub fn render_template(template: &str, ctx: &TemplateContext) -> String {
     let description = ctx.description.unwrap_or("");
+    let (position, total, base, stack_description, table, part) = match ctx.stack {
+        Some(stack) => (
+            stack.position.to_string(),
+            stack.entries.len().to_string(),
+            stack.base,
+            stack.description.unwrap_or(""),
+            render_stack_table(stack),
+            render_part(stack),
+        ),
+        None => Default::default(),
+    };
 
     template
         .replace("{{description}}", description)
         .replace("{{stack_name}}", ctx.stack_name)
         .replace("{{commit_sha}}", ctx.commit_sha)
         .replace("{{title}}", ctx.title)
+        .replace("{{position}}", &position)
+        .replace("{{total}}", &total)
+        .replace("{{base}}", base)
+        .replace("{{stack_description}}", stack_description)
+        .replace("{{stack_table}}", &table)
+        .replace("{{part}}", &part)
 }
 
 #[cfg(test)]
@@ -61,7 +185,7 @@ mod tests {
         fs::create_dir_all(&gg_dir).unwrap();
         fs::write(gg_dir.join("pr_template.md"), "Hello {{title}}").unwrap();
 
-        let template = load_template(temp.path());
+        let template = load_template(temp.path(), None);
         assert!(template.is_some());
         assert_eq!(template.unwrap(), "Hello {{title}}");
     }
@@ -69,7 +193,7 @@ mod tests {
     #[test]
     fn test_load_template_not_exists() {
         let temp = TempDir::new().unwrap();
-        let template = load_template(temp.path());
+        let template = load_template(temp.path(), None);
         assert!(template.is_none());
     }
 
@@ -82,6 +206,7 @@ mod tests {
             stack_name: "my-feature",
             commit_sha: "abc1234",
             title: "Add new feature",
+            stack: None,
         };
 
         let result = render_template(template, &ctx);
@@ -99,6 +224,7 @@ mod tests {
             stack_name: "stack",
             commit_sha: "abc",
             title: "Test",
+            stack: None,
         };
 
         let result = render_template(template, &ctx);
@@ -113,9 +239,115 @@ mod tests {
             stack_name: "stack",
             commit_sha: "abc",
             title: "Test",
+            stack: None,
         };
 
         let result = render_template(template, &ctx);
         assert_eq!(result, "Test - Test");
     }
+
+    #[test]
+    fn test_load_template_prefers_local_over_repo() {
+        let temp = TempDir::new().unwrap();
+        let git_dir = temp.path().join(".git");
+        fs::create_dir_all(temp.path().join(".gg")).unwrap();
+        fs::write(temp.path().join(".gg/pr_template.md"), "repo").unwrap();
+
+        let template = load_template(&git_dir, Some(temp.path()));
+        assert_eq!(template.as_deref(), Some("repo"));
+
+        fs::create_dir_all(git_dir.join("gg")).unwrap();
+        fs::write(git_dir.join("gg/pr_template.md"), "local").unwrap();
+        let template = load_template(&git_dir, Some(temp.path()));
+        assert_eq!(template.as_deref(), Some("local"));
+    }
+
+    fn sample_entries() -> Vec<TemplateStackEntry> {
+        vec![
+            TemplateStackEntry {
+                position: 1,
+                title: "Add parser".to_string(),
+                pr_number: Some(12),
+            },
+            TemplateStackEntry {
+                position: 2,
+                title: "Use a | b".to_string(),
+                pr_number: Some(13),
+            },
+            TemplateStackEntry {
+                position: 3,
+                title: "Wire up CLI".to_string(),
+                pr_number: None,
+            },
+        ]
+    }
+
+    #[test]
+    fn test_render_template_stack_placeholders() {
+        let entries = sample_entries();
+        let stack = TemplateStack {
+            position: 2,
+            base: "main",
+            description: Some("Faster parsing"),
+            number_prefix: "#",
+            entries: &entries,
+        };
+        let ctx = TemplateContext {
+            description: None,
+            stack_name: "parser",
+            commit_sha: "abc",
+            title: "Use a | b",
+            stack: Some(&stack),
+        };
+
+        let result = render_template(
+            "{{part}}\n{{position}}/{{total}} onto {{base}}: {{stack_description}}\n\n{{stack_table}}",
+            &ctx,
+        );
+        assert_eq!(
+            result,
+            "Part 2 of 3 · previous: #12\n2/3 onto main: Faster parsing\n\n\
+             | | # | PR | Title |\n\
+             |---|---|---|---|\n\
+             |  | 1 | #12 | Add parser |\n\
+             | 👉 | 2 | #13 | Use a \\| b |\n\
+             |  | 3 | — | Wire up CLI |"
+        );
+    }
+
+    #[test]
+    fn test_render_part_links_both_neighbours() {
+        let mut entries = sample_entries();
+        entries[2].pr_number = Some(14);
+        let stack = TemplateStack {
+            position: 2,
+            base: "main",
+            description: None,
+            number_prefix: "!",
+            entries: &entries,
+        };
+        assert_eq!(
+            render_part(&stack),
+            "Part 2 of 3 · previous: !12 · next: !14"
+        );
+    }
+
+    #[test]
+    fn test_render_template_stack_placeholders_without_stack() {
+        let ctx = TemplateContext {
+            description: None,
+            stack_name: "stack",
+            commit_sha: "abc",
+            title: "Test",
+            stack: None,
+        };
+        assert_eq!(render_template("[{{part}}{{stack_table}}]", &ctx), "[]");
+    }
+
+    #[test]
+    fn test_uses_stack() {
+        assert!(uses_stack("{{description}}\n{{stack_table}}"));
+        assert!(uses_stack("{{part}}"));
+        assert!(!uses_stack("{{title}} at {{position}}"));
+    }
 }
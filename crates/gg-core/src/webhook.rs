@@ -0,0 +1,394 @@
+//! Local webhook receiver for `gg land --wait --listen`.
+//!
+//! Binds a plain HTTP listener and turns incoming GitHub/GitLab webhook
+//! deliveries into wake-up signals for the land wait loop, so it re-checks a
+//! PR/MR as soon as CI or merge state changes instead of every poll interval.
+//!
+//! Events are never trusted for state: they only trigger a re-check through
+//! the provider API. A forged delivery can at most cause an extra poll, which
+//! is why deliveries are not authenticated. Requests are still bounded (header
+//! size, header count, line length, time) and each connection is handled on
+//! its own thread, so a misbehaving client cannot stall the receiver.
+
+use std::io::{BufRead, BufReader, Read, Write};
+use std::net::{TcpListener, TcpStream};
+use std::sync::atomic::{AtomicBool, AtomicUsize, Ordering};
+use std::sync::mpsc::{self, Receiver, RecvTimeoutError};
+use std::sync::Arc;
+use std::thread;
+use std::time::{Duration, Instant};
+
+use serde_json::Value;
+
+use crate::error::{GgError, Result};
+
+/// Address used by `--listen` when no value is given.
+pub const DEFAULT_LISTEN_ADDR: &str = "127.0.0.1:8787";
+
+/// Largest request body accepted; provider payloads are well below this.
+const MAX_BODY_BYTES: usize = 5 * 1024 * 1024;
+
+/// Largest request line plus headers accepted.
+const MAX_HEADER_BYTES: u64 = 64 * 1024;
+
+/// Most headers accepted in one request.
+const MAX_HEADERS: usize = 100;
+
+/// Longest single request or header line accepted.
+const MAX_LINE_BYTES: u64 = 8 * 1024;
+
+/// Time a client gets to send its whole request.
+const REQUEST_TIMEOUT: Duration = Duration::from_secs(10);
+
+/// Connections handled at once; further ones are dropped until one finishes.
+const MAX_CONNECTIONS: usize = 16;
+
+/// A webhook delivery reduced to what the wait loop needs.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct WebhookEvent {
+    /// Provider event name (e.g. `check_suite`, `Pipeline Hook`).
+    pub kind: String,
+    /// PR/MR numbers the event refers to. Empty means the event could not be
+    /// tied to a PR/MR (e.g. a commit status) and wakes every waiter.
+    pub pr_numbers: Vec<u64>,
+}
+
+impl WebhookEvent {
+    /// Whether this event may affect `pr_number`.
+    pub fn concerns(&self, pr_number: u64) -> bool {
+        self.pr_numbers.is_empty() || self.pr_numbers.contains(&pr_number)
+    }
+}
+
+/// Parse a delivery from its headers and JSON body. Returns `None` for
+/// requests that are not provider webhooks (or carry no usable event name).
+pub fn parse_event(headers: &[(String, String)], body: &str) -> Option<WebhookEvent> {
+    let header = |name: &str| {
+        headers
+            .iter()
+            .find(|(k, _)| k.eq_ignore_ascii_case(name))
+            .map(|(_, v)| v.as_str())
+    };
+    let payload: Value = serde_json::from_str(body).unwrap_or(Value::Null);
+
+    let number = |v: &Value| v.as_u64();
+    let (kind, pr_numbers) = if let Some(kind) = header("X-GitHub-Event") {
+        let numbers = match kind {
+            "pull_request" | "pull_request_review" => payload
+                .pointer("/pull_request/number")
+                .and_then(number)
+                .into_iter()
+                .collect(),
+            "check_suite" | "check_run" => payload
+                .pointer(&format!("/{}/pull_requests", kind))
+                .and_then(Value::as_array)
+                .map(|prs| prs.iter().filter_map(|pr| pr["number"].as_u64()).collect())
+                .unwrap_or_default(),
+            _ => Vec::new(),
+        };
+        (kind, numbers)
+    } else if let Some(kind) = header("X-Gitlab-Event") {
+        let pointer = match kind {
+            "Merge Request Hook" => Some("/object_attributes/iid"),
+            "Pipeline Hook" => Some("/merge_request/iid"),
+            _ => None,
+        };
+        let numbers = pointer
+            .and_then(|p| payload.pointer(p))
+            .and_then(number)
+            .into_iter()
+            .collect();
+        (kind, numbers)
+    } else {
+        return None;
+    };
+
+    Some(WebhookEvent {
+        kind: kind.to_string(),
+        pr_numbers,
+    })
+}
+
+/// A running webhook receiver. The accept thread stops when this is dropped.
+pub struct WebhookListener {
+    addr: String,
+    events: Receiver<WebhookEvent>,
+    stop: Arc<AtomicBool>,
+}
+
+impl WebhookListener {
+    /// Bind `addr` and start accepting deliveries in a background thread.
+    pub fn start(addr: &str) -> Result<Self> {
+        let listener = TcpListener::bind(addr).map_err(|e| {
+            GgError::Other(format!("Could not listen for webhooks on {}: {}", addr, e))
+        })?;
+        listener.set_nonblocking(true)?;
+        let local_addr = listener.local_addr()?.to_string();
+
+        let (tx, rx) = mpsc::channel();
+        let stop = Arc::new(AtomicBool::new(false));
+        let stop_flag = Arc::clone(&stop);
+        let active = Arc::new(AtomicUsize::new(0));
+        thread::spawn(move || {
+            while !stop_flag.load(Ordering::SeqCst) {
+                match listener.accept() {
+                    Ok((stream, _)) => {
+                        if active.fetch_add(1, Ordering::SeqCst) >= MAX_CONNECTIONS {
+                            active.fetch_sub(1, Ordering::SeqCst);
+                            continue;
+                        }
+                        let tx = tx.clone();
+                        let active = Arc::clone(&active);
+                        thread::spawn(move || {
+                            if let Some(event) = handle_connection(stream) {
+                                // The waiter may be gone already
+                                let _ = tx.send(event);
+                            }
+                            active.fetch_sub(1, Ordering::SeqCst);
+                        });
+                    }
+                    Err(e) if e.kind() == std::io::ErrorKind::WouldBlock => {
+                        thread::sleep(Duration::from_millis(100));
+                    }
+                    Err(_) => thread::sleep(Duration::from_millis(100)),
+                }
+            }
+        });
+
+        Ok(Self {
+            addr: local_addr,
+            events: rx,
+            stop,
+        })
+    }
+
+    /// The bound address (useful when binding port 0).
+    pub fn addr(&self) -> &str {
+        &self.addr
+    }
+
+    /// Wait up to `timeout` for an event concerning `pr_number`, checking
+    /// `interrupted` every 250ms. Returns whether such an event arrived.
+    pub fn wait_for(
+        &self,
+        pr_number: u64,
+        timeout: Duration,
+        interrupted: Option<&Arc<AtomicBool>>,
+    ) -> Result<bool> {
+        let deadline = Instant::now() + timeout;
+        loop {
+            if interrupted.is_some_and(|flag| flag.load(Ordering::SeqCst)) {
+                return Err(GgError::Other("Interrupted by user".to_string()));
+            }
+            let remaining = deadline.saturating_duration_since(Instant::now());
+            if remaining.is_zero() {
+                return Ok(false);
+            }
+            match self
+                .events
+                .recv_timeout(remaining.min(Duration::from_millis(250)))
+            {
+                Ok(event) if event.concerns(pr_number) => return Ok(true),
+                Ok(_) | Err(RecvTimeoutError::Timeout) => {}
+                Err(RecvTimeoutError::Disconnected) => {
+                    // The accept thread died; degrade to a plain sleep.
+                    thread::sleep(remaining.min(Duration::from_millis(250)));
+                }
+            }
+        }
+    }
+}
+
+impl Drop for WebhookListener {
+    fn drop(&mut self) {
+        self.stop.store(true, Ordering::SeqCst);
+    }
+}
+
+/// Read one HTTP request, answer `200 OK` (or `431` when its headers are
+/// over the limits), and parse the delivery.
+fn handle_connection(stream: TcpStream) -> Option<WebhookEvent> {
+    stream.set_nonblocking(false).ok()?;
+    stream.set_read_timeout(Some(Duration::from_secs(5))).ok()?;
+    let deadline = Instant::now() + REQUEST_TIMEOUT;
+    let mut reader = BufReader::new(stream.try_clone().ok()?.take(MAX_HEADER_BYTES));
+    let mut stream = stream;
+
+    let Some(request_line) = read_line(&mut reader) else {
+        let _ = stream.write_all(HEADERS_TOO_LARGE);
+        return None;
+    };
+
+    let mut headers = Vec::new();
+    let mut content_length = 0usize;
+    loop {
+        if headers.len() > MAX_HEADERS || Instant::now() > deadline {
+            let _ = stream.write_all(HEADERS_TOO_LARGE);
+            return None;
+        }
+        let Some(line) = read_line(&mut reader) else {
+            let _ = stream.write_all(HEADERS_TOO_LARGE);
+            return None;
+        };
+        let line = line.trim_end();
+        if line.is_empty() {
+            break;
+        }
+        if let Some((name, value)) = line.split_once(':') {
+            let (name, value) = (name.trim().to_string(), value.trim().to_string());
+            if name.eq_ignore_ascii_case("Content-Length") {
+                content_length = value.parse().unwrap_or(0);
+            }
+            headers.push((name, value));
+        }
+    }
+
+    let mut body = vec![0u8; content_length.min(MAX_BODY_BYTES)];
+    reader.get_mut().set_limit(body.len() as u64);
+    let read_ok = reader.read_exact(&mut body).is_ok();
+
+    let _ = stream.write_all(b"HTTP/1.1 200 OK\r\nContent-Length: 0\r\nConnection: close\r\n\r\n");
+
+    if !read_ok || !request_line.starts_with("POST ") {
+        return None;
+    }
+    parse_event(&headers, &String::from_utf8_lossy(&body))
+}
+
+const HEADERS_TOO_LARGE: &[u8] =
+    b"HTTP/1.1 431 Request Header Fields Too Large\r\nContent-Length: 0\r\nConnection: close\r\n\r\n";
+
+/// One CRLF- or LF-terminated line of at most [`MAX_LINE_BYTES`]. `None` when
+/// the line is longer, the header budget is spent, or the client stops early.
+fn read_line(reader: &mut impl BufRead) -> Option<String> {
+    let mut line = String::new();
+    reader
+        .by_ref()
+        .take(MAX_LINE_BYTES)
+        .read_line(&mut line)
+        .ok()?;
+    line.ends_with('\n').then_some(line)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn headers(name: &str, value: &str) -> Vec<(String, String)> {
+        vec![(name.to_string(), value.to_string())]
+    }
+
+    #[test]
+    fn parses_github_check_suite_pull_requests() {
+        let body = r#"{"check_suite": {"pull_requests": [{"number": 42}, {"number": 43}]}}"#;
+        let event = parse_event(&headers("X-GitHub-Event", "check_suite"), body).unwrap();
+        assert_eq!(event.kind, "check_suite");
+        assert_eq!(event.pr_numbers, vec![42, 43]);
+        assert!(event.concerns(43));
+        assert!(!event.concerns(44));
+    }
+
+    #[test]
+    fn parses_github_pull_request_and_status() {
+        let body = r#"{"action": "closed", "pull_request": {"number": 7}}"#;
+        let event = parse_event(&headers("x-github-event", "pull_request"), body).unwrap();
+        assert_eq!(event.pr_numbers, vec![7]);
+
+        // Commit statuses can't be tied to a PR, so they wake everyone.
+        let status = parse_event(&headers("X-GitHub-Event", "status"), "{}").unwrap();
+        assert!(status.concerns(1));
+    }
+
+    #[test]
+    fn parses_gitlab_pipeline_and_merge_request_hooks() {
+        let pipeline = r#"{"object_kind": "pipeline", "merge_request": {"iid": 12}}"#;
+        let event = parse_event(&headers("X-Gitlab-Event", "Pipeline Hook"), pipeline).unwrap();
+        assert_eq!(event.pr_numbers, vec![12]);
+
+        let mr = r#"{"object_attributes": {"iid": 13, "state": "merged"}}"#;
+        let event = parse_event(&headers("X-Gitlab-Event", "Merge Request Hook"), mr).unwrap();
+        assert_eq!(event.pr_numbers, vec![13]);
+    }
+
+    #[test]
+    fn ignores_requests_without_event_header() {
+        assert!(parse_event(&headers("Content-Type", "application/json"), "{}").is_none());
+    }
+
+    #[test]
+    fn listener_wakes_on_delivery() {
+        let listener = WebhookListener::start("127.0.0.1:0").unwrap();
+        let addr = listener.addr().to_string();
+        let sender = thread::spawn(move || {
+            let body = r#"{"pull_request": {"number": 5}}"#;
+            let mut stream = TcpStream::connect(addr).unwrap();
+            write!(
+                stream,
+                "POST / HTTP/1.1\r\nX-GitHub-Event: pull_request\r\nContent-Length: {}\r\n\r\n{}",
+                body.len(),
+                body
+            )
+            .unwrap();
+            let mut response = String::new();
+            let _ = stream.read_to_string(&mut response);
+            response
+        });
+
+        assert!(listener.wait_for(5, Duration::from_secs(5), None).unwrap());
+        assert!(sender.join().unwrap().starts_with("HTTP/1.1 200"));
+    }
+
+    #[test]
+    fn listener_survives_a_never_ending_header() {
+        let listener = WebhookListener::start("127.0.0.1:0").unwrap();
+        let addr = listener.addr().to_string();
+
+        // One header line that never ends, on a connection left open
+        let mut flood = TcpStream::connect(&addr).unwrap();
+        write!(
+            flood,
+            "POST / HTTP/1.1\r\nX-GitHub-Event: pull_request\r\nX-Pad: "
+        )
+        .unwrap();
+        let chunk = vec![b'a'; 16 * 1024];
+        for _ in 0..8 {
+            if flood.write_all(&chunk).is_err() {
+                break;
+            }
+        }
+
+        // Deliveries keep arriving meanwhile
+        let mut stream = TcpStream::connect(&addr).unwrap();
+        let body = r#"{"pull_request": {"number": 5}}"#;
+        write!(
+            stream,
+            "POST / HTTP/1.1\r\nX-GitHub-Event: pull_request\r\nContent-Length: {}\r\n\r\n{}",
+            body.len(),
+            body
+        )
+        .unwrap();
+        assert!(listener.wait_for(5, Duration::from_secs(5), None).unwrap());
+
+        // The flooding client is turned away instead of being read forever
+        // (the unread rest of its request may turn the close into a reset)
+        flood
+            .set_read_timeout(Some(Duration::from_secs(5)))
+            .unwrap();
+        let mut response = Vec::new();
+        match flood.read_to_end(&mut response) {
+            Ok(_) => assert!(
+                String::from_utf8_lossy(&response).starts_with("HTTP/1.1 431"),
+                "{:?}",
+                String::from_utf8_lossy(&response)
+            ),
+            Err(e) => assert!(
+                !matches!(
+                    e.kind(),
+                    std::io::ErrorKind::WouldBlock | std::io::ErrorKind::TimedOut
+                ),
+                "connection was kept open: {}",
+                e
+            ),
+        }
+    }
+}
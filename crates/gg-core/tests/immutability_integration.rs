@@ -109,6 +109,9 @@ fn build_stack(oids: &[git2::Oid], states: &[Option<PrState>]) -> Stack {
             position: i + 1,
             in_merge_train: false,
             merge_train_position: None,
+            unresolved_threads: None,
+            annotations: Default::default(),
+            signed: false,
         })
         .collect();
 
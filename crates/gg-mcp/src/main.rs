@@ -2,13 +2,34 @@
 //!
 //! Exposes git-gud operations as MCP tools for AI assistants.
 
+mod permissions;
 mod tools;
 
 use rmcp::{transport::stdio, ServiceExt};
 
+use permissions::{Permissions, ALLOW_ENV};
+
+const USAGE: &str = "Usage: gg-mcp [--allow <read,write,push,land|all>]...
+
+Serves git-gud tools over MCP on stdio. --allow (or GG_MCP_ALLOW) limits the
+tools to the given permissions; by default all of them are available.";
+
 #[tokio::main]
 async fn main() -> Result<(), Box<dyn std::error::Error>> {
-    let server = tools::GgMcpServer::new();
+    let args: Vec<String> = std::env::args().skip(1).collect();
+    if args.iter().any(|a| a == "--help" || a == "-h") {
+        println!("{}", USAGE);
+        return Ok(());
+    }
+    let permissions = match Permissions::from_args(args, std::env::var(ALLOW_ENV).ok()) {
+        Ok(permissions) => permissions,
+        Err(e) => {
+            eprintln!("gg-mcp: {}\n\n{}", e, USAGE);
+            std::process::exit(2);
+        }
+    };
+
+    let server = tools::GgMcpServer::with_permissions(permissions);
     let service = server.serve(stdio()).await?;
     service.waiting().await?;
     Ok(())
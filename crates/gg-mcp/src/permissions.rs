@@ -0,0 +1,246 @@
+//! Which tools an assistant may call.
+//!
+//! Every tool needs one permission:
+//!
+//! - `read`: inspect stacks, PRs/MRs, config, conflicts and the operation log
+//! - `write`: change local history and checkouts (rebase, squash, drop,
+//!   resolve conflicts, undo...)
+//! - `push`: push the stack and create or update PRs/MRs (`stack_sync`,
+//!   `pr_comment`, and `stack_drop` with `close_pr`)
+//! - `land`: merge PRs/MRs and clean up landed stacks
+//!
+//! `gg-mcp --allow read,write` (or `GG_MCP_ALLOW=read,write`) grants only
+//! those; without either, every tool is available. Tools outside the granted
+//! permissions are left out of the tool list, and calling one anyway fails
+//! with `GG_PERMISSION_DENIED`.
+
+use std::collections::BTreeSet;
+use std::fmt;
+
+use crate::tools::McpToolError;
+
+/// Environment variable read when `--allow` is not passed.
+pub const ALLOW_ENV: &str = "GG_MCP_ALLOW";
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum Permission {
+    Read,
+    Write,
+    Push,
+    Land,
+}
+
+impl Permission {
+    const ALL: [Permission; 4] = [
+        Permission::Read,
+        Permission::Write,
+        Permission::Push,
+        Permission::Land,
+    ];
+
+    fn name(self) -> &'static str {
+        match self {
+            Permission::Read => "read",
+            Permission::Write => "write",
+            Permission::Push => "push",
+            Permission::Land => "land",
+        }
+    }
+
+    /// The permission `tool` needs; `None` for tools gg-mcp doesn't have.
+    pub fn required(tool: &str) -> Option<Permission> {
+        Some(match tool {
+            "stack_list" | "stack_log" | "stack_list_all" | "stack_status" | "stack_inbox"
+            | "stack_check_msgs" | "stack_undo_list" | "pr_info" | "pr_review" | "config_show"
+            | "conflict_status" | "conflict_show" => Permission::Read,
+            "stack_checkout" | "stack_rebase" | "stack_squash" | "stack_absorb"
+            | "stack_reconcile" | "stack_move" | "stack_navigate" | "stack_lint" | "stack_drop"
+            | "stack_split" | "stack_reorder" | "stack_undo" | "stack_restack"
+            | "conflict_resolve" | "stack_continue" | "stack_abort" => Permission::Write,
+            "stack_sync" | "pr_comment" => Permission::Push,
+            "stack_land" | "stack_clean" => Permission::Land,
+            _ => return None,
+        })
+    }
+}
+
+impl fmt::Display for Permission {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.name())
+    }
+}
+
+/// The permissions granted to this server.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Permissions(BTreeSet<Permission>);
+
+impl Default for Permissions {
+    fn default() -> Self {
+        Self(Permission::ALL.into_iter().collect())
+    }
+}
+
+impl Permissions {
+    /// Parse `--allow` values: comma-separated permission names, or `all`.
+    pub fn parse<S: AsRef<str>>(values: &[S]) -> Result<Self, String> {
+        let mut granted = BTreeSet::new();
+        for name in values
+            .iter()
+            .flat_map(|value| value.as_ref().split(','))
+            .map(str::trim)
+            .filter(|name| !name.is_empty())
+        {
+            if name == "all" {
+                granted.extend(Permission::ALL);
+                continue;
+            }
+            let permission = Permission::ALL
+                .into_iter()
+                .find(|p| p.name() == name)
+                .ok_or_else(|| {
+                    format!(
+                        "Unknown permission '{}'. Use read, write, push, land or all",
+                        name
+                    )
+                })?;
+            granted.insert(permission);
+        }
+        if granted.is_empty() {
+            return Err("--allow needs at least one permission".to_string());
+        }
+        Ok(Self(granted))
+    }
+
+    /// The permissions from the command line (`--allow <list>`, repeatable),
+    /// falling back to [`ALLOW_ENV`], then to all of them.
+    pub fn from_args<I: IntoIterator<Item = String>>(
+        args: I,
+        env: Option<String>,
+    ) -> Result<Self, String> {
+        let mut values = Vec::new();
+        let mut args = args.into_iter();
+        while let Some(arg) = args.next() {
+            if let Some(value) = arg.strip_prefix("--allow=") {
+                values.push(value.to_string());
+            } else if arg == "--allow" {
+                values.push(args.next().ok_or("--allow needs a value")?);
+            } else {
+                return Err(format!("Unknown argument '{}'", arg));
+            }
+        }
+        match (values.is_empty(), env.filter(|v| !v.trim().is_empty())) {
+            (false, _) => Self::parse(&values),
+            (true, Some(env)) => Self::parse(&[env]),
+            (true, None) => Ok(Self::default()),
+        }
+    }
+
+    /// Whether every permission is granted.
+    pub fn is_all(&self) -> bool {
+        self.0.len() == Permission::ALL.len()
+    }
+
+    pub fn allows(&self, tool: &str) -> bool {
+        match Permission::required(tool) {
+            Some(permission) => self.0.contains(&permission),
+            None => self.is_all(),
+        }
+    }
+
+    /// Fail with `GG_PERMISSION_DENIED` when `tool` is not allowed.
+    pub fn check(&self, tool: &str) -> Result<(), McpToolError> {
+        if self.allows(tool) {
+            return Ok(());
+        }
+        Err(self.denied(
+            tool,
+            Permission::required(tool)
+                .map(|p| p.to_string())
+                .unwrap_or_else(|| "all".to_string()),
+        ))
+    }
+
+    /// Fail with `GG_PERMISSION_DENIED` unless `permission` is granted; for
+    /// tool options that need more than the tool itself (`stack_drop`'s
+    /// `close_pr` closes PRs/MRs, so it needs `push`).
+    pub fn check_option(&self, tool: &str, permission: Permission) -> Result<(), McpToolError> {
+        if self.0.contains(&permission) {
+            return Ok(());
+        }
+        Err(self.denied(tool, permission.to_string()))
+    }
+
+    fn denied(&self, tool: &str, required: String) -> McpToolError {
+        McpToolError::PermissionDenied {
+            tool: tool.to_string(),
+            required,
+            granted: self.to_string(),
+        }
+    }
+}
+
+impl fmt::Display for Permissions {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let names: Vec<&str> = self.0.iter().map(|p| p.name()).collect();
+        f.write_str(&names.join(","))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn args(args: &[&str]) -> Vec<String> {
+        args.iter().map(|a| a.to_string()).collect()
+    }
+
+    #[test]
+    fn parses_allow_flags_and_env() {
+        let permissions = Permissions::from_args(args(&["--allow", "read,write"]), None).unwrap();
+        assert_eq!(permissions.to_string(), "read,write");
+        let permissions =
+            Permissions::from_args(args(&["--allow=read", "--allow", "land"]), None).unwrap();
+        assert_eq!(permissions.to_string(), "read,land");
+
+        let from_env = Permissions::from_args(Vec::new(), Some("read".to_string())).unwrap();
+        assert_eq!(from_env.to_string(), "read");
+        let flag_wins =
+            Permissions::from_args(args(&["--allow", "push"]), Some("read".to_string())).unwrap();
+        assert_eq!(flag_wins.to_string(), "push");
+
+        assert!(Permissions::from_args(Vec::new(), None).unwrap().is_all());
+        assert!(Permissions::parse(&["all"]).unwrap().is_all());
+        assert!(Permissions::parse(&["admin"]).is_err());
+        assert!(Permissions::from_args(args(&["--allow"]), None).is_err());
+        assert!(Permissions::from_args(args(&["--verbose"]), None).is_err());
+    }
+
+    #[test]
+    fn read_only_denies_pushes_and_merges() {
+        let read = Permissions::parse(&["read"]).unwrap();
+        assert!(read.allows("stack_list"));
+        assert!(read.allows("conflict_status"));
+        assert!(!read.allows("stack_rebase"));
+        assert!(!read.allows("stack_sync"));
+        assert!(!read.allows("stack_land"));
+        assert!(!read.allows("not_a_tool"));
+
+        let error: String = read.check("stack_land").unwrap_err().into();
+        assert_eq!(
+            error,
+            "error[GG_PERMISSION_DENIED]: stack_land needs the `land` permission, but gg-mcp only allows read"
+        );
+    }
+
+    #[test]
+    fn closing_prs_on_drop_needs_push() {
+        let read_write = Permissions::parse(&["read,write"]).unwrap();
+        assert!(read_write.allows("stack_drop"));
+        assert!(read_write
+            .check_option("stack_drop", Permission::Push)
+            .is_err());
+        assert!(Permissions::default()
+            .check_option("stack_drop", Permission::Push)
+            .is_ok());
+    }
+}
@@ -3,20 +3,25 @@
 //! Read-only tools that expose stack and PR information.
 
 use rmcp::{
-    handler::server::{router::tool::ToolRouter, wrapper::Parameters},
-    model::{ServerCapabilities, ServerInfo},
-    schemars, tool, tool_handler, tool_router, ServerHandler,
+    handler::server::{router::tool::ToolRouter, tool::ToolCallContext, wrapper::Parameters},
+    model::{CallToolRequestParams, CallToolResult, ContentBlock, ServerCapabilities, ServerInfo},
+    schemars,
+    service::RequestContext,
+    tool, tool_handler, tool_router, ErrorData, RoleServer, ServerHandler,
 };
 use serde::{Deserialize, Serialize};
 use std::path::PathBuf;
 use std::process::Command;
 use thiserror::Error;
 
-use gg_core::config::Config;
+use gg_core::config::{Config, LintCommand};
+use gg_core::conflicts::{self, Resolution};
 use gg_core::git;
 use gg_core::provider::{CiStatus, PrState, Provider};
 use gg_core::stack::Stack;
 
+use crate::permissions::{Permission, Permissions};
+
 // --- Error types ---
 
 /// Errors that can occur during MCP tool execution.
@@ -37,11 +42,39 @@ pub enum McpToolError {
     /// Failed to retrieve PR/MR information.
     #[error("Failed to get PR #{number}: {reason}")]
     PrLookup { number: u64, reason: String },
+
+    /// Failed to read or resolve the conflicts of a paused operation.
+    #[error("{0}")]
+    Conflicts(gg_core::error::GgError),
+
+    /// The tool needs a permission this server was not started with.
+    #[error("{tool} needs the `{required}` permission, but gg-mcp only allows {granted}")]
+    PermissionDenied {
+        tool: String,
+        required: String,
+        granted: String,
+    },
 }
 
+impl McpToolError {
+    /// Stable error code, as printed by the CLI (see `GgError::code`).
+    pub fn code(&self) -> &'static str {
+        match self {
+            McpToolError::NotInRepo { .. } => "GG_NOT_IN_REPO",
+            McpToolError::ConfigLoad(e) => e.code(),
+            McpToolError::ProviderDetect(_) => "GG_PROVIDER_DETECT",
+            McpToolError::PrLookup { .. } => "GG_PR_LOOKUP",
+            McpToolError::Conflicts(e) => e.code(),
+            McpToolError::PermissionDenied { .. } => "GG_PERMISSION_DENIED",
+        }
+    }
+}
+
+/// Tool errors read like the CLI's: `error[GG_...]: message`, so clients
+/// can branch on the code whether a tool ran in-process or through `gg`.
 impl From<McpToolError> for String {
     fn from(err: McpToolError) -> Self {
-        err.to_string()
+        format!("error[{}]: {}", err.code(), err)
     }
 }
 
@@ -61,14 +94,21 @@ fn open_repo() -> Result<git2::Repository, McpToolError> {
     })
 }
 
-/// Load config from repo.
+/// Load config from repo, and apply its branch naming templates, commit
+/// signing and git hook settings (they may have changed since the last call).
 fn load_config(repo: &git2::Repository) -> Result<Config, McpToolError> {
-    Config::load_with_global(repo.commondir()).map_err(McpToolError::ConfigLoad)
+    let config = Config::load(repo.commondir()).map_err(McpToolError::ConfigLoad)?;
+    let naming = gg_core::branch_naming::BranchNaming::from_config(&config)
+        .map_err(|e| McpToolError::ConfigLoad(gg_core::error::GgError::Other(e)))?;
+    gg_core::branch_naming::set(naming);
+    gg_core::signing::set(config.defaults.sign_commits);
+    gg_core::hooks::set_git_hooks(config.defaults.run_hooks);
+    Ok(config)
 }
 
 /// Load current stack.
 fn load_stack(repo: &git2::Repository, config: &Config) -> Result<Stack, McpToolError> {
-    Stack::load(repo, config).map_err(McpToolError::ConfigLoad)
+    Stack::load_active(repo, config).map_err(McpToolError::ConfigLoad)
 }
 
 // --- Response types ---
@@ -82,7 +122,10 @@ struct StackEntryInfo {
     pr_number: Option<u64>,
     pr_state: Option<String>,
     approved: bool,
+    changes_requested: bool,
     ci_status: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    unresolved_threads: Option<usize>,
     is_current: bool,
 }
 
@@ -110,6 +153,8 @@ struct StackSummary {
     base: String,
     commit_count: usize,
     is_current: bool,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    description: Option<String>,
 }
 
 #[derive(Debug, Serialize)]
@@ -123,7 +168,7 @@ struct ConfigInfo {
     provider: Option<String>,
     base_branch: Option<String>,
     branch_username: Option<String>,
-    lint_commands: Vec<String>,
+    lint_commands: Vec<LintCommand>,
     auto_add_gg_ids: bool,
     land_auto_clean: bool,
     sync_auto_lint: bool,
@@ -137,6 +182,9 @@ pub struct StackListParams {
     /// Refresh PR/MR status from remote before listing
     #[serde(default)]
     pub refresh: bool,
+    /// Also fetch review state and unresolved review threads (implies refresh)
+    #[serde(default)]
+    pub prs: bool,
 }
 
 #[derive(Debug, Deserialize, schemars::JsonSchema)]
@@ -152,6 +200,14 @@ pub struct PrInfoParams {
     pub number: u64,
 }
 
+#[derive(Debug, Deserialize, schemars::JsonSchema)]
+pub struct PrReviewParams {
+    /// Entry whose review threads to fetch: position (1-indexed), short SHA,
+    /// or GG-ID (default: current)
+    #[serde(default)]
+    pub target: Option<String>,
+}
+
 #[derive(Debug, Deserialize, schemars::JsonSchema)]
 pub struct StackCheckoutParams {
     /// Stack name to create or switch to
@@ -159,6 +215,12 @@ pub struct StackCheckoutParams {
     /// Base branch (default: main/master)
     #[serde(default)]
     pub base: Option<String>,
+    /// Tag, commit or branch a new stack starts from (e.g. a release tag)
+    #[serde(default)]
+    pub from: Option<String>,
+    /// What the stack is for, shown in `gg ls` and available to PR templates
+    #[serde(default)]
+    pub description: Option<String>,
     /// Use a git worktree for isolation
     #[serde(default)]
     pub worktree: bool,
@@ -187,9 +249,18 @@ pub struct StackSyncParams {
     /// Only sync up to this position, GG-ID, or SHA
     #[serde(default)]
     pub until: Option<String>,
+    /// Sync just this entry (position, GG-ID, or SHA)
+    #[serde(default)]
+    pub only: Option<String>,
+    /// Request reviews on new PRs/MRs from these users or `org/team` teams
+    #[serde(default)]
+    pub reviewers: Vec<String>,
     /// Skip the pre-push hook (forwards `--no-verify` to `gg sync`)
     #[serde(default)]
     pub no_verify: bool,
+    /// Only report what would be pushed and which PRs/MRs would change
+    #[serde(default)]
+    pub dry_run: bool,
 }
 
 #[derive(Debug, Deserialize, schemars::JsonSchema)]
@@ -197,13 +268,17 @@ pub struct StackLandParams {
     /// Land all approved PRs (not just the first)
     #[serde(default)]
     pub all: bool,
-    /// Use squash merge
+    /// Merge strategy: "squash", "merge" or "rebase" (default: the repo's
+    /// `defaults.merge_strategy`, or squash)
+    #[serde(default)]
+    pub strategy: Option<String>,
+    /// Use squash merge (same as strategy "squash")
     #[serde(default)]
     pub squash: bool,
     /// Auto-clean the stack after landing
     #[serde(default)]
     pub auto_clean: bool,
-    /// Only land up to this position, GG-ID, or SHA
+    /// Only land up to this position, GG-ID, SHA, or PR/MR number (#123 / !123)
     #[serde(default)]
     pub until: Option<String>,
 }
@@ -213,6 +288,15 @@ pub struct StackCleanParams {
     /// Clean all merged stacks (not just current)
     #[serde(default)]
     pub all: bool,
+    /// Only report which stacks would be deleted or skipped, and why
+    #[serde(default)]
+    pub dry_run: bool,
+    /// Keep merged stack branches under refs/gg/archive instead of deleting them
+    #[serde(default)]
+    pub archive: bool,
+    /// Also clean stacks untouched for this long (e.g. "30d", "2w") with no open PRs/MRs
+    #[serde(default)]
+    pub stale: Option<String>,
 }
 
 #[derive(Debug, Deserialize, schemars::JsonSchema)]
@@ -242,6 +326,12 @@ pub struct StackSquashParams {
     /// Only set after surfacing the affected commits to the user.
     #[serde(default)]
     pub force: bool,
+    /// Amend into this entry (position, short SHA or GG-ID) instead of the
+    /// current one
+    pub into: Option<String>,
+    /// Skip git hooks (pre-commit, commit-msg, ...) for this amend
+    #[serde(default)]
+    pub no_verify: bool,
 }
 
 #[derive(Debug, Deserialize, schemars::JsonSchema)]
@@ -265,6 +355,18 @@ pub struct StackAbsorbParams {
     /// Only set after surfacing the affected commits to the user.
     #[serde(default)]
     pub force: bool,
+    /// Amend all staged changes into this entry (position, short SHA or
+    /// GG-ID) instead of attributing hunks
+    pub into: Option<String>,
+    /// Stage unstaged changes to tracked files and absorb them too
+    #[serde(default)]
+    pub all: bool,
+    /// With `all`, stage untracked files too
+    #[serde(default)]
+    pub include_untracked: bool,
+    /// Skip git hooks for the rebase (and amend) absorb runs
+    #[serde(default)]
+    pub no_verify: bool,
 }
 
 #[derive(Debug, Deserialize, schemars::JsonSchema)]
@@ -281,7 +383,8 @@ pub struct StackReconcileParams {
 
 #[derive(Debug, Deserialize, schemars::JsonSchema)]
 pub struct StackMoveParams {
-    /// Target: position number, GG-ID (e.g. c-abc1234), or SHA prefix
+    /// Target: position number, GG-ID (e.g. c-abc1234), PR/MR number (#123),
+    /// or SHA prefix
     pub target: String,
 }
 
@@ -296,6 +399,20 @@ pub struct StackLintParams {
     /// Only lint up to this position number
     #[serde(default)]
     pub until: Option<usize>,
+    /// Only lint from this position number
+    #[serde(default)]
+    pub from: Option<usize>,
+    /// Commits to lint in parallel (0 = auto, 1 = sequential; default from config)
+    #[serde(default)]
+    pub jobs: Option<usize>,
+}
+
+#[derive(Debug, Deserialize, schemars::JsonSchema)]
+pub struct StackCheckMsgsParams {
+    /// Commits to check as `<from>..<to>`, or a single ref meaning
+    /// `<ref>..HEAD` (default: the current stack)
+    #[serde(default)]
+    pub range: Option<String>,
 }
 
 #[derive(Debug, Deserialize, schemars::JsonSchema)]
@@ -310,6 +427,22 @@ pub struct StackDropParams {
     /// approved rewriting immutable commits.
     #[serde(default)]
     pub force: bool,
+    /// Also close the dropped entries' open PRs/MRs on the provider. Needs
+    /// the `push` permission.
+    #[serde(default)]
+    pub close_pr: bool,
+}
+
+#[derive(Debug, Deserialize, schemars::JsonSchema)]
+pub struct PrCommentParams {
+    /// Comment body (Markdown)
+    pub message: String,
+    /// Entry to comment on: position (1-indexed), short SHA, or GG-ID (default: current)
+    #[serde(default)]
+    pub target: Option<String>,
+    /// Comment on every entry that has a PR/MR
+    #[serde(default)]
+    pub all: bool,
 }
 
 #[derive(Debug, Deserialize, schemars::JsonSchema)]
@@ -322,6 +455,27 @@ pub struct StackRestackParams {
     pub from: Option<String>,
 }
 
+#[derive(Debug, Deserialize, schemars::JsonSchema)]
+pub struct ConflictShowParams {
+    /// Conflicted file, relative to the repository root
+    pub path: String,
+}
+
+#[derive(Debug, Deserialize, schemars::JsonSchema)]
+pub struct ConflictResolveParams {
+    /// Conflicted file, relative to the repository root
+    pub path: String,
+    /// Take one side of every conflict hunk: "ours" or "theirs"
+    #[serde(default)]
+    pub take: Option<String>,
+    /// The whole resolved file, without conflict markers
+    #[serde(default)]
+    pub content: Option<String>,
+    /// Resolve by deleting the file
+    #[serde(default)]
+    pub delete: bool,
+}
+
 #[derive(Debug, Deserialize, schemars::JsonSchema)]
 pub struct StackSplitParams {
     /// Target commit: position (1-indexed), short SHA, or GG-ID (default: current)
@@ -346,10 +500,14 @@ pub struct StackSplitParams {
 pub struct StackReorderParams {
     /// New order as positions (1-indexed), e.g., "3,1,2" or "3 1 2"
     pub order: String,
-    /// Bypass the immutability guard on merged / base-ancestor commits.
+    /// Bypass the immutability guard on merged / base-ancestor commits,
+    /// and the refusal of orders that will conflict.
     /// Only set after surfacing the affected commits to the user.
     #[serde(default)]
     pub force: bool,
+    /// Only report which dependencies between commits the order breaks
+    #[serde(default)]
+    pub dry_run: bool,
 }
 
 #[derive(Debug, Deserialize, schemars::JsonSchema)]
@@ -423,7 +581,9 @@ fn build_stack_info(stack: &Stack, repo: &git2::Repository) -> StackInfo {
                 pr_number: e.mr_number,
                 pr_state: e.mr_state.as_ref().map(pr_state_str).map(String::from),
                 approved: e.approved,
+                changes_requested: e.changes_requested,
                 ci_status: e.ci_status.as_ref().map(ci_status_str).map(String::from),
+                unresolved_threads: e.unresolved_threads,
                 is_current: head_oid.is_some_and(|oid| oid == e.oid),
             })
             .collect(),
@@ -469,8 +629,39 @@ fn to_json<T: Serialize>(value: &T) -> String {
     serde_json::to_string_pretty(value).unwrap_or_else(|e| format!("{{\"error\": \"{}\"}}", e))
 }
 
+/// The resolution `conflict_resolve` applies: exactly one of `take`,
+/// `content` and `delete`.
+fn conflict_resolution(params: &ConflictResolveParams) -> Result<Resolution, String> {
+    match (params.take.as_deref(), &params.content, params.delete) {
+        (Some("ours"), None, false) => Ok(Resolution::Ours),
+        (Some("theirs"), None, false) => Ok(Resolution::Theirs),
+        (Some(other), None, false) => Err(format!(
+            "Invalid take '{}'. Use \"ours\" or \"theirs\"",
+            other
+        )),
+        (None, Some(content), false) => Ok(Resolution::Content(content.clone())),
+        (None, None, true) => Ok(Resolution::Delete),
+        _ => Err("Pass exactly one of `take`, `content` or `delete`".to_string()),
+    }
+}
+
 /// Build the argv for `gg undo [--json] [<operation_id>]`.
 /// Extracted for unit testing — see `build_stack_undo_args_*` tests.
+fn build_pr_comment_args(params: PrCommentParams) -> Vec<String> {
+    let mut args = vec![
+        "comment".to_string(),
+        "--json".to_string(),
+        "--message".to_string(),
+        params.message,
+    ];
+    if params.all {
+        args.push("--all".to_string());
+    } else if let Some(target) = params.target {
+        args.push(target);
+    }
+    args
+}
+
 fn build_stack_undo_args(operation_id: Option<String>) -> Vec<String> {
     let mut args = vec!["undo".to_string(), "--json".to_string()];
     if let Some(id) = operation_id {
@@ -499,12 +690,29 @@ fn build_stack_undo_list_args(limit: Option<usize>) -> Vec<String> {
 pub struct GgMcpServer {
     #[allow(dead_code)]
     tool_router: ToolRouter<Self>,
+    permissions: Permissions,
+}
+
+impl Default for GgMcpServer {
+    /// A server with every tool.
+    fn default() -> Self {
+        Self::with_permissions(Permissions::default())
+    }
 }
 
 impl GgMcpServer {
-    pub fn new() -> Self {
+    /// A server whose tools are limited to `permissions`; the others are
+    /// left out of the tool list.
+    pub fn with_permissions(permissions: Permissions) -> Self {
+        let mut tool_router = Self::tool_router();
+        for tool in tool_router.list_all() {
+            if !permissions.allows(&tool.name) {
+                tool_router.disable_route(tool.name);
+            }
+        }
         Self {
-            tool_router: Self::tool_router(),
+            tool_router,
+            permissions,
         }
     }
 }
@@ -523,21 +731,10 @@ impl GgMcpServer {
         let config = load_config(&repo)?;
         let mut stack = load_stack(&repo, &config)?;
 
-        if params.refresh {
-            let provider =
-                Provider::detect(&repo).map_err(|e| McpToolError::ProviderDetect(e.to_string()))?;
-
-            for entry in &mut stack.entries {
-                if let Some(number) = entry.mr_number {
-                    if let Ok(info) = provider.get_pr_info(number) {
-                        entry.mr_state = Some(info.state);
-                        entry.approved = info.approved;
-                    }
-                    if let Ok(ci) = provider.get_pr_ci_status(number) {
-                        entry.ci_status = Some(ci);
-                    }
-                }
-            }
+        if params.refresh || params.prs {
+            let provider = Provider::for_stack(&repo, &config, &stack.name)
+                .map_err(|e| McpToolError::ProviderDetect(e.to_string()))?;
+            stack.refresh_pr_details(&provider, config.get_sync_jobs(), params.prs);
         }
 
         let info = build_stack_info(&stack, &repo);
@@ -557,8 +754,8 @@ impl GgMcpServer {
         let mut stack = load_stack(&repo, &config)?;
 
         if params.refresh {
-            let provider =
-                Provider::detect(&repo).map_err(|e| McpToolError::ProviderDetect(e.to_string()))?;
+            let provider = Provider::for_stack(&repo, &config, &stack.name)
+                .map_err(|e| McpToolError::ProviderDetect(e.to_string()))?;
             stack
                 .refresh_mr_info(&provider)
                 .map_err(McpToolError::ConfigLoad)?;
@@ -604,7 +801,7 @@ impl GgMcpServer {
                     .branch_username
                     .as_deref()
                     .unwrap_or("unknown");
-                let branch = format!("{}/{}", branch_username, stack_name);
+                let branch = git::format_stack_branch(branch_username, stack_name);
                 let commit_count = git::get_stack_commit_oids(&repo, &base, Some(&branch))
                     .map(|oids| oids.len())
                     .unwrap_or(0);
@@ -614,6 +811,7 @@ impl GgMcpServer {
                     base,
                     commit_count,
                     is_current,
+                    description: gg_core::stack::stack_description(&repo, &config, stack_name),
                 });
             }
         }
@@ -648,14 +846,33 @@ impl GgMcpServer {
         Ok(to_json(&status))
     }
 
+    /// Fetch unresolved review threads for a stack entry.
+    #[tool(
+        description = "Get the unresolved review threads of a stack entry's PR/MR (default: current entry), grouped by file and ordered by line, with every comment in full. Use it to address review feedback next to the code."
+    )]
+    fn pr_review(&self, Parameters(params): Parameters<PrReviewParams>) -> Result<String, String> {
+        let mut args = vec!["review".to_string(), "--json".to_string()];
+        if let Some(target) = params.target {
+            args.push(target);
+        }
+        run_gg_command(&args)
+    }
+
     /// Get detailed information about a specific PR/MR by number.
     #[tool(
         description = "Get PR/MR details: state, title, URL, approval status, mergeability, and CI status"
     )]
     fn pr_info(&self, Parameters(params): Parameters<PrInfoParams>) -> Result<String, String> {
         let repo = open_repo()?;
+        // The PR/MR lives behind the remote of the current stack, if any
+        let remote = match (load_config(&repo), git::current_branch_name(&repo)) {
+            (Ok(config), Some(branch)) => git::parse_stack_branch(&branch)
+                .map(|(_, name)| config.get_remote_for_stack(&name).to_string()),
+            _ => None,
+        };
         let provider =
-            Provider::detect(&repo).map_err(|e| McpToolError::ProviderDetect(e.to_string()))?;
+            Provider::for_remote(&repo, remote.as_deref().unwrap_or(git::DEFAULT_REMOTE))
+                .map_err(|e| McpToolError::ProviderDetect(e.to_string()))?;
 
         let info = provider
             .get_pr_info(params.number)
@@ -725,6 +942,14 @@ impl GgMcpServer {
             args.push("--base".to_string());
             args.push(base.clone());
         }
+        if let Some(ref from) = params.from {
+            args.push("--from".to_string());
+            args.push(from.clone());
+        }
+        if let Some(ref description) = params.description {
+            args.push("--description".to_string());
+            args.push(description.clone());
+        }
         if params.worktree {
             args.push("-w".to_string());
         }
@@ -762,9 +987,20 @@ impl GgMcpServer {
             args.push("--until".to_string());
             args.push(until.clone());
         }
+        if let Some(ref only) = params.only {
+            args.push("--only".to_string());
+            args.push(only.clone());
+        }
+        for reviewer in &params.reviewers {
+            args.push("--reviewer".to_string());
+            args.push(reviewer.clone());
+        }
         if params.no_verify {
             args.push("--no-verify".to_string());
         }
+        if params.dry_run {
+            args.push("--dry-run".to_string());
+        }
         run_gg_command(&args)
     }
 
@@ -780,8 +1016,13 @@ impl GgMcpServer {
         if params.all {
             args.push("--all".to_string());
         }
-        if params.squash {
-            args.push("--squash".to_string());
+        if let Some(strategy) = params
+            .strategy
+            .clone()
+            .or(params.squash.then(|| "squash".to_string()))
+        {
+            args.push("--strategy".to_string());
+            args.push(strategy);
         }
         if params.auto_clean {
             args.push("--auto-clean".to_string());
@@ -805,6 +1046,16 @@ impl GgMcpServer {
         if params.all {
             args.push("--all".to_string());
         }
+        if params.dry_run {
+            args.push("--dry-run".to_string());
+        }
+        if params.archive {
+            args.push("--archive".to_string());
+        }
+        if let Some(stale) = params.stale {
+            args.push("--stale".to_string());
+            args.push(stale);
+        }
         run_gg_command(&args)
     }
 
@@ -828,7 +1079,7 @@ impl GgMcpServer {
 
     /// Squash staged changes into the current commit.
     #[tool(
-        description = "Squash (amend) staged changes into the current commit. Use --all to stage all changes first."
+        description = "Squash (amend) staged changes into the current commit, or into another entry with `into`. Use --all to stage all changes first."
     )]
     fn stack_squash(
         &self,
@@ -841,18 +1092,30 @@ impl GgMcpServer {
         if params.force {
             args.push("--force".to_string());
         }
+        if let Some(into) = params.into {
+            args.push("--into".to_string());
+            args.push(into);
+        }
+        if params.no_verify {
+            args.push("--no-verify".to_string());
+        }
         run_gg_command(&args)
     }
 
     /// Auto-absorb staged changes into the appropriate commits.
     #[tool(
-        description = "Auto-absorb staged changes into the correct commits in the stack based on which files were modified."
+        description = "Auto-absorb staged changes into the correct commits in the stack based on which lines were modified. Returns JSON listing each hunk's target (or why it was left staged) and the fixup commits created, unless whole_file or into is set."
     )]
     fn stack_absorb(
         &self,
         Parameters(params): Parameters<StackAbsorbParams>,
     ) -> Result<String, String> {
         let mut args = vec!["absorb".to_string()];
+        // The JSON mode uses gg's own hunk attribution, which has no
+        // whole-file variant; `--into` skips attribution altogether.
+        if !params.whole_file && params.into.is_none() {
+            args.push("--json".to_string());
+        }
         if params.dry_run {
             args.push("--dry-run".to_string());
         }
@@ -871,6 +1134,19 @@ impl GgMcpServer {
         if params.force {
             args.push("--force".to_string());
         }
+        if let Some(into) = params.into {
+            args.push("--into".to_string());
+            args.push(into);
+        }
+        if params.all {
+            args.push("--all".to_string());
+        }
+        if params.include_untracked {
+            args.push("--include-untracked".to_string());
+        }
+        if params.no_verify {
+            args.push("--no-verify".to_string());
+        }
         run_gg_command(&args)
     }
 
@@ -934,10 +1210,34 @@ impl GgMcpServer {
         Parameters(params): Parameters<StackLintParams>,
     ) -> Result<String, String> {
         let mut args = vec!["lint".to_string(), "--json".to_string()];
+        if let Some(from) = params.from {
+            args.push("--from".to_string());
+            args.push(from.to_string());
+        }
         if let Some(until) = params.until {
             args.push("--until".to_string());
             args.push(until.to_string());
         }
+        if let Some(jobs) = params.jobs {
+            args.push("--jobs".to_string());
+            args.push(jobs.to_string());
+        }
+        run_gg_command(&args)
+    }
+
+    /// Check commit subjects against the commit message policy.
+    #[tool(
+        description = "Check the stack's commit subjects (or a commit range) against the commit message policy, Conventional Commits by default. Returns JSON with each commit's type, scope, breaking flag and whether it passes; fails when any subject doesn't."
+    )]
+    fn stack_check_msgs(
+        &self,
+        Parameters(params): Parameters<StackCheckMsgsParams>,
+    ) -> Result<String, String> {
+        let mut args = vec!["check-msgs".to_string(), "--json".to_string()];
+        if let Some(range) = params.range {
+            args.push("--range".to_string());
+            args.push(range);
+        }
         run_gg_command(&args)
     }
 
@@ -954,6 +1254,11 @@ impl GgMcpServer {
         if params.targets.is_empty() {
             return Err("At least one target is required".to_string());
         }
+        // Closing PRs/MRs changes the provider, not just local history
+        if params.close_pr {
+            self.permissions
+                .check_option("stack_drop", Permission::Push)?;
+        }
         // Always pass `--yes` to skip the interactive prompt. Only add
         // `--force` when the caller has explicitly opted into rewriting
         // merged/base-ancestor commits — otherwise the immutability guard
@@ -962,6 +1267,9 @@ impl GgMcpServer {
         if params.force {
             args.push("--force".to_string());
         }
+        if params.close_pr {
+            args.push("--close-pr".to_string());
+        }
         args.push("--json".to_string());
         args.extend(params.targets);
         run_gg_command(&args)
@@ -1029,6 +1337,9 @@ impl GgMcpServer {
         if params.force {
             args.push("--force".to_string());
         }
+        if params.dry_run {
+            args.push("--dry-run".to_string());
+        }
         run_gg_command(&args)
     }
 
@@ -1073,15 +1384,109 @@ impl GgMcpServer {
         }
         run_gg_command(&args)
     }
+
+    // --- Conflict tools ---
+
+    /// Show the paused operation and its conflicted files.
+    #[tool(
+        description = "Show the rebase (or merge/cherry-pick) paused on conflicts: which gg command started it, the commit being replayed, and each conflicted file with its kind (both_modified, both_added, deleted_by_us, deleted_by_them) and number of conflict hunks. During a rebase \"ours\" is the base with the commits replayed so far, \"theirs\" is the commit being replayed. Returns JSON; `files` is empty once everything is resolved."
+    )]
+    fn conflict_status(&self) -> Result<String, String> {
+        let repo = open_repo()?;
+        let status = conflicts::status(&repo).map_err(McpToolError::Conflicts)?;
+        Ok(to_json(&status))
+    }
+
+    /// Show the conflict hunks of a file.
+    #[tool(
+        description = "Show the conflict hunks of a conflicted file: for each `<<<<<<<` block its line, the ours and theirs text, and the common ancestor when merge.conflictStyle is diff3. Read the file itself for the surrounding code."
+    )]
+    fn conflict_show(
+        &self,
+        Parameters(params): Parameters<ConflictShowParams>,
+    ) -> Result<String, String> {
+        let repo = open_repo()?;
+        let details = conflicts::show(&repo, &params.path).map_err(McpToolError::Conflicts)?;
+        Ok(to_json(&details))
+    }
+
+    /// Resolve a conflicted file and stage it.
+    #[tool(
+        description = "Resolve a conflicted file and stage it. Pass exactly one of: `take` (\"ours\" or \"theirs\", applied to every hunk while keeping the cleanly merged parts), `content` (the whole resolved file, which must not contain conflict markers), or `delete: true`. Returns the updated conflict status; call stack_continue once no files are left."
+    )]
+    fn conflict_resolve(
+        &self,
+        Parameters(params): Parameters<ConflictResolveParams>,
+    ) -> Result<String, String> {
+        let resolution = conflict_resolution(&params)?;
+        let repo = open_repo()?;
+        conflicts::resolve(&repo, &params.path, resolution).map_err(McpToolError::Conflicts)?;
+        let status = conflicts::status(&repo).map_err(McpToolError::Conflicts)?;
+        Ok(to_json(&status))
+    }
+
+    /// Continue the paused operation.
+    #[tool(
+        description = "Continue the rebase and the gg command that paused on conflicts (gg continue), once every conflicted file is resolved. Fails with GG_REBASE_CONFLICT when the next commit conflicts too; check conflict_status again."
+    )]
+    fn stack_continue(&self) -> Result<String, String> {
+        run_gg_command(&["continue".to_string()])
+    }
+
+    /// Abort the paused operation.
+    #[tool(
+        description = "Abort the rebase paused on conflicts and put the branches and HEAD back where the gg command found them (gg abort)."
+    )]
+    fn stack_abort(&self) -> Result<String, String> {
+        run_gg_command(&["abort".to_string()])
+    }
+
+    /// Post a comment on stack entries' PRs/MRs.
+    #[tool(
+        description = "Post a Markdown comment on an entry's PR/MR (default: current entry), or on every PR/MR in the stack with `all: true`. Use it to reply to review feedback. Returns JSON with the commented positions and PR/MR numbers."
+    )]
+    fn pr_comment(
+        &self,
+        Parameters(params): Parameters<PrCommentParams>,
+    ) -> Result<String, String> {
+        if params.message.trim().is_empty() {
+            return Err("A non-empty message is required".to_string());
+        }
+        run_gg_command(&build_pr_comment_args(params))
+    }
 }
 
 #[tool_handler(router = self.tool_router)]
 impl ServerHandler for GgMcpServer {
     fn get_info(&self) -> ServerInfo {
-        ServerInfo::new(ServerCapabilities::builder().enable_tools().build()).with_instructions(
-            "git-gud (gg) MCP server. Provides tools to inspect and manage stacked-diffs \
-                 workflows for GitHub and GitLab repositories.",
-        )
+        let mut instructions = "git-gud (gg) MCP server. Provides tools to inspect and manage \
+             stacked-diffs workflows for GitHub and GitLab repositories."
+            .to_string();
+        if !self.permissions.is_all() {
+            instructions.push_str(&format!(
+                " This server only allows {} tools.",
+                self.permissions
+            ));
+        }
+        ServerInfo::new(ServerCapabilities::builder().enable_tools().build())
+            .with_instructions(instructions)
+    }
+
+    /// Tools outside the granted permissions are not listed, but a client
+    /// may still call one by name: answer with `GG_PERMISSION_DENIED`
+    /// rather than an unknown tool.
+    async fn call_tool(
+        &self,
+        request: CallToolRequestParams,
+        context: RequestContext<RoleServer>,
+    ) -> Result<CallToolResult, ErrorData> {
+        if let Err(denied) = self.permissions.check(&request.name) {
+            return Ok(CallToolResult::error(vec![ContentBlock::text(
+                String::from(denied),
+            )]));
+        }
+        let context = ToolCallContext::new(self, request, context);
+        self.tool_router.call(context).await
     }
 }
 
@@ -1137,7 +1542,7 @@ mod tests {
             provider: Some("github".to_string()),
             base_branch: Some("main".to_string()),
             branch_username: Some("user".to_string()),
-            lint_commands: vec!["cargo fmt".to_string()],
+            lint_commands: vec!["cargo fmt".into()],
             auto_add_gg_ids: true,
             land_auto_clean: false,
             sync_auto_lint: true,
@@ -1193,11 +1598,38 @@ mod tests {
         let err = McpToolError::ProviderDetect("test".to_string());
         let s: String = err.into();
         assert!(s.contains("Failed to detect provider"));
+        assert!(s.starts_with("error[GG_PROVIDER_DETECT]: "));
+
+        let s: String = McpToolError::ConfigLoad(gg_core::error::GgError::NotOnStack).into();
+        assert!(s.starts_with("error[GG_NOT_ON_STACK]: "), "{}", s);
+    }
+
+    #[test]
+    fn every_tool_needs_a_permission() {
+        for tool in GgMcpServer::tool_router().list_all() {
+            assert!(
+                Permission::required(&tool.name).is_some(),
+                "{} has no permission in Permission::required",
+                tool.name
+            );
+        }
+    }
+
+    #[test]
+    fn read_only_server_lists_only_read_tools() {
+        let server = GgMcpServer::with_permissions(Permissions::parse(&["read"]).unwrap());
+        let tools = server.tool_router.list_all();
+        assert!(tools.iter().any(|t| t.name == "stack_list"));
+        assert!(tools
+            .iter()
+            .all(|t| Permission::required(&t.name) == Some(Permission::Read)));
+        assert!(server.tool_router.get("stack_land").is_none());
+        assert!(server.tool_router.get("stack_sync").is_none());
     }
 
     #[test]
     fn test_server_creation() {
-        let server = GgMcpServer::new();
+        let server = GgMcpServer::default();
         let info = server.get_info();
         assert!(info.instructions.is_some());
         assert!(info.instructions.unwrap().contains("git-gud"));
@@ -1208,7 +1640,7 @@ mod tests {
 
     #[test]
     fn test_stack_navigate_validates_direction() {
-        let server = GgMcpServer::new();
+        let server = GgMcpServer::default();
         // We can't easily call the tool method directly due to the Parameters wrapper,
         // but we can test that the direction validation logic works
         let valid = ["first", "last", "prev", "next"];
@@ -1258,6 +1690,23 @@ mod tests {
         assert!(!params.no_verify);
     }
 
+    #[test]
+    fn pr_comment_builds_expected_gg_args() {
+        let params: PrCommentParams =
+            serde_json::from_str(r#"{"message": "Fixed, thanks!", "target": "2"}"#).unwrap();
+        assert_eq!(
+            build_pr_comment_args(params),
+            vec!["comment", "--json", "--message", "Fixed, thanks!", "2"]
+        );
+
+        let params: PrCommentParams =
+            serde_json::from_str(r#"{"message": "Rebased", "all": true}"#).unwrap();
+        assert_eq!(
+            build_pr_comment_args(params),
+            vec!["comment", "--json", "--message", "Rebased", "--all"]
+        );
+    }
+
     #[test]
     fn test_land_params_defaults() {
         let params: StackLandParams = serde_json::from_str("{}").unwrap();
@@ -1276,6 +1725,10 @@ mod tests {
         assert!(!params.one_fixup_per_commit);
         assert!(!params.squash);
         assert!(!params.force);
+        assert!(params.into.is_none());
+        assert!(!params.all);
+        assert!(!params.include_untracked);
+        assert!(!params.no_verify);
     }
 
     #[test]
@@ -1333,16 +1786,18 @@ mod tests {
         // Order is required for MCP (no TUI)
         let params: StackReorderParams = serde_json::from_str(r#"{"order": "3,1,2"}"#).unwrap();
         assert_eq!(params.order, "3,1,2");
-        // force defaults to false
+        // force and dry_run default to false
         assert!(!params.force);
+        assert!(!params.dry_run);
     }
 
     #[test]
     fn test_stack_drop_requires_targets() {
-        let server = GgMcpServer::new();
+        let server = GgMcpServer::default();
         let params = StackDropParams {
             targets: vec![],
             force: false,
+            close_pr: false,
         };
         let result = server.stack_drop(Parameters(params));
         assert!(result.is_err());
@@ -1352,9 +1807,24 @@ mod tests {
         );
     }
 
+    #[test]
+    fn test_stack_drop_close_pr_needs_push() {
+        let server = GgMcpServer::with_permissions(Permissions::parse(&["read,write"]).unwrap());
+        let params = StackDropParams {
+            targets: vec!["1".to_string()],
+            force: false,
+            close_pr: true,
+        };
+        let error = server.stack_drop(Parameters(params)).unwrap_err();
+        assert_eq!(
+            error,
+            "error[GG_PERMISSION_DENIED]: stack_drop needs the `push` permission, but gg-mcp only allows read,write"
+        );
+    }
+
     #[test]
     fn test_stack_split_requires_files() {
-        let server = GgMcpServer::new();
+        let server = GgMcpServer::default();
         let params = StackSplitParams {
             commit: None,
             files: vec![],
@@ -1370,6 +1840,33 @@ mod tests {
         );
     }
 
+    #[test]
+    fn conflict_resolve_takes_exactly_one_resolution() {
+        let params = |json: serde_json::Value| -> ConflictResolveParams {
+            serde_json::from_value(json).unwrap()
+        };
+        assert_eq!(
+            conflict_resolution(&params(serde_json::json!({"path": "a", "take": "theirs"}))),
+            Ok(Resolution::Theirs)
+        );
+        assert_eq!(
+            conflict_resolution(&params(serde_json::json!({"path": "a", "content": "x\n"}))),
+            Ok(Resolution::Content("x\n".to_string()))
+        );
+        assert_eq!(
+            conflict_resolution(&params(serde_json::json!({"path": "a", "delete": true}))),
+            Ok(Resolution::Delete)
+        );
+        assert!(conflict_resolution(&params(serde_json::json!({"path": "a"}))).is_err());
+        assert!(
+            conflict_resolution(&params(serde_json::json!({"path": "a", "take": "mine"}))).is_err()
+        );
+        assert!(conflict_resolution(&params(
+            serde_json::json!({"path": "a", "take": "ours", "delete": true})
+        ))
+        .is_err());
+    }
+
     #[test]
     fn test_reorder_params_supports_space_separated() {
         // Order can also be space-separated